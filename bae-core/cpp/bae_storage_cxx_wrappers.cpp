@@ -87,6 +87,16 @@ void set_enable_natpmp(SessionParams* params, bool enable) {
     libtorrent::set_enable_natpmp(params, enable);
 }
 
+// Wrapper for set_enable_dht
+void set_enable_dht(SessionParams* params, bool enable) {
+    libtorrent::set_enable_dht(params, enable);
+}
+
+// Wrapper for set_dht_bootstrap_nodes - converts rust::Str to std::string
+void set_dht_bootstrap_nodes(SessionParams* params, rust::Str nodes) {
+    libtorrent::set_dht_bootstrap_nodes(params, std::string(nodes));
+}
+
 // Wrapper for set_connections_limit
 void set_connections_limit(SessionParams* params, int32_t limit) {
     libtorrent::set_connections_limit(params, limit);
@@ -173,6 +183,28 @@ int32_t torrent_get_num_seeds(TorrentHandle* handle) {
     return libtorrent::torrent_get_num_seeds(handle);
 }
 
+int32_t torrent_get_download_rate(TorrentHandle* handle) {
+    return libtorrent::torrent_get_download_rate_internal(handle);
+}
+
+rust::Vec<uint8_t> torrent_get_pieces(TorrentHandle* handle) {
+    auto pieces = libtorrent::torrent_get_pieces_internal(handle);
+    rust::Vec<uint8_t> result;
+    result.reserve(pieces.size());
+    for (uint8_t have : pieces) {
+        result.push_back(have);
+    }
+    return result;
+}
+
+int64_t torrent_get_uploaded(TorrentHandle* handle) {
+    return libtorrent::torrent_get_all_time_upload(handle);
+}
+
+int64_t torrent_get_downloaded(TorrentHandle* handle) {
+    return libtorrent::torrent_get_all_time_download(handle);
+}
+
 rust::String torrent_get_tracker_status(TorrentHandle* handle) {
     std::string status = libtorrent::torrent_get_tracker_status(handle);
     return rust::String(status.data(), status.size());
@@ -245,6 +277,14 @@ void torrent_resume(TorrentHandle* handle) {
     libtorrent::torrent_resume(handle);
 }
 
+void session_pause(Session* sess) {
+    libtorrent::session_pause(sess);
+}
+
+void session_resume(Session* sess) {
+    libtorrent::session_resume(sess);
+}
+
 void session_remove_torrent(Session* sess, TorrentHandle* handle, bool delete_files) {
     libtorrent::session_remove_torrent(sess, handle, delete_files);
 }
@@ -222,6 +222,43 @@ int32_t torrent_get_num_seeds(torrent_handle* handle) {
     return static_cast<int32_t>(status.num_seeds);
 }
 
+int32_t torrent_get_download_rate_internal(torrent_handle* handle) {
+    if (!handle) {
+        return 0;
+    }
+    torrent_status status = handle->status();
+    return static_cast<int32_t>(status.download_payload_rate);
+}
+
+std::vector<uint8_t> torrent_get_pieces_internal(torrent_handle* handle) {
+    std::vector<uint8_t> result;
+    if (!handle) {
+        return result;
+    }
+    torrent_status status = handle->status(torrent_handle::query_pieces);
+    result.reserve(status.pieces.size());
+    for (bool have : status.pieces) {
+        result.push_back(have ? 1 : 0);
+    }
+    return result;
+}
+
+int64_t torrent_get_all_time_upload(torrent_handle* handle) {
+    if (!handle) {
+        return 0;
+    }
+    torrent_status status = handle->status();
+    return static_cast<int64_t>(status.all_time_upload);
+}
+
+int64_t torrent_get_all_time_download(torrent_handle* handle) {
+    if (!handle) {
+        return 0;
+    }
+    torrent_status status = handle->status();
+    return static_cast<int64_t>(status.all_time_download);
+}
+
 std::string torrent_get_tracker_status(torrent_handle* handle) {
     if (!handle) {
         return "No handle";
@@ -327,6 +364,18 @@ void set_enable_natpmp(session_params* params, bool enable) {
     }
 }
 
+void set_enable_dht(session_params* params, bool enable) {
+    if (params) {
+        params->settings.set_bool(settings_pack::enable_dht, enable);
+    }
+}
+
+void set_dht_bootstrap_nodes(session_params* params, const std::string& nodes) {
+    if (params && !nodes.empty()) {
+        params->settings.set_str(settings_pack::dht_bootstrap_nodes, nodes);
+    }
+}
+
 void set_connections_limit(session_params* params, int32_t limit) {
     if (params) {
         params->settings.set_int(settings_pack::connections_limit, limit);
@@ -377,6 +426,18 @@ void torrent_resume(torrent_handle* handle) {
     }
 }
 
+void session_pause(session* sess) {
+    if (sess) {
+        sess->pause();
+    }
+}
+
+void session_resume(session* sess) {
+    if (sess) {
+        sess->resume();
+    }
+}
+
 // Helper function to convert sha1_hash to hex string
 std::string hash_to_string(const libtorrent::sha1_hash& hash) {
     std::ostringstream oss;
@@ -106,6 +106,18 @@ int32_t torrent_get_num_peers(torrent_handle* handle);
 /// Get number of seeders
 int32_t torrent_get_num_seeds(torrent_handle* handle);
 
+/// Get payload download rate in bytes per second
+int32_t torrent_get_download_rate_internal(torrent_handle* handle);
+
+/// Get which pieces are downloaded, one byte per piece (1 = have)
+std::vector<uint8_t> torrent_get_pieces_internal(torrent_handle* handle);
+
+/// Get bytes uploaded since the torrent was added
+int64_t torrent_get_all_time_upload(torrent_handle* handle);
+
+/// Get bytes downloaded since the torrent was added
+int64_t torrent_get_all_time_download(torrent_handle* handle);
+
 /// Get tracker status as a formatted string
 std::string torrent_get_tracker_status(torrent_handle* handle);
 
@@ -142,6 +154,12 @@ void set_enable_upnp(session_params* params, bool enable);
 /// Set enable_natpmp on session_params
 void set_enable_natpmp(session_params* params, bool enable);
 
+/// Set enable_dht on session_params
+void set_enable_dht(session_params* params, bool enable);
+
+/// Set dht_bootstrap_nodes on session_params (comma-separated host:port list)
+void set_dht_bootstrap_nodes(session_params* params, const std::string& nodes);
+
 /// Set connections_limit on session_params (global max connections)
 void set_connections_limit(session_params* params, int32_t limit);
 
@@ -160,6 +178,12 @@ void torrent_pause(torrent_handle* handle);
 /// Resume a torrent
 void torrent_resume(torrent_handle* handle);
 
+/// Pause every torrent in a session
+void session_pause(session* sess);
+
+/// Resume a paused session
+void session_resume(session* sess);
+
 /// Alert handling functions for libtorrent's alert system
 /// Alert types (matching libtorrent alert_category_t)
 enum AlertType {
@@ -229,12 +253,18 @@ bool torrent_set_file_priorities(TorrentHandle* handle, rust::Vec<uint8_t> prior
 float torrent_get_progress(TorrentHandle* handle);
 int32_t torrent_get_num_peers(TorrentHandle* handle);
 int32_t torrent_get_num_seeds(TorrentHandle* handle);
+int32_t torrent_get_download_rate(TorrentHandle* handle);
+rust::Vec<uint8_t> torrent_get_pieces(TorrentHandle* handle);
+int64_t torrent_get_uploaded(TorrentHandle* handle);
+int64_t torrent_get_downloaded(TorrentHandle* handle);
 rust::String torrent_get_tracker_status(TorrentHandle* handle);
 rust::String session_get_listen_interfaces(Session* sess);
 rust::String session_get_listening_port(Session* sess);
 void set_paused(AddTorrentParams* params, bool paused);
 void torrent_pause(TorrentHandle* handle);
 void torrent_resume(TorrentHandle* handle);
+void session_pause(Session* sess);
+void session_resume(Session* sess);
 
 // Alert handling (implemented in bae_storage_helpers.cpp)
 struct AlertData;
@@ -253,6 +283,8 @@ void set_seed_mode(AddTorrentParams* params, bool seed_mode);
 void set_listen_interfaces(SessionParams* params, rust::Str interfaces);
 void set_enable_upnp(SessionParams* params, bool enable);
 void set_enable_natpmp(SessionParams* params, bool enable);
+void set_enable_dht(SessionParams* params, bool enable);
+void set_dht_bootstrap_nodes(SessionParams* params, rust::Str nodes);
 void set_connections_limit(SessionParams* params, int32_t limit);
 void set_unchoke_slots_limit(SessionParams* params, int32_t limit);
 rust::String torrent_get_name(TorrentHandle* handle);
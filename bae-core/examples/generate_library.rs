@@ -0,0 +1,53 @@
+//! Generate a deterministic library for benchmarks and the bae-mocks demo.
+//!
+//! ```sh
+//! cargo run -p bae-core --features test-utils --example generate_library -- \
+//!     --albums 2000 --seed 1 --db /tmp/bench.db --mock-json bae-mocks/fixtures/library.json
+//! ```
+use bae_core::db::Database;
+use bae_core::test_support::{GeneratedLibrary, LibrarySpec};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut spec = LibrarySpec::new(1000);
+    let mut db_path = None;
+    let mut mock_json_path = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", arg))?;
+        match arg.as_str() {
+            "--albums" => spec.albums = value.parse()?,
+            "--seed" => spec = spec.with_seed(value.parse()?),
+            "--profiles" => spec = spec.with_storage_profiles(value.parse()?),
+            "--corrupt" => spec = spec.with_corrupt_fraction(value.parse()?),
+            "--db" => db_path = Some(value),
+            "--mock-json" => mock_json_path = Some(value),
+            _ => return Err(format!("unknown argument {}", arg).into()),
+        }
+    }
+
+    let library = GeneratedLibrary::generate(&spec);
+    println!(
+        "Generated {} albums across {} storage profiles ({} corrupt)",
+        library.albums.len(),
+        library.storage_profiles.len(),
+        library.corrupt_albums().count()
+    );
+
+    if let Some(path) = db_path {
+        let database = Database::new(&path).await?;
+        library.insert_into(&database).await?;
+        println!("Wrote database to {}", path);
+    }
+    if let Some(path) = mock_json_path {
+        std::fs::write(
+            &path,
+            serde_json::to_string_pretty(&library.to_mock_fixture_json())?,
+        )?;
+        println!("Wrote mock fixture to {}", path);
+    }
+    Ok(())
+}
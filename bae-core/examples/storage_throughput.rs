@@ -0,0 +1,61 @@
+//! Measure how fast the import store phase can compress, encrypt and
+//! checksum, to compare machines and check the parallel encryption path.
+//!
+//! ```sh
+//! cargo run --release -p bae-core --example storage_throughput -- --mb 512
+//! ```
+use bae_core::encryption::{generate_random_key, EncryptionService};
+use bae_core::storage::compress_for_storage;
+use sha2::{Digest, Sha256};
+use std::time::Instant;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut megabytes = 256usize;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", arg))?;
+        match arg.as_str() {
+            "--mb" => megabytes = value.parse()?,
+            _ => return Err(format!("unknown argument {}", arg).into()),
+        }
+    }
+
+    // Noisy enough that compression has to work, like real audio
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let data: Vec<u8> = (0..megabytes * 1024 * 1024)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 56) as u8
+        })
+        .collect();
+    let encryption = EncryptionService::new(&hex::encode(generate_random_key()))?;
+
+    report("encrypt (all cores)", data.len(), || {
+        encryption.encrypt_chunked(&data);
+    });
+    report("encrypt (one core)", data.len(), || {
+        encryption.encrypt_chunked_single_core(&data);
+    });
+    report("sha-256 checksum", data.len(), || {
+        Sha256::digest(&data);
+    });
+    report("compress (wav)", data.len(), || {
+        let _ = compress_for_storage("track.wav", &data);
+    });
+    Ok(())
+}
+
+fn report(label: &str, bytes: usize, run: impl FnOnce()) {
+    let started = Instant::now();
+    run();
+    let seconds = started.elapsed().as_secs_f64();
+    println!(
+        "{:<22} {:>8.1} MB/s",
+        label,
+        bytes as f64 / 1_048_576.0 / seconds
+    );
+}
@@ -1,7 +1,8 @@
 //! Unified audio codec module using FFmpeg.
 //!
-//! Provides decoding (any format to PCM), encoding (PCM to FLAC), and
-//! seektable generation. Uses custom AVIO for in-memory decoding.
+//! Provides decoding (any format to PCM), encoding (PCM to FLAC, or to Opus
+//! for streaming), and seektable generation. Uses custom AVIO for in-memory
+//! decoding.
 
 use crate::playback::{SharedSparseBuffer, StreamingPcmSink};
 use std::cell::Cell;
@@ -86,6 +87,45 @@ pub struct DecodedAudio {
     pub sample_rate: u32,
     pub channels: u32,
     pub bits_per_sample: u32,
+    /// Set when the file only decoded with a fallback configuration
+    pub fallback: Option<DecodeFallback>,
+}
+
+/// A more permissive decode configuration, tried when a file won't open with
+/// the defaults (odd WAV chunks, broken tag padding and the like)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeFallback {
+    /// Probe further into the file, ignore declared chunk lengths and let the
+    /// demuxer drop corrupt packets instead of giving up
+    Lenient,
+    /// Lenient, and also skip everything before the first recognizable
+    /// container header
+    SkipLeadingJunk,
+}
+
+impl DecodeFallback {
+    /// Fallbacks in the order they're tried
+    const ALL: [DecodeFallback; 2] = [DecodeFallback::Lenient, DecodeFallback::SkipLeadingJunk];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DecodeFallback::Lenient => "lenient decoding",
+            DecodeFallback::SkipLeadingJunk => "skipping leading junk",
+        }
+    }
+}
+
+/// How far into a file to look for a container header when skipping junk
+const JUNK_SCAN_LIMIT: usize = 1024 * 1024;
+
+/// Offset of the first container header after the start of `data`, if any.
+///
+/// Only headers past offset 0 count: a file that starts with one already got
+/// the normal decode attempt.
+fn find_audio_start(data: &[u8]) -> Option<usize> {
+    const MARKERS: [&[u8]; 5] = [b"fLaC", b"RIFF", b"FORM", b"OggS", b"wvpk"];
+    let limit = data.len().min(JUNK_SCAN_LIMIT);
+    (1..limit).find(|&i| MARKERS.iter().any(|marker| data[i..].starts_with(marker)))
 }
 
 /// A seek point entry mapping sample number to byte offset.
@@ -201,12 +241,476 @@ pub fn decode_audio(
     unsafe { decode_audio_avio(data, start_ms, end_ms) }
 }
 
+/// Result of fully decoding a file to check that it's intact
+#[derive(Debug, Clone)]
+pub struct AudioVerification {
+    /// Duration of the audio that actually decoded
+    pub duration_ms: u64,
+    /// Duration the container/header claims, if it states one
+    pub declared_duration_ms: Option<u64>,
+    /// Packets the decoder rejected
+    pub decode_errors: u32,
+    /// Runs of consecutive full-scale samples (see [`ClipDetector`])
+    pub clipped_runs: u32,
+    /// Set when the file only decoded with a fallback configuration
+    pub fallback: Option<DecodeFallback>,
+}
+
+/// Decode a whole file without keeping the samples, to catch truncated or
+/// corrupt audio and measure clipping.
+pub fn verify_audio(data: &[u8]) -> Result<AudioVerification, String> {
+    let mut frames: u64 = 0;
+    let mut clip_detector: Option<ClipDetector> = None;
+
+    // Safety: FFmpeg operations are contained within decode_frames_avio
+    let (format, decode_errors, fallback) = unsafe {
+        decode_frames_with_fallback(data, None, None, &mut |format, samples| {
+            let channels = format.channels.max(1) as usize;
+            frames += (samples.len() / channels) as u64;
+            clip_detector
+                .get_or_insert_with(|| ClipDetector::new(channels, format.bits_per_sample))
+                .feed(samples);
+        })?
+    };
+
+    let duration_ms = if format.sample_rate > 0 {
+        frames * 1000 / format.sample_rate as u64
+    } else {
+        0
+    };
+
+    Ok(AudioVerification {
+        duration_ms,
+        declared_duration_ms: format.declared_duration_ms,
+        decode_errors,
+        clipped_runs: clip_detector.map(|d| d.clipped_runs()).unwrap_or(0),
+        fallback,
+    })
+}
+
+/// Counts clipped passages in interleaved decoded samples.
+///
+/// A lone full-scale sample is normal in loud masters; several in a row on the
+/// same channel means the waveform was flattened.
+pub struct ClipDetector {
+    threshold: i64,
+    run_lengths: Vec<u32>,
+    channel: usize,
+    clipped_runs: u32,
+}
+
+impl ClipDetector {
+    /// Consecutive full-scale samples that count as one clipped run
+    const MIN_RUN: u32 = 3;
+
+    /// `bits_per_sample` is as reported in [`DecodedAudio`]: 16 for samples in
+    /// i16 range, 32 for samples scaled to i32 range.
+    pub fn new(channels: usize, bits_per_sample: u32) -> Self {
+        let full_scale = if bits_per_sample <= 16 {
+            i16::MAX as i64
+        } else {
+            i32::MAX as i64
+        };
+        // 24-bit audio decodes left-shifted into i32, so its peak is a hair
+        // below i32::MAX
+        let threshold = full_scale - full_scale / 1000;
+        Self {
+            threshold,
+            run_lengths: vec![0; channels.max(1)],
+            channel: 0,
+            clipped_runs: 0,
+        }
+    }
+
+    /// Feed interleaved samples; may be called with any chunking
+    pub fn feed(&mut self, samples: &[i32]) {
+        for &sample in samples {
+            let run = &mut self.run_lengths[self.channel];
+            if (sample as i64).abs() >= self.threshold {
+                *run += 1;
+                if *run == Self::MIN_RUN {
+                    self.clipped_runs += 1;
+                }
+            } else {
+                *run = 0;
+            }
+            self.channel = (self.channel + 1) % self.run_lengths.len();
+        }
+    }
+
+    pub fn clipped_runs(&self) -> u32 {
+        self.clipped_runs
+    }
+}
+
+/// Peak and average level of decoded audio, in dB relative to full scale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoudnessStats {
+    pub peak_dbfs: f64,
+    pub rms_dbfs: f64,
+}
+
+impl LoudnessStats {
+    /// Peak-to-RMS ratio (crest factor). Heavily limited masters sit around
+    /// 6-8 dB; dynamic ones reach well past 12.
+    pub fn dynamic_range_db(&self) -> f64 {
+        self.peak_dbfs - self.rms_dbfs
+    }
+}
+
+/// Accumulates peak and RMS level across any number of files
+#[derive(Debug, Default)]
+pub struct LoudnessMeter {
+    peak: f64,
+    sum_squares: f64,
+    samples: u64,
+}
+
+impl LoudnessMeter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed interleaved samples; `bits_per_sample` as reported in [`DecodedAudio`]
+    pub fn feed(&mut self, samples: &[i32], bits_per_sample: u32) {
+        let full_scale = if bits_per_sample <= 16 {
+            i16::MAX as f64
+        } else {
+            i32::MAX as f64
+        };
+        for &sample in samples {
+            let level = sample as f64 / full_scale;
+            self.peak = self.peak.max(level.abs());
+            self.sum_squares += level * level;
+        }
+        self.samples += samples.len() as u64;
+    }
+
+    /// None until some non-silent audio has been fed
+    pub fn stats(&self) -> Option<LoudnessStats> {
+        if self.sum_squares == 0.0 {
+            return None;
+        }
+        let rms = (self.sum_squares / self.samples as f64).sqrt();
+        Some(LoudnessStats {
+            peak_dbfs: 20.0 * self.peak.log10(),
+            rms_dbfs: 20.0 * rms.log10(),
+        })
+    }
+}
+
+/// Decode a whole file into a loudness meter without keeping the samples
+pub fn measure_loudness(data: &[u8], meter: &mut LoudnessMeter) -> Result<(), String> {
+    // Safety: FFmpeg operations are contained within decode_frames_avio
+    unsafe {
+        decode_frames_with_fallback(data, None, None, &mut |format, samples| {
+            meter.feed(samples, format.bits_per_sample);
+        })?;
+    }
+    Ok(())
+}
+
+/// DR score as the Pleasurize Music Foundation meter computes it. Each
+/// channel is cut into 3-second blocks; its score is the second-highest block
+/// peak over the RMS of the loudest fifth of blocks, in dB. The track's score
+/// is the average over channels. Collectors read DR8 and below as a
+/// loudness-war master and DR12 and up as a dynamic one.
+#[derive(Debug, Default)]
+pub struct DynamicRangeMeter {
+    /// Per channel, the (peak, RMS) of each closed block
+    blocks: Vec<Vec<(f64, f64)>>,
+    current: Vec<DrBlock>,
+    channel: usize,
+    block_frames: u64,
+    frames: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct DrBlock {
+    peak: f64,
+    sum_squares: f64,
+}
+
+impl DynamicRangeMeter {
+    const BLOCK_SECS: u64 = 3;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed interleaved samples; `bits_per_sample` as reported in [`DecodedAudio`]
+    pub fn feed(&mut self, samples: &[i32], sample_rate: u32, channels: u32, bits_per_sample: u32) {
+        let channels = channels.max(1) as usize;
+        if self.current.len() != channels {
+            self.blocks = vec![Vec::new(); channels];
+            self.current = vec![DrBlock::default(); channels];
+            self.channel = 0;
+            self.frames = 0;
+        }
+        self.block_frames = (sample_rate as u64 * Self::BLOCK_SECS).max(1);
+        let full_scale = if bits_per_sample <= 16 {
+            i16::MAX as f64
+        } else {
+            i32::MAX as f64
+        };
+        for &sample in samples {
+            let level = sample as f64 / full_scale;
+            let block = &mut self.current[self.channel];
+            block.peak = block.peak.max(level.abs());
+            block.sum_squares += level * level;
+            self.channel += 1;
+            if self.channel == channels {
+                self.channel = 0;
+                self.frames += 1;
+                if self.frames == self.block_frames {
+                    self.close_block();
+                }
+            }
+        }
+    }
+
+    /// The score in dB, unrounded. None for silence or no audio.
+    pub fn finish(mut self) -> Option<f64> {
+        if self.frames > 0 {
+            self.close_block();
+        }
+        let scores: Vec<f64> = self
+            .blocks
+            .iter()
+            .filter_map(|blocks| channel_dynamic_range(blocks))
+            .collect();
+        if scores.is_empty() {
+            return None;
+        }
+        Some(scores.iter().sum::<f64>() / scores.len() as f64)
+    }
+
+    fn close_block(&mut self) {
+        for (blocks, block) in self.blocks.iter_mut().zip(&mut self.current) {
+            // The meter's RMS is scaled so a full-scale sine reads 0 dBFS
+            let rms = (2.0 * block.sum_squares / self.frames as f64).sqrt();
+            blocks.push((block.peak, rms));
+            *block = DrBlock::default();
+        }
+        self.frames = 0;
+    }
+}
+
+/// One channel's score from the (peak, RMS) of its blocks
+fn channel_dynamic_range(blocks: &[(f64, f64)]) -> Option<f64> {
+    let mut squares: Vec<f64> = blocks.iter().map(|(_, rms)| rms * rms).collect();
+    squares.sort_by(|a, b| b.total_cmp(a));
+    let loudest = ((blocks.len() as f64 * 0.2).round() as usize).max(1);
+    let rms = (squares.iter().take(loudest).sum::<f64>() / loudest as f64).sqrt();
+
+    let mut peaks: Vec<f64> = blocks.iter().map(|(peak, _)| *peak).collect();
+    peaks.sort_by(|a, b| b.total_cmp(a));
+    // The second-highest peak, so one stray click doesn't inflate the score
+    let peak = peaks.get(1).or(peaks.first()).copied()?;
+
+    if rms == 0.0 || peak == 0.0 {
+        return None;
+    }
+    Some(20.0 * (peak / rms).log10())
+}
+
+/// Decode a whole file into a DR meter without keeping the samples
+pub fn measure_dynamic_range(data: &[u8], meter: &mut DynamicRangeMeter) -> Result<(), String> {
+    // Safety: FFmpeg operations are contained within decode_frames_avio
+    unsafe {
+        decode_frames_with_fallback(data, None, None, &mut |format, samples| {
+            meter.feed(
+                samples,
+                format.sample_rate,
+                format.channels,
+                format.bits_per_sample,
+            );
+        })?;
+    }
+    Ok(())
+}
+
+/// RMS level of decoded audio in fixed-length windows, from 0.0 (silence)
+/// to 1.0 (full scale). Quiet windows mark the gaps between tracks.
+#[derive(Debug)]
+pub struct LevelEnvelope {
+    window_ms: u32,
+    levels: Vec<f32>,
+    sum_squares: f64,
+    window_samples: u64,
+}
+
+impl LevelEnvelope {
+    pub fn new(window_ms: u32) -> Self {
+        Self {
+            window_ms: window_ms.max(1),
+            levels: Vec::new(),
+            sum_squares: 0.0,
+            window_samples: 0,
+        }
+    }
+
+    pub fn window_ms(&self) -> u32 {
+        self.window_ms
+    }
+
+    /// Feed interleaved samples; `bits_per_sample` as reported in [`DecodedAudio`]
+    pub fn feed(&mut self, samples: &[i32], sample_rate: u32, channels: u32, bits_per_sample: u32) {
+        let full_scale = if bits_per_sample <= 16 {
+            i16::MAX as f64
+        } else {
+            i32::MAX as f64
+        };
+        let samples_per_window =
+            (sample_rate as u64 * self.window_ms as u64 / 1000).max(1) * channels.max(1) as u64;
+        for &sample in samples {
+            let level = sample as f64 / full_scale;
+            self.sum_squares += level * level;
+            self.window_samples += 1;
+            if self.window_samples == samples_per_window {
+                self.close_window();
+            }
+        }
+    }
+
+    /// Levels of each window so far, including a final partial one
+    pub fn finish(mut self) -> Vec<f32> {
+        if self.window_samples > 0 {
+            self.close_window();
+        }
+        self.levels
+    }
+
+    fn close_window(&mut self) {
+        let rms = (self.sum_squares / self.window_samples as f64).sqrt();
+        self.levels.push(rms as f32);
+        self.sum_squares = 0.0;
+        self.window_samples = 0;
+    }
+}
+
+/// Decode a whole file into a level envelope without keeping the samples
+pub fn measure_levels(data: &[u8], envelope: &mut LevelEnvelope) -> Result<(), String> {
+    // Safety: FFmpeg operations are contained within decode_frames_avio
+    unsafe {
+        decode_frames_with_fallback(data, None, None, &mut |format, samples| {
+            envelope.feed(
+                samples,
+                format.sample_rate,
+                format.channels,
+                format.bits_per_sample,
+            );
+        })?;
+    }
+    Ok(())
+}
+
 /// Internal AVIO-based decode implementation
 unsafe fn decode_audio_avio(
     data: &[u8],
     start_ms: Option<u64>,
     end_ms: Option<u64>,
 ) -> Result<DecodedAudio, String> {
+    let mut samples: Vec<i32> = Vec::new();
+    let (format, _, fallback) =
+        decode_frames_with_fallback(data, start_ms, end_ms, &mut |_, frame_samples| {
+            samples.extend_from_slice(frame_samples);
+        })?;
+
+    trace!(
+        "Decoded {} samples ({} frames) from audio",
+        samples.len(),
+        samples.len() / format.channels.max(1) as usize
+    );
+
+    Ok(DecodedAudio {
+        samples,
+        sample_rate: format.sample_rate,
+        channels: format.channels,
+        bits_per_sample: format.bits_per_sample,
+        fallback,
+    })
+}
+
+/// Stream format as reported by the decoder
+struct StreamFormat {
+    sample_rate: u32,
+    channels: u32,
+    bits_per_sample: u32,
+    declared_duration_ms: Option<u64>,
+}
+
+/// [`decode_frames_avio`], retrying with each [`DecodeFallback`] if the file
+/// won't open. Also returns the fallback that worked, if one was needed.
+///
+/// Retrying is safe because every error path in `decode_frames_avio` comes
+/// before the first call to `on_samples`.
+unsafe fn decode_frames_with_fallback(
+    data: &[u8],
+    start_ms: Option<u64>,
+    end_ms: Option<u64>,
+    on_samples: &mut dyn FnMut(&StreamFormat, &[i32]),
+) -> Result<(StreamFormat, u32, Option<DecodeFallback>), String> {
+    let first_error = match decode_frames_avio(data, start_ms, end_ms, None, on_samples) {
+        Ok((format, decode_errors)) => return Ok((format, decode_errors, None)),
+        Err(e) => e,
+    };
+
+    for fallback in DecodeFallback::ALL {
+        let data = match fallback {
+            DecodeFallback::Lenient => data,
+            DecodeFallback::SkipLeadingJunk => match find_audio_start(data) {
+                Some(offset) => &data[offset..],
+                None => continue,
+            },
+        };
+        match decode_frames_avio(data, start_ms, end_ms, Some(fallback), on_samples) {
+            Ok((format, decode_errors)) => {
+                warn!(
+                    "Decoded with fallback ({}) after: {}",
+                    fallback.label(),
+                    first_error
+                );
+                return Ok((format, decode_errors, Some(fallback)));
+            }
+            Err(e) => debug!("Decode fallback ({}) failed: {}", fallback.label(), e),
+        }
+    }
+
+    Err(first_error)
+}
+
+/// Make a format context more forgiving before opening input with it.
+///
+/// Returns the demuxer options to pass to `avformat_open_input`; the caller
+/// frees them.
+unsafe fn apply_lenient_options(
+    fmt_ctx: *mut ffmpeg_sys_next::AVFormatContext,
+) -> *mut ffmpeg_sys_next::AVDictionary {
+    use ffmpeg_sys_next::*;
+
+    (*fmt_ctx).flags |= AVFMT_FLAG_DISCARD_CORRUPT as c_int
+        | AVFMT_FLAG_GENPTS as c_int
+        | AVFMT_FLAG_IGNIDX as c_int;
+    (*fmt_ctx).probesize = 32 * 1024 * 1024;
+    (*fmt_ctx).max_analyze_duration = 30 * AV_TIME_BASE as i64;
+
+    let mut options: *mut AVDictionary = ptr::null_mut();
+    // WAV: don't trust the data chunk length
+    av_dict_set(&mut options, c"ignore_length".as_ptr(), c"1".as_ptr(), 0);
+    options
+}
+
+/// Decode the given range, handing each frame's interleaved samples to
+/// `on_samples`. Returns the stream format and the number of packets the
+/// decoder rejected.
+unsafe fn decode_frames_avio(
+    data: &[u8],
+    start_ms: Option<u64>,
+    end_ms: Option<u64>,
+    fallback: Option<DecodeFallback>,
+    on_samples: &mut dyn FnMut(&StreamFormat, &[i32]),
+) -> Result<(StreamFormat, u32), String> {
     use ffmpeg_sys_next::*;
 
     // Create our context for callbacks
@@ -246,8 +750,15 @@ unsafe fn decode_audio_avio(
     }
     (*fmt_ctx).pb = avio;
 
+    let mut options = if fallback.is_some() {
+        apply_lenient_options(fmt_ctx)
+    } else {
+        ptr::null_mut()
+    };
+
     // Open input (NULL filename since we're using custom I/O)
-    let ret = avformat_open_input(&mut fmt_ctx, ptr::null(), ptr::null_mut(), ptr::null_mut());
+    let ret = avformat_open_input(&mut fmt_ctx, ptr::null(), ptr::null_mut(), &mut options);
+    av_dict_free(&mut options);
     if ret < 0 {
         avformat_free_context(fmt_ctx);
         return Err(format!("Failed to open input: {}", av_err_str(ret)));
@@ -321,6 +832,20 @@ unsafe fn decode_audio_avio(
         _ => 16,
     };
 
+    // AV_NOPTS_VALUE (i64::MIN) when the container doesn't state a duration
+    let declared_duration_ms = if (*fmt_ctx).duration > 0 {
+        Some((*fmt_ctx).duration as u64 * 1000 / AV_TIME_BASE as u64)
+    } else {
+        None
+    };
+
+    let format = StreamFormat {
+        sample_rate,
+        channels,
+        bits_per_sample,
+        declared_duration_ms,
+    };
+
     // Calculate sample boundaries
     let start_sample = start_ms.map(|ms| (ms * sample_rate as u64) / 1000);
     let end_sample = end_ms.map(|ms| (ms * sample_rate as u64) / 1000);
@@ -346,9 +871,9 @@ unsafe fn decode_audio_avio(
         return Err("Failed to allocate frame/packet".to_string());
     }
 
-    let mut samples: Vec<i32> = Vec::new();
     let mut current_sample: u64 = 0;
     let mut collecting = start_sample.is_none();
+    let mut decode_errors: u32 = 0;
 
     // Read and decode packets
     while av_read_frame(fmt_ctx, packet) >= 0 {
@@ -361,6 +886,7 @@ unsafe fn decode_audio_avio(
         av_packet_unref(packet);
 
         if ret < 0 {
+            decode_errors += 1;
             continue;
         }
 
@@ -408,7 +934,7 @@ unsafe fn decode_audio_avio(
                 };
 
                 if skip_start < take_end && take_end <= frame_samples_vec.len() {
-                    samples.extend_from_slice(&frame_samples_vec[skip_start..take_end]);
+                    on_samples(&format, &frame_samples_vec[skip_start..take_end]);
                 }
             }
 
@@ -428,7 +954,7 @@ unsafe fn decode_audio_avio(
     while avcodec_receive_frame(codec_ctx, frame) >= 0 {
         if collecting {
             let frame_samples_vec = extract_samples_from_raw_frame(frame, channels as usize);
-            samples.extend_from_slice(&frame_samples_vec);
+            on_samples(&format, &frame_samples_vec);
         }
     }
 
@@ -442,18 +968,7 @@ unsafe fn decode_audio_avio(
     // Keep avio_ctx alive until here (prevent drop during FFmpeg operations)
     drop(avio_ctx);
 
-    trace!(
-        "Decoded {} samples ({} frames) from audio",
-        samples.len(),
-        samples.len() / channels.max(1) as usize
-    );
-
-    Ok(DecodedAudio {
-        samples,
-        sample_rate,
-        channels,
-        bits_per_sample,
-    })
+    Ok((format, decode_errors))
 }
 
 /// Extract samples from a raw AVFrame as i32
@@ -934,6 +1449,365 @@ unsafe fn encode_to_flac_avio(
     Ok(result)
 }
 
+/// Sample rate Opus encodes at
+const OPUS_SAMPLE_RATE: c_int = 48_000;
+
+/// Input frames converted to float and resampled per pass
+const OPUS_CHUNK_FRAMES: usize = 65_536;
+
+/// Encode PCM samples to Opus in an Ogg container, for streaming at a
+/// fraction of FLAC's size.
+///
+/// Takes interleaved i32 samples as returned by [`decode_audio`]. Opus only
+/// runs at 48 kHz, so other rates are resampled, and anything beyond stereo is
+/// folded down to two channels.
+pub fn encode_to_opus(
+    samples: &[i32],
+    sample_rate: u32,
+    channels: u32,
+    bits_per_sample: u32,
+    bitrate_kbps: u32,
+) -> Result<Vec<u8>, String> {
+    if channels == 0 {
+        return Err("No channels to encode".to_string());
+    }
+    unsafe {
+        encode_to_opus_avio(
+            samples,
+            sample_rate,
+            channels,
+            bits_per_sample,
+            bitrate_kbps,
+        )
+    }
+}
+
+/// FFmpeg state for an in-memory Opus encode, freed on drop
+struct OpusEncoder {
+    avio: *mut ffmpeg_sys_next::AVIOContext,
+    fmt_ctx: *mut ffmpeg_sys_next::AVFormatContext,
+    stream: *mut ffmpeg_sys_next::AVStream,
+    codec_ctx: *mut ffmpeg_sys_next::AVCodecContext,
+    swr: *mut ffmpeg_sys_next::SwrContext,
+    frame: *mut ffmpeg_sys_next::AVFrame,
+    packet: *mut ffmpeg_sys_next::AVPacket,
+}
+
+impl Drop for OpusEncoder {
+    fn drop(&mut self) {
+        use ffmpeg_sys_next::*;
+        unsafe {
+            av_packet_free(&mut self.packet);
+            av_frame_free(&mut self.frame);
+            swr_free(&mut self.swr);
+            avcodec_free_context(&mut self.codec_ctx);
+            if !self.fmt_ctx.is_null() {
+                // Custom IO is ours to free, not the format context's
+                (*self.fmt_ctx).pb = ptr::null_mut();
+                avformat_free_context(self.fmt_ctx);
+            }
+            if !self.avio.is_null() {
+                av_freep(&mut (*self.avio).buffer as *mut *mut u8 as *mut c_void);
+                avio_context_free(&mut self.avio);
+            }
+        }
+    }
+}
+
+impl OpusEncoder {
+    /// Resample interleaved float input onto the end of `out`. None drains
+    /// what the resampler is still holding.
+    unsafe fn resample(
+        &mut self,
+        input: Option<&[f32]>,
+        in_channels: usize,
+        out_channels: usize,
+        out: &mut Vec<f32>,
+    ) -> Result<(), String> {
+        use ffmpeg_sys_next::*;
+
+        let in_frames = input.map_or(0, |input| input.len() / in_channels) as c_int;
+        let capacity = swr_get_out_samples(self.swr, in_frames);
+        if capacity < 0 {
+            return Err(format!(
+                "Failed to size resampler output: {}",
+                av_err_str(capacity)
+            ));
+        }
+        let start = out.len();
+        out.resize(start + capacity as usize * out_channels, 0.0);
+        let mut out_planes = [out[start..].as_mut_ptr() as *mut u8];
+        let in_planes = input.map(|input| [input.as_ptr() as *const u8]);
+        let in_ptr = in_planes
+            .as_ref()
+            .map_or(ptr::null(), |planes| planes.as_ptr());
+        let converted = swr_convert(
+            self.swr,
+            out_planes.as_mut_ptr(),
+            capacity,
+            in_ptr,
+            in_frames,
+        );
+        if converted < 0 {
+            out.truncate(start);
+            return Err(format!("Failed to resample: {}", av_err_str(converted)));
+        }
+        out.truncate(start + converted as usize * out_channels);
+        Ok(())
+    }
+
+    /// Encode one frame of interleaved samples
+    unsafe fn encode_frame(
+        &mut self,
+        samples: &[f32],
+        channels: usize,
+        pts: &mut i64,
+    ) -> Result<(), String> {
+        use ffmpeg_sys_next::*;
+
+        let ret = av_frame_make_writable(self.frame);
+        if ret < 0 {
+            return Err(format!(
+                "Failed to make frame writable: {}",
+                av_err_str(ret)
+            ));
+        }
+        let frame_size = samples.len() / channels;
+        if (*self.frame).format == AVSampleFormat::AV_SAMPLE_FMT_FLTP as c_int {
+            for ch in 0..channels {
+                let plane = (*self.frame).data[ch] as *mut f32;
+                for i in 0..frame_size {
+                    *plane.add(i) = samples[i * channels + ch];
+                }
+            }
+        } else {
+            ptr::copy_nonoverlapping(
+                samples.as_ptr(),
+                (*self.frame).data[0] as *mut f32,
+                samples.len(),
+            );
+        }
+        (*self.frame).pts = *pts;
+        *pts += frame_size as i64;
+        self.send(self.frame)
+    }
+
+    /// Send a frame to the encoder (null to flush it) and write out the
+    /// packets it returns
+    unsafe fn send(&mut self, frame: *const ffmpeg_sys_next::AVFrame) -> Result<(), String> {
+        use ffmpeg_sys_next::*;
+
+        let ret = avcodec_send_frame(self.codec_ctx, frame);
+        if ret < 0 {
+            return Err(format!("Failed to send frame: {}", av_err_str(ret)));
+        }
+        loop {
+            let ret = avcodec_receive_packet(self.codec_ctx, self.packet);
+            if ret == AVERROR(EAGAIN) || ret == AVERROR_EOF {
+                return Ok(());
+            }
+            if ret < 0 {
+                return Err(format!("Failed to receive packet: {}", av_err_str(ret)));
+            }
+            (*self.packet).stream_index = 0;
+            av_packet_rescale_ts(
+                self.packet,
+                (*self.codec_ctx).time_base,
+                (*self.stream).time_base,
+            );
+            let ret = av_interleaved_write_frame(self.fmt_ctx, self.packet);
+            if ret < 0 {
+                return Err(format!("Failed to write packet: {}", av_err_str(ret)));
+            }
+        }
+    }
+}
+
+/// Internal AVIO-based Opus encoding implementation
+unsafe fn encode_to_opus_avio(
+    samples: &[i32],
+    sample_rate: u32,
+    channels: u32,
+    bits_per_sample: u32,
+    bitrate_kbps: u32,
+) -> Result<Vec<u8>, String> {
+    use ffmpeg_sys_next::*;
+
+    // Declared before the encoder so it outlives the AVIO context writing to it
+    let mut write_ctx = Box::new(WriteAvioContext {
+        data: Vec::new(),
+        pos: 0,
+    });
+    let mut enc = OpusEncoder {
+        avio: ptr::null_mut(),
+        fmt_ctx: ptr::null_mut(),
+        stream: ptr::null_mut(),
+        codec_ctx: ptr::null_mut(),
+        swr: ptr::null_mut(),
+        frame: ptr::null_mut(),
+        packet: ptr::null_mut(),
+    };
+
+    let avio_buffer_size = 32768;
+    let avio_buffer = av_malloc(avio_buffer_size) as *mut u8;
+    if avio_buffer.is_null() {
+        return Err("Failed to allocate AVIO buffer".to_string());
+    }
+    enc.avio = avio_alloc_context(
+        avio_buffer,
+        avio_buffer_size as c_int,
+        1, // write flag
+        write_ctx.as_mut() as *mut WriteAvioContext as *mut c_void,
+        None, // no read
+        Some(avio_write_callback),
+        Some(avio_write_seek_callback),
+    );
+    if enc.avio.is_null() {
+        av_free(avio_buffer as *mut c_void);
+        return Err("Failed to create AVIO context".to_string());
+    }
+
+    let ret =
+        avformat_alloc_output_context2(&mut enc.fmt_ctx, ptr::null(), c"ogg".as_ptr(), ptr::null());
+    if ret < 0 || enc.fmt_ctx.is_null() {
+        return Err("Failed to create output context".to_string());
+    }
+    (*enc.fmt_ctx).pb = enc.avio;
+    (*enc.fmt_ctx).flags |= AVFMT_FLAG_CUSTOM_IO as c_int;
+
+    // Prefer libopus; FFmpeg's own encoder is still marked experimental
+    let mut codec = avcodec_find_encoder_by_name(c"libopus".as_ptr());
+    let native = codec.is_null();
+    if native {
+        codec = avcodec_find_encoder(AVCodecID::AV_CODEC_ID_OPUS);
+    }
+    if codec.is_null() {
+        return Err("Opus encoder not found".to_string());
+    }
+
+    enc.codec_ctx = avcodec_alloc_context3(codec);
+    if enc.codec_ctx.is_null() {
+        return Err("Failed to allocate codec context".to_string());
+    }
+    let codec_ctx = enc.codec_ctx;
+    (*codec_ctx).sample_rate = OPUS_SAMPLE_RATE;
+    (*codec_ctx).time_base = AVRational {
+        num: 1,
+        den: OPUS_SAMPLE_RATE,
+    };
+    (*codec_ctx).bit_rate = i64::from(bitrate_kbps) * 1000;
+    if native {
+        (*codec_ctx).sample_fmt = AVSampleFormat::AV_SAMPLE_FMT_FLTP;
+        (*codec_ctx).strict_std_compliance = FF_COMPLIANCE_EXPERIMENTAL;
+    } else {
+        (*codec_ctx).sample_fmt = AVSampleFormat::AV_SAMPLE_FMT_FLT;
+    }
+    // FFmpeg's encoder only does mono and stereo
+    let out_channels = channels.min(2);
+    av_channel_layout_default(&mut (*codec_ctx).ch_layout, out_channels as c_int);
+    if (*(*enc.fmt_ctx).oformat).flags & AVFMT_GLOBALHEADER as c_int != 0 {
+        (*codec_ctx).flags |= AV_CODEC_FLAG_GLOBAL_HEADER as c_int;
+    }
+
+    let ret = avcodec_open2(codec_ctx, codec, ptr::null_mut());
+    if ret < 0 {
+        return Err(format!("Failed to open encoder: {}", av_err_str(ret)));
+    }
+
+    enc.stream = avformat_new_stream(enc.fmt_ctx, ptr::null());
+    if enc.stream.is_null() {
+        return Err("Failed to create stream".to_string());
+    }
+    (*enc.stream).time_base = (*codec_ctx).time_base;
+    let ret = avcodec_parameters_from_context((*enc.stream).codecpar, codec_ctx);
+    if ret < 0 {
+        return Err(format!("Failed to copy codec params: {}", av_err_str(ret)));
+    }
+    let ret = avformat_write_header(enc.fmt_ctx, ptr::null_mut());
+    if ret < 0 {
+        return Err(format!("Failed to write header: {}", av_err_str(ret)));
+    }
+
+    // Resampler takes interleaved float and hands back interleaved float at
+    // 48 kHz in the encoder's channel layout
+    let mut in_layout: AVChannelLayout = std::mem::zeroed();
+    av_channel_layout_default(&mut in_layout, channels as c_int);
+    let ret = swr_alloc_set_opts2(
+        &mut enc.swr,
+        &(*codec_ctx).ch_layout,
+        AVSampleFormat::AV_SAMPLE_FMT_FLT,
+        OPUS_SAMPLE_RATE,
+        &in_layout,
+        AVSampleFormat::AV_SAMPLE_FMT_FLT,
+        sample_rate as c_int,
+        0,
+        ptr::null_mut(),
+    );
+    av_channel_layout_uninit(&mut in_layout);
+    if ret < 0 || swr_init(enc.swr) < 0 {
+        return Err("Failed to set up resampler".to_string());
+    }
+
+    enc.frame = av_frame_alloc();
+    enc.packet = av_packet_alloc();
+    if enc.frame.is_null() || enc.packet.is_null() {
+        return Err("Failed to allocate frame".to_string());
+    }
+    let frame_size = if (*codec_ctx).frame_size > 0 {
+        (*codec_ctx).frame_size as usize
+    } else {
+        960 // 20 ms, Opus's usual frame
+    };
+    (*enc.frame).format = (*codec_ctx).sample_fmt as c_int;
+    (*enc.frame).sample_rate = OPUS_SAMPLE_RATE;
+    (*enc.frame).nb_samples = frame_size as c_int;
+    av_channel_layout_copy(&mut (*enc.frame).ch_layout, &(*codec_ctx).ch_layout);
+    let ret = av_frame_get_buffer(enc.frame, 0);
+    if ret < 0 {
+        return Err(format!(
+            "Failed to allocate frame buffer: {}",
+            av_err_str(ret)
+        ));
+    }
+
+    let scale = 1.0 / (1u64 << (bits_per_sample.clamp(2, 32) - 1)) as f32;
+    let in_channels = channels as usize;
+    let out_channels = out_channels as usize;
+    let frame_len = frame_size * out_channels;
+    let mut pending: Vec<f32> = Vec::new();
+    let mut pts: i64 = 0;
+
+    for chunk in samples.chunks(OPUS_CHUNK_FRAMES * in_channels) {
+        let input: Vec<f32> = chunk.iter().map(|&s| s as f32 * scale).collect();
+        enc.resample(Some(&input), in_channels, out_channels, &mut pending)?;
+        let whole = pending.len() / frame_len * frame_len;
+        for frame_samples in pending[..whole].chunks_exact(frame_len) {
+            enc.encode_frame(frame_samples, out_channels, &mut pts)?;
+        }
+        pending.drain(..whole);
+    }
+
+    // Drain the resampler, then pad the last frame out with silence
+    enc.resample(None, in_channels, out_channels, &mut pending)?;
+    for frame_samples in pending.chunks(frame_len) {
+        let mut padded = frame_samples.to_vec();
+        padded.resize(frame_len, 0.0);
+        enc.encode_frame(&padded, out_channels, &mut pts)?;
+    }
+    enc.send(ptr::null())?;
+
+    let ret = av_write_trailer(enc.fmt_ctx);
+    if ret < 0 {
+        return Err(format!("Failed to write trailer: {}", av_err_str(ret)));
+    }
+    avio_flush(enc.avio);
+    drop(enc);
+
+    debug!("Encoded {} bytes of Opus data", write_ctx.data.len());
+
+    Ok(write_ctx.data)
+}
+
 /// Build a frame-accurate seektable by scanning FLAC frames.
 ///
 /// This scans the FLAC byte stream for frame sync codes (0xFF 0xF8/0xF9),
@@ -1311,7 +2185,22 @@ pub fn decode_audio_streaming(
     install_ffmpeg_log_callback();
     reset_ffmpeg_errors();
 
-    unsafe { decode_audio_streaming_impl(buffer, sink, samples_to_skip) }
+    // Setup failures happen before anything is pushed to the sink, so the
+    // stream can be rewound and opened again leniently. Skipping leading junk
+    // isn't attempted here since it needs the whole file up front.
+    let start = buffer.position();
+    match unsafe { decode_audio_streaming_impl(buffer.clone(), sink, samples_to_skip, None) } {
+        Err(e) if !sink.is_cancelled() && buffer.seek(start) => {
+            let fallback = DecodeFallback::Lenient;
+            warn!(
+                "Streaming decode failed ({}), retrying with {}",
+                e,
+                fallback.label()
+            );
+            unsafe { decode_audio_streaming_impl(buffer, sink, samples_to_skip, Some(fallback)) }
+        }
+        result => result,
+    }
 }
 
 /// Internal AVIO-based streaming decode
@@ -1319,6 +2208,7 @@ unsafe fn decode_audio_streaming_impl(
     buffer: SharedSparseBuffer,
     sink: &mut StreamingPcmSink,
     samples_to_skip: u64,
+    fallback: Option<DecodeFallback>,
 ) -> Result<(), String> {
     use ffmpeg_sys_next::*;
 
@@ -1362,8 +2252,15 @@ unsafe fn decode_audio_streaming_impl(
     }
     (*fmt_ctx).pb = avio;
 
+    let mut options = if fallback.is_some() {
+        apply_lenient_options(fmt_ctx)
+    } else {
+        ptr::null_mut()
+    };
+
     // Open input
-    let ret = avformat_open_input(&mut fmt_ctx, ptr::null(), ptr::null_mut(), ptr::null_mut());
+    let ret = avformat_open_input(&mut fmt_ctx, ptr::null(), ptr::null_mut(), &mut options);
+    av_dict_free(&mut options);
     if ret < 0 {
         avformat_free_context(fmt_ctx);
         let _ = Box::from_raw(avio_ctx_ptr);
@@ -1638,6 +2535,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decode_clean_file_needs_no_fallback() {
+        init();
+
+        let flac_data = encode_to_flac(&vec![0i32; 44100 * 2], 44100, 2, 16).unwrap();
+        let decoded = decode_audio(&flac_data, None, None).unwrap();
+        assert_eq!(decoded.fallback, None);
+    }
+
+    #[test]
+    fn test_decode_skips_leading_junk() {
+        init();
+
+        let flac_data = encode_to_flac(&vec![0i32; 44100 * 2], 44100, 2, 16).unwrap();
+        let mut data = vec![0u8; 4096];
+        data.extend_from_slice(&flac_data);
+
+        let decoded = decode_audio(&data, None, None).unwrap();
+        assert_eq!(decoded.sample_rate, 44100);
+        assert!(decoded.fallback.is_some());
+    }
+
+    #[test]
+    fn test_find_audio_start() {
+        assert_eq!(find_audio_start(b"junkfLaC...."), Some(4));
+        assert_eq!(find_audio_start(b"fLaC...."), None);
+        assert_eq!(find_audio_start(b"nothing here"), None);
+    }
+
     #[test]
     fn test_encode_mono() {
         init();
@@ -1964,4 +2890,131 @@ mod tests {
         init();
         check_seek_produces_correct_samples(96000, 2, 24);
     }
+
+    #[test]
+    fn test_verify_audio_clean_file() {
+        init();
+
+        let samples: Vec<i32> = (0..44100)
+            .map(|i| ((i as f64 * 0.05).sin() * 20000.0) as i32)
+            .collect();
+        let flac_data = encode_to_flac(&samples, 44100, 1, 16).unwrap();
+
+        let verification = verify_audio(&flac_data).unwrap();
+        assert_eq!(verification.decode_errors, 0);
+        assert_eq!(verification.clipped_runs, 0);
+        assert!(verification.duration_ms.abs_diff(1000) < 50);
+        if let Some(declared) = verification.declared_duration_ms {
+            assert!(declared.abs_diff(verification.duration_ms) < 50);
+        }
+    }
+
+    #[test]
+    fn test_verify_audio_detects_clipping() {
+        init();
+
+        // A sine driven well past full scale, flattened at the rails
+        let samples: Vec<i32> = (0..44100)
+            .map(|i| ((i as f64 * 0.05).sin() * 60000.0).clamp(-32768.0, 32767.0) as i32)
+            .collect();
+        let flac_data = encode_to_flac(&samples, 44100, 1, 16).unwrap();
+
+        let verification = verify_audio(&flac_data).unwrap();
+        assert!(verification.clipped_runs > 0);
+    }
+
+    #[test]
+    fn test_clip_detector_tracks_channels_separately() {
+        let mut detector = ClipDetector::new(2, 16);
+
+        // Left channel pinned for three samples, right alternating
+        detector.feed(&[32767, 0, 32767]);
+        detector.feed(&[32767, 32767, 0]);
+        assert_eq!(detector.clipped_runs(), 1);
+
+        // Single full-scale samples don't count
+        let mut detector = ClipDetector::new(1, 16);
+        detector.feed(&[32767, 0, -32768, 0, 32767]);
+        assert_eq!(detector.clipped_runs(), 0);
+    }
+
+    #[test]
+    fn test_loudness_meter_full_scale_square_wave() {
+        let mut meter = LoudnessMeter::new();
+        assert_eq!(meter.stats(), None);
+
+        meter.feed(&[32767, -32767, 32767, -32767], 16);
+        let stats = meter.stats().unwrap();
+        assert!(stats.peak_dbfs.abs() < 0.01);
+        assert!(stats.dynamic_range_db().abs() < 0.01);
+    }
+
+    #[test]
+    fn test_measure_loudness_sine_crest_factor() {
+        init();
+
+        // Half-scale sine: peak near -6 dBFS, RMS 3 dB below the peak
+        let samples: Vec<i32> = (0..44100)
+            .map(|i| ((i as f64 * 0.05).sin() * 16384.0) as i32)
+            .collect();
+        let flac_data = encode_to_flac(&samples, 44100, 1, 16).unwrap();
+
+        let mut meter = LoudnessMeter::new();
+        measure_loudness(&flac_data, &mut meter).unwrap();
+        let stats = meter.stats().unwrap();
+        assert!((stats.peak_dbfs + 6.0).abs() < 0.1);
+        assert!((stats.dynamic_range_db() - 3.01).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_dynamic_range_of_sine_is_zero() {
+        init();
+
+        // Steady half-scale sine: every block's peak matches its scaled RMS
+        let samples: Vec<i32> = (0..44100 * 10)
+            .map(|i| ((i as f64 * 0.05).sin() * 16384.0) as i32)
+            .collect();
+        let flac_data = encode_to_flac(&samples, 44100, 1, 16).unwrap();
+
+        let mut meter = DynamicRangeMeter::new();
+        measure_dynamic_range(&flac_data, &mut meter).unwrap();
+        assert!(meter.finish().unwrap().abs() < 0.1);
+    }
+
+    #[test]
+    fn test_dynamic_range_quiet_sine_with_full_scale_hits() {
+        // A sine at a tenth of full scale with one full-scale hit per block:
+        // peaks 20 dB above the RMS
+        let mut meter = DynamicRangeMeter::new();
+        for _ in 0..5 {
+            let mut block: Vec<i32> = (0..3000)
+                .map(|i| ((i as f64 * 0.05).sin() * 3276.7) as i32)
+                .collect();
+            block[10] = 32767;
+            meter.feed(&block, 1000, 1, 16);
+        }
+        assert!((meter.finish().unwrap() - 20.0).abs() < 0.5);
+
+        assert_eq!(DynamicRangeMeter::new().finish(), None);
+    }
+
+    #[test]
+    fn test_level_envelope_windows() {
+        init();
+
+        // 100ms of full-scale square wave, then 100ms of silence
+        let mut samples: Vec<i32> = (0..4410)
+            .map(|i| if i % 2 == 0 { 32767 } else { -32767 })
+            .collect();
+        samples.resize(8820, 0);
+        let flac_data = encode_to_flac(&samples, 44100, 1, 16).unwrap();
+
+        let mut envelope = LevelEnvelope::new(50);
+        measure_levels(&flac_data, &mut envelope).unwrap();
+        let levels = envelope.finish();
+        assert_eq!(levels.len(), 4);
+        assert!(levels[0] > 0.99 && levels[1] > 0.99);
+        assert_eq!(levels[2], 0.0);
+        assert_eq!(levels[3], 0.0);
+    }
 }
@@ -43,7 +43,10 @@ struct CacheEntry {
 }
 
 /// LRU cache manager for downloaded files
-#[derive(Clone)]
+///
+/// Pinned entries are never evicted and don't count against the limits: once
+/// only pinned entries are left, new files are stored anyway.
+#[derive(Clone, Debug)]
 pub struct CacheManager {
     config: CacheConfig,
     /// In-memory index of cached files (cache_key -> CacheEntry)
@@ -96,6 +99,29 @@ impl CacheManager {
         }
     }
 
+    /// Whether a file is cached
+    pub async fn contains(&self, key: &str) -> bool {
+        self.entries.read().await.contains_key(key)
+    }
+
+    /// Where a pinned file is cached. None unless it's both pinned and
+    /// cached; unpinned files can be evicted while they're being read.
+    pub async fn pinned_path(&self, key: &str) -> Option<PathBuf> {
+        let is_pinned = self.pinned.read().await.contains(key);
+        if !is_pinned {
+            return None;
+        }
+        let entries = self.entries.read().await;
+        entries.get(key).map(|entry| entry.file_path.clone())
+    }
+
+    /// Where a file is cached. Unless it's pinned, it can be evicted at any
+    /// time, so this suits readers that can cope with it going away.
+    pub async fn cached_path(&self, key: &str) -> Option<PathBuf> {
+        let entries = self.entries.read().await;
+        entries.get(key).map(|entry| entry.file_path.clone())
+    }
+
     /// Put a file into the cache
     pub async fn put(&self, key: &str, data: &[u8]) -> Result<(), CacheError> {
         let size = data.len() as u64;
@@ -122,6 +148,25 @@ impl CacheManager {
         Ok(())
     }
 
+    /// Check the cache directory is still there and writable
+    pub async fn check(&self) -> Result<(), CacheError> {
+        fs::create_dir_all(&self.config.cache_dir).await?;
+        // Not `.enc`, so never indexed as an entry
+        let probe = self.config.cache_dir.join(".health-check");
+        fs::write(&probe, b"ok").await?;
+        fs::remove_file(&probe).await?;
+        Ok(())
+    }
+
+    /// Rebuild the index from the files on disk, recreating the directory if
+    /// it went missing. Pins are kept.
+    pub async fn reload(&self) -> Result<(), CacheError> {
+        fs::create_dir_all(&self.config.cache_dir).await?;
+        self.entries.write().await.clear();
+        *self.current_size.write().await = 0;
+        self.load_existing_cache().await
+    }
+
     /// Load existing cache entries from disk on startup
     async fn load_existing_cache(&self) -> Result<(), CacheError> {
         let mut entries = self.entries.write().await;
@@ -168,21 +213,26 @@ impl CacheManager {
     async fn ensure_space_available(&self, needed_bytes: u64) -> Result<(), CacheError> {
         let mut entries = self.entries.write().await;
         let mut current_size = self.current_size.write().await;
-        while *current_size + needed_bytes > self.config.max_size_bytes && !entries.is_empty() {
-            self.evict_lru(&mut entries, &mut current_size).await?;
+        while *current_size + needed_bytes > self.config.max_size_bytes {
+            if !self.evict_lru(&mut entries, &mut current_size).await? {
+                break;
+            }
         }
-        while entries.len() >= self.config.max_files && !entries.is_empty() {
-            self.evict_lru(&mut entries, &mut current_size).await?;
+        while entries.len() >= self.config.max_files {
+            if !self.evict_lru(&mut entries, &mut current_size).await? {
+                break;
+            }
         }
         Ok(())
     }
 
-    /// Evict the least recently used entry
+    /// Evict the least recently used unpinned entry. Returns false if there
+    /// was none.
     async fn evict_lru(
         &self,
         entries: &mut HashMap<String, CacheEntry>,
         current_size: &mut u64,
-    ) -> Result<(), CacheError> {
+    ) -> Result<bool, CacheError> {
         let pinned = self.pinned.read().await;
         let lru_key = entries
             .iter()
@@ -201,9 +251,44 @@ impl CacheManager {
                 *current_size = current_size.saturating_sub(entry.size_bytes);
 
                 debug!("Evicted {} ({} bytes)", key, entry.size_bytes);
+                return Ok(true);
             }
         }
-        Ok(())
+        Ok(false)
+    }
+
+    /// Remove every unpinned entry. Returns how many files were removed and
+    /// how many bytes they took up.
+    pub async fn clear(&self) -> Result<(usize, u64), CacheError> {
+        let mut entries = self.entries.write().await;
+        let mut current_size = self.current_size.write().await;
+        let pinned = self.pinned.read().await;
+        let keys: Vec<String> = entries
+            .keys()
+            .filter(|key| !pinned.contains(*key))
+            .cloned()
+            .collect();
+        let mut freed_bytes = 0;
+        for key in &keys {
+            if let Some(entry) = entries.remove(key) {
+                if let Err(e) = fs::remove_file(&entry.file_path).await {
+                    warn!(
+                        "Failed to remove cache file {}: {}",
+                        entry.file_path.display(),
+                        e
+                    );
+                }
+                *current_size = current_size.saturating_sub(entry.size_bytes);
+                freed_bytes += entry.size_bytes;
+            }
+        }
+
+        info!(
+            "Cleared {} cache entries ({} bytes)",
+            keys.len(),
+            freed_bytes
+        );
+        Ok((keys.len(), freed_bytes))
     }
 
     /// Pin a cache entry to prevent it from being evicted
@@ -234,3 +319,65 @@ impl CacheManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn small_cache(dir: &TempDir, max_size_bytes: u64) -> CacheManager {
+        CacheManager::with_config(CacheConfig {
+            cache_dir: dir.path().to_path_buf(),
+            max_size_bytes,
+            max_files: 10,
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_eviction_skips_pinned_entries() {
+        let dir = TempDir::new().unwrap();
+        let cache = small_cache(&dir, 10).await;
+        cache.put("pinned", b"12345").await.unwrap();
+        cache.pin("pinned").await;
+        cache.put("other", b"12345").await.unwrap();
+
+        cache.put("new", b"12345").await.unwrap();
+        assert!(cache.contains("pinned").await);
+        assert!(!cache.contains("other").await);
+        assert!(cache.contains("new").await);
+    }
+
+    #[tokio::test]
+    async fn test_put_when_only_pinned_entries_left() {
+        let dir = TempDir::new().unwrap();
+        let cache = small_cache(&dir, 10).await;
+        cache.put("a", b"1234567890").await.unwrap();
+        cache.pin("a").await;
+
+        cache.put("b", b"12345").await.unwrap();
+        assert!(cache.contains("a").await);
+        assert!(cache.contains("b").await);
+        assert!(cache.pinned_path("a").await.is_some());
+        assert_eq!(cache.pinned_path("b").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_clear_removes_unpinned_entries() {
+        let dir = TempDir::new().unwrap();
+        let cache = small_cache(&dir, 100).await;
+        cache.put("pinned", b"12345").await.unwrap();
+        cache.pin("pinned").await;
+        cache.put("a", b"123").await.unwrap();
+        cache.put("b", b"1234").await.unwrap();
+        let removed_path = cache.cached_path("a").await.unwrap();
+
+        assert_eq!(cache.clear().await.unwrap(), (2, 7));
+        assert!(cache.contains("pinned").await);
+        assert!(!cache.contains("a").await);
+        assert!(!cache.contains("b").await);
+        assert!(!removed_path.exists());
+        assert!(cache.pinned_path("pinned").await.unwrap().exists());
+    }
+}
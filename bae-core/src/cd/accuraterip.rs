@@ -0,0 +1,276 @@
+//! AccurateRip verification
+//!
+//! AccurateRip keeps checksums of rips submitted by other people. A track
+//! whose checksum matches theirs was read the same way on other drives, so
+//! it's almost certainly an exact copy of the disc. Checksums are worked out
+//! over the PCM as it's ripped; the database is asked once per disc.
+use crate::cd::drive::CdToc;
+use crate::cd::ripper::RipResult;
+use crate::db::AccurateRipStatus;
+use tracing::{info, warn};
+/// Stereo frames per CD sector
+const FRAMES_PER_SECTOR: usize = 588;
+/// Frames left out at the start of the first track and the end of the last,
+/// where drives with different read offsets disagree
+const SKIPPED_FRAMES: usize = FRAMES_PER_SECTOR * 5;
+/// AccurateRip v1 and v2 checksums of a track's PCM (16-bit stereo, little-endian)
+pub fn track_checksums(pcm: &[u8], is_first_track: bool, is_last_track: bool) -> (u32, u32) {
+    let frames = pcm.len() / 4;
+    let check_start = if is_first_track {
+        SKIPPED_FRAMES - 1
+    } else {
+        0
+    };
+    let check_end = if is_last_track {
+        frames.saturating_sub(SKIPPED_FRAMES)
+    } else {
+        frames
+    };
+    let mut v1 = 0u32;
+    let mut v2 = 0u32;
+    for (index, frame) in pcm.chunks_exact(4).enumerate() {
+        let position = index + 1;
+        if position < check_start || position > check_end {
+            continue;
+        }
+        let sample = u32::from_le_bytes([frame[0], frame[1], frame[2], frame[3]]);
+        let product = sample as u64 * position as u64;
+        v1 = v1.wrapping_add(product as u32);
+        v2 = v2
+            .wrapping_add(product as u32)
+            .wrapping_add((product >> 32) as u32);
+    }
+    (v1, v2)
+}
+/// The three disc IDs AccurateRip files a disc under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccurateRipDiscId {
+    pub track_count: u8,
+    pub id1: u32,
+    pub id2: u32,
+    /// The disc's FreeDB ID
+    pub cddb: u32,
+}
+impl AccurateRipDiscId {
+    pub fn from_toc(toc: &CdToc) -> Self {
+        let lbas: Vec<u32> = toc.track_offsets.iter().map(|o| o - 150).collect();
+        let leadout = toc.leadout_offset - 150;
+        let mut id1 = leadout;
+        let mut id2 = leadout.wrapping_mul(lbas.len() as u32 + 1);
+        let mut digit_sum = 0;
+        for (index, lba) in lbas.iter().enumerate() {
+            id1 = id1.wrapping_add(*lba);
+            id2 = id2.wrapping_add((*lba).max(1).wrapping_mul(index as u32 + 1));
+            let mut seconds = (lba + 150) / 75;
+            while seconds > 0 {
+                digit_sum += seconds % 10;
+                seconds /= 10;
+            }
+        }
+        let first_seconds = toc.track_offsets.first().map_or(0, |o| o / 75);
+        let length_seconds = toc.leadout_offset / 75 - first_seconds;
+        Self {
+            track_count: lbas.len() as u8,
+            id1,
+            id2,
+            cddb: ((digit_sum % 0xff) << 24) | (length_seconds << 8) | lbas.len() as u32,
+        }
+    }
+    /// Where the database keeps this disc's checksums
+    pub fn url(&self) -> String {
+        format!(
+            "http://www.accuraterip.com/accuraterip/{:x}/{:x}/{:x}/dBAR-{:03}-{:08x}-{:08x}-{:08x}.bin",
+            self.id1 & 0xf,
+            (self.id1 >> 4) & 0xf,
+            (self.id1 >> 8) & 0xf,
+            self.track_count,
+            self.id1,
+            self.id2,
+            self.cddb,
+        )
+    }
+}
+/// One pressing's checksums from the database
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccurateRipEntry {
+    /// (confidence, checksum) per track
+    pub tracks: Vec<(u32, u32)>,
+}
+/// Parse a database response: one block per pressing, each a header followed
+/// by nine bytes per track
+pub fn parse_response(data: &[u8]) -> Result<Vec<AccurateRipEntry>, String> {
+    let mut entries = Vec::new();
+    let mut rest = data;
+    while !rest.is_empty() {
+        let track_count = rest[0] as usize;
+        let len = 13 + track_count * 9;
+        if rest.len() < len {
+            return Err("Truncated AccurateRip response".to_string());
+        }
+        let tracks = rest[13..len]
+            .chunks_exact(9)
+            .map(|t| (t[0] as u32, u32::from_le_bytes([t[1], t[2], t[3], t[4]])))
+            .collect();
+        entries.push(AccurateRipEntry { tracks });
+        rest = &rest[len..];
+    }
+    Ok(entries)
+}
+/// How a ripped track compares to the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrackVerification {
+    pub status: AccurateRipStatus,
+    /// Submissions that agree with the rip, or for a mismatch, the
+    /// submissions it was compared against
+    pub confidence: u32,
+}
+impl Default for TrackVerification {
+    fn default() -> Self {
+        Self {
+            status: AccurateRipStatus::Unchecked,
+            confidence: 0,
+        }
+    }
+}
+/// Compare one track's checksums with every pressing in the database
+pub fn verify_track(
+    entries: &[AccurateRipEntry],
+    track_index: usize,
+    checksums: (u32, u32),
+) -> TrackVerification {
+    let (v1, v2) = checksums;
+    let mut matching = 0u32;
+    let mut total = 0u32;
+    let mut found = false;
+    for (confidence, checksum) in entries.iter().filter_map(|e| e.tracks.get(track_index)) {
+        found = true;
+        total += confidence;
+        if *checksum == v1 || *checksum == v2 {
+            matching += confidence;
+        }
+    }
+    let (status, confidence) = if matching > 0 {
+        (AccurateRipStatus::Accurate, matching)
+    } else if found {
+        (AccurateRipStatus::Mismatch, total)
+    } else {
+        (AccurateRipStatus::NotFound, 0)
+    };
+    TrackVerification { status, confidence }
+}
+/// Look the disc up in AccurateRip and record each track's verification.
+/// Tracks stay unchecked if the database can't be reached.
+pub async fn verify_rip(toc: &CdToc, rip_results: &mut [RipResult]) {
+    let disc_id = AccurateRipDiscId::from_toc(toc);
+    let entries = match fetch_entries(&disc_id).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!("AccurateRip lookup failed: {}", e);
+            return;
+        }
+    };
+    info!(
+        "AccurateRip has {} pressing(s) of disc {:08x}",
+        entries.len(),
+        disc_id.id1
+    );
+    for (index, result) in rip_results.iter_mut().enumerate() {
+        result.accuraterip = verify_track(&entries, index, result.accuraterip_checksums);
+    }
+}
+/// A disc AccurateRip doesn't know is no entries, not an error
+async fn fetch_entries(disc_id: &AccurateRipDiscId) -> Result<Vec<AccurateRipEntry>, String> {
+    let client = reqwest::Client::builder()
+        .user_agent("bae/1.0 +https://github.com/hideselfview/bae")
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let response = client
+        .get(disc_id.url())
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach AccurateRip: {}", e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+    }
+    if !response.status().is_success() {
+        return Err(format!("AccurateRip returned status {}", response.status()));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read AccurateRip response: {}", e))?;
+    parse_response(&bytes)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn pcm(frames: &[u32]) -> Vec<u8> {
+        frames.iter().flat_map(|f| f.to_le_bytes()).collect()
+    }
+    #[test]
+    fn test_track_checksums() {
+        assert_eq!(track_checksums(&pcm(&[1, 2, 3]), false, false), (14, 14));
+        // v2 folds the high half of each product back in
+        assert_eq!(
+            track_checksums(&pcm(&[0, 0xFFFF_FFFF]), false, false),
+            (0xFFFF_FFFE, 0xFFFF_FFFF)
+        );
+    }
+    #[test]
+    fn test_track_checksums_skip_disc_edges() {
+        let ones = pcm(&[1; 3000]);
+        let (first, _) = track_checksums(&ones, true, false);
+        assert_eq!(first, (2939..=3000).sum::<u32>());
+        let (last, _) = track_checksums(&ones, false, true);
+        assert_eq!(last, (1..=60).sum::<u32>());
+        assert_eq!(track_checksums(&pcm(&[1; 100]), true, true), (0, 0));
+    }
+    #[test]
+    fn test_disc_id() {
+        let toc = CdToc {
+            disc_id: "test".to_string(),
+            first_track: 1,
+            last_track: 3,
+            leadout_offset: 45150,
+            track_offsets: vec![150, 15150, 30150],
+        };
+        let disc_id = AccurateRipDiscId::from_toc(&toc);
+        assert_eq!(disc_id.id1, 0x00015f90);
+        assert_eq!(disc_id.id2, 0x000493e1);
+        assert_eq!(disc_id.cddb, 0x0c025803);
+        assert_eq!(
+            disc_id.url(),
+            "http://www.accuraterip.com/accuraterip/0/9/f/dBAR-003-00015f90-000493e1-0c025803.bin"
+        );
+    }
+    #[test]
+    fn test_parse_and_verify() {
+        let mut data = Vec::new();
+        for (confidence, checksum) in [(12u8, 0xAAAA_AAAAu32), (3, 0xBBBB_BBBB)] {
+            data.extend_from_slice(&[2]);
+            data.extend_from_slice(&[0; 12]);
+            for track_checksum in [checksum, 0x1234_5678] {
+                data.push(confidence);
+                data.extend_from_slice(&track_checksum.to_le_bytes());
+                data.extend_from_slice(&[0; 4]);
+            }
+        }
+        let entries = parse_response(&data).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].tracks, vec![(3, 0xBBBB_BBBB), (3, 0x1234_5678)]);
+        let accurate = verify_track(&entries, 0, (0xBBBB_BBBB, 0));
+        assert_eq!(accurate.status, AccurateRipStatus::Accurate);
+        assert_eq!(accurate.confidence, 3);
+        let both = verify_track(&entries, 1, (0, 0x1234_5678));
+        assert_eq!(both.confidence, 15);
+        let mismatch = verify_track(&entries, 0, (1, 2));
+        assert_eq!(mismatch.status, AccurateRipStatus::Mismatch);
+        assert_eq!(mismatch.confidence, 15);
+        assert_eq!(
+            verify_track(&entries, 2, (1, 2)).status,
+            AccurateRipStatus::NotFound
+        );
+        assert!(parse_response(&data[..20]).is_err());
+    }
+}
@@ -1,6 +1,8 @@
 //! CD drive detection and TOC reading
 use discid::DiscId;
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use thiserror::Error;
 #[derive(Debug, Error)]
 pub enum CdDriveError {
@@ -19,13 +21,43 @@ pub struct CdDrive {
     pub device_path: PathBuf,
     pub name: String,
 }
+/// Drives with a rip in progress. Each drive rips one disc at a time, while
+/// separate drives rip in parallel.
+#[derive(Debug, Clone, Default)]
+pub struct BusyDrives(Arc<Mutex<HashSet<PathBuf>>>);
+impl BusyDrives {
+    /// Claim a drive for a rip. None if it's already ripping.
+    pub fn claim(&self, device_path: &Path) -> Option<DriveClaim> {
+        let inserted = self.0.lock().unwrap().insert(device_path.to_path_buf());
+        inserted.then(|| DriveClaim {
+            drives: self.clone(),
+            device_path: device_path.to_path_buf(),
+        })
+    }
+    pub fn is_busy(&self, device_path: &Path) -> bool {
+        self.0.lock().unwrap().contains(device_path)
+    }
+}
+/// A drive claimed for a rip. The drive is free again once this is dropped.
+#[derive(Debug)]
+pub struct DriveClaim {
+    drives: BusyDrives,
+    device_path: PathBuf,
+}
+impl Drop for DriveClaim {
+    fn drop(&mut self) {
+        self.drives.0.lock().unwrap().remove(&self.device_path);
+    }
+}
 /// Table of Contents (TOC) information from a CD
 #[derive(Debug, Clone)]
 pub struct CdToc {
     pub disc_id: String,
     pub first_track: u8,
     pub last_track: u8,
-    pub leadout_track: u8,
+    /// Sector where the lead-out starts, counted like `track_offsets`
+    pub leadout_offset: u32,
+    /// Sector where each track starts (LBA + 150)
     pub track_offsets: Vec<u32>,
 }
 impl CdDrive {
@@ -48,6 +80,18 @@ impl CdDrive {
         }
         Ok(drives)
     }
+    /// Whether an audio CD is in this drive. False if the drive can't be opened.
+    pub fn has_audio_disc(&self) -> bool {
+        use crate::cd::ffi::LibcdioDrive;
+        LibcdioDrive::open(&self.device_path)
+            .map(|drive| drive.has_audio_disc())
+            .unwrap_or(false)
+    }
+    /// Eject the disc in this drive
+    pub fn eject(&self) -> Result<(), CdDriveError> {
+        crate::cd::ffi::eject_media(&self.device_path)
+            .map_err(|e| CdDriveError::Access(format!("Failed to eject: {}", e)))
+    }
     /// Read TOC from the disc in this drive
     pub fn read_toc(&self) -> Result<CdToc, CdDriveError> {
         use crate::cd::ffi::LibcdioDrive;
@@ -72,7 +116,6 @@ impl CdDrive {
         let leadout_lba = drive
             .leadout_lba()
             .map_err(|e| CdDriveError::DiscId(format!("Failed to get leadout: {}", e)))?;
-        let leadout_track = (leadout_lba + 150) as u8;
         let device_str = self
             .device_path
             .to_str()
@@ -83,8 +126,24 @@ impl CdDrive {
             disc_id: disc.id(),
             first_track,
             last_track,
-            leadout_track,
+            leadout_offset: leadout_lba + 150,
             track_offsets,
         })
     }
 }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_drive_claim_is_exclusive_until_dropped() {
+        let drives = BusyDrives::default();
+        let sr0 = Path::new("/dev/sr0");
+        let claim = drives.claim(sr0).unwrap();
+        assert!(drives.is_busy(sr0));
+        assert!(drives.claim(sr0).is_none());
+        assert!(drives.claim(Path::new("/dev/sr1")).is_some());
+        drop(claim);
+        assert!(!drives.is_busy(sr0));
+        assert!(drives.claim(sr0).is_some());
+    }
+}
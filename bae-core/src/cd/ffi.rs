@@ -113,6 +113,12 @@ impl LibcdioDrive {
             Ok(lba as u32)
         }
     }
+    /// Check if the disc is an audio CD (including mixed-mode discs with audio tracks)
+    pub fn has_audio_disc(&self) -> bool {
+        let mode = unsafe { libcdio_sys::cdio_get_discmode(self.device) };
+        mode == libcdio_sys::discmode_t_CDIO_DISC_MODE_CD_DA
+            || mode == libcdio_sys::discmode_t_CDIO_DISC_MODE_CD_MIXED
+    }
     /// Get the raw device pointer (for advanced operations)
     pub fn device_ptr(&self) -> *mut libcdio_sys::CdIo_t {
         self.device
@@ -127,6 +133,20 @@ impl Drop for LibcdioDrive {
         }
     }
 }
+/// Eject the disc in the drive at `device_path`. The drive must not be open.
+pub fn eject_media(device_path: &Path) -> Result<(), LibcdioError> {
+    let path_str = device_path.to_str().ok_or(LibcdioError::InvalidPath)?;
+    let c_path = CString::new(path_str).map_err(|_| LibcdioError::InvalidPath)?;
+    let result = unsafe { libcdio_sys::cdio_eject_media_drive(c_path.as_ptr()) };
+    if result == libcdio_sys::driver_return_code_t_DRIVER_OP_SUCCESS {
+        Ok(())
+    } else {
+        Err(LibcdioError::Libcdio(format!(
+            "Failed to eject {} (driver code {})",
+            path_str, result
+        )))
+    }
+}
 /// Detect available CD drives
 pub fn detect_drives() -> Result<Vec<PathBuf>, LibcdioError> {
     unsafe {
@@ -1,6 +1,7 @@
 //! Log file generation (EAC-style)
 use crate::cd::drive::CdToc;
 use crate::cd::ripper::RipResult;
+use crate::db::AccurateRipStatus;
 use std::io::Write;
 use std::path::PathBuf;
 /// Generates EAC-style log files documenting the ripping process
@@ -28,8 +29,13 @@ impl LogGenerator {
         writeln!(file)?;
         writeln!(file, "Read mode               : Secure")?;
         writeln!(file, "Utilize accurate stream : Yes")?;
-        writeln!(file, "Defeat audio cache      : Yes")?;
-        writeln!(file, "Make use of C2 pointers : No")?;
+        writeln!(file, "Defeat audio cache      : No")?;
+        let c2_pointers = rip_results.iter().any(|r| r.c2_pointers);
+        writeln!(
+            file,
+            "Make use of C2 pointers : {}",
+            if c2_pointers { "Yes" } else { "No" }
+        )?;
         writeln!(file)?;
         writeln!(file, "Read offset correction                      : 0")?;
         writeln!(file, "Overread into Lead-In and Lead-Out          : No")?;
@@ -98,23 +104,69 @@ impl LogGenerator {
             writeln!(file)?;
             writeln!(file, "     Pre-gap length : 00:00:00")?;
             writeln!(file)?;
-            writeln!(file, "     Track quality : 100.0 %")?;
-            writeln!(file, "         Test CRC : {:08X}", result.crc32)?;
-            writeln!(file, "         Copy CRC : {:08X}", result.crc32)?;
-            writeln!(file)?;
-            if result.errors > 0 {
-                writeln!(file, "     There were errors")?;
-                writeln!(file, "     Errors: {}", result.errors)?;
-                writeln!(file)?;
+            writeln!(file, "     Copy CRC {:08X}", result.crc32)?;
+            let (v1, v2) = result.accuraterip_checksums;
+            let verification = result.accuraterip;
+            match verification.status {
+                AccurateRipStatus::Accurate => writeln!(
+                    file,
+                    "     Accurately ripped (confidence {})  [{:08X}]  [{:08X}]",
+                    verification.confidence, v1, v2
+                )?,
+                AccurateRipStatus::Mismatch => writeln!(
+                    file,
+                    "     Cannot be verified as accurate (confidence {})  [{:08X}]  [{:08X}]",
+                    verification.confidence, v1, v2
+                )?,
+                AccurateRipStatus::NotFound => {
+                    writeln!(file, "     Track not present in AccurateRip database")?
+                }
+                AccurateRipStatus::Unchecked => {
+                    writeln!(file, "     AccurateRip database could not be reached")?
+                }
             }
+            let stats = result.stats;
+            if stats.is_clean() {
+                writeln!(file, "     Copy OK")?;
+            } else {
+                writeln!(file, "     Read errors        : {}", stats.read_errors)?;
+                writeln!(file, "     C2 errors          : {}", stats.c2_errors)?;
+                writeln!(
+                    file,
+                    "     Suspicious sectors : {}",
+                    stats.suspicious_sectors
+                )?;
+            }
+            writeln!(file)?;
         }
         writeln!(file)?;
-        let total_errors: u32 = rip_results.iter().map(|r| r.errors).sum();
-        if total_errors > 0 {
-            writeln!(file, "There were errors during extraction")?;
-            writeln!(file, "Total errors: {}", total_errors)?;
+        let count = |status: AccurateRipStatus| {
+            rip_results
+                .iter()
+                .filter(|r| r.accuraterip.status == status)
+                .count()
+        };
+        let accurate = count(AccurateRipStatus::Accurate);
+        if accurate == rip_results.len() {
+            writeln!(file, "All tracks accurately ripped")?;
         } else {
+            writeln!(
+                file,
+                "{} track(s) accurately ripped, {} not matching, {} not present in database",
+                accurate,
+                count(AccurateRipStatus::Mismatch),
+                count(AccurateRipStatus::NotFound)
+            )?;
+        }
+        writeln!(file)?;
+        let suspicious: u32 = rip_results.iter().map(|r| r.stats.suspicious_sectors).sum();
+        if rip_results.iter().all(|r| r.stats.is_clean()) {
             writeln!(file, "No errors occurred")?;
+        } else if suspicious > 0 {
+            writeln!(file, "There were errors during extraction")?;
+            writeln!(file, "Suspicious sectors: {}", suspicious)?;
+        } else {
+            writeln!(file, "Errors were corrected by re-reading")?;
         }
         writeln!(file)?;
         writeln!(file, "End of status report")?;
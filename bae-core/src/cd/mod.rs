@@ -1,14 +1,18 @@
 //! CD ripping module
 //!
-//! Provides functionality for ripping audio CDs using libcdio-paranoia
-//! for accurate audio extraction with error correction.
+//! Provides functionality for ripping audio CDs using libcdio in secure
+//! mode, with C2 error checking and AccurateRip verification.
+pub mod accuraterip;
 pub mod cue_generator;
 pub mod drive;
 pub mod ffi;
 pub mod log_generator;
 pub mod paranoia;
 pub mod ripper;
+pub mod secure;
+pub mod watcher;
 pub use cue_generator::CueGenerator;
-pub use drive::CdDrive;
+pub use drive::{BusyDrives, CdDrive, DriveClaim};
 pub use log_generator::LogGenerator;
 pub use ripper::{CdRipper, RipProgress};
+pub use watcher::watch_for_discs;
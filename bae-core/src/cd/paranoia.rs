@@ -1,9 +1,10 @@
 //! libcdio-paranoia FFI bindings for error-corrected CD audio reading
 //!
-//! This module provides safe wrappers around libcdio-paranoia functions
-//! for accurate audio extraction with error correction.
+//! This module provides safe wrappers around libcdio functions for secure
+//! audio extraction, using the drive's C2 error pointers where it has them.
 use crate::cd::ffi::LibcdioDrive;
 use crate::cd::ripper::RipProgress;
+use crate::cd::secure::{read_sector_secure, ReadStats, SectorRead, C2_BYTES, SECTOR_BYTES};
 use libc;
 use libcdio_sys;
 use thiserror::Error;
@@ -15,9 +16,15 @@ pub enum ParanoiaError {
     #[error("Read error: {0}")]
     Read(String),
 }
+/// `CDIO_MMC_READ_TYPE_CDDA`, for `mmc_read_cd`
+const READ_TYPE_CDDA: libc::c_int = 1;
+/// Ask `mmc_read_cd` for the 294 bytes of C2 error pointers after the audio
+const C2_ERROR_BITS: u8 = 1;
 /// Paranoia CDDA reader for error-corrected audio extraction
 pub struct ParanoiaReader {
     drive: LibcdioDrive,
+    /// The drive returns C2 error pointers with audio reads
+    c2_pointers: bool,
 }
 impl ParanoiaReader {
     /// Create a new paranoia reader for a CD drive
@@ -25,176 +32,145 @@ impl ParanoiaReader {
         if !drive.has_disc() {
             return Err(ParanoiaError::Init("No disc in drive".to_string()));
         }
-        Ok(Self { drive })
+        let mut reader = Self {
+            drive,
+            c2_pointers: false,
+        };
+        // Drives without C2 support reject the read outright
+        reader.c2_pointers = reader.read_sector_c2(0).is_some();
+        tracing::info!("Drive C2 error pointers: {}", reader.c2_pointers);
+        Ok(reader)
     }
-    /// Read audio sectors with paranoia error correction and progress updates
+    /// Whether reads are checked with the drive's C2 error pointers rather
+    /// than by reading every sector twice
+    pub fn uses_c2_pointers(&self) -> bool {
+        self.c2_pointers
+    }
+    fn read_sector(&self, lba: u32) -> Option<SectorRead> {
+        if self.c2_pointers {
+            return self.read_sector_c2(lba);
+        }
+        let mut data = vec![0u8; SECTOR_BYTES];
+        let result = unsafe {
+            libcdio_sys::cdio_read_audio_sector(
+                self.drive.device_ptr(),
+                data.as_mut_ptr() as *mut libc::c_void,
+                lba as libcdio_sys::lba_t,
+            )
+        };
+        (result == 0).then_some(SectorRead {
+            data,
+            c2_error: false,
+        })
+    }
+    fn read_sector_c2(&self, lba: u32) -> Option<SectorRead> {
+        let mut buffer = vec![0u8; SECTOR_BYTES + C2_BYTES];
+        let result = unsafe {
+            libcdio_sys::mmc_read_cd(
+                self.drive.device_ptr(),
+                buffer.as_mut_ptr() as *mut libc::c_void,
+                lba as libcdio_sys::lsn_t,
+                READ_TYPE_CDDA,
+                false,
+                false,
+                0,
+                true,
+                false,
+                C2_ERROR_BITS,
+                0,
+                (SECTOR_BYTES + C2_BYTES) as u16,
+                1,
+            )
+        };
+        if result != 0 {
+            return None;
+        }
+        let c2_error = buffer[SECTOR_BYTES..].iter().any(|b| *b != 0);
+        buffer.truncate(SECTOR_BYTES);
+        Some(SectorRead {
+            data: buffer,
+            c2_error,
+        })
+    }
+    /// Read audio sectors in secure mode, with progress updates
     ///
-    /// This uses libcdio's paranoia mode for accurate audio extraction
-    /// with error correction and jitter handling.
+    /// Each sector is re-read until the drive agrees with itself, see
+    /// [`crate::cd::secure`]. Unreadable sectors are filled with silence.
     /// Progress updates are sent every 1000 sectors if progress_tx is provided.
-    pub fn read_audio_sectors_paranoia_with_progress(
+    pub fn read_audio_sectors_secure(
         &self,
         start_lba: u32,
         num_sectors: u32,
         progress_tx: Option<mpsc::UnboundedSender<RipProgress>>,
         current_track: u8,
         total_tracks: u8,
-    ) -> Result<(Vec<u8>, u32), ParanoiaError> {
+    ) -> Result<(Vec<u8>, ReadStats), ParanoiaError> {
+        if num_sectors == 0 {
+            return Err(ParanoiaError::Read("No sectors to read".to_string()));
+        }
         tracing::info!(
-            "Starting paranoia read: {} sectors starting at LBA {}",
+            "Reading {} sectors starting at LBA {} (will read LBAs {} to {} inclusive)",
             num_sectors,
-            start_lba
+            start_lba,
+            start_lba,
+            start_lba + num_sectors - 1
         );
-        unsafe {
-            let sector_size = libcdio_sys::CDIO_CD_FRAMESIZE_RAW as usize;
-            let total_size = (num_sectors as usize) * sector_size;
-            let mut buffer = vec![0u8; total_size];
-            let mut errors = 0u32;
+        let track_index = (current_track - 1) as f32;
+        let send_progress = |track_fraction: f32| {
             if let Some(ref tx) = progress_tx {
-                let track_index = (current_track - 1) as usize;
-                let overall_percent = ((track_index as f32 / total_tracks as f32) * 100.0) as u8;
-                let _ = tx.send(RipProgress {
-                    percent: overall_percent,
-                });
+                let percent = ((track_index + track_fraction) / total_tracks as f32 * 100.0) as u8;
+                let _ = tx.send(RipProgress { percent });
             }
-            tracing::info!(
-                "Reading {} sectors starting at LBA {} (will read LBAs {} to {} inclusive)",
-                num_sectors,
-                start_lba,
-                start_lba,
-                start_lba + num_sectors - 1
-            );
-            let mut consecutive_failures = 0;
-            const MAX_CONSECUTIVE_FAILURES: u32 = 10;
-            for i in 0..num_sectors {
-                let lba = (start_lba + i) as libcdio_sys::lba_t;
-                let is_last_sector = i == num_sectors - 1;
-                let expected_last_lba = (start_lba + num_sectors - 1) as libcdio_sys::lba_t;
-                if lba > expected_last_lba {
-                    return Err(ParanoiaError::Read(format!(
-                        "LBA {} exceeds expected range (start={}, expected_last={})",
-                        lba, start_lba, expected_last_lba,
-                    )));
+        };
+        send_progress(0.0);
+        let mut buffer = vec![0u8; num_sectors as usize * SECTOR_BYTES];
+        let mut stats = ReadStats::default();
+        let mut consecutive_failures = 0;
+        const MAX_CONSECUTIVE_FAILURES: u32 = 10;
+        for i in 0..num_sectors {
+            let lba = start_lba + i;
+            if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                tracing::warn!(
+                    "Hit {} consecutive unreadable sectors - TOC leadout likely extends beyond readable area",
+                    consecutive_failures
+                );
+                tracing::warn!(
+                    "XLD-style truncation: Zero-filling remaining {} sectors (LBA {} to {})",
+                    num_sectors - i,
+                    lba,
+                    start_lba + num_sectors - 1
+                );
+                break;
+            }
+            if i > 0 && i % 1000 == 0 {
+                send_progress(i as f32 / num_sectors as f32);
+            }
+            let offset = i as usize * SECTOR_BYTES;
+            match read_sector_secure(|| self.read_sector(lba), self.c2_pointers, &mut stats) {
+                Some(data) => {
+                    buffer[offset..offset + SECTOR_BYTES].copy_from_slice(&data);
+                    consecutive_failures = 0;
                 }
-                if is_last_sector || lba == 46058 || (i > 0 && i % 5000 == 0) {
-                    tracing::info!(
-                        "Reading sector {} of {}: LBA {} (start_lba={}, expected_last={})",
-                        i + 1,
-                        num_sectors,
+                None => {
+                    consecutive_failures += 1;
+                    tracing::error!(
+                        "⚠️  UNREADABLE SECTOR: LBA {} filled with zeros, sector {} of {} will be SILENT in the output (track {})",
                         lba,
-                        start_lba,
-                        expected_last_lba
-                    );
-                }
-                if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
-                    tracing::warn!(
-                        "Hit {} consecutive unreadable sectors - TOC leadout likely extends beyond readable area",
-                        consecutive_failures
-                    );
-                    tracing::warn!(
-                        "XLD-style truncation: Zero-filling remaining {} sectors (LBA {} to {})",
-                        num_sectors - i,
-                        lba,
-                        start_lba + num_sectors - 1
-                    );
-                    let remaining_offset = (i as usize) * sector_size;
-                    buffer[remaining_offset..].fill(0);
-                    break;
-                }
-                let mut retries = 3;
-                let mut success = false;
-                let sectors_read = i + 1;
-                let should_send_progress = (i > 0 && i % 1000 == 0) || is_last_sector;
-                if should_send_progress {
-                    let track_progress = (sectors_read as f32 / num_sectors as f32) * 100.0;
-                    tracing::info!(
-                        "Reading sectors: {}/{} ({:.1}%){}",
-                        sectors_read,
+                        i + 1,
                         num_sectors,
-                        track_progress,
-                        if is_last_sector { " [LAST SECTOR]" } else { "" }
-                    );
-                    if let Some(ref tx) = progress_tx {
-                        let track_index = (current_track - 1) as usize;
-                        let track_progress_percent = track_progress / 100.0;
-                        let overall_percent = ((track_index as f32 + track_progress_percent)
-                            / total_tracks as f32
-                            * 100.0) as u8;
-                        let _ = tx.send(RipProgress {
-                            percent: overall_percent,
-                        });
-                    }
-                }
-                if is_last_sector {
-                    tracing::info!("About to read last sector at LBA {}", lba);
-                }
-                let mut attempt_num = 1;
-                while retries > 0 && !success {
-                    if is_last_sector {
-                        tracing::info!(
-                            "Reading last sector at LBA {}, attempt {} of 3",
-                            lba,
-                            attempt_num
-                        );
-                    }
-                    let result = libcdio_sys::cdio_read_audio_sector(
-                        self.drive.device_ptr(),
-                        buffer.as_mut_ptr().add((i as usize) * sector_size) as *mut libc::c_void,
-                        lba,
+                        current_track
                     );
-                    if result == 0 {
-                        success = true;
-                        consecutive_failures = 0;
-                        if is_last_sector {
-                            tracing::info!("Last sector read successfully at LBA {}", lba);
-                        }
-                    } else {
-                        if attempt_num == 1 {
-                            tracing::warn!(
-                                "Failed to read sector at LBA {} (attempt {} of 3, error code: {})",
-                                lba,
-                                attempt_num,
-                                result
-                            );
-                        }
-                        retries -= 1;
-                        errors += 1;
-                        attempt_num += 1;
-                        if retries == 0 {
-                            let sector_offset = (i as usize) * sector_size;
-                            let sector_slice =
-                                &mut buffer[sector_offset..sector_offset + sector_size];
-                            sector_slice.fill(0);
-                            consecutive_failures += 1;
-                            tracing::error!(
-                                "⚠️  UNREADABLE SECTOR: LBA {} failed after {} retries (error code: {})",
-                                lba, attempt_num - 1, result
-                            );
-                            tracing::error!(
-                                "⚠️  FILLED WITH ZEROS: Sector {} of {} will be SILENT in the output (track {})",
-                                i + 1, num_sectors, current_track
-                            );
-                            success = true;
-                        }
-                    }
                 }
             }
-            tracing::info!("Finished reading all {} sectors", num_sectors);
-            if let Some(ref tx) = progress_tx {
-                tracing::info!(
-                    "Sending final progress update (100%) for track {}",
-                    current_track
-                );
-                let track_index = (current_track - 1) as usize;
-                let overall_percent =
-                    (((track_index + 1) as f32 / total_tracks as f32) * 100.0) as u8;
-                let _ = tx.send(RipProgress {
-                    percent: overall_percent,
-                });
-                tracing::info!("Final progress update sent");
-            }
-            tracing::info!("Returning from read_audio_sectors_paranoia_with_progress");
-            Ok((buffer, errors))
         }
+        tracing::info!(
+            "Finished reading {} sectors: {} read errors, {} C2 errors, {} suspicious",
+            num_sectors,
+            stats.read_errors,
+            stats.c2_errors,
+            stats.suspicious_sectors
+        );
+        send_progress(1.0);
+        Ok((buffer, stats))
     }
 }
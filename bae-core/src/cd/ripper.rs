@@ -1,5 +1,7 @@
 //! CD ripping logic - streams bytes directly to FLAC encoder
+use crate::cd::accuraterip::{track_checksums, TrackVerification};
 use crate::cd::drive::{CdDrive, CdToc};
+use crate::cd::secure::ReadStats;
 use std::path::PathBuf;
 use thiserror::Error;
 use tokio::sync::mpsc;
@@ -26,9 +28,16 @@ pub struct RipResult {
     pub track_number: u8,
     pub output_path: PathBuf,
     pub bytes_written: u64,
-    pub errors: u32,
+    pub stats: ReadStats,
+    /// Reads were checked with the drive's C2 error pointers
+    pub c2_pointers: bool,
     pub duration_ms: u64,
+    /// CRC32 of the track's PCM, the log's copy CRC
     pub crc32: u32,
+    /// AccurateRip v1 and v2 checksums of the PCM
+    pub accuraterip_checksums: (u32, u32),
+    /// Filled in by [`crate::cd::accuraterip::verify_rip`] once the disc is ripped
+    pub accuraterip: TrackVerification,
 }
 /// CD ripper that streams audio directly to FLAC encoder
 pub struct CdRipper {
@@ -92,25 +101,30 @@ impl CdRipper {
         let channels = 2u32;
         let bits_per_sample = 16u32;
         let total_tracks = self.toc.last_track - self.toc.first_track + 1;
-        let (samples, errors) = match self
-            .read_track_samples(track_num, progress_tx, total_tracks)
+        let (pcm, stats, c2_pointers) = match self
+            .read_track_pcm(track_num, progress_tx, total_tracks)
             .await
         {
             Ok(data) => {
-                tracing::info!("read_track_samples returned Ok for track {}", track_num);
+                tracing::info!("read_track_pcm returned Ok for track {}", track_num);
                 data
             }
             Err(e) => {
-                tracing::info!(
-                    "read_track_samples returned Err for track {}: {}",
-                    track_num,
-                    e
-                );
+                tracing::info!("read_track_pcm returned Err for track {}: {}", track_num, e);
                 return Err(e);
             }
         };
+        let crc32 = crc32fast::hash(&pcm);
+        let accuraterip_checksums = track_checksums(
+            &pcm,
+            track_num == self.toc.first_track,
+            track_num == self.toc.last_track,
+        );
+        let samples: Vec<i32> = pcm
+            .chunks_exact(2)
+            .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]) as i32)
+            .collect();
         let flac_data = self.encode_to_flac(&samples, sample_rate, channels, bits_per_sample)?;
-        let crc32 = crc32fast::hash(&flac_data);
         tokio::fs::write(&output_path, &flac_data)
             .await
             .map_err(RipError::Io)?;
@@ -119,19 +133,22 @@ impl CdRipper {
             track_number: track_num,
             output_path,
             bytes_written: flac_data.len() as u64,
-            errors,
+            stats,
+            c2_pointers,
             duration_ms,
             crc32,
+            accuraterip_checksums,
+            accuraterip: TrackVerification::default(),
         })
     }
-    /// Read raw samples from a track using libcdio-paranoia
-    /// Returns samples and error count
-    async fn read_track_samples(
+    /// Read a track's PCM in secure mode
+    /// Returns the PCM, its read stats and whether C2 pointers were used
+    async fn read_track_pcm(
         &self,
         track_num: u8,
         progress_tx: Option<&mpsc::UnboundedSender<RipProgress>>,
         total_tracks: u8,
-    ) -> Result<(Vec<i32>, u32), RipError> {
+    ) -> Result<(Vec<u8>, ReadStats, bool), RipError> {
         use crate::cd::ffi::LibcdioDrive;
         use crate::cd::paranoia::ParanoiaReader;
         use tracing::info;
@@ -215,61 +232,25 @@ impl CdRipper {
                 "Paranoia reader initialized, reading {} sectors...",
                 num_sectors_for_read
             );
-            info!("Calling read_audio_sectors_paranoia_with_progress...");
-            let result = paranoia_reader.read_audio_sectors_paranoia_with_progress(
+            let c2_pointers = paranoia_reader.uses_c2_pointers();
+            let result = paranoia_reader.read_audio_sectors_secure(
                 start_lba_for_read,
                 num_sectors_for_read,
                 progress_tx_for_blocking,
                 track_num,
                 total_tracks,
             );
-            info!("Paranoia read completed, got result, checking if Ok...");
-            match &result {
-                Ok((buf, errs)) => {
-                    info!("Result is Ok: {} bytes, {} errors", buf.len(), errs);
-                }
-                Err(e) => {
-                    info!("Result is Err: {}", e);
-                }
-            }
-            info!("Unwrapping result...");
-            let mapped_result = result.map_err(|e| {
-                info!("Mapping error: {}", e);
-                RipError::Read(format!("Failed to read sectors: {}", e))
-            });
-            info!(
-                "Result mapped, returning from blocking task (buffer size: {:?})",
-                mapped_result.as_ref().ok().map(|(buf, _)| buf.len())
-            );
-            mapped_result
+            result
+                .map(|(buf, stats)| (buf, stats, c2_pointers))
+                .map_err(|e| RipError::Read(format!("Failed to read sectors: {}", e)))
         });
         info!("Blocking task spawned, awaiting result...");
         let result = blocking_task
             .await
             .map_err(|e| RipError::Read(format!("Task failed: {}", e)))?;
-        info!("Blocking task awaited successfully, unwrapping result...");
-        let (audio_data, errors) = match result {
-            Ok(data) => {
-                info!("Result is Ok, unwrapping tuple...");
-                data
-            }
-            Err(e) => {
-                info!("Result is Err: {}, returning error", e);
-                return Err(e);
-            }
-        };
-        info!("Blocking task completed, audio data received");
-        info!(
-            "Audio data read: {} bytes, {} errors",
-            audio_data.len(),
-            errors
-        );
-        let mut samples = Vec::with_capacity(audio_data.len() / 2);
-        for chunk in audio_data.chunks_exact(2) {
-            let sample = i16::from_le_bytes([chunk[0], chunk[1]]) as i32;
-            samples.push(sample);
-        }
-        Ok((samples, errors))
+        let (audio_data, stats, c2_pointers) = result?;
+        info!("Audio data read: {} bytes, {:?}", audio_data.len(), stats);
+        Ok((audio_data, stats, c2_pointers))
     }
     /// Encode samples to FLAC using FFmpeg
     fn encode_to_flac(
@@ -0,0 +1,130 @@
+//! Secure-mode sector reads
+//!
+//! Every sector is read until the drive gives the same audio twice, or,
+//! on drives that report C2 error pointers, until a read comes back with no
+//! C2 errors. A sector that never settles keeps its last read and is counted
+//! as suspicious, since its audio may be wrong.
+/// Bytes of audio in a CD sector
+pub const SECTOR_BYTES: usize = 2352;
+/// Bytes of C2 error pointers after a sector's audio, one bit per audio byte
+pub const C2_BYTES: usize = 294;
+/// Reads of one sector before it's given up on
+pub const MAX_READS: u32 = 16;
+/// One read of a sector
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectorRead {
+    pub data: Vec<u8>,
+    /// The drive flagged some of the audio as unreliable
+    pub c2_error: bool,
+}
+/// Error counts for a track, as reported in the rip log
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadStats {
+    /// Reads the drive failed outright
+    pub read_errors: u32,
+    /// Sectors the drive flagged with C2 errors at least once
+    pub c2_errors: u32,
+    /// Sectors no two reads agreed on
+    pub suspicious_sectors: u32,
+}
+impl ReadStats {
+    pub fn is_clean(&self) -> bool {
+        self.read_errors == 0 && self.c2_errors == 0 && self.suspicious_sectors == 0
+    }
+}
+/// Failed reads of a sector, with nothing read from it yet, before it's
+/// treated as unreadable
+const MAX_FAILED_READS: u32 = 3;
+/// Read one sector securely. `read` reads it once from the drive, None if
+/// the drive failed. Returns None if the sector couldn't be read at all.
+pub fn read_sector_secure(
+    mut read: impl FnMut() -> Option<SectorRead>,
+    use_c2: bool,
+    stats: &mut ReadStats,
+) -> Option<Vec<u8>> {
+    let mut last: Option<Vec<u8>> = None;
+    let mut flagged = false;
+    let mut failed = 0;
+    for _ in 0..MAX_READS {
+        let Some(sector) = read() else {
+            stats.read_errors += 1;
+            failed += 1;
+            if last.is_none() && failed >= MAX_FAILED_READS {
+                return None;
+            }
+            continue;
+        };
+        flagged |= sector.c2_error;
+        let settled = if use_c2 {
+            !sector.c2_error
+        } else {
+            last.as_ref() == Some(&sector.data)
+        };
+        if settled {
+            stats.c2_errors += flagged as u32;
+            return Some(sector.data);
+        }
+        last = Some(sector.data);
+    }
+    stats.c2_errors += flagged as u32;
+    stats.suspicious_sectors += last.is_some() as u32;
+    last
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    /// A drive that gives back `reads` in order, then fails
+    fn drive(reads: Vec<Option<(u8, bool)>>) -> impl FnMut() -> Option<SectorRead> {
+        let mut reads = reads.into_iter();
+        move || {
+            let (byte, c2_error) = reads.next().flatten()?;
+            Some(SectorRead {
+                data: vec![byte; 4],
+                c2_error,
+            })
+        }
+    }
+    #[test]
+    fn test_reads_until_two_agree() {
+        let mut stats = ReadStats::default();
+        let data = read_sector_secure(
+            drive(vec![Some((1, false)), Some((2, false)), Some((2, false))]),
+            false,
+            &mut stats,
+        );
+        assert_eq!(data, Some(vec![2; 4]));
+        assert!(stats.is_clean());
+    }
+    #[test]
+    fn test_c2_reads_until_clean() {
+        let mut stats = ReadStats::default();
+        let data = read_sector_secure(drive(vec![Some((1, false))]), true, &mut stats);
+        assert_eq!(data, Some(vec![1; 4]));
+        assert!(stats.is_clean());
+        let data = read_sector_secure(
+            drive(vec![Some((1, true)), None, Some((3, false))]),
+            true,
+            &mut stats,
+        );
+        assert_eq!(data, Some(vec![3; 4]));
+        assert_eq!(stats.c2_errors, 1);
+        assert_eq!(stats.read_errors, 1);
+        assert_eq!(stats.suspicious_sectors, 0);
+    }
+    #[test]
+    fn test_unsettled_sector_is_suspicious() {
+        let mut stats = ReadStats::default();
+        let reads = (0..MAX_READS).map(|i| Some((i as u8, true))).collect();
+        let data = read_sector_secure(drive(reads), true, &mut stats);
+        assert_eq!(data, Some(vec![MAX_READS as u8 - 1; 4]));
+        assert_eq!(stats.c2_errors, 1);
+        assert_eq!(stats.suspicious_sectors, 1);
+    }
+    #[test]
+    fn test_unreadable_sector() {
+        let mut stats = ReadStats::default();
+        assert_eq!(read_sector_secure(drive(vec![]), false, &mut stats), None);
+        assert_eq!(stats.read_errors, MAX_FAILED_READS);
+        assert_eq!(stats.suspicious_sectors, 0);
+    }
+}
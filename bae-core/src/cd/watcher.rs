@@ -0,0 +1,112 @@
+//! Audio CD insertion detection
+//!
+//! Polls the CD drives for audio discs and reports each disc that shows up.
+//! Discs already in a drive when watching starts aren't reported, so opening
+//! the app with a disc loaded doesn't start anything on its own.
+use crate::cd::CdDrive;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// How often drives are checked for a new disc
+pub const DISC_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Which drives held an audio disc at the last poll
+#[derive(Debug, Default)]
+struct DiscPresence {
+    loaded: HashSet<PathBuf>,
+}
+
+impl DiscPresence {
+    /// Record the drives holding an audio disc now, returning the ones that
+    /// didn't at the last poll
+    fn update(&mut self, loaded: HashSet<PathBuf>) -> Vec<PathBuf> {
+        let mut inserted: Vec<PathBuf> = loaded.difference(&self.loaded).cloned().collect();
+        inserted.sort();
+        self.loaded = loaded;
+        inserted
+    }
+}
+
+/// Drives holding an audio disc right now
+fn loaded_drives() -> HashSet<PathBuf> {
+    match CdDrive::detect_drives() {
+        Ok(drives) => drives
+            .into_iter()
+            .filter(|drive| drive.has_audio_disc())
+            .map(|drive| drive.device_path)
+            .collect(),
+        Err(e) => {
+            warn!("Failed to list CD drives: {}", e);
+            HashSet::new()
+        }
+    }
+}
+
+/// Watch for audio CDs being inserted. Yields the drive each time a disc
+/// goes in; stops when the receiver is dropped.
+pub fn watch_for_discs() -> mpsc::UnboundedReceiver<CdDrive> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let mut presence = DiscPresence::default();
+        let mut first_poll = true;
+        loop {
+            let loaded = tokio::task::spawn_blocking(loaded_drives)
+                .await
+                .unwrap_or_default();
+            let inserted = presence.update(loaded);
+            if !first_poll {
+                for device_path in inserted {
+                    info!("Audio CD inserted in {:?}", device_path);
+                    let name = device_path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("Unknown")
+                        .to_string();
+                    if tx.send(CdDrive { device_path, name }).is_err() {
+                        return;
+                    }
+                }
+            }
+            first_poll = false;
+            tokio::time::sleep(DISC_POLL_INTERVAL).await;
+            if tx.is_closed() {
+                return;
+            }
+        }
+    });
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drives(paths: &[&str]) -> HashSet<PathBuf> {
+        paths.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn test_reports_only_new_discs() {
+        let mut presence = DiscPresence::default();
+        presence.update(drives(&["/dev/sr0"]));
+        assert!(presence.update(drives(&["/dev/sr0"])).is_empty());
+        assert_eq!(
+            presence.update(drives(&["/dev/sr0", "/dev/sr1"])),
+            vec![PathBuf::from("/dev/sr1")]
+        );
+    }
+
+    #[test]
+    fn test_reinserted_disc_is_reported_again() {
+        let mut presence = DiscPresence::default();
+        presence.update(drives(&["/dev/sr0"]));
+        assert!(presence.update(drives(&[])).is_empty());
+        assert_eq!(
+            presence.update(drives(&["/dev/sr0"])),
+            vec![PathBuf::from("/dev/sr0")]
+        );
+    }
+}
@@ -1,9 +1,17 @@
 use aws_config::{BehaviorVersion, Region};
 use aws_credential_types::Credentials;
+use aws_sdk_s3::types::{ChecksumMode, GlacierJobParameters, RestoreRequest, StorageClass, Tier};
 use aws_sdk_s3::{primitives::ByteStreamError, Client, Error as S3Error};
+use base64::Engine;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::ops::Range;
 use thiserror::Error;
 use tracing::{debug, error, info, warn};
+mod b2;
+mod multipart;
+pub use b2::{B2CloudStorage, B2Config};
+pub use multipart::{PendingUpload, UploadJournal, UploadedPart, MULTIPART_THRESHOLD};
 #[derive(Error, Debug)]
 pub enum CloudStorageError {
     #[error("S3 error: {0}")]
@@ -18,6 +26,15 @@ pub enum CloudStorageError {
     Config(String),
     #[error("Download error: {0}")]
     Download(String),
+    #[error("Integrity error: {0}")]
+    Integrity(String),
+    #[error("Conflict: {0}")]
+    Conflict(String),
+    /// The key already holds another object, which must not be replaced
+    #[error("Occupied: {0}")]
+    Occupied(String),
+    #[error("B2 error: {0}")]
+    B2(String),
 }
 /// S3 configuration for cloud storage
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,10 +70,72 @@ impl S3Config {
         Ok(())
     }
 }
+/// Whether a stored object can be streamed right now.
+///
+/// Variants are ordered from most to least readable, so the status of a
+/// release is the `max` over its files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ArchiveStatus {
+    /// Object is in a standard tier and readable
+    Available,
+    /// A temporary restored copy of an archived object is readable
+    Restored,
+    /// Restore has been requested and is still running
+    Restoring,
+    /// Object is in an archival tier and must be restored before reading
+    Archived,
+}
+impl ArchiveStatus {
+    pub fn is_readable(self) -> bool {
+        matches!(self, ArchiveStatus::Available | ArchiveStatus::Restored)
+    }
+}
+/// Parse the S3 `x-amz-restore` header of an archived object.
+///
+/// The header looks like `ongoing-request="false", expiry-date="..."`.
+/// A missing header means no restore has been requested.
+fn archive_status_from_restore_header(restore: Option<&str>) -> ArchiveStatus {
+    match restore {
+        Some(header) if header.contains("ongoing-request=\"true\"") => ArchiveStatus::Restoring,
+        Some(header) if header.contains("ongoing-request=\"false\"") => ArchiveStatus::Restored,
+        _ => ArchiveStatus::Archived,
+    }
+}
+/// Result of an upload whose integrity was checked against the provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedUpload {
+    pub storage_location: String,
+    /// The provider returned a checksum and it matched ours. False when the
+    /// provider doesn't return checksums (some S3-compatible services).
+    pub checksum_verified: bool,
+}
 /// Trait for cloud storage operations (allows mocking for tests)
 #[async_trait::async_trait]
 pub trait CloudStorage: Send + Sync {
     async fn upload(&self, key: &str, data: &[u8]) -> Result<String, CloudStorageError>;
+    /// Upload and confirm the provider stored the same bytes.
+    /// Storage without provider-side checksums reports the upload unverified.
+    async fn upload_verified(
+        &self,
+        key: &str,
+        data: &[u8],
+    ) -> Result<VerifiedUpload, CloudStorageError> {
+        Ok(VerifiedUpload {
+            storage_location: self.upload(key, data).await?,
+            checksum_verified: false,
+        })
+    }
+    /// Upload so an interrupted attempt can pick up where it stopped, keeping
+    /// track of what was sent in `journal`. Storage without resumable uploads
+    /// starts over every time.
+    async fn upload_resumable(
+        &self,
+        key: &str,
+        data: &[u8],
+        _journal: &dyn UploadJournal,
+    ) -> Result<VerifiedUpload, CloudStorageError> {
+        self.upload_verified(key, data).await
+    }
     async fn download(&self, storage_location: &str) -> Result<Vec<u8>, CloudStorageError>;
     /// Download a specific byte range from storage.
     /// Range is inclusive start, exclusive end: [start, end)
@@ -67,6 +146,117 @@ pub trait CloudStorage: Send + Sync {
         end: u64,
     ) -> Result<Vec<u8>, CloudStorageError>;
     async fn delete(&self, storage_location: &str) -> Result<(), CloudStorageError>;
+    /// Check whether an object is readable or sits in an archival tier.
+    /// Storage without archival tiers is always available.
+    async fn archive_status(
+        &self,
+        _storage_location: &str,
+    ) -> Result<ArchiveStatus, CloudStorageError> {
+        Ok(ArchiveStatus::Available)
+    }
+    /// Request a temporary restored copy of an archived object, kept for `days`.
+    /// Requesting a restore that is already running succeeds.
+    async fn request_restore(
+        &self,
+        _storage_location: &str,
+        _days: u32,
+    ) -> Result<(), CloudStorageError> {
+        Ok(())
+    }
+}
+/// Upload attempts before giving up on a checksum mismatch or a write
+/// conflict
+const MAX_UPLOAD_ATTEMPTS: u32 = 3;
+/// Why the last attempt of a single-PUT upload failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UploadFailure {
+    /// The body was corrupted in transit or stored with the wrong checksum
+    Integrity,
+    /// Another write to the same key was in progress
+    Conflict,
+}
+/// The error for an upload that failed every attempt
+fn upload_attempts_exhausted(key: &str, last_failure: UploadFailure) -> CloudStorageError {
+    match last_failure {
+        UploadFailure::Integrity => CloudStorageError::Integrity(format!(
+            "Upload of {} failed checksum verification after {} attempts",
+            key, MAX_UPLOAD_ATTEMPTS
+        )),
+        UploadFailure::Conflict => CloudStorageError::Conflict(format!(
+            "{} was still being written by another upload after {} attempts",
+            key, MAX_UPLOAD_ATTEMPTS
+        )),
+    }
+}
+/// Base64 SHA-256, the form S3 uses in `x-amz-checksum-sha256`
+fn sha256_base64(data: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(Sha256::digest(data))
+}
+/// Outcome of comparing our checksum with the one the provider returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChecksumCheck {
+    Verified,
+    /// Provider didn't return a checksum
+    Unverified,
+    Mismatch,
+}
+fn check_returned_checksum(expected: &str, returned: Option<&str>) -> ChecksumCheck {
+    match returned {
+        Some(returned) if returned == expected => ChecksumCheck::Verified,
+        Some(_) => ChecksumCheck::Mismatch,
+        None => ChecksumCheck::Unverified,
+    }
+}
+/// Condition a write puts on the object already at its key, so two devices
+/// importing into the same bucket can't silently overwrite each other
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum WritePrecondition {
+    /// Nothing may exist at the key yet (`If-None-Match: *`)
+    Absent,
+    /// The key must still hold the object we wrote (`If-Match: <etag>`)
+    OurWrite(String),
+}
+/// Decide what to do when a conditional write finds another object at the
+/// key. The same bytes mean the work is already done (an interrupted import
+/// run again, or another device uploading identical data); anything else is
+/// someone else's object and must not be replaced. The caller keeps ours
+/// beside it instead.
+fn reconcile_existing_object(
+    key: &str,
+    expected: &str,
+    existing: Option<&str>,
+) -> Result<(), CloudStorageError> {
+    match check_returned_checksum(expected, existing) {
+        ChecksumCheck::Verified => Ok(()),
+        ChecksumCheck::Mismatch => Err(CloudStorageError::Occupied(format!(
+            "{} already holds different data, likely written by another device",
+            key
+        ))),
+        ChecksumCheck::Unverified => Err(CloudStorageError::Occupied(format!(
+            "{} already exists and its contents can't be compared",
+            key
+        ))),
+    }
+}
+/// Generate the object key using hash-based partitioning for better distribution
+fn object_key(key: &str) -> String {
+    if key.len() < 4 {
+        return format!("files/misc/{}", key);
+    }
+    let prefix = &key[..2];
+    let subprefix = &key[2..4];
+    format!("files/{}/{}/{}", prefix, subprefix, key)
+}
+/// Split `len` bytes into parts of `part_size`; the last part takes the rest
+fn part_ranges(len: usize, part_size: usize) -> Vec<Range<usize>> {
+    (0..len)
+        .step_by(part_size.max(1))
+        .map(|start| start..(start + part_size).min(len))
+        .collect()
+}
+/// Whether a storage location points into a cloud bucket rather than a local path
+pub fn is_cloud_location(location: &str) -> bool {
+    location.starts_with("s3://") || location.starts_with("b2://")
 }
 /// Format AWS SDK error for better debugging
 fn format_error_details(err: &dyn std::fmt::Debug) -> String {
@@ -192,35 +382,173 @@ impl S3CloudStorage {
             bucket_name,
         })
     }
-    /// Generate S3 key using hash-based partitioning for better distribution
-    fn object_key(&self, key: &str) -> String {
-        if key.len() < 4 {
-            return format!("files/misc/{}", key);
-        }
-        let prefix = &key[..2];
-        let subprefix = &key[2..4];
-        format!("files/{}/{}/{}", prefix, subprefix, key)
+
+    /// Check the bucket exists and the credentials can reach it
+    pub async fn check_bucket(&self) -> Result<(), CloudStorageError> {
+        self.client
+            .head_bucket()
+            .bucket(&self.bucket_name)
+            .send()
+            .await
+            .map_err(|e| {
+                CloudStorageError::SdkError(format!(
+                    "Bucket '{}' isn't reachable: {}",
+                    self.bucket_name,
+                    format_error_details(&e)
+                ))
+            })?;
+        Ok(())
     }
 }
 #[async_trait::async_trait]
 impl CloudStorage for S3CloudStorage {
     async fn upload(&self, key: &str, data: &[u8]) -> Result<String, CloudStorageError> {
-        let s3_key = self.object_key(key);
+        Ok(self.upload_verified(key, data).await?.storage_location)
+    }
 
-        debug!("Uploading {} ({} bytes)", key, data.len());
-        self.client
-            .put_object()
-            .bucket(&self.bucket_name)
-            .key(&s3_key)
-            .body(data.to_vec().into())
-            .content_type("application/octet-stream")
-            .send()
-            .await
-            .map_err(|e| CloudStorageError::SdkError(format!("Put object failed: {}", e)))?;
+    /// Sends our SHA-256 with the object so S3 rejects corrupted bodies, then
+    /// compares the checksum S3 echoes back. Mismatches and rejected bodies
+    /// are retried up to [`MAX_UPLOAD_ATTEMPTS`] times. So is a write that
+    /// conflicts with another in progress, after backing off.
+    ///
+    /// Writes are conditional: the first only succeeds if the key is empty,
+    /// and a retry only replaces the object our earlier attempt wrote. If
+    /// the condition fails, the object found there is reconciled against
+    /// ours with [`reconcile_existing_object`]. Nothing is ever written
+    /// unconditionally.
+    async fn upload_verified(
+        &self,
+        key: &str,
+        data: &[u8],
+    ) -> Result<VerifiedUpload, CloudStorageError> {
+        let s3_key = object_key(key);
+        let checksum = sha256_base64(data);
         let storage_location = format!("s3://{}/{}", self.bucket_name, s3_key);
 
-        debug!("Successfully uploaded to {}", storage_location);
-        Ok(storage_location)
+        let mut precondition = WritePrecondition::Absent;
+        let mut last_failure = UploadFailure::Integrity;
+        for attempt in 1..=MAX_UPLOAD_ATTEMPTS {
+            debug!(
+                "Uploading {} ({} bytes, attempt {})",
+                key,
+                data.len(),
+                attempt
+            );
+
+            let request = self
+                .client
+                .put_object()
+                .bucket(&self.bucket_name)
+                .key(&s3_key)
+                .body(data.to_vec().into())
+                .content_type("application/octet-stream")
+                .checksum_sha256(&checksum);
+            let request = match &precondition {
+                WritePrecondition::Absent => request.if_none_match("*"),
+                WritePrecondition::OurWrite(etag) => request.if_match(etag),
+            };
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(e) if format!("{:?}", e).contains("BadDigest") => {
+                    warn!("S3 rejected {} as corrupted in transit, retrying", key);
+                    last_failure = UploadFailure::Integrity;
+                    continue;
+                }
+                Err(e) if format!("{:?}", e).contains("ConditionalRequestConflict") => {
+                    last_failure = UploadFailure::Conflict;
+                    if attempt < MAX_UPLOAD_ATTEMPTS {
+                        let delay = multipart::backoff_delay(attempt);
+                        warn!(
+                            "Another write to {} is in progress, retrying in {:?}",
+                            key, delay
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                    continue;
+                }
+                Err(e) if format!("{:?}", e).contains("PreconditionFailed") => {
+                    let existing = self
+                        .client
+                        .head_object()
+                        .bucket(&self.bucket_name)
+                        .key(&s3_key)
+                        .checksum_mode(ChecksumMode::Enabled)
+                        .send()
+                        .await
+                        .map_err(|e| {
+                            CloudStorageError::SdkError(format!("Head object failed: {}", e))
+                        })?;
+                    reconcile_existing_object(key, &checksum, existing.checksum_sha256())?;
+                    info!(
+                        "{} already holds the same data, keeping it",
+                        storage_location
+                    );
+                    return Ok(VerifiedUpload {
+                        storage_location,
+                        checksum_verified: true,
+                    });
+                }
+                Err(e) => {
+                    return Err(CloudStorageError::SdkError(format!(
+                        "Put object failed: {}",
+                        e
+                    )));
+                }
+            };
+
+            match check_returned_checksum(&checksum, response.checksum_sha256()) {
+                ChecksumCheck::Verified => {
+                    debug!("Uploaded and verified {}", storage_location);
+                    return Ok(VerifiedUpload {
+                        storage_location,
+                        checksum_verified: true,
+                    });
+                }
+                ChecksumCheck::Unverified => {
+                    debug!(
+                        "Uploaded {} (provider returned no checksum)",
+                        storage_location
+                    );
+                    return Ok(VerifiedUpload {
+                        storage_location,
+                        checksum_verified: false,
+                    });
+                }
+                ChecksumCheck::Mismatch => {
+                    warn!(
+                        "Checksum mismatch for {} (attempt {}), retrying",
+                        storage_location, attempt
+                    );
+                    last_failure = UploadFailure::Integrity;
+                    // Our bad copy is at the key now, so the retry may only
+                    // replace that copy. Without an ETag to pin it, a retry
+                    // could replace another device's object instead.
+                    let Some(etag) = response.e_tag() else {
+                        return Err(CloudStorageError::Integrity(format!(
+                            "{} was stored with the wrong checksum and can't be safely replaced",
+                            key
+                        )));
+                    };
+                    precondition = WritePrecondition::OurWrite(etag.to_string());
+                }
+            }
+        }
+
+        Err(upload_attempts_exhausted(key, last_failure))
+    }
+
+    /// Objects from [`MULTIPART_THRESHOLD`] up go up as multipart uploads,
+    /// see [`S3CloudStorage::upload_multipart`]; smaller ones as one PUT.
+    async fn upload_resumable(
+        &self,
+        key: &str,
+        data: &[u8],
+        journal: &dyn UploadJournal,
+    ) -> Result<VerifiedUpload, CloudStorageError> {
+        if data.len() < MULTIPART_THRESHOLD {
+            return self.upload_verified(key, data).await;
+        }
+        self.upload_multipart(key, data, journal).await
     }
 
     async fn download(&self, storage_location: &str) -> Result<Vec<u8>, CloudStorageError> {
@@ -309,4 +637,166 @@ impl CloudStorage for S3CloudStorage {
         debug!("Successfully deleted from {}", storage_location);
         Ok(())
     }
+
+    async fn archive_status(
+        &self,
+        storage_location: &str,
+    ) -> Result<ArchiveStatus, CloudStorageError> {
+        let key = storage_location
+            .strip_prefix(&format!("s3://{}/", self.bucket_name))
+            .ok_or_else(|| {
+                CloudStorageError::Download(format!("Invalid S3 location: {}", storage_location))
+            })?;
+
+        let head = self
+            .client
+            .head_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| CloudStorageError::SdkError(format!("Head object failed: {}", e)))?;
+
+        // Glacier Instant Retrieval is readable without a restore
+        let in_archival_tier = matches!(
+            head.storage_class(),
+            Some(StorageClass::Glacier | StorageClass::DeepArchive)
+        ) || head.archive_status().is_some();
+        if !in_archival_tier {
+            return Ok(ArchiveStatus::Available);
+        }
+
+        Ok(archive_status_from_restore_header(head.restore()))
+    }
+
+    async fn request_restore(
+        &self,
+        storage_location: &str,
+        days: u32,
+    ) -> Result<(), CloudStorageError> {
+        let key = storage_location
+            .strip_prefix(&format!("s3://{}/", self.bucket_name))
+            .ok_or_else(|| {
+                CloudStorageError::Download(format!("Invalid S3 location: {}", storage_location))
+            })?;
+
+        let job_parameters = GlacierJobParameters::builder()
+            .tier(Tier::Standard)
+            .build()
+            .map_err(|e| CloudStorageError::SdkError(format!("Invalid restore request: {}", e)))?;
+        let restore_request = RestoreRequest::builder()
+            .days(days as i32)
+            .glacier_job_parameters(job_parameters)
+            .build();
+
+        info!(
+            "Requesting restore of {} for {} days",
+            storage_location, days
+        );
+
+        match self
+            .client
+            .restore_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .restore_request(restore_request)
+            .send()
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(e) if format!("{:?}", e).contains("RestoreAlreadyInProgress") => {
+                debug!("Restore already in progress for {}", storage_location);
+                Ok(())
+            }
+            Err(e) => Err(CloudStorageError::SdkError(format!(
+                "Restore object failed: {}",
+                format_error_details(&e)
+            ))),
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_status_from_restore_header() {
+        assert_eq!(
+            archive_status_from_restore_header(None),
+            ArchiveStatus::Archived
+        );
+        assert_eq!(
+            archive_status_from_restore_header(Some("ongoing-request=\"true\"")),
+            ArchiveStatus::Restoring
+        );
+        assert_eq!(
+            archive_status_from_restore_header(Some(
+                "ongoing-request=\"false\", expiry-date=\"Fri, 21 Dec 2012 00:00:00 GMT\""
+            )),
+            ArchiveStatus::Restored
+        );
+    }
+
+    #[test]
+    fn test_sha256_base64() {
+        assert_eq!(
+            sha256_base64(b"abc"),
+            "ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0="
+        );
+    }
+
+    #[test]
+    fn test_check_returned_checksum() {
+        let expected = sha256_base64(b"abc");
+        assert_eq!(
+            check_returned_checksum(&expected, Some(&expected)),
+            ChecksumCheck::Verified
+        );
+        assert_eq!(
+            check_returned_checksum(&expected, Some("AAAA")),
+            ChecksumCheck::Mismatch
+        );
+        assert_eq!(
+            check_returned_checksum(&expected, None),
+            ChecksumCheck::Unverified
+        );
+    }
+
+    #[test]
+    fn test_reconcile_existing_object() {
+        let expected = sha256_base64(b"abc");
+        assert!(reconcile_existing_object("k", &expected, Some(&expected)).is_ok());
+        assert!(matches!(
+            reconcile_existing_object("k", &expected, Some("AAAA")),
+            Err(CloudStorageError::Occupied(_))
+        ));
+        assert!(matches!(
+            reconcile_existing_object("k", &expected, None),
+            Err(CloudStorageError::Occupied(_))
+        ));
+    }
+
+    #[test]
+    fn test_upload_attempts_exhausted_reports_last_failure() {
+        assert!(matches!(
+            upload_attempts_exhausted("k", UploadFailure::Conflict),
+            CloudStorageError::Conflict(_)
+        ));
+        assert!(matches!(
+            upload_attempts_exhausted("k", UploadFailure::Integrity),
+            CloudStorageError::Integrity(_)
+        ));
+    }
+
+    #[test]
+    fn test_release_status_is_least_readable_file() {
+        let statuses = [
+            ArchiveStatus::Available,
+            ArchiveStatus::Restoring,
+            ArchiveStatus::Restored,
+        ];
+        assert_eq!(statuses.into_iter().max(), Some(ArchiveStatus::Restoring));
+        assert!(!ArchiveStatus::Restoring.is_readable());
+        assert!(ArchiveStatus::Restored.is_readable());
+    }
 }
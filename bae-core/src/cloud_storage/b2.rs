@@ -0,0 +1,652 @@
+//! Backblaze B2 native API storage
+//!
+//! B2's S3-compatible endpoint has quirks with large uploads and checksums,
+//! so B2 profiles talk to the native API instead. Files past the account's
+//! recommended part size go up as B2 large files, every body carries the
+//! SHA1 B2 checks it against, and deleting a file removes all its versions,
+//! since buckets keep old versions (and bill for them) by default.
+use super::{
+    check_returned_checksum, object_key, part_ranges, reconcile_existing_object, ChecksumCheck,
+    CloudStorage, CloudStorageError, VerifiedUpload, MAX_UPLOAD_ATTEMPTS,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+const AUTHORIZE_URL: &str = "https://api.backblazeb2.com/b2api/v2/b2_authorize_account";
+
+/// B2 configuration for cloud storage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct B2Config {
+    pub bucket_name: String,
+    /// Application key ID
+    pub key_id: String,
+    pub application_key: String,
+}
+
+impl B2Config {
+    pub fn validate(&self) -> Result<(), CloudStorageError> {
+        if self.bucket_name.trim().is_empty() {
+            return Err(CloudStorageError::Config(
+                "Bucket name cannot be empty".to_string(),
+            ));
+        }
+        if self.key_id.trim().is_empty() {
+            return Err(CloudStorageError::Config(
+                "Application key ID cannot be empty".to_string(),
+            ));
+        }
+        if self.application_key.trim().is_empty() {
+            return Err(CloudStorageError::Config(
+                "Application key cannot be empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// An authorized session. Tokens last a day, then calls fail with
+/// `expired_auth_token` and the account is authorized again.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Authorization {
+    account_id: String,
+    authorization_token: String,
+    api_url: String,
+    download_url: String,
+    recommended_part_size: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Bucket {
+    bucket_id: String,
+    bucket_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BucketList {
+    buckets: Vec<Bucket>,
+}
+
+/// Where to send a file or part, with the token that goes with it
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadUrl {
+    upload_url: String,
+    authorization_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileVersion {
+    file_id: String,
+    file_name: String,
+    #[serde(default)]
+    content_sha1: Option<String>,
+    #[serde(default)]
+    file_info: HashMap<String, String>,
+}
+
+impl FileVersion {
+    /// SHA1 of the whole file. B2 has none for large files; we record it in
+    /// `large_file_sha1` when starting them.
+    fn sha1(&self) -> Option<&str> {
+        match self.content_sha1.as_deref() {
+            Some(sha1) if sha1 != "none" => Some(sha1.trim_start_matches("unverified:")),
+            _ => self.file_info.get("large_file_sha1").map(String::as_str),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FileList {
+    files: Vec<FileVersion>,
+}
+
+/// A failed call, from B2's error body. Transport failures have status 0.
+#[derive(Debug, Deserialize)]
+struct ApiError {
+    #[serde(default)]
+    status: u16,
+    #[serde(default)]
+    code: String,
+    #[serde(default)]
+    message: String,
+}
+
+impl ApiError {
+    fn transport(e: reqwest::Error) -> Self {
+        ApiError {
+            status: 0,
+            code: "transport".to_string(),
+            message: e.to_string(),
+        }
+    }
+
+    fn is_expired_auth(&self) -> bool {
+        self.status == 401 && matches!(self.code.as_str(), "expired_auth_token" | "bad_auth_token")
+    }
+
+    /// B2 expects uploads that fail this way to get a fresh upload URL and
+    /// try again. A SHA1 mismatch means the body was corrupted in transit.
+    fn is_retryable_upload(&self) -> bool {
+        matches!(self.status, 0 | 401 | 408 | 429)
+            || self.status >= 500
+            || (self.status == 400 && self.message.contains("Sha1 did not match"))
+    }
+
+    fn into_error(self, call: &str) -> CloudStorageError {
+        CloudStorageError::B2(format!("{} failed: {} ({})", call, self.message, self.code))
+    }
+}
+
+async fn check_response(
+    response: reqwest::Result<reqwest::Response>,
+) -> Result<reqwest::Response, ApiError> {
+    let response = response.map_err(ApiError::transport)?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(serde_json::from_str(&body).unwrap_or(ApiError {
+        status: status.as_u16(),
+        code: String::new(),
+        message: body,
+    }))
+}
+
+async fn read_json<T: DeserializeOwned>(
+    response: reqwest::Result<reqwest::Response>,
+) -> Result<T, ApiError> {
+    check_response(response)
+        .await?
+        .json()
+        .await
+        .map_err(ApiError::transport)
+}
+
+fn sha1_hex(data: &[u8]) -> String {
+    hex::encode(Sha1::digest(data))
+}
+
+/// Percent-encode a file name for headers and download URLs. B2 keeps `/`
+/// as is.
+fn encode_file_name(file_name: &str) -> String {
+    urlencoding::encode(file_name).replace("%2F", "/")
+}
+
+/// Production B2 cloud storage implementation
+pub struct B2CloudStorage {
+    http: reqwest::Client,
+    config: B2Config,
+    bucket_id: String,
+    auth: RwLock<Authorization>,
+}
+
+impl B2CloudStorage {
+    /// Authorize and look up the bucket. Unlike S3, the bucket must already
+    /// exist: B2 bucket names are global and keys usually can't create them.
+    pub async fn new(config: B2Config) -> Result<Self, CloudStorageError> {
+        config.validate()?;
+        let http = reqwest::Client::new();
+        let auth = authorize(&http, &config)
+            .await
+            .map_err(|e| e.into_error("b2_authorize_account"))?;
+
+        let buckets: BucketList = read_json(
+            http.post(format!("{}/b2api/v2/b2_list_buckets", auth.api_url))
+                .header("Authorization", &auth.authorization_token)
+                .json(&json!({
+                    "accountId": auth.account_id,
+                    "bucketName": config.bucket_name,
+                }))
+                .send()
+                .await,
+        )
+        .await
+        .map_err(|e| e.into_error("b2_list_buckets"))?;
+        let bucket_id = buckets
+            .buckets
+            .into_iter()
+            .find(|b| b.bucket_name == config.bucket_name)
+            .map(|b| b.bucket_id)
+            .ok_or_else(|| {
+                CloudStorageError::Config(format!(
+                    "Bucket '{}' doesn't exist or this key can't access it",
+                    config.bucket_name
+                ))
+            })?;
+
+        info!("Using B2 bucket '{}' ({})", config.bucket_name, bucket_id);
+        Ok(B2CloudStorage {
+            http,
+            config,
+            bucket_id,
+            auth: RwLock::new(auth),
+        })
+    }
+
+    fn location(&self, file_name: &str) -> String {
+        format!("b2://{}/{}", self.config.bucket_name, file_name)
+    }
+
+    fn file_name<'a>(&self, storage_location: &'a str) -> Result<&'a str, CloudStorageError> {
+        storage_location
+            .strip_prefix(&format!("b2://{}/", self.config.bucket_name))
+            .ok_or_else(|| {
+                CloudStorageError::Download(format!("Invalid B2 location: {}", storage_location))
+            })
+    }
+
+    async fn reauthorize(&self) -> Result<(), CloudStorageError> {
+        debug!("B2 auth token expired, authorizing again");
+        let auth = authorize(&self.http, &self.config)
+            .await
+            .map_err(|e| e.into_error("b2_authorize_account"))?;
+        *self.auth.write().await = auth;
+        Ok(())
+    }
+
+    /// Call a B2 API, authorizing again once if the token has expired
+    async fn call<T: DeserializeOwned>(
+        &self,
+        name: &str,
+        body: serde_json::Value,
+    ) -> Result<T, CloudStorageError> {
+        let mut reauthorized = false;
+        loop {
+            let auth = self.auth.read().await.clone();
+            let result = read_json(
+                self.http
+                    .post(format!("{}/b2api/v2/{}", auth.api_url, name))
+                    .header("Authorization", &auth.authorization_token)
+                    .json(&body)
+                    .send()
+                    .await,
+            )
+            .await;
+            match result {
+                Ok(value) => return Ok(value),
+                Err(e) if e.is_expired_auth() && !reauthorized => {
+                    self.reauthorize().await?;
+                    reauthorized = true;
+                }
+                Err(e) => return Err(e.into_error(name)),
+            }
+        }
+    }
+
+    /// The visible version of a file, if there is one
+    async fn find_file(&self, file_name: &str) -> Result<Option<FileVersion>, CloudStorageError> {
+        let list: FileList = self
+            .call(
+                "b2_list_file_names",
+                json!({
+                    "bucketId": self.bucket_id,
+                    "startFileName": file_name,
+                    "prefix": file_name,
+                    "maxFileCount": 1,
+                }),
+            )
+            .await?;
+        Ok(list.files.into_iter().find(|f| f.file_name == file_name))
+    }
+
+    /// Upload in one request. Returns the SHA1 B2 stored.
+    async fn upload_file(
+        &self,
+        file_name: &str,
+        data: &[u8],
+        sha1: &str,
+    ) -> Result<Option<String>, CloudStorageError> {
+        let mut attempt = 1;
+        loop {
+            let target: UploadUrl = self
+                .call("b2_get_upload_url", json!({ "bucketId": self.bucket_id }))
+                .await?;
+            debug!(
+                "Uploading {} ({} bytes, attempt {})",
+                file_name,
+                data.len(),
+                attempt
+            );
+            let result: Result<FileVersion, ApiError> = read_json(
+                self.http
+                    .post(&target.upload_url)
+                    .header("Authorization", &target.authorization_token)
+                    .header("X-Bz-File-Name", encode_file_name(file_name))
+                    .header("Content-Type", "application/octet-stream")
+                    .header("X-Bz-Content-Sha1", sha1)
+                    .body(data.to_vec())
+                    .send()
+                    .await,
+            )
+            .await;
+            match result {
+                Ok(file) => return Ok(file.sha1().map(str::to_string)),
+                Err(e) if e.is_retryable_upload() && attempt < MAX_UPLOAD_ATTEMPTS => {
+                    warn!("Upload of {} failed ({}), retrying", file_name, e.message);
+                    attempt += 1;
+                }
+                Err(e) => return Err(e.into_error("b2_upload_file")),
+            }
+        }
+    }
+
+    /// Upload as a B2 large file, one part at a time. A failed upload is
+    /// cancelled, or its parts would stay in the bucket (and be billed)
+    /// until someone cancels it.
+    async fn upload_large_file(
+        &self,
+        file_name: &str,
+        data: &[u8],
+        sha1: &str,
+        part_size: usize,
+    ) -> Result<Option<String>, CloudStorageError> {
+        let file: FileVersion = self
+            .call(
+                "b2_start_large_file",
+                json!({
+                    "bucketId": self.bucket_id,
+                    "fileName": file_name,
+                    "contentType": "application/octet-stream",
+                    "fileInfo": { "large_file_sha1": sha1 },
+                }),
+            )
+            .await?;
+        debug!(
+            "Started large file {} ({} bytes in parts of {})",
+            file_name,
+            data.len(),
+            part_size
+        );
+
+        let result = async {
+            let part_sha1s = self.upload_parts(&file.file_id, data, part_size).await?;
+            self.call::<FileVersion>(
+                "b2_finish_large_file",
+                json!({ "fileId": file.file_id, "partSha1Array": part_sha1s }),
+            )
+            .await
+        }
+        .await;
+
+        match result {
+            Ok(finished) => Ok(finished.sha1().map(str::to_string)),
+            Err(e) => {
+                if let Err(cancel_err) = self
+                    .call::<serde_json::Value>(
+                        "b2_cancel_large_file",
+                        json!({ "fileId": file.file_id }),
+                    )
+                    .await
+                {
+                    warn!("Failed to cancel large file {}: {}", file_name, cancel_err);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Upload each part, returning their SHA1s in order
+    async fn upload_parts(
+        &self,
+        file_id: &str,
+        data: &[u8],
+        part_size: usize,
+    ) -> Result<Vec<String>, CloudStorageError> {
+        let mut target: Option<UploadUrl> = None;
+        let mut part_sha1s = Vec::new();
+        for (index, range) in part_ranges(data.len(), part_size).into_iter().enumerate() {
+            let part = &data[range];
+            let part_sha1 = sha1_hex(part);
+            let mut attempt = 1;
+            loop {
+                let url = match target.take() {
+                    Some(url) => url,
+                    None => {
+                        self.call("b2_get_upload_part_url", json!({ "fileId": file_id }))
+                            .await?
+                    }
+                };
+                let result = check_response(
+                    self.http
+                        .post(&url.upload_url)
+                        .header("Authorization", &url.authorization_token)
+                        .header("X-Bz-Part-Number", index + 1)
+                        .header("X-Bz-Content-Sha1", &part_sha1)
+                        .body(part.to_vec())
+                        .send()
+                        .await,
+                )
+                .await;
+                match result {
+                    Ok(_) => {
+                        target = Some(url);
+                        break;
+                    }
+                    Err(e) if e.is_retryable_upload() && attempt < MAX_UPLOAD_ATTEMPTS => {
+                        warn!("Part {} failed ({}), retrying", index + 1, e.message);
+                        attempt += 1;
+                    }
+                    Err(e) => return Err(e.into_error("b2_upload_part")),
+                }
+            }
+            part_sha1s.push(part_sha1);
+        }
+        Ok(part_sha1s)
+    }
+
+    async fn get(
+        &self,
+        storage_location: &str,
+        range: Option<String>,
+    ) -> Result<Vec<u8>, CloudStorageError> {
+        let file_name = self.file_name(storage_location)?;
+        let mut reauthorized = false;
+        loop {
+            let auth = self.auth.read().await.clone();
+            let mut request = self
+                .http
+                .get(format!(
+                    "{}/file/{}/{}",
+                    auth.download_url,
+                    self.config.bucket_name,
+                    encode_file_name(file_name)
+                ))
+                .header("Authorization", &auth.authorization_token);
+            if let Some(ref range) = range {
+                request = request.header("Range", range);
+            }
+            let result = match check_response(request.send().await).await {
+                Ok(response) => response.bytes().await.map_err(ApiError::transport),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(data) => return Ok(data.to_vec()),
+                Err(e) if e.is_expired_auth() && !reauthorized => {
+                    self.reauthorize().await?;
+                    reauthorized = true;
+                }
+                Err(e) => {
+                    return Err(CloudStorageError::Download(format!(
+                        "Download of {} failed: {} ({})",
+                        storage_location, e.message, e.code
+                    )));
+                }
+            }
+        }
+    }
+}
+
+async fn authorize(http: &reqwest::Client, config: &B2Config) -> Result<Authorization, ApiError> {
+    read_json(
+        http.get(AUTHORIZE_URL)
+            .basic_auth(&config.key_id, Some(&config.application_key))
+            .send()
+            .await,
+    )
+    .await
+}
+
+#[async_trait::async_trait]
+impl CloudStorage for B2CloudStorage {
+    async fn upload(&self, key: &str, data: &[u8]) -> Result<String, CloudStorageError> {
+        Ok(self.upload_verified(key, data).await?.storage_location)
+    }
+
+    /// B2 checks every body against the SHA1 sent with it, and we compare
+    /// the SHA1 it stored with ours. B2 has no conditional writes, so an
+    /// object already at the key is reconciled with
+    /// [`reconcile_existing_object`] before uploading.
+    async fn upload_verified(
+        &self,
+        key: &str,
+        data: &[u8],
+    ) -> Result<VerifiedUpload, CloudStorageError> {
+        let file_name = object_key(key);
+        let sha1 = sha1_hex(data);
+        let storage_location = self.location(&file_name);
+
+        if let Some(existing) = self.find_file(&file_name).await? {
+            reconcile_existing_object(key, &sha1, existing.sha1())?;
+            info!(
+                "{} already holds the same data, keeping it",
+                storage_location
+            );
+            return Ok(VerifiedUpload {
+                storage_location,
+                checksum_verified: true,
+            });
+        }
+
+        let part_size = self.auth.read().await.recommended_part_size as usize;
+        let stored_sha1 = if data.len() > part_size {
+            self.upload_large_file(&file_name, data, &sha1, part_size)
+                .await?
+        } else {
+            self.upload_file(&file_name, data, &sha1).await?
+        };
+
+        match check_returned_checksum(&sha1, stored_sha1.as_deref()) {
+            ChecksumCheck::Mismatch => Err(CloudStorageError::Integrity(format!(
+                "B2 stored {} with a different SHA1",
+                key
+            ))),
+            check => {
+                debug!("Uploaded {}", storage_location);
+                Ok(VerifiedUpload {
+                    storage_location,
+                    checksum_verified: check == ChecksumCheck::Verified,
+                })
+            }
+        }
+    }
+
+    async fn download(&self, storage_location: &str) -> Result<Vec<u8>, CloudStorageError> {
+        debug!("Downloading from {}", storage_location);
+        let data = self.get(storage_location, None).await?;
+        debug!("Successfully downloaded {} bytes", data.len());
+        Ok(data)
+    }
+
+    async fn download_range(
+        &self,
+        storage_location: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<u8>, CloudStorageError> {
+        if start >= end {
+            return Err(CloudStorageError::Download(format!(
+                "Invalid range: start ({}) >= end ({})",
+                start, end
+            )));
+        }
+        // Range header is inclusive on both ends; our API is [start, end)
+        let range = format!("bytes={}-{}", start, end - 1);
+        self.get(storage_location, Some(range)).await
+    }
+
+    /// Deletes every version of the file, hide markers included
+    async fn delete(&self, storage_location: &str) -> Result<(), CloudStorageError> {
+        let file_name = self.file_name(storage_location)?;
+        debug!("Deleting from {}", storage_location);
+        let versions: FileList = self
+            .call(
+                "b2_list_file_versions",
+                json!({
+                    "bucketId": self.bucket_id,
+                    "startFileName": file_name,
+                    "prefix": file_name,
+                    "maxFileCount": 100,
+                }),
+            )
+            .await?;
+        for version in versions.files.iter().filter(|f| f.file_name == file_name) {
+            self.call::<serde_json::Value>(
+                "b2_delete_file_version",
+                json!({ "fileName": file_name, "fileId": version.file_id }),
+            )
+            .await?;
+        }
+        debug!("Successfully deleted from {}", storage_location);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_ranges() {
+        assert_eq!(part_ranges(10, 4), vec![0..4, 4..8, 8..10]);
+        assert_eq!(part_ranges(8, 4), vec![0..4, 4..8]);
+        assert!(part_ranges(0, 4).is_empty());
+    }
+
+    #[test]
+    fn test_encode_file_name() {
+        assert_eq!(encode_file_name("files/ab/cd/abcd"), "files/ab/cd/abcd");
+        assert_eq!(encode_file_name("files/misc/a b+"), "files/misc/a%20b%2B");
+    }
+
+    #[test]
+    fn test_large_file_sha1_comes_from_file_info() {
+        let file: FileVersion = serde_json::from_value(json!({
+            "fileId": "id",
+            "fileName": "files/ab/cd/abcd",
+            "contentSha1": "none",
+            "fileInfo": { "large_file_sha1": "a9993e36" },
+        }))
+        .unwrap();
+        assert_eq!(file.sha1(), Some("a9993e36"));
+
+        let file: FileVersion = serde_json::from_value(json!({
+            "fileId": "id",
+            "fileName": "files/ab/cd/abcd",
+            "contentSha1": "unverified:a9993e36",
+        }))
+        .unwrap();
+        assert_eq!(file.sha1(), Some("a9993e36"));
+    }
+
+    #[test]
+    fn test_retryable_upload_errors() {
+        let error = |status: u16, message: &str| ApiError {
+            status,
+            code: String::new(),
+            message: message.to_string(),
+        };
+        assert!(error(503, "").is_retryable_upload());
+        assert!(error(400, "Sha1 did not match data received").is_retryable_upload());
+        assert!(!error(400, "bad file name").is_retryable_upload());
+        assert!(!error(403, "").is_retryable_upload());
+    }
+}
@@ -0,0 +1,486 @@
+//! Resumable S3 multipart uploads
+//!
+//! Large files go up in parts. Each part the provider accepts is recorded
+//! in an [`UploadJournal`], so after a dropped connection or a restart the
+//! upload continues from the first missing part instead of from zero.
+//! Failed parts are retried with exponential backoff; errors no retry can
+//! fix, like bad credentials, fail straight away.
+use super::{
+    check_returned_checksum, format_error_details, object_key, part_ranges,
+    reconcile_existing_object, sha256_base64, ChecksumCheck, CloudStorageError, S3CloudStorage,
+    VerifiedUpload,
+};
+use aws_sdk_s3::types::{ChecksumAlgorithm, ChecksumMode, CompletedMultipartUpload, CompletedPart};
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Objects at least this large are uploaded in parts
+pub const MULTIPART_THRESHOLD: usize = 16 * 1024 * 1024;
+/// Size of every part but the last. S3 requires at least 5 MiB.
+const PART_SIZE: usize = 8 * 1024 * 1024;
+/// Attempts per part before the upload fails (and waits to be resumed)
+const MAX_PART_ATTEMPTS: u32 = 6;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// A multipart upload that was started but not completed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingUpload {
+    pub upload_id: String,
+    pub part_size: u64,
+    /// Base64 SHA-256 of the whole object. A different value means the
+    /// parts already sent belong to other bytes.
+    pub payload_sha256: String,
+    pub parts: Vec<UploadedPart>,
+}
+
+/// A part the provider accepted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadedPart {
+    /// 1-based
+    pub part_number: i32,
+    pub etag: String,
+    /// Base64 SHA-256 of the part
+    pub checksum_sha256: String,
+}
+
+/// Where a resumable upload keeps its progress between attempts.
+///
+/// Failing to record progress only costs a re-upload, so implementations
+/// log their own errors rather than failing the upload.
+#[async_trait::async_trait]
+pub trait UploadJournal: Send + Sync {
+    async fn pending_upload(&self, key: &str) -> Option<PendingUpload>;
+    async fn start_upload(&self, key: &str, upload: &PendingUpload);
+    async fn record_part(&self, key: &str, part: &UploadedPart);
+    /// The upload completed or was abandoned
+    async fn finish_upload(&self, key: &str);
+}
+
+/// Wait before retry `attempt` (1-based): doubling from
+/// [`INITIAL_BACKOFF`], capped at [`MAX_BACKOFF`]
+pub(super) fn backoff_delay(attempt: u32) -> Duration {
+    INITIAL_BACKOFF
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(MAX_BACKOFF)
+}
+
+/// Errors a retry can't fix, so the upload fails without waiting
+fn is_fatal_error(details: &str) -> bool {
+    [
+        "AccessDenied",
+        "InvalidAccessKeyId",
+        "SignatureDoesNotMatch",
+        "NoSuchBucket",
+        "InvalidBucketName",
+    ]
+    .iter()
+    .any(|code| details.contains(code))
+}
+
+/// The checksum S3 reports for a multipart object: the SHA-256 of the
+/// parts' concatenated digests, suffixed with the part count
+fn composite_checksum(part_checksums: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for checksum in part_checksums {
+        let digest = base64::engine::general_purpose::STANDARD
+            .decode(checksum)
+            .unwrap_or_default();
+        hasher.update(digest);
+    }
+    format!(
+        "{}-{}",
+        base64::engine::general_purpose::STANDARD.encode(hasher.finalize()),
+        part_checksums.len()
+    )
+}
+
+/// How sending the missing parts went
+enum PartsOutcome {
+    Sent,
+    /// The provider no longer knows the upload ID (completed, aborted or
+    /// expired), so the recorded parts are worthless
+    UploadGone,
+}
+
+impl S3CloudStorage {
+    /// Upload `data` in parts, continuing the upload recorded in `journal`
+    /// if it was for the same bytes. A recorded upload for other bytes is
+    /// aborted so its parts stop taking up space.
+    ///
+    /// Completion is conditional on the key being empty, like
+    /// [`Self::upload_verified`]'s first write.
+    pub(super) async fn upload_multipart(
+        &self,
+        key: &str,
+        data: &[u8],
+        journal: &dyn UploadJournal,
+    ) -> Result<VerifiedUpload, CloudStorageError> {
+        let s3_key = object_key(key);
+        let storage_location = format!("s3://{}/{}", self.bucket_name, s3_key);
+        let payload_sha256 = sha256_base64(data);
+
+        let mut restarted = false;
+        loop {
+            let pending = match journal.pending_upload(key).await {
+                Some(pending)
+                    if pending.payload_sha256 == payload_sha256
+                        && pending.part_size == PART_SIZE as u64 =>
+                {
+                    Some(pending)
+                }
+                Some(stale) => {
+                    info!("Abandoning earlier upload of {}, its data changed", key);
+                    self.abort_multipart(&s3_key, &stale.upload_id).await;
+                    journal.finish_upload(key).await;
+                    None
+                }
+                None => None,
+            };
+
+            let (upload_id, mut parts) = match pending {
+                Some(pending) => {
+                    info!(
+                        "Resuming upload of {} with {} parts already sent",
+                        key,
+                        pending.parts.len()
+                    );
+                    (pending.upload_id, pending.parts)
+                }
+                None => {
+                    let upload_id = self.create_multipart(&s3_key).await?;
+                    journal
+                        .start_upload(
+                            key,
+                            &PendingUpload {
+                                upload_id: upload_id.clone(),
+                                part_size: PART_SIZE as u64,
+                                payload_sha256: payload_sha256.clone(),
+                                parts: Vec::new(),
+                            },
+                        )
+                        .await;
+                    (upload_id, Vec::new())
+                }
+            };
+
+            match self
+                .send_missing_parts(key, &s3_key, &upload_id, data, &mut parts, journal)
+                .await?
+            {
+                PartsOutcome::Sent => {}
+                PartsOutcome::UploadGone if !restarted => {
+                    warn!("Upload of {} expired on the provider, starting over", key);
+                    journal.finish_upload(key).await;
+                    restarted = true;
+                    continue;
+                }
+                PartsOutcome::UploadGone => {
+                    journal.finish_upload(key).await;
+                    return Err(CloudStorageError::SdkError(format!(
+                        "Multipart upload of {} disappeared twice",
+                        key
+                    )));
+                }
+            }
+
+            let result = self
+                .complete_multipart(key, &s3_key, &upload_id, &parts)
+                .await;
+            match &result {
+                Ok(_) => journal.finish_upload(key).await,
+                Err(
+                    CloudStorageError::Conflict(_)
+                    | CloudStorageError::Occupied(_)
+                    | CloudStorageError::Integrity(_),
+                ) => {
+                    self.abort_multipart(&s3_key, &upload_id).await;
+                    journal.finish_upload(key).await;
+                }
+                Err(_) => {}
+            }
+            return result.map(|checksum_verified| VerifiedUpload {
+                storage_location,
+                checksum_verified,
+            });
+        }
+    }
+
+    async fn create_multipart(&self, s3_key: &str) -> Result<String, CloudStorageError> {
+        let response = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket_name)
+            .key(s3_key)
+            .content_type("application/octet-stream")
+            .checksum_algorithm(ChecksumAlgorithm::Sha256)
+            .send()
+            .await
+            .map_err(|e| {
+                CloudStorageError::SdkError(format!("Create multipart upload failed: {}", e))
+            })?;
+        response.upload_id().map(str::to_string).ok_or_else(|| {
+            CloudStorageError::SdkError("Create multipart upload returned no upload ID".into())
+        })
+    }
+
+    /// Send every part not in `parts`, recording each as it lands
+    async fn send_missing_parts(
+        &self,
+        key: &str,
+        s3_key: &str,
+        upload_id: &str,
+        data: &[u8],
+        parts: &mut Vec<UploadedPart>,
+        journal: &dyn UploadJournal,
+    ) -> Result<PartsOutcome, CloudStorageError> {
+        for (index, range) in part_ranges(data.len(), PART_SIZE).into_iter().enumerate() {
+            let part_number = index as i32 + 1;
+            let body = &data[range];
+            let checksum = sha256_base64(body);
+            if parts
+                .iter()
+                .any(|p| p.part_number == part_number && p.checksum_sha256 == checksum)
+            {
+                continue;
+            }
+
+            let Some(part) = self
+                .upload_part(key, s3_key, upload_id, part_number, body, &checksum)
+                .await?
+            else {
+                return Ok(PartsOutcome::UploadGone);
+            };
+            journal.record_part(key, &part).await;
+            parts.retain(|p| p.part_number != part_number);
+            parts.push(part);
+        }
+        parts.sort_by_key(|p| p.part_number);
+        Ok(PartsOutcome::Sent)
+    }
+
+    /// Upload one part, retrying with backoff. Returns None if the upload
+    /// ID is no longer known.
+    async fn upload_part(
+        &self,
+        key: &str,
+        s3_key: &str,
+        upload_id: &str,
+        part_number: i32,
+        body: &[u8],
+        checksum: &str,
+    ) -> Result<Option<UploadedPart>, CloudStorageError> {
+        let mut last_error = String::new();
+        for attempt in 1..=MAX_PART_ATTEMPTS {
+            if attempt > 1 {
+                tokio::time::sleep(backoff_delay(attempt - 1)).await;
+            }
+            debug!(
+                "Uploading part {} of {} ({} bytes, attempt {})",
+                part_number,
+                key,
+                body.len(),
+                attempt
+            );
+
+            let result = self
+                .client
+                .upload_part()
+                .bucket(&self.bucket_name)
+                .key(s3_key)
+                .upload_id(upload_id)
+                .part_number(part_number)
+                .body(body.to_vec().into())
+                .checksum_sha256(checksum)
+                .send()
+                .await;
+            let response = match result {
+                Ok(response) => response,
+                Err(e) => {
+                    let details = format!("{:?}", e);
+                    if details.contains("NoSuchUpload") {
+                        return Ok(None);
+                    }
+                    if is_fatal_error(&details) {
+                        return Err(CloudStorageError::SdkError(format!(
+                            "Upload part failed: {}",
+                            format_error_details(&e)
+                        )));
+                    }
+                    last_error = format_error_details(&e);
+                    warn!(
+                        "Part {} of {} failed (attempt {}): {}",
+                        part_number, key, attempt, last_error
+                    );
+                    continue;
+                }
+            };
+
+            if check_returned_checksum(checksum, response.checksum_sha256())
+                == ChecksumCheck::Mismatch
+            {
+                last_error = "checksum mismatch".to_string();
+                warn!(
+                    "Checksum mismatch for part {} of {} (attempt {}), retrying",
+                    part_number, key, attempt
+                );
+                continue;
+            }
+            let etag = response.e_tag().ok_or_else(|| {
+                CloudStorageError::SdkError(format!(
+                    "Part {} of {} returned no ETag",
+                    part_number, key
+                ))
+            })?;
+            return Ok(Some(UploadedPart {
+                part_number,
+                etag: etag.to_string(),
+                checksum_sha256: checksum.to_string(),
+            }));
+        }
+
+        Err(CloudStorageError::SdkError(format!(
+            "Part {} of {} failed after {} attempts: {}",
+            part_number, key, MAX_PART_ATTEMPTS, last_error
+        )))
+    }
+
+    /// Assemble the parts into the object. Returns whether the provider's
+    /// checksum for the object matched ours.
+    async fn complete_multipart(
+        &self,
+        key: &str,
+        s3_key: &str,
+        upload_id: &str,
+        parts: &[UploadedPart],
+    ) -> Result<bool, CloudStorageError> {
+        let checksums: Vec<String> = parts.iter().map(|p| p.checksum_sha256.clone()).collect();
+        let expected = composite_checksum(&checksums);
+        let completed = CompletedMultipartUpload::builder()
+            .set_parts(Some(
+                parts
+                    .iter()
+                    .map(|p| {
+                        CompletedPart::builder()
+                            .part_number(p.part_number)
+                            .e_tag(&p.etag)
+                            .checksum_sha256(&p.checksum_sha256)
+                            .build()
+                    })
+                    .collect(),
+            ))
+            .build();
+
+        let response = match self
+            .client
+            .complete_multipart_upload()
+            .bucket(&self.bucket_name)
+            .key(s3_key)
+            .upload_id(upload_id)
+            .multipart_upload(completed)
+            .if_none_match("*")
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) if format!("{:?}", e).contains("PreconditionFailed") => {
+                let existing = self
+                    .client
+                    .head_object()
+                    .bucket(&self.bucket_name)
+                    .key(s3_key)
+                    .checksum_mode(ChecksumMode::Enabled)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        CloudStorageError::SdkError(format!("Head object failed: {}", e))
+                    })?;
+                reconcile_existing_object(key, &expected, existing.checksum_sha256())?;
+                info!("{} already holds the same data, keeping it", s3_key);
+                self.abort_multipart(s3_key, upload_id).await;
+                return Ok(true);
+            }
+            Err(e) => {
+                return Err(CloudStorageError::SdkError(format!(
+                    "Complete multipart upload failed: {}",
+                    format_error_details(&e)
+                )));
+            }
+        };
+
+        match check_returned_checksum(&expected, response.checksum_sha256()) {
+            ChecksumCheck::Verified => Ok(true),
+            ChecksumCheck::Unverified => Ok(false),
+            ChecksumCheck::Mismatch => Err(CloudStorageError::Integrity(format!(
+                "Assembled {} doesn't match the parts sent",
+                key
+            ))),
+        }
+    }
+
+    /// Best effort: an upload we can't abort expires on the provider
+    async fn abort_multipart(&self, s3_key: &str, upload_id: &str) {
+        if let Err(e) = self
+            .client
+            .abort_multipart_upload()
+            .bucket(&self.bucket_name)
+            .key(s3_key)
+            .upload_id(upload_id)
+            .send()
+            .await
+        {
+            debug!("Couldn't abort upload {} of {}: {}", upload_id, s3_key, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        assert_eq!(backoff_delay(1), Duration::from_millis(500));
+        assert_eq!(backoff_delay(2), Duration::from_secs(1));
+        assert_eq!(backoff_delay(4), Duration::from_secs(4));
+        assert_eq!(backoff_delay(7), MAX_BACKOFF);
+        assert_eq!(backoff_delay(40), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_fatal_errors_skip_retries() {
+        assert!(is_fatal_error(
+            "ServiceError { code: Some(\"AccessDenied\") }"
+        ));
+        assert!(is_fatal_error("code: Some(\"NoSuchBucket\")"));
+        assert!(!is_fatal_error(
+            "DispatchFailure(ConnectorError { kind: Timeout })"
+        ));
+        assert!(!is_fatal_error("code: Some(\"SlowDown\")"));
+    }
+
+    #[test]
+    fn test_composite_checksum_hashes_part_digests() {
+        let parts = [b"first part".as_slice(), b"second".as_slice()];
+        let checksums: Vec<String> = parts.iter().map(|p| sha256_base64(p)).collect();
+
+        let mut hasher = Sha256::new();
+        for part in parts {
+            hasher.update(Sha256::digest(part));
+        }
+        let expected = format!(
+            "{}-2",
+            base64::engine::general_purpose::STANDARD.encode(hasher.finalize())
+        );
+        assert_eq!(composite_checksum(&checksums), expected);
+        assert_ne!(composite_checksum(&checksums[..1]), expected);
+    }
+
+    #[test]
+    fn test_threshold_leaves_room_for_two_parts() {
+        assert!(MULTIPART_THRESHOLD >= 2 * PART_SIZE);
+        assert!(PART_SIZE >= 5 * 1024 * 1024);
+    }
+}
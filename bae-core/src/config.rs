@@ -1,3 +1,16 @@
+use crate::db::AudioQuality;
+use crate::import::memory_budget::DEFAULT_MEMORY_BUDGET_MB;
+use crate::import::queue::DEFAULT_MAX_CONCURRENT_IMPORTS;
+use crate::library::credits::CreditRules;
+use crate::library::snapshot::DEFAULT_SNAPSHOTS_KEPT;
+use crate::network::MeteredSettings;
+use crate::playback::data_source::DEFAULT_DOWNLOAD_CONCURRENCY;
+use crate::playback::{
+    BufferSettings, Dither, OutputBitDepth, OutputSettings, SkipSilenceSettings,
+};
+use crate::remote::DEFAULT_REMOTE_PORT;
+use crate::scrobble::{LastFmAccount, ScrobbleAccounts};
+use crate::transcode::DEFAULT_OPUS_BITRATE_KBPS;
 use serde::{Deserialize, Serialize};
 use std::io::{BufRead, Write};
 use std::path::PathBuf;
@@ -17,6 +30,63 @@ pub enum ConfigError {
     Io(#[from] std::io::Error),
 }
 
+/// S3 Standard's list price per GB-month
+pub const DEFAULT_CLOUD_PRICE_PER_GB: f64 = 0.023;
+
+/// What the window shows at launch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupView {
+    Library,
+    /// The library with the now playing queue open
+    NowPlaying,
+    /// Wherever the app was when it last closed
+    LastRoute,
+}
+
+/// What a single click on a track row does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrackClickAction {
+    Play,
+    AddToQueue,
+    /// Select the row; double-click plays
+    Select,
+}
+
+/// How featured-artist credits ("A feat. B", "A ft. B") are normalized at import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeaturedCreditStyle {
+    /// Leave credits as the metadata source spells them
+    Keep,
+    /// Spell every featuring marker as "feat."
+    Standardize,
+    /// Credit featured artists separately, after the main artist
+    Split,
+}
+
+/// Order of the library album grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LibrarySort {
+    Title,
+    DateAdded,
+    LastPlayed,
+    PlayCount,
+}
+
+/// What happens when an audio CD is inserted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CdInsertAction {
+    Nothing,
+    /// Open the CD import with the disc selected
+    OpenImport,
+    /// Open the CD import and start ripping if DiscID finds a single release
+    AutoRip,
+}
+
 fn default_true() -> bool {
     true
 }
@@ -25,6 +95,9 @@ fn default_true() -> bool {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ConfigYaml {
     pub library_id: Option<String>,
+    /// Identifies this machine in the library changelog. Generated and saved
+    /// on first launch.
+    pub device_id: Option<String>,
     pub torrent_bind_interface: Option<String>,
     /// Listening port for incoming torrent connections. None = random port.
     pub torrent_listen_port: Option<u16>,
@@ -34,6 +107,14 @@ pub struct ConfigYaml {
     /// Enable NAT-PMP port forwarding
     #[serde(default = "default_true")]
     pub torrent_enable_natpmp: bool,
+    /// Listen on IPv6 as well as IPv4
+    #[serde(default = "default_true")]
+    pub torrent_enable_ipv6: bool,
+    /// Enable the DHT
+    #[serde(default = "default_true")]
+    pub torrent_enable_dht: bool,
+    /// DHT bootstrap nodes as host:port. None = libtorrent defaults.
+    pub torrent_dht_bootstrap_nodes: Option<Vec<String>>,
     /// Global max connections. None = disabled/unlimited.
     pub torrent_max_connections: Option<i32>,
     /// Max connections per torrent. None = disabled/unlimited.
@@ -47,12 +128,90 @@ pub struct ConfigYaml {
     pub subsonic_enabled: bool,
     /// Subsonic server port
     pub subsonic_port: Option<u16>,
+    /// Opus bitrate for Subsonic clients that ask for transcoding. None = default.
+    pub subsonic_transcode_bitrate_kbps: Option<u32>,
+    /// Serve the phone remote on the LAN
+    #[serde(default)]
+    pub remote_enabled: bool,
+    /// Phone remote port. None = default.
+    pub remote_port: Option<u16>,
+    /// Parallel range requests when streaming from cloud storage. None = default.
+    pub cloud_download_concurrency: Option<usize>,
+    /// How far the decoder runs ahead of playback, in ms. None = default.
+    pub playback_decode_ahead_ms: Option<u32>,
+    /// How much of the next track is decoded before a gapless transition, in ms. None = default.
+    pub playback_gapless_preroll_ms: Option<u32>,
+    /// Output device to play through. None = system default.
+    pub playback_output_device: Option<String>,
+    /// Sample format requested from the output device. None = default.
+    pub playback_output_bit_depth: Option<OutputBitDepth>,
+    /// Dither used when the output is 16-bit. None = default.
+    pub playback_dither: Option<Dither>,
+    /// Output devices to hold exclusively while playing. None = none.
+    pub playback_exclusive_devices: Option<Vec<String>>,
+    /// Skip long silences during playback. None = off.
+    pub playback_skip_silence: Option<bool>,
+    /// How long silence plays before it's skipped, in ms. None = default.
+    pub playback_min_silence_ms: Option<u32>,
+    /// Keep playing from the tray when the window is closed. None = on.
+    pub playback_in_background: Option<bool>,
+    /// Cloud storage price per GB-month, for import cost estimates. None = default.
+    pub cloud_price_per_gb: Option<f64>,
+    /// What the window shows at launch. None = library.
+    pub startup_view: Option<StartupView>,
+    /// Route open when the app last closed, for `StartupView::LastRoute`
+    pub last_route: Option<String>,
+    /// Library sort at launch. None = title.
+    pub library_sort: Option<LibrarySort>,
+    /// Library quality filter at launch. None = all albums.
+    pub library_quality_filter: Option<AudioQuality>,
+    /// Days between scheduled library verification runs. None = not scheduled.
+    pub verification_interval_days: Option<u32>,
+    /// Storage profile library snapshots go to. None = no snapshots.
+    pub snapshot_profile_id: Option<String>,
+    /// Hours between scheduled library snapshots. None = not scheduled.
+    pub snapshot_interval_hours: Option<u32>,
+    /// Library snapshots kept in the bucket. None = default.
+    pub snapshots_kept: Option<u32>,
+    /// What clicking a track row does. None = select.
+    pub track_click_action: Option<TrackClickAction>,
+    /// Featured-artist credit normalization at import. None = standardize.
+    pub featured_credit_style: Option<FeaturedCreditStyle>,
+    /// Words that introduce a featured artist in a credit. None = default list.
+    pub featured_credit_markers: Option<Vec<String>>,
+    /// Restrict background network traffic. None = off.
+    pub metered_mode: Option<bool>,
+    /// Preload cloud tracks even in metered mode. None = no.
+    pub metered_allow_cloud_prefetch: Option<bool>,
+    /// Keep torrent traffic running in metered mode. None = no.
+    pub metered_allow_torrents: Option<bool>,
+    /// What inserting an audio CD does. None = nothing.
+    pub cd_insert_action: Option<CdInsertAction>,
+    /// Eject a CD once it's ripped. None = no.
+    pub cd_eject_after_rip: Option<bool>,
+    /// Folder-name templates tried in order when detecting an import's
+    /// metadata. None = none.
+    pub folder_patterns: Option<Vec<String>>,
+    /// Cap import work to one file or disc at a time. None = off.
+    pub low_power_import: Option<bool>,
+    /// Advanced: megabytes of file data imports may hold in memory at once.
+    /// None = default.
+    pub import_memory_budget_mb: Option<u32>,
+    /// Queued imports that run at once. None = default.
+    pub max_concurrent_imports: Option<u32>,
+    /// Connected Last.fm user; the session itself is in the keyring.
+    /// None = not connected.
+    pub lastfm_username: Option<String>,
+    /// Whether a ListenBrainz token is in the keyring. None = no.
+    pub listenbrainz_connected: Option<bool>,
 }
 
 /// Application configuration
 #[derive(Clone, Debug)]
 pub struct Config {
     pub library_id: String,
+    /// Identifies this machine in the library changelog
+    pub device_id: String,
     /// Discogs API key - loaded lazily from keyring when needed
     pub discogs_api_key: Option<String>,
     /// Encryption key - loaded lazily from keyring when needed (when creating encrypted storage profile)
@@ -61,12 +220,74 @@ pub struct Config {
     pub torrent_listen_port: Option<u16>,
     pub torrent_enable_upnp: bool,
     pub torrent_enable_natpmp: bool,
+    pub torrent_enable_ipv6: bool,
+    pub torrent_enable_dht: bool,
+    /// DHT bootstrap nodes as host:port. Empty = libtorrent defaults.
+    pub torrent_dht_bootstrap_nodes: Vec<String>,
     pub torrent_max_connections: Option<i32>,
     pub torrent_max_connections_per_torrent: Option<i32>,
     pub torrent_max_uploads: Option<i32>,
     pub torrent_max_uploads_per_torrent: Option<i32>,
     pub subsonic_enabled: bool,
     pub subsonic_port: u16,
+    /// Opus bitrate for Subsonic clients that ask for transcoding without a cap
+    pub subsonic_transcode_bitrate_kbps: u32,
+    pub remote_enabled: bool,
+    pub remote_port: u16,
+    /// Secret paired phones send, see [`crate::remote`]. Kept in the keyring.
+    pub remote_token: Option<String>,
+    pub cloud_download_concurrency: usize,
+    pub playback_decode_ahead_ms: u32,
+    pub playback_gapless_preroll_ms: u32,
+    /// Output device to play through. None follows the system default.
+    pub playback_output_device: Option<String>,
+    pub playback_output_bit_depth: OutputBitDepth,
+    pub playback_dither: Dither,
+    pub playback_exclusive_devices: Vec<String>,
+    pub playback_skip_silence: bool,
+    pub playback_min_silence_ms: u32,
+    /// Closing the window hides it to the tray instead of quitting
+    pub playback_in_background: bool,
+    pub cloud_price_per_gb: f64,
+    pub startup_view: StartupView,
+    /// Route open when the app last closed. Saved on its own through
+    /// [`Config::save_last_route`], so this is only the value at launch.
+    pub last_route: Option<String>,
+    pub library_sort: LibrarySort,
+    pub library_quality_filter: Option<AudioQuality>,
+    /// Days between scheduled library verification runs. None = not scheduled.
+    pub verification_interval_days: Option<u32>,
+    /// Storage profile library snapshots go to, see
+    /// [`crate::library::snapshot`]
+    pub snapshot_profile_id: Option<String>,
+    /// Hours between scheduled library snapshots. None = not scheduled.
+    pub snapshot_interval_hours: Option<u32>,
+    pub snapshots_kept: u32,
+    pub track_click_action: TrackClickAction,
+    pub featured_credit_style: FeaturedCreditStyle,
+    /// Words that introduce a featured artist, matched case-insensitively
+    pub featured_credit_markers: Vec<String>,
+    pub metered_mode: bool,
+    pub metered_allow_cloud_prefetch: bool,
+    pub metered_allow_torrents: bool,
+    pub cd_insert_action: CdInsertAction,
+    pub cd_eject_after_rip: bool,
+    /// Folder-name templates, see [`crate::import::folder_pattern`]
+    pub folder_patterns: Vec<String>,
+    /// Cap import work to one file or disc at a time
+    pub low_power_import: bool,
+    /// Megabytes of file data imports may hold in memory at once, see
+    /// [`crate::import::memory_budget`]
+    pub import_memory_budget_mb: u32,
+    /// Queued imports that run at once, see [`crate::import::queue`]
+    pub max_concurrent_imports: u32,
+    /// Last.fm scrobbling account, kept in the keyring. Saved on its own
+    /// through [`Config::save_scrobble_accounts`], so this is only the value
+    /// at launch.
+    pub lastfm_account: Option<LastFmAccount>,
+    /// ListenBrainz user token, kept in the keyring. Saved like
+    /// `lastfm_account`.
+    pub listenbrainz_token: Option<String>,
 }
 
 impl Config {
@@ -87,27 +308,82 @@ impl Config {
             warn!("No BAE_LIBRARY_ID in .env, generated new ID: {}", id);
             id
         });
+        let device_id = std::env::var("BAE_DEVICE_ID").unwrap_or_else(|_| {
+            let id = uuid::Uuid::new_v4().to_string();
+            warn!("No BAE_DEVICE_ID in .env, generated new ID: {}", id);
+            id
+        });
         // Load from env if present, otherwise will be loaded lazily from keyring
         let discogs_api_key = std::env::var("BAE_DISCOGS_API_KEY").ok();
         let encryption_key = std::env::var("BAE_ENCRYPTION_KEY").ok();
         let torrent_bind_interface = std::env::var("BAE_TORRENT_BIND_INTERFACE")
             .ok()
             .filter(|s| !s.is_empty());
+        let lastfm_account = std::env::var("BAE_LASTFM_ACCOUNT")
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok());
+        let listenbrainz_token = std::env::var("BAE_LISTENBRAINZ_TOKEN")
+            .ok()
+            .filter(|s| !s.is_empty());
+        let remote_token = std::env::var("BAE_REMOTE_TOKEN")
+            .ok()
+            .filter(|s| !s.is_empty());
 
         Self {
             library_id,
+            device_id,
             discogs_api_key,
             encryption_key,
             torrent_bind_interface,
             torrent_listen_port: None,
             torrent_enable_upnp: true,
             torrent_enable_natpmp: true,
+            torrent_enable_ipv6: true,
+            torrent_enable_dht: true,
+            torrent_dht_bootstrap_nodes: Vec::new(),
             torrent_max_connections: None,
             torrent_max_connections_per_torrent: None,
             torrent_max_uploads: None,
             torrent_max_uploads_per_torrent: None,
             subsonic_enabled: true,
             subsonic_port: 4533,
+            subsonic_transcode_bitrate_kbps: DEFAULT_OPUS_BITRATE_KBPS,
+            remote_enabled: false,
+            remote_port: DEFAULT_REMOTE_PORT,
+            remote_token,
+            cloud_download_concurrency: DEFAULT_DOWNLOAD_CONCURRENCY,
+            playback_decode_ahead_ms: BufferSettings::default().decode_ahead_ms,
+            playback_gapless_preroll_ms: BufferSettings::default().gapless_preroll_ms,
+            playback_output_device: None,
+            playback_output_bit_depth: OutputSettings::default().bit_depth,
+            playback_dither: OutputSettings::default().dither,
+            playback_exclusive_devices: Vec::new(),
+            playback_skip_silence: false,
+            playback_min_silence_ms: SkipSilenceSettings::default().min_silence_ms,
+            playback_in_background: true,
+            cloud_price_per_gb: DEFAULT_CLOUD_PRICE_PER_GB,
+            startup_view: StartupView::Library,
+            last_route: None,
+            library_sort: LibrarySort::Title,
+            library_quality_filter: None,
+            verification_interval_days: None,
+            snapshot_profile_id: None,
+            snapshot_interval_hours: None,
+            snapshots_kept: DEFAULT_SNAPSHOTS_KEPT,
+            track_click_action: TrackClickAction::Select,
+            featured_credit_style: CreditRules::default().style,
+            featured_credit_markers: CreditRules::default().markers,
+            metered_mode: false,
+            metered_allow_cloud_prefetch: false,
+            metered_allow_torrents: false,
+            cd_insert_action: CdInsertAction::Nothing,
+            cd_eject_after_rip: false,
+            folder_patterns: Vec::new(),
+            low_power_import: false,
+            import_memory_budget_mb: DEFAULT_MEMORY_BUDGET_MB,
+            max_concurrent_imports: DEFAULT_MAX_CONCURRENT_IMPORTS as u32,
+            lastfm_account,
+            listenbrainz_token,
         }
     }
 
@@ -125,22 +401,119 @@ impl Config {
         let library_id = yaml_config
             .library_id
             .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let saved_device_id = yaml_config.device_id;
+        let device_id = saved_device_id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
-        Self {
+        let config = Self {
             library_id,
+            device_id,
             discogs_api_key: None,
             encryption_key: None,
             torrent_bind_interface: yaml_config.torrent_bind_interface,
             torrent_listen_port: yaml_config.torrent_listen_port,
             torrent_enable_upnp: yaml_config.torrent_enable_upnp,
             torrent_enable_natpmp: yaml_config.torrent_enable_natpmp,
+            torrent_enable_ipv6: yaml_config.torrent_enable_ipv6,
+            torrent_enable_dht: yaml_config.torrent_enable_dht,
+            torrent_dht_bootstrap_nodes: yaml_config
+                .torrent_dht_bootstrap_nodes
+                .unwrap_or_default(),
             torrent_max_connections: yaml_config.torrent_max_connections,
             torrent_max_connections_per_torrent: yaml_config.torrent_max_connections_per_torrent,
             torrent_max_uploads: yaml_config.torrent_max_uploads,
             torrent_max_uploads_per_torrent: yaml_config.torrent_max_uploads_per_torrent,
             subsonic_enabled: yaml_config.subsonic_enabled,
             subsonic_port: yaml_config.subsonic_port.unwrap_or(4533),
+            subsonic_transcode_bitrate_kbps: yaml_config
+                .subsonic_transcode_bitrate_kbps
+                .unwrap_or(DEFAULT_OPUS_BITRATE_KBPS),
+            remote_enabled: yaml_config.remote_enabled,
+            remote_port: yaml_config.remote_port.unwrap_or(DEFAULT_REMOTE_PORT),
+            // Paired phones need it from launch, so read it only when the
+            // remote is on
+            remote_token: yaml_config
+                .remote_enabled
+                .then(|| keyring_secret("remote_token"))
+                .flatten(),
+            cloud_download_concurrency: yaml_config
+                .cloud_download_concurrency
+                .unwrap_or(DEFAULT_DOWNLOAD_CONCURRENCY),
+            playback_decode_ahead_ms: yaml_config
+                .playback_decode_ahead_ms
+                .unwrap_or(BufferSettings::default().decode_ahead_ms),
+            playback_gapless_preroll_ms: yaml_config
+                .playback_gapless_preroll_ms
+                .unwrap_or(BufferSettings::default().gapless_preroll_ms),
+            playback_output_device: yaml_config.playback_output_device,
+            playback_output_bit_depth: yaml_config
+                .playback_output_bit_depth
+                .unwrap_or(OutputSettings::default().bit_depth),
+            playback_dither: yaml_config
+                .playback_dither
+                .unwrap_or(OutputSettings::default().dither),
+            playback_exclusive_devices: yaml_config.playback_exclusive_devices.unwrap_or_default(),
+            playback_skip_silence: yaml_config.playback_skip_silence.unwrap_or(false),
+            playback_min_silence_ms: yaml_config
+                .playback_min_silence_ms
+                .unwrap_or(SkipSilenceSettings::default().min_silence_ms),
+            playback_in_background: yaml_config.playback_in_background.unwrap_or(true),
+            cloud_price_per_gb: yaml_config
+                .cloud_price_per_gb
+                .unwrap_or(DEFAULT_CLOUD_PRICE_PER_GB),
+            startup_view: yaml_config.startup_view.unwrap_or(StartupView::Library),
+            last_route: yaml_config.last_route,
+            library_sort: yaml_config.library_sort.unwrap_or(LibrarySort::Title),
+            library_quality_filter: yaml_config.library_quality_filter,
+            verification_interval_days: yaml_config.verification_interval_days,
+            snapshot_profile_id: yaml_config.snapshot_profile_id,
+            snapshot_interval_hours: yaml_config.snapshot_interval_hours,
+            snapshots_kept: yaml_config.snapshots_kept.unwrap_or(DEFAULT_SNAPSHOTS_KEPT),
+            track_click_action: yaml_config
+                .track_click_action
+                .unwrap_or(TrackClickAction::Select),
+            featured_credit_style: yaml_config
+                .featured_credit_style
+                .unwrap_or(CreditRules::default().style),
+            featured_credit_markers: yaml_config
+                .featured_credit_markers
+                .unwrap_or_else(|| CreditRules::default().markers),
+            metered_mode: yaml_config.metered_mode.unwrap_or(false),
+            metered_allow_cloud_prefetch: yaml_config.metered_allow_cloud_prefetch.unwrap_or(false),
+            metered_allow_torrents: yaml_config.metered_allow_torrents.unwrap_or(false),
+            cd_insert_action: yaml_config
+                .cd_insert_action
+                .unwrap_or(CdInsertAction::Nothing),
+            cd_eject_after_rip: yaml_config.cd_eject_after_rip.unwrap_or(false),
+            folder_patterns: yaml_config.folder_patterns.unwrap_or_default(),
+            low_power_import: yaml_config.low_power_import.unwrap_or(false),
+            import_memory_budget_mb: yaml_config
+                .import_memory_budget_mb
+                .unwrap_or(DEFAULT_MEMORY_BUDGET_MB),
+            max_concurrent_imports: yaml_config
+                .max_concurrent_imports
+                .unwrap_or(DEFAULT_MAX_CONCURRENT_IMPORTS as u32),
+            // Only touches the keyring for accounts that were connected,
+            // since scrobbling has to work from the first track played
+            lastfm_account: yaml_config
+                .lastfm_username
+                .and_then(|_| keyring_secret("lastfm_account"))
+                .and_then(|json| serde_json::from_str(&json).ok()),
+            listenbrainz_token: yaml_config
+                .listenbrainz_connected
+                .unwrap_or(false)
+                .then(|| keyring_secret("listenbrainz_token"))
+                .flatten(),
+        };
+
+        // A device ID that changed every launch would be useless in the changelog
+        if saved_device_id.is_none() {
+            if let Err(e) = config.save_device_id() {
+                warn!("Failed to save device ID: {}", e);
+            }
         }
+        config
     }
 
     pub fn get_library_path(&self) -> PathBuf {
@@ -149,6 +522,57 @@ impl Config {
             .unwrap_or_else(|_| dirs::home_dir().unwrap().join(".bae"))
     }
 
+    /// Playback buffering, clamped to safe bounds
+    pub fn playback_buffer_settings(&self) -> BufferSettings {
+        BufferSettings {
+            decode_ahead_ms: self.playback_decode_ahead_ms,
+            gapless_preroll_ms: self.playback_gapless_preroll_ms,
+        }
+        .clamped()
+    }
+
+    pub fn playback_output_settings(&self) -> OutputSettings {
+        OutputSettings {
+            device: self.playback_output_device.clone(),
+            bit_depth: self.playback_output_bit_depth,
+            dither: self.playback_dither,
+            exclusive_devices: self.playback_exclusive_devices.clone(),
+        }
+    }
+
+    /// Skip-silence, clamped to safe bounds
+    pub fn playback_skip_silence_settings(&self) -> SkipSilenceSettings {
+        SkipSilenceSettings {
+            enabled: self.playback_skip_silence,
+            min_silence_ms: self.playback_min_silence_ms,
+        }
+        .clamped()
+    }
+
+    /// Featured-artist credit rules for imports
+    pub fn credit_rules(&self) -> CreditRules {
+        CreditRules {
+            style: self.featured_credit_style,
+            markers: self.featured_credit_markers.clone(),
+        }
+    }
+
+    /// Metered mode and its per-feature overrides
+    pub fn metered_settings(&self) -> MeteredSettings {
+        MeteredSettings {
+            enabled: self.metered_mode,
+            allow_cloud_prefetch: self.metered_allow_cloud_prefetch,
+            allow_torrents: self.metered_allow_torrents,
+        }
+    }
+
+    pub fn scrobble_accounts(&self) -> ScrobbleAccounts {
+        ScrobbleAccounts {
+            lastfm: self.lastfm_account.clone(),
+            listenbrainz_token: self.listenbrainz_token.clone(),
+        }
+    }
+
     pub fn is_dev_mode() -> bool {
         std::env::var("BAE_DEV_MODE").is_ok() || std::path::Path::new(".env").exists()
     }
@@ -163,17 +587,9 @@ impl Config {
     }
 
     pub fn save_to_env(&self) -> Result<(), ConfigError> {
-        let env_path = std::path::Path::new(".env");
-        let mut lines: Vec<String> = if env_path.exists() {
-            std::io::BufReader::new(std::fs::File::open(env_path)?)
-                .lines()
-                .collect::<Result<Vec<_>, _>>()?
-        } else {
-            Vec::new()
-        };
-
         let mut new_values = std::collections::HashMap::new();
         new_values.insert("BAE_LIBRARY_ID", self.library_id.clone());
+        new_values.insert("BAE_DEVICE_ID", self.device_id.clone());
         if let Some(key) = &self.discogs_api_key {
             new_values.insert("BAE_DISCOGS_API_KEY", key.clone());
         }
@@ -183,27 +599,10 @@ impl Config {
         if let Some(iface) = &self.torrent_bind_interface {
             new_values.insert("BAE_TORRENT_BIND_INTERFACE", iface.clone());
         }
-
-        let mut found = std::collections::HashSet::new();
-        for line in &mut lines {
-            if let Some(eq) = line.find('=') {
-                let key = line[..eq].trim().to_string();
-                if let Some(val) = new_values.get(key.as_str()) {
-                    *line = format!("{}={}", key, val);
-                    found.insert(key);
-                }
-            }
+        if let Some(token) = &self.remote_token {
+            new_values.insert("BAE_REMOTE_TOKEN", token.clone());
         }
-        for (key, val) in &new_values {
-            if !found.contains(*key) {
-                lines.push(format!("{}={}", key, val));
-            }
-        }
-        let mut file = std::fs::File::create(env_path)?;
-        for line in lines {
-            writeln!(file, "{}", line)?;
-        }
-        Ok(())
+        update_env_file(&new_values)
     }
 
     pub fn save_to_keyring(&self) -> Result<(), ConfigError> {
@@ -213,24 +612,159 @@ impl Config {
         if let Some(key) = &self.encryption_key {
             keyring::Entry::new("bae", "encryption_master_key")?.set_password(key)?;
         }
+        if let Some(token) = &self.remote_token {
+            keyring::Entry::new("bae", "remote_token")?.set_password(token)?;
+        }
+        Ok(())
+    }
+
+    /// Save scrobbling accounts, touching nothing else. A disconnected
+    /// account's secret is removed rather than left behind.
+    pub fn save_scrobble_accounts(&self, accounts: &ScrobbleAccounts) -> Result<(), ConfigError> {
+        let lastfm_json = accounts
+            .lastfm
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| ConfigError::Serialization(e.to_string()))?;
+
+        if Self::is_dev_mode() {
+            // Written empty once disconnected, so the old value doesn't linger
+            let mut new_values = std::collections::HashMap::new();
+            new_values.insert("BAE_LASTFM_ACCOUNT", lastfm_json.unwrap_or_default());
+            new_values.insert(
+                "BAE_LISTENBRAINZ_TOKEN",
+                accounts.listenbrainz_token.clone().unwrap_or_default(),
+            );
+            return update_env_file(&new_values);
+        }
+
+        let lastfm_entry = keyring::Entry::new("bae", "lastfm_account")?;
+        match &lastfm_json {
+            Some(json) => lastfm_entry.set_password(json)?,
+            None => delete_keyring_secret(&lastfm_entry)?,
+        }
+        let listenbrainz_entry = keyring::Entry::new("bae", "listenbrainz_token")?;
+        match &accounts.listenbrainz_token {
+            Some(token) => listenbrainz_entry.set_password(token)?,
+            None => delete_keyring_secret(&listenbrainz_entry)?,
+        }
+
+        let config_dir = self.get_library_path();
+        std::fs::create_dir_all(&config_dir)?;
+        let yaml = ConfigYaml {
+            lastfm_username: accounts.lastfm.as_ref().map(|a| a.username.clone()),
+            listenbrainz_connected: Some(accounts.listenbrainz_token.is_some()),
+            ..self.read_config_yaml()
+        };
+        std::fs::write(
+            config_dir.join("config.yaml"),
+            serde_yaml::to_string(&yaml).unwrap(),
+        )?;
+        Ok(())
+    }
+
+    /// Config file as currently on disk, or defaults if missing or unreadable
+    fn read_config_yaml(&self) -> ConfigYaml {
+        std::fs::read_to_string(self.get_library_path().join("config.yaml"))
+            .ok()
+            .and_then(|s| serde_yaml::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Remember the open route for the next launch. Touches only that field,
+    /// so it can run on every navigation without rewriting other settings.
+    /// Dev mode keeps no route.
+    pub fn save_last_route(&self, route: &str) -> Result<(), ConfigError> {
+        if Self::is_dev_mode() {
+            return Ok(());
+        }
+        let config_dir = self.get_library_path();
+        std::fs::create_dir_all(&config_dir)?;
+        let yaml = ConfigYaml {
+            last_route: Some(route.to_string()),
+            ..self.read_config_yaml()
+        };
+        std::fs::write(
+            config_dir.join("config.yaml"),
+            serde_yaml::to_string(&yaml).unwrap(),
+        )?;
+        Ok(())
+    }
+
+    /// Write just the device ID, leaving other settings as they are on disk
+    fn save_device_id(&self) -> Result<(), ConfigError> {
+        let config_dir = self.get_library_path();
+        std::fs::create_dir_all(&config_dir)?;
+        let yaml = ConfigYaml {
+            device_id: Some(self.device_id.clone()),
+            ..self.read_config_yaml()
+        };
+        std::fs::write(
+            config_dir.join("config.yaml"),
+            serde_yaml::to_string(&yaml).unwrap(),
+        )?;
         Ok(())
     }
 
     pub fn save_to_config_yaml(&self) -> Result<(), ConfigError> {
         let config_dir = self.get_library_path();
         std::fs::create_dir_all(&config_dir)?;
+        let saved = self.read_config_yaml();
         let yaml = ConfigYaml {
             library_id: Some(self.library_id.clone()),
+            device_id: Some(self.device_id.clone()),
             torrent_bind_interface: self.torrent_bind_interface.clone(),
             torrent_listen_port: self.torrent_listen_port,
             torrent_enable_upnp: self.torrent_enable_upnp,
             torrent_enable_natpmp: self.torrent_enable_natpmp,
+            torrent_enable_ipv6: self.torrent_enable_ipv6,
+            torrent_enable_dht: self.torrent_enable_dht,
+            torrent_dht_bootstrap_nodes: Some(self.torrent_dht_bootstrap_nodes.clone()),
             torrent_max_connections: self.torrent_max_connections,
             torrent_max_connections_per_torrent: self.torrent_max_connections_per_torrent,
             torrent_max_uploads: self.torrent_max_uploads,
             torrent_max_uploads_per_torrent: self.torrent_max_uploads_per_torrent,
             subsonic_enabled: self.subsonic_enabled,
             subsonic_port: Some(self.subsonic_port),
+            subsonic_transcode_bitrate_kbps: Some(self.subsonic_transcode_bitrate_kbps),
+            remote_enabled: self.remote_enabled,
+            remote_port: Some(self.remote_port),
+            cloud_download_concurrency: Some(self.cloud_download_concurrency),
+            playback_decode_ahead_ms: Some(self.playback_decode_ahead_ms),
+            playback_gapless_preroll_ms: Some(self.playback_gapless_preroll_ms),
+            playback_output_device: self.playback_output_device.clone(),
+            playback_output_bit_depth: Some(self.playback_output_bit_depth),
+            playback_dither: Some(self.playback_dither),
+            playback_exclusive_devices: Some(self.playback_exclusive_devices.clone()),
+            playback_skip_silence: Some(self.playback_skip_silence),
+            playback_min_silence_ms: Some(self.playback_min_silence_ms),
+            playback_in_background: Some(self.playback_in_background),
+            cloud_price_per_gb: Some(self.cloud_price_per_gb),
+            startup_view: Some(self.startup_view),
+            // Saved separately; keep whatever was written since launch
+            last_route: saved.last_route,
+            library_sort: Some(self.library_sort),
+            library_quality_filter: self.library_quality_filter,
+            verification_interval_days: self.verification_interval_days,
+            snapshot_profile_id: self.snapshot_profile_id.clone(),
+            snapshot_interval_hours: self.snapshot_interval_hours,
+            snapshots_kept: Some(self.snapshots_kept),
+            track_click_action: Some(self.track_click_action),
+            featured_credit_style: Some(self.featured_credit_style),
+            featured_credit_markers: Some(self.featured_credit_markers.clone()),
+            metered_mode: Some(self.metered_mode),
+            metered_allow_cloud_prefetch: Some(self.metered_allow_cloud_prefetch),
+            metered_allow_torrents: Some(self.metered_allow_torrents),
+            cd_insert_action: Some(self.cd_insert_action),
+            cd_eject_after_rip: Some(self.cd_eject_after_rip),
+            folder_patterns: Some(self.folder_patterns.clone()),
+            low_power_import: Some(self.low_power_import),
+            import_memory_budget_mb: Some(self.import_memory_budget_mb),
+            max_concurrent_imports: Some(self.max_concurrent_imports),
+            // Saved separately, like last_route
+            lastfm_username: saved.lastfm_username,
+            listenbrainz_connected: saved.listenbrainz_connected,
         };
         std::fs::write(
             config_dir.join("config.yaml"),
@@ -267,3 +801,102 @@ impl Config {
         }
     }
 }
+
+/// Set keys in `.env`, keeping its other lines as they are
+fn update_env_file(
+    new_values: &std::collections::HashMap<&str, String>,
+) -> Result<(), ConfigError> {
+    let env_path = std::path::Path::new(".env");
+    let mut lines: Vec<String> = if env_path.exists() {
+        std::io::BufReader::new(std::fs::File::open(env_path)?)
+            .lines()
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        Vec::new()
+    };
+
+    let mut found = std::collections::HashSet::new();
+    for line in &mut lines {
+        if let Some(eq) = line.find('=') {
+            let key = line[..eq].trim().to_string();
+            if let Some(val) = new_values.get(key.as_str()) {
+                *line = format!("{}={}", key, val);
+                found.insert(key);
+            }
+        }
+    }
+    for (key, val) in new_values {
+        if !found.contains(*key) {
+            lines.push(format!("{}={}", key, val));
+        }
+    }
+    let mut file = std::fs::File::create(env_path)?;
+    for line in lines {
+        writeln!(file, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Check the system keyring can be read. A secret that was never stored
+/// still means the keyring works.
+pub fn check_keyring() -> Result<(), ConfigError> {
+    match keyring::Entry::new("bae", "encryption_master_key")?.get_password() {
+        Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn keyring_secret(name: &str) -> Option<String> {
+    keyring::Entry::new("bae", name)
+        .ok()
+        .and_then(|e| e.get_password().ok())
+}
+
+/// Remove a secret, treating one that was never stored as removed
+fn delete_keyring_secret(entry: &keyring::Entry) -> Result<(), ConfigError> {
+    match entry.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_device_survives_a_save_and_load() {
+        let yaml = ConfigYaml {
+            playback_output_device: Some("USB DAC".to_string()),
+            ..ConfigYaml::default()
+        };
+
+        let loaded: ConfigYaml =
+            serde_yaml::from_str(&serde_yaml::to_string(&yaml).unwrap()).unwrap();
+
+        assert_eq!(loaded.playback_output_device.as_deref(), Some("USB DAC"));
+    }
+
+    #[test]
+    fn test_config_without_an_output_device_follows_the_system_default() {
+        let loaded: ConfigYaml = serde_yaml::from_str("library_id: abc\n").unwrap();
+
+        assert_eq!(loaded.playback_output_device, None);
+    }
+
+    #[test]
+    fn test_track_click_action_is_stored_by_name() {
+        let yaml = ConfigYaml {
+            track_click_action: Some(TrackClickAction::AddToQueue),
+            ..ConfigYaml::default()
+        };
+        let saved = serde_yaml::to_string(&yaml).unwrap();
+        assert!(saved.contains("track_click_action: add_to_queue"));
+
+        let loaded: ConfigYaml = serde_yaml::from_str(&saved).unwrap();
+        assert_eq!(
+            loaded.track_click_action,
+            Some(TrackClickAction::AddToQueue)
+        );
+    }
+}
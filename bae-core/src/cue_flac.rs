@@ -125,17 +125,27 @@ impl CueFlacProcessor {
                 }
             }
         }
+        fn stem(path: &Path) -> &str {
+            path.file_stem().and_then(|s| s.to_str()).unwrap_or("")
+        }
         for cue_path in cue_files {
-            let cue_stem = cue_path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
-            for flac_path in &flac_files {
-                let flac_stem = flac_path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
-                if cue_stem == flac_stem {
-                    pairs.push(CueFlacPair {
-                        flac_path: flac_path.clone(),
-                        cue_path: cue_path.clone(),
-                    });
-                    break;
-                }
+            let cue_stem = stem(&cue_path);
+            // Prefer an exact stem match, then a case-insensitive one: rips made on
+            // Windows or macOS often differ in case between the CUE and FLAC names.
+            let flac_path = flac_files
+                .iter()
+                .find(|flac_path| stem(flac_path) == cue_stem)
+                .or_else(|| {
+                    let cue_stem_lower = cue_stem.to_lowercase();
+                    flac_files
+                        .iter()
+                        .find(|flac_path| stem(flac_path).to_lowercase() == cue_stem_lower)
+                });
+            if let Some(flac_path) = flac_path {
+                pairs.push(CueFlacPair {
+                    flac_path: flac_path.clone(),
+                    cue_path,
+                });
             }
         }
         Ok(pairs)
@@ -1116,6 +1126,37 @@ FILE "Test Artist - Test Album.flac" WAVE
         );
     }
 
+    #[test]
+    fn test_detect_cue_flac_from_paths_stem_case_differs() {
+        use std::path::PathBuf;
+
+        let paths = vec![
+            PathBuf::from("/music/Album.flac"),
+            PathBuf::from("/music/album.cue"),
+        ];
+
+        let pairs = CueFlacProcessor::detect_cue_flac_from_paths(&paths).unwrap();
+
+        assert_eq!(pairs.len(), 1, "Should pair stems that differ only in case");
+        assert_eq!(pairs[0].flac_path, PathBuf::from("/music/Album.flac"));
+    }
+
+    #[test]
+    fn test_detect_cue_flac_from_paths_prefers_exact_stem() {
+        use std::path::PathBuf;
+
+        let paths = vec![
+            PathBuf::from("/music/ALBUM.flac"),
+            PathBuf::from("/music/album.flac"),
+            PathBuf::from("/music/album.cue"),
+        ];
+
+        let pairs = CueFlacProcessor::detect_cue_flac_from_paths(&paths).unwrap();
+
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].flac_path, PathBuf::from("/music/album.flac"));
+    }
+
     #[test]
     fn test_detect_cue_flac_from_paths_no_match() {
         use std::path::PathBuf;
@@ -1,9 +1,93 @@
 use crate::db::models::*;
 use chrono::{DateTime, Utc};
 use sqlx::{Row, SqlitePool};
+use std::collections::HashMap;
 use tracing::info;
 use uuid::Uuid;
 const IMPORT_STATUS_QUEUED: &str = "queued";
+/// Number of recent values kept per (source, field) in search history
+pub(crate) const SEARCH_HISTORY_LIMIT: i64 = 20;
+/// Triggers that mark albums whose search index rows are out of date:
+/// (trigger name, event on table, IDs of the affected albums)
+const SEARCH_INDEX_TRIGGERS: &[(&str, &str, &str)] = &[
+    ("search_dirty_album_insert", "INSERT ON albums", "SELECT NEW.id"),
+    ("search_dirty_album_update", "UPDATE OF title ON albums", "SELECT NEW.id"),
+    ("search_dirty_album_delete", "DELETE ON albums", "SELECT OLD.id"),
+    (
+        "search_dirty_release_insert",
+        "INSERT ON releases",
+        "SELECT NEW.album_id",
+    ),
+    (
+        "search_dirty_release_update",
+        "UPDATE OF album_id, label, catalog_number ON releases",
+        "SELECT NEW.album_id UNION SELECT OLD.album_id",
+    ),
+    (
+        "search_dirty_release_delete",
+        "DELETE ON releases",
+        "SELECT OLD.album_id",
+    ),
+    (
+        "search_dirty_track_insert",
+        "INSERT ON tracks",
+        "SELECT album_id FROM releases WHERE id = NEW.release_id",
+    ),
+    (
+        "search_dirty_track_update",
+        "UPDATE OF title, release_id ON tracks",
+        "SELECT album_id FROM releases WHERE id IN (NEW.release_id, OLD.release_id)",
+    ),
+    (
+        "search_dirty_track_delete",
+        "DELETE ON tracks",
+        "SELECT album_id FROM releases WHERE id = OLD.release_id",
+    ),
+    (
+        "search_dirty_album_artist_insert",
+        "INSERT ON album_artists",
+        "SELECT NEW.album_id",
+    ),
+    (
+        "search_dirty_album_artist_delete",
+        "DELETE ON album_artists",
+        "SELECT OLD.album_id",
+    ),
+    (
+        "search_dirty_track_artist_insert",
+        "INSERT ON track_artists",
+        "SELECT r.album_id FROM tracks t JOIN releases r ON r.id = t.release_id WHERE t.id = NEW.track_id",
+    ),
+    (
+        "search_dirty_track_artist_delete",
+        "DELETE ON track_artists",
+        "SELECT r.album_id FROM tracks t JOIN releases r ON r.id = t.release_id WHERE t.id = OLD.track_id",
+    ),
+    (
+        "search_dirty_artist_update",
+        "UPDATE OF name ON artists",
+        "SELECT album_id FROM album_artists WHERE artist_id = NEW.id \
+         UNION SELECT r.album_id FROM track_artists ta \
+         JOIN tracks t ON t.id = ta.track_id JOIN releases r ON r.id = t.release_id \
+         WHERE ta.artist_id = NEW.id",
+    ),
+];
+/// Artists credited on an album, for the search index
+const SEARCH_ALBUM_ARTISTS: &str = "SELECT group_concat(ar.name, ', ') FROM album_artists aa \
+     JOIN artists ar ON ar.id = aa.artist_id WHERE aa.album_id = a.id";
+/// Track artists, falling back to the album artists, for listings of tracks
+/// (`t`) with their album (`a`)
+const TRACK_ARTIST_NAME: &str = "COALESCE(\
+     (SELECT GROUP_CONCAT(name, ', ') FROM (\
+         SELECT ar.name FROM track_artists ta JOIN artists ar ON ar.id = ta.artist_id \
+         WHERE ta.track_id = t.id ORDER BY ta.position)), \
+     (SELECT GROUP_CONCAT(name, ', ') FROM (\
+         SELECT ar.name FROM album_artists aa JOIN artists ar ON ar.id = aa.artist_id \
+         WHERE aa.album_id = a.id ORDER BY aa.position)))";
+/// Columns `row_to_library_track` reads, besides `artist_name`
+const LIBRARY_TRACK_COLUMNS: &str = "t.id, t.release_id, t.title, t.disc_number, \
+     t.track_number, t.duration_ms, t.discogs_position, t.import_status, t.created_at, \
+     a.id AS album_id, a.title AS album_title, a.year AS album_year";
 #[derive(Debug, Clone)]
 pub struct Database {
     pool: SqlitePool,
@@ -46,7 +130,12 @@ impl Database {
                 cover_art_url TEXT,
                 is_compilation BOOLEAN NOT NULL DEFAULT FALSE,
                 created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL
+                updated_at TEXT NOT NULL,
+                play_count INTEGER NOT NULL DEFAULT 0,
+                last_played_at TEXT,
+                dominant_color TEXT,
+                accent_color TEXT,
+                preferred_release_id TEXT
             )
             "#,
         )
@@ -85,6 +174,7 @@ impl Database {
                 album_id TEXT NOT NULL,
                 artist_id TEXT NOT NULL,
                 position INTEGER NOT NULL,
+                credited_as TEXT,
                 FOREIGN KEY (album_id) REFERENCES albums (id) ON DELETE CASCADE,
                 FOREIGN KEY (artist_id) REFERENCES artists (id) ON DELETE CASCADE,
                 UNIQUE(album_id, artist_id)
@@ -163,6 +253,9 @@ impl Database {
                 format TEXT NOT NULL,
                 source_path TEXT,
                 encryption_nonce BLOB,
+                compressed BOOLEAN NOT NULL DEFAULT FALSE,
+                checksum_verified BOOLEAN NOT NULL DEFAULT FALSE,
+                sha256 TEXT,
                 created_at TEXT NOT NULL,
                 FOREIGN KEY (release_id) REFERENCES releases (id) ON DELETE CASCADE
             )
@@ -220,6 +313,17 @@ impl Database {
         )
         .execute(&self.pool)
         .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_albums_created_at ON albums (created_at)")
+            .execute(&self.pool)
+            .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_albums_last_played_at ON albums (last_played_at)",
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_albums_play_count ON albums (play_count)")
+            .execute(&self.pool)
+            .await?;
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_releases_album_id ON releases (album_id)")
             .execute(&self.pool)
             .await?;
@@ -241,6 +345,12 @@ impl Database {
                 piece_length INTEGER NOT NULL,
                 num_pieces INTEGER NOT NULL,
                 is_seeding BOOLEAN NOT NULL DEFAULT FALSE,
+                seed_path TEXT,
+                uploaded_bytes INTEGER NOT NULL DEFAULT 0,
+                downloaded_bytes INTEGER NOT NULL DEFAULT 0,
+                seeding_secs INTEGER NOT NULL DEFAULT 0,
+                seed_ratio_goal REAL,
+                seed_time_goal_secs INTEGER,
                 created_at TEXT NOT NULL,
                 FOREIGN KEY (release_id) REFERENCES releases (id) ON DELETE CASCADE
             )
@@ -307,13 +417,20 @@ impl Database {
                 name TEXT NOT NULL UNIQUE,
                 location TEXT NOT NULL,
                 location_path TEXT NOT NULL,
+                extra_location_paths TEXT NOT NULL DEFAULT '[]',
+                placement_policy TEXT NOT NULL DEFAULT 'fill_first',
                 encrypted BOOLEAN NOT NULL DEFAULT FALSE,
+                compressed BOOLEAN NOT NULL DEFAULT FALSE,
+                filename_policy TEXT NOT NULL DEFAULT 'replace',
                 is_default BOOLEAN NOT NULL DEFAULT FALSE,
+                cloud_provider TEXT NOT NULL DEFAULT 's3',
                 cloud_bucket TEXT,
                 cloud_region TEXT,
                 cloud_endpoint TEXT,
                 cloud_access_key TEXT,
                 cloud_secret_key TEXT,
+                cloud_archival BOOLEAN NOT NULL DEFAULT FALSE,
+                quota_bytes INTEGER,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             )
@@ -349,6 +466,10 @@ impl Database {
                 album_title TEXT NOT NULL,
                 artist_name TEXT NOT NULL,
                 folder_path TEXT NOT NULL,
+                kind TEXT NOT NULL DEFAULT 'folder',
+                storage_profile_id TEXT,
+                selected_cover_filename TEXT,
+                excluded_files TEXT NOT NULL DEFAULT '[]',
                 created_at INTEGER NOT NULL,
                 updated_at INTEGER NOT NULL,
                 error_message TEXT
@@ -363,6 +484,306 @@ impl Database {
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_imports_release_id ON imports (release_id)")
             .execute(&self.pool)
             .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS search_history (
+                source TEXT NOT NULL,
+                field TEXT NOT NULL,
+                value TEXT NOT NULL,
+                used_at INTEGER NOT NULL,
+                PRIMARY KEY (source, field, value)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS release_templates (
+                id TEXT PRIMARY KEY,
+                artist TEXT NOT NULL,
+                title_pattern TEXT NOT NULL,
+                next_number INTEGER NOT NULL,
+                number_width INTEGER NOT NULL DEFAULT 0,
+                cover_url TEXT,
+                created_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS track_bookmarks (
+                id TEXT PRIMARY KEY,
+                track_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                position_ms INTEGER NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (track_id) REFERENCES tracks (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_track_bookmarks_track_id ON track_bookmarks (track_id)",
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS track_dynamic_range (
+                track_id TEXT PRIMARY KEY,
+                dr_score INTEGER,
+                measured_at TEXT NOT NULL,
+                FOREIGN KEY (track_id) REFERENCES tracks (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS track_rips (
+                track_id TEXT PRIMARY KEY,
+                copy_crc INTEGER NOT NULL,
+                read_errors INTEGER NOT NULL DEFAULT 0,
+                c2_errors INTEGER NOT NULL DEFAULT 0,
+                suspicious_sectors INTEGER NOT NULL DEFAULT 0,
+                accuraterip_status TEXT NOT NULL,
+                accuraterip_confidence INTEGER NOT NULL DEFAULT 0,
+                ripped_at TEXT NOT NULL,
+                FOREIGN KEY (track_id) REFERENCES tracks (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS client_shares (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                token TEXT NOT NULL UNIQUE,
+                created_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS client_share_albums (
+                share_id TEXT NOT NULL,
+                album_id TEXT NOT NULL,
+                PRIMARY KEY (share_id, album_id),
+                FOREIGN KEY (share_id) REFERENCES client_shares (id) ON DELETE CASCADE,
+                FOREIGN KEY (album_id) REFERENCES albums (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS verification_runs (
+                id TEXT PRIMARY KEY,
+                scheduled BOOLEAN NOT NULL,
+                started_at TEXT NOT NULL,
+                finished_at TEXT,
+                files_checked INTEGER NOT NULL DEFAULT 0
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS verification_issues (
+                id TEXT PRIMARY KEY,
+                run_id TEXT NOT NULL,
+                kind TEXT NOT NULL DEFAULT 'corrupt',
+                file_id TEXT,
+                release_id TEXT,
+                filename TEXT NOT NULL,
+                storage_profile_id TEXT NOT NULL,
+                storage_profile_name TEXT NOT NULL,
+                error TEXT NOT NULL,
+                repaired BOOLEAN NOT NULL DEFAULT FALSE,
+                FOREIGN KEY (run_id) REFERENCES verification_runs (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_verification_issues_run_id ON verification_issues (run_id)",
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS changelog (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                device_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                entity_id TEXT NOT NULL,
+                before_json TEXT,
+                after_json TEXT,
+                created_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_changelog_entity_id ON changelog (entity_id)")
+            .execute(&self.pool)
+            .await?;
+        sqlx::query(
+            r#"
+            CREATE VIRTUAL TABLE IF NOT EXISTS library_search USING fts5 (
+                title,
+                artist,
+                album,
+                label,
+                catalog,
+                kind UNINDEXED,
+                entity_id UNINDEXED,
+                album_id UNINDEXED,
+                release_id UNINDEXED,
+                tokenize = 'unicode61 remove_diacritics 2'
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS search_index_dirty (
+                album_id TEXT PRIMARY KEY NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        // No foreign key: a queued listen outlives its track
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS scrobble_queue (
+                id TEXT PRIMARY KEY,
+                target TEXT NOT NULL,
+                track_id TEXT NOT NULL,
+                artist TEXT NOT NULL,
+                title TEXT NOT NULL,
+                album TEXT,
+                album_artist TEXT,
+                track_number INTEGER,
+                duration_ms INTEGER,
+                played_at TEXT NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS discid_lookups (
+                release_id TEXT PRIMARY KEY,
+                mb_discid TEXT NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (release_id) REFERENCES releases (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS track_plays (
+                id TEXT PRIMARY KEY,
+                track_id TEXT NOT NULL,
+                played_at TEXT NOT NULL,
+                FOREIGN KEY (track_id) REFERENCES tracks (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_track_plays_track_id ON track_plays (track_id)",
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_track_plays_played_at ON track_plays (played_at)",
+        )
+        .execute(&self.pool)
+        .await?;
+        // Multipart uploads still in flight, so an interrupted import resumes
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS cloud_uploads (
+                key TEXT PRIMARY KEY,
+                upload_id TEXT NOT NULL,
+                part_size INTEGER NOT NULL,
+                payload_sha256 TEXT NOT NULL,
+                source_sha256 TEXT NOT NULL,
+                encryption_nonce BLOB,
+                created_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS cloud_upload_parts (
+                upload_key TEXT NOT NULL,
+                part_number INTEGER NOT NULL,
+                etag TEXT NOT NULL,
+                checksum_sha256 TEXT NOT NULL,
+                PRIMARY KEY (upload_key, part_number),
+                FOREIGN KEY (upload_key) REFERENCES cloud_uploads (key) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        // Albums kept in the local cache for offline playback
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS offline_albums (
+                album_id TEXT PRIMARY KEY,
+                pinned_at TEXT NOT NULL,
+                FOREIGN KEY (album_id) REFERENCES albums (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        for (name, event, album_ids) in SEARCH_INDEX_TRIGGERS {
+            sqlx::query(&format!(
+                "CREATE TRIGGER IF NOT EXISTS {} AFTER {} BEGIN \
+                 INSERT OR IGNORE INTO search_index_dirty (album_id) {}; END",
+                name, event, album_ids
+            ))
+            .execute(&self.pool)
+            .await?;
+        }
+        // Albums the index has never seen, e.g. imported before it existed
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO search_index_dirty (album_id)
+            SELECT id FROM albums
+            WHERE id NOT IN (SELECT album_id FROM library_search WHERE kind = 'album')
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
         Ok(())
     }
     /// Insert a new artist
@@ -386,6 +807,17 @@ impl Database {
         .await?;
         Ok(())
     }
+    /// Rename an artist, keeping the sort name in step
+    pub async fn update_artist_name(&self, artist_id: &str, name: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE artists SET name = ?, sort_name = ?, updated_at = ? WHERE id = ?")
+            .bind(name)
+            .bind(name)
+            .bind(Utc::now().to_rfc3339())
+            .bind(artist_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
     /// Get artist by Discogs artist ID (for deduplication)
     pub async fn get_artist_by_discogs_id(
         &self,
@@ -413,6 +845,47 @@ impl Database {
             Ok(None)
         }
     }
+    /// Get an artist by exact name, ignoring case
+    pub async fn get_artist_by_name(&self, name: &str) -> Result<Option<DbArtist>, sqlx::Error> {
+        let row = sqlx::query(
+            "SELECT * FROM artists WHERE name = ? COLLATE NOCASE ORDER BY created_at LIMIT 1",
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(|row| DbArtist {
+            id: row.get("id"),
+            name: row.get("name"),
+            sort_name: row.get("sort_name"),
+            discogs_artist_id: row.get("discogs_artist_id"),
+            bandcamp_artist_id: row.get("bandcamp_artist_id"),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
+                .unwrap()
+                .with_timezone(&Utc),
+        }))
+    }
+    pub async fn get_artist_by_id(&self, artist_id: &str) -> Result<Option<DbArtist>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM artists WHERE id = ?")
+            .bind(artist_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.map(|row| DbArtist {
+            id: row.get("id"),
+            name: row.get("name"),
+            sort_name: row.get("sort_name"),
+            discogs_artist_id: row.get("discogs_artist_id"),
+            bandcamp_artist_id: row.get("bandcamp_artist_id"),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                .unwrap()
+                .with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
+                .unwrap()
+                .with_timezone(&Utc),
+        }))
+    }
     /// Insert album-artist relationship
     pub async fn insert_album_artist(
         &self,
@@ -420,18 +893,68 @@ impl Database {
     ) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
-            INSERT INTO album_artists (id, album_id, artist_id, position)
-            VALUES (?, ?, ?, ?)
+            INSERT INTO album_artists (id, album_id, artist_id, position, credited_as)
+            VALUES (?, ?, ?, ?, ?)
             "#,
         )
         .bind(&album_artist.id)
         .bind(&album_artist.album_id)
         .bind(&album_artist.artist_id)
         .bind(album_artist.position)
+        .bind(&album_artist.credited_as)
         .execute(&self.pool)
         .await?;
         Ok(())
     }
+    /// Get an album's artist links (ordered by position)
+    pub async fn get_album_artists(
+        &self,
+        album_id: &str,
+    ) -> Result<Vec<DbAlbumArtist>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM album_artists WHERE album_id = ? ORDER BY position")
+            .bind(album_id)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| DbAlbumArtist {
+                id: row.get("id"),
+                album_id: row.get("album_id"),
+                artist_id: row.get("artist_id"),
+                position: row.get("position"),
+                credited_as: row.get("credited_as"),
+            })
+            .collect())
+    }
+    /// Replace all of an album's artist links
+    pub async fn replace_album_artists(
+        &self,
+        album_id: &str,
+        album_artists: &[DbAlbumArtist],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM album_artists WHERE album_id = ?")
+            .bind(album_id)
+            .execute(&mut *tx)
+            .await?;
+        for album_artist in album_artists {
+            sqlx::query(
+                r#"
+                INSERT INTO album_artists (id, album_id, artist_id, position, credited_as)
+                VALUES (?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&album_artist.id)
+            .bind(&album_artist.album_id)
+            .bind(&album_artist.artist_id)
+            .bind(album_artist.position)
+            .bind(&album_artist.credited_as)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
     /// Insert track-artist relationship
     pub async fn insert_track_artist(
         &self,
@@ -758,8 +1281,84 @@ impl Database {
             .await?;
         Ok(())
     }
-    /// Update release import status
-    pub async fn update_release_status(
+    /// Update album title
+    pub async fn update_album_title(&self, album_id: &str, title: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE albums SET title = ?, updated_at = ? WHERE id = ?")
+            .bind(title)
+            .bind(Utc::now().to_rfc3339())
+            .bind(album_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+    /// Update track title
+    pub async fn update_track_title(&self, track_id: &str, title: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE tracks SET title = ? WHERE id = ?")
+            .bind(title)
+            .bind(track_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+    /// Update an album's title and year
+    pub async fn update_album_metadata(
+        &self,
+        album_id: &str,
+        title: &str,
+        year: Option<i32>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE albums SET title = ?, year = ?, updated_at = ? WHERE id = ?")
+            .bind(title)
+            .bind(year)
+            .bind(Utc::now().to_rfc3339())
+            .bind(album_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+    /// Update a release's descriptive fields (name, year, format, label,
+    /// catalog number, country, barcode)
+    pub async fn update_release_metadata(&self, release: &DbRelease) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE releases SET
+                release_name = ?, year = ?, format = ?, label = ?,
+                catalog_number = ?, country = ?, barcode = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&release.release_name)
+        .bind(release.year)
+        .bind(&release.format)
+        .bind(&release.label)
+        .bind(&release.catalog_number)
+        .bind(&release.country)
+        .bind(&release.barcode)
+        .bind(Utc::now().to_rfc3339())
+        .bind(&release.id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+    /// Update a track's title and position
+    pub async fn update_track_metadata(
+        &self,
+        track_id: &str,
+        title: &str,
+        disc_number: Option<i32>,
+        track_number: Option<i32>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE tracks SET title = ?, disc_number = ?, track_number = ? WHERE id = ?")
+            .bind(title)
+            .bind(disc_number)
+            .bind(track_number)
+            .bind(track_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+    /// Update release import status
+    pub async fn update_release_status(
         &self,
         release_id: &str,
         status: ImportStatus,
@@ -778,7 +1377,8 @@ impl Database {
             r#"
             SELECT 
                 a.id, a.title, a.year, a.bandcamp_album_id, a.cover_image_id, a.cover_art_url,
-                a.is_compilation, a.created_at, a.updated_at,
+                a.is_compilation, a.created_at, a.updated_at, a.play_count, a.last_played_at,
+                a.dominant_color, a.accent_color, a.preferred_release_id,
                 ad.discogs_master_id, ad.discogs_release_id,
                 amb.musicbrainz_release_group_id, amb.musicbrainz_release_id
             FROM albums a
@@ -825,6 +1425,15 @@ impl Database {
                 updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
                     .unwrap()
                     .with_timezone(&Utc),
+                play_count: row.get("play_count"),
+                last_played_at: row.get::<Option<String>, _>("last_played_at").map(|s| {
+                    DateTime::parse_from_rfc3339(&s)
+                        .unwrap()
+                        .with_timezone(&Utc)
+                }),
+                dominant_color: row.get("dominant_color"),
+                accent_color: row.get("accent_color"),
+                preferred_release_id: row.get("preferred_release_id"),
             });
         }
         Ok(albums)
@@ -835,7 +1444,8 @@ impl Database {
             r#"
             SELECT 
                 a.id, a.title, a.year, a.bandcamp_album_id, a.cover_image_id, a.cover_art_url,
-                a.is_compilation, a.created_at, a.updated_at,
+                a.is_compilation, a.created_at, a.updated_at, a.play_count, a.last_played_at,
+                a.dominant_color, a.accent_color, a.preferred_release_id,
                 ad.discogs_master_id, ad.discogs_release_id,
                 amb.musicbrainz_release_group_id, amb.musicbrainz_release_id
             FROM albums a
@@ -882,6 +1492,15 @@ impl Database {
                 updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
                     .unwrap()
                     .with_timezone(&Utc),
+                play_count: row.get("play_count"),
+                last_played_at: row.get::<Option<String>, _>("last_played_at").map(|s| {
+                    DateTime::parse_from_rfc3339(&s)
+                        .unwrap()
+                        .with_timezone(&Utc)
+                }),
+                dominant_color: row.get("dominant_color"),
+                accent_color: row.get("accent_color"),
+                preferred_release_id: row.get("preferred_release_id"),
             }
         }))
     }
@@ -981,13 +1600,40 @@ impl Database {
         }
         Ok(tracks)
     }
+    /// Every track of each album's default edition (the preferred release,
+    /// else the first), so albums with several editions list each track once
+    pub async fn get_library_tracks(&self) -> Result<Vec<DbLibraryTrack>, sqlx::Error> {
+        let rows = sqlx::query(&format!(
+            r#"
+            WITH default_releases AS (
+                SELECT a.id AS album_id, COALESCE(
+                    (SELECT r.id FROM releases r
+                     WHERE r.id = a.preferred_release_id AND r.album_id = a.id),
+                    (SELECT r.id FROM releases r
+                     WHERE r.album_id = a.id ORDER BY r.created_at LIMIT 1)
+                ) AS release_id
+                FROM albums a
+            )
+            SELECT {}, {} AS artist_name
+            FROM tracks t
+            JOIN default_releases d ON d.release_id = t.release_id
+            JOIN albums a ON a.id = d.album_id
+            ORDER BY a.title, t.disc_number, t.track_number
+            "#,
+            LIBRARY_TRACK_COLUMNS, TRACK_ARTIST_NAME
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(Self::row_to_library_track).collect())
+    }
     /// Insert a new file record
     pub async fn insert_file(&self, file: &DbFile) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
             INSERT INTO files (
-                id, release_id, original_filename, file_size, format, source_path, encryption_nonce, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                id, release_id, original_filename, file_size, format, source_path, encryption_nonce,
+                compressed, checksum_verified, sha256, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&file.id)
@@ -997,11 +1643,35 @@ impl Database {
         .bind(&file.format)
         .bind(&file.source_path)
         .bind(&file.encryption_nonce)
+        .bind(file.compressed)
+        .bind(file.checksum_verified)
+        .bind(&file.sha256)
         .bind(file.created_at.to_rfc3339())
         .execute(&self.pool)
         .await?;
         Ok(())
     }
+    /// Point a file at where it's stored now, keeping its id so tracks still
+    /// find it
+    pub async fn update_file_storage(&self, file: &DbFile) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE files
+            SET source_path = ?, encryption_nonce = ?, compressed = ?, checksum_verified = ?,
+                sha256 = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&file.source_path)
+        .bind(&file.encryption_nonce)
+        .bind(file.compressed)
+        .bind(file.checksum_verified)
+        .bind(&file.sha256)
+        .bind(&file.id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
     /// Get files for a release
     pub async fn get_files_for_release(
         &self,
@@ -1021,6 +1691,9 @@ impl Database {
                 format: row.get("format"),
                 source_path: row.get("source_path"),
                 encryption_nonce: row.get("encryption_nonce"),
+                compressed: row.get("compressed"),
+                checksum_verified: row.get("checksum_verified"),
+                sha256: row.get("sha256"),
                 created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
                     .unwrap()
                     .with_timezone(&Utc),
@@ -1028,6 +1701,12 @@ impl Database {
         }
         Ok(files)
     }
+    /// Where every file in the library is stored
+    pub async fn get_file_source_paths(&self) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT source_path FROM files WHERE source_path IS NOT NULL")
+            .fetch_all(&self.pool)
+            .await
+    }
     /// Get a specific file by ID
     pub async fn get_file_by_id(&self, file_id: &str) -> Result<Option<DbFile>, sqlx::Error> {
         let row = sqlx::query("SELECT * FROM files WHERE id = ?")
@@ -1043,6 +1722,9 @@ impl Database {
                 format: row.get("format"),
                 source_path: row.get("source_path"),
                 encryption_nonce: row.get("encryption_nonce"),
+                compressed: row.get("compressed"),
+                checksum_verified: row.get("checksum_verified"),
+                sha256: row.get("sha256"),
                 created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
                     .unwrap()
                     .with_timezone(&Utc),
@@ -1117,6 +1799,60 @@ impl Database {
             Ok(None)
         }
     }
+    /// Quality of each track in a release that has an audio format
+    pub async fn get_track_qualities_for_release(
+        &self,
+        release_id: &str,
+    ) -> Result<HashMap<String, AudioQuality>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT af.track_id, af.format, af.sample_rate, af.bits_per_sample
+            FROM audio_formats af
+            JOIN tracks t ON af.track_id = t.id
+            WHERE t.release_id = ?
+            "#,
+        )
+        .bind(release_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let quality = AudioQuality::classify(
+                    &row.get::<String, _>("format"),
+                    row.get("sample_rate"),
+                    row.get("bits_per_sample"),
+                );
+                (row.get("track_id"), quality)
+            })
+            .collect())
+    }
+    /// Lowest track quality per album, across all of its releases
+    pub async fn get_album_qualities(&self) -> Result<HashMap<String, AudioQuality>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT r.album_id, af.format, af.sample_rate, af.bits_per_sample
+            FROM audio_formats af
+            JOIN tracks t ON af.track_id = t.id
+            JOIN releases r ON t.release_id = r.id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let mut qualities: HashMap<String, AudioQuality> = HashMap::new();
+        for row in &rows {
+            let quality = AudioQuality::classify(
+                &row.get::<String, _>("format"),
+                row.get("sample_rate"),
+                row.get("bits_per_sample"),
+            );
+            qualities
+                .entry(row.get("album_id"))
+                .and_modify(|q| *q = (*q).min(quality))
+                .or_insert(quality);
+        }
+        Ok(qualities)
+    }
     /// Delete a release by ID
     ///
     /// This will cascade delete all related records:
@@ -1170,7 +1906,8 @@ impl Database {
             r#"
             SELECT 
                 a.id, a.title, a.year, a.bandcamp_album_id, a.cover_image_id, a.cover_art_url,
-                a.is_compilation, a.created_at, a.updated_at,
+                a.is_compilation, a.created_at, a.updated_at, a.play_count, a.last_played_at,
+                a.dominant_color, a.accent_color, a.preferred_release_id,
                 ad.discogs_master_id, ad.discogs_release_id,
                 amb.musicbrainz_release_group_id, amb.musicbrainz_release_id
             FROM albums a
@@ -1183,7 +1920,8 @@ impl Database {
             r#"
             SELECT 
                 a.id, a.title, a.year, a.bandcamp_album_id, a.cover_image_id, a.cover_art_url,
-                a.is_compilation, a.created_at, a.updated_at,
+                a.is_compilation, a.created_at, a.updated_at, a.play_count, a.last_played_at,
+                a.dominant_color, a.accent_color, a.preferred_release_id,
                 ad.discogs_master_id, ad.discogs_release_id,
                 amb.musicbrainz_release_group_id, amb.musicbrainz_release_id
             FROM albums a
@@ -1196,7 +1934,8 @@ impl Database {
             r#"
             SELECT 
                 a.id, a.title, a.year, a.bandcamp_album_id, a.cover_image_id, a.cover_art_url,
-                a.is_compilation, a.created_at, a.updated_at,
+                a.is_compilation, a.created_at, a.updated_at, a.play_count, a.last_played_at,
+                a.dominant_color, a.accent_color, a.preferred_release_id,
                 ad.discogs_master_id, ad.discogs_release_id,
                 amb.musicbrainz_release_group_id, amb.musicbrainz_release_id
             FROM albums a
@@ -1265,6 +2004,15 @@ impl Database {
                 updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
                     .unwrap()
                     .with_timezone(&Utc),
+                play_count: row.get("play_count"),
+                last_played_at: row.get::<Option<String>, _>("last_played_at").map(|s| {
+                    DateTime::parse_from_rfc3339(&s)
+                        .unwrap()
+                        .with_timezone(&Utc)
+                }),
+                dominant_color: row.get("dominant_color"),
+                accent_color: row.get("accent_color"),
+                preferred_release_id: row.get("preferred_release_id"),
             }
         }))
     }
@@ -1281,7 +2029,8 @@ impl Database {
             r#"
             SELECT 
                 a.id, a.title, a.year, a.bandcamp_album_id, a.cover_image_id, a.cover_art_url,
-                a.is_compilation, a.created_at, a.updated_at,
+                a.is_compilation, a.created_at, a.updated_at, a.play_count, a.last_played_at,
+                a.dominant_color, a.accent_color, a.preferred_release_id,
                 ad.discogs_master_id, ad.discogs_release_id,
                 amb.musicbrainz_release_group_id, amb.musicbrainz_release_id
             FROM albums a
@@ -1294,7 +2043,8 @@ impl Database {
             r#"
             SELECT 
                 a.id, a.title, a.year, a.bandcamp_album_id, a.cover_image_id, a.cover_art_url,
-                a.is_compilation, a.created_at, a.updated_at,
+                a.is_compilation, a.created_at, a.updated_at, a.play_count, a.last_played_at,
+                a.dominant_color, a.accent_color, a.preferred_release_id,
                 ad.discogs_master_id, ad.discogs_release_id,
                 amb.musicbrainz_release_group_id, amb.musicbrainz_release_id
             FROM albums a
@@ -1307,7 +2057,8 @@ impl Database {
             r#"
             SELECT 
                 a.id, a.title, a.year, a.bandcamp_album_id, a.cover_image_id, a.cover_art_url,
-                a.is_compilation, a.created_at, a.updated_at,
+                a.is_compilation, a.created_at, a.updated_at, a.play_count, a.last_played_at,
+                a.dominant_color, a.accent_color, a.preferred_release_id,
                 ad.discogs_master_id, ad.discogs_release_id,
                 amb.musicbrainz_release_group_id, amb.musicbrainz_release_id
             FROM albums a
@@ -1376,6 +2127,15 @@ impl Database {
                 updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
                     .unwrap()
                     .with_timezone(&Utc),
+                play_count: row.get("play_count"),
+                last_played_at: row.get::<Option<String>, _>("last_played_at").map(|s| {
+                    DateTime::parse_from_rfc3339(&s)
+                        .unwrap()
+                        .with_timezone(&Utc)
+                }),
+                dominant_color: row.get("dominant_color"),
+                accent_color: row.get("accent_color"),
+                preferred_release_id: row.get("preferred_release_id"),
             }
         }))
     }
@@ -1385,8 +2145,10 @@ impl Database {
             r#"
             INSERT INTO torrents (
                 id, release_id, info_hash, magnet_link, torrent_name,
-                total_size_bytes, piece_length, num_pieces, is_seeding, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                total_size_bytes, piece_length, num_pieces, is_seeding, seed_path,
+                uploaded_bytes, downloaded_bytes, seeding_secs, seed_ratio_goal,
+                seed_time_goal_secs, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&torrent.id)
@@ -1398,6 +2160,12 @@ impl Database {
         .bind(torrent.piece_length)
         .bind(torrent.num_pieces)
         .bind(torrent.is_seeding)
+        .bind(&torrent.seed_path)
+        .bind(torrent.uploaded_bytes)
+        .bind(torrent.downloaded_bytes)
+        .bind(torrent.seeding_secs)
+        .bind(torrent.seed_ratio_goal)
+        .bind(torrent.seed_time_goal_secs)
         .bind(torrent.created_at.to_rfc3339())
         .execute(&self.pool)
         .await?;
@@ -1411,7 +2179,9 @@ impl Database {
         let row = sqlx::query(
             r#"
             SELECT id, release_id, info_hash, magnet_link, torrent_name,
-                   total_size_bytes, piece_length, num_pieces, is_seeding, created_at
+                   total_size_bytes, piece_length, num_pieces, is_seeding, seed_path,
+                   uploaded_bytes, downloaded_bytes, seeding_secs, seed_ratio_goal,
+                   seed_time_goal_secs, created_at
             FROM torrents
             WHERE release_id = ?
             LIMIT 1
@@ -1430,6 +2200,12 @@ impl Database {
             piece_length: row.get("piece_length"),
             num_pieces: row.get("num_pieces"),
             is_seeding: row.get("is_seeding"),
+            seed_path: row.get("seed_path"),
+            uploaded_bytes: row.get("uploaded_bytes"),
+            downloaded_bytes: row.get("downloaded_bytes"),
+            seeding_secs: row.get("seeding_secs"),
+            seed_ratio_goal: row.get("seed_ratio_goal"),
+            seed_time_goal_secs: row.get("seed_time_goal_secs"),
             created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
                 .unwrap()
                 .with_timezone(&Utc),
@@ -1533,7 +2309,9 @@ impl Database {
         let rows = sqlx::query(
             r#"
             SELECT id, release_id, info_hash, magnet_link, torrent_name,
-                   total_size_bytes, piece_length, num_pieces, is_seeding, created_at
+                   total_size_bytes, piece_length, num_pieces, is_seeding, seed_path,
+                   uploaded_bytes, downloaded_bytes, seeding_secs, seed_ratio_goal,
+                   seed_time_goal_secs, created_at
             FROM torrents
             WHERE is_seeding = TRUE
             "#,
@@ -1552,12 +2330,106 @@ impl Database {
                 piece_length: row.get("piece_length"),
                 num_pieces: row.get("num_pieces"),
                 is_seeding: row.get("is_seeding"),
+                seed_path: row.get("seed_path"),
+                uploaded_bytes: row.get("uploaded_bytes"),
+                downloaded_bytes: row.get("downloaded_bytes"),
+                seeding_secs: row.get("seeding_secs"),
+                seed_ratio_goal: row.get("seed_ratio_goal"),
+                seed_time_goal_secs: row.get("seed_time_goal_secs"),
+                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                    .unwrap()
+                    .with_timezone(&Utc),
+            })
+            .collect())
+    }
+    /// Get every torrent whose original download is kept for seeding,
+    /// seeding or not, oldest first
+    pub async fn get_kept_torrents(&self) -> Result<Vec<DbTorrent>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, release_id, info_hash, magnet_link, torrent_name,
+                   total_size_bytes, piece_length, num_pieces, is_seeding, seed_path,
+                   uploaded_bytes, downloaded_bytes, seeding_secs, seed_ratio_goal,
+                   seed_time_goal_secs, created_at
+            FROM torrents
+            WHERE seed_path IS NOT NULL
+            ORDER BY created_at
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| DbTorrent {
+                id: row.get("id"),
+                release_id: row.get("release_id"),
+                info_hash: row.get("info_hash"),
+                magnet_link: row.get("magnet_link"),
+                torrent_name: row.get("torrent_name"),
+                total_size_bytes: row.get("total_size_bytes"),
+                piece_length: row.get("piece_length"),
+                num_pieces: row.get("num_pieces"),
+                is_seeding: row.get("is_seeding"),
+                seed_path: row.get("seed_path"),
+                uploaded_bytes: row.get("uploaded_bytes"),
+                downloaded_bytes: row.get("downloaded_bytes"),
+                seeding_secs: row.get("seeding_secs"),
+                seed_ratio_goal: row.get("seed_ratio_goal"),
+                seed_time_goal_secs: row.get("seed_time_goal_secs"),
                 created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
                     .unwrap()
                     .with_timezone(&Utc),
             })
             .collect())
     }
+    /// Record where a torrent's original download is kept (None = discarded)
+    /// and how much was downloaded to get it
+    pub async fn update_torrent_seed_path(
+        &self,
+        torrent_id: &str,
+        seed_path: Option<&str>,
+        downloaded_bytes: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE torrents SET seed_path = ?, downloaded_bytes = ? WHERE id = ?")
+            .bind(seed_path)
+            .bind(downloaded_bytes)
+            .bind(torrent_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+    /// Update a torrent's running upload and seeding time totals
+    pub async fn update_torrent_seed_stats(
+        &self,
+        torrent_id: &str,
+        uploaded_bytes: i64,
+        seeding_secs: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE torrents SET uploaded_bytes = ?, seeding_secs = ? WHERE id = ?")
+            .bind(uploaded_bytes)
+            .bind(seeding_secs)
+            .bind(torrent_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+    /// Set when a torrent should stop seeding (None = no goal)
+    pub async fn update_torrent_seed_goals(
+        &self,
+        torrent_id: &str,
+        ratio_goal: Option<f64>,
+        time_goal_secs: Option<i64>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE torrents SET seed_ratio_goal = ?, seed_time_goal_secs = ? WHERE id = ?",
+        )
+        .bind(ratio_goal)
+        .bind(time_goal_secs)
+        .bind(torrent_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
     /// Insert an image record
     pub async fn insert_image(&self, image: &DbImage) -> Result<(), sqlx::Error> {
         sqlx::query(
@@ -1693,11 +2565,57 @@ impl Database {
             format: row.get("format"),
             source_path: row.get("source_path"),
             encryption_nonce: row.get("encryption_nonce"),
+            compressed: row.get("compressed"),
+            checksum_verified: row.get("checksum_verified"),
+            sha256: row.get("sha256"),
             created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
                 .unwrap()
                 .with_timezone(&Utc),
         }))
     }
+    /// Record a completed play of one of the album's tracks
+    pub async fn record_album_play(
+        &self,
+        album_id: &str,
+        played_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE albums SET play_count = play_count + 1, last_played_at = ? WHERE id = ?",
+        )
+        .bind(played_at.to_rfc3339())
+        .bind(album_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+    /// Store the palette extracted from the album's cover
+    pub async fn set_album_palette(
+        &self,
+        album_id: &str,
+        dominant_color: &str,
+        accent_color: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE albums SET dominant_color = ?, accent_color = ? WHERE id = ?")
+            .bind(dominant_color)
+            .bind(accent_color)
+            .bind(album_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+    /// Set or clear the edition an album plays by default
+    pub async fn set_album_preferred_release(
+        &self,
+        album_id: &str,
+        release_id: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE albums SET preferred_release_id = ? WHERE id = ?")
+            .bind(release_id)
+            .bind(album_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
     /// Update album's cover_image_id
     pub async fn set_album_cover_image(
         &self,
@@ -1719,23 +2637,34 @@ impl Database {
         sqlx::query(
             r#"
             INSERT INTO storage_profiles (
-                id, name, location, location_path, encrypted, is_default,
-                cloud_bucket, cloud_region, cloud_endpoint, cloud_access_key, cloud_secret_key,
-                created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                id, name, location, location_path, extra_location_paths, placement_policy,
+                encrypted, compressed, filename_policy, is_default, cloud_provider,
+                cloud_bucket, cloud_region, cloud_endpoint, cloud_access_key,
+                cloud_secret_key, cloud_archival, quota_bytes, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&profile.id)
         .bind(&profile.name)
         .bind(profile.location.as_str())
         .bind(&profile.location_path)
+        .bind(
+            serde_json::to_string(&profile.extra_location_paths)
+                .unwrap_or_else(|_| "[]".to_string()),
+        )
+        .bind(profile.placement_policy.as_str())
         .bind(profile.encrypted)
+        .bind(profile.compressed)
+        .bind(profile.filename_policy.as_str())
         .bind(profile.is_default)
+        .bind(profile.cloud_provider.as_str())
         .bind(&profile.cloud_bucket)
         .bind(&profile.cloud_region)
         .bind(&profile.cloud_endpoint)
         .bind(&profile.cloud_access_key)
         .bind(&profile.cloud_secret_key)
+        .bind(profile.cloud_archival)
+        .bind(profile.quota_bytes.map(|b| b as i64))
         .bind(profile.created_at.to_rfc3339())
         .bind(profile.updated_at.to_rfc3339())
         .execute(&self.pool)
@@ -1780,24 +2709,35 @@ impl Database {
         sqlx::query(
             r#"
             UPDATE storage_profiles SET
-                name = ?, location = ?, location_path = ?, encrypted = ?, 
-                is_default = ?,
-                cloud_bucket = ?, cloud_region = ?, cloud_endpoint = ?,
-                cloud_access_key = ?, cloud_secret_key = ?,
-                updated_at = ?
+                name = ?, location = ?, location_path = ?, extra_location_paths = ?,
+                placement_policy = ?, encrypted = ?, compressed = ?, filename_policy = ?,
+                is_default = ?, cloud_provider = ?, cloud_bucket = ?, cloud_region = ?,
+                cloud_endpoint = ?,
+                cloud_access_key = ?, cloud_secret_key = ?, cloud_archival = ?,
+                quota_bytes = ?, updated_at = ?
             WHERE id = ?
             "#,
         )
         .bind(&profile.name)
         .bind(profile.location.as_str())
         .bind(&profile.location_path)
+        .bind(
+            serde_json::to_string(&profile.extra_location_paths)
+                .unwrap_or_else(|_| "[]".to_string()),
+        )
+        .bind(profile.placement_policy.as_str())
         .bind(profile.encrypted)
+        .bind(profile.compressed)
+        .bind(profile.filename_policy.as_str())
         .bind(profile.is_default)
+        .bind(profile.cloud_provider.as_str())
         .bind(&profile.cloud_bucket)
         .bind(&profile.cloud_region)
         .bind(&profile.cloud_endpoint)
         .bind(&profile.cloud_access_key)
         .bind(&profile.cloud_secret_key)
+        .bind(profile.cloud_archival)
+        .bind(profile.quota_bytes.map(|b| b as i64))
         .bind(profile.updated_at.to_rfc3339())
         .bind(&profile.id)
         .execute(&self.pool)
@@ -1823,10 +2763,32 @@ impl Database {
             .await?;
         Ok(())
     }
+    /// Total size of release files stored under each profile, keyed by profile ID.
+    /// Profiles with no releases are absent.
+    pub async fn get_storage_usage_by_profile(&self) -> Result<HashMap<String, u64>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT rs.storage_profile_id, SUM(f.file_size) AS used_bytes
+            FROM release_storage rs
+            JOIN files f ON f.release_id = rs.release_id
+            GROUP BY rs.storage_profile_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let used: i64 = row.get("used_bytes");
+                (row.get("storage_profile_id"), used as u64)
+            })
+            .collect())
+    }
     fn row_to_storage_profile(&self, row: &sqlx::sqlite::SqliteRow) -> DbStorageProfile {
         let location_str: String = row.get("location");
         let location = match location_str.as_str() {
             "cloud" => StorageLocation::Cloud,
+            "reference" => StorageLocation::Reference,
             _ => StorageLocation::Local,
         };
         DbStorageProfile {
@@ -1834,13 +2796,25 @@ impl Database {
             name: row.get("name"),
             location,
             location_path: row.get("location_path"),
+            extra_location_paths: serde_json::from_str(
+                &row.get::<String, _>("extra_location_paths"),
+            )
+            .unwrap_or_default(),
+            placement_policy: PlacementPolicy::from_db_str(
+                &row.get::<String, _>("placement_policy"),
+            ),
             encrypted: row.get("encrypted"),
+            compressed: row.get("compressed"),
+            filename_policy: FilenamePolicy::from_db_str(&row.get::<String, _>("filename_policy")),
             is_default: row.get("is_default"),
+            cloud_provider: CloudProvider::from_db_str(&row.get::<String, _>("cloud_provider")),
             cloud_bucket: row.get("cloud_bucket"),
             cloud_region: row.get("cloud_region"),
             cloud_endpoint: row.get("cloud_endpoint"),
             cloud_access_key: row.get("cloud_access_key"),
             cloud_secret_key: row.get("cloud_secret_key"),
+            cloud_archival: row.get("cloud_archival"),
+            quota_bytes: row.get::<Option<i64>, _>("quota_bytes").map(|b| b as u64),
             created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
                 .unwrap()
                 .with_timezone(&Utc),
@@ -1868,6 +2842,19 @@ impl Database {
         .await?;
         Ok(())
     }
+    /// Move a release to another storage profile
+    pub async fn set_release_storage_profile(
+        &self,
+        release_id: &str,
+        storage_profile_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE release_storage SET storage_profile_id = ? WHERE release_id = ?")
+            .bind(storage_profile_id)
+            .bind(release_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
     /// Get storage configuration for a release
     pub async fn get_release_storage(
         &self,
@@ -1886,6 +2873,19 @@ impl Database {
                 .with_timezone(&Utc),
         }))
     }
+    /// Releases stored under a profile
+    pub async fn get_release_ids_for_storage_profile(
+        &self,
+        profile_id: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT release_id FROM release_storage WHERE storage_profile_id = ? ORDER BY created_at",
+        )
+        .bind(profile_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(|row| row.get("release_id")).collect())
+    }
     /// Get storage profile for a release (joins release_storage with storage_profiles)
     pub async fn get_storage_profile_for_release(
         &self,
@@ -1909,8 +2909,9 @@ impl Database {
             r#"
             INSERT INTO imports (
                 id, status, release_id, album_title, artist_name,
-                folder_path, created_at, updated_at, error_message
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                folder_path, kind, storage_profile_id, selected_cover_filename,
+                excluded_files, created_at, updated_at, error_message
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&import.id)
@@ -1919,6 +2920,10 @@ impl Database {
         .bind(&import.album_title)
         .bind(&import.artist_name)
         .bind(&import.folder_path)
+        .bind(import.kind.as_str())
+        .bind(&import.storage_profile_id)
+        .bind(&import.selected_cover_filename)
+        .bind(serde_json::to_string(&import.excluded_files).unwrap_or_else(|_| "[]".to_string()))
         .bind(import.created_at)
         .bind(import.updated_at)
         .bind(&import.error_message)
@@ -1934,28 +2939,43 @@ impl Database {
             .await?;
         Ok(row.map(|row| self.row_to_import(&row)))
     }
-    /// Get all active (non-complete, non-failed) imports
+    /// The latest import that created or refreshed a release
+    pub async fn get_import_for_release(
+        &self,
+        release_id: &str,
+    ) -> Result<Option<DbImport>, sqlx::Error> {
+        let row = sqlx::query(
+            "SELECT * FROM imports WHERE release_id = ? ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(release_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(|row| self.row_to_import(&row)))
+    }
+    /// Get all active (unfinished) imports, newest first
     pub async fn get_active_imports(&self) -> Result<Vec<DbImport>, sqlx::Error> {
         let rows = sqlx::query(
-                "SELECT * FROM imports WHERE status IN ('preparing', 'importing') ORDER BY created_at DESC",
+                "SELECT * FROM imports WHERE status IN ('preparing', 'queued', 'importing', 'paused') ORDER BY created_at DESC",
             )
             .fetch_all(&self.pool)
             .await?;
         Ok(rows.iter().map(|row| self.row_to_import(row)).collect())
     }
-    /// Update import status
+    /// Update import status, clearing any earlier error
     pub async fn update_import_status(
         &self,
         id: &str,
         status: ImportOperationStatus,
     ) -> Result<(), sqlx::Error> {
         let now = Utc::now().timestamp();
-        sqlx::query("UPDATE imports SET status = ?, updated_at = ? WHERE id = ?")
-            .bind(status.as_str())
-            .bind(now)
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+        sqlx::query(
+            "UPDATE imports SET status = ?, error_message = NULL, updated_at = ? WHERE id = ?",
+        )
+        .bind(status.as_str())
+        .bind(now)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
         Ok(())
     }
     /// Update import with error message and set status to Failed
@@ -1988,6 +3008,38 @@ impl Database {
         Ok(())
     }
 
+    /// Remove what a partial import of a release stored: its file, image and
+    /// audio format records and its storage link. The release and its tracks
+    /// stay, queued to be imported again.
+    pub async fn clear_release_import(&self, release_id: &str) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "DELETE FROM audio_formats WHERE track_id IN (SELECT id FROM tracks WHERE release_id = ?)",
+        )
+        .bind(release_id)
+        .execute(&mut *tx)
+        .await?;
+        for table in ["images", "files", "release_storage"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE release_id = ?", table))
+                .bind(release_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        sqlx::query("UPDATE tracks SET import_status = ? WHERE release_id = ?")
+            .bind(ImportStatus::Queued)
+            .bind(release_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("UPDATE releases SET import_status = ?, updated_at = ? WHERE id = ?")
+            .bind(ImportStatus::Queued)
+            .bind(Utc::now().to_rfc3339())
+            .bind(release_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
     /// Delete an import record from the database.
     /// Used by UI to dismiss stuck imports so they don't reappear after restart.
     pub async fn delete_import(&self, id: &str) -> Result<(), sqlx::Error> {
@@ -1998,15 +3050,1304 @@ impl Database {
         Ok(())
     }
 
-    fn row_to_import(&self, row: &sqlx::sqlite::SqliteRow) -> DbImport {
-        let status_str: String = row.get("status");
-        let status = match status_str.as_str() {
-            "preparing" => ImportOperationStatus::Preparing,
-            "importing" => ImportOperationStatus::Importing,
-            "complete" => ImportOperationStatus::Complete,
-            "failed" => ImportOperationStatus::Failed,
-            _ => ImportOperationStatus::Preparing,
-        };
+    /// Record a manual search value, bumping it to most recent if already known.
+    /// Older values beyond the per-field limit are dropped.
+    pub async fn record_search_value(
+        &self,
+        source: &str,
+        field: &str,
+        value: &str,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now().timestamp();
+        sqlx::query(
+            r#"
+            INSERT INTO search_history (source, field, value, used_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (source, field, value) DO UPDATE SET used_at = excluded.used_at
+            "#,
+        )
+        .bind(source)
+        .bind(field)
+        .bind(value)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+        sqlx::query(
+            r#"
+            DELETE FROM search_history
+            WHERE source = ? AND field = ? AND value NOT IN (
+                SELECT value FROM search_history
+                WHERE source = ? AND field = ?
+                ORDER BY used_at DESC, rowid DESC
+                LIMIT ?
+            )
+            "#,
+        )
+        .bind(source)
+        .bind(field)
+        .bind(source)
+        .bind(field)
+        .bind(SEARCH_HISTORY_LIMIT)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Get all search history entries, most recent first
+    pub async fn get_search_history(&self) -> Result<Vec<DbSearchHistoryEntry>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM search_history ORDER BY used_at DESC, rowid DESC")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| DbSearchHistoryEntry {
+                source: row.get("source"),
+                field: row.get("field"),
+                value: row.get("value"),
+                used_at: row.get("used_at"),
+            })
+            .collect())
+    }
+
+    pub async fn insert_release_template(
+        &self,
+        template: &DbReleaseTemplate,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO release_templates (
+                id, artist, title_pattern, next_number, number_width, cover_url, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&template.id)
+        .bind(&template.artist)
+        .bind(&template.title_pattern)
+        .bind(template.next_number)
+        .bind(template.number_width)
+        .bind(&template.cover_url)
+        .bind(template.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// All release templates, oldest first
+    pub async fn get_release_templates(&self) -> Result<Vec<DbReleaseTemplate>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM release_templates ORDER BY created_at, rowid")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.iter().map(Self::row_to_release_template).collect())
+    }
+
+    pub async fn set_release_template_next_number(
+        &self,
+        id: &str,
+        next_number: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE release_templates SET next_number = ? WHERE id = ?")
+            .bind(next_number)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_release_template(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM release_templates WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn insert_track_bookmark(
+        &self,
+        bookmark: &DbTrackBookmark,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO track_bookmarks (id, track_id, name, position_ms, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&bookmark.id)
+        .bind(&bookmark.track_id)
+        .bind(&bookmark.name)
+        .bind(bookmark.position_ms)
+        .bind(bookmark.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Bookmarks on a track, in playback order
+    pub async fn get_bookmarks_for_track(
+        &self,
+        track_id: &str,
+    ) -> Result<Vec<DbTrackBookmark>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM track_bookmarks WHERE track_id = ? ORDER BY position_ms, created_at",
+        )
+        .bind(track_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(Self::row_to_track_bookmark).collect())
+    }
+
+    /// Bookmarks on all tracks of a release, in playback order within each track
+    pub async fn get_bookmarks_for_release(
+        &self,
+        release_id: &str,
+    ) -> Result<Vec<DbTrackBookmark>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT b.* FROM track_bookmarks b
+            JOIN tracks t ON t.id = b.track_id
+            WHERE t.release_id = ?
+            ORDER BY b.track_id, b.position_ms, b.created_at
+            "#,
+        )
+        .bind(release_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(Self::row_to_track_bookmark).collect())
+    }
+
+    pub async fn delete_track_bookmark(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM track_bookmarks WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn insert_scrobble(&self, scrobble: &DbScrobble) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO scrobble_queue (
+                id, target, track_id, artist, title, album, album_artist,
+                track_number, duration_ms, played_at, attempts
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&scrobble.id)
+        .bind(scrobble.target)
+        .bind(&scrobble.track_id)
+        .bind(&scrobble.artist)
+        .bind(&scrobble.title)
+        .bind(&scrobble.album)
+        .bind(&scrobble.album_artist)
+        .bind(scrobble.track_number)
+        .bind(scrobble.duration_ms)
+        .bind(scrobble.played_at.to_rfc3339())
+        .bind(scrobble.attempts)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Oldest queued scrobbles for a service, up to `limit`
+    pub async fn get_pending_scrobbles(
+        &self,
+        target: ScrobbleTarget,
+        limit: i64,
+    ) -> Result<Vec<DbScrobble>, sqlx::Error> {
+        let rows =
+            sqlx::query("SELECT * FROM scrobble_queue WHERE target = ? ORDER BY played_at LIMIT ?")
+                .bind(target)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows.iter().map(Self::row_to_scrobble).collect())
+    }
+
+    pub async fn count_pending_scrobbles(&self) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM scrobble_queue")
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Remove submitted scrobbles from the queue
+    pub async fn delete_scrobbles(&self, ids: &[String]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        for id in ids {
+            sqlx::query("DELETE FROM scrobble_queue WHERE id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Count a failed submission against each scrobble, keeping them queued
+    pub async fn record_scrobble_attempt(&self, ids: &[String]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        for id in ids {
+            sqlx::query("UPDATE scrobble_queue SET attempts = attempts + 1 WHERE id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Drop everything queued for a service, e.g. once it's disconnected
+    pub async fn clear_scrobbles(&self, target: ScrobbleTarget) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM scrobble_queue WHERE target = ?")
+            .bind(target)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Queue a ripped disc to be identified once its DiscID can be looked up
+    pub async fn insert_discid_lookup(&self, lookup: &DbDiscIdLookup) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO discid_lookups (release_id, mb_discid, attempts, last_error, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&lookup.release_id)
+        .bind(&lookup.mb_discid)
+        .bind(lookup.attempts)
+        .bind(&lookup.last_error)
+        .bind(lookup.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Discs waiting to be identified, oldest first
+    pub async fn get_discid_lookups(&self) -> Result<Vec<DbDiscIdLookup>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM discid_lookups ORDER BY created_at")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.iter().map(Self::row_to_discid_lookup).collect())
+    }
+
+    /// The queued lookup for a release, if it's still waiting
+    pub async fn get_discid_lookup(
+        &self,
+        release_id: &str,
+    ) -> Result<Option<DbDiscIdLookup>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM discid_lookups WHERE release_id = ?")
+            .bind(release_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.as_ref().map(Self::row_to_discid_lookup))
+    }
+
+    /// Count a failed lookup against a disc, keeping it queued
+    pub async fn record_discid_lookup_attempt(
+        &self,
+        release_id: &str,
+        error: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE discid_lookups SET attempts = attempts + 1, last_error = ? WHERE release_id = ?",
+        )
+        .bind(error)
+        .bind(release_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_discid_lookup(&self, release_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM discid_lookups WHERE release_id = ?")
+            .bind(release_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Link an album to a MusicBrainz release, replacing any earlier link
+    pub async fn set_album_musicbrainz_release(
+        &self,
+        album_id: &str,
+        mb_release: &MusicBrainzRelease,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO album_musicbrainz (
+                id, album_id, musicbrainz_release_group_id, musicbrainz_release_id
+            ) VALUES (?, ?, ?, ?)
+            ON CONFLICT (album_id) DO UPDATE SET
+                musicbrainz_release_group_id = excluded.musicbrainz_release_group_id,
+                musicbrainz_release_id = excluded.musicbrainz_release_id
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(album_id)
+        .bind(&mb_release.release_group_id)
+        .bind(&mb_release.release_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record a completed play of a track
+    pub async fn insert_track_play(
+        &self,
+        track_id: &str,
+        played_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT INTO track_plays (id, track_id, played_at) VALUES (?, ?, ?)")
+            .bind(Uuid::new_v4().to_string())
+            .bind(track_id)
+            .bind(played_at.to_rfc3339())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Plays between `from` (inclusive) and `to` (exclusive), newest first,
+    /// up to `limit`. Either bound may be left open.
+    pub async fn get_track_plays(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<DbTrackPlay>, sqlx::Error> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT p.id AS play_id, p.played_at, {}, {} AS artist_name
+            FROM track_plays p
+            JOIN tracks t ON t.id = p.track_id
+            JOIN releases r ON r.id = t.release_id
+            JOIN albums a ON a.id = r.album_id
+            WHERE (?1 IS NULL OR p.played_at >= ?1) AND (?2 IS NULL OR p.played_at < ?2)
+            ORDER BY p.played_at DESC
+            LIMIT ?3
+            "#,
+            LIBRARY_TRACK_COLUMNS, TRACK_ARTIST_NAME
+        ))
+        .bind(from.map(|t| t.to_rfc3339()))
+        .bind(to.map(|t| t.to_rfc3339()))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .iter()
+            .map(|row| DbTrackPlay {
+                id: row.get("play_id"),
+                track: Self::row_to_library_track(row),
+                played_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("played_at"))
+                    .unwrap()
+                    .with_timezone(&Utc),
+            })
+            .collect())
+    }
+
+    /// Tracks played most between `from` and `to`, ties going to the one
+    /// played last
+    pub async fn get_most_played_tracks(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<DbTrackPlayStats>, sqlx::Error> {
+        self.get_track_play_stats(from, to, "play_count DESC, last_played_at DESC", limit)
+            .await
+    }
+
+    /// Tracks by when they were last played, newest first
+    pub async fn get_recently_played_tracks(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<DbTrackPlayStats>, sqlx::Error> {
+        self.get_track_play_stats(None, None, "last_played_at DESC", limit)
+            .await
+    }
+
+    async fn get_track_play_stats(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        order_by: &str,
+        limit: i64,
+    ) -> Result<Vec<DbTrackPlayStats>, sqlx::Error> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT {}, {} AS artist_name, s.play_count, s.last_played_at
+            FROM (
+                SELECT track_id, COUNT(*) AS play_count, MAX(played_at) AS last_played_at
+                FROM track_plays
+                WHERE (?1 IS NULL OR played_at >= ?1) AND (?2 IS NULL OR played_at < ?2)
+                GROUP BY track_id
+            ) s
+            JOIN tracks t ON t.id = s.track_id
+            JOIN releases r ON r.id = t.release_id
+            JOIN albums a ON a.id = r.album_id
+            ORDER BY {}
+            LIMIT ?3
+            "#,
+            LIBRARY_TRACK_COLUMNS, TRACK_ARTIST_NAME, order_by
+        ))
+        .bind(from.map(|t| t.to_rfc3339()))
+        .bind(to.map(|t| t.to_rfc3339()))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .iter()
+            .map(|row| DbTrackPlayStats {
+                track: Self::row_to_library_track(row),
+                play_count: row.get("play_count"),
+                last_played_at: DateTime::parse_from_rfc3339(
+                    &row.get::<String, _>("last_played_at"),
+                )
+                .unwrap()
+                .with_timezone(&Utc),
+            })
+            .collect())
+    }
+
+    /// Record a track's DR score, replacing an earlier measurement. None
+    /// marks a silent track as measured.
+    pub async fn set_track_dynamic_range(
+        &self,
+        track_id: &str,
+        dr_score: Option<i32>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO track_dynamic_range (track_id, dr_score, measured_at)
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(track_id)
+        .bind(dr_score)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// DR score per track ID, for the measured tracks of a release. Silent
+    /// tracks map to None.
+    pub async fn get_track_dynamic_ranges_for_release(
+        &self,
+        release_id: &str,
+    ) -> Result<HashMap<String, Option<i32>>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT d.track_id, d.dr_score FROM track_dynamic_range d
+            JOIN tracks t ON t.id = d.track_id
+            WHERE t.release_id = ?
+            "#,
+        )
+        .bind(release_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .iter()
+            .map(|row| (row.get("track_id"), row.get("dr_score")))
+            .collect())
+    }
+
+    /// Imported releases with an audio track that has no DR score yet
+    pub async fn get_release_ids_missing_dynamic_range(&self) -> Result<Vec<String>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT t.release_id FROM tracks t
+            JOIN releases r ON r.id = t.release_id
+            JOIN audio_formats af ON af.track_id = t.id
+            LEFT JOIN track_dynamic_range d ON d.track_id = t.id
+            WHERE r.import_status = ? AND d.track_id IS NULL
+            "#,
+        )
+        .bind(ImportStatus::Complete.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(|row| row.get("release_id")).collect())
+    }
+
+    /// Record how a track was ripped, replacing an earlier rip of it
+    pub async fn set_track_rip(&self, rip: &DbTrackRip) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO track_rips (
+                track_id, copy_crc, read_errors, c2_errors, suspicious_sectors,
+                accuraterip_status, accuraterip_confidence, ripped_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&rip.track_id)
+        .bind(rip.copy_crc)
+        .bind(rip.read_errors)
+        .bind(rip.c2_errors)
+        .bind(rip.suspicious_sectors)
+        .bind(rip.accuraterip_status)
+        .bind(rip.accuraterip_confidence)
+        .bind(rip.ripped_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Rip status of the ripped tracks of a release, in track order
+    pub async fn get_track_rips_for_release(
+        &self,
+        release_id: &str,
+    ) -> Result<Vec<DbTrackRip>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT r.* FROM track_rips r
+            JOIN tracks t ON t.id = r.track_id
+            WHERE t.release_id = ?
+            ORDER BY t.disc_number, t.track_number
+            "#,
+        )
+        .bind(release_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(Self::row_to_track_rip).collect())
+    }
+
+    pub async fn insert_client_share(&self, share: &DbClientShare) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO client_shares (id, name, token, created_at)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(&share.id)
+        .bind(&share.name)
+        .bind(&share.token)
+        .bind(share.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Client shares, oldest first
+    pub async fn get_client_shares(&self) -> Result<Vec<DbClientShare>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM client_shares ORDER BY created_at")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.iter().map(Self::row_to_client_share).collect())
+    }
+
+    pub async fn get_client_share_by_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<DbClientShare>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM client_shares WHERE token = ?")
+            .bind(token)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.as_ref().map(Self::row_to_client_share))
+    }
+
+    pub async fn delete_client_share(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM client_shares WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Add an album to a share, or take it out
+    pub async fn set_album_shared(
+        &self,
+        share_id: &str,
+        album_id: &str,
+        shared: bool,
+    ) -> Result<(), sqlx::Error> {
+        let query = if shared {
+            "INSERT OR IGNORE INTO client_share_albums (share_id, album_id) VALUES (?, ?)"
+        } else {
+            "DELETE FROM client_share_albums WHERE share_id = ? AND album_id = ?"
+        };
+        sqlx::query(query)
+            .bind(share_id)
+            .bind(album_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// IDs of the albums in a share
+    pub async fn get_shared_album_ids(&self, share_id: &str) -> Result<Vec<String>, sqlx::Error> {
+        let rows = sqlx::query("SELECT album_id FROM client_share_albums WHERE share_id = ?")
+            .bind(share_id)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.iter().map(|row| row.get("album_id")).collect())
+    }
+
+    /// Re-read the albums marked out of date into the search index
+    pub async fn refresh_search_index(&self) -> Result<(), sqlx::Error> {
+        let dirty: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM search_index_dirty")
+            .fetch_one(&self.pool)
+            .await?;
+        if dirty == 0 {
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "DELETE FROM library_search WHERE album_id IN (SELECT album_id FROM search_index_dirty)",
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(&format!(
+            r#"
+            INSERT INTO library_search (
+                title, artist, album, label, catalog, kind, entity_id, album_id, release_id
+            )
+            SELECT
+                a.title,
+                COALESCE(({}), ''),
+                '',
+                COALESCE((SELECT group_concat(DISTINCT label) FROM releases WHERE album_id = a.id), ''),
+                COALESCE((SELECT group_concat(DISTINCT catalog_number) FROM releases WHERE album_id = a.id), ''),
+                'album', a.id, a.id, NULL
+            FROM albums a
+            WHERE a.id IN (SELECT album_id FROM search_index_dirty)
+            "#,
+            SEARCH_ALBUM_ARTISTS
+        ))
+        .execute(&mut *tx)
+        .await?;
+        // Tracks without their own credits are by the album's artists
+        sqlx::query(&format!(
+            r#"
+            INSERT INTO library_search (
+                title, artist, album, label, catalog, kind, entity_id, album_id, release_id
+            )
+            SELECT
+                t.title,
+                COALESCE(
+                    (SELECT group_concat(ar.name, ', ') FROM track_artists ta
+                     JOIN artists ar ON ar.id = ta.artist_id WHERE ta.track_id = t.id),
+                    ({}),
+                    ''
+                ),
+                a.title, '', '', 'track', t.id, a.id, t.release_id
+            FROM tracks t
+            JOIN releases r ON r.id = t.release_id
+            JOIN albums a ON a.id = r.album_id
+            WHERE a.id IN (SELECT album_id FROM search_index_dirty)
+            "#,
+            SEARCH_ALBUM_ARTISTS
+        ))
+        .execute(&mut *tx)
+        .await?;
+        // Artist rows aren't tied to one album, so redo them all
+        sqlx::query("DELETE FROM library_search WHERE kind = 'artist'")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO library_search (
+                title, artist, album, label, catalog, kind, entity_id, album_id, release_id
+            )
+            SELECT ar.name, '', '', '', '', 'artist', ar.id, NULL, NULL
+            FROM artists ar
+            WHERE EXISTS (SELECT 1 FROM album_artists WHERE artist_id = ar.id)
+               OR EXISTS (SELECT 1 FROM track_artists WHERE artist_id = ar.id)
+            "#,
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM search_index_dirty")
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Ranked matches for an FTS5 query, at most `limit` of each kind.
+    /// Titles weigh most, then artists, then album titles, labels and
+    /// catalog numbers.
+    pub async fn search_library(
+        &self,
+        fts_query: &str,
+        limit: i64,
+    ) -> Result<DbSearchResults, sqlx::Error> {
+        let mut results = DbSearchResults::default();
+        for (kind, hits) in [
+            ("artist", &mut results.artists),
+            ("album", &mut results.albums),
+            ("track", &mut results.tracks),
+        ] {
+            let rows = sqlx::query(
+                r#"
+                SELECT entity_id, title, artist, album, album_id, release_id
+                FROM library_search
+                WHERE library_search MATCH ? AND kind = ?
+                ORDER BY bm25(library_search, 10.0, 5.0, 2.0, 1.0, 1.0)
+                LIMIT ?
+                "#,
+            )
+            .bind(fts_query)
+            .bind(kind)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+            *hits = rows
+                .iter()
+                .map(|row| DbSearchHit {
+                    id: row.get("entity_id"),
+                    title: row.get("title"),
+                    artist: row.get("artist"),
+                    album_title: row.get("album"),
+                    album_id: row.get("album_id"),
+                    release_id: row.get("release_id"),
+                })
+                .collect();
+        }
+        Ok(results)
+    }
+
+    pub async fn insert_verification_run(
+        &self,
+        run: &DbVerificationRun,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO verification_runs (id, scheduled, started_at, finished_at, files_checked)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&run.id)
+        .bind(run.scheduled)
+        .bind(run.started_at.to_rfc3339())
+        .bind(run.finished_at.map(|t| t.to_rfc3339()))
+        .bind(run.files_checked)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn finish_verification_run(
+        &self,
+        run_id: &str,
+        files_checked: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE verification_runs SET finished_at = ?, files_checked = ? WHERE id = ?")
+            .bind(Utc::now().to_rfc3339())
+            .bind(files_checked)
+            .bind(run_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn insert_verification_issue(
+        &self,
+        issue: &DbVerificationIssue,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO verification_issues (
+                id, run_id, kind, file_id, release_id, filename,
+                storage_profile_id, storage_profile_name, error, repaired
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&issue.id)
+        .bind(&issue.run_id)
+        .bind(issue.kind)
+        .bind(&issue.file_id)
+        .bind(&issue.release_id)
+        .bind(&issue.filename)
+        .bind(&issue.storage_profile_id)
+        .bind(&issue.storage_profile_name)
+        .bind(&issue.error)
+        .bind(issue.repaired)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_verification_issue(
+        &self,
+        id: &str,
+    ) -> Result<Option<DbVerificationIssue>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM verification_issues WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.as_ref().map(Self::row_to_verification_issue))
+    }
+
+    pub async fn mark_verification_issue_repaired(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE verification_issues SET repaired = TRUE WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Verification runs, newest first
+    pub async fn get_verification_runs(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<DbVerificationRun>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM verification_runs ORDER BY started_at DESC LIMIT ?")
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.iter().map(Self::row_to_verification_run).collect())
+    }
+
+    pub async fn get_verification_issues(
+        &self,
+        run_id: &str,
+    ) -> Result<Vec<DbVerificationIssue>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM verification_issues WHERE run_id = ? ORDER BY storage_profile_name, filename",
+        )
+        .bind(run_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().map(Self::row_to_verification_issue).collect())
+    }
+
+    /// When each file was first reported damaged, by file ID. Only files
+    /// with at least one issue are included.
+    pub async fn get_first_verification_failures(
+        &self,
+    ) -> Result<HashMap<String, DateTime<Utc>>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT i.file_id, MIN(r.started_at) AS first_seen
+            FROM verification_issues i
+            JOIN verification_runs r ON r.id = i.run_id
+            WHERE i.file_id IS NOT NULL
+            GROUP BY i.file_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let first_seen = DateTime::parse_from_rfc3339(&row.get::<String, _>("first_seen"))
+                    .unwrap()
+                    .with_timezone(&Utc);
+                (row.get("file_id"), first_seen)
+            })
+            .collect())
+    }
+
+    /// The unfinished multipart upload to `key`, with the parts sent so far
+    pub async fn get_cloud_upload(&self, key: &str) -> Result<Option<DbCloudUpload>, sqlx::Error> {
+        let Some(row) = sqlx::query("SELECT * FROM cloud_uploads WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let parts = sqlx::query(
+            "SELECT * FROM cloud_upload_parts WHERE upload_key = ? ORDER BY part_number",
+        )
+        .bind(key)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(Some(DbCloudUpload {
+            key: row.get("key"),
+            upload_id: row.get("upload_id"),
+            part_size: row.get("part_size"),
+            payload_sha256: row.get("payload_sha256"),
+            source_sha256: row.get("source_sha256"),
+            encryption_nonce: row.get("encryption_nonce"),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                .unwrap()
+                .with_timezone(&Utc),
+            parts: parts
+                .iter()
+                .map(|part| DbCloudUploadPart {
+                    part_number: part.get("part_number"),
+                    etag: part.get("etag"),
+                    checksum_sha256: part.get("checksum_sha256"),
+                })
+                .collect(),
+        }))
+    }
+
+    /// Start tracking a multipart upload, replacing any earlier one to the same key
+    pub async fn insert_cloud_upload(&self, upload: &DbCloudUpload) -> Result<(), sqlx::Error> {
+        self.delete_cloud_upload(&upload.key).await?;
+        sqlx::query(
+            r#"
+            INSERT INTO cloud_uploads (
+                key, upload_id, part_size, payload_sha256, source_sha256,
+                encryption_nonce, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&upload.key)
+        .bind(&upload.upload_id)
+        .bind(upload.part_size)
+        .bind(&upload.payload_sha256)
+        .bind(&upload.source_sha256)
+        .bind(&upload.encryption_nonce)
+        .bind(upload.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn insert_cloud_upload_part(
+        &self,
+        key: &str,
+        part: &DbCloudUploadPart,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO cloud_upload_parts (upload_key, part_number, etag, checksum_sha256)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(key)
+        .bind(part.part_number)
+        .bind(&part.etag)
+        .bind(&part.checksum_sha256)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Forget a multipart upload once it completed or was abandoned
+    pub async fn delete_cloud_upload(&self, key: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM cloud_upload_parts WHERE upload_key = ?")
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("DELETE FROM cloud_uploads WHERE key = ?")
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// IDs of the albums kept offline
+    pub async fn get_offline_album_ids(&self) -> Result<Vec<String>, sqlx::Error> {
+        let rows = sqlx::query("SELECT album_id FROM offline_albums ORDER BY pinned_at")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.iter().map(|row| row.get("album_id")).collect())
+    }
+
+    pub async fn insert_offline_album(&self, album_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT OR IGNORE INTO offline_albums (album_id, pinned_at) VALUES (?, ?)")
+            .bind(album_id)
+            .bind(Utc::now().to_rfc3339())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_offline_album(&self, album_id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM offline_albums WHERE album_id = ?")
+            .bind(album_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Append a change to the changelog. Returns its sequence number.
+    pub async fn insert_change(&self, change: &DbChange) -> Result<i64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO changelog (device_id, kind, entity_id, before_json, after_json, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&change.device_id)
+        .bind(change.kind.as_str())
+        .bind(&change.entity_id)
+        .bind(change.before.as_ref().map(|v| v.to_string()))
+        .bind(change.after.as_ref().map(|v| v.to_string()))
+        .bind(change.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Most recent changes first, optionally only those to one entity
+    pub async fn get_changes(
+        &self,
+        entity_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<DbChange>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM changelog
+            WHERE ?1 IS NULL OR entity_id = ?1
+            ORDER BY seq DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(entity_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.iter().filter_map(Self::row_to_change).collect())
+    }
+
+    /// Drop changes made before `cutoff`, keeping the latest change to each
+    /// entity so every album still shows how it last changed. Returns how
+    /// many entries were removed.
+    pub async fn compact_changelog(&self, cutoff: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM changelog
+            WHERE created_at < ?
+            AND seq NOT IN (SELECT MAX(seq) FROM changelog GROUP BY entity_id)
+            "#,
+        )
+        .bind(cutoff.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Rebuild album play counts and last-played times from the listening
+    /// history, which drifts from them when tracks are deleted. Albums with no
+    /// history rows are left alone: their plays predate per-track history.
+    /// Returns how many albums were updated.
+    pub async fn recompute_album_play_stats(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE albums
+            SET play_count = stats.play_count, last_played_at = stats.last_played_at
+            FROM (
+                SELECT r.album_id, COUNT(*) AS play_count, MAX(p.played_at) AS last_played_at
+                FROM track_plays p
+                JOIN tracks t ON t.id = p.track_id
+                JOIN releases r ON r.id = t.release_id
+                GROUP BY r.album_id
+            ) AS stats
+            WHERE albums.id = stats.album_id
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Rewrite the database file to reclaim space left by deleted rows, then
+    /// refresh the query planner's statistics
+    pub async fn compact(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("VACUUM").execute(&self.pool).await?;
+        sqlx::query("ANALYZE").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Run a trivial query, to check the database still answers
+    pub async fn ping(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Write a consistent copy of the database to `path`, which must not
+    /// exist yet. Safe to run while the library is in use.
+    pub async fn copy_to(&self, path: &std::path::Path) -> Result<(), sqlx::Error> {
+        sqlx::query("VACUUM INTO ?")
+            .bind(path.to_string_lossy().into_owned())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Problems SQLite finds in the database file at `database_path`, opened
+    /// read-only. Empty when the file is intact.
+    pub async fn check_file(database_path: &str) -> Result<Vec<String>, sqlx::Error> {
+        let database_url = format!("sqlite://{}?mode=ro", database_path);
+        let pool = SqlitePool::connect(&database_url).await?;
+        let rows = sqlx::query("PRAGMA quick_check").fetch_all(&pool).await;
+        pool.close().await;
+        Ok(rows?
+            .iter()
+            .map(|row| row.get::<String, _>(0))
+            .filter(|message| message != "ok")
+            .collect())
+    }
+
+    /// None for entries written by a newer version with kinds this one
+    /// doesn't know
+    fn row_to_change(row: &sqlx::sqlite::SqliteRow) -> Option<DbChange> {
+        let kind = ChangeKind::from_db_str(&row.get::<String, _>("kind"))?;
+        let parse_json = |column: &str| {
+            row.get::<Option<String>, _>(column)
+                .and_then(|json| serde_json::from_str(&json).ok())
+        };
+        Some(DbChange {
+            seq: row.get("seq"),
+            device_id: row.get("device_id"),
+            kind,
+            entity_id: row.get("entity_id"),
+            before: parse_json("before_json"),
+            after: parse_json("after_json"),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                .unwrap()
+                .with_timezone(&Utc),
+        })
+    }
+
+    fn row_to_verification_run(row: &sqlx::sqlite::SqliteRow) -> DbVerificationRun {
+        DbVerificationRun {
+            id: row.get("id"),
+            scheduled: row.get("scheduled"),
+            started_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("started_at"))
+                .unwrap()
+                .with_timezone(&Utc),
+            finished_at: row
+                .get::<Option<String>, _>("finished_at")
+                .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+                .map(|t| t.with_timezone(&Utc)),
+            files_checked: row.get("files_checked"),
+        }
+    }
+
+    fn row_to_verification_issue(row: &sqlx::sqlite::SqliteRow) -> DbVerificationIssue {
+        DbVerificationIssue {
+            id: row.get("id"),
+            run_id: row.get("run_id"),
+            kind: row.get("kind"),
+            file_id: row.get("file_id"),
+            release_id: row.get("release_id"),
+            filename: row.get("filename"),
+            storage_profile_id: row.get("storage_profile_id"),
+            storage_profile_name: row.get("storage_profile_name"),
+            error: row.get("error"),
+            repaired: row.get("repaired"),
+        }
+    }
+
+    fn row_to_release_template(row: &sqlx::sqlite::SqliteRow) -> DbReleaseTemplate {
+        DbReleaseTemplate {
+            id: row.get("id"),
+            artist: row.get("artist"),
+            title_pattern: row.get("title_pattern"),
+            next_number: row.get("next_number"),
+            number_width: row.get("number_width"),
+            cover_url: row.get("cover_url"),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    fn row_to_track_rip(row: &sqlx::sqlite::SqliteRow) -> DbTrackRip {
+        DbTrackRip {
+            track_id: row.get("track_id"),
+            copy_crc: row.get("copy_crc"),
+            read_errors: row.get("read_errors"),
+            c2_errors: row.get("c2_errors"),
+            suspicious_sectors: row.get("suspicious_sectors"),
+            accuraterip_status: row.get("accuraterip_status"),
+            accuraterip_confidence: row.get("accuraterip_confidence"),
+            ripped_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("ripped_at"))
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    fn row_to_track_bookmark(row: &sqlx::sqlite::SqliteRow) -> DbTrackBookmark {
+        DbTrackBookmark {
+            id: row.get("id"),
+            track_id: row.get("track_id"),
+            name: row.get("name"),
+            position_ms: row.get("position_ms"),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    fn row_to_library_track(row: &sqlx::sqlite::SqliteRow) -> DbLibraryTrack {
+        DbLibraryTrack {
+            track: DbTrack {
+                id: row.get("id"),
+                release_id: row.get("release_id"),
+                title: row.get("title"),
+                disc_number: row.get("disc_number"),
+                track_number: row.get("track_number"),
+                duration_ms: row.get("duration_ms"),
+                discogs_position: row.get("discogs_position"),
+                import_status: row.get("import_status"),
+                created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                    .unwrap()
+                    .with_timezone(&Utc),
+            },
+            album_id: row.get("album_id"),
+            album_title: row.get("album_title"),
+            album_year: row.get("album_year"),
+            artist_name: row.get("artist_name"),
+        }
+    }
+
+    fn row_to_scrobble(row: &sqlx::sqlite::SqliteRow) -> DbScrobble {
+        DbScrobble {
+            id: row.get("id"),
+            target: row.get("target"),
+            track_id: row.get("track_id"),
+            artist: row.get("artist"),
+            title: row.get("title"),
+            album: row.get("album"),
+            album_artist: row.get("album_artist"),
+            track_number: row.get("track_number"),
+            duration_ms: row.get("duration_ms"),
+            played_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("played_at"))
+                .unwrap()
+                .with_timezone(&Utc),
+            attempts: row.get("attempts"),
+        }
+    }
+
+    fn row_to_discid_lookup(row: &sqlx::sqlite::SqliteRow) -> DbDiscIdLookup {
+        DbDiscIdLookup {
+            release_id: row.get("release_id"),
+            mb_discid: row.get("mb_discid"),
+            attempts: row.get("attempts"),
+            last_error: row.get("last_error"),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    fn row_to_client_share(row: &sqlx::sqlite::SqliteRow) -> DbClientShare {
+        DbClientShare {
+            id: row.get("id"),
+            name: row.get("name"),
+            token: row.get("token"),
+            created_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("created_at"))
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    fn row_to_import(&self, row: &sqlx::sqlite::SqliteRow) -> DbImport {
+        let status_str: String = row.get("status");
+        let status = match status_str.as_str() {
+            "preparing" => ImportOperationStatus::Preparing,
+            "queued" => ImportOperationStatus::Queued,
+            "importing" => ImportOperationStatus::Importing,
+            "paused" => ImportOperationStatus::Paused,
+            "complete" => ImportOperationStatus::Complete,
+            "failed" => ImportOperationStatus::Failed,
+            "cancelled" => ImportOperationStatus::Cancelled,
+            _ => ImportOperationStatus::Preparing,
+        };
+        let kind_str: String = row.get("kind");
+        let kind = match kind_str.as_str() {
+            "torrent" => ImportKind::Torrent,
+            "cd" => ImportKind::Cd,
+            _ => ImportKind::Folder,
+        };
+        let excluded_files: String = row.get("excluded_files");
         DbImport {
             id: row.get("id"),
             status,
@@ -2014,6 +4355,10 @@ impl Database {
             album_title: row.get("album_title"),
             artist_name: row.get("artist_name"),
             folder_path: row.get("folder_path"),
+            kind,
+            storage_profile_id: row.get("storage_profile_id"),
+            selected_cover_filename: row.get("selected_cover_filename"),
+            excluded_files: serde_json::from_str(&excluded_files).unwrap_or_default(),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
             error_message: row.get("error_message"),
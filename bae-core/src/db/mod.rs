@@ -1,4 +1,5 @@
 mod client;
 mod models;
+pub(crate) use client::SEARCH_HISTORY_LIMIT;
 pub use client::Database;
 pub use models::*;
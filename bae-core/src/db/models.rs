@@ -67,6 +67,9 @@ pub struct DbAlbumArtist {
     pub artist_id: String,
     /// Order of this artist in multi-artist albums (0-indexed)
     pub position: i32,
+    /// The credit as the metadata source gave it, when normalization changed
+    /// it (e.g. "A ft. B" for an artist now credited as "A feat. B")
+    pub credited_as: Option<String>,
 }
 /// Links artists to tracks (many-to-many)
 ///
@@ -132,6 +135,16 @@ pub struct DbAlbum {
     pub is_compilation: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Number of completed track plays across all releases
+    pub play_count: i64,
+    /// When a track from this album last finished playing
+    pub last_played_at: Option<DateTime<Utc>>,
+    /// Most common cover color as `#rrggbb`, set after import
+    pub dominant_color: Option<String>,
+    /// Most saturated cover color distinct from the dominant one, as `#rrggbb`
+    pub accent_color: Option<String>,
+    /// Edition that plays when the album is played without picking one
+    pub preferred_release_id: Option<String>,
 }
 /// Release metadata - represents a specific version/pressing of an album
 ///
@@ -226,6 +239,15 @@ pub struct DbFile {
     /// Only set when file is encrypted with chunked encryption.
     /// Stored at import time, used during seek to avoid fetching nonce from cloud.
     pub encryption_nonce: Option<Vec<u8>>,
+    /// Stored bytes are zstd-compressed (under any encryption) and must be
+    /// decompressed after reading
+    pub compressed: bool,
+    /// The cloud provider confirmed the uploaded object's SHA-256 matches the
+    /// bytes we sent. Always false for local storage.
+    pub checksum_verified: bool,
+    /// SHA-256 of the original bytes, taken when bae stored the file. None
+    /// for files bae doesn't manage and ones stored before it was recorded.
+    pub sha256: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 /// Audio format metadata for a track
@@ -280,6 +302,19 @@ pub struct DbAudioFormat {
     pub created_at: DateTime<Utc>,
 }
 impl DbArtist {
+    /// Create an artist known only by name
+    pub fn new(name: &str) -> Self {
+        let now = Utc::now();
+        DbArtist {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            sort_name: Some(name.to_string()),
+            discogs_artist_id: None,
+            bandcamp_artist_id: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
     /// Create an artist from Discogs artist data
     pub fn from_discogs_artist(discogs_artist_id: &str, name: &str) -> Self {
         let now = Utc::now();
@@ -301,6 +336,7 @@ impl DbAlbumArtist {
             album_id: album_id.to_string(),
             artist_id: artist_id.to_string(),
             position,
+            credited_as: None,
         }
     }
 }
@@ -316,6 +352,14 @@ impl DbTrackArtist {
     }
 }
 impl DbAlbum {
+    /// The preferred edition if it's among `releases`, else the first one
+    pub fn default_release<'a>(&self, releases: &'a [DbRelease]) -> Option<&'a DbRelease> {
+        self.preferred_release_id
+            .as_ref()
+            .and_then(|id| releases.iter().find(|r| &r.id == id))
+            .or_else(|| releases.first())
+    }
+
     #[cfg(test)]
     pub fn new_test(title: &str) -> Self {
         let now = chrono::Utc::now();
@@ -331,6 +375,11 @@ impl DbAlbum {
             is_compilation: false,
             created_at: now,
             updated_at: now,
+            play_count: 0,
+            last_played_at: None,
+            dominant_color: None,
+            accent_color: None,
+            preferred_release_id: None,
         }
     }
     /// Create a logical album from a Discogs release
@@ -361,6 +410,11 @@ impl DbAlbum {
             is_compilation: false,
             created_at: now,
             updated_at: now,
+            play_count: 0,
+            last_played_at: None,
+            dominant_color: None,
+            accent_color: None,
+            preferred_release_id: None,
         }
     }
     /// cover_art_url is for immediate display before import completes.
@@ -391,6 +445,11 @@ impl DbAlbum {
             is_compilation: false,
             created_at: now,
             updated_at: now,
+            play_count: 0,
+            last_played_at: None,
+            dominant_color: None,
+            accent_color: None,
+            preferred_release_id: None,
         }
     }
 }
@@ -512,6 +571,9 @@ impl DbFile {
             format: format.to_string(),
             source_path: None,
             encryption_nonce: None,
+            compressed: false,
+            checksum_verified: false,
+            sha256: None,
             created_at: Utc::now(),
         }
     }
@@ -633,6 +695,34 @@ impl DbAudioFormat {
             created_at: Utc::now(),
         }
     }
+
+    pub fn quality(&self) -> AudioQuality {
+        AudioQuality::classify(&self.format, self.sample_rate, self.bits_per_sample)
+    }
+}
+/// Technical quality tier of stored audio, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioQuality {
+    Lossy,
+    /// Lossless at CD resolution or below (16-bit, up to 48kHz)
+    Lossless,
+    /// Lossless above CD resolution
+    HiRes,
+}
+impl AudioQuality {
+    const LOSSLESS_FORMATS: [&'static str; 6] = ["flac", "wav", "aiff", "alac", "wv", "ape"];
+
+    pub fn classify(format: &str, sample_rate: i64, bits_per_sample: i64) -> Self {
+        let format = format.to_lowercase();
+        if !Self::LOSSLESS_FORMATS.contains(&format.as_str()) {
+            AudioQuality::Lossy
+        } else if bits_per_sample > 16 || sample_rate > 48000 {
+            AudioQuality::HiRes
+        } else {
+            AudioQuality::Lossless
+        }
+    }
 }
 /// Torrent import metadata for a release
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -646,6 +736,19 @@ pub struct DbTorrent {
     pub piece_length: i32,
     pub num_pieces: i32,
     pub is_seeding: bool,
+    /// Directory the original download is kept in and seeded from
+    /// (None = not kept)
+    pub seed_path: Option<String>,
+    /// Bytes uploaded to peers, across every seeding session
+    pub uploaded_bytes: i64,
+    /// Bytes downloaded from peers when the torrent was imported
+    pub downloaded_bytes: i64,
+    /// Time spent seeding, across every seeding session
+    pub seeding_secs: i64,
+    /// Stop seeding once the upload ratio reaches this
+    pub seed_ratio_goal: Option<f64>,
+    /// Stop seeding after this long
+    pub seed_time_goal_secs: Option<i64>,
     pub created_at: DateTime<Utc>,
 }
 /// Maps torrent pieces to bae chunks
@@ -678,6 +781,12 @@ impl DbTorrent {
             piece_length,
             num_pieces,
             is_seeding: false,
+            seed_path: None,
+            uploaded_bytes: 0,
+            downloaded_bytes: 0,
+            seeding_secs: 0,
+            seed_ratio_goal: None,
+            seed_time_goal_secs: None,
             created_at: Utc::now(),
         }
     }
@@ -704,31 +813,69 @@ impl DbTorrentPieceMapping {
     }
 }
 const IMPORT_OP_STATUS_PREPARING: &str = "preparing";
+const IMPORT_OP_STATUS_QUEUED: &str = "queued";
 const IMPORT_OP_STATUS_IMPORTING: &str = "importing";
+const IMPORT_OP_STATUS_PAUSED: &str = "paused";
 const IMPORT_OP_STATUS_COMPLETE: &str = "complete";
 const IMPORT_OP_STATUS_FAILED: &str = "failed";
+const IMPORT_OP_STATUS_CANCELLED: &str = "cancelled";
 /// Status of an import operation (distinct from release/track ImportStatus)
 ///
 /// Tracks the lifecycle of an import from button click through completion:
 /// - Preparing: Phase 0 work in ImportHandle (parsing, validation, DB setup)
+/// - Queued: Waiting in the import queue for a free slot
 /// - Importing: Phase 1 work in ImportService (file processing, encryption, upload)
+/// - Paused: Held in the queue until resumed
 /// - Complete: Successfully finished
 /// - Failed: Error occurred
+/// - Cancelled: Stopped by the user; its release was removed
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
 #[sqlx(type_name = "TEXT", rename_all = "lowercase")]
 pub enum ImportOperationStatus {
     Preparing,
+    Queued,
     Importing,
+    Paused,
     Complete,
     Failed,
+    Cancelled,
 }
 impl ImportOperationStatus {
     pub fn as_str(&self) -> &'static str {
         match self {
             ImportOperationStatus::Preparing => IMPORT_OP_STATUS_PREPARING,
+            ImportOperationStatus::Queued => IMPORT_OP_STATUS_QUEUED,
             ImportOperationStatus::Importing => IMPORT_OP_STATUS_IMPORTING,
+            ImportOperationStatus::Paused => IMPORT_OP_STATUS_PAUSED,
             ImportOperationStatus::Complete => IMPORT_OP_STATUS_COMPLETE,
             ImportOperationStatus::Failed => IMPORT_OP_STATUS_FAILED,
+            ImportOperationStatus::Cancelled => IMPORT_OP_STATUS_CANCELLED,
+        }
+    }
+    /// Whether the import has stopped for good
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            ImportOperationStatus::Complete
+                | ImportOperationStatus::Failed
+                | ImportOperationStatus::Cancelled
+        )
+    }
+}
+/// Where an import's files come from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum ImportKind {
+    Folder,
+    Torrent,
+    Cd,
+}
+impl ImportKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportKind::Folder => "folder",
+            ImportKind::Torrent => "torrent",
+            ImportKind::Cd => "cd",
         }
     }
 }
@@ -749,6 +896,14 @@ pub struct DbImport {
     pub artist_name: String,
     /// Source folder path
     pub folder_path: String,
+    pub kind: ImportKind,
+    /// Storage profile the files go to. None leaves them in place.
+    pub storage_profile_id: Option<String>,
+    /// User-selected cover image filename, relative to the folder
+    pub selected_cover_filename: Option<String>,
+    /// Files in the folder left out of the import, so a queued folder
+    /// import can be rebuilt after a restart
+    pub excluded_files: Vec<String>,
     pub created_at: i64,
     pub updated_at: i64,
     /// Error message if status is Failed
@@ -764,11 +919,471 @@ impl DbImport {
             album_title: album_title.to_string(),
             artist_name: artist_name.to_string(),
             folder_path: folder_path.to_string(),
+            kind: ImportKind::Folder,
+            storage_profile_id: None,
+            selected_cover_filename: None,
+            excluded_files: Vec::new(),
             created_at: now,
             updated_at: now,
             error_message: None,
         }
     }
+    pub fn with_kind(mut self, kind: ImportKind) -> Self {
+        self.kind = kind;
+        self
+    }
+    /// Keep the options the import was started with, to rebuild it later
+    pub fn with_options(
+        mut self,
+        storage_profile_id: Option<String>,
+        selected_cover_filename: Option<String>,
+        excluded_files: Vec<String>,
+    ) -> Self {
+        self.storage_profile_id = storage_profile_id;
+        self.selected_cover_filename = selected_cover_filename;
+        self.excluded_files = excluded_files;
+        self
+    }
+}
+/// A value the user searched for during manual import matching
+///
+/// Kept per source and field so the search panel can offer recent values
+/// (same label, same artist) across batch-import sessions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbSearchHistoryEntry {
+    /// Metadata source searched: "musicbrainz", "discogs" or "both"
+    pub source: String,
+    /// Search field: "artist", "album", "catalog_number" or "barcode"
+    pub field: String,
+    pub value: String,
+    /// Unix timestamp of the most recent use
+    pub used_at: i64,
+}
+/// A track in the flat all-tracks listing, with the album it belongs to
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbLibraryTrack {
+    pub track: DbTrack,
+    pub album_id: String,
+    pub album_title: String,
+    pub album_year: Option<i32>,
+    /// Track artists, falling back to the album artists, joined with ", "
+    pub artist_name: Option<String>,
+}
+/// One completed play of a track
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbTrackPlay {
+    pub id: String,
+    pub track: DbLibraryTrack,
+    pub played_at: DateTime<Utc>,
+}
+/// How often, and when last, a track was played
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbTrackPlayStats {
+    pub track: DbLibraryTrack,
+    pub play_count: i64,
+    pub last_played_at: DateTime<Utc>,
+}
+/// A named position inside a track, e.g. a cue point in a DJ mix
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbTrackBookmark {
+    pub id: String,
+    pub track_id: String,
+    pub name: String,
+    /// Offset from the start of the track
+    pub position_ms: i64,
+    pub created_at: DateTime<Utc>,
+}
+impl DbTrackBookmark {
+    pub fn new(track_id: &str, name: &str, position_ms: i64) -> Self {
+        DbTrackBookmark {
+            id: Uuid::new_v4().to_string(),
+            track_id: track_id.to_string(),
+            name: name.to_string(),
+            position_ms,
+            created_at: Utc::now(),
+        }
+    }
+}
+/// A series imported by hand again and again, like a monthly mixtape or a
+/// podcast, see [`crate::import::release_template`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbReleaseTemplate {
+    pub id: String,
+    pub artist: String,
+    /// Album title with `{n}` for the volume number, e.g. "Monthly Mix Vol. {n}"
+    pub title_pattern: String,
+    /// Volume number of the next release in the series
+    pub next_number: i64,
+    /// Numbers are zero-padded to this many digits, so "05" is followed by "06"
+    pub number_width: i64,
+    pub cover_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+/// How a ripped track compared to the AccurateRip database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+pub enum AccurateRipStatus {
+    /// Matches other people's rips
+    Accurate,
+    /// In the database, but no rip there matches
+    Mismatch,
+    /// The database has no rips of this track
+    NotFound,
+    /// The database couldn't be reached
+    Unchecked,
+}
+impl AccurateRipStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccurateRipStatus::Accurate => "accurate",
+            AccurateRipStatus::Mismatch => "mismatch",
+            AccurateRipStatus::NotFound => "not_found",
+            AccurateRipStatus::Unchecked => "unchecked",
+        }
+    }
+}
+/// How a track ripped from CD was read, one per ripped track
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbTrackRip {
+    pub track_id: String,
+    /// CRC32 of the track's PCM
+    pub copy_crc: i64,
+    /// Reads the drive failed outright
+    pub read_errors: i64,
+    /// Sectors the drive flagged with C2 errors
+    pub c2_errors: i64,
+    /// Sectors no two reads agreed on, whose audio may be wrong
+    pub suspicious_sectors: i64,
+    pub accuraterip_status: AccurateRipStatus,
+    /// Matching AccurateRip submissions, or for a mismatch, all of them
+    pub accuraterip_confidence: i64,
+    pub ripped_at: DateTime<Utc>,
+}
+/// A set of albums a Subsonic client may see, unlocked by its token
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbClientShare {
+    pub id: String,
+    pub name: String,
+    /// Secret the client sends as its password
+    pub token: String,
+    pub created_at: DateTime<Utc>,
+}
+impl DbClientShare {
+    pub fn new(name: &str) -> Self {
+        DbClientShare {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            token: Uuid::new_v4().simple().to_string(),
+            created_at: Utc::now(),
+        }
+    }
+}
+/// Service a scrobble is queued for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum ScrobbleTarget {
+    LastFm,
+    ListenBrainz,
+}
+impl ScrobbleTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScrobbleTarget::LastFm => "lastfm",
+            ScrobbleTarget::ListenBrainz => "listenbrainz",
+        }
+    }
+}
+/// A listen waiting to be submitted to one service
+///
+/// Holds a snapshot of the track's metadata, so the listen is still sent as
+/// it was heard if the track is edited or deleted before going out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbScrobble {
+    pub id: String,
+    pub target: ScrobbleTarget,
+    pub track_id: String,
+    pub artist: String,
+    pub title: String,
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    pub track_number: Option<i32>,
+    pub duration_ms: Option<i64>,
+    /// When the listen started
+    pub played_at: DateTime<Utc>,
+    /// Failed submissions so far
+    pub attempts: i32,
+}
+/// A ripped disc waiting to be identified
+///
+/// Queued when a CD is ripped with placeholder metadata because its DiscID
+/// couldn't be looked up, e.g. while MusicBrainz was unreachable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbDiscIdLookup {
+    pub release_id: String,
+    /// MusicBrainz DiscID read from the disc's TOC
+    pub mb_discid: String,
+    /// Failed lookups so far
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+/// A library search match
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbSearchHit {
+    /// Artist, album or track ID
+    pub id: String,
+    pub title: String,
+    /// Credited artists; empty for artist matches
+    pub artist: String,
+    /// Title of the track's album; empty for other matches
+    pub album_title: String,
+    /// Album to open; None for artist matches
+    pub album_id: Option<String>,
+    /// Release the track is on; None for other matches
+    pub release_id: Option<String>,
+}
+/// Library search matches grouped by kind, best first within each group
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DbSearchResults {
+    pub artists: Vec<DbSearchHit>,
+    pub albums: Vec<DbSearchHit>,
+    pub tracks: Vec<DbSearchHit>,
+}
+/// One pass of the library verification job over stored files
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbVerificationRun {
+    pub id: String,
+    /// Started by the schedule rather than by hand
+    pub scheduled: bool,
+    pub started_at: DateTime<Utc>,
+    /// None while running, or if the app quit mid-run
+    pub finished_at: Option<DateTime<Utc>>,
+    pub files_checked: i64,
+}
+impl DbVerificationRun {
+    pub fn new(scheduled: bool) -> Self {
+        DbVerificationRun {
+            id: Uuid::new_v4().to_string(),
+            scheduled,
+            started_at: Utc::now(),
+            finished_at: None,
+            files_checked: 0,
+        }
+    }
+}
+/// What a verification run found wrong with a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+pub enum VerificationIssueKind {
+    /// Not where its record says it's stored
+    Missing,
+    /// Stored, but doesn't read back as it was imported
+    Corrupt,
+    /// In a local profile's folder without a release referring to it
+    Orphaned,
+}
+impl VerificationIssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerificationIssueKind::Missing => "missing",
+            VerificationIssueKind::Corrupt => "corrupt",
+            VerificationIssueKind::Orphaned => "orphaned",
+        }
+    }
+}
+/// A file a verification run found missing, damaged or orphaned.
+///
+/// Filename and profile name are copied so the history stays readable after
+/// the release or profile is deleted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbVerificationIssue {
+    pub id: String,
+    pub run_id: String,
+    pub kind: VerificationIssueKind,
+    /// None for orphaned files, which have no record
+    pub file_id: Option<String>,
+    pub release_id: Option<String>,
+    /// Original filename, or the path on disk of an orphaned file
+    pub filename: String,
+    pub storage_profile_id: String,
+    pub storage_profile_name: String,
+    pub error: String,
+    /// Restored from the import's source, or deleted if orphaned
+    pub repaired: bool,
+}
+impl DbVerificationIssue {
+    pub fn new(
+        run_id: &str,
+        kind: VerificationIssueKind,
+        file: &DbFile,
+        storage_profile: &DbStorageProfile,
+        error: String,
+    ) -> Self {
+        DbVerificationIssue {
+            id: Uuid::new_v4().to_string(),
+            run_id: run_id.to_string(),
+            kind,
+            file_id: Some(file.id.clone()),
+            release_id: Some(file.release_id.clone()),
+            filename: file.original_filename.clone(),
+            storage_profile_id: storage_profile.id.clone(),
+            storage_profile_name: storage_profile.name.clone(),
+            error,
+            repaired: false,
+        }
+    }
+
+    /// A file found in a profile's folder that no release refers to
+    pub fn orphaned(run_id: &str, path: &str, storage_profile: &DbStorageProfile) -> Self {
+        DbVerificationIssue {
+            id: Uuid::new_v4().to_string(),
+            run_id: run_id.to_string(),
+            kind: VerificationIssueKind::Orphaned,
+            file_id: None,
+            release_id: None,
+            filename: path.to_string(),
+            storage_profile_id: storage_profile.id.clone(),
+            storage_profile_name: storage_profile.name.clone(),
+            error: "Not part of any release".to_string(),
+            repaired: false,
+        }
+    }
+}
+/// A multipart cloud upload that hasn't completed yet.
+///
+/// The plaintext hash and encryption nonce let a restarted import rebuild
+/// the same ciphertext, so parts already sent still fit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbCloudUpload {
+    /// Key the object is uploaded under, before partitioning
+    pub key: String,
+    pub upload_id: String,
+    pub part_size: i64,
+    /// Base64 SHA-256 of the bytes being uploaded
+    pub payload_sha256: String,
+    /// Hex SHA-256 of the file before compression and encryption
+    pub source_sha256: String,
+    /// Base nonce the payload was encrypted with, if it was
+    pub encryption_nonce: Option<Vec<u8>>,
+    pub created_at: DateTime<Utc>,
+    pub parts: Vec<DbCloudUploadPart>,
+}
+/// A part of a [`DbCloudUpload`] the provider has accepted
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbCloudUploadPart {
+    /// 1-based, as S3 numbers them
+    pub part_number: i64,
+    pub etag: String,
+    /// Base64 SHA-256 of the part
+    pub checksum_sha256: String,
+}
+
+/// What a changelog entry records happening
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    AlbumAdded,
+    AlbumDeleted,
+    ReleaseDeleted,
+    AlbumRenamed,
+    TrackRenamed,
+    ArtistRenamed,
+    AlbumArtistsChanged,
+    PreferredReleaseChanged,
+    CoverChanged,
+    /// Title or year edited after import
+    AlbumEdited,
+    /// Album's artists set by hand, e.g. to fix how a credit was split
+    AlbumArtistsEdited,
+    ReleaseEdited,
+    /// Title or position edited after import
+    TrackEdited,
+}
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::AlbumAdded => "album_added",
+            ChangeKind::AlbumDeleted => "album_deleted",
+            ChangeKind::ReleaseDeleted => "release_deleted",
+            ChangeKind::AlbumRenamed => "album_renamed",
+            ChangeKind::TrackRenamed => "track_renamed",
+            ChangeKind::ArtistRenamed => "artist_renamed",
+            ChangeKind::AlbumArtistsChanged => "album_artists_changed",
+            ChangeKind::PreferredReleaseChanged => "preferred_release_changed",
+            ChangeKind::CoverChanged => "cover_changed",
+            ChangeKind::AlbumEdited => "album_edited",
+            ChangeKind::AlbumArtistsEdited => "album_artists_edited",
+            ChangeKind::ReleaseEdited => "release_edited",
+            ChangeKind::TrackEdited => "track_edited",
+        }
+    }
+    /// Edits a user makes by hand, which undo can revert. Imports,
+    /// deletions and library-wide jobs can't be undone.
+    pub fn is_undoable(&self) -> bool {
+        matches!(
+            self,
+            ChangeKind::AlbumRenamed
+                | ChangeKind::TrackRenamed
+                | ChangeKind::PreferredReleaseChanged
+                | ChangeKind::AlbumEdited
+                | ChangeKind::AlbumArtistsEdited
+                | ChangeKind::ReleaseEdited
+                | ChangeKind::TrackEdited
+        )
+    }
+    pub fn from_db_str(s: &str) -> Option<Self> {
+        match s {
+            "album_added" => Some(ChangeKind::AlbumAdded),
+            "album_deleted" => Some(ChangeKind::AlbumDeleted),
+            "release_deleted" => Some(ChangeKind::ReleaseDeleted),
+            "album_renamed" => Some(ChangeKind::AlbumRenamed),
+            "track_renamed" => Some(ChangeKind::TrackRenamed),
+            "artist_renamed" => Some(ChangeKind::ArtistRenamed),
+            "album_artists_changed" => Some(ChangeKind::AlbumArtistsChanged),
+            "preferred_release_changed" => Some(ChangeKind::PreferredReleaseChanged),
+            "cover_changed" => Some(ChangeKind::CoverChanged),
+            "album_edited" => Some(ChangeKind::AlbumEdited),
+            "album_artists_edited" => Some(ChangeKind::AlbumArtistsEdited),
+            "release_edited" => Some(ChangeKind::ReleaseEdited),
+            "track_edited" => Some(ChangeKind::TrackEdited),
+            _ => None,
+        }
+    }
+}
+/// One library mutation in the changelog, in the order it was applied.
+///
+/// The changed values are copied in as JSON, so an entry still reads after
+/// the album, track or artist it names is gone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbChange {
+    /// Position in the log, assigned on insert; later changes sort higher
+    pub seq: i64,
+    /// Device that made the change
+    pub device_id: String,
+    pub kind: ChangeKind,
+    /// Album, release, track or artist the change applies to
+    pub entity_id: String,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+impl DbChange {
+    pub fn new(
+        device_id: &str,
+        kind: ChangeKind,
+        entity_id: &str,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    ) -> Self {
+        DbChange {
+            seq: 0,
+            device_id: device_id.to_string(),
+            kind,
+            entity_id: entity_id.to_string(),
+            before,
+            after,
+            created_at: Utc::now(),
+        }
+    }
 }
 /// Source of an image file
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
@@ -841,12 +1456,92 @@ pub enum StorageLocation {
     Local,
     /// Cloud storage (S3/MinIO, bae manages storage)
     Cloud,
+    /// Existing folder indexed in place. bae never copies into it or deletes
+    /// from it.
+    Reference,
 }
 impl StorageLocation {
     pub fn as_str(&self) -> &'static str {
         match self {
             StorageLocation::Local => "local",
             StorageLocation::Cloud => "cloud",
+            StorageLocation::Reference => "reference",
+        }
+    }
+    /// Whether files are read straight off a filesystem
+    pub fn is_on_disk(&self) -> bool {
+        matches!(self, StorageLocation::Local | StorageLocation::Reference)
+    }
+}
+/// How stored filenames are rewritten when they contain characters that
+/// aren't valid on common filesystems (`<>:"/\|?*` and control characters)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FilenamePolicy {
+    /// Remove illegal characters
+    Strip,
+    /// Replace illegal characters with `_`
+    Replace,
+    /// Replace illegal characters with legal lookalikes (`:` becomes `-`)
+    Transliterate,
+}
+impl FilenamePolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilenamePolicy::Strip => "strip",
+            FilenamePolicy::Replace => "replace",
+            FilenamePolicy::Transliterate => "transliterate",
+        }
+    }
+    pub fn from_db_str(s: &str) -> Self {
+        match s {
+            "strip" => FilenamePolicy::Strip,
+            "transliterate" => FilenamePolicy::Transliterate,
+            _ => FilenamePolicy::Replace,
+        }
+    }
+}
+/// Which root of a multi-root local profile new files go to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlacementPolicy {
+    /// The first root, in order, with room for the file
+    FillFirst,
+    /// Whichever root has the most free space
+    MostFreeSpace,
+}
+impl PlacementPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlacementPolicy::FillFirst => "fill_first",
+            PlacementPolicy::MostFreeSpace => "most_free_space",
+        }
+    }
+    pub fn from_db_str(s: &str) -> Self {
+        match s {
+            "most_free_space" => PlacementPolicy::MostFreeSpace,
+            _ => PlacementPolicy::FillFirst,
+        }
+    }
+}
+/// API used to talk to a cloud profile's bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CloudProvider {
+    /// S3 or an S3-compatible service
+    #[default]
+    S3,
+    /// Backblaze B2's native API
+    B2,
+}
+impl CloudProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CloudProvider::S3 => "s3",
+            CloudProvider::B2 => "b2",
+        }
+    }
+    pub fn from_db_str(s: &str) -> Self {
+        match s {
+            "b2" => CloudProvider::B2,
+            _ => CloudProvider::S3,
         }
     }
 }
@@ -858,24 +1553,40 @@ impl StorageLocation {
 pub struct DbStorageProfile {
     pub id: String,
     pub name: String,
-    /// Where to store: local filesystem or cloud
+    /// Where to store: local filesystem, cloud, or a referenced folder
     pub location: StorageLocation,
-    /// Path for local storage (ignored for cloud)
+    /// Path for local storage, or the referenced folder (ignored for cloud)
     pub location_path: String,
+    /// Further roots for local storage spanning several drives, after
+    /// `location_path` in fill order
+    pub extra_location_paths: Vec<String>,
+    /// How new files are spread across the local roots
+    pub placement_policy: PlacementPolicy,
     /// Whether to encrypt data
     pub encrypted: bool,
+    /// Whether to zstd-compress non-audio files (documents, artwork) before encrypting
+    pub compressed: bool,
+    /// How to rewrite filenames that are illegal on the target filesystem
+    pub filename_policy: FilenamePolicy,
     /// True if this is the default profile for new imports
     pub is_default: bool,
-    /// S3 bucket name
+    /// API used for the bucket (ignored for local)
+    pub cloud_provider: CloudProvider,
+    /// Bucket name
     pub cloud_bucket: Option<String>,
-    /// AWS region (e.g., "us-east-1")
+    /// AWS region (e.g., "us-east-1"). Unused for B2.
     pub cloud_region: Option<String>,
-    /// Custom endpoint URL for S3-compatible services (MinIO, etc.)
+    /// Custom endpoint URL for S3-compatible services (MinIO, etc.). Unused for B2.
     pub cloud_endpoint: Option<String>,
-    /// Access key ID
+    /// Access key ID (B2: application key ID)
     pub cloud_access_key: Option<String>,
-    /// Secret access key
+    /// Secret access key (B2: application key)
     pub cloud_secret_key: Option<String>,
+    /// Bucket transitions objects to archival tiers (Glacier, Deep Archive),
+    /// so files may need a restore before they can be streamed
+    pub cloud_archival: bool,
+    /// Soft limit on stored bytes. Imports past it need an explicit override.
+    pub quota_bytes: Option<u64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -888,17 +1599,31 @@ impl DbStorageProfile {
             name: name.to_string(),
             location: StorageLocation::Local,
             location_path: path.to_string(),
+            extra_location_paths: Vec::new(),
+            placement_policy: PlacementPolicy::FillFirst,
             encrypted,
+            compressed: false,
+            filename_policy: FilenamePolicy::Replace,
             is_default: false,
+            cloud_provider: CloudProvider::S3,
             cloud_bucket: None,
             cloud_region: None,
             cloud_endpoint: None,
             cloud_access_key: None,
             cloud_secret_key: None,
+            cloud_archival: false,
+            quota_bytes: None,
             created_at: now,
             updated_at: now,
         }
     }
+    /// Create a profile referencing the files under `path` where they are
+    pub fn new_reference(name: &str, path: &str) -> Self {
+        DbStorageProfile {
+            location: StorageLocation::Reference,
+            ..Self::new_local(name, path, false)
+        }
+    }
     /// Create a new cloud storage profile
     pub fn new_cloud(
         name: &str,
@@ -915,13 +1640,20 @@ impl DbStorageProfile {
             name: name.to_string(),
             location: StorageLocation::Cloud,
             location_path: String::new(),
+            extra_location_paths: Vec::new(),
+            placement_policy: PlacementPolicy::FillFirst,
             encrypted,
+            compressed: false,
+            filename_policy: FilenamePolicy::Replace,
             is_default: false,
+            cloud_provider: CloudProvider::S3,
             cloud_bucket: Some(bucket.to_string()),
             cloud_region: Some(region.to_string()),
             cloud_endpoint: endpoint.map(|s| s.to_string()),
             cloud_access_key: Some(access_key.to_string()),
             cloud_secret_key: Some(secret_key.to_string()),
+            cloud_archival: false,
+            quota_bytes: None,
             created_at: now,
             updated_at: now,
         }
@@ -930,11 +1662,50 @@ impl DbStorageProfile {
         self.is_default = is_default;
         self
     }
+    pub fn with_filename_policy(mut self, filename_policy: FilenamePolicy) -> Self {
+        self.filename_policy = filename_policy;
+        self
+    }
+    pub fn with_compressed(mut self, compressed: bool) -> Self {
+        self.compressed = compressed;
+        self
+    }
+    pub fn with_cloud_provider(mut self, cloud_provider: CloudProvider) -> Self {
+        self.cloud_provider = cloud_provider;
+        self
+    }
+    pub fn with_cloud_archival(mut self, cloud_archival: bool) -> Self {
+        self.cloud_archival = cloud_archival;
+        self
+    }
+
+    pub fn with_quota_bytes(mut self, quota_bytes: Option<u64>) -> Self {
+        self.quota_bytes = quota_bytes;
+        self
+    }
+
+    pub fn with_extra_location_paths(
+        mut self,
+        extra_location_paths: Vec<String>,
+        placement_policy: PlacementPolicy,
+    ) -> Self {
+        self.extra_location_paths = extra_location_paths;
+        self.placement_policy = placement_policy;
+        self
+    }
+
+    /// Every root of a local profile, `location_path` first
+    pub fn local_roots(&self) -> Vec<&str> {
+        std::iter::once(self.location_path.as_str())
+            .chain(self.extra_location_paths.iter().map(String::as_str))
+            .filter(|root| !root.is_empty())
+            .collect()
+    }
 
     /// Convert cloud storage fields to S3Config for creating a client.
     /// Returns None if this is not a cloud profile or credentials are missing.
     pub fn to_s3_config(&self) -> Option<crate::cloud_storage::S3Config> {
-        if self.location != StorageLocation::Cloud {
+        if self.location != StorageLocation::Cloud || self.cloud_provider != CloudProvider::S3 {
             return None;
         }
         Some(crate::cloud_storage::S3Config {
@@ -945,6 +1716,19 @@ impl DbStorageProfile {
             endpoint_url: self.cloud_endpoint.clone(),
         })
     }
+
+    /// Convert cloud storage fields to B2Config for creating a client.
+    /// Returns None if this is not a B2 profile or credentials are missing.
+    pub fn to_b2_config(&self) -> Option<crate::cloud_storage::B2Config> {
+        if self.location != StorageLocation::Cloud || self.cloud_provider != CloudProvider::B2 {
+            return None;
+        }
+        Some(crate::cloud_storage::B2Config {
+            bucket_name: self.cloud_bucket.clone()?,
+            key_id: self.cloud_access_key.clone()?,
+            application_key: self.cloud_secret_key.clone()?,
+        })
+    }
 }
 /// Links a release to its storage profile
 ///
@@ -20,6 +20,31 @@ pub enum DiscogsError {
 #[derive(Debug, Deserialize)]
 struct SearchResponse {
     results: Vec<DiscogsSearchResult>,
+    pagination: Option<Pagination>,
+}
+#[derive(Debug, Deserialize)]
+struct Pagination {
+    pages: u32,
+    items: u32,
+}
+/// Number of results requested per search page
+pub const SEARCH_PAGE_SIZE: u32 = 25;
+/// One page of Discogs search results
+#[derive(Debug, Clone)]
+pub struct DiscogsSearchPage {
+    pub results: Vec<DiscogsSearchResult>,
+    /// Total number of results matching the query (across all pages)
+    pub total: u32,
+    /// Zero-based page index this page was fetched for
+    pub page: u32,
+    /// Total number of pages available
+    pub pages: u32,
+}
+impl DiscogsSearchPage {
+    /// Whether another page can be fetched after this one
+    pub fn has_more(&self) -> bool {
+        self.page + 1 < self.pages
+    }
 }
 /// Search parameters for flexible Discogs queries
 #[derive(Debug, Clone, Default)]
@@ -44,6 +69,8 @@ pub struct DiscogsSearchResult {
     pub format: Option<Vec<String>>,
     pub country: Option<String>,
     pub label: Option<Vec<String>>,
+    pub catno: Option<String>,
+    pub barcode: Option<Vec<String>>,
     pub cover_image: Option<String>,
     pub thumb: Option<String>,
     pub master_id: Option<u64>,
@@ -103,14 +130,23 @@ impl DiscogsClient {
         }
     }
     /// Flexible search using any combination of supported parameters
+    ///
+    /// `page` is zero-based; Discogs itself numbers pages from 1.
     pub async fn search_with_params(
         &self,
         params: &DiscogsSearchParams,
-    ) -> Result<Vec<DiscogsSearchResult>, DiscogsError> {
+        page: u32,
+    ) -> Result<DiscogsSearchPage, DiscogsError> {
         use tracing::{debug, info, warn};
         let url = format!("{}/database/search", self.base_url);
-        let mut query_params: Vec<(&str, &str)> =
-            vec![("type", "release"), ("token", &self.api_key)];
+        let per_page = SEARCH_PAGE_SIZE.to_string();
+        let api_page = (page + 1).to_string();
+        let mut query_params: Vec<(&str, &str)> = vec![
+            ("type", "release"),
+            ("token", &self.api_key),
+            ("per_page", &per_page),
+            ("page", &api_page),
+        ];
         if let Some(ref artist) = params.artist {
             query_params.push(("artist", artist));
         }
@@ -135,7 +171,10 @@ impl DiscogsClient {
         if let Some(ref country) = params.country {
             query_params.push(("country", country));
         }
-        info!("📡 Discogs API: GET {} with params: {:?}", url, params);
+        info!(
+            "📡 Discogs API: GET {} with params: {:?} (page {})",
+            url, params, page
+        );
         let response = self
             .client
             .get(&url)
@@ -160,13 +199,27 @@ impl DiscogsClient {
                     result.master_id
                 );
             }
+            let (total, pages) = search_response
+                .pagination
+                .as_ref()
+                .map(|p| (p.items, p.pages))
+                .unwrap_or((search_response.results.len() as u32, page + 1));
             let releases: Vec<_> = search_response
                 .results
                 .into_iter()
                 .filter(|r| r.result_type == "release")
                 .collect();
-            info!("  → {} release(s) after filtering", releases.len());
-            Ok(releases)
+            info!(
+                "  → {} release(s) after filtering, {} total",
+                releases.len(),
+                total
+            );
+            Ok(DiscogsSearchPage {
+                results: releases,
+                total,
+                page,
+                pages,
+            })
         } else if response.status() == 429 {
             warn!("✗ Discogs rate limit exceeded");
             Err(DiscogsError::RateLimit)
@@ -11,6 +11,13 @@ pub const ENCRYPTED_CHUNK_SIZE: usize = CHUNK_SIZE + sodium_ffi::ABYTES;
 
 static SODIUM_INIT: Once = Once::new();
 
+/// Size of [`EncryptionService::encrypt_chunked`] output for a plaintext of
+/// this length: the nonce header plus one auth tag per chunk
+pub fn encrypted_size(plaintext_len: u64) -> u64 {
+    let chunks = plaintext_len.div_ceil(CHUNK_SIZE as u64).max(1);
+    sodium_ffi::NPUBBYTES as u64 + plaintext_len + chunks * sodium_ffi::ABYTES as u64
+}
+
 /// Ensure libsodium is initialized. Safe to call multiple times.
 pub fn ensure_sodium_init() {
     SODIUM_INIT.call_once(|| {
@@ -29,6 +36,14 @@ pub fn generate_random_key() -> [u8; 32] {
     key
 }
 
+/// Generate a random base nonce for [`EncryptionService::encrypt_chunked_with_nonce`].
+pub fn random_base_nonce() -> [u8; sodium_ffi::NPUBBYTES] {
+    ensure_sodium_init();
+    let mut nonce = [0u8; sodium_ffi::NPUBBYTES];
+    unsafe { sodium_ffi::randombytes_buf(nonce.as_mut_ptr(), sodium_ffi::NPUBBYTES) };
+    nonce
+}
+
 #[derive(Error, Debug)]
 pub enum EncryptionError {
     #[error("Encryption failed: {0}")]
@@ -98,65 +113,85 @@ impl EncryptionService {
 
     /// Encrypt data using chunked XChaCha20-Poly1305 format.
     /// Returns: [base_nonce: 24 bytes][chunk_0][chunk_1]...
-    /// Each chunk is independently encrypted, enabling random-access decryption.
+    /// Each chunk is independently encrypted, enabling random-access decryption,
+    /// and large inputs are encrypted on all cores.
     pub fn encrypt_chunked(&self, plaintext: &[u8]) -> Vec<u8> {
-        ensure_sodium_init();
+        self.seal_chunks(plaintext, &random_base_nonce(), true)
+    }
 
-        // Generate random base nonce
-        let mut base_nonce = [0u8; sodium_ffi::NPUBBYTES];
-        unsafe {
-            sodium_ffi::randombytes_buf(base_nonce.as_mut_ptr(), sodium_ffi::NPUBBYTES);
-        }
+    /// [`Self::encrypt_chunked`] on the calling thread only, for when imports
+    /// must not load every core
+    pub fn encrypt_chunked_single_core(&self, plaintext: &[u8]) -> Vec<u8> {
+        self.seal_chunks(plaintext, &random_base_nonce(), false)
+    }
+
+    /// [`Self::encrypt_chunked`] with a caller-chosen base nonce, so a resumed
+    /// upload reproduces the ciphertext it already sent part of.
+    ///
+    /// The nonce must only ever be reused for the same plaintext.
+    pub fn encrypt_chunked_with_nonce(
+        &self,
+        plaintext: &[u8],
+        base_nonce: &[u8; sodium_ffi::NPUBBYTES],
+        single_core: bool,
+    ) -> Vec<u8> {
+        self.seal_chunks(plaintext, base_nonce, !single_core)
+    }
+
+    fn seal_chunks(
+        &self,
+        plaintext: &[u8],
+        base_nonce: &[u8; sodium_ffi::NPUBBYTES],
+        parallel: bool,
+    ) -> Vec<u8> {
+        ensure_sodium_init();
 
-        let mut output = base_nonce.to_vec();
+        let mut output = vec![0u8; encrypted_size(plaintext.len() as u64) as usize];
+        output[..sodium_ffi::NPUBBYTES].copy_from_slice(base_nonce);
+        let body = &mut output[sodium_ffi::NPUBBYTES..];
 
         // Handle empty plaintext - still produce one chunk with just auth tag
         if plaintext.is_empty() {
-            let nonce = chunk_nonce(&base_nonce, 0);
-            let mut ciphertext = vec![0u8; sodium_ffi::ABYTES];
-            let mut ciphertext_len: u64 = 0;
-
-            unsafe {
-                sodium_ffi::crypto_aead_xchacha20poly1305_ietf_encrypt(
-                    ciphertext.as_mut_ptr(),
-                    &mut ciphertext_len,
-                    ptr::null(),
-                    0,
-                    ptr::null(),
-                    0,
-                    ptr::null(),
-                    nonce.as_ptr(),
-                    self.key.as_ptr(),
-                );
-            }
-
-            output.extend(&ciphertext[..ciphertext_len as usize]);
+            self.encrypt_chunk_into(base_nonce, 0, &[], body);
             return output;
         }
 
-        for (i, chunk) in plaintext.chunks(CHUNK_SIZE).enumerate() {
-            let nonce = chunk_nonce(&base_nonce, i as u64);
-            let mut ciphertext = vec![0u8; chunk.len() + sodium_ffi::ABYTES];
-            let mut ciphertext_len: u64 = 0;
-
-            unsafe {
-                sodium_ffi::crypto_aead_xchacha20poly1305_ietf_encrypt(
-                    ciphertext.as_mut_ptr(),
-                    &mut ciphertext_len,
-                    chunk.as_ptr(),
-                    chunk.len() as u64,
-                    ptr::null(),
-                    0,
-                    ptr::null(),
-                    nonce.as_ptr(),
-                    self.key.as_ptr(),
-                );
-            }
+        for_each_chunk(body, plaintext, parallel, |i, chunk, out| {
+            self.encrypt_chunk_into(base_nonce, i, chunk, out)
+        });
+        output
+    }
 
-            output.extend(&ciphertext[..ciphertext_len as usize]);
-        }
+    /// Encrypt one chunk into `out`, which holds exactly its ciphertext and tag
+    fn encrypt_chunk_into(
+        &self,
+        base_nonce: &[u8; sodium_ffi::NPUBBYTES],
+        chunk_index: u64,
+        chunk: &[u8],
+        out: &mut [u8],
+    ) {
+        debug_assert_eq!(out.len(), chunk.len() + sodium_ffi::ABYTES);
+        let nonce = chunk_nonce(base_nonce, chunk_index);
+        let mut ciphertext_len: u64 = 0;
+        let message = if chunk.is_empty() {
+            ptr::null()
+        } else {
+            chunk.as_ptr()
+        };
 
-        output
+        unsafe {
+            sodium_ffi::crypto_aead_xchacha20poly1305_ietf_encrypt(
+                out.as_mut_ptr(),
+                &mut ciphertext_len,
+                message,
+                chunk.len() as u64,
+                ptr::null(),
+                0,
+                ptr::null(),
+                nonce.as_ptr(),
+                self.key.as_ptr(),
+            );
+        }
     }
 
     /// Decrypt a specific chunk from chunked encrypted data.
@@ -415,6 +450,55 @@ impl EncryptionService {
     }
 }
 
+/// Below this much plaintext, spreading chunks across threads costs more than
+/// it saves
+#[cfg(any(target_os = "macos", target_os = "linux", target_os = "windows"))]
+const PARALLEL_MIN_BYTES: usize = 1024 * 1024;
+
+/// Call `f(index, plaintext chunk, encrypted chunk)` for every chunk. Chunks
+/// are sealed independently, so with `parallel` large files are spread across
+/// all cores.
+#[cfg(any(target_os = "macos", target_os = "linux", target_os = "windows"))]
+fn for_each_chunk(
+    body: &mut [u8],
+    plaintext: &[u8],
+    parallel: bool,
+    f: impl Fn(u64, &[u8], &mut [u8]) + Sync,
+) {
+    use rayon::prelude::*;
+
+    if !parallel || plaintext.len() < PARALLEL_MIN_BYTES {
+        for (i, (out, chunk)) in body
+            .chunks_mut(ENCRYPTED_CHUNK_SIZE)
+            .zip(plaintext.chunks(CHUNK_SIZE))
+            .enumerate()
+        {
+            f(i as u64, chunk, out);
+        }
+        return;
+    }
+    body.par_chunks_mut(ENCRYPTED_CHUNK_SIZE)
+        .zip(plaintext.par_chunks(CHUNK_SIZE))
+        .enumerate()
+        .for_each(|(i, (out, chunk))| f(i as u64, chunk, out));
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+fn for_each_chunk(
+    body: &mut [u8],
+    plaintext: &[u8],
+    _parallel: bool,
+    f: impl Fn(u64, &[u8], &mut [u8]) + Sync,
+) {
+    for (i, (out, chunk)) in body
+        .chunks_mut(ENCRYPTED_CHUNK_SIZE)
+        .zip(plaintext.chunks(CHUNK_SIZE))
+        .enumerate()
+    {
+        f(i as u64, chunk, out);
+    }
+}
+
 /// Derive nonce for chunk i: base_nonce XOR i (little-endian)
 fn chunk_nonce(
     base_nonce: &[u8; sodium_ffi::NPUBBYTES],
@@ -511,6 +595,45 @@ mod tests {
         assert_eq!(decrypted, plaintext);
     }
 
+    #[test]
+    fn test_roundtrip_parallel_chunks() {
+        let service = create_test_service();
+        // Large enough to be encrypted across threads, with a partial last chunk
+        let plaintext: Vec<u8> = (0..CHUNK_SIZE * 40 + 123)
+            .map(|i| (i % 251) as u8)
+            .collect();
+
+        for ciphertext in [
+            service.encrypt_chunked(&plaintext),
+            service.encrypt_chunked_single_core(&plaintext),
+        ] {
+            assert_eq!(
+                ciphertext.len() as u64,
+                encrypted_size(plaintext.len() as u64)
+            );
+            // Chunks stay independently decryptable
+            let last = service.decrypt_chunk(&ciphertext, 40).unwrap();
+            assert_eq!(last, &plaintext[CHUNK_SIZE * 40..]);
+            assert_eq!(service.decrypt(&ciphertext).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn test_same_nonce_reproduces_ciphertext() {
+        let service = create_test_service();
+        let plaintext = vec![0x5Au8; CHUNK_SIZE * 2 + 17];
+        let nonce = random_base_nonce();
+
+        let parallel = service.encrypt_chunked_with_nonce(&plaintext, &nonce, false);
+        let single = service.encrypt_chunked_with_nonce(&plaintext, &nonce, true);
+        assert_eq!(parallel, single);
+        assert_eq!(&parallel[..sodium_ffi::NPUBBYTES], &nonce);
+        assert_eq!(service.decrypt(&parallel).unwrap(), plaintext);
+
+        let fresh = service.encrypt_chunked(&plaintext);
+        assert_ne!(fresh, parallel);
+    }
+
     #[test]
     fn test_random_access_chunk() {
         let service = create_test_service();
@@ -579,6 +702,15 @@ mod tests {
         assert_eq!(decrypted, plaintext);
     }
 
+    #[test]
+    fn test_encrypted_size_matches_output() {
+        let service = create_test_service();
+        for len in [0, 1, CHUNK_SIZE, CHUNK_SIZE + 1, CHUNK_SIZE * 3] {
+            let ciphertext = service.encrypt(&vec![7u8; len]);
+            assert_eq!(encrypted_size(len as u64), ciphertext.len() as u64);
+        }
+    }
+
     #[test]
     fn test_single_byte() {
         let service = create_test_service();
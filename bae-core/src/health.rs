@@ -0,0 +1,241 @@
+//! Health of the app's long-running subsystems.
+//!
+//! Each subsystem is checked on demand, and a [`HealthTracker`] turns the
+//! results into the state the health panel shows: whether it's running,
+//! since when, and the last error it reported. The last error is kept after
+//! a subsystem recovers, so a problem that fixed itself is still visible.
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A subsystem shown in the health panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServiceKind {
+    Database,
+    Cache,
+    CloudStorage,
+    Torrent,
+    PlaybackOutput,
+    Keyring,
+}
+
+impl ServiceKind {
+    /// In display order
+    pub const ALL: [ServiceKind; 6] = [
+        ServiceKind::Database,
+        ServiceKind::Cache,
+        ServiceKind::CloudStorage,
+        ServiceKind::Torrent,
+        ServiceKind::PlaybackOutput,
+        ServiceKind::Keyring,
+    ];
+
+    /// Whether the subsystem can be restarted on its own. The rest need the
+    /// app restarted.
+    pub fn can_restart(self) -> bool {
+        matches!(
+            self,
+            ServiceKind::Cache | ServiceKind::CloudStorage | ServiceKind::PlaybackOutput
+        )
+    }
+}
+
+/// What the last check found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceStatus {
+    Running,
+    /// Starts on first use and hasn't been needed yet, or isn't set up
+    NotStarted,
+    Failed,
+}
+
+/// An error a subsystem reported, and when
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceError {
+    pub message: String,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceHealth {
+    pub kind: ServiceKind,
+    pub status: ServiceStatus,
+    /// When the subsystem last started or recovered; None unless running
+    pub running_since: Option<DateTime<Utc>>,
+    pub last_error: Option<ServiceError>,
+}
+
+/// Outcome of checking one subsystem
+pub type CheckResult = Result<ServiceStatus, String>;
+
+/// Health of every subsystem, built up from checks
+#[derive(Clone)]
+pub struct HealthTracker {
+    launched_at: DateTime<Utc>,
+    services: Arc<Mutex<HashMap<ServiceKind, ServiceHealth>>>,
+}
+
+impl HealthTracker {
+    /// A subsystem found running on its first check counts as running
+    /// since `launched_at`
+    pub fn new(launched_at: DateTime<Utc>) -> Self {
+        HealthTracker {
+            launched_at,
+            services: Arc::default(),
+        }
+    }
+
+    /// Record a check of `kind` made at `now`
+    pub fn record(&self, kind: ServiceKind, result: CheckResult, now: DateTime<Utc>) {
+        let mut services = self.services.lock().unwrap();
+        let previous = services.get(&kind);
+        let last_error = previous.and_then(|p| p.last_error.clone());
+        let health = match result {
+            Ok(ServiceStatus::Running) => ServiceHealth {
+                kind,
+                status: ServiceStatus::Running,
+                running_since: match previous {
+                    Some(p) if p.status == ServiceStatus::Running => p.running_since,
+                    Some(_) => Some(now),
+                    None => Some(self.launched_at),
+                },
+                last_error,
+            },
+            Ok(status) => ServiceHealth {
+                kind,
+                status,
+                running_since: None,
+                last_error,
+            },
+            Err(message) => ServiceHealth {
+                kind,
+                status: ServiceStatus::Failed,
+                running_since: None,
+                last_error: Some(ServiceError { message, at: now }),
+            },
+        };
+        services.insert(kind, health);
+    }
+
+    /// `kind` was restarted at `now`; its uptime starts over
+    pub fn restarted(&self, kind: ServiceKind, now: DateTime<Utc>) {
+        let mut services = self.services.lock().unwrap();
+        if let Some(health) = services.get_mut(&kind) {
+            if health.status == ServiceStatus::Running {
+                health.running_since = Some(now);
+            }
+        }
+    }
+
+    /// Every subsystem checked so far, in display order
+    pub fn snapshot(&self) -> Vec<ServiceHealth> {
+        let services = self.services.lock().unwrap();
+        ServiceKind::ALL
+            .iter()
+            .filter_map(|kind| services.get(kind).cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn launch() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 5, 1, 9, 0, 0).unwrap()
+    }
+
+    fn health(tracker: &HealthTracker, kind: ServiceKind) -> ServiceHealth {
+        tracker
+            .snapshot()
+            .into_iter()
+            .find(|h| h.kind == kind)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_running_since_launch_until_failure() {
+        let tracker = HealthTracker::new(launch());
+        let later = launch() + Duration::minutes(30);
+        tracker.record(ServiceKind::Cache, Ok(ServiceStatus::Running), later);
+        assert_eq!(
+            health(&tracker, ServiceKind::Cache).running_since,
+            Some(launch())
+        );
+
+        let failed_at = later + Duration::minutes(5);
+        tracker.record(ServiceKind::Cache, Err("disk full".into()), failed_at);
+        let failed = health(&tracker, ServiceKind::Cache);
+        assert_eq!(failed.status, ServiceStatus::Failed);
+        assert_eq!(failed.running_since, None);
+        assert_eq!(
+            failed.last_error,
+            Some(ServiceError {
+                message: "disk full".into(),
+                at: failed_at,
+            })
+        );
+    }
+
+    #[test]
+    fn test_recovery_keeps_last_error() {
+        let tracker = HealthTracker::new(launch());
+        let failed_at = launch() + Duration::minutes(1);
+        let recovered_at = launch() + Duration::minutes(2);
+        tracker.record(
+            ServiceKind::PlaybackOutput,
+            Err("no device".into()),
+            failed_at,
+        );
+        tracker.record(
+            ServiceKind::PlaybackOutput,
+            Ok(ServiceStatus::Running),
+            recovered_at,
+        );
+        tracker.record(
+            ServiceKind::PlaybackOutput,
+            Ok(ServiceStatus::Running),
+            recovered_at + Duration::minutes(10),
+        );
+
+        let recovered = health(&tracker, ServiceKind::PlaybackOutput);
+        assert_eq!(recovered.status, ServiceStatus::Running);
+        assert_eq!(recovered.running_since, Some(recovered_at));
+        assert_eq!(recovered.last_error.unwrap().at, failed_at);
+    }
+
+    #[test]
+    fn test_restart_resets_uptime() {
+        let tracker = HealthTracker::new(launch());
+        let now = launch() + Duration::hours(3);
+        tracker.record(ServiceKind::Cache, Ok(ServiceStatus::Running), now);
+        tracker.restarted(ServiceKind::Cache, now);
+        assert_eq!(
+            health(&tracker, ServiceKind::Cache).running_since,
+            Some(now)
+        );
+    }
+
+    #[test]
+    fn test_snapshot_in_display_order() {
+        let tracker = HealthTracker::new(launch());
+        tracker.record(ServiceKind::Keyring, Ok(ServiceStatus::Running), launch());
+        tracker.record(
+            ServiceKind::Torrent,
+            Ok(ServiceStatus::NotStarted),
+            launch(),
+        );
+        tracker.record(ServiceKind::Database, Ok(ServiceStatus::Running), launch());
+        let kinds: Vec<ServiceKind> = tracker.snapshot().iter().map(|h| h.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ServiceKind::Database,
+                ServiceKind::Torrent,
+                ServiceKind::Keyring
+            ]
+        );
+    }
+}
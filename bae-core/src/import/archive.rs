@@ -0,0 +1,240 @@
+//! Archive downloads (zip, 7z, rar).
+//!
+//! Stores like Bandcamp and Qobuz hand out releases as archives. Scanning an
+//! archive extracts it into its own folder under the extraction root, then
+//! scans that folder like any other, so categorization and metadata detection
+//! are shared with folder import. The extraction is deleted once the release
+//! has been copied into storage, or when its candidate is dropped.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Archive formats that can be imported
+pub const ARCHIVE_EXTENSIONS: &[&str] = &["zip", "7z", "rar"];
+
+/// Where archives are extracted to while they're imported
+pub fn extraction_root() -> PathBuf {
+    std::env::temp_dir().join("bae_archives")
+}
+
+/// Check if a file is an archive we can extract
+pub fn is_archive_file(path: &Path) -> bool {
+    archive_extension(path).is_some()
+}
+
+fn archive_extension(path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    ARCHIVE_EXTENSIONS.contains(&ext.as_str()).then_some(ext)
+}
+
+/// Extract an archive under `root` and return the folder holding its
+/// contents, named after the archive so the release gets a sensible name.
+///
+/// Each archive gets its own directory, so two downloads with the same name
+/// don't collide. A failed extraction leaves nothing behind.
+pub fn extract_archive(archive: &Path, root: &Path) -> Result<PathBuf, String> {
+    let ext = archive_extension(archive)
+        .ok_or_else(|| format!("Not a supported archive: {}", archive.display()))?;
+    let stem = archive
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Archive");
+    let container = root.join(uuid::Uuid::new_v4().to_string());
+    let folder = container.join(stem);
+    fs::create_dir_all(&folder)
+        .map_err(|e| format!("Failed to create extraction folder: {}", e))?;
+
+    info!("Extracting {} to {}", archive.display(), folder.display());
+    let result = match ext.as_str() {
+        "zip" => extract_zip(archive, &folder),
+        "7z" => sevenz_rust::decompress_file(archive, &folder)
+            .map_err(|e| format!("Failed to extract 7z archive: {}", e)),
+        _ => extract_rar(archive, &folder),
+    };
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&container);
+        return Err(e);
+    }
+    Ok(folder)
+}
+
+fn extract_zip(archive: &Path, folder: &Path) -> Result<(), String> {
+    let file = fs::File::open(archive).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut zip =
+        zip::ZipArchive::new(file).map_err(|e| format!("Failed to read zip archive: {}", e))?;
+    zip.extract(folder)
+        .map_err(|e| format!("Failed to extract zip archive: {}", e))
+}
+
+fn extract_rar(archive: &Path, folder: &Path) -> Result<(), String> {
+    let mut rar = unrar::Archive::new(archive)
+        .open_for_processing()
+        .map_err(|e| format!("Failed to open rar archive: {}", e))?;
+    while let Some(header) = rar
+        .read_header()
+        .map_err(|e| format!("Failed to read rar archive: {}", e))?
+    {
+        rar = if header.entry().is_file() {
+            header
+                .extract_with_base(folder)
+                .map_err(|e| format!("Failed to extract rar archive: {}", e))?
+        } else {
+            header
+                .skip()
+                .map_err(|e| format!("Failed to read rar archive: {}", e))?
+        };
+    }
+    Ok(())
+}
+
+/// Delete an extracted release folder, and the directories above it up to
+/// `root` once they're empty. Other releases from the same archive are kept.
+/// Does nothing for folders that weren't extracted.
+pub fn remove_extracted(folder: &Path, root: &Path) {
+    if !folder.starts_with(root) || folder == root {
+        return;
+    }
+    if let Err(e) = fs::remove_dir_all(folder) {
+        warn!(
+            "Failed to remove extracted folder {}: {}",
+            folder.display(),
+            e
+        );
+        return;
+    }
+    let mut dir = folder.parent();
+    while let Some(parent) = dir.filter(|d| *d != root) {
+        // Stops at the first directory that still has something in it
+        if fs::remove_dir(parent).is_err() {
+            break;
+        }
+        dir = parent.parent();
+    }
+}
+
+/// Delete extractions under `root` left over from a previous run, except
+/// those holding a folder in `keep`: releases still waiting in the import
+/// queue
+pub fn clear_extractions(root: &Path, keep: &[PathBuf]) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if keep.iter().any(|folder| folder.starts_with(&path)) {
+            continue;
+        }
+        if let Err(e) = fs::remove_dir_all(&path) {
+            warn!(
+                "Failed to clear extracted archive {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn write_zip(path: &Path, entries: &[(&str, &str)]) {
+        let mut writer = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        let options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        for (name, data) in entries {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(data.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_is_archive_file() {
+        assert!(is_archive_file(Path::new("Artist - Album.zip")));
+        assert!(is_archive_file(Path::new("release.RAR")));
+        assert!(is_archive_file(Path::new("release.7z")));
+        assert!(!is_archive_file(Path::new("track.flac")));
+        assert!(!is_archive_file(Path::new("zip")));
+    }
+
+    #[test]
+    fn test_extract_zip_into_folder_named_after_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("Artist - Album.zip");
+        write_zip(
+            &archive,
+            &[("01 Intro.flac", "audio"), ("Disc 2/01 Outro.flac", "more")],
+        );
+        let root = dir.path().join("extracted");
+
+        let folder = extract_archive(&archive, &root).unwrap();
+        assert_eq!(folder.file_name().unwrap(), "Artist - Album");
+        assert!(folder.starts_with(&root));
+        assert_eq!(
+            fs::read_to_string(folder.join("01 Intro.flac")).unwrap(),
+            "audio"
+        );
+        assert_eq!(
+            fs::read_to_string(folder.join("Disc 2").join("01 Outro.flac")).unwrap(),
+            "more"
+        );
+    }
+
+    #[test]
+    fn test_failed_extraction_leaves_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("broken.zip");
+        fs::write(&archive, b"not a zip").unwrap();
+        let root = dir.path().join("extracted");
+
+        assert!(extract_archive(&archive, &root).is_err());
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_remove_extracted_keeps_sibling_releases() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("Discography.zip");
+        write_zip(
+            &archive,
+            &[("Album A/01.flac", "a"), ("Album B/01.flac", "b")],
+        );
+        let root = dir.path().join("extracted");
+        let folder = extract_archive(&archive, &root).unwrap();
+
+        remove_extracted(&folder.join("Album A"), &root);
+        assert!(!folder.join("Album A").exists());
+        assert!(folder.join("Album B").exists());
+
+        remove_extracted(&folder.join("Album B"), &root);
+        assert!(root.exists());
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 0);
+
+        // Folders outside the root are never touched
+        let outside = dir.path().join("keep");
+        fs::create_dir(&outside).unwrap();
+        remove_extracted(&outside, &root);
+        assert!(outside.exists());
+    }
+
+    #[test]
+    fn test_clear_extractions_keeps_queued_releases() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("extracted");
+        let mut folders = Vec::new();
+        for name in ["Queued", "Stale"] {
+            let archive = dir.path().join(format!("{}.zip", name));
+            write_zip(&archive, &[("01.flac", "a")]);
+            folders.push(extract_archive(&archive, &root).unwrap());
+        }
+
+        clear_extractions(&root, &folders[..1]);
+        assert!(folders[0].join("01.flac").exists());
+        assert!(!folders[1].exists());
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 1);
+    }
+}
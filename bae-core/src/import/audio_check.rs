@@ -0,0 +1,131 @@
+//! Optional deep audio check run before an import is confirmed.
+//!
+//! Unlike [`file_validation`](super::file_validation), which only reads
+//! headers, this fully decodes every file. It's slow, so it runs on request.
+
+use crate::audio_codec;
+use std::path::{Path, PathBuf};
+
+/// Measured duration may differ from the declared one by this much before
+/// it's reported (encoder padding and header rounding stay well under it)
+const DURATION_TOLERANCE_MS: u64 = 1000;
+
+/// Outcome of fully decoding one audio file
+#[derive(Debug, Clone)]
+pub struct AudioFileCheck {
+    pub path: PathBuf,
+    /// Set when the file couldn't be read or opened by the decoder at all
+    pub error: Option<String>,
+    pub decode_errors: u32,
+    pub duration_ms: Option<u64>,
+    pub declared_duration_ms: Option<u64>,
+    pub clipped_runs: u32,
+    /// Set when the file only opened with a more lenient decoder setup
+    pub decoder_fallback: Option<audio_codec::DecodeFallback>,
+}
+
+impl AudioFileCheck {
+    /// How far the decoded duration falls short of (or exceeds) the declared
+    /// one, if that's beyond [`DURATION_TOLERANCE_MS`]
+    pub fn duration_mismatch_ms(&self) -> Option<i64> {
+        let measured = self.duration_ms?;
+        let declared = self.declared_duration_ms?;
+        let diff = measured as i64 - declared as i64;
+        (diff.unsigned_abs() > DURATION_TOLERANCE_MS).then_some(diff)
+    }
+
+    /// Whether the file is unreadable, damaged or truncated. Clipping is
+    /// reported but doesn't count: it's a mastering choice, not damage.
+    pub fn is_damaged(&self) -> bool {
+        self.error.is_some() || self.decode_errors > 0 || self.duration_mismatch_ms().is_some()
+    }
+}
+
+/// Fully decode each file, in order. Blocking; call from a blocking task.
+pub fn check_audio_files(paths: &[PathBuf]) -> Vec<AudioFileCheck> {
+    paths.iter().map(|path| check_audio_file(path)).collect()
+}
+
+fn check_audio_file(path: &Path) -> AudioFileCheck {
+    let mut check = AudioFileCheck {
+        path: path.to_path_buf(),
+        error: None,
+        decode_errors: 0,
+        duration_ms: None,
+        declared_duration_ms: None,
+        clipped_runs: 0,
+        decoder_fallback: None,
+    };
+
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) => {
+            check.error = Some(e.to_string());
+            return check;
+        }
+    };
+
+    match audio_codec::verify_audio(&data) {
+        Ok(verification) => {
+            check.decode_errors = verification.decode_errors;
+            check.duration_ms = Some(verification.duration_ms);
+            check.declared_duration_ms = verification.declared_duration_ms;
+            check.clipped_runs = verification.clipped_runs;
+            check.decoder_fallback = verification.fallback;
+        }
+        Err(e) => check.error = Some(e),
+    }
+
+    check
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_flac(data: &[u8]) -> tempfile::NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(".flac").tempfile().unwrap();
+        file.write_all(data).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    fn sine_flac(seconds: usize) -> Vec<u8> {
+        let samples: Vec<i32> = (0..44100 * seconds)
+            .map(|i| ((i as f64 * 0.05).sin() * 20000.0) as i32)
+            .collect();
+        audio_codec::encode_to_flac(&samples, 44100, 1, 16).unwrap()
+    }
+
+    #[test]
+    fn test_intact_file_passes() {
+        audio_codec::init();
+
+        let file = write_flac(&sine_flac(3));
+        let checks = check_audio_files(&[file.path().to_path_buf()]);
+
+        assert_eq!(checks.len(), 1);
+        assert!(!checks[0].is_damaged(), "{:?}", checks[0]);
+    }
+
+    #[test]
+    fn test_truncated_file_reports_duration_mismatch() {
+        audio_codec::init();
+
+        let flac = sine_flac(5);
+        let file = write_flac(&flac[..flac.len() / 2]);
+        let checks = check_audio_files(&[file.path().to_path_buf()]);
+
+        let mismatch = checks[0].duration_mismatch_ms().expect("mismatch");
+        assert!(mismatch < 0);
+        assert!(checks[0].is_damaged());
+    }
+
+    #[test]
+    fn test_unreadable_file_reports_error() {
+        let checks = check_audio_files(&[PathBuf::from("/nonexistent/track.flac")]);
+        assert!(checks[0].error.is_some());
+        assert!(checks[0].is_damaged());
+    }
+}
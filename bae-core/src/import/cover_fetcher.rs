@@ -0,0 +1,357 @@
+//! Cover thumbnails for match candidates, fetched a few at a time.
+//!
+//! A page of search results can hold dozens of MusicBrainz releases, each
+//! needing a Cover Art Archive request. [`CoverFetcher`] works through the
+//! list on screen at most [`MAX_CONCURRENT_FETCHES`] at a time, starting them
+//! at least [`MIN_FETCH_INTERVAL`] apart, and takes releases scrolled into
+//! view first. Showing another list cancels whatever is left of the last one.
+//!
+//! Thumbnails go into the app's cache, the one library images and files use,
+//! so a release seen before shows without a request.
+
+use crate::cache::CacheManager;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::{AbortHandle, JoinSet};
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+/// Cover Art Archive requests in flight at once
+pub const MAX_CONCURRENT_FETCHES: usize = 4;
+/// Least time between starting two requests
+pub const MIN_FETCH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Cache key of a release's cover thumbnail
+pub fn thumbnail_cache_key(release_id: &str) -> String {
+    format!("cover-thumb:{}", release_id)
+}
+
+/// Full-size front cover of a release on the Cover Art Archive. Only worth
+/// offering once a thumbnail showed the release has one.
+pub fn front_cover_url(release_id: &str) -> String {
+    format!("https://coverartarchive.org/release/{}/front", release_id)
+}
+
+/// Where thumbnails come from
+#[async_trait::async_trait]
+pub trait ThumbnailSource: Send + Sync {
+    /// Image bytes of the release's front cover thumbnail. None if it has
+    /// none or the request failed.
+    async fn fetch_thumbnail(&self, release_id: &str) -> Option<Vec<u8>>;
+}
+
+/// Front cover thumbnails from the Cover Art Archive
+pub struct CoverArtArchive {
+    client: reqwest::Client,
+}
+
+impl CoverArtArchive {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .user_agent("bae/1.0 +https://github.com/hideselfview/bae")
+            .build()
+            .unwrap_or_else(|e| {
+                warn!("Failed to create HTTP client for Cover Art Archive: {}", e);
+                reqwest::Client::new()
+            });
+        CoverArtArchive { client }
+    }
+}
+
+impl Default for CoverArtArchive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl ThumbnailSource for CoverArtArchive {
+    async fn fetch_thumbnail(&self, release_id: &str) -> Option<Vec<u8>> {
+        let url = format!(
+            "https://coverartarchive.org/release/{}/front-250",
+            release_id
+        );
+        let response = match self.client.get(&url).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                debug!("Failed to fetch thumbnail for {}: {}", release_id, e);
+                return None;
+            }
+        };
+        if !response.status().is_success() {
+            debug!(
+                "Cover Art Archive returned status {} for thumbnail of {}",
+                response.status(),
+                release_id
+            );
+            return None;
+        }
+        match response.bytes().await {
+            Ok(bytes) => Some(bytes.to_vec()),
+            Err(e) => {
+                debug!("Failed to read thumbnail for {}: {}", release_id, e);
+                None
+            }
+        }
+    }
+}
+
+/// A cached thumbnail, ready to show
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverThumbnail {
+    pub release_id: String,
+    pub path: PathBuf,
+}
+
+/// The list being worked through, and the task doing it
+struct CurrentList {
+    release_ids: Vec<String>,
+    task: AbortHandle,
+}
+
+/// Fetches cover thumbnails for the list of candidates on screen
+#[derive(Clone)]
+pub struct CoverFetcher {
+    cache: CacheManager,
+    source: Arc<dyn ThumbnailSource>,
+    /// Releases scrolled into view
+    visible: Arc<Mutex<HashSet<String>>>,
+    /// Releases whose request came back empty, not asked for again this session
+    missing: Arc<Mutex<HashSet<String>>>,
+    current: Arc<Mutex<Option<CurrentList>>>,
+}
+
+impl CoverFetcher {
+    pub fn new(cache: CacheManager, source: Arc<dyn ThumbnailSource>) -> Self {
+        CoverFetcher {
+            cache,
+            source,
+            visible: Arc::default(),
+            missing: Arc::default(),
+            current: Arc::default(),
+        }
+    }
+
+    /// Start on a new list of releases, cancelling the fetches left from the
+    /// last one. Thumbnails are sent as they're ready, cached ones first. The
+    /// same list again carries on undisturbed.
+    pub fn fetch(&self, release_ids: Vec<String>, tx: mpsc::UnboundedSender<CoverThumbnail>) {
+        let mut current = self.current.lock().unwrap();
+        if let Some(list) = current.as_ref() {
+            if list.release_ids == release_ids && !list.task.is_finished() {
+                return;
+            }
+            list.task.abort();
+        }
+        let missing = self.missing.lock().unwrap().clone();
+        let wanted: Vec<String> = release_ids
+            .iter()
+            .filter(|id| !missing.contains(*id))
+            .cloned()
+            .collect();
+        let task = tokio::spawn(self.clone().run(wanted, tx)).abort_handle();
+        *current = Some(CurrentList { release_ids, task });
+    }
+
+    /// Stop fetching for the current list
+    pub fn cancel(&self) {
+        if let Some(list) = self.current.lock().unwrap().take() {
+            list.task.abort();
+        }
+    }
+
+    /// Note a release scrolling into or out of view
+    pub fn set_visible(&self, release_id: &str, visible: bool) {
+        let mut visible_ids = self.visible.lock().unwrap();
+        if visible {
+            visible_ids.insert(release_id.to_string());
+        } else {
+            visible_ids.remove(release_id);
+        }
+    }
+
+    async fn run(self, release_ids: Vec<String>, tx: mpsc::UnboundedSender<CoverThumbnail>) {
+        let mut waiting = Vec::new();
+        for release_id in release_ids {
+            match self
+                .cache
+                .cached_path(&thumbnail_cache_key(&release_id))
+                .await
+            {
+                Some(path) => {
+                    let _ = tx.send(CoverThumbnail { release_id, path });
+                }
+                None => waiting.push(release_id),
+            }
+        }
+
+        // Dropping the set when the task is aborted aborts the fetches too
+        let mut fetches = JoinSet::new();
+        let mut last_start: Option<Instant> = None;
+        loop {
+            while fetches.len() < MAX_CONCURRENT_FETCHES && !waiting.is_empty() {
+                if let Some(last_start) = last_start {
+                    tokio::time::sleep_until(last_start + MIN_FETCH_INTERVAL).await;
+                }
+                last_start = Some(Instant::now());
+                // Picked after the wait, so a scroll during it counts
+                let release_id = self.take_next(&mut waiting);
+                let source = self.source.clone();
+                fetches.spawn(async move {
+                    let data = source.fetch_thumbnail(&release_id).await;
+                    (release_id, data)
+                });
+            }
+            let Some(result) = fetches.join_next().await else {
+                break;
+            };
+            let Ok((release_id, data)) = result else {
+                continue;
+            };
+            let Some(data) = data else {
+                self.missing.lock().unwrap().insert(release_id);
+                continue;
+            };
+            let key = thumbnail_cache_key(&release_id);
+            if let Err(e) = self.cache.put(&key, &data).await {
+                warn!("Failed to cache thumbnail for {}: {}", release_id, e);
+                continue;
+            }
+            if let Some(path) = self.cache.cached_path(&key).await {
+                let _ = tx.send(CoverThumbnail { release_id, path });
+            }
+        }
+    }
+
+    /// The first release in view, else the first in the list
+    fn take_next(&self, waiting: &mut Vec<String>) -> String {
+        let visible = self.visible.lock().unwrap();
+        let index = waiting
+            .iter()
+            .position(|id| visible.contains(id))
+            .unwrap_or(0);
+        waiting.remove(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheConfig;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    /// Records the order of requests and the most it saw at once
+    #[derive(Default)]
+    struct FakeSource {
+        delay: Duration,
+        requested: Mutex<Vec<String>>,
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl ThumbnailSource for FakeSource {
+        async fn fetch_thumbnail(&self, release_id: &str) -> Option<Vec<u8>> {
+            self.requested.lock().unwrap().push(release_id.to_string());
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            (release_id != "none").then(|| release_id.as_bytes().to_vec())
+        }
+    }
+
+    async fn fetcher(dir: &TempDir, delay: Duration) -> (CoverFetcher, Arc<FakeSource>) {
+        let cache = CacheManager::with_config(CacheConfig {
+            cache_dir: dir.path().to_path_buf(),
+            max_size_bytes: 1024 * 1024,
+            max_files: 100,
+        })
+        .await
+        .unwrap();
+        let source = Arc::new(FakeSource {
+            delay,
+            ..Default::default()
+        });
+        (CoverFetcher::new(cache, source.clone()), source)
+    }
+
+    fn ids(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("release-{}", i)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_limits_concurrent_fetches() {
+        let dir = TempDir::new().unwrap();
+        // Slow enough that the first fetch is still running when the
+        // fifth could start
+        let (fetcher, source) = fetcher(&dir, Duration::from_secs(1)).await;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        fetcher.fetch(ids(6), tx);
+
+        let mut received = Vec::new();
+        while let Some(thumbnail) = rx.recv().await {
+            received.push(thumbnail.release_id);
+        }
+        assert_eq!(received.len(), 6);
+        assert_eq!(
+            source.max_in_flight.load(Ordering::SeqCst),
+            MAX_CONCURRENT_FETCHES
+        );
+    }
+
+    #[tokio::test]
+    async fn test_visible_releases_go_first() {
+        let dir = TempDir::new().unwrap();
+        let (fetcher, source) = fetcher(&dir, Duration::from_millis(10)).await;
+        fetcher.set_visible("release-2", true);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        fetcher.fetch(ids(3), tx);
+        while rx.recv().await.is_some() {}
+
+        let requested = source.requested.lock().unwrap();
+        assert_eq!(*requested, ["release-2", "release-0", "release-1"]);
+    }
+
+    #[tokio::test]
+    async fn test_new_list_cancels_old() {
+        let dir = TempDir::new().unwrap();
+        let (fetcher, source) = fetcher(&dir, Duration::from_millis(10)).await;
+        let (old_tx, mut old_rx) = mpsc::unbounded_channel();
+        fetcher.fetch(ids(10), old_tx);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        fetcher.fetch(vec!["other".to_string()], tx);
+        assert_eq!(rx.recv().await.unwrap().release_id, "other");
+        assert!(rx.recv().await.is_none());
+        assert!(old_rx.recv().await.is_none());
+        assert_eq!(
+            *source.requested.lock().unwrap(),
+            vec!["release-0".to_string(), "other".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cached_and_missing_thumbnails_skip_requests() {
+        let dir = TempDir::new().unwrap();
+        let (fetcher, source) = fetcher(&dir, Duration::from_millis(10)).await;
+        let list = vec!["release-0".to_string(), "none".to_string()];
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        fetcher.fetch(list.clone(), tx);
+        while rx.recv().await.is_some() {}
+
+        fetcher.cancel();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        fetcher.fetch(list, tx);
+        let thumbnail = rx.recv().await.unwrap();
+        assert_eq!(thumbnail.release_id, "release-0");
+        assert_eq!(std::fs::read(thumbnail.path).unwrap(), b"release-0");
+        assert!(rx.recv().await.is_none());
+        assert_eq!(source.requested.lock().unwrap().len(), 2);
+    }
+}
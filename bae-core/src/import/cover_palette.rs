@@ -0,0 +1,152 @@
+//! Cover art palette extraction.
+//!
+//! Runs once per album at import so the UI can tint headers and Now Playing
+//! from stored colors instead of sampling images while rendering.
+use image::RgbImage;
+
+/// Covers are downscaled to this size before sampling
+const SAMPLE_SIZE: u32 = 64;
+/// Accent candidates must cover at least this fraction of sampled pixels
+const MIN_ACCENT_SHARE: f32 = 0.01;
+/// Minimum RGB distance between dominant and accent colors
+const MIN_ACCENT_DISTANCE: f32 = 64.0;
+
+/// Dominant and accent colors of an album cover
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoverPalette {
+    pub dominant: [u8; 3],
+    pub accent: [u8; 3],
+}
+
+impl CoverPalette {
+    pub fn dominant_hex(&self) -> String {
+        to_hex(self.dominant)
+    }
+    pub fn accent_hex(&self) -> String {
+        to_hex(self.accent)
+    }
+}
+
+/// Decode an encoded cover image and extract its palette.
+/// Returns None if the image can't be decoded.
+pub fn extract_palette(image_data: &[u8]) -> Option<CoverPalette> {
+    let image = image::load_from_memory(image_data).ok()?;
+    Some(palette_from_rgb(
+        &image.thumbnail(SAMPLE_SIZE, SAMPLE_SIZE).to_rgb8(),
+    ))
+}
+
+/// Bucket pixels at 4 bits per channel. The most populated bucket is the
+/// dominant color; the accent is the most saturated sizeable bucket that is
+/// visibly different from it.
+pub fn palette_from_rgb(image: &RgbImage) -> CoverPalette {
+    let mut buckets = vec![Bucket::default(); 4096];
+    for pixel in image.pixels() {
+        let [r, g, b] = pixel.0;
+        let index = ((r as usize >> 4) << 8) | ((g as usize >> 4) << 4) | (b as usize >> 4);
+        buckets[index].add(pixel.0);
+    }
+    let total = (image.width() * image.height()).max(1) as f32;
+
+    let dominant = buckets
+        .iter()
+        .max_by_key(|b| b.count)
+        .map(Bucket::mean)
+        .unwrap_or_default();
+
+    let accent = buckets
+        .iter()
+        .filter(|b| b.count as f32 / total >= MIN_ACCENT_SHARE)
+        .map(Bucket::mean)
+        .filter(|color| distance(*color, dominant) >= MIN_ACCENT_DISTANCE)
+        .max_by(|a, b| saturation(*a).total_cmp(&saturation(*b)))
+        .unwrap_or_else(|| shade(dominant));
+
+    CoverPalette { dominant, accent }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Bucket {
+    count: u32,
+    sum: [u32; 3],
+}
+
+impl Bucket {
+    fn add(&mut self, rgb: [u8; 3]) {
+        self.count += 1;
+        for (sum, channel) in self.sum.iter_mut().zip(rgb) {
+            *sum += channel as u32;
+        }
+    }
+    fn mean(&self) -> [u8; 3] {
+        let count = self.count.max(1);
+        self.sum.map(|sum| (sum / count) as u8)
+    }
+}
+
+fn to_hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn distance(a: [u8; 3], b: [u8; 3]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (*x as f32 - y as f32).powi(2))
+        .sum::<f32>()
+        .sqrt()
+}
+
+/// HSL-style saturation in 0.0-1.0
+fn saturation(rgb: [u8; 3]) -> f32 {
+    let max = *rgb.iter().max().unwrap() as f32 / 255.0;
+    let min = *rgb.iter().min().unwrap() as f32 / 255.0;
+    let lightness = (max + min) / 2.0;
+    if max == min {
+        0.0
+    } else {
+        (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+    }
+}
+
+/// Fallback accent for single-color covers: lighten dark colors, darken light ones
+fn shade(rgb: [u8; 3]) -> [u8; 3] {
+    let luminance = 0.2126 * rgb[0] as f32 + 0.7152 * rgb[1] as f32 + 0.0722 * rgb[2] as f32;
+    if luminance < 128.0 {
+        rgb.map(|c| c + (255 - c) / 2)
+    } else {
+        rgb.map(|c| c / 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    #[test]
+    fn test_dominant_and_accent_colors() {
+        // Mostly dark blue with an orange stripe
+        let image = RgbImage::from_fn(64, 64, |x, _| {
+            if x < 8 {
+                Rgb([240, 120, 20])
+            } else {
+                Rgb([20, 30, 90])
+            }
+        });
+        let palette = palette_from_rgb(&image);
+        assert_eq!(palette.dominant_hex(), "#141e5a");
+        assert_eq!(palette.accent_hex(), "#f07814");
+    }
+
+    #[test]
+    fn test_single_color_cover_gets_shaded_accent() {
+        let palette = palette_from_rgb(&RgbImage::from_pixel(16, 16, Rgb([10, 10, 10])));
+        assert_eq!(palette.dominant, [10, 10, 10]);
+        assert_eq!(palette.accent, [132, 132, 132]);
+    }
+
+    #[test]
+    fn test_extract_palette_rejects_garbage() {
+        assert_eq!(extract_palette(b"not an image"), None);
+    }
+}
@@ -0,0 +1,266 @@
+//! BIN/CUE disc images.
+//!
+//! A BIN is the raw CD-DA stream of a whole disc: 16-bit stereo PCM at 44.1kHz
+//! with no header. We encode it to FLAC once and write a copy of the CUE that
+//! points at the FLAC, so the image imports through the regular CUE/FLAC path.
+//! The extracted pair lives in the release's `.bae` folder, next to downloaded
+//! cover art, and is reused on later scans.
+//!
+//! ISO images hold a data filesystem, not CD-DA, so they have no audio to
+//! extract.
+
+use super::folder_scanner::{self, AudioContent};
+use crate::audio_codec;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+const CD_SAMPLE_RATE: u32 = 44100;
+const CD_CHANNELS: u32 = 2;
+const CD_BITS_PER_SAMPLE: u32 = 16;
+
+/// Where extracted images go, relative to the release root
+const EXTRACTED_DIR: &str = ".bae/disc_images";
+
+/// A CUE sheet describing a single raw audio image
+#[derive(Debug, Clone)]
+pub struct DiscImage {
+    pub cue_path: PathBuf,
+    pub bin_path: PathBuf,
+    /// `FILE ... MOTOROLA` images store samples big-endian
+    big_endian: bool,
+}
+
+impl DiscImage {
+    /// Read a CUE sheet and return the image it describes, if it's a single
+    /// `BINARY`/`MOTOROLA` file holding only audio tracks.
+    ///
+    /// Multi-file images (one BIN per track) and mixed-mode discs with data
+    /// tracks aren't supported.
+    pub fn from_cue(cue_path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(cue_path).ok()?;
+
+        let mut file_entries = Vec::new();
+        let mut track_count = 0;
+        for line in content.lines().map(str::trim) {
+            if let Some(rest) = line.strip_prefix("FILE ") {
+                file_entries.push(parse_file_entry(rest)?);
+            } else if let Some(rest) = line.strip_prefix("TRACK ") {
+                if !rest.trim_end().ends_with("AUDIO") {
+                    return None;
+                }
+                track_count += 1;
+            }
+        }
+
+        let [(name, file_type)] = file_entries.as_slice() else {
+            return None;
+        };
+        let big_endian = match file_type.to_uppercase().as_str() {
+            "BINARY" => false,
+            "MOTOROLA" => true,
+            _ => return None,
+        };
+        if track_count == 0 {
+            return None;
+        }
+
+        Some(DiscImage {
+            cue_path: cue_path.to_path_buf(),
+            bin_path: cue_path.with_file_name(name),
+            big_endian,
+        })
+    }
+
+    /// Paths of the extracted (FLAC, CUE) pair. The FLAC takes the CUE's stem
+    /// so CUE/FLAC detection pairs them.
+    pub fn extracted_paths(&self, release_root: &Path) -> (PathBuf, PathBuf) {
+        let dir = release_root.join(EXTRACTED_DIR);
+        let cue_name = self.cue_path.file_name().unwrap_or_default();
+        let stem = self
+            .cue_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("disc");
+        (dir.join(format!("{}.flac", stem)), dir.join(cue_name))
+    }
+
+    pub fn is_extracted(&self, release_root: &Path) -> bool {
+        let (flac_path, cue_path) = self.extracted_paths(release_root);
+        flac_path.exists() && cue_path.exists()
+    }
+
+    /// Encode the image to FLAC and write the rewritten CUE next to it.
+    /// Does nothing if a previous scan already extracted it.
+    ///
+    /// Blocking, and holds the whole disc in memory while encoding.
+    pub fn extract(&self, release_root: &Path) -> Result<(), String> {
+        if self.is_extracted(release_root) {
+            return Ok(());
+        }
+        let (flac_path, cue_path) = self.extracted_paths(release_root);
+
+        info!("Extracting disc image {:?}", self.bin_path);
+
+        let data = fs::read(&self.bin_path)
+            .map_err(|e| format!("Failed to read disc image {:?}: {}", self.bin_path, e))?;
+        let samples = pcm_samples(&data, self.big_endian);
+        drop(data);
+        let flac =
+            audio_codec::encode_to_flac(&samples, CD_SAMPLE_RATE, CD_CHANNELS, CD_BITS_PER_SAMPLE)?;
+
+        let cue = fs::read_to_string(&self.cue_path)
+            .map_err(|e| format!("Failed to read CUE {:?}: {}", self.cue_path, e))?;
+        let flac_name = flac_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+
+        let dir = flac_path.parent().unwrap_or(release_root);
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+        // Write the CUE last: its presence marks a finished extraction
+        fs::write(&flac_path, flac)
+            .map_err(|e| format!("Failed to write {:?}: {}", flac_path, e))?;
+        fs::write(&cue_path, point_cue_at(&cue, flac_name))
+            .map_err(|e| format!("Failed to write {:?}: {}", cue_path, e))?;
+
+        Ok(())
+    }
+}
+
+/// Images in a release folder that haven't been extracted yet
+pub fn pending_disc_images(release_root: &Path) -> Result<Vec<DiscImage>, String> {
+    let categorized = folder_scanner::collect_release_files(release_root)?;
+    let AudioContent::CueFlacPairs(pairs) = categorized.audio else {
+        return Ok(Vec::new());
+    };
+    // Extracted pairs point at the rewritten CUE, which names a WAVE file
+    Ok(pairs
+        .iter()
+        .filter_map(|pair| DiscImage::from_cue(&pair.cue_file.path))
+        .collect())
+}
+
+/// Parse the `"name" TYPE` part of a FILE line
+fn parse_file_entry(rest: &str) -> Option<(String, String)> {
+    let rest = rest.trim();
+    let (name, file_type) = if let Some(quoted) = rest.strip_prefix('"') {
+        let end = quoted.find('"')?;
+        (&quoted[..end], &quoted[end + 1..])
+    } else {
+        rest.split_once(' ')?
+    };
+    Some((name.to_string(), file_type.trim().to_string()))
+}
+
+/// Interleaved 16-bit samples widened to i32, as `encode_to_flac` expects
+fn pcm_samples(data: &[u8], big_endian: bool) -> Vec<i32> {
+    data.chunks_exact(2)
+        .map(|b| {
+            let bytes = [b[0], b[1]];
+            if big_endian {
+                i16::from_be_bytes(bytes) as i32
+            } else {
+                i16::from_le_bytes(bytes) as i32
+            }
+        })
+        .collect()
+}
+
+/// Replace the CUE's FILE line with one naming the extracted FLAC
+fn point_cue_at(cue: &str, flac_name: &str) -> String {
+    cue.lines()
+        .map(|line| {
+            if line.trim_start().starts_with("FILE ") {
+                format!("FILE \"{}\" WAVE", flac_name)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cue_flac::CueFlacProcessor;
+
+    const IMAGE_CUE: &str = r#"PERFORMER "Test Artist"
+TITLE "Test Album"
+FILE "Test Album.bin" BINARY
+  TRACK 01 AUDIO
+    TITLE "Track One"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "Track Two"
+    INDEX 01 00:01:00
+"#;
+
+    #[test]
+    fn test_from_cue_accepts_audio_image() {
+        let dir = tempfile::tempdir().unwrap();
+        let cue_path = dir.path().join("Test Album.cue");
+        fs::write(&cue_path, IMAGE_CUE).unwrap();
+
+        let image = DiscImage::from_cue(&cue_path).unwrap();
+        assert_eq!(image.bin_path, dir.path().join("Test Album.bin"));
+        assert!(!image.big_endian);
+    }
+
+    #[test]
+    fn test_from_cue_rejects_data_tracks_and_flac_cues() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mixed_mode = dir.path().join("mixed.cue");
+        fs::write(
+            &mixed_mode,
+            "FILE \"mixed.bin\" BINARY\n  TRACK 01 MODE1/2352\n    INDEX 01 00:00:00\n",
+        )
+        .unwrap();
+        assert!(DiscImage::from_cue(&mixed_mode).is_none());
+
+        let flac_cue = dir.path().join("album.cue");
+        fs::write(
+            &flac_cue,
+            "FILE \"album.flac\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00\n",
+        )
+        .unwrap();
+        assert!(DiscImage::from_cue(&flac_cue).is_none());
+    }
+
+    #[test]
+    fn test_extract_writes_flac_and_rewritten_cue() {
+        audio_codec::init();
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let cue_path = root.join("Test Album.cue");
+        fs::write(&cue_path, IMAGE_CUE).unwrap();
+        // Two seconds of a quiet ramp, little-endian stereo
+        let bin: Vec<u8> = (0..CD_SAMPLE_RATE as i32 * 2 * 2)
+            .flat_map(|i| ((i % 2000) as i16).to_le_bytes())
+            .collect();
+        fs::write(root.join("Test Album.bin"), bin).unwrap();
+
+        let image = DiscImage::from_cue(&cue_path).unwrap();
+        image.extract(root).unwrap();
+
+        let (flac_path, extracted_cue) = image.extracted_paths(root);
+        let info = CueFlacProcessor::analyze_flac(&flac_path).unwrap();
+        assert_eq!(info.sample_rate, CD_SAMPLE_RATE);
+        assert!(info.duration_ms().abs_diff(2000) < 50);
+
+        let cue = fs::read_to_string(&extracted_cue).unwrap();
+        assert!(cue.contains("FILE \"Test Album.flac\" WAVE"));
+        let sheet = CueFlacProcessor::parse_cue_sheet(&extracted_cue).unwrap();
+        assert_eq!(sheet.tracks.len(), 2);
+    }
+
+    #[test]
+    fn test_pcm_samples_byte_order() {
+        assert_eq!(pcm_samples(&[0x01, 0x80], false), vec![-32767]);
+        assert_eq!(pcm_samples(&[0x80, 0x01], true), vec![-32767]);
+    }
+}
@@ -0,0 +1,269 @@
+//! Identifying discs that were ripped before they could be looked up.
+//!
+//! When MusicBrainz can't be reached during a CD import, the disc can be
+//! ripped anyway under placeholder metadata. Its DiscID is queued in the
+//! database and looked up again every few minutes. Once it matches exactly
+//! one release, that release's metadata is saved over the placeholder as an
+//! ordinary album edit, so it shows in the album's history and can be undone.
+
+use crate::db::{DbTrack, ImportStatus};
+use crate::import::discogs_parser::ParsedAlbum;
+use crate::import::musicbrainz_parser::fetch_and_parse_mb_release;
+use crate::library::edit::{AlbumEdits, AlbumFields, ReleaseFields, TrackFields};
+use crate::library::{LibraryManager, SharedLibraryManager};
+use crate::musicbrainz::{lookup_by_discid, MbRelease, MusicBrainzError};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// How often queued lookups are retried
+const RETRY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+pub struct DiscIdRetryService;
+
+impl DiscIdRetryService {
+    pub fn start(runtime_handle: tokio::runtime::Handle, library_manager: SharedLibraryManager) {
+        runtime_handle.spawn(retry_loop(library_manager));
+    }
+}
+
+async fn retry_loop(library_manager: SharedLibraryManager) {
+    let mut retry = tokio::time::interval(RETRY_INTERVAL);
+    loop {
+        retry.tick().await;
+        retry_pending_lookups(library_manager.get()).await;
+    }
+}
+
+/// Look up every queued disc, stopping at the first lookup that can't reach
+/// MusicBrainz
+async fn retry_pending_lookups(library_manager: &LibraryManager) {
+    let database = library_manager.database();
+    let lookups = match database.get_discid_lookups().await {
+        Ok(lookups) => lookups,
+        Err(e) => {
+            error!("Failed to read DiscID lookup queue: {}", e);
+            return;
+        }
+    };
+    for lookup in lookups {
+        // Leave the tracks alone until the rip has finished writing them
+        match library_manager.get_release(&lookup.release_id).await {
+            Ok(Some(release)) if release.import_status == ImportStatus::Complete => {}
+            Ok(_) => continue,
+            Err(e) => {
+                error!("Failed to read release {}: {}", lookup.release_id, e);
+                continue;
+            }
+        }
+
+        let releases = match lookup_by_discid(&lookup.mb_discid).await {
+            Ok((releases, _)) => releases,
+            Err(MusicBrainzError::NotFound(_)) => {
+                info!(
+                    "DiscID {} isn't in MusicBrainz; leaving release {} as entered",
+                    lookup.mb_discid, lookup.release_id
+                );
+                remove_lookup(library_manager, &lookup.release_id).await;
+                continue;
+            }
+            Err(e) => {
+                warn!("DiscID lookup failed, will retry: {}", e);
+                if let Err(e) = database
+                    .record_discid_lookup_attempt(&lookup.release_id, &e.to_string())
+                    .await
+                {
+                    error!("Failed to record DiscID lookup attempt: {}", e);
+                }
+                return;
+            }
+        };
+
+        match releases.as_slice() {
+            [mb_release] => {
+                match apply_match(library_manager, &lookup.release_id, mb_release).await {
+                    Ok(()) => {
+                        info!(
+                            "Identified release {} as {} by {}",
+                            lookup.release_id, mb_release.title, mb_release.artist
+                        );
+                        remove_lookup(library_manager, &lookup.release_id).await;
+                    }
+                    Err(e) => {
+                        warn!("Failed to apply match to {}: {}", lookup.release_id, e);
+                        if let Err(e) = database
+                            .record_discid_lookup_attempt(&lookup.release_id, &e)
+                            .await
+                        {
+                            error!("Failed to record DiscID lookup attempt: {}", e);
+                        }
+                    }
+                }
+            }
+            // Picking between pressings is up to the user
+            _ => {
+                info!(
+                    "DiscID {} matches {} releases; leaving release {} to be identified by hand",
+                    lookup.mb_discid,
+                    releases.len(),
+                    lookup.release_id
+                );
+                remove_lookup(library_manager, &lookup.release_id).await;
+            }
+        }
+    }
+}
+
+async fn remove_lookup(library_manager: &LibraryManager, release_id: &str) {
+    if let Err(e) = library_manager
+        .database()
+        .delete_discid_lookup(release_id)
+        .await
+    {
+        error!("Failed to remove DiscID lookup: {}", e);
+    }
+}
+
+/// Save a MusicBrainz release's metadata over a placeholder release
+async fn apply_match(
+    library_manager: &LibraryManager,
+    release_id: &str,
+    mb_release: &MbRelease,
+) -> Result<(), String> {
+    let database = library_manager.database();
+    let album_id = database
+        .get_album_id_for_release(release_id)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?
+        .ok_or_else(|| format!("No release {}", release_id))?;
+    let tracks = library_manager
+        .get_tracks(release_id)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+
+    let master_year = mb_release
+        .first_release_date
+        .as_deref()
+        .and_then(|date| date.get(..4))
+        .and_then(|year| year.parse().ok())
+        .unwrap_or(1970);
+    let parsed = fetch_and_parse_mb_release(&mb_release.release_id, master_year, None).await?;
+
+    let edits = edits_from_match(&album_id, release_id, &tracks, &parsed);
+    library_manager
+        .save_album_edits(&edits)
+        .await
+        .map_err(|e| format!("Failed to save metadata: {}", e))?;
+    if let Some(mb_release) = &parsed.0.musicbrainz_release {
+        database
+            .set_album_musicbrainz_release(&album_id, mb_release)
+            .await
+            .map_err(|e| format!("Failed to link MusicBrainz release: {}", e))?;
+    }
+    library_manager.notify_albums_changed();
+    Ok(())
+}
+
+/// Edits that turn a placeholder release into the matched one.
+///
+/// Tracks are matched in disc and track order. If the counts differ, e.g.
+/// the match is a multi-disc set, track titles are left as they are.
+fn edits_from_match(
+    album_id: &str,
+    release_id: &str,
+    tracks: &[DbTrack],
+    parsed: &ParsedAlbum,
+) -> AlbumEdits {
+    let (album, release, parsed_tracks, artists, album_artists) = parsed;
+
+    let mut album_artists = album_artists.clone();
+    album_artists.sort_by_key(|a| a.position);
+    let artist_names = album_artists
+        .iter()
+        .filter_map(|link| artists.iter().find(|a| a.id == link.artist_id))
+        .map(|artist| artist.name.clone())
+        .collect();
+
+    let in_order = |tracks: &[DbTrack]| {
+        let mut sorted = tracks.to_vec();
+        sorted.sort_by_key(|t| (t.disc_number, t.track_number));
+        sorted
+    };
+    let track_edits = if tracks.len() == parsed_tracks.len() {
+        in_order(tracks)
+            .into_iter()
+            .zip(in_order(parsed_tracks))
+            .map(|(placeholder, matched)| (placeholder.id, TrackFields::from(&matched)))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    AlbumEdits {
+        album_id: album_id.to_string(),
+        album: Some(AlbumFields::from(album)),
+        artists: Some(artist_names),
+        releases: vec![(release_id.to_string(), ReleaseFields::from(release))],
+        tracks: track_edits,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::manual_release::{parse_manual_release, ManualRelease};
+
+    fn release(title: &str, artist: &str, tracks: &[&str]) -> ParsedAlbum {
+        let release = ManualRelease {
+            title: title.to_string(),
+            artist: artist.to_string(),
+            year: Some(1959),
+            tracks: tracks.iter().map(|t| t.to_string()).collect(),
+        };
+        parse_manual_release(&release, None).unwrap()
+    }
+
+    #[test]
+    fn test_edits_from_match_replaces_placeholder() {
+        let (album, db_release, tracks, _, _) =
+            release("Unknown Album", "Unknown Artist", &["", ""]);
+        let matched = release(
+            "Kind of Blue",
+            "Miles Davis",
+            &["So What", "Freddie Freeloader"],
+        );
+
+        let edits = edits_from_match(&album.id, &db_release.id, &tracks, &matched);
+
+        assert_eq!(edits.album.as_ref().unwrap().title, "Kind of Blue");
+        assert_eq!(edits.album.as_ref().unwrap().year, Some(1959));
+        assert_eq!(edits.artists, Some(vec!["Miles Davis".to_string()]));
+        assert_eq!(edits.releases[0].0, db_release.id);
+        let titles: Vec<(&str, &str)> = edits
+            .tracks
+            .iter()
+            .map(|(id, fields)| (id.as_str(), fields.title.as_str()))
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                (tracks[0].id.as_str(), "So What"),
+                (tracks[1].id.as_str(), "Freddie Freeloader"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edits_from_match_keeps_tracks_when_counts_differ() {
+        let (album, db_release, tracks, _, _) = release("Unknown Album", "Unknown Artist", &[""]);
+        let matched = release(
+            "Kind of Blue",
+            "Miles Davis",
+            &["So What", "Freddie Freeloader"],
+        );
+
+        let edits = edits_from_match(&album.id, &db_release.id, &tracks, &matched);
+
+        assert_eq!(edits.album.unwrap().title, "Kind of Blue");
+        assert!(edits.tracks.is_empty());
+    }
+}
@@ -44,6 +44,7 @@ pub fn parse_discogs_release(
             album_id: album.id.clone(),
             artist_id: artist.id.clone(),
             position: 0,
+            credited_as: None,
         };
         artists.push(artist);
         album_artists.push(album_artist);
@@ -63,6 +64,7 @@ pub fn parse_discogs_release(
                 album_id: album.id.clone(),
                 artist_id: artist.id.clone(),
                 position: position as i32,
+                credited_as: None,
             };
             artists.push(artist);
             album_artists.push(album_artist);
@@ -0,0 +1,185 @@
+//! Duplicate encodings of the same track inside one release folder.
+//!
+//! Some folders carry a track twice, e.g. a hi-res and a CD-resolution copy.
+//! File names and sizes don't reliably give that away, so the opening of each
+//! file is fingerprinted with Chromaprint and the fingerprints are compared.
+
+use crate::audio_codec;
+use rusty_chromaprint::{Configuration, Fingerprinter};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Only this much of each file is decoded and fingerprinted
+const FINGERPRINT_DURATION_MS: u64 = 120_000;
+
+/// Fingerprints are compared at shifts up to this many items either way, to
+/// line up copies with different encoder delay (an item is about 0.12s)
+const MAX_ALIGNMENT_OFFSET: usize = 8;
+
+/// Fraction of matching fingerprint bits above which two files count as the
+/// same recording. Unrelated audio sits around 0.5.
+const DUPLICATE_SIMILARITY: f64 = 0.85;
+
+/// Files in a release that hold the same recording
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateAudioGroup {
+    pub files: Vec<PathBuf>,
+}
+
+/// Group the given files by recording, returning only groups with more than
+/// one file. Files that can't be decoded are left out. Blocking; call from a
+/// blocking task.
+pub fn find_duplicate_audio(paths: &[PathBuf]) -> Vec<DuplicateAudioGroup> {
+    let fingerprints: Vec<(PathBuf, Vec<u32>)> = paths
+        .iter()
+        .filter_map(|path| match fingerprint_file(path) {
+            Ok(fingerprint) => Some((path.clone(), fingerprint)),
+            Err(e) => {
+                warn!("Failed to fingerprint {}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect();
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for i in 0..fingerprints.len() {
+        let existing = groups.iter_mut().find(|group| {
+            group.iter().any(|&j| {
+                fingerprint_similarity(&fingerprints[i].1, &fingerprints[j].1)
+                    >= DUPLICATE_SIMILARITY
+            })
+        });
+        match existing {
+            Some(group) => group.push(i),
+            None => groups.push(vec![i]),
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|group| group.len() > 1)
+        .map(|group| DuplicateAudioGroup {
+            files: group
+                .into_iter()
+                .map(|i| fingerprints[i].0.clone())
+                .collect(),
+        })
+        .collect()
+}
+
+fn fingerprint_file(path: &Path) -> Result<Vec<u32>, String> {
+    let data = std::fs::read(path).map_err(|e| e.to_string())?;
+    let decoded = audio_codec::decode_audio(&data, None, Some(FINGERPRINT_DURATION_MS))?;
+
+    // Decoded samples are either 16-bit or full-scale 32-bit; Chromaprint wants 16-bit
+    let shift = if decoded.bits_per_sample > 16 { 16 } else { 0 };
+    let samples: Vec<i16> = decoded
+        .samples
+        .iter()
+        .map(|&s| (s >> shift) as i16)
+        .collect();
+
+    let mut printer = Fingerprinter::new(&Configuration::preset_test2());
+    printer
+        .start(decoded.sample_rate, decoded.channels)
+        .map_err(|e| format!("{:?}", e))?;
+    printer.consume(&samples);
+    printer.finish();
+    Ok(printer.fingerprint().to_vec())
+}
+
+/// Best fraction of equal bits between two fingerprints over small alignment
+/// shifts. 1.0 is identical, unrelated audio lands near 0.5.
+fn fingerprint_similarity(a: &[u32], b: &[u32]) -> f64 {
+    let mut best = 0.0;
+    for offset in 0..=MAX_ALIGNMENT_OFFSET {
+        for (x, y) in [(a, b), (b, a)] {
+            if offset >= x.len() {
+                continue;
+            }
+            let x = &x[offset..];
+            let len = x.len().min(y.len());
+            if len == 0 {
+                continue;
+            }
+            let differing: u32 = x[..len]
+                .iter()
+                .zip(&y[..len])
+                .map(|(p, q)| (p ^ q).count_ones())
+                .sum();
+            let similarity = 1.0 - differing as f64 / (len as f64 * 32.0);
+            if similarity > best {
+                best = similarity;
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_flac(data: &[u8]) -> tempfile::NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(".flac").tempfile().unwrap();
+        file.write_all(data).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    fn chirp(seconds: usize) -> Vec<i32> {
+        (0..44100 * seconds)
+            .map(|i| {
+                let t = i as f64 / 44100.0;
+                ((t * (200.0 + 150.0 * t) * std::f64::consts::TAU).sin() * 12000.0) as i32
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_identical_fingerprints_match() {
+        let fp: Vec<u32> = (0..100u32).map(|i| i.wrapping_mul(2_654_435_761)).collect();
+        assert_eq!(fingerprint_similarity(&fp, &fp), 1.0);
+    }
+
+    #[test]
+    fn test_shifted_fingerprints_match() {
+        let fp: Vec<u32> = (0..100u32).map(|i| i.wrapping_mul(2_654_435_761)).collect();
+        assert_eq!(fingerprint_similarity(&fp[3..], &fp), 1.0);
+    }
+
+    #[test]
+    fn test_inverted_fingerprints_do_not_match() {
+        let fp: Vec<u32> = (0..100u32).map(|i| i.wrapping_mul(2_654_435_761)).collect();
+        let inverted: Vec<u32> = fp.iter().map(|x| !x).collect();
+        assert!(fingerprint_similarity(&fp, &inverted) < DUPLICATE_SIMILARITY);
+    }
+
+    #[test]
+    fn test_empty_fingerprint_never_matches() {
+        assert_eq!(fingerprint_similarity(&[], &[1, 2, 3]), 0.0);
+    }
+
+    #[test]
+    fn test_same_recording_at_two_bit_depths_is_grouped() {
+        audio_codec::init();
+
+        let samples = chirp(10);
+        let cd = write_flac(&audio_codec::encode_to_flac(&samples, 44100, 1, 16).unwrap());
+        let hi_res_samples: Vec<i32> = samples.iter().map(|s| s << 8).collect();
+        let hi_res =
+            write_flac(&audio_codec::encode_to_flac(&hi_res_samples, 44100, 1, 24).unwrap());
+
+        let groups = find_duplicate_audio(&[cd.path().to_path_buf(), hi_res.path().to_path_buf()]);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].files.len(), 2);
+    }
+
+    #[test]
+    fn test_unreadable_file_is_skipped() {
+        let groups = find_duplicate_audio(&[PathBuf::from("/nonexistent/track.flac")]);
+        assert!(groups.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::cue_flac::CueFlacProcessor;
+use crate::import::folder_pattern::{match_folder_patterns, FolderPattern};
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
@@ -602,12 +603,18 @@ fn parse_folder_name(folder_path: &Path) -> (Option<String>, Option<String>, Vec
 /// Detect folder contents and metadata from a folder containing audio files
 pub fn detect_folder_contents(
     folder_path: PathBuf,
+    patterns: &[FolderPattern],
 ) -> Result<FolderContents, MetadataDetectionError> {
-    let metadata = detect_metadata(folder_path)?;
+    let metadata = detect_metadata(folder_path, patterns)?;
     Ok(FolderContents { metadata })
 }
-/// Detect metadata from a folder containing audio files
-pub fn detect_metadata(folder_path: PathBuf) -> Result<FolderMetadata, MetadataDetectionError> {
+/// Detect metadata from a folder containing audio files. A folder that fits
+/// one of the user's folder-name `patterns` is read with it, which is trusted
+/// over tags but not over a rip's CUE sheet.
+pub fn detect_metadata(
+    folder_path: PathBuf,
+    patterns: &[FolderPattern],
+) -> Result<FolderMetadata, MetadataDetectionError> {
     use tracing::info;
     info!(
         "📁 Starting metadata detection for folder: {:?}",
@@ -780,7 +787,22 @@ pub fn detect_metadata(folder_path: PathBuf) -> Result<FolderMetadata, MetadataD
     if track_count.is_none() {
         track_count = Some(audio_files.len() as u32);
     }
-    let (folder_artist, folder_album, folder_tokens) = parse_folder_name(&folder_path);
+    let (folder_artist, folder_album, mut folder_tokens) = parse_folder_name(&folder_path);
+    if let Some(fields) = match_folder_patterns(patterns, &folder_path) {
+        debug!("Folder fits a folder pattern: {:?}", fields);
+        if let Some(a) = fields.artist {
+            artist_sources.push((a, 0.85));
+        }
+        if let Some(alb) = fields.album {
+            album_sources.push((alb, 0.85));
+        }
+        if let Some(y) = fields.year {
+            year_sources.push((y, 0.85));
+        }
+        // Not detected fields, but good search terms
+        let extra = [fields.catalog_number, fields.label, fields.format];
+        folder_tokens.splice(0..0, extra.into_iter().flatten());
+    }
     if let Some(ref a) = folder_artist {
         debug!("Parsed folder name: artist='{}'", a);
         artist_sources.push((a.clone(), 0.3));
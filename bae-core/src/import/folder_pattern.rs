@@ -0,0 +1,252 @@
+//! Folder-name templates such as `{artist}/{year} - {album} [{format}]`.
+//!
+//! Each `/`-separated part of a template matches one folder, counted from
+//! the end of the import path, so the template above reads the artist from
+//! the parent folder and the rest from the release folder. Placeholders are
+//! `{artist}`, `{album}`, `{year}`, `{format}`, `{label}` and `{catalog}`;
+//! `{*}` matches anything and is ignored. Everything else must match as
+//! written.
+
+use regex::Regex;
+use std::path::{Component, Path};
+use thiserror::Error;
+use tracing::warn;
+
+/// Built-in templates for common layouts: (name, template)
+pub const FOLDER_PATTERN_PRESETS: &[(&str, &str)] = &[
+    ("Artist - Album", "{artist} - {album}"),
+    ("Artist - Year - Album", "{artist} - {year} - {album}"),
+    ("Artist - Album (Year)", "{artist} - {album} ({year})"),
+    (
+        "Artist - Album (Year) [Format]",
+        "{artist} - {album} ({year}) [{format}]",
+    ),
+    ("Artist/Album", "{artist}/{album}"),
+    ("Artist/Year - Album", "{artist}/{year} - {album}"),
+    (
+        "Artist/Year - Album [Format]",
+        "{artist}/{year} - {album} [{format}]",
+    ),
+    (
+        "Label/Catalog - Artist - Album",
+        "{label}/{catalog} - {artist} - {album}",
+    ),
+];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum FolderPatternError {
+    #[error("Unknown placeholder {{{0}}}")]
+    UnknownPlaceholder(String),
+    #[error("{{{0}}} appears more than once")]
+    DuplicatePlaceholder(String),
+    #[error("A {{ is never closed")]
+    Unclosed,
+    #[error("Two placeholders need text between them")]
+    AdjacentPlaceholders,
+    #[error("The pattern has no placeholders")]
+    NoPlaceholders,
+}
+
+/// What a template read from a folder path. Blank values are left out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PatternFields {
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub year: Option<u32>,
+    pub format: Option<String>,
+    pub label: Option<String>,
+    pub catalog_number: Option<String>,
+}
+
+/// A parsed folder-name template
+#[derive(Debug, Clone)]
+pub struct FolderPattern {
+    template: String,
+    regex: Regex,
+    /// Folders the template spans
+    depth: usize,
+}
+
+impl FolderPattern {
+    pub fn parse(template: &str) -> Result<Self, FolderPatternError> {
+        let template = template.trim();
+        let mut pattern = String::from("^");
+        let mut seen: Vec<&str> = Vec::new();
+        let mut last_was_placeholder = false;
+        let mut rest = template;
+
+        while let Some(open) = rest.find('{') {
+            let literal = &rest[..open];
+            pattern.push_str(&regex::escape(literal));
+            if last_was_placeholder && literal.is_empty() {
+                return Err(FolderPatternError::AdjacentPlaceholders);
+            }
+            let close = rest[open..].find('}').ok_or(FolderPatternError::Unclosed)? + open;
+            let name = rest[open + 1..close].trim();
+            let group = match name {
+                "*" => "[^/]*?".to_string(),
+                "year" => r"(?P<year>\d{4})".to_string(),
+                "artist" | "album" | "format" | "label" | "catalog" => {
+                    format!("(?P<{}>[^/]+?)", name)
+                }
+                _ => return Err(FolderPatternError::UnknownPlaceholder(name.to_string())),
+            };
+            if name != "*" {
+                if seen.contains(&name) {
+                    return Err(FolderPatternError::DuplicatePlaceholder(name.to_string()));
+                }
+                seen.push(name);
+            }
+            pattern.push_str(&group);
+            last_was_placeholder = true;
+            rest = &rest[close + 1..];
+        }
+        pattern.push_str(&regex::escape(rest));
+        pattern.push('$');
+
+        if seen.is_empty() {
+            return Err(FolderPatternError::NoPlaceholders);
+        }
+        Ok(FolderPattern {
+            template: template.to_string(),
+            // Every piece is escaped or a fixed group, so this always compiles
+            regex: Regex::new(&pattern).expect("folder pattern regex"),
+            depth: template.split('/').count(),
+        })
+    }
+
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Read fields from the last folders of `path`, if they fit the template
+    pub fn match_path(&self, path: &Path) -> Option<PatternFields> {
+        let folders: Vec<&str> = path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect();
+        if folders.len() < self.depth {
+            return None;
+        }
+        let tail = folders[folders.len() - self.depth..].join("/");
+        let captures = self.regex.captures(&tail)?;
+        let field = |name: &str| {
+            captures
+                .name(name)
+                .map(|m| m.as_str().trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Some(PatternFields {
+            artist: field("artist"),
+            album: field("album"),
+            year: field("year").and_then(|y| y.parse().ok()),
+            format: field("format"),
+            label: field("label"),
+            catalog_number: field("catalog"),
+        })
+    }
+}
+
+/// Parse saved templates, leaving out any that no longer parse
+pub fn parse_folder_patterns(templates: &[String]) -> Vec<FolderPattern> {
+    templates
+        .iter()
+        .filter_map(|template| match FolderPattern::parse(template) {
+            Ok(pattern) => Some(pattern),
+            Err(e) => {
+                warn!("Skipping folder pattern {:?}: {}", template, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Fields from the first template that fits `path`
+pub fn match_folder_patterns(patterns: &[FolderPattern], path: &Path) -> Option<PatternFields> {
+    patterns.iter().find_map(|p| p.match_path(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_pattern_reads_parent_folder() {
+        let pattern = FolderPattern::parse("{artist}/{year} - {album} [{format}]").unwrap();
+        let fields = pattern
+            .match_path(Path::new(
+                "/music/Miles Davis/1959 - Kind of Blue [FLAC 24-96]",
+            ))
+            .unwrap();
+
+        assert_eq!(fields.artist.as_deref(), Some("Miles Davis"));
+        assert_eq!(fields.year, Some(1959));
+        assert_eq!(fields.album.as_deref(), Some("Kind of Blue"));
+        assert_eq!(fields.format.as_deref(), Some("FLAC 24-96"));
+    }
+
+    #[test]
+    fn test_literal_text_must_match() {
+        let pattern = FolderPattern::parse("{artist} - {album} ({year})").unwrap();
+        assert!(pattern
+            .match_path(Path::new("Can - Tago Mago (1971)"))
+            .is_some());
+        assert_eq!(pattern.match_path(Path::new("Can - Tago Mago")), None);
+        assert_eq!(
+            pattern.match_path(Path::new("Can - Tago Mago (Remaster)")),
+            None
+        );
+        // Too few folders for a nested template
+        let nested = FolderPattern::parse("{artist}/{album}").unwrap();
+        assert_eq!(nested.match_path(Path::new("Tago Mago")), None);
+    }
+
+    #[test]
+    fn test_first_matching_pattern_wins() {
+        let patterns = vec![
+            FolderPattern::parse("{artist} - {album} ({year})").unwrap(),
+            FolderPattern::parse("{artist} - {album}").unwrap(),
+        ];
+        let fields = match_folder_patterns(&patterns, Path::new("Neu! - Neu! 75 (1975)")).unwrap();
+        assert_eq!(fields.album.as_deref(), Some("Neu! 75"));
+        assert_eq!(fields.year, Some(1975));
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        assert_eq!(
+            FolderPattern::parse("{artist} - {title}").unwrap_err(),
+            FolderPatternError::UnknownPlaceholder("title".to_string())
+        );
+        assert_eq!(
+            FolderPattern::parse("{artist}/{artist} - {album}").unwrap_err(),
+            FolderPatternError::DuplicatePlaceholder("artist".to_string())
+        );
+        assert_eq!(
+            FolderPattern::parse("{artist - {album}").unwrap_err(),
+            FolderPatternError::UnknownPlaceholder("artist - {album".to_string())
+        );
+        assert_eq!(
+            FolderPattern::parse("{artist} - {album").unwrap_err(),
+            FolderPatternError::Unclosed
+        );
+        assert_eq!(
+            FolderPattern::parse("{artist}{album}").unwrap_err(),
+            FolderPatternError::AdjacentPlaceholders
+        );
+        assert_eq!(
+            FolderPattern::parse("Music").unwrap_err(),
+            FolderPatternError::NoPlaceholders
+        );
+    }
+
+    #[test]
+    fn test_presets_parse() {
+        for (name, template) in FOLDER_PATTERN_PRESETS {
+            assert!(FolderPattern::parse(template).is_ok(), "{}", name);
+        }
+    }
+}
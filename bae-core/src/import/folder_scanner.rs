@@ -4,6 +4,7 @@
 //! 1. Single release (flat) - audio files in root, optional artwork subfolders
 //! 2. Single release (multi-disc) - disc subfolders with audio, optional artwork
 //! 3. Collections - recursive tree where leaves are single releases
+use super::disc_image::DiscImage;
 use super::file_validation;
 use crate::cue_flac::CueFlacProcessor;
 use std::fs;
@@ -13,6 +14,7 @@ const MAX_RECURSION_DEPTH: usize = 10;
 const AUDIO_EXTENSIONS: &[&str] = &["flac"];
 const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp", "gif", "bmp"];
 const DOCUMENT_EXTENSIONS: &[&str] = &["cue", "log", "txt", "nfo", "m3u", "m3u8"];
+const VIDEO_EXTENSIONS: &[&str] = &["mkv", "mp4", "m4v", "mov", "webm"];
 /// A file discovered during folder scanning
 #[derive(Debug, Clone)]
 pub struct ScannedFile {
@@ -55,6 +57,8 @@ pub struct CategorizedFiles {
     pub artwork: Vec<ScannedFile>,
     /// Document files (.log, .txt, .nfo) - CUE files in pairs are NOT included here
     pub documents: Vec<ScannedFile>,
+    /// Video extras (.mkv, .mp4) - music videos, DVD bonus content
+    pub videos: Vec<ScannedFile>,
     /// Number of audio files that are corrupt or incomplete (0-byte, bad headers,
     /// or truncated). Not included in `audio` — counted here so the UI can
     /// explain why a release is incomplete.
@@ -95,6 +99,13 @@ fn is_document_file(path: &Path) -> bool {
         .map(|ext| DOCUMENT_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
         .unwrap_or(false)
 }
+/// Check if a file is a video extra (.mkv, .mp4, ...)
+pub fn is_video_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| VIDEO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
 /// Check if a file is a CUE file
 fn is_cue_file(path: &Path) -> bool {
     path.extension()
@@ -126,6 +137,12 @@ fn has_audio_files(dir: &Path) -> Result<bool, String> {
                 }
             }
         }
+        if path.is_file()
+            && is_cue_file(&path)
+            && DiscImage::from_cue(&path).is_some_and(|image| image.bin_path.is_file())
+        {
+            return Ok(true);
+        }
     }
     Ok(false)
 }
@@ -198,29 +215,33 @@ fn subdirs_are_disc_folders(dir: &Path) -> Result<bool, String> {
         }
     }
 
-    if subdir_names.is_empty() {
-        return Ok(false);
+    Ok(names_look_like_disc_folders(&subdir_names))
+}
+
+/// Name heuristic behind [`subdirs_are_disc_folders`]: numbered, or short
+/// with a common prefix (CD1/CD2, Disc 1/Disc 2, Side A/Side B)
+fn names_look_like_disc_folders(names: &[String]) -> bool {
+    if names.is_empty() {
+        return false;
     }
 
     // All just numbers? (1, 2, 3 or 01, 02, 03)
-    if subdir_names
+    if names
         .iter()
         .all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
     {
-        return Ok(true);
+        return true;
     }
 
     // Check if names are short (disc folders are typically short)
-    let all_short = subdir_names
-        .iter()
-        .all(|n| n.len() <= MAX_DISC_FOLDER_NAME_LENGTH);
+    let all_short = names.iter().all(|n| n.len() <= MAX_DISC_FOLDER_NAME_LENGTH);
     if !all_short {
-        return Ok(false);
+        return false;
     }
 
     // Check for a meaningful common prefix (at least 2 chars)
-    let prefix = longest_common_prefix(&subdir_names);
-    Ok(prefix.len() >= 2)
+    let prefix = longest_common_prefix(names);
+    prefix.len() >= 2
 }
 
 /// Determine if a directory is a leaf (single release).
@@ -285,6 +306,36 @@ where
     }
     Ok(())
 }
+/// Release folders in a list of file paths that aren't on disk yet, such as
+/// a torrent's file list. Uses the same leaf rules as the folder scan: a
+/// folder with audio is a release, unless it's one of several disc folders
+/// whose parent has no audio of its own, in which case the parent is.
+pub fn release_folders_from_paths(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let audio_dirs: std::collections::BTreeSet<PathBuf> = paths
+        .iter()
+        .filter(|p| is_audio_file(p))
+        .filter_map(|p| p.parent().map(Path::to_path_buf))
+        .collect();
+
+    let mut releases = std::collections::BTreeSet::new();
+    for dir in &audio_dirs {
+        let parent = dir.parent().filter(|p| !p.as_os_str().is_empty());
+        let disc_parent = parent.filter(|parent| {
+            if audio_dirs.contains(*parent) {
+                return false;
+            }
+            let siblings: Vec<String> = audio_dirs
+                .iter()
+                .filter(|d| d.parent() == Some(*parent))
+                .filter_map(|d| d.file_name().and_then(|n| n.to_str()))
+                .map(str::to_string)
+                .collect();
+            siblings.len() > 1 && names_look_like_disc_folders(&siblings)
+        });
+        releases.insert(disc_parent.unwrap_or(dir).to_path_buf());
+    }
+    releases.into_iter().collect()
+}
 /// Scan a folder for candidates and invoke callback as each is found.
 pub fn scan_for_candidates_with_callback<F>(
     root: PathBuf,
@@ -300,13 +351,14 @@ where
 /// Collect all files from a release directory and categorize them
 ///
 /// This collects files recursively within a single release, preserving relative paths,
-/// and categorizes them into audio (CUE/FLAC pairs or track files), artwork, and documents.
+/// and categorizes them into audio (CUE/FLAC pairs or track files), artwork, documents and videos.
 /// Unrecognized file types are ignored.
 pub fn collect_release_files(release_root: &Path) -> Result<CategorizedFiles, String> {
     let mut all_audio: Vec<ScannedFile> = Vec::new();
     let mut all_cue: Vec<ScannedFile> = Vec::new();
     let mut artwork: Vec<ScannedFile> = Vec::new();
     let mut documents: Vec<ScannedFile> = Vec::new();
+    let mut videos: Vec<ScannedFile> = Vec::new();
     let mut bad_audio_count: usize = 0;
     let mut bad_image_count: usize = 0;
     collect_files_into_vectors(
@@ -316,9 +368,18 @@ pub fn collect_release_files(release_root: &Path) -> Result<CategorizedFiles, St
         &mut all_cue,
         &mut artwork,
         &mut documents,
+        &mut videos,
         &mut bad_audio_count,
         &mut bad_image_count,
     )?;
+    let mut image_pairs = Vec::new();
+    all_cue.retain(|cue| match disc_image_pair(release_root, cue) {
+        Some(pair) => {
+            image_pairs.push(pair);
+            false
+        }
+        None => true,
+    });
     let audio_paths: Vec<PathBuf> = all_audio.iter().map(|f| f.path.clone()).collect();
     let cue_paths: Vec<PathBuf> = all_cue.iter().map(|f| f.path.clone()).collect();
     let all_paths: Vec<PathBuf> = audio_paths
@@ -328,8 +389,8 @@ pub fn collect_release_files(release_root: &Path) -> Result<CategorizedFiles, St
         .collect();
     let detected_pairs = CueFlacProcessor::detect_cue_flac_from_paths(&all_paths)
         .map_err(|e| format!("CUE/FLAC detection failed: {}", e))?;
-    let audio = if !detected_pairs.is_empty() {
-        let mut pairs = Vec::new();
+    let audio = if !detected_pairs.is_empty() || !image_pairs.is_empty() {
+        let mut pairs = image_pairs;
         let mut used_audio_paths = std::collections::HashSet::new();
         let mut used_cue_paths = std::collections::HashSet::new();
         for pair in detected_pairs {
@@ -374,14 +435,52 @@ pub fn collect_release_files(release_root: &Path) -> Result<CategorizedFiles, St
     };
     artwork.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
     documents.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    videos.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
     Ok(CategorizedFiles {
         audio,
         artwork,
         documents,
+        videos,
         bad_audio_count,
         bad_image_count,
     })
 }
+/// Pair a BIN/CUE image's CUE with its audio: the extracted FLAC and CUE once
+/// they exist, the raw BIN until the import extracts it
+fn disc_image_pair(release_root: &Path, cue: &ScannedFile) -> Option<ScannedCueFlacPair> {
+    let image = DiscImage::from_cue(&cue.path)?;
+    let scanned = |path: &Path| -> Option<ScannedFile> {
+        let size = fs::metadata(path).ok()?.len();
+        let relative_path = path
+            .strip_prefix(release_root)
+            .ok()?
+            .to_string_lossy()
+            .to_string();
+        Some(ScannedFile {
+            path: path.to_path_buf(),
+            relative_path,
+            size,
+        })
+    };
+    let (cue_file, audio_file) = if image.is_extracted(release_root) {
+        let (flac_path, cue_path) = image.extracted_paths(release_root);
+        (scanned(&cue_path)?, scanned(&flac_path)?)
+    } else {
+        (cue.clone(), scanned(&image.bin_path)?)
+    };
+    let track_count = match CueFlacProcessor::parse_cue_sheet(&cue_file.path) {
+        Ok(cue_sheet) => cue_sheet.tracks.len(),
+        Err(e) => {
+            warn!("Failed to parse CUE sheet {:?}: {}", cue_file.path, e);
+            0
+        }
+    };
+    Some(ScannedCueFlacPair {
+        cue_file,
+        audio_file,
+        track_count,
+    })
+}
 /// Recursively collect files into separate vectors by type
 fn collect_files_into_vectors(
     current_dir: &Path,
@@ -390,6 +489,7 @@ fn collect_files_into_vectors(
     cue: &mut Vec<ScannedFile>,
     artwork: &mut Vec<ScannedFile>,
     documents: &mut Vec<ScannedFile>,
+    videos: &mut Vec<ScannedFile>,
     bad_audio_count: &mut usize,
     bad_image_count: &mut usize,
 ) -> Result<(), String> {
@@ -447,6 +547,8 @@ fn collect_files_into_vectors(
                 artwork.push(file);
             } else if is_document_file(&path) {
                 documents.push(file);
+            } else if is_video_file(&path) {
+                videos.push(file);
             }
             // Other file types are ignored
         } else if path.is_dir() {
@@ -457,6 +559,7 @@ fn collect_files_into_vectors(
                 cue,
                 artwork,
                 documents,
+                videos,
                 bad_audio_count,
                 bad_image_count,
             )?;
@@ -488,6 +591,43 @@ mod tests {
         assert!(!is_audio_file(Path::new("cover.jpg")));
         assert!(!is_audio_file(Path::new("notes.txt")));
     }
+    #[test]
+    fn test_release_folders_from_paths_discography() {
+        let paths: Vec<PathBuf> = [
+            "Artist - Discography/1999 - First/01.flac",
+            "Artist - Discography/1999 - First/02.flac",
+            "Artist - Discography/1999 - First/cover.jpg",
+            "Artist - Discography/2003 - Second/CD1/01.flac",
+            "Artist - Discography/2003 - Second/CD2/01.flac",
+            "Artist - Discography/2003 - Second/Scans/front.jpg",
+            "Artist - Discography/info.nfo",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+
+        assert_eq!(
+            release_folders_from_paths(&paths),
+            vec![
+                PathBuf::from("Artist - Discography/1999 - First"),
+                PathBuf::from("Artist - Discography/2003 - Second"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_release_folders_from_paths_single_album() {
+        let paths: Vec<PathBuf> = ["Album/01.flac", "Album/02.flac", "Album/album.cue"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+
+        assert_eq!(
+            release_folders_from_paths(&paths),
+            vec![PathBuf::from("Album")]
+        );
+    }
+
     #[test]
     fn test_is_cue_file() {
         assert!(is_cue_file(Path::new("album.cue")));
@@ -536,6 +676,34 @@ mod tests {
         assert!(files.documents.is_empty());
     }
 
+    #[test]
+    fn test_collect_release_files_keeps_videos() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+
+        std::fs::write(root.join("track.flac"), fake_flac()).unwrap();
+        let extras = root.join("Extras");
+        std::fs::create_dir(&extras).unwrap();
+        std::fs::write(extras.join("Making Of.MKV"), b"video").unwrap();
+        std::fs::write(root.join("Music Video.mp4"), b"video").unwrap();
+
+        let files = collect_release_files(root).unwrap();
+
+        let video_paths: Vec<_> = files
+            .videos
+            .iter()
+            .map(|f| f.relative_path.as_str())
+            .collect();
+        assert_eq!(
+            video_paths,
+            vec![
+                Path::new("Extras").join("Making Of.MKV").to_str().unwrap(),
+                "Music Video.mp4"
+            ]
+        );
+        assert!(files.documents.is_empty());
+    }
+
     /// Creates a minimal CUE file content that references the given FLAC filename
     fn make_cue_content(flac_filename: &str, title: &str) -> String {
         format!(
@@ -552,6 +720,26 @@ FILE "{flac_filename}" WAVE
         )
     }
 
+    #[test]
+    fn test_bin_cue_image_pairs_with_its_bin() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+
+        let cue = make_cue_content("Album.bin", "Album").replace("WAVE", "BINARY");
+        std::fs::write(root.join("Album.cue"), cue).unwrap();
+        std::fs::write(root.join("Album.bin"), vec![0u8; 2352]).unwrap();
+
+        let files = collect_release_files(root).unwrap();
+
+        let AudioContent::CueFlacPairs(pairs) = &files.audio else {
+            panic!("expected CUE/FLAC pairs");
+        };
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].cue_file.relative_path, "Album.cue");
+        assert_eq!(pairs[0].audio_file.relative_path, "Album.bin");
+        assert_eq!(pairs[0].track_count, 2);
+    }
+
     #[test]
     fn test_collection_of_albums_detected_as_separate_candidates() {
         // This replicates a common layout: a collection folder containing multiple
@@ -1,25 +1,37 @@
+#[cfg(feature = "cd-rip")]
+use crate::cd::BusyDrives;
 use crate::cue_flac::CueFlacProcessor;
 #[cfg(feature = "torrent")]
 use crate::db::DbTorrent;
-use crate::db::{Database, DbImport, ImageSource, ImportOperationStatus};
+use crate::db::{
+    Database, DbAlbumArtist, DbArtist, DbImport, ImageSource, ImportKind, ImportOperationStatus,
+};
 use crate::discogs::DiscogsRelease;
 use crate::import::cover_art::download_cover_art_to_bae_folder;
+use crate::import::disc_image::pending_disc_images;
 #[cfg(feature = "cd-rip")]
 use crate::import::discogs_parser::parse_discogs_release;
+use crate::import::discogs_parser::ParsedAlbum;
 use crate::import::folder_scanner::DetectedCandidate;
+use crate::import::manual_release::ManualRelease;
 #[cfg(feature = "cd-rip")]
 use crate::import::musicbrainz_parser::fetch_and_parse_mb_release;
+use crate::import::needle_drop::{split_sides, split_tracks, SideSplit};
 use crate::import::progress::ImportProgressHandle;
+use crate::import::queue::{ImportQueue, QueueAction};
+use crate::import::stored_release::{read_stored_release, write_stored_release, StoredRelease};
+use crate::import::throttle::ImportThrottle;
 use crate::import::track_to_file_mapper::map_tracks_to_files;
 #[cfg(feature = "torrent")]
 use crate::import::types::TorrentSource;
 use crate::import::types::{
     DiscoveredFile, ImportCommand, ImportProgress, ImportRequest, PrepareStep, TrackFile,
 };
+use crate::library::credits::{normalize_album_artists, CreditRules};
 use crate::library::{LibraryManager, SharedLibraryManager};
 use crate::musicbrainz::MbRelease;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, info, warn};
 /// Handle for sending import requests and subscribing to progress updates
@@ -33,6 +45,15 @@ pub struct ImportServiceHandle {
     pub runtime_handle: tokio::runtime::Handle,
     pub scan_tx: mpsc::UnboundedSender<ScanRequest>,
     pub scan_events_tx: broadcast::Sender<ScanEvent>,
+    /// Featured-artist credit rules applied to each import's metadata
+    pub credit_rules: Arc<RwLock<CreditRules>>,
+    /// Paces storing and ripping, shared with the import service
+    pub throttle: ImportThrottle,
+    /// Import queue, steered from here and run by the import service
+    pub queue: ImportQueue,
+    /// CD drives with a rip in progress
+    #[cfg(feature = "cd-rip")]
+    pub busy_drives: BusyDrives,
 }
 
 #[derive(Debug, Clone)]
@@ -76,6 +97,8 @@ impl ImportServiceHandle {
         runtime_handle: tokio::runtime::Handle,
         scan_tx: mpsc::UnboundedSender<ScanRequest>,
         scan_events_tx: broadcast::Sender<ScanEvent>,
+        throttle: ImportThrottle,
+        queue: ImportQueue,
     ) -> Self {
         let progress_handle = ImportProgressHandle::new(progress_rx, runtime_handle.clone());
         Self {
@@ -87,9 +110,126 @@ impl ImportServiceHandle {
             runtime_handle,
             scan_tx,
             scan_events_tx,
+            credit_rules: Arc::new(RwLock::new(CreditRules::default())),
+            throttle,
+            queue,
+            #[cfg(feature = "cd-rip")]
+            busy_drives: BusyDrives::default(),
+        }
+    }
+
+    /// Use these credit rules for imports from now on
+    pub fn set_credit_rules(&self, rules: CreditRules) {
+        *self.credit_rules.write().unwrap() = rules;
+    }
+
+    /// Credit rules imports currently use
+    pub fn credit_rules(&self) -> CreditRules {
+        self.credit_rules.read().unwrap().clone()
+    }
+
+    /// Cap imports to one file or disc at a time, resting between them
+    pub fn set_low_power_import(&self, low_power: bool) {
+        self.throttle.set_low_power(low_power);
+    }
+
+    /// Cap the file data imports hold in memory at once
+    pub fn set_import_memory_budget(&self, megabytes: u32) {
+        self.throttle.set_memory_budget_mb(megabytes);
+    }
+
+    /// How many queued imports run at once
+    pub fn set_max_concurrent_imports(&self, max_concurrent: usize) {
+        self.queue.set_max_concurrent(max_concurrent);
+    }
+
+    /// Hold a queued or running import until it's resumed. A running import
+    /// starts over when resumed, so only folder imports can be paused once
+    /// they've started.
+    pub async fn pause_import(&self, import_id: &str) -> Result<(), String> {
+        let import = self.get_queued_import(import_id).await?;
+        match import.status {
+            ImportOperationStatus::Queued if import.kind != ImportKind::Cd => {}
+            ImportOperationStatus::Importing if import.kind == ImportKind::Folder => {}
+            _ => return Err("This import can't be paused".to_string()),
+        }
+        self.queue.request(import_id, QueueAction::Pause);
+        Ok(())
+    }
+
+    /// Put a paused import back at the end of the queue
+    pub async fn resume_import(&self, import_id: &str) -> Result<(), String> {
+        let import = self.get_queued_import(import_id).await?;
+        if import.status != ImportOperationStatus::Paused {
+            return Err("Import isn't paused".to_string());
+        }
+        self.queue.request(import_id, QueueAction::Resume);
+        Ok(())
+    }
+
+    /// Stop an unfinished import and remove its release. CD rips and
+    /// downloads can't be cancelled once they've started.
+    pub async fn cancel_import(&self, import_id: &str) -> Result<(), String> {
+        let import = self.get_queued_import(import_id).await?;
+        match import.status {
+            ImportOperationStatus::Queued | ImportOperationStatus::Paused
+                if import.kind != ImportKind::Cd => {}
+            ImportOperationStatus::Importing if import.kind == ImportKind::Folder => {}
+            _ => return Err("This import can't be cancelled".to_string()),
+        }
+        self.queue.request(import_id, QueueAction::Cancel);
+        Ok(())
+    }
+
+    /// Queue a failed folder import again, from the start
+    pub async fn retry_import(&self, import_id: &str) -> Result<(), String> {
+        let import = self.get_queued_import(import_id).await?;
+        if import.status != ImportOperationStatus::Failed
+            || import.kind != ImportKind::Folder
+            || import.release_id.is_none()
+        {
+            return Err("Only failed folder imports can be retried".to_string());
         }
+        self.queue.request(import_id, QueueAction::Retry);
+        Ok(())
     }
 
+    async fn get_queued_import(&self, import_id: &str) -> Result<DbImport, String> {
+        self.database
+            .get_import(import_id)
+            .await
+            .map_err(|e| format!("Failed to load import: {}", e))?
+            .ok_or_else(|| format!("Import not found: {}", import_id))
+    }
+
+    async fn set_import_status(
+        &self,
+        import_id: &str,
+        status: ImportOperationStatus,
+        kind: ImportKind,
+    ) -> Result<(), String> {
+        self.database
+            .update_import_status(import_id, status)
+            .await
+            .map_err(|e| format!("Failed to update import status: {}", e))?;
+        let _ = self.progress_tx.send(ImportProgress::StatusChanged {
+            import_id: import_id.to_string(),
+            status,
+            kind,
+        });
+        Ok(())
+    }
+
+    fn normalize_credits(
+        &self,
+        artists: Vec<DbArtist>,
+        album_artists: Vec<DbAlbumArtist>,
+    ) -> (Vec<DbArtist>, Vec<DbAlbumArtist>) {
+        normalize_album_artists(&self.credit_rules(), artists, album_artists)
+    }
+
+    /// Scan a folder for releases. Archives are extracted and their contents
+    /// scanned the same way.
     pub fn enqueue_folder_scan(&self, path: std::path::PathBuf) -> Result<(), String> {
         self.scan_tx
             .send(ScanRequest { path })
@@ -113,26 +253,47 @@ impl ImportServiceHandle {
                 import_id,
                 discogs_release,
                 mb_release,
+                manual_release,
                 folder,
                 master_year,
                 cover_art_url,
                 storage_profile_id,
                 selected_cover_filename,
+                excluded_files,
+                needle_drop_sides,
             } => {
                 self.send_folder_request(
                     import_id,
                     discogs_release,
                     mb_release,
+                    manual_release,
                     folder,
                     master_year,
                     cover_art_url,
                     storage_profile_id,
                     selected_cover_filename,
+                    excluded_files,
+                    needle_drop_sides,
+                )
+                .await
+            }
+            ImportRequest::Replay {
+                import_id,
+                folder,
+                storage_profile_id,
+                selected_cover_filename,
+            } => {
+                self.send_replay_request(
+                    import_id,
+                    folder,
+                    storage_profile_id,
+                    selected_cover_filename,
                 )
                 .await
             }
             #[cfg(feature = "torrent")]
             ImportRequest::Torrent {
+                import_id,
                 torrent_source,
                 discogs_release,
                 mb_release,
@@ -144,6 +305,7 @@ impl ImportServiceHandle {
                 selected_cover_filename,
             } => {
                 self.send_torrent_request(
+                    import_id,
                     torrent_source,
                     discogs_release,
                     mb_release,
@@ -158,22 +320,28 @@ impl ImportServiceHandle {
             }
             #[cfg(feature = "cd-rip")]
             ImportRequest::CD {
+                import_id,
                 discogs_release,
                 mb_release,
+                manual_release,
                 drive_path,
                 master_year,
                 cover_art_url,
                 storage_profile_id,
                 selected_cover_filename,
+                eject_when_ripped,
             } => {
                 self.send_cd_request(
+                    import_id,
                     discogs_release,
                     mb_release,
+                    manual_release,
                     drive_path,
                     master_year,
                     cover_art_url,
                     storage_profile_id,
                     selected_cover_filename,
+                    eject_when_ripped,
                 )
                 .await
             }
@@ -184,16 +352,20 @@ impl ImportServiceHandle {
         import_id: String,
         discogs_release: Option<DiscogsRelease>,
         mb_release: Option<MbRelease>,
+        manual_release: Option<ManualRelease>,
         folder: std::path::PathBuf,
         master_year: u32,
         cover_art_url: Option<String>,
         storage_profile_id: Option<String>,
         selected_cover_filename: Option<String>,
+        excluded_files: Vec<std::path::PathBuf>,
+        needle_drop_sides: Vec<SideSplit>,
     ) -> Result<(String, String), String> {
-        if discogs_release.is_none() && mb_release.is_none() {
-            return Err("Either discogs_release or mb_release must be provided".to_string());
+        if discogs_release.is_none() && mb_release.is_none() && manual_release.is_none() {
+            return Err(
+                "One of discogs_release, mb_release or manual_release must be provided".to_string(),
+            );
         }
-        let library_manager = self.library_manager.get();
         let (album_title, artist_name) = if let Some(ref discogs_rel) = discogs_release {
             let artist = discogs_rel
                 .artists
@@ -204,14 +376,32 @@ impl ImportServiceHandle {
             (discogs_rel.title.clone(), artist)
         } else if let Some(ref mb_rel) = mb_release {
             (mb_rel.title.clone(), mb_rel.artist.clone())
+        } else if let Some(ref manual) = manual_release {
+            (manual.title.clone(), manual.artist.clone())
         } else {
             return Err("No release provided".to_string());
         };
+        // Split sides are left out too; their tracks are picked up again
+        // from the `.bae` folder if the import is rebuilt
+        let left_out_paths: Vec<std::path::PathBuf> = excluded_files
+            .iter()
+            .chain(needle_drop_sides.iter().map(|side| &side.path))
+            .cloned()
+            .collect();
+        let left_out = left_out_paths
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
         let db_import = DbImport::new(
             &import_id,
             &album_title,
             &artist_name,
             folder.to_str().unwrap_or(""),
+        )
+        .with_options(
+            storage_profile_id.clone(),
+            selected_cover_filename.clone(),
+            left_out,
         );
         self.database
             .insert_import(&db_import)
@@ -242,9 +432,13 @@ impl ImportServiceHandle {
                 use crate::import::musicbrainz_parser::fetch_and_parse_mb_release;
                 fetch_and_parse_mb_release(&mb_rel.release_id, master_year, cover_art_url.clone())
                     .await?
+            } else if let Some(ref manual) = manual_release {
+                use crate::import::manual_release::parse_manual_release;
+                parse_manual_release(manual, cover_art_url.clone())?
             } else {
                 return Err("No release provided".to_string());
             };
+        let (artists, album_artists) = self.normalize_credits(artists, album_artists);
         if let Some(ref url) = cover_art_url {
             emit_preparing(PrepareStep::DownloadingCoverArt);
             let source = if mb_release.is_some() {
@@ -261,8 +455,174 @@ impl ImportServiceHandle {
                 }
             }
         }
+        let disc_images = pending_disc_images(&folder)?;
+        if !disc_images.is_empty() {
+            emit_preparing(PrepareStep::ExtractingDiscImages);
+            let release_root = folder.clone();
+            tokio::task::spawn_blocking(move || {
+                disc_images
+                    .iter()
+                    .try_for_each(|image| image.extract(&release_root))
+            })
+            .await
+            .map_err(|e| format!("Disc image extraction task failed: {}", e))??;
+        }
+        let side_tracks = if needle_drop_sides.is_empty() {
+            Vec::new()
+        } else {
+            emit_preparing(PrepareStep::SplittingSides);
+            let release_root = folder.clone();
+            let sides = needle_drop_sides.clone();
+            tokio::task::spawn_blocking(move || split_sides(&release_root, &sides))
+                .await
+                .map_err(|e| format!("Side splitting task failed: {}", e))??
+        };
         emit_preparing(PrepareStep::DiscoveringFiles);
-        let discovered_files = discover_folder_files(&folder)?;
+        let mut discovered_files = discover_folder_files(&folder)?;
+        // Split sides import as their tracks
+        discovered_files.retain(|f| {
+            !excluded_files.contains(&f.path)
+                && !needle_drop_sides.iter().any(|side| side.path == f.path)
+        });
+        discovered_files.extend(side_tracks);
+        let stored = StoredRelease::new(
+            artist_name,
+            (
+                db_album.clone(),
+                db_release.clone(),
+                db_tracks.clone(),
+                artists.clone(),
+                album_artists.clone(),
+            ),
+            &folder,
+            &left_out_paths,
+            selected_cover_filename.clone(),
+        );
+        let queued = self
+            .queue_folder_release(
+                import_id,
+                (db_album, db_release, db_tracks, artists, album_artists),
+                folder.clone(),
+                discovered_files,
+                storage_profile_id,
+                selected_cover_filename,
+                emit_preparing,
+            )
+            .await?;
+        // Lets the folder be imported again without matching, e.g. after
+        // restoring it from a backup
+        if let Err(e) = write_stored_release(&folder, &stored) {
+            warn!("Failed to store release metadata in folder: {}", e);
+        }
+        Ok(queued)
+    }
+
+    /// Import a folder again as the release stored in its `.bae` folder
+    async fn send_replay_request(
+        &self,
+        import_id: String,
+        folder: std::path::PathBuf,
+        storage_profile_id: Option<String>,
+        selected_cover_filename: Option<String>,
+    ) -> Result<(String, String), String> {
+        let stored = read_stored_release(&folder)?
+            .ok_or_else(|| format!("{:?} has no bae metadata", folder))?;
+        let library_manager = self.library_manager.get();
+        let existing = library_manager
+            .get_release(&stored.release.id)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+        if existing.is_some() {
+            return Err(format!(
+                "This release already exists in your library: {}",
+                stored.album.title
+            ));
+        }
+        let excluded_files = stored.excluded_paths(&folder);
+        let selected_cover_filename =
+            selected_cover_filename.or_else(|| stored.selected_cover_filename.clone());
+        let db_import = DbImport::new(
+            &import_id,
+            &stored.album.title,
+            &stored.artist_name,
+            folder.to_str().unwrap_or(""),
+        )
+        .with_options(
+            storage_profile_id.clone(),
+            selected_cover_filename.clone(),
+            excluded_files
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect(),
+        );
+        self.database
+            .insert_import(&db_import)
+            .await
+            .map_err(|e| format!("Failed to create import record: {}", e))?;
+        let emit_preparing = {
+            let import_id = import_id.clone();
+            let album_title = stored.album.title.clone();
+            let artist_name = stored.artist_name.clone();
+            let cover_art_url = stored.album.cover_art_url.clone();
+            let progress_tx = self.progress_tx.clone();
+            move |step: PrepareStep| {
+                let _ = progress_tx.send(ImportProgress::Preparing {
+                    import_id: import_id.clone(),
+                    step,
+                    album_title: album_title.clone(),
+                    artist_name: artist_name.clone(),
+                    cover_art_url: cover_art_url.clone(),
+                });
+            }
+        };
+        let disc_images = pending_disc_images(&folder)?;
+        if !disc_images.is_empty() {
+            emit_preparing(PrepareStep::ExtractingDiscImages);
+            let release_root = folder.clone();
+            tokio::task::spawn_blocking(move || {
+                disc_images
+                    .iter()
+                    .try_for_each(|image| image.extract(&release_root))
+            })
+            .await
+            .map_err(|e| format!("Disc image extraction task failed: {}", e))??;
+        }
+        emit_preparing(PrepareStep::DiscoveringFiles);
+        let mut discovered_files = discover_folder_files(&folder)?;
+        discovered_files.retain(|f| !excluded_files.contains(&f.path));
+        // Sides split by the first import are already in the `.bae` folder
+        discovered_files.extend(split_tracks(&folder));
+        self.queue_folder_release(
+            import_id,
+            (
+                stored.album,
+                stored.release,
+                stored.tracks,
+                stored.artists,
+                stored.album_artists,
+            ),
+            folder,
+            discovered_files,
+            storage_profile_id,
+            selected_cover_filename,
+            emit_preparing,
+        )
+        .await
+    }
+
+    /// Save a folder's parsed release to the database and queue its files
+    /// for import
+    async fn queue_folder_release(
+        &self,
+        import_id: String,
+        (db_album, db_release, db_tracks, artists, album_artists): ParsedAlbum,
+        folder: std::path::PathBuf,
+        discovered_files: Vec<DiscoveredFile>,
+        storage_profile_id: Option<String>,
+        selected_cover_filename: Option<String>,
+        emit_preparing: impl Fn(PrepareStep),
+    ) -> Result<(String, String), String> {
+        let library_manager = self.library_manager.get();
         emit_preparing(PrepareStep::ValidatingTracks);
         let mapping_result = map_tracks_to_files(&db_tracks, &discovered_files).await?;
         let tracks_to_files = mapping_result.track_files.clone();
@@ -320,16 +680,19 @@ impl ImportServiceHandle {
             db_release.id,
             db_tracks.len()
         );
-        self.database
-            .update_import_status(&import_id, ImportOperationStatus::Importing)
-            .await
-            .map_err(|e| format!("Failed to update import status: {}", e))?;
+        self.set_import_status(
+            &import_id,
+            ImportOperationStatus::Queued,
+            ImportKind::Folder,
+        )
+        .await?;
         let album_id = db_album.id.clone();
         let release_id = db_release.id.clone();
         self.requests_tx
             .send(ImportCommand::Folder {
                 db_album,
                 db_release,
+                folder,
                 tracks_to_files,
                 discovered_files,
                 cue_flac_metadata,
@@ -343,6 +706,7 @@ impl ImportServiceHandle {
     #[cfg(feature = "torrent")]
     async fn send_torrent_request(
         &self,
+        import_id: String,
         torrent_source: TorrentSource,
         discogs_release: Option<DiscogsRelease>,
         mb_release: Option<MbRelease>,
@@ -364,6 +728,42 @@ impl ImportServiceHandle {
             torrent_metadata.num_pieces,
             torrent_metadata.total_size_bytes
         );
+        let (album_title, artist_name) = if let Some(ref discogs_rel) = discogs_release {
+            let artist = discogs_rel
+                .artists
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            (discogs_rel.title.clone(), artist)
+        } else if let Some(ref mb_rel) = mb_release {
+            (mb_rel.title.clone(), mb_rel.artist.clone())
+        } else {
+            return Err("No release provided".to_string());
+        };
+        let db_import = DbImport::new(
+            &import_id,
+            &album_title,
+            &artist_name,
+            &torrent_metadata.torrent_name,
+        )
+        .with_kind(ImportKind::Torrent)
+        .with_options(
+            storage_profile_id.clone(),
+            selected_cover_filename.clone(),
+            Vec::new(),
+        );
+        self.database
+            .insert_import(&db_import)
+            .await
+            .map_err(|e| format!("Failed to create import record: {}", e))?;
+        let _ = self.progress_tx.send(ImportProgress::Preparing {
+            import_id: import_id.clone(),
+            step: PrepareStep::ParsingMetadata,
+            album_title,
+            artist_name,
+            cover_art_url: cover_art_url.clone(),
+        });
         let (db_album, db_release, db_tracks, artists, album_artists) =
             if let Some(ref discogs_rel) = discogs_release {
                 use crate::import::discogs_parser::parse_discogs_release;
@@ -375,6 +775,7 @@ impl ImportServiceHandle {
             } else {
                 return Err("No release provided".to_string());
             };
+        let (artists, album_artists) = self.normalize_credits(artists, album_artists);
         let temp_dir = std::env::temp_dir();
         let discovered_files: Vec<DiscoveredFile> = torrent_metadata
             .file_list
@@ -412,6 +813,10 @@ impl ImportServiceHandle {
             .insert_album_with_release_and_tracks(&db_album, &db_release, &db_tracks)
             .await
             .map_err(|e| format!("Database error: {}", e))?;
+        self.database
+            .link_import_to_release(&import_id, &db_release.id)
+            .await
+            .map_err(|e| format!("Failed to link import to release: {}", e))?;
         extract_and_store_durations(library_manager, &tracks_to_files).await?;
         for album_artist in &album_artists {
             let actual_artist_id = artist_id_map.get(&album_artist.artist_id).ok_or_else(|| {
@@ -446,6 +851,12 @@ impl ImportServiceHandle {
             db_release.id,
             db_tracks.len()
         );
+        self.set_import_status(
+            &import_id,
+            ImportOperationStatus::Queued,
+            ImportKind::Torrent,
+        )
+        .await?;
         let album_id = db_album.id.clone();
         let release_id = db_release.id.clone();
         self.requests_tx
@@ -459,6 +870,7 @@ impl ImportServiceHandle {
                 cover_art_url,
                 storage_profile_id,
                 selected_cover_filename,
+                import_id,
             })
             .map_err(|_| "Failed to queue validated torrent for import".to_string())?;
         Ok((album_id, release_id))
@@ -466,16 +878,21 @@ impl ImportServiceHandle {
     #[cfg(feature = "cd-rip")]
     async fn send_cd_request(
         &self,
+        import_id: String,
         discogs_release: Option<DiscogsRelease>,
         mb_release: Option<MbRelease>,
+        manual_release: Option<ManualRelease>,
         drive_path: std::path::PathBuf,
         master_year: u32,
         cover_art_url: Option<String>,
         storage_profile_id: Option<String>,
         selected_cover_filename: Option<String>,
+        eject_when_ripped: bool,
     ) -> Result<(String, String), String> {
-        if discogs_release.is_none() && mb_release.is_none() {
-            return Err("Either discogs_release or mb_release must be provided".to_string());
+        if discogs_release.is_none() && mb_release.is_none() && manual_release.is_none() {
+            return Err(
+                "One of discogs_release, mb_release or manual_release must be provided".to_string(),
+            );
         }
         let library_manager = self.library_manager.get();
         use crate::cd::CdDrive;
@@ -483,18 +900,63 @@ impl ImportServiceHandle {
             device_path: drive_path.clone(),
             name: drive_path.to_str().unwrap_or("Unknown").to_string(),
         };
+        let drive_claim = self
+            .busy_drives
+            .claim(&drive.device_path)
+            .ok_or_else(|| format!("{} is already ripping a disc", drive.name))?;
         let toc = drive
             .read_toc()
             .map_err(|e| format!("Failed to read CD TOC: {}", e))?;
+        let (album_title, artist_name) = if let Some(ref discogs_rel) = discogs_release {
+            let artist = discogs_rel
+                .artists
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            (discogs_rel.title.clone(), artist)
+        } else if let Some(ref mb_rel) = mb_release {
+            (mb_rel.title.clone(), mb_rel.artist.clone())
+        } else if let Some(ref manual) = manual_release {
+            (manual.title.clone(), manual.artist.clone())
+        } else {
+            return Err("No release provided".to_string());
+        };
+        let db_import = DbImport::new(
+            &import_id,
+            &album_title,
+            &artist_name,
+            drive_path.to_str().unwrap_or(""),
+        )
+        .with_kind(ImportKind::Cd);
+        self.database
+            .insert_import(&db_import)
+            .await
+            .map_err(|e| format!("Failed to create import record: {}", e))?;
+        let emit_preparing = |step: PrepareStep| {
+            let _ = self.progress_tx.send(ImportProgress::Preparing {
+                import_id: import_id.clone(),
+                step,
+                album_title: album_title.clone(),
+                artist_name: artist_name.clone(),
+                cover_art_url: cover_art_url.clone(),
+            });
+        };
+        emit_preparing(PrepareStep::ParsingMetadata);
         let (db_album, db_release, db_tracks, artists, album_artists) =
             if let Some(ref discogs_rel) = discogs_release {
                 parse_discogs_release(discogs_rel, master_year, cover_art_url.clone())?
             } else if let Some(ref mb_rel) = mb_release {
                 fetch_and_parse_mb_release(&mb_rel.release_id, master_year, cover_art_url.clone())
                     .await?
+            } else if let Some(ref manual) = manual_release {
+                use crate::import::manual_release::parse_manual_release;
+                parse_manual_release(manual, cover_art_url.clone())?
             } else {
                 return Err("No release provided".to_string());
             };
+        let (artists, album_artists) = self.normalize_credits(artists, album_artists);
+        emit_preparing(PrepareStep::SavingToDatabase);
         let mut artist_id_map = std::collections::HashMap::new();
         for artist in &artists {
             let parsed_id = artist.id.clone();
@@ -521,6 +983,10 @@ impl ImportServiceHandle {
             .insert_album_with_release_and_tracks(&db_album, &db_release, &db_tracks)
             .await
             .map_err(|e| format!("Database error: {}", e))?;
+        self.database
+            .link_import_to_release(&import_id, &db_release.id)
+            .await
+            .map_err(|e| format!("Failed to link import to release: {}", e))?;
         for album_artist in &album_artists {
             let actual_artist_id = artist_id_map.get(&album_artist.artist_id).ok_or_else(|| {
                 format!(
@@ -535,6 +1001,21 @@ impl ImportServiceHandle {
                 .await
                 .map_err(|e| format!("Failed to insert album-artist relationship: {}", e))?;
         }
+        if manual_release.is_some() {
+            let lookup = crate::db::DbDiscIdLookup {
+                release_id: db_release.id.clone(),
+                mb_discid: toc.disc_id.clone(),
+                attempts: 0,
+                last_error: None,
+                created_at: chrono::Utc::now(),
+            };
+            self.database
+                .insert_discid_lookup(&lookup)
+                .await
+                .map_err(|e| format!("Failed to queue DiscID lookup: {}", e))?;
+        }
+        self.set_import_status(&import_id, ImportOperationStatus::Importing, ImportKind::Cd)
+            .await?;
         let album_id = db_album.id.clone();
         let release_id = db_release.id.clone();
         self.requests_tx
@@ -546,6 +1027,9 @@ impl ImportServiceHandle {
                 toc,
                 storage_profile_id,
                 selected_cover_filename,
+                eject_when_ripped,
+                import_id,
+                drive_claim,
             })
             .map_err(|_| "Failed to queue validated CD import".to_string())?;
         Ok((album_id, release_id))
@@ -682,7 +1166,7 @@ fn extract_flac_duration(file_path: &Path) -> Option<i64> {
 /// - Collections - recursive tree where leaves are single releases
 ///
 /// Files are sorted by path for consistent ordering across runs.
-fn discover_folder_files(folder: &Path) -> Result<Vec<DiscoveredFile>, String> {
+pub(super) fn discover_folder_files(folder: &Path) -> Result<Vec<DiscoveredFile>, String> {
     use crate::import::folder_scanner::{self, AudioContent};
     let categorized = folder_scanner::collect_release_files(folder)?;
     let mut files: Vec<DiscoveredFile> = Vec::new();
@@ -720,5 +1204,11 @@ fn discover_folder_files(folder: &Path) -> Result<Vec<DiscoveredFile>, String> {
             size: f.size,
         });
     }
+    for f in categorized.videos {
+        files.push(DiscoveredFile {
+            path: f.path,
+            size: f.size,
+        });
+    }
     Ok(files)
 }
@@ -0,0 +1,151 @@
+use crate::db::{DbAlbum, DbAlbumArtist, DbArtist, DbRelease, DbTrack, ImportStatus};
+use crate::import::discogs_parser::ParsedAlbum;
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Release details typed in by the user, for music no metadata database
+/// knows about: own recordings, rehearsal tapes, bootlegs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManualRelease {
+    pub title: String,
+    pub artist: String,
+    pub year: Option<u32>,
+    /// Track titles in the order of the release's audio files
+    pub tracks: Vec<String>,
+}
+
+/// Turn a manually entered release into database models.
+///
+/// Tracks are numbered in the order given, which is also the order they're
+/// matched to audio files. Blank track titles become "Track N".
+///
+/// Returns: (album, release, tracks, artists, album_artists)
+pub fn parse_manual_release(
+    release: &ManualRelease,
+    cover_art_url: Option<String>,
+) -> Result<ParsedAlbum, String> {
+    let title = release.title.trim();
+    let artist_name = release.artist.trim();
+    if title.is_empty() {
+        return Err("Album title is required".to_string());
+    }
+    if artist_name.is_empty() {
+        return Err("Artist is required".to_string());
+    }
+    if release.tracks.is_empty() {
+        return Err("A release needs at least one track".to_string());
+    }
+
+    let now = Utc::now();
+    let album = DbAlbum {
+        id: Uuid::new_v4().to_string(),
+        title: title.to_string(),
+        year: release.year.map(|y| y as i32),
+        discogs_release: None,
+        musicbrainz_release: None,
+        bandcamp_album_id: None,
+        cover_image_id: None,
+        cover_art_url,
+        is_compilation: false,
+        created_at: now,
+        updated_at: now,
+        play_count: 0,
+        last_played_at: None,
+        dominant_color: None,
+        accent_color: None,
+        preferred_release_id: None,
+    };
+    let db_release = DbRelease {
+        id: Uuid::new_v4().to_string(),
+        album_id: album.id.clone(),
+        release_name: None,
+        year: release.year.map(|y| y as i32),
+        discogs_release_id: None,
+        bandcamp_release_id: None,
+        format: None,
+        label: None,
+        catalog_number: None,
+        country: None,
+        barcode: None,
+        import_status: ImportStatus::Queued,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let artist = DbArtist::new(artist_name);
+    let album_artist = DbAlbumArtist::new(&album.id, &artist.id, 0);
+
+    let tracks = release
+        .tracks
+        .iter()
+        .enumerate()
+        .map(|(index, track_title)| {
+            let track_title = track_title.trim();
+            DbTrack {
+                id: Uuid::new_v4().to_string(),
+                release_id: db_release.id.clone(),
+                title: if track_title.is_empty() {
+                    format!("Track {}", index + 1)
+                } else {
+                    track_title.to_string()
+                },
+                disc_number: None,
+                track_number: Some((index + 1) as i32),
+                duration_ms: None,
+                discogs_position: None,
+                import_status: ImportStatus::Queued,
+                created_at: now,
+            }
+        })
+        .collect();
+
+    Ok((album, db_release, tracks, vec![artist], vec![album_artist]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tracks: &[&str]) -> ManualRelease {
+        ManualRelease {
+            title: " Basement Tapes ".to_string(),
+            artist: "The Garage Band".to_string(),
+            year: Some(2019),
+            tracks: tracks.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_manual_release() {
+        let (album, db_release, tracks, artists, album_artists) =
+            parse_manual_release(&release(&["Warmup", "", "Jam"]), None).unwrap();
+
+        assert_eq!(album.title, "Basement Tapes");
+        assert_eq!(album.year, Some(2019));
+        assert!(album.discogs_release.is_none() && album.musicbrainz_release.is_none());
+        assert_eq!(db_release.album_id, album.id);
+
+        let titles: Vec<_> = tracks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, ["Warmup", "Track 2", "Jam"]);
+        assert_eq!(tracks[2].track_number, Some(3));
+        assert!(tracks.iter().all(|t| t.release_id == db_release.id));
+
+        assert_eq!(artists.len(), 1);
+        assert_eq!(artists[0].name, "The Garage Band");
+        assert_eq!(album_artists[0].artist_id, artists[0].id);
+        assert_eq!(album_artists[0].album_id, album.id);
+    }
+
+    #[test]
+    fn test_parse_manual_release_requires_details() {
+        let mut missing_title = release(&["Warmup"]);
+        missing_title.title = "  ".to_string();
+        assert!(parse_manual_release(&missing_title, None).is_err());
+
+        let mut missing_artist = release(&["Warmup"]);
+        missing_artist.artist = String::new();
+        assert!(parse_manual_release(&missing_artist, None).is_err());
+
+        assert!(parse_manual_release(&release(&[]), None).is_err());
+    }
+}
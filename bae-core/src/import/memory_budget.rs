@@ -0,0 +1,158 @@
+//! Caps how much file data imports hold in memory at once.
+//!
+//! Storing a file keeps it in memory alongside its compressed and encrypted
+//! copies until the write or upload finishes. Every import reserves those
+//! bytes from one shared budget before reading a file, and waits while other
+//! imports hold too much, so a big batch of imports can't balloon memory.
+
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+/// Default budget when none is configured
+pub const DEFAULT_MEMORY_BUDGET_MB: u32 = 1024;
+/// Smallest budget that can be set
+pub const MIN_MEMORY_BUDGET_MB: u32 = 64;
+
+/// Bytes held in memory while storing a file of `size` bytes: the file, and
+/// a copy for each transform the profile applies
+pub fn bytes_in_flight(size: u64, compressed: bool, encrypted: bool) -> u64 {
+    size * (1 + compressed as u64 + encrypted as u64)
+}
+
+#[derive(Debug)]
+struct BudgetState {
+    budget: u64,
+    reserved: u64,
+}
+
+/// Shared by every import and the handle, so the budget can change while
+/// imports run
+#[derive(Clone)]
+pub struct MemoryBudget {
+    state: Arc<Mutex<BudgetState>>,
+    released: Arc<Notify>,
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_MEMORY_BUDGET_MB as u64 * 1024 * 1024)
+    }
+}
+
+impl MemoryBudget {
+    pub fn new(budget_bytes: u64) -> Self {
+        MemoryBudget {
+            state: Arc::new(Mutex::new(BudgetState {
+                budget: budget_bytes,
+                reserved: 0,
+            })),
+            released: Arc::new(Notify::new()),
+        }
+    }
+
+    pub fn set_budget_mb(&self, megabytes: u32) {
+        self.state.lock().unwrap().budget =
+            megabytes.max(MIN_MEMORY_BUDGET_MB) as u64 * 1024 * 1024;
+        // A bigger budget may fit someone who's waiting
+        self.released.notify_waiters();
+    }
+
+    /// Bytes currently reserved across imports
+    pub fn reserved(&self) -> u64 {
+        self.state.lock().unwrap().reserved
+    }
+
+    /// Wait until `bytes` fit in the budget and reserve them. Anything bigger
+    /// than the whole budget goes through once nothing else is reserved, so
+    /// it runs alone instead of never.
+    pub async fn reserve(&self, bytes: u64) -> MemoryReservation {
+        loop {
+            // Registered before checking, so a release in between isn't missed
+            let released = self.released.notified();
+            tokio::pin!(released);
+            released.as_mut().enable();
+
+            {
+                let mut state = self.state.lock().unwrap();
+                if state.reserved == 0 || state.reserved + bytes <= state.budget {
+                    state.reserved += bytes;
+                    return MemoryReservation {
+                        budget: self.clone(),
+                        bytes,
+                    };
+                }
+            }
+            released.await;
+        }
+    }
+}
+
+/// Reserved bytes, given back on drop
+pub struct MemoryReservation {
+    budget: MemoryBudget,
+    bytes: u64,
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.budget.state.lock().unwrap().reserved -= self.bytes;
+        self.budget.released.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const MB: u64 = 1024 * 1024;
+
+    #[tokio::test]
+    async fn test_reserve_waits_for_room() {
+        let budget = MemoryBudget::new(100 * MB);
+        let first = budget.reserve(60 * MB).await;
+
+        let waiting = tokio::spawn({
+            let budget = budget.clone();
+            async move { budget.reserve(60 * MB).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiting.is_finished());
+        assert_eq!(budget.reserved(), 60 * MB);
+
+        drop(first);
+        let second = tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .expect("reservation should go through once room frees up")
+            .unwrap();
+        assert_eq!(budget.reserved(), 60 * MB);
+        drop(second);
+        assert_eq!(budget.reserved(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_reservation_runs_alone() {
+        let budget = MemoryBudget::new(100 * MB);
+        let huge = budget.reserve(500 * MB).await;
+        assert_eq!(budget.reserved(), 500 * MB);
+
+        let waiting = tokio::spawn({
+            let budget = budget.clone();
+            async move { budget.reserve(MB).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiting.is_finished());
+        drop(huge);
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .expect("small reservation should follow the oversized one")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_bytes_in_flight_counts_each_copy() {
+        assert_eq!(bytes_in_flight(10, false, false), 10);
+        assert_eq!(bytes_in_flight(10, true, false), 20);
+        assert_eq!(bytes_in_flight(10, true, true), 30);
+    }
+}
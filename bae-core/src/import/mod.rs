@@ -1,23 +1,43 @@
+pub mod archive;
+mod audio_check;
 pub mod cover_art;
+pub mod cover_fetcher;
+pub mod cover_palette;
+mod disc_image;
+mod discid_retry;
 mod discogs_matcher;
 mod discogs_parser;
+mod duplicate_audio;
 mod file_validation;
 mod folder_metadata_detector;
+pub mod folder_pattern;
 pub mod folder_scanner;
 mod handle;
+mod manual_release;
+pub mod memory_budget;
 mod musicbrainz_parser;
+pub mod release_template;
+pub mod needle_drop;
 mod progress;
+pub mod queue;
 mod service;
+pub mod stored_release;
+pub mod throttle;
 mod track_to_file_mapper;
 mod types;
+pub use audio_check::{check_audio_files, AudioFileCheck};
+pub use discid_retry::DiscIdRetryService;
 pub use discogs_matcher::{rank_discogs_matches, rank_mb_matches, MatchCandidate, MatchSource};
+pub use duplicate_audio::{find_duplicate_audio, DuplicateAudioGroup};
 pub use folder_metadata_detector::{detect_folder_contents, detect_metadata, FolderMetadata};
 pub use folder_scanner::{scan_for_candidates_with_callback, CategorizedFiles, DetectedCandidate};
 pub use handle::{ImportServiceHandle, ScanEvent};
+pub use manual_release::ManualRelease;
 #[cfg(feature = "torrent")]
 pub use handle::{TorrentFileMetadata, TorrentImportMetadata};
 pub use progress::ImportProgressHandle;
+pub use queue::{ImportQueue, QueueAction, DEFAULT_MAX_CONCURRENT_IMPORTS};
 pub use service::ImportService;
 #[cfg(feature = "torrent")]
 pub use types::TorrentSource;
-pub use types::{ImportPhase, ImportProgress, ImportRequest, PrepareStep};
+pub use types::{DownloadHealth, ImportPhase, ImportProgress, ImportRequest, PrepareStep};
@@ -96,6 +96,7 @@ fn parse_mb_release_from_json(
                     album_id: album.id.clone(),
                     artist_id: artist.id.clone(),
                     position: position as i32,
+                    credited_as: None,
                 };
                 artists.push(artist);
                 album_artists.push(album_artist);
@@ -118,6 +119,7 @@ fn parse_mb_release_from_json(
             album_id: album.id.clone(),
             artist_id: artist.id.clone(),
             position: 0,
+            credited_as: None,
         };
         artists.push(artist);
         album_artists.push(album_artist);
@@ -0,0 +1,560 @@
+//! Vinyl needle drops: one recording per side.
+//!
+//! Each side is split into tracks at start points the user confirms before
+//! importing. Suggested starts come from the quiet grooves between tracks,
+//! matched against the release's track lengths. The split tracks are encoded
+//! to FLAC in the release's `.bae` folder, next to extracted disc images, and
+//! import as ordinary one-file-per-track audio.
+
+use super::types::DiscoveredFile;
+use crate::audio_codec::{self, LevelEnvelope};
+use crate::discogs::DiscogsRelease;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Where split tracks go, relative to the release root
+const SPLIT_DIR: &str = ".bae/needle_drop";
+
+/// Length of each level measurement
+const LEVEL_WINDOW_MS: u32 = 50;
+
+/// Below this RMS level (about -40 dBFS) audio counts as the groove between
+/// tracks. Surface noise on a clean record sits well under it.
+const GAP_LEVEL: f32 = 0.01;
+
+/// Quiet stretches shorter than this are pauses within a track
+const MIN_GAP_MS: u64 = 800;
+
+/// How far a gap can be from where the track lengths put a track start and
+/// still be taken as that start
+const SNAP_TOLERANCE_MS: u64 = 20_000;
+
+/// Lead-in kept before the first track's music, so the attack isn't clipped
+const LEAD_IN_MARGIN_MS: u64 = 300;
+
+/// Points in the waveform overview of each side
+pub const WAVEFORM_POINTS: usize = 400;
+
+/// A track as the matched release lists it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReleaseTrack {
+    /// Position as printed on the release, e.g. "A1"
+    pub position: String,
+    pub title: String,
+    pub duration_ms: Option<u64>,
+}
+
+impl ReleaseTrack {
+    /// Side letter of a vinyl position ("A" for "A1")
+    fn side(&self) -> Option<char> {
+        let first = self.position.trim().chars().next()?;
+        first
+            .is_ascii_alphabetic()
+            .then_some(first.to_ascii_uppercase())
+    }
+}
+
+/// Tracks of a Discogs release, in tracklist order
+pub fn release_tracks_from_discogs(release: &DiscogsRelease) -> Vec<ReleaseTrack> {
+    release
+        .tracklist
+        .iter()
+        .map(|t| ReleaseTrack {
+            position: t.position.clone(),
+            title: t.title.clone(),
+            duration_ms: t.duration.as_deref().and_then(parse_duration),
+        })
+        .collect()
+}
+
+/// Tracks of a MusicBrainz release, from the release lookup's JSON
+pub fn release_tracks_from_mb_json(json: &serde_json::Value) -> Vec<ReleaseTrack> {
+    let Some(media) = json.get("media").and_then(|m| m.as_array()) else {
+        return Vec::new();
+    };
+    media
+        .iter()
+        .filter_map(|medium| medium.get("tracks").and_then(|t| t.as_array()))
+        .flatten()
+        // Titles and lengths as the import reads them, from the recording
+        .filter_map(|track| {
+            let recording = track.get("recording")?;
+            let length = track
+                .get("length")
+                .and_then(|v| v.as_u64())
+                .or_else(|| recording.get("length").and_then(|v| v.as_u64()));
+            Some(ReleaseTrack {
+                position: track
+                    .get("number")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                title: recording
+                    .get("title")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("Unknown Track")
+                    .to_string(),
+                duration_ms: length,
+            })
+        })
+        .collect()
+}
+
+/// Parse a Discogs duration ("4:32" or "1:02:03") to milliseconds
+fn parse_duration(duration: &str) -> Option<u64> {
+    let mut total = 0u64;
+    for part in duration.trim().split(':') {
+        total = total * 60 + part.trim().parse::<u64>().ok()?;
+    }
+    (total > 0).then_some(total * 1000)
+}
+
+/// Which release tracks are on each side.
+///
+/// Vinyl positions ("A1", "B3") name the side directly. Without them, or when
+/// the release has a different number of sides than there are recordings, the
+/// track lengths are laid end to end across the recordings instead.
+pub fn assign_tracks_to_sides(
+    tracks: &[ReleaseTrack],
+    side_durations_ms: &[u64],
+) -> Result<Vec<Vec<ReleaseTrack>>, String> {
+    if side_durations_ms.is_empty() {
+        return Err("No sides to split".to_string());
+    }
+    if tracks.len() < side_durations_ms.len() {
+        return Err(format!(
+            "The release has {} tracks but there are {} sides",
+            tracks.len(),
+            side_durations_ms.len()
+        ));
+    }
+
+    if tracks.iter().all(|t| t.side().is_some()) {
+        let mut sides: Vec<Vec<ReleaseTrack>> = Vec::new();
+        let mut current_side = None;
+        for track in tracks {
+            if track.side() != current_side {
+                current_side = track.side();
+                sides.push(Vec::new());
+            }
+            sides.last_mut().unwrap().push(track.clone());
+        }
+        if sides.len() == side_durations_ms.len() {
+            return Ok(sides);
+        }
+    }
+
+    let durations: Option<Vec<u64>> = tracks.iter().map(|t| t.duration_ms).collect();
+    let Some(durations) = durations else {
+        return Err(format!(
+            "Can't tell which tracks are on which of the {} sides: the release doesn't list every track's side or length",
+            side_durations_ms.len()
+        ));
+    };
+
+    // Scale the tracklist to the recordings, then put each track on the side
+    // its midpoint falls in
+    let tracks_total: u64 = durations.iter().sum();
+    let sides_total: u64 = side_durations_ms.iter().sum();
+    let scale = sides_total as f64 / tracks_total.max(1) as f64;
+    let mut side_ends = Vec::new();
+    let mut end = 0;
+    for duration in side_durations_ms {
+        end += duration;
+        side_ends.push(end);
+    }
+    let mut sides = vec![Vec::new(); side_durations_ms.len()];
+    let mut elapsed = 0;
+    for (track, duration) in tracks.iter().zip(&durations) {
+        let midpoint = ((elapsed + duration / 2) as f64 * scale) as u64;
+        let side = side_ends
+            .iter()
+            .position(|&end| midpoint < end)
+            .unwrap_or(side_ends.len() - 1);
+        sides[side].push(track.clone());
+        elapsed += duration;
+    }
+    if sides.iter().any(|side| side.is_empty()) {
+        return Err("The track lengths don't fit the sides".to_string());
+    }
+    Ok(sides)
+}
+
+/// A quiet stretch in a side's recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SilentGap {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+impl SilentGap {
+    pub fn midpoint_ms(&self) -> u64 {
+        (self.start_ms + self.end_ms) / 2
+    }
+}
+
+/// What's needed to suggest and show where a side splits
+#[derive(Debug, Clone, PartialEq)]
+pub struct SideAnalysis {
+    pub duration_ms: u64,
+    /// Where the music starts, after the lead-in groove
+    pub music_start_ms: u64,
+    /// Quiet stretches between the first and last music
+    pub gaps: Vec<SilentGap>,
+    /// Level across the side, [`WAVEFORM_POINTS`] values from 0.0 to 1.0
+    pub waveform: Vec<f32>,
+}
+
+/// Measure a side's levels to find the gaps between its tracks.
+///
+/// Blocking; decodes the whole recording.
+pub fn analyze_side(path: &Path) -> Result<SideAnalysis, String> {
+    let data = fs::read(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    let mut envelope = LevelEnvelope::new(LEVEL_WINDOW_MS);
+    audio_codec::measure_levels(&data, &mut envelope)
+        .map_err(|e| format!("Failed to decode {:?}: {}", path, e))?;
+    Ok(analyze_levels(&envelope.finish(), LEVEL_WINDOW_MS as u64))
+}
+
+fn analyze_levels(levels: &[f32], window_ms: u64) -> SideAnalysis {
+    let duration_ms = levels.len() as u64 * window_ms;
+    let first_loud = levels.iter().position(|&l| l >= GAP_LEVEL);
+    let last_loud = levels.iter().rposition(|&l| l >= GAP_LEVEL);
+
+    let mut gaps = Vec::new();
+    if let (Some(first), Some(last)) = (first_loud, last_loud) {
+        let mut run_start = None;
+        for (i, &level) in levels.iter().enumerate().take(last + 1).skip(first) {
+            match (level < GAP_LEVEL, run_start) {
+                (true, None) => run_start = Some(i),
+                (false, Some(start)) => {
+                    let gap = SilentGap {
+                        start_ms: start as u64 * window_ms,
+                        end_ms: i as u64 * window_ms,
+                    };
+                    if gap.end_ms - gap.start_ms >= MIN_GAP_MS {
+                        gaps.push(gap);
+                    }
+                    run_start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    SideAnalysis {
+        duration_ms,
+        music_start_ms: first_loud.unwrap_or(0) as u64 * window_ms,
+        gaps,
+        waveform: waveform(levels),
+    }
+}
+
+/// Loudest level in each of [`WAVEFORM_POINTS`] equal slices of the side
+fn waveform(levels: &[f32]) -> Vec<f32> {
+    if levels.is_empty() {
+        return Vec::new();
+    }
+    let points = WAVEFORM_POINTS.min(levels.len());
+    (0..points)
+        .map(|i| {
+            let start = i * levels.len() / points;
+            let end = ((i + 1) * levels.len() / points).max(start + 1);
+            levels[start..end]
+                .iter()
+                .fold(0.0f32, |peak, &l| peak.max(l))
+                .min(1.0)
+        })
+        .collect()
+}
+
+/// Suggested start of each track on a side, in milliseconds.
+///
+/// With every track's length known, each start goes to the gap nearest where
+/// the lengths put it; otherwise to the longest gaps, or evenly spaced when
+/// the side doesn't have enough.
+pub fn suggest_track_starts(analysis: &SideAnalysis, tracks: &[ReleaseTrack]) -> Vec<u64> {
+    if tracks.is_empty() {
+        return Vec::new();
+    }
+    let first = analysis.music_start_ms.saturating_sub(LEAD_IN_MARGIN_MS);
+    let mut starts = vec![first];
+    let remaining = analysis.duration_ms.saturating_sub(first);
+
+    let durations: Option<Vec<u64>> = tracks.iter().map(|t| t.duration_ms).collect();
+    if let Some(durations) = durations {
+        let total: u64 = durations.iter().sum();
+        let scale = remaining as f64 / total.max(1) as f64;
+        let mut elapsed = 0;
+        for duration in &durations[..durations.len() - 1] {
+            elapsed += duration;
+            let target = first + (elapsed as f64 * scale) as u64;
+            let previous = *starts.last().unwrap();
+            let snapped = analysis
+                .gaps
+                .iter()
+                .map(|g| g.midpoint_ms())
+                .filter(|&m| m > previous && m.abs_diff(target) <= SNAP_TOLERANCE_MS)
+                .min_by_key(|&m| m.abs_diff(target));
+            starts.push(snapped.unwrap_or(target.max(previous + 1)));
+        }
+        return starts;
+    }
+
+    let needed = tracks.len() - 1;
+    if analysis.gaps.len() >= needed {
+        let mut longest = analysis.gaps.clone();
+        longest.sort_by_key(|g| std::cmp::Reverse(g.end_ms - g.start_ms));
+        let mut points: Vec<u64> = longest[..needed].iter().map(|g| g.midpoint_ms()).collect();
+        points.sort();
+        starts.extend(points);
+    } else {
+        let length = remaining / tracks.len() as u64;
+        starts.extend((1..tracks.len() as u64).map(|i| first + i * length));
+    }
+    starts
+}
+
+/// A side recording and where each of its tracks starts
+#[derive(Debug, Clone, PartialEq)]
+pub struct SideSplit {
+    pub path: PathBuf,
+    /// Ascending; audio before the first start (the lead-in) is dropped
+    pub track_starts_ms: Vec<u64>,
+}
+
+/// Split each side into tracks and encode them to FLAC, numbered in order
+/// across the sides. Replaces anything a previous split left behind.
+///
+/// Blocking, and holds a whole side in memory while splitting it.
+pub fn split_sides(
+    release_root: &Path,
+    sides: &[SideSplit],
+) -> Result<Vec<DiscoveredFile>, String> {
+    let dir = release_root.join(SPLIT_DIR);
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| format!("Failed to clear {:?}: {}", dir, e))?;
+    }
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+
+    let mut files = Vec::new();
+    for side in sides {
+        info!(
+            "Splitting {:?} into {} tracks",
+            side.path,
+            side.track_starts_ms.len()
+        );
+        if side.track_starts_ms.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(format!(
+                "Track starts in {:?} are out of order",
+                side.path.file_name().unwrap_or_default()
+            ));
+        }
+
+        let data =
+            fs::read(&side.path).map_err(|e| format!("Failed to read {:?}: {}", side.path, e))?;
+        let decoded = audio_codec::decode_audio(&data, None, None)?;
+        drop(data);
+        let channels = decoded.channels.max(1) as usize;
+        let frames = decoded.samples.len() / channels;
+        let frame_at = |ms: u64| ((ms * decoded.sample_rate as u64 / 1000) as usize).min(frames);
+
+        for (i, &start_ms) in side.track_starts_ms.iter().enumerate() {
+            let start = frame_at(start_ms);
+            let end = side
+                .track_starts_ms
+                .get(i + 1)
+                .map_or(frames, |&next| frame_at(next));
+            if start >= end {
+                return Err(format!(
+                    "Track {} of {:?} starts after the recording ends",
+                    i + 1,
+                    side.path.file_name().unwrap_or_default()
+                ));
+            }
+            let flac = audio_codec::encode_to_flac(
+                &decoded.samples[start * channels..end * channels],
+                decoded.sample_rate,
+                decoded.channels,
+                decoded.bits_per_sample,
+            )?;
+            let path = dir.join(format!("{:02}.flac", files.len() + 1));
+            fs::write(&path, &flac).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+            files.push(DiscoveredFile {
+                path,
+                size: flac.len() as u64,
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// Tracks an earlier split left in the release's `.bae` folder, in order.
+/// Lets a queued import be rebuilt without splitting its sides again.
+pub fn split_tracks(release_root: &Path) -> Vec<DiscoveredFile> {
+    let Ok(entries) = fs::read_dir(release_root.join(SPLIT_DIR)) else {
+        return Vec::new();
+    };
+    let mut files: Vec<DiscoveredFile> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let size = fs::metadata(&path).ok()?.len();
+            (path.extension()? == "flac").then_some(DiscoveredFile { path, size })
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cue_flac::CueFlacProcessor;
+
+    fn track(position: &str, duration_ms: Option<u64>) -> ReleaseTrack {
+        ReleaseTrack {
+            position: position.to_string(),
+            title: format!("Track {}", position),
+            duration_ms,
+        }
+    }
+
+    /// Loud windows with quiet runs at the given window ranges
+    fn levels(windows: usize, quiet: &[std::ops::Range<usize>]) -> Vec<f32> {
+        (0..windows)
+            .map(|i| {
+                if quiet.iter().any(|r| r.contains(&i)) {
+                    0.001
+                } else {
+                    0.3
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("4:32"), Some(272_000));
+        assert_eq!(parse_duration("1:02:03"), Some(3_723_000));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("0:00"), None);
+    }
+
+    #[test]
+    fn test_assign_by_side_letter() {
+        let tracks = vec![
+            track("A1", None),
+            track("A2", None),
+            track("B1", None),
+            track("B2", None),
+            track("B3", None),
+        ];
+        let sides = assign_tracks_to_sides(&tracks, &[1_000_000, 1_000_000]).unwrap();
+        assert_eq!(sides[0].len(), 2);
+        assert_eq!(sides[1].len(), 3);
+    }
+
+    #[test]
+    fn test_assign_by_length_without_side_letters() {
+        let tracks = vec![
+            track("1", Some(300_000)),
+            track("2", Some(300_000)),
+            track("3", Some(200_000)),
+            track("4", Some(400_000)),
+        ];
+        // A slightly fast recording of a 600s side and a 600s side
+        let sides = assign_tracks_to_sides(&tracks, &[590_000, 595_000]).unwrap();
+        assert_eq!(sides[0].len(), 2);
+        assert_eq!(sides[1].len(), 2);
+
+        let unknown = vec![track("1", None), track("2", None)];
+        assert!(assign_tracks_to_sides(&unknown, &[1, 1]).is_err());
+    }
+
+    #[test]
+    fn test_gaps_skip_lead_in_and_short_pauses() {
+        // 50ms windows: 1s lead-in, 0.5s pause, 2s gap, 1s lead-out
+        let analysis = analyze_levels(&levels(200, &[0..20, 60..70, 120..160, 180..200]), 50);
+        assert_eq!(analysis.duration_ms, 10_000);
+        assert_eq!(analysis.music_start_ms, 1_000);
+        assert_eq!(
+            analysis.gaps,
+            vec![SilentGap {
+                start_ms: 6_000,
+                end_ms: 8_000
+            }]
+        );
+    }
+
+    #[test]
+    fn test_starts_snap_to_nearest_gap() {
+        let analysis = SideAnalysis {
+            duration_ms: 600_000,
+            music_start_ms: 2_000,
+            gaps: vec![
+                SilentGap {
+                    start_ms: 150_000,
+                    end_ms: 152_000,
+                },
+                SilentGap {
+                    start_ms: 305_000,
+                    end_ms: 307_000,
+                },
+            ],
+            waveform: Vec::new(),
+        };
+        let tracks = vec![track("A1", Some(300_000)), track("A2", Some(298_000))];
+        let starts = suggest_track_starts(&analysis, &tracks);
+        assert_eq!(starts, vec![1_700, 306_000]);
+
+        // Without lengths, the longest gaps win
+        let tracks = vec![track("A1", None), track("A2", None), track("A3", None)];
+        assert_eq!(
+            suggest_track_starts(&analysis, &tracks),
+            vec![1_700, 151_000, 306_000]
+        );
+    }
+
+    #[test]
+    fn test_split_sides_numbers_tracks_across_sides() {
+        audio_codec::init();
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        // Two seconds of a quiet ramp, mono
+        let samples: Vec<i32> = (0..88_200).map(|i| i % 2000).collect();
+        let flac = audio_codec::encode_to_flac(&samples, 44_100, 1, 16).unwrap();
+        fs::write(root.join("Side A.flac"), &flac).unwrap();
+        fs::write(root.join("Side B.flac"), &flac).unwrap();
+
+        let files = split_sides(
+            root,
+            &[
+                SideSplit {
+                    path: root.join("Side A.flac"),
+                    track_starts_ms: vec![0, 500],
+                },
+                SideSplit {
+                    path: root.join("Side B.flac"),
+                    track_starts_ms: vec![1_000],
+                },
+            ],
+        )
+        .unwrap();
+
+        let names: Vec<_> = files
+            .iter()
+            .map(|f| f.path.file_name().unwrap().to_str().unwrap().to_string())
+            .collect();
+        assert_eq!(names, vec!["01.flac", "02.flac", "03.flac"]);
+        let duration = |i: usize| {
+            CueFlacProcessor::analyze_flac(&files[i].path)
+                .unwrap()
+                .duration_ms()
+        };
+        assert!(duration(0).abs_diff(500) < 20);
+        assert!(duration(1).abs_diff(1_500) < 20);
+        assert!(duration(2).abs_diff(1_000) < 20);
+    }
+}
@@ -27,8 +27,11 @@ impl SubscriptionFilter {
         match self {
             SubscriptionFilter::Release { release_id } => match progress {
                 ImportProgress::Preparing { .. } => false,
+                ImportProgress::StatusChanged { .. } => false,
                 ImportProgress::Started { id, .. } => id == release_id,
                 ImportProgress::Progress { id, .. } => id == release_id,
+                ImportProgress::Downloading { id, .. } => id == release_id,
+                ImportProgress::Stored { id, .. } => id == release_id,
                 ImportProgress::Complete {
                     id,
                     release_id: rid,
@@ -38,22 +41,33 @@ impl SubscriptionFilter {
             },
             SubscriptionFilter::Track { track_id } => match progress {
                 ImportProgress::Preparing { .. } => false,
+                ImportProgress::StatusChanged { .. } => false,
                 ImportProgress::Started { id, .. } => id == track_id,
                 ImportProgress::Progress { id, .. } => id == track_id,
+                ImportProgress::Downloading { .. } => false,
+                ImportProgress::Stored { .. } => false,
                 ImportProgress::Complete { id, .. } => id == track_id,
                 ImportProgress::Failed { id, .. } => id == track_id,
             },
             SubscriptionFilter::Import { import_id } => match progress {
                 ImportProgress::Preparing { import_id: iid, .. } => iid == import_id,
+                ImportProgress::StatusChanged { import_id: iid, .. } => iid == import_id,
                 ImportProgress::Started { import_id: iid, .. } => iid.as_ref() == Some(import_id),
                 ImportProgress::Progress { import_id: iid, .. } => iid.as_ref() == Some(import_id),
+                ImportProgress::Downloading { import_id: iid, .. } => {
+                    iid.as_ref() == Some(import_id)
+                }
+                ImportProgress::Stored { import_id: iid, .. } => iid.as_ref() == Some(import_id),
                 ImportProgress::Complete { import_id: iid, .. } => iid.as_ref() == Some(import_id),
                 ImportProgress::Failed { import_id: iid, .. } => iid.as_ref() == Some(import_id),
             },
             SubscriptionFilter::AllImports => match progress {
                 ImportProgress::Preparing { .. } => true,
+                ImportProgress::StatusChanged { .. } => true,
                 ImportProgress::Started { import_id, .. } => import_id.is_some(),
                 ImportProgress::Progress { import_id, .. } => import_id.is_some(),
+                ImportProgress::Downloading { import_id, .. } => import_id.is_some(),
+                ImportProgress::Stored { import_id, .. } => import_id.is_some(),
                 ImportProgress::Complete { import_id, .. } => import_id.is_some(),
                 ImportProgress::Failed { import_id, .. } => import_id.is_some(),
             },
@@ -173,6 +187,7 @@ impl ImportProgressHandle {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::{ImportKind, ImportOperationStatus};
     use crate::import::types::{ImportPhase, PrepareStep};
     #[test]
     fn test_release_filter_matches_release_events() {
@@ -380,10 +395,33 @@ mod tests {
         },),);
     }
     #[test]
+    fn test_status_changes_reach_import_subscribers_only() {
+        let status_changed = ImportProgress::StatusChanged {
+            import_id: "import-1".to_string(),
+            status: ImportOperationStatus::Paused,
+            kind: ImportKind::Folder,
+        };
+        assert!(SubscriptionFilter::AllImports.matches(&status_changed));
+        assert!(SubscriptionFilter::Import {
+            import_id: "import-1".to_string(),
+        }
+        .matches(&status_changed));
+        assert!(!SubscriptionFilter::Import {
+            import_id: "import-2".to_string(),
+        }
+        .matches(&status_changed));
+        assert!(!SubscriptionFilter::Release {
+            release_id: "import-1".to_string(),
+        }
+        .matches(&status_changed));
+    }
+    #[test]
     fn test_all_prepare_steps_exist() {
         let steps = [
             PrepareStep::ParsingMetadata,
             PrepareStep::DownloadingCoverArt,
+            PrepareStep::ExtractingDiscImages,
+            PrepareStep::SplittingSides,
             PrepareStep::DiscoveringFiles,
             PrepareStep::ValidatingTracks,
             PrepareStep::SavingToDatabase,
@@ -0,0 +1,212 @@
+//! The import queue.
+//!
+//! Folder and torrent imports wait in the queue for a free slot and start
+//! oldest first, at most `max_concurrent` at a time. CD rips don't queue;
+//! each drive starts right away (see `ImportService::run`). A waiting import
+//! can be paused and resumed, a running folder import can be paused (it
+//! starts over when resumed) and anything not yet finished can be cancelled.
+//!
+//! The order lives in memory with the import service. Each import's status
+//! is kept in the imports table, so the service rebuilds the queue from it
+//! after a restart.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+/// Imports that run at once unless configured otherwise
+pub const DEFAULT_MAX_CONCURRENT_IMPORTS: usize = 1;
+
+/// What the user asked the queue to do with an import
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueAction {
+    Pause,
+    Resume,
+    Cancel,
+    Retry,
+}
+
+/// Shared by the import service and its handle, so the queue can be
+/// steered and its concurrency changed while imports run
+#[derive(Clone)]
+pub struct ImportQueue {
+    max_concurrent: Arc<AtomicUsize>,
+    actions: Arc<Mutex<Vec<(String, QueueAction)>>>,
+    changed: Arc<Notify>,
+}
+
+impl Default for ImportQueue {
+    fn default() -> Self {
+        ImportQueue {
+            max_concurrent: Arc::new(AtomicUsize::new(DEFAULT_MAX_CONCURRENT_IMPORTS)),
+            actions: Arc::new(Mutex::new(Vec::new())),
+            changed: Arc::new(Notify::new()),
+        }
+    }
+}
+
+impl ImportQueue {
+    pub fn set_max_concurrent(&self, max_concurrent: usize) {
+        self.max_concurrent
+            .store(max_concurrent.max(1), Ordering::Relaxed);
+        self.changed.notify_one();
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent.load(Ordering::Relaxed)
+    }
+
+    /// Ask the service to act on an import
+    pub fn request(&self, import_id: &str, action: QueueAction) {
+        self.actions
+            .lock()
+            .unwrap()
+            .push((import_id.to_string(), action));
+        self.changed.notify_one();
+    }
+
+    /// Actions asked for since the last call, oldest first
+    pub(crate) fn take_actions(&self) -> Vec<(String, QueueAction)> {
+        std::mem::take(&mut *self.actions.lock().unwrap())
+    }
+
+    /// Wait until an action is asked for or the concurrency changes
+    pub(crate) async fn changed(&self) {
+        self.changed.notified().await
+    }
+}
+
+/// Where an import stands in the queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum QueuePosition {
+    Waiting,
+    Paused,
+    Running,
+    /// Not in the queue: finished, or never queued
+    Absent,
+}
+
+/// The order imports start in, by import ID
+#[derive(Debug, Default)]
+pub(crate) struct QueueOrder {
+    waiting: VecDeque<String>,
+    paused: HashSet<String>,
+    running: HashSet<String>,
+}
+
+impl QueueOrder {
+    pub fn position(&self, import_id: &str) -> QueuePosition {
+        if self.running.contains(import_id) {
+            QueuePosition::Running
+        } else if self.paused.contains(import_id) {
+            QueuePosition::Paused
+        } else if self.waiting.iter().any(|id| id == import_id) {
+            QueuePosition::Waiting
+        } else {
+            QueuePosition::Absent
+        }
+    }
+
+    /// Add an import at the back of the queue
+    pub fn push(&mut self, import_id: &str) {
+        self.remove(import_id);
+        self.waiting.push_back(import_id.to_string());
+    }
+
+    /// Hold an import until it's resumed. A running import stops running;
+    /// the caller stops its task.
+    pub fn pause(&mut self, import_id: &str) {
+        self.remove(import_id);
+        self.paused.insert(import_id.to_string());
+    }
+
+    /// Take an import out of the queue, wherever it is
+    pub fn remove(&mut self, import_id: &str) {
+        self.waiting.retain(|id| id != import_id);
+        self.paused.remove(import_id);
+        self.running.remove(import_id);
+    }
+
+    pub fn paused_count(&self) -> usize {
+        self.paused.len()
+    }
+
+    /// The next import to start, if a slot is free. It counts as running
+    /// from here on.
+    pub fn next(&mut self, max_concurrent: usize) -> Option<String> {
+        if self.running.len() >= max_concurrent.max(1) {
+            return None;
+        }
+        let import_id = self.waiting.pop_front()?;
+        self.running.insert(import_id.clone());
+        Some(import_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_respects_concurrency() {
+        let mut order = QueueOrder::default();
+        for id in ["a", "b", "c"] {
+            order.push(id);
+        }
+
+        assert_eq!(order.next(2), Some("a".to_string()));
+        assert_eq!(order.next(2), Some("b".to_string()));
+        assert_eq!(order.next(2), None);
+
+        order.remove("a");
+        assert_eq!(order.next(2), Some("c".to_string()));
+        assert_eq!(order.position("c"), QueuePosition::Running);
+    }
+
+    #[test]
+    fn test_paused_import_is_skipped_until_resumed() {
+        let mut order = QueueOrder::default();
+        order.push("a");
+        order.push("b");
+        order.pause("a");
+
+        assert_eq!(order.position("a"), QueuePosition::Paused);
+        assert_eq!(order.next(1), Some("b".to_string()));
+
+        // Resuming puts it at the back of the queue
+        order.remove("b");
+        order.push("a");
+        assert_eq!(order.next(1), Some("a".to_string()));
+        assert_eq!(order.position("b"), QueuePosition::Absent);
+    }
+
+    #[test]
+    fn test_pausing_running_import_frees_its_slot() {
+        let mut order = QueueOrder::default();
+        order.push("a");
+        order.push("b");
+        assert_eq!(order.next(1), Some("a".to_string()));
+
+        order.pause("a");
+        assert_eq!(order.next(1), Some("b".to_string()));
+    }
+
+    #[test]
+    fn test_actions_are_taken_once() {
+        let queue = ImportQueue::default();
+        queue.request("a", QueueAction::Pause);
+        queue.request("b", QueueAction::Cancel);
+        queue.set_max_concurrent(0);
+
+        assert_eq!(
+            queue.take_actions(),
+            vec![
+                ("a".to_string(), QueueAction::Pause),
+                ("b".to_string(), QueueAction::Cancel),
+            ]
+        );
+        assert!(queue.take_actions().is_empty());
+        assert_eq!(queue.max_concurrent(), 1);
+    }
+}
@@ -0,0 +1,102 @@
+//! Templates for series imported by hand again and again, like a monthly
+//! mixtape or a podcast. A template keeps the artist, a cover and the album
+//! title with `{n}` where the volume number goes; each import that uses the
+//! template moves the number on by one.
+
+use crate::db::DbReleaseTemplate;
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Where the volume number goes in a template's title
+pub const NUMBER_PLACEHOLDER: &str = "{n}";
+
+/// Make a template from one release of a series. The last number in the
+/// title is the volume number, and that release is the template's next one,
+/// so importing it moves the template on. None if the title has no number.
+pub fn template_from_release(
+    artist: &str,
+    title: &str,
+    cover_url: Option<String>,
+) -> Option<DbReleaseTemplate> {
+    let artist = artist.trim();
+    let title = title.trim();
+    if artist.is_empty() {
+        return None;
+    }
+    let end = title.rfind(|c: char| c.is_ascii_digit())? + 1;
+    let start = title[..end]
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    let digits = &title[start..end];
+    let next_number = digits.parse().ok()?;
+    let number_width = if digits.starts_with('0') {
+        digits.len() as i64
+    } else {
+        0
+    };
+
+    Some(DbReleaseTemplate {
+        id: Uuid::new_v4().to_string(),
+        artist: artist.to_string(),
+        title_pattern: format!("{}{}{}", &title[..start], NUMBER_PLACEHOLDER, &title[end..]),
+        next_number,
+        number_width,
+        cover_url,
+        created_at: Utc::now(),
+    })
+}
+
+/// Album title of the next release in a template's series
+pub fn template_title(template: &DbReleaseTemplate) -> String {
+    let number = format!(
+        "{:0width$}",
+        template.next_number,
+        width = template.number_width.max(0) as usize
+    );
+    template.title_pattern.replace(NUMBER_PLACEHOLDER, &number)
+}
+
+/// Whether `artist` and `title` are the release a template would fill in
+pub fn is_next_release(template: &DbReleaseTemplate, artist: &str, title: &str) -> bool {
+    template.artist.eq_ignore_ascii_case(artist.trim()) && template_title(template) == title.trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_from_release_uses_last_number() {
+        let template = template_from_release(
+            "DJ Ampersand",
+            "Monthly Mix 2024 Vol. 12 ",
+            Some("https://example.com/mix.jpg".to_string()),
+        )
+        .unwrap();
+        assert_eq!(template.title_pattern, "Monthly Mix 2024 Vol. {n}");
+        assert_eq!(template.next_number, 12);
+        assert_eq!(template_title(&template), "Monthly Mix 2024 Vol. 12");
+
+        assert!(template_from_release("DJ Ampersand", "Live at the Den", None).is_none());
+        assert!(template_from_release(" ", "Mix 3", None).is_none());
+    }
+
+    #[test]
+    fn test_number_padding_is_kept() {
+        let mut template = template_from_release("Cast", "Episode 009 (Extended)", None).unwrap();
+        assert_eq!(template.title_pattern, "Episode {n} (Extended)");
+        assert_eq!(template_title(&template), "Episode 009 (Extended)");
+
+        template.next_number = 10;
+        assert_eq!(template_title(&template), "Episode 010 (Extended)");
+    }
+
+    #[test]
+    fn test_is_next_release() {
+        let template = template_from_release("DJ Ampersand", "Mix 7", None).unwrap();
+
+        assert!(is_next_release(&template, "dj ampersand", " Mix 7"));
+        assert!(!is_next_release(&template, "Someone Else", "Mix 7"));
+        assert!(!is_next_release(&template, "DJ Ampersand", "Mix 6"));
+    }
+}
@@ -6,12 +6,21 @@ use crate::cd::RipProgress;
 use crate::db::DbAlbum;
 #[cfg(feature = "cd-rip")]
 use crate::db::DbTrack;
-use crate::db::{Database, DbFile, DbRelease, DbStorageProfile, ImportOperationStatus};
+use crate::db::{
+    Database, DbFile, DbImport, DbRelease, DbStorageProfile, ImportKind, ImportOperationStatus,
+    StorageLocation,
+};
 use crate::encryption::EncryptionService;
+use crate::import::archive;
 use crate::import::folder_scanner::scan_for_candidates_with_callback;
 #[cfg(feature = "torrent")]
 use crate::import::handle::TorrentImportMetadata;
-use crate::import::handle::{ImportServiceHandle, ScanEvent, ScanRequest};
+use crate::import::handle::{discover_folder_files, ImportServiceHandle, ScanEvent, ScanRequest};
+use crate::import::memory_budget::bytes_in_flight;
+use crate::import::needle_drop::split_tracks;
+use crate::import::queue::{ImportQueue, QueueAction, QueueOrder, QueuePosition};
+use crate::import::throttle::ImportThrottle;
+use crate::import::track_to_file_mapper::map_tracks_to_files;
 #[cfg(feature = "torrent")]
 use crate::import::types::TorrentSource;
 use crate::import::types::{
@@ -20,14 +29,17 @@ use crate::import::types::{
 use crate::library::{LibraryManager, SharedLibraryManager};
 use crate::storage::{ReleaseStorage, ReleaseStorageImpl};
 #[cfg(feature = "torrent")]
+use crate::torrent::client::TorrentHandle;
+#[cfg(feature = "torrent")]
+use crate::torrent::health::read_health;
+#[cfg(feature = "torrent")]
 use crate::torrent::LazyTorrentManager;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc};
-#[cfg(any(feature = "torrent", feature = "cd-rip"))]
-use tracing::warn;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 /// Calculate track progress percentage based on bytes written.
 ///
@@ -50,10 +62,113 @@ fn calculate_track_percent(bytes_written: usize, start_byte: i64, end_byte: i64)
     }
 }
 
+/// Eject a disc that's done ripping. A failed eject leaves the disc in the
+/// drive and doesn't fail the import.
+#[cfg(feature = "cd-rip")]
+async fn eject_ripped_disc(drive: crate::cd::CdDrive) {
+    match tokio::task::spawn_blocking(move || drive.eject()).await {
+        Ok(Ok(())) => info!("Ejected ripped disc"),
+        Ok(Err(e)) => warn!("{}", e),
+        Err(e) => warn!("Eject task failed: {}", e),
+    }
+}
+
+/// Record each ripped track's read errors and AccurateRip result. Tracks are
+/// matched to rips by track number.
+#[cfg(feature = "cd-rip")]
+async fn save_track_rips(
+    library_manager: &LibraryManager,
+    db_tracks: &[DbTrack],
+    rip_results: &[crate::cd::ripper::RipResult],
+) -> Result<(), String> {
+    let ripped_at = chrono::Utc::now();
+    for result in rip_results {
+        let Some(track) = db_tracks
+            .iter()
+            .find(|t| t.track_number == Some(result.track_number as i32))
+        else {
+            continue;
+        };
+        let rip = crate::db::DbTrackRip {
+            track_id: track.id.clone(),
+            copy_crc: result.crc32 as i64,
+            read_errors: result.stats.read_errors as i64,
+            c2_errors: result.stats.c2_errors as i64,
+            suspicious_sectors: result.stats.suspicious_sectors as i64,
+            accuraterip_status: result.accuraterip.status,
+            accuraterip_confidence: result.accuraterip.confidence as i64,
+            ripped_at,
+        };
+        library_manager
+            .save_track_rip(&rip)
+            .await
+            .map_err(|e| format!("Failed to save rip status: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Download only the files listed in the import request. For a release picked
+/// out of a multi-album torrent, the other folders are never fetched.
+/// Returns the wanted paths, relative to the torrent root.
+#[cfg(feature = "torrent")]
+async fn select_torrent_files(
+    torrent_handle: &TorrentHandle,
+    torrent_metadata: &TorrentImportMetadata,
+) -> Result<Vec<PathBuf>, String> {
+    use crate::torrent::file_selection::priorities_for_files;
+    let all_files = torrent_handle
+        .get_file_list()
+        .await
+        .map_err(|e| format!("Failed to get torrent file list: {}", e))?;
+    let wanted: Vec<PathBuf> = torrent_metadata
+        .file_list
+        .iter()
+        .map(|f| f.path.clone())
+        .collect();
+    if wanted.len() < all_files.len() {
+        info!(
+            "Downloading {} of {} torrent files",
+            wanted.len(),
+            all_files.len()
+        );
+    }
+    torrent_handle
+        .set_file_priorities(priorities_for_files(&all_files, &wanted))
+        .await
+        .map_err(|e| format!("Failed to set file priorities: {}", e))?;
+    Ok(wanted)
+}
+
+/// What the service holds for the queue: commands of imports waiting their
+/// turn and tasks of those running
+#[derive(Default)]
+struct QueuedImports {
+    order: QueueOrder,
+    commands: HashMap<String, ImportCommand>,
+    tasks: HashMap<String, tokio::task::AbortHandle>,
+}
+
+impl QueuedImports {
+    fn push(&mut self, command: ImportCommand) {
+        let import_id = command.import_id().to_string();
+        self.order.push(&import_id);
+        self.commands.insert(import_id, command);
+    }
+
+    /// Stop a running import's task. False if it had already finished.
+    fn stop(&mut self, import_id: &str) -> bool {
+        match self.tasks.remove(import_id) {
+            Some(task) if !task.is_finished() => {
+                task.abort();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
 /// Import service that orchestrates the album import workflow
 pub struct ImportService {
-    /// Channel for receiving import commands from clients
-    commands_rx: mpsc::UnboundedReceiver<ImportCommand>,
     /// Channel for sending progress updates to subscribers
     progress_tx: mpsc::UnboundedSender<ImportProgress>,
     /// Service for encrypting files before upload (None if not configured)
@@ -65,6 +180,10 @@ pub struct ImportService {
     torrent_manager: LazyTorrentManager,
     /// Database for storage operations
     database: Arc<Database>,
+    /// Paces storing and ripping under load or in low power mode
+    throttle: ImportThrottle,
+    /// Pause, cancel and retry requests, and how many imports run at once
+    queue: ImportQueue,
     /// Optional pre-built cloud storage (for testing with MockCloudStorage)
     #[cfg(feature = "test-utils")]
     injected_cloud: Option<Arc<dyn crate::cloud_storage::CloudStorage>>,
@@ -82,6 +201,11 @@ impl ImportService {
                 let path = request.path;
 
                 let result = tokio::task::spawn_blocking(move || {
+                    let path = if archive::is_archive_file(&path) {
+                        archive::extract_archive(&path, &archive::extraction_root())?
+                    } else {
+                        path
+                    };
                     scan_for_candidates_with_callback(path, |candidate| {
                         let _ = tx.send(ScanEvent::Candidate(candidate));
                     })
@@ -107,8 +231,9 @@ impl ImportService {
     }
     /// Start the import service worker.
     ///
-    /// Creates one worker task that imports validated albums sequentially from a queue.
-    /// Multiple imports will be queued and handled one at a time, not concurrently.
+    /// Creates one worker task that imports validated albums from a queue.
+    /// Folder and torrent imports wait their turn in the queue; CD imports
+    /// run alongside them, one per drive.
     /// Returns a handle that can be cloned and used throughout the app to submit import requests.
     #[cfg(feature = "torrent")]
     pub fn start(
@@ -125,35 +250,32 @@ impl ImportService {
         let progress_tx_for_handle = progress_tx.clone();
         let library_manager_for_worker = library_manager.clone();
         let database_for_handle = database.clone();
+        let throttle = ImportThrottle::default();
+        let throttle_for_worker = throttle.clone();
+        let queue = ImportQueue::default();
+        let queue_for_worker = queue.clone();
 
         ImportService::start_scan_worker(&runtime_handle, scan_rx, scan_events_tx.clone());
 
         std::thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().expect("Failed to create runtime");
-            rt.block_on(async move {
-                let mut service = ImportService {
-                    commands_rx,
+            // CD imports run as local tasks alongside the queue (see `run`)
+            let local = tokio::task::LocalSet::new();
+            local.block_on(&rt, async move {
+                let service = ImportService {
                     progress_tx,
                     library_manager: library_manager_for_worker,
                     encryption_service,
                     torrent_manager,
                     database,
+                    throttle: throttle_for_worker,
+                    queue: queue_for_worker,
                     #[cfg(feature = "test-utils")]
                     injected_cloud: None,
                 };
 
                 info!("Worker started");
-                loop {
-                    match service.commands_rx.recv().await {
-                        Some(command) => {
-                            service.do_import(command).await;
-                        }
-                        None => {
-                            info!("Worker receive channel closed");
-                            break;
-                        }
-                    }
-                }
+                service.run(commands_rx).await;
             });
         });
 
@@ -166,6 +288,8 @@ impl ImportService {
             runtime_handle,
             scan_tx,
             scan_events_tx,
+            throttle,
+            queue,
         )
     }
 
@@ -184,34 +308,31 @@ impl ImportService {
         let progress_tx_for_handle = progress_tx.clone();
         let library_manager_for_worker = library_manager.clone();
         let database_for_handle = database.clone();
+        let throttle = ImportThrottle::default();
+        let throttle_for_worker = throttle.clone();
+        let queue = ImportQueue::default();
+        let queue_for_worker = queue.clone();
 
         ImportService::start_scan_worker(&runtime_handle, scan_rx, scan_events_tx.clone());
 
         std::thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().expect("Failed to create runtime");
-            rt.block_on(async move {
-                let mut service = ImportService {
-                    commands_rx,
+            // CD imports run as local tasks alongside the queue (see `run`)
+            let local = tokio::task::LocalSet::new();
+            local.block_on(&rt, async move {
+                let service = ImportService {
                     progress_tx,
                     library_manager: library_manager_for_worker,
                     encryption_service,
                     database,
+                    throttle: throttle_for_worker,
+                    queue: queue_for_worker,
                     #[cfg(feature = "test-utils")]
                     injected_cloud: None,
                 };
 
                 info!("Worker started");
-                loop {
-                    match service.commands_rx.recv().await {
-                        Some(command) => {
-                            service.do_import(command).await;
-                        }
-                        None => {
-                            info!("Worker receive channel closed");
-                            break;
-                        }
-                    }
-                }
+                service.run(commands_rx).await;
             });
         });
 
@@ -224,6 +345,8 @@ impl ImportService {
             runtime_handle,
             scan_tx,
             scan_events_tx,
+            throttle,
+            queue,
         )
     }
 
@@ -245,35 +368,32 @@ impl ImportService {
         let progress_tx_for_handle = progress_tx.clone();
         let library_manager_for_worker = library_manager.clone();
         let database_for_handle = database.clone();
+        let throttle = ImportThrottle::default();
+        let throttle_for_worker = throttle.clone();
+        let queue = ImportQueue::default();
+        let queue_for_worker = queue.clone();
         let runtime_handle = tokio::runtime::Handle::current();
 
         ImportService::start_scan_worker(&runtime_handle, scan_rx, scan_events_tx.clone());
 
         std::thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().expect("Failed to create runtime");
-            rt.block_on(async move {
-                let mut service = ImportService {
-                    commands_rx,
+            // CD imports run as local tasks alongside the queue (see `run`)
+            let local = tokio::task::LocalSet::new();
+            local.block_on(&rt, async move {
+                let service = ImportService {
                     progress_tx,
                     library_manager: library_manager_for_worker,
                     encryption_service,
                     torrent_manager,
                     database,
+                    throttle: throttle_for_worker,
+                    queue: queue_for_worker,
                     injected_cloud: Some(cloud),
                 };
 
                 info!("Worker started (with injected cloud)");
-                loop {
-                    match service.commands_rx.recv().await {
-                        Some(command) => {
-                            service.do_import(command).await;
-                        }
-                        None => {
-                            info!("Worker receive channel closed");
-                            break;
-                        }
-                    }
-                }
+                service.run(commands_rx).await;
             });
         });
 
@@ -286,6 +406,8 @@ impl ImportService {
             runtime_handle,
             scan_tx,
             scan_events_tx,
+            throttle,
+            queue,
         )
     }
 
@@ -306,34 +428,31 @@ impl ImportService {
         let progress_tx_for_handle = progress_tx.clone();
         let library_manager_for_worker = library_manager.clone();
         let database_for_handle = database.clone();
+        let throttle = ImportThrottle::default();
+        let throttle_for_worker = throttle.clone();
+        let queue = ImportQueue::default();
+        let queue_for_worker = queue.clone();
         let runtime_handle = tokio::runtime::Handle::current();
 
         ImportService::start_scan_worker(&runtime_handle, scan_rx, scan_events_tx.clone());
 
         std::thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().expect("Failed to create runtime");
-            rt.block_on(async move {
-                let mut service = ImportService {
-                    commands_rx,
+            // CD imports run as local tasks alongside the queue (see `run`)
+            let local = tokio::task::LocalSet::new();
+            local.block_on(&rt, async move {
+                let service = ImportService {
                     progress_tx,
                     library_manager: library_manager_for_worker,
                     encryption_service,
                     database,
+                    throttle: throttle_for_worker,
+                    queue: queue_for_worker,
                     injected_cloud: Some(cloud),
                 };
 
                 info!("Worker started (with injected cloud)");
-                loop {
-                    match service.commands_rx.recv().await {
-                        Some(command) => {
-                            service.do_import(command).await;
-                        }
-                        None => {
-                            info!("Worker receive channel closed");
-                            break;
-                        }
-                    }
-                }
+                service.run(commands_rx).await;
             });
         });
 
@@ -346,9 +465,328 @@ impl ImportService {
             runtime_handle,
             scan_tx,
             scan_events_tx,
+            throttle,
+            queue,
         )
     }
 
+    /// Run imports until the command channel closes.
+    ///
+    /// Folder and torrent imports wait in the queue and start oldest first,
+    /// as many at once as the [`ImportQueue`] allows. CD imports each start
+    /// right away, so every drive rips in parallel; a drive never rips two
+    /// discs at once (see [`BusyDrives`]).
+    ///
+    /// [`BusyDrives`]: crate::cd::BusyDrives
+    async fn run(self, mut commands_rx: mpsc::UnboundedReceiver<ImportCommand>) {
+        let service = Rc::new(self);
+        let mut queued = QueuedImports::default();
+        let (finished_tx, mut finished_rx) = mpsc::unbounded_channel::<String>();
+
+        service.restore_queue(&mut queued).await;
+
+        loop {
+            while let Some(import_id) = queued.order.next(service.queue.max_concurrent()) {
+                let Some(command) = queued.commands.remove(&import_id) else {
+                    queued.order.remove(&import_id);
+                    continue;
+                };
+                let service = service.clone();
+                let finished_tx = finished_tx.clone();
+                let finished_id = import_id.clone();
+                let task = tokio::task::spawn_local(async move {
+                    service.start_import(command).await;
+                    let _ = finished_tx.send(finished_id);
+                });
+                queued.tasks.insert(import_id, task.abort_handle());
+            }
+
+            tokio::select! {
+                command = commands_rx.recv() => {
+                    let Some(command) = command else { break };
+                    #[cfg(feature = "cd-rip")]
+                    if matches!(command, ImportCommand::CD { .. }) {
+                        let service = service.clone();
+                        tokio::task::spawn_local(async move { service.do_import(command).await });
+                        continue;
+                    }
+                    queued.push(command);
+                }
+                Some(import_id) = finished_rx.recv() => {
+                    queued.order.remove(&import_id);
+                    queued.tasks.remove(&import_id);
+                }
+                _ = service.queue.changed() => {
+                    for (import_id, action) in service.queue.take_actions() {
+                        service.apply_action(&import_id, action, &mut queued).await;
+                    }
+                }
+            }
+        }
+        info!("Worker receive channel closed");
+    }
+
+    /// Pick the queue back up after a restart. Queued folder imports are
+    /// rebuilt from their folders, and one that was running starts over.
+    /// Other unfinished imports can't be picked up again, so they fail.
+    async fn restore_queue(&self, queued: &mut QueuedImports) {
+        let imports = match self.library_manager.get().get_active_imports().await {
+            Ok(imports) => imports,
+            Err(e) => {
+                error!("Failed to load unfinished imports: {}", e);
+                Vec::new()
+            }
+        };
+
+        // Archive extractions are kept for the imports still queued
+        let keep: Vec<PathBuf> = imports
+            .iter()
+            .filter(|import| {
+                import.kind == ImportKind::Folder
+                    && import.status != ImportOperationStatus::Preparing
+            })
+            .map(|import| PathBuf::from(&import.folder_path))
+            .collect();
+        let _ = tokio::task::spawn_blocking(move || {
+            archive::clear_extractions(&archive::extraction_root(), &keep)
+        })
+        .await;
+
+        // Oldest first, so they keep their place in the queue
+        for import in imports.into_iter().rev() {
+            match (import.kind, import.status) {
+                (ImportKind::Folder, ImportOperationStatus::Paused) => {
+                    queued.order.pause(&import.id);
+                }
+                (
+                    ImportKind::Folder,
+                    ImportOperationStatus::Queued | ImportOperationStatus::Importing,
+                ) => {
+                    if import.status == ImportOperationStatus::Importing {
+                        self.reset_import(&import).await;
+                    }
+                    match self.rebuild_folder_command(&import).await {
+                        Ok(command) => {
+                            queued.push(command);
+                            self.set_status(&import.id, ImportOperationStatus::Queued, import.kind)
+                                .await;
+                        }
+                        Err(e) => self.fail_import(&import, &e).await,
+                    }
+                }
+                (_, ImportOperationStatus::Preparing) => {
+                    self.fail_import(&import, "Interrupted before it was queued")
+                        .await;
+                }
+                _ => self.fail_import(&import, "Interrupted by a restart").await,
+            }
+        }
+        info!(
+            "Restored import queue: {} queued, {} paused",
+            queued.commands.len(),
+            queued.order.paused_count()
+        );
+    }
+
+    /// Carry out a pause, resume, cancel or retry asked for through the queue
+    async fn apply_action(&self, import_id: &str, action: QueueAction, queued: &mut QueuedImports) {
+        let import = match self.database.get_import(import_id).await {
+            Ok(Some(import)) => import,
+            Ok(None) => return,
+            Err(e) => {
+                error!("Failed to load import {}: {}", import_id, e);
+                return;
+            }
+        };
+        info!("{:?} import {}", action, import_id);
+        match (action, queued.order.position(import_id)) {
+            (QueueAction::Pause, QueuePosition::Waiting) => {
+                queued.order.pause(import_id);
+                self.set_status(import_id, ImportOperationStatus::Paused, import.kind)
+                    .await;
+            }
+            (QueueAction::Pause, QueuePosition::Running) => {
+                // Started over when resumed, from what's left in the folder
+                if !queued.stop(import_id) {
+                    return;
+                }
+                queued.order.pause(import_id);
+                self.reset_import(&import).await;
+                self.set_status(import_id, ImportOperationStatus::Paused, import.kind)
+                    .await;
+            }
+            (QueueAction::Resume, QueuePosition::Paused) => {
+                self.requeue(&import, queued).await;
+            }
+            (QueueAction::Cancel, QueuePosition::Waiting | QueuePosition::Paused) => {
+                queued.order.remove(import_id);
+                queued.commands.remove(import_id);
+                self.cancel_import(&import).await;
+            }
+            (QueueAction::Cancel, QueuePosition::Running) => {
+                if !queued.stop(import_id) {
+                    return;
+                }
+                queued.order.remove(import_id);
+                self.cancel_import(&import).await;
+            }
+            (QueueAction::Retry, QueuePosition::Absent)
+                if import.status == ImportOperationStatus::Failed =>
+            {
+                self.reset_import(&import).await;
+                self.requeue(&import, queued).await;
+            }
+            _ => {}
+        }
+    }
+
+    /// Put an import at the back of the queue, rebuilding its command if the
+    /// service no longer holds it
+    async fn requeue(&self, import: &DbImport, queued: &mut QueuedImports) {
+        if queued.commands.contains_key(&import.id) {
+            queued.order.push(&import.id);
+        } else {
+            match self.rebuild_folder_command(import).await {
+                Ok(command) => queued.push(command),
+                Err(e) => {
+                    queued.order.remove(&import.id);
+                    self.fail_import(import, &e).await;
+                    return;
+                }
+            }
+        }
+        self.set_status(&import.id, ImportOperationStatus::Queued, import.kind)
+            .await;
+    }
+
+    /// Rebuild a folder import's command from its import record, the release
+    /// it created and the files in its folder
+    async fn rebuild_folder_command(&self, import: &DbImport) -> Result<ImportCommand, String> {
+        if import.kind != ImportKind::Folder {
+            return Err("Only folder imports can be queued again".to_string());
+        }
+        let release_id = import
+            .release_id
+            .as_deref()
+            .ok_or_else(|| "Import has no release".to_string())?;
+        let library_manager = self.library_manager.get();
+        let db_release = library_manager
+            .get_release(release_id)
+            .await
+            .map_err(|e| format!("Failed to load release: {}", e))?
+            .ok_or_else(|| format!("Release not found: {}", release_id))?;
+        let db_album = library_manager
+            .get_album_by_id(&db_release.album_id)
+            .await
+            .map_err(|e| format!("Failed to load album: {}", e))?
+            .ok_or_else(|| format!("Album not found: {}", db_release.album_id))?;
+        let db_tracks = library_manager
+            .get_tracks(release_id)
+            .await
+            .map_err(|e| format!("Failed to load tracks: {}", e))?;
+
+        let folder = PathBuf::from(&import.folder_path);
+        let mut discovered_files = discover_folder_files(&folder)?;
+        discovered_files.retain(|f| {
+            !import
+                .excluded_files
+                .iter()
+                .any(|excluded| f.path == Path::new(excluded))
+        });
+        discovered_files.extend(split_tracks(&folder));
+        let mapping_result = map_tracks_to_files(&db_tracks, &discovered_files).await?;
+
+        Ok(ImportCommand::Folder {
+            db_album,
+            db_release,
+            folder,
+            tracks_to_files: mapping_result.track_files,
+            discovered_files,
+            cue_flac_metadata: mapping_result.cue_flac_metadata,
+            storage_profile_id: import.storage_profile_id.clone(),
+            selected_cover_filename: import.selected_cover_filename.clone(),
+            import_id: import.id.clone(),
+        })
+    }
+
+    /// Throw away what a stopped import stored, so it can start over
+    async fn reset_import(&self, import: &DbImport) {
+        if let Some(ref release_id) = import.release_id {
+            if let Err(e) = self
+                .library_manager
+                .get()
+                .reset_release_import(release_id)
+                .await
+            {
+                error!("Failed to reset release {}: {}", release_id, e);
+            }
+        }
+    }
+
+    /// Stop an import for good. Its release is removed, along with an
+    /// archive's extraction.
+    async fn cancel_import(&self, import: &DbImport) {
+        if let Some(ref release_id) = import.release_id {
+            if let Err(e) = self
+                .library_manager
+                .get()
+                .delete_release(release_id, false)
+                .await
+            {
+                error!("Failed to remove cancelled release {}: {}", release_id, e);
+            }
+        }
+        if import.kind == ImportKind::Folder {
+            archive::remove_extracted(Path::new(&import.folder_path), &archive::extraction_root());
+        }
+        self.set_status(&import.id, ImportOperationStatus::Cancelled, import.kind)
+            .await;
+    }
+
+    /// Give up on an import the queue can't run
+    async fn fail_import(&self, import: &DbImport, error: &str) {
+        warn!("Import {} failed: {}", import.id, error);
+        let library_manager = self.library_manager.get();
+        if let Err(e) = library_manager.update_import_error(&import.id, error).await {
+            error!("Failed to record import error: {}", e);
+        }
+        if let Some(ref release_id) = import.release_id {
+            if let Err(e) = library_manager.mark_release_failed(release_id).await {
+                error!("Failed to mark release as failed: {}", e);
+            }
+        }
+        let _ = self.progress_tx.send(ImportProgress::Failed {
+            id: import
+                .release_id
+                .clone()
+                .unwrap_or_else(|| import.id.clone()),
+            error: error.to_string(),
+            import_id: Some(import.id.clone()),
+        });
+    }
+
+    async fn set_status(&self, import_id: &str, status: ImportOperationStatus, kind: ImportKind) {
+        if let Err(e) = self.database.update_import_status(import_id, status).await {
+            error!("Failed to update import status: {}", e);
+        }
+        let _ = self.progress_tx.send(ImportProgress::StatusChanged {
+            import_id: import_id.to_string(),
+            status,
+            kind,
+        });
+    }
+
+    /// Run a queued import now that it has a slot
+    async fn start_import(&self, command: ImportCommand) {
+        self.set_status(
+            command.import_id(),
+            ImportOperationStatus::Importing,
+            command.kind(),
+        )
+        .await;
+        self.do_import(command).await;
+    }
+
     async fn do_import(&self, command: ImportCommand) {
         let (release_id_for_error, import_id_for_error) = match &command {
             ImportCommand::Folder {
@@ -357,15 +795,24 @@ impl ImportService {
                 ..
             } => (db_release.id.clone(), Some(import_id.clone())),
             #[cfg(feature = "torrent")]
-            ImportCommand::Torrent { db_release, .. } => (db_release.id.clone(), None),
+            ImportCommand::Torrent {
+                db_release,
+                import_id,
+                ..
+            } => (db_release.id.clone(), Some(import_id.clone())),
             #[cfg(feature = "cd-rip")]
-            ImportCommand::CD { db_release, .. } => (db_release.id.clone(), None),
+            ImportCommand::CD {
+                db_release,
+                import_id,
+                ..
+            } => (db_release.id.clone(), Some(import_id.clone())),
         };
 
         let result = match command {
             ImportCommand::Folder {
                 db_album,
                 db_release,
+                folder,
                 tracks_to_files,
                 discovered_files,
                 cue_flac_metadata,
@@ -377,9 +824,10 @@ impl ImportService {
                 match storage_profile_id {
                     Some(profile_id) => {
                         match self.database.get_storage_profile(&profile_id).await {
-                            Ok(Some(profile)) => {
-                                self.run_storage_import(
+                            Ok(Some(profile)) if profile.location == StorageLocation::Reference => {
+                                self.run_reference_import(
                                     &db_release,
+                                    &folder,
                                     &discovered_files,
                                     &tracks_to_files,
                                     cue_flac_metadata,
@@ -389,6 +837,25 @@ impl ImportService {
                                 )
                                 .await
                             }
+                            Ok(Some(profile)) => {
+                                let result = self
+                                    .run_storage_import(
+                                        &db_release,
+                                        &discovered_files,
+                                        &tracks_to_files,
+                                        cue_flac_metadata,
+                                        profile,
+                                        selected_cover_filename,
+                                        &import_id,
+                                    )
+                                    .await;
+                                // The files are in storage now, so an archive's
+                                // extraction isn't needed anymore
+                                if result.is_ok() {
+                                    archive::remove_extracted(&folder, &archive::extraction_root());
+                                }
+                                result
+                            }
                             Ok(None) => Err(format!("Storage profile not found: {}", profile_id)),
                             Err(e) => Err(format!("Failed to fetch storage profile: {}", e)),
                         }
@@ -417,6 +884,7 @@ impl ImportService {
                 cover_art_url,
                 storage_profile_id,
                 selected_cover_filename,
+                import_id,
             } => {
                 info!("Starting torrent import for '{}'", db_album.title);
                 match storage_profile_id {
@@ -433,6 +901,7 @@ impl ImportService {
                                     cover_art_url,
                                     profile,
                                     selected_cover_filename,
+                                    &import_id,
                                 )
                                 .await
                             }
@@ -449,6 +918,7 @@ impl ImportService {
                             torrent_metadata,
                             cover_art_url,
                             selected_cover_filename,
+                            &import_id,
                         )
                         .await
                     }
@@ -463,6 +933,9 @@ impl ImportService {
                 toc,
                 storage_profile_id,
                 selected_cover_filename,
+                eject_when_ripped,
+                import_id,
+                drive_claim,
             } => {
                 info!("Starting CD import for '{}'", db_album.title);
                 match storage_profile_id {
@@ -477,6 +950,9 @@ impl ImportService {
                                     toc,
                                     profile,
                                     selected_cover_filename,
+                                    eject_when_ripped,
+                                    &import_id,
+                                    drive_claim,
                                 )
                                 .await
                             }
@@ -492,6 +968,9 @@ impl ImportService {
                             drive_path,
                             toc,
                             selected_cover_filename,
+                            eject_when_ripped,
+                            &import_id,
+                            drive_claim,
                         )
                         .await
                     }
@@ -501,6 +980,16 @@ impl ImportService {
 
         if let Err(e) = result {
             error!("Import failed: {}", e);
+            if let Some(ref import_id) = import_id_for_error {
+                if let Err(db_err) = self
+                    .library_manager
+                    .get()
+                    .update_import_error(import_id, &e)
+                    .await
+                {
+                    error!("Failed to record import error: {}", db_err);
+                }
+            }
             if let Err(db_err) = self
                 .library_manager
                 .mark_release_failed(&release_id_for_error)
@@ -521,6 +1010,9 @@ impl ImportService {
         &self,
         profile: DbStorageProfile,
     ) -> Result<ReleaseStorageImpl, String> {
+        // Low power imports keep encryption off the other cores too
+        let single_core = self.throttle.low_power();
+
         // In tests, use injected cloud if available
         #[cfg(feature = "test-utils")]
         if let Some(ref cloud) = self.injected_cloud {
@@ -529,7 +1021,8 @@ impl ImportService {
                 self.encryption_service.clone(),
                 cloud.clone(),
                 self.database.clone(),
-            ));
+            )
+            .single_core(single_core));
         }
 
         ReleaseStorageImpl::from_profile(
@@ -538,6 +1031,7 @@ impl ImportService {
             self.database.clone(),
         )
         .await
+        .map(|storage| storage.single_core(single_core))
         .map_err(|e| format!("Failed to create storage: {}", e))
     }
 
@@ -550,16 +1044,12 @@ impl ImportService {
     async fn build_track_progress_map(
         &self,
         tracks_to_files: &[TrackFile],
-        file_data: &[(String, Vec<u8>, PathBuf)],
+        file_sizes: &HashMap<String, usize>,
         cue_flac_metadata: &Option<HashMap<PathBuf, CueFlacMetadata>>,
     ) -> Result<HashMap<String, Vec<(String, i64, i64)>>, String> {
         use crate::cue_flac::CueFlacProcessor;
 
         let mut result: HashMap<String, Vec<(String, i64, i64)>> = HashMap::new();
-        let file_sizes: HashMap<&str, usize> = file_data
-            .iter()
-            .map(|(name, data, _)| (name.as_str(), data.len()))
-            .collect();
 
         if let Some(ref cue_metadata) = cue_flac_metadata {
             for (flac_path, metadata) in cue_metadata {
@@ -656,15 +1146,18 @@ impl ImportService {
             .await
             .map_err(|e| format!("Failed to link release to storage profile: {}", e))?;
 
+        let (compressed, encrypted) = (storage_profile.compressed, storage_profile.encrypted);
         let storage = self.create_storage(storage_profile).await?;
         let total_files = discovered_files.len();
+        let store_started = std::time::Instant::now();
 
         info!(
             "Starting storage import for release {} ({} files)",
             db_release.id, total_files
         );
 
-        let mut file_data: Vec<(String, Vec<u8>, PathBuf)> = Vec::with_capacity(total_files);
+        // Files are read one at a time as they're stored, not all up front
+        let mut files: Vec<(String, usize, PathBuf)> = Vec::with_capacity(total_files);
         for file in discovered_files.iter() {
             let filename = file
                 .path
@@ -672,32 +1165,45 @@ impl ImportService {
                 .and_then(|n| n.to_str())
                 .ok_or_else(|| format!("Invalid filename: {:?}", file.path))?
                 .to_string();
-            let data = tokio::fs::read(&file.path)
+            let size = tokio::fs::metadata(&file.path)
                 .await
-                .map_err(|e| format!("Failed to read file {:?}: {}", file.path, e))?;
-            file_data.push((filename, data, file.path.clone()));
+                .map_err(|e| format!("Failed to read file {:?}: {}", file.path, e))?
+                .len() as usize;
+            files.push((filename, size, file.path.clone()));
         }
+        let file_sizes: HashMap<String, usize> = files
+            .iter()
+            .map(|(name, size, _)| (name.clone(), *size))
+            .collect();
 
         let file_to_tracks = self
-            .build_track_progress_map(tracks_to_files, &file_data, &cue_flac_metadata)
+            .build_track_progress_map(tracks_to_files, &file_sizes, &cue_flac_metadata)
             .await?;
-        let release_total_bytes: usize = file_data.iter().map(|(_, data, _)| data.len()).sum();
+        let release_total_bytes: usize = files.iter().map(|(_, size, _)| size).sum();
         let mut release_bytes_written = 0usize;
 
         let import_id_owned = import_id.to_string();
-        for (idx, (filename, data, _path)) in file_data.iter().enumerate() {
+        for (idx, (filename, file_size, path)) in files.iter().enumerate() {
             let track_infos = file_to_tracks.get(filename).cloned().unwrap_or_default();
             let progress_tx = self.progress_tx.clone();
             let release_id = db_release.id.clone();
             let import_id_for_closure = import_id_owned.clone();
-            let file_size = data.len();
+            let file_size = *file_size;
             let base_bytes = release_bytes_written;
 
+            let _memory = self
+                .throttle
+                .reserve_memory(bytes_in_flight(file_size as u64, compressed, encrypted))
+                .await;
+            let data = tokio::fs::read(path)
+                .await
+                .map_err(|e| format!("Failed to read file {:?}: {}", path, e))?;
+            let _slot = self.throttle.acquire().await;
             storage
                 .write_file(
                     &db_release.id,
                     filename,
-                    data,
+                    &data,
                     Box::new(move |file_bytes_written, _file_total| {
                         let bytes_written = file_bytes_written as i64;
                         for (track_id, start_byte, end_byte) in &track_infos {
@@ -740,6 +1246,21 @@ impl ImportService {
             );
         }
 
+        let elapsed = store_started.elapsed();
+        info!(
+            "Stored {} bytes for release {} in {:.1}s ({:.1} MB/s)",
+            release_total_bytes,
+            db_release.id,
+            elapsed.as_secs_f64(),
+            release_total_bytes as f64 / 1_048_576.0 / elapsed.as_secs_f64().max(0.001)
+        );
+        let _ = self.progress_tx.send(ImportProgress::Stored {
+            id: db_release.id.clone(),
+            bytes: release_total_bytes as u64,
+            elapsed_ms: elapsed.as_millis() as u64,
+            import_id: Some(import_id.to_string()),
+        });
+
         // Build file_ids map: filename -> DbFile.id
         let files = library_manager
             .get_files_for_release(&db_release.id)
@@ -850,7 +1371,7 @@ impl ImportService {
         });
 
         let mut cover_image_id: Option<String> = None;
-        for (_file, relative_path) in &image_files {
+        for (file, relative_path) in &image_files {
             let source = if relative_path.starts_with(".bae/") {
                 let filename_lower = relative_path.to_lowercase();
                 if filename_lower.contains("-mb") || filename_lower.contains("musicbrainz") {
@@ -880,6 +1401,7 @@ impl ImportService {
                     .await
                     .map_err(|e| format!("Failed to set album cover image: {}", e))?;
                 cover_image_id = Some(image_id.clone());
+                Self::store_cover_palette(album_id, &file.path, library_manager).await;
             }
 
             info!(
@@ -891,6 +1413,36 @@ impl ImportService {
         Ok(cover_image_id)
     }
 
+    /// Extract the cover's palette and store it on the album.
+    /// Failures only cost the UI tint, so they are logged and ignored.
+    async fn store_cover_palette(
+        album_id: &str,
+        cover_path: &std::path::Path,
+        library_manager: &LibraryManager,
+    ) {
+        let data = match tokio::fs::read(cover_path).await {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("Failed to read cover {:?} for palette: {}", cover_path, e);
+                return;
+            }
+        };
+        let palette = tokio::task::spawn_blocking(move || {
+            crate::import::cover_palette::extract_palette(&data)
+        })
+        .await
+        .ok()
+        .flatten();
+        let Some(palette) = palette else {
+            warn!("Could not extract palette from cover {:?}", cover_path);
+            return;
+        };
+
+        if let Err(e) = library_manager.set_album_palette(album_id, &palette).await {
+            warn!("Failed to store cover palette: {}", e);
+        }
+    }
+
     fn image_cover_priority(filename: &str) -> u8 {
         let lower = filename.to_lowercase();
         if lower.starts_with(".bae/") {
@@ -1139,6 +1691,45 @@ impl ImportService {
     }
 
     /// Import for None storage: just record file paths, no storage management.
+    /// Folder import into a reference profile. The files are indexed where
+    /// they are, as for None storage, with the release linked to the profile.
+    #[allow(clippy::too_many_arguments)]
+    async fn run_reference_import(
+        &self,
+        db_release: &DbRelease,
+        folder: &Path,
+        discovered_files: &[DiscoveredFile],
+        tracks_to_files: &[TrackFile],
+        cue_flac_metadata: Option<HashMap<PathBuf, CueFlacMetadata>>,
+        storage_profile: DbStorageProfile,
+        selected_cover_filename: Option<String>,
+        import_id: &str,
+    ) -> Result<(), String> {
+        if !folder.starts_with(&storage_profile.location_path) {
+            return Err(format!(
+                "{} is outside the folder referenced by '{}'",
+                folder.display(),
+                storage_profile.name
+            ));
+        }
+
+        let release_storage = crate::db::DbReleaseStorage::new(&db_release.id, &storage_profile.id);
+        self.database
+            .insert_release_storage(&release_storage)
+            .await
+            .map_err(|e| format!("Failed to link release to storage profile: {}", e))?;
+
+        self.run_none_import(
+            db_release,
+            discovered_files,
+            tracks_to_files,
+            cue_flac_metadata,
+            selected_cover_filename,
+            import_id,
+        )
+        .await
+    }
+
     async fn run_none_import(
         &self,
         db_release: &DbRelease,
@@ -1291,6 +1882,7 @@ impl ImportService {
         torrent_metadata: TorrentImportMetadata,
         cover_art_url: Option<String>,
         selected_cover_filename: Option<String>,
+        import_id: &str,
     ) -> Result<(), String> {
         let library_manager = self.library_manager.get();
         library_manager
@@ -1305,7 +1897,7 @@ impl ImportService {
 
         let _ = self.progress_tx.send(ImportProgress::Started {
             id: db_release.id.clone(),
-            import_id: None,
+            import_id: Some(import_id.to_string()),
         });
 
         info!("Starting torrent download (acquire phase)");
@@ -1320,6 +1912,7 @@ impl ImportService {
             .wait_for_metadata()
             .await
             .map_err(|e| format!("Failed to wait for metadata: {}", e))?;
+        let wanted_files = select_torrent_files(&torrent_handle, &torrent_metadata).await?;
 
         loop {
             let progress = torrent_handle
@@ -1331,8 +1924,18 @@ impl ImportService {
                 id: db_release.id.clone(),
                 percent,
                 phase: Some(ImportPhase::Acquire),
-                import_id: None,
+                import_id: Some(import_id.to_string()),
             });
+            match read_health(&torrent_handle).await {
+                Ok(health) => {
+                    let _ = self.progress_tx.send(ImportProgress::Downloading {
+                        id: db_release.id.clone(),
+                        health,
+                        import_id: Some(import_id.to_string()),
+                    });
+                }
+                Err(e) => warn!("Failed to read torrent health: {}", e),
+            }
             if progress >= 1.0 {
                 break;
             }
@@ -1352,6 +1955,7 @@ impl ImportService {
 
         let mut discovered_files: Vec<DiscoveredFile> = torrent_files
             .iter()
+            .filter(|tf| wanted_files.contains(&tf.path))
             .map(|tf| DiscoveredFile {
                 path: temp_dir.join(&tf.path),
                 size: tf.size as u64,
@@ -1431,7 +2035,7 @@ impl ImportService {
                 id: db_release.id.clone(),
                 percent: release_percent,
                 phase: Some(ImportPhase::Store),
-                import_id: None,
+                import_id: Some(import_id.to_string()),
             });
         }
 
@@ -1444,7 +2048,7 @@ impl ImportService {
                 id: track_file.db_track_id.clone(),
                 release_id: Some(db_release.id.clone()),
                 cover_image_id: None,
-                import_id: None,
+                import_id: Some(import_id.to_string()),
             });
         }
 
@@ -1463,11 +2067,16 @@ impl ImportService {
             .await
             .map_err(|e| format!("Failed to mark release complete: {}", e))?;
 
+        let _ = self
+            .database
+            .update_import_status(import_id, ImportOperationStatus::Complete)
+            .await;
+
         let _ = self.progress_tx.send(ImportProgress::Complete {
             id: db_release.id.clone(),
             release_id: None,
             cover_image_id,
-            import_id: None,
+            import_id: Some(import_id.to_string()),
         });
 
         info!(
@@ -1491,6 +2100,7 @@ impl ImportService {
         cover_art_url: Option<String>,
         storage_profile: DbStorageProfile,
         selected_cover_filename: Option<String>,
+        import_id: &str,
     ) -> Result<(), String> {
         let library_manager = self.library_manager.get();
         library_manager
@@ -1502,7 +2112,7 @@ impl ImportService {
 
         let _ = self.progress_tx.send(ImportProgress::Started {
             id: db_release.id.clone(),
-            import_id: None,
+            import_id: Some(import_id.to_string()),
         });
 
         // Download torrent
@@ -1517,6 +2127,7 @@ impl ImportService {
             .wait_for_metadata()
             .await
             .map_err(|e| format!("Failed to wait for metadata: {}", e))?;
+        let wanted_files = select_torrent_files(&torrent_handle, &torrent_metadata).await?;
 
         loop {
             let progress = torrent_handle
@@ -1528,8 +2139,18 @@ impl ImportService {
                 id: db_release.id.clone(),
                 percent,
                 phase: Some(ImportPhase::Acquire),
-                import_id: None,
+                import_id: Some(import_id.to_string()),
             });
+            match read_health(&torrent_handle).await {
+                Ok(health) => {
+                    let _ = self.progress_tx.send(ImportProgress::Downloading {
+                        id: db_release.id.clone(),
+                        health,
+                        import_id: Some(import_id.to_string()),
+                    });
+                }
+                Err(e) => warn!("Failed to read torrent health: {}", e),
+            }
             if progress >= 1.0 {
                 break;
             }
@@ -1549,6 +2170,7 @@ impl ImportService {
 
         let mut discovered_files: Vec<DiscoveredFile> = torrent_files
             .iter()
+            .filter(|tf| wanted_files.contains(&tf.path))
             .map(|tf| DiscoveredFile {
                 path: temp_dir.join(&tf.path),
                 size: tf.size as u64,
@@ -1604,9 +2226,6 @@ impl ImportService {
             Some(cue_flac_metadata)
         };
 
-        // Use the import ID as a placeholder since torrent imports don't have one
-        let import_id = format!("torrent-{}", db_release.id);
-
         // Import using storage
         self.run_storage_import(
             &db_release,
@@ -1615,25 +2234,42 @@ impl ImportService {
             cue_flac_opt,
             storage_profile,
             selected_cover_filename,
-            &import_id,
+            import_id,
         )
         .await?;
 
-        if seed_after_download {
+        // Keep the original download to seed from; the copy in storage may
+        // not hash to the torrent's pieces. Only a complete download can be
+        // seeded.
+        if seed_after_download && wanted_files.len() == torrent_files.len() {
+            if let Err(e) = self
+                .torrent_manager
+                .get()
+                .keep_seeding(
+                    db_release.id.clone(),
+                    torrent_handle,
+                    torrent_source,
+                    torrent_save_dir.clone(),
+                )
+                .await
+            {
+                warn!("Failed to keep '{}' for seeding: {}", db_album.title, e);
+            }
+        } else {
+            if seed_after_download {
+                info!(
+                    "Not seeding '{}': only some of its files were downloaded",
+                    db_album.title
+                );
+            }
             let _ = self
                 .torrent_manager
                 .get()
-                .start_seeding(db_release.id.clone())
+                .remove_torrent(torrent_handle, true)
                 .await;
         }
 
-        let _ = self
-            .torrent_manager
-            .get()
-            .remove_torrent(torrent_handle, true)
-            .await;
-
-        // Clean up temp files
+        // Clean up temp files, unless they were kept for seeding
         if torrent_save_dir.exists() {
             match tokio::fs::remove_dir_all(&torrent_save_dir).await {
                 Ok(_) => {
@@ -1666,7 +2302,11 @@ impl ImportService {
         toc: CdToc,
         storage_profile: DbStorageProfile,
         selected_cover_filename: Option<String>,
+        eject_when_ripped: bool,
+        import_id: &str,
+        drive_claim: crate::cd::DriveClaim,
     ) -> Result<(), String> {
+        use crate::cd::accuraterip::verify_rip;
         use crate::cd::{CdDrive, CdRipper, CueGenerator, LogGenerator};
         use crate::import::track_to_file_mapper::map_tracks_to_files;
 
@@ -1680,7 +2320,7 @@ impl ImportService {
 
         let _ = self.progress_tx.send(ImportProgress::Started {
             id: db_release.id.clone(),
-            import_id: None,
+            import_id: Some(import_id.to_string()),
         });
 
         // Rip CD
@@ -1698,6 +2338,7 @@ impl ImportService {
         let (rip_progress_tx, mut rip_progress_rx) = mpsc::unbounded_channel::<RipProgress>();
 
         let release_id_for_progress = db_release.id.clone();
+        let import_id_for_progress = import_id.to_string();
         let progress_tx_for_ripping = self.progress_tx.clone();
 
         tokio::spawn(async move {
@@ -1706,17 +2347,27 @@ impl ImportService {
                     id: release_id_for_progress.clone(),
                     percent: rip_progress.percent,
                     phase: Some(ImportPhase::Acquire),
-                    import_id: None,
+                    import_id: Some(import_id_for_progress.clone()),
                 });
             }
         });
 
-        let rip_results = ripper
+        let rip_slot = self.throttle.acquire().await;
+        let mut rip_results = ripper
             .rip_all_tracks(Some(rip_progress_tx))
             .await
             .map_err(|e| format!("Failed to rip CD: {}", e))?;
+        drop(rip_slot);
 
         info!("CD ripping completed, {} tracks ripped", rip_results.len());
+        if eject_when_ripped {
+            eject_ripped_disc(drive.clone()).await;
+        }
+        // The rest works from the ripped files, so the drive is free
+        drop(drive_claim);
+
+        verify_rip(&toc, &mut rip_results).await;
+        save_track_rips(library_manager, &db_tracks, &rip_results).await?;
 
         // Generate CUE and log files
         let artist_name = "Unknown Artist".to_string();
@@ -1778,9 +2429,6 @@ impl ImportService {
             .await
             .map_err(|e| format!("Failed to extract durations: {}", e))?;
 
-        // Use the import ID as a placeholder
-        let import_id = format!("cd-{}", db_release.id);
-
         // Import using storage
         self.run_storage_import(
             &db_release,
@@ -1789,7 +2437,7 @@ impl ImportService {
             cue_flac_metadata,
             storage_profile,
             selected_cover_filename,
-            &import_id,
+            import_id,
         )
         .await?;
 
@@ -1814,8 +2462,12 @@ impl ImportService {
         drive_path: PathBuf,
         toc: CdToc,
         _selected_cover_filename: Option<String>,
+        eject_when_ripped: bool,
+        import_id: &str,
+        drive_claim: crate::cd::DriveClaim,
     ) -> Result<(), String> {
-        use crate::cd::{CdDrive, CdRipper};
+        use crate::cd::accuraterip::verify_rip;
+        use crate::cd::{CdDrive, CdRipper, LogGenerator};
 
         let library_manager = self.library_manager.get();
         library_manager
@@ -1831,7 +2483,7 @@ impl ImportService {
 
         let _ = self.progress_tx.send(ImportProgress::Started {
             id: db_release.id.clone(),
-            import_id: None,
+            import_id: Some(import_id.to_string()),
         });
 
         let temp_dir = std::env::temp_dir().join(format!("bae_cd_rip_{}", uuid::Uuid::new_v4()));
@@ -1845,12 +2497,21 @@ impl ImportService {
         };
 
         let ripper = CdRipper::new(drive.clone(), toc.clone(), temp_dir.clone());
-        let rip_results = ripper
+        let rip_slot = self.throttle.acquire().await;
+        let mut rip_results = ripper
             .rip_all_tracks(None)
             .await
             .map_err(|e| format!("Failed to rip CD: {}", e))?;
+        drop(rip_slot);
 
         info!("CD ripping completed, {} tracks ripped", rip_results.len());
+        if eject_when_ripped {
+            eject_ripped_disc(drive.clone()).await;
+        }
+        drop(drive_claim);
+
+        verify_rip(&toc, &mut rip_results).await;
+        save_track_rips(library_manager, &db_tracks, &rip_results).await?;
 
         for (idx, result) in rip_results.iter().enumerate() {
             let filename = result
@@ -1882,6 +2543,26 @@ impl ImportService {
             );
         }
 
+        // The rip log sits next to the audio like in storage
+        let log_filename = format!("{}.log", db_album.title.replace("/", "_"));
+        let log_path = temp_dir.join(&log_filename);
+        LogGenerator::write_log_file(&toc, &rip_results, &drive.name, &log_path)
+            .map_err(|e| format!("Failed to write log file: {}", e))?;
+        let log_size = tokio::fs::metadata(&log_path)
+            .await
+            .map(|m| m.len() as i64)
+            .unwrap_or(0);
+        let log_source = log_path
+            .to_str()
+            .ok_or_else(|| format!("Cannot convert path to string: {:?}", log_path))?;
+        library_manager
+            .add_file(
+                &DbFile::new(&db_release.id, &log_filename, log_size, "log")
+                    .with_source_path(log_source),
+            )
+            .await
+            .map_err(|e| format!("Failed to add file record: {}", e))?;
+
         for track in &db_tracks {
             library_manager
                 .mark_track_complete(&track.id)
@@ -1900,11 +2581,16 @@ impl ImportService {
             .await
             .map_err(|e| format!("Failed to mark release complete: {}", e))?;
 
+        let _ = self
+            .database
+            .update_import_status(import_id, ImportOperationStatus::Complete)
+            .await;
+
         let _ = self.progress_tx.send(ImportProgress::Complete {
             id: db_release.id.clone(),
             release_id: None,
             cover_image_id: None,
-            import_id: None,
+            import_id: Some(import_id.to_string()),
         });
 
         info!(
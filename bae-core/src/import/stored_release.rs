@@ -0,0 +1,158 @@
+//! Release metadata kept in a release folder's `.bae` folder.
+//!
+//! A folder import writes the release it was matched to into
+//! [`STORED_RELEASE_FILE`], next to the cover art it downloaded. A folder
+//! that already has one, say restored from a backup, can be imported again
+//! straight from it: no matching and no metadata lookups, just its files
+//! stored and registered again.
+
+use crate::db::{DbAlbum, DbAlbumArtist, DbArtist, DbRelease, DbTrack};
+use crate::import::discogs_parser::ParsedAlbum;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Where a release folder keeps its release, relative to the folder
+pub const STORED_RELEASE_FILE: &str = ".bae/release.json";
+
+/// Bumped when the file changes in a way older readers can't follow
+const STORED_RELEASE_VERSION: u32 = 1;
+
+/// The release a folder was imported as
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredRelease {
+    pub version: u32,
+    /// Album artists as shown while importing
+    pub artist_name: String,
+    pub album: DbAlbum,
+    pub release: DbRelease,
+    pub tracks: Vec<DbTrack>,
+    pub artists: Vec<DbArtist>,
+    pub album_artists: Vec<DbAlbumArtist>,
+    /// Files left out of the import, relative to the release folder
+    pub excluded_files: Vec<PathBuf>,
+    /// Image picked as the cover, relative to the release folder
+    pub selected_cover_filename: Option<String>,
+}
+
+impl StoredRelease {
+    /// `excluded_files` are absolute paths inside `folder`
+    pub fn new(
+        artist_name: String,
+        (album, release, tracks, artists, album_artists): ParsedAlbum,
+        folder: &Path,
+        excluded_files: &[PathBuf],
+        selected_cover_filename: Option<String>,
+    ) -> Self {
+        StoredRelease {
+            version: STORED_RELEASE_VERSION,
+            artist_name,
+            album,
+            release,
+            tracks,
+            artists,
+            album_artists,
+            excluded_files: excluded_files
+                .iter()
+                .map(|path| path.strip_prefix(folder).unwrap_or(path).to_path_buf())
+                .collect(),
+            selected_cover_filename,
+        }
+    }
+
+    /// Files left out of the import, as absolute paths inside `folder`
+    pub fn excluded_paths(&self, folder: &Path) -> Vec<PathBuf> {
+        self.excluded_files
+            .iter()
+            .map(|path| folder.join(path))
+            .collect()
+    }
+}
+
+/// Write the release into the folder's `.bae` folder, replacing one there
+pub fn write_stored_release(folder: &Path, stored: &StoredRelease) -> Result<(), String> {
+    let path = folder.join(STORED_RELEASE_FILE);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+    let json = serde_json::to_vec_pretty(stored)
+        .map_err(|e| format!("Failed to serialize release: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
+
+/// The release stored in the folder, if it has one
+pub fn read_stored_release(folder: &Path) -> Result<Option<StoredRelease>, String> {
+    let path = folder.join(STORED_RELEASE_FILE);
+    let json = match std::fs::read(&path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read {:?}: {}", path, e)),
+    };
+    let stored: StoredRelease =
+        serde_json::from_slice(&json).map_err(|e| format!("Failed to parse {:?}: {}", path, e))?;
+    if stored.version > STORED_RELEASE_VERSION {
+        return Err(format!("{:?} was written by a newer version of bae", path));
+    }
+    Ok(Some(stored))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import::manual_release::{parse_manual_release, ManualRelease};
+
+    fn stored(folder: &Path) -> StoredRelease {
+        let parsed = parse_manual_release(
+            &ManualRelease {
+                title: "Rehearsal".to_string(),
+                artist: "The Garage".to_string(),
+                year: Some(2019),
+                tracks: vec!["One".to_string(), "Two".to_string()],
+            },
+            None,
+        )
+        .unwrap();
+        StoredRelease::new(
+            "The Garage".to_string(),
+            parsed,
+            folder,
+            &[folder.join("extra").join("take 2.flac")],
+            Some(".bae/cover-mb.jpg".to_string()),
+        )
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let written = stored(dir.path());
+        write_stored_release(dir.path(), &written).unwrap();
+
+        let read = read_stored_release(dir.path()).unwrap().unwrap();
+        assert_eq!(read.album, written.album);
+        assert_eq!(read.release, written.release);
+        assert_eq!(read.tracks, written.tracks);
+        assert_eq!(
+            read.excluded_files,
+            vec![Path::new("extra").join("take 2.flac")]
+        );
+        assert_eq!(
+            read.excluded_paths(Path::new("/restored")),
+            vec![Path::new("/restored").join("extra").join("take 2.flac")]
+        );
+    }
+
+    #[test]
+    fn test_folder_without_stored_release() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(read_stored_release(dir.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_newer_version_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut newer = stored(dir.path());
+        newer.version = STORED_RELEASE_VERSION + 1;
+        write_stored_release(dir.path(), &newer).unwrap();
+        assert!(read_stored_release(dir.path()).is_err());
+    }
+}
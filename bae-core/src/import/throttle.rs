@@ -0,0 +1,191 @@
+//! Keeps imports from overheating the machine.
+//!
+//! Storing a file (compressing and encrypting it) and ripping a CD each take
+//! a slot first. Taking a slot samples the load average and CPU temperature,
+//! and rests longer while the system is under pressure. Low power mode caps
+//! that work at one slot across every import, CD drives included, keeps
+//! encryption on one core, and always rests between files so a fanless
+//! machine can shed heat. The memory that file data takes while it's stored
+//! is capped separately, see [`crate::import::memory_budget`].
+
+use crate::import::memory_budget::{MemoryBudget, MemoryReservation};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::debug;
+
+/// Rest before each slot in low power mode
+const LOW_POWER_REST: Duration = Duration::from_millis(500);
+/// Rest before each slot while the system is busy or hot
+const PRESSURE_REST: Duration = Duration::from_secs(2);
+/// One-minute load per core above which the system counts as busy
+const BUSY_LOAD_PER_CORE: f64 = 0.9;
+/// CPU temperature above which the system counts as hot
+const HOT_CELSIUS: f64 = 85.0;
+
+/// Load and temperature at one moment. Either is None where it can't be read.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SystemPressure {
+    pub load_per_core: Option<f64>,
+    pub cpu_celsius: Option<f64>,
+}
+
+impl SystemPressure {
+    pub fn sample() -> Self {
+        SystemPressure {
+            load_per_core: load_per_core(),
+            cpu_celsius: cpu_celsius(),
+        }
+    }
+
+    /// Whether the system is busy or hot enough to back off
+    pub fn is_high(&self) -> bool {
+        self.load_per_core
+            .is_some_and(|load| load > BUSY_LOAD_PER_CORE)
+            || self.cpu_celsius.is_some_and(|c| c > HOT_CELSIUS)
+    }
+}
+
+/// How long to rest before the next slot
+pub fn import_rest(pressure: SystemPressure, low_power: bool) -> Duration {
+    if pressure.is_high() {
+        PRESSURE_REST
+    } else if low_power {
+        LOW_POWER_REST
+    } else {
+        Duration::ZERO
+    }
+}
+
+/// Shared by the import service and its handle, so low power mode can be
+/// switched while imports run
+#[derive(Clone)]
+pub struct ImportThrottle {
+    low_power: Arc<AtomicBool>,
+    low_power_slot: Arc<Semaphore>,
+    memory: MemoryBudget,
+}
+
+impl Default for ImportThrottle {
+    fn default() -> Self {
+        ImportThrottle {
+            low_power: Arc::new(AtomicBool::new(false)),
+            low_power_slot: Arc::new(Semaphore::new(1)),
+            memory: MemoryBudget::default(),
+        }
+    }
+}
+
+impl ImportThrottle {
+    pub fn set_low_power(&self, low_power: bool) {
+        self.low_power.store(low_power, Ordering::Relaxed);
+    }
+
+    pub fn low_power(&self) -> bool {
+        self.low_power.load(Ordering::Relaxed)
+    }
+
+    pub fn set_memory_budget_mb(&self, megabytes: u32) {
+        self.memory.set_budget_mb(megabytes);
+    }
+
+    /// Wait until `bytes` of file data fit in the memory budget. Hold the
+    /// reservation until the data is dropped.
+    pub async fn reserve_memory(&self, bytes: u64) -> MemoryReservation {
+        self.memory.reserve(bytes).await
+    }
+
+    /// Wait for a slot to store a file or rip a disc. Hold the slot until
+    /// the work is done.
+    pub async fn acquire(&self) -> ImportSlot {
+        let low_power = self.low_power();
+        let permit = if low_power {
+            // The semaphore is never closed
+            self.low_power_slot.clone().acquire_owned().await.ok()
+        } else {
+            None
+        };
+
+        let pressure = SystemPressure::sample();
+        let rest = import_rest(pressure, low_power);
+        if !rest.is_zero() {
+            debug!("Resting import for {:?} ({:?})", rest, pressure);
+            tokio::time::sleep(rest).await;
+        }
+        ImportSlot { _permit: permit }
+    }
+}
+
+/// A held import slot, released on drop
+pub struct ImportSlot {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn load_per_core() -> Option<f64> {
+    let mut loads = [0f64; 3];
+    // SAFETY: the buffer holds the one sample asked for
+    let read = unsafe { libc::getloadavg(loads.as_mut_ptr(), 1) };
+    if read < 1 {
+        return None;
+    }
+    let cores = std::thread::available_parallelism().ok()?.get();
+    Some(loads[0] / cores as f64)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn load_per_core() -> Option<f64> {
+    None
+}
+
+/// Hottest thermal zone, in degrees Celsius
+#[cfg(target_os = "linux")]
+fn cpu_celsius() -> Option<f64> {
+    std::fs::read_dir("/sys/class/thermal")
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with("thermal_zone")
+        })
+        .filter_map(|entry| std::fs::read_to_string(entry.path().join("temp")).ok())
+        .filter_map(|millis| millis.trim().parse::<f64>().ok())
+        .map(|millis| millis / 1000.0)
+        .reduce(f64::max)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn cpu_celsius() -> Option<f64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_rest_backs_off_under_pressure() {
+        let calm = SystemPressure {
+            load_per_core: Some(0.3),
+            cpu_celsius: Some(55.0),
+        };
+        let busy = SystemPressure {
+            load_per_core: Some(1.5),
+            cpu_celsius: None,
+        };
+        let hot = SystemPressure {
+            load_per_core: Some(0.3),
+            cpu_celsius: Some(92.0),
+        };
+
+        assert_eq!(import_rest(calm, false), Duration::ZERO);
+        assert_eq!(import_rest(calm, true), LOW_POWER_REST);
+        assert_eq!(import_rest(busy, false), PRESSURE_REST);
+        assert_eq!(import_rest(hot, true), PRESSURE_REST);
+        // Nothing readable counts as calm
+        assert!(!SystemPressure::default().is_high());
+    }
+}
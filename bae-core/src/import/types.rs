@@ -23,12 +23,16 @@
 #[cfg(feature = "cd-rip")]
 use crate::cd::drive::CdToc;
 #[cfg(feature = "cd-rip")]
+use crate::cd::DriveClaim;
+#[cfg(feature = "cd-rip")]
 use crate::db::DbTrack;
 #[cfg(feature = "torrent")]
 use crate::import::handle::TorrentImportMetadata;
+use crate::import::manual_release::ManualRelease;
+use crate::import::needle_drop::SideSplit;
 use crate::{
     cue_flac::CueSheet,
-    db::{DbAlbum, DbRelease},
+    db::{DbAlbum, DbRelease, ImportKind, ImportOperationStatus},
     discogs::DiscogsRelease,
     musicbrainz::MbRelease,
 };
@@ -42,6 +46,8 @@ pub enum ImportRequest {
         import_id: String,
         discogs_release: Option<DiscogsRelease>,
         mb_release: Option<MbRelease>,
+        /// Details typed in by the user, for releases no database knows about
+        manual_release: Option<ManualRelease>,
         folder: PathBuf,
         master_year: u32,
         cover_art_url: Option<String>,
@@ -50,9 +56,28 @@ pub enum ImportRequest {
         /// User-selected cover image filename (relative path from album folder).
         /// If set, this image will be marked as the album cover instead of using priority logic.
         selected_cover_filename: Option<String>,
+        /// Audio files in the folder to leave out, e.g. unwanted duplicate copies of a track
+        excluded_files: Vec<PathBuf>,
+        /// Vinyl sides recorded as one file each, split into tracks before
+        /// import. Empty for folders with a file per track.
+        needle_drop_sides: Vec<SideSplit>,
+    },
+    /// A folder imported before, imported again as the release stored in its
+    /// `.bae` folder, without matching
+    Replay {
+        /// Unique ID for this import operation (generated by UI for progress tracking)
+        import_id: String,
+        folder: PathBuf,
+        /// Storage profile ID. None means no bae storage (files stay in place).
+        storage_profile_id: Option<String>,
+        /// User-selected cover image filename (relative path from album folder).
+        /// None keeps the cover picked when the folder was first imported.
+        selected_cover_filename: Option<String>,
     },
     #[cfg(feature = "torrent")]
     Torrent {
+        /// Unique ID for this import operation (generated by UI for progress tracking)
+        import_id: String,
         torrent_source: TorrentSource,
         discogs_release: Option<DiscogsRelease>,
         mb_release: Option<MbRelease>,
@@ -67,8 +92,13 @@ pub enum ImportRequest {
     },
     #[cfg(feature = "cd-rip")]
     CD {
+        /// Unique ID for this import operation (generated by UI for progress tracking)
+        import_id: String,
         discogs_release: Option<DiscogsRelease>,
         mb_release: Option<MbRelease>,
+        /// Placeholder details for a disc whose DiscID couldn't be looked up.
+        /// The lookup is queued and the match applied once it succeeds.
+        manual_release: Option<ManualRelease>,
         drive_path: PathBuf,
         master_year: u32,
         cover_art_url: Option<String>,
@@ -76,6 +106,8 @@ pub enum ImportRequest {
         storage_profile_id: Option<String>,
         /// User-selected cover image filename (relative path from album folder).
         selected_cover_filename: Option<String>,
+        /// Eject the disc once every track is ripped
+        eject_when_ripped: bool,
     },
 }
 
@@ -98,6 +130,13 @@ pub enum ImportProgress {
         artist_name: String,
         cover_art_url: Option<String>,
     },
+    /// The import moved through the queue: queued, started, paused or
+    /// cancelled
+    StatusChanged {
+        import_id: String,
+        status: ImportOperationStatus,
+        kind: ImportKind,
+    },
     Started {
         id: String,
         import_id: Option<String>,
@@ -112,6 +151,21 @@ pub enum ImportProgress {
         phase: Option<ImportPhase>,
         import_id: Option<String>,
     },
+    /// Live state of a torrent download, sent with each acquire-phase
+    /// progress update
+    Downloading {
+        id: String,
+        health: DownloadHealth,
+        import_id: Option<String>,
+    },
+    /// A release's files are all stored. Reports the store phase's
+    /// throughput; sent just before the release's `Complete`.
+    Stored {
+        id: String,
+        bytes: u64,
+        elapsed_ms: u64,
+        import_id: Option<String>,
+    },
     Complete {
         id: String,
         /// For track completions, this is the parent release ID (for filtering)
@@ -128,6 +182,22 @@ pub enum ImportProgress {
     },
 }
 
+/// How a torrent download is going: whether it's worth waiting for
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DownloadHealth {
+    /// Payload bytes per second
+    pub download_rate: u64,
+    pub peers: u32,
+    pub seeds: u32,
+    pub downloaded_bytes: u64,
+    pub total_bytes: u64,
+    /// None while nothing is coming in
+    pub eta_secs: Option<u64>,
+    /// Share of each stretch of the torrent that's downloaded, from 0.0 to
+    /// 1.0, in torrent order
+    pub piece_bar: Vec<f32>,
+}
+
 /// Phase of import process (applies to all import types)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ImportPhase {
@@ -146,6 +216,8 @@ pub enum ImportPhase {
 pub enum PrepareStep {
     ParsingMetadata,
     DownloadingCoverArt,
+    ExtractingDiscImages,
+    SplittingSides,
     DiscoveringFiles,
     ValidatingTracks,
     SavingToDatabase,
@@ -158,6 +230,8 @@ impl PrepareStep {
         match self {
             PrepareStep::ParsingMetadata => "Parsing metadata...",
             PrepareStep::DownloadingCoverArt => "Downloading cover art...",
+            PrepareStep::ExtractingDiscImages => "Extracting disc images...",
+            PrepareStep::SplittingSides => "Splitting vinyl sides...",
             PrepareStep::DiscoveringFiles => "Discovering files...",
             PrepareStep::ValidatingTracks => "Validating tracks...",
             PrepareStep::SavingToDatabase => "Saving to database...",
@@ -233,6 +307,8 @@ pub enum ImportCommand {
         db_album: DbAlbum,
         /// Database release record
         db_release: DbRelease,
+        /// Release folder the files were discovered in
+        folder: PathBuf,
         /// Logical track → physical file mappings
         tracks_to_files: Vec<TrackFile>,
         /// Files discovered during folder scan
@@ -268,6 +344,8 @@ pub enum ImportCommand {
         storage_profile_id: Option<String>,
         /// User-selected cover image filename
         selected_cover_filename: Option<String>,
+        /// Import operation ID for progress tracking
+        import_id: String,
     },
     /// CD-based import: service will rip CD first (acquire phase), then process like folder import
     #[cfg(feature = "cd-rip")]
@@ -286,5 +364,33 @@ pub enum ImportCommand {
         storage_profile_id: Option<String>,
         /// User-selected cover image filename
         selected_cover_filename: Option<String>,
+        /// Eject the disc once every track is ripped
+        eject_when_ripped: bool,
+        /// Import operation ID for progress tracking
+        import_id: String,
+        /// Keeps the drive from starting another rip until this one is ripped
+        drive_claim: DriveClaim,
     },
 }
+
+impl ImportCommand {
+    pub fn import_id(&self) -> &str {
+        match self {
+            ImportCommand::Folder { import_id, .. } => import_id,
+            #[cfg(feature = "torrent")]
+            ImportCommand::Torrent { import_id, .. } => import_id,
+            #[cfg(feature = "cd-rip")]
+            ImportCommand::CD { import_id, .. } => import_id,
+        }
+    }
+
+    pub fn kind(&self) -> ImportKind {
+        match self {
+            ImportCommand::Folder { .. } => ImportKind::Folder,
+            #[cfg(feature = "torrent")]
+            ImportCommand::Torrent { .. } => ImportKind::Torrent,
+            #[cfg(feature = "cd-rip")]
+            ImportCommand::CD { .. } => ImportKind::Cd,
+        }
+    }
+}
@@ -9,11 +9,14 @@ pub mod cue_flac;
 pub mod db;
 pub mod discogs;
 pub mod encryption;
+pub mod health;
 pub mod import;
 pub mod library;
 pub mod musicbrainz;
 pub mod network;
 pub mod playback;
+pub mod remote;
+pub mod scrobble;
 pub mod sodium_ffi;
 pub mod storage;
 pub mod subsonic;
@@ -21,3 +24,4 @@ pub mod subsonic;
 pub mod test_support;
 #[cfg(feature = "torrent")]
 pub mod torrent;
+pub mod transcode;
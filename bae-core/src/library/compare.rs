@@ -0,0 +1,293 @@
+//! Side-by-side comparison of two releases (editions) of an album.
+//!
+//! Track lists are lined up by title first, since remasters often add or
+//! reorder tracks, then by disc and track number for retitled ones. Format
+//! details come from the stored audio formats. Loudness needs every audio
+//! file decoded, so it's measured separately and can fill in later.
+
+use crate::audio_codec::{self, LoudnessMeter, LoudnessStats};
+use crate::db::DbTrack;
+use crate::import::folder_scanner::is_audio_file;
+use crate::library::dynamic_range::release_dynamic_range;
+use crate::library::export::read_release_file;
+use crate::library::{LibraryError, LibraryManager};
+use crate::storage::create_storage_reader;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Format and size of one edition
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditionSummary {
+    pub release_id: String,
+    pub track_count: usize,
+    pub total_duration_ms: i64,
+    /// Format of the first track with a stored audio format
+    pub format: Option<String>,
+    pub sample_rate: Option<i64>,
+    pub bits_per_sample: Option<i64>,
+    /// Average over all audio files, from their size and the total duration
+    pub bitrate_kbps: Option<i64>,
+    /// DR score from the tracks measured so far
+    pub dynamic_range: Option<i32>,
+}
+
+/// A track and its counterpart on the other edition, if there is one
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackPair {
+    pub left: Option<DbTrack>,
+    pub right: Option<DbTrack>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReleaseComparison {
+    pub left: EditionSummary,
+    pub right: EditionSummary,
+    pub tracks: Vec<TrackPair>,
+}
+
+/// Compare the track lists and formats of two releases
+pub async fn compare_releases(
+    library_manager: &LibraryManager,
+    left_release_id: &str,
+    right_release_id: &str,
+) -> Result<ReleaseComparison, LibraryError> {
+    let left_tracks = library_manager.get_tracks(left_release_id).await?;
+    let right_tracks = library_manager.get_tracks(right_release_id).await?;
+
+    Ok(ReleaseComparison {
+        left: summarize_edition(library_manager, left_release_id, &left_tracks).await?,
+        right: summarize_edition(library_manager, right_release_id, &right_tracks).await?,
+        tracks: align_tracks(&left_tracks, &right_tracks),
+    })
+}
+
+async fn summarize_edition(
+    library_manager: &LibraryManager,
+    release_id: &str,
+    tracks: &[DbTrack],
+) -> Result<EditionSummary, LibraryError> {
+    let total_duration_ms: i64 = tracks.iter().filter_map(|t| t.duration_ms).sum();
+
+    let mut summary = EditionSummary {
+        release_id: release_id.to_string(),
+        track_count: tracks.len(),
+        total_duration_ms,
+        format: None,
+        sample_rate: None,
+        bits_per_sample: None,
+        bitrate_kbps: None,
+        dynamic_range: None,
+    };
+
+    let mut audio_file_ids = HashSet::new();
+    for track in tracks {
+        let Some(audio_format) = library_manager
+            .get_audio_format_by_track_id(&track.id)
+            .await?
+        else {
+            continue;
+        };
+        if summary.format.is_none() {
+            summary.format = Some(audio_format.format.to_uppercase());
+            summary.sample_rate = Some(audio_format.sample_rate);
+            summary.bits_per_sample = Some(audio_format.bits_per_sample);
+        }
+        if let Some(file_id) = audio_format.file_id {
+            audio_file_ids.insert(file_id);
+        }
+    }
+
+    let audio_bytes: i64 = library_manager
+        .get_files_for_release(release_id)
+        .await?
+        .iter()
+        .filter(|f| audio_file_ids.contains(&f.id))
+        .map(|f| f.file_size)
+        .sum();
+    if audio_bytes > 0 && total_duration_ms > 0 {
+        summary.bitrate_kbps = Some(audio_bytes * 8 / total_duration_ms);
+    }
+
+    let track_scores = library_manager
+        .get_track_dynamic_ranges_for_release(release_id)
+        .await?;
+    summary.dynamic_range = release_dynamic_range(track_scores.into_values().flatten());
+
+    Ok(summary)
+}
+
+/// Pair up tracks of two editions: same title first, then same disc and
+/// track number. Left order is kept; tracks only on the right follow at the end.
+pub fn align_tracks(left: &[DbTrack], right: &[DbTrack]) -> Vec<TrackPair> {
+    let mut right_used = vec![false; right.len()];
+    let mut matches: Vec<Option<usize>> = vec![None; left.len()];
+
+    for (i, track) in left.iter().enumerate() {
+        let title = normalize_title(&track.title);
+        if let Some(j) =
+            (0..right.len()).find(|&j| !right_used[j] && normalize_title(&right[j].title) == title)
+        {
+            right_used[j] = true;
+            matches[i] = Some(j);
+        }
+    }
+
+    for (i, track) in left.iter().enumerate() {
+        if matches[i].is_some() {
+            continue;
+        }
+        let position = (track.disc_number.unwrap_or(1), track.track_number);
+        if let Some(j) = (0..right.len()).find(|&j| {
+            !right_used[j]
+                && track.track_number.is_some()
+                && (right[j].disc_number.unwrap_or(1), right[j].track_number) == position
+        }) {
+            right_used[j] = true;
+            matches[i] = Some(j);
+        }
+    }
+
+    let mut pairs: Vec<TrackPair> = left
+        .iter()
+        .zip(&matches)
+        .map(|(track, matched)| TrackPair {
+            left: Some(track.clone()),
+            right: matched.map(|j| right[j].clone()),
+        })
+        .collect();
+    pairs.extend(
+        right
+            .iter()
+            .zip(&right_used)
+            .filter(|(_, used)| !**used)
+            .map(|(track, _)| TrackPair {
+                left: None,
+                right: Some(track.clone()),
+            }),
+    );
+    pairs
+}
+
+/// Lowercased title without punctuation or a trailing "(Remastered ...)" style note
+fn normalize_title(title: &str) -> String {
+    let title = title.to_lowercase();
+    let title = match title.find(['(', '[']) {
+        Some(index) if index > 0 => &title[..index],
+        _ => &title,
+    };
+    title
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Peak and RMS level over all audio files of a release. Decodes every file,
+/// so this takes a while; None if the release has no decodable audio.
+pub async fn measure_release_loudness(
+    library_manager: &LibraryManager,
+    release_id: &str,
+) -> Result<Option<LoudnessStats>, String> {
+    let storage_profile = library_manager
+        .get_storage_profile_for_release(release_id)
+        .await
+        .map_err(|e| format!("Failed to get storage profile: {}", e))?;
+    let storage = match &storage_profile {
+        Some(profile) => Some(
+            create_storage_reader(profile)
+                .await
+                .map_err(|e| format!("Failed to create storage reader: {}", e))?,
+        ),
+        None => None,
+    };
+
+    let files = library_manager
+        .get_files_for_release(release_id)
+        .await
+        .map_err(|e| format!("Failed to get files: {}", e))?;
+
+    let mut meter = LoudnessMeter::new();
+    for file in files
+        .iter()
+        .filter(|f| is_audio_file(Path::new(&f.original_filename)))
+    {
+        let data = read_release_file(
+            file,
+            storage.as_deref().zip(storage_profile.as_ref()),
+            library_manager.encryption_service(),
+        )
+        .await?;
+
+        meter = tokio::task::spawn_blocking(move || {
+            audio_codec::measure_loudness(&data, &mut meter).map(|_| meter)
+        })
+        .await
+        .map_err(|e| format!("Loudness task failed: {}", e))??;
+    }
+
+    Ok(meter.stats())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(title: &str, disc: Option<i32>, number: i32) -> DbTrack {
+        DbTrack {
+            disc_number: disc,
+            ..DbTrack::new_test(
+                "release",
+                &format!("{}-{}", title, number),
+                title,
+                Some(number),
+            )
+        }
+    }
+
+    fn titles(pairs: &[TrackPair]) -> Vec<(Option<&str>, Option<&str>)> {
+        pairs
+            .iter()
+            .map(|p| {
+                (
+                    p.left.as_ref().map(|t| t.title.as_str()),
+                    p.right.as_ref().map(|t| t.title.as_str()),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_align_tracks_by_title_ignores_remaster_note() {
+        let original = vec![track("Intro", None, 1), track("So What", None, 2)];
+        let remaster = vec![
+            track("So What (2009 Remaster)", None, 1),
+            track("Intro", None, 2),
+            track("So What - Alternate Take", None, 3),
+        ];
+
+        assert_eq!(
+            titles(&align_tracks(&original, &remaster)),
+            vec![
+                (Some("Intro"), Some("Intro")),
+                (Some("So What"), Some("So What (2009 Remaster)")),
+                (None, Some("So What - Alternate Take")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_align_retitled_tracks_by_position() {
+        let left = vec![track("Side A", Some(1), 1), track("Only Here", Some(2), 1)];
+        let right = vec![track("Part One", Some(1), 1)];
+
+        assert_eq!(
+            titles(&align_tracks(&left, &right)),
+            vec![
+                (Some("Side A"), Some("Part One")),
+                (Some("Only Here"), None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,412 @@
+//! Featured-artist credit normalization.
+//!
+//! Metadata sources spell the same credit several ways ("A feat. B",
+//! "A ft. B", "A (featuring B)"), which spreads one artist over several
+//! names. Album credits are normalized at import by the configured rules.
+//! The credit as the source gave it stays on the album-artist link, so the
+//! library can be normalized again from the originals when the rules change.
+
+use crate::config::FeaturedCreditStyle;
+use crate::db::{DbAlbumArtist, DbArtist};
+use crate::library::{LibraryError, LibraryManager};
+use tracing::info;
+
+/// How featuring markers are recognized and what a credit becomes
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreditRules {
+    pub style: FeaturedCreditStyle,
+    /// Words that introduce a featured artist, matched case-insensitively
+    pub markers: Vec<String>,
+}
+
+impl Default for CreditRules {
+    fn default() -> Self {
+        CreditRules {
+            style: FeaturedCreditStyle::Standardize,
+            markers: ["featuring", "feat.", "feat", "ft.", "ft"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+impl CreditRules {
+    /// Artist names a credit normalizes to, main artist first. A credit
+    /// without a featured artist, or any credit under `Keep`, is returned as is.
+    pub fn normalize(&self, credit: &str) -> Vec<String> {
+        let split = match self.style {
+            FeaturedCreditStyle::Keep => None,
+            _ => self.split_featured(credit),
+        };
+        let Some((main, featured)) = split else {
+            return vec![credit.to_string()];
+        };
+        if self.style == FeaturedCreditStyle::Standardize {
+            return vec![format!("{} feat. {}", main, featured)];
+        }
+        std::iter::once(main.to_string())
+            .chain(
+                featured
+                    .split([',', '&'])
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(String::from),
+            )
+            .collect()
+    }
+
+    /// Main artist and featured part of a credit, at the first marker that
+    /// stands as its own word, e.g. "A (ft. B)" -> ("A", "B")
+    fn split_featured<'a>(&self, credit: &'a str) -> Option<(&'a str, &'a str)> {
+        let mut markers: Vec<&str> = self.markers.iter().map(String::as_str).collect();
+        // Longest first, so "feat." wins over "feat"
+        markers.sort_by_key(|m| std::cmp::Reverse(m.len()));
+
+        let mut previous: Option<char> = None;
+        for (index, c) in credit.char_indices() {
+            let at_word_start =
+                matches!(previous, Some(p) if p.is_whitespace() || p == '(' || p == '[');
+            previous = Some(c);
+            if !at_word_start {
+                continue;
+            }
+            for marker in &markers {
+                let end = index + marker.len();
+                let is_marker = credit
+                    .get(index..end)
+                    .is_some_and(|word| word.eq_ignore_ascii_case(marker));
+                if !is_marker || !credit[end..].starts_with(char::is_whitespace) {
+                    continue;
+                }
+                let main = credit[..index].trim_end();
+                let opened = main.ends_with(['(', '[']);
+                let main = main.trim_end_matches(['(', '[']).trim_end();
+                let mut featured = credit[end..].trim();
+                if opened {
+                    featured = featured.trim_end_matches([')', ']']).trim_end();
+                }
+                if main.is_empty() || featured.is_empty() {
+                    return None;
+                }
+                return Some((main, featured));
+            }
+        }
+        None
+    }
+}
+
+/// Normalize the credits of an album about to be imported. Renamed artists
+/// keep their links; featured artists split off get new artists linked
+/// after the one they were credited with.
+pub fn normalize_album_artists(
+    rules: &CreditRules,
+    artists: Vec<DbArtist>,
+    mut album_artists: Vec<DbAlbumArtist>,
+) -> (Vec<DbArtist>, Vec<DbAlbumArtist>) {
+    album_artists.sort_by_key(|link| link.position);
+
+    let mut normalized_artists = Vec::new();
+    let mut normalized_links = Vec::new();
+    for link in album_artists {
+        let Some(artist) = artists.iter().find(|a| a.id == link.artist_id) else {
+            continue;
+        };
+        let names = rules.normalize(&artist.name);
+        let credited_as = (names != [artist.name.clone()]).then(|| artist.name.clone());
+        for (index, name) in names.into_iter().enumerate() {
+            let artist = if index == 0 {
+                DbArtist {
+                    sort_name: Some(name.clone()),
+                    name,
+                    ..artist.clone()
+                }
+            } else {
+                DbArtist::new(&name)
+            };
+            normalized_links.push(DbAlbumArtist {
+                credited_as: credited_as.clone(),
+                ..DbAlbumArtist::new(&link.album_id, &artist.id, normalized_links.len() as i32)
+            });
+            normalized_artists.push(artist);
+        }
+    }
+    (normalized_artists, normalized_links)
+}
+
+/// One credit of an album and the artists it's credited to now
+#[derive(Debug, Clone, PartialEq)]
+struct CreditGroup {
+    /// The credit as the source gave it
+    credit: String,
+    artists: Vec<DbArtist>,
+    /// Whether normalization changed the credit last time
+    normalized: bool,
+}
+
+/// Group an album's artist links back into the credits they came from.
+/// Links split from one credit share its `credited_as` and sit next to each other.
+fn group_credits(links: &[DbAlbumArtist], artists: &[DbArtist]) -> Vec<CreditGroup> {
+    let mut groups: Vec<CreditGroup> = Vec::new();
+    for link in links {
+        let Some(artist) = artists.iter().find(|a| a.id == link.artist_id) else {
+            continue;
+        };
+        if let (Some(credit), Some(group)) = (&link.credited_as, groups.last_mut()) {
+            if group.normalized && group.credit == *credit {
+                group.artists.push(artist.clone());
+                continue;
+            }
+        }
+        groups.push(CreditGroup {
+            credit: link
+                .credited_as
+                .clone()
+                .unwrap_or_else(|| artist.name.clone()),
+            artists: vec![artist.clone()],
+            normalized: link.credited_as.is_some(),
+        });
+    }
+    groups
+}
+
+/// Normalize every album's credits again from the credits the sources gave,
+/// e.g. after the rules changed. Returns how many albums changed.
+pub async fn renormalize_library_credits(
+    library_manager: &LibraryManager,
+    rules: &CreditRules,
+) -> Result<usize, LibraryError> {
+    let mut changed = 0;
+    for album in library_manager.get_albums().await? {
+        let links = library_manager.get_album_artists(&album.id).await?;
+        let artists = library_manager.get_artists_for_album(&album.id).await?;
+
+        let mut new_links = Vec::new();
+        let mut renamed = false;
+        for group in group_credits(&links, &artists) {
+            let names = rules.normalize(&group.credit);
+            let credited_as = (names != [group.credit.clone()]).then(|| group.credit.clone());
+            for (index, name) in names.into_iter().enumerate() {
+                let artist_id = match group.artists.get(index) {
+                    Some(artist) => {
+                        if artist.name != name {
+                            library_manager
+                                .update_artist_name(&artist.id, &name)
+                                .await?;
+                            renamed = true;
+                        }
+                        artist.id.clone()
+                    }
+                    None => {
+                        let artist = DbArtist::new(&name);
+                        library_manager.insert_artist(&artist).await?;
+                        artist.id
+                    }
+                };
+                new_links.push(DbAlbumArtist {
+                    credited_as: credited_as.clone(),
+                    ..DbAlbumArtist::new(&album.id, &artist_id, new_links.len() as i32)
+                });
+            }
+        }
+
+        let relinked = new_links.len() != links.len()
+            || new_links.iter().zip(&links).any(|(new, old)| {
+                new.artist_id != old.artist_id || new.credited_as != old.credited_as
+            });
+        if relinked {
+            library_manager
+                .replace_album_artists(&album.id, &new_links)
+                .await?;
+        }
+        if relinked || renamed {
+            changed += 1;
+        }
+    }
+    info!("Re-normalized artist credits on {} albums", changed);
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{Database, DbAlbum, DbRelease};
+    use tempfile::TempDir;
+
+    fn rules(style: FeaturedCreditStyle) -> CreditRules {
+        CreditRules {
+            style,
+            ..CreditRules::default()
+        }
+    }
+
+    #[test]
+    fn test_standardize_spells_every_marker_as_feat() {
+        let rules = rules(FeaturedCreditStyle::Standardize);
+        for credit in [
+            "Artist feat. Guest",
+            "Artist ft. Guest",
+            "Artist Featuring Guest",
+            "Artist (feat. Guest)",
+            "Artist [FT Guest]",
+        ] {
+            assert_eq!(
+                rules.normalize(credit),
+                vec!["Artist feat. Guest"],
+                "{}",
+                credit
+            );
+        }
+    }
+
+    #[test]
+    fn test_markers_only_match_whole_words() {
+        let rules = rules(FeaturedCreditStyle::Standardize);
+        assert_eq!(rules.normalize("Daft Punk"), vec!["Daft Punk"]);
+        assert_eq!(rules.normalize("Feat Alone"), vec!["Feat Alone"]);
+        assert_eq!(rules.normalize("The Ftones"), vec!["The Ftones"]);
+    }
+
+    #[test]
+    fn test_split_credits_featured_artists_separately() {
+        let rules = rules(FeaturedCreditStyle::Split);
+        assert_eq!(
+            rules.normalize("Artist ft. Guest One & Guest Two"),
+            vec!["Artist", "Guest One", "Guest Two"]
+        );
+        assert_eq!(
+            rules.normalize("Simon & Garfunkel"),
+            vec!["Simon & Garfunkel"]
+        );
+    }
+
+    #[test]
+    fn test_keep_leaves_credits_alone() {
+        let rules = rules(FeaturedCreditStyle::Keep);
+        assert_eq!(
+            rules.normalize("Artist ft. Guest"),
+            vec!["Artist ft. Guest"]
+        );
+    }
+
+    #[test]
+    fn test_normalize_album_artists_keeps_original_credit() {
+        let artist = DbArtist::new("Artist ft. Guest");
+        let other = DbArtist::new("Other");
+        let links = vec![
+            DbAlbumArtist::new("album", &other.id, 1),
+            DbAlbumArtist::new("album", &artist.id, 0),
+        ];
+
+        let (artists, links) = normalize_album_artists(
+            &rules(FeaturedCreditStyle::Split),
+            vec![artist.clone(), other.clone()],
+            links,
+        );
+
+        let names: Vec<&str> = artists.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["Artist", "Guest", "Other"]);
+        assert_eq!(artists[0].id, artist.id);
+        assert_eq!(
+            links.iter().map(|l| l.position).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(links[0].credited_as.as_deref(), Some("Artist ft. Guest"));
+        assert_eq!(links[1].credited_as.as_deref(), Some("Artist ft. Guest"));
+        assert_eq!(links[2].credited_as, None);
+    }
+
+    #[test]
+    fn test_group_credits_rejoins_split_artists() {
+        let main = DbArtist::new("Artist");
+        let guest = DbArtist::new("Guest");
+        let other = DbArtist::new("Other");
+        let links = vec![
+            DbAlbumArtist {
+                credited_as: Some("Artist ft. Guest".to_string()),
+                ..DbAlbumArtist::new("album", &main.id, 0)
+            },
+            DbAlbumArtist {
+                credited_as: Some("Artist ft. Guest".to_string()),
+                ..DbAlbumArtist::new("album", &guest.id, 1)
+            },
+            DbAlbumArtist::new("album", &other.id, 2),
+        ];
+
+        let groups = group_credits(&links, &[main, guest, other]);
+
+        let credits: Vec<(&str, usize)> = groups
+            .iter()
+            .map(|g| (g.credit.as_str(), g.artists.len()))
+            .collect();
+        assert_eq!(credits, vec![("Artist ft. Guest", 2), ("Other", 1)]);
+    }
+
+    async fn album_artist_names(manager: &LibraryManager, album_id: &str) -> Vec<String> {
+        manager
+            .get_artists_for_album(album_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|a| a.name)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_renormalize_library_works_from_original_credit() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+        let manager = LibraryManager::new(database, None);
+
+        let album = DbAlbum::new_test("Album");
+        let release = DbRelease::new_test(&album.id, "release");
+        manager
+            .insert_album_with_release_and_tracks(&album, &release, &[])
+            .await
+            .unwrap();
+        // Imported before normalization existed
+        let artist = DbArtist::new("Artist ft. Guest");
+        manager.insert_artist(&artist).await.unwrap();
+        manager
+            .insert_album_artist(&DbAlbumArtist::new(&album.id, &artist.id, 0))
+            .await
+            .unwrap();
+
+        let split = rules(FeaturedCreditStyle::Split);
+        assert_eq!(
+            renormalize_library_credits(&manager, &split).await.unwrap(),
+            1
+        );
+        assert_eq!(
+            album_artist_names(&manager, &album.id).await,
+            vec!["Artist", "Guest"]
+        );
+        assert_eq!(
+            renormalize_library_credits(&manager, &split).await.unwrap(),
+            0
+        );
+
+        let standardize = rules(FeaturedCreditStyle::Standardize);
+        assert_eq!(
+            renormalize_library_credits(&manager, &standardize)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            album_artist_names(&manager, &album.id).await,
+            vec!["Artist feat. Guest"]
+        );
+
+        let keep = rules(FeaturedCreditStyle::Keep);
+        renormalize_library_credits(&manager, &keep).await.unwrap();
+        assert_eq!(
+            album_artist_names(&manager, &album.id).await,
+            vec!["Artist ft. Guest"]
+        );
+        let links = manager.get_album_artists(&album.id).await.unwrap();
+        assert_eq!(links[0].artist_id, artist.id);
+        assert_eq!(links[0].credited_as, None);
+    }
+}
@@ -0,0 +1,185 @@
+//! DR (dynamic range) scores per track, and per release from its tracks.
+//!
+//! Each track's audio is decoded and scored with [`DynamicRangeMeter`]. A
+//! release's score is the rounded average of its tracks' scores, which is
+//! how DR meters report an album. Releases are scored once their import
+//! completes; a backfill pass catches ones imported before scoring existed
+//! or whose scoring was cut short.
+
+use crate::audio_codec::{self, DynamicRangeMeter};
+use crate::db::DbAudioFormat;
+use crate::library::export::read_release_file;
+use crate::library::{LibraryError, LibraryManager};
+use crate::storage::create_storage_reader;
+use tracing::{info, warn};
+
+/// Pause between releases so a backfill stays in the background
+pub const DYNAMIC_RANGE_PACE: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// A release's score from its tracks' scores; None if none were measured
+pub fn release_dynamic_range(track_scores: impl IntoIterator<Item = i32>) -> Option<i32> {
+    let scores: Vec<i32> = track_scores.into_iter().collect();
+    if scores.is_empty() {
+        return None;
+    }
+    let sum: i32 = scores.iter().sum();
+    Some((sum as f64 / scores.len() as f64).round() as i32)
+}
+
+/// Score every track of a release that has no score yet. Returns how many
+/// tracks were scored; a track that fails to decode is skipped and retried
+/// by the next backfill.
+pub async fn measure_release_dynamic_range(
+    library_manager: &LibraryManager,
+    release_id: &str,
+) -> Result<usize, String> {
+    let storage_profile = library_manager
+        .get_storage_profile_for_release(release_id)
+        .await
+        .map_err(|e| format!("Failed to get storage profile: {}", e))?;
+    if storage_profile.as_ref().is_some_and(|p| p.cloud_archival) {
+        // Reading it back would need a restore
+        return Ok(0);
+    }
+    let storage = match &storage_profile {
+        Some(profile) => Some(
+            create_storage_reader(profile)
+                .await
+                .map_err(|e| format!("Failed to create storage reader: {}", e))?,
+        ),
+        None => None,
+    };
+
+    let database = library_manager.database();
+    let tracks = library_manager
+        .get_tracks(release_id)
+        .await
+        .map_err(|e| format!("Failed to get tracks: {}", e))?;
+    let files = library_manager
+        .get_files_for_release(release_id)
+        .await
+        .map_err(|e| format!("Failed to get files: {}", e))?;
+    let measured = database
+        .get_track_dynamic_ranges_for_release(release_id)
+        .await
+        .map_err(|e| format!("Failed to get scores: {}", e))?;
+
+    // CUE/FLAC tracks share one file, so keep the last one read
+    let mut last_file: Option<(String, Vec<u8>)> = None;
+    let mut scored = 0;
+    for track in tracks.iter().filter(|t| !measured.contains_key(&t.id)) {
+        let audio_format = match library_manager
+            .get_audio_format_by_track_id(&track.id)
+            .await
+        {
+            Ok(Some(audio_format)) => audio_format,
+            Ok(None) => continue,
+            Err(e) => return Err(format!("Failed to get audio format: {}", e)),
+        };
+        let Some(file) = audio_format
+            .file_id
+            .as_ref()
+            .and_then(|id| files.iter().find(|f| &f.id == id))
+        else {
+            continue;
+        };
+
+        if last_file.as_ref().map(|(id, _)| id) != Some(&file.id) {
+            let data = read_release_file(
+                file,
+                storage.as_deref().zip(storage_profile.as_ref()),
+                library_manager.encryption_service(),
+            )
+            .await?;
+            last_file = Some((file.id.clone(), data));
+        }
+        let Some((_, data)) = &last_file else {
+            continue;
+        };
+        let track_data = track_audio(&audio_format, data);
+
+        let score = tokio::task::spawn_blocking(move || {
+            let mut meter = DynamicRangeMeter::new();
+            audio_codec::measure_dynamic_range(&track_data, &mut meter).map(|_| meter.finish())
+        })
+        .await
+        .map_err(|e| format!("DR task failed: {}", e))?;
+
+        let score = match score {
+            // Silence has no score, but counts as measured
+            Ok(score) => score.map(|dr| dr.round().max(0.0) as i32),
+            Err(e) => {
+                warn!("Failed to decode {} for DR: {}", track.title, e);
+                continue;
+            }
+        };
+        database
+            .set_track_dynamic_range(&track.id, score)
+            .await
+            .map_err(|e| format!("Failed to save score: {}", e))?;
+        scored += 1;
+    }
+
+    Ok(scored)
+}
+
+/// Score the tracks of every imported release that is missing scores.
+/// Returns how many tracks were scored.
+pub async fn backfill_dynamic_range(
+    library_manager: &LibraryManager,
+    pace: std::time::Duration,
+) -> Result<usize, LibraryError> {
+    let release_ids = library_manager
+        .database()
+        .get_release_ids_missing_dynamic_range()
+        .await?;
+    if release_ids.is_empty() {
+        return Ok(0);
+    }
+    info!("Measuring DR for {} releases", release_ids.len());
+
+    let mut scored = 0;
+    for release_id in release_ids {
+        match measure_release_dynamic_range(library_manager, &release_id).await {
+            Ok(count) => scored += count,
+            Err(e) => warn!("Failed to measure DR of release {}: {}", release_id, e),
+        }
+        tokio::time::sleep(pace).await;
+    }
+    info!("Measured DR for {} tracks", scored);
+    Ok(scored)
+}
+
+/// A track's audio within its file: the whole file, or for a CUE/FLAC track
+/// its byte range behind the FLAC headers, as playback reads it
+fn track_audio(audio_format: &DbAudioFormat, data: &[u8]) -> Vec<u8> {
+    let (Some(start), Some(end)) = (audio_format.start_byte_offset, audio_format.end_byte_offset)
+    else {
+        return data.to_vec();
+    };
+    let end = (end as usize).min(data.len());
+    let start = (start as usize).min(end);
+    let headers = audio_format
+        .flac_headers
+        .as_deref()
+        .filter(|_| audio_format.needs_headers)
+        .unwrap_or_default();
+    let mut track_data = Vec::with_capacity(headers.len() + end - start);
+    track_data.extend_from_slice(headers);
+    track_data.extend_from_slice(&data[start..end]);
+    track_data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_dynamic_range_rounds_track_average() {
+        assert_eq!(release_dynamic_range([]), None);
+        assert_eq!(release_dynamic_range([7]), Some(7));
+        // 8.5 rounds up, as DR meters do
+        assert_eq!(release_dynamic_range([8, 9, 8, 9]), Some(9));
+        assert_eq!(release_dynamic_range([6, 7, 7]), Some(7));
+    }
+}
@@ -0,0 +1,194 @@
+//! Editing album, release and track metadata after import.
+//!
+//! Each edit is logged to the changelog with the fields before and after,
+//! which is the album's, release's or track's edit history. Saving an album
+//! page's edits writes only what changed, and the whole save undoes as one
+//! step (see [`save_album_edits`](crate::library::LibraryManager::save_album_edits)).
+
+use crate::db::{DbAlbum, DbRelease, DbTrack};
+use crate::library::LibraryError;
+use serde::{Deserialize, Serialize};
+
+/// Editable fields of an album
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlbumFields {
+    pub title: String,
+    pub year: Option<i32>,
+}
+
+impl From<&DbAlbum> for AlbumFields {
+    fn from(album: &DbAlbum) -> Self {
+        AlbumFields {
+            title: album.title.clone(),
+            year: album.year,
+        }
+    }
+}
+
+/// Editable fields of a release
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReleaseFields {
+    pub release_name: Option<String>,
+    pub year: Option<i32>,
+    pub format: Option<String>,
+    pub label: Option<String>,
+    pub catalog_number: Option<String>,
+    pub country: Option<String>,
+    pub barcode: Option<String>,
+}
+
+impl From<&DbRelease> for ReleaseFields {
+    fn from(release: &DbRelease) -> Self {
+        ReleaseFields {
+            release_name: release.release_name.clone(),
+            year: release.year,
+            format: release.format.clone(),
+            label: release.label.clone(),
+            catalog_number: release.catalog_number.clone(),
+            country: release.country.clone(),
+            barcode: release.barcode.clone(),
+        }
+    }
+}
+
+impl ReleaseFields {
+    /// `release` with these fields in place of its own
+    pub(crate) fn applied_to(&self, release: &DbRelease) -> DbRelease {
+        DbRelease {
+            release_name: self.release_name.clone(),
+            year: self.year,
+            format: self.format.clone(),
+            label: self.label.clone(),
+            catalog_number: self.catalog_number.clone(),
+            country: self.country.clone(),
+            barcode: self.barcode.clone(),
+            ..release.clone()
+        }
+    }
+}
+
+/// Editable fields of a track
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackFields {
+    pub title: String,
+    pub disc_number: Option<i32>,
+    pub track_number: Option<i32>,
+}
+
+impl From<&DbTrack> for TrackFields {
+    fn from(track: &DbTrack) -> Self {
+        TrackFields {
+            title: track.title.clone(),
+            disc_number: track.disc_number,
+            track_number: track.track_number,
+        }
+    }
+}
+
+/// Everything edited on one album's page, saved together. Fields left as
+/// they were are skipped when saving.
+#[derive(Debug, Clone, Default)]
+pub struct AlbumEdits {
+    pub album_id: String,
+    pub album: Option<AlbumFields>,
+    /// The album's artists in credit order, by name. A name that isn't in
+    /// the library yet becomes a new artist.
+    pub artists: Option<Vec<String>>,
+    /// (release ID, fields)
+    pub releases: Vec<(String, ReleaseFields)>,
+    /// (track ID, fields)
+    pub tracks: Vec<(String, TrackFields)>,
+}
+
+impl AlbumEdits {
+    /// Reject edits that would leave an album or track without a title or
+    /// an album without artists
+    pub fn validate(&self) -> Result<(), LibraryError> {
+        if self
+            .album
+            .as_ref()
+            .is_some_and(|a| a.title.trim().is_empty())
+        {
+            return Err(LibraryError::InvalidEdit(
+                "an album needs a title".to_string(),
+            ));
+        }
+        if self
+            .artists
+            .as_ref()
+            .is_some_and(|names| clean_artist_names(names).is_empty())
+        {
+            return Err(LibraryError::InvalidEdit(
+                "an album needs at least one artist".to_string(),
+            ));
+        }
+        if self.tracks.iter().any(|(_, t)| t.title.trim().is_empty()) {
+            return Err(LibraryError::InvalidEdit(
+                "every track needs a title".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Artist names as entered: trimmed, without blanks or repeats
+pub fn clean_artist_names(names: &[String]) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for name in names {
+        let name = name.trim();
+        if !name.is_empty() && !cleaned.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+            cleaned.push(name.to_string());
+        }
+    }
+    cleaned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_artist_names() {
+        let names = vec![
+            " Earth, Wind & Fire ".to_string(),
+            String::new(),
+            "earth, wind & fire".to_string(),
+            "The Emotions".to_string(),
+        ];
+        assert_eq!(
+            clean_artist_names(&names),
+            vec!["Earth, Wind & Fire", "The Emotions"]
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_blank_titles() {
+        let mut edits = AlbumEdits {
+            album_id: "album".to_string(),
+            album: Some(AlbumFields {
+                title: "  ".to_string(),
+                year: None,
+            }),
+            ..Default::default()
+        };
+        assert!(edits.validate().is_err());
+
+        edits.album = None;
+        edits.artists = Some(vec![" ".to_string()]);
+        assert!(edits.validate().is_err());
+
+        edits.artists = None;
+        edits.tracks = vec![(
+            "track".to_string(),
+            TrackFields {
+                title: String::new(),
+                disc_number: None,
+                track_number: Some(1),
+            },
+        )];
+        assert!(edits.validate().is_err());
+
+        edits.tracks[0].1.title = "Track".to_string();
+        assert!(edits.validate().is_ok());
+    }
+}
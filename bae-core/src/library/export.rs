@@ -1,10 +1,11 @@
 use crate::cache::CacheManager;
 use crate::cloud_storage::CloudStorage;
+use crate::db::{DbFile, DbStorageProfile};
 use crate::encryption::EncryptionService;
 use crate::library::LibraryManager;
 use crate::playback::track_loader::load_track_audio;
-use crate::storage::create_storage_reader;
-use std::path::Path;
+use crate::storage::{create_storage_reader, decompress, long_path};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tracing::{debug, info};
 
@@ -50,40 +51,12 @@ impl ExportService {
         }
 
         for file in &files {
-            let file_data = if let Some(ref source_path) = file.source_path {
-                // Read from storage
-                debug!("Reading file from storage: {}", source_path);
-                let data = storage
-                    .download(source_path)
-                    .await
-                    .map_err(|e| format!("Failed to read file {}: {}", source_path, e))?;
-
-                // Decrypt if profile has encryption enabled
-                if storage_profile.encrypted {
-                    let enc_service = encryption_service
-                        .ok_or_else(|| {
-                            "Cannot export encrypted files: encryption not configured".to_string()
-                        })?
-                        .clone();
-                    tokio::task::spawn_blocking(move || {
-                        enc_service
-                            .decrypt(&data)
-                            .map_err(|e| format!("Failed to decrypt file: {}", e))
-                    })
-                    .await
-                    .map_err(|e| format!("Decryption task failed: {}", e))??
-                } else {
-                    data
-                }
-            } else {
-                return Err(format!(
-                    "File {} has no source path",
-                    file.original_filename
-                ));
-            };
+            let file_data =
+                read_stored_file(file, storage.as_ref(), &storage_profile, encryption_service)
+                    .await?;
 
             // Ensure subdirectories exist for nested filenames
-            let file_path = target_dir.join(&file.original_filename);
+            let file_path = long_path(&target_dir.join(&file.original_filename));
             if let Some(parent) = file_path.parent() {
                 std::fs::create_dir_all(parent)
                     .map_err(|e| format!("Failed to create directory: {}", e))?;
@@ -107,6 +80,64 @@ impl ExportService {
         Ok(())
     }
 
+    /// Export a single release file to a directory, for opening it outside bae
+    /// (video extras play in the system player).
+    ///
+    /// Returns the written path. Files of releases without a storage profile
+    /// are still at their original path, which is returned as-is.
+    pub async fn export_file(
+        file_id: &str,
+        target_dir: &Path,
+        library_manager: &LibraryManager,
+        encryption_service: Option<&EncryptionService>,
+    ) -> Result<PathBuf, String> {
+        let file = library_manager
+            .get_file_by_id(file_id)
+            .await
+            .map_err(|e| format!("Failed to get file: {}", e))?
+            .ok_or_else(|| format!("File not found: {}", file_id))?;
+
+        let Some(storage_profile) = library_manager
+            .get_storage_profile_for_release(&file.release_id)
+            .await
+            .map_err(|e| format!("Failed to get storage profile: {}", e))?
+        else {
+            return file
+                .source_path
+                .map(PathBuf::from)
+                .ok_or_else(|| format!("File {} has no source path", file.original_filename));
+        };
+
+        let storage = create_storage_reader(&storage_profile)
+            .await
+            .map_err(|e| format!("Failed to create storage reader: {}", e))?;
+        let file_data = read_stored_file(
+            &file,
+            storage.as_ref(),
+            &storage_profile,
+            encryption_service,
+        )
+        .await?;
+
+        // Nested filenames are flattened: only the file itself is needed
+        let filename = Path::new(&file.original_filename)
+            .file_name()
+            .ok_or_else(|| format!("Invalid filename: {}", file.original_filename))?;
+        std::fs::create_dir_all(long_path(target_dir))
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+        let file_path = target_dir.join(filename);
+        std::fs::write(long_path(&file_path), &file_data)
+            .map_err(|e| format!("Failed to write file {}: {}", file.original_filename, e))?;
+
+        info!(
+            "Exported file {} to {}",
+            file.original_filename,
+            file_path.display()
+        );
+
+        Ok(file_path)
+    }
+
     /// Export a single track as a FLAC file
     ///
     /// For one-file-per-track: extracts the original file.
@@ -139,7 +170,7 @@ impl ExportService {
         )
         .map_err(|e| format!("Failed to encode FLAC: {}", e))?;
 
-        std::fs::write(output_path, &flac_data)
+        std::fs::write(long_path(output_path), &flac_data)
             .map_err(|e| format!("Failed to write track file: {}", e))?;
 
         info!(
@@ -150,3 +181,79 @@ impl ExportService {
         Ok(())
     }
 }
+
+/// Read a stored file's original bytes: download, then decrypt and
+/// decompress as the profile and file record require
+pub(crate) async fn read_stored_file(
+    file: &DbFile,
+    storage: &dyn CloudStorage,
+    storage_profile: &DbStorageProfile,
+    encryption_service: Option<&EncryptionService>,
+) -> Result<Vec<u8>, String> {
+    let Some(ref source_path) = file.source_path else {
+        return Err(format!(
+            "File {} has no source path",
+            file.original_filename
+        ));
+    };
+
+    debug!("Reading file from storage: {}", source_path);
+    let data = storage
+        .download(source_path)
+        .await
+        .map_err(|e| format!("Failed to read file {}: {}", source_path, e))?;
+    unpack_stored_file(file, data, storage_profile, encryption_service).await
+}
+
+/// Turn a file's bytes as stored back into its original bytes
+pub(crate) async fn unpack_stored_file(
+    file: &DbFile,
+    data: Vec<u8>,
+    storage_profile: &DbStorageProfile,
+    encryption_service: Option<&EncryptionService>,
+) -> Result<Vec<u8>, String> {
+    // Decrypt if profile has encryption enabled
+    let data = if storage_profile.encrypted {
+        let enc_service = encryption_service
+            .ok_or_else(|| "Cannot export encrypted files: encryption not configured".to_string())?
+            .clone();
+        tokio::task::spawn_blocking(move || {
+            enc_service
+                .decrypt(&data)
+                .map_err(|e| format!("Failed to decrypt file: {}", e))
+        })
+        .await
+        .map_err(|e| format!("Decryption task failed: {}", e))??
+    } else {
+        data
+    };
+
+    if file.compressed {
+        decompress(&data).map_err(|e| format!("Failed to decompress file: {}", e))
+    } else {
+        Ok(data)
+    }
+}
+
+/// Read a release file's original bytes: from storage if the release has a
+/// storage profile, otherwise from where it was imported
+pub(crate) async fn read_release_file(
+    file: &DbFile,
+    storage: Option<(&dyn CloudStorage, &DbStorageProfile)>,
+    encryption_service: Option<&EncryptionService>,
+) -> Result<Vec<u8>, String> {
+    match storage {
+        Some((storage, profile)) => {
+            read_stored_file(file, storage, profile, encryption_service).await
+        }
+        None => {
+            let path = file
+                .source_path
+                .as_ref()
+                .ok_or_else(|| format!("File {} has no source path", file.original_filename))?;
+            tokio::fs::read(path)
+                .await
+                .map_err(|e| format!("Failed to read {}: {}", path, e))
+        }
+    }
+}
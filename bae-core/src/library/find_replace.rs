@@ -0,0 +1,242 @@
+//! Find-and-replace across album titles, track titles and artist names.
+//!
+//! A preview lists every value that would change. Applying writes exactly
+//! the previewed rows (see [`LibraryManager::apply_replacements`]), so what
+//! was reviewed is what lands, and the whole batch undoes as one step.
+
+use crate::db::ChangeKind;
+use crate::library::{LibraryError, LibraryManager};
+use regex::{NoExpand, Regex, RegexBuilder};
+use serde_json::json;
+use std::collections::HashSet;
+
+/// Which values a find-and-replace looks at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReplaceField {
+    AlbumTitle,
+    TrackTitle,
+    ArtistName,
+}
+
+impl ReplaceField {
+    /// Changelog kind a replacement in this field is recorded as
+    pub(crate) fn change_kind(&self) -> ChangeKind {
+        match self {
+            ReplaceField::AlbumTitle => ChangeKind::AlbumRenamed,
+            ReplaceField::TrackTitle => ChangeKind::TrackRenamed,
+            ReplaceField::ArtistName => ChangeKind::ArtistRenamed,
+        }
+    }
+
+    /// A value as the changelog records it for this field
+    pub(crate) fn change_value(&self, value: &str) -> serde_json::Value {
+        match self {
+            ReplaceField::AlbumTitle | ReplaceField::TrackTitle => json!({ "title": value }),
+            ReplaceField::ArtistName => json!({ "name": value }),
+        }
+    }
+}
+
+/// What to find, what to put in its place, and where to look
+#[derive(Debug, Clone)]
+pub struct FindReplace {
+    pub find: String,
+    /// With `use_regex`, may refer to capture groups as `$1` or `${name}`
+    pub replace: String,
+    pub use_regex: bool,
+    pub case_sensitive: bool,
+    pub fields: Vec<ReplaceField>,
+}
+
+impl FindReplace {
+    /// Compile the pattern. Fails if it's empty or not a valid regex.
+    pub fn compile(&self) -> Result<Replacer, LibraryError> {
+        if self.find.is_empty() {
+            return Err(LibraryError::InvalidPattern("nothing to find".to_string()));
+        }
+        let pattern = if self.use_regex {
+            self.find.clone()
+        } else {
+            regex::escape(&self.find)
+        };
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+            .map_err(|e| LibraryError::InvalidPattern(e.to_string()))?;
+        Ok(Replacer {
+            regex,
+            replace: self.replace.clone(),
+            expand: self.use_regex,
+        })
+    }
+}
+
+/// A compiled [`FindReplace`] pattern
+pub struct Replacer {
+    regex: Regex,
+    replace: String,
+    /// Expand `$1`-style group references in the replacement
+    expand: bool,
+}
+
+impl Replacer {
+    /// `text` with every match replaced, or None if nothing matched.
+    ///
+    /// Whitespace is tidied afterwards, so stripping "[Remastered 2011]" off
+    /// the end of a title doesn't leave a trailing space. A result that would
+    /// be empty or unchanged is None too.
+    pub fn apply(&self, text: &str) -> Option<String> {
+        if !self.regex.is_match(text) {
+            return None;
+        }
+        let replaced = if self.expand {
+            self.regex.replace_all(text, self.replace.as_str())
+        } else {
+            self.regex.replace_all(text, NoExpand(&self.replace))
+        };
+        let tidied = replaced.split_whitespace().collect::<Vec<_>>().join(" ");
+        (!tidied.is_empty() && tidied != text).then_some(tidied)
+    }
+}
+
+/// One value a find-and-replace would change
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplacePreview {
+    pub field: ReplaceField,
+    /// Album, track or artist the value belongs to
+    pub entity_id: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Every value in the given albums that `spec` would change: album titles,
+/// then artist names, then track titles, album by album. An artist credited
+/// on several albums is listed once; renaming it renames it everywhere.
+pub async fn preview_find_replace(
+    library_manager: &LibraryManager,
+    album_ids: &[String],
+    spec: &FindReplace,
+) -> Result<Vec<ReplacePreview>, LibraryError> {
+    let replacer = spec.compile()?;
+    let wants = |field| spec.fields.contains(&field);
+    let mut rows = Vec::new();
+    let mut seen_artists = HashSet::new();
+    let mut push = |field, entity_id: &str, before: &str| {
+        if let Some(after) = replacer.apply(before) {
+            rows.push(ReplacePreview {
+                field,
+                entity_id: entity_id.to_string(),
+                before: before.to_string(),
+                after,
+            });
+        }
+    };
+
+    for album_id in album_ids {
+        let Some(album) = library_manager.get_album_by_id(album_id).await? else {
+            continue;
+        };
+        if wants(ReplaceField::AlbumTitle) {
+            push(ReplaceField::AlbumTitle, &album.id, &album.title);
+        }
+
+        let mut tracks = Vec::new();
+        for release in library_manager.get_releases_for_album(&album.id).await? {
+            tracks.extend(library_manager.get_tracks(&release.id).await?);
+        }
+
+        if wants(ReplaceField::ArtistName) {
+            let mut artists = library_manager.get_artists_for_album(&album.id).await?;
+            for track in &tracks {
+                artists.extend(library_manager.get_artists_for_track(&track.id).await?);
+            }
+            for artist in artists {
+                if seen_artists.insert(artist.id.clone()) {
+                    push(ReplaceField::ArtistName, &artist.id, &artist.name);
+                }
+            }
+        }
+
+        if wants(ReplaceField::TrackTitle) {
+            for track in &tracks {
+                push(ReplaceField::TrackTitle, &track.id, &track.title);
+            }
+        }
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(find: &str, replace: &str, use_regex: bool) -> FindReplace {
+        FindReplace {
+            find: find.to_string(),
+            replace: replace.to_string(),
+            use_regex,
+            case_sensitive: false,
+            fields: vec![ReplaceField::AlbumTitle],
+        }
+    }
+
+    #[test]
+    fn test_plain_text_strips_suffix_and_tidies_whitespace() {
+        let replacer = spec("[Remastered 2011]", "", false).compile().unwrap();
+        assert_eq!(
+            replacer.apply("Abbey Road [Remastered 2011]"),
+            Some("Abbey Road".to_string())
+        );
+        assert_eq!(
+            replacer.apply("Let It Be [remastered 2011] (Live)"),
+            Some("Let It Be (Live)".to_string())
+        );
+        assert_eq!(replacer.apply("Revolver"), None);
+    }
+
+    #[test]
+    fn test_plain_text_replacement_is_literal() {
+        let replacer = spec("and", "$1 &", false).compile().unwrap();
+        assert_eq!(
+            replacer.apply("Simon and Garfunkel"),
+            Some("Simon $1 & Garfunkel".to_string())
+        );
+    }
+
+    #[test]
+    fn test_regex_with_groups() {
+        let replacer = spec(r"\s*\((\d{4}) Remaster\)$", " [$1]", true)
+            .compile()
+            .unwrap();
+        assert_eq!(
+            replacer.apply("Song (2009 Remaster)"),
+            Some("Song [2009]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_case_sensitive() {
+        let mut spec = spec("live", "Live", false);
+        spec.case_sensitive = true;
+        let replacer = spec.compile().unwrap();
+        assert_eq!(
+            replacer.apply("Alive (live)"),
+            Some("ALive (Live)".to_string())
+        );
+        assert_eq!(replacer.apply("LIVE"), None);
+    }
+
+    #[test]
+    fn test_never_blanks_a_value() {
+        let replacer = spec("Untitled", "", false).compile().unwrap();
+        assert_eq!(replacer.apply("Untitled"), None);
+    }
+
+    #[test]
+    fn test_rejects_bad_patterns() {
+        assert!(spec("", "x", false).compile().is_err());
+        assert!(spec("(unclosed", "x", true).compile().is_err());
+        assert!(spec("(unclosed", "x", false).compile().is_ok());
+    }
+}
@@ -0,0 +1,426 @@
+//! Gift packages: a release exported for someone who doesn't use bae.
+//!
+//! The package is a folder, or a zip of one, holding the decrypted audio, the
+//! cover, and an `index.html` player listing the tracks. Opening the page in
+//! any browser plays the album; nothing in it needs bae or a network.
+//!
+//! Tracks that are whole files are copied as they were imported. Tracks cut
+//! from a single CUE/FLAC image are decoded and written as their own FLAC.
+
+use crate::cache::CacheManager;
+use crate::db::FilenamePolicy;
+use crate::library::export::read_release_file;
+use crate::library::LibraryManager;
+use crate::playback::track_loader::load_track_audio;
+use crate::storage::{create_storage_reader, long_path, sanitize_filename};
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// What the player page shows and plays
+#[derive(Debug, Serialize)]
+struct GiftManifest {
+    title: String,
+    artist: String,
+    year: Option<i32>,
+    /// Cover filename inside the package
+    cover: Option<String>,
+    tracks: Vec<GiftTrack>,
+}
+
+#[derive(Debug, Serialize)]
+struct GiftTrack {
+    title: String,
+    artist: String,
+    /// Audio filename inside the package
+    file: String,
+    duration_ms: Option<i64>,
+}
+
+/// Export a release as a gift package in `target_dir`, zipped if `zip` is
+/// set. Returns the folder or zip that was written.
+pub async fn export_gift(
+    release_id: &str,
+    target_dir: &Path,
+    zip: bool,
+    library_manager: &LibraryManager,
+    cache: &CacheManager,
+) -> Result<PathBuf, String> {
+    let album_id = library_manager
+        .get_album_id_for_release(release_id)
+        .await
+        .map_err(|e| format!("Failed to get album: {}", e))?;
+    let album = library_manager
+        .get_album_by_id(&album_id)
+        .await
+        .map_err(|e| format!("Failed to get album: {}", e))?
+        .ok_or_else(|| format!("Album not found: {}", album_id))?;
+    let album_artist = library_manager
+        .get_artists_for_album(&album_id)
+        .await
+        .map_err(|e| format!("Failed to get artists: {}", e))?
+        .into_iter()
+        .map(|a| a.name)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let tracks = library_manager
+        .get_tracks(release_id)
+        .await
+        .map_err(|e| format!("Failed to get tracks: {}", e))?;
+    if tracks.is_empty() {
+        return Err("Release has no tracks".to_string());
+    }
+    let files = library_manager
+        .get_files_for_release(release_id)
+        .await
+        .map_err(|e| format!("Failed to get files: {}", e))?;
+    let storage_profile = library_manager
+        .get_storage_profile_for_release(release_id)
+        .await
+        .map_err(|e| format!("Failed to get storage profile: {}", e))?;
+    let storage = match &storage_profile {
+        Some(profile) => Some(
+            create_storage_reader(profile)
+                .await
+                .map_err(|e| format!("Failed to create storage reader: {}", e))?,
+        ),
+        None => None,
+    };
+    let encryption_service = library_manager.encryption_service();
+
+    let package_name = if album_artist.is_empty() {
+        package_filename(&album.title)
+    } else {
+        package_filename(&format!("{} - {}", album_artist, album.title))
+    };
+    let package_dir = unused_path(target_dir, &package_name, "");
+    std::fs::create_dir_all(long_path(&package_dir))
+        .map_err(|e| format!("Failed to create {}: {}", package_dir.display(), e))?;
+    info!(
+        "Exporting gift package of release {} to {}",
+        release_id,
+        package_dir.display()
+    );
+
+    let mut manifest = GiftManifest {
+        title: album.title.clone(),
+        artist: album_artist.clone(),
+        year: album.year,
+        cover: None,
+        tracks: Vec::with_capacity(tracks.len()),
+    };
+
+    for (index, track) in tracks.iter().enumerate() {
+        let audio_format = library_manager
+            .get_audio_format_by_track_id(&track.id)
+            .await
+            .map_err(|e| format!("Failed to get audio format: {}", e))?
+            .ok_or_else(|| format!("Track {} has no audio", track.title))?;
+
+        let (data, extension) = if audio_format.start_byte_offset.is_none() {
+            let file = audio_format
+                .file_id
+                .as_ref()
+                .and_then(|id| files.iter().find(|f| &f.id == id))
+                .ok_or_else(|| format!("Track {} has no audio file", track.title))?;
+            let data = read_release_file(
+                file,
+                storage.as_deref().zip(storage_profile.as_ref()),
+                encryption_service,
+            )
+            .await?;
+            let extension = Path::new(&file.original_filename)
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or(&audio_format.format)
+                .to_lowercase();
+            (data, extension)
+        } else {
+            let pcm = load_track_audio(
+                &track.id,
+                library_manager,
+                storage.clone(),
+                cache,
+                encryption_service,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            let data = crate::audio_codec::encode_to_flac(
+                pcm.raw_samples(),
+                pcm.sample_rate(),
+                pcm.channels(),
+                pcm.bits_per_sample(),
+            )
+            .map_err(|e| format!("Failed to encode FLAC: {}", e))?;
+            (data, "flac".to_string())
+        };
+
+        let number = track.track_number.unwrap_or(index as i32 + 1);
+        let filename = package_filename(&format!("{:02} - {}.{}", number, track.title, extension));
+        std::fs::write(long_path(&package_dir.join(&filename)), &data)
+            .map_err(|e| format!("Failed to write {}: {}", filename, e))?;
+
+        let track_artist = library_manager
+            .get_artists_for_track(&track.id)
+            .await
+            .map_err(|e| format!("Failed to get artists: {}", e))?
+            .into_iter()
+            .map(|a| a.name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        manifest.tracks.push(GiftTrack {
+            title: track.title.clone(),
+            artist: if track_artist.is_empty() {
+                album_artist.clone()
+            } else {
+                track_artist
+            },
+            file: filename,
+            duration_ms: track.duration_ms,
+        });
+    }
+
+    if let Some(cover) = library_manager
+        .get_cover_image_for_release(release_id)
+        .await
+        .map_err(|e| format!("Failed to get cover: {}", e))?
+    {
+        let extension = Path::new(&cover.filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("jpg")
+            .to_lowercase();
+        let filename = format!("cover.{}", extension);
+        let data = library_manager
+            .fetch_image_bytes(&cover.id)
+            .await
+            .map_err(|e| format!("Failed to read cover: {}", e))?;
+        std::fs::write(long_path(&package_dir.join(&filename)), &data)
+            .map_err(|e| format!("Failed to write cover: {}", e))?;
+        manifest.cover = Some(filename);
+    }
+
+    std::fs::write(
+        long_path(&package_dir.join("index.html")),
+        player_page(&manifest)?,
+    )
+    .map_err(|e| format!("Failed to write player: {}", e))?;
+
+    if !zip {
+        info!("Exported gift package to {}", package_dir.display());
+        return Ok(package_dir);
+    }
+
+    let zip_path = unused_path(target_dir, &package_name, ".zip");
+    let (dir, path) = (package_dir.clone(), zip_path.clone());
+    tokio::task::spawn_blocking(move || zip_folder(&dir, &path))
+        .await
+        .map_err(|e| format!("Zip task failed: {}", e))?
+        .map_err(|e| format!("Failed to write zip: {}", e))?;
+    std::fs::remove_dir_all(long_path(&package_dir))
+        .map_err(|e| format!("Failed to clean up {}: {}", package_dir.display(), e))?;
+
+    info!("Exported gift package to {}", zip_path.display());
+    Ok(zip_path)
+}
+
+/// A single path component safe on every filesystem
+fn package_filename(name: &str) -> String {
+    sanitize_filename(&name.replace('/', "-"), FilenamePolicy::Transliterate)
+}
+
+/// `dir/name{suffix}`, numbered if something is already there
+fn unused_path(dir: &Path, name: &str, suffix: &str) -> PathBuf {
+    let mut path = dir.join(format!("{}{}", name, suffix));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{} ({}){}", name, n, suffix));
+        n += 1;
+    }
+    path
+}
+
+/// Zip a flat folder, keeping the folder as the top-level entry. Audio and
+/// images are already compressed, so entries are stored as-is.
+fn zip_folder(dir: &Path, zip_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let folder = dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid folder name")?;
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(long_path(zip_path))?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .large_file(true);
+
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(long_path(dir))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    entries.sort();
+    for path in entries {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        zip.start_file(format!("{}/{}", folder, name), options)?;
+        zip.write_all(&std::fs::read(&path)?)?;
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+/// The player page, with the manifest embedded for its script to render
+fn player_page(manifest: &GiftManifest) -> Result<String, String> {
+    let data = serde_json::to_string(manifest)
+        .map_err(|e| format!("Failed to serialize track list: {}", e))?
+        // Keep titles from closing the script tag
+        .replace("</", "<\\/");
+    Ok(PLAYER_HTML
+        .replace("{{TITLE}}", &html_escape(&manifest.title))
+        .replace("{{DATA}}", &data))
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const PLAYER_HTML: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{{TITLE}}</title>
+<style>
+  body { margin: 0; font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; background: #0f1116; color: #e5e7eb; }
+  main { max-width: 40rem; margin: 0 auto; padding: 3rem 1.5rem; }
+  header { display: flex; gap: 1.5rem; align-items: flex-end; margin-bottom: 2rem; }
+  header img { width: 10rem; height: 10rem; object-fit: cover; border-radius: 0.5rem; }
+  h1 { margin: 0 0 0.25rem; font-size: 1.75rem; color: #fff; }
+  .artist { color: #9ca3af; }
+  ol { list-style: none; margin: 0 0 1.5rem; padding: 0; }
+  li { display: flex; gap: 1rem; padding: 0.6rem 0.75rem; border-radius: 0.375rem; cursor: pointer; }
+  li:hover { background: #1f2937; }
+  li.playing { background: #312e81; color: #fff; }
+  .number, .duration { color: #6b7280; font-variant-numeric: tabular-nums; }
+  .title { flex: 1; }
+  .track-artist { color: #9ca3af; font-size: 0.85em; }
+  audio { width: 100%; }
+</style>
+</head>
+<body>
+<main>
+  <header>
+    <img id="cover" alt="" hidden>
+    <div>
+      <h1 id="title"></h1>
+      <div class="artist" id="artist"></div>
+    </div>
+  </header>
+  <ol id="tracks"></ol>
+  <audio id="player" controls preload="none"></audio>
+</main>
+<script>
+  const album = {{DATA}};
+  const player = document.getElementById("player");
+  const list = document.getElementById("tracks");
+  let current = -1;
+
+  document.getElementById("title").textContent = album.title;
+  document.getElementById("artist").textContent =
+    [album.artist, album.year].filter(Boolean).join(" · ");
+  if (album.cover) {
+    const cover = document.getElementById("cover");
+    cover.src = encodeURI(album.cover);
+    cover.hidden = false;
+  }
+
+  function duration(ms) {
+    if (!ms) return "";
+    const seconds = Math.round(ms / 1000);
+    return Math.floor(seconds / 60) + ":" + String(seconds % 60).padStart(2, "0");
+  }
+
+  function play(index) {
+    if (index >= album.tracks.length) return;
+    current = index;
+    player.src = encodeURI(album.tracks[index].file);
+    player.play();
+    list.querySelectorAll("li").forEach((row, i) => row.classList.toggle("playing", i === index));
+  }
+
+  album.tracks.forEach((track, index) => {
+    const row = document.createElement("li");
+    const number = document.createElement("span");
+    number.className = "number";
+    number.textContent = index + 1;
+    const title = document.createElement("span");
+    title.className = "title";
+    title.textContent = track.title;
+    if (track.artist && track.artist !== album.artist) {
+      const artist = document.createElement("div");
+      artist.className = "track-artist";
+      artist.textContent = track.artist;
+      title.appendChild(artist);
+    }
+    const length = document.createElement("span");
+    length.className = "duration";
+    length.textContent = duration(track.duration_ms);
+    row.append(number, title, length);
+    row.addEventListener("click", () => play(index));
+    list.appendChild(row);
+  });
+
+  player.addEventListener("ended", () => play(current + 1));
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_player_page_embeds_manifest_safely() {
+        let manifest = GiftManifest {
+            title: "Songs </script> & <More>".to_string(),
+            artist: "Artist".to_string(),
+            year: Some(2024),
+            cover: Some("cover.jpg".to_string()),
+            tracks: vec![GiftTrack {
+                title: "One".to_string(),
+                artist: "Artist".to_string(),
+                file: "01 - One.flac".to_string(),
+                duration_ms: Some(180_000),
+            }],
+        };
+        let page = player_page(&manifest).unwrap();
+
+        assert!(page.contains("<title>Songs &lt;/script&gt; &amp; &lt;More&gt;</title>"));
+        assert_eq!(page.matches("</script>").count(), 1);
+        assert!(page.contains(r#""file":"01 - One.flac""#));
+    }
+
+    #[test]
+    fn test_package_filename_is_one_component() {
+        assert_eq!(
+            package_filename("AC/DC - Back: In Black"),
+            "AC-DC - Back- In Black"
+        );
+    }
+
+    #[test]
+    fn test_unused_path_numbers_taken_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = unused_path(dir.path(), "Album", ".zip");
+        assert_eq!(first, dir.path().join("Album.zip"));
+        std::fs::write(&first, b"").unwrap();
+        assert_eq!(
+            unused_path(dir.path(), "Album", ".zip"),
+            dir.path().join("Album (2).zip")
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Lyrics embedded in a track's FLAC tags.
+//!
+//! Taggers store lyrics in a `LYRICS` or `UNSYNCEDLYRICS` Vorbis comment,
+//! either as plain text or as LRC with a `[mm:ss.xx]` timestamp per line.
+//! bae doesn't keep lyrics in the database; they're read from the file when
+//! asked for.
+
+/// Lyrics split into lines, with start times when the tag had them
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lyrics {
+    /// Every line has a start time
+    pub synced: bool,
+    pub lines: Vec<LyricLine>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LyricLine {
+    /// Where the line starts in the track, for synced lyrics
+    pub start_ms: Option<u64>,
+    pub text: String,
+}
+
+/// FLAC metadata block holding the Vorbis comments
+const VORBIS_COMMENT_BLOCK: u8 = 4;
+
+/// Tags lyrics are kept under, in the order they're preferred
+const LYRICS_TAGS: [&str; 2] = ["LYRICS", "UNSYNCEDLYRICS"];
+
+/// Lyrics from the tags at the start of a FLAC stream. None when the stream
+/// has no lyrics tag or its metadata can't be read.
+pub fn flac_lyrics(flac_data: &[u8]) -> Option<Lyrics> {
+    let comments = vorbis_comments(flac_data)?;
+    let text = LYRICS_TAGS.iter().find_map(|tag| {
+        comments
+            .iter()
+            .find(|(key, value)| key.eq_ignore_ascii_case(tag) && !value.trim().is_empty())
+            .map(|(_, value)| value.as_str())
+    })?;
+    Some(parse_lyrics(text))
+}
+
+/// Split lyrics text into lines. Text where every line carries an LRC
+/// timestamp comes back synced; anything else is plain lines.
+pub fn parse_lyrics(text: &str) -> Lyrics {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !is_lrc_tag(line))
+        .collect();
+
+    let mut synced_lines = Vec::new();
+    for line in &lines {
+        let (stamps, text) = lrc_timestamps(line);
+        if stamps.is_empty() {
+            // Not LRC after all
+            return Lyrics {
+                synced: false,
+                lines: lines
+                    .iter()
+                    .map(|line| LyricLine {
+                        start_ms: None,
+                        text: line.to_string(),
+                    })
+                    .collect(),
+            };
+        }
+        // A line sung more than once carries a timestamp for each time
+        for start_ms in stamps {
+            synced_lines.push(LyricLine {
+                start_ms: Some(start_ms),
+                text: text.to_string(),
+            });
+        }
+    }
+    synced_lines.sort_by_key(|line| line.start_ms);
+    Lyrics {
+        synced: !synced_lines.is_empty(),
+        lines: synced_lines,
+    }
+}
+
+/// LRC header tags like `[ar:Artist]`, which aren't lyrics
+fn is_lrc_tag(line: &str) -> bool {
+    let Some(inner) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) else {
+        return false;
+    };
+    inner
+        .split_once(':')
+        .is_some_and(|(key, _)| !key.is_empty() && key.chars().all(|c| c.is_ascii_alphabetic()))
+}
+
+/// Leading `[mm:ss]` / `[mm:ss.xx]` stamps on a line, in ms, and the text after them
+fn lrc_timestamps(line: &str) -> (Vec<u64>, &str) {
+    let mut stamps = Vec::new();
+    let mut rest = line;
+    while let Some(inner) = rest.strip_prefix('[') {
+        let Some(end) = inner.find(']') else {
+            break;
+        };
+        let Some(ms) = parse_timestamp(&inner[..end]) else {
+            break;
+        };
+        stamps.push(ms);
+        rest = &inner[end + 1..];
+    }
+    (stamps, rest.trim())
+}
+
+fn parse_timestamp(stamp: &str) -> Option<u64> {
+    let (minutes, seconds) = stamp.split_once(':')?;
+    let minutes: u64 = minutes.parse().ok()?;
+    let (whole, fraction) = seconds.split_once('.').unwrap_or((seconds, ""));
+    if whole.len() != 2 || fraction.len() > 3 {
+        return None;
+    }
+    let whole: u64 = whole.parse().ok()?;
+    let fraction_ms = match fraction.len() {
+        0 => 0,
+        len => fraction.parse::<u64>().ok()? * 10u64.pow(3 - len as u32),
+    };
+    Some(minutes * 60_000 + whole * 1000 + fraction_ms)
+}
+
+/// `KEY=value` pairs from the Vorbis comment block of a FLAC stream
+fn vorbis_comments(flac_data: &[u8]) -> Option<Vec<(String, String)>> {
+    let mut pos = 4;
+    if flac_data.get(..pos)? != b"fLaC" {
+        return None;
+    }
+    loop {
+        let header = flac_data.get(pos..pos + 4)?;
+        let is_last = header[0] & 0x80 != 0;
+        let block_type = header[0] & 0x7f;
+        let length = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+        let body = flac_data.get(pos + 4..pos + 4 + length)?;
+        if block_type == VORBIS_COMMENT_BLOCK {
+            return parse_vorbis_comment(body);
+        }
+        if is_last {
+            return None;
+        }
+        pos += 4 + length;
+    }
+}
+
+fn parse_vorbis_comment(body: &[u8]) -> Option<Vec<(String, String)>> {
+    let mut reader = LeReader { data: body, pos: 0 };
+    let vendor_length = reader.u32()? as usize;
+    reader.bytes(vendor_length)?;
+    let count = reader.u32()?;
+    let mut comments = Vec::new();
+    for _ in 0..count {
+        let length = reader.u32()? as usize;
+        let comment = String::from_utf8_lossy(reader.bytes(length)?);
+        if let Some((key, value)) = comment.split_once('=') {
+            comments.push((key.to_string(), value.to_string()));
+        }
+    }
+    Some(comments)
+}
+
+/// Vorbis comment lengths are little-endian, unlike the rest of FLAC
+struct LeReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> LeReader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let bytes = self.bytes(4)?;
+        Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flac_with_comments(comments: &[&str]) -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend_from_slice(&4u32.to_le_bytes());
+        body.extend_from_slice(b"test");
+        body.extend_from_slice(&(comments.len() as u32).to_le_bytes());
+        for comment in comments {
+            body.extend_from_slice(&(comment.len() as u32).to_le_bytes());
+            body.extend_from_slice(comment.as_bytes());
+        }
+
+        let mut data = b"fLaC".to_vec();
+        // STREAMINFO, zeroed
+        data.extend_from_slice(&[0, 0, 0, 34]);
+        data.extend_from_slice(&[0; 34]);
+        data.push(0x80 | VORBIS_COMMENT_BLOCK);
+        data.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        data.extend_from_slice(&body);
+        data
+    }
+
+    #[test]
+    fn test_flac_lyrics_reads_tag() {
+        let data = flac_with_comments(&["TITLE=Song", "unsyncedlyrics=First line\nSecond line"]);
+        let lyrics = flac_lyrics(&data).unwrap();
+        assert!(!lyrics.synced);
+        assert_eq!(lyrics.lines.len(), 2);
+        assert_eq!(lyrics.lines[1].text, "Second line");
+
+        assert_eq!(flac_lyrics(&flac_with_comments(&["TITLE=Song"])), None);
+        assert_eq!(flac_lyrics(b"ID3 not flac"), None);
+    }
+
+    #[test]
+    fn test_parse_lrc() {
+        let lyrics = parse_lyrics(
+            "[ar:Someone]\n[00:12.50]First line\n\n[01:02.3][00:05]Chorus\n[00:20.125] Last",
+        );
+        assert!(lyrics.synced);
+        let lines: Vec<_> = lyrics
+            .lines
+            .iter()
+            .map(|line| (line.start_ms.unwrap(), line.text.as_str()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (5_000, "Chorus"),
+                (12_500, "First line"),
+                (20_125, "Last"),
+                (62_300, "Chorus"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_lyrics_mixed_is_plain() {
+        let lyrics = parse_lyrics("[00:01.00]Timed\nNot timed");
+        assert!(!lyrics.synced);
+        assert_eq!(lyrics.lines[0].text, "[00:01.00]Timed");
+        assert!(lyrics.lines.iter().all(|line| line.start_ms.is_none()));
+    }
+}
@@ -1,16 +1,35 @@
 use crate::cache::CacheManager;
-use crate::cloud_storage::CloudStorageError;
+use crate::cloud_storage::{ArchiveStatus, CloudStorageError};
 use crate::db::{
-    Database, DbAlbum, DbAlbumArtist, DbArtist, DbAudioFormat, DbFile, DbImage, DbImport,
-    DbRelease, DbStorageProfile, DbTorrent, DbTrack, DbTrackArtist, ImportOperationStatus,
-    ImportStatus,
+    AudioQuality, ChangeKind, Database, DbAlbum, DbAlbumArtist, DbArtist, DbAudioFormat, DbChange,
+    DbClientShare, DbFile, DbImage, DbImport, DbLibraryTrack, DbRelease, DbReleaseTemplate,
+    DbSearchHistoryEntry, DbSearchResults, DbStorageProfile, DbTorrent, DbTrack, DbTrackArtist,
+    DbTrackBookmark, DbTrackPlay, DbTrackPlayStats, DbTrackRip, DbVerificationIssue,
+    DbVerificationRun, ImportOperationStatus, ImportStatus, StorageLocation,
 };
 use crate::encryption::EncryptionService;
+use crate::import::cover_palette::CoverPalette;
+use crate::import::release_template::{is_next_release, template_from_release};
+use crate::library::edit::{
+    clean_artist_names, AlbumEdits, AlbumFields, ReleaseFields, TrackFields,
+};
 use crate::library::export::ExportService;
-use std::path::Path;
+use crate::library::find_replace::{ReplaceField, ReplacePreview};
+use crate::library::undo::UndoHistory;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use thiserror::Error;
 use tokio::sync::broadcast;
-use tracing::warn;
+use tracing::{info, warn};
+/// How long a restored copy of an archived file stays readable
+const ARCHIVE_RESTORE_DAYS: u32 = 7;
+/// Changelog entries recorded before `device_id` is set
+const UNKNOWN_DEVICE_ID: &str = "unknown";
 #[derive(Error, Debug)]
 pub enum LibraryError {
     #[error("Database error: {0}")]
@@ -25,6 +44,14 @@ pub enum LibraryError {
     CloudStorage(#[from] CloudStorageError),
     #[error("Encryption error: {0}")]
     Encryption(#[from] crate::encryption::EncryptionError),
+    #[error("Can't undo: {0}")]
+    Undo(String),
+    #[error("Can't repair: {0}")]
+    Repair(String),
+    #[error("Invalid find pattern: {0}")]
+    InvalidPattern(String),
+    #[error("Invalid edit: {0}")]
+    InvalidEdit(String),
 }
 
 /// Events emitted by LibraryManager when data changes
@@ -44,6 +71,10 @@ pub struct LibraryManager {
     database: Database,
     encryption_service: Option<EncryptionService>,
     event_tx: broadcast::Sender<LibraryEvent>,
+    /// Recorded with every changelog entry this manager writes
+    device_id: String,
+    /// Edits made this session that can be undone, shared between clones
+    history: Arc<Mutex<UndoHistory>>,
 }
 
 impl std::fmt::Debug for LibraryManager {
@@ -61,6 +92,8 @@ impl Clone for LibraryManager {
             database: self.database.clone(),
             encryption_service: self.encryption_service.clone(),
             event_tx: self.event_tx.clone(),
+            device_id: self.device_id.clone(),
+            history: self.history.clone(),
         }
     }
 }
@@ -72,9 +105,17 @@ impl LibraryManager {
             database,
             encryption_service,
             event_tx,
+            device_id: UNKNOWN_DEVICE_ID.to_string(),
+            history: Arc::new(Mutex::new(UndoHistory::default())),
         }
     }
 
+    /// Tag changelog entries written by this manager with a device
+    pub fn with_device_id(mut self, device_id: &str) -> Self {
+        self.device_id = device_id.to_string();
+        self
+    }
+
     /// Subscribe to library events (albums changed, etc.)
     pub fn subscribe_events(&self) -> broadcast::Receiver<LibraryEvent> {
         self.event_tx.subscribe()
@@ -102,6 +143,394 @@ impl LibraryManager {
     pub fn database(&self) -> &Database {
         &self.database
     }
+    /// Append a change to the changelog. A failure here is logged rather
+    /// than failing the change itself, which has already been applied.
+    async fn record_change(
+        &self,
+        kind: ChangeKind,
+        entity_id: &str,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    ) {
+        let change = DbChange::new(&self.device_id, kind, entity_id, before, after);
+        self.log_change(&change).await;
+        self.history.lock().unwrap().push(change);
+    }
+    async fn log_change(&self, change: &DbChange) {
+        if let Err(e) = self.database.insert_change(change).await {
+            warn!(
+                "Failed to record {} in changelog: {}",
+                change.kind.as_str(),
+                e
+            );
+        }
+    }
+    /// Revert the most recent edit made this session. Returns the changes
+    /// that were reverted, or None when there's nothing to undo.
+    pub async fn undo(&self) -> Result<Option<Vec<DbChange>>, LibraryError> {
+        let Some(changes) = self.history.lock().unwrap().pop_undo() else {
+            return Ok(None);
+        };
+        for change in changes.iter().rev() {
+            self.apply_change(change, change.after.as_ref(), change.before.as_ref())
+                .await?;
+        }
+        self.notify_if_renamed(&changes);
+        self.history.lock().unwrap().push_undone(changes.clone());
+        Ok(Some(changes))
+    }
+    /// Apply the most recently undone edit again. Returns its changes, or
+    /// None when there's nothing to redo.
+    pub async fn redo(&self) -> Result<Option<Vec<DbChange>>, LibraryError> {
+        let Some(changes) = self.history.lock().unwrap().pop_redo() else {
+            return Ok(None);
+        };
+        for change in &changes {
+            self.apply_change(change, change.before.as_ref(), change.after.as_ref())
+                .await?;
+        }
+        self.notify_if_renamed(&changes);
+        self.history.lock().unwrap().push_redone(changes.clone());
+        Ok(Some(changes))
+    }
+    /// Album and artist names show in the library grid, so reload it
+    fn notify_if_renamed(&self, changes: &[DbChange]) {
+        if changes.iter().any(|c| {
+            matches!(
+                c.kind,
+                ChangeKind::AlbumRenamed
+                    | ChangeKind::ArtistRenamed
+                    | ChangeKind::AlbumEdited
+                    | ChangeKind::AlbumArtistsEdited
+            )
+        }) {
+            self.notify_albums_changed();
+        }
+    }
+    /// Write the rows of a find-and-replace preview, logging each to the
+    /// changelog. Together they undo as one step. Returns the changes made.
+    pub async fn apply_replacements(
+        &self,
+        rows: &[ReplacePreview],
+    ) -> Result<Vec<DbChange>, LibraryError> {
+        let mut changes = Vec::with_capacity(rows.len());
+        let mut result = Ok(());
+        for row in rows {
+            if let Err(e) = self.write_replacement(row).await {
+                result = Err(e);
+                break;
+            }
+            let change = DbChange::new(
+                &self.device_id,
+                row.field.change_kind(),
+                &row.entity_id,
+                Some(row.field.change_value(&row.before)),
+                Some(row.field.change_value(&row.after)),
+            );
+            self.log_change(&change).await;
+            changes.push(change);
+        }
+
+        // Whatever was written stays undoable, even if a later row failed
+        self.history.lock().unwrap().push_batch(changes.clone());
+        self.notify_albums_changed();
+        result.map(|_| changes)
+    }
+    async fn write_replacement(&self, row: &ReplacePreview) -> Result<(), LibraryError> {
+        match row.field {
+            ReplaceField::AlbumTitle => {
+                self.database
+                    .update_album_title(&row.entity_id, &row.after)
+                    .await?
+            }
+            ReplaceField::TrackTitle => {
+                self.database
+                    .update_track_title(&row.entity_id, &row.after)
+                    .await?
+            }
+            ReplaceField::ArtistName => {
+                self.database
+                    .update_artist_name(&row.entity_id, &row.after)
+                    .await?
+            }
+        }
+        Ok(())
+    }
+    /// Write the fields of an album page's edits that differ from what's
+    /// stored, logging each to the changelog. Together they undo as one
+    /// step. Returns the changes made.
+    pub async fn save_album_edits(
+        &self,
+        edits: &AlbumEdits,
+    ) -> Result<Vec<DbChange>, LibraryError> {
+        edits.validate()?;
+        let mut changes = Vec::new();
+        let result = self.write_album_edits(edits, &mut changes).await;
+
+        // Whatever was written stays undoable, even if a later write failed
+        self.history.lock().unwrap().push_batch(changes.clone());
+        self.notify_if_renamed(&changes);
+        result.map(|_| changes)
+    }
+    async fn write_album_edits(
+        &self,
+        edits: &AlbumEdits,
+        changes: &mut Vec<DbChange>,
+    ) -> Result<(), LibraryError> {
+        let album_id = &edits.album_id;
+        if let Some(fields) = &edits.album {
+            let album = self
+                .database
+                .get_album_by_id(album_id)
+                .await?
+                .ok_or_else(|| LibraryError::InvalidEdit(format!("no album {}", album_id)))?;
+            let before = AlbumFields::from(&album);
+            let after = AlbumFields {
+                title: fields.title.trim().to_string(),
+                year: fields.year,
+            };
+            if before != after {
+                self.database
+                    .update_album_metadata(album_id, &after.title, after.year)
+                    .await?;
+                changes.push(
+                    self.log_edit(ChangeKind::AlbumEdited, album_id, &before, &after)
+                        .await,
+                );
+            }
+        }
+
+        if let Some(names) = &edits.artists {
+            let before = self.database.get_album_artists(album_id).await?;
+            let mut links = Vec::new();
+            for name in clean_artist_names(names) {
+                let artist = match self.database.get_artist_by_name(&name).await? {
+                    Some(artist) => artist,
+                    None => {
+                        let artist = DbArtist::new(&name);
+                        self.database.insert_artist(&artist).await?;
+                        artist
+                    }
+                };
+                // Keep the original credit of an artist still in the same place
+                let credited_as = before
+                    .iter()
+                    .find(|l| l.artist_id == artist.id && l.position == links.len() as i32)
+                    .and_then(|l| l.credited_as.clone());
+                links.push(DbAlbumArtist {
+                    credited_as,
+                    ..DbAlbumArtist::new(album_id, &artist.id, links.len() as i32)
+                });
+            }
+            let (before, after) = (album_artist_ids(&before), album_artist_ids(&links));
+            if before != after {
+                self.database
+                    .replace_album_artists(album_id, &links)
+                    .await?;
+                changes.push(
+                    self.log_edit(ChangeKind::AlbumArtistsEdited, album_id, &before, &after)
+                        .await,
+                );
+            }
+        }
+
+        if !edits.releases.is_empty() {
+            let releases = self.database.get_releases_for_album(album_id).await?;
+            for (release_id, fields) in &edits.releases {
+                let release = releases
+                    .iter()
+                    .find(|r| &r.id == release_id)
+                    .ok_or_else(|| {
+                        LibraryError::InvalidEdit(format!(
+                            "no release {} on this album",
+                            release_id
+                        ))
+                    })?;
+                let before = ReleaseFields::from(release);
+                if &before != fields {
+                    self.database
+                        .update_release_metadata(&fields.applied_to(release))
+                        .await?;
+                    changes.push(
+                        self.log_edit(ChangeKind::ReleaseEdited, release_id, &before, fields)
+                            .await,
+                    );
+                }
+            }
+        }
+
+        for (track_id, fields) in &edits.tracks {
+            let track = self
+                .database
+                .get_track_by_id(track_id)
+                .await?
+                .ok_or_else(|| LibraryError::InvalidEdit(format!("no track {}", track_id)))?;
+            let before = TrackFields::from(&track);
+            let after = TrackFields {
+                title: fields.title.trim().to_string(),
+                ..fields.clone()
+            };
+            if before != after {
+                self.database
+                    .update_track_metadata(
+                        track_id,
+                        &after.title,
+                        after.disc_number,
+                        after.track_number,
+                    )
+                    .await?;
+                changes.push(
+                    self.log_edit(ChangeKind::TrackEdited, track_id, &before, &after)
+                        .await,
+                );
+            }
+        }
+        Ok(())
+    }
+    /// Log an edit with the fields before and after it
+    async fn log_edit<T: Serialize>(
+        &self,
+        kind: ChangeKind,
+        entity_id: &str,
+        before: &T,
+        after: &T,
+    ) -> DbChange {
+        let change = DbChange::new(
+            &self.device_id,
+            kind,
+            entity_id,
+            serde_json::to_value(before).ok(),
+            serde_json::to_value(after).ok(),
+        );
+        self.log_change(&change).await;
+        change
+    }
+    pub fn can_undo(&self) -> bool {
+        self.history.lock().unwrap().can_undo()
+    }
+    pub fn can_redo(&self) -> bool {
+        self.history.lock().unwrap().can_redo()
+    }
+    /// Set an edited entity back to `from` or forward to `to` values for
+    /// undo and redo, and log that in the changelog like any other change
+    async fn apply_change(
+        &self,
+        change: &DbChange,
+        from: Option<&serde_json::Value>,
+        to: Option<&serde_json::Value>,
+    ) -> Result<(), LibraryError> {
+        let Some(values) = to else {
+            return Err(LibraryError::Undo(format!(
+                "no earlier value recorded for {}",
+                change.entity_id
+            )));
+        };
+        match change.kind {
+            ChangeKind::AlbumRenamed => {
+                let title = values["title"]
+                    .as_str()
+                    .ok_or_else(|| LibraryError::Undo("no album title recorded".to_string()))?;
+                self.database
+                    .update_album_title(&change.entity_id, title)
+                    .await?;
+            }
+            ChangeKind::ArtistRenamed => {
+                let name = values["name"]
+                    .as_str()
+                    .ok_or_else(|| LibraryError::Undo("no artist name recorded".to_string()))?;
+                self.database
+                    .update_artist_name(&change.entity_id, name)
+                    .await?;
+            }
+            ChangeKind::TrackRenamed => {
+                let title = values["title"]
+                    .as_str()
+                    .ok_or_else(|| LibraryError::Undo("no track title recorded".to_string()))?;
+                self.database
+                    .update_track_title(&change.entity_id, title)
+                    .await?;
+            }
+            ChangeKind::PreferredReleaseChanged => {
+                self.database
+                    .set_album_preferred_release(&change.entity_id, values["release_id"].as_str())
+                    .await?;
+            }
+            ChangeKind::AlbumEdited => {
+                let fields: AlbumFields = edited_fields(values)?;
+                self.database
+                    .update_album_metadata(&change.entity_id, &fields.title, fields.year)
+                    .await?;
+            }
+            ChangeKind::AlbumArtistsEdited => {
+                let artist_ids: Vec<String> = edited_fields(values)?;
+                let links: Vec<DbAlbumArtist> = artist_ids
+                    .iter()
+                    .enumerate()
+                    .map(|(i, artist_id)| {
+                        DbAlbumArtist::new(&change.entity_id, artist_id, i as i32)
+                    })
+                    .collect();
+                self.database
+                    .replace_album_artists(&change.entity_id, &links)
+                    .await?;
+            }
+            ChangeKind::ReleaseEdited => {
+                let fields: ReleaseFields = edited_fields(values)?;
+                let release = self.get_release(&change.entity_id).await?.ok_or_else(|| {
+                    LibraryError::Undo(format!("release {} is gone", change.entity_id))
+                })?;
+                self.database
+                    .update_release_metadata(&fields.applied_to(&release))
+                    .await?;
+            }
+            ChangeKind::TrackEdited => {
+                let fields: TrackFields = edited_fields(values)?;
+                self.database
+                    .update_track_metadata(
+                        &change.entity_id,
+                        &fields.title,
+                        fields.disc_number,
+                        fields.track_number,
+                    )
+                    .await?;
+            }
+            kind => {
+                return Err(LibraryError::Undo(format!(
+                    "{} can't be undone",
+                    kind.as_str()
+                )));
+            }
+        }
+        self.log_change(&DbChange::new(
+            &self.device_id,
+            change.kind,
+            &change.entity_id,
+            from.cloned(),
+            to.cloned(),
+        ))
+        .await;
+        Ok(())
+    }
+    /// Most recent changelog entries first, optionally only those to one
+    /// album, release, track or artist
+    pub async fn get_changelog(
+        &self,
+        entity_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<DbChange>, LibraryError> {
+        Ok(self.database.get_changes(entity_id, limit).await?)
+    }
+    /// Drop changelog entries older than `retention`, keeping the latest
+    /// change to each entity. Returns how many entries were removed.
+    pub async fn compact_changelog(
+        &self,
+        retention: chrono::Duration,
+    ) -> Result<u64, LibraryError> {
+        Ok(self
+            .database
+            .compact_changelog(Utc::now() - retention)
+            .await?)
+    }
     /// Insert album, release, and tracks into database in a transaction
     pub async fn insert_album_with_release_and_tracks(
         &self,
@@ -112,6 +541,13 @@ impl LibraryManager {
         self.database
             .insert_album_with_release_and_tracks(album, release, tracks)
             .await?;
+        self.record_change(
+            ChangeKind::AlbumAdded,
+            &album.id,
+            None,
+            Some(json!({ "title": album.title, "release_id": release.id })),
+        )
+        .await;
         Ok(())
     }
     /// Mark release as importing when pipeline starts processing
@@ -146,6 +582,19 @@ impl LibraryManager {
             .await?;
         Ok(())
     }
+    /// Rename a track (inline edit from the tracklist)
+    pub async fn rename_track(&self, track_id: &str, title: &str) -> Result<(), LibraryError> {
+        let before = self.database.get_track_by_id(track_id).await?;
+        self.database.update_track_title(track_id, title).await?;
+        self.record_change(
+            ChangeKind::TrackRenamed,
+            track_id,
+            before.map(|track| json!({ "title": track.title })),
+            Some(json!({ "title": title })),
+        )
+        .await;
+        Ok(())
+    }
     /// Mark release as complete after successful import
     pub async fn mark_release_complete(&self, release_id: &str) -> Result<(), LibraryError> {
         self.database
@@ -195,6 +644,22 @@ impl LibraryManager {
     pub async fn get_seeding_torrents(&self) -> Result<Vec<DbTorrent>, LibraryError> {
         Ok(self.database.get_seeding_torrents().await?)
     }
+    /// Get all torrents whose original download is kept for seeding
+    pub async fn get_kept_torrents(&self) -> Result<Vec<DbTorrent>, LibraryError> {
+        Ok(self.database.get_kept_torrents().await?)
+    }
+    /// Set when a torrent stops seeding (None = no goal)
+    pub async fn set_torrent_seed_goals(
+        &self,
+        torrent_id: &str,
+        ratio_goal: Option<f64>,
+        time_goal_secs: Option<i64>,
+    ) -> Result<(), LibraryError> {
+        self.database
+            .update_torrent_seed_goals(torrent_id, ratio_goal, time_goal_secs)
+            .await?;
+        Ok(())
+    }
     /// Mark a torrent as seeding
     pub async fn set_torrent_seeding(
         &self,
@@ -221,10 +686,53 @@ impl LibraryManager {
     ) -> Result<Vec<DbRelease>, LibraryError> {
         Ok(self.database.get_releases_for_album(album_id).await?)
     }
+    /// Get a release by ID
+    pub async fn get_release(&self, release_id: &str) -> Result<Option<DbRelease>, LibraryError> {
+        let Some(album_id) = self.database.get_album_id_for_release(release_id).await? else {
+            return Ok(None);
+        };
+        let releases = self.database.get_releases_for_album(&album_id).await?;
+        Ok(releases.into_iter().find(|r| r.id == release_id))
+    }
+    /// The edition an album plays when none is picked: its preferred release,
+    /// or the first one
+    pub async fn get_default_release(
+        &self,
+        album_id: &str,
+    ) -> Result<Option<DbRelease>, LibraryError> {
+        let Some(album) = self.database.get_album_by_id(album_id).await? else {
+            return Ok(None);
+        };
+        let releases = self.database.get_releases_for_album(album_id).await?;
+        Ok(album.default_release(&releases).cloned())
+    }
+    /// Mark the edition an album plays by default, or clear it
+    pub async fn set_preferred_release(
+        &self,
+        album_id: &str,
+        release_id: Option<&str>,
+    ) -> Result<(), LibraryError> {
+        let before = self.database.get_album_by_id(album_id).await?;
+        self.database
+            .set_album_preferred_release(album_id, release_id)
+            .await?;
+        self.record_change(
+            ChangeKind::PreferredReleaseChanged,
+            album_id,
+            before.map(|album| json!({ "release_id": album.preferred_release_id })),
+            Some(json!({ "release_id": release_id })),
+        )
+        .await;
+        Ok(())
+    }
     /// Get tracks for a specific release
     pub async fn get_tracks(&self, release_id: &str) -> Result<Vec<DbTrack>, LibraryError> {
         Ok(self.database.get_tracks_for_release(release_id).await?)
     }
+    /// All tracks across the library, one edition per album
+    pub async fn get_library_tracks(&self) -> Result<Vec<DbLibraryTrack>, LibraryError> {
+        Ok(self.database.get_library_tracks().await?)
+    }
     /// Get a single track by ID
     pub async fn get_track(&self, track_id: &str) -> Result<Option<DbTrack>, LibraryError> {
         Ok(self.database.get_track_by_id(track_id).await?)
@@ -254,6 +762,30 @@ impl LibraryManager {
     ) -> Result<Option<DbAudioFormat>, LibraryError> {
         Ok(self.database.get_audio_format_by_track_id(track_id).await?)
     }
+    /// Quality of each track in a release, for tracks with an audio format
+    pub async fn get_track_qualities_for_release(
+        &self,
+        release_id: &str,
+    ) -> Result<HashMap<String, AudioQuality>, LibraryError> {
+        Ok(self
+            .database
+            .get_track_qualities_for_release(release_id)
+            .await?)
+    }
+    /// DR score per measured track of a release (None for a silent track)
+    pub async fn get_track_dynamic_ranges_for_release(
+        &self,
+        release_id: &str,
+    ) -> Result<HashMap<String, Option<i32>>, LibraryError> {
+        Ok(self
+            .database
+            .get_track_dynamic_ranges_for_release(release_id)
+            .await?)
+    }
+    /// Lowest track quality per album, so an album with any lossy track reads as lossy
+    pub async fn get_album_qualities(&self) -> Result<HashMap<String, AudioQuality>, LibraryError> {
+        Ok(self.database.get_album_qualities().await?)
+    }
     /// Get release ID for a track
     pub async fn get_release_id_for_track(&self, track_id: &str) -> Result<String, LibraryError> {
         let track = self
@@ -324,6 +856,57 @@ impl LibraryManager {
     ) -> Result<Vec<DbArtist>, LibraryError> {
         Ok(self.database.get_artists_for_album(album_id).await?)
     }
+    /// Get an album's artist links, with the credits they came from
+    pub async fn get_album_artists(
+        &self,
+        album_id: &str,
+    ) -> Result<Vec<DbAlbumArtist>, LibraryError> {
+        Ok(self.database.get_album_artists(album_id).await?)
+    }
+    /// Replace all of an album's artist links
+    pub async fn replace_album_artists(
+        &self,
+        album_id: &str,
+        album_artists: &[DbAlbumArtist],
+    ) -> Result<(), LibraryError> {
+        let before = self.database.get_album_artists(album_id).await?;
+        self.database
+            .replace_album_artists(album_id, album_artists)
+            .await?;
+        self.record_change(
+            ChangeKind::AlbumArtistsChanged,
+            album_id,
+            Some(album_artist_ids(&before)),
+            Some(album_artist_ids(album_artists)),
+        )
+        .await;
+        Ok(())
+    }
+    /// Rename an artist
+    pub async fn update_artist_name(
+        &self,
+        artist_id: &str,
+        name: &str,
+    ) -> Result<(), LibraryError> {
+        let before = self.database.get_artist_by_id(artist_id).await?;
+        self.database.update_artist_name(artist_id, name).await?;
+        self.record_change(
+            ChangeKind::ArtistRenamed,
+            artist_id,
+            before.map(|artist| json!({ "name": artist.name })),
+            Some(json!({ "name": name })),
+        )
+        .await;
+        Ok(())
+    }
+    /// Rebuild album play stats from the listening history, then reclaim
+    /// unused space in the database file
+    pub async fn compact_database(&self) -> Result<(), LibraryError> {
+        let updated = self.database.recompute_album_play_stats().await?;
+        info!("Recomputed play stats for {} album(s)", updated);
+        self.database.compact().await?;
+        Ok(())
+    }
     /// Get artists for a track
     pub async fn get_artists_for_track(
         &self,
@@ -378,7 +961,7 @@ impl LibraryManager {
             .await?)
     }
 
-    /// Fetch image bytes from storage, handling S3 download and decryption as needed
+    /// Fetch image bytes from storage, handling S3 download, decryption and decompression as needed
     pub async fn fetch_image_bytes(&self, image_id: &str) -> Result<Vec<u8>, LibraryError> {
         let image = self
             .get_image_by_id(image_id)
@@ -408,14 +991,20 @@ impl LibraryManager {
             .get_storage_profile_for_release(&image.release_id)
             .await?;
 
-        let raw_data = if source_path.starts_with("s3://") {
+        let raw_data = if crate::cloud_storage::is_cloud_location(&source_path) {
             let profile = storage_profile
                 .as_ref()
                 .ok_or_else(|| LibraryError::Import("No storage profile for cloud image".into()))?;
             let storage = crate::storage::create_storage_reader(profile).await?;
             storage.download(&source_path).await?
         } else {
-            tokio::fs::read(&source_path).await?
+            let path = match &storage_profile {
+                Some(profile) => {
+                    crate::storage::resolve_local_path(&profile.local_roots(), &source_path)
+                }
+                None => source_path.clone().into(),
+            };
+            tokio::fs::read(path).await?
         };
 
         let data = if storage_profile
@@ -428,6 +1017,9 @@ impl LibraryManager {
             raw_data
         };
 
+        if file.compressed {
+            return Ok(crate::storage::decompress(&data)?);
+        }
         Ok(data)
     }
 
@@ -437,45 +1029,149 @@ impl LibraryManager {
         album_id: &str,
         cover_image_id: &str,
     ) -> Result<(), LibraryError> {
+        let before = self.database.get_album_by_id(album_id).await?;
         self.database
             .set_album_cover_image(album_id, cover_image_id)
             .await?;
+        self.record_change(
+            ChangeKind::CoverChanged,
+            album_id,
+            before.map(|album| json!({ "image_id": album.cover_image_id })),
+            Some(json!({ "image_id": cover_image_id })),
+        )
+        .await;
         Ok(())
     }
-    /// Delete a release and its associated data
-    ///
-    /// This will:
-    /// 1. Delete files from storage (errors are logged but don't stop deletion)
-    /// 2. Delete the release from database (cascades to tracks, files, etc.)
-    /// 3. If this was the last release for the album, also delete the album
-    pub async fn delete_release(&self, release_id: &str) -> Result<(), LibraryError> {
-        let album_id = self.get_album_id_for_release(release_id).await?;
 
-        // Try to get storage reader for file cleanup
-        if let Ok(Some(profile)) = self
+    /// Store the colors extracted from an album's cover
+    pub async fn set_album_palette(
+        &self,
+        album_id: &str,
+        palette: &CoverPalette,
+    ) -> Result<(), LibraryError> {
+        self.database
+            .set_album_palette(album_id, &palette.dominant_hex(), &palette.accent_hex())
+            .await?;
+        Ok(())
+    }
+    /// Bytes a release's files take up in bae's storage. A release without a
+    /// storage profile, or on a reference profile, keeps its files where they
+    /// were imported from, so deleting it frees nothing and this is 0.
+    pub async fn stored_bytes(&self, release_id: &str) -> Result<u64, LibraryError> {
+        match self
+            .database
+            .get_storage_profile_for_release(release_id)
+            .await?
+        {
+            Some(profile) if profile.location != StorageLocation::Reference => {}
+            _ => return Ok(0),
+        }
+        let files = self.get_files_for_release(release_id).await?;
+        Ok(files.iter().map(|f| f.file_size.max(0) as u64).sum())
+    }
+
+    /// Delete a release's files from its storage profile, local or cloud.
+    /// Files under a reference profile aren't bae's, so they're left alone.
+    /// Errors are logged but don't stop the deletion.
+    async fn delete_release_files(&self, release_id: &str) -> Result<(), LibraryError> {
+        let Ok(Some(profile)) = self
             .database
             .get_storage_profile_for_release(release_id)
             .await
-        {
-            if let Ok(storage) = crate::storage::create_storage_reader(&profile).await {
-                let files = self.get_files_for_release(release_id).await?;
-                for file in &files {
-                    if let Some(ref source_path) = file.source_path {
-                        if let Err(e) = storage.delete(source_path).await {
-                            warn!(
-                                "Failed to delete file {} from storage: {}. Continuing with database deletion.",
-                                file.id, e
-                            );
-                        }
+        else {
+            return Ok(());
+        };
+        if profile.location == StorageLocation::Reference {
+            return Ok(());
+        }
+        let Ok(storage) = crate::storage::create_storage_reader(&profile).await else {
+            return Ok(());
+        };
+        let files = self.get_files_for_release(release_id).await?;
+        for file in &files {
+            if let Some(ref source_path) = file.source_path {
+                if let Err(e) = storage.delete(source_path).await {
+                    warn!(
+                        "Failed to delete file {} from storage: {}. Continuing with database deletion.",
+                        file.id, e
+                    );
+                }
+            }
+        }
+        if profile.location == StorageLocation::Local {
+            // Clear out the release's now-empty folders on every root, leaving
+            // any that still hold something
+            let release_dirs: Vec<_> = profile
+                .local_roots()
+                .into_iter()
+                .map(|root| Path::new(root).join(release_id))
+                .collect();
+            for file in &files {
+                let mut dir = file
+                    .source_path
+                    .as_deref()
+                    .and_then(|p| Path::new(p).parent());
+                while let Some(current) = dir.filter(|d| {
+                    release_dirs
+                        .iter()
+                        .any(|release_dir| d.starts_with(release_dir))
+                }) {
+                    if tokio::fs::remove_dir(crate::storage::long_path(current))
+                        .await
+                        .is_err()
+                    {
+                        break;
                     }
+                    dir = current.parent();
                 }
             }
         }
+        Ok(())
+    }
+
+    /// Delete a release and its associated data
+    ///
+    /// This will:
+    /// 1. Delete files from storage unless `keep_files` (errors are logged but don't stop deletion)
+    /// 2. Delete the release from database (cascades to tracks, files, etc.)
+    /// 3. If this was the last release for the album, also delete the album
+    pub async fn delete_release(
+        &self,
+        release_id: &str,
+        keep_files: bool,
+    ) -> Result<(), LibraryError> {
+        let album_id = self.get_album_id_for_release(release_id).await?;
+
+        if !keep_files {
+            self.delete_release_files(release_id).await?;
+        }
 
+        let release = self
+            .get_releases_for_album(&album_id)
+            .await?
+            .into_iter()
+            .find(|release| release.id == release_id);
         self.database.delete_release(release_id).await?;
+        self.record_change(
+            ChangeKind::ReleaseDeleted,
+            release_id,
+            release.map(|release| {
+                json!({ "album_id": release.album_id, "release_name": release.release_name })
+            }),
+            None,
+        )
+        .await;
         let remaining_releases = self.get_releases_for_album(&album_id).await?;
         if remaining_releases.is_empty() {
+            let album = self.database.get_album_by_id(&album_id).await?;
             self.database.delete_album(&album_id).await?;
+            self.record_change(
+                ChangeKind::AlbumDeleted,
+                &album_id,
+                album.map(|album| json!({ "title": album.title })),
+                None,
+            )
+            .await;
         }
 
         // Notify UI that library has changed
@@ -488,39 +1184,99 @@ impl LibraryManager {
     ///
     /// This will:
     /// 1. Get all releases for the album
-    /// 2. For each release, delete files from storage
+    /// 2. For each release, delete files from storage unless `keep_files`
     /// 3. Delete the album from database (cascades to releases and all related data)
-    pub async fn delete_album(&self, album_id: &str) -> Result<(), LibraryError> {
-        let releases = self.get_releases_for_album(album_id).await?;
-        for release in &releases {
-            // Try to get storage reader for file cleanup
-            if let Ok(Some(profile)) = self
-                .database
-                .get_storage_profile_for_release(&release.id)
-                .await
-            {
-                if let Ok(storage) = crate::storage::create_storage_reader(&profile).await {
-                    let files = self.get_files_for_release(&release.id).await?;
-                    for file in &files {
-                        if let Some(ref source_path) = file.source_path {
-                            if let Err(e) = storage.delete(source_path).await {
-                                warn!(
-                                    "Failed to delete file {} from storage: {}. Continuing with database deletion.",
-                                    file.id, e
-                                );
-                            }
-                        }
-                    }
-                }
+    pub async fn delete_album(&self, album_id: &str, keep_files: bool) -> Result<(), LibraryError> {
+        if !keep_files {
+            for release in self.get_releases_for_album(album_id).await? {
+                self.delete_release_files(&release.id).await?;
             }
         }
+        let album = self.database.get_album_by_id(album_id).await?;
         self.database.delete_album(album_id).await?;
+        self.record_change(
+            ChangeKind::AlbumDeleted,
+            album_id,
+            album.map(|album| json!({ "title": album.title })),
+            None,
+        )
+        .await;
 
         // Notify UI that library has changed
         self.notify_albums_changed();
 
         Ok(())
     }
+    /// Copy a release out of its reference profile into `target_profile_id`
+    ///
+    /// Every file is stored first, then the file records are pointed at the
+    /// copies in place, so tracks and images keep finding them. The originals
+    /// are left where they are.
+    pub async fn migrate_release(
+        &self,
+        release_id: &str,
+        target_profile_id: &str,
+    ) -> Result<(), LibraryError> {
+        let source = self
+            .database
+            .get_storage_profile_for_release(release_id)
+            .await?;
+        if !source.is_some_and(|profile| profile.location == StorageLocation::Reference) {
+            return Err(LibraryError::Import(format!(
+                "Release {} isn't in a reference profile",
+                release_id
+            )));
+        }
+        let target = self
+            .database
+            .get_storage_profile(target_profile_id)
+            .await?
+            .ok_or_else(|| {
+                LibraryError::Import(format!("Storage profile not found: {}", target_profile_id))
+            })?;
+        let storage = crate::storage::ReleaseStorageImpl::from_profile(
+            target,
+            self.encryption_service.clone(),
+            Arc::new(self.database.clone()),
+        )
+        .await
+        .map_err(|e| LibraryError::Import(e.to_string()))?;
+
+        let mut migrated = Vec::new();
+        for file in self.get_files_for_release(release_id).await? {
+            let Some(source_path) = file.source_path.as_deref() else {
+                continue;
+            };
+            let data = tokio::fs::read(crate::storage::long_path(Path::new(source_path))).await?;
+            let stored = storage
+                .store_file(
+                    release_id,
+                    &file.original_filename,
+                    &data,
+                    Box::new(|_, _| {}),
+                )
+                .await
+                .map_err(|e| LibraryError::Import(e.to_string()))?;
+            migrated.push(DbFile {
+                source_path: stored.source_path,
+                encryption_nonce: stored.encryption_nonce,
+                compressed: stored.compressed,
+                checksum_verified: stored.checksum_verified,
+                sha256: stored.sha256,
+                ..file
+            });
+        }
+
+        for file in &migrated {
+            self.database.update_file_storage(file).await?;
+        }
+        self.database
+            .set_release_storage_profile(release_id, target_profile_id)
+            .await?;
+        self.notify_albums_changed();
+        Ok(())
+    }
+
     /// Export all files for a release to a directory
     ///
     /// Copies files from storage to the target directory.
@@ -541,6 +1297,30 @@ impl LibraryManager {
         .await
         .map_err(LibraryError::Import)
     }
+    /// Export a release as a gift package (audio, cover and a browser player)
+    /// in `target_dir`, zipped if `zip` is set. Returns the folder or zip.
+    pub async fn export_gift(
+        &self,
+        release_id: &str,
+        target_dir: &Path,
+        zip: bool,
+        cache: &CacheManager,
+    ) -> Result<PathBuf, LibraryError> {
+        crate::library::gift::export_gift(release_id, target_dir, zip, self, cache)
+            .await
+            .map_err(LibraryError::Import)
+    }
+    /// Export a single release file to a directory and return its path.
+    /// Files of unmanaged releases are returned at their original path.
+    pub async fn export_file(
+        &self,
+        file_id: &str,
+        target_dir: &Path,
+    ) -> Result<PathBuf, LibraryError> {
+        ExportService::export_file(file_id, target_dir, self, self.encryption_service.as_ref())
+            .await
+            .map_err(LibraryError::Import)
+    }
     /// Export a single track as a FLAC file
     ///
     /// For one-file-per-track: extracts the original file.
@@ -608,6 +1388,10 @@ impl LibraryManager {
     pub async fn get_all_storage_profiles(&self) -> Result<Vec<DbStorageProfile>, LibraryError> {
         Ok(self.database.get_all_storage_profiles().await?)
     }
+    /// Bytes stored under each profile, keyed by profile ID
+    pub async fn get_storage_usage_by_profile(&self) -> Result<HashMap<String, u64>, LibraryError> {
+        Ok(self.database.get_storage_usage_by_profile().await?)
+    }
     /// Get the default storage profile
     pub async fn get_default_storage_profile(
         &self,
@@ -649,6 +1433,50 @@ impl LibraryManager {
             .get_storage_profile_for_release(release_id)
             .await?)
     }
+    /// Check whether a release stored on an archival profile can be streamed.
+    ///
+    /// Returns None when the release's profile isn't marked archival, so
+    /// ordinary releases never pay for the per-file status requests.
+    pub async fn get_release_archive_status(
+        &self,
+        release_id: &str,
+    ) -> Result<Option<ArchiveStatus>, LibraryError> {
+        let Some(profile) = self
+            .database
+            .get_storage_profile_for_release(release_id)
+            .await?
+            .filter(|p| p.cloud_archival)
+        else {
+            return Ok(None);
+        };
+        let storage = crate::storage::create_storage_reader(&profile).await?;
+        let mut status = ArchiveStatus::Available;
+        for file in self.get_files_for_release(release_id).await? {
+            if let Some(ref source_path) = file.source_path {
+                status = status.max(storage.archive_status(source_path).await?);
+            }
+        }
+        Ok(Some(status))
+    }
+    /// Request restores for every archived file of a release
+    pub async fn request_release_restore(&self, release_id: &str) -> Result<(), LibraryError> {
+        let profile = self
+            .database
+            .get_storage_profile_for_release(release_id)
+            .await?
+            .ok_or_else(|| LibraryError::Import("No storage profile for release".to_string()))?;
+        let storage = crate::storage::create_storage_reader(&profile).await?;
+        for file in self.get_files_for_release(release_id).await? {
+            if let Some(ref source_path) = file.source_path {
+                if storage.archive_status(source_path).await? == ArchiveStatus::Archived {
+                    storage
+                        .request_restore(source_path, ARCHIVE_RESTORE_DAYS)
+                        .await?;
+                }
+            }
+        }
+        Ok(())
+    }
     /// Insert a new import operation record
     pub async fn insert_import(&self, import: &DbImport) -> Result<(), LibraryError> {
         Ok(self.database.insert_import(import).await?)
@@ -676,31 +1504,270 @@ impl LibraryManager {
     pub async fn update_import_error(&self, id: &str, error: &str) -> Result<(), LibraryError> {
         Ok(self.database.update_import_error(id, error).await?)
     }
-    /// Get all active (non-complete, non-failed) imports
+    /// Get all active (unfinished) imports
     pub async fn get_active_imports(&self) -> Result<Vec<DbImport>, LibraryError> {
         Ok(self.database.get_active_imports().await?)
     }
 
+    /// Undo a partial import of a release so it can be imported again from
+    /// the start: stored files are deleted, along with their records
+    pub async fn reset_release_import(&self, release_id: &str) -> Result<(), LibraryError> {
+        self.delete_release_files(release_id).await?;
+        Ok(self.database.clear_release_import(release_id).await?)
+    }
+
     /// Delete an import record (used by UI to dismiss stuck imports)
     pub async fn delete_import(&self, id: &str) -> Result<(), LibraryError> {
         Ok(self.database.delete_import(id).await?)
     }
-}
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::db::{DbAlbum, DbRelease, ImportStatus};
-    use chrono::Utc;
-    use tempfile::TempDir;
-    use uuid::Uuid;
 
-    async fn setup_test_manager() -> (LibraryManager, TempDir) {
-        let temp_dir = TempDir::new().unwrap();
-        let db_path = temp_dir.path().join("test.db");
-        let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
-        let encryption_service = EncryptionService::new_with_key(&[0u8; 32]);
-        let manager = LibraryManager::new(database, Some(encryption_service));
-        (manager, temp_dir)
+    /// Record that a track finished playing, in its listening history and
+    /// its album's play stats
+    ///
+    /// Returns the updated album so callers can refresh displayed stats.
+    pub async fn record_track_play(&self, track_id: &str) -> Result<Option<DbAlbum>, LibraryError> {
+        let album_id = self.get_album_id_for_track(track_id).await?;
+        let played_at = chrono::Utc::now();
+        self.database.insert_track_play(track_id, played_at).await?;
+        self.database
+            .record_album_play(&album_id, played_at)
+            .await?;
+        Ok(self.database.get_album_by_id(&album_id).await?)
+    }
+
+    /// Plays between `from` and `to` (exclusive), newest first
+    pub async fn get_listening_history(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<DbTrackPlay>, LibraryError> {
+        Ok(self.database.get_track_plays(from, to, limit).await?)
+    }
+
+    /// Tracks played most between `from` and `to` (exclusive)
+    pub async fn get_most_played_tracks(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<DbTrackPlayStats>, LibraryError> {
+        Ok(self
+            .database
+            .get_most_played_tracks(from, to, limit)
+            .await?)
+    }
+
+    /// Tracks played most recently
+    pub async fn get_recently_played_tracks(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<DbTrackPlayStats>, LibraryError> {
+        Ok(self.database.get_recently_played_tracks(limit).await?)
+    }
+
+    /// Remember a value used in a manual metadata search
+    pub async fn record_search_value(
+        &self,
+        source: &str,
+        field: &str,
+        value: &str,
+    ) -> Result<(), LibraryError> {
+        Ok(self
+            .database
+            .record_search_value(source, field, value)
+            .await?)
+    }
+
+    /// Get remembered manual search values, most recent first
+    pub async fn get_search_history(&self) -> Result<Vec<DbSearchHistoryEntry>, LibraryError> {
+        Ok(self.database.get_search_history().await?)
+    }
+
+    /// Save a series template from one of its releases, see
+    /// [`crate::import::release_template`]
+    pub async fn save_release_template(
+        &self,
+        artist: &str,
+        title: &str,
+        cover_url: Option<String>,
+    ) -> Result<DbReleaseTemplate, LibraryError> {
+        let template = template_from_release(artist, title, cover_url).ok_or_else(|| {
+            LibraryError::Import(
+                "A template needs an artist and a volume number in the title".to_string(),
+            )
+        })?;
+        self.database.insert_release_template(&template).await?;
+        Ok(template)
+    }
+
+    /// Get release templates, oldest first
+    pub async fn get_release_templates(&self) -> Result<Vec<DbReleaseTemplate>, LibraryError> {
+        Ok(self.database.get_release_templates().await?)
+    }
+
+    pub async fn delete_release_template(&self, id: &str) -> Result<(), LibraryError> {
+        Ok(self.database.delete_release_template(id).await?)
+    }
+
+    /// Move the templates that would have filled in this release on to their
+    /// next volume. Returns whether any moved.
+    pub async fn advance_release_templates(
+        &self,
+        artist: &str,
+        title: &str,
+    ) -> Result<bool, LibraryError> {
+        let mut advanced = false;
+        for template in self.database.get_release_templates().await? {
+            if is_next_release(&template, artist, title) {
+                self.database
+                    .set_release_template_next_number(&template.id, template.next_number + 1)
+                    .await?;
+                advanced = true;
+            }
+        }
+        Ok(advanced)
+    }
+
+    /// Bookmark a position inside a track
+    pub async fn add_bookmark(
+        &self,
+        track_id: &str,
+        name: &str,
+        position_ms: i64,
+    ) -> Result<DbTrackBookmark, LibraryError> {
+        let bookmark = DbTrackBookmark::new(track_id, name.trim(), position_ms.max(0));
+        self.database.insert_track_bookmark(&bookmark).await?;
+        Ok(bookmark)
+    }
+
+    pub async fn get_bookmarks_for_track(
+        &self,
+        track_id: &str,
+    ) -> Result<Vec<DbTrackBookmark>, LibraryError> {
+        Ok(self.database.get_bookmarks_for_track(track_id).await?)
+    }
+
+    pub async fn get_bookmarks_for_release(
+        &self,
+        release_id: &str,
+    ) -> Result<Vec<DbTrackBookmark>, LibraryError> {
+        Ok(self.database.get_bookmarks_for_release(release_id).await?)
+    }
+
+    pub async fn delete_bookmark(&self, bookmark_id: &str) -> Result<(), LibraryError> {
+        Ok(self.database.delete_track_bookmark(bookmark_id).await?)
+    }
+
+    /// Record how a track was ripped from CD
+    pub async fn save_track_rip(&self, rip: &DbTrackRip) -> Result<(), LibraryError> {
+        Ok(self.database.set_track_rip(rip).await?)
+    }
+
+    /// Rip status of each ripped track of a release, in track order
+    pub async fn get_track_rips_for_release(
+        &self,
+        release_id: &str,
+    ) -> Result<Vec<DbTrackRip>, LibraryError> {
+        Ok(self.database.get_track_rips_for_release(release_id).await?)
+    }
+
+    /// Create a client share with a fresh token and no albums
+    pub async fn create_client_share(&self, name: &str) -> Result<DbClientShare, LibraryError> {
+        let share = DbClientShare::new(name.trim());
+        self.database.insert_client_share(&share).await?;
+        Ok(share)
+    }
+
+    pub async fn get_client_shares(&self) -> Result<Vec<DbClientShare>, LibraryError> {
+        Ok(self.database.get_client_shares().await?)
+    }
+
+    pub async fn delete_client_share(&self, share_id: &str) -> Result<(), LibraryError> {
+        Ok(self.database.delete_client_share(share_id).await?)
+    }
+
+    pub async fn set_album_shared(
+        &self,
+        share_id: &str,
+        album_id: &str,
+        shared: bool,
+    ) -> Result<(), LibraryError> {
+        Ok(self
+            .database
+            .set_album_shared(share_id, album_id, shared)
+            .await?)
+    }
+
+    pub async fn get_shared_album_ids(&self, share_id: &str) -> Result<Vec<String>, LibraryError> {
+        Ok(self.database.get_shared_album_ids(share_id).await?)
+    }
+
+    /// Search artists, albums and tracks for typed text, best matches
+    /// first, at most `limit` of each
+    pub async fn search_library(
+        &self,
+        text: &str,
+        limit: usize,
+    ) -> Result<DbSearchResults, LibraryError> {
+        let Some(query) = crate::library::search::fts_query(text) else {
+            return Ok(DbSearchResults::default());
+        };
+        self.database.refresh_search_index().await?;
+        Ok(self.database.search_library(&query, limit as i64).await?)
+    }
+
+    /// Most recent verification runs, newest first
+    pub async fn get_verification_runs(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<DbVerificationRun>, LibraryError> {
+        Ok(self.database.get_verification_runs(limit).await?)
+    }
+
+    pub async fn get_verification_issues(
+        &self,
+        run_id: &str,
+    ) -> Result<Vec<DbVerificationIssue>, LibraryError> {
+        Ok(self.database.get_verification_issues(run_id).await?)
+    }
+
+    /// When each damaged file was first reported, by file ID
+    pub async fn get_first_verification_failures(
+        &self,
+    ) -> Result<HashMap<String, DateTime<Utc>>, LibraryError> {
+        Ok(self.database.get_first_verification_failures().await?)
+    }
+}
+/// Artist IDs an album is credited to, in credit order, as changelog JSON
+/// Fields recorded with an edit, read back for undo and redo
+fn edited_fields<T: DeserializeOwned>(values: &serde_json::Value) -> Result<T, LibraryError> {
+    serde_json::from_value(values.clone())
+        .map_err(|e| LibraryError::Undo(format!("unreadable edit: {}", e)))
+}
+fn album_artist_ids(album_artists: &[DbAlbumArtist]) -> serde_json::Value {
+    let mut album_artists = album_artists.to_vec();
+    album_artists.sort_by_key(|link| link.position);
+    json!(album_artists
+        .iter()
+        .map(|link| &link.artist_id)
+        .collect::<Vec<_>>())
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{AccurateRipStatus, DbAlbum, DbRelease, ImportStatus, SEARCH_HISTORY_LIMIT};
+    use chrono::Utc;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    async fn setup_test_manager() -> (LibraryManager, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+        let encryption_service = EncryptionService::new_with_key(&[0u8; 32]);
+        let manager = LibraryManager::new(database, Some(encryption_service));
+        (manager, temp_dir)
     }
 
     fn create_test_album() -> DbAlbum {
@@ -716,6 +1783,11 @@ mod tests {
             is_compilation: false,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            play_count: 0,
+            last_played_at: None,
+            dominant_color: None,
+            accent_color: None,
+            preferred_release_id: None,
         }
     }
 
@@ -747,7 +1819,7 @@ mod tests {
         manager.database.insert_album(&album).await.unwrap();
         manager.database.insert_release(&release).await.unwrap();
 
-        manager.delete_release(&release.id).await.unwrap();
+        manager.delete_release(&release.id, false).await.unwrap();
 
         let album_result = manager.database.get_album_by_id(&album.id).await.unwrap();
         assert!(album_result.is_none());
@@ -770,7 +1842,7 @@ mod tests {
         manager.database.insert_release(&release1).await.unwrap();
         manager.database.insert_release(&release2).await.unwrap();
 
-        manager.delete_release(&release1.id).await.unwrap();
+        manager.delete_release(&release1.id, false).await.unwrap();
 
         let album_result = manager.database.get_album_by_id(&album.id).await.unwrap();
         assert!(album_result.is_some());
@@ -794,7 +1866,7 @@ mod tests {
         manager.database.insert_release(&release1).await.unwrap();
         manager.database.insert_release(&release2).await.unwrap();
 
-        manager.delete_album(&album.id).await.unwrap();
+        manager.delete_album(&album.id, false).await.unwrap();
 
         let album_result = manager.database.get_album_by_id(&album.id).await.unwrap();
         assert!(album_result.is_none());
@@ -805,4 +1877,1101 @@ mod tests {
             .unwrap();
         assert!(releases.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_search_history_bumps_existing_values() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+
+        manager
+            .record_search_value("musicbrainz", "artist", "Hendrix")
+            .await
+            .unwrap();
+        manager
+            .record_search_value("discogs", "artist", "Hendrix")
+            .await
+            .unwrap();
+        manager
+            .record_search_value("musicbrainz", "artist", "Hendrix")
+            .await
+            .unwrap();
+
+        let history = manager.get_search_history().await.unwrap();
+        let mb_artists: Vec<_> = history
+            .iter()
+            .filter(|e| e.source == "musicbrainz" && e.field == "artist")
+            .collect();
+        assert_eq!(mb_artists.len(), 1);
+        assert_eq!(history.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_release_template_advances_when_used() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+
+        manager
+            .save_release_template("Night Shift Radio", "Night Shift Mix Vol. 12", None)
+            .await
+            .unwrap();
+        assert!(manager
+            .save_release_template("Night Shift Radio", "Live at the Den", None)
+            .await
+            .is_err());
+
+        // A different release in the series leaves the template alone
+        assert!(!manager
+            .advance_release_templates("Night Shift Radio", "Night Shift Mix Vol. 11")
+            .await
+            .unwrap());
+        assert!(manager
+            .advance_release_templates("Night Shift Radio", "Night Shift Mix Vol. 12")
+            .await
+            .unwrap());
+
+        let templates = manager.get_release_templates().await.unwrap();
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].next_number, 13);
+
+        manager
+            .delete_release_template(&templates[0].id)
+            .await
+            .unwrap();
+        assert!(manager.get_release_templates().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_history_is_capped_per_field() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+
+        for i in 0..(SEARCH_HISTORY_LIMIT + 5) {
+            manager
+                .record_search_value("musicbrainz", "catalog_number", &format!("CAT-{}", i))
+                .await
+                .unwrap();
+        }
+        manager
+            .record_search_value("musicbrainz", "barcode", "4943674251780")
+            .await
+            .unwrap();
+
+        let history = manager.get_search_history().await.unwrap();
+        let catalog_count = history
+            .iter()
+            .filter(|e| e.field == "catalog_number")
+            .count();
+        assert_eq!(catalog_count as i64, SEARCH_HISTORY_LIMIT);
+        assert!(history.iter().any(|e| e.field == "barcode"));
+    }
+
+    #[tokio::test]
+    async fn test_record_track_play_updates_album_stats() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let track = DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "Track", Some(1));
+
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&release).await.unwrap();
+        manager.database.insert_track(&track).await.unwrap();
+
+        let stored = manager.get_album_by_id(&album.id).await.unwrap().unwrap();
+        assert_eq!(stored.play_count, 0);
+        assert!(stored.last_played_at.is_none());
+
+        manager.record_track_play(&track.id).await.unwrap();
+        let updated = manager.record_track_play(&track.id).await.unwrap().unwrap();
+        assert_eq!(updated.play_count, 2);
+        assert!(updated.last_played_at.is_some());
+
+        let history = manager.get_listening_history(None, None, 10).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].track.track.id, track.id);
+        assert_eq!(history[0].track.album_id, album.id);
+    }
+
+    /// A release stored in a local profile rooted at `root`, with two files
+    /// on disk. Returns the album, the release and the file paths.
+    async fn stored_release(
+        manager: &LibraryManager,
+        root: &Path,
+    ) -> (DbAlbum, DbRelease, Vec<PathBuf>) {
+        use crate::db::{DbFile, DbReleaseStorage, DbStorageProfile};
+
+        let profile = DbStorageProfile::new_local("Local", root.to_str().unwrap(), false);
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        manager
+            .database
+            .insert_storage_profile(&profile)
+            .await
+            .unwrap();
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&release).await.unwrap();
+        manager
+            .database
+            .insert_release_storage(&DbReleaseStorage::new(&release.id, &profile.id))
+            .await
+            .unwrap();
+
+        let release_dir = root.join(&release.id);
+        let mut paths = Vec::new();
+        for (name, contents) in [("CD1/01.flac", &b"audio"[..]), ("cover.jpg", &b"jpg"[..])] {
+            let path = release_dir.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, contents).unwrap();
+            let file = DbFile::new(&release.id, name, contents.len() as i64, "flac")
+                .with_source_path(path.to_str().unwrap());
+            manager.database.insert_file(&file).await.unwrap();
+            paths.push(path);
+        }
+        (album, release, paths)
+    }
+
+    #[tokio::test]
+    async fn test_stored_bytes_sums_stored_files() {
+        let (manager, temp_dir) = setup_test_manager().await;
+        let (_, release, _) = stored_release(&manager, &temp_dir.path().join("storage")).await;
+        assert_eq!(manager.stored_bytes(&release.id).await.unwrap(), 8);
+
+        // Files of a release without a storage profile aren't bae's to free
+        let album = create_test_album();
+        let unmanaged = create_test_release(&album.id);
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&unmanaged).await.unwrap();
+        let file = crate::db::DbFile::new(&unmanaged.id, "01.flac", 1_000, "flac")
+            .with_source_path("/music/01.flac");
+        manager.database.insert_file(&file).await.unwrap();
+        assert_eq!(manager.stored_bytes(&unmanaged.id).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_delete_release_removes_stored_files() {
+        let (manager, temp_dir) = setup_test_manager().await;
+        let root = temp_dir.path().join("storage");
+        let (album, release, paths) = stored_release(&manager, &root).await;
+
+        manager.delete_release(&release.id, false).await.unwrap();
+
+        assert!(paths.iter().all(|p| !p.exists()));
+        assert!(!root.join(&release.id).exists());
+        assert!(manager
+            .get_files_for_release(&release.id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(manager
+            .database
+            .get_album_by_id(&album.id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_release_keeping_files() {
+        let (manager, temp_dir) = setup_test_manager().await;
+        let (album, release, paths) =
+            stored_release(&manager, &temp_dir.path().join("storage")).await;
+
+        manager.delete_release(&release.id, true).await.unwrap();
+
+        assert!(paths.iter().all(|p| p.exists()));
+        assert!(manager
+            .get_files_for_release(&release.id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(manager
+            .database
+            .get_album_by_id(&album.id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_album_keeping_files() {
+        let (manager, temp_dir) = setup_test_manager().await;
+        let (album, release, paths) =
+            stored_release(&manager, &temp_dir.path().join("storage")).await;
+
+        manager.delete_album(&album.id, true).await.unwrap();
+
+        assert!(paths.iter().all(|p| p.exists()));
+        assert!(manager
+            .get_files_for_release(&release.id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(manager
+            .database
+            .get_album_by_id(&album.id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_compact_recomputes_album_play_stats() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let track = DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "Track", Some(1));
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&release).await.unwrap();
+        manager.database.insert_track(&track).await.unwrap();
+
+        manager.record_track_play(&track.id).await.unwrap();
+        // A play counted on the album with no history row behind it
+        manager
+            .database
+            .record_album_play(&album.id, Utc::now())
+            .await
+            .unwrap();
+        let stale = manager.get_album_by_id(&album.id).await.unwrap().unwrap();
+        assert_eq!(stale.play_count, 2);
+
+        manager.compact_database().await.unwrap();
+        let history = manager.get_listening_history(None, None, 10).await.unwrap();
+        let album = manager.get_album_by_id(&album.id).await.unwrap().unwrap();
+        assert_eq!(album.play_count, 1);
+        assert_eq!(
+            album.last_played_at.map(|t| t.timestamp_millis()),
+            Some(history[0].played_at.timestamp_millis())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compact_keeps_stats_of_albums_without_history() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        manager.database.insert_album(&album).await.unwrap();
+        for _ in 0..3 {
+            manager
+                .database
+                .record_album_play(&album.id, Utc::now())
+                .await
+                .unwrap();
+        }
+
+        manager.compact_database().await.unwrap();
+        let stored = manager.get_album_by_id(&album.id).await.unwrap().unwrap();
+        assert_eq!(stored.play_count, 3);
+        assert!(stored.last_played_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_play_stats_respect_date_range() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let first = DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "First", Some(1));
+        let second = DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "Second", Some(2));
+
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&release).await.unwrap();
+        manager.database.insert_track(&first).await.unwrap();
+        manager.database.insert_track(&second).await.unwrap();
+
+        let day = |d: u32| {
+            chrono::NaiveDate::from_ymd_opt(2024, 6, d)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        for (track, played_at) in [
+            (&first, day(1)),
+            (&first, day(2)),
+            (&first, day(3)),
+            (&second, day(10)),
+            (&second, day(11)),
+        ] {
+            manager
+                .database
+                .insert_track_play(&track.id, played_at)
+                .await
+                .unwrap();
+        }
+
+        let top = manager
+            .get_most_played_tracks(None, None, 10)
+            .await
+            .unwrap();
+        assert_eq!(top[0].track.track.id, first.id);
+        assert_eq!(top[0].play_count, 3);
+
+        let top = manager
+            .get_most_played_tracks(Some(day(5)), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].track.track.id, second.id);
+
+        let recent = manager.get_recently_played_tracks(10).await.unwrap();
+        assert_eq!(recent[0].track.track.id, second.id);
+        assert_eq!(recent[0].last_played_at, day(11));
+
+        let history = manager
+            .get_listening_history(Some(day(2)), Some(day(10)), 10)
+            .await
+            .unwrap();
+        let played: Vec<_> = history.iter().map(|p| p.played_at).collect();
+        assert_eq!(played, vec![day(3), day(2)]);
+    }
+
+    #[tokio::test]
+    async fn test_bookmarks_are_ordered_by_position() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let track = DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "Mix", Some(1));
+
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&release).await.unwrap();
+        manager.database.insert_track(&track).await.unwrap();
+
+        manager
+            .add_bookmark(&track.id, "Drop", 754_000)
+            .await
+            .unwrap();
+        let intro = manager.add_bookmark(&track.id, " Intro ", 0).await.unwrap();
+        assert_eq!(intro.name, "Intro");
+
+        let names: Vec<_> = manager
+            .get_bookmarks_for_release(&release.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|b| b.name)
+            .collect();
+        assert_eq!(names, vec!["Intro", "Drop"]);
+
+        manager.delete_bookmark(&intro.id).await.unwrap();
+        let remaining = manager.get_bookmarks_for_track(&track.id).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].position_ms, 754_000);
+    }
+
+    #[tokio::test]
+    async fn test_track_rip_is_replaced_by_rerip() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let track = DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "Intro", Some(1));
+
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&release).await.unwrap();
+        manager.database.insert_track(&track).await.unwrap();
+
+        let mut rip = DbTrackRip {
+            track_id: track.id.clone(),
+            copy_crc: 0xDEAD_BEEF,
+            read_errors: 2,
+            c2_errors: 1,
+            suspicious_sectors: 1,
+            accuraterip_status: AccurateRipStatus::Mismatch,
+            accuraterip_confidence: 7,
+            ripped_at: Utc::now(),
+        };
+        manager.save_track_rip(&rip).await.unwrap();
+        rip.suspicious_sectors = 0;
+        rip.accuraterip_status = AccurateRipStatus::Accurate;
+        manager.save_track_rip(&rip).await.unwrap();
+
+        let rips = manager
+            .get_track_rips_for_release(&release.id)
+            .await
+            .unwrap();
+        assert_eq!(rips.len(), 1);
+        assert_eq!(rips[0].copy_crc, 0xDEAD_BEEF);
+        assert_eq!(rips[0].suspicious_sectors, 0);
+        assert_eq!(rips[0].accuraterip_status, AccurateRipStatus::Accurate);
+    }
+
+    #[tokio::test]
+    async fn test_reset_release_import_keeps_release() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let track = DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "Intro", Some(1));
+
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&release).await.unwrap();
+        manager.database.insert_track(&track).await.unwrap();
+        let file = DbFile::new(&release.id, "01 Intro.flac", 1024, "flac");
+        manager.add_file(&file).await.unwrap();
+        manager.mark_track_complete(&track.id).await.unwrap();
+
+        manager.reset_release_import(&release.id).await.unwrap();
+
+        assert!(manager
+            .get_files_for_release(&release.id)
+            .await
+            .unwrap()
+            .is_empty());
+        let tracks = manager
+            .database
+            .get_tracks_for_release(&release.id)
+            .await
+            .unwrap();
+        assert_eq!(tracks.len(), 1);
+        assert_eq!(tracks[0].import_status, ImportStatus::Queued);
+    }
+
+    #[tokio::test]
+    async fn test_default_release_follows_preference() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let original = create_test_release(&album.id);
+        let remaster = create_test_release(&album.id);
+
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&original).await.unwrap();
+        manager.database.insert_release(&remaster).await.unwrap();
+
+        let first = manager.get_releases_for_album(&album.id).await.unwrap()[0]
+            .id
+            .clone();
+        let other = if first == original.id {
+            &remaster.id
+        } else {
+            &original.id
+        };
+        let default = manager.get_default_release(&album.id).await.unwrap();
+        assert_eq!(default.unwrap().id, first);
+
+        manager
+            .set_preferred_release(&album.id, Some(other.as_str()))
+            .await
+            .unwrap();
+        let default = manager.get_default_release(&album.id).await.unwrap();
+        assert_eq!(&default.unwrap().id, other);
+
+        // A deleted preferred release falls back to the first remaining one
+        manager.delete_release(other, false).await.unwrap();
+        let default = manager.get_default_release(&album.id).await.unwrap();
+        assert_eq!(default.unwrap().id, first);
+    }
+
+    #[tokio::test]
+    async fn test_library_tracks_list_one_edition_per_album() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let original = create_test_release(&album.id);
+        let remaster = create_test_release(&album.id);
+        let artist = DbArtist::from_discogs_artist("1", "Miles Davis");
+
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&original).await.unwrap();
+        manager.database.insert_release(&remaster).await.unwrap();
+        manager.database.insert_artist(&artist).await.unwrap();
+        manager
+            .database
+            .insert_album_artist(&DbAlbumArtist::new(&album.id, &artist.id, 0))
+            .await
+            .unwrap();
+        for release in [&original, &remaster] {
+            for (number, title) in [(1, "So What"), (2, "Freddie Freeloader")] {
+                let track = DbTrack::new_test(
+                    &release.id,
+                    &Uuid::new_v4().to_string(),
+                    title,
+                    Some(number),
+                );
+                manager.database.insert_track(&track).await.unwrap();
+            }
+        }
+
+        let first = manager.get_releases_for_album(&album.id).await.unwrap()[0]
+            .id
+            .clone();
+        let tracks = manager.get_library_tracks().await.unwrap();
+        assert_eq!(tracks.len(), 2);
+        assert!(tracks.iter().all(|t| t.track.release_id == first));
+        assert_eq!(tracks[0].artist_name.as_deref(), Some("Miles Davis"));
+        assert_eq!(tracks[0].album_title, "Test Album");
+
+        let other = if first == original.id {
+            &remaster.id
+        } else {
+            &original.id
+        };
+        manager
+            .set_preferred_release(&album.id, Some(other.as_str()))
+            .await
+            .unwrap();
+        let tracks = manager.get_library_tracks().await.unwrap();
+        assert!(tracks.iter().all(|t| &t.track.release_id == other));
+    }
+
+    #[tokio::test]
+    async fn test_rename_track() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let track = DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "Tarck", Some(1));
+
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&release).await.unwrap();
+        manager.database.insert_track(&track).await.unwrap();
+
+        manager.rename_track(&track.id, "Track").await.unwrap();
+        let renamed = manager.get_track(&track.id).await.unwrap().unwrap();
+        assert_eq!(renamed.title, "Track");
+    }
+
+    #[tokio::test]
+    async fn test_changelog_records_who_changed_what() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let manager = manager.with_device_id("laptop");
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let track = DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "Tarck", Some(1));
+
+        manager
+            .insert_album_with_release_and_tracks(&album, &release, std::slice::from_ref(&track))
+            .await
+            .unwrap();
+        manager.rename_track(&track.id, "Track").await.unwrap();
+        manager.delete_album(&album.id, false).await.unwrap();
+
+        let renames = manager.get_changelog(Some(&track.id), 10).await.unwrap();
+        assert_eq!(renames.len(), 1);
+        assert_eq!(renames[0].kind, ChangeKind::TrackRenamed);
+        assert_eq!(renames[0].before, Some(json!({ "title": "Tarck" })));
+        assert_eq!(renames[0].after, Some(json!({ "title": "Track" })));
+
+        let album_changes = manager.get_changelog(Some(&album.id), 10).await.unwrap();
+        let kinds: Vec<ChangeKind> = album_changes.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![ChangeKind::AlbumDeleted, ChangeKind::AlbumAdded]
+        );
+        assert!(album_changes.iter().all(|c| c.device_id == "laptop"));
+        assert_eq!(
+            album_changes[0].before,
+            Some(json!({ "title": "Test Album" }))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_undo_and_redo_track_rename() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let track = DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "Tarck", Some(1));
+
+        manager
+            .insert_album_with_release_and_tracks(&album, &release, std::slice::from_ref(&track))
+            .await
+            .unwrap();
+        assert!(!manager.can_undo());
+        manager.rename_track(&track.id, "Track").await.unwrap();
+
+        let undone = manager.undo().await.unwrap().unwrap();
+        assert_eq!(undone[0].kind, ChangeKind::TrackRenamed);
+        let restored = manager.get_track(&track.id).await.unwrap().unwrap();
+        assert_eq!(restored.title, "Tarck");
+        assert!(manager.can_redo());
+        assert!(manager.undo().await.unwrap().is_none());
+
+        manager.redo().await.unwrap().unwrap();
+        let redone = manager.get_track(&track.id).await.unwrap().unwrap();
+        assert_eq!(redone.title, "Track");
+        assert!(manager.can_undo());
+
+        // The rename, its undo and its redo are all in the changelog
+        let changes = manager.get_changelog(Some(&track.id), 10).await.unwrap();
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[1].after, Some(json!({ "title": "Tarck" })));
+    }
+
+    #[tokio::test]
+    async fn test_album_edits_save_and_undo_as_one_step() {
+        use crate::library::edit::{AlbumEdits, AlbumFields, ReleaseFields, TrackFields};
+
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let tracks = vec![
+            DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "Won", Some(1)),
+            DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "Two", Some(2)),
+        ];
+        manager
+            .insert_album_with_release_and_tracks(&album, &release, &tracks)
+            .await
+            .unwrap();
+        let duo = DbArtist::new("Simon & Garfunkel");
+        manager.insert_artist(&duo).await.unwrap();
+        manager
+            .insert_album_artist(&DbAlbumArtist::new(&album.id, &duo.id, 0))
+            .await
+            .unwrap();
+
+        let edits = AlbumEdits {
+            album_id: album.id.clone(),
+            album: Some(AlbumFields {
+                title: "Test Album".to_string(),
+                year: Some(1970),
+            }),
+            artists: Some(vec!["Simon".to_string(), "Garfunkel".to_string()]),
+            releases: vec![(
+                release.id.clone(),
+                ReleaseFields {
+                    label: Some("Columbia".to_string()),
+                    ..ReleaseFields::from(&release)
+                },
+            )],
+            tracks: tracks
+                .iter()
+                .map(|t| {
+                    let title = if t.title == "Won" { "One " } else { "Two" };
+                    (
+                        t.id.clone(),
+                        TrackFields {
+                            title: title.to_string(),
+                            ..TrackFields::from(t)
+                        },
+                    )
+                })
+                .collect(),
+        };
+        let changes = manager.save_album_edits(&edits).await.unwrap();
+        // The unchanged track isn't logged
+        let kinds: Vec<ChangeKind> = changes.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ChangeKind::AlbumEdited,
+                ChangeKind::AlbumArtistsEdited,
+                ChangeKind::ReleaseEdited,
+                ChangeKind::TrackEdited,
+            ]
+        );
+
+        let edited = manager.get_album_by_id(&album.id).await.unwrap().unwrap();
+        assert_eq!(edited.year, Some(1970));
+        let names: Vec<String> = manager
+            .get_artists_for_album(&album.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|a| a.name)
+            .collect();
+        assert_eq!(names, vec!["Simon", "Garfunkel"]);
+        let track = manager.get_track(&tracks[0].id).await.unwrap().unwrap();
+        assert_eq!(track.title, "One");
+        let history = manager
+            .get_changelog(Some(&tracks[0].id), 10)
+            .await
+            .unwrap();
+        assert_eq!(history[0].before.as_ref().unwrap()["title"], "Won");
+
+        manager.undo().await.unwrap().unwrap();
+        let restored = manager.get_album_by_id(&album.id).await.unwrap().unwrap();
+        assert_eq!(restored.year, Some(2024));
+        let artists = manager.get_artists_for_album(&album.id).await.unwrap();
+        assert_eq!(artists.len(), 1);
+        assert_eq!(artists[0].id, duo.id);
+        let release = manager.get_release(&release.id).await.unwrap().unwrap();
+        assert_eq!(release.label, None);
+        let track = manager.get_track(&tracks[0].id).await.unwrap().unwrap();
+        assert_eq!(track.title, "Won");
+        assert!(!manager.can_undo());
+    }
+
+    #[tokio::test]
+    async fn test_find_replace_applies_and_undoes_as_one_step() {
+        use crate::library::find_replace::{preview_find_replace, FindReplace};
+
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = DbAlbum {
+            title: "Album [Remastered 2011]".to_string(),
+            ..create_test_album()
+        };
+        let release = create_test_release(&album.id);
+        let tracks = vec![
+            DbTrack::new_test(
+                &release.id,
+                &Uuid::new_v4().to_string(),
+                "One [Remastered 2011]",
+                Some(1),
+            ),
+            DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "Two", Some(2)),
+        ];
+        manager
+            .insert_album_with_release_and_tracks(&album, &release, &tracks)
+            .await
+            .unwrap();
+
+        let spec = FindReplace {
+            find: "[remastered 2011]".to_string(),
+            replace: String::new(),
+            use_regex: false,
+            case_sensitive: false,
+            fields: vec![ReplaceField::AlbumTitle, ReplaceField::TrackTitle],
+        };
+        let rows = preview_find_replace(&manager, std::slice::from_ref(&album.id), &spec)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].after, "Album");
+        assert_eq!(rows[1].after, "One");
+
+        assert_eq!(manager.apply_replacements(&rows).await.unwrap().len(), 2);
+        let renamed = manager.get_album_by_id(&album.id).await.unwrap().unwrap();
+        assert_eq!(renamed.title, "Album");
+
+        let undone = manager.undo().await.unwrap().unwrap();
+        assert_eq!(undone.len(), 2);
+        let restored = manager.get_album_by_id(&album.id).await.unwrap().unwrap();
+        assert_eq!(restored.title, "Album [Remastered 2011]");
+        let track = manager.get_track(&tracks[0].id).await.unwrap().unwrap();
+        assert_eq!(track.title, "One [Remastered 2011]");
+        assert!(!manager.can_undo());
+    }
+
+    #[tokio::test]
+    async fn test_compact_changelog_keeps_latest_change_per_entity() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let track = DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "One", Some(1));
+
+        manager
+            .insert_album_with_release_and_tracks(&album, &release, std::slice::from_ref(&track))
+            .await
+            .unwrap();
+        manager.rename_track(&track.id, "Two").await.unwrap();
+        manager.rename_track(&track.id, "Three").await.unwrap();
+
+        let removed = manager
+            .compact_changelog(chrono::Duration::zero())
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+
+        let remaining = manager.get_changelog(None, 10).await.unwrap();
+        assert_eq!(remaining.len(), 2);
+        let rename = remaining.iter().find(|c| c.entity_id == track.id).unwrap();
+        assert_eq!(rename.after, Some(json!({ "title": "Three" })));
+    }
+
+    #[tokio::test]
+    async fn test_release_archive_status_only_for_archival_profiles() {
+        let (manager, temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let profile =
+            DbStorageProfile::new_local("Local", temp_dir.path().to_str().unwrap(), false);
+
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&release).await.unwrap();
+        manager.insert_storage_profile(&profile).await.unwrap();
+        manager
+            .database
+            .insert_release_storage(&crate::db::DbReleaseStorage::new(&release.id, &profile.id))
+            .await
+            .unwrap();
+
+        let status = manager
+            .get_release_archive_status(&release.id)
+            .await
+            .unwrap();
+        assert_eq!(status, None);
+
+        let archival = profile.with_cloud_archival(true);
+        manager.update_storage_profile(&archival).await.unwrap();
+        let status = manager
+            .get_release_archive_status(&release.id)
+            .await
+            .unwrap();
+        assert_eq!(status, Some(ArchiveStatus::Available));
+    }
+
+    #[tokio::test]
+    async fn test_migrate_release_out_of_reference_profile() {
+        let (manager, temp_dir) = setup_test_manager().await;
+        let nas = temp_dir.path().join("nas");
+        let managed = temp_dir.path().join("managed");
+        let original = nas.join("Album").join("01.flac");
+        std::fs::create_dir_all(original.parent().unwrap()).unwrap();
+        std::fs::write(&original, b"audio").unwrap();
+
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let reference = DbStorageProfile::new_reference("NAS", nas.to_str().unwrap());
+        let target = DbStorageProfile::new_local("Managed", managed.to_str().unwrap(), true);
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&release).await.unwrap();
+        manager.insert_storage_profile(&reference).await.unwrap();
+        manager.insert_storage_profile(&target).await.unwrap();
+        manager
+            .database
+            .insert_release_storage(&crate::db::DbReleaseStorage::new(
+                &release.id,
+                &reference.id,
+            ))
+            .await
+            .unwrap();
+        let file = DbFile::new(&release.id, "01.flac", 5, "flac")
+            .with_source_path(original.to_str().unwrap());
+        manager.add_file(&file).await.unwrap();
+
+        manager
+            .migrate_release(&release.id, &target.id)
+            .await
+            .unwrap();
+
+        let migrated = manager.get_file_by_id(&file.id).await.unwrap().unwrap();
+        let stored = migrated.source_path.unwrap();
+        assert!(Path::new(&stored).starts_with(&managed));
+        assert!(migrated.encryption_nonce.is_some());
+        assert_eq!(std::fs::read(&original).unwrap(), b"audio");
+        let profile = manager
+            .get_storage_profile_for_release(&release.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(profile.id, target.id);
+
+        // Managed now, so there's nothing left to migrate
+        manager
+            .migrate_release(&release.id, &target.id)
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_delete_release_keeps_referenced_files() {
+        let (manager, temp_dir) = setup_test_manager().await;
+        let original = temp_dir.path().join("Album").join("01.flac");
+        std::fs::create_dir_all(original.parent().unwrap()).unwrap();
+        std::fs::write(&original, b"audio").unwrap();
+
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let reference = DbStorageProfile::new_reference("NAS", temp_dir.path().to_str().unwrap());
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&release).await.unwrap();
+        manager.insert_storage_profile(&reference).await.unwrap();
+        manager
+            .database
+            .insert_release_storage(&crate::db::DbReleaseStorage::new(
+                &release.id,
+                &reference.id,
+            ))
+            .await
+            .unwrap();
+        let file = DbFile::new(&release.id, "01.flac", 5, "flac")
+            .with_source_path(original.to_str().unwrap());
+        manager.add_file(&file).await.unwrap();
+
+        manager.delete_release(&release.id, false).await.unwrap();
+        assert!(original.exists());
+    }
+
+    #[tokio::test]
+    async fn test_album_quality_is_lowest_track_quality() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        let release = create_test_release(&album.id);
+        let hi_res = DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "One", Some(1));
+        let cd = DbTrack::new_test(&release.id, &Uuid::new_v4().to_string(), "Two", Some(2));
+
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&release).await.unwrap();
+        manager.database.insert_track(&hi_res).await.unwrap();
+        manager.database.insert_track(&cd).await.unwrap();
+        for (track, sample_rate, bits) in [(&hi_res, 96000, 24), (&cd, 44100, 16)] {
+            let format = DbAudioFormat::new(
+                &track.id,
+                "flac",
+                None,
+                false,
+                sample_rate,
+                bits,
+                "[]".into(),
+                0,
+            );
+            manager.add_audio_format(&format).await.unwrap();
+        }
+
+        let tracks = manager
+            .get_track_qualities_for_release(&release.id)
+            .await
+            .unwrap();
+        assert_eq!(tracks[&hi_res.id], AudioQuality::HiRes);
+        assert_eq!(tracks[&cd.id], AudioQuality::Lossless);
+
+        let albums = manager.get_album_qualities().await.unwrap();
+        assert_eq!(albums[&album.id], AudioQuality::Lossless);
+        assert_eq!(
+            AudioQuality::classify("MP3", 44100, 16),
+            AudioQuality::Lossy
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_library_follows_edits_and_deletes() {
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let mut album = create_test_album();
+        album.title = "Kind of Blue".to_string();
+        let mut release = create_test_release(&album.id);
+        release.label = Some("Columbia".to_string());
+        release.catalog_number = Some("CL 1355".to_string());
+        let track = DbTrack::new_test(&release.id, "track-1", "So What", Some(1));
+        let artist = DbArtist::new("Miles Davis");
+        manager.database.insert_album(&album).await.unwrap();
+        manager.database.insert_release(&release).await.unwrap();
+        manager.database.insert_track(&track).await.unwrap();
+        manager.database.insert_artist(&artist).await.unwrap();
+        manager
+            .database
+            .insert_album_artist(&DbAlbumArtist::new(&album.id, &artist.id, 0))
+            .await
+            .unwrap();
+
+        let results = manager.search_library("mil", 10).await.unwrap();
+        assert_eq!(results.artists.len(), 1);
+        assert_eq!(results.artists[0].id, artist.id);
+        assert_eq!(results.albums.len(), 1);
+        // The track takes its artist from the album
+        assert_eq!(results.tracks.len(), 1);
+        assert_eq!(results.tracks[0].artist, "Miles Davis");
+
+        let results = manager.search_library("so wh", 10).await.unwrap();
+        assert!(results.albums.is_empty());
+        assert_eq!(results.tracks[0].id, track.id);
+        assert_eq!(
+            results.tracks[0].album_id.as_deref(),
+            Some(album.id.as_str())
+        );
+        assert_eq!(
+            results.tracks[0].release_id.as_deref(),
+            Some(release.id.as_str())
+        );
+
+        let results = manager.search_library("cl 1355", 10).await.unwrap();
+        assert_eq!(results.albums[0].id, album.id);
+        assert!(results.tracks.is_empty());
+
+        manager
+            .database
+            .update_album_title(&album.id, "Sketches of Spain")
+            .await
+            .unwrap();
+        assert!(manager
+            .search_library("kind", 10)
+            .await
+            .unwrap()
+            .albums
+            .is_empty());
+        assert_eq!(
+            manager.search_library("sketch", 10).await.unwrap().albums[0].title,
+            "Sketches of Spain"
+        );
+
+        manager.delete_album(&album.id, false).await.unwrap();
+        let results = manager.search_library("sketch", 10).await.unwrap();
+        assert!(results.albums.is_empty());
+        assert!(results.tracks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_client_share_limits_albums_to_its_token() {
+        use crate::library::shares::{resolve_share_scope, ShareCredentials, ShareScope};
+
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let shared = create_test_album();
+        let private = create_test_album();
+        manager.database.insert_album(&shared).await.unwrap();
+        manager.database.insert_album(&private).await.unwrap();
+
+        // No shares: the whole library, token or not
+        assert_eq!(
+            resolve_share_scope(&manager, &ShareCredentials::None)
+                .await
+                .unwrap(),
+            Some(ShareScope::Library)
+        );
+
+        let share = manager.create_client_share("Phone").await.unwrap();
+        manager
+            .set_album_shared(&share.id, &shared.id, true)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            resolve_share_scope(&manager, &ShareCredentials::None)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            resolve_share_scope(&manager, &ShareCredentials::Password("wrong".to_string()))
+                .await
+                .unwrap(),
+            None
+        );
+        let scope = resolve_share_scope(&manager, &ShareCredentials::Password(share.token.clone()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(scope.allows(&shared.id));
+        assert!(!scope.allows(&private.id));
+
+        manager.delete_client_share(&share.id).await.unwrap();
+        assert_eq!(
+            resolve_share_scope(&manager, &ShareCredentials::None)
+                .await
+                .unwrap(),
+            Some(ShareScope::Library)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_share_accepts_token_authentication() {
+        use crate::library::shares::{
+            resolve_share_scope, salted_token, ShareCredentials, ShareScope,
+        };
+
+        let (manager, _temp_dir) = setup_test_manager().await;
+        let album = create_test_album();
+        manager.database.insert_album(&album).await.unwrap();
+        let share = manager.create_client_share("Phone").await.unwrap();
+        manager
+            .set_album_shared(&share.id, &album.id, true)
+            .await
+            .unwrap();
+
+        let signed_in = |token: String, salt: &str| ShareCredentials::SaltedToken {
+            token,
+            salt: salt.to_string(),
+        };
+        let scope = resolve_share_scope(
+            &manager,
+            &signed_in(salted_token(&share.token, "c19b2d"), "c19b2d"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            scope,
+            Some(ShareScope::Albums([album.id.clone()].into_iter().collect()))
+        );
+
+        // Clients may send the hash in upper case
+        let upper = salted_token(&share.token, "c19b2d").to_uppercase();
+        assert!(resolve_share_scope(&manager, &signed_in(upper, "c19b2d"))
+            .await
+            .unwrap()
+            .is_some());
+
+        // Hashed with a different salt than the one sent
+        let wrong_salt = signed_in(salted_token(&share.token, "other"), "c19b2d");
+        assert_eq!(
+            resolve_share_scope(&manager, &wrong_salt).await.unwrap(),
+            None
+        );
+    }
 }
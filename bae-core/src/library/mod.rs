@@ -1,5 +1,18 @@
+pub mod compare;
 pub mod context;
+pub mod credits;
+pub mod dynamic_range;
+pub mod edit;
 pub mod export;
+pub mod find_replace;
+pub mod gift;
+pub mod lyrics;
 pub mod manager;
+pub mod offline;
+pub mod search;
+pub mod shares;
+pub mod snapshot;
+pub mod undo;
+pub mod verify;
 pub use context::*;
 pub use manager::*;
@@ -0,0 +1,152 @@
+//! Albums kept offline: their cloud-stored files pinned in the local cache.
+//!
+//! Pinning an album records it in the database, pins the cache entries of
+//! every file in its cloud releases and downloads the ones that aren't cached
+//! yet. Playback prefers a pinned copy over the cloud, so a pinned album plays
+//! without a connection. Cache pins only live in memory, so on startup each
+//! album in [`offline_album_ids`] goes through [`keep_album_offline`] again,
+//! which pins it and resumes a download that didn't finish.
+
+use crate::cache::{CacheError, CacheManager};
+use crate::cloud_storage::CloudStorage;
+use crate::db::{DbFile, DbStorageProfile, StorageLocation};
+use crate::library::{LibraryError, LibraryManager};
+use crate::storage::create_storage_reader;
+use std::sync::Arc;
+use tracing::info;
+
+/// Cache key of a stored file's bytes, as downloaded from storage
+pub fn file_cache_key(file_id: &str) -> String {
+    format!("file:{}", file_id)
+}
+
+/// How far an album's offline download has got
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OfflineProgress {
+    pub files_done: usize,
+    pub files_total: usize,
+}
+
+/// The album's files in cloud storage: the ones that need a cached copy to
+/// play offline. Files on local profiles or outside bae's storage are
+/// already on disk.
+async fn cloud_files(
+    library_manager: &LibraryManager,
+    album_id: &str,
+) -> Result<Vec<(DbFile, DbStorageProfile)>, LibraryError> {
+    let mut files = Vec::new();
+    for release in library_manager.get_releases_for_album(album_id).await? {
+        let Some(profile) = library_manager
+            .get_storage_profile_for_release(&release.id)
+            .await?
+        else {
+            continue;
+        };
+        if profile.location != StorageLocation::Cloud {
+            continue;
+        }
+        for file in library_manager.get_files_for_release(&release.id).await? {
+            files.push((file, profile.clone()));
+        }
+    }
+    Ok(files)
+}
+
+/// Albums kept offline, oldest first
+pub async fn offline_album_ids(
+    library_manager: &LibraryManager,
+) -> Result<Vec<String>, LibraryError> {
+    Ok(library_manager.database().get_offline_album_ids().await?)
+}
+
+/// Whether the album has anything in cloud storage to keep offline
+pub async fn can_keep_offline(
+    library_manager: &LibraryManager,
+    album_id: &str,
+) -> Result<bool, LibraryError> {
+    Ok(!cloud_files(library_manager, album_id).await?.is_empty())
+}
+
+/// Keep an album offline: pin its files and download the missing ones,
+/// reporting progress after each file. Also resumes an album that's already
+/// kept offline.
+///
+/// The album stays marked offline if a download fails, so the next call
+/// (or the next startup) picks up where this one stopped.
+pub async fn keep_album_offline(
+    library_manager: &LibraryManager,
+    cache: &CacheManager,
+    album_id: &str,
+    on_progress: impl Fn(OfflineProgress),
+) -> Result<(), LibraryError> {
+    library_manager
+        .database()
+        .insert_offline_album(album_id)
+        .await?;
+
+    let files = cloud_files(library_manager, album_id).await?;
+    let keys: Vec<String> = files
+        .iter()
+        .map(|(file, _)| file_cache_key(&file.id))
+        .collect();
+    cache.pin_all(&keys).await;
+
+    let mut progress = OfflineProgress {
+        files_done: 0,
+        files_total: files.len(),
+    };
+    on_progress(progress);
+
+    let mut storage: Option<(String, Arc<dyn CloudStorage>)> = None;
+    for ((file, profile), key) in files.iter().zip(&keys) {
+        if !cache.contains(key).await {
+            let Some(ref source_path) = file.source_path else {
+                return Err(LibraryError::Import(format!(
+                    "File {} has no source path",
+                    file.original_filename
+                )));
+            };
+            // Releases of one album share a profile in practice; reconnect
+            // only when it changes
+            let reader = match &storage {
+                Some((profile_id, reader)) if *profile_id == profile.id => reader.clone(),
+                _ => create_storage_reader(profile).await?,
+            };
+            storage = Some((profile.id.clone(), reader.clone()));
+            let data = reader.download(source_path).await?;
+            cache
+                .put(key, &data)
+                .await
+                .map_err(|CacheError::Io(e)| LibraryError::Io(e))?;
+        }
+        progress.files_done += 1;
+        on_progress(progress);
+    }
+
+    info!(
+        "Album {} is available offline ({} files)",
+        album_id,
+        files.len()
+    );
+    Ok(())
+}
+
+/// Stop keeping an album offline. Its cached files become ordinary cache
+/// entries and can be evicted again.
+pub async fn remove_album_offline(
+    library_manager: &LibraryManager,
+    cache: &CacheManager,
+    album_id: &str,
+) -> Result<(), LibraryError> {
+    library_manager
+        .database()
+        .delete_offline_album(album_id)
+        .await?;
+    let keys: Vec<String> = cloud_files(library_manager, album_id)
+        .await?
+        .iter()
+        .map(|(file, _)| file_cache_key(&file.id))
+        .collect();
+    cache.unpin_all(&keys).await;
+    Ok(())
+}
@@ -0,0 +1,32 @@
+//! Library search over the full-text index.
+//!
+//! Every word typed must match the start of a word in a title, artist,
+//! album title, label or catalog number, so results narrow while typing.
+
+/// Turn typed text into an FTS5 query: each word quoted (so FTS5 syntax
+/// is taken literally) and prefix-matched. None if there's nothing to find.
+pub fn fts_query(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fts_query_quotes_and_prefixes_words() {
+        assert_eq!(fts_query("kind of"), Some("\"kind\"* \"of\"*".to_string()));
+        assert_eq!(
+            fts_query("  AND \"blue\" "),
+            Some("\"AND\"* \"\"\"blue\"\"\"*".to_string())
+        );
+        assert_eq!(fts_query("CAT-123"), Some("\"CAT-123\"*".to_string()));
+        assert_eq!(fts_query(" - "), None);
+        assert_eq!(fts_query(""), None);
+    }
+}
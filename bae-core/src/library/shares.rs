@@ -0,0 +1,79 @@
+//! Client shares: the albums a Subsonic client may see.
+//!
+//! With no shares the server exposes the whole library. Once a share exists,
+//! a client must sign in with a share's token, as its password or through
+//! token authentication, and sees only that share's albums. The check covers streaming as well as browsing, so a
+//! client can't fetch a track of an album it wasn't given.
+
+use crate::library::{LibraryError, LibraryManager};
+use md5::{Digest, Md5};
+use std::collections::HashSet;
+
+/// How a client signed in
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShareCredentials {
+    None,
+    /// A share's token sent as the password
+    Password(String),
+    /// Token authentication: MD5 of a share's token followed by `salt`, in hex
+    SaltedToken {
+        token: String,
+        salt: String,
+    },
+}
+
+/// Hex MD5 of `secret` followed by `salt`, as token authentication sends it
+pub fn salted_token(secret: &str, salt: &str) -> String {
+    let mut hasher = Md5::new();
+    hasher.update(secret.as_bytes());
+    hasher.update(salt.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// What a request may see
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShareScope {
+    /// No shares are set up, so everything
+    Library,
+    /// The album IDs of the share whose token was sent
+    Albums(HashSet<String>),
+}
+
+impl ShareScope {
+    pub fn allows(&self, album_id: &str) -> bool {
+        match self {
+            ShareScope::Library => true,
+            ShareScope::Albums(ids) => ids.contains(album_id),
+        }
+    }
+}
+
+/// The scope for a request signed in with `credentials`, or None if shares
+/// are set up and the credentials match none of them.
+///
+/// With no shares, every request sees the whole library, credentials or not.
+/// That includes a library whose last share was deleted: deleting it opens
+/// the library to every client on the network again.
+pub async fn resolve_share_scope(
+    library_manager: &LibraryManager,
+    credentials: &ShareCredentials,
+) -> Result<Option<ShareScope>, LibraryError> {
+    let database = library_manager.database();
+    let shares = database.get_client_shares().await?;
+    if shares.is_empty() {
+        return Ok(Some(ShareScope::Library));
+    }
+    let share = match credentials {
+        ShareCredentials::None => None,
+        ShareCredentials::Password(token) => database.get_client_share_by_token(token).await?,
+        // The server can't invert the hash, so try each share's token
+        ShareCredentials::SaltedToken { token, salt } => shares
+            .into_iter()
+            .find(|share| salted_token(&share.token, salt).eq_ignore_ascii_case(token)),
+    };
+    let Some(share) = share else {
+        return Ok(None);
+    };
+    let album_ids = database.get_shared_album_ids(&share.id).await?;
+    Ok(Some(ShareScope::Albums(album_ids.into_iter().collect())))
+}
@@ -0,0 +1,401 @@
+//! Library snapshots: encrypted copies of the library database kept in a
+//! cloud bucket.
+//!
+//! Files in the bucket are encrypted and the server never sees the key, so
+//! it can't rebuild a lost library for us. A snapshot is a consistent copy of
+//! the database, encrypted with the library key and uploaded like any other
+//! file; the newest few are kept and older ones deleted.
+//!
+//! The database holds the storage profiles, so it can't be the only record of
+//! where its snapshots are. `snapshots.json`, next to the database, keeps the
+//! profile snapshots go to and the snapshots taken. When the database is
+//! damaged at launch, that's enough to download one and put it back.
+//!
+//! A restore is staged next to the database and only swapped in at the next
+//! launch, before anything opens it (see [`apply_pending_restore`]).
+
+use crate::cloud_storage::CloudStorage;
+use crate::db::{Database, DbStorageProfile};
+use crate::encryption::EncryptionService;
+use crate::library::LibraryError;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// The library database, inside the library directory
+pub const DATABASE_FILENAME: &str = "library.db";
+
+/// Snapshot index, next to the database
+pub const SNAPSHOT_INDEX_FILENAME: &str = "snapshots.json";
+
+/// A downloaded snapshot waiting to replace the database at the next launch
+pub const PENDING_RESTORE_FILENAME: &str = "library.restore.db";
+
+/// Snapshots kept unless configured otherwise
+pub const DEFAULT_SNAPSHOTS_KEPT: u32 = 7;
+
+/// Scratch copy of the database while a snapshot is taken
+const SNAPSHOT_SCRATCH_FILENAME: &str = "library.snapshot.db";
+
+/// One snapshot in the bucket
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LibrarySnapshot {
+    pub location: String,
+    pub created_at: DateTime<Utc>,
+    /// Size of the database copy, before encryption
+    pub size_bytes: u64,
+}
+
+/// What `snapshots.json` holds
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotIndex {
+    /// Profile the snapshots are in, with its credentials
+    pub profile: Option<DbStorageProfile>,
+    /// Newest first
+    pub snapshots: Vec<LibrarySnapshot>,
+}
+
+impl SnapshotIndex {
+    /// The index for a library. Missing or unreadable means no snapshots.
+    pub fn load(library_path: &Path) -> Self {
+        let path = library_path.join(SNAPSHOT_INDEX_FILENAME);
+        match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                warn!(
+                    "Ignoring unreadable snapshot index {}: {}",
+                    path.display(),
+                    e
+                );
+                SnapshotIndex::default()
+            }),
+            Err(_) => SnapshotIndex::default(),
+        }
+    }
+
+    pub fn save(&self, library_path: &Path) -> std::io::Result<()> {
+        let path = library_path.join(SNAPSHOT_INDEX_FILENAME);
+        let tmp_path = path.with_extension("json.tmp");
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(&tmp_path, json)?;
+        std::fs::rename(&tmp_path, &path)
+    }
+
+    /// When the newest snapshot to `profile_id` was taken. Snapshots to
+    /// another profile don't count.
+    pub fn last_snapshot_at(&self, profile_id: &str) -> Option<DateTime<Utc>> {
+        self.profile
+            .as_ref()
+            .filter(|profile| profile.id == profile_id)
+            .and_then(|_| self.snapshots.first())
+            .map(|s| s.created_at)
+    }
+
+    /// Add a snapshot taken to `profile`, keeping the newest `keep`. Returns
+    /// the snapshots that fell off, for the caller to delete. Moving to
+    /// another profile starts over; snapshots in the old bucket are left
+    /// there.
+    pub fn record(
+        &mut self,
+        profile: &DbStorageProfile,
+        snapshot: LibrarySnapshot,
+        keep: u32,
+    ) -> Vec<LibrarySnapshot> {
+        if self.profile.as_ref().map(|p| p.id.as_str()) != Some(profile.id.as_str()) {
+            self.snapshots.clear();
+        }
+        self.profile = Some(profile.clone());
+        self.snapshots.insert(0, snapshot);
+        self.snapshots
+            .split_off((keep.max(1) as usize).min(self.snapshots.len()))
+    }
+}
+
+/// Whether a scheduled snapshot is due: none taken yet, or the last one is
+/// at least `interval_hours` old
+pub fn snapshot_due(last: Option<DateTime<Utc>>, interval_hours: u32, now: DateTime<Utc>) -> bool {
+    match last {
+        None => true,
+        Some(last) => now - last >= Duration::hours(interval_hours as i64),
+    }
+}
+
+/// Snapshot the database to `storage`, under `profile`, and record it in the
+/// library's index. Snapshots past the newest `keep` are deleted.
+pub async fn snapshot_library(
+    database: &Database,
+    encryption_service: &EncryptionService,
+    storage: &dyn CloudStorage,
+    profile: &DbStorageProfile,
+    library_path: &Path,
+    library_id: &str,
+    keep: u32,
+) -> Result<LibrarySnapshot, LibraryError> {
+    let scratch_path = library_path.join(SNAPSHOT_SCRATCH_FILENAME);
+    // Left over from an interrupted snapshot; VACUUM INTO won't overwrite it
+    let _ = std::fs::remove_file(&scratch_path);
+    database.copy_to(&scratch_path).await?;
+    let data = std::fs::read(&scratch_path);
+    let _ = std::fs::remove_file(&scratch_path);
+    let data = data?;
+
+    let created_at = Utc::now();
+    let key = format!(
+        "{}-snapshot-{}",
+        library_id,
+        created_at.format("%Y%m%dT%H%M%SZ")
+    );
+    let location = storage
+        .upload(&key, &encryption_service.encrypt(&data))
+        .await?;
+    let snapshot = LibrarySnapshot {
+        location,
+        created_at,
+        size_bytes: data.len() as u64,
+    };
+    info!(
+        "Snapshot of the library ({} bytes) uploaded to {}",
+        snapshot.size_bytes, snapshot.location
+    );
+
+    let mut index = SnapshotIndex::load(library_path);
+    let pruned = index.record(profile, snapshot.clone(), keep);
+    index.save(library_path)?;
+    for old in pruned {
+        if let Err(e) = storage.delete(&old.location).await {
+            warn!("Failed to delete old snapshot {}: {}", old.location, e);
+        }
+    }
+    Ok(snapshot)
+}
+
+/// Download a snapshot and stage it to replace the database at the next
+/// launch. The snapshot is checked before it's staged, so a bad download
+/// never replaces anything.
+pub async fn stage_restore(
+    snapshot: &LibrarySnapshot,
+    storage: &dyn CloudStorage,
+    encryption_service: &EncryptionService,
+    library_path: &Path,
+) -> Result<(), LibraryError> {
+    let data = encryption_service.decrypt(&storage.download(&snapshot.location).await?)?;
+    let pending_path = library_path.join(PENDING_RESTORE_FILENAME);
+    let part_path = pending_path.with_extension("db.part");
+    std::fs::write(&part_path, data)?;
+    if let Err(e) = check_database(&part_path).await {
+        let _ = std::fs::remove_file(&part_path);
+        return Err(LibraryError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!(
+                "Snapshot from {} is damaged: {}",
+                snapshot.created_at.format("%Y-%m-%d %H:%M"),
+                e
+            ),
+        )));
+    }
+    std::fs::rename(&part_path, &pending_path)?;
+    info!("Staged snapshot {} for restore", snapshot.location);
+    Ok(())
+}
+
+/// Swap a staged restore in for the database. Call before the database is
+/// opened. The replaced database is kept beside it. Returns whether a
+/// restore was applied.
+pub fn apply_pending_restore(library_path: &Path) -> std::io::Result<bool> {
+    let pending_path = library_path.join(PENDING_RESTORE_FILENAME);
+    if !pending_path.exists() {
+        return Ok(false);
+    }
+    let database_path = library_path.join(DATABASE_FILENAME);
+    if database_path.exists() {
+        let kept = set_aside_database(&database_path, "replaced")?;
+        info!("Replaced database kept at {}", kept.display());
+    }
+    std::fs::rename(&pending_path, &database_path)?;
+    info!("Restored the library database from a snapshot");
+    Ok(true)
+}
+
+/// Check the database file at `path`. A missing file is fine: it's created
+/// on open.
+pub async fn check_database(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Ok(());
+    }
+    match Database::check_file(&path.to_string_lossy()).await {
+        Ok(problems) if problems.is_empty() => Ok(()),
+        Ok(problems) => Err(problems.join("; ")),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Move a database and its journal files out of the way, renamed with
+/// `label` and the time. Returns where the database went.
+pub fn set_aside_database(database_path: &Path, label: &str) -> std::io::Result<PathBuf> {
+    let suffix = format!("{}-{}", label, Utc::now().format("%Y%m%dT%H%M%S"));
+    let kept_path = PathBuf::from(format!("{}.{}", database_path.display(), suffix));
+    std::fs::rename(database_path, &kept_path)?;
+    for journal in ["-wal", "-shm"] {
+        let journal_path = PathBuf::from(format!("{}{}", database_path.display(), journal));
+        if journal_path.exists() {
+            std::fs::rename(
+                &journal_path,
+                PathBuf::from(format!("{}.{}", journal_path.display(), suffix)),
+            )?;
+        }
+    }
+    Ok(kept_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cloud_storage::CloudStorageError;
+    use crate::db::DbAlbum;
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    #[derive(Default)]
+    struct MemoryStorage {
+        files: Mutex<HashMap<String, Vec<u8>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl CloudStorage for MemoryStorage {
+        async fn upload(&self, key: &str, data: &[u8]) -> Result<String, CloudStorageError> {
+            let location = format!("s3://test-bucket/files/{}", key);
+            self.files
+                .lock()
+                .unwrap()
+                .insert(location.clone(), data.to_vec());
+            Ok(location)
+        }
+
+        async fn download(&self, storage_location: &str) -> Result<Vec<u8>, CloudStorageError> {
+            self.files
+                .lock()
+                .unwrap()
+                .get(storage_location)
+                .cloned()
+                .ok_or_else(|| CloudStorageError::Download(storage_location.to_string()))
+        }
+
+        async fn download_range(
+            &self,
+            storage_location: &str,
+            start: u64,
+            end: u64,
+        ) -> Result<Vec<u8>, CloudStorageError> {
+            let data = self.download(storage_location).await?;
+            Ok(data[start as usize..end as usize].to_vec())
+        }
+
+        async fn delete(&self, storage_location: &str) -> Result<(), CloudStorageError> {
+            self.files.lock().unwrap().remove(storage_location);
+            Ok(())
+        }
+    }
+
+    fn profile() -> DbStorageProfile {
+        DbStorageProfile::new_cloud("Cloud", "bucket", "us-east-1", None, "key", "secret", true)
+    }
+
+    fn snapshot_at(day: u32) -> LibrarySnapshot {
+        LibrarySnapshot {
+            location: format!("s3://bucket/files/snapshot-{}", day),
+            created_at: Utc.with_ymd_and_hms(2026, 3, day, 4, 0, 0).unwrap(),
+            size_bytes: 1024,
+        }
+    }
+
+    #[test]
+    fn test_record_keeps_newest() {
+        let profile = profile();
+        let mut index = SnapshotIndex::default();
+        for day in 1..=3 {
+            assert!(index.record(&profile, snapshot_at(day), 3).is_empty());
+        }
+
+        let pruned = index.record(&profile, snapshot_at(4), 3);
+        assert_eq!(pruned, vec![snapshot_at(1)]);
+        assert_eq!(index.snapshots.len(), 3);
+        assert_eq!(
+            index.last_snapshot_at(&profile.id),
+            Some(snapshot_at(4).created_at)
+        );
+    }
+
+    #[test]
+    fn test_record_to_another_profile_starts_over() {
+        let mut index = SnapshotIndex::default();
+        index.record(&profile(), snapshot_at(1), 3);
+
+        let other = profile();
+        assert_eq!(index.last_snapshot_at(&other.id), None);
+        let pruned = index.record(&other, snapshot_at(2), 3);
+        assert!(pruned.is_empty());
+        assert_eq!(index.snapshots, vec![snapshot_at(2)]);
+        assert_eq!(index.profile, Some(other));
+    }
+
+    #[test]
+    fn test_snapshot_due_after_interval() {
+        let last = Utc.with_ymd_and_hms(2026, 3, 1, 4, 0, 0).unwrap();
+        assert!(snapshot_due(None, 24, last));
+        assert!(!snapshot_due(Some(last), 24, last + Duration::hours(23)));
+        assert!(snapshot_due(Some(last), 24, last + Duration::hours(24)));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_restores_after_corruption() {
+        let temp_dir = TempDir::new().unwrap();
+        let library_path = temp_dir.path();
+        let database_path = library_path.join(DATABASE_FILENAME);
+        let database = Database::new(database_path.to_str().unwrap())
+            .await
+            .unwrap();
+        let album = DbAlbum::new_test("Kept Album");
+        database.insert_album(&album).await.unwrap();
+
+        let storage = MemoryStorage::default();
+        let encryption_service = EncryptionService::new_with_key(&[7u8; 32]);
+        let profile = profile();
+        let snapshot = snapshot_library(
+            &database,
+            &encryption_service,
+            &storage,
+            &profile,
+            library_path,
+            "library",
+            2,
+        )
+        .await
+        .unwrap();
+        drop(database);
+
+        // The bucket only ever sees ciphertext
+        let stored = storage.download(&snapshot.location).await.unwrap();
+        assert!(!stored.starts_with(b"SQLite format 3"));
+        assert_eq!(
+            SnapshotIndex::load(library_path).snapshots,
+            vec![snapshot.clone()]
+        );
+
+        std::fs::write(&database_path, b"not a database").unwrap();
+        assert!(check_database(&database_path).await.is_err());
+        set_aside_database(&database_path, "damaged").unwrap();
+
+        stage_restore(&snapshot, &storage, &encryption_service, library_path)
+            .await
+            .unwrap();
+        assert!(apply_pending_restore(library_path).unwrap());
+        assert!(check_database(&database_path).await.is_ok());
+
+        let database = Database::new(database_path.to_str().unwrap())
+            .await
+            .unwrap();
+        assert!(database.get_album_by_id(&album.id).await.unwrap().is_some());
+    }
+}
@@ -0,0 +1,151 @@
+//! Undo and redo for metadata edits made this session
+//!
+//! Edits are recorded in the changelog with their before and after values;
+//! undoing one writes the before values back and logs that as a change too.
+//! A batch edit is undone and redone as one step.
+
+use crate::db::DbChange;
+
+/// Edits kept for undo; older ones drop off
+const MAX_UNDO: usize = 50;
+
+/// Undoable edits made this session, most recent last. Each step holds
+/// the changes it made, in the order they were applied.
+#[derive(Debug, Default)]
+pub struct UndoHistory {
+    undo: Vec<Vec<DbChange>>,
+    redo: Vec<Vec<DbChange>>,
+}
+
+impl UndoHistory {
+    /// A new edit becomes the next to undo, and edits undone before it can
+    /// no longer be redone. Changes that can't be undone are ignored.
+    pub fn push(&mut self, change: DbChange) {
+        if !change.kind.is_undoable() {
+            return;
+        }
+        self.push_batch(vec![change]);
+    }
+
+    /// Several changes made together by one action, undone as one step
+    pub fn push_batch(&mut self, changes: Vec<DbChange>) {
+        if changes.is_empty() {
+            return;
+        }
+        self.redo.clear();
+        self.undo.push(changes);
+        if self.undo.len() > MAX_UNDO {
+            self.undo.remove(0);
+        }
+    }
+
+    pub fn pop_undo(&mut self) -> Option<Vec<DbChange>> {
+        self.undo.pop()
+    }
+
+    pub fn pop_redo(&mut self) -> Option<Vec<DbChange>> {
+        self.redo.pop()
+    }
+
+    /// An edit that was just undone, so it can be redone
+    pub fn push_undone(&mut self, changes: Vec<DbChange>) {
+        self.redo.push(changes);
+    }
+
+    /// An edit that was just redone, so it can be undone again
+    pub fn push_redone(&mut self, changes: Vec<DbChange>) {
+        self.undo.push(changes);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::ChangeKind;
+    use serde_json::json;
+
+    fn rename(track_id: &str, from: &str, to: &str) -> DbChange {
+        DbChange::new(
+            "device",
+            ChangeKind::TrackRenamed,
+            track_id,
+            Some(json!({ "title": from })),
+            Some(json!({ "title": to })),
+        )
+    }
+
+    #[test]
+    fn test_new_edit_clears_redo() {
+        let mut history = UndoHistory::default();
+        history.push(rename("t1", "a", "b"));
+        let undone = history.pop_undo().unwrap();
+        history.push_undone(undone);
+        assert!(history.can_redo());
+
+        history.push(rename("t2", "c", "d"));
+        assert!(!history.can_redo());
+        assert_eq!(history.pop_undo().unwrap()[0].entity_id, "t2");
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn test_redo_keeps_later_redos() {
+        let mut history = UndoHistory::default();
+        history.push(rename("t1", "a", "b"));
+        history.push(rename("t2", "c", "d"));
+        for _ in 0..2 {
+            let undone = history.pop_undo().unwrap();
+            history.push_undone(undone);
+        }
+
+        let redone = history.pop_redo().unwrap();
+        assert_eq!(redone[0].entity_id, "t1");
+        history.push_redone(redone);
+        assert!(history.can_redo());
+        assert_eq!(history.pop_redo().unwrap()[0].entity_id, "t2");
+    }
+
+    #[test]
+    fn test_batch_is_one_step() {
+        let mut history = UndoHistory::default();
+        history.push_batch(vec![rename("t1", "a", "b"), rename("t2", "c", "d")]);
+        history.push_batch(Vec::new());
+
+        assert_eq!(history.pop_undo().unwrap().len(), 2);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn test_ignores_changes_that_cannot_be_undone() {
+        let mut history = UndoHistory::default();
+        history.push(DbChange::new(
+            "device",
+            ChangeKind::AlbumDeleted,
+            "album",
+            None,
+            None,
+        ));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn test_oldest_edits_drop_off() {
+        let mut history = UndoHistory::default();
+        for i in 0..MAX_UNDO + 5 {
+            history.push(rename(&i.to_string(), "a", "b"));
+        }
+        let mut count = 0;
+        while history.pop_undo().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, MAX_UNDO);
+    }
+}
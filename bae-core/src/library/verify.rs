@@ -0,0 +1,516 @@
+//! Library verification: check every stored file is there and intact.
+//!
+//! Files are downloaded, decrypted and decompressed the same way export reads
+//! them; one that can't be read at all is reported missing. Decryption
+//! authenticates the data, so a flipped bit in an encrypted file fails there.
+//! The bytes are then checked against the SHA-256 taken when the file was
+//! stored, and audio files are fully decoded to catch damage in the stream
+//! itself. Any of those failing reports the file corrupt. Local profiles'
+//! release folders are also walked for files no release refers to, which
+//! are reported orphaned.
+//!
+//! Issues are recorded per run, so the history shows when a file first went
+//! bad and on which storage profile. Each can be repaired: a missing or
+//! corrupt file is stored again from the folder it was imported from, if the
+//! original is still there unchanged, and an orphaned file is deleted.
+
+use crate::audio_codec;
+use crate::db::{
+    DbFile, DbStorageProfile, DbVerificationIssue, DbVerificationRun, StorageLocation,
+    VerificationIssueKind,
+};
+use crate::encryption::EncryptionService;
+use crate::import::folder_scanner::is_audio_file;
+use crate::library::export::unpack_stored_file;
+use crate::library::{LibraryError, LibraryManager};
+use crate::storage::{create_storage_reader, long_path, ReleaseStorageImpl};
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Pause between files so a run stays in the background instead of
+/// saturating disk, network and CPU
+pub const VERIFICATION_PACE: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Whether a scheduled run is due: never run before, or the last run started
+/// at least `interval_days` ago
+pub fn verification_due(
+    last_run: Option<&DbVerificationRun>,
+    interval_days: u32,
+    now: DateTime<Utc>,
+) -> bool {
+    match last_run {
+        None => true,
+        Some(run) => now - run.started_at >= Duration::days(interval_days as i64),
+    }
+}
+
+/// Verify every file stored under a storage profile, recording issues as a
+/// new run. Archival profiles are skipped, since reading them back would need
+/// a restore. Returns the finished run.
+pub async fn verify_library(
+    library_manager: &LibraryManager,
+    encryption_service: Option<&EncryptionService>,
+    scheduled: bool,
+    pace: std::time::Duration,
+) -> Result<DbVerificationRun, LibraryError> {
+    let database = library_manager.database();
+    let mut run = DbVerificationRun::new(scheduled);
+    database.insert_verification_run(&run).await?;
+    info!("Starting library verification run {}", run.id);
+
+    let tracked: Arc<HashSet<PathBuf>> = Arc::new(
+        database
+            .get_file_source_paths()
+            .await?
+            .into_iter()
+            .map(PathBuf::from)
+            .collect(),
+    );
+    let mut files_checked = 0i64;
+    let mut issue_count = 0usize;
+
+    for profile in database.get_all_storage_profiles().await? {
+        if profile.cloud_archival {
+            info!("Skipping archival storage profile '{}'", profile.name);
+            continue;
+        }
+
+        let storage = match create_storage_reader(&profile).await {
+            Ok(storage) => Some(storage),
+            Err(e) => {
+                warn!("Can't read storage profile '{}': {}", profile.name, e);
+                None
+            }
+        };
+
+        for release_id in database
+            .get_release_ids_for_storage_profile(&profile.id)
+            .await?
+        {
+            for file in database.get_files_for_release(&release_id).await? {
+                let result = match &storage {
+                    Some(storage) => {
+                        verify_file(&file, storage.as_ref(), &profile, encryption_service).await
+                    }
+                    None => Err((
+                        VerificationIssueKind::Missing,
+                        "Storage profile could not be opened".to_string(),
+                    )),
+                };
+                files_checked += 1;
+
+                if let Err((kind, error)) = result {
+                    warn!(
+                        "Verification failed for {}: {}",
+                        file.original_filename, error
+                    );
+                    let issue = DbVerificationIssue::new(&run.id, kind, &file, &profile, error);
+                    database.insert_verification_issue(&issue).await?;
+                    issue_count += 1;
+                }
+
+                tokio::time::sleep(pace).await;
+            }
+        }
+
+        // Referenced folders hold the user's own files, tracked or not
+        if profile.location == StorageLocation::Local {
+            let roots: Vec<PathBuf> = profile
+                .local_roots()
+                .into_iter()
+                .map(PathBuf::from)
+                .collect();
+            let tracked = tracked.clone();
+            let orphans = tokio::task::spawn_blocking(move || orphaned_files(&roots, &tracked))
+                .await
+                .map_err(|e| LibraryError::Io(std::io::Error::other(e)))?;
+            for path in orphans {
+                warn!("Orphaned file in '{}': {}", profile.name, path.display());
+                let path = path.to_string_lossy();
+                let issue = DbVerificationIssue::orphaned(&run.id, &path, &profile);
+                database.insert_verification_issue(&issue).await?;
+                issue_count += 1;
+            }
+        }
+    }
+
+    database
+        .finish_verification_run(&run.id, files_checked)
+        .await?;
+    run.files_checked = files_checked;
+    run.finished_at = Some(Utc::now());
+    info!(
+        "Verification run {} checked {} files, {} issues",
+        run.id, files_checked, issue_count
+    );
+    Ok(run)
+}
+
+async fn verify_file(
+    file: &DbFile,
+    storage: &dyn crate::cloud_storage::CloudStorage,
+    profile: &DbStorageProfile,
+    encryption_service: Option<&EncryptionService>,
+) -> Result<(), (VerificationIssueKind, String)> {
+    let missing = |error: String| (VerificationIssueKind::Missing, error);
+    let corrupt = |error: String| (VerificationIssueKind::Corrupt, error);
+
+    let source_path = file
+        .source_path
+        .as_deref()
+        .ok_or_else(|| missing("No storage location recorded".to_string()))?;
+    let stored = storage
+        .download(source_path)
+        .await
+        .map_err(|e| missing(format!("Failed to read {}: {}", source_path, e)))?;
+    let data = unpack_stored_file(file, stored, profile, encryption_service)
+        .await
+        .map_err(corrupt)?;
+
+    if data.len() as i64 != file.file_size {
+        return Err(corrupt(format!(
+            "Size mismatch: expected {} bytes, read {}",
+            file.file_size,
+            data.len()
+        )));
+    }
+
+    if let Some(expected) = &file.sha256 {
+        if hex::encode(Sha256::digest(&data)) != *expected {
+            return Err(corrupt(
+                "Checksum doesn't match the one recorded at import".to_string(),
+            ));
+        }
+    }
+
+    if !is_audio_file(Path::new(&file.original_filename)) {
+        return Ok(());
+    }
+
+    let verification = tokio::task::spawn_blocking(move || audio_codec::verify_audio(&data))
+        .await
+        .map_err(|e| corrupt(format!("Decode task failed: {}", e)))?
+        .map_err(corrupt)?;
+    if verification.decode_errors > 0 {
+        return Err(corrupt(format!(
+            "{} decode errors in audio stream",
+            verification.decode_errors
+        )));
+    }
+    Ok(())
+}
+
+/// Files under the release folders of local storage roots that aren't in
+/// `tracked`. Only folders named for a release are walked, so anything else
+/// kept in a root is left alone.
+fn orphaned_files(roots: &[PathBuf], tracked: &HashSet<PathBuf>) -> Vec<PathBuf> {
+    fn walk(dir: &Path, tracked: &HashSet<PathBuf>, orphans: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(long_path(dir)) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = dir.join(entry.file_name());
+            match entry.file_type() {
+                Ok(t) if t.is_dir() => walk(&path, tracked, orphans),
+                Ok(t) if t.is_file() && !tracked.contains(&path) => orphans.push(path),
+                _ => {}
+            }
+        }
+    }
+
+    let mut orphans = Vec::new();
+    for root in roots {
+        let Ok(entries) = std::fs::read_dir(long_path(root)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let is_release_dir = entry.file_type().is_ok_and(|t| t.is_dir())
+                && entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| uuid::Uuid::parse_str(name).is_ok());
+            if is_release_dir {
+                walk(&root.join(entry.file_name()), tracked, &mut orphans);
+            }
+        }
+    }
+    orphans.sort();
+    orphans
+}
+
+/// Repair an issue from a verification run: store a missing or corrupt file
+/// again from the folder it was imported from, or delete an orphaned file
+pub async fn repair_issue(
+    library_manager: &LibraryManager,
+    issue_id: &str,
+) -> Result<(), LibraryError> {
+    let database = library_manager.database();
+    let issue = database
+        .get_verification_issue(issue_id)
+        .await?
+        .ok_or_else(|| LibraryError::Repair(format!("no issue {}", issue_id)))?;
+    if issue.repaired {
+        return Ok(());
+    }
+
+    match (issue.kind, issue.file_id.as_deref()) {
+        (VerificationIssueKind::Orphaned, _) => {
+            let path = PathBuf::from(&issue.filename);
+            // Only delete what's still untracked and inside the profile
+            let profile = database
+                .get_storage_profile(&issue.storage_profile_id)
+                .await?
+                .ok_or_else(|| LibraryError::Repair("storage profile is gone".to_string()))?;
+            let in_profile = profile
+                .local_roots()
+                .iter()
+                .any(|root| path.starts_with(root));
+            let tracked = database
+                .get_file_source_paths()
+                .await?
+                .iter()
+                .any(|p| Path::new(p) == path);
+            if !in_profile || tracked {
+                return Err(LibraryError::Repair(format!(
+                    "{} is no longer an orphan",
+                    path.display()
+                )));
+            }
+            match tokio::fs::remove_file(long_path(&path)).await {
+                Ok(()) => info!("Deleted orphaned file {}", path.display()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        (_, Some(file_id)) => restore_file(library_manager, file_id).await?,
+        (_, None) => {
+            return Err(LibraryError::Repair(
+                "no file recorded for this issue".to_string(),
+            ))
+        }
+    }
+
+    database.mark_verification_issue_repaired(issue_id).await?;
+    Ok(())
+}
+
+/// Store a file again from its import's source folder, keeping its record
+async fn restore_file(library_manager: &LibraryManager, file_id: &str) -> Result<(), LibraryError> {
+    let database = library_manager.database();
+    let file = database
+        .get_file_by_id(file_id)
+        .await?
+        .ok_or_else(|| LibraryError::Repair("the file was removed from the library".to_string()))?;
+    let profile = database
+        .get_storage_profile_for_release(&file.release_id)
+        .await?
+        .ok_or_else(|| LibraryError::Repair("the release has no storage profile".to_string()))?;
+    let import = database
+        .get_import_for_release(&file.release_id)
+        .await?
+        .ok_or_else(|| LibraryError::Repair("no import recorded for the release".to_string()))?;
+
+    let original = Path::new(&import.folder_path).join(&file.original_filename);
+    let data = tokio::fs::read(long_path(&original)).await.map_err(|_| {
+        LibraryError::Repair(format!(
+            "the original is no longer at {}",
+            original.display()
+        ))
+    })?;
+    let unchanged = match &file.sha256 {
+        Some(expected) => hex::encode(Sha256::digest(&data)) == *expected,
+        None => data.len() as i64 == file.file_size,
+    };
+    if !unchanged {
+        return Err(LibraryError::Repair(format!(
+            "{} has changed since it was imported",
+            original.display()
+        )));
+    }
+
+    let storage = ReleaseStorageImpl::from_profile(
+        profile,
+        library_manager.encryption_service().cloned(),
+        Arc::new(database.clone()),
+    )
+    .await
+    .map_err(|e| LibraryError::Repair(e.to_string()))?;
+    let stored = storage
+        .store_file(
+            &file.release_id,
+            &file.original_filename,
+            &data,
+            Box::new(|_, _| {}),
+        )
+        .await
+        .map_err(|e| LibraryError::Repair(e.to_string()))?;
+    database
+        .update_file_storage(&DbFile {
+            source_path: stored.source_path,
+            encryption_nonce: stored.encryption_nonce,
+            compressed: stored.compressed,
+            checksum_verified: stored.checksum_verified,
+            sha256: stored.sha256,
+            ..file
+        })
+        .await?;
+    info!("Restored {} from {}", file_id, original.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{Database, DbAlbum, DbImport, DbRelease, DbReleaseStorage};
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn run_started(at: DateTime<Utc>) -> DbVerificationRun {
+        DbVerificationRun {
+            started_at: at,
+            ..DbVerificationRun::new(true)
+        }
+    }
+
+    #[test]
+    fn test_first_run_is_due() {
+        assert!(verification_due(None, 30, Utc::now()));
+    }
+
+    #[test]
+    fn test_run_due_after_interval() {
+        let last = run_started(Utc.with_ymd_and_hms(2026, 1, 1, 3, 0, 0).unwrap());
+
+        let before = Utc.with_ymd_and_hms(2026, 1, 30, 3, 0, 0).unwrap();
+        let after = Utc.with_ymd_and_hms(2026, 1, 31, 3, 0, 0).unwrap();
+        assert!(!verification_due(Some(&last), 30, before));
+        assert!(verification_due(Some(&last), 30, after));
+    }
+
+    #[tokio::test]
+    async fn test_missing_file_is_recorded_with_first_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+        let manager = LibraryManager::new(database, None);
+
+        let album = DbAlbum::new_test("Album");
+        let release = DbRelease::new_test(&album.id, "release-1");
+        let profile =
+            DbStorageProfile::new_local("Local", temp_dir.path().to_str().unwrap(), false);
+        let intact_path = temp_dir.path().join("notes.txt");
+        std::fs::write(&intact_path, b"liner notes").unwrap();
+        let intact = DbFile::new(&release.id, "notes.txt", 11, "txt")
+            .with_source_path(intact_path.to_str().unwrap());
+        let missing = DbFile::new(&release.id, "cover.jpg", 100, "jpg")
+            .with_source_path(temp_dir.path().join("cover.jpg").to_str().unwrap());
+
+        let db = manager.database();
+        db.insert_album(&album).await.unwrap();
+        db.insert_release(&release).await.unwrap();
+        db.insert_storage_profile(&profile).await.unwrap();
+        db.insert_release_storage(&DbReleaseStorage::new(&release.id, &profile.id))
+            .await
+            .unwrap();
+        db.insert_file(&intact).await.unwrap();
+        db.insert_file(&missing).await.unwrap();
+
+        let pace = std::time::Duration::ZERO;
+        let first = verify_library(&manager, None, false, pace).await.unwrap();
+        let second = verify_library(&manager, None, true, pace).await.unwrap();
+        assert_eq!(second.files_checked, 2);
+
+        let issues = db.get_verification_issues(&second.id).await.unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, VerificationIssueKind::Missing);
+        assert_eq!(issues[0].file_id.as_deref(), Some(missing.id.as_str()));
+        assert_eq!(issues[0].storage_profile_name, "Local");
+
+        let first_failures = db.get_first_verification_failures().await.unwrap();
+        assert_eq!(first_failures.get(&missing.id), Some(&first.started_at));
+    }
+
+    #[test]
+    fn test_orphans_are_untracked_files_in_release_folders() {
+        let root = TempDir::new().unwrap();
+        let release_dir = root.path().join(uuid::Uuid::new_v4().to_string());
+        std::fs::create_dir_all(release_dir.join("CD1")).unwrap();
+        let kept = release_dir.join("CD1").join("01.flac");
+        let stray = release_dir.join("CD1").join("02.flac");
+        std::fs::write(&kept, b"kept").unwrap();
+        std::fs::write(&stray, b"stray").unwrap();
+        // Not a release folder, so not bae's to judge
+        std::fs::write(root.path().join("README.txt"), b"notes").unwrap();
+
+        let tracked = HashSet::from([kept]);
+        let orphans = orphaned_files(&[root.path().to_path_buf()], &tracked);
+        assert_eq!(orphans, vec![stray]);
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_file_is_restored_from_import_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+        let manager = LibraryManager::new(database, None);
+        let db = manager.database();
+
+        let source_dir = temp_dir.path().join("source");
+        std::fs::create_dir_all(&source_dir).unwrap();
+        std::fs::write(source_dir.join("notes.txt"), b"liner notes").unwrap();
+
+        let album = DbAlbum::new_test("Album");
+        let release = DbRelease::new_test(&album.id, &uuid::Uuid::new_v4().to_string());
+        let storage_dir = temp_dir.path().join("storage");
+        let profile = DbStorageProfile::new_local("Local", storage_dir.to_str().unwrap(), false);
+        db.insert_album(&album).await.unwrap();
+        db.insert_release(&release).await.unwrap();
+        db.insert_storage_profile(&profile).await.unwrap();
+        db.insert_release_storage(&DbReleaseStorage::new(&release.id, &profile.id))
+            .await
+            .unwrap();
+        let import = DbImport::new("import-1", "Album", "Artist", source_dir.to_str().unwrap());
+        db.insert_import(&import).await.unwrap();
+        db.link_import_to_release(&import.id, &release.id)
+            .await
+            .unwrap();
+
+        let storage = ReleaseStorageImpl::from_profile(profile, None, Arc::new(db.clone()))
+            .await
+            .unwrap();
+        let file = storage
+            .store_file(
+                &release.id,
+                "notes.txt",
+                b"liner notes",
+                Box::new(|_, _| {}),
+            )
+            .await
+            .unwrap();
+        db.insert_file(&file).await.unwrap();
+        let stored_path = file.source_path.clone().unwrap();
+        std::fs::write(&stored_path, b"liner nOtes").unwrap();
+
+        let pace = std::time::Duration::ZERO;
+        let run = verify_library(&manager, None, false, pace).await.unwrap();
+        let issues = db.get_verification_issues(&run.id).await.unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, VerificationIssueKind::Corrupt);
+
+        repair_issue(&manager, &issues[0].id).await.unwrap();
+        assert_eq!(std::fs::read(&stored_path).unwrap(), b"liner notes");
+        let repaired = db.get_verification_issue(&issues[0].id).await.unwrap();
+        assert!(repaired.unwrap().repaired);
+
+        let run = verify_library(&manager, None, false, pace).await.unwrap();
+        assert!(db
+            .get_verification_issues(&run.id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
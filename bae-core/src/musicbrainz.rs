@@ -573,22 +573,47 @@ pub fn extract_search_tokens(metadata: &crate::import::FolderMetadata) -> Vec<St
     }
     tokens
 }
+/// Number of releases requested per search page
+pub const SEARCH_PAGE_SIZE: u32 = 25;
+/// One page of release search results
+#[derive(Debug, Clone)]
+pub struct MbSearchPage {
+    pub releases: Vec<MbRelease>,
+    /// Total number of releases matching the query (across all pages)
+    pub total: u32,
+    /// Zero-based page index this page was fetched for
+    pub page: u32,
+}
+impl MbSearchPage {
+    /// Whether another page can be fetched after this one
+    pub fn has_more(&self) -> bool {
+        (self.page + 1) * SEARCH_PAGE_SIZE < self.total
+    }
+}
 /// Search MusicBrainz for releases using structured parameters
+///
+/// `page` is zero-based; each page holds up to `SEARCH_PAGE_SIZE` releases.
 pub async fn search_releases_with_params(
     params: &ReleaseSearchParams,
-) -> Result<Vec<MbRelease>, MusicBrainzError> {
+    page: u32,
+) -> Result<MbSearchPage, MusicBrainzError> {
     if !params.has_any_field() {
         return Err(MusicBrainzError::Api(
             "At least one search field must be provided".to_string(),
         ));
     }
     let query = params.build_query();
-    info!("🎵 MusicBrainz: Searching with params: {:?}", params);
+    let limit = SEARCH_PAGE_SIZE.to_string();
+    let offset = (page * SEARCH_PAGE_SIZE).to_string();
+    info!(
+        "🎵 MusicBrainz: Searching with params: {:?} (page {})",
+        params, page
+    );
     info!("   Query: {}", query);
     let url = "https://musicbrainz.org/ws/2/release";
     debug!(
-        "MusicBrainz API request: {}?query={}&limit=25&inc=recordings+artist-credits+release-groups+labels+media+url-rels",
-        url, query
+        "MusicBrainz API request: {}?query={}&limit={}&offset={}&inc=recordings+artist-credits+release-groups+labels+media+url-rels",
+        url, query, limit, offset
     );
     let client = reqwest::Client::builder()
         .user_agent("bae/1.0 +https://github.com/hideselfview/bae")
@@ -598,7 +623,8 @@ pub async fn search_releases_with_params(
         .get(url)
         .query(&[
             ("query", query.as_str()),
-            ("limit", "25"),
+            ("limit", limit.as_str()),
+            ("offset", offset.as_str()),
             (
                 "inc",
                 "recordings+artist-credits+release-groups+labels+media+url-rels",
@@ -619,7 +645,11 @@ pub async fn search_releases_with_params(
             status, error_text
         );
         if status == 404 {
-            return Ok(Vec::new());
+            return Ok(MbSearchPage {
+                releases: Vec::new(),
+                total: 0,
+                page,
+            });
         }
         return Err(MusicBrainzError::Api(format!(
             "MusicBrainz API returned status {}: {}",
@@ -721,8 +751,17 @@ pub async fn search_releases_with_params(
             }
         }
     }
-    info!("✓ Found {} release(s)", releases.len());
-    Ok(releases)
+    let total = json
+        .get("count")
+        .and_then(|c| c.as_u64())
+        .map(|c| c as u32)
+        .unwrap_or(page * SEARCH_PAGE_SIZE + releases.len() as u32);
+    info!("✓ Found {} release(s) of {} total", releases.len(), total);
+    Ok(MbSearchPage {
+        releases,
+        total,
+        page,
+    })
 }
 #[cfg(test)]
 mod tests {
@@ -768,4 +807,17 @@ mod tests {
             "artist:\"ACDC\" AND catno:\"A2 16018\""
         );
     }
+    #[test]
+    fn test_search_page_has_more() {
+        let page = |page, total| MbSearchPage {
+            releases: Vec::new(),
+            total,
+            page,
+        };
+        assert!(page(0, 26).has_more());
+        assert!(!page(0, 25).has_more());
+        assert!(page(1, 51).has_more());
+        assert!(!page(1, 50).has_more());
+        assert!(!page(0, 0).has_more());
+    }
 }
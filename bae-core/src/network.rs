@@ -30,6 +30,24 @@ fn collect_available_ips(interfaces: &[if_addrs::Interface]) -> Vec<String> {
         })
         .collect()
 }
+/// This machine's address on the local network, for links other devices open.
+/// Prefers a private IPv4 address, since that's what a phone on the same Wi-Fi
+/// can reach.
+pub fn lan_ipv4() -> Option<IpAddr> {
+    let interfaces = get_interfaces().ok()?;
+    let candidates: Vec<_> = interfaces
+        .iter()
+        .filter_map(|iface| match iface.addr.ip() {
+            IpAddr::V4(ip) if !ip.is_loopback() && !ip.is_link_local() => Some(ip),
+            _ => None,
+        })
+        .collect();
+    candidates
+        .iter()
+        .find(|ip| ip.is_private())
+        .or(candidates.first())
+        .map(|ip| IpAddr::V4(*ip))
+}
 /// Validate that an IP address is bound to an interface
 fn validate_ip_address(ip: IpAddr, allow_unspecified: bool) -> Result<(), String> {
     if ip.is_unspecified() {
@@ -89,3 +107,54 @@ pub fn validate_network_interface(interface: &str) -> Result<(), String> {
         ))
     }
 }
+
+/// Background traffic allowed while on a metered connection. Each `allow_*`
+/// flag keeps one feature running in metered mode anyway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MeteredSettings {
+    /// Whether metered mode is on
+    pub enabled: bool,
+    /// Keep preloading the next track from cloud storage
+    pub allow_cloud_prefetch: bool,
+    /// Keep torrent downloads and seeding running
+    pub allow_torrents: bool,
+}
+
+impl MeteredSettings {
+    pub fn cloud_prefetch_allowed(&self) -> bool {
+        !self.enabled || self.allow_cloud_prefetch
+    }
+
+    pub fn torrents_allowed(&self) -> bool {
+        !self.enabled || self.allow_torrents
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_everything_allowed_when_not_metered() {
+        let settings = MeteredSettings::default();
+        assert!(settings.cloud_prefetch_allowed());
+        assert!(settings.torrents_allowed());
+    }
+
+    #[test]
+    fn test_metered_mode_holds_back_unless_allowed() {
+        let metered = MeteredSettings {
+            enabled: true,
+            ..MeteredSettings::default()
+        };
+        assert!(!metered.cloud_prefetch_allowed());
+        assert!(!metered.torrents_allowed());
+
+        let torrents_only = MeteredSettings {
+            allow_torrents: true,
+            ..metered
+        };
+        assert!(!torrents_only.cloud_prefetch_allowed());
+        assert!(torrents_only.torrents_allowed());
+    }
+}
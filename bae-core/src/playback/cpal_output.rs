@@ -1,8 +1,14 @@
+use crate::playback::exclusive::{ExclusiveDevice, ExclusiveError};
+use crate::playback::output_format::{
+    Dither, OutputBitDepth, OutputFormat, OutputSampleFormat, OutputSettings, Quantizer,
+};
+use crate::playback::pitch::{PitchShifter, MAX_SEMITONES};
+use crate::playback::silence::{SilenceSkipper, SkipSilenceSettings};
 use crate::playback::streaming_source::StreamingPcmSource;
 use cpal::traits::{DeviceTrait, HostTrait};
-use cpal::{Device, Stream, StreamConfig};
+use cpal::{Device, SampleFormat, Stream, StreamConfig};
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use std::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, AtomicU8, Ordering};
 use std::sync::Arc;
 use std::sync::{mpsc, Mutex};
 use tracing::{error, info, trace, warn};
@@ -56,27 +62,24 @@ impl std::error::Error for AudioError {}
 /// State and volume are shared atomics - set them directly, no command channel needed.
 pub struct AudioOutput {
     device: Device,
+    settings: OutputSettings,
     stream_config: StreamConfig,
+    format: OutputFormat,
+    /// Held while playing in exclusive mode, released on stop
+    exclusive: Option<ExclusiveDevice>,
+    /// Set from the stream's error callback when the device goes away
+    device_lost: Arc<AtomicBool>,
     state: Arc<AtomicU8>,
     volume: Arc<AtomicU32>,
+    /// Pitch shift in semitones, picked up by the stream as it plays
+    pitch: Arc<AtomicI32>,
 }
 
 impl AudioOutput {
     /// Create a new audio output manager
-    pub fn new() -> Result<Self, AudioError> {
-        let host = cpal::default_host();
-        let device = host
-            .default_output_device()
-            .ok_or(AudioError::DeviceNotFound)?;
-        let default_config = device
-            .default_output_config()
-            .map_err(|e| AudioError::StreamConfigError(e.to_string()))?;
-        let sample_format = default_config.sample_format();
-        let stream_config = StreamConfig::from(default_config.clone());
-        info!(
-            "Audio device: {} channels, {} Hz, {:?}",
-            stream_config.channels, stream_config.sample_rate.0, sample_format
-        );
+    pub fn new(settings: OutputSettings) -> Result<Self, AudioError> {
+        let device = open_device(settings.device.as_deref())?;
+        let (stream_config, format) = negotiate_format(&device, &settings)?;
         let initial_volume = if std::env::var("SKIP_AUDIO_TESTS").is_ok()
             || std::env::var("MUTE_TEST_AUDIO").is_ok()
         {
@@ -86,16 +89,150 @@ impl AudioOutput {
         };
         Ok(Self {
             device,
+            settings,
             stream_config,
+            format,
+            exclusive: None,
+            device_lost: Arc::new(AtomicBool::new(false)),
             state: Arc::new(AtomicU8::new(AudioState::Stopped as u8)),
             volume: Arc::new(AtomicU32::new(initial_volume)),
+            pitch: Arc::new(AtomicI32::new(0)),
         })
     }
 
+    /// The format streams are created with
+    pub fn format(&self) -> &OutputFormat {
+        &self.format
+    }
+
+    pub fn settings(&self) -> &OutputSettings {
+        &self.settings
+    }
+
+    /// Renegotiate the device format, opening a different device if the
+    /// preferred one changed. Streams already playing keep theirs; the next
+    /// stream uses the new one.
+    pub fn set_output_settings(&mut self, settings: OutputSettings) -> Result<(), AudioError> {
+        let device_changed = settings.device != self.settings.device;
+        self.settings = settings;
+        if device_changed {
+            return self.reopen();
+        }
+        if !self.settings.is_exclusive(&self.format.device_name) {
+            self.release_exclusive();
+        }
+        self.renegotiate()
+    }
+
+    /// Whether the stream's device went away since the last call
+    pub fn take_device_lost(&self) -> bool {
+        self.device_lost.swap(false, Ordering::Relaxed)
+    }
+
+    /// Whether playback should move to another device: the preferred one
+    /// came back, or the system default changed while following it
+    pub fn device_changed(&self) -> bool {
+        self.wanted_device_name()
+            .is_some_and(|name| name != self.format.device_name)
+    }
+
+    /// Whether there's a device to open at all
+    pub fn device_available(&self) -> bool {
+        self.wanted_device_name().is_some()
+    }
+
+    /// The device `reopen` would open now, without logging when the
+    /// preferred one is missing
+    fn wanted_device_name(&self) -> Option<String> {
+        self.settings
+            .device
+            .as_deref()
+            .and_then(find_device)
+            .or_else(|| cpal::default_host().default_output_device())
+            .and_then(|device| device.name().ok())
+    }
+
+    /// In exclusive mode, take the device and switch it to the source's rate
+    /// so samples reach it unresampled and undithered. In shared mode, let go
+    /// of the device if we held it.
+    ///
+    /// On error the device stays shared; the next stream plays through the
+    /// system mixer as usual.
+    pub fn prepare_exclusive(&mut self, source_sample_rate: u32) -> Result<(), ExclusiveError> {
+        if !self.settings.is_exclusive(&self.format.device_name) {
+            self.release_exclusive();
+            return Ok(());
+        }
+
+        if self.exclusive.is_none() {
+            let device = ExclusiveDevice::acquire(
+                &self.format.device_name,
+                self.stream_config.sample_rate.0,
+            )?;
+            self.exclusive = Some(device);
+        }
+        let Some(device) = &self.exclusive else {
+            return Ok(());
+        };
+
+        let rate = cpal::SampleRate(source_sample_rate);
+        let sample_format = cpal_sample_format(self.format.sample_format);
+        if supports_config(
+            &self.device,
+            sample_format,
+            self.stream_config.channels,
+            rate,
+        ) {
+            device.set_sample_rate(source_sample_rate)?;
+            self.stream_config.sample_rate = rate;
+            self.format.sample_rate = source_sample_rate;
+        } else {
+            warn!(
+                "{} doesn't support {} Hz, resampling to {} Hz",
+                self.format.device_name, source_sample_rate, self.format.sample_rate
+            );
+        }
+        self.format.dither = Dither::Off;
+        self.format.exclusive = true;
+
+        Ok(())
+    }
+
+    /// Give the device back to other apps and restore its shared format
+    pub fn release_exclusive(&mut self) {
+        if self.exclusive.take().is_none() {
+            return;
+        }
+        if let Err(e) = self.renegotiate() {
+            warn!("Failed to renegotiate output format: {}", e);
+        }
+    }
+
+    /// Open the preferred device again, or the system default when it's
+    /// gone, keeping settings, state and volume. For when the device failed,
+    /// went away or was switched; streams must be dropped first.
+    pub fn reopen(&mut self) -> Result<(), AudioError> {
+        self.release_exclusive();
+        self.device = open_device(self.settings.device.as_deref())?;
+        self.device_lost.store(false, Ordering::Relaxed);
+        self.renegotiate()
+    }
+
+    fn renegotiate(&mut self) -> Result<(), AudioError> {
+        let (stream_config, mut format) = negotiate_format(&self.device, &self.settings)?;
+        format.exclusive = self.exclusive.is_some();
+        self.stream_config = stream_config;
+        self.format = format;
+        Ok(())
+    }
+
     /// Create an audio output stream.
     ///
     /// Pulls f32 samples from a `StreamingPcmSource` ring buffer fed by a decoder thread.
-    /// Handles buffer underrun with silence.
+    /// Handles buffer underrun with silence. Integer devices get the f32 mix
+    /// quantized (and for 16-bit, dithered) at the end of the callback.
+    /// With skip-silence on, long silent runs are dropped as they're pulled.
+    /// With a pitch shift set, pulled samples are transposed before the rest.
     pub fn create_stream(
         &mut self,
         source: Arc<Mutex<StreamingPcmSource>>,
@@ -103,6 +240,7 @@ impl AudioOutput {
         source_channels: u32,
         position_tx: mpsc::Sender<std::time::Duration>,
         completion_tx: mpsc::Sender<()>,
+        skip_silence: SkipSilenceSettings,
     ) -> Result<Stream, AudioError> {
         let output_sample_rate = self.stream_config.sample_rate.0;
         let output_channels = self.stream_config.channels as usize;
@@ -111,141 +249,196 @@ impl AudioOutput {
 
         let state = self.state.clone();
         let volume = self.volume.clone();
+        let pitch = self.pitch.clone();
 
         let mut resample_buffer: Vec<f32> = Vec::new();
         let mut resample_pos = 0usize;
         let mut last_position_update = std::time::Instant::now();
         let position_update_interval = std::time::Duration::from_millis(250);
         let mut completion_sent = false;
+        let mut silence_skipper = skip_silence
+            .enabled
+            .then(|| SilenceSkipper::new(skip_silence, source_sample_rate, source_channels));
+        let mut pitch_shifter: Option<PitchShifter> = None;
 
-        let stream = self
-            .device
-            .build_output_stream(
-                &self.stream_config,
-                move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-                    // Check state - only output samples when Playing
-                    if AudioState::from_u8(state.load(Ordering::Relaxed)) != AudioState::Playing {
-                        data.fill(0.0);
-                        return;
-                    }
+        let mut render = move |data: &mut [f32]| {
+            // Check state - only output samples when Playing
+            if AudioState::from_u8(state.load(Ordering::Relaxed)) != AudioState::Playing {
+                data.fill(0.0);
+                return;
+            }
 
-                    let vol = volume.load(Ordering::Relaxed) as f32 / 10000.0;
-                    let mut output_pos = 0;
+            let vol = volume.load(Ordering::Relaxed) as f32 / 10000.0;
+            let mut output_pos = 0;
 
-                    // Try to lock the source (non-blocking in audio callback)
-                    let mut source_guard = match source.try_lock() {
-                        Ok(guard) => guard,
-                        Err(_) => {
-                            // Can't get lock, output silence
-                            data.fill(0.0);
-                            return;
-                        }
-                    };
+            // Try to lock the source (non-blocking in audio callback)
+            let mut source_guard = match source.try_lock() {
+                Ok(guard) => guard,
+                Err(_) => {
+                    // Can't get lock, output silence
+                    data.fill(0.0);
+                    return;
+                }
+            };
 
-                    while output_pos < data.len() {
-                        if resample_pos >= resample_buffer.len() {
-                            // Need more samples from source
-                            let samples_needed =
-                                (data.len() as f64 * sample_rate_ratio) as usize + source_channels;
-                            let mut raw_samples = vec![0.0f32; samples_needed];
-                            let read = source_guard.pull_samples(&mut raw_samples);
-
-                            if read == 0 {
-                                if source_guard.is_finished() {
-                                    // End of stream
-                                    if !completion_sent {
-                                        info!("Streaming audio callback: End of stream");
-                                        state.store(AudioState::Stopped as u8, Ordering::Relaxed);
-                                        if completion_tx.send(()).is_err() {
-                                            warn!("Failed to send completion signal");
-                                        }
-                                        completion_sent = true;
-                                    }
-                                    data[output_pos..].fill(0.0);
-                                    return;
-                                } else {
-                                    // Buffer underrun - output silence and continue
-                                    trace!("Streaming buffer underrun");
-                                    data[output_pos..].fill(0.0);
-                                    return;
+            while output_pos < data.len() {
+                if resample_pos >= resample_buffer.len() {
+                    // Need more samples from source
+                    let samples_needed =
+                        (data.len() as f64 * sample_rate_ratio) as usize + source_channels;
+                    let mut raw_samples = vec![0.0f32; samples_needed];
+                    let read = source_guard.pull_samples(&mut raw_samples);
+
+                    if read == 0 {
+                        if source_guard.is_finished() {
+                            // End of stream
+                            if !completion_sent {
+                                info!("Streaming audio callback: End of stream");
+                                state.store(AudioState::Stopped as u8, Ordering::Relaxed);
+                                if completion_tx.send(()).is_err() {
+                                    warn!("Failed to send completion signal");
                                 }
+                                completion_sent = true;
                             }
+                            data[output_pos..].fill(0.0);
+                            return;
+                        } else {
+                            // Buffer underrun - output silence and continue
+                            trace!("Streaming buffer underrun");
+                            data[output_pos..].fill(0.0);
+                            return;
+                        }
+                    }
 
-                            raw_samples.truncate(read);
-                            resample_buffer.clear();
-                            resample_pos = 0;
-
-                            let input_frames = raw_samples.len() / source_channels;
-
-                            // Resample if needed
-                            let converted = if sample_rate_ratio != 1.0 {
-                                let output_frames =
-                                    (input_frames as f64 / sample_rate_ratio) as usize;
-                                let mut resampled =
-                                    Vec::with_capacity(output_frames * source_channels);
-
-                                for frame_idx in 0..output_frames {
-                                    let src_idx = (frame_idx as f64 * sample_rate_ratio) as usize;
-                                    if src_idx < input_frames {
-                                        for ch in 0..source_channels {
-                                            let idx = src_idx * source_channels + ch;
-                                            if idx < raw_samples.len() {
-                                                resampled.push(raw_samples[idx]);
-                                            } else {
-                                                resampled.push(0.0);
-                                            }
-                                        }
-                                    }
-                                }
-                                resampled
-                            } else {
-                                raw_samples
-                            };
-
-                            // Channel conversion
-                            let frames = converted.len() / source_channels;
-                            if source_channels != output_channels {
-                                for frame_idx in 0..frames {
-                                    let base_idx = frame_idx * source_channels;
-                                    if output_channels == 1 && source_channels >= 1 {
-                                        resample_buffer.push(converted[base_idx]);
-                                    } else if output_channels == 2 && source_channels == 1 {
-                                        let sample = converted[base_idx];
-                                        resample_buffer.push(sample);
-                                        resample_buffer.push(sample);
-                                    } else if output_channels == 2 && source_channels >= 2 {
-                                        resample_buffer.push(converted[base_idx]);
-                                        resample_buffer.push(converted[base_idx + 1]);
+                    raw_samples.truncate(read);
+                    if let Some(skipper) = &mut silence_skipper {
+                        skipper.filter(&mut raw_samples);
+                        if raw_samples.is_empty() {
+                            // All of it was skipped silence; pull more
+                            continue;
+                        }
+                    }
+                    // A new shift starts a fresh shifter; none at all plays
+                    // the samples untouched
+                    let semitones = pitch.load(Ordering::Relaxed);
+                    if pitch_shifter.as_ref().map(PitchShifter::semitones) != Some(semitones) {
+                        pitch_shifter = (semitones != 0).then(|| {
+                            PitchShifter::new(semitones, source_sample_rate, source_channels)
+                        });
+                    }
+                    if let Some(shifter) = &mut pitch_shifter {
+                        shifter.process(&mut raw_samples);
+                        if raw_samples.is_empty() {
+                            // Still filling the shifter's look-ahead
+                            continue;
+                        }
+                    }
+                    resample_buffer.clear();
+                    resample_pos = 0;
+
+                    let input_frames = raw_samples.len() / source_channels;
+
+                    // Resample if needed
+                    let converted = if sample_rate_ratio != 1.0 {
+                        let output_frames = (input_frames as f64 / sample_rate_ratio) as usize;
+                        let mut resampled = Vec::with_capacity(output_frames * source_channels);
+
+                        for frame_idx in 0..output_frames {
+                            let src_idx = (frame_idx as f64 * sample_rate_ratio) as usize;
+                            if src_idx < input_frames {
+                                for ch in 0..source_channels {
+                                    let idx = src_idx * source_channels + ch;
+                                    if idx < raw_samples.len() {
+                                        resampled.push(raw_samples[idx]);
                                     } else {
-                                        resample_buffer
-                                            .extend(std::iter::repeat_n(0.0, output_channels));
+                                        resampled.push(0.0);
                                     }
                                 }
-                            } else {
-                                resample_buffer = converted;
                             }
                         }
+                        resampled
+                    } else {
+                        raw_samples
+                    };
 
-                        // Copy from resample buffer to output
-                        while output_pos < data.len() && resample_pos < resample_buffer.len() {
-                            data[output_pos] = resample_buffer[resample_pos] * vol;
-                            output_pos += 1;
-                            resample_pos += 1;
+                    // Channel conversion
+                    let frames = converted.len() / source_channels;
+                    if source_channels != output_channels {
+                        for frame_idx in 0..frames {
+                            let base_idx = frame_idx * source_channels;
+                            if output_channels == 1 && source_channels >= 1 {
+                                resample_buffer.push(converted[base_idx]);
+                            } else if output_channels == 2 && source_channels == 1 {
+                                let sample = converted[base_idx];
+                                resample_buffer.push(sample);
+                                resample_buffer.push(sample);
+                            } else if output_channels == 2 && source_channels >= 2 {
+                                resample_buffer.push(converted[base_idx]);
+                                resample_buffer.push(converted[base_idx + 1]);
+                            } else {
+                                resample_buffer.extend(std::iter::repeat_n(0.0, output_channels));
+                            }
                         }
+                    } else {
+                        resample_buffer = converted;
                     }
+                }
 
-                    // Position updates
-                    if last_position_update.elapsed() >= position_update_interval {
-                        let _ = position_tx.send(source_guard.position());
-                        last_position_update = std::time::Instant::now();
-                    }
+                // Copy from resample buffer to output
+                while output_pos < data.len() && resample_pos < resample_buffer.len() {
+                    data[output_pos] = resample_buffer[resample_pos] * vol;
+                    output_pos += 1;
+                    resample_pos += 1;
+                }
+            }
+
+            // Position updates
+            if last_position_update.elapsed() >= position_update_interval {
+                let _ = position_tx.send(source_guard.position());
+                last_position_update = std::time::Instant::now();
+            }
+        };
+
+        let device_lost = self.device_lost.clone();
+        let on_error = move |err: cpal::StreamError| {
+            error!("Streaming audio error: {:?}", err);
+            if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+                device_lost.store(true, Ordering::Relaxed);
+            }
+        };
+        let sample_format = self.format.sample_format;
+        let mut quantizer =
+            Quantizer::new(sample_format.bits(), self.format.dither, output_channels);
+        let mut mix: Vec<f32> = Vec::new();
+        let stream = match sample_format {
+            OutputSampleFormat::F32 => self.device.build_output_stream(
+                &self.stream_config,
+                move |data: &mut [f32], _: &cpal::OutputCallbackInfo| render(data),
+                on_error,
+                None,
+            ),
+            OutputSampleFormat::I24In32 => self.device.build_output_stream(
+                &self.stream_config,
+                move |data: &mut [i32], _: &cpal::OutputCallbackInfo| {
+                    mix.resize(data.len(), 0.0);
+                    render(&mut mix);
+                    quantizer.quantize_i32(&mix, data);
                 },
-                |err| {
-                    error!("Streaming audio error: {:?}", err);
+                on_error,
+                None,
+            ),
+            OutputSampleFormat::I16 => self.device.build_output_stream(
+                &self.stream_config,
+                move |data: &mut [i16], _: &cpal::OutputCallbackInfo| {
+                    mix.resize(data.len(), 0.0);
+                    render(&mut mix);
+                    quantizer.quantize_i16(&mix, data);
                 },
+                on_error,
                 None,
-            )
-            .map_err(|e| AudioError::StreamBuildError(e.to_string()))?;
+            ),
+        }
+        .map_err(|e| AudioError::StreamBuildError(e.to_string()))?;
 
         Ok(stream)
     }
@@ -265,6 +458,19 @@ impl AudioOutput {
         self.get_state() == AudioState::Paused
     }
 
+    /// Transpose playback by `semitones`, clamped to ±12. Zero turns the
+    /// shift off. Applies to the playing stream right away.
+    pub fn set_pitch(&self, semitones: i32) {
+        self.pitch.store(
+            semitones.clamp(-MAX_SEMITONES, MAX_SEMITONES),
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn pitch(&self) -> i32 {
+        self.pitch.load(Ordering::Relaxed)
+    }
+
     /// Set volume (0.0 to 1.0)
     pub fn set_volume(&self, volume: f32) {
         self.volume
@@ -273,6 +479,129 @@ impl AudioOutput {
 }
 impl Default for AudioOutput {
     fn default() -> Self {
-        Self::new().expect("Failed to initialize audio output")
+        Self::new(OutputSettings::default()).expect("Failed to initialize audio output")
+    }
+}
+
+/// Pick a stream config for the requested bit depth.
+///
+/// Keeps the device's default rate and channel count and looks for a
+/// supported config in the wanted sample format. Falls back to the default
+/// config when the device doesn't offer one, and to f32 when the default
+/// format is one we don't write.
+fn negotiate_format(
+    device: &Device,
+    settings: &OutputSettings,
+) -> Result<(StreamConfig, OutputFormat), AudioError> {
+    let default_config = device
+        .default_output_config()
+        .map_err(|e| AudioError::StreamConfigError(e.to_string()))?;
+    let rate = default_config.sample_rate();
+    let channels = default_config.channels();
+
+    let wanted = match settings.bit_depth {
+        OutputBitDepth::Auto => default_config.sample_format(),
+        OutputBitDepth::Float32 => SampleFormat::F32,
+        OutputBitDepth::Int24 => SampleFormat::I32,
+        OutputBitDepth::Int16 => SampleFormat::I16,
+    };
+    let supports = |format: SampleFormat| {
+        format == default_config.sample_format() || supports_config(device, format, channels, rate)
+    };
+    let sample_format = [wanted, default_config.sample_format(), SampleFormat::F32]
+        .into_iter()
+        .find_map(|format| output_sample_format(format).filter(|_| supports(format)))
+        .unwrap_or(OutputSampleFormat::F32);
+    if settings.bit_depth != OutputBitDepth::Auto
+        && output_sample_format(wanted) != Some(sample_format)
+    {
+        warn!(
+            "Output device doesn't support {:?}, using {:?}",
+            settings.bit_depth, sample_format
+        );
+    }
+
+    let format = OutputFormat {
+        device_name: device
+            .name()
+            .unwrap_or_else(|_| "Unknown device".to_string()),
+        sample_rate: rate.0,
+        channels,
+        sample_format,
+        dither: if sample_format.is_dithered() {
+            settings.dither
+        } else {
+            Dither::Off
+        },
+        exclusive: false,
+    };
+
+    info!("Audio device: {} ({})", format.device_name, format);
+
+    Ok((StreamConfig::from(default_config), format))
+}
+
+/// The named output device, or the system default when it isn't connected
+fn open_device(name: Option<&str>) -> Result<Device, AudioError> {
+    if let Some(name) = name {
+        match find_device(name) {
+            Some(device) => return Ok(device),
+            None => warn!("Output device {} not found, using the default", name),
+        }
+    }
+    cpal::default_host()
+        .default_output_device()
+        .ok_or(AudioError::DeviceNotFound)
+}
+
+fn find_device(name: &str) -> Option<Device> {
+    cpal::default_host()
+        .output_devices()
+        .ok()?
+        .find(|d| d.name().is_ok_and(|n| n == name))
+}
+
+/// Names of the output devices on the default host
+pub fn output_device_names() -> Vec<String> {
+    cpal::default_host()
+        .output_devices()
+        .map(|devices| devices.filter_map(|d| d.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+fn supports_config(
+    device: &Device,
+    format: SampleFormat,
+    channels: u16,
+    rate: cpal::SampleRate,
+) -> bool {
+    device
+        .supported_output_configs()
+        .map(|mut configs| {
+            configs.any(|c| {
+                c.sample_format() == format
+                    && c.channels() == channels
+                    && c.min_sample_rate() <= rate
+                    && rate <= c.max_sample_rate()
+            })
+        })
+        .unwrap_or(false)
+}
+
+fn cpal_sample_format(format: OutputSampleFormat) -> SampleFormat {
+    match format {
+        OutputSampleFormat::F32 => SampleFormat::F32,
+        OutputSampleFormat::I24In32 => SampleFormat::I32,
+        OutputSampleFormat::I16 => SampleFormat::I16,
+    }
+}
+
+/// The sample formats we can write
+fn output_sample_format(format: SampleFormat) -> Option<OutputSampleFormat> {
+    match format {
+        SampleFormat::F32 => Some(OutputSampleFormat::F32),
+        SampleFormat::I32 => Some(OutputSampleFormat::I24In32),
+        SampleFormat::I16 => Some(OutputSampleFormat::I16),
+        _ => None,
     }
 }
@@ -18,6 +18,9 @@ pub enum PlaybackError {
     /// Invalid or corrupt FLAC data
     #[error("Invalid FLAC: {0}")]
     InvalidFlac(String),
+    /// File opened from disk couldn't be decoded or converted to FLAC
+    #[error("Unsupported audio file: {0}")]
+    UnsupportedFile(String),
     /// File system IO error
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -41,6 +44,9 @@ impl PlaybackError {
     pub fn flac(msg: impl Into<String>) -> Self {
         Self::InvalidFlac(msg.into())
     }
+    pub fn unsupported(msg: impl Into<String>) -> Self {
+        Self::UnsupportedFile(msg.into())
+    }
     pub fn task(e: impl std::fmt::Display) -> Self {
         Self::TaskFailed(e.to_string())
     }
@@ -0,0 +1,127 @@
+//! Exclusive device access for bit-perfect output.
+//!
+//! On macOS this is CoreAudio hog mode: while bae holds a device, other apps
+//! can't play through it, and bae can switch its sample rate to the source's
+//! so the system mixer never resamples. cpal has no WASAPI exclusive mode, so
+//! other platforms always share the device.
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use tracing::{info, warn};
+
+/// Whether exclusive mode is available on this platform
+pub fn exclusive_mode_supported() -> bool {
+    cfg!(target_os = "macos")
+}
+
+#[derive(Debug)]
+pub enum ExclusiveError {
+    Unsupported,
+    DeviceNotFound(String),
+    /// Another process already holds the device
+    InUse {
+        pid: i32,
+    },
+    Failed(String),
+}
+
+impl Display for ExclusiveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            ExclusiveError::Unsupported => {
+                write!(f, "Exclusive mode isn't supported on this platform")
+            }
+            ExclusiveError::DeviceNotFound(name) => write!(f, "Device not found: {}", name),
+            ExclusiveError::InUse { pid } => {
+                write!(f, "Device is held exclusively by another app (pid {})", pid)
+            }
+            ExclusiveError::Failed(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ExclusiveError {}
+
+/// A device held exclusively. Released, and its sample rate restored, on drop.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub struct ExclusiveDevice {
+    device_name: String,
+    /// Rate the device ran at before we took it
+    original_rate: u32,
+    #[cfg(target_os = "macos")]
+    device_id: coreaudio::sys::AudioDeviceID,
+}
+
+impl ExclusiveDevice {
+    #[cfg(target_os = "macos")]
+    pub fn acquire(device_name: &str, original_rate: u32) -> Result<Self, ExclusiveError> {
+        use coreaudio::audio_unit::macos_helpers::{
+            get_device_id_from_name, get_hogging_pid, toggle_hog_mode,
+        };
+
+        let device_id = get_device_id_from_name(device_name, false)
+            .ok_or_else(|| ExclusiveError::DeviceNotFound(device_name.to_string()))?;
+        let our_pid = std::process::id() as i32;
+
+        let holder = get_hogging_pid(device_id)
+            .map_err(|e| ExclusiveError::Failed(format!("Failed to query hog mode: {}", e)))?;
+        if holder != -1 && holder != our_pid {
+            return Err(ExclusiveError::InUse { pid: holder });
+        }
+        if holder != our_pid {
+            // Toggling races with other apps; the returned pid says who won
+            let holder = toggle_hog_mode(device_id)
+                .map_err(|e| ExclusiveError::Failed(format!("Failed to take device: {}", e)))?;
+            if holder != our_pid {
+                return Err(ExclusiveError::InUse { pid: holder });
+            }
+        }
+
+        info!("Took exclusive access to {}", device_name);
+
+        Ok(Self {
+            device_name: device_name.to_string(),
+            original_rate,
+            device_id,
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub fn acquire(_device_name: &str, _original_rate: u32) -> Result<Self, ExclusiveError> {
+        Err(ExclusiveError::Unsupported)
+    }
+
+    /// Switch the device's nominal rate. Only the holder should do this, as
+    /// it changes the rate for everything on the device.
+    #[cfg(target_os = "macos")]
+    pub fn set_sample_rate(&self, rate: u32) -> Result<(), ExclusiveError> {
+        coreaudio::audio_unit::macos_helpers::set_device_sample_rate(self.device_id, rate as f64)
+            .map_err(|e| ExclusiveError::Failed(format!("Failed to set sample rate: {}", e)))
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub fn set_sample_rate(&self, _rate: u32) -> Result<(), ExclusiveError> {
+        Err(ExclusiveError::Unsupported)
+    }
+}
+
+impl Drop for ExclusiveDevice {
+    fn drop(&mut self) {
+        if let Err(e) = self.set_sample_rate(self.original_rate) {
+            warn!("Failed to restore {} sample rate: {}", self.device_name, e);
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            use coreaudio::audio_unit::macos_helpers::{get_hogging_pid, toggle_hog_mode};
+
+            // Toggling releases only if we still hold it
+            if get_hogging_pid(self.device_id).ok() == Some(std::process::id() as i32) {
+                if let Err(e) = toggle_hog_mode(self.device_id) {
+                    warn!("Failed to release {}: {}", self.device_name, e);
+                }
+            }
+        }
+
+        info!("Released exclusive access to {}", self.device_name);
+    }
+}
@@ -0,0 +1,212 @@
+//! External files played without importing them
+//!
+//! The playback queue holds track IDs. Files opened directly from disk get a
+//! synthetic ID (`external:` followed by the absolute path) so they can sit in
+//! the queue next to library tracks without creating any database rows.
+//!
+//! Playback reads FLAC. Other formats FFmpeg can decode are converted to FLAC
+//! in a temporary folder first.
+
+use crate::audio_codec;
+use crate::db::{DbTrack, ImportStatus};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const EXTERNAL_TRACK_PREFIX: &str = "external:";
+
+/// Queue ID for a file on disk that isn't in the library
+pub fn external_track_id(path: &Path) -> String {
+    format!("{}{}", EXTERNAL_TRACK_PREFIX, path.display())
+}
+
+/// Path of an external file, or None for library track IDs
+pub fn external_track_path(track_id: &str) -> Option<PathBuf> {
+    track_id
+        .strip_prefix(EXTERNAL_TRACK_PREFIX)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+pub fn is_external_track(track_id: &str) -> bool {
+    external_track_path(track_id).is_some()
+}
+
+/// Where external files that aren't FLAC are converted to
+pub(crate) fn conversion_root() -> PathBuf {
+    std::env::temp_dir().join("bae_external")
+}
+
+fn is_flac_file(path: &Path) -> std::io::Result<bool> {
+    let mut magic = [0u8; 4];
+    let mut file = fs::File::open(path)?;
+    Ok(file.read_exact(&mut magic).is_ok() && &magic == b"fLaC")
+}
+
+/// A FLAC file with the audio of `path`: the file itself when it's FLAC,
+/// otherwise a conversion under `root`. Conversions are named after the
+/// source's path, size and modification time, so they're reused until the
+/// file changes.
+pub(crate) fn playable_flac_path(path: &Path, root: &Path) -> Result<PathBuf, String> {
+    if is_flac_file(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))? {
+        return Ok(path.to_path_buf());
+    }
+
+    let metadata = fs::metadata(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    metadata.len().hash(&mut hasher);
+    metadata.modified().ok().hash(&mut hasher);
+    let converted = root.join(format!("{:016x}.flac", hasher.finish()));
+    if converted.exists() {
+        return Ok(converted);
+    }
+
+    let data = fs::read(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    let decoded = audio_codec::decode_audio(&data, None, None)
+        .map_err(|e| format!("{:?}: {}", path.file_name().unwrap_or_default(), e))?;
+    drop(data);
+    // Lossy codecs decode to float, which comes out as full-range 32-bit
+    // samples; FLAC tops out at 24 bits
+    let (samples, bits_per_sample) = if decoded.bits_per_sample > 24 {
+        let shift = decoded.bits_per_sample.min(32) - 24;
+        let samples = decoded.samples.iter().map(|s| s >> shift).collect();
+        (samples, 24)
+    } else {
+        (decoded.samples, decoded.bits_per_sample)
+    };
+    let flac = audio_codec::encode_to_flac(
+        &samples,
+        decoded.sample_rate,
+        decoded.channels,
+        bits_per_sample,
+    )?;
+    fs::create_dir_all(root).map_err(|e| format!("Failed to create {:?}: {}", root, e))?;
+    // Write under a temporary name so a half-written file is never reused
+    let partial = converted.with_extension("partial");
+    fs::write(&partial, &flac).map_err(|e| format!("Failed to write {:?}: {}", partial, e))?;
+    fs::rename(&partial, &converted)
+        .map_err(|e| format!("Failed to write {:?}: {}", converted, e))?;
+    Ok(converted)
+}
+
+/// Stand-in track row for an external file. Titled after the file name and
+/// linked to no release.
+pub(crate) fn external_db_track(track_id: &str, path: &Path, duration_ms: Option<i64>) -> DbTrack {
+    let title = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Unknown file")
+        .to_string();
+    DbTrack {
+        id: track_id.to_string(),
+        release_id: String::new(),
+        title,
+        disc_number: None,
+        track_number: None,
+        duration_ms,
+        discogs_position: None,
+        import_status: ImportStatus::Complete,
+        created_at: chrono::Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_external_track_id_round_trip() {
+        let path = Path::new("/music/loose/01 Intro.flac");
+        let id = external_track_id(path);
+        assert_eq!(id, "external:/music/loose/01 Intro.flac");
+        assert_eq!(external_track_path(&id), Some(path.to_path_buf()));
+        assert!(is_external_track(&id));
+    }
+
+    #[test]
+    fn test_library_ids_are_not_external() {
+        assert!(!is_external_track("2f1c7a9e-52b4-4a1f-9d3e-0c5b1e8f7a21"));
+        assert!(!is_external_track("external:"));
+    }
+
+    /// A 16-bit PCM WAV file holding `samples`
+    fn wav_file(samples: &[i16], sample_rate: u32, channels: u16) -> Vec<u8> {
+        let data_len = (samples.len() * 2) as u32;
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&channels.to_le_bytes());
+        wav.extend_from_slice(&sample_rate.to_le_bytes());
+        wav.extend_from_slice(&(sample_rate * channels as u32 * 2).to_le_bytes());
+        wav.extend_from_slice(&(channels * 2).to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        for sample in samples {
+            wav.extend_from_slice(&sample.to_le_bytes());
+        }
+        wav
+    }
+
+    #[test]
+    fn test_flac_files_play_as_they_are() {
+        audio_codec::init();
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("track.flac");
+        fs::write(
+            &path,
+            audio_codec::encode_to_flac(&[0; 44_100], 44_100, 1, 16).unwrap(),
+        )
+        .unwrap();
+
+        let root = dir.path().join("converted");
+        assert_eq!(playable_flac_path(&path, &root).unwrap(), path);
+        assert!(!root.exists());
+    }
+
+    #[test]
+    fn test_other_formats_are_converted_to_flac() {
+        audio_codec::init();
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("track.wav");
+        fs::write(&path, wav_file(&[0; 44_100 * 2], 44_100, 2)).unwrap();
+        let root = dir.path().join("converted");
+
+        let converted = playable_flac_path(&path, &root).unwrap();
+        assert!(converted.starts_with(&root));
+        assert!(is_flac_file(&converted).unwrap());
+        let decoded =
+            audio_codec::decode_audio(&fs::read(&converted).unwrap(), None, None).unwrap();
+        assert_eq!(decoded.sample_rate, 44_100);
+        assert_eq!(decoded.channels, 2);
+
+        // The conversion is reused
+        assert_eq!(playable_flac_path(&path, &root).unwrap(), converted);
+    }
+
+    #[test]
+    fn test_undecodable_files_are_rejected() {
+        audio_codec::init();
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("notes.txt");
+        fs::write(&path, b"not audio at all").unwrap();
+
+        let err = playable_flac_path(&path, &dir.path().join("converted")).unwrap_err();
+        assert!(err.contains("notes.txt"), "{}", err);
+    }
+
+    #[test]
+    fn test_external_db_track_uses_file_stem() {
+        let path = Path::new("/tmp/Side A.flac");
+        let track = external_db_track(&external_track_id(path), path, Some(1_000));
+        assert_eq!(track.title, "Side A");
+        assert!(track.release_id.is_empty());
+        assert_eq!(track.duration_ms, Some(1_000));
+    }
+}
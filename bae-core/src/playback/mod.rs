@@ -1,19 +1,33 @@
 mod cpal_output;
 pub mod data_source;
 mod error;
+mod exclusive;
+mod external;
+mod output_format;
 mod pcm_source;
+mod pitch;
 pub mod progress;
 pub mod service;
+mod shuffle;
+mod silence;
 pub mod sparse_buffer;
 pub mod streaming_source;
 pub mod track_loader;
 
+pub use cpal_output::output_device_names;
 pub use error::PlaybackError;
+pub use exclusive::{exclusive_mode_supported, ExclusiveError};
+pub use external::{external_track_id, external_track_path, is_external_track};
+pub use output_format::{Dither, OutputBitDepth, OutputFormat, OutputSampleFormat, OutputSettings};
 pub use pcm_source::PcmSource;
 pub use progress::PlaybackProgress;
 pub use service::{PlaybackHandle, PlaybackService, PlaybackState, RepeatMode};
+pub use silence::SkipSilenceSettings;
 pub use sparse_buffer::SharedSparseBuffer;
-pub use streaming_source::{create_streaming_pair, StreamingPcmSink, StreamingPcmSource};
+pub use streaming_source::{
+    create_streaming_pair, create_streaming_pair_with_buffer_ms, BufferSettings, StreamingPcmSink,
+    StreamingPcmSource,
+};
 
 #[cfg(test)]
 pub use streaming_source::create_streaming_pair_with_capacity;
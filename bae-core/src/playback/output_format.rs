@@ -0,0 +1,253 @@
+//! Output sample format negotiation and dithering.
+//!
+//! Playback mixes in f32. The device may want integers; when it does, samples
+//! are quantized at the end of the audio callback. Reducing to 16 bits throws
+//! away resolution the source has, so that step can be dithered.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Sample format requested from the output device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputBitDepth {
+    /// Whatever the device reports as its default
+    Auto,
+    Float32,
+    /// 24-bit samples, sent in a 32-bit container
+    Int24,
+    Int16,
+}
+
+/// Dither applied when quantizing to a 16-bit device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Dither {
+    /// Plain rounding
+    Off,
+    /// Triangular noise of ±1 LSB, decorrelating the error from the signal
+    Tpdf,
+    /// TPDF with first-order error feedback, moving the noise toward high
+    /// frequencies where it's less audible
+    NoiseShaped,
+}
+
+/// Output preferences, applied the next time a stream is created
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputSettings {
+    /// Name of the device to play through. None follows the system default.
+    pub device: Option<String>,
+    pub bit_depth: OutputBitDepth,
+    pub dither: Dither,
+    /// Names of devices to hold exclusively while playing
+    pub exclusive_devices: Vec<String>,
+}
+
+impl Default for OutputSettings {
+    fn default() -> Self {
+        Self {
+            device: None,
+            bit_depth: OutputBitDepth::Auto,
+            dither: Dither::Tpdf,
+            exclusive_devices: Vec::new(),
+        }
+    }
+}
+
+impl OutputSettings {
+    pub fn is_exclusive(&self, device_name: &str) -> bool {
+        self.exclusive_devices.iter().any(|d| d == device_name)
+    }
+}
+
+/// Sample format actually used for the output stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputSampleFormat {
+    F32,
+    /// 24-bit audio in the top bits of an i32
+    I24In32,
+    I16,
+}
+
+impl OutputSampleFormat {
+    pub fn bits(&self) -> u32 {
+        match self {
+            OutputSampleFormat::F32 => 32,
+            OutputSampleFormat::I24In32 => 24,
+            OutputSampleFormat::I16 => 16,
+        }
+    }
+
+    /// Only the reduction to 16 bits is dithered
+    pub fn is_dithered(&self) -> bool {
+        *self == OutputSampleFormat::I16
+    }
+}
+
+/// The format negotiated with the output device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputFormat {
+    pub device_name: String,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub sample_format: OutputSampleFormat,
+    /// Dither in effect: always [`Dither::Off`] unless the output is 16-bit
+    /// and shared
+    pub dither: Dither,
+    /// Whether bae holds the device exclusively
+    pub exclusive: bool,
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let depth = match self.sample_format {
+            OutputSampleFormat::F32 => "32-bit float",
+            OutputSampleFormat::I24In32 => "24-bit",
+            OutputSampleFormat::I16 => "16-bit",
+        };
+        write!(
+            f,
+            "{} Hz, {} ch, {}",
+            self.sample_rate, self.channels, depth
+        )?;
+        if self.exclusive {
+            write!(f, ", exclusive")?;
+        }
+        Ok(())
+    }
+}
+
+/// Converts f32 samples to integers at a fixed bit depth, with optional dither.
+///
+/// Keeps per-channel error state for noise shaping, so one quantizer must be
+/// fed one interleaved stream.
+pub struct Quantizer {
+    bits: u32,
+    scale: f32,
+    min: f32,
+    max: f32,
+    dither: Dither,
+    channels: usize,
+    /// Error of the previous sample on each channel, in LSBs
+    error: Vec<f32>,
+    channel: usize,
+    rng: u32,
+}
+
+impl Quantizer {
+    pub fn new(bits: u32, dither: Dither, channels: usize) -> Self {
+        let scale = (1u32 << (bits - 1)) as f32;
+        let channels = channels.max(1);
+        Self {
+            bits,
+            scale,
+            min: -scale,
+            max: scale - 1.0,
+            dither,
+            channels,
+            error: vec![0.0; channels],
+            channel: 0,
+            rng: 0x9E37_79B9,
+        }
+    }
+
+    /// Quantize one sample, in LSBs of the target depth
+    pub fn quantize(&mut self, sample: f32) -> i32 {
+        let target = sample * self.scale;
+        let value = match self.dither {
+            Dither::Off => target.round(),
+            Dither::Tpdf => (target + self.tpdf()).round(),
+            Dither::NoiseShaped => {
+                let shaped = target - self.error[self.channel];
+                let value = (shaped + self.tpdf()).round();
+                self.error[self.channel] = value - shaped;
+                value
+            }
+        };
+        self.channel = (self.channel + 1) % self.channels;
+        value.clamp(self.min, self.max) as i32
+    }
+
+    pub fn quantize_i16(&mut self, input: &[f32], output: &mut [i16]) {
+        for (out, &sample) in output.iter_mut().zip(input) {
+            *out = self.quantize(sample) as i16;
+        }
+    }
+
+    /// Quantize to the quantizer's depth and left-align in an i32
+    pub fn quantize_i32(&mut self, input: &[f32], output: &mut [i32]) {
+        let shift = 32 - self.bits;
+        for (out, &sample) in output.iter_mut().zip(input) {
+            *out = self.quantize(sample) << shift;
+        }
+    }
+
+    /// Triangular noise in (-1, 1) LSB: the difference of two uniform values
+    fn tpdf(&mut self) -> f32 {
+        self.next_uniform() - self.next_uniform()
+    }
+
+    /// xorshift32; cheap enough for the audio callback and needs no locking
+    fn next_uniform(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        (self.rng >> 8) as f32 / (1u32 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undithered_rounds_and_clips() {
+        let mut q = Quantizer::new(16, Dither::Off, 1);
+        assert_eq!(q.quantize(0.5), 16384);
+        assert_eq!(q.quantize(1.0), 32767);
+        assert_eq!(q.quantize(-1.5), -32768);
+        assert_eq!(q.quantize(0.0), 0);
+    }
+
+    #[test]
+    fn test_24_bit_is_left_aligned() {
+        let mut q = Quantizer::new(24, Dither::Off, 2);
+        let mut out = [0i32; 2];
+        q.quantize_i32(&[1.0, -1.0], &mut out);
+        assert_eq!(out, [0x7F_FFFF << 8, i32::MIN]);
+    }
+
+    #[test]
+    fn test_tpdf_stays_within_one_lsb_and_averages_out() {
+        let mut q = Quantizer::new(16, Dither::Tpdf, 2);
+        let samples: Vec<i32> = (0..10_000).map(|_| q.quantize(0.0)).collect();
+
+        assert!(samples.iter().all(|s| s.abs() <= 1));
+        assert!(samples.iter().any(|&s| s != 0));
+        let mean = samples.iter().sum::<i32>() as f64 / samples.len() as f64;
+        assert!(mean.abs() < 0.05, "mean {}", mean);
+    }
+
+    #[test]
+    fn test_exclusive_is_per_device() {
+        let settings = OutputSettings {
+            exclusive_devices: vec!["USB DAC".to_string()],
+            ..OutputSettings::default()
+        };
+        assert!(settings.is_exclusive("USB DAC"));
+        assert!(!settings.is_exclusive("Built-in Output"));
+    }
+
+    #[test]
+    fn test_noise_shaping_preserves_sub_lsb_level() {
+        // A DC level of a quarter LSB is lost by plain rounding; dither keeps
+        // it as the average of the output
+        let level = 0.25 / 32768.0;
+        let mut q = Quantizer::new(16, Dither::NoiseShaped, 1);
+        let samples: Vec<i32> = (0..20_000).map(|_| q.quantize(level)).collect();
+
+        let mean = samples.iter().sum::<i32>() as f64 / samples.len() as f64;
+        assert!((mean - 0.25).abs() < 0.05, "mean {}", mean);
+        assert_eq!(Quantizer::new(16, Dither::Off, 1).quantize(level), 0);
+    }
+}
@@ -0,0 +1,399 @@
+//! Pitch shifting for practice: transposing by semitones at the same tempo.
+//!
+//! Two stages, run on decoded samples as they're pulled into the audio
+//! callback. A windowed-sinc resampler reads the input faster (or slower) by
+//! the pitch ratio, which raises (or lowers) the pitch but also shortens (or
+//! lengthens) the audio. WSOLA then stretches it back to its original length
+//! by overlapping short segments, each picked from a small search range to
+//! line up with the one before it. Neither stage is bit-exact, so a shift of
+//! zero bypasses both.
+
+use std::f64::consts::PI;
+
+/// Furthest shift either way, in semitones
+pub const MAX_SEMITONES: i32 = 12;
+
+/// Zero crossings of the sinc kernel on each side of its center
+const SINC_ZERO_CROSSINGS: usize = 16;
+/// Kernel table entries per zero crossing
+const SINC_TABLE_STEPS: usize = 256;
+/// Share of the band kept by the resampler's low-pass, leaving room for its
+/// transition below Nyquist
+const SINC_BANDWIDTH: f64 = 0.95;
+/// Length of the segments WSOLA overlaps
+const SEGMENT_MS: u32 = 40;
+/// How far a segment may move from where it ideally starts to line up with
+/// the previous one
+const TOLERANCE_MS: u32 = 10;
+/// Offsets tried in the first pass of the alignment search; the best one is
+/// refined at every offset around it
+const COARSE_SEARCH_STEP: usize = 4;
+
+/// Frequency ratio of a shift by `semitones`
+pub fn pitch_ratio(semitones: i32) -> f64 {
+    2f64.powf(semitones as f64 / 12.0)
+}
+
+/// Shifts the pitch of one interleaved stream, keeping its length.
+///
+/// Keeps look-ahead and overlap state across calls, so one shifter must be
+/// fed one stream. The output lags the input by up to a segment and a half
+/// plus the search tolerance, scaled by the pitch ratio (140 ms an octave
+/// up): the first calls give back less than they're given.
+pub struct PitchShifter {
+    semitones: i32,
+    resampler: SincResampler,
+    stretcher: Wsola,
+    resampled: Vec<f32>,
+}
+
+impl PitchShifter {
+    pub fn new(semitones: i32, sample_rate: u32, channels: usize) -> Self {
+        let semitones = semitones.clamp(-MAX_SEMITONES, MAX_SEMITONES);
+        let ratio = pitch_ratio(semitones);
+        let channels = channels.max(1);
+        Self {
+            semitones,
+            resampler: SincResampler::new(ratio, channels),
+            stretcher: Wsola::new(ratio, sample_rate, channels),
+            resampled: Vec::new(),
+        }
+    }
+
+    pub fn semitones(&self) -> i32 {
+        self.semitones
+    }
+
+    /// Replace `samples` with the shifted audio that's ready so far
+    pub fn process(&mut self, samples: &mut Vec<f32>) {
+        self.resampled.clear();
+        self.resampler.process(samples, &mut self.resampled);
+        samples.clear();
+        self.stretcher.process(&self.resampled, samples);
+    }
+}
+
+/// Band-limited resampling by a fixed ratio, interpolating with a
+/// Blackman-Harris windowed sinc
+struct SincResampler {
+    channels: usize,
+    /// Input frames advanced per output frame
+    step: f64,
+    /// Low-pass cutoff as a share of the input's Nyquist. Below one when
+    /// reading faster, so content above the output's Nyquist is removed
+    /// instead of folding back down.
+    cutoff: f64,
+    /// Input frames the kernel reaches on each side
+    reach: f64,
+    /// Windowed sinc from 0 to [`SINC_ZERO_CROSSINGS`]
+    table: Vec<f32>,
+    /// Input still in reach of upcoming output frames, interleaved
+    buffer: Vec<f32>,
+    /// Where the next output frame falls in `buffer`, in frames
+    pos: f64,
+    weights: Vec<f32>,
+}
+
+impl SincResampler {
+    fn new(step: f64, channels: usize) -> Self {
+        let cutoff = SINC_BANDWIDTH * (1.0 / step).min(1.0);
+        let reach = SINC_ZERO_CROSSINGS as f64 / cutoff;
+        let table = (0..=SINC_ZERO_CROSSINGS * SINC_TABLE_STEPS)
+            .map(|i| {
+                let x = i as f64 / SINC_TABLE_STEPS as f64;
+                (sinc(x) * blackman_harris(x / SINC_ZERO_CROSSINGS as f64)) as f32
+            })
+            .collect();
+        // Silence before the first frame, so the kernel has history to reach
+        let history = reach.ceil() as usize;
+        Self {
+            channels,
+            step,
+            cutoff,
+            reach,
+            table,
+            buffer: vec![0.0; history * channels],
+            pos: history as f64,
+            weights: Vec::new(),
+        }
+    }
+
+    fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        let channels = self.channels;
+        self.buffer.extend_from_slice(input);
+        let frames = self.buffer.len() / channels;
+
+        while self.pos + self.reach < frames as f64 {
+            let first = (self.pos - self.reach).ceil().max(0.0) as usize;
+            let last = (self.pos + self.reach).floor() as usize;
+            self.weights.clear();
+            for k in first..=last {
+                let weight = self.kernel(self.pos - k as f64);
+                self.weights.push(weight);
+            }
+            for channel in 0..channels {
+                let sample = self
+                    .weights
+                    .iter()
+                    .enumerate()
+                    .map(|(i, w)| w * self.buffer[(first + i) * channels + channel])
+                    .sum();
+                output.push(sample);
+            }
+            self.pos += self.step;
+        }
+
+        // Keep only what the kernel can still reach
+        let consumed = (self.pos - self.reach).floor().max(0.0) as usize;
+        if consumed > 0 {
+            self.buffer.drain(..consumed * channels);
+            self.pos -= consumed as f64;
+        }
+    }
+
+    /// Kernel weight at `offset` input frames from the output frame
+    fn kernel(&self, offset: f64) -> f32 {
+        let x = offset.abs() * self.cutoff * SINC_TABLE_STEPS as f64;
+        let index = x as usize;
+        if index + 1 >= self.table.len() {
+            return 0.0;
+        }
+        let frac = (x - index as f64) as f32;
+        let value = self.table[index] + (self.table[index + 1] - self.table[index]) * frac;
+        value * self.cutoff as f32
+    }
+}
+
+/// Time stretching by waveform-similarity overlap-add.
+///
+/// Output is built from Hann-windowed segments overlapping by half. Segments
+/// are taken from the input at a different spacing than they're laid down
+/// at, which changes the length; each one may move within
+/// [`TOLERANCE_MS`] of its ideal start to wherever it best continues the
+/// previous segment, which keeps the overlaps from beating.
+struct Wsola {
+    channels: usize,
+    /// Frames per segment; even, so it splits into two overlaps
+    segment: usize,
+    /// Frames each segment overlaps the next by, and the output step
+    overlap: usize,
+    tolerance: usize,
+    /// Input frames between ideal segment starts
+    input_step: f64,
+    window: Vec<f32>,
+    /// Input not yet fully used, interleaved
+    input: Vec<f32>,
+    /// `input` summed across channels, for the alignment search
+    mono: Vec<f32>,
+    /// Ideal start of the next segment in `input`, in frames
+    next_start: f64,
+    /// Where the previous segment would have carried on, in `input`
+    continuation: Option<usize>,
+    /// Windowed second half of the previous segment, waiting for the next
+    tail: Vec<f32>,
+}
+
+impl Wsola {
+    fn new(stretch: f64, sample_rate: u32, channels: usize) -> Self {
+        let segment = ((sample_rate * SEGMENT_MS / 1000) as usize / 2 * 2).max(2);
+        let overlap = segment / 2;
+        let tolerance = (sample_rate * TOLERANCE_MS / 1000) as usize;
+        // Periodic Hann: the halves of overlapping windows add up to one
+        let window = (0..segment)
+            .map(|i| (0.5 - 0.5 * (2.0 * PI * i as f64 / segment as f64).cos()) as f32)
+            .collect();
+        Self {
+            channels,
+            segment,
+            overlap,
+            tolerance,
+            input_step: overlap as f64 / stretch,
+            window,
+            input: Vec::new(),
+            mono: Vec::new(),
+            next_start: tolerance as f64,
+            continuation: None,
+            tail: vec![0.0; overlap * channels],
+        }
+    }
+
+    fn process(&mut self, input: &[f32], output: &mut Vec<f32>) {
+        let channels = self.channels;
+        self.input.extend_from_slice(input);
+        self.mono.extend(
+            input
+                .chunks_exact(channels)
+                .map(|frame| frame.iter().sum::<f32>()),
+        );
+
+        loop {
+            let ideal = self.next_start.round() as usize;
+            if ideal + self.tolerance + self.segment > self.mono.len() {
+                break;
+            }
+            let start = match self.continuation {
+                Some(continuation) => self.best_start(continuation, ideal),
+                None => ideal,
+            };
+
+            for i in 0..self.overlap {
+                let weight = self.window[i];
+                for channel in 0..channels {
+                    let sample = self.input[(start + i) * channels + channel];
+                    output.push(self.tail[i * channels + channel] + sample * weight);
+                }
+            }
+            for i in 0..self.overlap {
+                let weight = self.window[self.overlap + i];
+                for channel in 0..channels {
+                    self.tail[i * channels + channel] =
+                        self.input[(start + self.overlap + i) * channels + channel] * weight;
+                }
+            }
+            self.continuation = Some(start + self.overlap);
+            self.next_start += self.input_step;
+
+            // Keep the next search range and the continuation to compare with
+            let consumed =
+                (self.next_start.round() as usize - self.tolerance).min(start + self.overlap);
+            if consumed > 0 {
+                self.input.drain(..consumed * channels);
+                self.mono.drain(..consumed);
+                self.next_start -= consumed as f64;
+                self.continuation = Some(start + self.overlap - consumed);
+            }
+        }
+    }
+
+    /// Start within tolerance of `ideal` whose opening best matches what
+    /// follows the previous segment at `continuation`
+    fn best_start(&self, continuation: usize, ideal: usize) -> usize {
+        let reference = &self.mono[continuation..continuation + self.overlap];
+        let lowest = ideal - self.tolerance;
+        let highest = ideal + self.tolerance;
+        let score = |start: usize| similarity(reference, &self.mono[start..start + self.overlap]);
+
+        let coarse = (lowest..=highest)
+            .step_by(COARSE_SEARCH_STEP)
+            .map(|start| (start, score(start)))
+            .fold(
+                (ideal, f32::MIN),
+                |best, c| if c.1 > best.1 { c } else { best },
+            );
+        let refine_low = coarse.0.saturating_sub(COARSE_SEARCH_STEP - 1).max(lowest);
+        let refine_high = (coarse.0 + COARSE_SEARCH_STEP - 1).min(highest);
+        (refine_low..=refine_high)
+            .map(|start| (start, score(start)))
+            .fold(coarse, |best, c| if c.1 > best.1 { c } else { best })
+            .0
+    }
+}
+
+/// Cross-correlation of `candidate` with `reference`, normalized by the
+/// candidate's energy so loud stretches don't win just for being loud.
+/// Every other frame is compared; the search doesn't need more.
+fn similarity(reference: &[f32], candidate: &[f32]) -> f32 {
+    let (mut correlation, mut energy) = (0.0f32, 0.0f32);
+    for (r, c) in reference.iter().zip(candidate).step_by(2) {
+        correlation += r * c;
+        energy += c * c;
+    }
+    correlation / (energy + 1e-9).sqrt()
+}
+
+fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+/// Four-term Blackman-Harris window over -1..1
+fn blackman_harris(x: f64) -> f64 {
+    let t = PI * x;
+    0.35875 + 0.48829 * t.cos() + 0.14128 * (2.0 * t).cos() + 0.01168 * (3.0 * t).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 44_100;
+
+    fn sine(frequency: f64, frames: usize) -> Vec<f32> {
+        (0..frames)
+            .flat_map(|i| {
+                let s = (2.0 * PI * frequency * i as f64 / RATE as f64).sin() as f32 * 0.5;
+                [s, s]
+            })
+            .collect()
+    }
+
+    /// Feed `input` through in callback-sized chunks
+    fn shift(semitones: i32, input: &[f32]) -> Vec<f32> {
+        let mut shifter = PitchShifter::new(semitones, RATE, 2);
+        let mut output = Vec::new();
+        for chunk in input.chunks(1024) {
+            let mut samples = chunk.to_vec();
+            shifter.process(&mut samples);
+            output.extend(samples);
+        }
+        output
+    }
+
+    /// Rising zero crossings per second in the left channel
+    fn frequency(samples: &[f32]) -> f64 {
+        let left: Vec<f32> = samples.iter().step_by(2).copied().collect();
+        let rising = left
+            .windows(2)
+            .filter(|w| w[0] < 0.0 && w[1] >= 0.0)
+            .count();
+        rising as f64 * RATE as f64 / left.len() as f64
+    }
+
+    #[test]
+    fn test_pitch_ratio() {
+        assert_eq!(pitch_ratio(0), 1.0);
+        assert!((pitch_ratio(12) - 2.0).abs() < 1e-9);
+        assert!((pitch_ratio(-12) - 0.5).abs() < 1e-9);
+        assert!((pitch_ratio(7) - 1.4983).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_shift_changes_pitch() {
+        let input = sine(440.0, RATE as usize * 2);
+        for (semitones, expected) in [(12, 880.0), (-12, 220.0), (5, 440.0 * pitch_ratio(5))] {
+            let output = shift(semitones, &input);
+            // Past the fade-in, clear of the tail still in the shifter
+            let steady = &output[RATE as usize / 2 * 2..RATE as usize * 3 / 2 * 2];
+            let measured = frequency(steady);
+            assert!(
+                (measured - expected).abs() < expected * 0.02,
+                "{} semitones: {} Hz, expected {} Hz",
+                semitones,
+                measured,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_shift_keeps_length() {
+        let input = sine(440.0, RATE as usize * 2);
+        for semitones in [-MAX_SEMITONES, -3, 4, MAX_SEMITONES] {
+            let output = shift(semitones, &input);
+            // Short by at most what's held back for look-ahead and overlap,
+            // plus the last chunk
+            let held_ms = (SEGMENT_MS * 3 / 2 + TOLERANCE_MS) as f64 * pitch_ratio(semitones);
+            let max_lag = (held_ms * RATE as f64 / 1000.0) as usize * 2 + 1024;
+            let lag = input.len() as i64 - output.len() as i64;
+            assert!(
+                (0..max_lag as i64).contains(&lag),
+                "{} semitones: {} samples short",
+                semitones,
+                lag
+            );
+        }
+    }
+}
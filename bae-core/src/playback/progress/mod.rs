@@ -1,5 +1,6 @@
 pub mod handle;
 use crate::playback::service::{PlaybackState, RepeatMode};
+use crate::playback::OutputFormat;
 pub use handle::PlaybackProgressHandle;
 use std::time::Duration;
 /// Progress updates during playback
@@ -36,10 +37,22 @@ pub enum PlaybackProgress {
     QueueUpdated {
         tracks: Vec<String>,
     },
+    /// Tracks that played before the current one, most recent first
+    HistoryUpdated {
+        tracks: Vec<String>,
+    },
     /// Repeat mode changed
     RepeatModeChanged {
         mode: RepeatMode,
     },
+    /// Shuffle turned on or off
+    ShuffleChanged {
+        enabled: bool,
+    },
+    /// Pitch shift changed; zero when off
+    PitchChanged {
+        semitones: i32,
+    },
     /// Playback error occurred (e.g. storage offline)
     PlaybackError {
         message: String,
@@ -53,4 +66,9 @@ pub enum PlaybackProgress {
         /// Total samples decoded (to verify audio was actually produced)
         samples_decoded: u64,
     },
+    /// Format negotiated with the output device. Sent when a stream starts
+    /// and when output settings change (those apply from the next stream).
+    OutputFormatChanged {
+        format: OutputFormat,
+    },
 }
@@ -14,30 +14,49 @@
 //!
 //! 1. Cancel old streaming source (makes callback output silence)
 //! 2. Create new seek buffer (local: fresh file reader, cloud: fresh range request)
+//!    and cancel the downloads feeding the old one
 //! 3. Spawn decoder on seek buffer
 //! 4. Wait for buffer to be 50% full
 //! 5. Call `init_streaming()` which drops old stream and creates new one
 //! 6. State remains unchanged (Playing or Paused) - new stream inherits it
 //! 7. Send `Seeked` progress event
 
+use crate::cache::CacheManager;
 use crate::cloud_storage::CloudStorage;
+use crate::cue_flac::CueFlacProcessor;
 use crate::db::DbTrack;
 use crate::encryption::EncryptionService;
+use crate::library::offline::file_cache_key;
 use crate::library::LibraryManager;
 use crate::playback::cpal_output::AudioOutput;
 use crate::playback::data_source::{
     AudioDataReader, AudioReadConfig, CloudStorageReader, LocalFileReader,
+    DEFAULT_DOWNLOAD_CONCURRENCY,
 };
 use crate::playback::error::PlaybackError;
+use crate::playback::external::{
+    conversion_root, external_db_track, external_track_path, playable_flac_path,
+};
 use crate::playback::progress::{PlaybackProgress, PlaybackProgressHandle};
+use crate::playback::shuffle::{shuffle, unshuffle};
 use crate::playback::sparse_buffer::{create_sparse_buffer, SharedSparseBuffer};
-use crate::playback::{create_streaming_pair, StreamingPcmSource};
-use crate::storage::create_storage_reader;
+use crate::playback::{
+    create_streaming_pair_with_buffer_ms, BufferSettings, OutputSettings, SkipSilenceSettings,
+    StreamingPcmSource,
+};
+use crate::storage::{create_storage_reader, resolve_local_path, LocalFileStorage};
 use cpal::traits::StreamTrait;
 use std::collections::VecDeque;
 use std::sync::{mpsc, Arc, Mutex};
 use tokio::sync::mpsc as tokio_mpsc;
-use tracing::{error, info, trace};
+use tracing::{error, info, trace, warn};
+
+/// Played tracks kept for going back and for the history view
+const HISTORY_LIMIT: usize = 200;
+
+/// How often the output device is checked for having gone away or changed
+const DEVICE_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
 /// Repeat mode for playback
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum RepeatMode {
@@ -62,6 +81,8 @@ pub enum PlaybackCommand {
     Previous,
     Seek(std::time::Duration),
     SetVolume(f32),
+    /// Transpose by semitones, keeping tempo. Zero turns it off.
+    SetPitch(i32),
     AddToQueue(Vec<String>),
     AddNext(Vec<String>),
     RemoveFromQueue(usize),
@@ -71,7 +92,25 @@ pub enum PlaybackCommand {
     },
     ClearQueue,
     GetQueue,
+    ClearHistory,
     SetRepeatMode(RepeatMode),
+    /// Shuffle the upcoming queue, or restore its order
+    SetShuffle(bool),
+    /// Max parallel range requests when streaming from cloud storage
+    SetDownloadConcurrency(usize),
+    /// Decode-ahead and gapless pre-roll durations
+    SetBufferSettings(BufferSettings),
+    /// Output device, bit depth and dither. A new device takes over the
+    /// current stream; the rest apply from the next one.
+    SetOutputSettings(OutputSettings),
+    /// Skip long silences, from the next stream
+    SetSkipSilence(SkipSilenceSettings),
+    /// Whether the next track is preloaded when it's in cloud storage
+    SetCloudPrefetch(bool),
+    /// Open the output device again and carry on playing through it
+    ReopenOutput,
+    /// Cache to play pinned offline copies from, instead of the cloud
+    SetOfflineCache(CacheManager),
 }
 /// Current playback state
 #[derive(Debug, Clone)]
@@ -105,6 +144,8 @@ pub enum PlaybackState {
 pub struct PlaybackHandle {
     command_tx: tokio_mpsc::UnboundedSender<PlaybackCommand>,
     progress_handle: PlaybackProgressHandle,
+    /// Why the output device couldn't be opened, if it couldn't
+    output_error: Arc<Mutex<Option<String>>>,
 }
 impl PlaybackHandle {
     pub fn play(&self, track_id: String) {
@@ -134,6 +175,9 @@ impl PlaybackHandle {
     pub fn set_volume(&self, volume: f32) {
         let _ = self.command_tx.send(PlaybackCommand::SetVolume(volume));
     }
+    pub fn set_pitch(&self, semitones: i32) {
+        let _ = self.command_tx.send(PlaybackCommand::SetPitch(semitones));
+    }
     pub async fn get_state(&self) -> PlaybackState {
         PlaybackState::Stopped
     }
@@ -162,9 +206,52 @@ impl PlaybackHandle {
     pub fn get_queue(&self) {
         let _ = self.command_tx.send(PlaybackCommand::GetQueue);
     }
+    pub fn clear_history(&self) {
+        let _ = self.command_tx.send(PlaybackCommand::ClearHistory);
+    }
     pub fn set_repeat_mode(&self, mode: RepeatMode) {
         let _ = self.command_tx.send(PlaybackCommand::SetRepeatMode(mode));
     }
+    pub fn set_shuffle(&self, enabled: bool) {
+        let _ = self.command_tx.send(PlaybackCommand::SetShuffle(enabled));
+    }
+    pub fn set_download_concurrency(&self, concurrency: usize) {
+        let _ = self
+            .command_tx
+            .send(PlaybackCommand::SetDownloadConcurrency(concurrency));
+    }
+    pub fn set_buffer_settings(&self, settings: BufferSettings) {
+        let _ = self
+            .command_tx
+            .send(PlaybackCommand::SetBufferSettings(settings));
+    }
+    pub fn set_output_settings(&self, settings: OutputSettings) {
+        let _ = self
+            .command_tx
+            .send(PlaybackCommand::SetOutputSettings(settings));
+    }
+    pub fn set_skip_silence(&self, settings: SkipSilenceSettings) {
+        let _ = self
+            .command_tx
+            .send(PlaybackCommand::SetSkipSilence(settings));
+    }
+    pub fn set_cloud_prefetch(&self, enabled: bool) {
+        let _ = self
+            .command_tx
+            .send(PlaybackCommand::SetCloudPrefetch(enabled));
+    }
+    pub fn reopen_output(&self) {
+        let _ = self.command_tx.send(PlaybackCommand::ReopenOutput);
+    }
+    pub fn set_offline_cache(&self, cache: CacheManager) {
+        let _ = self
+            .command_tx
+            .send(PlaybackCommand::SetOfflineCache(cache));
+    }
+    /// Why the output device couldn't be opened. None while it's working.
+    pub fn output_error(&self) -> Option<String> {
+        self.output_error.lock().unwrap().clone()
+    }
 }
 
 /// Prepared track data for playback.
@@ -185,6 +272,8 @@ struct PreparedTrack {
     file_size: u64,
     /// Source path for re-reading on seek past buffer
     source_path: String,
+    /// Size of the stored source file, bounds cloud range requests on seek
+    source_file_size: u64,
     /// Pre-gap duration in ms (for CUE/FLAC tracks)
     pregap_ms: Option<i64>,
     /// Track duration from metadata
@@ -211,8 +300,14 @@ struct PreparedTrack {
 async fn prepare_track(
     library_manager: &LibraryManager,
     encryption_service: Option<&EncryptionService>,
+    offline_cache: Option<&CacheManager>,
     track_id: &str,
+    download_concurrency: usize,
 ) -> Result<PreparedTrack, PlaybackError> {
+    if let Some(path) = external_track_path(track_id) {
+        return prepare_external_track(track_id, &path).await;
+    }
+
     let track = library_manager
         .get_track(track_id)
         .await
@@ -245,6 +340,30 @@ async fn prepare_track(
         .source_path
         .ok_or_else(|| PlaybackError::not_found("source_path", track_id))?;
 
+    // A pinned offline copy holds the stored bytes, so it reads the same way
+    // as the cloud object, just from disk
+    let pinned_path = match (offline_cache, &storage_profile) {
+        (Some(cache), Some(profile)) if profile.location == crate::db::StorageLocation::Cloud => {
+            cache.pinned_path(&file_cache_key(&audio_file.id)).await
+        }
+        _ => None,
+    };
+    let is_pinned = pinned_path.is_some();
+    let source_path = match pinned_path {
+        Some(path) => {
+            info!("Playing offline copy of {}", audio_file.id);
+            path.to_string_lossy().to_string()
+        }
+        None => match &storage_profile {
+            Some(profile) if profile.location == crate::db::StorageLocation::Local => {
+                resolve_local_path(&profile.local_roots(), &source_path)
+                    .to_string_lossy()
+                    .to_string()
+            }
+            _ => source_path,
+        },
+    };
+
     let pregap_ms = audio_format.pregap_ms;
 
     let (start_byte, end_byte) =
@@ -297,28 +416,33 @@ async fn prepare_track(
                 None,
                 false,
             ),
-            Some(profile)
-                if !profile.encrypted && profile.location == crate::db::StorageLocation::Local =>
-            {
-                (
-                    Box::new(LocalFileReader::new(read_config)),
-                    true,
-                    None,
-                    false,
-                )
-            }
+            Some(profile) if !profile.encrypted && profile.location.is_on_disk() => (
+                Box::new(LocalFileReader::new(read_config)),
+                true,
+                None,
+                false,
+            ),
             Some(profile) => {
-                let storage = create_storage_reader(profile)
-                    .await
-                    .map_err(PlaybackError::cloud)?;
+                let storage: Arc<dyn CloudStorage> = if is_pinned {
+                    Arc::new(LocalFileStorage::default())
+                } else {
+                    create_storage_reader(profile)
+                        .await
+                        .map_err(PlaybackError::cloud)?
+                };
                 let encrypted = profile.encrypted;
                 (
-                    Box::new(CloudStorageReader::new(
-                        read_config,
-                        storage.clone(),
-                        encryption_service.map(|e| Arc::new(e.clone())),
-                        encrypted,
-                    )),
+                    Box::new(
+                        CloudStorageReader::new(
+                            read_config,
+                            storage.clone(),
+                            encryption_service.map(|e| Arc::new(e.clone())),
+                            encrypted,
+                        )
+                        .with_encryption_nonce(audio_file.encryption_nonce.clone())
+                        .with_file_size(audio_file.file_size as u64)
+                        .with_download_concurrency(download_concurrency),
+                    ),
                     false,
                     Some(storage),
                     encrypted,
@@ -350,6 +474,7 @@ async fn prepare_track(
         audio_data_start,
         file_size: file_size + headers_len,
         source_path,
+        source_file_size: audio_file.file_size as u64,
         pregap_ms,
         duration,
         is_local_storage,
@@ -361,6 +486,98 @@ async fn prepare_track(
     })
 }
 
+/// Prepare a file opened from disk. Nothing is stored in the library, so the
+/// FLAC headers and seektable are built here the same way import builds them.
+/// Files in other formats are converted to FLAC first.
+async fn prepare_external_track(
+    track_id: &str,
+    path: &std::path::Path,
+) -> Result<PreparedTrack, PlaybackError> {
+    let original_path = path.to_path_buf();
+    let (flac_path, flac_headers, flac_info, seektable_json, source_file_size) =
+        tokio::task::spawn_blocking(move || {
+            let flac_path = playable_flac_path(&original_path, &conversion_root())
+                .map_err(PlaybackError::unsupported)?;
+            let flac_headers = CueFlacProcessor::extract_flac_headers(&flac_path)
+                .ok()
+                .map(|h| h.headers);
+            let file_data = std::fs::read(&flac_path)?;
+            let flac_info = CueFlacProcessor::analyze_flac(&flac_path)
+                .map_err(|e| PlaybackError::flac(e.to_string()))?;
+            let seektable = CueFlacProcessor::build_dense_seektable(&file_data, &flac_info);
+            let seektable_json = serde_json::to_string(&seektable.entries)
+                .map_err(|e| PlaybackError::flac(e.to_string()))?;
+            Ok::<_, PlaybackError>((
+                flac_path,
+                flac_headers,
+                flac_info,
+                seektable_json,
+                file_data.len() as u64,
+            ))
+        })
+        .await
+        .map_err(PlaybackError::task)??;
+
+    let duration_ms = flac_info.duration_ms();
+    let track = external_db_track(track_id, path, Some(duration_ms as i64));
+    let source_path = flac_path.to_string_lossy().to_string();
+
+    let buffer = create_sparse_buffer();
+    let reader: Box<dyn AudioDataReader> = Box::new(LocalFileReader::new(AudioReadConfig {
+        path: source_path.clone(),
+        flac_headers: None,
+        start_byte: None,
+        end_byte: None,
+    }));
+    reader.start_reading(buffer.clone());
+
+    Ok(PreparedTrack {
+        track,
+        buffer,
+        flac_headers,
+        seektable_json,
+        sample_rate: flac_info.sample_rate,
+        audio_data_start: flac_info.audio_data_start,
+        file_size: source_file_size,
+        source_path,
+        source_file_size,
+        pregap_ms: None,
+        duration: std::time::Duration::from_millis(duration_ms),
+        is_local_storage: true,
+        track_start_byte_offset: None,
+        track_end_byte_offset: None,
+        cloud_storage: None,
+        cloud_encrypted: false,
+        encryption_nonce: None,
+    })
+}
+
+/// Whether a track streams from cloud storage rather than local disk
+async fn is_cloud_track(library_manager: &LibraryManager, track_id: &str) -> bool {
+    if external_track_path(track_id).is_some() {
+        return false;
+    }
+    let Ok(Some(track)) = library_manager.get_track(track_id).await else {
+        return false;
+    };
+    matches!(
+        library_manager
+            .get_storage_profile_for_release(&track.release_id)
+            .await,
+        Ok(Some(profile)) if !profile.location.is_on_disk()
+    )
+}
+
+/// Whether the next track may be preloaded. With cloud prefetch off (metered
+/// mode), only tracks on local disk are.
+async fn preload_allowed(
+    library_manager: &LibraryManager,
+    cloud_prefetch: bool,
+    track_id: &str,
+) -> bool {
+    cloud_prefetch || !is_cloud_track(library_manager, track_id).await
+}
+
 /// Playback service that manages audio playback
 pub struct PlaybackService {
     library_manager: LibraryManager,
@@ -368,7 +585,8 @@ pub struct PlaybackService {
     command_rx: tokio_mpsc::UnboundedReceiver<PlaybackCommand>,
     progress_tx: tokio_mpsc::UnboundedSender<PlaybackProgress>,
     queue: VecDeque<String>,
-    previous_track_id: Option<String>,
+    /// Tracks that played before the current one, oldest first
+    history: Vec<String>,
     current_position_shared: Arc<std::sync::Mutex<Option<std::time::Duration>>>,
     /// Generation counter to invalidate old position listeners after seek
     position_generation: Arc<std::sync::atomic::AtomicU64>,
@@ -378,11 +596,32 @@ pub struct PlaybackService {
     current_prepared: Option<PreparedTrack>,
     /// Current streaming source (decoder output)
     current_streaming_source: Option<Arc<Mutex<StreamingPcmSource>>>,
+    /// Track position the current source starts at
+    current_position_offset: std::time::Duration,
+    /// Buffer the current track is decoded from after a seek
+    seek_buffer: Option<SharedSparseBuffer>,
     /// Preloaded next track prepared data
     next_prepared: Option<PreparedTrack>,
     /// Preloaded next track streaming source (decoder already started)
     next_streaming_source: Option<Arc<Mutex<StreamingPcmSource>>>,
     repeat_mode: RepeatMode,
+    shuffle: bool,
+    /// Queue order from before shuffling, restored when shuffle is turned off
+    unshuffled: Vec<String>,
+    /// Max parallel range requests when streaming from cloud storage
+    download_concurrency: usize,
+    /// Decode-ahead and gapless pre-roll durations for new decoders
+    buffer_settings: BufferSettings,
+    skip_silence: SkipSilenceSettings,
+    /// Preload the next track when it's in cloud storage. Off in metered mode.
+    cloud_prefetch: bool,
+    /// Shared with the handle, for the health panel
+    output_error: Arc<Mutex<Option<String>>>,
+    /// Output devices present when opening one last failed. Opening is only
+    /// tried again by itself once this list changes.
+    devices_at_failure: Vec<String>,
+    /// Where pinned offline copies of cloud files are kept
+    offline_cache: Option<CacheManager>,
 }
 
 impl PlaybackService {
@@ -455,6 +694,14 @@ impl PlaybackService {
             }
         });
 
+        if let Err(e) = self.audio_output.prepare_exclusive(source_sample_rate) {
+            warn!("Exclusive mode unavailable, playing shared: {}", e);
+
+            let _ = self.progress_tx.send(PlaybackProgress::PlaybackError {
+                message: format!("Exclusive mode unavailable: {}", e),
+            });
+        }
+
         // Create streaming audio output
         let stream = match self.audio_output.create_stream(
             source.clone(),
@@ -462,6 +709,7 @@ impl PlaybackService {
             source_channels,
             position_tx,
             completion_tx,
+            self.skip_silence,
         ) {
             Ok(stream) => stream,
             Err(e) => {
@@ -474,10 +722,12 @@ impl PlaybackService {
             error!("Failed to start streaming playback: {:?}", e);
             return false;
         }
+        self.send_output_format();
 
         // Update state
         self.stream = Some(stream);
         self.current_streaming_source = Some(source.clone());
+        self.current_position_offset = position_offset;
         *self.current_position_shared.lock().unwrap() = Some(position_offset);
 
         // Spawn position/completion listener
@@ -537,9 +787,11 @@ impl PlaybackService {
         let (command_tx, command_rx) = tokio_mpsc::unbounded_channel();
         let (progress_tx, progress_rx) = tokio_mpsc::unbounded_channel();
         let progress_handle = PlaybackProgressHandle::new(progress_rx, runtime_handle.clone());
+        let output_error = Arc::new(Mutex::new(None));
         let handle = PlaybackHandle {
             command_tx: command_tx.clone(),
             progress_handle: progress_handle.clone(),
+            output_error: output_error.clone(),
         };
         let command_tx_for_completion = command_tx.clone();
         let progress_handle_for_completion = progress_handle.clone();
@@ -558,29 +810,53 @@ impl PlaybackService {
         std::thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().expect("Failed to create runtime");
             rt.block_on(async move {
-                let audio_output = match AudioOutput::new() {
-                    Ok(output) => output,
-                    Err(e) => {
-                        error!("Failed to initialize audio output: {:?}", e);
-                        return;
+                let mut command_rx = command_rx;
+                let audio_output = loop {
+                    match AudioOutput::new(OutputSettings::default()) {
+                        Ok(output) => break output,
+                        Err(e) => {
+                            error!("Failed to initialize audio output: {:?}", e);
+                            *output_error.lock().unwrap() = Some(e.to_string());
+                        }
+                    }
+                    // Nothing can play without a device; wait to be asked to
+                    // try again
+                    loop {
+                        match command_rx.recv().await {
+                            Some(PlaybackCommand::ReopenOutput) => break,
+                            Some(_) => continue,
+                            None => return,
+                        }
                     }
                 };
+                *output_error.lock().unwrap() = None;
                 let mut service = PlaybackService {
                     library_manager,
                     encryption_service,
                     command_rx,
                     progress_tx,
                     queue: VecDeque::new(),
-                    previous_track_id: None,
+                    history: Vec::new(),
                     current_position_shared: Arc::new(std::sync::Mutex::new(None)),
                     position_generation: Arc::new(std::sync::atomic::AtomicU64::new(0)),
                     audio_output,
                     stream: None,
                     current_prepared: None,
                     current_streaming_source: None,
+                    current_position_offset: std::time::Duration::ZERO,
+                    seek_buffer: None,
                     next_prepared: None,
                     next_streaming_source: None,
                     repeat_mode: RepeatMode::None,
+                    shuffle: false,
+                    unshuffled: Vec::new(),
+                    download_concurrency: DEFAULT_DOWNLOAD_CONCURRENCY,
+                    buffer_settings: BufferSettings::default(),
+                    skip_silence: SkipSilenceSettings::default(),
+                    cloud_prefetch: true,
+                    output_error,
+                    devices_at_failure: Vec::new(),
+                    offline_cache: None,
                 };
                 service.run().await;
             });
@@ -589,7 +865,9 @@ impl PlaybackService {
     }
     async fn run(&mut self) {
         info!("PlaybackService started");
-        while let Some(command) = self.command_rx.recv().await {
+        let mut device_check = tokio::time::interval(DEVICE_CHECK_INTERVAL);
+        device_check.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        while let Some(command) = self.next_command(&mut device_check).await {
             match command {
                 PlaybackCommand::Play(track_id) => {
                     if let Some(stream) = self.stream.take() {
@@ -598,9 +876,7 @@ impl PlaybackService {
                     self.audio_output
                         .set_state(crate::playback::cpal_output::AudioState::Stopped);
                     self.clear_next_track_state();
-                    if let Some(id) = self.current_track_id() {
-                        self.previous_track_id = Some(id.to_string());
-                    }
+                    self.push_history();
                     self.queue.clear();
                     self.emit_queue_update();
                     if let Ok(Some(track)) = self.library_manager.get_track(&track_id).await {
@@ -625,16 +901,6 @@ impl PlaybackService {
                                     disc_cmp
                                 }
                             });
-                            if self.previous_track_id.is_none() {
-                                let mut previous_track_id = None;
-                                for release_track in &release_tracks {
-                                    if release_track.id == track_id {
-                                        break;
-                                    }
-                                    previous_track_id = Some(release_track.id.clone());
-                                }
-                                self.previous_track_id = previous_track_id;
-                            }
                             let mut found_current = false;
                             for release_track in release_tracks {
                                 if found_current {
@@ -643,19 +909,23 @@ impl PlaybackService {
                                     found_current = true;
                                 }
                             }
+                            if self.shuffle {
+                                self.shuffle_queue();
+                            }
                             self.emit_queue_update();
                         }
                     }
                     self.play_track(&track_id, false, false).await; // Direct selection: skip pregap, start playing
                 }
                 PlaybackCommand::PlayAlbum(track_ids) => {
-                    if let Some(id) = self.current_track_id() {
-                        self.previous_track_id = Some(id.to_string());
-                    }
+                    self.push_history();
                     self.queue.clear();
                     for track_id in track_ids {
                         self.queue.push_back(track_id);
                     }
+                    if self.shuffle {
+                        self.shuffle_queue();
+                    }
                     if let Some(first_track) = self.queue.pop_front() {
                         self.emit_queue_update();
                         self.play_track(&first_track, false, false).await; // Direct selection: skip pregap, start playing
@@ -677,9 +947,7 @@ impl PlaybackService {
                     if let Some(preloaded_track_id) = self.next_track_id().map(|s| s.to_string()) {
                         if self.next_streaming_source.is_some() {
                             info!("Using preloaded track: {}", preloaded_track_id);
-                            if let Some(id) = self.current_track_id() {
-                                self.previous_track_id = Some(id.to_string());
-                            }
+                            self.push_history();
                             if self
                                 .queue
                                 .front()
@@ -699,9 +967,7 @@ impl PlaybackService {
                     } else if let Some(next_track) = self.queue.pop_front() {
                         info!("No preloaded track, playing from queue: {}", next_track);
                         self.emit_queue_update();
-                        if let Some(id) = self.current_track_id() {
-                            self.previous_track_id = Some(id.to_string());
-                        }
+                        self.push_history();
                         self.play_track(&next_track, false, true).await; // preserve paused
                     } else {
                         info!("No next track available, stopping");
@@ -731,9 +997,7 @@ impl PlaybackService {
                     if let Some(preloaded_track_id) = self.next_track_id().map(|s| s.to_string()) {
                         if self.next_streaming_source.is_some() {
                             info!("Using preloaded track: {}", preloaded_track_id);
-                            if let Some(id) = self.current_track_id() {
-                                self.previous_track_id = Some(id.to_string());
-                            }
+                            self.push_history();
                             if self
                                 .queue
                                 .front()
@@ -753,9 +1017,7 @@ impl PlaybackService {
                     } else if let Some(next_track) = self.queue.pop_front() {
                         info!("No preloaded track, playing from queue: {}", next_track);
                         self.emit_queue_update();
-                        if let Some(id) = self.current_track_id() {
-                            self.previous_track_id = Some(id.to_string());
-                        }
+                        self.push_history();
                         self.play_track(&next_track, true, false).await; // start playing
                     } else if self.repeat_mode == RepeatMode::Album {
                         if let Some((first_track, rest)) =
@@ -763,7 +1025,11 @@ impl PlaybackService {
                         {
                             info!("Repeat mode: album, restarting from {}", first_track);
                             self.queue = rest;
+                            if self.shuffle {
+                                self.shuffle_queue();
+                            }
                             self.emit_queue_update();
+                            self.push_history();
 
                             self.play_track(&first_track, true, false).await;
                         } else {
@@ -784,69 +1050,50 @@ impl PlaybackService {
                             .lock()
                             .unwrap()
                             .unwrap_or(std::time::Duration::ZERO);
-                        if current_position < std::time::Duration::from_secs(3) {
-                            if let Some(previous_track_id) = self.previous_track_id.clone() {
-                                info!("Going to previous track: {}", previous_track_id);
-                                if let Ok(Some(previous_track)) =
-                                    self.library_manager.get_track(&previous_track_id).await
-                                {
-                                    if let Ok(mut release_tracks) = self
-                                        .library_manager
-                                        .get_tracks(&previous_track.release_id)
-                                        .await
-                                    {
-                                        release_tracks.sort_by(|a, b| {
-                                            match (a.track_number, b.track_number) {
-                                                (Some(a_num), Some(b_num)) => a_num.cmp(&b_num),
-                                                (Some(_), None) => std::cmp::Ordering::Less,
-                                                (None, Some(_)) => std::cmp::Ordering::Greater,
-                                                (None, None) => std::cmp::Ordering::Equal,
-                                            }
-                                        });
-                                        let mut new_previous_track_id = None;
-                                        for release_track in &release_tracks {
-                                            if release_track.id == previous_track_id {
-                                                break;
-                                            }
-                                            new_previous_track_id = Some(release_track.id.clone());
-                                        }
-                                        self.previous_track_id = new_previous_track_id;
-                                        self.queue.clear();
-                                        let mut found_current = false;
-                                        for release_track in release_tracks {
-                                            if found_current {
-                                                self.queue.push_back(release_track.id);
-                                            } else if release_track.id == previous_track_id {
-                                                found_current = true;
-                                            }
-                                        }
-                                        self.emit_queue_update();
-                                    }
-                                }
-                                self.clear_next_track_state();
-                                self.play_track(&previous_track_id, false, true).await;
-                            // preserve paused
-                            } else {
-                                info!("No previous track, restarting current track");
-                                self.play_track(&current_track_id, false, true).await;
-                                // preserve paused
-                            }
-                        } else {
+                        if current_position >= std::time::Duration::from_secs(3) {
                             info!("Restarting current track from beginning");
-                            let saved_previous = self.previous_track_id.clone();
                             self.play_track(&current_track_id, false, true).await; // preserve paused
-                            if saved_previous.is_some() {
-                                self.previous_track_id = saved_previous;
-                            }
+                            continue;
+                        }
+
+                        // What actually played comes first; with no history, fall
+                        // back to the track before this one on its release
+                        let previous_track_id = match self.history.pop() {
+                            Some(id) => Some(id),
+                            None => self.preceding_release_track(&current_track_id).await,
+                        };
+                        if let Some(previous_track_id) = previous_track_id {
+                            info!("Going to previous track: {}", previous_track_id);
+                            // The current track comes up again on Next
+                            self.queue.push_front(current_track_id);
+                            self.emit_queue_update();
+                            self.emit_history_update();
+                            self.clear_next_track_state();
+                            self.play_track(&previous_track_id, false, true).await;
+                        // preserve paused
+                        } else {
+                            info!("No previous track, restarting current track");
+                            self.play_track(&current_track_id, false, true).await;
+                            // preserve paused
                         }
                     }
                 }
+                PlaybackCommand::ClearHistory => {
+                    self.history.clear();
+                    self.emit_history_update();
+                }
                 PlaybackCommand::Seek(position) => {
                     self.seek(position).await;
                 }
                 PlaybackCommand::SetVolume(volume) => {
                     self.audio_output.set_volume(volume);
                 }
+                PlaybackCommand::SetPitch(semitones) => {
+                    self.audio_output.set_pitch(semitones);
+                    let _ = self.progress_tx.send(PlaybackProgress::PitchChanged {
+                        semitones: self.audio_output.pitch(),
+                    });
+                }
                 PlaybackCommand::AddToQueue(track_ids) => {
                     for track_id in track_ids {
                         self.queue.push_back(track_id);
@@ -900,10 +1147,108 @@ impl PlaybackService {
                             .send(PlaybackProgress::RepeatModeChanged { mode });
                     }
                 }
+                PlaybackCommand::SetShuffle(enabled) => {
+                    if self.shuffle != enabled {
+                        self.shuffle = enabled;
+                        if enabled {
+                            self.shuffle_queue();
+                        } else {
+                            let queue: Vec<String> = self.queue.drain(..).collect();
+                            self.queue = unshuffle(&self.unshuffled, &queue).into();
+                            self.unshuffled.clear();
+                        }
+                        // The preloaded track may no longer be next
+                        self.clear_next_track_state();
+                        self.emit_queue_update();
+                        if self.current_prepared.is_some() {
+                            if let Some(next_id) = self.queue.front().cloned() {
+                                self.preload_next_track(&next_id).await;
+                            }
+                        }
+                        let _ = self
+                            .progress_tx
+                            .send(PlaybackProgress::ShuffleChanged { enabled });
+                    }
+                }
+                PlaybackCommand::SetDownloadConcurrency(concurrency) => {
+                    self.download_concurrency = concurrency.max(1);
+                }
+                PlaybackCommand::SetBufferSettings(settings) => {
+                    self.buffer_settings = settings.clamped();
+                }
+                PlaybackCommand::SetOutputSettings(settings) => {
+                    let switching = settings.device != self.audio_output.settings().device;
+                    if switching {
+                        // The old device's stream must go before the new one opens
+                        self.stream = None;
+                    }
+                    let result = self.audio_output.set_output_settings(settings);
+                    if let Err(e) = &result {
+                        error!("Failed to apply output settings: {:?}", e);
+                    }
+                    if switching {
+                        self.set_output_error(result.as_ref().err().map(|e| e.to_string()));
+                        if result.is_ok() {
+                            self.restart_stream().await;
+                        }
+                    }
+
+                    self.send_output_format();
+                }
+                PlaybackCommand::SetSkipSilence(settings) => {
+                    self.skip_silence = settings.clamped();
+                }
+                PlaybackCommand::SetCloudPrefetch(enabled) => {
+                    self.cloud_prefetch = enabled;
+                }
+                PlaybackCommand::ReopenOutput => {
+                    self.reopen_output().await;
+                }
+                PlaybackCommand::SetOfflineCache(cache) => {
+                    self.offline_cache = Some(cache);
+                }
             }
         }
         info!("PlaybackService stopped");
     }
+    /// Wait for the next command, checking the output device in between
+    async fn next_command(
+        &mut self,
+        device_check: &mut tokio::time::Interval,
+    ) -> Option<PlaybackCommand> {
+        loop {
+            tokio::select! {
+                command = self.command_rx.recv() => return command,
+                _ = device_check.tick() => self.check_output_device().await,
+            }
+        }
+    }
+    /// Move to another device when the current one went away (headphones
+    /// unplugged, Bluetooth dropped), the preferred one came back, or the
+    /// system default changed. After a failed reopen, try again only once
+    /// the devices change, rather than failing the same way every check.
+    async fn check_output_device(&mut self) {
+        let failed = self.output_error.lock().unwrap().is_some();
+        let reopen = if failed {
+            device_list_changed(
+                &self.devices_at_failure,
+                &crate::playback::cpal_output::output_device_names(),
+            ) && self.audio_output.device_available()
+        } else {
+            self.audio_output.take_device_lost() || self.audio_output.device_changed()
+        };
+        if reopen {
+            info!("Output device changed, switching");
+            self.reopen_output().await;
+        }
+    }
+    fn send_output_format(&self) {
+        let _ = self
+            .progress_tx
+            .send(PlaybackProgress::OutputFormatChanged {
+                format: self.audio_output.format().clone(),
+            });
+    }
     /// Play a track.
     /// - `is_natural_transition`: if true, plays from INDEX 00 (pregap included)
     /// - `preserve_paused`: if true, inherits current paused state; if false, always starts playing
@@ -928,13 +1273,18 @@ impl PlaybackService {
         let prepared = match prepare_track(
             &self.library_manager,
             self.encryption_service.as_ref(),
+            self.offline_cache.as_ref(),
             track_id,
+            self.download_concurrency,
         )
         .await
         {
             Ok(p) => p,
             Err(e) => {
                 error!("Failed to prepare track {}: {}", track_id, e);
+                let _ = self.progress_tx.send(PlaybackProgress::PlaybackError {
+                    message: format!("Couldn't play track: {}", e),
+                });
                 self.stop().await;
                 return;
             }
@@ -959,7 +1309,11 @@ impl PlaybackService {
         });
 
         // Create decoder sink/source with track's actual sample rate
-        let (mut sink, source, _ready) = create_streaming_pair(prepared.sample_rate, 2);
+        let (mut sink, source, _ready) = create_streaming_pair_with_buffer_ms(
+            prepared.sample_rate,
+            2,
+            self.buffer_settings.decode_ahead_ms,
+        );
 
         // Spawn decoder thread
         let decoder_buffer = prepared.buffer.clone();
@@ -987,6 +1341,7 @@ impl PlaybackService {
         let pregap_ms = prepared.pregap_ms;
 
         // Store prepared track state
+        self.cancel_current_downloads();
         self.current_prepared = Some(prepared);
 
         // Initialize streaming
@@ -1037,11 +1392,18 @@ impl PlaybackService {
     /// Preload the next track for gapless playback.
     /// This eagerly starts the decoder so samples are ready when we switch tracks.
     async fn preload_next_track(&mut self, track_id: &str) {
+        if !preload_allowed(&self.library_manager, self.cloud_prefetch, track_id).await {
+            info!("Cloud prefetch is off, not preloading {}", track_id);
+            return;
+        }
+
         // Prepare track: fetch metadata, create buffer, start reading
         let prepared = match prepare_track(
             &self.library_manager,
             self.encryption_service.as_ref(),
+            self.offline_cache.as_ref(),
             track_id,
+            self.download_concurrency,
         )
         .await
         {
@@ -1052,8 +1414,13 @@ impl PlaybackService {
             }
         };
 
-        // Create decoder sink/source and start decoder eagerly for gapless playback
-        let (mut sink, source, _ready) = create_streaming_pair(prepared.sample_rate, 2);
+        // Create decoder sink/source and start decoder eagerly for gapless playback.
+        // The ring buffer size is how much of the next track gets pre-rolled.
+        let (mut sink, source, _ready) = create_streaming_pair_with_buffer_ms(
+            prepared.sample_rate,
+            2,
+            self.buffer_settings.gapless_preroll_ms,
+        );
         let decoder_buffer = prepared.buffer.clone();
         std::thread::spawn(move || {
             if let Err(e) = crate::audio_codec::decode_audio_streaming(decoder_buffer, &mut sink, 0)
@@ -1120,6 +1487,16 @@ impl PlaybackService {
         }
     }
 
+    /// Cancel the current track's buffers, stopping their cloud downloads
+    fn cancel_current_downloads(&mut self) {
+        if let Some(prepared) = &self.current_prepared {
+            prepared.buffer.cancel();
+        }
+        if let Some(buffer) = self.seek_buffer.take() {
+            buffer.cancel();
+        }
+    }
+
     fn clear_next_track_state(&mut self) {
         // Cancel any active streaming source for the next track
         if let Some(source) = self.next_streaming_source.take() {
@@ -1176,9 +1553,7 @@ impl PlaybackService {
                 guard.cancel();
             }
         }
-        if let Some(prepared) = &self.current_prepared {
-            prepared.buffer.cancel();
-        }
+        self.cancel_current_downloads();
 
         // Swap next to current
         self.current_prepared = Some(next_prepared);
@@ -1237,6 +1612,8 @@ impl PlaybackService {
         if let Some(stream) = self.stream.take() {
             drop(stream);
         }
+        self.audio_output.release_exclusive();
+        self.send_output_format();
 
         // Cancel streaming source if active
         if let Some(source) = self.current_streaming_source.take() {
@@ -1245,11 +1622,7 @@ impl PlaybackService {
             }
         }
 
-        // Cancel sparse buffer if active
-        if let Some(prepared) = &self.current_prepared {
-            prepared.buffer.cancel();
-        }
-
+        self.cancel_current_downloads();
         self.current_prepared = None;
         self.clear_next_track_state();
         *self.current_position_shared.lock().unwrap() = None;
@@ -1259,6 +1632,58 @@ impl PlaybackService {
             state: PlaybackState::Stopped,
         });
     }
+    /// Open the output device again, the preferred one if it's connected,
+    /// and carry on from the same position. Stops if there's no device.
+    async fn reopen_output(&mut self) {
+        self.stream = None;
+        let result = self.audio_output.reopen();
+        match &result {
+            Ok(()) => {
+                info!("Reopened audio output");
+                self.restart_stream().await;
+            }
+            Err(e) => {
+                error!("Failed to reopen audio output: {:?}", e);
+                self.stop().await;
+            }
+        }
+        self.set_output_error(result.err().map(|e| e.to_string()));
+        self.send_output_format();
+    }
+    /// Record why the output couldn't be opened, and which devices were
+    /// there at the time
+    fn set_output_error(&mut self, error: Option<String>) {
+        self.devices_at_failure = if error.is_some() {
+            crate::playback::cpal_output::output_device_names()
+        } else {
+            Vec::new()
+        };
+        *self.output_error.lock().unwrap() = error;
+    }
+    /// Create the stream again for the current source, on whatever device
+    /// the output has now. The source keeps its place and the output keeps
+    /// its state, so playback picks up where it was, or stays paused.
+    async fn restart_stream(&mut self) {
+        let (Some(source), Some(track_id)) = (
+            self.current_streaming_source.clone(),
+            self.current_track_id().map(str::to_string),
+        ) else {
+            return;
+        };
+        self.position_generation
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+        let position = *self.current_position_shared.lock().unwrap();
+        if !self
+            .init_streaming(source, self.current_position_offset, track_id)
+            .await
+        {
+            return;
+        }
+        // init_streaming rewinds the shared position to the offset until the
+        // first update; keep the real one
+        *self.current_position_shared.lock().unwrap() = position;
+    }
     async fn seek(&mut self, position: std::time::Duration) {
         // Verify streaming state is available
         if self.current_streaming_source.is_none() {
@@ -1348,9 +1773,18 @@ impl PlaybackService {
             self.create_seek_buffer_for_cloud(prepared, file_byte)
         };
 
+        // The old decoder is done with its buffer, so stop filling it
+        let sample_rate = prepared.sample_rate;
+        self.cancel_current_downloads();
+        self.seek_buffer = Some(seek_buffer.clone());
+
         // Spawn decoder on the seek buffer, skipping sample_offset samples
         // to reach the exact seek position (not just the frame boundary)
-        let (mut sink, source, ready_rx) = create_streaming_pair(prepared.sample_rate, 2);
+        let (mut sink, source, ready_rx) = create_streaming_pair_with_buffer_ms(
+            sample_rate,
+            2,
+            self.buffer_settings.decode_ahead_ms,
+        );
         std::thread::spawn(move || {
             if let Err(e) =
                 crate::audio_codec::decode_audio_streaming(seek_buffer, &mut sink, sample_offset)
@@ -1449,7 +1883,9 @@ impl PlaybackService {
                         .map(|e| Arc::new(e.clone())),
                     prepared.cloud_encrypted,
                 )
-                .with_encryption_nonce(prepared.encryption_nonce.clone()),
+                .with_encryption_nonce(prepared.encryption_nonce.clone())
+                .with_file_size(prepared.source_file_size)
+                .with_download_concurrency(self.download_concurrency),
             );
             reader.start_reading(seek_buffer.clone());
         } else {
@@ -1468,6 +1904,46 @@ impl PlaybackService {
             .send(PlaybackProgress::QueueUpdated { tracks: track_ids });
     }
 
+    /// Record the current track as played before moving off it
+    fn push_history(&mut self) {
+        let Some(id) = self.current_track_id().map(|s| s.to_string()) else {
+            return;
+        };
+        self.history.push(id);
+        if self.history.len() > HISTORY_LIMIT {
+            self.history.remove(0);
+        }
+        self.emit_history_update();
+    }
+
+    /// Emit play history to all subscribers, most recent first
+    fn emit_history_update(&self) {
+        let track_ids: Vec<String> = self.history.iter().rev().cloned().collect();
+        let _ = self
+            .progress_tx
+            .send(PlaybackProgress::HistoryUpdated { tracks: track_ids });
+    }
+
+    /// Track before the given one on its release, in disc and track order
+    async fn preceding_release_track(&self, track_id: &str) -> Option<String> {
+        let track = self.library_manager.get_track(track_id).await.ok()??;
+        let mut release_tracks = self
+            .library_manager
+            .get_tracks(&track.release_id)
+            .await
+            .ok()?;
+        release_tracks.sort_by_key(|t| (t.disc_number.unwrap_or(1), t.track_number));
+        let index = release_tracks.iter().position(|t| t.id == track_id)?;
+        index.checked_sub(1).map(|i| release_tracks[i].id.clone())
+    }
+
+    /// Shuffle the upcoming queue, remembering its order for when shuffle is
+    /// turned off
+    fn shuffle_queue(&mut self) {
+        self.unshuffled = self.queue.iter().cloned().collect();
+        shuffle(self.queue.make_contiguous());
+    }
+
     async fn rebuild_queue_for_repeat_album(&mut self) -> Option<(String, VecDeque<String>)> {
         let current_release_id = self
             .current_prepared
@@ -1504,7 +1980,6 @@ impl PlaybackService {
         let mut iter = tracks.into_iter();
         let first_track = iter.next()?.id;
         let rest = iter.map(|track| track.id).collect();
-        self.previous_track_id = None;
         Some((first_track, rest))
     }
 }
@@ -1595,6 +2070,16 @@ fn pregap_seek_position(
     }
 }
 
+/// Whether a device was connected or disconnected since opening the output
+/// failed. The order devices are listed in doesn't count.
+fn device_list_changed(at_failure: &[String], now: &[String]) -> bool {
+    let mut at_failure = at_failure.to_vec();
+    let mut now = now.to_vec();
+    at_failure.sort();
+    now.sort();
+    at_failure != now
+}
+
 /// Validate a seek position against the decoded audio duration.
 ///
 /// IMPORTANT: This must use `decoded_duration` (actual PCM length including pregap),
@@ -2141,4 +2626,87 @@ mod tests {
             track1_end
         );
     }
+
+    /// A library track stored under a new profile with `location`
+    async fn track_stored_in(
+        library_manager: &LibraryManager,
+        location: crate::db::StorageLocation,
+    ) -> String {
+        use crate::db::{DbAlbum, DbRelease, DbReleaseStorage, DbStorageProfile, StorageLocation};
+
+        let db = library_manager.database();
+        let profile = match location {
+            StorageLocation::Local => DbStorageProfile::new_local("Local", "/music", false),
+            StorageLocation::Reference => DbStorageProfile::new_reference("Folder", "/music"),
+            StorageLocation::Cloud => DbStorageProfile::new_cloud(
+                "Cloud",
+                "bucket",
+                "us-east-1",
+                None,
+                "key",
+                "secret",
+                false,
+            ),
+        };
+        let album = DbAlbum::new_test("Album");
+        let release = DbRelease::new_test(&album.id, &uuid::Uuid::new_v4().to_string());
+        let track = DbTrack::new_test(
+            &release.id,
+            &uuid::Uuid::new_v4().to_string(),
+            "Track",
+            Some(1),
+        );
+        db.insert_storage_profile(&profile).await.unwrap();
+        db.insert_album(&album).await.unwrap();
+        db.insert_release(&release).await.unwrap();
+        db.insert_track(&track).await.unwrap();
+        db.insert_release_storage(&DbReleaseStorage::new(&release.id, &profile.id))
+            .await
+            .unwrap();
+        track.id
+    }
+
+    #[tokio::test]
+    async fn test_metered_mode_only_holds_back_cloud_preloads() {
+        use crate::db::{Database, StorageLocation};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let database = Database::new(dir.path().join("test.db").to_str().unwrap())
+            .await
+            .unwrap();
+        let library_manager = LibraryManager::new(database, None);
+        let cloud = track_stored_in(&library_manager, StorageLocation::Cloud).await;
+        let local = track_stored_in(&library_manager, StorageLocation::Local).await;
+        let reference = track_stored_in(&library_manager, StorageLocation::Reference).await;
+        let external = crate::playback::external_track_id(std::path::Path::new("/tmp/a.flac"));
+
+        for track_id in [&cloud, &local, &reference, &external] {
+            assert!(preload_allowed(&library_manager, true, track_id).await);
+        }
+        assert!(!preload_allowed(&library_manager, false, &cloud).await);
+        assert!(preload_allowed(&library_manager, false, &local).await);
+        assert!(preload_allowed(&library_manager, false, &reference).await);
+        assert!(preload_allowed(&library_manager, false, &external).await);
+    }
+
+    #[test]
+    fn test_failed_output_is_retried_only_when_devices_change() {
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        let at_failure = names(&["MacBook Speakers", "USB DAC"]);
+
+        assert!(!device_list_changed(&at_failure, &at_failure));
+        assert!(!device_list_changed(
+            &at_failure,
+            &names(&["USB DAC", "MacBook Speakers"])
+        ));
+        assert!(device_list_changed(
+            &at_failure,
+            &names(&["MacBook Speakers", "USB DAC", "AirPods"])
+        ));
+        assert!(device_list_changed(
+            &at_failure,
+            &names(&["MacBook Speakers"])
+        ));
+        assert!(device_list_changed(&[], &names(&["MacBook Speakers"])));
+    }
 }
@@ -0,0 +1,95 @@
+//! Queue shuffling
+//!
+//! Shuffle reorders the upcoming queue in place. The order from before
+//! shuffling is kept so turning shuffle off can put the queue back the way
+//! it was, minus whatever played in between and plus whatever got added.
+
+use std::collections::HashMap;
+
+/// Shuffle `items` in place (Fisher–Yates)
+pub(crate) fn shuffle<T>(items: &mut [T]) {
+    let mut state = uuid::Uuid::new_v4().as_u128() as u64 | 1;
+    for i in (1..items.len()).rev() {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let j = (state % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+/// Restore the pre-shuffle order of `queue`.
+///
+/// Tracks still queued come back in the order they had in `original`;
+/// tracks added while shuffled follow, in their current queue order.
+pub(crate) fn unshuffle(original: &[String], queue: &[String]) -> Vec<String> {
+    let mut remaining: HashMap<&str, usize> = HashMap::new();
+    for track_id in queue {
+        *remaining.entry(track_id.as_str()).or_default() += 1;
+    }
+
+    let mut restored = Vec::with_capacity(queue.len());
+    for track_id in original {
+        if let Some(count) = remaining.get_mut(track_id.as_str()) {
+            if *count > 0 {
+                *count -= 1;
+                restored.push(track_id.clone());
+            }
+        }
+    }
+
+    // Whatever is left over was added after shuffling
+    for track_id in queue {
+        if let Some(count) = remaining.get_mut(track_id.as_str()) {
+            if *count > 0 {
+                *count -= 1;
+                restored.push(track_id.clone());
+            }
+        }
+    }
+    restored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_shuffle_keeps_every_item() {
+        let mut items: Vec<u32> = (0..100).collect();
+        shuffle(&mut items);
+        let mut sorted = items.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_unshuffle_restores_original_order() {
+        let original = ids(&["a", "b", "c", "d"]);
+        let queue = ids(&["c", "a", "d", "b"]);
+        assert_eq!(unshuffle(&original, &queue), original);
+    }
+
+    #[test]
+    fn test_unshuffle_drops_played_and_appends_added() {
+        let original = ids(&["a", "b", "c", "d"]);
+        // "c" played, "x" and "y" were queued while shuffled
+        let queue = ids(&["a", "x", "d", "b", "y"]);
+        assert_eq!(
+            unshuffle(&original, &queue),
+            ids(&["a", "b", "d", "x", "y"])
+        );
+    }
+
+    #[test]
+    fn test_unshuffle_handles_repeated_tracks() {
+        let original = ids(&["a", "b", "a"]);
+        let queue = ids(&["a", "a", "b", "a"]);
+        assert_eq!(unshuffle(&original, &queue), ids(&["a", "b", "a", "a"]));
+    }
+}
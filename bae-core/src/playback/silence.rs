@@ -0,0 +1,141 @@
+//! Skipping long silences during playback.
+//!
+//! Silence is detected on decoded samples as they're pulled into the audio
+//! callback, so it works without analysis ahead of time. A silent stretch
+//! plays normally up to the minimum length; past that, the rest of it is
+//! dropped until the sound comes back. Dropped samples still count toward
+//! the track position, so the seek bar jumps ahead instead of stalling.
+
+/// Below this peak level a frame counts as silent (about -50 dBFS): quiet
+/// enough to leave fades and room tone in soft passages alone
+const SILENCE_LEVEL: f32 = 0.003;
+
+/// Skip-silence preferences, applied the next time a stream is created
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkipSilenceSettings {
+    pub enabled: bool,
+    /// How long silence plays before the rest of it is skipped
+    pub min_silence_ms: u32,
+}
+
+impl SkipSilenceSettings {
+    pub const MIN_MS: u32 = 500;
+    pub const MAX_MS: u32 = 60_000;
+
+    pub fn clamped(self) -> Self {
+        Self {
+            enabled: self.enabled,
+            min_silence_ms: self.min_silence_ms.clamp(Self::MIN_MS, Self::MAX_MS),
+        }
+    }
+}
+
+impl Default for SkipSilenceSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_silence_ms: 2000,
+        }
+    }
+}
+
+/// Drops frames from silent runs longer than the minimum.
+///
+/// Keeps the length of the current run across calls, so one skipper must be
+/// fed one interleaved stream.
+pub struct SilenceSkipper {
+    channels: usize,
+    min_silent_frames: u64,
+    silent_frames: u64,
+}
+
+impl SilenceSkipper {
+    pub fn new(settings: SkipSilenceSettings, sample_rate: u32, channels: usize) -> Self {
+        let settings = settings.clamped();
+        Self {
+            channels: channels.max(1),
+            min_silent_frames: settings.min_silence_ms as u64 * sample_rate as u64 / 1000,
+            silent_frames: 0,
+        }
+    }
+
+    /// Remove skipped frames from `samples` in place
+    pub fn filter(&mut self, samples: &mut Vec<f32>) {
+        let channels = self.channels;
+        let mut kept = 0;
+        for frame in 0..samples.len() / channels {
+            let start = frame * channels;
+            let silent = samples[start..start + channels]
+                .iter()
+                .all(|s| s.abs() < SILENCE_LEVEL);
+            if silent {
+                self.silent_frames += 1;
+                if self.silent_frames > self.min_silent_frames {
+                    continue;
+                }
+            } else {
+                self.silent_frames = 0;
+            }
+            samples.copy_within(start..start + channels, kept * channels);
+            kept += 1;
+        }
+        samples.truncate(kept * channels);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(min_silence_ms: u32) -> SkipSilenceSettings {
+        SkipSilenceSettings {
+            enabled: true,
+            min_silence_ms,
+        }
+    }
+
+    #[test]
+    fn test_long_silence_is_cut_to_minimum() {
+        // 1 kHz mono: 1s of tone, 5s of silence, 1s of tone
+        let mut samples = vec![0.5f32; 1000];
+        samples.extend(vec![0.0; 5000]);
+        samples.extend(vec![-0.5; 1000]);
+
+        let mut skipper = SilenceSkipper::new(settings(1000), 1000, 1);
+        skipper.filter(&mut samples);
+
+        assert_eq!(samples.len(), 3000);
+        assert_eq!(samples[999], 0.5);
+        assert_eq!(samples[1999], 0.0);
+        assert_eq!(samples[2000], -0.5);
+    }
+
+    #[test]
+    fn test_short_gaps_are_kept() {
+        let mut samples: Vec<f32> = (0..10)
+            .flat_map(|_| [vec![0.5; 100], vec![0.0; 400]].concat())
+            .collect();
+        let len = samples.len();
+
+        let mut skipper = SilenceSkipper::new(settings(500), 1000, 1);
+        skipper.filter(&mut samples);
+
+        assert_eq!(samples.len(), len);
+    }
+
+    #[test]
+    fn test_run_carries_across_calls_and_needs_all_channels_silent() {
+        let mut skipper = SilenceSkipper::new(settings(500), 1000, 2);
+
+        // Stereo with only the right channel sounding isn't silence
+        let mut one_sided: Vec<f32> = (0..1000).flat_map(|_| [0.0, 0.2]).collect();
+        skipper.filter(&mut one_sided);
+        assert_eq!(one_sided.len(), 2000);
+
+        let mut first = vec![0.0f32; 800];
+        let mut second = vec![0.0f32; 800];
+        skipper.filter(&mut first);
+        skipper.filter(&mut second);
+        assert_eq!(first.len() + second.len(), 1000);
+    }
+}
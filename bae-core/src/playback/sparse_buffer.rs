@@ -5,6 +5,7 @@
 //! download position (which creates gaps that are filled later).
 
 use std::sync::{Arc, Condvar, Mutex};
+use tokio::sync::watch;
 
 /// A contiguous range of buffered data.
 #[derive(Debug, Clone)]
@@ -48,9 +49,12 @@ struct SparseInner {
 /// - `is_buffered()`: Check if a position is available
 /// - `seek()`: Move read position
 /// - `read()`: Blocking read from current position
+/// - `watch_position()`: Follow the read position from an async downloader
 pub struct SparseStreamingBuffer {
     inner: Mutex<SparseInner>,
     data_available: Condvar,
+    /// Read position, published for downloaders fetching ahead of it
+    read_pos_tx: watch::Sender<u64>,
 }
 
 impl SparseStreamingBuffer {
@@ -65,6 +69,7 @@ impl SparseStreamingBuffer {
                 cancelled: false,
             }),
             data_available: Condvar::new(),
+            read_pos_tx: watch::Sender::new(0),
         }
     }
 
@@ -185,9 +190,22 @@ impl SparseStreamingBuffer {
             return false;
         }
         inner.read_pos = pos;
+        self.read_pos_tx.send_replace(pos);
         true
     }
 
+    /// Current read position.
+    pub fn position(&self) -> u64 {
+        self.inner.lock().unwrap().read_pos
+    }
+
+    /// Subscribe to read position changes.
+    ///
+    /// Also wakes on cancel, so a waiting downloader can notice it.
+    pub fn watch_position(&self) -> watch::Receiver<u64> {
+        self.read_pos_tx.subscribe()
+    }
+
     /// Blocking read from current position.
     ///
     /// Waits until data is available at current position, then reads.
@@ -211,6 +229,7 @@ impl SparseStreamingBuffer {
                     buf[..to_read]
                         .copy_from_slice(&range.data[offset_in_range..offset_in_range + to_read]);
                     inner.read_pos += to_read as u64;
+                    self.read_pos_tx.send_replace(inner.read_pos);
                     return Some(to_read);
                 }
             }
@@ -268,6 +287,7 @@ impl SparseStreamingBuffer {
         let mut inner = self.inner.lock().unwrap();
         inner.cancelled = true;
         self.data_available.notify_all();
+        self.read_pos_tx.send_modify(|_| {});
     }
 
     /// Check if cancelled.
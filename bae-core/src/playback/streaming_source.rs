@@ -15,6 +15,41 @@ use tokio::sync::oneshot;
 /// Buffer holds this much audio regardless of sample rate.
 const DEFAULT_BUFFER_MS: u32 = 100;
 
+/// How much decoded audio is buffered ahead of playback.
+///
+/// Small buffers start playback sooner; larger ones ride out stalls from
+/// high-latency sources like cloud storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferSettings {
+    /// Ring buffer size for the playing track: how far the decoder runs ahead
+    pub decode_ahead_ms: u32,
+    /// Ring buffer size for the preloaded next track: how much of it is
+    /// decoded before the gapless transition
+    pub gapless_preroll_ms: u32,
+}
+
+impl BufferSettings {
+    pub const MIN_MS: u32 = 50;
+    pub const MAX_MS: u32 = 5000;
+
+    /// Clamp both durations into `MIN_MS..=MAX_MS`
+    pub fn clamped(self) -> Self {
+        Self {
+            decode_ahead_ms: self.decode_ahead_ms.clamp(Self::MIN_MS, Self::MAX_MS),
+            gapless_preroll_ms: self.gapless_preroll_ms.clamp(Self::MIN_MS, Self::MAX_MS),
+        }
+    }
+}
+
+impl Default for BufferSettings {
+    fn default() -> Self {
+        Self {
+            decode_ahead_ms: DEFAULT_BUFFER_MS,
+            gapless_preroll_ms: DEFAULT_BUFFER_MS,
+        }
+    }
+}
+
 /// Shared state between sink and source
 pub struct StreamingState {
     /// Audio sample rate
@@ -284,9 +319,17 @@ pub fn create_streaming_pair(
     sample_rate: u32,
     channels: u32,
 ) -> (StreamingPcmSink, StreamingPcmSource, ReadyReceiver) {
-    // Calculate capacity for DEFAULT_BUFFER_MS milliseconds of audio
-    let capacity_samples =
-        (sample_rate as usize * channels as usize * DEFAULT_BUFFER_MS as usize) / 1000;
+    create_streaming_pair_with_buffer_ms(sample_rate, channels, DEFAULT_BUFFER_MS)
+}
+
+/// Create a streaming source/sink pair holding `buffer_ms` milliseconds of audio.
+/// Returns a ready receiver that resolves when buffer is 50% full.
+pub fn create_streaming_pair_with_buffer_ms(
+    sample_rate: u32,
+    channels: u32,
+    buffer_ms: u32,
+) -> (StreamingPcmSink, StreamingPcmSource, ReadyReceiver) {
+    let capacity_samples = (sample_rate as usize * channels as usize * buffer_ms as usize) / 1000;
     create_streaming_pair_with_capacity(sample_rate, channels, capacity_samples)
 }
 
@@ -390,6 +433,17 @@ mod tests {
         assert!(pushed <= 10);
     }
 
+    #[test]
+    fn test_buffer_settings_clamped() {
+        let settings = BufferSettings {
+            decode_ahead_ms: 0,
+            gapless_preroll_ms: 60_000,
+        }
+        .clamped();
+        assert_eq!(settings.decode_ahead_ms, BufferSettings::MIN_MS);
+        assert_eq!(settings.gapless_preroll_ms, BufferSettings::MAX_MS);
+    }
+
     #[test]
     fn test_buffer_empty() {
         let (_sink, mut source, _ready) = create_streaming_pair(44100, 2);
@@ -66,7 +66,7 @@ pub async fn load_track_audio(
         debug!("Downloading from cloud: {}", key);
 
         // Check cache first
-        let cache_key = format!("file:{}", audio_file.id);
+        let cache_key = crate::library::offline::file_cache_key(&audio_file.id);
         let encrypted_data = match cache.get(&cache_key).await {
             Ok(Some(cached_data)) => {
                 debug!("Cache hit for file: {}", audio_file.id);
@@ -0,0 +1,115 @@
+//! Pairing and access for the phone remote.
+//!
+//! The remote is a small HTTP API on the LAN that a phone's browser drives.
+//! Pairing hands the phone a secret token through a QR code shown in
+//! Settings; the code holds a URL with the token in its fragment, so the
+//! token isn't sent with the page request or kept in logs. The page stores
+//! it and sends it with every API call. Unpairing replaces the token, which
+//! locks out every phone paired with the old one.
+
+use qrcode::render::svg;
+use qrcode::QrCode;
+use std::net::IpAddr;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+/// Default port for the remote API
+pub const DEFAULT_REMOTE_PORT: u16 = 4534;
+
+/// A fresh pairing token
+pub fn generate_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+/// URL a phone opens to pair, with the token in the fragment
+pub fn pairing_url(ip: IpAddr, port: u16, token: &str) -> String {
+    let host = match ip {
+        IpAddr::V4(ip) => ip.to_string(),
+        IpAddr::V6(ip) => format!("[{}]", ip),
+    };
+    format!("http://{}:{}/#token={}", host, port, token)
+}
+
+/// QR code for `url`, as an SVG document
+pub fn pairing_qr_svg(url: &str) -> Option<String> {
+    let code = QrCode::new(url.as_bytes()).ok()?;
+    Some(
+        code.render::<svg::Color>()
+            .min_dimensions(200, 200)
+            .dark_color(svg::Color("#000000"))
+            .light_color(svg::Color("#ffffff"))
+            .build(),
+    )
+}
+
+/// The token phones must send, shared between the server and Settings so
+/// unpairing takes effect without a restart
+#[derive(Clone, Default)]
+pub struct RemoteAccess {
+    token: Arc<RwLock<Option<String>>>,
+}
+
+impl RemoteAccess {
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            token: Arc::new(RwLock::new(token)),
+        }
+    }
+
+    pub fn token(&self) -> Option<String> {
+        self.token.read().unwrap().clone()
+    }
+
+    pub fn set_token(&self, token: Option<String>) {
+        *self.token.write().unwrap() = token;
+    }
+
+    /// Whether `provided` is the current token. Nothing is allowed before
+    /// a token exists.
+    pub fn allows(&self, provided: &str) -> bool {
+        match self.token.read().unwrap().as_deref() {
+            Some(token) => constant_time_eq(token.as_bytes(), provided.as_bytes()),
+            None => false,
+        }
+    }
+}
+
+/// Compare without returning early, so response times don't reveal how much
+/// of a guess was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_access_checks_current_token() {
+        let access = RemoteAccess::new(None);
+        assert!(!access.allows(""));
+
+        let token = generate_token();
+        access.set_token(Some(token.clone()));
+        assert!(access.allows(&token));
+        assert!(!access.allows(&token[1..]));
+        assert!(!access.allows(&generate_token()));
+
+        // Unpairing locks out the old token
+        access.set_token(Some(generate_token()));
+        assert!(!access.allows(&token));
+    }
+
+    #[test]
+    fn test_pairing_url() {
+        assert_eq!(
+            pairing_url(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20)), 4534, "abc"),
+            "http://192.168.1.20:4534/#token=abc"
+        );
+        assert_eq!(
+            pairing_url(IpAddr::V6(Ipv6Addr::LOCALHOST), 4534, "abc"),
+            "http://[::1]:4534/#token=abc"
+        );
+    }
+}
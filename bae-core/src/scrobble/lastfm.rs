@@ -0,0 +1,205 @@
+//! Last.fm scrobbling API.
+//!
+//! Connecting uses Last.fm's desktop auth: [`get_token`], then the user
+//! approves the token at [`auth_url`] in a browser, then [`get_session`]
+//! trades it for a session key that doesn't expire. Every call is signed
+//! with the API account's shared secret.
+
+use super::ScrobbleError;
+use crate::db::DbScrobble;
+use md5::{Digest, Md5};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+const API_URL: &str = "https://ws.audioscrobbler.com/2.0/";
+/// Most scrobbles Last.fm takes in one request
+pub const MAX_BATCH: usize = 50;
+
+/// A connected Last.fm account. The API key and secret come from the user's
+/// own API account at last.fm/api.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LastFmAccount {
+    pub api_key: String,
+    pub api_secret: String,
+    pub session_key: String,
+    pub username: String,
+}
+
+/// Page where the user approves a token from [`get_token`]
+pub fn auth_url(api_key: &str, token: &str) -> String {
+    format!(
+        "https://www.last.fm/api/auth/?api_key={}&token={}",
+        urlencoding::encode(api_key),
+        urlencoding::encode(token)
+    )
+}
+
+/// Start connecting an account: a token for the user to approve
+pub async fn get_token(api_key: &str, api_secret: &str) -> Result<String, ScrobbleError> {
+    let response = call(
+        api_secret,
+        params([("method", "auth.getToken"), ("api_key", api_key)]),
+    )
+    .await?;
+    response["token"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| ScrobbleError::Service("Last.fm returned no token".to_string()))
+}
+
+/// Finish connecting once the user approved `token`
+pub async fn get_session(
+    api_key: &str,
+    api_secret: &str,
+    token: &str,
+) -> Result<LastFmAccount, ScrobbleError> {
+    let response = call(
+        api_secret,
+        params([
+            ("method", "auth.getSession"),
+            ("api_key", api_key),
+            ("token", token),
+        ]),
+    )
+    .await?;
+    let session = &response["session"];
+    match (session["key"].as_str(), session["name"].as_str()) {
+        (Some(key), Some(name)) => Ok(LastFmAccount {
+            api_key: api_key.to_string(),
+            api_secret: api_secret.to_string(),
+            session_key: key.to_string(),
+            username: name.to_string(),
+        }),
+        _ => Err(ScrobbleError::Service(
+            "Last.fm returned no session".to_string(),
+        )),
+    }
+}
+
+pub async fn update_now_playing(
+    account: &LastFmAccount,
+    track: &DbScrobble,
+) -> Result<(), ScrobbleError> {
+    let mut request = params([
+        ("method", "track.updateNowPlaying"),
+        ("api_key", &account.api_key),
+        ("sk", &account.session_key),
+        ("artist", &track.artist),
+        ("track", &track.title),
+    ]);
+    if let Some(album) = &track.album {
+        request.insert("album".to_string(), album.clone());
+    }
+    if let Some(album_artist) = &track.album_artist {
+        request.insert("albumArtist".to_string(), album_artist.clone());
+    }
+    if let Some(duration_ms) = track.duration_ms {
+        request.insert("duration".to_string(), (duration_ms / 1000).to_string());
+    }
+    call(&account.api_secret, request).await?;
+    Ok(())
+}
+
+/// Submit up to [`MAX_BATCH`] scrobbles. Ones Last.fm ignores (e.g. as
+/// spam or too old) are dropped, not retried.
+pub async fn scrobble(account: &LastFmAccount, batch: &[DbScrobble]) -> Result<(), ScrobbleError> {
+    let mut request = params([
+        ("method", "track.scrobble"),
+        ("api_key", &account.api_key),
+        ("sk", &account.session_key),
+    ]);
+    for (i, track) in batch.iter().take(MAX_BATCH).enumerate() {
+        let mut field = |name: &str, value: String| {
+            request.insert(format!("{}[{}]", name, i), value);
+        };
+        field("artist", track.artist.clone());
+        field("track", track.title.clone());
+        field("timestamp", track.played_at.timestamp().to_string());
+        if let Some(album) = &track.album {
+            field("album", album.clone());
+        }
+        if let Some(album_artist) = &track.album_artist {
+            field("albumArtist", album_artist.clone());
+        }
+        if let Some(number) = track.track_number {
+            field("trackNumber", number.to_string());
+        }
+        if let Some(duration_ms) = track.duration_ms {
+            field("duration", (duration_ms / 1000).to_string());
+        }
+    }
+    call(&account.api_secret, request).await?;
+    Ok(())
+}
+
+fn params<'a>(pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> BTreeMap<String, String> {
+    pairs
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// `api_sig`: MD5 of every parameter as name then value in name order,
+/// followed by the secret
+fn signature(params: &BTreeMap<String, String>, api_secret: &str) -> String {
+    let mut hasher = Md5::new();
+    for (name, value) in params {
+        hasher.update(name.as_bytes());
+        hasher.update(value.as_bytes());
+    }
+    hasher.update(api_secret.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+async fn call(
+    api_secret: &str,
+    mut params: BTreeMap<String, String>,
+) -> Result<Value, ScrobbleError> {
+    let api_sig = signature(&params, api_secret);
+    params.insert("api_sig".to_string(), api_sig);
+    // Not signed
+    params.insert("format".to_string(), "json".to_string());
+
+    let response: Value = reqwest::Client::new()
+        .post(API_URL)
+        .form(&params)
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    if let Some(code) = response["error"].as_i64() {
+        let message = response["message"]
+            .as_str()
+            .unwrap_or("unknown error")
+            .to_string();
+        return Err(match code {
+            // Invalid session, API key or signature, or an unapproved token
+            4 | 9 | 10 | 13 | 14 | 15 | 26 => ScrobbleError::Unauthorized(message),
+            _ => ScrobbleError::Service(message),
+        });
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_orders_params_by_name() {
+        let request = params([
+            ("method", "auth.getSession"),
+            ("token", "t0ken"),
+            ("api_key", "k3y"),
+        ]);
+        // md5("api_keyk3ymethodauth.getSessiontokent0kensecret")
+        let mut hasher = Md5::new();
+        hasher.update(b"api_keyk3ymethodauth.getSessiontokent0kensecret");
+        assert_eq!(
+            signature(&request, "secret"),
+            hex::encode(hasher.finalize())
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! ListenBrainz listen submission API, authenticated with the user token
+//! from their ListenBrainz profile page.
+
+use super::ScrobbleError;
+use crate::db::DbScrobble;
+use serde_json::{json, Value};
+
+const API_URL: &str = "https://api.listenbrainz.org/1";
+/// Most listens bae sends in one request
+pub const MAX_BATCH: usize = 100;
+
+/// The username a token belongs to; `Unauthorized` if it isn't valid
+pub async fn validate_token(token: &str) -> Result<String, ScrobbleError> {
+    let response: Value = reqwest::Client::new()
+        .get(format!("{}/validate-token", API_URL))
+        .header("Authorization", format!("Token {}", token))
+        .send()
+        .await?
+        .json()
+        .await?;
+    match response["user_name"].as_str() {
+        Some(name) if response["valid"].as_bool() == Some(true) => Ok(name.to_string()),
+        _ => Err(ScrobbleError::Unauthorized(
+            response["message"]
+                .as_str()
+                .unwrap_or("Invalid token")
+                .to_string(),
+        )),
+    }
+}
+
+pub async fn update_now_playing(token: &str, track: &DbScrobble) -> Result<(), ScrobbleError> {
+    submit(
+        token,
+        json!({
+            "listen_type": "playing_now",
+            "payload": [{ "track_metadata": track_metadata(track) }],
+        }),
+    )
+    .await
+}
+
+/// Submit up to [`MAX_BATCH`] listens
+pub async fn submit_listens(token: &str, batch: &[DbScrobble]) -> Result<(), ScrobbleError> {
+    let payload: Vec<Value> = batch
+        .iter()
+        .take(MAX_BATCH)
+        .map(|track| {
+            json!({
+                "listened_at": track.played_at.timestamp(),
+                "track_metadata": track_metadata(track),
+            })
+        })
+        .collect();
+    let listen_type = if payload.len() == 1 {
+        "single"
+    } else {
+        "import"
+    };
+    submit(
+        token,
+        json!({ "listen_type": listen_type, "payload": payload }),
+    )
+    .await
+}
+
+fn track_metadata(track: &DbScrobble) -> Value {
+    let mut info = json!({
+        "media_player": "bae",
+        "submission_client": "bae",
+        "submission_client_version": env!("CARGO_PKG_VERSION"),
+    });
+    if let Some(number) = track.track_number {
+        info["tracknumber"] = json!(number);
+    }
+    if let Some(duration_ms) = track.duration_ms {
+        info["duration_ms"] = json!(duration_ms);
+    }
+    let mut metadata = json!({
+        "artist_name": track.artist,
+        "track_name": track.title,
+        "additional_info": info,
+    });
+    if let Some(album) = &track.album {
+        metadata["release_name"] = json!(album);
+    }
+    metadata
+}
+
+async fn submit(token: &str, body: Value) -> Result<(), ScrobbleError> {
+    let response = reqwest::Client::new()
+        .post(format!("{}/submit-listens", API_URL))
+        .header("Authorization", format!("Token {}", token))
+        .json(&body)
+        .send()
+        .await?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let message = response
+        .json::<Value>()
+        .await
+        .ok()
+        .and_then(|v| v["error"].as_str().map(str::to_string))
+        .unwrap_or_else(|| status.to_string());
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        Err(ScrobbleError::Unauthorized(message))
+    } else {
+        Err(ScrobbleError::Service(message))
+    }
+}
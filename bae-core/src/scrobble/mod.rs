@@ -0,0 +1,383 @@
+//! Scrobbling listens to Last.fm and ListenBrainz.
+//!
+//! The service follows playback progress. When a track starts it tells each
+//! connected service what's now playing; once a listen earns a scrobble it's
+//! queued in the database, one row per service, and submitted from there.
+//! Anything that can't be sent, e.g. while offline, stays queued and is
+//! retried on the next scrobble or every few minutes.
+
+pub mod lastfm;
+pub mod listenbrainz;
+mod rules;
+
+pub use lastfm::LastFmAccount;
+pub use rules::{ScrobbleEvent, ScrobbleTracker};
+
+use crate::db::{DbScrobble, ScrobbleTarget};
+use crate::library::SharedLibraryManager;
+use crate::playback::{is_external_track, PlaybackProgress};
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::{mpsc, Notify};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+/// How often queued scrobbles are retried without new ones arriving
+const RETRY_INTERVAL: Duration = Duration::from_secs(10 * 60);
+/// Rejected submissions before a scrobble is dropped
+const MAX_ATTEMPTS: i32 = 10;
+
+#[derive(Error, Debug)]
+pub enum ScrobbleError {
+    #[error("HTTP request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Not authorized: {0}")]
+    Unauthorized(String),
+    #[error("{0}")]
+    Service(String),
+}
+
+/// Connected scrobbling accounts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScrobbleAccounts {
+    pub lastfm: Option<LastFmAccount>,
+    pub listenbrainz_token: Option<String>,
+}
+
+impl ScrobbleAccounts {
+    fn targets(&self) -> Vec<ScrobbleTarget> {
+        let mut targets = Vec::new();
+        if self.lastfm.is_some() {
+            targets.push(ScrobbleTarget::LastFm);
+        }
+        if self.listenbrainz_token.is_some() {
+            targets.push(ScrobbleTarget::ListenBrainz);
+        }
+        targets
+    }
+}
+
+/// Handle to the running scrobbler
+#[derive(Clone)]
+pub struct ScrobbleHandle {
+    accounts: Arc<RwLock<ScrobbleAccounts>>,
+    flush: Arc<Notify>,
+    library_manager: SharedLibraryManager,
+    runtime_handle: tokio::runtime::Handle,
+}
+
+impl ScrobbleHandle {
+    /// Switch accounts. Scrobbles queued for a service that was disconnected
+    /// are dropped; ones for a newly connected service go out right away.
+    pub fn set_accounts(&self, accounts: ScrobbleAccounts) {
+        let previous = std::mem::replace(&mut *self.accounts.write().unwrap(), accounts.clone());
+        let removed: Vec<ScrobbleTarget> = previous
+            .targets()
+            .into_iter()
+            .filter(|target| !accounts.targets().contains(target))
+            .collect();
+        if !removed.is_empty() {
+            let library_manager = self.library_manager.clone();
+            self.runtime_handle.spawn(async move {
+                for target in removed {
+                    if let Err(e) = library_manager.database().clear_scrobbles(target).await {
+                        error!("Failed to clear {} scrobbles: {}", target.as_str(), e);
+                    }
+                }
+            });
+        }
+        self.flush.notify_one();
+    }
+
+    pub fn accounts(&self) -> ScrobbleAccounts {
+        self.accounts.read().unwrap().clone()
+    }
+
+    /// Scrobbles waiting to be submitted, across services
+    pub async fn pending_count(&self) -> i64 {
+        self.library_manager
+            .database()
+            .count_pending_scrobbles()
+            .await
+            .unwrap_or(0)
+    }
+}
+
+pub struct ScrobbleService;
+
+impl ScrobbleService {
+    pub fn start(
+        runtime_handle: tokio::runtime::Handle,
+        library_manager: SharedLibraryManager,
+        progress_rx: mpsc::UnboundedReceiver<PlaybackProgress>,
+    ) -> ScrobbleHandle {
+        let handle = ScrobbleHandle {
+            accounts: Arc::new(RwLock::new(ScrobbleAccounts::default())),
+            flush: Arc::new(Notify::new()),
+            library_manager: library_manager.clone(),
+            runtime_handle: runtime_handle.clone(),
+        };
+
+        runtime_handle.spawn(flush_loop(
+            library_manager.clone(),
+            handle.accounts.clone(),
+            handle.flush.clone(),
+        ));
+        runtime_handle.spawn(listen_loop(
+            library_manager,
+            handle.accounts.clone(),
+            handle.flush.clone(),
+            progress_rx,
+        ));
+        handle
+    }
+}
+
+async fn listen_loop(
+    library_manager: SharedLibraryManager,
+    accounts: Arc<RwLock<ScrobbleAccounts>>,
+    flush: Arc<Notify>,
+    mut progress_rx: mpsc::UnboundedReceiver<PlaybackProgress>,
+) {
+    let mut tracker = ScrobbleTracker::new();
+    while let Some(progress) = progress_rx.recv().await {
+        for event in tracker.on_progress(&progress, Utc::now()) {
+            let current = accounts.read().unwrap().clone();
+            if current.targets().is_empty() {
+                continue;
+            }
+            match event {
+                ScrobbleEvent::NowPlaying { track_id } => {
+                    if let Some(track) = track_info(&library_manager, &track_id).await {
+                        // Best effort; a missed now-playing isn't worth retrying
+                        tokio::spawn(send_now_playing(current, track));
+                    }
+                }
+                ScrobbleEvent::Scrobble {
+                    track_id,
+                    started_at,
+                } => {
+                    let Some(track) = track_info(&library_manager, &track_id).await else {
+                        continue;
+                    };
+                    for target in current.targets() {
+                        let scrobble = track.to_scrobble(target, started_at);
+                        if let Err(e) = library_manager.database().insert_scrobble(&scrobble).await
+                        {
+                            error!("Failed to queue scrobble: {}", e);
+                        }
+                    }
+                    flush.notify_one();
+                }
+            }
+        }
+    }
+}
+
+/// What gets sent about a track
+struct TrackInfo {
+    track_id: String,
+    artist: String,
+    title: String,
+    album: Option<String>,
+    album_artist: Option<String>,
+    track_number: Option<i32>,
+    duration_ms: Option<i64>,
+}
+
+impl TrackInfo {
+    fn to_scrobble(&self, target: ScrobbleTarget, played_at: DateTime<Utc>) -> DbScrobble {
+        DbScrobble {
+            id: Uuid::new_v4().to_string(),
+            target,
+            track_id: self.track_id.clone(),
+            artist: self.artist.clone(),
+            title: self.title.clone(),
+            album: self.album.clone(),
+            album_artist: self.album_artist.clone(),
+            track_number: self.track_number,
+            duration_ms: self.duration_ms,
+            played_at,
+            attempts: 0,
+        }
+    }
+}
+
+/// None for tracks that can't be scrobbled: files played from outside the
+/// library, and tracks with no artist to credit
+async fn track_info(library_manager: &SharedLibraryManager, track_id: &str) -> Option<TrackInfo> {
+    if is_external_track(track_id) {
+        return None;
+    }
+    let library = library_manager.get();
+    let track = match library.get_track(track_id).await {
+        Ok(Some(track)) => track,
+        Ok(None) => return None,
+        Err(e) => {
+            error!("Failed to load track {} to scrobble: {}", track_id, e);
+            return None;
+        }
+    };
+    let join_names = |artists: Vec<crate::db::DbArtist>| {
+        let names: Vec<String> = artists.into_iter().map(|a| a.name).collect();
+        (!names.is_empty()).then(|| names.join(", "))
+    };
+
+    let album_id = library
+        .get_album_id_for_release(&track.release_id)
+        .await
+        .ok();
+    let mut album = None;
+    let mut album_artist = None;
+    if let Some(album_id) = album_id {
+        album = library
+            .get_album_by_id(&album_id)
+            .await
+            .ok()
+            .flatten()
+            .map(|album| album.title);
+        album_artist = library
+            .get_artists_for_album(&album_id)
+            .await
+            .ok()
+            .and_then(join_names);
+    }
+    let artist = library
+        .get_artists_for_track(track_id)
+        .await
+        .ok()
+        .and_then(join_names)
+        .or_else(|| album_artist.clone());
+    let Some(artist) = artist else {
+        debug!("Not scrobbling track {} with no artist", track_id);
+        return None;
+    };
+
+    Some(TrackInfo {
+        track_id: track.id,
+        artist,
+        title: track.title,
+        album,
+        album_artist,
+        track_number: track.track_number,
+        duration_ms: track.duration_ms,
+    })
+}
+
+async fn send_now_playing(accounts: ScrobbleAccounts, track: TrackInfo) {
+    let now = Utc::now();
+    if let Some(account) = &accounts.lastfm {
+        let scrobble = track.to_scrobble(ScrobbleTarget::LastFm, now);
+        if let Err(e) = lastfm::update_now_playing(account, &scrobble).await {
+            debug!("Last.fm now playing failed: {}", e);
+        }
+    }
+    if let Some(token) = &accounts.listenbrainz_token {
+        let scrobble = track.to_scrobble(ScrobbleTarget::ListenBrainz, now);
+        if let Err(e) = listenbrainz::update_now_playing(token, &scrobble).await {
+            debug!("ListenBrainz now playing failed: {}", e);
+        }
+    }
+}
+
+/// Submit queued scrobbles whenever new ones arrive, accounts change, or
+/// the retry interval passes
+async fn flush_loop(
+    library_manager: SharedLibraryManager,
+    accounts: Arc<RwLock<ScrobbleAccounts>>,
+    flush: Arc<Notify>,
+) {
+    let mut retry = tokio::time::interval(RETRY_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = flush.notified() => {}
+            _ = retry.tick() => {}
+        }
+        let current = accounts.read().unwrap().clone();
+        for target in current.targets() {
+            flush_target(&library_manager, &current, target).await;
+        }
+    }
+}
+
+/// Submit everything queued for one service, a batch at a time, stopping at
+/// the first batch that fails
+async fn flush_target(
+    library_manager: &SharedLibraryManager,
+    accounts: &ScrobbleAccounts,
+    target: ScrobbleTarget,
+) {
+    let database = library_manager.database();
+    let batch_size = match target {
+        ScrobbleTarget::LastFm => lastfm::MAX_BATCH,
+        ScrobbleTarget::ListenBrainz => listenbrainz::MAX_BATCH,
+    };
+    loop {
+        let batch = match database
+            .get_pending_scrobbles(target, batch_size as i64)
+            .await
+        {
+            Ok(batch) if !batch.is_empty() => batch,
+            Ok(_) => return,
+            Err(e) => {
+                error!("Failed to read scrobble queue: {}", e);
+                return;
+            }
+        };
+        let result = match (target, accounts) {
+            (
+                ScrobbleTarget::LastFm,
+                ScrobbleAccounts {
+                    lastfm: Some(account),
+                    ..
+                },
+            ) => lastfm::scrobble(account, &batch).await,
+            (
+                ScrobbleTarget::ListenBrainz,
+                ScrobbleAccounts {
+                    listenbrainz_token: Some(token),
+                    ..
+                },
+            ) => listenbrainz::submit_listens(token, &batch).await,
+            _ => return,
+        };
+        let ids: Vec<String> = batch.iter().map(|s| s.id.clone()).collect();
+
+        match result {
+            Ok(()) => {
+                info!("Submitted {} scrobbles to {}", ids.len(), target.as_str());
+                if let Err(e) = database.delete_scrobbles(&ids).await {
+                    error!("Failed to remove submitted scrobbles: {}", e);
+                    return;
+                }
+            }
+            // Offline, or the account needs reconnecting: keep everything
+            Err(e @ (ScrobbleError::Request(_) | ScrobbleError::Unauthorized(_))) => {
+                warn!(
+                    "Scrobbling to {} failed, will retry: {}",
+                    target.as_str(),
+                    e
+                );
+                return;
+            }
+            // The service turned the batch down; give up on it eventually so
+            // one bad listen can't hold up the rest
+            Err(e) => {
+                warn!("{} rejected scrobbles: {}", target.as_str(), e);
+                let (expired, retrying): (Vec<_>, Vec<_>) =
+                    batch.iter().partition(|s| s.attempts + 1 >= MAX_ATTEMPTS);
+                let expired: Vec<String> = expired.iter().map(|s| s.id.clone()).collect();
+                let retrying: Vec<String> = retrying.iter().map(|s| s.id.clone()).collect();
+                if let Err(e) = database.delete_scrobbles(&expired).await {
+                    error!("Failed to drop rejected scrobbles: {}", e);
+                }
+                if let Err(e) = database.record_scrobble_attempt(&retrying).await {
+                    error!("Failed to record scrobble attempt: {}", e);
+                }
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! When a listen counts as a scrobble.
+//!
+//! Last.fm and ListenBrainz share the rule: a track longer than 30 seconds
+//! is scrobbled once it has played for half its length or four minutes,
+//! whichever comes first. Only time actually played counts, so seeking
+//! ahead doesn't earn a scrobble.
+
+use crate::playback::{PlaybackProgress, PlaybackState};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Tracks this short or shorter are never scrobbled
+const MIN_TRACK_LENGTH: Duration = Duration::from_secs(30);
+/// Played time that always earns a scrobble
+const MAX_REQUIRED_PLAY: Duration = Duration::from_secs(240);
+/// Position jumps larger than this between updates are seeks, not playback
+const MAX_POSITION_STEP: Duration = Duration::from_secs(5);
+
+/// Something to tell the scrobbling services
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScrobbleEvent {
+    /// A track started playing
+    NowPlaying { track_id: String },
+    /// A track played long enough to scrobble
+    Scrobble {
+        track_id: String,
+        started_at: DateTime<Utc>,
+    },
+}
+
+/// The listen in progress
+#[derive(Debug)]
+struct Listen {
+    track_id: String,
+    started_at: DateTime<Utc>,
+    duration: Option<Duration>,
+    played: Duration,
+    last_position: Duration,
+    scrobbled: bool,
+}
+
+impl Listen {
+    fn is_scrobblable(&self) -> bool {
+        let required = match self.duration {
+            Some(duration) if duration <= MIN_TRACK_LENGTH => return false,
+            Some(duration) => (duration / 2).min(MAX_REQUIRED_PLAY),
+            None => MAX_REQUIRED_PLAY,
+        };
+        self.played >= required
+    }
+}
+
+/// Follows playback progress and reports now-playing changes and listens
+/// that earned a scrobble
+#[derive(Debug, Default)]
+pub struct ScrobbleTracker {
+    current: Option<Listen>,
+}
+
+impl ScrobbleTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_progress(
+        &mut self,
+        progress: &PlaybackProgress,
+        now: DateTime<Utc>,
+    ) -> Vec<ScrobbleEvent> {
+        match progress {
+            PlaybackProgress::StateChanged {
+                state:
+                    PlaybackState::Playing {
+                        track,
+                        position,
+                        duration,
+                        ..
+                    },
+            } => self.start(&track.id, *position, *duration, now),
+            PlaybackProgress::StateChanged {
+                state: PlaybackState::Stopped,
+            } => {
+                self.current = None;
+                Vec::new()
+            }
+            PlaybackProgress::PositionUpdate { position, track_id } => {
+                let mut events = self.start(track_id, *position, None, now);
+                if let Some(event) = self.advance(*position) {
+                    events.push(event);
+                }
+                events
+            }
+            PlaybackProgress::Seeked {
+                position, track_id, ..
+            } => {
+                if let Some(listen) = self.current.as_mut().filter(|l| &l.track_id == track_id) {
+                    listen.last_position = *position;
+                }
+                Vec::new()
+            }
+            // A repeat of the same track is a new listen
+            PlaybackProgress::TrackCompleted { .. } => {
+                self.current = None;
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Begin a listen unless `track_id` is the one already playing, as it is
+    /// when resuming from pause
+    fn start(
+        &mut self,
+        track_id: &str,
+        position: Duration,
+        duration: Option<Duration>,
+        now: DateTime<Utc>,
+    ) -> Vec<ScrobbleEvent> {
+        if let Some(listen) = self.current.as_mut().filter(|l| l.track_id == track_id) {
+            if listen.duration.is_none() {
+                listen.duration = duration;
+            }
+            return Vec::new();
+        }
+        self.current = Some(Listen {
+            track_id: track_id.to_string(),
+            started_at: now - chrono::Duration::from_std(position).unwrap_or_default(),
+            duration,
+            played: Duration::ZERO,
+            last_position: position,
+            scrobbled: false,
+        });
+        vec![ScrobbleEvent::NowPlaying {
+            track_id: track_id.to_string(),
+        }]
+    }
+
+    fn advance(&mut self, position: Duration) -> Option<ScrobbleEvent> {
+        let listen = self.current.as_mut()?;
+        if position > listen.last_position {
+            let step = position - listen.last_position;
+            if step <= MAX_POSITION_STEP {
+                listen.played += step;
+            }
+        }
+        listen.last_position = position;
+
+        if listen.scrobbled || !listen.is_scrobblable() {
+            return None;
+        }
+        listen.scrobbled = true;
+        Some(ScrobbleEvent::Scrobble {
+            track_id: listen.track_id.clone(),
+            started_at: listen.started_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::DbTrack;
+
+    fn playing(track_id: &str, duration_secs: u64) -> PlaybackProgress {
+        let track = DbTrack::new_test("release-1", track_id, "Track", Some(1));
+        PlaybackProgress::StateChanged {
+            state: PlaybackState::Playing {
+                track,
+                position: Duration::ZERO,
+                duration: Some(Duration::from_secs(duration_secs)),
+                decoded_duration: Duration::from_secs(duration_secs),
+                pregap_ms: None,
+            },
+        }
+    }
+
+    /// Feed one-second position updates up to `until_secs`, returning the
+    /// scrobbles they produced
+    fn play_to(
+        tracker: &mut ScrobbleTracker,
+        track_id: &str,
+        from_secs: u64,
+        until_secs: u64,
+    ) -> Vec<ScrobbleEvent> {
+        let now = Utc::now();
+        (from_secs..=until_secs)
+            .flat_map(|secs| {
+                tracker.on_progress(
+                    &PlaybackProgress::PositionUpdate {
+                        position: Duration::from_secs(secs),
+                        track_id: track_id.to_string(),
+                    },
+                    now,
+                )
+            })
+            .filter(|e| matches!(e, ScrobbleEvent::Scrobble { .. }))
+            .collect()
+    }
+
+    #[test]
+    fn test_scrobbles_at_half_or_four_minutes() {
+        let mut tracker = ScrobbleTracker::new();
+        let events = tracker.on_progress(&playing("t1", 200), Utc::now());
+        assert_eq!(
+            events,
+            vec![ScrobbleEvent::NowPlaying {
+                track_id: "t1".to_string()
+            }]
+        );
+        assert!(play_to(&mut tracker, "t1", 0, 99).is_empty());
+        assert_eq!(play_to(&mut tracker, "t1", 100, 150).len(), 1);
+        // Only once per listen
+        assert!(play_to(&mut tracker, "t1", 151, 200).is_empty());
+
+        // A long track needs four minutes, not half
+        let mut tracker = ScrobbleTracker::new();
+        tracker.on_progress(&playing("t2", 1200), Utc::now());
+        assert!(play_to(&mut tracker, "t2", 0, 239).is_empty());
+        assert_eq!(play_to(&mut tracker, "t2", 240, 240).len(), 1);
+    }
+
+    #[test]
+    fn test_seeking_ahead_and_short_tracks_do_not_scrobble() {
+        let mut tracker = ScrobbleTracker::new();
+        tracker.on_progress(&playing("t1", 200), Utc::now());
+        play_to(&mut tracker, "t1", 0, 10);
+        tracker.on_progress(
+            &PlaybackProgress::Seeked {
+                position: Duration::from_secs(180),
+                track_id: "t1".to_string(),
+                was_paused: false,
+            },
+            Utc::now(),
+        );
+        assert!(play_to(&mut tracker, "t1", 180, 200).is_empty());
+
+        let mut tracker = ScrobbleTracker::new();
+        tracker.on_progress(&playing("t2", 30), Utc::now());
+        assert!(play_to(&mut tracker, "t2", 0, 30).is_empty());
+    }
+
+    #[test]
+    fn test_resume_keeps_listen_and_repeat_starts_new_one() {
+        let mut tracker = ScrobbleTracker::new();
+        tracker.on_progress(&playing("t1", 200), Utc::now());
+        play_to(&mut tracker, "t1", 0, 60);
+        // Resuming after a pause is the same listen
+        assert!(tracker
+            .on_progress(&playing("t1", 200), Utc::now())
+            .is_empty());
+        assert_eq!(play_to(&mut tracker, "t1", 61, 100).len(), 1);
+
+        tracker.on_progress(
+            &PlaybackProgress::TrackCompleted {
+                track_id: "t1".to_string(),
+            },
+            Utc::now(),
+        );
+        assert_eq!(
+            tracker.on_progress(&playing("t1", 200), Utc::now()).len(),
+            1
+        );
+        assert_eq!(play_to(&mut tracker, "t1", 0, 100).len(), 1);
+    }
+}
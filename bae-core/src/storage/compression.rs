@@ -0,0 +1,80 @@
+//! Optional zstd compression for stored release files
+//!
+//! Applied before encryption (ciphertext doesn't compress) for profiles with
+//! compression enabled. Whether an object was compressed is recorded on its
+//! [`DbFile`](crate::db::DbFile), so reads decompress only what was compressed.
+//!
+//! Audio is never compressed: it's already compressed, and playback reads it
+//! by byte range, which a zstd frame can't serve.
+
+const ZSTD_LEVEL: i32 = 3;
+
+/// Formats that are already compressed, skipped without trying
+const PRECOMPRESSED_EXTENSIONS: [&str; 19] = [
+    "flac", "mp3", "m4a", "aac", "ogg", "opus", "wv", "ape", "jpg", "jpeg", "png", "webp", "gif",
+    "zip", "mkv", "mp4", "m4v", "mov", "webm",
+];
+
+/// Fraction of the original size the compressed copy must save to be kept
+const MIN_SAVINGS: f64 = 0.05;
+
+/// Compress a file for storage if it's worth it.
+///
+/// Returns None for precompressed formats and for data that doesn't shrink
+/// by at least [`MIN_SAVINGS`]; the caller stores the original bytes then.
+pub fn compress_for_storage(filename: &str, data: &[u8]) -> std::io::Result<Option<Vec<u8>>> {
+    let extension = std::path::Path::new(filename)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    if PRECOMPRESSED_EXTENSIONS.contains(&extension.as_str()) || data.is_empty() {
+        return Ok(None);
+    }
+
+    let compressed = zstd::encode_all(data, ZSTD_LEVEL)?;
+    if compressed.len() as f64 > data.len() as f64 * (1.0 - MIN_SAVINGS) {
+        return Ok(None);
+    }
+    Ok(Some(compressed))
+}
+
+/// Reverse [`compress_for_storage`]
+pub fn decompress(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    zstd::decode_all(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let log = b"Track 01: Accurately ripped (confidence 12)\n".repeat(200);
+        let compressed = compress_for_storage("rip.log", &log).unwrap().unwrap();
+        assert!(compressed.len() < log.len());
+        assert_eq!(decompress(&compressed).unwrap(), log);
+    }
+
+    #[test]
+    fn test_skips_precompressed_formats() {
+        let data = vec![0u8; 4096];
+        assert_eq!(compress_for_storage("01 Track.FLAC", &data).unwrap(), None);
+        assert_eq!(compress_for_storage("cover.jpg", &data).unwrap(), None);
+    }
+
+    #[test]
+    fn test_skips_incompressible_data() {
+        // xorshift noise doesn't compress
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let noise: Vec<u8> = (0..4096)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect();
+        assert_eq!(compress_for_storage("scan.tiff", &noise).unwrap(), None);
+    }
+}
@@ -0,0 +1,70 @@
+//! Estimated cloud footprint of a release, shown before an import is confirmed.
+//!
+//! Each release file becomes one object. Encryption adds a nonce and an auth
+//! tag per chunk; compression isn't predicted, so for compressed profiles the
+//! estimate is an upper bound (only small text files ever shrink).
+
+use crate::encryption::encrypted_size;
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Objects and bytes a release would take up in cloud storage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageEstimate {
+    pub object_count: usize,
+    /// Size as stored, after encryption
+    pub stored_bytes: u64,
+}
+
+impl StorageEstimate {
+    /// Monthly cost at a flat price per GB-month
+    pub fn monthly_cost(&self, price_per_gb: f64) -> f64 {
+        self.stored_bytes as f64 / BYTES_PER_GB * price_per_gb
+    }
+}
+
+/// Estimate storage for files of the given sizes
+pub fn estimate_storage(
+    file_sizes: impl IntoIterator<Item = u64>,
+    encrypted: bool,
+) -> StorageEstimate {
+    let mut estimate = StorageEstimate {
+        object_count: 0,
+        stored_bytes: 0,
+    };
+    for size in file_sizes {
+        estimate.object_count += 1;
+        estimate.stored_bytes += if encrypted {
+            encrypted_size(size)
+        } else {
+            size
+        };
+    }
+    estimate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encryption_overhead_is_counted_per_object() {
+        let sizes = [30_000_000, 1_200_000, 2_000];
+        let plain = estimate_storage(sizes, false);
+        let encrypted = estimate_storage(sizes, true);
+
+        assert_eq!(plain.object_count, 3);
+        assert_eq!(plain.stored_bytes, 31_202_000);
+        let overhead: u64 = sizes.iter().map(|&s| encrypted_size(s) - s).sum();
+        assert_eq!(encrypted.stored_bytes, plain.stored_bytes + overhead);
+    }
+
+    #[test]
+    fn test_monthly_cost() {
+        let estimate = StorageEstimate {
+            object_count: 12,
+            stored_bytes: 2 * 1024 * 1024 * 1024,
+        };
+        assert!((estimate.monthly_cost(0.023) - 0.046).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,182 @@
+//! Filesystem-safe names for stored release files
+//!
+//! Source filenames can contain characters that are illegal on the storage
+//! filesystem (Windows rejects `<>:"\|?*`). The profile's [`FilenamePolicy`]
+//! decides how they are rewritten, and [`StoredNames`] keeps the rewritten
+//! names unique within a release.
+use crate::db::FilenamePolicy;
+use std::collections::HashSet;
+
+/// Names Windows reserves regardless of extension
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Sanitize a relative file path, keeping `/` as the directory separator.
+pub fn sanitize_filename(path: &str, policy: FilenamePolicy) -> String {
+    path.split('/')
+        .filter(|component| !component.is_empty())
+        .map(|component| sanitize_component(component, policy))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn sanitize_component(component: &str, policy: FilenamePolicy) -> String {
+    let mut sanitized = String::with_capacity(component.len());
+    for c in component.chars() {
+        if !is_illegal(c) {
+            sanitized.push(c);
+            continue;
+        }
+        match policy {
+            FilenamePolicy::Strip => {}
+            FilenamePolicy::Replace => sanitized.push('_'),
+            FilenamePolicy::Transliterate => {
+                if let Some(lookalike) = lookalike(c) {
+                    sanitized.push_str(lookalike);
+                }
+            }
+        }
+    }
+
+    // Windows silently drops trailing dots and spaces
+    let trimmed_len = sanitized.trim_end_matches(['.', ' ']).len();
+    sanitized.truncate(trimmed_len);
+    if sanitized.is_empty() || sanitized == ".." {
+        return "_".to_string();
+    }
+
+    let stem = sanitized.split('.').next().unwrap_or("");
+    if RESERVED_NAMES
+        .iter()
+        .any(|reserved| stem.eq_ignore_ascii_case(reserved))
+    {
+        sanitized.insert(stem.len(), '_');
+    }
+    sanitized
+}
+
+fn is_illegal(c: char) -> bool {
+    c.is_control() || matches!(c, '<' | '>' | ':' | '"' | '\\' | '|' | '?' | '*')
+}
+
+fn lookalike(c: char) -> Option<&'static str> {
+    match c {
+        ':' | '|' | '\\' | '*' => Some("-"),
+        '"' => Some("'"),
+        '<' => Some("("),
+        '>' => Some(")"),
+        _ => None,
+    }
+}
+
+/// Tracks stored names within a release so sanitized names never collide.
+///
+/// Comparison is case-insensitive because Windows and macOS filesystems are.
+/// Collisions get ` (2)`, ` (3)`, ... before the extension, in write order.
+#[derive(Debug, Default)]
+pub struct StoredNames {
+    taken: HashSet<String>,
+}
+
+impl StoredNames {
+    /// Reserve a unique stored name for an already-sanitized path
+    pub fn reserve(&mut self, sanitized: &str) -> String {
+        if self.taken.insert(sanitized.to_lowercase()) {
+            return sanitized.to_string();
+        }
+
+        let (dir, name) = match sanitized.rsplit_once('/') {
+            Some((dir, name)) => (format!("{}/", dir), name),
+            None => (String::new(), sanitized),
+        };
+        let (stem, ext) = match name.rfind('.') {
+            Some(idx) if idx > 0 => (&name[..idx], &name[idx..]),
+            _ => (name, ""),
+        };
+        let mut n = 2;
+        loop {
+            let candidate = format!("{}{} ({}){}", dir, stem, n, ext);
+            if self.taken.insert(candidate.to_lowercase()) {
+                return candidate;
+            }
+            n += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_replace() {
+        assert_eq!(
+            sanitize_filename("01 - What?: Part 1*.flac", FilenamePolicy::Replace),
+            "01 - What__ Part 1_.flac"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_strip() {
+        assert_eq!(
+            sanitize_filename("01 - What?: Part 1*.flac", FilenamePolicy::Strip),
+            "01 - What Part 1.flac"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_transliterate() {
+        assert_eq!(
+            sanitize_filename(
+                "01 - \"Live\" <Tokyo>: Part 1.flac",
+                FilenamePolicy::Transliterate
+            ),
+            "01 - 'Live' (Tokyo)- Part 1.flac"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_keeps_directories() {
+        assert_eq!(
+            sanitize_filename("Disc 1?/01.flac", FilenamePolicy::Replace),
+            "Disc 1_/01.flac"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_trailing_dots_and_reserved_names() {
+        assert_eq!(
+            sanitize_filename("notes. ", FilenamePolicy::Replace),
+            "notes"
+        );
+        assert_eq!(
+            sanitize_filename("con.txt", FilenamePolicy::Replace),
+            "con_.txt"
+        );
+        assert_eq!(sanitize_filename("???", FilenamePolicy::Strip), "_");
+    }
+
+    #[test]
+    fn test_sanitize_legal_name_unchanged() {
+        let name = "01 - Björk (Live) [2024].flac";
+        for policy in [
+            FilenamePolicy::Strip,
+            FilenamePolicy::Replace,
+            FilenamePolicy::Transliterate,
+        ] {
+            assert_eq!(sanitize_filename(name, policy), name);
+        }
+    }
+
+    #[test]
+    fn test_stored_names_resolve_collisions_deterministically() {
+        let mut names = StoredNames::default();
+        assert_eq!(names.reserve("a_.flac"), "a_.flac");
+        assert_eq!(names.reserve("a_.flac"), "a_ (2).flac");
+        assert_eq!(names.reserve("A_.FLAC"), "A_ (3).FLAC");
+        assert_eq!(names.reserve("scans/README"), "scans/README");
+        assert_eq!(names.reserve("scans/readme"), "scans/readme (2)");
+    }
+}
@@ -0,0 +1,129 @@
+//! Local filesystem path handling for storage
+//!
+//! Windows limits ordinary paths to MAX_PATH (260 characters), which deeply
+//! nested release folders exceed. Local storage I/O goes through [`long_path`]
+//! so those writes use the `\\?\` extended-length form instead.
+use std::path::{Path, PathBuf};
+
+/// Path to use for local storage I/O.
+///
+/// On Windows, absolute paths get the `\\?\` extended-length prefix. Elsewhere
+/// the path is returned unchanged.
+pub fn long_path(path: &Path) -> PathBuf {
+    #[cfg(windows)]
+    {
+        if let Some(extended) = path.to_str().and_then(extended_length) {
+            return PathBuf::from(extended);
+        }
+    }
+    path.to_path_buf()
+}
+
+/// Convert an absolute Windows path to extended-length form.
+///
+/// Extended-length paths skip Win32 normalization, so separators are converted
+/// to `\`, `.` and `..` are resolved here, and the drive letter is uppercased
+/// so profiles on the same drive produce identical paths. Returns `None` for
+/// relative and drive-relative (`D:music`) paths, which can't be prefixed.
+#[cfg(any(windows, test))]
+fn extended_length(path: &str) -> Option<String> {
+    if path.starts_with(r"\\?\") {
+        return Some(path.to_string());
+    }
+    let path = path.replace('/', "\\");
+    let (mut result, rest) = if let Some(unc) = path.strip_prefix(r"\\") {
+        let mut parts = unc.splitn(3, '\\');
+        let server = parts.next().filter(|s| !s.is_empty())?;
+        let share = parts.next().filter(|s| !s.is_empty())?;
+        (
+            format!(r"\\?\UNC\{}\{}", server, share),
+            parts.next().unwrap_or(""),
+        )
+    } else {
+        let bytes = path.as_bytes();
+        if bytes.len() < 3
+            || !bytes[0].is_ascii_alphabetic()
+            || bytes[1] != b':'
+            || bytes[2] != b'\\'
+        {
+            return None;
+        }
+        (
+            format!(r"\\?\{}:", (bytes[0] as char).to_ascii_uppercase()),
+            &path[3..],
+        )
+    };
+    let mut components: Vec<&str> = Vec::new();
+    for component in rest.split('\\') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            c => components.push(c),
+        }
+    }
+    result.push('\\');
+    result.push_str(&components.join("\\"));
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extended_length_drive_path() {
+        assert_eq!(
+            extended_length(r"C:\Music\bae\release").as_deref(),
+            Some(r"\\?\C:\Music\bae\release")
+        );
+    }
+
+    #[test]
+    fn test_extended_length_normalizes_drive_letter_and_separators() {
+        assert_eq!(
+            extended_length("d:/Music/./bae/../bae/release").as_deref(),
+            Some(r"\\?\D:\Music\bae\release")
+        );
+    }
+
+    #[test]
+    fn test_extended_length_drive_root() {
+        assert_eq!(extended_length(r"E:\").as_deref(), Some(r"\\?\E:\"));
+    }
+
+    #[test]
+    fn test_extended_length_unc_path() {
+        assert_eq!(
+            extended_length(r"\\nas\music\bae\release").as_deref(),
+            Some(r"\\?\UNC\nas\music\bae\release")
+        );
+    }
+
+    #[test]
+    fn test_extended_length_keeps_prefixed_path() {
+        assert_eq!(
+            extended_length(r"\\?\C:\Music").as_deref(),
+            Some(r"\\?\C:\Music")
+        );
+    }
+
+    #[test]
+    fn test_extended_length_rejects_relative_paths() {
+        assert_eq!(extended_length(r"Music\bae"), None);
+        assert_eq!(extended_length("D:Music"), None);
+        assert_eq!(extended_length(r"\\server"), None);
+    }
+
+    #[test]
+    fn test_long_path_handles_deep_paths() {
+        let deep = std::env::temp_dir()
+            .join("a".repeat(120))
+            .join("b".repeat(120));
+        let path = long_path(&deep.join("track.flac"));
+        assert!(path.ends_with("track.flac"));
+        #[cfg(windows)]
+        assert!(path.to_string_lossy().starts_with(r"\\?\"));
+    }
+}
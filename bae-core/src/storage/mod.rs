@@ -1,10 +1,26 @@
 //! Storage abstraction layer
 //!
 //! Provides flexible storage options for releases. Storage is configured via
-//! StorageProfile (location + encrypted) and implemented by a single
+//! StorageProfile (location + compressed + encrypted) and implemented by a single
 //! ReleaseStorageImpl that applies transforms based on the profile.
+mod compression;
+mod estimate;
+mod filename;
+mod local_path;
+mod placement;
+mod quota;
 mod reader;
 mod traits;
+mod upload_journal;
 
-pub use reader::create_storage_reader;
+pub use compression::{compress_for_storage, decompress};
+pub use estimate::{estimate_storage, StorageEstimate};
+pub use filename::{sanitize_filename, StoredNames};
+pub use local_path::long_path;
+pub use placement::{choose_root, has_room, resolve_local_path};
+pub use quota::{quota_level, QuotaLevel};
+pub use reader::{
+    check_cloud_storage, create_cloud_storage, create_storage_reader, LocalFileStorage,
+};
 pub use traits::{ReleaseStorage, ReleaseStorageImpl};
+pub use upload_journal::{resume_nonce, DbUploadJournal};
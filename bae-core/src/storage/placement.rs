@@ -0,0 +1,120 @@
+//! Local profiles spanning several drives
+//!
+//! A local profile can have more than one root. New files go to the root its
+//! [`PlacementPolicy`] picks, and each file's full path is recorded, so a
+//! release split across drives reads back as usual. Files found missing at
+//! their recorded path are looked for at the same place under the other roots,
+//! which covers releases moved between drives by hand.
+use crate::db::{DbStorageProfile, PlacementPolicy};
+use crate::storage::long_path;
+use std::path::{Path, PathBuf};
+
+/// Free space to leave on a root before moving on to the next
+const HEADROOM_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Root to write a file of `size` bytes under
+pub fn choose_root(profile: &DbStorageProfile, size: u64) -> PathBuf {
+    let roots = profile.local_roots();
+    if roots.len() <= 1 {
+        return PathBuf::from(&profile.location_path);
+    }
+    let free: Vec<Option<u64>> = roots.iter().map(|r| free_space(Path::new(r))).collect();
+    PathBuf::from(roots[place(&free, size, profile.placement_policy)])
+}
+
+/// Whether `root` can take a file of `size` bytes and keep its headroom
+pub fn has_room(root: &Path, size: u64) -> bool {
+    free_space(root).is_some_and(|free| free >= size.saturating_add(HEADROOM_BYTES))
+}
+
+/// Where a stored local file is now: `path` if it's there, otherwise the first
+/// other root holding the same relative path. Falls back to `path`, so a
+/// missing file still fails with its recorded location.
+pub fn resolve_local_path<R: AsRef<Path>>(roots: &[R], path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if roots.len() <= 1 || long_path(path).exists() {
+        return path.to_path_buf();
+    }
+    let Some(relative) = roots
+        .iter()
+        .find_map(|root| path.strip_prefix(root.as_ref()).ok())
+    else {
+        return path.to_path_buf();
+    };
+    roots
+        .iter()
+        .map(|root| root.as_ref().join(relative))
+        .find(|candidate| long_path(candidate).exists())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Index of the root to use, given each root's free space (None when it
+/// can't be read, as for an unmounted drive)
+fn place(free: &[Option<u64>], size: u64, policy: PlacementPolicy) -> usize {
+    let most_free = || {
+        free.iter()
+            .enumerate()
+            .max_by_key(|(i, free)| (free.unwrap_or(0), std::cmp::Reverse(*i)))
+            .map_or(0, |(i, _)| i)
+    };
+    match policy {
+        PlacementPolicy::FillFirst => free
+            .iter()
+            .position(|free| free.is_some_and(|f| f >= size.saturating_add(HEADROOM_BYTES)))
+            .unwrap_or_else(most_free),
+        PlacementPolicy::MostFreeSpace => most_free(),
+    }
+}
+
+fn free_space(root: &Path) -> Option<u64> {
+    // A root not made yet can go on a mounted drive, but look no further up:
+    // a missing parent is more likely an unmounted drive than a new folder
+    fs4::available_space(root)
+        .ok()
+        .or_else(|| fs4::available_space(root.parent()?).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn test_place_fill_first() {
+        let policy = PlacementPolicy::FillFirst;
+        assert_eq!(place(&[Some(10 * GB), Some(100 * GB)], GB, policy), 0);
+        // The first drive is full, or gone
+        assert_eq!(place(&[Some(GB), Some(100 * GB)], GB, policy), 1);
+        assert_eq!(place(&[None, Some(100 * GB)], GB, policy), 1);
+        // Nowhere has room: try the emptiest
+        assert_eq!(place(&[Some(GB / 2), Some(GB)], 2 * GB, policy), 1);
+        assert_eq!(place(&[None, None], GB, policy), 0);
+    }
+
+    #[test]
+    fn test_place_most_free_space() {
+        let policy = PlacementPolicy::MostFreeSpace;
+        assert_eq!(place(&[Some(10 * GB), Some(100 * GB)], GB, policy), 1);
+        assert_eq!(place(&[Some(10 * GB), None], GB, policy), 0);
+        // Ties go to the earlier root
+        assert_eq!(place(&[Some(GB), Some(GB)], GB, policy), 0);
+    }
+
+    #[test]
+    fn test_resolve_local_path_checks_other_roots() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        let roots = [first.path(), second.path()];
+        let recorded = first.path().join("release").join("01.flac");
+        let recorded = recorded.to_str().unwrap();
+
+        // Missing everywhere: the recorded path, for the error
+        assert_eq!(resolve_local_path(&roots, recorded), Path::new(recorded));
+
+        let moved = second.path().join("release").join("01.flac");
+        std::fs::create_dir_all(moved.parent().unwrap()).unwrap();
+        std::fs::write(&moved, b"audio").unwrap();
+        assert_eq!(resolve_local_path(&roots, recorded), moved);
+    }
+}
@@ -0,0 +1,48 @@
+//! Soft storage quotas per profile.
+//!
+//! A quota never stops a write. Settings shows usage against it, and the import
+//! confirm step asks for an override before adding to a profile that's over.
+
+/// Fraction of the quota at which usage starts being flagged
+const QUOTA_WARNING_FRACTION: f64 = 0.8;
+
+/// How close a profile is to its quota
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaLevel {
+    Normal,
+    Warning,
+    Exceeded,
+}
+
+/// Classify usage against a quota. Reaching the quota exactly counts as exceeded,
+/// since the next import would go over.
+pub fn quota_level(used_bytes: u64, quota_bytes: u64) -> QuotaLevel {
+    if used_bytes >= quota_bytes {
+        QuotaLevel::Exceeded
+    } else if used_bytes as f64 >= quota_bytes as f64 * QUOTA_WARNING_FRACTION {
+        QuotaLevel::Warning
+    } else {
+        QuotaLevel::Normal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    #[test]
+    fn test_levels() {
+        assert_eq!(quota_level(0, 500 * GB), QuotaLevel::Normal);
+        assert_eq!(quota_level(399 * GB, 500 * GB), QuotaLevel::Normal);
+        assert_eq!(quota_level(400 * GB, 500 * GB), QuotaLevel::Warning);
+        assert_eq!(quota_level(500 * GB, 500 * GB), QuotaLevel::Exceeded);
+        assert_eq!(quota_level(600 * GB, 500 * GB), QuotaLevel::Exceeded);
+    }
+
+    #[test]
+    fn test_zero_quota_is_always_exceeded() {
+        assert_eq!(quota_level(0, 0), QuotaLevel::Exceeded);
+    }
+}
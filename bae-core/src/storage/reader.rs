@@ -1,14 +1,16 @@
 //! Storage reader utilities for creating storage clients from profiles
-use crate::cloud_storage::{CloudStorage, CloudStorageError, S3CloudStorage};
-use crate::db::{DbStorageProfile, StorageLocation};
+use crate::cloud_storage::{B2CloudStorage, CloudStorage, CloudStorageError, S3CloudStorage};
+use crate::db::{CloudProvider, DbStorageProfile, StorageLocation};
+use crate::storage::{long_path, resolve_local_path};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::io::{AsyncReadExt, AsyncSeekExt};
 use tracing::debug;
 
 /// Create a storage reader from a profile.
 ///
-/// For cloud profiles: creates S3CloudStorage from profile credentials
-/// For local profiles: returns LocalFileStorage that reads from disk
+/// For cloud profiles: creates the provider's client from profile credentials
+/// For local and reference profiles: returns LocalFileStorage that reads from disk
 pub async fn create_storage_reader(
     profile: &DbStorageProfile,
 ) -> Result<Arc<dyn CloudStorage>, CloudStorageError> {
@@ -18,29 +20,90 @@ pub async fn create_storage_reader(
     );
 
     match profile.location {
-        StorageLocation::Cloud => {
+        StorageLocation::Cloud => create_cloud_storage(profile).await,
+        StorageLocation::Local | StorageLocation::Reference => Ok(Arc::new(LocalFileStorage::new(
+            profile
+                .local_roots()
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+        ))),
+    }
+}
+
+/// Create the client for a cloud profile's bucket, using the profile's provider
+pub async fn create_cloud_storage(
+    profile: &DbStorageProfile,
+) -> Result<Arc<dyn CloudStorage>, CloudStorageError> {
+    match profile.cloud_provider {
+        CloudProvider::S3 => {
             let s3_config = profile.to_s3_config().ok_or_else(|| {
                 CloudStorageError::Config("Missing S3 credentials in profile".into())
             })?;
-            let client = S3CloudStorage::new(s3_config).await?;
-            Ok(Arc::new(client))
+            Ok(Arc::new(S3CloudStorage::new(s3_config).await?))
+        }
+        CloudProvider::B2 => {
+            let b2_config = profile.to_b2_config().ok_or_else(|| {
+                CloudStorageError::Config("Missing B2 credentials in profile".into())
+            })?;
+            Ok(Arc::new(B2CloudStorage::new(b2_config).await?))
+        }
+    }
+}
+
+/// Check a cloud profile's bucket is reachable with its credentials.
+/// Unlike [`create_cloud_storage`], never creates the bucket.
+pub async fn check_cloud_storage(profile: &DbStorageProfile) -> Result<(), CloudStorageError> {
+    match profile.cloud_provider {
+        CloudProvider::S3 => {
+            let s3_config = profile.to_s3_config().ok_or_else(|| {
+                CloudStorageError::Config("Missing S3 credentials in profile".into())
+            })?;
+            S3CloudStorage::new_with_bucket_creation(s3_config, false)
+                .await?
+                .check_bucket()
+                .await
+        }
+        CloudProvider::B2 => {
+            let b2_config = profile.to_b2_config().ok_or_else(|| {
+                CloudStorageError::Config("Missing B2 credentials in profile".into())
+            })?;
+            // Connecting authorizes the key and looks the bucket up
+            B2CloudStorage::new(b2_config).await.map(|_| ())
         }
-        StorageLocation::Local => Ok(Arc::new(LocalFileStorage)),
     }
 }
 
 /// Local file storage that reads files from disk paths.
-pub struct LocalFileStorage;
+///
+/// With the roots of a multi-root profile, files missing from their path are
+/// looked for under the other roots.
+#[derive(Default)]
+pub struct LocalFileStorage {
+    roots: Vec<PathBuf>,
+}
+
+impl LocalFileStorage {
+    pub fn new(roots: Vec<PathBuf>) -> Self {
+        LocalFileStorage { roots }
+    }
+
+    fn resolve(&self, path: &str) -> PathBuf {
+        long_path(&resolve_local_path(&self.roots, path))
+    }
+}
 
 #[async_trait::async_trait]
 impl CloudStorage for LocalFileStorage {
     async fn upload(&self, path: &str, data: &[u8]) -> Result<String, CloudStorageError> {
-        tokio::fs::write(path, data).await?;
+        tokio::fs::write(long_path(Path::new(path)), data).await?;
         Ok(path.to_string())
     }
 
     async fn download(&self, path: &str) -> Result<Vec<u8>, CloudStorageError> {
-        tokio::fs::read(path).await.map_err(CloudStorageError::Io)
+        tokio::fs::read(self.resolve(path))
+            .await
+            .map_err(CloudStorageError::Io)
     }
 
     async fn download_range(
@@ -56,7 +119,7 @@ impl CloudStorage for LocalFileStorage {
             )));
         }
 
-        let mut file = tokio::fs::File::open(path).await?;
+        let mut file = tokio::fs::File::open(self.resolve(path)).await?;
         file.seek(std::io::SeekFrom::Start(start)).await?;
 
         let max_len = (end - start) as usize;
@@ -69,7 +132,7 @@ impl CloudStorage for LocalFileStorage {
     }
 
     async fn delete(&self, path: &str) -> Result<(), CloudStorageError> {
-        tokio::fs::remove_file(path)
+        tokio::fs::remove_file(self.resolve(path))
             .await
             .map_err(CloudStorageError::Io)
     }
@@ -1,12 +1,19 @@
 //! Storage trait and implementation
-use crate::cloud_storage::{CloudStorage, S3CloudStorage};
+use crate::cloud_storage::{CloudStorage, CloudStorageError, VerifiedUpload};
 use crate::db::{Database, DbFile, DbStorageProfile, StorageLocation};
-use crate::encryption::EncryptionService;
+use crate::encryption::{random_base_nonce, EncryptionService};
+use crate::sodium_ffi;
+use crate::storage::{
+    choose_root, compress_for_storage, create_cloud_storage, has_room, long_path, resume_nonce,
+    sanitize_filename, DbUploadJournal, StoredNames,
+};
 use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use thiserror::Error;
-use tracing::info;
+use tracing::{info, warn};
 
 #[derive(Error, Debug)]
 pub enum StorageError {
@@ -18,6 +25,8 @@ pub enum StorageError {
     Cloud(String),
     #[error("Database error: {0}")]
     Database(String),
+    #[error("Reference profiles are read-only")]
+    ReadOnly,
 }
 
 /// Progress callback type: (bytes_written, total_bytes)
@@ -31,7 +40,8 @@ pub type ProgressCallback = Box<dyn Fn(usize, usize) + Send + Sync>;
 pub trait ReleaseStorage: Send + Sync {
     /// Write a file to storage with progress reporting.
     ///
-    /// Streams write in 1MB batches for progress reporting.
+    /// Streams write in 1MB batches for progress reporting. The stored name is
+    /// sanitized per the profile's filename policy; the DbFile keeps `filename`.
     async fn write_file(
         &self,
         release_id: &str,
@@ -43,32 +53,41 @@ pub trait ReleaseStorage: Send + Sync {
 
 /// Storage implementation that applies transforms based on StorageProfile flags
 ///
-/// Handles combinations of (local/cloud) × (encrypted/plain).
-/// Transforms are applied in sequence: encrypt → store.
+/// Handles combinations of (local/cloud) × (compressed/plain) × (encrypted/plain).
+/// Transforms are applied in sequence: compress → encrypt → store.
 #[derive(Clone)]
 pub struct ReleaseStorageImpl {
     profile: DbStorageProfile,
     encryption: Option<EncryptionService>,
     cloud: Option<Arc<dyn CloudStorage>>,
     database: Option<Arc<Database>>,
+    /// Stored names already used per release, for collision resolution
+    stored_names: Arc<Mutex<HashMap<String, StoredNames>>>,
+    /// Local root each release is being written to
+    release_roots: Arc<Mutex<HashMap<String, PathBuf>>>,
+    /// Encrypt on the calling thread instead of every core
+    single_core: bool,
 }
 
 impl ReleaseStorageImpl {
-    /// Create storage from a profile, creating S3 client from profile credentials if needed.
+    /// Create storage from a profile, creating the cloud client from profile credentials if needed.
     pub async fn from_profile(
         profile: DbStorageProfile,
         encryption: Option<EncryptionService>,
         database: Arc<Database>,
     ) -> Result<Self, StorageError> {
+        if profile.location == StorageLocation::Reference {
+            return Err(StorageError::ReadOnly);
+        }
         let cloud: Option<Arc<dyn CloudStorage>> = if profile.location == StorageLocation::Cloud {
-            let s3_config = profile
-                .to_s3_config()
-                .ok_or_else(|| StorageError::Cloud("Missing S3 credentials in profile".into()))?;
-            let client = S3CloudStorage::new(s3_config)
+            let client = create_cloud_storage(&profile)
                 .await
                 .map_err(|e| StorageError::Cloud(e.to_string()))?;
-            info!("Created S3 client for profile: {}", profile.name);
-            Some(Arc::new(client))
+            info!(
+                "Created {:?} client for profile: {}",
+                profile.cloud_provider, profile.name
+            );
+            Some(client)
         } else {
             None
         };
@@ -78,6 +97,9 @@ impl ReleaseStorageImpl {
             encryption,
             cloud,
             database: Some(database),
+            stored_names: Arc::default(),
+            release_roots: Arc::default(),
+            single_core: false,
         })
     }
 
@@ -94,21 +116,69 @@ impl ReleaseStorageImpl {
             encryption,
             cloud: Some(cloud),
             database: Some(database),
+            stored_names: Arc::default(),
+            release_roots: Arc::default(),
+            single_core: false,
         }
     }
 
-    /// Get the local path for a release's files
-    fn release_path(&self, release_id: &str) -> PathBuf {
-        PathBuf::from(&self.profile.location_path).join(release_id)
+    /// Keep encryption on one core, as low power imports do
+    pub fn single_core(mut self, single_core: bool) -> Self {
+        self.single_core = single_core;
+        self
+    }
+
+    /// Local root for a release's next file of `size` bytes. A release stays
+    /// on one root until that root runs out of room.
+    fn release_root(&self, release_id: &str, size: u64) -> PathBuf {
+        let mut roots = self.release_roots.lock().unwrap();
+        if let Some(root) = roots.get(release_id) {
+            if has_room(root, size) {
+                return root.clone();
+            }
+        }
+        let root = choose_root(&self.profile, size);
+        roots.insert(release_id.to_string(), root.clone());
+        root
     }
 
     /// Get the full path for a specific file
-    fn file_path(&self, release_id: &str, filename: &str) -> PathBuf {
-        self.release_path(release_id).join(filename)
+    fn file_path(&self, release_id: &str, filename: &str, size: u64) -> PathBuf {
+        self.release_root(release_id, size)
+            .join(release_id)
+            .join(filename)
+    }
+
+    /// Filesystem-safe name to store `filename` under, unique within the release
+    fn stored_name(&self, release_id: &str, filename: &str) -> String {
+        let sanitized = sanitize_filename(filename, self.profile.filename_policy);
+        self.stored_names
+            .lock()
+            .unwrap()
+            .entry(release_id.to_string())
+            .or_default()
+            .reserve(&sanitized)
+    }
+
+    /// Compress data if the profile enables it and the file benefits.
+    /// Returns None when the original bytes should be stored.
+    fn compress_if_needed(
+        &self,
+        filename: &str,
+        data: &[u8],
+    ) -> Result<Option<Vec<u8>>, StorageError> {
+        if !self.profile.compressed {
+            return Ok(None);
+        }
+        Ok(compress_for_storage(filename, data)?)
     }
 
-    /// Encrypt data if encryption is enabled
-    fn encrypt_if_needed(&self, data: &[u8]) -> Result<Vec<u8>, StorageError> {
+    /// Encrypt data with `base_nonce` if encryption is enabled
+    fn encrypt_if_needed(
+        &self,
+        data: &[u8],
+        base_nonce: &[u8; sodium_ffi::NPUBBYTES],
+    ) -> Result<Vec<u8>, StorageError> {
         if !self.profile.encrypted {
             return Ok(data.to_vec());
         }
@@ -116,40 +186,77 @@ impl ReleaseStorageImpl {
             .encryption
             .as_ref()
             .ok_or(StorageError::NotConfigured)?;
-        Ok(encryption.encrypt(data))
+        Ok(encryption.encrypt_chunked_with_nonce(data, base_nonce, self.single_core))
     }
 
     /// Generate a storage key for cloud storage
     fn cloud_key(&self, release_id: &str, filename: &str) -> String {
         format!("{}/{}", release_id, filename)
     }
-}
 
-#[async_trait]
-impl ReleaseStorage for ReleaseStorageImpl {
-    async fn write_file(
+    /// Upload to the cloud, resumably when there's a database to journal in
+    async fn upload_to_cloud(
+        &self,
+        cloud: &dyn CloudStorage,
+        key: &str,
+        data: &[u8],
+        source_sha256: &str,
+        base_nonce: &[u8; sodium_ffi::NPUBBYTES],
+    ) -> Result<VerifiedUpload, CloudStorageError> {
+        match &self.database {
+            Some(db) => {
+                let journal = DbUploadJournal::new(
+                    db.clone(),
+                    source_sha256.to_string(),
+                    self.profile.encrypted.then(|| base_nonce.to_vec()),
+                );
+                cloud.upload_resumable(key, data, &journal).await
+            }
+            None => cloud.upload_verified(key, data).await,
+        }
+    }
+
+    /// Store a file without recording it. Returns a new DbFile describing
+    /// where and how it was stored, for the caller to insert or to copy onto
+    /// an existing record.
+    pub async fn store_file(
         &self,
         release_id: &str,
         filename: &str,
         data: &[u8],
         on_progress: ProgressCallback,
-    ) -> Result<(), StorageError> {
+    ) -> Result<DbFile, StorageError> {
         use tokio::io::AsyncWriteExt;
 
         let total_bytes = data.len();
         on_progress(0, total_bytes);
 
-        let data_to_store = self.encrypt_if_needed(data)?;
+        let stored_name = self.stored_name(release_id, filename);
+        let key = self.cloud_key(release_id, &stored_name);
 
-        let storage_path = match self.profile.location {
+        // An interrupted cloud upload of this same file is resumed, which
+        // needs the ciphertext it was sending reproduced exactly
+        let source_sha256 = hex::encode(Sha256::digest(data));
+        let resumable = match (&self.database, self.profile.location) {
+            (Some(db), StorageLocation::Cloud) => resume_nonce(db, &key, &source_sha256).await,
+            _ => None,
+        };
+        let base_nonce = resumable.unwrap_or_else(random_base_nonce);
+
+        let compressed = self.compress_if_needed(filename, data)?;
+        let data_to_store =
+            self.encrypt_if_needed(compressed.as_deref().unwrap_or(data), &base_nonce)?;
+
+        let (storage_path, checksum_verified) = match self.profile.location {
             StorageLocation::Local => {
-                let path = self.file_path(release_id, filename);
-                if let Some(parent) = path.parent() {
+                let path = self.file_path(release_id, &stored_name, data_to_store.len() as u64);
+                let io_path = long_path(&path);
+                if let Some(parent) = io_path.parent() {
                     tokio::fs::create_dir_all(parent).await?;
                 }
 
                 let batch_size = 1_048_576; // 1MB batches for progress reporting
-                let file = tokio::fs::File::create(&path).await?;
+                let file = tokio::fs::File::create(&io_path).await?;
                 let mut writer = tokio::io::BufWriter::new(file);
                 let mut bytes_written = 0usize;
 
@@ -168,35 +275,78 @@ impl ReleaseStorage for ReleaseStorageImpl {
                 }
 
                 writer.flush().await?;
-                path.display().to_string()
+                (path.display().to_string(), false)
             }
             StorageLocation::Cloud => {
-                let cloud = self.cloud.as_ref().ok_or(StorageError::NotConfigured)?;
-                let key = self.cloud_key(release_id, filename);
-                let storage_location = cloud
-                    .upload(&key, &data_to_store)
+                let cloud = self.cloud.as_deref().ok_or(StorageError::NotConfigured)?;
+                let upload = match self
+                    .upload_to_cloud(cloud, &key, &data_to_store, &source_sha256, &base_nonce)
                     .await
-                    .map_err(|e| StorageError::Cloud(e.to_string()))?;
+                {
+                    // Another device stored different data at this key
+                    // first. Keep its copy and put ours beside it.
+                    Err(CloudStorageError::Occupied(reason)) => {
+                        let beside = beside_key(&key, &source_sha256);
+                        warn!("{}, storing ours at {}", reason, beside);
+                        self.upload_to_cloud(
+                            cloud,
+                            &beside,
+                            &data_to_store,
+                            &source_sha256,
+                            &base_nonce,
+                        )
+                        .await
+                    }
+                    result => result,
+                }
+                .map_err(|e| StorageError::Cloud(e.to_string()))?;
                 on_progress(total_bytes, total_bytes);
-                storage_location
+                (upload.storage_location, upload.checksum_verified)
             }
+            StorageLocation::Reference => return Err(StorageError::ReadOnly),
         };
 
-        if let Some(db) = &self.database {
-            let format = std::path::Path::new(filename)
-                .extension()
-                .and_then(|e| e.to_str())
-                .unwrap_or("bin")
-                .to_lowercase();
-
-            let mut db_file = DbFile::new(release_id, filename, data.len() as i64, &format);
-            db_file.source_path = Some(storage_path);
-
-            // Extract and store encryption nonce for efficient range requests
-            if self.profile.encrypted && data_to_store.len() >= 24 {
-                db_file.encryption_nonce = Some(data_to_store[..24].to_vec());
-            }
+        let format = std::path::Path::new(filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("bin")
+            .to_lowercase();
+
+        let mut db_file = DbFile::new(release_id, filename, data.len() as i64, &format);
+        db_file.source_path = Some(storage_path);
+        db_file.compressed = compressed.is_some();
+        db_file.checksum_verified = checksum_verified;
+        db_file.sha256 = Some(source_sha256);
+
+        // Extract and store encryption nonce for efficient range requests
+        if self.profile.encrypted && data_to_store.len() >= 24 {
+            db_file.encryption_nonce = Some(data_to_store[..24].to_vec());
+        }
+
+        Ok(db_file)
+    }
+}
 
+/// Key for our copy of a file when `key` already holds another device's.
+/// Named by the file's checksum, so the same file always lands there.
+fn beside_key(key: &str, source_sha256: &str) -> String {
+    format!("{}.{}", key, &source_sha256[..16])
+}
+
+#[async_trait]
+impl ReleaseStorage for ReleaseStorageImpl {
+    async fn write_file(
+        &self,
+        release_id: &str,
+        filename: &str,
+        data: &[u8],
+        on_progress: ProgressCallback,
+    ) -> Result<(), StorageError> {
+        let db_file = self
+            .store_file(release_id, filename, data, on_progress)
+            .await?;
+
+        if let Some(db) = &self.database {
             db.insert_file(&db_file)
                 .await
                 .map_err(|e| StorageError::Database(e.to_string()))?;
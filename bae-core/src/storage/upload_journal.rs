@@ -0,0 +1,195 @@
+//! Upload progress kept in the library database, so a resumed import
+//! continues cloud uploads where the last run stopped.
+use crate::cloud_storage::{PendingUpload, UploadJournal, UploadedPart};
+use crate::db::{Database, DbCloudUpload, DbCloudUploadPart};
+use crate::sodium_ffi;
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::warn;
+
+/// [`UploadJournal`] for one file, stored in the `cloud_uploads` tables.
+///
+/// Besides the parts sent, it records which plaintext the upload is of and
+/// the nonce it was encrypted with. A rerun encrypting the same file with
+/// that nonce produces the same bytes, so the parts still fit.
+pub struct DbUploadJournal {
+    database: Arc<Database>,
+    source_sha256: String,
+    encryption_nonce: Option<Vec<u8>>,
+}
+
+impl DbUploadJournal {
+    pub fn new(
+        database: Arc<Database>,
+        source_sha256: String,
+        encryption_nonce: Option<Vec<u8>>,
+    ) -> Self {
+        DbUploadJournal {
+            database,
+            source_sha256,
+            encryption_nonce,
+        }
+    }
+}
+
+/// The nonce an unfinished upload to `key` encrypted `source_sha256` with.
+/// None if there's no such upload, or it was of different data: a nonce
+/// must never encrypt two different plaintexts.
+pub async fn resume_nonce(
+    database: &Database,
+    key: &str,
+    source_sha256: &str,
+) -> Option<[u8; sodium_ffi::NPUBBYTES]> {
+    let upload = match database.get_cloud_upload(key).await {
+        Ok(upload) => upload?,
+        Err(e) => {
+            warn!("Failed to look up earlier upload of {}: {}", key, e);
+            return None;
+        }
+    };
+    if upload.source_sha256 != source_sha256 {
+        return None;
+    }
+    upload.encryption_nonce?.as_slice().try_into().ok()
+}
+
+#[async_trait::async_trait]
+impl UploadJournal for DbUploadJournal {
+    async fn pending_upload(&self, key: &str) -> Option<PendingUpload> {
+        let upload = match self.database.get_cloud_upload(key).await {
+            Ok(upload) => upload?,
+            Err(e) => {
+                warn!("Failed to look up earlier upload of {}: {}", key, e);
+                return None;
+            }
+        };
+        Some(PendingUpload {
+            upload_id: upload.upload_id,
+            part_size: upload.part_size as u64,
+            payload_sha256: upload.payload_sha256,
+            parts: upload
+                .parts
+                .into_iter()
+                .map(|part| UploadedPart {
+                    part_number: part.part_number as i32,
+                    etag: part.etag,
+                    checksum_sha256: part.checksum_sha256,
+                })
+                .collect(),
+        })
+    }
+
+    async fn start_upload(&self, key: &str, upload: &PendingUpload) {
+        let row = DbCloudUpload {
+            key: key.to_string(),
+            upload_id: upload.upload_id.clone(),
+            part_size: upload.part_size as i64,
+            payload_sha256: upload.payload_sha256.clone(),
+            source_sha256: self.source_sha256.clone(),
+            encryption_nonce: self.encryption_nonce.clone(),
+            created_at: Utc::now(),
+            parts: Vec::new(),
+        };
+        if let Err(e) = self.database.insert_cloud_upload(&row).await {
+            warn!("Failed to record upload of {}: {}", key, e);
+        }
+    }
+
+    async fn record_part(&self, key: &str, part: &UploadedPart) {
+        let row = DbCloudUploadPart {
+            part_number: part.part_number as i64,
+            etag: part.etag.clone(),
+            checksum_sha256: part.checksum_sha256.clone(),
+        };
+        if let Err(e) = self.database.insert_cloud_upload_part(key, &row).await {
+            warn!(
+                "Failed to record part {} of {}: {}",
+                part.part_number, key, e
+            );
+        }
+    }
+
+    async fn finish_upload(&self, key: &str) {
+        if let Err(e) = self.database.delete_cloud_upload(key).await {
+            warn!("Failed to clear upload record of {}: {}", key, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn test_database() -> (TempDir, Arc<Database>) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("library.db");
+        let database = Database::new(path.to_str().unwrap()).await.unwrap();
+        (dir, Arc::new(database))
+    }
+
+    fn part(part_number: i32) -> UploadedPart {
+        UploadedPart {
+            part_number,
+            etag: format!("etag-{}", part_number),
+            checksum_sha256: format!("sum-{}", part_number),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_journal_round_trip() {
+        let (_dir, database) = test_database().await;
+        let nonce = vec![7u8; sodium_ffi::NPUBBYTES];
+        let journal = DbUploadJournal::new(database.clone(), "source".into(), Some(nonce));
+        let key = "release/track.flac";
+        assert_eq!(journal.pending_upload(key).await, None);
+
+        journal
+            .start_upload(
+                key,
+                &PendingUpload {
+                    upload_id: "upload-1".into(),
+                    part_size: 1024,
+                    payload_sha256: "payload".into(),
+                    parts: Vec::new(),
+                },
+            )
+            .await;
+        journal.record_part(key, &part(2)).await;
+        journal.record_part(key, &part(1)).await;
+
+        let pending = journal.pending_upload(key).await.unwrap();
+        assert_eq!(pending.upload_id, "upload-1");
+        assert_eq!(pending.part_size, 1024);
+        assert_eq!(pending.parts, vec![part(1), part(2)]);
+
+        journal.finish_upload(key).await;
+        assert_eq!(journal.pending_upload(key).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_resume_nonce_requires_same_source() {
+        let (_dir, database) = test_database().await;
+        let nonce = [9u8; sodium_ffi::NPUBBYTES];
+        let journal = DbUploadJournal::new(database.clone(), "source".into(), Some(nonce.to_vec()));
+        let key = "release/track.flac";
+        journal
+            .start_upload(
+                key,
+                &PendingUpload {
+                    upload_id: "upload-1".into(),
+                    part_size: 1024,
+                    payload_sha256: "payload".into(),
+                    parts: Vec::new(),
+                },
+            )
+            .await;
+
+        assert_eq!(resume_nonce(&database, key, "source").await, Some(nonce));
+        assert_eq!(resume_nonce(&database, key, "edited").await, None);
+        assert_eq!(
+            resume_nonce(&database, "release/other.flac", "source").await,
+            None
+        );
+    }
+}
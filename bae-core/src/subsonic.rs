@@ -1,26 +1,50 @@
+use crate::audio_codec;
+use crate::db::DbArtist;
+use crate::library::lyrics::{self, Lyrics};
+use crate::library::shares::{resolve_share_scope, ShareCredentials, ShareScope};
 use crate::library::LibraryError;
 use crate::library::SharedLibraryManager;
-use crate::storage::create_storage_reader;
+use crate::storage::{create_storage_reader, resolve_local_path};
+use crate::transcode::{StreamFormat, TranscodeKey, Transcoder};
 use axum::{
     extract::{Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     routing::get,
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io::Cursor;
 use tower_http::cors::CorsLayer;
 use tracing::{debug, error, info};
+/// Subsonic API version implemented
+const API_VERSION: &str = "1.16.1";
+/// OpenSubsonic extensions supported, with their versions
+const OPEN_SUBSONIC_EXTENSIONS: &[(&str, &[u32])] =
+    &[("songLyrics", &[1]), ("transcodeOffset", &[1])];
 /// Subsonic API server state
 #[derive(Clone)]
 pub struct SubsonicState {
     pub library_manager: SharedLibraryManager,
     pub encryption_service: Option<crate::encryption::EncryptionService>,
+    pub transcoder: Transcoder,
 }
 /// Common query parameters for Subsonic API
 #[derive(Debug, Deserialize)]
-pub struct SubsonicQuery {}
+pub struct SubsonicQuery {
+    /// Password, plain or `enc:` hex; carries a client share's token
+    pub p: Option<String>,
+    /// Token authentication: MD5 of a client share's token followed by `s`
+    pub t: Option<String>,
+    /// Salt for `t`
+    pub s: Option<String>,
+}
+impl SubsonicQuery {
+    fn credentials(&self) -> ShareCredentials {
+        credentials(self.p.as_deref(), self.t.as_deref(), self.s.as_deref())
+    }
+}
 /// Standard Subsonic API response envelope
 #[derive(Debug, Serialize)]
 pub struct SubsonicResponse<T> {
@@ -31,9 +55,37 @@ pub struct SubsonicResponse<T> {
 pub struct SubsonicResponseInner<T> {
     pub status: String,
     pub version: String,
+    /// Server name, for OpenSubsonic clients
+    #[serde(rename = "type")]
+    pub server_type: String,
+    #[serde(rename = "serverVersion")]
+    pub server_version: String,
+    /// Tells clients the OpenSubsonic extensions and fields are available
+    #[serde(rename = "openSubsonic")]
+    pub open_subsonic: bool,
     #[serde(flatten)]
     pub data: T,
 }
+impl<T> SubsonicResponse<T> {
+    pub fn ok(data: T) -> Self {
+        Self::with_status("ok", data)
+    }
+    pub fn failed(data: T) -> Self {
+        Self::with_status("failed", data)
+    }
+    fn with_status(status: &str, data: T) -> Self {
+        SubsonicResponse {
+            subsonic_response: SubsonicResponseInner {
+                status: status.to_string(),
+                version: API_VERSION.to_string(),
+                server_type: "bae".to_string(),
+                server_version: env!("CARGO_PKG_VERSION").to_string(),
+                open_subsonic: true,
+                data,
+            },
+        }
+    }
+}
 /// Error response for Subsonic API
 #[derive(Debug, Serialize)]
 pub struct SubsonicError {
@@ -47,6 +99,12 @@ pub struct License {
     pub email: String,
     pub key: String,
 }
+/// One of the artists credited on an album or song (OpenSubsonic)
+#[derive(Debug, Clone, Serialize)]
+pub struct ArtistRef {
+    pub id: String,
+    pub name: String,
+}
 /// Artist info for browsing
 #[derive(Debug, Serialize)]
 pub struct Artist {
@@ -70,6 +128,10 @@ pub struct Album {
     pub genre: Option<String>,
     #[serde(rename = "coverArt")]
     pub cover_art: Option<String>,
+    /// Each credited artist, where `artist` joins them into one name
+    pub artists: Vec<ArtistRef>,
+    #[serde(rename = "displayArtist")]
+    pub display_artist: String,
 }
 /// Song/track info for browsing
 #[derive(Debug, Serialize)]
@@ -95,6 +157,13 @@ pub struct Song {
     #[serde(rename = "bitRate")]
     pub bit_rate: Option<i32>,
     pub path: String,
+    pub artists: Vec<ArtistRef>,
+    #[serde(rename = "displayArtist")]
+    pub display_artist: String,
+    #[serde(rename = "albumArtists")]
+    pub album_artists: Vec<ArtistRef>,
+    #[serde(rename = "displayAlbumArtist")]
+    pub display_album_artist: String,
 }
 /// Artists index response
 #[derive(Debug, Serialize)]
@@ -120,35 +189,59 @@ pub struct AlbumListResponse {
 pub struct AlbumList {
     pub album: Vec<Album>,
 }
+/// An OpenSubsonic extension the server supports
+#[derive(Debug, Serialize)]
+pub struct OpenSubsonicExtension {
+    pub name: String,
+    pub versions: Vec<u32>,
+}
+/// Lyrics of one song (OpenSubsonic `songLyrics`)
+#[derive(Debug, Serialize)]
+pub struct StructuredLyrics {
+    #[serde(rename = "displayTitle")]
+    pub display_title: String,
+    pub lang: String,
+    pub synced: bool,
+    pub line: Vec<LyricLine>,
+}
+#[derive(Debug, Serialize)]
+pub struct LyricLine {
+    /// Start of the line in ms, for synced lyrics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<u64>,
+    pub value: String,
+}
 /// Create the Subsonic API router
 pub fn create_router(
     library_manager: SharedLibraryManager,
     encryption_service: Option<crate::encryption::EncryptionService>,
+    transcoder: Transcoder,
 ) -> Router {
     let state = SubsonicState {
         library_manager,
         encryption_service,
+        transcoder,
     };
     Router::new()
         .route("/rest/ping", get(ping))
         .route("/rest/getLicense", get(get_license))
+        .route(
+            "/rest/getOpenSubsonicExtensions",
+            get(get_open_subsonic_extensions),
+        )
         .route("/rest/getArtists", get(get_artists))
         .route("/rest/getAlbumList", get(get_album_list))
         .route("/rest/getAlbum", get(get_album))
         .route("/rest/stream", get(stream_song))
+        .route("/rest/getCoverArt", get(get_cover_art))
+        .route("/rest/getLyricsBySongId", get(get_lyrics_by_song_id))
         .layer(CorsLayer::permissive())
         .with_state(state)
 }
 /// Ping endpoint - basic connectivity test
 /// Ping endpoint - params required by Subsonic API spec but not used for simple health check
 async fn ping(Query(_params): Query<SubsonicQuery>) -> impl IntoResponse {
-    let response = SubsonicResponse {
-        subsonic_response: SubsonicResponseInner {
-            status: "ok".to_string(),
-            version: "1.16.1".to_string(),
-            data: serde_json::json!({}),
-        },
-    };
+    let response = SubsonicResponse::ok(serde_json::json!({}));
     Json(response)
 }
 /// Get license info - always return valid for open source
@@ -159,30 +252,35 @@ async fn get_license(Query(_params): Query<SubsonicQuery>) -> impl IntoResponse
         email: "opensource@bae.music".to_string(),
         key: "bae-open-source".to_string(),
     };
-    let response = SubsonicResponse {
-        subsonic_response: SubsonicResponseInner {
-            status: "ok".to_string(),
-            version: "1.16.1".to_string(),
-            data: serde_json::json!({ "license" : license }),
-        },
-    };
+    let response = SubsonicResponse::ok(serde_json::json!({ "license" : license }));
     Json(response)
 }
+/// OpenSubsonic extensions supported. Needs no auth, since clients ask
+/// before logging in.
+async fn get_open_subsonic_extensions() -> impl IntoResponse {
+    let extensions: Vec<OpenSubsonicExtension> = OPEN_SUBSONIC_EXTENSIONS
+        .iter()
+        .map(|(name, versions)| OpenSubsonicExtension {
+            name: name.to_string(),
+            versions: versions.to_vec(),
+        })
+        .collect();
+    Json(SubsonicResponse::ok(
+        serde_json::json!({ "openSubsonicExtensions" : extensions }),
+    ))
+}
 /// Get artists index
-/// params required by Subsonic API spec but not currently validated
 async fn get_artists(
-    Query(_params): Query<SubsonicQuery>,
+    Query(params): Query<SubsonicQuery>,
     State(state): State<SubsonicState>,
 ) -> impl IntoResponse {
-    match load_artists(&state.library_manager).await {
+    let scope = match request_scope(&state, params.credentials()).await {
+        Ok(scope) => scope,
+        Err(response) => return response,
+    };
+    match load_artists(&state.library_manager, &scope).await {
         Ok(artists_response) => {
-            let response = SubsonicResponse {
-                subsonic_response: SubsonicResponseInner {
-                    status: "ok".to_string(),
-                    version: "1.16.1".to_string(),
-                    data: serde_json::json!(artists_response),
-                },
-            };
+            let response = SubsonicResponse::ok(serde_json::json!(artists_response));
             Json(response).into_response()
         }
         Err(e) => {
@@ -190,32 +288,23 @@ async fn get_artists(
                 code: 0,
                 message: format!("Failed to load artists: {}", e),
             };
-            let response = SubsonicResponse {
-                subsonic_response: SubsonicResponseInner {
-                    status: "failed".to_string(),
-                    version: "1.16.1".to_string(),
-                    data: serde_json::json!({ "error" : error }),
-                },
-            };
+            let response = SubsonicResponse::failed(serde_json::json!({ "error" : error }));
             (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response()
         }
     }
 }
 /// Get album list
-/// params required by Subsonic API spec but not currently validated
 async fn get_album_list(
-    Query(_params): Query<SubsonicQuery>,
+    Query(params): Query<SubsonicQuery>,
     State(state): State<SubsonicState>,
 ) -> impl IntoResponse {
-    match load_albums(&state.library_manager).await {
+    let scope = match request_scope(&state, params.credentials()).await {
+        Ok(scope) => scope,
+        Err(response) => return response,
+    };
+    match load_albums(&state.library_manager, &scope).await {
         Ok(album_response) => {
-            let response = SubsonicResponse {
-                subsonic_response: SubsonicResponseInner {
-                    status: "ok".to_string(),
-                    version: "1.16.1".to_string(),
-                    data: serde_json::json!(album_response),
-                },
-            };
+            let response = SubsonicResponse::ok(serde_json::json!(album_response));
             Json(response).into_response()
         }
         Err(e) => {
@@ -223,13 +312,7 @@ async fn get_album_list(
                 code: 0,
                 message: format!("Failed to load albums: {}", e),
             };
-            let response = SubsonicResponse {
-                subsonic_response: SubsonicResponseInner {
-                    status: "failed".to_string(),
-                    version: "1.16.1".to_string(),
-                    data: serde_json::json!({ "error" : error }),
-                },
-            };
+            let response = SubsonicResponse::failed(serde_json::json!({ "error" : error }));
             (StatusCode::INTERNAL_SERVER_ERROR, Json(response)).into_response()
         }
     }
@@ -246,25 +329,22 @@ async fn get_album(
                 code: 10,
                 message: "Required parameter 'id' missing".to_string(),
             };
-            let response = SubsonicResponse {
-                subsonic_response: SubsonicResponseInner {
-                    status: "failed".to_string(),
-                    version: "1.16.1".to_string(),
-                    data: serde_json::json!({ "error" : error }),
-                },
-            };
+            let response = SubsonicResponse::failed(serde_json::json!({ "error" : error }));
             return (StatusCode::BAD_REQUEST, Json(response)).into_response();
         }
     };
-    match load_album_with_songs(&state.library_manager, &album_id).await {
+    let scope = match request_scope(&state, query_credentials(&params)).await {
+        Ok(scope) => scope,
+        Err(response) => return response,
+    };
+    let album = if scope.allows(&album_id) {
+        load_album_with_songs(&state.library_manager, &album_id).await
+    } else {
+        Err(LibraryError::Import("Album not found".to_string()))
+    };
+    match album {
         Ok(album_response) => {
-            let response = SubsonicResponse {
-                subsonic_response: SubsonicResponseInner {
-                    status: "ok".to_string(),
-                    version: "1.16.1".to_string(),
-                    data: album_response,
-                },
-            };
+            let response = SubsonicResponse::ok(album_response);
             Json(response).into_response()
         }
         Err(e) => {
@@ -272,13 +352,7 @@ async fn get_album(
                 code: 70,
                 message: format!("Album not found: {}", e),
             };
-            let response = SubsonicResponse {
-                subsonic_response: SubsonicResponseInner {
-                    status: "failed".to_string(),
-                    version: "1.16.1".to_string(),
-                    data: serde_json::json!({ "error" : error }),
-                },
-            };
+            let response = SubsonicResponse::failed(serde_json::json!({ "error" : error }));
             (StatusCode::NOT_FOUND, Json(response)).into_response()
         }
     }
@@ -294,11 +368,54 @@ async fn stream_song(
             return (StatusCode::BAD_REQUEST, "Missing song ID").into_response();
         }
     };
-    info!("Streaming request for song ID: {}", song_id);
-    match stream_track_audio(&state, &song_id).await {
+    let scope = match request_scope(&state, query_credentials(&params)).await {
+        Ok(scope) => scope,
+        Err(response) => return response,
+    };
+    if !track_in_scope(&state.library_manager, &scope, &song_id).await {
+        return (StatusCode::NOT_FOUND, "Song not found").into_response();
+    }
+    // transcodeOffset: clients resuming mid-song ask for it to start later
+    let offset_ms = params
+        .get("timeOffset")
+        .and_then(|s| s.parse::<u64>().ok())
+        .filter(|&seconds| seconds > 0)
+        .map(|seconds| seconds * 1000);
+    let format = StreamFormat::negotiate(
+        params.get("format").map(String::as_str),
+        params.get("maxBitRate").and_then(|s| s.parse().ok()),
+        state.transcoder.default_bitrate_kbps(),
+    );
+    info!("Streaming request for song ID: {} as {:?}", song_id, format);
+    let audio = match format {
+        StreamFormat::Original => match stream_track_audio(&state, &song_id).await {
+            Ok(audio_data) => match offset_ms {
+                Some(offset_ms) => audio_from_offset(audio_data, offset_ms).await,
+                None => Ok(audio_data),
+            },
+            Err(e) => Err(e),
+        },
+        StreamFormat::Opus { bitrate_kbps } => {
+            let key = TranscodeKey {
+                track_id: song_id.clone(),
+                bitrate_kbps,
+                offset_ms: offset_ms.unwrap_or(0),
+            };
+            state
+                .transcoder
+                .opus(key, stream_track_audio(&state, &song_id))
+                .await
+                .map(|encoded| encoded.to_vec())
+        }
+    };
+    let content_type = match format {
+        StreamFormat::Original => "audio/flac",
+        StreamFormat::Opus { .. } => "audio/ogg",
+    };
+    match audio {
         Ok(audio_data) => {
             let headers = [
-                ("Content-Type", "audio/flac"),
+                ("Content-Type", content_type),
                 ("Content-Length", &audio_data.len().to_string()),
                 ("Accept-Ranges", "bytes"),
             ];
@@ -314,13 +431,205 @@ async fn stream_song(
         }
     }
 }
+/// Get an album's cover, scaled down to fit `size` pixels when given
+async fn get_cover_art(
+    Query(params): Query<HashMap<String, String>>,
+    State(state): State<SubsonicState>,
+) -> impl IntoResponse {
+    let Some(album_id) = params.get("id").cloned() else {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            10,
+            "Required parameter 'id' missing".to_string(),
+        );
+    };
+    let scope = match request_scope(&state, query_credentials(&params)).await {
+        Ok(scope) => scope,
+        Err(response) => return response,
+    };
+    if !scope.allows(&album_id) {
+        return error_response(StatusCode::NOT_FOUND, 70, "Cover art not found".to_string());
+    }
+    let size = params
+        .get("size")
+        .and_then(|s| s.parse::<u32>().ok())
+        .filter(|&size| size > 0);
+    match load_cover_art(&state.library_manager, &album_id, size).await {
+        Ok(Some((data, content_type))) => {
+            ([(header::CONTENT_TYPE, content_type)], data).into_response()
+        }
+        Ok(None) => error_response(StatusCode::NOT_FOUND, 70, "Cover art not found".to_string()),
+        Err(e) => {
+            error!("Cover art error for album {}: {}", album_id, e);
+            error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                0,
+                format!("Failed to load cover art: {}", e),
+            )
+        }
+    }
+}
+/// Get a song's lyrics from its tags (OpenSubsonic `songLyrics`)
+async fn get_lyrics_by_song_id(
+    Query(params): Query<HashMap<String, String>>,
+    State(state): State<SubsonicState>,
+) -> impl IntoResponse {
+    let Some(song_id) = params.get("id").cloned() else {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            10,
+            "Required parameter 'id' missing".to_string(),
+        );
+    };
+    let scope = match request_scope(&state, query_credentials(&params)).await {
+        Ok(scope) => scope,
+        Err(response) => return response,
+    };
+    if !track_in_scope(&state.library_manager, &scope, &song_id).await {
+        return error_response(StatusCode::NOT_FOUND, 70, "Song not found".to_string());
+    }
+    let structured: Vec<StructuredLyrics> = match load_lyrics(&state, &song_id).await {
+        Ok(lyrics) => lyrics
+            .into_iter()
+            .map(|(title, lyrics)| StructuredLyrics {
+                display_title: title,
+                // Tags don't say which language the lyrics are in
+                lang: "und".to_string(),
+                synced: lyrics.synced,
+                line: lyrics
+                    .lines
+                    .into_iter()
+                    .map(|line| LyricLine {
+                        start: line.start_ms,
+                        value: line.text,
+                    })
+                    .collect(),
+            })
+            .collect(),
+        Err(e) => {
+            error!("Lyrics error for song {}: {}", song_id, e);
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                0,
+                format!("Failed to load lyrics: {}", e),
+            );
+        }
+    };
+    let response = SubsonicResponse::ok(
+        serde_json::json!({ "lyricsList" : { "structuredLyrics" : structured } }),
+    );
+    Json(response).into_response()
+}
+/// What a request may see, or the error response when a client share's
+/// token is needed and missing or wrong
+async fn request_scope(
+    state: &SubsonicState,
+    credentials: ShareCredentials,
+) -> Result<ShareScope, Response> {
+    match resolve_share_scope(state.library_manager.get(), &credentials).await {
+        Ok(Some(scope)) => Ok(scope),
+        Ok(None) => Err(error_response(
+            StatusCode::UNAUTHORIZED,
+            40,
+            "Wrong username or password".to_string(),
+        )),
+        Err(e) => Err(error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            0,
+            format!("Failed to check access: {}", e),
+        )),
+    }
+}
+
+/// How the request signed in: token authentication when both `t` and `s`
+/// are sent, otherwise the password
+fn credentials(p: Option<&str>, t: Option<&str>, s: Option<&str>) -> ShareCredentials {
+    match (t, s, p) {
+        (Some(token), Some(salt), _) => ShareCredentials::SaltedToken {
+            token: token.to_string(),
+            salt: salt.to_string(),
+        },
+        (_, _, Some(password)) => {
+            decode_password(password).map_or(ShareCredentials::None, ShareCredentials::Password)
+        }
+        _ => ShareCredentials::None,
+    }
+}
+
+fn query_credentials(params: &HashMap<String, String>) -> ShareCredentials {
+    let param = |name: &str| params.get(name).map(String::as_str);
+    credentials(param("p"), param("t"), param("s"))
+}
+
+/// A password as sent, plain or as `enc:` followed by its hex
+fn decode_password(password: &str) -> Option<String> {
+    match password.strip_prefix("enc:") {
+        Some(encoded) => String::from_utf8(hex::decode(encoded).ok()?).ok(),
+        None => Some(password.to_string()),
+    }
+}
+
+fn error_response(status: StatusCode, code: u32, message: String) -> Response {
+    let response =
+        SubsonicResponse::failed(serde_json::json!({ "error" : SubsonicError { code, message } }));
+    (status, Json(response)).into_response()
+}
+
+/// Whether a track belongs to an album the request may see
+async fn track_in_scope(
+    library_manager: &SharedLibraryManager,
+    scope: &ShareScope,
+    track_id: &str,
+) -> bool {
+    if *scope == ShareScope::Library {
+        return true;
+    }
+    let Ok(Some(track)) = library_manager.get().get_track(track_id).await else {
+        return false;
+    };
+    library_manager
+        .get()
+        .get_album_id_for_release(&track.release_id)
+        .await
+        .is_ok_and(|album_id| scope.allows(&album_id))
+}
+
+/// Subsonic artist ID, derived from the name
+fn artist_id(name: &str) -> String {
+    format!("artist_{}", name.replace(' ', "_"))
+}
+
+fn artist_refs(artists: &[DbArtist]) -> Vec<ArtistRef> {
+    artists
+        .iter()
+        .map(|a| ArtistRef {
+            id: artist_id(&a.name),
+            name: a.name.clone(),
+        })
+        .collect()
+}
+
+/// Credited artists joined into one name
+fn display_name(artists: &[DbArtist]) -> String {
+    if artists.is_empty() {
+        "Unknown Artist".to_string()
+    } else {
+        artists
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 /// Load artists from database and group by first letter
 async fn load_artists(
     library_manager: &SharedLibraryManager,
+    scope: &ShareScope,
 ) -> Result<ArtistsResponse, LibraryError> {
     let albums = library_manager.get().get_albums().await?;
     let mut artist_map: HashMap<String, HashMap<String, u32>> = HashMap::new();
-    for album in &albums {
+    for album in albums.iter().filter(|a| scope.allows(&a.id)) {
         let artists = library_manager
             .get()
             .get_artists_for_album(&album.id)
@@ -342,7 +651,7 @@ async fn load_artists(
         let artist_list: Vec<Artist> = artists
             .into_iter()
             .map(|(name, count)| Artist {
-                id: format!("artist_{}", name.replace(' ', "_")),
+                id: artist_id(&name),
                 name,
                 album_count: count,
             })
@@ -362,34 +671,32 @@ async fn load_artists(
 /// Load albums from database
 async fn load_albums(
     library_manager: &SharedLibraryManager,
+    scope: &ShareScope,
 ) -> Result<AlbumListResponse, LibraryError> {
     let db_albums = library_manager.get().get_albums().await?;
     let mut albums = Vec::new();
-    for db_album in db_albums {
+    for db_album in db_albums.into_iter().filter(|a| scope.allows(&a.id)) {
         let tracks = library_manager.get().get_tracks(&db_album.id).await?;
         let artists = library_manager
             .get()
             .get_artists_for_album(&db_album.id)
             .await?;
-        let artist_name = if artists.is_empty() {
-            "Unknown Artist".to_string()
-        } else {
-            artists
-                .iter()
-                .map(|a| a.name.as_str())
-                .collect::<Vec<_>>()
-                .join(", ")
-        };
+        let artist_name = display_name(&artists);
         albums.push(Album {
             id: db_album.id.clone(),
             name: db_album.title,
             artist: artist_name.clone(),
-            artist_id: format!("artist_{}", artist_name.replace(' ', "_")),
+            artist_id: artist_id(&artist_name),
             song_count: tracks.len() as u32,
             duration: 0,
             year: db_album.year,
             genre: None,
-            cover_art: None,
+            cover_art: db_album
+                .cover_image_id
+                .as_ref()
+                .map(|_| db_album.id.clone()),
+            artists: artist_refs(&artists),
+            display_artist: artist_name,
         });
     }
     Ok(AlbumListResponse {
@@ -411,29 +718,23 @@ async fn load_album_with_songs(
         .get()
         .get_artists_for_album(&db_album.id)
         .await?;
-    let album_artist_name = if album_artists.is_empty() {
-        "Unknown Artist".to_string()
-    } else {
-        album_artists
-            .iter()
-            .map(|a| a.name.as_str())
-            .collect::<Vec<_>>()
-            .join(", ")
-    };
+    let album_artist_name = display_name(&album_artists);
+    let album_artist_refs = artist_refs(&album_artists);
+    // Covers are served per album, so songs point at their album's
+    let cover_art = db_album
+        .cover_image_id
+        .as_ref()
+        .map(|_| db_album.id.clone());
     let mut songs = Vec::new();
     for track in tracks {
         let track_artists = library_manager
             .get()
             .get_artists_for_track(&track.id)
             .await?;
-        let track_artist_name = if track_artists.is_empty() {
-            album_artist_name.clone()
+        let (track_artist_name, track_artist_refs) = if track_artists.is_empty() {
+            (album_artist_name.clone(), album_artist_refs.clone())
         } else {
-            track_artists
-                .iter()
-                .map(|a| a.name.as_str())
-                .collect::<Vec<_>>()
-                .join(", ")
+            (display_name(&track_artists), artist_refs(&track_artists))
         };
         songs.push(Song {
             id: track.id,
@@ -441,37 +742,113 @@ async fn load_album_with_songs(
             album: db_album.title.clone(),
             artist: track_artist_name.clone(),
             album_id: db_album.id.clone(),
-            artist_id: format!("artist_{}", track_artist_name.replace(' ', "_")),
+            artist_id: artist_id(&track_artist_name),
             track: track.track_number,
             year: db_album.year,
             genre: None,
-            cover_art: None,
+            cover_art: cover_art.clone(),
             size: None,
             content_type: "audio/flac".to_string(),
             suffix: "flac".to_string(),
             duration: track.duration_ms.map(|ms| (ms / 1000) as i32),
             bit_rate: None,
             path: format!("{}/{}", album_artist_name, db_album.title),
+            artists: track_artist_refs,
+            display_artist: track_artist_name,
+            album_artists: album_artist_refs.clone(),
+            display_album_artist: album_artist_name.clone(),
         });
     }
     let album = Album {
         id: db_album.id.clone(),
         name: db_album.title,
         artist: album_artist_name.clone(),
-        artist_id: format!("artist_{}", album_artist_name.replace(' ', "_")),
+        artist_id: artist_id(&album_artist_name),
         song_count: songs.len() as u32,
         duration: songs.iter().map(|s| s.duration.unwrap_or(0) as u32).sum(),
         year: db_album.year,
         genre: None,
-        cover_art: None,
+        cover_art,
+        artists: album_artist_refs,
+        display_artist: album_artist_name,
     };
     Ok(serde_json::json!(
         { "album" : { "id" : album.id, "name" : album.name, "artist" : album.artist,
         "artistId" : album.artist_id, "songCount" : album.song_count, "duration" :
-        album.duration, "year" : album.year, "coverArt" : album.cover_art, "song" :
-        songs } }
+        album.duration, "year" : album.year, "coverArt" : album.cover_art, "artists" :
+        album.artists, "displayArtist" : album.display_artist, "song" : songs } }
     ))
 }
+/// Album cover bytes and content type. Scaled covers come back as JPEG.
+async fn load_cover_art(
+    library_manager: &SharedLibraryManager,
+    album_id: &str,
+    size: Option<u32>,
+) -> Result<Option<(Vec<u8>, &'static str)>, Box<dyn std::error::Error + Send + Sync>> {
+    let Some(album) = library_manager.get().get_album_by_id(album_id).await? else {
+        return Ok(None);
+    };
+    let Some(image_id) = album.cover_image_id else {
+        return Ok(None);
+    };
+    let data = library_manager.get().fetch_image_bytes(&image_id).await?;
+    let Some(size) = size else {
+        let content_type = image::guess_format(&data)
+            .map(|format| format.to_mime_type())
+            .unwrap_or("application/octet-stream");
+        return Ok(Some((data, content_type)));
+    };
+    let scaled = tokio::task::spawn_blocking(move || scale_cover(&data, size)).await??;
+    Ok(Some((scaled, "image/jpeg")))
+}
+/// Fit a cover within `size`x`size` as JPEG, without enlarging small ones
+fn scale_cover(data: &[u8], size: u32) -> Result<Vec<u8>, image::ImageError> {
+    let mut cover = image::load_from_memory(data)?;
+    if cover.width() > size || cover.height() > size {
+        cover = cover.thumbnail(size, size);
+    }
+    let mut out = Cursor::new(Vec::new());
+    cover
+        .to_rgb8()
+        .write_to(&mut out, image::ImageFormat::Jpeg)?;
+    Ok(out.into_inner())
+}
+/// A track's lyrics and title, None when its tags have no lyrics
+async fn load_lyrics(
+    state: &SubsonicState,
+    track_id: &str,
+) -> Result<Option<(String, Lyrics)>, Box<dyn std::error::Error + Send + Sync>> {
+    let library_manager = state.library_manager.get();
+    // A CUE/FLAC track's headers carry the whole disc's tags, not its own
+    let audio_format = library_manager
+        .get_audio_format_by_track_id(track_id)
+        .await?;
+    if audio_format.map_or(true, |format| format.needs_headers) {
+        return Ok(None);
+    }
+    let Some(track) = library_manager.get_track(track_id).await? else {
+        return Ok(None);
+    };
+    let audio_data = stream_track_audio(state, track_id).await?;
+    Ok(lyrics::flac_lyrics(&audio_data).map(|lyrics| (track.title, lyrics)))
+}
+/// Re-encode audio to start `offset_ms` in
+async fn audio_from_offset(
+    audio_data: Vec<u8>,
+    offset_ms: u64,
+) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    let encoded = tokio::task::spawn_blocking(move || {
+        let decoded = audio_codec::decode_audio(&audio_data, Some(offset_ms), None)?;
+        audio_codec::encode_to_flac(
+            &decoded.samples,
+            decoded.sample_rate,
+            decoded.channels,
+            decoded.bits_per_sample,
+        )
+    })
+    .await??;
+    Ok(encoded)
+}
 /// Stream track audio - read file and decrypt if needed
 async fn stream_track_audio(
     state: &SubsonicState,
@@ -519,7 +896,11 @@ async fn stream_track_audio(
     // Read file data
     let file_data = if let Some(ref source_path) = audio_file.source_path {
         debug!("Reading from local file: {}", source_path);
-        tokio::fs::read(source_path)
+        let path = match &storage_profile {
+            Some(profile) => resolve_local_path(&profile.local_roots(), source_path),
+            None => source_path.into(),
+        };
+        tokio::fs::read(path)
             .await
             .map_err(|e| format!("Failed to read file: {}", e))?
     } else if let Some(ref profile) = storage_profile {
@@ -568,3 +949,64 @@ async fn stream_track_audio(
     );
     Ok(audio_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library::shares::salted_token;
+
+    #[test]
+    fn test_decode_password() {
+        assert_eq!(decode_password("sesame").as_deref(), Some("sesame"));
+        assert_eq!(
+            decode_password("enc:736573616d65").as_deref(),
+            Some("sesame")
+        );
+        assert_eq!(decode_password("enc:zz"), None);
+    }
+
+    #[test]
+    fn test_credentials_prefer_token_authentication() {
+        assert_eq!(
+            credentials(
+                Some("sesame"),
+                Some("26719a1196d2a940705a59634eb18eab"),
+                Some("c19b2d")
+            ),
+            ShareCredentials::SaltedToken {
+                token: "26719a1196d2a940705a59634eb18eab".to_string(),
+                salt: "c19b2d".to_string(),
+            }
+        );
+        assert_eq!(
+            credentials(Some("enc:736573616d65"), None, Some("c19b2d")),
+            ShareCredentials::Password("sesame".to_string())
+        );
+        assert_eq!(
+            credentials(None, Some("26719a11"), None),
+            ShareCredentials::None
+        );
+        assert_eq!(
+            credentials(Some("enc:zz"), None, None),
+            ShareCredentials::None
+        );
+    }
+
+    #[test]
+    fn test_salted_token_matches_the_subsonic_example() {
+        // From the Subsonic API docs: password "sesame", salt "c19b2d"
+        assert_eq!(
+            salted_token("sesame", "c19b2d"),
+            "26719a1196d2a940705a59634eb18eab"
+        );
+    }
+
+    #[test]
+    fn test_response_advertises_open_subsonic() {
+        let response = serde_json::to_value(SubsonicResponse::ok(serde_json::json!({}))).unwrap();
+        let inner = &response["subsonic-response"];
+        assert_eq!(inner["status"], "ok");
+        assert_eq!(inner["type"], "bae");
+        assert_eq!(inner["openSubsonic"], true);
+    }
+}
@@ -0,0 +1,451 @@
+use crate::db::{
+    Database, DbAlbum, DbAlbumArtist, DbArtist, DbFile, DbRelease, DbReleaseStorage,
+    DbStorageProfile, DbTrack, ImportStatus,
+};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde_json::json;
+use std::path::Path;
+
+const ADJECTIVES: &[&str] = &[
+    "Velvet", "Neon", "Quiet", "Electric", "Hollow", "Golden", "Paper", "Glass", "Midnight",
+    "Silver", "Wild", "Distant", "Broken", "Lunar", "Crimson", "Static", "Northern", "Slow",
+];
+const NOUNS: &[&str] = &[
+    "Harbor",
+    "Signal",
+    "Garden",
+    "Engine",
+    "Tides",
+    "Orchard",
+    "Circuit",
+    "Canyon",
+    "Motel",
+    "Satellites",
+    "Choir",
+    "Highway",
+    "Parade",
+    "Atlas",
+    "Reverie",
+    "Lanterns",
+];
+const TRACK_WORDS: &[&str] = &[
+    "Morning",
+    "Drift",
+    "Static",
+    "Echo",
+    "Runaway",
+    "Horizon",
+    "Undertow",
+    "Fever",
+    "Ghosts",
+    "Afterglow",
+    "Carousel",
+    "Voltage",
+    "Weather",
+    "Letters",
+    "Pulse",
+    "Embers",
+];
+const FORMATS: &[&str] = &["Digital", "CD", "Vinyl"];
+const GENRES: &[&str] = &[
+    "Electronic",
+    "Indie Rock",
+    "Jazz",
+    "Ambient",
+    "Folk",
+    "Hip Hop",
+];
+
+/// Shape of a library to generate
+#[derive(Debug, Clone)]
+pub struct LibrarySpec {
+    pub albums: usize,
+    pub seed: u64,
+    /// Number of storage profiles releases are spread across, alternating
+    /// local and cloud, encrypted and plain
+    pub storage_profiles: usize,
+    /// Fraction of albums (0.0-1.0) that get a [`Corruption`]
+    pub corrupt_fraction: f64,
+}
+
+impl LibrarySpec {
+    pub fn new(albums: usize) -> Self {
+        LibrarySpec {
+            albums,
+            seed: 0,
+            storage_profiles: 4,
+            corrupt_fraction: 0.05,
+        }
+    }
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+    pub fn with_storage_profiles(mut self, storage_profiles: usize) -> Self {
+        self.storage_profiles = storage_profiles.max(1);
+        self
+    }
+    pub fn with_corrupt_fraction(mut self, corrupt_fraction: f64) -> Self {
+        self.corrupt_fraction = corrupt_fraction.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// Broken states a real library can end up in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corruption {
+    /// Import failed partway: release Failed, tracks still Queued, no files
+    FailedImport,
+    /// Release and tracks claim Complete but no file records exist
+    MissingFiles,
+    /// File records exist but their recorded size is zero
+    TruncatedFiles,
+}
+
+/// One generated album with its single release
+#[derive(Debug, Clone)]
+pub struct GeneratedAlbum {
+    pub artist: DbArtist,
+    pub album: DbAlbum,
+    pub release: DbRelease,
+    pub tracks: Vec<DbTrack>,
+    pub files: Vec<DbFile>,
+    pub storage_profile_id: String,
+    pub genre: &'static str,
+    pub corruption: Option<Corruption>,
+}
+
+/// Deterministic library fixture: the same spec always produces the same
+/// IDs, names, and timestamps.
+///
+/// Only database rows are generated; file contents are never written, so
+/// healthy entries look complete to the DB but are not playable.
+#[derive(Debug, Clone)]
+pub struct GeneratedLibrary {
+    pub storage_profiles: Vec<DbStorageProfile>,
+    pub albums: Vec<GeneratedAlbum>,
+}
+
+impl GeneratedLibrary {
+    pub fn generate(spec: &LibrarySpec) -> Self {
+        let mut rng = SplitMix64(spec.seed);
+        let epoch = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+        let storage_profiles: Vec<DbStorageProfile> = (0..spec.storage_profiles.max(1))
+            .map(|i| {
+                let encrypted = i % 4 >= 2;
+                let mut profile = if i % 2 == 0 {
+                    DbStorageProfile::new_local(
+                        &format!("Generated Local {}", i + 1),
+                        &format!("/generated/library-{}", i + 1),
+                        encrypted,
+                    )
+                } else {
+                    DbStorageProfile::new_cloud(
+                        &format!("Generated Cloud {}", i + 1),
+                        &format!("generated-{}", i + 1),
+                        "us-east-1",
+                        None,
+                        "generated",
+                        "generated",
+                        encrypted,
+                    )
+                };
+                profile.id = rng.uuid();
+                profile.is_default = i == 0;
+                profile.created_at = epoch;
+                profile.updated_at = epoch;
+                profile
+            })
+            .collect();
+
+        let corrupt_every = (spec.corrupt_fraction > 0.0)
+            .then(|| (1.0 / spec.corrupt_fraction).round().max(1.0) as usize);
+
+        let albums = (0..spec.albums)
+            .map(|index| {
+                let corruption = corrupt_every
+                    .filter(|every| index % every == every - 1)
+                    .map(|_| match rng.below(3) {
+                        0 => Corruption::FailedImport,
+                        1 => Corruption::MissingFiles,
+                        _ => Corruption::TruncatedFiles,
+                    });
+                let profile = &storage_profiles[index % storage_profiles.len()];
+                generate_album(&mut rng, epoch, index, profile, corruption)
+            })
+            .collect();
+
+        GeneratedLibrary {
+            storage_profiles,
+            albums,
+        }
+    }
+
+    pub fn corrupt_albums(&self) -> impl Iterator<Item = &GeneratedAlbum> {
+        self.albums.iter().filter(|a| a.corruption.is_some())
+    }
+
+    /// Insert every profile, artist, album, release, track, and file
+    pub async fn insert_into(&self, database: &Database) -> Result<(), sqlx::Error> {
+        for profile in &self.storage_profiles {
+            database.insert_storage_profile(profile).await?;
+        }
+        for generated in &self.albums {
+            database.insert_artist(&generated.artist).await?;
+            database
+                .insert_album_with_release_and_tracks(
+                    &generated.album,
+                    &generated.release,
+                    &generated.tracks,
+                )
+                .await?;
+            let mut album_artist = DbAlbumArtist::new(&generated.album.id, &generated.artist.id, 0);
+            album_artist.id = format!("{}-artist", generated.album.id);
+            database.insert_album_artist(&album_artist).await?;
+            let mut release_storage =
+                DbReleaseStorage::new(&generated.release.id, &generated.storage_profile_id);
+            release_storage.id = format!("{}-storage", generated.release.id);
+            release_storage.created_at = generated.release.created_at;
+            database.insert_release_storage(&release_storage).await?;
+            for file in &generated.files {
+                database.insert_file(file).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Serialize in the `bae-mocks/fixtures` album format
+    pub fn to_mock_fixture_json(&self) -> serde_json::Value {
+        let albums: Vec<serde_json::Value> = self
+            .albums
+            .iter()
+            .map(|generated| {
+                json!({
+                    "artist": generated.artist.name,
+                    "title": generated.album.title,
+                    "year": generated.album.year,
+                    "genre": generated.genre,
+                    "tracks": generated.tracks.iter().map(|t| &t.title).collect::<Vec<_>>(),
+                    "unavailable": generated.corruption.is_some(),
+                })
+            })
+            .collect();
+        json!({ "albums": albums })
+    }
+}
+
+fn generate_album(
+    rng: &mut SplitMix64,
+    epoch: DateTime<Utc>,
+    index: usize,
+    profile: &DbStorageProfile,
+    corruption: Option<Corruption>,
+) -> GeneratedAlbum {
+    let created_at = epoch + Duration::minutes(index as i64 * 37);
+    let artist_name = format!("The {} {}", rng.pick(ADJECTIVES), rng.pick(NOUNS));
+    // Index suffix keeps titles unique so search benchmarks have distinct targets
+    let title = format!("{} {} {}", rng.pick(ADJECTIVES), rng.pick(NOUNS), index + 1);
+    let year = 1965 + rng.below(60) as i32;
+
+    let artist = DbArtist {
+        id: rng.uuid(),
+        name: artist_name,
+        sort_name: None,
+        discogs_artist_id: None,
+        bandcamp_artist_id: None,
+        created_at,
+        updated_at: created_at,
+    };
+    let album = DbAlbum {
+        id: rng.uuid(),
+        title,
+        year: Some(year),
+        discogs_release: None,
+        musicbrainz_release: None,
+        bandcamp_album_id: None,
+        cover_image_id: None,
+        cover_art_url: None,
+        is_compilation: rng.below(20) == 0,
+        created_at,
+        updated_at: created_at,
+        play_count: 0,
+        last_played_at: None,
+        dominant_color: None,
+        accent_color: None,
+        preferred_release_id: None,
+    };
+    let release_status = match corruption {
+        Some(Corruption::FailedImport) => ImportStatus::Failed,
+        _ => ImportStatus::Complete,
+    };
+    let release = DbRelease {
+        id: rng.uuid(),
+        album_id: album.id.clone(),
+        release_name: None,
+        year: Some(year + rng.below(3) as i32),
+        discogs_release_id: None,
+        bandcamp_release_id: None,
+        format: Some(rng.pick(FORMATS).to_string()),
+        label: None,
+        catalog_number: None,
+        country: None,
+        barcode: None,
+        import_status: release_status,
+        created_at,
+        updated_at: created_at,
+    };
+
+    let track_status = match corruption {
+        Some(Corruption::FailedImport) => ImportStatus::Queued,
+        _ => ImportStatus::Complete,
+    };
+    let track_count = 6 + rng.below(9) as usize;
+    let tracks: Vec<DbTrack> = (0..track_count)
+        .map(|i| DbTrack {
+            id: rng.uuid(),
+            release_id: release.id.clone(),
+            title: format!("{} {}", rng.pick(TRACK_WORDS), rng.pick(TRACK_WORDS)),
+            disc_number: Some(1),
+            track_number: Some(i as i32 + 1),
+            duration_ms: Some(120_000 + rng.below(300_000) as i64),
+            discogs_position: None,
+            import_status: track_status,
+            created_at,
+        })
+        .collect();
+
+    let files = match corruption {
+        Some(Corruption::FailedImport | Corruption::MissingFiles) => Vec::new(),
+        _ => tracks
+            .iter()
+            .map(|track| {
+                let filename = format!(
+                    "{:02} {}.flac",
+                    track.track_number.unwrap_or_default(),
+                    track.title
+                );
+                let file_size = match corruption {
+                    Some(Corruption::TruncatedFiles) => 0,
+                    _ => track.duration_ms.unwrap_or_default() * 100,
+                };
+                let source_path = if profile.cloud_bucket.is_some() {
+                    format!(
+                        "s3://{}/{}/{}",
+                        profile.cloud_bucket.as_deref().unwrap_or_default(),
+                        release.id,
+                        filename
+                    )
+                } else {
+                    Path::new(&profile.location_path)
+                        .join(&release.id)
+                        .join(&filename)
+                        .display()
+                        .to_string()
+                };
+                let mut file = DbFile::new(&release.id, &filename, file_size, "flac")
+                    .with_source_path(&source_path);
+                file.id = rng.uuid();
+                file.created_at = created_at;
+                file
+            })
+            .collect(),
+    };
+
+    GeneratedAlbum {
+        artist,
+        album,
+        release,
+        tracks,
+        files,
+        storage_profile_id: profile.id.clone(),
+        genre: rng.pick(GENRES),
+        corruption,
+    }
+}
+
+/// Small seeded PRNG so fixtures don't depend on `rand` versions
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize]
+    }
+    fn uuid(&mut self) -> String {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.next().to_le_bytes());
+        bytes[8..].copy_from_slice(&self.next().to_le_bytes());
+        uuid::Builder::from_random_bytes(bytes)
+            .into_uuid()
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generation_is_deterministic() {
+        let spec = LibrarySpec::new(50).with_seed(7);
+        let a = GeneratedLibrary::generate(&spec);
+        let b = GeneratedLibrary::generate(&spec);
+        assert_eq!(a.to_mock_fixture_json(), b.to_mock_fixture_json());
+        assert_eq!(a.albums[10].release.id, b.albums[10].release.id);
+        assert_ne!(
+            a.albums[10].release.id,
+            GeneratedLibrary::generate(&spec.clone().with_seed(8)).albums[10]
+                .release
+                .id
+        );
+    }
+
+    #[test]
+    fn test_spec_controls_profiles_and_corruption() {
+        let spec = LibrarySpec::new(100)
+            .with_storage_profiles(3)
+            .with_corrupt_fraction(0.1);
+        let library = GeneratedLibrary::generate(&spec);
+        assert_eq!(library.albums.len(), 100);
+        assert_eq!(library.storage_profiles.len(), 3);
+        assert_eq!(library.corrupt_albums().count(), 10);
+        for generated in library.corrupt_albums() {
+            match generated.corruption.unwrap() {
+                Corruption::FailedImport | Corruption::MissingFiles => {
+                    assert!(generated.files.is_empty())
+                }
+                Corruption::TruncatedFiles => {
+                    assert!(generated.files.iter().all(|f| f.file_size == 0))
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_insert_into_database() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let database = Database::new(temp_dir.path().join("library.db").to_str().unwrap())
+            .await
+            .unwrap();
+        let library = GeneratedLibrary::generate(&LibrarySpec::new(20));
+        library.insert_into(&database).await.unwrap();
+
+        assert_eq!(database.get_albums().await.unwrap().len(), 20);
+        let generated = &library.albums[0];
+        let files = database
+            .get_files_for_release(&generated.release.id)
+            .await
+            .unwrap();
+        assert_eq!(files.len(), generated.files.len());
+    }
+}
@@ -1,4 +1,23 @@
-use crate::cloud_storage::{CloudStorage, CloudStorageError};
+//! Test harness for integration tests and bug reproductions (`test-utils` feature).
+//!
+//! - [`MockCloudStorage`]: in-memory `CloudStorage` for unit-level tests
+//! - [`SimulatedS3`]: in-process S3 server with latency and error injection
+//! - [`LoopbackPeer`]: seeds fixtures to libtorrent over loopback (`torrent` feature)
+//! - [`Scenario`]: scripted import → playback → verify flows against real services
+//! - [`GeneratedLibrary`]: deterministic libraries of any size for benchmarks and demos
+mod library_generator;
+mod s3;
+mod scenario;
+#[cfg(feature = "torrent")]
+mod torrent_peer;
+
+pub use library_generator::{Corruption, GeneratedAlbum, GeneratedLibrary, LibrarySpec};
+pub use s3::{S3Request, SimulatedS3, SIMULATED_S3_BUCKET};
+pub use scenario::Scenario;
+#[cfg(feature = "torrent")]
+pub use torrent_peer::LoopbackPeer;
+
+use crate::cloud_storage::{CloudStorage, CloudStorageError, VerifiedUpload};
 use std::collections::HashMap;
 use std::sync::Mutex;
 
@@ -23,17 +42,21 @@ impl MockCloudStorage {
     pub fn new() -> Self {
         Self::default()
     }
-}
 
-#[async_trait::async_trait]
-impl CloudStorage for MockCloudStorage {
-    async fn upload(&self, key: &str, data: &[u8]) -> Result<String, CloudStorageError> {
-        let location = format!(
+    fn location(key: &str) -> String {
+        format!(
             "s3://test-bucket/files/{}/{}/{}",
             &key[0..2],
             &key[2..4],
             key,
-        );
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl CloudStorage for MockCloudStorage {
+    async fn upload(&self, key: &str, data: &[u8]) -> Result<String, CloudStorageError> {
+        let location = Self::location(key);
         self.files
             .lock()
             .unwrap()
@@ -41,6 +64,29 @@ impl CloudStorage for MockCloudStorage {
         Ok(location)
     }
 
+    /// Stored bytes are exactly what was sent, so uploads always verify.
+    /// Like the real conditional writes, different data already at the key
+    /// is never replaced.
+    async fn upload_verified(
+        &self,
+        key: &str,
+        data: &[u8],
+    ) -> Result<VerifiedUpload, CloudStorageError> {
+        let location = Self::location(key);
+        if let Some(existing) = self.files.lock().unwrap().get(&location) {
+            if existing.as_slice() != data {
+                return Err(CloudStorageError::Occupied(format!(
+                    "{} already holds different data",
+                    location
+                )));
+            }
+        }
+        Ok(VerifiedUpload {
+            storage_location: self.upload(key, data).await?,
+            checksum_verified: true,
+        })
+    }
+
     async fn download(&self, storage_location: &str) -> Result<Vec<u8>, CloudStorageError> {
         self.files
             .lock()
@@ -0,0 +1,403 @@
+//! In-process S3-compatible server for end-to-end tests.
+//!
+//! Speaks enough of the S3 REST API (path-style buckets, object PUT/GET/HEAD/DELETE,
+//! ranged GETs) for the real `S3CloudStorage` client to import and stream against it.
+//! Latency and failures can be injected to reproduce slow or flaky object stores.
+use crate::db::DbStorageProfile;
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::any;
+use axum::Router;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::debug;
+
+pub const SIMULATED_S3_BUCKET: &str = "bae-test";
+const SIMULATED_S3_REGION: &str = "us-east-1";
+
+/// A request received by the simulated server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct S3Request {
+    pub method: String,
+    pub bucket: String,
+    /// Object key, empty for bucket-level requests
+    pub key: String,
+    /// Raw `Range` header, if any
+    pub range: Option<String>,
+}
+
+#[derive(Default)]
+struct S3Inner {
+    buckets: HashSet<String>,
+    objects: BTreeMap<(String, String), Vec<u8>>,
+    latency: Duration,
+    /// Remaining requests to reject with 503 SlowDown
+    fail_next: usize,
+    /// Keys containing any of these fail with 500 InternalError
+    failing_keys: Vec<String>,
+    requests: Vec<S3Request>,
+}
+
+type SharedState = Arc<Mutex<S3Inner>>;
+
+/// In-process S3 server bound to a loopback port.
+///
+/// The server stops when this is dropped.
+pub struct SimulatedS3 {
+    endpoint: String,
+    state: SharedState,
+    server: tokio::task::JoinHandle<()>,
+}
+
+impl SimulatedS3 {
+    /// Start a server on a random loopback port
+    pub async fn start() -> std::io::Result<Self> {
+        let state = SharedState::default();
+        let app = Router::new()
+            .route("/:bucket", any(bucket_request))
+            .route("/:bucket/", any(bucket_request))
+            .route("/:bucket/*key", any(object_request))
+            .with_state(state.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let endpoint = format!("http://{}", listener.local_addr()?);
+        let server = tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+
+        debug!("Simulated S3 listening on {}", endpoint);
+
+        Ok(Self {
+            endpoint,
+            state,
+            server,
+        })
+    }
+
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Cloud storage profile pointing at this server
+    pub fn storage_profile(&self, name: &str, encrypted: bool) -> DbStorageProfile {
+        DbStorageProfile::new_cloud(
+            name,
+            SIMULATED_S3_BUCKET,
+            SIMULATED_S3_REGION,
+            Some(&self.endpoint),
+            "test-access-key",
+            "test-secret-key",
+            encrypted,
+        )
+    }
+
+    /// Delay every response by `latency`
+    pub fn set_latency(&self, latency: Duration) {
+        self.state.lock().unwrap().latency = latency;
+    }
+
+    /// Reject the next `count` requests with 503 SlowDown, which clients retry
+    pub fn fail_next(&self, count: usize) {
+        self.state.lock().unwrap().fail_next = count;
+    }
+
+    /// Reject every request for keys containing `pattern` with 500 InternalError
+    pub fn fail_keys_containing(&self, pattern: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .failing_keys
+            .push(pattern.to_string());
+    }
+
+    /// Remove all injected latency and failures
+    pub fn clear_faults(&self) {
+        let mut inner = self.state.lock().unwrap();
+        inner.latency = Duration::ZERO;
+        inner.fail_next = 0;
+        inner.failing_keys.clear();
+    }
+
+    /// All requests received so far, in arrival order
+    pub fn requests(&self) -> Vec<S3Request> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Keys of all stored objects in `bucket`
+    pub fn object_keys(&self, bucket: &str) -> Vec<String> {
+        self.state
+            .lock()
+            .unwrap()
+            .objects
+            .keys()
+            .filter(|(b, _)| b == bucket)
+            .map(|(_, key)| key.clone())
+            .collect()
+    }
+
+    /// Stored bytes for an object
+    pub fn object(&self, bucket: &str, key: &str) -> Option<Vec<u8>> {
+        self.state
+            .lock()
+            .unwrap()
+            .objects
+            .get(&(bucket.to_string(), key.to_string()))
+            .cloned()
+    }
+}
+
+impl Drop for SimulatedS3 {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// Record the request and apply injected latency and failures.
+///
+/// Returns the error response to send if a fault fires.
+async fn admit(state: &SharedState, request: S3Request) -> Option<Response> {
+    let (latency, fault) = {
+        let mut inner = state.lock().unwrap();
+        let fault = if inner.fail_next > 0 {
+            inner.fail_next -= 1;
+            Some((StatusCode::SERVICE_UNAVAILABLE, "SlowDown"))
+        } else if inner
+            .failing_keys
+            .iter()
+            .any(|pattern| request.key.contains(pattern.as_str()))
+        {
+            Some((StatusCode::INTERNAL_SERVER_ERROR, "InternalError"))
+        } else {
+            None
+        };
+        inner.requests.push(request);
+        (inner.latency, fault)
+    };
+    if !latency.is_zero() {
+        tokio::time::sleep(latency).await;
+    }
+    fault.map(|(status, code)| error_response(status, code))
+}
+
+async fn bucket_request(
+    State(state): State<SharedState>,
+    Path(bucket): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+    method: Method,
+    headers: HeaderMap,
+) -> Response {
+    let request = S3Request {
+        method: method.to_string(),
+        bucket: bucket.clone(),
+        key: String::new(),
+        range: range_header(&headers),
+    };
+    if let Some(response) = admit(&state, request).await {
+        return response;
+    }
+
+    let mut inner = state.lock().unwrap();
+    match method {
+        Method::HEAD if inner.buckets.contains(&bucket) => StatusCode::OK.into_response(),
+        Method::HEAD => StatusCode::NOT_FOUND.into_response(),
+        Method::PUT => {
+            inner.buckets.insert(bucket);
+            StatusCode::OK.into_response()
+        }
+        Method::GET if inner.buckets.contains(&bucket) => {
+            let prefix = query.get("prefix").cloned().unwrap_or_default();
+            let contents: String = inner
+                .objects
+                .iter()
+                .filter(|((b, key), _)| *b == bucket && key.starts_with(&prefix))
+                .map(|((_, key), data)| {
+                    format!(
+                        "<Contents><Key>{}</Key><Size>{}</Size></Contents>",
+                        key,
+                        data.len()
+                    )
+                })
+                .collect();
+            xml_response(
+                StatusCode::OK,
+                format!(
+                    "<ListBucketResult><Name>{}</Name><Prefix>{}</Prefix><IsTruncated>false</IsTruncated>{}</ListBucketResult>",
+                    bucket, prefix, contents
+                ),
+            )
+        }
+        Method::DELETE => {
+            inner.buckets.remove(&bucket);
+            StatusCode::NO_CONTENT.into_response()
+        }
+        _ => error_response(StatusCode::NOT_FOUND, "NoSuchBucket"),
+    }
+}
+
+async fn object_request(
+    State(state): State<SharedState>,
+    Path((bucket, key)): Path<(String, String)>,
+    method: Method,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let range = range_header(&headers);
+    let request = S3Request {
+        method: method.to_string(),
+        bucket: bucket.clone(),
+        key: key.clone(),
+        range: range.clone(),
+    };
+    if let Some(response) = admit(&state, request).await {
+        return response;
+    }
+
+    let mut inner = state.lock().unwrap();
+    if !inner.buckets.contains(&bucket) {
+        return error_response(StatusCode::NOT_FOUND, "NoSuchBucket");
+    }
+    let id = (bucket, key);
+    match method {
+        Method::PUT => {
+            let data = if is_aws_chunked(&headers) {
+                match decode_aws_chunked(&body) {
+                    Some(data) => data,
+                    None => return error_response(StatusCode::BAD_REQUEST, "IncompleteBody"),
+                }
+            } else {
+                body.to_vec()
+            };
+            inner.objects.insert(id, data);
+            (StatusCode::OK, [(header::ETAG, "\"simulated\"")]).into_response()
+        }
+        Method::GET | Method::HEAD => {
+            let Some(data) = inner.objects.get(&id) else {
+                return error_response(StatusCode::NOT_FOUND, "NoSuchKey");
+            };
+            let total = data.len() as u64;
+            let (status, start, end) = match range.as_deref().map(|r| parse_range(r, total)) {
+                None => (StatusCode::OK, 0, total),
+                Some(Some((start, end))) => (StatusCode::PARTIAL_CONTENT, start, end),
+                Some(None) => {
+                    return error_response(StatusCode::RANGE_NOT_SATISFIABLE, "InvalidRange")
+                }
+            };
+            let mut response_headers = HeaderMap::new();
+            response_headers.insert(header::CONTENT_LENGTH, (end - start).into());
+            response_headers.insert(header::ETAG, "\"simulated\"".parse().unwrap());
+            if status == StatusCode::PARTIAL_CONTENT {
+                response_headers.insert(
+                    header::CONTENT_RANGE,
+                    format!("bytes {}-{}/{}", start, end - 1, total)
+                        .parse()
+                        .unwrap(),
+                );
+            }
+            let body = if method == Method::HEAD {
+                Vec::new()
+            } else {
+                data[start as usize..end as usize].to_vec()
+            };
+            (status, response_headers, body).into_response()
+        }
+        Method::DELETE => {
+            inner.objects.remove(&id);
+            StatusCode::NO_CONTENT.into_response()
+        }
+        _ => error_response(StatusCode::METHOD_NOT_ALLOWED, "MethodNotAllowed"),
+    }
+}
+
+fn range_header(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
+
+/// Parse `bytes=start-end` (inclusive end) into `[start, end)` clamped to `total`
+fn parse_range(range: &str, total: u64) -> Option<(u64, u64)> {
+    let (start, end) = range.strip_prefix("bytes=")?.split_once('-')?;
+    let start: u64 = start.parse().ok()?;
+    let end = if end.is_empty() {
+        total
+    } else {
+        (end.parse::<u64>().ok()? + 1).min(total)
+    };
+    (start < end).then_some((start, end))
+}
+
+fn is_aws_chunked(headers: &HeaderMap) -> bool {
+    let header_contains = |name: &str, needle: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.contains(needle))
+    };
+    header_contains("content-encoding", "aws-chunked")
+        || header_contains("x-amz-content-sha256", "STREAMING-")
+}
+
+/// Decode an `aws-chunked` body: `<hex size>[;ext]\r\n<data>\r\n` repeated,
+/// ending with a zero-size chunk and optional trailers.
+fn decode_aws_chunked(body: &[u8]) -> Option<Vec<u8>> {
+    let mut data = Vec::new();
+    let mut rest = body;
+    loop {
+        let line_end = rest.windows(2).position(|w| w == b"\r\n")?;
+        let line = std::str::from_utf8(&rest[..line_end]).ok()?;
+        let size_hex = line.split(';').next()?.trim();
+        let size = usize::from_str_radix(size_hex, 16).ok()?;
+        rest = &rest[line_end + 2..];
+        if size == 0 {
+            return Some(data);
+        }
+        data.extend_from_slice(rest.get(..size)?);
+        rest = rest.get(size + 2..)?;
+    }
+}
+
+fn xml_response(status: StatusCode, body: String) -> Response {
+    (
+        status,
+        [(header::CONTENT_TYPE, "application/xml")],
+        format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>{}", body),
+    )
+        .into_response()
+}
+
+fn error_response(status: StatusCode, code: &str) -> Response {
+    xml_response(
+        status,
+        format!(
+            "<Error><Code>{}</Code><Message>Simulated {}</Message></Error>",
+            code, code
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-9", 100), Some((0, 10)));
+        assert_eq!(parse_range("bytes=90-", 100), Some((90, 100)));
+        assert_eq!(parse_range("bytes=95-200", 100), Some((95, 100)));
+        assert_eq!(parse_range("bytes=100-", 100), None);
+        assert_eq!(parse_range("items=0-9", 100), None);
+    }
+
+    #[test]
+    fn test_decode_aws_chunked() {
+        let body = b"5;chunk-signature=abc\r\nhello\r\n6\r\n world\r\n0\r\nx-amz-checksum-crc32:AAAA\r\n\r\n";
+        assert_eq!(
+            decode_aws_chunked(body).as_deref(),
+            Some(&b"hello world"[..])
+        );
+        assert_eq!(decode_aws_chunked(b"5\r\nhel"), None);
+    }
+}
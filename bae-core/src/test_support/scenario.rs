@@ -0,0 +1,384 @@
+//! Scripted end-to-end scenarios: import → playback → verify.
+//!
+//! A [`Scenario`] owns a throwaway library (temp dir, database, import service,
+//! playback service) wired exactly like the app. Tests and bug reproductions
+//! drive it step by step; each step panics with context when it fails, so a
+//! failing scenario reads like the bug report it reproduces.
+use crate::db::{Database, DbStorageProfile, DbTrack, ImportStatus};
+use crate::discogs::models::{DiscogsArtist, DiscogsRelease, DiscogsTrack};
+use crate::encryption::EncryptionService;
+use crate::import::{ImportProgress, ImportRequest, ImportService, ImportServiceHandle};
+use crate::library::{LibraryManager, SharedLibraryManager};
+use crate::playback::{PlaybackHandle, PlaybackProgress, PlaybackService};
+use crate::storage::create_storage_reader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tracing::info;
+
+/// How long a single import may take before the scenario fails
+const IMPORT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A throwaway library driven through the real import and playback services
+pub struct Scenario {
+    database: Database,
+    library_manager: LibraryManager,
+    encryption_service: EncryptionService,
+    import_handle: ImportServiceHandle,
+    storage_profile: Option<DbStorageProfile>,
+    playback: Option<(PlaybackHandle, UnboundedReceiver<PlaybackProgress>)>,
+    temp_dir: TempDir,
+}
+
+impl Scenario {
+    /// Library without storage: imports leave files where they are
+    pub async fn new() -> Self {
+        Self::build(None).await
+    }
+
+    /// Library that imports into `profile` (e.g. [`super::SimulatedS3::storage_profile`])
+    pub async fn with_storage(profile: DbStorageProfile) -> Self {
+        Self::build(Some(profile)).await
+    }
+
+    async fn build(storage_profile: Option<DbStorageProfile>) -> Self {
+        let temp_dir = TempDir::new().expect("scenario temp dir");
+        let db_path = temp_dir.path().join("library.db");
+        let database = Database::new(db_path.to_str().unwrap())
+            .await
+            .expect("scenario database");
+        if let Some(profile) = &storage_profile {
+            database
+                .insert_storage_profile(profile)
+                .await
+                .expect("insert storage profile");
+        }
+
+        let encryption_service = EncryptionService::new_with_key(&[0u8; 32]);
+        let library_manager =
+            LibraryManager::new(database.clone(), Some(encryption_service.clone()));
+        let import_handle =
+            start_import_service(&temp_dir, &database, &library_manager, &encryption_service).await;
+
+        Self {
+            database,
+            library_manager,
+            encryption_service,
+            import_handle,
+            storage_profile,
+            playback: None,
+            temp_dir,
+        }
+    }
+
+    pub fn database(&self) -> &Database {
+        &self.database
+    }
+
+    pub fn library_manager(&self) -> &LibraryManager {
+        &self.library_manager
+    }
+
+    /// Whether playback steps can run (there is an audio output device)
+    pub fn audio_available() -> bool {
+        use cpal::traits::HostTrait;
+        std::env::var("SKIP_AUDIO_TESTS").is_err()
+            && cpal::default_host().default_output_device().is_some()
+    }
+
+    /// Copy `files` from `tests/fixtures/<fixture>` into a fresh album folder
+    pub fn copy_fixture(&self, fixture: &str, files: &[&str]) -> PathBuf {
+        let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join(fixture);
+        let target = self.temp_dir.path().join("albums").join(fixture);
+        std::fs::create_dir_all(&target).expect("album folder");
+        for file in files {
+            std::fs::copy(source.join(file), target.join(file))
+                .unwrap_or_else(|e| panic!("copy fixture {}/{}: {}", fixture, file, e));
+        }
+        target
+    }
+
+    /// Discogs release whose tracklist matches `track_titles`, for fixture imports
+    pub fn discogs_release(title: &str, track_titles: &[&str]) -> DiscogsRelease {
+        DiscogsRelease {
+            id: format!("scenario-{}", title.to_lowercase().replace(' ', "-")),
+            title: title.to_string(),
+            year: Some(2024),
+            genre: vec![],
+            style: vec![],
+            format: vec![],
+            country: None,
+            label: vec![],
+            cover_image: None,
+            thumb: None,
+            artists: vec![DiscogsArtist {
+                name: "Scenario Artist".to_string(),
+                id: "scenario-artist".to_string(),
+            }],
+            tracklist: track_titles
+                .iter()
+                .enumerate()
+                .map(|(i, track_title)| DiscogsTrack {
+                    position: (i + 1).to_string(),
+                    title: track_title.to_string(),
+                    duration: None,
+                })
+                .collect(),
+            master_id: "scenario-master".to_string(),
+        }
+    }
+
+    /// Import a folder and wait for it to finish. Returns the release ID.
+    pub async fn import_folder(&self, folder: &Path, release: DiscogsRelease) -> String {
+        info!("Scenario: importing folder {}", folder.display());
+
+        let (_album_id, release_id) = self
+            .import_handle
+            .send_request(ImportRequest::Folder {
+                import_id: uuid::Uuid::new_v4().to_string(),
+                discogs_release: Some(release),
+                mb_release: None,
+                manual_release: None,
+                folder: folder.to_path_buf(),
+                master_year: 2024,
+                cover_art_url: None,
+                storage_profile_id: self.storage_profile.as_ref().map(|p| p.id.clone()),
+                selected_cover_filename: None,
+                excluded_files: Vec::new(),
+                needle_drop_sides: Vec::new(),
+            })
+            .await
+            .unwrap_or_else(|e| panic!("import request rejected: {}", e));
+        self.wait_for_import(&release_id).await;
+        release_id
+    }
+
+    /// Import a torrent served by `peer` and wait for it to finish. Returns the release ID.
+    #[cfg(feature = "torrent")]
+    pub async fn import_torrent(
+        &self,
+        peer: &super::LoopbackPeer,
+        release: DiscogsRelease,
+    ) -> String {
+        use crate::import::TorrentSource;
+
+        info!(
+            "Scenario: importing torrent {}",
+            peer.torrent_path().display()
+        );
+
+        let (_album_id, release_id) = self
+            .import_handle
+            .send_request(ImportRequest::Torrent {
+                import_id: uuid::Uuid::new_v4().to_string(),
+                torrent_source: TorrentSource::File(peer.torrent_path().to_path_buf()),
+                discogs_release: Some(release),
+                mb_release: None,
+                master_year: 2024,
+                seed_after_download: false,
+                torrent_metadata: peer.import_metadata(),
+                cover_art_url: None,
+                storage_profile_id: self.storage_profile.as_ref().map(|p| p.id.clone()),
+                selected_cover_filename: None,
+            })
+            .await
+            .unwrap_or_else(|e| panic!("torrent import request rejected: {}", e));
+        self.wait_for_import(&release_id).await;
+        release_id
+    }
+
+    async fn wait_for_import(&self, release_id: &str) {
+        let mut progress_rx = self.import_handle.subscribe_release(release_id.to_string());
+        let completed = tokio::time::timeout(IMPORT_TIMEOUT, async {
+            while let Some(progress) = progress_rx.recv().await {
+                match progress {
+                    ImportProgress::Complete {
+                        release_id: None, ..
+                    } => return,
+                    ImportProgress::Failed { error, .. } => panic!("import failed: {}", error),
+                    _ => {}
+                }
+            }
+            panic!("import progress channel closed before completion");
+        })
+        .await;
+        assert!(
+            completed.is_ok(),
+            "import of release {} did not finish within {:?}",
+            release_id,
+            IMPORT_TIMEOUT
+        );
+    }
+
+    /// Tracks of an imported release, asserting they all completed
+    pub async fn tracks(&self, release_id: &str) -> Vec<DbTrack> {
+        let tracks = self
+            .library_manager
+            .get_tracks(release_id)
+            .await
+            .expect("get tracks");
+        assert!(!tracks.is_empty(), "release {} has no tracks", release_id);
+        for track in &tracks {
+            assert_eq!(
+                track.import_status,
+                ImportStatus::Complete,
+                "track '{}' did not complete import",
+                track.title
+            );
+        }
+        tracks
+    }
+
+    /// Play a track and wait until audio position advances past zero
+    pub async fn play(&mut self, track_id: &str, within: Duration) {
+        info!("Scenario: playing track {}", track_id);
+
+        let (handle, progress_rx) = self.playback.get_or_insert_with(|| {
+            std::env::set_var("MUTE_TEST_AUDIO", "1");
+            let handle = PlaybackService::start(
+                self.library_manager.clone(),
+                Some(self.encryption_service.clone()),
+                tokio::runtime::Handle::current(),
+            );
+            handle.set_volume(0.0);
+            let progress_rx = handle.subscribe_progress();
+            (handle, progress_rx)
+        });
+        handle.play(track_id.to_string());
+
+        let started = tokio::time::timeout(within, async {
+            while let Some(progress) = progress_rx.recv().await {
+                match progress {
+                    PlaybackProgress::PositionUpdate {
+                        position,
+                        track_id: playing,
+                    } if playing == track_id && !position.is_zero() => return,
+                    PlaybackProgress::PlaybackError { message } => {
+                        panic!("playback of {} failed: {}", track_id, message)
+                    }
+                    _ => {}
+                }
+            }
+            panic!("playback progress channel closed");
+        })
+        .await;
+        assert!(
+            started.is_ok(),
+            "track {} did not start playing within {:?}",
+            track_id,
+            within
+        );
+    }
+
+    /// Stop playback, if it was started
+    pub fn stop(&self) {
+        if let Some((handle, _)) = &self.playback {
+            handle.stop();
+        }
+    }
+
+    /// Read every stored file of a release back through its storage (decrypting
+    /// if needed) and compare it byte-for-byte with `source_folder`.
+    pub async fn verify_release_files(&self, release_id: &str, source_folder: &Path) {
+        let files = self
+            .library_manager
+            .get_files_for_release(release_id)
+            .await
+            .expect("get files");
+        assert!(!files.is_empty(), "release {} has no files", release_id);
+
+        let profile = self
+            .library_manager
+            .get_storage_profile_for_release(release_id)
+            .await
+            .expect("get storage profile");
+        let reader = match &profile {
+            Some(profile) => Some(
+                create_storage_reader(profile)
+                    .await
+                    .expect("create storage reader"),
+            ),
+            None => None,
+        };
+
+        for file in &files {
+            let source_path = file
+                .source_path
+                .as_ref()
+                .unwrap_or_else(|| panic!("file {} has no source path", file.original_filename));
+            let stored = match &reader {
+                Some(reader) => reader
+                    .download(source_path)
+                    .await
+                    .unwrap_or_else(|e| panic!("read back {}: {}", file.original_filename, e)),
+                None => std::fs::read(source_path)
+                    .unwrap_or_else(|e| panic!("read {}: {}", source_path, e)),
+            };
+            let stored = if profile.as_ref().is_some_and(|p| p.encrypted) {
+                self.encryption_service
+                    .decrypt(&stored)
+                    .unwrap_or_else(|e| panic!("decrypt {}: {}", file.original_filename, e))
+            } else {
+                stored
+            };
+            let expected = std::fs::read(source_folder.join(&file.original_filename))
+                .unwrap_or_else(|e| panic!("read source {}: {}", file.original_filename, e));
+            assert!(
+                stored == expected,
+                "stored {} ({} bytes) differs from source ({} bytes)",
+                file.original_filename,
+                stored.len(),
+                expected.len()
+            );
+        }
+    }
+}
+
+#[cfg(not(feature = "torrent"))]
+async fn start_import_service(
+    _temp_dir: &TempDir,
+    database: &Database,
+    library_manager: &LibraryManager,
+    encryption_service: &EncryptionService,
+) -> ImportServiceHandle {
+    ImportService::start(
+        tokio::runtime::Handle::current(),
+        SharedLibraryManager::new(library_manager.clone()),
+        Some(encryption_service.clone()),
+        Arc::new(database.clone()),
+    )
+}
+
+/// Torrent builds need a torrent manager; it listens on loopback only so
+/// scenarios never reach the network.
+#[cfg(feature = "torrent")]
+async fn start_import_service(
+    temp_dir: &TempDir,
+    database: &Database,
+    library_manager: &LibraryManager,
+    encryption_service: &EncryptionService,
+) -> ImportServiceHandle {
+    use crate::torrent::client::TorrentClientOptions;
+    use crate::torrent::LazyTorrentManager;
+
+    let torrent_manager = LazyTorrentManager::new(
+        temp_dir.path().join("seeding"),
+        database.clone(),
+        TorrentClientOptions {
+            bind_interface: Some("127.0.0.1:0".to_string()),
+            ..TorrentClientOptions::default()
+        },
+    );
+    ImportService::start(
+        tokio::runtime::Handle::current(),
+        SharedLibraryManager::new(library_manager.clone()),
+        Some(encryption_service.clone()),
+        torrent_manager,
+        Arc::new(database.clone()),
+    )
+}
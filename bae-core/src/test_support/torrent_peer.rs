@@ -0,0 +1,380 @@
+//! Loopback BitTorrent seeder for end-to-end torrent import tests.
+//!
+//! Builds a `.torrent` for a fixture folder, announces itself through an
+//! in-process HTTP tracker, and serves pieces over the peer wire protocol on
+//! 127.0.0.1. libtorrent downloads from it like any other seed, without
+//! touching the network.
+use crate::import::{TorrentFileMetadata, TorrentImportMetadata};
+use axum::routing::get;
+use axum::Router;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, warn};
+
+const PIECE_LENGTH: usize = 32 * 1024;
+const PROTOCOL: &[u8] = b"BitTorrent protocol";
+const PEER_ID: &[u8; 20] = b"-BT0001-loopbackpeer";
+
+const MSG_UNCHOKE: u8 = 1;
+const MSG_BITFIELD: u8 = 5;
+const MSG_REQUEST: u8 = 6;
+const MSG_PIECE: u8 = 7;
+
+/// Seeds a fixture folder to libtorrent over loopback.
+///
+/// The tracker and peer stop when this is dropped.
+pub struct LoopbackPeer {
+    torrent_path: PathBuf,
+    metadata: TorrentImportMetadata,
+    tasks: Vec<tokio::task::JoinHandle<()>>,
+    _torrent_dir: TempDir,
+}
+
+struct Seed {
+    info_hash: [u8; 20],
+    content: Vec<u8>,
+    num_pieces: usize,
+}
+
+impl LoopbackPeer {
+    /// Start seeding every file under `folder` as a torrent named `name`
+    pub async fn start(folder: &Path, name: &str) -> std::io::Result<Self> {
+        let files = collect_files(folder)?;
+        let mut content = Vec::new();
+        let mut file_entries = Vec::new();
+        let mut file_list = Vec::new();
+        for relative in &files {
+            let data = std::fs::read(folder.join(relative))?;
+            let path_components: Vec<Bencode> = relative
+                .components()
+                .map(|c| Bencode::bytes(c.as_os_str().to_string_lossy().as_bytes()))
+                .collect();
+            file_entries.push(Bencode::dict([
+                ("length", Bencode::Int(data.len() as i64)),
+                ("path", Bencode::List(path_components)),
+            ]));
+            file_list.push(TorrentFileMetadata {
+                path: PathBuf::from(name).join(relative),
+                size: data.len() as i64,
+            });
+            content.extend_from_slice(&data);
+        }
+
+        let pieces: Vec<u8> = content.chunks(PIECE_LENGTH).flat_map(sha1).collect();
+        let num_pieces = content.len().div_ceil(PIECE_LENGTH);
+        let info = Bencode::dict([
+            ("files", Bencode::List(file_entries)),
+            ("name", Bencode::bytes(name.as_bytes())),
+            ("piece length", Bencode::Int(PIECE_LENGTH as i64)),
+            ("pieces", Bencode::Bytes(pieces)),
+        ]);
+        let info_hash = sha1(&info.encode());
+
+        let peer_listener = TcpListener::bind("127.0.0.1:0").await?;
+        let peer_addr = peer_listener.local_addr()?;
+        let tracker_listener = TcpListener::bind("127.0.0.1:0").await?;
+        let announce = format!("http://{}/announce", tracker_listener.local_addr()?);
+
+        let torrent_dir = TempDir::new()?;
+        let torrent_path = torrent_dir.path().join(format!("{}.torrent", name));
+        let torrent = Bencode::dict([
+            ("announce", Bencode::bytes(announce.as_bytes())),
+            ("info", info),
+        ]);
+        std::fs::write(&torrent_path, torrent.encode())?;
+
+        let metadata = TorrentImportMetadata {
+            info_hash: hex::encode(info_hash),
+            magnet_link: None,
+            torrent_name: name.to_string(),
+            total_size_bytes: content.len() as i64,
+            piece_length: PIECE_LENGTH as i32,
+            num_pieces: num_pieces as i32,
+            seed_after_download: false,
+            file_list,
+        };
+
+        let seed = Arc::new(Seed {
+            info_hash,
+            content,
+            num_pieces,
+        });
+        let tracker = Router::new().route(
+            "/announce",
+            get(move || async move { tracker_response(peer_addr) }),
+        );
+        let tasks = vec![
+            tokio::spawn(async move {
+                let _ = axum::serve(tracker_listener, tracker).await;
+            }),
+            tokio::spawn(accept_peers(peer_listener, seed)),
+        ];
+
+        debug!("Loopback peer seeding '{}' on {}", name, peer_addr);
+
+        Ok(Self {
+            torrent_path,
+            metadata,
+            tasks,
+            _torrent_dir: torrent_dir,
+        })
+    }
+
+    /// Path to the generated `.torrent` file
+    pub fn torrent_path(&self) -> &Path {
+        &self.torrent_path
+    }
+
+    /// Import metadata matching the generated torrent
+    pub fn import_metadata(&self) -> TorrentImportMetadata {
+        self.metadata.clone()
+    }
+}
+
+impl Drop for LoopbackPeer {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Relative paths of all files under `folder`, sorted for a stable piece layout
+fn collect_files(folder: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![folder.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if let Ok(relative) = path.strip_prefix(folder) {
+                files.push(relative.to_path_buf());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Compact tracker response listing only the loopback peer
+fn tracker_response(peer_addr: SocketAddr) -> Vec<u8> {
+    let mut peers = match peer_addr.ip() {
+        std::net::IpAddr::V4(ip) => ip.octets().to_vec(),
+        std::net::IpAddr::V6(_) => unreachable!("loopback peer binds to IPv4"),
+    };
+    peers.extend_from_slice(&peer_addr.port().to_be_bytes());
+    Bencode::dict([
+        ("interval", Bencode::Int(1800)),
+        ("peers", Bencode::Bytes(peers)),
+    ])
+    .encode()
+}
+
+async fn accept_peers(listener: TcpListener, seed: Arc<Seed>) {
+    while let Ok((stream, addr)) = listener.accept().await {
+        let seed = seed.clone();
+        tokio::spawn(async move {
+            if let Err(e) = serve_peer(stream, &seed).await {
+                debug!("Loopback peer connection from {} closed: {}", addr, e);
+            }
+        });
+    }
+}
+
+/// Handshake, advertise every piece, unchoke, then answer block requests
+async fn serve_peer(mut stream: TcpStream, seed: &Seed) -> std::io::Result<()> {
+    let mut handshake = [0u8; 68];
+    stream.read_exact(&mut handshake).await?;
+    if handshake[0] as usize != PROTOCOL.len() || &handshake[1..20] != PROTOCOL {
+        return Err(std::io::Error::other("not a BitTorrent handshake"));
+    }
+    if handshake[28..48] != seed.info_hash {
+        warn!("Loopback peer got handshake for an unknown info hash");
+        return Err(std::io::Error::other("unknown info hash"));
+    }
+
+    let mut reply = Vec::with_capacity(68);
+    reply.push(PROTOCOL.len() as u8);
+    reply.extend_from_slice(PROTOCOL);
+    reply.extend_from_slice(&[0u8; 8]);
+    reply.extend_from_slice(&seed.info_hash);
+    reply.extend_from_slice(PEER_ID);
+    stream.write_all(&reply).await?;
+
+    let mut bitfield = vec![0u8; seed.num_pieces.div_ceil(8)];
+    for piece in 0..seed.num_pieces {
+        bitfield[piece / 8] |= 0x80 >> (piece % 8);
+    }
+    write_message(&mut stream, MSG_BITFIELD, &bitfield).await?;
+    write_message(&mut stream, MSG_UNCHOKE, &[]).await?;
+
+    loop {
+        let len = stream.read_u32().await? as usize;
+        if len == 0 {
+            continue; // keep-alive
+        }
+        let mut message = vec![0u8; len];
+        stream.read_exact(&mut message).await?;
+        if message[0] != MSG_REQUEST || message.len() != 13 {
+            continue;
+        }
+
+        let field = |i: usize| u32::from_be_bytes(message[i..i + 4].try_into().unwrap()) as usize;
+        let (index, begin, length) = (field(1), field(5), field(9));
+        let start = index * PIECE_LENGTH + begin;
+        let Some(block) = seed.content.get(start..start + length) else {
+            return Err(std::io::Error::other("request out of range"));
+        };
+        let mut payload = Vec::with_capacity(8 + length);
+        payload.extend_from_slice(&(index as u32).to_be_bytes());
+        payload.extend_from_slice(&(begin as u32).to_be_bytes());
+        payload.extend_from_slice(block);
+        write_message(&mut stream, MSG_PIECE, &payload).await?;
+    }
+}
+
+async fn write_message(stream: &mut TcpStream, id: u8, payload: &[u8]) -> std::io::Result<()> {
+    let mut message = Vec::with_capacity(5 + payload.len());
+    message.extend_from_slice(&(payload.len() as u32 + 1).to_be_bytes());
+    message.push(id);
+    message.extend_from_slice(payload);
+    stream.write_all(&message).await
+}
+
+/// Minimal bencode encoder for building torrents and tracker responses
+enum Bencode {
+    Int(i64),
+    Bytes(Vec<u8>),
+    List(Vec<Bencode>),
+    Dict(BTreeMap<Vec<u8>, Bencode>),
+}
+
+impl Bencode {
+    fn bytes(bytes: &[u8]) -> Self {
+        Bencode::Bytes(bytes.to_vec())
+    }
+
+    fn dict<const N: usize>(entries: [(&str, Bencode); N]) -> Self {
+        Bencode::Dict(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.as_bytes().to_vec(), value))
+                .collect(),
+        )
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode_into(&mut out);
+        out
+    }
+
+    fn encode_into(&self, out: &mut Vec<u8>) {
+        match self {
+            Bencode::Int(value) => out.extend_from_slice(format!("i{}e", value).as_bytes()),
+            Bencode::Bytes(bytes) => {
+                out.extend_from_slice(format!("{}:", bytes.len()).as_bytes());
+                out.extend_from_slice(bytes);
+            }
+            Bencode::List(items) => {
+                out.push(b'l');
+                for item in items {
+                    item.encode_into(out);
+                }
+                out.push(b'e');
+            }
+            // BTreeMap keeps keys in the sorted order bencode requires
+            Bencode::Dict(entries) => {
+                out.push(b'd');
+                for (key, value) in entries {
+                    Bencode::Bytes(key.clone()).encode_into(out);
+                    value.encode_into(out);
+                }
+                out.push(b'e');
+            }
+        }
+    }
+}
+
+/// SHA-1 digest, used for piece hashes and the info hash
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (state, value) in h.iter_mut().zip([a, b, c, d, e]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (i, word) in h.iter().enumerate() {
+        digest[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha1_known_vectors() {
+        assert_eq!(
+            hex::encode(sha1(b"abc")),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            hex::encode(sha1(b"")),
+            "da39a3ee5e6b4b0d3255bfef95601890afd80709"
+        );
+    }
+
+    #[test]
+    fn test_bencode_sorts_dict_keys() {
+        let value = Bencode::dict([
+            ("peers", Bencode::bytes(b"ab")),
+            ("interval", Bencode::Int(30)),
+            ("files", Bencode::List(vec![Bencode::Int(-1)])),
+        ]);
+        assert_eq!(value.encode(), b"d5:filesli-1ee8:intervali30e5:peers2:abe");
+    }
+}
@@ -1,16 +1,19 @@
 use crate::torrent::ffi::{
     self, create_session_params_default, create_session_params_with_storage,
     create_session_with_params, get_session_ptr, load_torrent_file, parse_magnet_uri,
-    session_add_torrent, session_pop_alerts, session_remove_torrent, set_listen_interfaces,
-    set_paused, torrent_get_file_list, torrent_get_name, torrent_get_num_peers,
-    torrent_get_num_pieces, torrent_get_num_seeds, torrent_get_piece_length, torrent_get_progress,
+    session_add_torrent, session_pause, session_pop_alerts, session_remove_torrent, session_resume,
+    set_listen_interfaces, set_paused, torrent_get_download_rate, torrent_get_downloaded,
+    torrent_get_file_list, torrent_get_name, torrent_get_num_peers, torrent_get_num_pieces,
+    torrent_get_num_seeds, torrent_get_piece_length, torrent_get_pieces, torrent_get_progress,
     torrent_get_storage_index, torrent_get_total_size, torrent_get_tracker_status,
-    torrent_has_metadata, torrent_pause, torrent_resume, torrent_set_file_priorities,
-    AddTorrentParams, AlertData, Session, TorrentFileInfo, TorrentHandle as FfiTorrentHandle,
+    torrent_get_uploaded, torrent_has_metadata, torrent_pause, torrent_resume,
+    torrent_set_file_priorities, AddTorrentParams, AlertData, Session, TorrentFileInfo,
+    TorrentHandle as FfiTorrentHandle,
 };
 use crate::torrent::storage::{create_bae_storage_constructor, BaeStorage};
 use cxx::UniquePtr;
 use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::Arc;
@@ -64,6 +67,12 @@ pub struct TorrentClientOptions {
     pub enable_upnp: bool,
     /// Enable NAT-PMP port forwarding
     pub enable_natpmp: bool,
+    /// Listen on IPv6 addresses as well as IPv4
+    pub enable_ipv6: bool,
+    /// Enable the DHT for finding peers without a tracker
+    pub enable_dht: bool,
+    /// DHT bootstrap nodes as host:port. Empty = libtorrent defaults.
+    pub dht_bootstrap_nodes: Vec<String>,
     /// Global max connections. None = unlimited.
     pub max_connections: Option<i32>,
     /// Global max upload slots. None = unlimited.
@@ -290,6 +299,25 @@ impl TorrentClient {
         drop(session_guard);
         Ok(())
     }
+    /// Pause or resume every torrent in the session
+    pub async fn set_paused(&self, paused: bool) -> Result<(), TorrentError> {
+        let mut session_guard = self.session.write().await;
+        let session_ptr = get_session_ptr(&mut session_guard);
+        if session_ptr.is_null() {
+            return Err(TorrentError::Libtorrent(
+                "Failed to get session pointer".to_string(),
+            ));
+        }
+        unsafe {
+            if paused {
+                session_pause(session_ptr);
+            } else {
+                session_resume(session_ptr);
+            }
+        }
+        drop(session_guard);
+        Ok(())
+    }
     /// Pop all pending alerts from the session
     pub async fn pop_alerts(&self) -> Vec<AlertData> {
         let mut session_guard = self.session.write().await;
@@ -509,6 +537,58 @@ impl TorrentHandle {
         drop(handle_guard);
         Ok(num_seeds)
     }
+    /// Get payload download rate in bytes per second
+    pub async fn download_rate(&self) -> Result<i32, TorrentError> {
+        let handle_guard = self.handle.0.read().await;
+        let handle_ptr = *handle_guard;
+        if handle_ptr.is_null() {
+            return Err(TorrentError::Libtorrent(
+                "Invalid torrent handle".to_string(),
+            ));
+        }
+        let rate = unsafe { torrent_get_download_rate(handle_ptr) };
+        drop(handle_guard);
+        Ok(rate)
+    }
+    /// Get which pieces are downloaded, one entry per piece
+    pub async fn pieces(&self) -> Result<Vec<bool>, TorrentError> {
+        let handle_guard = self.handle.0.read().await;
+        let handle_ptr = *handle_guard;
+        if handle_ptr.is_null() {
+            return Err(TorrentError::Libtorrent(
+                "Invalid torrent handle".to_string(),
+            ));
+        }
+        let pieces = unsafe { torrent_get_pieces(handle_ptr) };
+        drop(handle_guard);
+        Ok(pieces.into_iter().map(|have| have != 0).collect())
+    }
+    /// Bytes uploaded since the torrent was added to the session
+    pub async fn uploaded_bytes(&self) -> Result<i64, TorrentError> {
+        let handle_guard = self.handle.0.read().await;
+        let handle_ptr = *handle_guard;
+        if handle_ptr.is_null() {
+            return Err(TorrentError::Libtorrent(
+                "Invalid torrent handle".to_string(),
+            ));
+        }
+        let uploaded = unsafe { torrent_get_uploaded(handle_ptr) };
+        drop(handle_guard);
+        Ok(uploaded)
+    }
+    /// Bytes downloaded since the torrent was added to the session
+    pub async fn downloaded_bytes(&self) -> Result<i64, TorrentError> {
+        let handle_guard = self.handle.0.read().await;
+        let handle_ptr = *handle_guard;
+        if handle_ptr.is_null() {
+            return Err(TorrentError::Libtorrent(
+                "Invalid torrent handle".to_string(),
+            ));
+        }
+        let downloaded = unsafe { torrent_get_downloaded(handle_ptr) };
+        drop(handle_guard);
+        Ok(downloaded)
+    }
     /// Get tracker status as a formatted string
     pub async fn tracker_status(&self) -> Result<String, TorrentError> {
         let handle_guard = self.handle.0.read().await;
@@ -593,6 +673,27 @@ fn apply_options_to_session_params(
         }
     }
 
+    // Apply DHT settings
+    unsafe {
+        if let Some(pinned_params) = session_params.as_mut() {
+            let params_ptr = std::pin::Pin::get_unchecked_mut(pinned_params) as *mut _;
+            ffi::set_enable_dht(params_ptr, options.enable_dht);
+            if options.enable_dht && !options.dht_bootstrap_nodes.is_empty() {
+                ffi::set_dht_bootstrap_nodes(params_ptr, &options.dht_bootstrap_nodes.join(","));
+            }
+        }
+    }
+    if !options.enable_dht {
+        info!("DHT: disabled");
+    } else if options.dht_bootstrap_nodes.is_empty() {
+        info!("DHT: enabled, default bootstrap nodes");
+    } else {
+        info!(
+            "DHT: enabled, bootstrap nodes: {}",
+            options.dht_bootstrap_nodes.join(", ")
+        );
+    }
+
     // Apply connection limits
     if let Some(max_connections) = options.max_connections {
         unsafe {
@@ -623,10 +724,7 @@ fn build_listen_interface(options: &TorrentClientOptions) -> Option<String> {
     use tracing::warn;
 
     // If there's a specific port, use it
-    let port = options
-        .listen_port
-        .map(|p| p.to_string())
-        .unwrap_or_else(|| "0".to_string());
+    let port = options.listen_port.unwrap_or(0);
 
     if let Some(interface) = &options.bind_interface {
         // If interface already contains a port, use as-is
@@ -634,17 +732,9 @@ fn build_listen_interface(options: &TorrentClientOptions) -> Option<String> {
             return Some(interface.clone());
         }
 
-        // Try to resolve interface name to IP
-        match get_interface_ip_and_format(interface) {
-            Ok(formatted) => {
-                // Replace :0 with actual port
-                let formatted = if port != "0" {
-                    formatted.replace(":0", &format!(":{}", port))
-                } else {
-                    formatted
-                };
-                Some(formatted)
-            }
+        // Try to resolve interface name to IPs
+        match get_interface_ips(interface, options.enable_ipv6) {
+            Ok(ips) => Some(format_listen_addrs(&ips, port)),
             Err(e) => {
                 warn!(
                     "Failed to resolve IP for interface '{}': {}. Using interface:port format.",
@@ -653,16 +743,29 @@ fn build_listen_interface(options: &TorrentClientOptions) -> Option<String> {
                 Some(format!("{}:{}", interface, port))
             }
         }
-    } else if options.listen_port.is_some() {
-        // No interface but specific port - bind to all interfaces on that port
-        Some(format!("0.0.0.0:{}", port))
+    } else if options.listen_port.is_some() || !options.enable_ipv6 {
+        // No interface - bind to all addresses on the port. libtorrent's own
+        // default already covers both IPv4 and IPv6 on a random port.
+        let mut ips = vec![IpAddr::V4(Ipv4Addr::UNSPECIFIED)];
+        if options.enable_ipv6 {
+            ips.push(IpAddr::V6(Ipv6Addr::UNSPECIFIED));
+        }
+        Some(format_listen_addrs(&ips, port))
     } else {
         // No interface, no port - use libtorrent defaults
         None
     }
 }
-/// Get the IP address of a network interface and format it as IP:port for libtorrent
-fn get_interface_ip_and_format(interface_name: &str) -> Result<String, String> {
+/// Format addresses as libtorrent's comma-separated listen_interfaces list
+fn format_listen_addrs(ips: &[IpAddr], port: u16) -> String {
+    ips.iter()
+        .map(|ip| SocketAddr::new(*ip, port).to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+/// Get the addresses of a network interface: its first IPv4 address, plus its
+/// first IPv6 address when `include_ipv6` is set
+fn get_interface_ips(interface_name: &str, include_ipv6: bool) -> Result<Vec<IpAddr>, String> {
     use if_addrs::get_if_addrs;
     let interfaces =
         get_if_addrs().map_err(|e| format!("Failed to enumerate interfaces: {}", e))?;
@@ -678,13 +781,20 @@ fn get_interface_ip_and_format(interface_name: &str) -> Result<String, String> {
             }
         }
     }
-    let ip = ipv4_addr.or(ipv6_addr).ok_or_else(|| {
-        format!(
+    let ips: Vec<IpAddr> = match (ipv4_addr, ipv6_addr) {
+        (Some(v4), Some(v6)) if include_ipv6 => vec![v4, v6],
+        (Some(v4), _) => vec![v4],
+        // An IPv6-only interface is used even with IPv6 off, as before
+        (None, Some(v6)) => vec![v6],
+        (None, None) => Vec::new(),
+    };
+    if ips.is_empty() {
+        return Err(format!(
             "Interface '{}' not found or has no IP address",
             interface_name
-        )
-    })?;
-    Ok(format!("{}:0", ip))
+        ));
+    }
+    Ok(ips)
 }
 /// Create empty storage registry and index map
 fn create_empty_storage_registries() -> (StorageRegistry, StorageIndexMap) {
@@ -744,3 +854,42 @@ fn create_session_from_params(
     }
     Ok(session_arc)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listen_interface_defaults_to_libtorrent() {
+        let options = TorrentClientOptions {
+            enable_ipv6: true,
+            ..TorrentClientOptions::default()
+        };
+        assert_eq!(build_listen_interface(&options), None);
+    }
+
+    #[test]
+    fn test_listen_port_binds_ipv4_and_ipv6() {
+        let options = TorrentClientOptions {
+            listen_port: Some(6881),
+            enable_ipv6: true,
+            ..TorrentClientOptions::default()
+        };
+        assert_eq!(
+            build_listen_interface(&options).as_deref(),
+            Some("0.0.0.0:6881,[::]:6881")
+        );
+    }
+
+    #[test]
+    fn test_ipv6_disabled_binds_ipv4_only() {
+        let options = TorrentClientOptions {
+            enable_ipv6: false,
+            ..TorrentClientOptions::default()
+        };
+        assert_eq!(
+            build_listen_interface(&options).as_deref(),
+            Some("0.0.0.0:0")
+        );
+    }
+}
@@ -55,6 +55,16 @@ mod ffi {
         /// # Safety
         /// `params` must be a valid pointer to SessionParams that outlives the call.
         unsafe fn set_enable_natpmp(params: *mut SessionParams, enable: bool);
+        /// Set enable_dht on session_params
+        ///
+        /// # Safety
+        /// `params` must be a valid pointer to SessionParams that outlives the call.
+        unsafe fn set_enable_dht(params: *mut SessionParams, enable: bool);
+        /// Set dht_bootstrap_nodes on session_params (comma-separated host:port list)
+        ///
+        /// # Safety
+        /// `params` must be a valid pointer to SessionParams that outlives the call.
+        unsafe fn set_dht_bootstrap_nodes(params: *mut SessionParams, nodes: &str);
         /// Set connections_limit on session_params (global max connections)
         ///
         /// # Safety
@@ -156,6 +166,26 @@ mod ffi {
         /// # Safety
         /// `handle` must be a valid pointer to a TorrentHandle that outlives the call.
         unsafe fn torrent_get_num_seeds(handle: *mut TorrentHandle) -> i32;
+        /// Get payload download rate in bytes per second
+        ///
+        /// # Safety
+        /// `handle` must be a valid pointer to a TorrentHandle that outlives the call.
+        unsafe fn torrent_get_download_rate(handle: *mut TorrentHandle) -> i32;
+        /// Get which pieces are downloaded, one byte per piece (1 = have)
+        ///
+        /// # Safety
+        /// `handle` must be a valid pointer to a TorrentHandle that outlives the call.
+        unsafe fn torrent_get_pieces(handle: *mut TorrentHandle) -> Vec<u8>;
+        /// Get bytes uploaded since the torrent was added to the session
+        ///
+        /// # Safety
+        /// `handle` must be a valid pointer to a TorrentHandle that outlives the call.
+        unsafe fn torrent_get_uploaded(handle: *mut TorrentHandle) -> i64;
+        /// Get bytes downloaded since the torrent was added to the session
+        ///
+        /// # Safety
+        /// `handle` must be a valid pointer to a TorrentHandle that outlives the call.
+        unsafe fn torrent_get_downloaded(handle: *mut TorrentHandle) -> i64;
         /// Get tracker status as a formatted string
         ///
         /// # Safety
@@ -171,6 +201,16 @@ mod ffi {
         /// # Safety
         /// `handle` must be a valid pointer to a TorrentHandle that outlives the call.
         unsafe fn torrent_resume(handle: *mut TorrentHandle);
+        /// Pause every torrent in a session
+        ///
+        /// # Safety
+        /// `sess` must be a valid pointer to a Session that outlives the call.
+        unsafe fn session_pause(sess: *mut Session);
+        /// Resume a paused session
+        ///
+        /// # Safety
+        /// `sess` must be a valid pointer to a Session that outlives the call.
+        unsafe fn session_resume(sess: *mut Session);
         /// Remove a torrent from a session
         ///
         /// If `delete_files` is true, also deletes the downloaded files from disk.
@@ -229,13 +269,14 @@ mod ffi {
 pub use ffi::{
     create_bae_storage_constructor, create_session_params_default,
     create_session_params_with_storage, create_session_with_params, get_session_ptr,
-    get_torrent_info, load_torrent_file, parse_magnet_uri, session_add_torrent, session_pop_alerts,
-    session_remove_torrent, set_connections_limit, set_enable_natpmp, set_enable_upnp,
+    get_torrent_info, load_torrent_file, parse_magnet_uri, session_add_torrent, session_pause,
+    session_pop_alerts, session_remove_torrent, session_resume, set_connections_limit,
+    set_dht_bootstrap_nodes, set_enable_dht, set_enable_natpmp, set_enable_upnp,
     set_listen_interfaces, set_paused, set_seed_mode, set_unchoke_slots_limit,
-    torrent_get_file_list, torrent_get_name, torrent_get_num_peers, torrent_get_num_pieces,
-    torrent_get_num_seeds, torrent_get_piece_length, torrent_get_progress,
+    torrent_get_downloaded, torrent_get_file_list, torrent_get_name, torrent_get_num_peers,
+    torrent_get_num_pieces, torrent_get_num_seeds, torrent_get_piece_length, torrent_get_progress,
     torrent_get_storage_index, torrent_get_total_size, torrent_get_tracker_status,
-    torrent_has_metadata, torrent_pause, torrent_resume, torrent_set_file_priorities,
-    AddTorrentParams, AlertData, BaeStorageConstructor, Session, SessionParams, TorrentFileInfo,
-    TorrentHandle, TorrentInfo,
+    torrent_get_uploaded, torrent_has_metadata, torrent_pause, torrent_resume,
+    torrent_set_file_priorities, AddTorrentParams, AlertData, BaeStorageConstructor, Session,
+    SessionParams, TorrentFileInfo, TorrentHandle, TorrentInfo,
 };
@@ -0,0 +1,117 @@
+//! Downloading part of a multi-album torrent
+//!
+//! Discography torrents hold many release folders. The import picks the
+//! folders it wants and everything else is set to not download.
+use crate::import::folder_scanner::release_folders_from_paths;
+use crate::torrent::client::{FilePriority, TorrentFile};
+use std::path::{Path, PathBuf};
+
+/// A release folder found in a torrent's file list
+#[derive(Debug, Clone, PartialEq)]
+pub struct TorrentAlbumFolder {
+    /// Folder path relative to the torrent root
+    pub path: PathBuf,
+    pub file_count: usize,
+    pub total_size: i64,
+}
+
+/// Release folders in the torrent, with the files and bytes under each
+pub fn detect_album_folders(files: &[TorrentFile]) -> Vec<TorrentAlbumFolder> {
+    let paths: Vec<PathBuf> = files.iter().map(|f| f.path.clone()).collect();
+    release_folders_from_paths(&paths)
+        .into_iter()
+        .map(|folder| {
+            let inside: Vec<&TorrentFile> = files
+                .iter()
+                .filter(|f| is_in_folder(&f.path, &folder))
+                .collect();
+            TorrentAlbumFolder {
+                file_count: inside.len(),
+                total_size: inside.iter().map(|f| f.size).sum(),
+                path: folder,
+            }
+        })
+        .collect()
+}
+
+/// Files under any of the given folders
+pub fn files_in_folders(files: &[TorrentFile], folders: &[PathBuf]) -> Vec<TorrentFile> {
+    files
+        .iter()
+        .filter(|f| folders.iter().any(|folder| is_in_folder(&f.path, folder)))
+        .cloned()
+        .collect()
+}
+
+/// Per-file priorities that download only `wanted`, in torrent file order
+pub fn priorities_for_files(files: &[TorrentFile], wanted: &[PathBuf]) -> Vec<FilePriority> {
+    files
+        .iter()
+        .map(|f| {
+            if wanted.contains(&f.path) {
+                FilePriority::Normal
+            } else {
+                FilePriority::DoNotDownload
+            }
+        })
+        .collect()
+}
+
+fn is_in_folder(path: &Path, folder: &Path) -> bool {
+    folder.as_os_str().is_empty() || path.starts_with(folder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, size: i64) -> TorrentFile {
+        TorrentFile {
+            path: PathBuf::from(path),
+            size,
+        }
+    }
+
+    fn discography() -> Vec<TorrentFile> {
+        vec![
+            file("Disco/1999 - First Album/01.flac", 30),
+            file("Disco/1999 - First Album/02.flac", 30),
+            file("Disco/2003 - Second Album/01.flac", 40),
+            file("Disco/2003 - Second Album/cover.jpg", 5),
+            file("Disco/readme.txt", 1),
+        ]
+    }
+
+    #[test]
+    fn test_detect_album_folders_counts_files() {
+        let folders = detect_album_folders(&discography());
+
+        assert_eq!(folders.len(), 2);
+        assert_eq!(folders[0].path, PathBuf::from("Disco/1999 - First Album"));
+        assert_eq!(folders[0].file_count, 2);
+        assert_eq!(folders[0].total_size, 60);
+        assert_eq!(folders[1].file_count, 2);
+        assert_eq!(folders[1].total_size, 45);
+    }
+
+    #[test]
+    fn test_priorities_download_only_selected_folder() {
+        let files = discography();
+        let wanted: Vec<PathBuf> =
+            files_in_folders(&files, &[PathBuf::from("Disco/2003 - Second Album")])
+                .into_iter()
+                .map(|f| f.path)
+                .collect();
+
+        assert_eq!(
+            priorities_for_files(&files, &wanted),
+            vec![
+                FilePriority::DoNotDownload,
+                FilePriority::DoNotDownload,
+                FilePriority::Normal,
+                FilePriority::Normal,
+                FilePriority::DoNotDownload,
+            ]
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! Download health for the import page
+//!
+//! While a torrent downloads, the import page shows how fast it's coming in,
+//! who it's coming from and which parts of it have arrived, so a dead
+//! torrent is obvious before the user waits on it.
+use crate::import::DownloadHealth;
+use crate::torrent::client::{TorrentError, TorrentHandle};
+
+/// Stretches in the piece bar
+pub const PIECE_BAR_LEN: usize = 100;
+
+/// Read the download's current health from the torrent engine
+pub async fn read_health(handle: &TorrentHandle) -> Result<DownloadHealth, TorrentError> {
+    let download_rate = handle.download_rate().await?.max(0) as u64;
+    let total_bytes = handle.total_size().await?.max(0) as u64;
+    let progress = handle.progress().await?.clamp(0.0, 1.0);
+    let downloaded_bytes = (total_bytes as f64 * progress as f64) as u64;
+    Ok(DownloadHealth {
+        download_rate,
+        peers: handle.num_peers().await?.max(0) as u32,
+        seeds: handle.num_seeds().await?.max(0) as u32,
+        downloaded_bytes,
+        total_bytes,
+        eta_secs: eta_secs(total_bytes - downloaded_bytes, download_rate),
+        piece_bar: piece_bar(&handle.pieces().await?, PIECE_BAR_LEN),
+    })
+}
+
+/// Seconds until `remaining_bytes` arrive at the current rate. None when
+/// nothing is arriving.
+pub fn eta_secs(remaining_bytes: u64, download_rate: u64) -> Option<u64> {
+    if remaining_bytes == 0 {
+        return Some(0);
+    }
+    if download_rate == 0 {
+        return None;
+    }
+    Some(remaining_bytes.div_ceil(download_rate))
+}
+
+/// Squeeze the pieces into at most `len` stretches, each the share of its
+/// pieces that are downloaded
+pub fn piece_bar(pieces: &[bool], len: usize) -> Vec<f32> {
+    if pieces.is_empty() || len == 0 {
+        return Vec::new();
+    }
+    let len = len.min(pieces.len());
+    (0..len)
+        .map(|i| {
+            let start = i * pieces.len() / len;
+            let end = (i + 1) * pieces.len() / len;
+            let have = pieces[start..end].iter().filter(|&&have| have).count();
+            have as f32 / (end - start) as f32
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eta_secs() {
+        assert_eq!(eta_secs(1000, 300), Some(4));
+        assert_eq!(eta_secs(1000, 0), None);
+        assert_eq!(eta_secs(0, 0), Some(0));
+    }
+
+    #[test]
+    fn test_piece_bar() {
+        let pieces = [true, true, false, false, true, false];
+        assert_eq!(piece_bar(&pieces, 3), vec![1.0, 0.0, 0.5]);
+        assert_eq!(piece_bar(&pieces, 2), vec![2.0 / 3.0, 1.0 / 3.0]);
+        // Fewer pieces than stretches: one stretch per piece
+        assert_eq!(piece_bar(&[true, false], 100), vec![1.0, 0.0]);
+        assert!(piece_bar(&[], 100).is_empty());
+    }
+}
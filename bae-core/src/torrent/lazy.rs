@@ -1,9 +1,10 @@
-use crate::cache::CacheManager;
 use crate::db::Database;
 use crate::torrent::client::TorrentClientOptions;
 use crate::torrent::manager::{
     start_torrent_manager, start_torrent_manager_noop, TorrentManagerHandle,
 };
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, OnceLock};
 use tracing::info;
 
@@ -13,13 +14,15 @@ use tracing::info;
 #[derive(Clone)]
 pub struct LazyTorrentManager {
     inner: Arc<LazyTorrentManagerInner>,
+    /// Whether torrent traffic is paused, applied when the session starts
+    paused: Arc<AtomicBool>,
 }
 
 enum LazyTorrentManagerInner {
     /// Lazy initialization with real torrent functionality
     Lazy {
         handle: OnceLock<TorrentManagerHandle>,
-        cache_manager: CacheManager,
+        seed_dir: PathBuf,
         database: Database,
         options: TorrentClientOptions,
     },
@@ -29,19 +32,17 @@ enum LazyTorrentManagerInner {
 
 impl LazyTorrentManager {
     /// Create a new lazy torrent manager with the given dependencies.
+    /// Finished downloads kept for seeding go in `seed_dir`.
     /// Does NOT start the libtorrent session yet.
-    pub fn new(
-        cache_manager: CacheManager,
-        database: Database,
-        options: TorrentClientOptions,
-    ) -> Self {
+    pub fn new(seed_dir: PathBuf, database: Database, options: TorrentClientOptions) -> Self {
         Self {
             inner: Arc::new(LazyTorrentManagerInner::Lazy {
                 handle: OnceLock::new(),
-                cache_manager,
+                seed_dir,
                 database,
                 options,
             }),
+            paused: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -52,6 +53,7 @@ impl LazyTorrentManager {
             inner: Arc::new(LazyTorrentManagerInner::Noop(start_torrent_manager_noop(
                 runtime_handle,
             ))),
+            paused: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -61,15 +63,69 @@ impl LazyTorrentManager {
         match &*self.inner {
             LazyTorrentManagerInner::Lazy {
                 handle,
-                cache_manager,
+                seed_dir,
                 database,
                 options,
                 ..
             } => handle.get_or_init(|| {
                 info!("Initializing torrent manager (first access)...");
-                start_torrent_manager(cache_manager.clone(), database.clone(), options.clone())
+                let handle =
+                    start_torrent_manager(seed_dir.clone(), database.clone(), options.clone());
+                if self.paused.load(Ordering::SeqCst) {
+                    handle.set_paused(true);
+                }
+                handle
             }),
             LazyTorrentManagerInner::Noop(handle) => handle,
         }
     }
+
+    /// Whether the libtorrent session has been started
+    pub fn is_started(&self) -> bool {
+        match &*self.inner {
+            LazyTorrentManagerInner::Lazy { handle, .. } => handle.get().is_some(),
+            LazyTorrentManagerInner::Noop(_) => true,
+        }
+    }
+
+    /// Pause or resume all torrent traffic. Doesn't start the session; if it
+    /// hasn't started yet, it starts in this state.
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+        match &*self.inner {
+            LazyTorrentManagerInner::Lazy { handle, .. } => {
+                if let Some(handle) = handle.get() {
+                    handle.set_paused(paused);
+                }
+            }
+            LazyTorrentManagerInner::Noop(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pausing_does_not_start_the_session() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let database = Database::new(dir.path().join("test.db").to_str().unwrap())
+            .await
+            .unwrap();
+        let manager = LazyTorrentManager::new(
+            dir.path().join("seeding"),
+            database,
+            TorrentClientOptions::default(),
+        );
+
+        manager.set_paused(true);
+        assert!(!manager.is_started());
+        // Remembered for when the session does start
+        assert!(manager.paused.load(Ordering::SeqCst));
+
+        manager.set_paused(false);
+        assert!(!manager.is_started());
+        assert!(!manager.paused.load(Ordering::SeqCst));
+    }
 }
@@ -1,9 +1,13 @@
-use crate::cache::CacheManager;
-use crate::db::{Database, DbTorrent, DbTorrentPieceMapping};
+use crate::db::{Database, DbTorrent};
 use crate::import::{FolderMetadata, TorrentFileMetadata, TorrentSource};
 use crate::torrent::client::{TorrentClient, TorrentClientOptions, TorrentError, TorrentHandle};
 use crate::torrent::progress::{TorrentProgress, TorrentProgressHandle};
-use crate::torrent::{BaeStorage, TorrentPieceMapper};
+use crate::torrent::seeding::{
+    metainfo_path, move_payload, seed_goal_reached, seed_path_for, SEED_STATS_INTERVAL,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Instant;
 use thiserror::Error;
 use tokio::sync::{mpsc, oneshot};
 use tracing::{info, warn};
@@ -11,12 +15,14 @@ use tracing::{info, warn};
 pub enum SeederError {
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
-    #[error("Cache error: {0}")]
-    Cache(#[from] crate::cache::CacheError),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
     #[error("Torrent error: {0}")]
     Torrent(#[from] TorrentError),
-    #[error("Piece mapping error: {0}")]
-    PieceMapping(String),
+    #[error("Torrent metadata error: {0}")]
+    Metainfo(String),
+    #[error("The original download isn't kept")]
+    NotKept,
 }
 /// Complete torrent information for import preparation
 #[derive(Debug, Clone)]
@@ -52,6 +58,23 @@ pub enum TorrentManagerCommand {
         release_id: String,
         response_tx: oneshot::Sender<Result<(), SeederError>>,
     },
+    /// Keep a finished download and seed it
+    KeepSeeding {
+        release_id: String,
+        handle: TorrentHandle,
+        source: TorrentSource,
+        payload: PathBuf,
+        response_tx: oneshot::Sender<Result<(), SeederError>>,
+    },
+    /// Stop seeding and delete the kept download
+    RemoveSeed {
+        release_id: String,
+        response_tx: oneshot::Sender<Result<(), SeederError>>,
+    },
+    /// Seed again whatever was seeding when the app last closed
+    ResumeSeeding,
+    /// Pause or resume all torrent traffic, downloads and seeding alike
+    SetPaused { paused: bool },
 }
 /// Handle to the torrent manager service for sending commands
 #[derive(Clone)]
@@ -129,6 +152,65 @@ impl TorrentManagerHandle {
             ))
         })?
     }
+    /// Keep a finished download to seed from instead of deleting it. The
+    /// download moves from `payload` into the seed directory; the handle is
+    /// taken off the download session either way.
+    pub async fn keep_seeding(
+        &self,
+        release_id: String,
+        handle: TorrentHandle,
+        source: TorrentSource,
+        payload: PathBuf,
+    ) -> Result<(), SeederError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(TorrentManagerCommand::KeepSeeding {
+                release_id,
+                handle,
+                source,
+                payload,
+                response_tx: tx,
+            })
+            .map_err(|_| {
+                SeederError::Torrent(TorrentError::Libtorrent(
+                    "TorrentManager channel closed".to_string(),
+                ))
+            })?;
+        rx.await.map_err(|_| {
+            SeederError::Torrent(TorrentError::Libtorrent(
+                "TorrentManager response channel closed".to_string(),
+            ))
+        })?
+    }
+    /// Stop seeding a release and delete its kept download
+    pub async fn remove_seed(&self, release_id: String) -> Result<(), SeederError> {
+        let (tx, rx) = oneshot::channel();
+        self.command_tx
+            .send(TorrentManagerCommand::RemoveSeed {
+                release_id,
+                response_tx: tx,
+            })
+            .map_err(|_| {
+                SeederError::Torrent(TorrentError::Libtorrent(
+                    "TorrentManager channel closed".to_string(),
+                ))
+            })?;
+        rx.await.map_err(|_| {
+            SeederError::Torrent(TorrentError::Libtorrent(
+                "TorrentManager response channel closed".to_string(),
+            ))
+        })?
+    }
+    /// Seed again every torrent that was seeding when the app last closed
+    pub fn resume_seeding(&self) {
+        let _ = self.command_tx.send(TorrentManagerCommand::ResumeSeeding);
+    }
+    /// Pause or resume all torrent traffic
+    pub fn set_paused(&self, paused: bool) {
+        let _ = self
+            .command_tx
+            .send(TorrentManagerCommand::SetPaused { paused });
+    }
     /// Prepare a torrent for import: add, wait for metadata, query all info, detect metadata
     pub async fn prepare_import_torrent(
         &self,
@@ -169,20 +251,43 @@ struct TorrentManager {
     command_rx: mpsc::UnboundedReceiver<TorrentManagerCommand>,
     download_client: TorrentClient,
     seeding_client: TorrentClient,
-    cache_manager: CacheManager,
+    /// Where finished downloads are kept for seeding
+    seed_dir: PathBuf,
     database: Database,
     progress_tx: mpsc::UnboundedSender<TorrentProgress>,
+    /// Torrents in the seeding session, by torrent ID
+    seeding: HashMap<String, SeedingTorrent>,
 }
-/// Start the torrent manager service
+/// A torrent in the seeding session
+struct SeedingTorrent {
+    release_id: String,
+    handle: TorrentHandle,
+    /// Bytes uploaded in earlier sessions
+    uploaded_before: i64,
+    /// Seconds seeded in earlier sessions
+    seeded_before: i64,
+    started: Instant,
+}
+impl SeedingTorrent {
+    /// Bytes uploaded and seconds seeded, this session included
+    async fn totals(&self) -> (i64, i64) {
+        let uploaded = self.handle.uploaded_bytes().await.unwrap_or(0);
+        (
+            self.uploaded_before + uploaded,
+            self.seeded_before + self.started.elapsed().as_secs() as i64,
+        )
+    }
+}
+/// Start the torrent manager service. Finished downloads kept for seeding
+/// go in `seed_dir`.
 /// Returns a handle for sending commands to the manager
 pub fn start_torrent_manager(
-    cache_manager: CacheManager,
+    seed_dir: PathBuf,
     database: Database,
     options: TorrentClientOptions,
 ) -> TorrentManagerHandle {
     let (command_tx, command_rx) = mpsc::unbounded_channel();
     let (progress_tx, progress_rx) = mpsc::unbounded_channel();
-    let cache_manager_for_worker = cache_manager.clone();
     let database_for_worker = database.clone();
     let progress_tx_for_worker = progress_tx.clone();
     let progress_rx_for_handle = progress_rx;
@@ -208,17 +313,20 @@ pub fn start_torrent_manager(
                 TorrentClient::new_with_default_storage(rt_handle.clone(), options.clone())
                     .expect("Failed to create download torrent client");
             info!("TorrentManager: Download client created successfully");
-            info!("TorrentManager: Creating seeding client (custom storage)...");
-            let seeding_client = TorrentClient::new_with_bae_storage(rt_handle.clone(), options)
-                .expect("Failed to create seeding torrent client");
+            // Seeds kept downloads straight from disk
+            info!("TorrentManager: Creating seeding client (default storage)...");
+            let seeding_client =
+                TorrentClient::new_with_default_storage(rt_handle.clone(), options)
+                    .expect("Failed to create seeding torrent client");
             info!("TorrentManager: Seeding client created successfully");
             let service = TorrentManager {
                 command_rx,
                 download_client,
                 seeding_client,
-                cache_manager: cache_manager_for_worker,
+                seed_dir,
                 database: database_for_worker,
                 progress_tx: progress_tx_for_worker,
+                seeding: HashMap::new(),
             };
             service.run_manager_worker().await;
         });
@@ -250,31 +358,94 @@ pub fn start_torrent_manager_noop(runtime_handle: tokio::runtime::Handle) -> Tor
 impl TorrentManager {
     async fn run_manager_worker(mut self) {
         info!("TorrentManager worker started");
+        let mut seed_stats = tokio::time::interval(SEED_STATS_INTERVAL);
         loop {
             tokio::select! {
-                cmd = self.command_rx.recv() => { match cmd {
-                Some(TorrentManagerCommand::AddTorrent { source, response_tx, }) => { let
-                result = match source { TorrentSource::File(path) => { self
-                .download_client.add_torrent_file(& path). await }
-                TorrentSource::MagnetLink(magnet) => { self.download_client
-                .add_magnet_link(& magnet). await } }; let _ = response_tx.send(result);
-                } Some(TorrentManagerCommand::RemoveTorrent { handle, delete_files,
-                response_tx, }) => { let result = if delete_files { self.download_client
-                .remove_torrent_and_delete_data(& handle). await } else { self
-                .download_client.remove_torrent_and_keep_data(& handle). await }; let _ =
-                response_tx.send(result); }
-                Some(TorrentManagerCommand::PrepareImportTorrent { source, response_tx,
-                }) => { let result = self.prepare_import_torrent_handler(source). await;
-                let _ = response_tx.send(result); }
-                Some(TorrentManagerCommand::StartSeeding { release_id, response_tx, }) =>
-                { let result = self.start_seeding(& release_id). await; let _ =
-                response_tx.send(result); } Some(TorrentManagerCommand::StopSeeding {
-                release_id, response_tx, }) => { let result = self.stop_seeding(&
-                release_id). await; let _ = response_tx.send(result); } None => {
-                info!("TorrentManager command channel closed"); break; } } } _ =
-                tokio::time::sleep(tokio::time::Duration::from_millis(100)) => { let
-                alerts = self.download_client.pop_alerts(). await; for alert in alerts {
-                self.process_alert(alert). await; } }
+                cmd = self.command_rx.recv() => match cmd {
+                    Some(TorrentManagerCommand::AddTorrent { source, response_tx }) => {
+                        let result = match source {
+                            TorrentSource::File(path) => {
+                                self.download_client.add_torrent_file(&path).await
+                            }
+                            TorrentSource::MagnetLink(magnet) => {
+                                self.download_client.add_magnet_link(&magnet).await
+                            }
+                        };
+                        let _ = response_tx.send(result);
+                    }
+                    Some(TorrentManagerCommand::RemoveTorrent {
+                        handle,
+                        delete_files,
+                        response_tx,
+                    }) => {
+                        let result = if delete_files {
+                            self.download_client
+                                .remove_torrent_and_delete_data(&handle)
+                                .await
+                        } else {
+                            self.download_client
+                                .remove_torrent_and_keep_data(&handle)
+                                .await
+                        };
+                        let _ = response_tx.send(result);
+                    }
+                    Some(TorrentManagerCommand::PrepareImportTorrent { source, response_tx }) => {
+                        let result = self.prepare_import_torrent_handler(source).await;
+                        let _ = response_tx.send(result);
+                    }
+                    Some(TorrentManagerCommand::StartSeeding {
+                        release_id,
+                        response_tx,
+                    }) => {
+                        let result = self.start_seeding(&release_id).await;
+                        let _ = response_tx.send(result);
+                    }
+                    Some(TorrentManagerCommand::StopSeeding {
+                        release_id,
+                        response_tx,
+                    }) => {
+                        let result = self.stop_seeding(&release_id).await;
+                        let _ = response_tx.send(result);
+                    }
+                    Some(TorrentManagerCommand::KeepSeeding {
+                        release_id,
+                        handle,
+                        source,
+                        payload,
+                        response_tx,
+                    }) => {
+                        let result = self
+                            .keep_seeding(&release_id, handle, source, payload)
+                            .await;
+                        let _ = response_tx.send(result);
+                    }
+                    Some(TorrentManagerCommand::RemoveSeed {
+                        release_id,
+                        response_tx,
+                    }) => {
+                        let result = self.remove_seed(&release_id).await;
+                        let _ = response_tx.send(result);
+                    }
+                    Some(TorrentManagerCommand::ResumeSeeding) => {
+                        self.resume_seeding().await;
+                    }
+                    Some(TorrentManagerCommand::SetPaused { paused }) => {
+                        self.set_paused(paused).await;
+                    }
+                    None => {
+                        info!("TorrentManager command channel closed");
+                        break;
+                    }
+                },
+                _ = seed_stats.tick() => {
+                    self.record_seed_stats().await;
+                }
+                _ = tokio::time::sleep(tokio::time::Duration::from_millis(100)) => {
+                    let alerts = self.download_client.pop_alerts().await;
+                    for alert in alerts {
+                        self.process_alert(alert).await;
+                    }
+                }
             }
         }
         info!("TorrentManager worker stopped");
@@ -340,94 +511,200 @@ impl TorrentManager {
             _ => {}
         }
     }
-    /// Start seeding a torrent for a release
-    async fn start_seeding(&self, release_id: &str) -> Result<(), SeederError> {
+    async fn set_paused(&self, paused: bool) {
+        info!(
+            "{} torrent traffic",
+            if paused { "Pausing" } else { "Resuming" }
+        );
+        for client in [&self.download_client, &self.seeding_client] {
+            if let Err(e) = client.set_paused(paused).await {
+                warn!("Failed to set torrent session paused={}: {}", paused, e);
+            }
+        }
+    }
+    /// Start seeding a release from its kept download
+    async fn start_seeding(&mut self, release_id: &str) -> Result<(), SeederError> {
         let torrent = self.get_torrent_by_release(release_id).await?;
+        if self.seeding.contains_key(&torrent.id) {
+            return Ok(());
+        }
+        let seed_path = torrent
+            .seed_path
+            .as_ref()
+            .map(PathBuf::from)
+            .filter(|path| path.exists())
+            .ok_or(SeederError::NotKept)?;
         info!(
-            "Starting seeding for release {} (torrent: {})",
-            release_id, torrent.info_hash
+            "Starting seeding for release {} (torrent: {}) from {:?}",
+            release_id, torrent.info_hash, seed_path
         );
-        let magnet_link = torrent.magnet_link.as_ref().ok_or_else(|| {
-            SeederError::PieceMapping("Torrent has no magnet link stored".to_string())
-        })?;
-        use crate::torrent::ffi::{parse_magnet_uri, set_seed_mode};
-        let temp_path = std::env::temp_dir().to_string_lossy().to_string();
-        let mut params = parse_magnet_uri(magnet_link, &temp_path);
+        use crate::torrent::ffi::{load_torrent_file, parse_magnet_uri, set_seed_mode};
+        let save_path = seed_path.to_string_lossy().to_string();
+        let metainfo = metainfo_path(&seed_path);
+        let mut params = if metainfo.exists() {
+            load_torrent_file(&metainfo.to_string_lossy(), &save_path)
+        } else {
+            let magnet_link = torrent.magnet_link.as_ref().ok_or_else(|| {
+                SeederError::Metainfo("No .torrent file or magnet link kept".to_string())
+            })?;
+            parse_magnet_uri(magnet_link, &save_path)
+        };
         if params.is_null() {
-            return Err(SeederError::Torrent(TorrentError::InvalidTorrent(
-                "Failed to parse magnet URI".to_string(),
-            )));
+            return Err(SeederError::Metainfo(
+                "Failed to load torrent metadata".to_string(),
+            ));
         }
+        // The payload finished downloading, so skip checking every piece
         unsafe {
             if let Some(pinned_params) = params.as_mut() {
                 let params_ptr = std::pin::Pin::get_unchecked_mut(pinned_params) as *mut _;
                 set_seed_mode(params_ptr, true);
             }
         }
-        let torrent_handle = self
-            .seeding_client
-            .add_torrent_with_params(params)
-            .await
-            .map_err(SeederError::Torrent)?;
-        torrent_handle
-            .wait_for_metadata()
-            .await
-            .map_err(SeederError::Torrent)?;
-        let storage_index = torrent_handle
-            .storage_index()
-            .await
-            .map_err(SeederError::Torrent)?;
-        // TODO: Seeding functionality needs rework after chunking removal
-        let default_chunk_size = 1024 * 1024; // 1MB default for piece mapper
-        let piece_mapper = TorrentPieceMapper::new(
-            torrent.piece_length as usize,
-            default_chunk_size,
-            torrent.num_pieces as usize,
-            torrent.total_size_bytes as usize,
-        );
-        let bae_storage = BaeStorage::new(
-            self.cache_manager.clone(),
-            self.database.clone(),
-            piece_mapper,
-            torrent.info_hash.clone(),
+        let handle = self.seeding_client.add_torrent_with_params(params).await?;
+        self.seeding.insert(
+            torrent.id.clone(),
+            SeedingTorrent {
+                release_id: release_id.to_string(),
+                handle,
+                uploaded_before: torrent.uploaded_bytes,
+                seeded_before: torrent.seeding_secs,
+                started: Instant::now(),
+            },
         );
-        self.seeding_client
-            .register_storage(storage_index, torrent.info_hash.clone(), bae_storage)
-            .await;
-        let piece_mappings = self.get_piece_mappings(&torrent.id).await?;
-        let mut chunk_ids = Vec::new();
-        for mapping in &piece_mappings {
-            let ids: Vec<String> = serde_json::from_str(&mapping.chunk_ids).map_err(|e| {
-                SeederError::PieceMapping(format!("Failed to parse chunk IDs: {}", e))
-            })?;
-            chunk_ids.extend(ids);
-        }
-        self.cache_manager.pin_all(&chunk_ids).await;
-        info!("Pinned {} chunks for seeding", chunk_ids.len());
         self.mark_torrent_seeding(&torrent.id, true).await?;
         info!("Successfully started seeding for release {}", release_id);
         Ok(())
     }
-    /// Stop seeding a torrent
-    async fn stop_seeding(&self, release_id: &str) -> Result<(), SeederError> {
+    /// Stop seeding a release, keeping its download
+    async fn stop_seeding(&mut self, release_id: &str) -> Result<(), SeederError> {
         let torrent = self.get_torrent_by_release(release_id).await?;
         info!(
             "Stopping seeding for release {} (torrent: {})",
             release_id, torrent.info_hash
         );
-        let piece_mappings = self.get_piece_mappings(&torrent.id).await?;
-        let mut chunk_ids = Vec::new();
-        for mapping in &piece_mappings {
-            let ids: Vec<String> = serde_json::from_str(&mapping.chunk_ids).map_err(|e| {
-                SeederError::PieceMapping(format!("Failed to parse chunk IDs: {}", e))
-            })?;
-            chunk_ids.extend(ids);
+        if let Some(seeding) = self.seeding.remove(&torrent.id) {
+            let (uploaded, seeded) = seeding.totals().await;
+            self.database
+                .update_torrent_seed_stats(&torrent.id, uploaded, seeded)
+                .await?;
+            self.seeding_client
+                .remove_torrent_and_keep_data(&seeding.handle)
+                .await?;
         }
-        self.cache_manager.unpin_all(&chunk_ids).await;
-        info!("Unpinned {} chunks", chunk_ids.len());
         self.mark_torrent_seeding(&torrent.id, false).await?;
         Ok(())
     }
+    /// Move a finished download into the seed directory and seed it
+    async fn keep_seeding(
+        &mut self,
+        release_id: &str,
+        handle: TorrentHandle,
+        source: TorrentSource,
+        payload: PathBuf,
+    ) -> Result<(), SeederError> {
+        let torrent = self.get_torrent_by_release(release_id).await?;
+        let downloaded = handle.downloaded_bytes().await.unwrap_or(0);
+        self.download_client
+            .remove_torrent_and_keep_data(&handle)
+            .await?;
+
+        let seed_path = seed_path_for(&self.seed_dir, &torrent.info_hash);
+        let file_name = payload.file_name().ok_or_else(|| {
+            SeederError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Download has no file name: {:?}", payload),
+            ))
+        })?;
+        move_payload(&payload, &seed_path.join(file_name)).await?;
+        if let TorrentSource::File(path) = &source {
+            if let Err(e) = tokio::fs::copy(path, metainfo_path(&seed_path)).await {
+                warn!("Failed to keep .torrent file {:?}: {}", path, e);
+            }
+        }
+        info!(
+            "Kept download for release {} in {:?}",
+            release_id, seed_path
+        );
+
+        self.database
+            .update_torrent_seed_path(&torrent.id, Some(&seed_path.to_string_lossy()), downloaded)
+            .await?;
+        self.start_seeding(release_id).await
+    }
+    /// Stop seeding a release and delete its kept download
+    async fn remove_seed(&mut self, release_id: &str) -> Result<(), SeederError> {
+        self.stop_seeding(release_id).await?;
+        let torrent = self.get_torrent_by_release(release_id).await?;
+        if let Some(seed_path) = torrent.seed_path.as_ref().map(PathBuf::from) {
+            if seed_path.exists() {
+                tokio::fs::remove_dir_all(&seed_path).await?;
+            }
+            let metainfo = metainfo_path(&seed_path);
+            if metainfo.exists() {
+                tokio::fs::remove_file(&metainfo).await?;
+            }
+            info!("Deleted kept download {:?}", seed_path);
+        }
+        self.database
+            .update_torrent_seed_path(&torrent.id, None, torrent.downloaded_bytes)
+            .await?;
+        Ok(())
+    }
+    /// Seed again every torrent that was seeding when the app last closed
+    async fn resume_seeding(&mut self) {
+        let torrents = match self.database.get_seeding_torrents().await {
+            Ok(torrents) => torrents,
+            Err(e) => {
+                warn!("Failed to load seeding torrents: {}", e);
+                return;
+            }
+        };
+        for torrent in torrents {
+            match self.start_seeding(&torrent.release_id).await {
+                Ok(()) => {}
+                Err(SeederError::NotKept) => {
+                    warn!(
+                        "Download for {} is gone, no longer seeding it",
+                        torrent.torrent_name
+                    );
+                    let _ = self.mark_torrent_seeding(&torrent.id, false).await;
+                }
+                Err(e) => warn!("Failed to resume seeding {}: {}", torrent.torrent_name, e),
+            }
+        }
+    }
+    /// Save upload and seeding time totals, stopping torrents that have met
+    /// their goal
+    async fn record_seed_stats(&mut self) {
+        let mut goals_met = Vec::new();
+        for (torrent_id, seeding) in &self.seeding {
+            let (uploaded, seeded) = seeding.totals().await;
+            if let Err(e) = self
+                .database
+                .update_torrent_seed_stats(torrent_id, uploaded, seeded)
+                .await
+            {
+                warn!("Failed to save seeding stats: {}", e);
+                continue;
+            }
+            if let Ok(Some(torrent)) = self
+                .database
+                .get_torrent_by_release(&seeding.release_id)
+                .await
+            {
+                if seed_goal_reached(&torrent) {
+                    goals_met.push(seeding.release_id.clone());
+                }
+            }
+        }
+        for release_id in goals_met {
+            info!("Seeding goal met for release {}", release_id);
+            if let Err(e) = self.stop_seeding(&release_id).await {
+                warn!("Failed to stop seeding release {}: {}", release_id, e);
+            }
+        }
+    }
     /// Get torrent by release ID
     async fn get_torrent_by_release(&self, release_id: &str) -> Result<DbTorrent, SeederError> {
         self.database
@@ -435,13 +712,6 @@ impl TorrentManager {
             .await?
             .ok_or_else(|| SeederError::Database(sqlx::Error::RowNotFound))
     }
-    /// Get piece mappings for a torrent
-    async fn get_piece_mappings(
-        &self,
-        torrent_id: &str,
-    ) -> Result<Vec<DbTorrentPieceMapping>, SeederError> {
-        Ok(self.database.get_torrent_piece_mappings(torrent_id).await?)
-    }
     /// Mark torrent as seeding or not
     async fn mark_torrent_seeding(
         &self,
@@ -547,3 +817,32 @@ impl TorrentManager {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_set_paused_sends_command_to_worker() {
+        let (command_tx, mut command_rx) = mpsc::unbounded_channel();
+        let (_progress_tx, progress_rx) = mpsc::unbounded_channel();
+        let handle = TorrentManagerHandle {
+            command_tx,
+            progress_handle: TorrentProgressHandle::new(
+                progress_rx,
+                tokio::runtime::Handle::current(),
+            ),
+        };
+
+        handle.set_paused(true);
+        handle.set_paused(false);
+        assert!(matches!(
+            command_rx.recv().await,
+            Some(TorrentManagerCommand::SetPaused { paused: true })
+        ));
+        assert!(matches!(
+            command_rx.recv().await,
+            Some(TorrentManagerCommand::SetPaused { paused: false })
+        ));
+    }
+}
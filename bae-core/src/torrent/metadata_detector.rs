@@ -141,7 +141,9 @@ pub async fn detect_metadata_from_torrent_file(
     let torrent_name = handle.name().await?;
     let save_dir = temp_path.join(&torrent_name);
     if save_dir.exists() {
-        match detect_metadata(save_dir.clone()) {
+        // Folder patterns describe the library's layout on disk, not
+        // torrent names
+        match detect_metadata(save_dir.clone(), &[]) {
             Ok(metadata) => {
                 info!("Successfully detected metadata from torrent CUE/log files");
                 Ok(Some(metadata))
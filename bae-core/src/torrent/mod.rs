@@ -1,12 +1,16 @@
 pub mod client;
 pub mod ffi;
+pub mod file_selection;
+pub mod health;
 pub mod lazy;
 pub mod manager;
 pub mod metadata_detector;
 pub mod parser;
 pub mod piece_mapper;
 pub mod progress;
+pub mod seeding;
 pub mod storage;
+pub use file_selection::{detect_album_folders, TorrentAlbumFolder};
 pub use lazy::LazyTorrentManager;
 pub use metadata_detector::detect_metadata_from_torrent_file;
 pub use parser::parse_torrent_info;
@@ -0,0 +1,141 @@
+//! Seeding finished torrent downloads
+//!
+//! After a torrent import the original download is moved out of the temp
+//! directory into the seed directory and seeded from there from disk. The
+//! library's copy may be re-encoded or encrypted in storage, so only the
+//! original payload still hashes to the torrent's pieces.
+//!
+//! Upload and seeding time are added up across sessions, so a private
+//! tracker's ratio and seed-time goals carry over restarts.
+use crate::db::DbTorrent;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How often upload and seeding time are saved and goals checked
+pub const SEED_STATS_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Bytes uploaded per byte downloaded. A torrent that downloaded nothing
+/// (it was added from a kept payload) counts against its size.
+pub fn upload_ratio(uploaded_bytes: i64, downloaded_bytes: i64, total_size_bytes: i64) -> f64 {
+    let basis = if downloaded_bytes > 0 {
+        downloaded_bytes
+    } else {
+        total_size_bytes
+    };
+    if basis <= 0 {
+        return 0.0;
+    }
+    uploaded_bytes as f64 / basis as f64
+}
+
+/// Whether a torrent has met its ratio or seed-time goal
+pub fn seed_goal_reached(torrent: &DbTorrent) -> bool {
+    let ratio = upload_ratio(
+        torrent.uploaded_bytes,
+        torrent.downloaded_bytes,
+        torrent.total_size_bytes,
+    );
+    let ratio_met = torrent.seed_ratio_goal.is_some_and(|goal| ratio >= goal);
+    let time_met = torrent
+        .seed_time_goal_secs
+        .is_some_and(|goal| torrent.seeding_secs >= goal);
+    ratio_met || time_met
+}
+
+/// Directory a torrent's original download is kept in
+pub fn seed_path_for(seed_dir: &Path, info_hash: &str) -> PathBuf {
+    seed_dir.join(info_hash)
+}
+
+/// Copy of the .torrent file kept beside the payload, so seeding can start
+/// again without fetching metadata from peers
+pub fn metainfo_path(seed_path: &Path) -> PathBuf {
+    seed_path.with_extension("torrent")
+}
+
+/// Move a file or directory, copying it across when it's on another
+/// filesystem (the temp directory often is)
+pub async fn move_payload(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    if tokio::fs::rename(from, to).await.is_ok() {
+        return Ok(());
+    }
+    let (from, to) = (from.to_path_buf(), to.to_path_buf());
+    tokio::task::spawn_blocking(move || {
+        copy_recursive(&from, &to)?;
+        if from.is_dir() {
+            std::fs::remove_dir_all(&from)
+        } else {
+            std::fs::remove_file(&from)
+        }
+    })
+    .await
+    .map_err(io::Error::other)?
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> io::Result<()> {
+    if !from.is_dir() {
+        std::fs::copy(from, to)?;
+        return Ok(());
+    }
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn torrent(uploaded: i64, downloaded: i64, seeding_secs: i64) -> DbTorrent {
+        let mut torrent = DbTorrent::new("release", "hash", None, "name", 1000, 256, 4);
+        torrent.uploaded_bytes = uploaded;
+        torrent.downloaded_bytes = downloaded;
+        torrent.seeding_secs = seeding_secs;
+        torrent
+    }
+
+    #[test]
+    fn test_ratio_falls_back_to_size() {
+        assert_eq!(upload_ratio(500, 250, 1000), 2.0);
+        assert_eq!(upload_ratio(500, 0, 1000), 0.5);
+        assert_eq!(upload_ratio(500, 0, 0), 0.0);
+    }
+
+    #[test]
+    fn test_goal_reached_on_ratio_or_time() {
+        let mut t = torrent(900, 1000, 60);
+        assert!(!seed_goal_reached(&t));
+
+        t.seed_ratio_goal = Some(1.0);
+        assert!(!seed_goal_reached(&t));
+        t.uploaded_bytes = 1000;
+        assert!(seed_goal_reached(&t));
+
+        let mut t = torrent(0, 1000, 3600);
+        t.seed_time_goal_secs = Some(7200);
+        assert!(!seed_goal_reached(&t));
+        t.seeding_secs = 7200;
+        assert!(seed_goal_reached(&t));
+    }
+
+    #[tokio::test]
+    async fn test_move_payload_directory() {
+        let temp = tempfile::tempdir().unwrap();
+        let from = temp.path().join("download/Album");
+        std::fs::create_dir_all(from.join("CD1")).unwrap();
+        std::fs::write(from.join("CD1/01.flac"), b"audio").unwrap();
+
+        let to = seed_path_for(&temp.path().join("seeding"), "abc").join("Album");
+        move_payload(&from, &to).await.unwrap();
+
+        assert!(!from.exists());
+        assert_eq!(std::fs::read(to.join("CD1/01.flac")).unwrap(), b"audio");
+    }
+}
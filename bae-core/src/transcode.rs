@@ -0,0 +1,253 @@
+//! Transcoding for streaming clients on slow links
+//!
+//! The Subsonic server hands out stored FLAC as is unless a client asks for
+//! something smaller, in which case it's re-encoded to Opus. Encodes run on a
+//! few blocking threads at most, so a client skipping through an album can't
+//! take every core, and finished encodes are kept in an LRU cache bounded by
+//! size, since clients ask for the same track again when they retry or seek.
+
+use crate::audio_codec;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
+use tracing::debug;
+
+/// Opus bitrates offered in settings, in kbps
+pub const OPUS_BITRATES_KBPS: [u32; 6] = [64, 96, 128, 160, 192, 256];
+
+pub const DEFAULT_OPUS_BITRATE_KBPS: u32 = 128;
+
+/// Bitrates a client can ask for are held to this range, in kbps
+const MIN_OPUS_BITRATE_KBPS: u32 = 32;
+const MAX_OPUS_BITRATE_KBPS: u32 = 256;
+
+/// How much encoded audio the cache keeps
+const CACHE_BYTES: usize = 256 * 1024 * 1024;
+
+/// What a client is streamed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamFormat {
+    /// The stored file, untouched
+    Original,
+    /// Opus in Ogg
+    Opus { bitrate_kbps: u32 },
+}
+
+impl StreamFormat {
+    /// Pick a format from a Subsonic client's `format` and `maxBitRate`
+    /// (kbps, 0 for no limit). Asking for `opus` or capping the bitrate gets
+    /// Opus, at the cap if there is one and `default_kbps` otherwise. Asking
+    /// for `raw`, or for nothing, gets the original.
+    pub fn negotiate(
+        format: Option<&str>,
+        max_bitrate_kbps: Option<u32>,
+        default_kbps: u32,
+    ) -> Self {
+        let cap = max_bitrate_kbps.filter(|&kbps| kbps > 0);
+        let opus = |kbps: u32| StreamFormat::Opus {
+            bitrate_kbps: kbps.clamp(MIN_OPUS_BITRATE_KBPS, MAX_OPUS_BITRATE_KBPS),
+        };
+        match format.map(str::to_ascii_lowercase).as_deref() {
+            Some("raw") | Some("flac") => StreamFormat::Original,
+            Some("opus") | Some("ogg") => opus(cap.unwrap_or(default_kbps)),
+            _ => cap.map_or(StreamFormat::Original, opus),
+        }
+    }
+}
+
+/// Identifies one encode in the cache
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TranscodeKey {
+    pub track_id: String,
+    pub bitrate_kbps: u32,
+    /// Where the stream starts, for clients resuming partway through
+    pub offset_ms: u64,
+}
+
+/// Shared transcoder: worker pool and cache
+#[derive(Clone)]
+pub struct Transcoder {
+    /// Bitrate for clients that ask for Opus without saying how much
+    default_bitrate_kbps: u32,
+    workers: Arc<Semaphore>,
+    cache: Arc<Mutex<TranscodeCache>>,
+}
+
+impl Transcoder {
+    pub fn new(default_bitrate_kbps: u32) -> Self {
+        // Leave half the cores for playback and the UI
+        let workers = std::thread::available_parallelism()
+            .map_or(1, |n| n.get() / 2)
+            .max(1);
+        Transcoder {
+            default_bitrate_kbps,
+            workers: Arc::new(Semaphore::new(workers)),
+            cache: Arc::new(Mutex::new(TranscodeCache::new(CACHE_BYTES))),
+        }
+    }
+
+    pub fn default_bitrate_kbps(&self) -> u32 {
+        self.default_bitrate_kbps
+    }
+
+    /// Opus for `key`, from the cache or encoded from the FLAC `source`
+    /// yields. The source is only read on a cache miss.
+    pub async fn opus<F>(
+        &self,
+        key: TranscodeKey,
+        source: F,
+    ) -> Result<Arc<Vec<u8>>, Box<dyn std::error::Error + Send + Sync>>
+    where
+        F: Future<Output = Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>>>,
+    {
+        let cached = self.cache.lock().unwrap().get(&key);
+        if let Some(encoded) = cached {
+            return Ok(encoded);
+        }
+        let audio_data = source.await?;
+        let _permit = self.workers.acquire().await?;
+        // The same encode may have finished while this one waited its turn
+        let cached = self.cache.lock().unwrap().get(&key);
+        if let Some(encoded) = cached {
+            return Ok(encoded);
+        }
+
+        debug!(
+            "Transcoding track {} to Opus at {} kbps",
+            key.track_id, key.bitrate_kbps
+        );
+        let bitrate_kbps = key.bitrate_kbps;
+        let start_ms = Some(key.offset_ms).filter(|&ms| ms > 0);
+        let encoded = tokio::task::spawn_blocking(move || {
+            let decoded = audio_codec::decode_audio(&audio_data, start_ms, None)?;
+            audio_codec::encode_to_opus(
+                &decoded.samples,
+                decoded.sample_rate,
+                decoded.channels,
+                decoded.bits_per_sample,
+                bitrate_kbps,
+            )
+        })
+        .await??;
+
+        let encoded = Arc::new(encoded);
+        self.cache.lock().unwrap().insert(key, encoded.clone());
+        Ok(encoded)
+    }
+}
+
+/// Encoded streams up to a byte budget, least recently used dropped first
+struct TranscodeCache {
+    entries: HashMap<TranscodeKey, CacheEntry>,
+    bytes: usize,
+    max_bytes: usize,
+    /// Bumped on every access, to order entries by use
+    clock: u64,
+}
+
+struct CacheEntry {
+    data: Arc<Vec<u8>>,
+    last_used: u64,
+}
+
+impl TranscodeCache {
+    fn new(max_bytes: usize) -> Self {
+        TranscodeCache {
+            entries: HashMap::new(),
+            bytes: 0,
+            max_bytes,
+            clock: 0,
+        }
+    }
+
+    fn get(&mut self, key: &TranscodeKey) -> Option<Arc<Vec<u8>>> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(key)?;
+        entry.last_used = self.clock;
+        Some(entry.data.clone())
+    }
+
+    fn insert(&mut self, key: TranscodeKey, data: Arc<Vec<u8>>) {
+        if data.len() > self.max_bytes {
+            return;
+        }
+        self.clock += 1;
+        self.bytes += data.len();
+        let entry = CacheEntry {
+            data,
+            last_used: self.clock,
+        };
+        if let Some(replaced) = self.entries.insert(key, entry) {
+            self.bytes -= replaced.data.len();
+        }
+        while self.bytes > self.max_bytes {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            if let Some(evicted) = self.entries.remove(&oldest) {
+                self.bytes -= evicted.data.len();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(track_id: &str) -> TranscodeKey {
+        TranscodeKey {
+            track_id: track_id.to_string(),
+            bitrate_kbps: 128,
+            offset_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let negotiate = StreamFormat::negotiate;
+        assert_eq!(negotiate(None, None, 128), StreamFormat::Original);
+        assert_eq!(negotiate(None, Some(0), 128), StreamFormat::Original);
+        assert_eq!(
+            negotiate(Some("raw"), Some(96), 128),
+            StreamFormat::Original
+        );
+        assert_eq!(
+            negotiate(Some("opus"), None, 128),
+            StreamFormat::Opus { bitrate_kbps: 128 }
+        );
+        assert_eq!(
+            negotiate(Some("mp3"), Some(96), 128),
+            StreamFormat::Opus { bitrate_kbps: 96 }
+        );
+        assert_eq!(
+            negotiate(None, Some(320), 128),
+            StreamFormat::Opus { bitrate_kbps: 256 }
+        );
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let mut cache = TranscodeCache::new(10);
+        cache.insert(key("a"), Arc::new(vec![0; 4]));
+        cache.insert(key("b"), Arc::new(vec![0; 4]));
+        assert!(cache.get(&key("a")).is_some());
+
+        cache.insert(key("c"), Arc::new(vec![0; 4]));
+        assert!(cache.get(&key("b")).is_none());
+        assert!(cache.get(&key("a")).is_some());
+        assert!(cache.get(&key("c")).is_some());
+        assert_eq!(cache.bytes, 8);
+
+        // Too big to ever fit
+        cache.insert(key("d"), Arc::new(vec![0; 11]));
+        assert!(cache.get(&key("d")).is_none());
+        assert_eq!(cache.bytes, 8);
+    }
+}
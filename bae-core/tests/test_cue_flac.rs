@@ -55,11 +55,14 @@ async fn test_cue_flac_records_track_positions() {
             import_id,
             discogs_release: Some(discogs_release),
             mb_release: None,
+            manual_release: None,
             folder: album_dir,
             master_year: 2024,
             cover_art_url: None,
             storage_profile_id: None,
             selected_cover_filename: None,
+            excluded_files: Vec::new(),
+            needle_drop_sides: Vec::new(),
         })
         .await
         .expect("send request");
@@ -204,11 +207,14 @@ async fn test_cue_flac_playback_uses_track_positions() {
             import_id,
             discogs_release: Some(discogs_release),
             mb_release: None,
+            manual_release: None,
             folder: album_dir,
             master_year: 2024,
             cover_art_url: None,
             storage_profile_id: None,
             selected_cover_filename: None,
+            excluded_files: Vec::new(),
+            needle_drop_sides: Vec::new(),
         })
         .await
         .expect("send request");
@@ -357,11 +363,14 @@ async fn test_cue_flac_decoded_duration_matches_cue_timing() {
             import_id,
             discogs_release: Some(discogs_release),
             mb_release: None,
+            manual_release: None,
             folder: album_dir,
             master_year: 2024,
             cover_art_url: None,
             storage_profile_id: None,
             selected_cover_filename: None,
+            excluded_files: Vec::new(),
+            needle_drop_sides: Vec::new(),
         })
         .await
         .expect("send request");
@@ -495,11 +504,14 @@ async fn test_cue_flac_byte_ranges_have_no_gaps() {
             import_id,
             discogs_release: Some(discogs_release),
             mb_release: None,
+            manual_release: None,
             folder: album_dir,
             master_year: 2024,
             cover_art_url: None,
             storage_profile_id: None,
             selected_cover_filename: None,
+            excluded_files: Vec::new(),
+            needle_drop_sides: Vec::new(),
         })
         .await
         .expect("send request");
@@ -648,11 +660,14 @@ async fn test_cue_flac_builds_dense_seektable() {
             import_id,
             discogs_release: Some(discogs_release),
             mb_release: None,
+            manual_release: None,
             folder: album_dir.clone(),
             master_year: 2024,
             cover_art_url: None,
             storage_profile_id: None,
             selected_cover_filename: None,
+            excluded_files: Vec::new(),
+            needle_drop_sides: Vec::new(),
         })
         .await
         .expect("send request");
@@ -32,6 +32,11 @@ fn create_test_album() -> DbAlbum {
         is_compilation: false,
         created_at: Utc::now(),
         updated_at: Utc::now(),
+        play_count: 0,
+        last_played_at: None,
+        dominant_color: None,
+        accent_color: None,
+        preferred_release_id: None,
     }
 }
 
@@ -81,7 +86,11 @@ async fn test_delete_album_integration() {
     database.insert_track(&track1).await.unwrap();
     database.insert_track(&track2).await.unwrap();
 
-    library_manager.get().delete_album(&album.id).await.unwrap();
+    library_manager
+        .get()
+        .delete_album(&album.id, false)
+        .await
+        .unwrap();
 
     let album_result = library_manager
         .get()
@@ -118,7 +127,7 @@ async fn test_delete_release_integration() {
 
     library_manager
         .get()
-        .delete_release(&release1.id)
+        .delete_release(&release1.id, false)
         .await
         .unwrap();
 
@@ -163,7 +172,7 @@ async fn test_delete_last_release_deletes_album() {
 
     library_manager
         .get()
-        .delete_release(&release.id)
+        .delete_release(&release.id, false)
         .await
         .unwrap();
 
@@ -15,8 +15,9 @@
 //! - Stuck imports (preparing with no release_id)
 //! - Clearing/dismissing imports from the UI
 //! - App restart loading active imports from DB
+//! - Queued and paused imports, with the options needed to rebuild them
 
-use bae_core::db::{Database, DbImport, ImportOperationStatus};
+use bae_core::db::{Database, DbImport, ImportKind, ImportOperationStatus};
 use tempfile::TempDir;
 
 fn tracing_init() {
@@ -310,3 +311,60 @@ async fn test_active_imports_ordered_by_created_at_desc() {
     assert_eq!(active[1].id, "second");
     assert_eq!(active[2].id, "first");
 }
+
+/// Queued and paused imports are still active, and keep what's needed to
+/// rebuild them after a restart.
+#[tokio::test]
+async fn test_queued_import_keeps_options() {
+    tracing_init();
+    let (db, _temp) = create_test_db().await;
+
+    let import = DbImport::new(
+        "queued-import",
+        "Glass Harbor",
+        "Low Tide",
+        "/music/low-tide/glass-harbor",
+    )
+    .with_options(
+        Some("profile-1".to_string()),
+        Some("scans/front.jpg".to_string()),
+        vec!["/music/low-tide/glass-harbor/01 (copy).flac".to_string()],
+    );
+    db.insert_import(&import).await.unwrap();
+    db.update_import_status("queued-import", ImportOperationStatus::Queued)
+        .await
+        .unwrap();
+
+    let paused = DbImport::new("paused-import", "Tin Roof", "Low Tide", "/music/tin-roof")
+        .with_kind(ImportKind::Torrent);
+    db.insert_import(&paused).await.unwrap();
+    db.update_import_status("paused-import", ImportOperationStatus::Paused)
+        .await
+        .unwrap();
+
+    assert_eq!(db.get_active_imports().await.unwrap().len(), 2);
+
+    let queued = db.get_import("queued-import").await.unwrap().unwrap();
+    assert_eq!(queued.status, ImportOperationStatus::Queued);
+    assert_eq!(queued.kind, ImportKind::Folder);
+    assert_eq!(queued.storage_profile_id.as_deref(), Some("profile-1"));
+    assert_eq!(
+        queued.selected_cover_filename.as_deref(),
+        Some("scans/front.jpg")
+    );
+    assert_eq!(queued.excluded_files.len(), 1);
+
+    let paused = db.get_import("paused-import").await.unwrap().unwrap();
+    assert_eq!(paused.kind, ImportKind::Torrent);
+
+    // A retried import starts over without its old error
+    db.update_import_error("queued-import", "Disk full")
+        .await
+        .unwrap();
+    db.update_import_status("queued-import", ImportOperationStatus::Queued)
+        .await
+        .unwrap();
+    let retried = db.get_import("queued-import").await.unwrap().unwrap();
+    assert_eq!(retried.status, ImportOperationStatus::Queued);
+    assert!(retried.error_message.is_none());
+}
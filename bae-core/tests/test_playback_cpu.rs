@@ -186,11 +186,14 @@ impl CueFlacTestFixture {
                 import_id,
                 discogs_release: Some(discogs_release),
                 mb_release: None,
+                manual_release: None,
                 folder: album_dir.clone(),
                 master_year,
                 cover_art_url: None,
                 storage_profile_id: None,
                 selected_cover_filename: None,
+                excluded_files: Vec::new(),
+                needle_drop_sides: Vec::new(),
             })
             .await?;
 
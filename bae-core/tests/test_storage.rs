@@ -4,6 +4,7 @@
 //! Tests:
 //! - Local/Cloud storage with encryption permutations
 //! - Storageless (files stay in place, no encryption)
+//! - Two devices colliding on a cloud key keep both copies
 mod support;
 use crate::support::test_encryption_service;
 use bae_core::cache::CacheManager;
@@ -13,7 +14,7 @@ use bae_core::discogs::models::{DiscogsRelease, DiscogsTrack};
 use bae_core::encryption::EncryptionService;
 use bae_core::import::{ImportPhase, ImportProgress, ImportRequest, ImportService};
 use bae_core::library::LibraryManager;
-use bae_core::storage::create_storage_reader;
+use bae_core::storage::{create_storage_reader, ReleaseStorageImpl};
 use bae_core::test_support::MockCloudStorage;
 use std::path::Path;
 use std::sync::Arc;
@@ -99,11 +100,14 @@ async fn test_storageless_import() {
             import_id,
             discogs_release: Some(discogs_release),
             mb_release: None,
+            manual_release: None,
             folder: album_dir.clone(),
             master_year: 2024,
             cover_art_url: None,
             storage_profile_id: None, // Storageless
             selected_cover_filename: None,
+            excluded_files: Vec::new(),
+            needle_drop_sides: Vec::new(),
         })
         .await
         .expect("send request");
@@ -249,11 +253,14 @@ async fn test_storageless_delete_preserves_files() {
             import_id,
             discogs_release: Some(discogs_release),
             mb_release: None,
+            manual_release: None,
             folder: album_dir.clone(),
             master_year: 2024,
             cover_art_url: None,
             storage_profile_id: None, // Storageless
             selected_cover_filename: None,
+            excluded_files: Vec::new(),
+            needle_drop_sides: Vec::new(),
         })
         .await
         .expect("send request");
@@ -309,7 +316,7 @@ async fn test_storageless_delete_preserves_files() {
     info!("Deleting release {}", release_id);
     shared_library_manager
         .get()
-        .delete_release(&release_id)
+        .delete_release(&release_id, false)
         .await
         .expect("delete release");
 
@@ -344,6 +351,43 @@ async fn test_storageless_delete_preserves_files() {
     info!("✅ Storageless delete preserves original files");
 }
 
+/// Two devices importing the same release into one bucket: the second finds
+/// the first's file at the key and keeps both copies.
+#[tokio::test]
+async fn test_cloud_collision_keeps_both_copies() {
+    tracing_init();
+    let temp_root = TempDir::new().expect("Failed to create temp root");
+    let cloud = Arc::new(MockCloudStorage::new());
+    let profile = create_storage_profile(&StorageLocation::Cloud, false, "");
+
+    let mut stored = Vec::new();
+    for (device, data) in [
+        ("first", b"first device".as_slice()),
+        ("second", b"second device".as_slice()),
+    ] {
+        let db_file = temp_root.path().join(format!("{}.db", device));
+        let database = Database::new(db_file.to_str().unwrap())
+            .await
+            .expect("database");
+        let storage = ReleaseStorageImpl::with_cloud(
+            profile.clone(),
+            None,
+            cloud.clone(),
+            Arc::new(database),
+        );
+        let file = storage
+            .store_file("release-1", "01 Track One.flac", data, Box::new(|_, _| {}))
+            .await
+            .expect("store file");
+        stored.push((file.source_path.expect("stored location"), data));
+    }
+
+    assert_ne!(stored[0].0, stored[1].0);
+    for (location, data) in stored {
+        assert_eq!(cloud.download(&location).await.unwrap(), data);
+    }
+}
+
 async fn run_storage_test(location: StorageLocation, encrypted: bool) {
     let temp_root = TempDir::new().expect("Failed to create temp root");
     let album_dir = temp_root.path().join("album");
@@ -417,11 +461,14 @@ async fn run_storage_test(location: StorageLocation, encrypted: bool) {
         .send_request(ImportRequest::Folder {
             discogs_release: Some(discogs_release),
             mb_release: None,
+            manual_release: None,
             folder: album_dir.clone(),
             master_year,
             cover_art_url: None,
             storage_profile_id: Some(storage_profile_id.clone()),
             selected_cover_filename: Some(selected_cover.clone()),
+            excluded_files: Vec::new(),
+            needle_drop_sides: Vec::new(),
             import_id: uuid::Uuid::new_v4().to_string(),
         })
         .await
@@ -607,6 +654,9 @@ async fn run_storage_test(location: StorageLocation, encrypted: bool) {
     info!("✓ Cover image data is loadable");
     verify_storage_state(location, encrypted, &files, mock_cloud.as_ref()).await;
     verify_roundtrip(&tracks, &library_manager, encrypted).await;
+    if location == StorageLocation::Local {
+        verify_delete_reclaims_storage(&library_manager, &release_id, &files, &storage_dir).await;
+    }
     info!(
         "\n✅ Test passed: {:?} / encrypted={}\n",
         location, encrypted
@@ -625,6 +675,7 @@ fn create_storage_profile(
     );
     match location {
         StorageLocation::Local => DbStorageProfile::new_local(&name, storage_path, encrypted),
+        StorageLocation::Reference => DbStorageProfile::new_reference(&name, storage_path),
         StorageLocation::Cloud => DbStorageProfile::new_cloud(
             &name,
             "test-bucket",
@@ -787,9 +838,13 @@ async fn verify_storage_state(
     mock_cloud: Option<&Arc<MockCloudStorage>>,
 ) {
     match location {
-        StorageLocation::Local => {
+        StorageLocation::Local | StorageLocation::Reference => {
             // Verify local files exist
             for file in files {
+                assert!(
+                    !file.checksum_verified,
+                    "Local files have no provider checksum"
+                );
                 if let Some(ref source_path) = file.source_path {
                     let path = PathBuf::from(source_path);
                     assert!(path.exists(), "Local file should exist at: {}", source_path);
@@ -813,6 +868,11 @@ async fn verify_storage_state(
             let cloud = mock_cloud.expect("Mock cloud should exist for cloud tests");
             let stored_files = cloud.files.lock().unwrap();
             for file in files {
+                assert!(
+                    file.checksum_verified,
+                    "Cloud upload should be checksum-verified: {}",
+                    file.original_filename
+                );
                 if let Some(ref source_path) = file.source_path {
                     assert!(
                         stored_files.contains_key(source_path),
@@ -840,6 +900,38 @@ async fn verify_storage_state(
     }
 }
 
+async fn verify_delete_reclaims_storage(
+    library_manager: &LibraryManager,
+    release_id: &str,
+    files: &[bae_core::db::DbFile],
+    storage_dir: &Path,
+) {
+    let stored_bytes = library_manager
+        .stored_bytes(release_id)
+        .await
+        .expect("Failed to get stored bytes");
+    let expected: u64 = files.iter().map(|f| f.file_size as u64).sum();
+    assert_eq!(stored_bytes, expected, "Stored bytes should sum the files");
+
+    library_manager
+        .delete_release(release_id, false)
+        .await
+        .expect("Failed to delete release");
+    for file in files {
+        let path = file.source_path.as_ref().unwrap();
+        assert!(
+            !Path::new(path).exists(),
+            "Stored file should be deleted: {}",
+            path
+        );
+    }
+    assert!(
+        !storage_dir.join(release_id).exists(),
+        "Release folder should be removed once empty"
+    );
+    info!("✓ Deleting the release removed its stored files");
+}
+
 async fn verify_roundtrip(
     tracks: &[bae_core::db::DbTrack],
     library_manager: &LibraryManager,
@@ -929,11 +1021,14 @@ async fn run_real_album_test(album_dir: PathBuf, location: StorageLocation, encr
         .send_request(ImportRequest::Folder {
             discogs_release: None,
             mb_release: None,
+            manual_release: None,
             folder: album_dir.clone(),
             master_year: 1981,
             cover_art_url: None,
             storage_profile_id: Some(storage_profile_id.clone()),
             selected_cover_filename: None,
+            excluded_files: Vec::new(),
+            needle_drop_sides: Vec::new(),
             import_id: uuid::Uuid::new_v4().to_string(),
         })
         .await
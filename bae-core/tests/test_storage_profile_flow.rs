@@ -6,9 +6,12 @@
 //! - Read operations retrieve the same storage profile
 //! - The profile's credentials would be used (verified via mock)
 
-use bae_core::db::{Database, DbAlbum, DbRelease, DbStorageProfile, ImportStatus};
-use bae_core::storage::create_storage_reader;
+use bae_core::db::{
+    CloudProvider, Database, DbAlbum, DbRelease, DbStorageProfile, FilenamePolicy, ImportStatus,
+};
+use bae_core::storage::{create_storage_reader, ReleaseStorage, ReleaseStorageImpl};
 use chrono::Utc;
+use std::sync::Arc;
 use tempfile::TempDir;
 use uuid::Uuid;
 
@@ -243,6 +246,141 @@ async fn test_multiple_releases_different_profiles() {
     println!("✓ Release 2 correctly uses Profile B (bucket-b, eu-west-1, minio endpoint)");
 }
 
+/// Local storage must handle release paths longer than Windows' MAX_PATH (260)
+#[tokio::test]
+async fn test_local_storage_deep_release_paths() {
+    tracing_init();
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let storage_path = temp_dir
+        .path()
+        .join("a".repeat(100))
+        .join("b".repeat(100))
+        .join("c".repeat(100));
+    std::fs::create_dir_all(bae_core::storage::long_path(&storage_path)).unwrap();
+
+    let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+    let album = create_test_album("Deep Album");
+    let release = create_test_release(&album.id);
+    database.insert_album(&album).await.unwrap();
+    database.insert_release(&release).await.unwrap();
+
+    let profile =
+        DbStorageProfile::new_local("Deep Profile", storage_path.to_str().unwrap(), false);
+    let storage =
+        ReleaseStorageImpl::from_profile(profile.clone(), None, Arc::new(database.clone()))
+            .await
+            .unwrap();
+
+    let data = b"deep file data".to_vec();
+    storage
+        .write_file(
+            &release.id,
+            "Disc 1/01 Track.flac",
+            &data,
+            Box::new(|_, _| {}),
+        )
+        .await
+        .unwrap();
+
+    let files = database.get_files_for_release(&release.id).await.unwrap();
+    assert_eq!(files.len(), 1);
+    let source_path = files[0].source_path.clone().unwrap();
+    assert!(
+        source_path.len() > 260,
+        "Path should exceed MAX_PATH: {}",
+        source_path
+    );
+
+    let reader = create_storage_reader(&profile).await.unwrap();
+    let downloaded = reader.download(&source_path).await.unwrap();
+    assert_eq!(downloaded, data);
+
+    reader.delete(&source_path).await.unwrap();
+}
+
+/// Illegal characters are sanitized per profile policy, collisions get a
+/// numbered suffix, and DbFile keeps the original name
+#[tokio::test]
+async fn test_local_storage_sanitizes_filenames() {
+    tracing_init();
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let storage_path = temp_dir.path().join("storage");
+    std::fs::create_dir_all(&storage_path).unwrap();
+
+    let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+    let album = create_test_album("Sanitized Album");
+    let release = create_test_release(&album.id);
+    database.insert_album(&album).await.unwrap();
+    database.insert_release(&release).await.unwrap();
+
+    let profile = DbStorageProfile::new_local("Local", storage_path.to_str().unwrap(), false)
+        .with_filename_policy(FilenamePolicy::Replace);
+    let storage = ReleaseStorageImpl::from_profile(profile, None, Arc::new(database.clone()))
+        .await
+        .unwrap();
+
+    for filename in ["What?.flac", "What_.flac"] {
+        storage
+            .write_file(&release.id, filename, b"data", Box::new(|_, _| {}))
+            .await
+            .unwrap();
+    }
+
+    let files = database.get_files_for_release(&release.id).await.unwrap();
+    let stored: Vec<(String, String)> = files
+        .iter()
+        .map(|f| {
+            let path = std::path::PathBuf::from(f.source_path.clone().unwrap());
+            let stored_name = path.file_name().unwrap().to_string_lossy().to_string();
+            (f.original_filename.clone(), stored_name)
+        })
+        .collect();
+    assert!(stored.contains(&("What?.flac".to_string(), "What_.flac".to_string())));
+    assert!(stored.contains(&("What_.flac".to_string(), "What_ (2).flac".to_string())));
+}
+
+/// A B2 profile keeps its provider through the database and hands its
+/// credentials to the B2 client, not the S3 one
+#[tokio::test]
+async fn test_b2_profile_round_trip() {
+    tracing_init();
+
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().join("test.db");
+    let database = Database::new(db_path.to_str().unwrap()).await.unwrap();
+
+    let profile = DbStorageProfile::new_cloud(
+        "B2",
+        "my-b2-bucket",
+        "",
+        None,
+        "0012345abcdef0000000001",
+        "K001secretapplicationkey",
+        true,
+    )
+    .with_cloud_provider(CloudProvider::B2);
+    database.insert_storage_profile(&profile).await.unwrap();
+
+    let retrieved = database
+        .get_storage_profile(&profile.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(retrieved.cloud_provider, CloudProvider::B2);
+    assert!(retrieved.to_s3_config().is_none());
+
+    let b2_config = retrieved
+        .to_b2_config()
+        .expect("B2 profile should have B2 config");
+    assert_eq!(b2_config.bucket_name, "my-b2-bucket");
+    assert_eq!(b2_config.key_id, "0012345abcdef0000000001");
+    assert_eq!(b2_config.application_key, "K001secretapplicationkey");
+}
+
 // Helper functions to create test data
 
 fn create_test_album(title: &str) -> DbAlbum {
@@ -259,6 +397,11 @@ fn create_test_album(title: &str) -> DbAlbum {
         is_compilation: false,
         created_at: now,
         updated_at: now,
+        play_count: 0,
+        last_played_at: None,
+        dominant_color: None,
+        accent_color: None,
+        preferred_release_id: None,
     }
 }
 
@@ -280,6 +280,19 @@ if [[ -f "$CUSTOM_PLIST" ]]; then
         echo "  ✓ Added SUEnableAutomaticChecks"
     fi
     
+    # URL scheme registration for bae:// deep links
+    if /usr/libexec/PlistBuddy -c "Print :CFBundleURLTypes" "$CUSTOM_PLIST" &>/dev/null; then
+        URL_SCHEME=$(/usr/libexec/PlistBuddy -c "Print :CFBundleURLTypes:0:CFBundleURLSchemes:0" "$CUSTOM_PLIST")
+        URL_NAME=$(/usr/libexec/PlistBuddy -c "Print :CFBundleURLTypes:0:CFBundleURLName" "$CUSTOM_PLIST")
+        /usr/libexec/PlistBuddy -c "Delete :CFBundleURLTypes" "$INFO_PLIST" 2>/dev/null || true
+        /usr/libexec/PlistBuddy -c "Add :CFBundleURLTypes array" "$INFO_PLIST"
+        /usr/libexec/PlistBuddy -c "Add :CFBundleURLTypes:0 dict" "$INFO_PLIST"
+        /usr/libexec/PlistBuddy -c "Add :CFBundleURLTypes:0:CFBundleURLName string '$URL_NAME'" "$INFO_PLIST"
+        /usr/libexec/PlistBuddy -c "Add :CFBundleURLTypes:0:CFBundleURLSchemes array" "$INFO_PLIST"
+        /usr/libexec/PlistBuddy -c "Add :CFBundleURLTypes:0:CFBundleURLSchemes:0 string '$URL_SCHEME'" "$INFO_PLIST"
+        echo "  ✓ Added CFBundleURLTypes"
+    fi
+    
     echo "✓ Info.plist merged"
 fi
 
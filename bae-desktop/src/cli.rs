@@ -0,0 +1,118 @@
+//! Maintenance commands run from the command line instead of opening the app
+//!
+//! `bae maintenance compact-db` and `bae maintenance clear-cache` do the same
+//! work as the buttons in Settings → Library, then exit.
+//! `bae maintenance changelog [id]` prints recent library changes, optionally
+//! only those to one album, release, track or artist.
+
+use crate::{create_cache_manager, create_database, create_library_manager};
+use bae_core::config;
+
+const USAGE: &str = "Usage: bae maintenance <compact-db | clear-cache | changelog [id]>";
+
+/// Changelog entries printed by `bae maintenance changelog`
+const CHANGELOG_LIMIT: i64 = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MaintenanceCommand {
+    CompactDatabase,
+    ClearCache,
+    Changelog { entity_id: Option<String> },
+}
+
+impl MaintenanceCommand {
+    fn parse(mut args: impl Iterator<Item = String>) -> Option<Self> {
+        match args.next()?.as_str() {
+            "compact-db" => Some(MaintenanceCommand::CompactDatabase),
+            "clear-cache" => Some(MaintenanceCommand::ClearCache),
+            "changelog" => Some(MaintenanceCommand::Changelog {
+                entity_id: args.next(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The maintenance command given at launch, if any. Exits with usage when
+/// `maintenance` is followed by something unknown.
+pub fn maintenance_command() -> Option<MaintenanceCommand> {
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() != Some("maintenance") {
+        return None;
+    }
+    match MaintenanceCommand::parse(args) {
+        Some(command) => Some(command),
+        None => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    }
+}
+
+/// Run a maintenance command to completion. Returns the process exit code.
+pub async fn run_maintenance(command: MaintenanceCommand, config: &config::Config) -> i32 {
+    match command {
+        MaintenanceCommand::CompactDatabase => {
+            let database = create_database(config).await;
+            let library_manager = create_library_manager(database, None, config);
+            println!("Compacting database...");
+            match library_manager.get().compact_database().await {
+                Ok(()) => {
+                    println!("Database compacted");
+                    0
+                }
+                Err(e) => {
+                    eprintln!("Failed to compact database: {}", e);
+                    1
+                }
+            }
+        }
+        MaintenanceCommand::ClearCache => {
+            let cache_manager = create_cache_manager().await;
+            println!("Clearing cache...");
+            match cache_manager.clear().await {
+                Ok((count, bytes)) => {
+                    println!("Removed {} files ({} bytes)", count, bytes);
+                    0
+                }
+                Err(e) => {
+                    eprintln!("Failed to clear cache: {}", e);
+                    1
+                }
+            }
+        }
+        MaintenanceCommand::Changelog { entity_id } => {
+            let database = create_database(config).await;
+            let library_manager = create_library_manager(database, None, config);
+            let changes = match library_manager
+                .get()
+                .get_changelog(entity_id.as_deref(), CHANGELOG_LIMIT)
+                .await
+            {
+                Ok(changes) => changes,
+                Err(e) => {
+                    eprintln!("Failed to read changelog: {}", e);
+                    return 1;
+                }
+            };
+            // Oldest first, like a log
+            for change in changes.iter().rev() {
+                println!(
+                    "{:>6}  {}  {}  {}  {}",
+                    change.seq,
+                    change.created_at.format("%Y-%m-%d %H:%M:%S"),
+                    change.device_id,
+                    change.kind.as_str(),
+                    change.entity_id
+                );
+                if let Some(before) = &change.before {
+                    println!("        before: {}", before);
+                }
+                if let Some(after) = &change.after {
+                    println!("        after:  {}", after);
+                }
+            }
+            0
+        }
+    }
+}
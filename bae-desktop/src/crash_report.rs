@@ -1,44 +1,133 @@
-use std::path::PathBuf;
+//! Crash reports
+//!
+//! A panic writes a report to `~/.bae/crash-reports/` with the backtrace, the
+//! last log lines (breadcrumbs, collected by [`BreadcrumbLayer`]) and a summary
+//! of what the app was doing: the route on screen, the latest import step and
+//! the playback state, as set through [`set_context`]. On the next launch the
+//! user is asked whether to send it; nothing leaves the machine unless they
+//! say yes.
 
-fn crash_log_path() -> Option<PathBuf> {
-    dirs::home_dir().map(|h| h.join(".bae").join("crash.log"))
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+/// Log lines kept for the next report
+const MAX_BREADCRUMBS: usize = 100;
+
+/// Reports kept on disk, newest first
+const MAX_REPORTS: usize = 10;
+
+/// Names the report waiting to be offered on next launch
+const PENDING_FILE: &str = "pending";
+
+/// How far down the stack the panic machinery can reach
+const PANIC_FRAMES: usize = 40;
+
+static BREADCRUMBS: Mutex<Breadcrumbs> = Mutex::new(Breadcrumbs::new());
+
+static CONTEXT: Mutex<BTreeMap<&'static str, String>> = Mutex::new(BTreeMap::new());
+
+fn reports_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".bae").join("crash-reports"))
+}
+
+/// Record what the app is doing, under `key`, for any crash that follows
+pub fn set_context(key: &'static str, value: impl Into<String>) {
+    if let Ok(mut context) = CONTEXT.lock() {
+        context.insert(key, value.into());
+    }
+}
+
+/// Most recent log lines, oldest first
+struct Breadcrumbs {
+    lines: VecDeque<String>,
+}
+
+impl Breadcrumbs {
+    const fn new() -> Self {
+        Breadcrumbs {
+            lines: VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, line: String) {
+        if self.lines.len() == MAX_BREADCRUMBS {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+}
+
+/// Tracing layer that keeps the last log lines for crash reports
+pub struct BreadcrumbLayer;
+
+impl<S: tracing::Subscriber> Layer<S> for BreadcrumbLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = BreadcrumbVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let line = format!(
+            "{} {:5} {}: {}{}",
+            chrono::Utc::now().format("%H:%M:%S%.3f"),
+            metadata.level().as_str(),
+            metadata.target(),
+            visitor.message,
+            visitor.fields,
+        );
+        if let Ok(mut breadcrumbs) = BREADCRUMBS.lock() {
+            breadcrumbs.push(line);
+        }
+    }
+}
+
+#[derive(Default)]
+struct BreadcrumbVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for BreadcrumbVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
 }
 
 pub fn install_panic_hook() {
     let default_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
+        // A failed webview call under a guard is recovered from, not a crash
+        if bae_ui::ipc::in_guarded_call() && bae_ui::ipc::is_decode_failure(&panic_message(info)) {
+            return;
+        }
+
         let _ = (|| -> std::io::Result<()> {
-            let path = match crash_log_path() {
-                Some(p) => p,
+            let dir = match reports_dir() {
+                Some(dir) => dir,
                 None => return Ok(()),
             };
+            std::fs::create_dir_all(&dir)?;
 
-            if let Some(parent) = path.parent() {
-                std::fs::create_dir_all(parent)?;
-            }
-
-            let message = if let Some(s) = info.payload().downcast_ref::<&str>() {
-                s.to_string()
-            } else if let Some(s) = info.payload().downcast_ref::<String>() {
-                s.clone()
-            } else {
-                "unknown panic".to_string()
-            };
+            let message = panic_message(info);
 
             let location = info
                 .location()
                 .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
                 .unwrap_or_else(|| "unknown".to_string());
 
-            let backtrace = std::backtrace::Backtrace::force_capture();
-            let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
-            let version = env!("BAE_VERSION");
-
-            let report = format!(
-                "bae crash report\n================\nTime: {now}\nVersion: {version}\n\nPanic: {message}\nLocation: {location}\n\nBacktrace:\n{backtrace}",
-            );
-
+            let report = build_report(&message, &location);
+            let now = chrono::Utc::now();
+            let path = dir.join(format!("crash-{}.txt", now.format("%Y%m%dT%H%M%S%.3fZ")));
             std::fs::write(&path, report)?;
+            std::fs::write(dir.join(PENDING_FILE), path.to_string_lossy().as_bytes())?;
+            prune_reports(&dir);
             Ok(())
         })();
 
@@ -46,26 +135,163 @@ pub fn install_panic_hook() {
     }));
 }
 
+fn panic_message(info: &std::panic::PanicHookInfo<'_>) -> String {
+    if let Some(s) = info.payload().downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = info.payload().downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn build_report(message: &str, location: &str) -> String {
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
+    let version = env!("BAE_VERSION");
+    let os = std::env::consts::OS;
+    let arch = std::env::consts::ARCH;
+    let thread = std::thread::current()
+        .name()
+        .unwrap_or("unnamed")
+        .to_string();
+
+    let mut report = format!(
+        "bae crash report\n================\nTime: {now}\nVersion: {version}\nPlatform: {os} {arch}\nThread: {thread}\n\nPanic: {message}\nLocation: {location}\n",
+    );
+
+    // try_lock throughout: the panic may have happened while one was held
+    report.push_str("\nState:\n");
+    match CONTEXT.try_lock() {
+        Ok(context) if !context.is_empty() => {
+            for (key, value) in context.iter() {
+                let _ = writeln!(report, "  {key}: {value}");
+            }
+        }
+        Ok(_) => report.push_str("  (none recorded)\n"),
+        Err(_) => report.push_str("  (unavailable)\n"),
+    }
+
+    report.push_str("\nBacktrace:\n");
+    report.push_str(&format_backtrace(&backtrace::Backtrace::new()));
+
+    report.push_str("\nRecent log:\n");
+    match BREADCRUMBS.try_lock() {
+        Ok(breadcrumbs) => {
+            for line in &breadcrumbs.lines {
+                let _ = writeln!(report, "  {line}");
+            }
+        }
+        Err(_) => report.push_str("  (unavailable)\n"),
+    }
+
+    report
+}
+
+/// Frames with symbol names and source lines where the binary has them, and
+/// otherwise the address and its offset into the module, which can be
+/// symbolicated later against the release's debug info
+fn format_backtrace(backtrace: &backtrace::Backtrace) -> String {
+    let mut out = String::new();
+    let frames = backtrace.frames();
+    // The capture, this hook and the panic machinery come first; start below them
+    let start = frames
+        .iter()
+        .take(PANIC_FRAMES)
+        .rposition(is_panic_machinery)
+        .map_or(0, |i| i + 1);
+    for (index, frame) in frames[start..].iter().enumerate() {
+        let symbols = frame.symbols();
+        if symbols.iter().all(|symbol| symbol.name().is_none()) {
+            let offset = frame
+                .module_base_address()
+                .map(|base| {
+                    let offset = (frame.ip() as usize).wrapping_sub(base as usize);
+                    format!(" (module+{:#x})", offset)
+                })
+                .unwrap_or_default();
+            let _ = writeln!(out, "{index:4}: {:?}{offset}", frame.ip());
+            continue;
+        }
+        for symbol in symbols {
+            let name = symbol
+                .name()
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| "<unknown>".to_string());
+            let _ = writeln!(out, "{index:4}: {name}");
+            if let (Some(file), Some(line)) = (symbol.filename(), symbol.lineno()) {
+                let _ = writeln!(out, "          at {}:{line}", file.display());
+            }
+        }
+    }
+    out
+}
+
+fn is_panic_machinery(frame: &backtrace::BacktraceFrame) -> bool {
+    frame.symbols().iter().any(|symbol| {
+        symbol.name().is_some_and(|name| {
+            let name = name.to_string();
+            name.contains("panicking")
+                || name.contains("rust_begin_unwind")
+                || name.contains("__rust_end_short_backtrace")
+        })
+    })
+}
+
+/// Delete all but the newest reports
+fn prune_reports(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut reports: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    // Names sort by time
+    reports.sort();
+    let excess = reports.len().saturating_sub(MAX_REPORTS);
+    for path in &reports[..excess] {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Offer to send the report from a crash in the last session
 pub fn check_for_crash_report() {
-    let path = match crash_log_path() {
-        Some(p) if p.exists() => p,
-        _ => return,
+    let Some(dir) = reports_dir() else {
+        return;
     };
+    let pending = dir.join(PENDING_FILE);
+    let Ok(report_path) = std::fs::read_to_string(&pending) else {
+        return;
+    };
+    let _ = std::fs::remove_file(&pending);
 
-    let report = match std::fs::read_to_string(&path) {
+    let report_path = PathBuf::from(report_path.trim());
+    let report = match std::fs::read_to_string(&report_path) {
         Ok(r) => r,
         Err(_) => return,
     };
 
-    let _ = std::fs::remove_file(&path);
-
-    let should_report = rfd::MessageDialog::new()
+    let send = "Send Report".to_string();
+    let choice = rfd::MessageDialog::new()
         .set_title("bae crashed")
-        .set_description("bae crashed during the last session. Would you like to open a GitHub issue with the crash report?")
-        .set_buttons(rfd::MessageButtons::YesNo)
+        .set_description(format!(
+            "bae crashed during the last session. The crash report was saved to {}.\n\n\
+             Sending it opens a GitHub issue with the report filled in, so you can read it before submitting. \
+             It includes recent log lines, which may mention album and file names.",
+            report_path.display()
+        ))
+        .set_buttons(rfd::MessageButtons::OkCancelCustom(
+            send.clone(),
+            "Don't Send".to_string(),
+        ))
         .show();
 
-    if should_report == rfd::MessageDialogResult::Yes {
+    let should_send = match choice {
+        rfd::MessageDialogResult::Ok => true,
+        rfd::MessageDialogResult::Custom(label) => label == send,
+        _ => false,
+    };
+    if should_send {
         // Truncate report for URL length limits
         let truncated: String = report.chars().take(4000).collect();
         let body = format!(
@@ -80,3 +306,22 @@ pub fn check_for_crash_report() {
         let _ = std::process::Command::new("open").arg(&url).spawn();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breadcrumbs_keep_most_recent() {
+        let mut breadcrumbs = Breadcrumbs::new();
+        for i in 0..MAX_BREADCRUMBS + 5 {
+            breadcrumbs.push(i.to_string());
+        }
+        assert_eq!(breadcrumbs.lines.len(), MAX_BREADCRUMBS);
+        assert_eq!(breadcrumbs.lines.front().map(String::as_str), Some("5"));
+        assert_eq!(
+            breadcrumbs.lines.back(),
+            Some(&(MAX_BREADCRUMBS + 4).to_string())
+        );
+    }
+}
@@ -1,20 +1,28 @@
 use bae_core::db::Database;
-use bae_core::library::SharedLibraryManager;
+use bae_core::library::{snapshot, SharedLibraryManager};
+use bae_core::remote::{generate_token, RemoteAccess};
 use bae_core::subsonic::create_router;
-use bae_core::{audio_codec, cache, config, encryption, import, playback};
+use bae_core::transcode::Transcoder;
+use bae_core::{audio_codec, cache, config, encryption, import, playback, scrobble};
 #[cfg(feature = "torrent")]
 use bae_core::{network, torrent};
+use std::path::PathBuf;
 #[cfg(feature = "torrent")]
 use tracing::warn;
 use tracing::{error, info};
 
+mod cli;
 mod crash_report;
 mod media_controls;
+mod remote;
 mod ui;
 mod updater;
 
 pub use ui::AppContext;
 
+/// How long every changelog entry is kept before compaction
+const CHANGELOG_RETENTION: chrono::Duration = chrono::Duration::days(90);
+
 /// Initialize cache manager
 async fn create_cache_manager() -> cache::CacheManager {
     let cache_manager = cache::CacheManager::new()
@@ -29,7 +37,7 @@ async fn create_database(config: &config::Config) -> Database {
     let library_path = config.get_library_path();
     info!("Creating library directory: {}", library_path.display());
     std::fs::create_dir_all(&library_path).expect("Failed to create library directory");
-    let db_path = library_path.join("library.db");
+    let db_path = library_path.join(snapshot::DATABASE_FILENAME);
     info!("Initializing database at: {}", db_path.display());
     let database = Database::new(db_path.to_str().unwrap())
         .await
@@ -38,12 +46,37 @@ async fn create_database(config: &config::Config) -> Database {
     database
 }
 
+/// Swap in a restored snapshot if one is waiting, then check the database.
+/// A damaged database is moved aside so a fresh one can be created; returns
+/// where it went, so the app can offer a snapshot to restore.
+async fn prepare_database_file(config: &config::Config) -> Option<PathBuf> {
+    let library_path = config.get_library_path();
+    if let Err(e) = snapshot::apply_pending_restore(&library_path) {
+        error!("Failed to apply restored snapshot: {}", e);
+    }
+    let db_path = library_path.join(snapshot::DATABASE_FILENAME);
+    let problem = snapshot::check_database(&db_path).await.err()?;
+    error!("Library database is damaged: {}", problem);
+    match snapshot::set_aside_database(&db_path, "damaged") {
+        Ok(kept_path) => {
+            info!("Damaged database moved to {}", kept_path.display());
+            Some(kept_path)
+        }
+        Err(e) => {
+            error!("Failed to move damaged database aside: {}", e);
+            None
+        }
+    }
+}
+
 /// Initialize library manager with all dependencies
 fn create_library_manager(
     database: Database,
     encryption_service: Option<encryption::EncryptionService>,
+    config: &config::Config,
 ) -> SharedLibraryManager {
-    let library_manager = bae_core::library::LibraryManager::new(database, encryption_service);
+    let library_manager = bae_core::library::LibraryManager::new(database, encryption_service)
+        .with_device_id(&config.device_id);
     info!("Library manager created");
     let shared_library = SharedLibraryManager::new(library_manager);
     info!("SharedLibraryManager created");
@@ -71,6 +104,7 @@ fn configure_logging() {
             .with(env_filter)
             .with(fmt_layer)
             .with(oslog_layer)
+            .with(crash_report::BreadcrumbLayer)
             .init();
         return;
     }
@@ -78,12 +112,13 @@ fn configure_logging() {
     tracing_subscriber::registry()
         .with(env_filter)
         .with(fmt_layer)
+        .with(crash_report::BreadcrumbLayer)
         .init();
 }
 
 fn main() {
     crash_report::install_panic_hook();
-    let config = config::Config::load();
+    let mut config = config::Config::load();
     configure_logging();
     crash_report::check_for_crash_report();
 
@@ -93,8 +128,14 @@ fn main() {
     let runtime = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
     let runtime_handle = runtime.handle().clone();
 
+    if let Some(command) = cli::maintenance_command() {
+        let code = runtime_handle.block_on(cli::run_maintenance(command, &config));
+        std::process::exit(code);
+    }
+
     info!("Building dependencies...");
     let cache_manager = runtime_handle.block_on(create_cache_manager());
+    let damaged_database = runtime_handle.block_on(prepare_database_file(&config));
     let database = runtime_handle.block_on(create_database(&config));
 
     // Create encryption service only if key is configured (loaded lazily from keyring)
@@ -102,12 +143,48 @@ fn main() {
         .encryption_key
         .as_ref()
         .and_then(|key| encryption::EncryptionService::new(key).ok());
-    let library_manager = create_library_manager(database.clone(), encryption_service.clone());
+    let library_manager =
+        create_library_manager(database.clone(), encryption_service.clone(), &config);
+
+    // Old changelog entries only matter for the latest state of each entity
+    runtime_handle.spawn({
+        let library_manager = library_manager.clone();
+        async move {
+            match library_manager
+                .get()
+                .compact_changelog(CHANGELOG_RETENTION)
+                .await
+            {
+                Ok(0) => {}
+                Ok(removed) => info!("Compacted changelog, removed {} entries", removed),
+                Err(e) => error!("Failed to compact changelog: {}", e),
+            }
+        }
+    });
 
     #[cfg(feature = "torrent")]
     let torrent_manager = {
         let torrent_options = torrent_options_from_config(&config);
-        torrent::LazyTorrentManager::new(cache_manager.clone(), database.clone(), torrent_options)
+        let manager = torrent::LazyTorrentManager::new(
+            config.get_library_path().join("seeding"),
+            database.clone(),
+            torrent_options,
+        );
+        manager.set_paused(!config.metered_settings().torrents_allowed());
+
+        // Only start the session at launch if something was left seeding
+        runtime_handle.spawn({
+            let manager = manager.clone();
+            let database = database.clone();
+            async move {
+                match database.get_seeding_torrents().await {
+                    Ok(torrents) if !torrents.is_empty() => manager.get().resume_seeding(),
+                    Ok(_) => {}
+                    Err(e) => error!("Failed to check for seeding torrents: {}", e),
+                }
+            }
+        });
+        manager
     };
 
     #[cfg(feature = "torrent")]
@@ -126,28 +203,37 @@ fn main() {
         std::sync::Arc::new(database.clone()),
     );
 
+    import_handle.set_credit_rules(config.credit_rules());
+    import_handle.set_low_power_import(config.low_power_import);
+    import_handle.set_import_memory_budget(config.import_memory_budget_mb);
+    import_handle.set_max_concurrent_imports(config.max_concurrent_imports as usize);
+
     let playback_handle = playback::PlaybackService::start(
         library_manager.get().clone(),
         encryption_service.clone(),
         runtime_handle.clone(),
     );
+    playback_handle.set_download_concurrency(config.cloud_download_concurrency);
+    playback_handle.set_buffer_settings(config.playback_buffer_settings());
+    playback_handle.set_output_settings(config.playback_output_settings());
+    playback_handle.set_skip_silence(config.playback_skip_silence_settings());
+    playback_handle.set_cloud_prefetch(config.metered_settings().cloud_prefetch_allowed());
+    playback_handle.set_offline_cache(cache_manager.clone());
 
-    let media_controls = match media_controls::setup_media_controls(
+    let scrobble_handle = scrobble::ScrobbleService::start(
+        runtime_handle.clone(),
+        library_manager.clone(),
+        playback_handle.subscribe_progress(),
+    );
+    scrobble_handle.set_accounts(config.scrobble_accounts());
+
+    import::DiscIdRetryService::start(runtime_handle.clone(), library_manager.clone());
+
+    let media_controls = media_controls::start_media_controls(
         playback_handle.clone(),
         library_manager.clone(),
         runtime_handle.clone(),
-    ) {
-        Ok(controls) => {
-            info!("Media controls setup successful");
-            Some(controls)
-        }
-        Err(e) => {
-            error!("Failed to setup media controls: {:?}", e);
-            error!("Media key support will not be available");
-            None
-        }
-    };
-    let _keep_alive = media_controls;
+    );
 
     // Initialize navigation + playback channels for menu shortcuts (must be before menu setup)
     ui::shortcuts::init_nav_channel();
@@ -155,22 +241,53 @@ fn main() {
     #[cfg(target_os = "macos")]
     ui::shortcuts::init_playback_channel();
 
+    // Phones pair with a token made when the remote is turned on in Settings;
+    // make one if the config was edited by hand or the keyring lost it
+    if config.remote_enabled && config.remote_token.is_none() {
+        config.remote_token = Some(generate_token());
+        if let Err(e) = config.save() {
+            error!("Failed to save phone remote token: {}", e);
+        }
+    }
+    let remote_access = RemoteAccess::new(config.remote_token.clone());
+
     let ui_context = AppContext {
         library_manager: library_manager.clone(),
         config: config.clone(),
         import_handle,
         playback_handle,
+        scrobble_handle,
+        remote_access: remote_access.clone(),
+        media_controls,
         #[cfg(feature = "torrent")]
         torrent_manager,
         cache: cache_manager.clone(),
+        damaged_database,
     };
 
     if config.subsonic_enabled {
         let subsonic_library = library_manager.clone();
         let subsonic_encryption = encryption_service.clone();
         let subsonic_port = config.subsonic_port;
+        let transcoder = Transcoder::new(config.subsonic_transcode_bitrate_kbps);
+        runtime_handle.spawn(async move {
+            start_subsonic_server(
+                subsonic_library,
+                subsonic_encryption,
+                transcoder,
+                subsonic_port,
+            )
+            .await
+        });
+    }
+
+    if config.remote_enabled {
+        let remote_playback = ui_context.playback_handle.clone();
+        let remote_library = library_manager.clone();
+        let remote_port = config.remote_port;
         runtime_handle.spawn(async move {
-            start_subsonic_server(subsonic_library, subsonic_encryption, subsonic_port).await
+            remote::start_remote_server(remote_playback, remote_library, remote_access, remote_port)
+                .await
         });
     }
 
@@ -186,10 +303,11 @@ fn main() {
 async fn start_subsonic_server(
     library_manager: SharedLibraryManager,
     encryption_service: Option<encryption::EncryptionService>,
+    transcoder: Transcoder,
     port: u16,
 ) {
     info!("Starting Subsonic API server...");
-    let app = create_router(library_manager, encryption_service);
+    let app = create_router(library_manager, encryption_service, transcoder);
     let addr = format!("127.0.0.1:{}", port);
     let listener = match tokio::net::TcpListener::bind(&addr).await {
         Ok(listener) => {
@@ -235,6 +353,9 @@ fn torrent_options_from_config(config: &config::Config) -> torrent::client::Torr
         listen_port: config.torrent_listen_port,
         enable_upnp: config.torrent_enable_upnp,
         enable_natpmp: config.torrent_enable_natpmp,
+        enable_ipv6: config.torrent_enable_ipv6,
+        enable_dht: config.torrent_enable_dht,
+        dht_bootstrap_nodes: config.torrent_dht_bootstrap_nodes.clone(),
         max_connections: config.torrent_max_connections,
         max_uploads: config.torrent_max_uploads,
     }
@@ -2,18 +2,94 @@ use bae_core::library::SharedLibraryManager;
 use bae_core::playback::{PlaybackHandle, PlaybackProgress, PlaybackState};
 use souvlaki::{
     MediaControlEvent, MediaControls, MediaMetadata, MediaPlayback, MediaPosition, PlatformConfig,
+    SeekDirection,
 };
+use std::ffi::c_void;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tracing::{error, info, trace};
-/// Initialize media controls for macOS
-/// This handles system media key events (play/pause FN key)
-/// Returns the MediaControls handle which must be kept alive for the app lifetime
-pub fn setup_media_controls(
+
+/// How far a plain seek forward or back moves, for controls that don't say
+const SEEK_STEP: Duration = Duration::from_secs(10);
+
+/// The app's media controls. Keep it for the life of the app: dropping the
+/// last clone unregisters them.
+#[derive(Clone)]
+pub struct MediaControlsHandle {
+    /// Registered controls; elsewhere than Windows, only held to keep them
+    #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+    controls: Arc<Mutex<Option<Arc<Mutex<MediaControls>>>>>,
+    /// What the controls are built from, held until the window exists
+    #[cfg(target_os = "windows")]
+    pending: Arc<Mutex<Option<MediaControlsServices>>>,
+}
+
+struct MediaControlsServices {
+    playback_handle: PlaybackHandle,
+    library_manager: SharedLibraryManager,
+    runtime_handle: tokio::runtime::Handle,
+}
+
+/// Show playback in the OS media controls (MPRIS on Linux, Now Playing on
+/// macOS, the media overlay on Windows) and take play/pause/next from media
+/// keys. Windows ties the controls to a window, so there they're registered
+/// by [`attach_to_window`] once the UI is up.
+pub fn start_media_controls(
     playback_handle: PlaybackHandle,
     library_manager: SharedLibraryManager,
     runtime_handle: tokio::runtime::Handle,
+) -> MediaControlsHandle {
+    let services = MediaControlsServices {
+        playback_handle,
+        library_manager,
+        runtime_handle,
+    };
+    #[cfg(not(target_os = "windows"))]
+    let controls = register(services, None);
+    #[cfg(target_os = "windows")]
+    let controls = None;
+    MediaControlsHandle {
+        controls: Arc::new(Mutex::new(controls)),
+        #[cfg(target_os = "windows")]
+        pending: Arc::new(Mutex::new(Some(services))),
+    }
+}
+
+/// Register the controls against the main window's handle
+#[cfg(target_os = "windows")]
+pub fn attach_to_window(media_controls: &MediaControlsHandle, hwnd: *mut c_void) {
+    if let Some(services) = media_controls.pending.lock().unwrap().take() {
+        *media_controls.controls.lock().unwrap() = register(services, Some(hwnd));
+    }
+}
+
+fn register(
+    services: MediaControlsServices,
+    hwnd: Option<*mut c_void>,
+) -> Option<Arc<Mutex<MediaControls>>> {
+    match setup_media_controls(services, hwnd) {
+        Ok(controls) => {
+            info!("Media controls setup successful");
+            Some(controls)
+        }
+        Err(e) => {
+            error!("Failed to setup media controls: {:?}", e);
+            error!("Media key support will not be available");
+            None
+        }
+    }
+}
+
+fn setup_media_controls(
+    services: MediaControlsServices,
+    hwnd: Option<*mut c_void>,
 ) -> Result<Arc<Mutex<MediaControls>>, souvlaki::Error> {
+    let MediaControlsServices {
+        playback_handle,
+        library_manager,
+        runtime_handle,
+    } = services;
     let current_state = Arc::new(Mutex::new(PlaybackState::Stopped));
     let playback_handle_for_controls = playback_handle.clone();
     let playback_handle_for_progress = playback_handle.clone();
@@ -23,82 +99,22 @@ pub fn setup_media_controls(
     let config = PlatformConfig {
         dbus_name: "com.bae.app",
         display_name: "bae",
-        hwnd: None,
+        hwnd,
     };
     let mut controls = MediaControls::new(config)?;
     controls.attach(move |event: MediaControlEvent| {
-        let playback = playback_handle_for_controls.clone();
-        let state = current_state_for_controls.clone();
-        match event {
-            MediaControlEvent::Toggle => {
-                info!("Media key event received: Toggle");
-                let state = state.lock().unwrap();
-                info!("Media key Toggle pressed, current state: {:?}", *state);
-                match *state {
-                    PlaybackState::Playing { .. } => {
-                        info!("Media key: Pausing playback");
-                        playback.pause();
-                    }
-                    PlaybackState::Paused { .. } => {
-                        info!("Media key: Resuming playback");
-                        playback.resume();
-                    }
-                    PlaybackState::Stopped | PlaybackState::Loading { .. } => {
-                        info!("Media key: Ignored (stopped or loading)");
-                    }
-                }
-            }
-            MediaControlEvent::Play => {
-                info!("Media control event received: Play");
-                let state = state.lock().unwrap();
-                match *state {
-                    PlaybackState::Paused { .. } => {
-                        info!("Media control: Resuming playback");
-                        playback.resume();
-                    }
-                    PlaybackState::Stopped | PlaybackState::Loading { .. } => {
-                        info!("Media control: Ignored (stopped or loading)");
-                    }
-                    PlaybackState::Playing { .. } => {
-                        info!("Media control: Already playing, ignoring Play");
-                    }
-                }
-            }
-            MediaControlEvent::Pause => {
-                info!("Media control event received: Pause");
-                let state = state.lock().unwrap();
-                match *state {
-                    PlaybackState::Playing { .. } => {
-                        info!("Media control: Pausing playback");
-                        playback.pause();
-                    }
-                    PlaybackState::Paused { .. }
-                    | PlaybackState::Stopped
-                    | PlaybackState::Loading { .. } => {
-                        info!("Media control: Already paused/stopped, ignoring Pause");
-                    }
-                }
-            }
-            MediaControlEvent::Next => {
-                info!("Media key event received: Next");
-                playback.next();
-            }
-            MediaControlEvent::Previous => {
-                info!("Media key event received: Previous");
-                playback.previous();
-            }
-            MediaControlEvent::SetPosition(media_position) => {
-                let position = media_position.0;
-                info!("Media control: SetPosition requested: {:?}", position);
-                playback.seek(position);
-            }
-            MediaControlEvent::Stop => {
-                info!("Media key event received: Stop");
-                playback.stop();
-            }
-            _ => {
-                info!("Media key event received: {:?}", event);
-            }
+        let playback = &playback_handle_for_controls;
+        let command = media_command(&event, &current_state_for_controls.lock().unwrap());
+        info!("Media control event {:?}: {:?}", event, command);
+        match command {
+            Some(MediaCommand::Pause) => playback.pause(),
+            Some(MediaCommand::Resume) => playback.resume(),
+            Some(MediaCommand::Next) => playback.next(),
+            Some(MediaCommand::Previous) => playback.previous(),
+            Some(MediaCommand::Stop) => playback.stop(),
+            Some(MediaCommand::Seek(position)) => playback.seek(position),
+            Some(MediaCommand::SetVolume(volume)) => playback.set_volume(volume),
+            None => {}
         }
     })?;
     let controls_shared = Arc::new(Mutex::new(controls));
@@ -108,6 +124,7 @@ pub fn setup_media_controls(
             let mut progress_rx = playback_handle_for_progress.subscribe_progress();
             let current_state = current_state_for_progress;
             let library_manager = library_manager_for_metadata;
+            let mut shown_track_id: Option<String> = None;
             while let Some(progress) = progress_rx.recv().await {
                 match progress {
                     PlaybackProgress::StateChanged { state } => {
@@ -120,6 +137,11 @@ pub fn setup_media_controls(
                                 *state_guard
                             );
                         }
+                        // Keep the last track up while the next one loads, so the
+                        // controls don't blank out between tracks
+                        if matches!(state, PlaybackState::Loading { .. }) {
+                            continue;
+                        }
                         {
                             let mut controls = controls_shared.lock().unwrap();
                             let playback_state = match state {
@@ -150,15 +172,20 @@ pub fn setup_media_controls(
                                 duration,
                                 ..
                             } => {
-                                update_media_metadata(
-                                    &controls_shared,
-                                    &library_manager,
-                                    track,
-                                    duration,
-                                )
-                                .await;
+                                // Artists and cover only need looking up when the track changes
+                                if shown_track_id.as_deref() != Some(track.id.as_str()) {
+                                    shown_track_id = Some(track.id.clone());
+                                    update_media_metadata(
+                                        &controls_shared,
+                                        &library_manager,
+                                        track,
+                                        duration,
+                                    )
+                                    .await;
+                                }
                             }
                             PlaybackState::Stopped | PlaybackState::Loading { .. } => {
+                                shown_track_id = None;
                                 let mut controls = controls_shared.lock().unwrap();
                                 if let Err(e) = controls.set_metadata(MediaMetadata::default()) {
                                     error!("Failed to clear media metadata: {:?}", e);
@@ -180,20 +207,30 @@ pub fn setup_media_controls(
     info!("Media controls initialized");
     Ok(controls_shared)
 }
-/// Update playback position in macOS media controls
+/// Update playback position in system media controls
 fn update_playback_position(
     controls_shared: &Arc<Mutex<MediaControls>>,
     current_state: &Arc<Mutex<PlaybackState>>,
     position: std::time::Duration,
 ) {
-    let state_guard = current_state.lock().unwrap();
-    let playback_state = match *state_guard {
-        PlaybackState::Playing { .. } => MediaPlayback::Playing {
-            progress: Some(MediaPosition(position)),
-        },
-        PlaybackState::Paused { .. } => MediaPlayback::Paused {
-            progress: Some(MediaPosition(position)),
-        },
+    let mut state_guard = current_state.lock().unwrap();
+    let playback_state = match &mut *state_guard {
+        PlaybackState::Playing {
+            position: tracked, ..
+        } => {
+            *tracked = position;
+            MediaPlayback::Playing {
+                progress: Some(MediaPosition(position)),
+            }
+        }
+        PlaybackState::Paused {
+            position: tracked, ..
+        } => {
+            *tracked = position;
+            MediaPlayback::Paused {
+                progress: Some(MediaPosition(position)),
+            }
+        }
         PlaybackState::Stopped | PlaybackState::Loading { .. } => {
             return;
         }
@@ -209,6 +246,62 @@ fn update_playback_position(
         );
     }
 }
+/// What a media control event asks playback to do
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MediaCommand {
+    Pause,
+    Resume,
+    Next,
+    Previous,
+    Stop,
+    Seek(Duration),
+    SetVolume(f32),
+}
+
+/// Map an event from the OS controls to a playback command, given the last
+/// reported state. None when the event doesn't apply, like Play while
+/// already playing or a seek with nothing loaded.
+fn media_command(event: &MediaControlEvent, state: &PlaybackState) -> Option<MediaCommand> {
+    let position = match state {
+        PlaybackState::Playing { position, .. } | PlaybackState::Paused { position, .. } => {
+            Some(*position)
+        }
+        PlaybackState::Stopped | PlaybackState::Loading { .. } => None,
+    };
+    let seek = |direction: &SeekDirection, amount: Duration| {
+        let position = position?;
+        Some(MediaCommand::Seek(match direction {
+            SeekDirection::Forward => position + amount,
+            SeekDirection::Backward => position.saturating_sub(amount),
+        }))
+    };
+    match event {
+        MediaControlEvent::Toggle => match state {
+            PlaybackState::Playing { .. } => Some(MediaCommand::Pause),
+            PlaybackState::Paused { .. } => Some(MediaCommand::Resume),
+            PlaybackState::Stopped | PlaybackState::Loading { .. } => None,
+        },
+        MediaControlEvent::Play => {
+            matches!(state, PlaybackState::Paused { .. }).then_some(MediaCommand::Resume)
+        }
+        MediaControlEvent::Pause => {
+            matches!(state, PlaybackState::Playing { .. }).then_some(MediaCommand::Pause)
+        }
+        MediaControlEvent::Next => Some(MediaCommand::Next),
+        MediaControlEvent::Previous => Some(MediaCommand::Previous),
+        MediaControlEvent::Stop => Some(MediaCommand::Stop),
+        MediaControlEvent::SetPosition(MediaPosition(position)) => {
+            Some(MediaCommand::Seek(*position))
+        }
+        MediaControlEvent::Seek(direction) => seek(direction, SEEK_STEP),
+        MediaControlEvent::SeekBy(direction, amount) => seek(direction, *amount),
+        MediaControlEvent::SetVolume(volume) => {
+            Some(MediaCommand::SetVolume(volume.clamp(0.0, 1.0) as f32))
+        }
+        _ => None,
+    }
+}
+
 /// Update media metadata in system media controls
 async fn update_media_metadata(
     controls: &Arc<Mutex<MediaControls>>,
@@ -340,7 +433,7 @@ async fn resolve_cover_file_url(
     Some(format!("file://{}", cover_path.display()))
 }
 
-/// Check if the image can be served directly from a local unencrypted file.
+/// Check if the image can be served directly from a local unencrypted, uncompressed file.
 /// Returns the file path if so, None otherwise.
 async fn get_direct_file_path(
     library_manager: &SharedLibraryManager,
@@ -364,10 +457,14 @@ async fn get_direct_file_path(
         .await
         .ok()??;
 
+    if file.compressed {
+        return None;
+    }
+
     let source_path = file.source_path?;
 
     // Cloud storage needs download
-    if source_path.starts_with("s3://") {
+    if bae_core::cloud_storage::is_cloud_location(&source_path) {
         return None;
     }
 
@@ -393,3 +490,113 @@ fn get_media_cover_cache_path(image_id: &str) -> PathBuf {
         .join("media-covers")
         .join(image_id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bae_core::db::{DbTrack, ImportStatus};
+
+    fn track() -> DbTrack {
+        DbTrack {
+            id: "track".to_string(),
+            release_id: "release".to_string(),
+            title: "Airbag".to_string(),
+            disc_number: None,
+            track_number: Some(1),
+            duration_ms: Some(284_000),
+            discogs_position: None,
+            import_status: ImportStatus::Complete,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn playing(position: Duration) -> PlaybackState {
+        PlaybackState::Playing {
+            track: track(),
+            position,
+            duration: None,
+            decoded_duration: Duration::from_secs(284),
+            pregap_ms: None,
+        }
+    }
+
+    fn paused(position: Duration) -> PlaybackState {
+        PlaybackState::Paused {
+            track: track(),
+            position,
+            duration: None,
+            decoded_duration: Duration::from_secs(284),
+            pregap_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_play_and_pause_only_act_when_they_change_something() {
+        let playing = playing(Duration::ZERO);
+        let paused = paused(Duration::ZERO);
+        let stopped = PlaybackState::Stopped;
+
+        let toggle = MediaControlEvent::Toggle;
+        assert_eq!(media_command(&toggle, &playing), Some(MediaCommand::Pause));
+        assert_eq!(media_command(&toggle, &paused), Some(MediaCommand::Resume));
+        assert_eq!(media_command(&toggle, &stopped), None);
+
+        let play = MediaControlEvent::Play;
+        assert_eq!(media_command(&play, &paused), Some(MediaCommand::Resume));
+        assert_eq!(media_command(&play, &playing), None);
+        assert_eq!(media_command(&play, &stopped), None);
+
+        let pause = MediaControlEvent::Pause;
+        assert_eq!(media_command(&pause, &playing), Some(MediaCommand::Pause));
+        assert_eq!(media_command(&pause, &paused), None);
+    }
+
+    #[test]
+    fn test_seeks_are_relative_to_the_last_position() {
+        let state = playing(Duration::from_secs(30));
+
+        assert_eq!(
+            media_command(&MediaControlEvent::Seek(SeekDirection::Forward), &state),
+            Some(MediaCommand::Seek(Duration::from_secs(40)))
+        );
+        assert_eq!(
+            media_command(
+                &MediaControlEvent::SeekBy(SeekDirection::Backward, Duration::from_secs(45)),
+                &state
+            ),
+            Some(MediaCommand::Seek(Duration::ZERO))
+        );
+        assert_eq!(
+            media_command(
+                &MediaControlEvent::SetPosition(MediaPosition(Duration::from_secs(90))),
+                &PlaybackState::Stopped
+            ),
+            Some(MediaCommand::Seek(Duration::from_secs(90)))
+        );
+        assert_eq!(
+            media_command(
+                &MediaControlEvent::Seek(SeekDirection::Forward),
+                &PlaybackState::Stopped
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_volume_is_clamped() {
+        let state = PlaybackState::Stopped;
+
+        assert_eq!(
+            media_command(&MediaControlEvent::SetVolume(1.5), &state),
+            Some(MediaCommand::SetVolume(1.0))
+        );
+        assert_eq!(
+            media_command(&MediaControlEvent::SetVolume(-0.5), &state),
+            Some(MediaCommand::SetVolume(0.0))
+        );
+        assert_eq!(
+            media_command(&MediaControlEvent::SetVolume(0.25), &state),
+            Some(MediaCommand::SetVolume(0.25))
+        );
+    }
+}
@@ -0,0 +1,464 @@
+//! Phone remote server
+//!
+//! Serves a small page and JSON API on the local network so a phone's browser
+//! can control playback and browse the library. Every API request needs the
+//! pairing token from Settings, sent as a bearer token (or a `token` query
+//! parameter for cover images, which the page loads with plain `<img>` tags).
+//! Responses use the bae-ui display types, with cover URLs pointed at
+//! `/api/images` instead of the desktop-only `bae://` scheme.
+
+use crate::ui::app_service::load_queue_items;
+use crate::ui::display_types::{album_from_db_ref, artist_from_db_ref, track_from_db_ref};
+use crate::ui::protocol_handler::{serve_image, ImageServices};
+use axum::extract::{Path, Request, State};
+use axum::http::{header, HeaderMap, StatusCode, Uri};
+use axum::middleware::{self, Next};
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use bae_core::library::SharedLibraryManager;
+use bae_core::playback::{PlaybackHandle, PlaybackProgress, PlaybackState};
+use bae_core::remote::RemoteAccess;
+use bae_ui::stores::PlaybackStatus;
+use bae_ui::{Album, Artist, QueueItem, Track};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+const PAGE: &str = include_str!("remote.html");
+
+#[derive(Clone)]
+struct RemoteState {
+    playback: PlaybackHandle,
+    library_manager: SharedLibraryManager,
+    access: RemoteAccess,
+    player: Arc<Mutex<Player>>,
+}
+
+/// Playback as last reported by the playback service
+struct Player {
+    state: PlaybackState,
+    position: Duration,
+    queue: Vec<String>,
+    /// The service doesn't report volume, so this is what the remote last set
+    volume: f32,
+}
+
+#[derive(Serialize)]
+struct NowPlaying {
+    status: PlaybackStatus,
+    current: Option<QueueItem>,
+    artist: String,
+    position_ms: u64,
+    duration_ms: u64,
+    volume: f32,
+    queue: Vec<QueueItem>,
+}
+
+#[derive(Serialize)]
+struct AlbumEntry {
+    #[serde(flatten)]
+    album: Album,
+    artists: Vec<Artist>,
+}
+
+#[derive(Serialize)]
+struct AlbumDetail {
+    #[serde(flatten)]
+    album: Album,
+    artists: Vec<Artist>,
+    tracks: Vec<Track>,
+}
+
+#[derive(Deserialize)]
+struct PlayRequest {
+    track_ids: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct QueueRequest {
+    track_ids: Vec<String>,
+    /// Insert after the current track instead of at the end
+    #[serde(default)]
+    next: bool,
+}
+
+#[derive(Deserialize)]
+struct SeekRequest {
+    position_ms: u64,
+}
+
+#[derive(Deserialize)]
+struct VolumeRequest {
+    volume: f32,
+}
+
+/// Serve the phone remote on all interfaces until the app quits
+pub async fn start_remote_server(
+    playback: PlaybackHandle,
+    library_manager: SharedLibraryManager,
+    access: RemoteAccess,
+    port: u16,
+) {
+    info!("Starting phone remote server...");
+    let player = Arc::new(Mutex::new(Player {
+        state: PlaybackState::Stopped,
+        position: Duration::ZERO,
+        queue: Vec::new(),
+        volume: 1.0,
+    }));
+    tokio::spawn(track_playback(
+        playback.subscribe_progress(),
+        player.clone(),
+    ));
+
+    let app = create_router(RemoteState {
+        playback,
+        library_manager,
+        access,
+        player,
+    });
+    let addr = format!("0.0.0.0:{}", port);
+    let listener = match tokio::net::TcpListener::bind(&addr).await {
+        Ok(listener) => {
+            info!("Phone remote listening on http://{}", addr);
+            listener
+        }
+        Err(e) => {
+            error!("Failed to bind phone remote server: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = axum::serve(listener, app).await {
+        error!("Phone remote server error: {}", e);
+    }
+}
+
+fn create_router(state: RemoteState) -> Router {
+    let api = Router::new()
+        .route("/api/now-playing", get(now_playing))
+        .route("/api/play", post(play))
+        .route("/api/pause", post(pause))
+        .route("/api/resume", post(resume))
+        .route("/api/next", post(next))
+        .route("/api/previous", post(previous))
+        .route("/api/seek", post(seek))
+        .route("/api/volume", post(set_volume))
+        .route("/api/queue", post(add_to_queue).delete(clear_queue))
+        .route("/api/queue/:index", delete(remove_from_queue))
+        .route("/api/albums", get(albums))
+        .route("/api/albums/:id", get(album))
+        .route("/api/images/:id", get(image))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token));
+
+    Router::new()
+        .route("/", get(|| async { Html(PAGE) }))
+        .merge(api)
+        .with_state(state)
+}
+
+async fn require_token(State(state): State<RemoteState>, request: Request, next: Next) -> Response {
+    let allowed = bearer_token(request.headers())
+        .or_else(|| query_token(request.uri()))
+        .is_some_and(|token| state.access.allows(token));
+    if allowed {
+        next.run(request).await
+    } else {
+        StatusCode::UNAUTHORIZED.into_response()
+    }
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
+
+fn query_token(uri: &Uri) -> Option<&str> {
+    uri.query()?
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+}
+
+/// Keep the snapshot the API answers from in step with the playback service
+async fn track_playback(
+    mut progress_rx: tokio::sync::mpsc::UnboundedReceiver<PlaybackProgress>,
+    player: Arc<Mutex<Player>>,
+) {
+    while let Some(progress) = progress_rx.recv().await {
+        let mut player = player.lock().unwrap();
+        match progress {
+            PlaybackProgress::StateChanged { state } => {
+                player.position = match &state {
+                    PlaybackState::Playing { position, .. }
+                    | PlaybackState::Paused { position, .. } => *position,
+                    PlaybackState::Stopped | PlaybackState::Loading { .. } => Duration::ZERO,
+                };
+                player.state = state;
+            }
+            PlaybackProgress::PositionUpdate { position, .. }
+            | PlaybackProgress::Seeked { position, .. } => {
+                player.position = position;
+            }
+            PlaybackProgress::QueueUpdated { tracks } => {
+                player.queue = tracks;
+            }
+            _ => {}
+        }
+    }
+}
+
+async fn now_playing(State(state): State<RemoteState>) -> Json<NowPlaying> {
+    let (playback_state, position, queue_ids, volume) = {
+        let player = state.player.lock().unwrap();
+        (
+            player.state.clone(),
+            player.position,
+            player.queue.clone(),
+            player.volume,
+        )
+    };
+
+    let (status, track_id, duration) = match playback_state {
+        PlaybackState::Stopped => (PlaybackStatus::Stopped, None, None),
+        PlaybackState::Loading { track_id } => (PlaybackStatus::Loading, Some(track_id), None),
+        PlaybackState::Playing {
+            track, duration, ..
+        } => (PlaybackStatus::Playing, Some(track.id), duration),
+        PlaybackState::Paused {
+            track, duration, ..
+        } => (PlaybackStatus::Paused, Some(track.id), duration),
+    };
+
+    let (current, artist) = match track_id {
+        Some(track_id) => {
+            let current = load_queue_items(&state.library_manager, &[track_id.clone()])
+                .await
+                .pop()
+                .map(remote_queue_item);
+            (
+                current,
+                track_artist(&state.library_manager, &track_id).await,
+            )
+        }
+        None => (None, String::new()),
+    };
+    let queue = load_queue_items(&state.library_manager, &queue_ids)
+        .await
+        .into_iter()
+        .map(remote_queue_item)
+        .collect();
+
+    Json(NowPlaying {
+        status,
+        current,
+        artist,
+        position_ms: position.as_millis() as u64,
+        duration_ms: duration.unwrap_or_default().as_millis() as u64,
+        volume,
+        queue,
+    })
+}
+
+/// Names of the artists on a track's album, for the now playing line
+async fn track_artist(library_manager: &SharedLibraryManager, track_id: &str) -> String {
+    let Ok(album_id) = library_manager.get().get_album_id_for_track(track_id).await else {
+        return String::new();
+    };
+    library_manager
+        .get()
+        .get_artists_for_album(&album_id)
+        .await
+        .map(|artists| {
+            artists
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_default()
+}
+
+async fn play(State(state): State<RemoteState>, Json(request): Json<PlayRequest>) -> StatusCode {
+    if request.track_ids.is_empty() {
+        return StatusCode::BAD_REQUEST;
+    }
+    state.playback.play_album(request.track_ids);
+    StatusCode::NO_CONTENT
+}
+
+async fn pause(State(state): State<RemoteState>) -> StatusCode {
+    state.playback.pause();
+    StatusCode::NO_CONTENT
+}
+
+async fn resume(State(state): State<RemoteState>) -> StatusCode {
+    state.playback.resume();
+    StatusCode::NO_CONTENT
+}
+
+async fn next(State(state): State<RemoteState>) -> StatusCode {
+    state.playback.next();
+    StatusCode::NO_CONTENT
+}
+
+async fn previous(State(state): State<RemoteState>) -> StatusCode {
+    state.playback.previous();
+    StatusCode::NO_CONTENT
+}
+
+async fn seek(State(state): State<RemoteState>, Json(request): Json<SeekRequest>) -> StatusCode {
+    state
+        .playback
+        .seek(Duration::from_millis(request.position_ms));
+    StatusCode::NO_CONTENT
+}
+
+async fn set_volume(
+    State(state): State<RemoteState>,
+    Json(request): Json<VolumeRequest>,
+) -> StatusCode {
+    if !request.volume.is_finite() {
+        return StatusCode::BAD_REQUEST;
+    }
+    let volume = request.volume.clamp(0.0, 1.0);
+    state.playback.set_volume(volume);
+    state.player.lock().unwrap().volume = volume;
+    StatusCode::NO_CONTENT
+}
+
+async fn add_to_queue(
+    State(state): State<RemoteState>,
+    Json(request): Json<QueueRequest>,
+) -> StatusCode {
+    if request.next {
+        state.playback.add_next(request.track_ids);
+    } else {
+        state.playback.add_to_queue(request.track_ids);
+    }
+    StatusCode::NO_CONTENT
+}
+
+async fn remove_from_queue(
+    State(state): State<RemoteState>,
+    Path(index): Path<usize>,
+) -> StatusCode {
+    state.playback.remove_from_queue(index);
+    StatusCode::NO_CONTENT
+}
+
+async fn clear_queue(State(state): State<RemoteState>) -> StatusCode {
+    state.playback.clear_queue();
+    StatusCode::NO_CONTENT
+}
+
+async fn albums(State(state): State<RemoteState>) -> Result<Json<Vec<AlbumEntry>>, StatusCode> {
+    let library = state.library_manager.get();
+    let db_albums = library.get_albums().await.map_err(internal_error)?;
+    let mut entries = Vec::with_capacity(db_albums.len());
+    for db_album in &db_albums {
+        let artists = library
+            .get_artists_for_album(&db_album.id)
+            .await
+            .map_err(internal_error)?;
+        entries.push(AlbumEntry {
+            album: remote_album(album_from_db_ref(db_album)),
+            artists: artists.iter().map(artist_from_db_ref).collect(),
+        });
+    }
+    entries.sort_by_cached_key(|entry| {
+        (
+            entry
+                .artists
+                .first()
+                .map(|a| a.name.to_lowercase())
+                .unwrap_or_default(),
+            entry.album.title.to_lowercase(),
+        )
+    });
+    Ok(Json(entries))
+}
+
+async fn album(
+    State(state): State<RemoteState>,
+    Path(album_id): Path<String>,
+) -> Result<Json<AlbumDetail>, StatusCode> {
+    let library = state.library_manager.get();
+    let db_album = library
+        .get_album_by_id(&album_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let artists = library
+        .get_artists_for_album(&album_id)
+        .await
+        .map_err(internal_error)?;
+
+    // Tracks of the preferred edition, or the first one if none is set
+    let release_id = match db_album.preferred_release_id.clone() {
+        Some(id) => Some(id),
+        None => library
+            .get_releases_for_album(&album_id)
+            .await
+            .map_err(internal_error)?
+            .into_iter()
+            .next()
+            .map(|release| release.id),
+    };
+    let tracks = match release_id {
+        Some(release_id) => library
+            .get_tracks(&release_id)
+            .await
+            .map_err(internal_error)?
+            .iter()
+            .map(track_from_db_ref)
+            .collect(),
+        None => Vec::new(),
+    };
+
+    Ok(Json(AlbumDetail {
+        album: remote_album(album_from_db_ref(&db_album)),
+        artists: artists.iter().map(artist_from_db_ref).collect(),
+        tracks,
+    }))
+}
+
+async fn image(State(state): State<RemoteState>, Path(image_id): Path<String>) -> Response {
+    let services = ImageServices {
+        library_manager: state.library_manager.clone(),
+    };
+    match serve_image(&image_id, &services).await {
+        Ok((data, mime_type)) => ([(header::CONTENT_TYPE, mime_type)], data).into_response(),
+        Err(e) => {
+            warn!("Phone remote image request failed: {}", e);
+            StatusCode::NOT_FOUND.into_response()
+        }
+    }
+}
+
+fn internal_error(e: impl std::fmt::Display) -> StatusCode {
+    error!("Phone remote request failed: {}", e);
+    StatusCode::INTERNAL_SERVER_ERROR
+}
+
+fn remote_album(mut album: Album) -> Album {
+    album.cover_url = album.cover_url.map(remote_image_url);
+    album
+}
+
+fn remote_queue_item(mut item: QueueItem) -> QueueItem {
+    item.cover_url = item.cover_url.map(remote_image_url);
+    item
+}
+
+/// Point `bae://image/` covers at the API. Covers hosted elsewhere are left
+/// as they are.
+fn remote_image_url(url: String) -> String {
+    match url.strip_prefix("bae://image/") {
+        Some(image_id) => format!("/api/images/{}", image_id),
+        None => url,
+    }
+}
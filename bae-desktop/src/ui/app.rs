@@ -20,10 +20,16 @@ pub enum Route {
     #[layout(AppLayout)]
     #[route("/")]
     Library {},
-    #[route("/album/:album_id?:release_id")]
-    AlbumDetail { album_id: String, release_id: String },
+    #[route("/album/:album_id?:release_id&:track_id")]
+    AlbumDetail { album_id: String, release_id: String, track_id: String },
+    #[route("/tracks")]
+    Tracks {},
+    #[route("/history")]
+    History {},
     #[route("/import")]
     ImportWorkflowManager {},
+    #[route("/imports")]
+    ImportQueue {},
     #[route("/settings")]
     Settings {},
 }
@@ -70,8 +76,12 @@ pub fn launch_app(context: AppContext) {
         config: context.config.clone(),
         import_handle: context.import_handle.clone(),
         playback_handle: context.playback_handle.clone(),
+        scrobble_handle: context.scrobble_handle.clone(),
+        remote_access: context.remote_access.clone(),
+        media_controls: context.media_controls.clone(),
         cache: context.cache.clone(),
         torrent_manager: context.torrent_manager.clone(),
+        damaged_database: context.damaged_database.clone(),
     };
     #[cfg(not(feature = "torrent"))]
     let services = super::app_context::AppServices {
@@ -79,7 +89,11 @@ pub fn launch_app(context: AppContext) {
         config: context.config.clone(),
         import_handle: context.import_handle.clone(),
         playback_handle: context.playback_handle.clone(),
+        scrobble_handle: context.scrobble_handle.clone(),
+        remote_access: context.remote_access.clone(),
+        media_controls: context.media_controls.clone(),
         cache: context.cache.clone(),
+        damaged_database: context.damaged_database.clone(),
     };
 
     LaunchBuilder::desktop()
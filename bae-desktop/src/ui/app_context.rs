@@ -4,13 +4,17 @@
 //! This file contains the `AppServices` struct for passing backend service handles
 //! from main.rs through the launch boundary.
 
+use crate::media_controls::MediaControlsHandle;
 use bae_core::cache;
 use bae_core::config;
 use bae_core::import;
 use bae_core::library::SharedLibraryManager;
 use bae_core::playback;
+use bae_core::remote::RemoteAccess;
+use bae_core::scrobble;
 #[cfg(feature = "torrent")]
 use bae_core::torrent;
+use std::path::PathBuf;
 
 /// Service handles provided at app launch (Send + Sync safe).
 ///
@@ -27,11 +31,19 @@ pub struct AppServices {
     pub import_handle: import::ImportServiceHandle,
     /// Playback service handle for audio control
     pub playback_handle: playback::PlaybackHandle,
+    /// Scrobbler handle for switching accounts
+    pub scrobble_handle: scrobble::ScrobbleHandle,
+    /// Token the phone remote checks, updated when phones are unpaired
+    pub remote_access: RemoteAccess,
+    /// OS media controls, attached to the window on Windows
+    pub media_controls: MediaControlsHandle,
     /// Cache manager for images/files
     pub cache: cache::CacheManager,
     /// Torrent manager (feature-gated)
     #[cfg(feature = "torrent")]
     pub torrent_manager: torrent::LazyTorrentManager,
+    /// Where a database found damaged at launch was moved
+    pub damaged_database: Option<PathBuf>,
 }
 
 // =============================================================================
@@ -44,7 +56,11 @@ pub struct AppContext {
     pub config: config::Config,
     pub import_handle: import::ImportServiceHandle,
     pub playback_handle: playback::PlaybackHandle,
+    pub scrobble_handle: scrobble::ScrobbleHandle,
+    pub remote_access: RemoteAccess,
+    pub media_controls: MediaControlsHandle,
     pub cache: cache::CacheManager,
     #[cfg(feature = "torrent")]
     pub torrent_manager: torrent::LazyTorrentManager,
+    pub damaged_database: Option<PathBuf>,
 }
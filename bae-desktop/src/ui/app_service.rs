@@ -10,28 +10,71 @@
 //! - Read state reactively from `app.state`
 //! - Call action methods like `app.play_album()`
 
+#[cfg(feature = "torrent")]
+use crate::ui::display_types::seeding_torrent_from_db;
 use crate::ui::display_types::{
-    album_from_db_ref, artist_from_db_ref, release_from_db_ref, track_from_db_ref,
+    album_edits_from_display, album_from_db_ref, artist_from_db_ref, bookmark_from_db,
+    cd_insert_action_to_display, dither_to_display, featured_credit_style_to_display,
+    file_from_db_ref, find_replace_from_display, find_replace_row_from_display,
+    find_replace_row_to_display, library_snapshot_from_core, library_sort_to_display,
+    library_track_from_db, loudness_to_display, output_bit_depth_to_display,
+    output_format_to_display, palette_from_db_ref, quality_from_db, release_comparison_from_core,
+    release_from_db_ref, service_health_to_display, service_kind_from_display,
+    startup_view_to_display, track_click_action_to_display, track_from_db_ref,
+    track_play_count_from_db, track_play_from_db, verification_run_from_db,
 };
 use crate::ui::image_url;
-use crate::ui::import_helpers::consume_scan_events;
+use crate::ui::import_helpers::{consume_scan_events, load_release_templates, load_search_history};
+use crate::ui::local_file_url::local_file_url;
 use bae_core::cache;
+use bae_core::cloud_storage::ArchiveStatus;
 use bae_core::config;
-use bae_core::db::{DbStorageProfile, ImportStatus, StorageLocation};
+use bae_core::db::{
+    ChangeKind, CloudProvider, DbChange, DbStorageProfile, FilenamePolicy, ImportStatus,
+    PlacementPolicy, StorageLocation,
+};
+use bae_core::health::{CheckResult, HealthTracker, ServiceKind, ServiceStatus};
+use bae_core::import::cover_fetcher::{front_cover_url, CoverArtArchive, CoverFetcher};
 use bae_core::import::{self, ImportProgress};
-use bae_core::library::{LibraryEvent, SharedLibraryManager};
+use bae_core::library::compare;
+use bae_core::library::credits::renormalize_library_credits;
+use bae_core::library::dynamic_range::{
+    backfill_dynamic_range, measure_release_dynamic_range, release_dynamic_range,
+    DYNAMIC_RANGE_PACE,
+};
+use bae_core::library::find_replace::preview_find_replace;
+use bae_core::library::offline::{self, OfflineProgress};
+use bae_core::library::snapshot::{snapshot_due, snapshot_library, stage_restore, SnapshotIndex};
+use bae_core::library::verify::{
+    repair_issue, verification_due, verify_library, VERIFICATION_PACE,
+};
+use bae_core::library::{LibraryError, LibraryEvent, SharedLibraryManager};
 use bae_core::playback::{self, PlaybackProgress};
+use bae_core::remote::RemoteAccess;
+use bae_core::scrobble;
+use bae_core::storage::{self, QuotaLevel};
 #[cfg(feature = "torrent")]
 use bae_core::torrent;
-use bae_ui::display_types::{QueueItem, TrackImportState};
+use bae_ui::display_types::{
+    OfflineDownload, QueueItem, ReleaseArchiveStatus, ReleaseReference, Track, TrackBookmark,
+    TrackImportState,
+};
+#[cfg(feature = "torrent")]
+use bae_ui::stores::SeedingStateStoreExt;
 use bae_ui::stores::{
     ActiveImport, ActiveImportsUiStateStoreExt, AlbumDetailStateStoreExt, AppState,
-    AppStateStoreExt, ConfigStateStoreExt, ImportOperationStatus, LibraryStateStoreExt,
-    PlaybackStatus, PlaybackUiStateStoreExt, PrepareStep, RepeatMode, StorageProfilesStateStoreExt,
+    AppStateStoreExt, ClientSharesStateStoreExt, ConfigStateStoreExt, HealthStateStoreExt,
+    HistoryStateStoreExt, ImportKind, ImportOperationStatus, LibraryStateStoreExt,
+    MaintenanceStateStoreExt, PlaybackStatus, PlaybackUiStateStoreExt, PrepareStep, RepeatMode,
+    SnapshotsStateStoreExt, StorageProfilesStateStoreExt, TracksStateStoreExt, UndoStateStoreExt,
+    VerificationStateStoreExt,
 };
-use bae_ui::StorageProfile;
+use bae_ui::{ClientShare, LibrarySnapshot, StorageProfile};
+use chrono::{DateTime, Utc};
 use dioxus::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
 
 use super::app_context::AppServices;
 
@@ -52,37 +95,64 @@ pub struct AppService {
     pub import_handle: import::ImportServiceHandle,
     /// Playback service handle for audio control
     pub playback_handle: playback::PlaybackHandle,
+    /// Scrobbler handle for switching accounts
+    pub scrobble_handle: scrobble::ScrobbleHandle,
+    /// Token the phone remote checks
+    pub remote_access: RemoteAccess,
     /// Cache manager for images/files
     pub cache: cache::CacheManager,
     /// Torrent manager (feature-gated)
     #[cfg(feature = "torrent")]
     pub torrent_manager: torrent::LazyTorrentManager,
+    /// Subsystem health, built up from checks
+    health: HealthTracker,
+    /// Cover thumbnails for the match candidates on screen
+    pub cover_fetcher: CoverFetcher,
 }
 
 impl AppService {
     /// Create a new AppService from backend services
     pub fn new(services: &AppServices) -> Self {
+        let mut initial_state = AppState::default();
+        initial_state.snapshots.damaged_database = services
+            .damaged_database
+            .as_ref()
+            .map(|path| path.display().to_string());
         #[cfg(feature = "torrent")]
         {
             Self {
-                state: Store::new(AppState::default()),
+                state: Store::new(initial_state),
                 library_manager: services.library_manager.clone(),
                 config: services.config.clone(),
                 import_handle: services.import_handle.clone(),
                 playback_handle: services.playback_handle.clone(),
+                scrobble_handle: services.scrobble_handle.clone(),
+                remote_access: services.remote_access.clone(),
                 cache: services.cache.clone(),
                 torrent_manager: services.torrent_manager.clone(),
+                health: HealthTracker::new(Utc::now()),
+                cover_fetcher: CoverFetcher::new(
+                    services.cache.clone(),
+                    Arc::new(CoverArtArchive::new()),
+                ),
             }
         }
         #[cfg(not(feature = "torrent"))]
         {
             Self {
-                state: Store::new(AppState::default()),
+                state: Store::new(initial_state),
                 library_manager: services.library_manager.clone(),
                 config: services.config.clone(),
                 import_handle: services.import_handle.clone(),
                 playback_handle: services.playback_handle.clone(),
+                scrobble_handle: services.scrobble_handle.clone(),
+                remote_access: services.remote_access.clone(),
                 cache: services.cache.clone(),
+                health: HealthTracker::new(Utc::now()),
+                cover_fetcher: CoverFetcher::new(
+                    services.cache.clone(),
+                    Arc::new(CoverArtArchive::new()),
+                ),
             }
         }
     }
@@ -98,6 +168,10 @@ impl AppService {
         self.subscribe_library_events();
         self.subscribe_folder_scan_events();
         self.load_initial_data();
+        self.start_verification_schedule();
+        self.start_snapshot_schedule();
+        self.start_dynamic_range_backfill();
+        self.start_offline_restore();
     }
 
     // =========================================================================
@@ -168,6 +242,13 @@ impl AppService {
                             ),
                         };
 
+                        crate::crash_report::set_context(
+                            "playback",
+                            match &current_track_id {
+                                Some(track_id) => format!("{:?} {}", status, track_id),
+                                None => format!("{:?}", status),
+                            },
+                        );
                         state.playback().status().set(status);
                         state
                             .playback()
@@ -179,9 +260,14 @@ impl AppService {
                         state.playback().pregap_ms().set(pregap_ms);
 
                         // Load album and artist info for current track
-                        let (current_track, artist_name, cover_url) = if let Some(track) = db_track
+                        let (current_track, artist_name, cover_url, palette) = if let Some(item) =
+                            db_track
+                                .as_ref()
+                                .and_then(|t| external_queue_item(&t.id, t.duration_ms))
                         {
-                            let (album_title, cover, artist) = if let Some(ref track_id) =
+                            (Some(item), String::new(), None, None)
+                        } else if let Some(track) = db_track {
+                            let (album_title, cover, palette, artist) = if let Some(ref track_id) =
                                 current_track_id
                             {
                                 if let Ok(album_id) =
@@ -195,9 +281,10 @@ impl AppService {
                                             .as_ref()
                                             .map(|id| image_url(id))
                                             .or(album.cover_art_url.clone());
-                                        (album.title, cover)
+                                        let palette = palette_from_db_ref(&album);
+                                        (album.title, cover, palette)
                                     } else {
-                                        ("Unknown Album".to_string(), None)
+                                        ("Unknown Album".to_string(), None, None)
                                     };
 
                                     // Get artist name
@@ -209,30 +296,41 @@ impl AppService {
                                         String::new()
                                     };
 
-                                    (album_info.0, album_info.1, artist)
+                                    (album_info.0, album_info.1, album_info.2, artist)
                                 } else {
-                                    ("Unknown Album".to_string(), None, String::new())
+                                    ("Unknown Album".to_string(), None, None, String::new())
                                 }
                             } else {
-                                ("Unknown Album".to_string(), None, String::new())
+                                ("Unknown Album".to_string(), None, None, String::new())
                             };
 
+                            let mut display_track = track_from_db_ref(&track);
+                            display_track.bookmarks = library_manager
+                                .get()
+                                .get_bookmarks_for_track(&track.id)
+                                .await
+                                .map(|b| b.iter().map(bookmark_from_db).collect())
+                                .unwrap_or_default();
+
                             (
                                 Some(QueueItem {
-                                    track: track_from_db_ref(&track),
+                                    track: display_track,
                                     album_title,
                                     cover_url: cover.clone(),
+                                    is_external: false,
                                 }),
                                 artist,
                                 cover,
+                                palette,
                             )
                         } else {
-                            (None, String::new(), None)
+                            (None, String::new(), None, None)
                         };
 
                         state.playback().current_track().set(current_track);
                         state.playback().artist_name().set(artist_name);
                         state.playback().cover_url().set(cover_url);
+                        state.playback().palette().set(palette);
                     }
                     PlaybackProgress::PositionUpdate { position, .. } => {
                         state
@@ -268,38 +366,43 @@ impl AppService {
                     PlaybackProgress::QueueUpdated { tracks } => {
                         state.playback().queue().set(tracks.clone());
 
-                        // Load track/album details for queue items
-                        let mut queue_items = Vec::new();
-                        for track_id in &tracks {
-                            if let Ok(Some(track)) = library_manager.get().get_track(track_id).await
-                            {
-                                let (album_title, cover_url) = if let Ok(album_id) =
-                                    library_manager.get().get_album_id_for_track(track_id).await
+                        let queue_items = load_queue_items(&library_manager, &tracks).await;
+                        state.playback().queue_items().set(queue_items);
+                    }
+                    PlaybackProgress::HistoryUpdated { tracks } => {
+                        let history_items = load_queue_items(&library_manager, &tracks).await;
+                        state.playback().history_items().set(history_items);
+                    }
+                    PlaybackProgress::TrackCompleted { track_id }
+                        if playback::is_external_track(&track_id) => {}
+                    PlaybackProgress::TrackCompleted { track_id } => {
+                        match library_manager.get().record_track_play(&track_id).await {
+                            Ok(Some(db_album)) => {
+                                let album = album_from_db_ref(&db_album);
+                                if let Some(existing) = state
+                                    .library()
+                                    .albums()
+                                    .write()
+                                    .iter_mut()
+                                    .find(|a| a.id == album.id)
                                 {
-                                    if let Ok(Some(album)) =
-                                        library_manager.get().get_album_by_id(&album_id).await
-                                    {
-                                        let cover = album
-                                            .cover_image_id
-                                            .as_ref()
-                                            .map(|id| image_url(id))
-                                            .or(album.cover_art_url.clone());
-                                        (album.title, cover)
-                                    } else {
-                                        ("Unknown Album".to_string(), None)
-                                    }
-                                } else {
-                                    ("Unknown Album".to_string(), None)
-                                };
+                                    *existing = album.clone();
+                                }
 
-                                queue_items.push(QueueItem {
-                                    track: track_from_db_ref(&track),
-                                    album_title,
-                                    cover_url,
-                                });
+                                let mut detail_album = state.album_detail().album();
+                                let showing = detail_album
+                                    .read()
+                                    .as_ref()
+                                    .is_some_and(|a| a.id == album.id);
+                                if showing {
+                                    detail_album.set(Some(album));
+                                }
+                            }
+                            Ok(None) => {}
+                            Err(e) => {
+                                tracing::warn!("Failed to record play for {}: {}", track_id, e);
                             }
                         }
-                        state.playback().queue_items().set(queue_items);
                     }
                     PlaybackProgress::RepeatModeChanged { mode } => {
                         let ui_mode = match mode {
@@ -312,6 +415,18 @@ impl AppService {
                         #[cfg(target_os = "macos")]
                         crate::ui::window_activation::set_playback_repeat_mode(mode);
                     }
+                    PlaybackProgress::ShuffleChanged { enabled } => {
+                        state.playback().shuffle().set(enabled);
+                    }
+                    PlaybackProgress::PitchChanged { semitones } => {
+                        state.playback().pitch_semitones().set(semitones);
+                    }
+                    PlaybackProgress::OutputFormatChanged { format } => {
+                        state
+                            .playback()
+                            .output_format()
+                            .set(Some(output_format_to_display(&format)));
+                    }
                     _ => {}
                 }
             }
@@ -357,11 +472,37 @@ impl AppService {
             while let Some(event) = progress_rx.recv().await {
                 // Reload library when import completes
                 let should_reload = matches!(event, ImportProgress::Complete { .. });
+                // A finished release gets its DR scores measured
+                if let ImportProgress::Complete {
+                    id,
+                    release_id: None,
+                    ..
+                } = &event
+                {
+                    let release_id = id.clone();
+                    let library_manager = library_manager.clone();
+                    spawn(async move {
+                        match measure_release_dynamic_range(library_manager.get(), &release_id)
+                            .await
+                        {
+                            Ok(_) => {
+                                refresh_dynamic_range(&state, &library_manager, &release_id).await
+                            }
+                            Err(e) => {
+                                tracing::warn!("Failed to measure DR of {}: {}", release_id, e)
+                            }
+                        }
+                    });
+                }
 
+                if let Some(summary) = import_summary(&event) {
+                    crate::crash_report::set_context("import", summary);
+                }
                 handle_import_progress(&state, event);
 
                 if should_reload {
                     load_library(&state, &library_manager).await;
+                    refresh_storage_usage(&state, &library_manager).await;
                 }
             }
         });
@@ -378,6 +519,7 @@ impl AppService {
                 match event {
                     LibraryEvent::AlbumsChanged => {
                         load_library(&state, &library_manager).await;
+                        refresh_storage_usage(&state, &library_manager).await;
                     }
                 }
             }
@@ -400,6 +542,11 @@ impl AppService {
         self.load_active_imports();
         self.load_library();
         self.load_storage_profiles();
+        self.load_client_shares();
+        self.load_search_history();
+        self.load_release_templates();
+        self.load_verification_history();
+        self.load_snapshots();
     }
 
     /// Load config into Store
@@ -417,6 +564,140 @@ impl AppService {
             .config()
             .subsonic_port()
             .set(config.subsonic_port);
+        self.state
+            .config()
+            .subsonic_transcode_bitrate_kbps()
+            .set(config.subsonic_transcode_bitrate_kbps);
+        self.state
+            .config()
+            .remote_enabled()
+            .set(config.remote_enabled);
+        self.state.config().remote_port().set(config.remote_port);
+        self.state
+            .config()
+            .remote_token()
+            .set(config.remote_token.clone());
+        self.state
+            .config()
+            .playback_decode_ahead_ms()
+            .set(config.playback_decode_ahead_ms);
+        self.state
+            .config()
+            .playback_gapless_preroll_ms()
+            .set(config.playback_gapless_preroll_ms);
+        self.state
+            .config()
+            .playback_output_device()
+            .set(config.playback_output_device.clone());
+        self.state
+            .config()
+            .playback_output_bit_depth()
+            .set(Some(output_bit_depth_to_display(
+                config.playback_output_bit_depth,
+            )));
+        self.state
+            .config()
+            .playback_dither()
+            .set(Some(dither_to_display(config.playback_dither)));
+        self.state
+            .config()
+            .playback_exclusive_devices()
+            .set(config.playback_exclusive_devices.clone());
+        self.state
+            .config()
+            .playback_skip_silence()
+            .set(config.playback_skip_silence);
+        self.state
+            .config()
+            .playback_min_silence_ms()
+            .set(config.playback_min_silence_ms);
+        self.state
+            .config()
+            .playback_in_background()
+            .set(config.playback_in_background);
+        self.state.config().metered_mode().set(config.metered_mode);
+        self.state
+            .config()
+            .metered_allow_cloud_prefetch()
+            .set(config.metered_allow_cloud_prefetch);
+        self.state
+            .config()
+            .metered_allow_torrents()
+            .set(config.metered_allow_torrents);
+        self.state
+            .config()
+            .cd_insert_action()
+            .set(Some(cd_insert_action_to_display(config.cd_insert_action)));
+        self.state
+            .config()
+            .cd_eject_after_rip()
+            .set(config.cd_eject_after_rip);
+        self.state
+            .config()
+            .folder_patterns()
+            .set(config.folder_patterns.clone());
+        self.state
+            .config()
+            .low_power_import()
+            .set(config.low_power_import);
+        self.state
+            .config()
+            .max_concurrent_imports()
+            .set(config.max_concurrent_imports);
+        self.state.config().lastfm_username().set(
+            config
+                .lastfm_account
+                .as_ref()
+                .map(|account| account.username.clone()),
+        );
+        self.state
+            .config()
+            .listenbrainz_connected()
+            .set(config.listenbrainz_token.is_some());
+        self.state
+            .config()
+            .cloud_price_per_gb()
+            .set(config.cloud_price_per_gb);
+        self.state
+            .config()
+            .startup_view()
+            .set(Some(startup_view_to_display(config.startup_view)));
+        self.state
+            .config()
+            .library_sort()
+            .set(Some(library_sort_to_display(config.library_sort)));
+        self.state
+            .config()
+            .library_quality_filter()
+            .set(config.library_quality_filter.map(quality_from_db));
+        self.state
+            .config()
+            .verification_interval_days()
+            .set(config.verification_interval_days);
+        self.state
+            .config()
+            .snapshot_profile_id()
+            .set(config.snapshot_profile_id.clone());
+        self.state
+            .config()
+            .snapshot_interval_hours()
+            .set(config.snapshot_interval_hours);
+        self.state
+            .config()
+            .snapshots_kept()
+            .set(config.snapshots_kept);
+        self.state
+            .config()
+            .track_click_action()
+            .set(Some(track_click_action_to_display(
+                config.track_click_action,
+            )));
+        self.state
+            .config()
+            .featured_credit_style()
+            .set(Some(featured_credit_style_to_display(
+                config.featured_credit_style,
+            )));
         self.state
             .config()
             .torrent_bind_interface()
@@ -429,6 +710,18 @@ impl AppService {
             .config()
             .torrent_enable_upnp()
             .set(config.torrent_enable_upnp);
+        self.state
+            .config()
+            .torrent_enable_ipv6()
+            .set(config.torrent_enable_ipv6);
+        self.state
+            .config()
+            .torrent_enable_dht()
+            .set(config.torrent_enable_dht);
+        self.state
+            .config()
+            .torrent_dht_bootstrap_nodes()
+            .set(config.torrent_dht_bootstrap_nodes.clone());
         self.state
             .config()
             .torrent_max_connections()
@@ -463,11 +756,14 @@ impl AppService {
                             album_title: db.album_title,
                             artist_name: db.artist_name,
                             status: convert_import_status(db.status),
+                            kind: convert_import_kind(db.kind),
                             current_step: None,
                             progress_percent: None,
                             release_id: db.release_id,
                             cover_art_url: None,
                             cover_image_id: None,
+                            store_bytes_per_sec: None,
+                            error_message: db.error_message,
                         })
                         .collect();
                     state.active_imports().imports().set(imports);
@@ -480,6 +776,26 @@ impl AppService {
         });
     }
 
+    /// Load remembered manual search values from database
+    fn load_search_history(&self) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+
+        spawn(async move {
+            load_search_history(&state, &library_manager).await;
+        });
+    }
+
+    /// Load saved series templates from database
+    fn load_release_templates(&self) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+
+        spawn(async move {
+            load_release_templates(&state, &library_manager).await;
+        });
+    }
+
     /// Load library albums from database
     fn load_library(&self) {
         let state = self.state;
@@ -490,6 +806,26 @@ impl AppService {
         });
     }
 
+    /// Load every track of the library into the Store (called when opening the tracks view)
+    pub fn load_library_tracks(&self) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+
+        spawn(async move {
+            load_library_tracks(&state, &library_manager).await;
+        });
+    }
+
+    /// Load plays and most played tracks between `from` and `to` (exclusive)
+    pub fn load_listening_history(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+
+        spawn(async move {
+            load_listening_history(&state, &library_manager, from, to).await;
+        });
+    }
+
     // =========================================================================
     // Album Detail Methods
     // =========================================================================
@@ -506,146 +842,1407 @@ impl AppService {
         });
     }
 
-    // =========================================================================
-    // Config Methods
-    // =========================================================================
+    /// Request a restore of an archived release, then poll until it can stream.
+    /// The album detail banner follows along while the release is on screen.
+    pub fn request_release_restore(&self, release_id: &str) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        let release_id = release_id.to_string();
 
-    /// Update config and save to disk
-    pub fn save_config(&self, updater: impl FnOnce(&mut config::Config)) {
-        // Clone current config, apply update, save to disk, update Store
-        let mut new_config = self.config.clone();
-        updater(&mut new_config);
+        spawn(async move {
+            if let Err(e) = library_manager
+                .get()
+                .request_release_restore(&release_id)
+                .await
+            {
+                tracing::error!("Failed to request restore for {}: {}", release_id, e);
+                return;
+            }
 
-        // Save to disk
-        if let Err(e) = new_config.save() {
-            tracing::error!("Failed to save config: {}", e);
-            return;
-        }
+            set_archive_status_if_selected(&state, &release_id, Some(ArchiveStatus::Restoring));
+            loop {
+                tokio::time::sleep(ARCHIVE_RESTORE_POLL_INTERVAL).await;
+                match library_manager
+                    .get()
+                    .get_release_archive_status(&release_id)
+                    .await
+                {
+                    Ok(Some(status)) if !status.is_readable() => continue,
+                    Ok(status) => {
+                        tracing::info!("Release {} restored and ready to stream", release_id);
 
-        // Update Store
-        self.state
-            .config()
-            .discogs_api_key()
-            .set(new_config.discogs_api_key.clone());
-        self.state
-            .config()
-            .subsonic_enabled()
-            .set(new_config.subsonic_enabled);
-        self.state
-            .config()
-            .subsonic_port()
-            .set(new_config.subsonic_port);
-        self.state
-            .config()
-            .torrent_bind_interface()
-            .set(new_config.torrent_bind_interface.clone());
-        self.state
-            .config()
-            .torrent_listen_port()
-            .set(new_config.torrent_listen_port);
-        self.state
-            .config()
-            .torrent_enable_upnp()
-            .set(new_config.torrent_enable_upnp);
-        self.state
-            .config()
-            .torrent_max_connections()
-            .set(new_config.torrent_max_connections);
-        self.state
-            .config()
-            .torrent_max_connections_per_torrent()
-            .set(new_config.torrent_max_connections_per_torrent);
-        self.state
-            .config()
-            .torrent_max_uploads()
-            .set(new_config.torrent_max_uploads);
-        self.state
-            .config()
-            .torrent_max_uploads_per_torrent()
-            .set(new_config.torrent_max_uploads_per_torrent);
+                        set_archive_status_if_selected(&state, &release_id, status);
+                        break;
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to check restore of {}: {}", release_id, e);
+                    }
+                }
+            }
+        });
     }
 
-    // =========================================================================
-    // Storage Profile Methods
-    // =========================================================================
-
-    /// Load storage profiles from database into Store
-    pub fn load_storage_profiles(&self) {
+    /// Copy a release indexed from a reference profile into the default
+    /// profile, then reload the page so it shows the managed copy
+    pub fn migrate_release(&self, release_id: &str) {
         let state = self.state;
         let library_manager = self.library_manager.clone();
+        let release_id = release_id.to_string();
 
         spawn(async move {
-            state.storage_profiles().loading().set(true);
-            state.storage_profiles().error().set(None);
+            let target = match library_manager.get().get_default_storage_profile().await {
+                Ok(Some(profile)) if profile.location != StorageLocation::Reference => profile,
+                Ok(_) => return,
+                Err(e) => {
+                    tracing::error!("Failed to load default storage profile: {}", e);
+                    return;
+                }
+            };
+            update_reference_if_selected(&state, &release_id, |reference| {
+                reference.migrating = true;
+                reference.error = None;
+            });
 
-            match library_manager.get_all_storage_profiles().await {
-                Ok(db_profiles) => {
-                    let profiles = db_profiles.iter().map(storage_profile_from_db).collect();
-                    state.storage_profiles().profiles().set(profiles);
+            let result = library_manager
+                .get()
+                .migrate_release(&release_id, &target.id)
+                .await;
+            match result {
+                Ok(()) => {
+                    tracing::info!("Moved release {} to '{}'", release_id, target.name);
+                    let selected = state.album_detail().selected_release_id().read().clone();
+                    if selected.as_deref() != Some(release_id.as_str()) {
+                        return;
+                    }
+                    if let Ok(album_id) = library_manager
+                        .get()
+                        .get_album_id_for_release(&release_id)
+                        .await
+                    {
+                        load_album_detail(&state, &library_manager, &album_id, Some(&release_id))
+                            .await;
+                    }
                 }
                 Err(e) => {
-                    state
-                        .storage_profiles()
-                        .error()
-                        .set(Some(format!("Failed to load storage profiles: {}", e)));
+                    tracing::error!("Failed to move release {}: {}", release_id, e);
+                    update_reference_if_selected(&state, &release_id, |reference| {
+                        reference.migrating = false;
+                        reference.error = Some(format!("Failed to move: {}", e));
+                    });
                 }
             }
-
-            state.storage_profiles().loading().set(false);
         });
     }
 
-    /// Save (create or update) a storage profile
-    pub fn save_storage_profile(&self, profile: StorageProfile) {
+    /// Compare two editions of the open album. Track lists and formats show
+    /// right away; loudness fills in per edition once its audio is decoded.
+    pub fn compare_releases(&self, left_release_id: &str, right_release_id: &str) {
         let state = self.state;
         let library_manager = self.library_manager.clone();
-        let is_new = profile.id.is_empty();
+        let left_release_id = left_release_id.to_string();
+        let right_release_id = right_release_id.to_string();
 
         spawn(async move {
-            let result = if is_new {
-                let location = storage_location_from_display(profile.location);
-                let db_profile = if location == StorageLocation::Local {
-                    DbStorageProfile::new_local(
-                        &profile.name,
-                        &profile.location_path,
-                        profile.encrypted,
-                    )
-                } else {
-                    DbStorageProfile::new_cloud(
-                        &profile.name,
-                        profile.cloud_bucket.as_deref().unwrap_or(""),
-                        profile.cloud_region.as_deref().unwrap_or(""),
-                        profile.cloud_endpoint.as_deref(),
-                        profile.cloud_access_key.as_deref().unwrap_or(""),
-                        profile.cloud_secret_key.as_deref().unwrap_or(""),
-                        profile.encrypted,
-                    )
+            let comparison = match compare::compare_releases(
+                library_manager.get(),
+                &left_release_id,
+                &right_release_id,
+            )
+            .await
+            {
+                Ok(comparison) => comparison,
+                Err(e) => {
+                    tracing::error!("Failed to compare releases: {}", e);
+                    return;
                 }
-                .with_default(profile.is_default);
-                library_manager.insert_storage_profile(&db_profile).await
-            } else {
-                let mut db_profile = DbStorageProfile {
-                    id: profile.id.clone(),
-                    name: profile.name.clone(),
-                    location: storage_location_from_display(profile.location),
-                    location_path: profile.location_path.clone(),
-                    encrypted: profile.encrypted,
-                    is_default: profile.is_default,
-                    cloud_bucket: profile.cloud_bucket.clone(),
-                    cloud_region: profile.cloud_region.clone(),
-                    cloud_endpoint: profile.cloud_endpoint.clone(),
-                    cloud_access_key: profile.cloud_access_key.clone(),
+            };
+            let mut comparison_store = state.album_detail().comparison();
+            comparison_store.set(Some(release_comparison_from_core(&comparison)));
+
+            for release_id in [left_release_id, right_release_id] {
+                let loudness =
+                    match compare::measure_release_loudness(library_manager.get(), &release_id)
+                        .await
+                    {
+                        Ok(loudness) => loudness,
+                        Err(e) => {
+                            tracing::warn!("Failed to measure loudness of {}: {}", release_id, e);
+                            None
+                        }
+                    };
+                let loudness = loudness_to_display(loudness);
+
+                // The user may have picked another pair meanwhile; a release
+                // still on screen gets its measurement either way
+                comparison_store.with_mut(|comparison| {
+                    let Some(comparison) = comparison else {
+                        return;
+                    };
+                    for edition in [&mut comparison.left, &mut comparison.right] {
+                        if edition.release_id == release_id {
+                            edition.loudness = loudness.clone();
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    /// Mark the edition an album plays by default, or clear it with None
+    pub fn set_preferred_release(&self, album_id: &str, release_id: Option<String>) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        let album_id = album_id.to_string();
+
+        spawn(async move {
+            match library_manager
+                .get()
+                .set_preferred_release(&album_id, release_id.as_deref())
+                .await
+            {
+                Ok(()) => {
+                    let is_open = state.album_detail().album().read().as_ref().map(|a| &a.id)
+                        == Some(&album_id);
+                    if is_open {
+                        state.album_detail().preferred_release_id().set(release_id);
+                    }
+                }
+                Err(e) => tracing::error!("Failed to set preferred release: {}", e),
+            }
+        });
+    }
+
+    /// Switch the open album's page into edit mode
+    pub fn start_album_edit(&self) {
+        self.state.album_detail().edit_error().set(None);
+        self.state.album_detail().editing().set(true);
+    }
+
+    pub fn cancel_album_edit(&self) {
+        self.state.album_detail().edit_error().set(None);
+        self.state.album_detail().editing().set(false);
+    }
+
+    /// Save what was changed in edit mode. The save undoes as one step.
+    pub fn save_album_edits(&self, edit: bae_ui::AlbumEdit) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        let edits = album_edits_from_display(&edit);
+
+        spawn(async move {
+            match library_manager.get().save_album_edits(&edits).await {
+                Ok(changes) => {
+                    state.album_detail().edit_error().set(None);
+                    state.album_detail().editing().set(false);
+                    let release_id = state.album_detail().selected_release_id().read().clone();
+                    load_album_detail(
+                        &state,
+                        &library_manager,
+                        &edits.album_id,
+                        release_id.as_deref(),
+                    )
+                    .await;
+                    if !changes.is_empty() {
+                        let message = match changes.len() {
+                            1 => "Saved 1 change".to_string(),
+                            n => format!("Saved {} changes", n),
+                        };
+                        state.undo().message().set(Some(message));
+                        state.undo().offers_redo().set(false);
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to save album edits: {}", e);
+                    state.album_detail().edit_error().set(Some(e.to_string()));
+                }
+            }
+        });
+    }
+
+    // =========================================================================
+    // Undo Methods
+    // =========================================================================
+
+    /// Revert the last metadata edit made this session
+    pub fn undo(&self) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        spawn(async move {
+            match library_manager.get().undo().await {
+                Ok(Some(changes)) => {
+                    for change in &changes {
+                        apply_change_to_state(&state, change, change.before.as_ref());
+                    }
+                    reload_album_if_edited(&state, &library_manager, &changes).await;
+                    state
+                        .undo()
+                        .message()
+                        .set(Some(undo_message(&changes, true)));
+                    state.undo().offers_redo().set(true);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::error!("Failed to undo: {}", e);
+                    state
+                        .undo()
+                        .message()
+                        .set(Some(format!("Undo failed: {}", e)));
+                    state.undo().offers_redo().set(false);
+                }
+            }
+        });
+    }
+
+    /// Apply the last undone edit again
+    pub fn redo(&self) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        spawn(async move {
+            match library_manager.get().redo().await {
+                Ok(Some(changes)) => {
+                    for change in &changes {
+                        apply_change_to_state(&state, change, change.after.as_ref());
+                    }
+                    reload_album_if_edited(&state, &library_manager, &changes).await;
+                    state
+                        .undo()
+                        .message()
+                        .set(Some(undo_message(&changes, false)));
+                    state.undo().offers_redo().set(false);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::error!("Failed to redo: {}", e);
+                    state
+                        .undo()
+                        .message()
+                        .set(Some(format!("Redo failed: {}", e)));
+                    state.undo().offers_redo().set(false);
+                }
+            }
+        });
+    }
+
+    pub fn dismiss_undo_toast(&self) {
+        self.state.undo().message().set(None);
+    }
+
+    // =========================================================================
+    // Find & Replace Methods
+    // =========================================================================
+
+    /// Work out every value a find-and-replace would change, for review
+    pub fn preview_find_replace(&self, request: bae_ui::FindReplaceRequest) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        spawn(async move {
+            let spec = find_replace_from_display(&request);
+            match preview_find_replace(library_manager.get(), &request.album_ids, &spec).await {
+                Ok(rows) => {
+                    state
+                        .library()
+                        .find_replace_preview()
+                        .set(Some(rows.iter().map(find_replace_row_to_display).collect()));
+                    state.library().find_replace_error().set(None);
+                }
+                Err(e) => {
+                    state.library().find_replace_preview().set(None);
+                    state
+                        .library()
+                        .find_replace_error()
+                        .set(Some(e.to_string()));
+                }
+            }
+        });
+    }
+
+    /// Write a reviewed find-and-replace preview. The toast offers to undo
+    /// the whole batch.
+    pub fn apply_find_replace(&self, rows: Vec<bae_ui::FindReplaceRow>) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        spawn(async move {
+            let rows: Vec<_> = rows.iter().map(find_replace_row_from_display).collect();
+            state.library().find_replace_preview().set(None);
+            match library_manager.get().apply_replacements(&rows).await {
+                Ok(changes) => {
+                    for change in &changes {
+                        apply_change_to_state(&state, change, change.after.as_ref());
+                    }
+                    let message = match changes.len() {
+                        1 => "Changed 1 value".to_string(),
+                        n => format!("Changed {} values", n),
+                    };
+                    state.undo().message().set(Some(message));
+                }
+                Err(e) => {
+                    tracing::error!("Find and replace failed: {}", e);
+                    // Rows written before the failure can still be undone
+                    state
+                        .undo()
+                        .message()
+                        .set(Some(format!("Find and replace stopped: {}", e)));
+                }
+            }
+            state.undo().offers_redo().set(false);
+        });
+    }
+
+    /// Drop a find-and-replace preview that no longer matches the form
+    pub fn reset_find_replace(&self) {
+        self.state.library().find_replace_preview().set(None);
+        self.state.library().find_replace_error().set(None);
+    }
+
+    // =========================================================================
+    // Bookmark Methods
+    // =========================================================================
+
+    /// Play a track from a bookmarked position, seeking in place if it's
+    /// already the current track
+    pub fn jump_to_bookmark(&self, track_id: &str, position_ms: u64) {
+        let is_current =
+            self.state.playback().current_track_id().read().as_deref() == Some(track_id);
+        if !is_current {
+            self.playback_handle.play(track_id.to_string());
+        }
+        // Commands run in order, so this seeks the track started above
+        self.playback_handle
+            .seek(std::time::Duration::from_millis(position_ms));
+    }
+
+    /// Bookmark the current playback position
+    pub fn add_bookmark(&self, name: &str) {
+        let Some(track_id) = self.state.playback().current_track_id().read().clone() else {
+            return;
+        };
+        let position_ms = *self.state.playback().position_ms().read();
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        let name = name.to_string();
+
+        spawn(async move {
+            match library_manager
+                .get()
+                .add_bookmark(&track_id, &name, position_ms as i64)
+                .await
+            {
+                Ok(bookmark) => {
+                    let bookmark = bookmark_from_db(&bookmark);
+                    update_bookmarks(&state, |track| {
+                        if track.id == bookmark.track_id {
+                            track.bookmarks.push(bookmark.clone());
+                            track.bookmarks.sort_by_key(|b| b.position_ms);
+                        }
+                    });
+                }
+                Err(e) => tracing::error!("Failed to add bookmark: {}", e),
+            }
+        });
+    }
+
+    pub fn delete_bookmark(&self, bookmark_id: &str) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        let bookmark_id = bookmark_id.to_string();
+
+        spawn(async move {
+            if let Err(e) = library_manager.get().delete_bookmark(&bookmark_id).await {
+                tracing::error!("Failed to delete bookmark: {}", e);
+                return;
+            }
+            update_bookmarks(&state, |track| {
+                track.bookmarks.retain(|b| b.id != bookmark_id)
+            });
+        });
+    }
+
+    // =========================================================================
+    // Library Verification Methods
+    // =========================================================================
+
+    /// Load recent verification runs into Store
+    fn load_verification_history(&self) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+
+        spawn(async move {
+            load_verification_history(&state, &library_manager).await;
+        });
+    }
+
+    /// Check periodically whether a scheduled verification run is due
+    fn start_verification_schedule(&self) {
+        let app = self.clone();
+
+        spawn(async move {
+            // Leave startup alone before reading the whole library back
+            tokio::time::sleep(VERIFICATION_STARTUP_DELAY).await;
+            loop {
+                let interval_days = *app.state.config().verification_interval_days().read();
+                if let Some(days) = interval_days {
+                    match app.library_manager.get().get_verification_runs(1).await {
+                        Ok(runs) => {
+                            if verification_due(runs.first(), days, chrono::Utc::now()) {
+                                app.run_verification(true).await;
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to check last verification run: {}", e);
+                        }
+                    }
+                }
+                tokio::time::sleep(VERIFICATION_SCHEDULE_CHECK_INTERVAL).await;
+            }
+        });
+    }
+
+    /// Load the library's snapshots from its snapshot index into Store
+    fn load_snapshots(&self) {
+        let index = SnapshotIndex::load(&self.config.get_library_path());
+        self.state.snapshots().snapshots().set(
+            index
+                .snapshots
+                .iter()
+                .map(library_snapshot_from_core)
+                .collect(),
+        );
+    }
+
+    /// Check periodically whether a scheduled library snapshot is due
+    fn start_snapshot_schedule(&self) {
+        let app = self.clone();
+
+        spawn(async move {
+            tokio::time::sleep(SNAPSHOT_STARTUP_DELAY).await;
+            loop {
+                let interval_hours = *app.state.config().snapshot_interval_hours().read();
+                let profile_id = app.state.config().snapshot_profile_id().read().clone();
+                // A library that replaced a damaged one is empty or being
+                // restored; snapshotting it would push good snapshots out
+                let damaged = app.state.snapshots().damaged_database().read().is_some();
+                if let (Some(hours), Some(profile_id)) = (interval_hours, profile_id) {
+                    let index = SnapshotIndex::load(&app.config.get_library_path());
+                    let last = index.last_snapshot_at(&profile_id);
+                    if !damaged && snapshot_due(last, hours, Utc::now()) {
+                        app.run_snapshot().await;
+                    }
+                }
+                tokio::time::sleep(SNAPSHOT_SCHEDULE_CHECK_INTERVAL).await;
+            }
+        });
+    }
+
+    /// Measure DR for releases imported before it was measured, once per launch
+    fn start_dynamic_range_backfill(&self) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+
+        spawn(async move {
+            tokio::time::sleep(DYNAMIC_RANGE_STARTUP_DELAY).await;
+            match backfill_dynamic_range(library_manager.get(), DYNAMIC_RANGE_PACE).await {
+                Ok(0) => {}
+                Ok(_) => {
+                    let selected = state.album_detail().selected_release_id().read().clone();
+                    if let Some(release_id) = selected {
+                        refresh_dynamic_range(&state, &library_manager, &release_id).await;
+                    }
+                }
+                Err(e) => tracing::warn!("DR backfill failed: {}", e),
+            }
+        });
+    }
+
+    /// Pin offline albums in the cache again, since pins don't outlive the
+    /// app, and finish any downloads the last run didn't
+    fn start_offline_restore(&self) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        let cache = self.cache.clone();
+
+        spawn(async move {
+            let album_ids = match offline::offline_album_ids(library_manager.get()).await {
+                Ok(album_ids) => album_ids,
+                Err(e) => {
+                    tracing::warn!("Failed to load offline albums: {}", e);
+                    return;
+                }
+            };
+            for album_id in album_ids {
+                download_album_offline(&state, &library_manager, &cache, &album_id).await;
+            }
+        });
+    }
+
+    /// Keep an album offline, downloading its cloud files into the cache, or
+    /// drop its offline copy
+    pub fn set_album_offline(&self, album_id: &str, keep: bool) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        let cache = self.cache.clone();
+        let album_id = album_id.to_string();
+
+        spawn(async move {
+            if keep {
+                set_album_offline_flag(&state, &album_id, true);
+                download_album_offline(&state, &library_manager, &cache, &album_id).await;
+                return;
+            }
+            match offline::remove_album_offline(library_manager.get(), &cache, &album_id).await {
+                Ok(()) => {
+                    state.library().offline_downloads().with_mut(|downloads| {
+                        downloads.remove(&album_id);
+                    });
+                    set_album_offline_flag(&state, &album_id, false);
+                }
+                Err(e) => tracing::error!("Failed to remove offline copy: {}", e),
+            }
+        });
+    }
+
+    /// Fetch cover thumbnails for the match list on screen, replacing the
+    /// fetches for the last one
+    pub fn fetch_cover_thumbnails(&self, release_ids: Vec<String>) {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        self.cover_fetcher.fetch(release_ids, tx);
+
+        let state = self.state;
+        spawn(async move {
+            while let Some(thumbnail) = rx.recv().await {
+                state.import().write().set_cover_thumbnail(
+                    &thumbnail.release_id,
+                    &local_file_url(&thumbnail.path),
+                    &front_cover_url(&thumbnail.release_id),
+                );
+            }
+        });
+    }
+
+    /// Start a verification run now, unless one is already going
+    pub fn verify_library_now(&self) {
+        let app = self.clone();
+        spawn(async move {
+            app.run_verification(false).await;
+        });
+    }
+
+    /// Repair an issue from a verification run, one at a time
+    pub fn repair_verification_issue(&self, issue_id: String) {
+        if self
+            .state
+            .verification()
+            .repairing_issue_id()
+            .read()
+            .is_some()
+        {
+            return;
+        }
+        self.state
+            .verification()
+            .repairing_issue_id()
+            .set(Some(issue_id.clone()));
+        self.state.verification().repair_error().set(None);
+
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        spawn(async move {
+            if let Err(e) = repair_issue(library_manager.get(), &issue_id).await {
+                tracing::error!("Failed to repair verification issue {}: {}", issue_id, e);
+                state.verification().repair_error().set(Some(e.to_string()));
+            }
+            load_verification_history(&state, &library_manager).await;
+            state.verification().repairing_issue_id().set(None);
+        });
+    }
+
+    /// Take a library snapshot now, unless one is already uploading
+    pub fn snapshot_library_now(&self) {
+        let app = self.clone();
+        spawn(async move {
+            app.run_snapshot().await;
+        });
+    }
+
+    /// Download a snapshot to replace the damaged database at the next launch
+    pub fn restore_snapshot(&self, snapshot: LibrarySnapshot) {
+        if *self.state.snapshots().is_restoring().read() {
+            return;
+        }
+        self.state.snapshots().is_restoring().set(true);
+        self.state.snapshots().restore_error().set(None);
+
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        let library_path = self.config.get_library_path();
+        spawn(async move {
+            let result =
+                stage_snapshot_restore(&library_manager, &library_path, &snapshot.location).await;
+            match result {
+                Ok(()) => state.snapshots().restore_staged().set(true),
+                Err(e) => {
+                    tracing::error!("Failed to restore snapshot: {}", e);
+                    state.snapshots().restore_error().set(Some(e));
+                }
+            }
+            state.snapshots().is_restoring().set(false);
+        });
+    }
+
+    /// Start a new copy of bae and quit this one, so a staged restore is
+    /// swapped in
+    pub fn restart_app(&self) {
+        match std::env::current_exe().and_then(|exe| std::process::Command::new(exe).spawn()) {
+            Ok(_) => std::process::exit(0),
+            Err(e) => {
+                tracing::error!("Failed to restart: {}", e);
+                self.state.snapshots().restore_error().set(Some(
+                    "Couldn't restart bae. Quit and open it again.".to_string(),
+                ));
+            }
+        }
+    }
+
+    /// Normalize every album's artist credits again with the current rules
+    pub fn renormalize_artist_credits(&self) {
+        if *self.state.maintenance().is_renormalizing_credits().read() {
+            return;
+        }
+        self.state
+            .maintenance()
+            .is_renormalizing_credits()
+            .set(true);
+
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        // Saved settings reach the import handle, not `self.config`
+        let rules = self.import_handle.credit_rules();
+        spawn(async move {
+            let result = renormalize_library_credits(library_manager.get(), &rules).await;
+            let message = match result {
+                Ok(0) => "All credits already match the rules".to_string(),
+                Ok(1) => "Updated credits on 1 album".to_string(),
+                Ok(count) => format!("Updated credits on {} albums", count),
+                Err(e) => {
+                    tracing::error!("Failed to re-normalize artist credits: {}", e);
+                    format!("Failed: {}", e)
+                }
+            };
+            state.maintenance().credits_result().set(Some(message));
+            state.maintenance().is_renormalizing_credits().set(false);
+        });
+    }
+
+    /// Compact the library database, unless a compaction is already running
+    pub fn compact_database(&self) {
+        if *self.state.maintenance().is_compacting_database().read() {
+            return;
+        }
+        self.state.maintenance().is_compacting_database().set(true);
+
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        spawn(async move {
+            let message = match library_manager.get().compact_database().await {
+                Ok(()) => "Database compacted".to_string(),
+                Err(e) => {
+                    tracing::error!("Failed to compact database: {}", e);
+                    format!("Failed: {}", e)
+                }
+            };
+            state.maintenance().database_result().set(Some(message));
+            state.maintenance().is_compacting_database().set(false);
+        });
+    }
+
+    /// Remove every cached file that isn't pinned by a running download
+    pub fn clear_cache(&self) {
+        if *self.state.maintenance().is_clearing_cache().read() {
+            return;
+        }
+        self.state.maintenance().is_clearing_cache().set(true);
+
+        let state = self.state;
+        let cache = self.cache.clone();
+        spawn(async move {
+            let message = match cache.clear().await {
+                Ok((0, _)) => "Cache is already empty".to_string(),
+                Ok((count, bytes)) => format!(
+                    "Removed {} files ({})",
+                    count,
+                    bae_ui::format_file_size(bytes as i64)
+                ),
+                Err(e) => {
+                    tracing::error!("Failed to clear cache: {}", e);
+                    format!("Failed: {}", e)
+                }
+            };
+            state.maintenance().cache_result().set(Some(message));
+            state.maintenance().is_clearing_cache().set(false);
+        });
+    }
+
+    /// Check every subsystem and show the results in the health panel
+    pub fn check_service_health(&self) {
+        if *self.state.health().is_checking().read() {
+            return;
+        }
+        self.state.health().is_checking().set(true);
+
+        let app = self.clone();
+        spawn(async move {
+            for kind in ServiceKind::ALL {
+                if let Some(result) = app.check_service(kind).await {
+                    app.health.record(kind, result, Utc::now());
+                }
+            }
+            app.publish_health();
+            app.state.health().is_checking().set(false);
+        });
+    }
+
+    /// Restart one subsystem without restarting bae, then check it again
+    pub fn restart_service(&self, kind: bae_ui::ServiceKind) {
+        if self.state.health().restarting().read().is_some() {
+            return;
+        }
+        self.state.health().restarting().set(Some(kind));
+
+        let kind = service_kind_from_display(kind);
+        let app = self.clone();
+        spawn(async move {
+            let restarted = match kind {
+                ServiceKind::Cache => app.cache.reload().await.map_err(|e| e.to_string()),
+                ServiceKind::PlaybackOutput => {
+                    app.playback_handle.reopen_output();
+                    tokio::time::sleep(OUTPUT_REOPEN_WAIT).await;
+                    Ok(())
+                }
+                // Cloud clients are created per use, so checking again
+                // is reconnecting
+                _ => Ok(()),
+            };
+            let result = match restarted {
+                Ok(()) => app.check_service(kind).await,
+                Err(e) => Some(Err(e)),
+            };
+            if let Some(result) = result {
+                let now = Utc::now();
+                let running = result == Ok(ServiceStatus::Running);
+                app.health.record(kind, result, now);
+                if running {
+                    app.health.restarted(kind, now);
+                }
+            }
+            app.publish_health();
+            app.state.health().restarting().set(None);
+        });
+    }
+
+    /// Check one subsystem. None if it isn't part of this build.
+    async fn check_service(&self, kind: ServiceKind) -> Option<CheckResult> {
+        let result = match kind {
+            ServiceKind::Database => self
+                .library_manager
+                .get()
+                .database()
+                .ping()
+                .await
+                .map(|()| ServiceStatus::Running)
+                .map_err(|e| e.to_string()),
+            ServiceKind::Cache => self
+                .cache
+                .check()
+                .await
+                .map(|()| ServiceStatus::Running)
+                .map_err(|e| e.to_string()),
+            ServiceKind::CloudStorage => self.check_cloud_profiles().await,
+            #[cfg(feature = "torrent")]
+            ServiceKind::Torrent => Ok(if self.torrent_manager.is_started() {
+                ServiceStatus::Running
+            } else {
+                ServiceStatus::NotStarted
+            }),
+            #[cfg(not(feature = "torrent"))]
+            ServiceKind::Torrent => return None,
+            ServiceKind::PlaybackOutput => match self.playback_handle.output_error() {
+                None => Ok(ServiceStatus::Running),
+                Some(e) => Err(e),
+            },
+            // Dev mode reads secrets from .env instead
+            ServiceKind::Keyring if config::Config::is_dev_mode() => Ok(ServiceStatus::NotStarted),
+            ServiceKind::Keyring => tokio::task::spawn_blocking(config::check_keyring)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| result.map_err(|e| e.to_string()))
+                .map(|()| ServiceStatus::Running),
+        };
+        Some(result)
+    }
+
+    /// Check every cloud profile's bucket. Not in use without cloud profiles.
+    async fn check_cloud_profiles(&self) -> CheckResult {
+        let profiles = self
+            .library_manager
+            .get()
+            .get_all_storage_profiles()
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut saw_cloud = false;
+        let mut failures = Vec::new();
+        for profile in profiles
+            .iter()
+            .filter(|p| p.location == StorageLocation::Cloud)
+        {
+            saw_cloud = true;
+            if let Err(e) = storage::check_cloud_storage(profile).await {
+                failures.push(format!("{}: {}", profile.name, e));
+            }
+        }
+        match (saw_cloud, failures.is_empty()) {
+            (false, _) => Ok(ServiceStatus::NotStarted),
+            (true, true) => Ok(ServiceStatus::Running),
+            (true, false) => Err(failures.join("; ")),
+        }
+    }
+
+    fn publish_health(&self) {
+        let now = Utc::now();
+        self.state.health().services().set(
+            self.health
+                .snapshot()
+                .iter()
+                .map(|service| service_health_to_display(service, now))
+                .collect(),
+        );
+    }
+
+    async fn run_verification(&self, scheduled: bool) {
+        if *self.state.verification().is_running().read() {
+            return;
+        }
+        self.state.verification().is_running().set(true);
+
+        let library_manager = self.library_manager.get();
+        if let Err(e) = verify_library(
+            library_manager,
+            library_manager.encryption_service(),
+            scheduled,
+            VERIFICATION_PACE,
+        )
+        .await
+        {
+            tracing::error!("Library verification failed: {}", e);
+        }
+
+        self.state.verification().is_running().set(false);
+        load_verification_history(&self.state, &self.library_manager).await;
+    }
+
+    async fn run_snapshot(&self) {
+        if *self.state.snapshots().is_snapshotting().read() {
+            return;
+        }
+        let Some(profile_id) = self.state.config().snapshot_profile_id().read().clone() else {
+            return;
+        };
+        let keep = *self.state.config().snapshots_kept().read();
+        self.state.snapshots().is_snapshotting().set(true);
+
+        let message = match self.take_snapshot(&profile_id, keep).await {
+            Ok(()) => "Snapshot uploaded".to_string(),
+            Err(e) => {
+                tracing::error!("Library snapshot failed: {}", e);
+                format!("Failed: {}", e)
+            }
+        };
+
+        self.state.snapshots().snapshot_result().set(Some(message));
+        self.state.snapshots().is_snapshotting().set(false);
+        self.load_snapshots();
+    }
+
+    async fn take_snapshot(&self, profile_id: &str, keep: u32) -> Result<(), String> {
+        let library_manager = self.library_manager.get();
+        let encryption_service = library_manager
+            .encryption_service()
+            .ok_or("Snapshots need an encryption key")?;
+        let profile = library_manager
+            .database()
+            .get_storage_profile(profile_id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("The snapshot storage profile no longer exists")?;
+        let storage = storage::create_cloud_storage(&profile)
+            .await
+            .map_err(|e| e.to_string())?;
+        snapshot_library(
+            library_manager.database(),
+            encryption_service,
+            storage.as_ref(),
+            &profile,
+            &self.config.get_library_path(),
+            &self.config.library_id,
+            keep,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    // =========================================================================
+    // Seeding Methods
+    // =========================================================================
+
+    /// Load the torrents kept for seeding into the store
+    #[cfg(feature = "torrent")]
+    pub fn load_seeding(&self) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        spawn(async move {
+            load_seeding_torrents(&state, &library_manager).await;
+        });
+    }
+
+    /// Stop seeding a release until it's resumed
+    #[cfg(feature = "torrent")]
+    pub fn pause_seeding(&self, release_id: String) {
+        let torrent_manager = self.torrent_manager.clone();
+        self.run_seeding_change(async move {
+            torrent_manager
+                .get()
+                .stop_seeding(release_id)
+                .await
+                .map_err(|e| format!("Failed to pause seeding: {}", e))
+        });
+    }
+
+    /// Seed a paused release again
+    #[cfg(feature = "torrent")]
+    pub fn resume_seeding(&self, release_id: String) {
+        let torrent_manager = self.torrent_manager.clone();
+        self.run_seeding_change(async move {
+            torrent_manager
+                .get()
+                .start_seeding(release_id)
+                .await
+                .map_err(|e| format!("Failed to resume seeding: {}", e))
+        });
+    }
+
+    /// Stop seeding a release and delete its kept download
+    #[cfg(feature = "torrent")]
+    pub fn remove_seed(&self, release_id: String) {
+        let torrent_manager = self.torrent_manager.clone();
+        self.run_seeding_change(async move {
+            torrent_manager
+                .get()
+                .remove_seed(release_id)
+                .await
+                .map_err(|e| format!("Failed to remove seed: {}", e))
+        });
+    }
+
+    /// Set the ratio and seed time goals for a release (None = no goal)
+    #[cfg(feature = "torrent")]
+    pub fn set_seed_goals(
+        &self,
+        release_id: String,
+        ratio_goal: Option<f64>,
+        time_goal_hours: Option<u32>,
+    ) {
+        let library_manager = self.library_manager.clone();
+        self.run_seeding_change(async move {
+            let library_manager = library_manager.get();
+            let torrent = library_manager
+                .get_torrent_by_release(&release_id)
+                .await
+                .map_err(|e| format!("Failed to save goals: {}", e))?
+                .ok_or_else(|| "Failed to save goals: torrent not found".to_string())?;
+            library_manager
+                .set_torrent_seed_goals(
+                    &torrent.id,
+                    ratio_goal,
+                    time_goal_hours.map(|hours| hours as i64 * 3600),
+                )
+                .await
+                .map_err(|e| format!("Failed to save goals: {}", e))
+        });
+    }
+
+    /// Run a seeding change, then show its error (if any) and reload
+    #[cfg(feature = "torrent")]
+    fn run_seeding_change(
+        &self,
+        change: impl std::future::Future<Output = Result<(), String>> + 'static,
+    ) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        spawn(async move {
+            let error = change.await.err();
+            if let Some(ref error) = error {
+                tracing::error!("{}", error);
+            }
+            state.seeding().error().set(error);
+            load_seeding_torrents(&state, &library_manager).await;
+        });
+    }
+
+    // =========================================================================
+    // Config Methods
+    // =========================================================================
+
+    /// Update config and save to disk
+    pub fn save_config(&self, updater: impl FnOnce(&mut config::Config)) {
+        // Clone current config, apply update, save to disk, update Store
+        let mut new_config = self.config.clone();
+        // Unpairing replaces the token after launch, so start from the live one
+        new_config.remote_token = self.remote_access.token();
+        updater(&mut new_config);
+
+        // Save to disk
+        if let Err(e) = new_config.save() {
+            tracing::error!("Failed to save config: {}", e);
+            return;
+        }
+
+        // Update Store
+        self.state
+            .config()
+            .discogs_api_key()
+            .set(new_config.discogs_api_key.clone());
+        self.state
+            .config()
+            .subsonic_enabled()
+            .set(new_config.subsonic_enabled);
+        self.state
+            .config()
+            .subsonic_port()
+            .set(new_config.subsonic_port);
+        self.state
+            .config()
+            .subsonic_transcode_bitrate_kbps()
+            .set(new_config.subsonic_transcode_bitrate_kbps);
+        self.state
+            .config()
+            .remote_enabled()
+            .set(new_config.remote_enabled);
+        self.state
+            .config()
+            .remote_port()
+            .set(new_config.remote_port);
+        self.state
+            .config()
+            .remote_token()
+            .set(new_config.remote_token.clone());
+        self.state
+            .config()
+            .playback_decode_ahead_ms()
+            .set(new_config.playback_decode_ahead_ms);
+        self.state
+            .config()
+            .playback_gapless_preroll_ms()
+            .set(new_config.playback_gapless_preroll_ms);
+        self.state
+            .config()
+            .playback_output_device()
+            .set(new_config.playback_output_device.clone());
+        self.state
+            .config()
+            .playback_output_bit_depth()
+            .set(Some(output_bit_depth_to_display(
+                new_config.playback_output_bit_depth,
+            )));
+        self.state
+            .config()
+            .playback_dither()
+            .set(Some(dither_to_display(new_config.playback_dither)));
+        self.state
+            .config()
+            .playback_exclusive_devices()
+            .set(new_config.playback_exclusive_devices.clone());
+        self.state
+            .config()
+            .playback_skip_silence()
+            .set(new_config.playback_skip_silence);
+        self.state
+            .config()
+            .playback_min_silence_ms()
+            .set(new_config.playback_min_silence_ms);
+        self.state
+            .config()
+            .playback_in_background()
+            .set(new_config.playback_in_background);
+        self.state
+            .config()
+            .metered_mode()
+            .set(new_config.metered_mode);
+        self.state
+            .config()
+            .metered_allow_cloud_prefetch()
+            .set(new_config.metered_allow_cloud_prefetch);
+        self.state
+            .config()
+            .metered_allow_torrents()
+            .set(new_config.metered_allow_torrents);
+        self.state
+            .config()
+            .cd_insert_action()
+            .set(Some(cd_insert_action_to_display(
+                new_config.cd_insert_action,
+            )));
+        self.state
+            .config()
+            .cd_eject_after_rip()
+            .set(new_config.cd_eject_after_rip);
+        self.state
+            .config()
+            .folder_patterns()
+            .set(new_config.folder_patterns.clone());
+        self.state
+            .config()
+            .low_power_import()
+            .set(new_config.low_power_import);
+        self.state
+            .config()
+            .max_concurrent_imports()
+            .set(new_config.max_concurrent_imports);
+        self.state
+            .config()
+            .cloud_price_per_gb()
+            .set(new_config.cloud_price_per_gb);
+        self.state
+            .config()
+            .startup_view()
+            .set(Some(startup_view_to_display(new_config.startup_view)));
+        self.state
+            .config()
+            .library_sort()
+            .set(Some(library_sort_to_display(new_config.library_sort)));
+        self.state
+            .config()
+            .library_quality_filter()
+            .set(new_config.library_quality_filter.map(quality_from_db));
+        self.state
+            .config()
+            .verification_interval_days()
+            .set(new_config.verification_interval_days);
+        self.state
+            .config()
+            .snapshot_profile_id()
+            .set(new_config.snapshot_profile_id.clone());
+        self.state
+            .config()
+            .snapshot_interval_hours()
+            .set(new_config.snapshot_interval_hours);
+        self.state
+            .config()
+            .snapshots_kept()
+            .set(new_config.snapshots_kept);
+        self.state
+            .config()
+            .track_click_action()
+            .set(Some(track_click_action_to_display(
+                new_config.track_click_action,
+            )));
+        self.state
+            .config()
+            .featured_credit_style()
+            .set(Some(featured_credit_style_to_display(
+                new_config.featured_credit_style,
+            )));
+        self.state
+            .config()
+            .torrent_bind_interface()
+            .set(new_config.torrent_bind_interface.clone());
+        self.state
+            .config()
+            .torrent_listen_port()
+            .set(new_config.torrent_listen_port);
+        self.state
+            .config()
+            .torrent_enable_upnp()
+            .set(new_config.torrent_enable_upnp);
+        self.state
+            .config()
+            .torrent_enable_ipv6()
+            .set(new_config.torrent_enable_ipv6);
+        self.state
+            .config()
+            .torrent_enable_dht()
+            .set(new_config.torrent_enable_dht);
+        self.state
+            .config()
+            .torrent_dht_bootstrap_nodes()
+            .set(new_config.torrent_dht_bootstrap_nodes.clone());
+        self.state
+            .config()
+            .torrent_max_connections()
+            .set(new_config.torrent_max_connections);
+        self.state
+            .config()
+            .torrent_max_connections_per_torrent()
+            .set(new_config.torrent_max_connections_per_torrent);
+        self.state
+            .config()
+            .torrent_max_uploads()
+            .set(new_config.torrent_max_uploads);
+        self.state
+            .config()
+            .torrent_max_uploads_per_torrent()
+            .set(new_config.torrent_max_uploads_per_torrent);
+
+        // Playback settings apply to the next stream without a restart
+        self.playback_handle
+            .set_buffer_settings(new_config.playback_buffer_settings());
+        self.playback_handle
+            .set_output_settings(new_config.playback_output_settings());
+        self.playback_handle
+            .set_skip_silence(new_config.playback_skip_silence_settings());
+
+        // Unpaired phones are locked out right away
+        self.remote_access
+            .set_token(new_config.remote_token.clone());
+
+        // Metered mode applies right away
+        let metered = new_config.metered_settings();
+        self.playback_handle
+            .set_cloud_prefetch(metered.cloud_prefetch_allowed());
+        #[cfg(feature = "torrent")]
+        self.torrent_manager.set_paused(!metered.torrents_allowed());
+
+        // Credit rules apply to the next import
+        self.import_handle
+            .set_credit_rules(new_config.credit_rules());
+        self.import_handle
+            .set_low_power_import(new_config.low_power_import);
+        self.import_handle
+            .set_import_memory_budget(new_config.import_memory_budget_mb);
+        self.import_handle
+            .set_max_concurrent_imports(new_config.max_concurrent_imports as usize);
+    }
+
+    /// Change scrobbling accounts. They're saved apart from the rest of the
+    /// config, and the scrobbler picks them up right away.
+    pub fn update_scrobble_accounts(
+        &self,
+        updater: impl FnOnce(&mut scrobble::ScrobbleAccounts),
+    ) -> Result<(), config::ConfigError> {
+        let mut accounts = self.scrobble_handle.accounts();
+        updater(&mut accounts);
+        self.config.save_scrobble_accounts(&accounts)?;
+        self.state.config().lastfm_username().set(
+            accounts
+                .lastfm
+                .as_ref()
+                .map(|account| account.username.clone()),
+        );
+        self.state
+            .config()
+            .listenbrainz_connected()
+            .set(accounts.listenbrainz_token.is_some());
+        self.scrobble_handle.set_accounts(accounts);
+        Ok(())
+    }
+
+    // =========================================================================
+    // Storage Profile Methods
+    // =========================================================================
+
+    /// Load storage profiles from database into Store
+    pub fn load_storage_profiles(&self) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+
+        spawn(async move {
+            state.storage_profiles().loading().set(true);
+            state.storage_profiles().error().set(None);
+
+            match fetch_storage_profiles(&library_manager).await {
+                Ok(profiles) => {
+                    state.storage_profiles().profiles().set(profiles);
+                }
+                Err(e) => {
+                    state
+                        .storage_profiles()
+                        .error()
+                        .set(Some(format!("Failed to load storage profiles: {}", e)));
+                }
+            }
+
+            state.storage_profiles().loading().set(false);
+        });
+    }
+
+    /// Save (create or update) a storage profile
+    pub fn save_storage_profile(&self, profile: StorageProfile) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        let is_new = profile.id.is_empty();
+
+        spawn(async move {
+            let result = if is_new {
+                let location = storage_location_from_display(profile.location);
+                let db_profile = match location {
+                    StorageLocation::Local => DbStorageProfile::new_local(
+                        &profile.name,
+                        &profile.location_path,
+                        profile.encrypted,
+                    ),
+                    StorageLocation::Reference => {
+                        DbStorageProfile::new_reference(&profile.name, &profile.location_path)
+                    }
+                    StorageLocation::Cloud => DbStorageProfile::new_cloud(
+                        &profile.name,
+                        profile.cloud_bucket.as_deref().unwrap_or(""),
+                        profile.cloud_region.as_deref().unwrap_or(""),
+                        profile.cloud_endpoint.as_deref(),
+                        profile.cloud_access_key.as_deref().unwrap_or(""),
+                        profile.cloud_secret_key.as_deref().unwrap_or(""),
+                        profile.encrypted,
+                    ),
+                }
+                .with_default(profile.is_default)
+                .with_filename_policy(filename_policy_from_display(profile.filename_policy))
+                .with_cloud_provider(cloud_provider_from_display(profile.cloud_provider))
+                .with_compressed(profile.compressed)
+                .with_cloud_archival(profile.cloud_archival)
+                .with_quota_bytes(profile.quota_bytes)
+                .with_extra_location_paths(
+                    profile.extra_location_paths.clone(),
+                    placement_policy_from_display(profile.placement_policy),
+                );
+                library_manager.insert_storage_profile(&db_profile).await
+            } else {
+                let mut db_profile = DbStorageProfile {
+                    id: profile.id.clone(),
+                    name: profile.name.clone(),
+                    location: storage_location_from_display(profile.location),
+                    location_path: profile.location_path.clone(),
+                    extra_location_paths: profile.extra_location_paths.clone(),
+                    placement_policy: placement_policy_from_display(profile.placement_policy),
+                    encrypted: profile.encrypted,
+                    compressed: profile.compressed,
+                    filename_policy: filename_policy_from_display(profile.filename_policy),
+                    is_default: profile.is_default,
+                    cloud_provider: cloud_provider_from_display(profile.cloud_provider),
+                    cloud_bucket: profile.cloud_bucket.clone(),
+                    cloud_region: profile.cloud_region.clone(),
+                    cloud_endpoint: profile.cloud_endpoint.clone(),
+                    cloud_access_key: profile.cloud_access_key.clone(),
                     cloud_secret_key: profile.cloud_secret_key.clone(),
+                    cloud_archival: profile.cloud_archival,
+                    quota_bytes: profile.quota_bytes,
                     created_at: chrono::Utc::now(),
                     updated_at: chrono::Utc::now(),
                 };
 
-                if db_profile.location == StorageLocation::Local {
+                if db_profile.location.is_on_disk() {
                     db_profile.cloud_bucket = None;
                     db_profile.cloud_region = None;
                     db_profile.cloud_endpoint = None;
                     db_profile.cloud_access_key = None;
                     db_profile.cloud_secret_key = None;
+                    db_profile.cloud_archival = false;
                 }
 
                 library_manager.update_storage_profile(&db_profile).await
@@ -655,8 +2252,7 @@ impl AppService {
                 Ok(()) => {
                     tracing::info!("Saved storage profile: {}", profile.name);
                     // Reload profiles
-                    if let Ok(db_profiles) = library_manager.get_all_storage_profiles().await {
-                        let profiles = db_profiles.iter().map(storage_profile_from_db).collect();
+                    if let Ok(profiles) = fetch_storage_profiles(&library_manager).await {
                         state.storage_profiles().profiles().set(profiles);
                     }
                 }
@@ -682,8 +2278,7 @@ impl AppService {
                 Ok(()) => {
                     tracing::info!("Deleted storage profile: {}", profile_id);
                     // Reload profiles
-                    if let Ok(db_profiles) = library_manager.get_all_storage_profiles().await {
-                        let profiles = db_profiles.iter().map(storage_profile_from_db).collect();
+                    if let Ok(profiles) = fetch_storage_profiles(&library_manager).await {
                         state.storage_profiles().profiles().set(profiles);
                     }
                 }
@@ -712,8 +2307,7 @@ impl AppService {
                 Ok(()) => {
                     tracing::info!("Set default storage profile: {}", profile_id);
                     // Reload profiles
-                    if let Ok(db_profiles) = library_manager.get_all_storage_profiles().await {
-                        let profiles = db_profiles.iter().map(storage_profile_from_db).collect();
+                    if let Ok(profiles) = fetch_storage_profiles(&library_manager).await {
                         state.storage_profiles().profiles().set(profiles);
                     }
                 }
@@ -727,26 +2321,170 @@ impl AppService {
             }
         });
     }
+
+    // =========================================================================
+    // Client Share Methods
+    // =========================================================================
+
+    /// Load client shares from database into Store
+    pub fn load_client_shares(&self) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+
+        spawn(async move {
+            refresh_client_shares(&state, &library_manager).await;
+        });
+    }
+
+    /// Create a client share with a fresh token
+    pub fn create_client_share(&self, name: String) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+
+        spawn(async move {
+            match library_manager.create_client_share(&name).await {
+                Ok(share) => {
+                    tracing::info!("Created client share: {}", share.name);
+                    refresh_client_shares(&state, &library_manager).await;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to create client share: {}", e);
+                    state
+                        .client_shares()
+                        .error()
+                        .set(Some(format!("Failed to create share: {}", e)));
+                }
+            }
+        });
+    }
+
+    /// Delete a client share; its token stops working
+    pub fn delete_client_share(&self, share_id: &str) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        let share_id = share_id.to_string();
+
+        spawn(async move {
+            match library_manager.delete_client_share(&share_id).await {
+                Ok(()) => {
+                    tracing::info!("Deleted client share: {}", share_id);
+                    refresh_client_shares(&state, &library_manager).await;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to delete client share: {}", e);
+                    state
+                        .client_shares()
+                        .error()
+                        .set(Some(format!("Failed to delete share: {}", e)));
+                }
+            }
+        });
+    }
+
+    /// Add an album to a client share, or take it out
+    pub fn set_album_shared(&self, share_id: &str, album_id: &str, shared: bool) {
+        let state = self.state;
+        let library_manager = self.library_manager.clone();
+        let share_id = share_id.to_string();
+        let album_id = album_id.to_string();
+
+        spawn(async move {
+            match library_manager
+                .set_album_shared(&share_id, &album_id, shared)
+                .await
+            {
+                Ok(()) => refresh_client_shares(&state, &library_manager).await,
+                Err(e) => {
+                    tracing::error!("Failed to update client share: {}", e);
+                    state
+                        .client_shares()
+                        .error()
+                        .set(Some(format!("Failed to update share: {}", e)));
+                }
+            }
+        });
+    }
+}
+
+// =============================================================================
+// Helper Functions
+// =============================================================================
+
+/// Reload client shares and their albums into the Store
+async fn refresh_client_shares(state: &Store<AppState>, library_manager: &SharedLibraryManager) {
+    let result: Result<Vec<ClientShare>, LibraryError> = async {
+        let mut shares = Vec::new();
+        for share in library_manager.get_client_shares().await? {
+            let album_ids = library_manager.get_shared_album_ids(&share.id).await?;
+            shares.push(ClientShare {
+                id: share.id,
+                name: share.name,
+                token: share.token,
+                album_ids,
+            });
+        }
+        Ok(shares)
+    }
+    .await;
+    match result {
+        Ok(shares) => {
+            state.client_shares().shares().set(shares);
+            state.client_shares().error().set(None);
+        }
+        Err(e) => state
+            .client_shares()
+            .error()
+            .set(Some(format!("Failed to load shares: {}", e))),
+    }
 }
 
-// =============================================================================
-// Helper Functions
-// =============================================================================
+/// Load all storage profiles with how much each one stores
+async fn fetch_storage_profiles(
+    library_manager: &SharedLibraryManager,
+) -> Result<Vec<StorageProfile>, LibraryError> {
+    let db_profiles = library_manager.get_all_storage_profiles().await?;
+    let usage = library_manager.get_storage_usage_by_profile().await?;
+    Ok(db_profiles
+        .iter()
+        .map(|p| storage_profile_from_db(p, usage.get(&p.id).copied().unwrap_or(0)))
+        .collect())
+}
+
+/// Reload profiles so settings and the import confirm step see current quota usage
+async fn refresh_storage_usage(state: &Store<AppState>, library_manager: &SharedLibraryManager) {
+    match fetch_storage_profiles(library_manager).await {
+        Ok(profiles) => state.storage_profiles().profiles().set(profiles),
+        Err(e) => {
+            tracing::warn!("Failed to refresh storage usage: {}", e);
+        }
+    }
+}
 
 /// Convert DbStorageProfile to display StorageProfile
-fn storage_profile_from_db(p: &DbStorageProfile) -> StorageProfile {
+fn storage_profile_from_db(p: &DbStorageProfile, used_bytes: u64) -> StorageProfile {
     StorageProfile {
         id: p.id.clone(),
         name: p.name.clone(),
         location: storage_location_to_display(p.location),
         location_path: p.location_path.clone(),
+        extra_location_paths: p.extra_location_paths.clone(),
+        placement_policy: placement_policy_to_display(p.placement_policy),
         encrypted: p.encrypted,
+        compressed: p.compressed,
+        filename_policy: filename_policy_to_display(p.filename_policy),
         is_default: p.is_default,
+        cloud_provider: cloud_provider_to_display(p.cloud_provider),
         cloud_bucket: p.cloud_bucket.clone(),
         cloud_region: p.cloud_region.clone(),
         cloud_endpoint: p.cloud_endpoint.clone(),
         cloud_access_key: p.cloud_access_key.clone(),
         cloud_secret_key: p.cloud_secret_key.clone(),
+        cloud_archival: p.cloud_archival,
+        quota_bytes: p.quota_bytes,
+        used_bytes,
+        quota_level: p
+            .quota_bytes
+            .map(|quota| quota_level_to_display(storage::quota_level(used_bytes, quota))),
     }
 }
 
@@ -755,7 +2493,88 @@ fn storage_location_to_display(loc: StorageLocation) -> bae_ui::StorageLocation
     match loc {
         StorageLocation::Local => bae_ui::StorageLocation::Local,
         StorageLocation::Cloud => bae_ui::StorageLocation::Cloud,
+        StorageLocation::Reference => bae_ui::StorageLocation::Reference,
+    }
+}
+
+/// Convert QuotaLevel to display type
+fn quota_level_to_display(level: QuotaLevel) -> bae_ui::QuotaLevel {
+    match level {
+        QuotaLevel::Normal => bae_ui::QuotaLevel::Normal,
+        QuotaLevel::Warning => bae_ui::QuotaLevel::Warning,
+        QuotaLevel::Exceeded => bae_ui::QuotaLevel::Exceeded,
+    }
+}
+
+/// Track, album title and cover for each queued or played track ID
+pub(crate) async fn load_queue_items(
+    library_manager: &SharedLibraryManager,
+    track_ids: &[String],
+) -> Vec<QueueItem> {
+    let mut queue_items = Vec::new();
+    for track_id in track_ids {
+        if let Some(item) = external_queue_item(track_id, None) {
+            queue_items.push(item);
+        } else if let Ok(Some(track)) = library_manager.get().get_track(track_id).await {
+            let (album_title, cover_url) = if let Ok(album_id) =
+                library_manager.get().get_album_id_for_track(track_id).await
+            {
+                if let Ok(Some(album)) = library_manager.get().get_album_by_id(&album_id).await {
+                    let cover = album
+                        .cover_image_id
+                        .as_ref()
+                        .map(|id| image_url(id))
+                        .or(album.cover_art_url.clone());
+                    (album.title, cover)
+                } else {
+                    ("Unknown Album".to_string(), None)
+                }
+            } else {
+                ("Unknown Album".to_string(), None)
+            };
+
+            queue_items.push(QueueItem {
+                track: track_from_db_ref(&track),
+                album_title,
+                cover_url,
+                is_external: false,
+            });
+        }
     }
+    queue_items
+}
+
+/// Queue entry for a file played from disk, labelled with its folder name.
+/// None for library tracks.
+fn external_queue_item(track_id: &str, duration_ms: Option<i64>) -> Option<QueueItem> {
+    let path = playback::external_track_path(track_id)?;
+    let title = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Unknown file")
+        .to_string();
+    let folder = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_string();
+    Some(QueueItem {
+        track: Track {
+            id: track_id.to_string(),
+            title,
+            track_number: None,
+            disc_number: None,
+            duration_ms,
+            is_available: true,
+            import_state: TrackImportState::Complete,
+            quality: None,
+            bookmarks: Vec::new(),
+        },
+        album_title: folder,
+        cover_url: None,
+        is_external: true,
+    })
 }
 
 /// Convert display StorageLocation to DB type
@@ -763,6 +2582,57 @@ fn storage_location_from_display(loc: bae_ui::StorageLocation) -> StorageLocatio
     match loc {
         bae_ui::StorageLocation::Local => StorageLocation::Local,
         bae_ui::StorageLocation::Cloud => StorageLocation::Cloud,
+        bae_ui::StorageLocation::Reference => StorageLocation::Reference,
+    }
+}
+
+/// Convert FilenamePolicy to display type
+fn filename_policy_to_display(policy: FilenamePolicy) -> bae_ui::FilenamePolicy {
+    match policy {
+        FilenamePolicy::Strip => bae_ui::FilenamePolicy::Strip,
+        FilenamePolicy::Replace => bae_ui::FilenamePolicy::Replace,
+        FilenamePolicy::Transliterate => bae_ui::FilenamePolicy::Transliterate,
+    }
+}
+
+/// Convert display FilenamePolicy to DB type
+fn filename_policy_from_display(policy: bae_ui::FilenamePolicy) -> FilenamePolicy {
+    match policy {
+        bae_ui::FilenamePolicy::Strip => FilenamePolicy::Strip,
+        bae_ui::FilenamePolicy::Replace => FilenamePolicy::Replace,
+        bae_ui::FilenamePolicy::Transliterate => FilenamePolicy::Transliterate,
+    }
+}
+
+/// Convert PlacementPolicy to display type
+fn placement_policy_to_display(policy: PlacementPolicy) -> bae_ui::PlacementPolicy {
+    match policy {
+        PlacementPolicy::FillFirst => bae_ui::PlacementPolicy::FillFirst,
+        PlacementPolicy::MostFreeSpace => bae_ui::PlacementPolicy::MostFreeSpace,
+    }
+}
+
+/// Convert display PlacementPolicy to DB type
+fn placement_policy_from_display(policy: bae_ui::PlacementPolicy) -> PlacementPolicy {
+    match policy {
+        bae_ui::PlacementPolicy::FillFirst => PlacementPolicy::FillFirst,
+        bae_ui::PlacementPolicy::MostFreeSpace => PlacementPolicy::MostFreeSpace,
+    }
+}
+
+/// Convert CloudProvider to display type
+fn cloud_provider_to_display(provider: CloudProvider) -> bae_ui::CloudProvider {
+    match provider {
+        CloudProvider::S3 => bae_ui::CloudProvider::S3,
+        CloudProvider::B2 => bae_ui::CloudProvider::B2,
+    }
+}
+
+/// Convert display CloudProvider to DB type
+fn cloud_provider_from_display(provider: bae_ui::CloudProvider) -> CloudProvider {
+    match provider {
+        bae_ui::CloudProvider::S3 => CloudProvider::S3,
+        bae_ui::CloudProvider::B2 => CloudProvider::B2,
     }
 }
 
@@ -781,7 +2651,25 @@ async fn load_library(state: &Store<AppState>, library_manager: &SharedLibraryMa
                     artists_map.insert(album.id.clone(), artists);
                 }
             }
-            let display_albums = album_list.iter().map(album_from_db_ref).collect();
+            let qualities = library_manager
+                .get()
+                .get_album_qualities()
+                .await
+                .unwrap_or_default();
+            let offline_ids: HashSet<String> = offline::offline_album_ids(library_manager.get())
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            let display_albums = album_list
+                .iter()
+                .map(|db_album| {
+                    let mut album = album_from_db_ref(db_album);
+                    album.quality = qualities.get(&db_album.id).copied().map(quality_from_db);
+                    album.is_offline = offline_ids.contains(&db_album.id);
+                    album
+                })
+                .collect();
 
             state.library().albums().set(display_albums);
             state.library().artists_by_album().set(artists_map);
@@ -797,6 +2685,444 @@ async fn load_library(state: &Store<AppState>, library_manager: &SharedLibraryMa
     state.library().loading().set(false);
 }
 
+/// Load the tracks of each album's default edition into the Store
+async fn load_library_tracks(state: &Store<AppState>, library_manager: &SharedLibraryManager) {
+    state.tracks().loading().set(true);
+    state.tracks().error().set(None);
+
+    match library_manager.get().get_library_tracks().await {
+        Ok(tracks) => {
+            state
+                .tracks()
+                .tracks()
+                .set(tracks.iter().map(library_track_from_db).collect());
+        }
+        Err(e) => {
+            state
+                .tracks()
+                .error()
+                .set(Some(format!("Failed to load tracks: {}", e)));
+        }
+    }
+
+    state.tracks().loading().set(false);
+}
+
+/// Plays shown in the listening history
+const HISTORY_PLAY_LIMIT: i64 = 500;
+
+/// Tracks listed as most played next to the history
+const HISTORY_TOP_TRACKS: i64 = 10;
+
+async fn load_listening_history(
+    state: &Store<AppState>,
+    library_manager: &SharedLibraryManager,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) {
+    state.history().loading().set(true);
+    state.history().error().set(None);
+
+    let library_manager = library_manager.get();
+    let result: Result<_, LibraryError> = async {
+        let plays = library_manager
+            .get_listening_history(from, to, HISTORY_PLAY_LIMIT)
+            .await?;
+        let top_tracks = library_manager
+            .get_most_played_tracks(from, to, HISTORY_TOP_TRACKS)
+            .await?;
+        Ok((plays, top_tracks))
+    }
+    .await;
+
+    match result {
+        Ok((plays, top_tracks)) => {
+            state
+                .history()
+                .plays()
+                .set(plays.iter().map(track_play_from_db).collect());
+            state
+                .history()
+                .top_tracks()
+                .set(top_tracks.iter().map(track_play_count_from_db).collect());
+        }
+        Err(e) => {
+            state
+                .history()
+                .error()
+                .set(Some(format!("Failed to load history: {}", e)));
+        }
+    }
+
+    state.history().loading().set(false);
+}
+
+/// Verification runs kept on screen
+const VERIFICATION_HISTORY_LIMIT: i64 = 24;
+
+/// Wait after launch before the first check for a due verification run
+const VERIFICATION_STARTUP_DELAY: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// Wait after launch before measuring DR of releases that lack it
+const DYNAMIC_RANGE_STARTUP_DELAY: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// How often to check whether a scheduled verification run is due
+const VERIFICATION_SCHEDULE_CHECK_INTERVAL: std::time::Duration =
+    std::time::Duration::from_secs(3600);
+
+/// Wait after launch before the first check for a due library snapshot
+const SNAPSHOT_STARTUP_DELAY: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// How often to check whether a scheduled library snapshot is due
+const SNAPSHOT_SCHEDULE_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(900);
+
+/// Time the playback thread gets to reopen the output device before it's
+/// checked again
+const OUTPUT_REOPEN_WAIT: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Download a snapshot from the profile in the snapshot index, which is
+/// still there when the database isn't
+async fn stage_snapshot_restore(
+    library_manager: &SharedLibraryManager,
+    library_path: &Path,
+    location: &str,
+) -> Result<(), String> {
+    let encryption_service = library_manager
+        .get()
+        .encryption_service()
+        .ok_or("Restoring a snapshot needs the library's encryption key")?;
+    let index = SnapshotIndex::load(library_path);
+    let snapshot = index
+        .snapshots
+        .iter()
+        .find(|s| s.location == location)
+        .ok_or("Snapshot is no longer in the index")?;
+    let profile = index
+        .profile
+        .as_ref()
+        .ok_or("Snapshot index has no storage profile")?;
+    let storage = storage::create_cloud_storage(profile)
+        .await
+        .map_err(|e| e.to_string())?;
+    stage_restore(snapshot, storage.as_ref(), encryption_service, library_path)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Load recent verification runs with their issues
+async fn load_verification_history(
+    state: &Store<AppState>,
+    library_manager: &SharedLibraryManager,
+) {
+    let library_manager = library_manager.get();
+    let result: Result<_, LibraryError> = async {
+        let first_failures = library_manager.get_first_verification_failures().await?;
+        let mut runs = Vec::new();
+        for run in library_manager
+            .get_verification_runs(VERIFICATION_HISTORY_LIMIT)
+            .await?
+        {
+            let issues = library_manager.get_verification_issues(&run.id).await?;
+            runs.push(verification_run_from_db(&run, &issues, &first_failures));
+        }
+        Ok(runs)
+    }
+    .await;
+
+    match result {
+        Ok(runs) => state.verification().runs().set(runs),
+        Err(e) => tracing::warn!("Failed to load verification history: {}", e),
+    }
+}
+
+#[cfg(feature = "torrent")]
+async fn load_seeding_torrents(state: &Store<AppState>, library_manager: &SharedLibraryManager) {
+    match library_manager.get().get_kept_torrents().await {
+        Ok(torrents) => state
+            .seeding()
+            .torrents()
+            .set(torrents.iter().map(seeding_torrent_from_db).collect()),
+        Err(e) => tracing::warn!("Failed to load seeding torrents: {}", e),
+    }
+}
+
+/// How often to check whether a requested restore has finished
+const ARCHIVE_RESTORE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Apply a bookmark change to the album detail tracks and the playing track,
+/// whichever show it
+fn update_bookmarks(state: &Store<AppState>, update: impl Fn(&mut Track)) {
+    state
+        .album_detail()
+        .tracks()
+        .with_mut(|tracks| tracks.iter_mut().for_each(&update));
+    state.playback().current_track().with_mut(|item| {
+        if let Some(item) = item {
+            update(&mut item.track);
+        }
+    });
+}
+
+/// Convert a release's archive status to the banner shown on album detail.
+/// Plainly available releases get no banner.
+fn archive_status_to_display(status: Option<ArchiveStatus>) -> Option<ReleaseArchiveStatus> {
+    match status? {
+        ArchiveStatus::Available => None,
+        ArchiveStatus::Archived => Some(ReleaseArchiveStatus::Archived),
+        ArchiveStatus::Restoring => Some(ReleaseArchiveStatus::Restoring),
+        ArchiveStatus::Restored => Some(ReleaseArchiveStatus::Restored),
+    }
+}
+
+/// Update the album detail banner, unless the user has moved on to another release
+fn set_archive_status_if_selected(
+    state: &Store<AppState>,
+    release_id: &str,
+    status: Option<ArchiveStatus>,
+) {
+    let selected = state.album_detail().selected_release_id().read().clone();
+    if selected.as_deref() == Some(release_id) {
+        state
+            .album_detail()
+            .archive_status()
+            .set(archive_status_to_display(status));
+    }
+}
+
+/// Show the reference banner when a release is indexed from a reference profile
+async fn load_release_reference(
+    state: &Store<AppState>,
+    library_manager: &SharedLibraryManager,
+    release_id: &str,
+) {
+    let library_manager = library_manager.get();
+    let profile = match library_manager
+        .get_storage_profile_for_release(release_id)
+        .await
+    {
+        Ok(Some(profile)) if profile.location == StorageLocation::Reference => profile,
+        Ok(_) => return,
+        Err(e) => {
+            tracing::warn!("Failed to load storage profile of {}: {}", release_id, e);
+            return;
+        }
+    };
+    let target_profile_name = match library_manager.get_default_storage_profile().await {
+        Ok(Some(target)) if target.location != StorageLocation::Reference => Some(target.name),
+        _ => None,
+    };
+    state.album_detail().reference().set(Some(ReleaseReference {
+        profile_name: profile.name,
+        target_profile_name,
+        migrating: false,
+        error: None,
+    }));
+}
+
+/// Update the reference banner, unless the user has moved on to another release
+fn update_reference_if_selected(
+    state: &Store<AppState>,
+    release_id: &str,
+    update: impl FnOnce(&mut ReleaseReference),
+) {
+    let selected = state.album_detail().selected_release_id().read().clone();
+    if selected.as_deref() != Some(release_id) {
+        return;
+    }
+    if let Some(reference) = state.album_detail().reference().write().as_mut() {
+        update(reference);
+    }
+}
+
+/// Pin an album and download its missing files, showing progress while it
+/// runs and the error if it stops
+async fn download_album_offline(
+    state: &Store<AppState>,
+    library_manager: &SharedLibraryManager,
+    cache: &cache::CacheManager,
+    album_id: &str,
+) {
+    let result = offline::keep_album_offline(library_manager.get(), cache, album_id, |progress| {
+        set_offline_progress(state, album_id, progress);
+    })
+    .await;
+    state
+        .library()
+        .offline_downloads()
+        .with_mut(|downloads| match result {
+            Ok(()) => {
+                downloads.remove(album_id);
+            }
+            Err(e) => {
+                tracing::error!("Failed to download {} for offline: {}", album_id, e);
+                let download = downloads
+                    .entry(album_id.to_string())
+                    .or_insert(OfflineDownload {
+                        files_done: 0,
+                        files_total: 0,
+                        error: None,
+                    });
+                download.error = Some(e.to_string());
+            }
+        });
+}
+
+/// Record how far an album's offline download has got
+fn set_offline_progress(state: &Store<AppState>, album_id: &str, progress: OfflineProgress) {
+    state.library().offline_downloads().with_mut(|downloads| {
+        downloads.insert(
+            album_id.to_string(),
+            OfflineDownload {
+                files_done: progress.files_done,
+                files_total: progress.files_total,
+                error: None,
+            },
+        );
+    });
+}
+
+/// Mark an album kept offline, or not, in the library grid and its open page
+fn set_album_offline_flag(state: &Store<AppState>, album_id: &str, is_offline: bool) {
+    state.library().albums().with_mut(|albums| {
+        if let Some(album) = albums.iter_mut().find(|a| a.id == album_id) {
+            album.is_offline = is_offline;
+        }
+    });
+    state.album_detail().album().with_mut(|album| {
+        if let Some(album) = album.as_mut().filter(|a| a.id == album_id) {
+            album.is_offline = is_offline;
+        }
+    });
+}
+
+/// Show an undone or redone edit in whichever views have the entity loaded
+fn apply_change_to_state(
+    state: &Store<AppState>,
+    change: &DbChange,
+    values: Option<&serde_json::Value>,
+) {
+    let Some(values) = values else {
+        return;
+    };
+    match change.kind {
+        ChangeKind::AlbumRenamed => {
+            let Some(title) = values["title"].as_str() else {
+                return;
+            };
+            state.album_detail().album().with_mut(|album| {
+                if let Some(album) = album.as_mut().filter(|a| a.id == change.entity_id) {
+                    album.title = title.to_string();
+                }
+            });
+        }
+        ChangeKind::TrackRenamed => {
+            let Some(title) = values["title"].as_str() else {
+                return;
+            };
+            state.album_detail().tracks().with_mut(|tracks| {
+                if let Some(track) = tracks.iter_mut().find(|t| t.id == change.entity_id) {
+                    track.title = title.to_string();
+                }
+            });
+            state.tracks().tracks().with_mut(|tracks| {
+                if let Some(track) = tracks.iter_mut().find(|t| t.id == change.entity_id) {
+                    track.title = title.to_string();
+                }
+            });
+        }
+        ChangeKind::PreferredReleaseChanged => {
+            let is_open = state.album_detail().album().read().as_ref().map(|a| &a.id)
+                == Some(&change.entity_id);
+            if is_open {
+                state
+                    .album_detail()
+                    .preferred_release_id()
+                    .set(values["release_id"].as_str().map(str::to_string));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Edits from the album page's edit mode touch several fields at once, so
+/// the open album is reloaded rather than patched
+async fn reload_album_if_edited(
+    state: &Store<AppState>,
+    library_manager: &SharedLibraryManager,
+    changes: &[DbChange],
+) {
+    let edited = changes.iter().any(|c| {
+        matches!(
+            c.kind,
+            ChangeKind::AlbumEdited
+                | ChangeKind::AlbumArtistsEdited
+                | ChangeKind::ReleaseEdited
+                | ChangeKind::TrackEdited
+        )
+    });
+    let open_album_id = state
+        .album_detail()
+        .album()
+        .read()
+        .as_ref()
+        .map(|a| a.id.clone());
+    let (true, Some(album_id)) = (edited, open_album_id) else {
+        return;
+    };
+    let release_id = state.album_detail().selected_release_id().read().clone();
+    load_album_detail(state, library_manager, &album_id, release_id.as_deref()).await;
+}
+
+/// Toast text for an edit that was just undone (or redone)
+fn undo_message(changes: &[DbChange], undone: bool) -> String {
+    let change = match changes {
+        [change] => change,
+        _ if undone => return format!("Undid {} changes", changes.len()),
+        _ => return format!("Redid {} changes", changes.len()),
+    };
+    match (change.kind, undone) {
+        (ChangeKind::TrackRenamed, true) => {
+            match change.before.as_ref().and_then(|v| v["title"].as_str()) {
+                Some(title) => format!("Renamed back to “{}”", title),
+                None => "Undid rename".to_string(),
+            }
+        }
+        (ChangeKind::TrackRenamed, false) => {
+            match change.after.as_ref().and_then(|v| v["title"].as_str()) {
+                Some(title) => format!("Renamed to “{}”", title),
+                None => "Redid rename".to_string(),
+            }
+        }
+        (ChangeKind::PreferredReleaseChanged, true) => "Default edition restored".to_string(),
+        (ChangeKind::PreferredReleaseChanged, false) => "Default edition changed again".to_string(),
+        (_, true) => "Undone".to_string(),
+        (_, false) => "Redone".to_string(),
+    }
+}
+
+/// Show a release's DR score if it's the one on screen
+async fn refresh_dynamic_range(
+    state: &Store<AppState>,
+    library_manager: &SharedLibraryManager,
+    release_id: &str,
+) {
+    let selected = state.album_detail().selected_release_id().read().clone();
+    if selected.as_deref() != Some(release_id) {
+        return;
+    }
+    match library_manager
+        .get()
+        .get_track_dynamic_ranges_for_release(release_id)
+        .await
+    {
+        Ok(scores) => state
+            .album_detail()
+            .dynamic_range()
+            .set(release_dynamic_range(scores.into_values().flatten())),
+        Err(e) => tracing::warn!("Failed to load DR of {}: {}", release_id, e),
+    }
+}
+
 /// Load album detail data into the Store
 async fn load_album_detail(
     state: &Store<AppState>,
@@ -804,12 +3130,27 @@ async fn load_album_detail(
     album_id: &str,
     release_id_param: Option<&str>,
 ) {
+    // Leaving an album mid-edit drops the edit
+    let same_album = state
+        .album_detail()
+        .album()
+        .read()
+        .as_ref()
+        .map(|a| a.id.as_str())
+        == Some(album_id);
+    if !same_album {
+        state.album_detail().editing().set(false);
+        state.album_detail().edit_error().set(None);
+    }
     state.album_detail().loading().set(true);
     state.album_detail().error().set(None);
+    state.album_detail().archive_status().set(None);
+    state.album_detail().reference().set(None);
+    state.album_detail().comparison().set(None);
 
     // Load album
-    let album = match library_manager.get().get_album_by_id(album_id).await {
-        Ok(Some(db_album)) => Some(album_from_db_ref(&db_album)),
+    let db_album = match library_manager.get().get_album_by_id(album_id).await {
+        Ok(Some(db_album)) => db_album,
         Ok(None) => {
             state
                 .album_detail()
@@ -827,7 +3168,16 @@ async fn load_album_detail(
             return;
         }
     };
-    state.album_detail().album().set(album);
+    let mut album = album_from_db_ref(&db_album);
+    album.is_offline = offline::offline_album_ids(library_manager.get())
+        .await
+        .unwrap_or_default()
+        .contains(&db_album.id);
+    state.album_detail().album().set(Some(album));
+    state
+        .album_detail()
+        .preferred_release_id()
+        .set(db_album.preferred_release_id.clone());
 
     // Load releases
     let releases = match library_manager.get().get_releases_for_album(album_id).await {
@@ -851,14 +3201,15 @@ async fn load_album_detail(
         return;
     }
 
-    // Determine selected release
+    // Determine selected release: the one asked for, else the album's default edition
+    let default_release = db_album.default_release(&releases).unwrap_or(&releases[0]);
     let selected_release = if let Some(rid) = release_id_param {
         releases
             .iter()
             .find(|r| r.id == rid)
-            .unwrap_or(&releases[0])
+            .unwrap_or(default_release)
     } else {
-        &releases[0]
+        default_release
     };
     let selected_release_id = selected_release.id.clone();
 
@@ -872,10 +3223,32 @@ async fn load_album_detail(
 
     let display_releases = releases.iter().map(release_from_db_ref).collect();
     state.album_detail().releases().set(display_releases);
+    let mut stored_bytes = HashMap::new();
+    for release in &releases {
+        match library_manager.get().stored_bytes(&release.id).await {
+            Ok(bytes) => {
+                stored_bytes.insert(release.id.clone(), bytes);
+            }
+            Err(e) => tracing::warn!("Failed to get stored size of {}: {}", release.id, e),
+        }
+    }
+    state.album_detail().stored_bytes().set(stored_bytes);
+    let can_keep_offline = offline::can_keep_offline(library_manager.get(), album_id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to check cloud files of {}: {}", album_id, e);
+            false
+        });
+    state
+        .album_detail()
+        .can_keep_offline()
+        .set(can_keep_offline);
     state
         .album_detail()
         .selected_release_id()
         .set(Some(selected_release_id.clone()));
+    state.album_detail().dynamic_range().set(None);
+    refresh_dynamic_range(state, library_manager, &selected_release_id).await;
 
     // Load artists
     if let Ok(db_artists) = library_manager.get().get_artists_for_album(album_id).await {
@@ -886,7 +3259,32 @@ async fn load_album_detail(
     // Load tracks for selected release (sorted by disc/track number)
     match library_manager.get().get_tracks(&selected_release_id).await {
         Ok(db_tracks) => {
-            let mut tracks: Vec<_> = db_tracks.iter().map(track_from_db_ref).collect();
+            let qualities = library_manager
+                .get()
+                .get_track_qualities_for_release(&selected_release_id)
+                .await
+                .unwrap_or_default();
+            let mut bookmarks: HashMap<String, Vec<TrackBookmark>> = HashMap::new();
+            for bookmark in library_manager
+                .get()
+                .get_bookmarks_for_release(&selected_release_id)
+                .await
+                .unwrap_or_default()
+            {
+                bookmarks
+                    .entry(bookmark.track_id.clone())
+                    .or_default()
+                    .push(bookmark_from_db(&bookmark));
+            }
+            let mut tracks: Vec<_> = db_tracks
+                .iter()
+                .map(|db_track| {
+                    let mut track = track_from_db_ref(db_track);
+                    track.quality = qualities.get(&db_track.id).copied().map(quality_from_db);
+                    track.bookmarks = bookmarks.remove(&db_track.id).unwrap_or_default();
+                    track
+                })
+                .collect();
             tracks.sort_by(|a, b| {
                 (a.disc_number, a.track_number).cmp(&(b.disc_number, b.track_number))
             });
@@ -911,16 +3309,60 @@ async fn load_album_detail(
         }
     }
 
+    // Video extras, kept with the release's files
+    let videos = match library_manager
+        .get()
+        .get_files_for_release(&selected_release_id)
+        .await
+    {
+        Ok(files) => files
+            .iter()
+            .filter(|f| import::folder_scanner::is_video_file(Path::new(&f.original_filename)))
+            .map(file_from_db_ref)
+            .collect(),
+        Err(e) => {
+            tracing::warn!("Failed to load files for release: {}", e);
+            Vec::new()
+        }
+    };
+    state.album_detail().videos().set(videos);
+
+    load_release_reference(state, library_manager, &selected_release_id).await;
+
     state.album_detail().loading().set(false);
+
+    // Archive checks hit S3 per file, so they run after the page is shown
+    match library_manager
+        .get()
+        .get_release_archive_status(&selected_release_id)
+        .await
+    {
+        Ok(status) => set_archive_status_if_selected(state, &selected_release_id, status),
+        Err(e) => {
+            tracing::warn!("Failed to check archive status: {}", e);
+        }
+    }
 }
 
 /// Convert bae_core ImportOperationStatus to bae_ui ImportOperationStatus
 fn convert_import_status(status: bae_core::db::ImportOperationStatus) -> ImportOperationStatus {
     match status {
         bae_core::db::ImportOperationStatus::Preparing => ImportOperationStatus::Preparing,
+        bae_core::db::ImportOperationStatus::Queued => ImportOperationStatus::Queued,
         bae_core::db::ImportOperationStatus::Importing => ImportOperationStatus::Importing,
+        bae_core::db::ImportOperationStatus::Paused => ImportOperationStatus::Paused,
         bae_core::db::ImportOperationStatus::Complete => ImportOperationStatus::Complete,
         bae_core::db::ImportOperationStatus::Failed => ImportOperationStatus::Failed,
+        bae_core::db::ImportOperationStatus::Cancelled => ImportOperationStatus::Cancelled,
+    }
+}
+
+/// Convert bae_core ImportKind to bae_ui ImportKind
+fn convert_import_kind(kind: bae_core::db::ImportKind) -> ImportKind {
+    match kind {
+        bae_core::db::ImportKind::Folder => ImportKind::Folder,
+        bae_core::db::ImportKind::Torrent => ImportKind::Torrent,
+        bae_core::db::ImportKind::Cd => ImportKind::Cd,
     }
 }
 
@@ -929,6 +3371,8 @@ fn convert_prepare_step(step: bae_core::import::PrepareStep) -> PrepareStep {
     match step {
         bae_core::import::PrepareStep::ParsingMetadata => PrepareStep::ParsingMetadata,
         bae_core::import::PrepareStep::DownloadingCoverArt => PrepareStep::DownloadingCoverArt,
+        bae_core::import::PrepareStep::ExtractingDiscImages => PrepareStep::ExtractingDiscImages,
+        bae_core::import::PrepareStep::SplittingSides => PrepareStep::SplittingSides,
         bae_core::import::PrepareStep::DiscoveringFiles => PrepareStep::DiscoveringFiles,
         bae_core::import::PrepareStep::ValidatingTracks => PrepareStep::ValidatingTracks,
         bae_core::import::PrepareStep::SavingToDatabase => PrepareStep::SavingToDatabase,
@@ -936,6 +3380,26 @@ fn convert_prepare_step(step: bae_core::import::PrepareStep) -> PrepareStep {
     }
 }
 
+/// One line on where imports are at, for crash reports. Skips the chatty
+/// per-track events.
+fn import_summary(event: &ImportProgress) -> Option<String> {
+    match event {
+        ImportProgress::Preparing {
+            import_id, step, ..
+        } => Some(format!("{} preparing: {:?}", import_id, step)),
+        ImportProgress::Progress {
+            id, percent, phase, ..
+        } => Some(format!("{} {:?} {}%", id, phase, percent)),
+        ImportProgress::Complete {
+            id,
+            release_id: None,
+            ..
+        } => Some(format!("{} complete", id)),
+        ImportProgress::Failed { id, error, .. } => Some(format!("{} failed: {}", id, error)),
+        _ => None,
+    }
+}
+
 /// Handle import progress events and update Store
 fn handle_import_progress(state: &Store<AppState>, event: ImportProgress) {
     match event {
@@ -956,15 +3420,37 @@ fn handle_import_progress(state: &Store<AppState>, event: ImportProgress) {
                         album_title,
                         artist_name,
                         status: ImportOperationStatus::Preparing,
+                        kind: ImportKind::default(),
                         current_step: Some(convert_prepare_step(step)),
                         progress_percent: None,
                         release_id: None,
                         cover_art_url,
                         cover_image_id: None,
+                        store_bytes_per_sec: None,
+                        error_message: None,
                     });
                 }
             });
         }
+        ImportProgress::StatusChanged {
+            import_id,
+            status,
+            kind,
+        } => {
+            state.active_imports().imports().with_mut(|list| {
+                if let Some(import) = list.iter_mut().find(|i| i.import_id == import_id) {
+                    import.status = convert_import_status(status);
+                    import.kind = convert_import_kind(kind);
+                    import.current_step = None;
+                    if status != bae_core::db::ImportOperationStatus::Importing {
+                        import.progress_percent = None;
+                    }
+                    if status == bae_core::db::ImportOperationStatus::Queued {
+                        import.error_message = None;
+                    }
+                }
+            });
+        }
         ImportProgress::Started { id, import_id, .. } => {
             if let Some(ref iid) = import_id {
                 state.active_imports().imports().with_mut(|list| {
@@ -1004,6 +3490,22 @@ fn handle_import_progress(state: &Store<AppState>, event: ImportProgress) {
             // Update overall import progress for album detail
             state.album_detail().import_progress().set(Some(percent));
         }
+        // Shown on the import page; the percentage comes with Progress
+        ImportProgress::Downloading { .. } => {}
+        ImportProgress::Stored {
+            bytes,
+            elapsed_ms,
+            import_id,
+            ..
+        } => {
+            if let Some(ref iid) = import_id {
+                state.active_imports().imports().with_mut(|list| {
+                    if let Some(import) = list.iter_mut().find(|i| &i.import_id == iid) {
+                        import.store_bytes_per_sec = Some(bytes * 1000 / elapsed_ms.max(1));
+                    }
+                });
+            }
+        }
         ImportProgress::Complete {
             id,
             import_id,
@@ -1049,6 +3551,7 @@ fn handle_import_progress(state: &Store<AppState>, event: ImportProgress) {
                 state.active_imports().imports().with_mut(|list| {
                     if let Some(import) = list.iter_mut().find(|i| &i.import_id == iid) {
                         import.status = ImportOperationStatus::Failed;
+                        import.error_message = Some(error.clone());
                     }
                 });
             }
@@ -5,19 +5,24 @@ use super::utils::maybe_not_empty;
 use super::AlbumDetailView;
 use crate::ui::app_service::use_app;
 use crate::ui::Route;
-use bae_ui::display_types::PlaybackDisplay;
+use bae_ui::display_types::{AlbumEdit, PlaybackDisplay, TrackClickAction};
 use bae_ui::stores::{
-    AlbumDetailStateStoreExt, AppStateStoreExt, PlaybackStatus, PlaybackUiStateStoreExt,
+    AlbumDetailStateStoreExt, AppStateStoreExt, ConfigStateStoreExt, LibraryStateStoreExt,
+    PlaybackStatus, PlaybackUiStateStoreExt,
 };
 use dioxus::prelude::*;
-use rfd::AsyncFileDialog;
+use rfd::{AsyncFileDialog, AsyncMessageDialog, MessageButtons, MessageDialogResult};
 use tracing::error;
 
 /// Album detail page showing album info and tracklist
 ///
 /// Passes state lens to AlbumDetailView - no memos, just direct lens access.
 #[component]
-pub fn AlbumDetail(album_id: ReadSignal<String>, release_id: ReadSignal<String>) -> Element {
+pub fn AlbumDetail(
+    album_id: ReadSignal<String>,
+    release_id: ReadSignal<String>,
+    track_id: ReadSignal<String>,
+) -> Element {
     let app = use_app();
 
     // Load album detail data into Store on mount/param change
@@ -124,6 +129,50 @@ pub fn AlbumDetail(album_id: ReadSignal<String>, release_id: ReadSignal<String>)
         }
     });
 
+    // Inline rename: update the store optimistically, revert if persisting fails
+    let on_track_rename = EventHandler::new({
+        let library_manager = library_manager.clone();
+        move |(track_id, title): (String, String)| {
+            let mut tracks = tracks;
+            let mut old_title = None;
+            tracks.with_mut(|tracks| {
+                if let Some(track) = tracks.iter_mut().find(|t| t.id == track_id) {
+                    old_title = Some(std::mem::replace(&mut track.title, title.clone()));
+                }
+            });
+            let Some(old_title) = old_title else {
+                return;
+            };
+
+            let library_manager = library_manager.clone();
+            spawn(async move {
+                if let Err(e) = library_manager.get().rename_track(&track_id, &title).await {
+                    error!("Failed to rename track: {}", e);
+
+                    let mut tracks = tracks;
+                    tracks.with_mut(|tracks| {
+                        if let Some(track) = tracks.iter_mut().find(|t| t.id == track_id) {
+                            track.title = old_title;
+                        }
+                    });
+                }
+            });
+        }
+    });
+
+    let on_track_bookmark_jump = EventHandler::new({
+        let app = app.clone();
+        move |(track_id, position_ms): (String, u64)| {
+            app.jump_to_bookmark(&track_id, position_ms);
+        }
+    });
+    let on_track_bookmark_delete = EventHandler::new({
+        let app = app.clone();
+        move |bookmark_id: String| {
+            app.delete_bookmark(&bookmark_id);
+        }
+    });
+
     // Album playback callbacks
     let on_play_album = EventHandler::new({
         let playback = playback.clone();
@@ -138,6 +187,83 @@ pub fn AlbumDetail(album_id: ReadSignal<String>, release_id: ReadSignal<String>)
         }
     });
 
+    let on_restore_release = EventHandler::new({
+        let app = app.clone();
+        move |release_id: String| {
+            app.request_release_restore(&release_id);
+        }
+    });
+
+    let on_migrate_release = EventHandler::new({
+        let app = app.clone();
+        move |release_id: String| {
+            app.migrate_release(&release_id);
+        }
+    });
+
+    let on_compare_releases = EventHandler::new({
+        let app = app.clone();
+        move |(left, right): (String, String)| {
+            app.compare_releases(&left, &right);
+        }
+    });
+
+    let on_set_preferred_release = EventHandler::new({
+        let app = app.clone();
+        move |release_id: Option<String>| {
+            app.set_preferred_release(&album_id(), release_id);
+        }
+    });
+
+    let on_set_offline = EventHandler::new({
+        let app = app.clone();
+        move |keep: bool| app.set_album_offline(&album_id(), keep)
+    });
+    let offline_download = app
+        .state
+        .library()
+        .offline_downloads()
+        .read()
+        .get(&album_id())
+        .cloned();
+
+    let on_edit = EventHandler::new({
+        let app = app.clone();
+        move |_| app.start_album_edit()
+    });
+    let on_cancel_edit = EventHandler::new({
+        let app = app.clone();
+        move |_| app.cancel_album_edit()
+    });
+    let on_save_edits = EventHandler::new({
+        let app = app.clone();
+        move |edit: AlbumEdit| app.save_album_edits(edit)
+    });
+
+    // Videos are exported to a temp folder (stored copies may be encrypted or
+    // remote) and handed to the system player
+    let on_play_video = EventHandler::new({
+        let library_manager = library_manager.clone();
+        move |file_id: String| {
+            let library_manager = library_manager.clone();
+            spawn(async move {
+                let target_dir = std::env::temp_dir().join("bae-videos").join(&file_id);
+                match library_manager
+                    .get()
+                    .export_file(&file_id, &target_dir)
+                    .await
+                {
+                    Ok(path) => {
+                        let _ = std::process::Command::new("open").arg(&path).spawn();
+                    }
+                    Err(e) => {
+                        error!("Failed to open video: {}", e);
+                    }
+                }
+            });
+        }
+    });
+
     // Export release callback
     let on_export_release = EventHandler::new({
         let library_manager = library_manager.clone();
@@ -164,11 +290,61 @@ pub fn AlbumDetail(album_id: ReadSignal<String>, release_id: ReadSignal<String>)
         }
     });
 
+    // Export a release as a gift package: zip or folder, then where to put it
+    let on_export_gift = EventHandler::new({
+        let library_manager = library_manager.clone();
+        let cache = cache.clone();
+        move |release_id: String| {
+            let library_manager = library_manager.clone();
+            let cache = cache.clone();
+            spawn(async move {
+                let choice = AsyncMessageDialog::new()
+                    .set_title("Export as Gift")
+                    .set_description(
+                        "The package has the audio, the cover and a player page that works in any browser.",
+                    )
+                    .set_buttons(MessageButtons::YesNoCancelCustom(
+                        "Zip".to_string(),
+                        "Folder".to_string(),
+                        "Cancel".to_string(),
+                    ))
+                    .show()
+                    .await;
+                let zip = match choice {
+                    MessageDialogResult::Custom(label) if label == "Zip" => true,
+                    MessageDialogResult::Custom(label) if label == "Folder" => false,
+                    _ => return,
+                };
+                let Some(folder_handle) = AsyncFileDialog::new()
+                    .set_title("Select Export Directory")
+                    .pick_folder()
+                    .await
+                else {
+                    return;
+                };
+                let target_dir = folder_handle.path().to_path_buf();
+                match library_manager
+                    .get()
+                    .export_gift(&release_id, &target_dir, zip, &cache)
+                    .await
+                {
+                    Ok(path) => {
+                        let _ = std::process::Command::new("open")
+                            .arg("-R")
+                            .arg(&path)
+                            .spawn();
+                    }
+                    Err(e) => error!("Failed to export gift package: {}", e),
+                }
+            });
+        }
+    });
+
     // Delete release callback
     let on_delete_release = EventHandler::new({
         let library_manager = library_manager.clone();
         let playback = playback.clone();
-        move |release_id: String| {
+        move |(release_id, keep_files): (String, bool)| {
             // Stop playback if current track belongs to the release being deleted
             let status = *playback_store.status().read();
             if matches!(status, PlaybackStatus::Playing | PlaybackStatus::Paused) {
@@ -181,7 +357,11 @@ pub fn AlbumDetail(album_id: ReadSignal<String>, release_id: ReadSignal<String>)
 
             let library_manager = library_manager.clone();
             spawn(async move {
-                if let Err(e) = library_manager.get().delete_release(&release_id).await {
+                if let Err(e) = library_manager
+                    .get()
+                    .delete_release(&release_id, keep_files)
+                    .await
+                {
                     error!("Failed to delete release: {}", e);
                 }
             });
@@ -196,7 +376,7 @@ pub fn AlbumDetail(album_id: ReadSignal<String>, release_id: ReadSignal<String>)
     let on_delete_album = EventHandler::new({
         let library_manager = library_manager.clone();
         let playback = playback.clone();
-        move |album_id: String| {
+        move |(album_id, keep_files): (String, bool)| {
             // Stop playback if current track belongs to the album being deleted
             let status = *playback_store.status().read();
             if matches!(status, PlaybackStatus::Playing | PlaybackStatus::Paused) {
@@ -210,7 +390,11 @@ pub fn AlbumDetail(album_id: ReadSignal<String>, release_id: ReadSignal<String>)
 
             let library_manager = library_manager.clone();
             spawn(async move {
-                if let Err(e) = library_manager.get().delete_album(&album_id).await {
+                if let Err(e) = library_manager
+                    .get()
+                    .delete_album(&album_id, keep_files)
+                    .await
+                {
                     error!("Failed to delete album: {}", e);
                 }
             });
@@ -223,6 +407,7 @@ pub fn AlbumDetail(album_id: ReadSignal<String>, release_id: ReadSignal<String>)
             navigator().push(Route::AlbumDetail {
                 album_id: album_id(),
                 release_id: new_release_id,
+                track_id: String::new(),
             });
         }
     };
@@ -231,6 +416,12 @@ pub fn AlbumDetail(album_id: ReadSignal<String>, release_id: ReadSignal<String>)
     let loading = *state.loading().read();
     let error = state.error().read().clone();
     let has_album = state.album().read().is_some();
+    let track_click_action = app
+        .state
+        .config()
+        .track_click_action()
+        .read()
+        .unwrap_or(TrackClickAction::Select);
 
     rsx! {
         BackButton {}
@@ -243,6 +434,8 @@ pub fn AlbumDetail(album_id: ReadSignal<String>, release_id: ReadSignal<String>)
                 state,
                 tracks,
                 playback: playback_display(),
+                track_click_action,
+                highlighted_track_id: maybe_not_empty(track_id()),
                 on_release_select,
                 on_album_deleted,
                 on_export_release,
@@ -254,8 +447,22 @@ pub fn AlbumDetail(album_id: ReadSignal<String>, release_id: ReadSignal<String>)
                 on_track_add_next,
                 on_track_add_to_queue,
                 on_track_export,
+                on_track_rename,
+                on_track_bookmark_jump,
+                on_track_bookmark_delete,
                 on_play_album,
                 on_add_album_to_queue,
+                on_restore_release,
+                on_migrate_release,
+                on_play_video,
+                on_compare_releases,
+                on_set_preferred_release,
+                on_edit,
+                on_cancel_edit,
+                on_save_edits,
+                on_export_gift: Some(on_export_gift),
+                offline_download,
+                on_set_offline: Some(on_set_offline),
             }
         } else {
             AlbumDetailLoading {}
@@ -9,21 +9,17 @@ pub fn maybe_not_empty(s: String) -> Option<String> {
     }
 }
 
-/// Get track IDs for an album's first release, sorted by track number.
+/// Get track IDs for an album's default release (the preferred edition, else
+/// the first), sorted by track number.
 /// Returns track IDs ready to be passed to playback.play_album().
 pub async fn get_album_track_ids(
     library_manager: &SharedLibraryManager,
     album_id: &str,
 ) -> Result<Vec<String>, LibraryError> {
-    let releases = library_manager
-        .get()
-        .get_releases_for_album(album_id)
-        .await?;
-    if releases.is_empty() {
+    let Some(release) = library_manager.get().get_default_release(album_id).await? else {
         return Ok(Vec::new());
-    }
-    let first_release = &releases[0];
-    let mut tracks = library_manager.get().get_tracks(&first_release.id).await?;
+    };
+    let mut tracks = library_manager.get().get_tracks(&release.id).await?;
     tracks.sort_by(|a, b| match (a.track_number, b.track_number) {
         (Some(a_num), Some(b_num)) => a_num.cmp(&b_num),
         (Some(_), None) => std::cmp::Ordering::Less,
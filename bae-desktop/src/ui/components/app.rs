@@ -1,5 +1,6 @@
 use crate::ui::app_context::AppServices;
 use crate::ui::app_service::AppService;
+use crate::ui::tray::use_tray;
 use crate::ui::{Route, FAVICON, FLOATING_UI_CORE, FLOATING_UI_DOM, MAIN_CSS, TAILWIND_CSS};
 use dioxus::prelude::*;
 use tracing::debug;
@@ -20,6 +21,20 @@ pub fn App() -> Element {
     // Provide AppService as context for all components
     use_context_provider(|| app_service.clone());
 
+    // Mini player in the tray, which also brings back a hidden window
+    use_tray(&app_service);
+
+    // Windows media controls attach to the window, which exists from here on
+    #[cfg(target_os = "windows")]
+    {
+        use dioxus::desktop::tao::platform::windows::WindowExtWindows;
+        let window = dioxus::desktop::use_window();
+        let media_controls = services.media_controls.clone();
+        use_hook(move || {
+            crate::media_controls::attach_to_window(&media_controls, window.window.hwnd() as _)
+        });
+    }
+
     rsx! {
         document::Link { rel: "icon", href: FAVICON }
         document::Link { rel: "stylesheet", href: MAIN_CSS }
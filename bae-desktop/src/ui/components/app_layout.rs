@@ -4,20 +4,32 @@
 
 use super::now_playing_bar::NowPlayingBar;
 use super::queue_sidebar::QueueSidebar;
+use super::snapshot_restore::SnapshotRestoreHandler;
+use super::undo_toast::UndoToastHandler;
 use super::TitleBar;
+use crate::ui::deep_link::DeepLinkHandler;
+use crate::ui::disc_insert::DiscInsertHandler;
 use crate::ui::shortcuts::ShortcutsHandler;
+use crate::ui::startup::StartupHandler;
 use crate::ui::Route;
-use bae_ui::AppLayoutView;
+use bae_ui::{AppLayoutView, IpcBoundary};
 use dioxus::prelude::*;
 
-/// Layout component that includes title bar, content, playback bar, and sidebar
+/// Layout component that includes title bar, content, playback bar, and sidebar.
+/// Also hosts the deep link, startup and disc insert handlers, which need the
+/// router, the app-wide undo toast and the restore picker for a damaged
+/// library database. The title bar and page each recover on their own from
+/// failed webview calls.
 #[component]
 pub fn AppLayout() -> Element {
     rsx! {
+        DeepLinkHandler {}
+        StartupHandler {}
+        DiscInsertHandler {}
         ShortcutsHandler {
             AppLayoutView {
                 title_bar: rsx! {
-                    TitleBar {}
+                    IpcBoundary { TitleBar {} }
                 },
                 playback_bar: rsx! {
                     NowPlayingBar {}
@@ -25,8 +37,10 @@ pub fn AppLayout() -> Element {
                 queue_sidebar: rsx! {
                     QueueSidebar {}
                 },
-                Outlet::<Route> {}
+                IpcBoundary { Outlet::<Route> {} }
             }
+            UndoToastHandler {}
+            SnapshotRestoreHandler {}
         }
     }
 }
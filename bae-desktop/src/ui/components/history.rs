@@ -0,0 +1,64 @@
+//! History page component
+//!
+//! Uses bae-ui's HistoryView; reloads the listening history whenever the
+//! date range changes.
+
+use crate::ui::app_service::use_app;
+use crate::ui::Route;
+use bae_ui::stores::AppStateStoreExt;
+use bae_ui::HistoryView;
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use dioxus::prelude::*;
+
+/// Start of a YYYY-MM-DD day, `days_after` days later (UTC)
+fn day_start(date: &str, days_after: u64) -> Option<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    Some(
+        date.checked_add_days(Days::new(days_after))?
+            .and_hms_opt(0, 0, 0)?
+            .and_utc(),
+    )
+}
+
+/// History page component - passes state lens to bae-ui's HistoryView
+#[component]
+pub fn HistoryPage() -> Element {
+    let app = use_app();
+    let mut from = use_signal(String::new);
+    let mut to = use_signal(String::new);
+
+    use_effect({
+        let app = app.clone();
+        // The "to" day is included, so the range runs to the start of the next
+        move || app.load_listening_history(day_start(&from(), 0), day_start(&to(), 1))
+    });
+
+    let state = app.state.history();
+
+    let on_play = {
+        let playback = app.playback_handle.clone();
+        move |track_ids: Vec<String>| playback.play_album(track_ids)
+    };
+
+    let on_album_click = move |album_id: String| {
+        navigator().push(Route::AlbumDetail {
+            album_id,
+            release_id: String::new(),
+            track_id: String::new(),
+        });
+    };
+
+    rsx! {
+        HistoryView {
+            state,
+            from: from(),
+            to: to(),
+            on_from_change: move |value| from.set(value),
+            on_to_change: move |value| to.set(value),
+            on_play,
+            on_album_click,
+        }
+    }
+}
+
+pub use HistoryPage as History;
@@ -1,17 +1,18 @@
 //! CD import workflow wrapper - reads context and delegates to CdImportView
 
-use crate::ui::app_service::use_app;
+use crate::ui::app_service::{use_app, AppService};
 use crate::ui::import_helpers::{
-    confirm_and_start_import, lookup_discid, search_by_barcode, search_by_catalog_number,
-    search_general, DiscIdLookupResult,
+    confirm_and_start_import, load_more_search_results, lookup_discid, remember_current_search,
+    search_by_barcode, search_by_catalog_number, search_general, DiscIdLookupResult, SearchPages,
 };
 use bae_core::cd::CdDrive;
 use bae_ui::components::import::CdImportView;
-use bae_ui::display_types::{CdDriveInfo, MatchCandidate, SearchSource, SearchTab};
+use bae_ui::display_types::{CdDriveInfo, ManualEntry, MatchCandidate, SearchSource, SearchTab};
 use bae_ui::stores::import::CandidateEvent;
-use bae_ui::stores::{AppStateStoreExt, StorageProfilesStateStoreExt};
+use bae_ui::stores::{AppStateStoreExt, ImportStateStoreExt, StorageProfilesStateStoreExt};
 use bae_ui::ImportSource;
 use dioxus::prelude::*;
+use dioxus::router::Navigator;
 use tracing::{info, warn};
 
 #[component]
@@ -26,9 +27,11 @@ pub fn CdImport() -> Element {
 
     // Scan for drives on mount
     use_effect({
+        let busy_drives = app.import_handle.busy_drives.clone();
         let mut is_scanning = is_scanning;
         let mut drives = drives;
         move || {
+            let busy_drives = busy_drives.clone();
             spawn(async move {
                 is_scanning.set(true);
                 match CdDrive::detect_drives() {
@@ -38,6 +41,7 @@ pub fn CdImport() -> Element {
                             .map(|d| CdDriveInfo {
                                 device_path: d.device_path.to_string_lossy().to_string(),
                                 name: d.name.clone(),
+                                is_ripping: busy_drives.is_busy(&d.device_path),
                             })
                             .collect();
                         drives.set(display_drives);
@@ -64,54 +68,34 @@ pub fn CdImport() -> Element {
         let mut selected_drive = selected_drive;
         move |device_path: String| {
             let app = app.clone();
-            let device_path_clone = device_path.clone();
             selected_drive.set(Some(device_path.clone()));
-
             spawn(async move {
-                // Set the CD path in state
-                let mut import_store = app.state.import();
-                import_store
-                    .write()
-                    .set_cd_candidate(device_path_clone.clone());
+                select_drive(&app, device_path).await;
+            });
+        }
+    };
 
-                // Attempt DiscID lookup
-                import_store.write().is_looking_up = true;
-                if let Some(mb_discid) = import_store
-                    .read()
-                    .get_metadata()
-                    .and_then(|m| m.mb_discid.clone())
-                {
-                    match lookup_discid(&mb_discid).await {
-                        Ok(result) => {
-                            let matches = match result {
-                                DiscIdLookupResult::NoMatches => vec![],
-                                DiscIdLookupResult::SingleMatch(c) => vec![*c],
-                                DiscIdLookupResult::MultipleMatches(cs) => cs,
-                            };
-                            import_store.write().is_looking_up = false;
-                            import_store
-                                .write()
-                                .dispatch(CandidateEvent::DiscIdLookupComplete {
-                                    matches,
-                                    error: None,
-                                });
-                        }
-                        Err(e) => {
-                            import_store.write().is_looking_up = false;
-                            import_store
-                                .write()
-                                .dispatch(CandidateEvent::DiscIdLookupComplete {
-                                    matches: vec![],
-                                    error: Some(e),
-                                });
-                        }
-                    }
-                } else {
-                    import_store.write().is_looking_up = false;
+    // Pick up a disc inserted while the app is open
+    use_effect({
+        let app = app.clone();
+        let mut selected_drive = selected_drive;
+        move || {
+            if app.state.import().inserted_cd().read().is_none() {
+                return;
+            }
+            let Some(inserted) = app.state.import().write().inserted_cd.take() else {
+                return;
+            };
+            selected_drive.set(Some(inserted.device_path.clone()));
+            let app = app.clone();
+            spawn(async move {
+                select_drive(&app, inserted.device_path).await;
+                if inserted.auto_rip {
+                    auto_rip(&app, navigator).await;
                 }
             });
         }
-    };
+    });
 
     let on_clear = {
         let app = app.clone();
@@ -176,6 +160,8 @@ pub fn CdImport() -> Element {
                     return;
                 };
 
+                remember_current_search(&app);
+
                 let tab = search_state.search_tab;
                 let source = search_state.search_source;
 
@@ -195,6 +181,7 @@ pub fn CdImport() -> Element {
                                 .write()
                                 .dispatch(CandidateEvent::SearchComplete {
                                     results: vec![],
+                                    result_counts: vec![],
                                     error: Some("Please fill in at least one field".to_string()),
                                 });
                             return;
@@ -202,14 +189,23 @@ pub fn CdImport() -> Element {
 
                         import_store.write().dispatch(CandidateEvent::StartSearch);
 
-                        let result =
-                            search_general(metadata, source, artist, album, year, label).await;
+                        let result = search_general(
+                            metadata,
+                            source,
+                            artist,
+                            album,
+                            year,
+                            label,
+                            SearchPages::default(),
+                        )
+                        .await;
                         match result {
-                            Ok(candidates) => {
+                            Ok(page) => {
                                 import_store
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
-                                        results: candidates,
+                                        results: page.candidates,
+                                        result_counts: page.result_counts,
                                         error: None,
                                     });
                             }
@@ -218,6 +214,7 @@ pub fn CdImport() -> Element {
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
                                         results: vec![],
+                                        result_counts: vec![],
                                         error: Some(format!("Search failed: {}", e)),
                                     });
                             }
@@ -230,6 +227,7 @@ pub fn CdImport() -> Element {
                                 .write()
                                 .dispatch(CandidateEvent::SearchComplete {
                                     results: vec![],
+                                    result_counts: vec![],
                                     error: Some("Please enter a catalog number".to_string()),
                                 });
                             return;
@@ -237,13 +235,20 @@ pub fn CdImport() -> Element {
 
                         import_store.write().dispatch(CandidateEvent::StartSearch);
 
-                        let result = search_by_catalog_number(metadata, source, catno).await;
+                        let result = search_by_catalog_number(
+                            metadata,
+                            source,
+                            catno,
+                            SearchPages::default(),
+                        )
+                        .await;
                         match result {
-                            Ok(candidates) => {
+                            Ok(page) => {
                                 import_store
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
-                                        results: candidates,
+                                        results: page.candidates,
+                                        result_counts: page.result_counts,
                                         error: None,
                                     });
                             }
@@ -252,6 +257,7 @@ pub fn CdImport() -> Element {
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
                                         results: vec![],
+                                        result_counts: vec![],
                                         error: Some(format!("Search failed: {}", e)),
                                     });
                             }
@@ -264,6 +270,7 @@ pub fn CdImport() -> Element {
                                 .write()
                                 .dispatch(CandidateEvent::SearchComplete {
                                     results: vec![],
+                                    result_counts: vec![],
                                     error: Some("Please enter a barcode".to_string()),
                                 });
                             return;
@@ -271,13 +278,16 @@ pub fn CdImport() -> Element {
 
                         import_store.write().dispatch(CandidateEvent::StartSearch);
 
-                        let result = search_by_barcode(metadata, source, barcode).await;
+                        let result =
+                            search_by_barcode(metadata, source, barcode, SearchPages::default())
+                                .await;
                         match result {
-                            Ok(candidates) => {
+                            Ok(page) => {
                                 import_store
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
-                                        results: candidates,
+                                        results: page.candidates,
+                                        result_counts: page.result_counts,
                                         error: None,
                                     });
                             }
@@ -286,6 +296,7 @@ pub fn CdImport() -> Element {
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
                                         results: vec![],
+                                        result_counts: vec![],
                                         error: Some(format!("Search failed: {}", e)),
                                     });
                             }
@@ -307,6 +318,16 @@ pub fn CdImport() -> Element {
         }
     };
 
+    let on_load_more = {
+        let app = app.clone();
+        move |_| {
+            let app = app.clone();
+            spawn(async move {
+                load_more_search_results(&app).await;
+            });
+        }
+    };
+
     let on_manual_match_select = {
         let app = app.clone();
         move |index: usize| {
@@ -327,6 +348,13 @@ pub fn CdImport() -> Element {
         }
     };
 
+    let on_cover_visible = EventHandler::new({
+        let app = app.clone();
+        move |(release_id, visible): (String, bool)| {
+            app.cover_fetcher.set_visible(&release_id, visible)
+        }
+    });
+
     let on_retry_discid_lookup = {
         let app = app.clone();
         move |_| {
@@ -378,6 +406,16 @@ pub fn CdImport() -> Element {
         }
     };
 
+    let on_rip_unidentified = {
+        let app = app.clone();
+        move |_| {
+            let app = app.clone();
+            spawn(async move {
+                rip_unidentified(&app, navigator).await;
+            });
+        }
+    };
+
     let on_edit = {
         let app = app.clone();
         move |_| {
@@ -504,8 +542,11 @@ pub fn CdImport() -> Element {
             on_manual_match_select,
             on_search: move |_| perform_search(),
             on_cancel_search: move |_| cancel_search(),
+            on_load_more,
             on_manual_confirm,
             on_retry_discid_lookup,
+            on_rip_unidentified,
+            on_cover_visible: Some(on_cover_visible),
             on_select_remote_cover: |_| {},
             on_select_local_cover: |_| {},
             on_storage_profile_change: |_| {},
@@ -517,3 +558,106 @@ pub fn CdImport() -> Element {
         }
     }
 }
+
+/// Select the disc in a drive and look up its DiscID
+async fn select_drive(app: &AppService, device_path: String) {
+    let mut import_store = app.state.import();
+    import_store.write().set_cd_candidate(device_path);
+
+    import_store.write().is_looking_up = true;
+    if let Some(mb_discid) = import_store
+        .read()
+        .get_metadata()
+        .and_then(|m| m.mb_discid.clone())
+    {
+        match lookup_discid(&mb_discid).await {
+            Ok(result) => {
+                let matches = match result {
+                    DiscIdLookupResult::NoMatches => vec![],
+                    DiscIdLookupResult::SingleMatch(c) => vec![*c],
+                    DiscIdLookupResult::MultipleMatches(cs) => cs,
+                };
+                import_store.write().is_looking_up = false;
+                import_store
+                    .write()
+                    .dispatch(CandidateEvent::DiscIdLookupComplete {
+                        matches,
+                        error: None,
+                    });
+            }
+            Err(e) => {
+                import_store.write().is_looking_up = false;
+                import_store
+                    .write()
+                    .dispatch(CandidateEvent::DiscIdLookupComplete {
+                        matches: vec![],
+                        error: Some(e),
+                    });
+            }
+        }
+    } else {
+        import_store.write().is_looking_up = false;
+    }
+}
+
+/// Start ripping into the default storage profile if the DiscID lookup
+/// settled on a single release. Otherwise the import stays open for the
+/// user to pick one.
+async fn auto_rip(app: &AppService, navigator: Navigator) {
+    let Some(candidate) = app.state.import().read().get_confirmed_candidate() else {
+        info!("Disc not recognized, waiting for a release to be picked");
+        return;
+    };
+    info!("Disc recognized as '{}', ripping", candidate.title);
+    rip_into_default_profile(app, candidate, navigator).await;
+}
+
+/// Rip a disc whose DiscID lookup failed under placeholder metadata. The
+/// lookup is queued and the release filled in once it succeeds.
+async fn rip_unidentified(app: &AppService, navigator: Navigator) {
+    let toc_info = app.state.import().read().cd_toc_info.clone();
+    let Some((_, first_track, last_track)) = toc_info else {
+        warn!("No TOC for the selected disc, can't rip it unidentified");
+        return;
+    };
+    let entry = ManualEntry {
+        title: "Unknown Album".to_string(),
+        artist: "Unknown Artist".to_string(),
+        year: String::new(),
+        // Blank titles are numbered when the release is saved
+        tracks: vec![String::new(); (first_track..=last_track).count()],
+        cover_url: None,
+    };
+    app.state
+        .import()
+        .write()
+        .dispatch(CandidateEvent::ConfirmManualEntry(entry));
+    let Some(candidate) = app.state.import().read().get_confirmed_candidate() else {
+        return;
+    };
+    info!("Ripping unidentified disc, DiscID lookup queued");
+    rip_into_default_profile(app, candidate, navigator).await;
+}
+
+async fn rip_into_default_profile(
+    app: &AppService,
+    candidate: MatchCandidate,
+    navigator: Navigator,
+) {
+    let default_profile = app
+        .state
+        .storage_profiles()
+        .profiles()
+        .read()
+        .iter()
+        .find(|p| p.is_default)
+        .map(|p| p.id.clone());
+    app.state
+        .import()
+        .write()
+        .dispatch(CandidateEvent::SelectStorageProfile(default_profile));
+
+    if let Err(e) = confirm_and_start_import(app, candidate, ImportSource::Cd, navigator).await {
+        warn!("Failed to start rip: {}", e);
+    }
+}
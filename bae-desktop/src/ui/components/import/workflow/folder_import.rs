@@ -2,15 +2,22 @@
 
 use crate::ui::app_service::use_app;
 use crate::ui::import_helpers::{
-    confirm_and_start_import, lookup_discid, search_by_barcode, search_by_catalog_number,
-    search_general, DiscIdLookupResult,
+    confirm_and_start_import, fetch_release_tracks, load_more_search_results, lookup_discid,
+    pick_and_scan, remember_current_search, save_release_template, search_by_barcode,
+    search_by_catalog_number, search_general, DiscIdLookupResult, SearchPages,
 };
 use crate::ui::Route;
+use bae_core::import::needle_drop::{analyze_side, assign_tracks_to_sides, suggest_track_starts};
+use bae_core::storage::estimate_storage;
 use bae_ui::components::import::FolderImportView;
-use bae_ui::display_types::{MatchCandidate, SearchSource, SearchTab, SelectedCover};
+use bae_ui::display_types::{
+    AudioContentInfo, AudioFileQuality, AudioQualityCheck, DuplicateAudioCheck,
+    DuplicateAudioGroup, ManualEntry, MatchCandidate, NeedleDropCheck, NeedleDropSide,
+    NeedleDropTrack, SearchSource, SearchTab, SelectedCover, StorageEstimate,
+};
 use bae_ui::stores::import::CandidateEvent;
-use bae_ui::stores::{AppStateStoreExt, StorageProfilesStateStoreExt};
-use bae_ui::ImportSource;
+use bae_ui::stores::{AppStateStoreExt, ConfigStateStoreExt, StorageProfilesStateStoreExt};
+use bae_ui::{ImportSource, StorageLocation};
 use dioxus::prelude::*;
 use tracing::{info, warn};
 
@@ -27,8 +34,28 @@ pub fn FolderImport() -> Element {
     let import_state = app.state.import();
     let storage_profiles = app.state.storage_profiles().profiles();
 
+    // Cloud footprint of the current release, when a cloud profile is selected
+    let config_store = app.state.config();
+    let storage_estimate = use_memo(move || {
+        let st = import_state.read();
+        let profile_id = st.get_storage_profile_id()?;
+        let profiles = storage_profiles.read();
+        let profile = profiles.iter().find(|p| p.id == profile_id)?;
+        if profile.location != StorageLocation::Cloud {
+            return None;
+        }
+        let files = st.current_candidate_state()?.files();
+        let estimate = estimate_storage(files.file_sizes(), profile.encrypted);
+        Some(StorageEstimate {
+            object_count: estimate.object_count,
+            stored_bytes: estimate.stored_bytes,
+            monthly_cost: estimate.monthly_cost(*config_store.cloud_price_per_gb().read()),
+        })
+    });
+
     // Extract values needed by handlers (handlers need current values, not lenses)
     let current_candidate_key = import_state.read().current_candidate_key.clone();
+    let import_source = import_state.read().selected_import_source;
 
     // Handlers
     let on_folder_select = {
@@ -36,25 +63,7 @@ pub fn FolderImport() -> Element {
         move |_| {
             let app = app.clone();
             spawn(async move {
-                if let Some(path) = rfd::AsyncFileDialog::new().pick_folder().await {
-                    let path_str = path.path().to_string_lossy().to_string();
-                    let import_handle = app.import_handle.clone();
-
-                    // Clear existing candidates if this is the first folder
-                    {
-                        let mut import_store = app.state.import();
-                        if import_store.read().detected_candidates.is_empty() {
-                            import_store.write().reset();
-                        }
-                        import_store.write().is_scanning_candidates = true;
-                    }
-
-                    if let Err(e) =
-                        import_handle.enqueue_folder_scan(std::path::PathBuf::from(path_str))
-                    {
-                        warn!("Failed to add folder to scan: {}", e);
-                    }
-                }
+                pick_and_scan(&app).await;
             });
         }
     };
@@ -113,6 +122,8 @@ pub fn FolderImport() -> Element {
                     return;
                 };
 
+                remember_current_search(&app);
+
                 let tab = search_state.search_tab;
                 let source = search_state.search_source;
 
@@ -132,6 +143,7 @@ pub fn FolderImport() -> Element {
                                 .write()
                                 .dispatch(CandidateEvent::SearchComplete {
                                     results: vec![],
+                                    result_counts: vec![],
                                     error: Some("Please fill in at least one field".to_string()),
                                 });
                             return;
@@ -139,14 +151,23 @@ pub fn FolderImport() -> Element {
 
                         import_store.write().dispatch(CandidateEvent::StartSearch);
 
-                        let result =
-                            search_general(metadata, source, artist, album, year, label).await;
+                        let result = search_general(
+                            metadata,
+                            source,
+                            artist,
+                            album,
+                            year,
+                            label,
+                            SearchPages::default(),
+                        )
+                        .await;
                         match result {
-                            Ok(candidates) => {
+                            Ok(page) => {
                                 import_store
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
-                                        results: candidates,
+                                        results: page.candidates,
+                                        result_counts: page.result_counts,
                                         error: None,
                                     });
                             }
@@ -155,6 +176,7 @@ pub fn FolderImport() -> Element {
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
                                         results: vec![],
+                                        result_counts: vec![],
                                         error: Some(format!("Search failed: {}", e)),
                                     });
                             }
@@ -167,6 +189,7 @@ pub fn FolderImport() -> Element {
                                 .write()
                                 .dispatch(CandidateEvent::SearchComplete {
                                     results: vec![],
+                                    result_counts: vec![],
                                     error: Some("Please enter a catalog number".to_string()),
                                 });
                             return;
@@ -174,13 +197,20 @@ pub fn FolderImport() -> Element {
 
                         import_store.write().dispatch(CandidateEvent::StartSearch);
 
-                        let result = search_by_catalog_number(metadata, source, catno).await;
+                        let result = search_by_catalog_number(
+                            metadata,
+                            source,
+                            catno,
+                            SearchPages::default(),
+                        )
+                        .await;
                         match result {
-                            Ok(candidates) => {
+                            Ok(page) => {
                                 import_store
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
-                                        results: candidates,
+                                        results: page.candidates,
+                                        result_counts: page.result_counts,
                                         error: None,
                                     });
                             }
@@ -189,6 +219,7 @@ pub fn FolderImport() -> Element {
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
                                         results: vec![],
+                                        result_counts: vec![],
                                         error: Some(format!("Search failed: {}", e)),
                                     });
                             }
@@ -201,6 +232,7 @@ pub fn FolderImport() -> Element {
                                 .write()
                                 .dispatch(CandidateEvent::SearchComplete {
                                     results: vec![],
+                                    result_counts: vec![],
                                     error: Some("Please enter a barcode".to_string()),
                                 });
                             return;
@@ -208,13 +240,16 @@ pub fn FolderImport() -> Element {
 
                         import_store.write().dispatch(CandidateEvent::StartSearch);
 
-                        let result = search_by_barcode(metadata, source, barcode).await;
+                        let result =
+                            search_by_barcode(metadata, source, barcode, SearchPages::default())
+                                .await;
                         match result {
-                            Ok(candidates) => {
+                            Ok(page) => {
                                 import_store
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
-                                        results: candidates,
+                                        results: page.candidates,
+                                        result_counts: page.result_counts,
                                         error: None,
                                     });
                             }
@@ -223,6 +258,7 @@ pub fn FolderImport() -> Element {
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
                                         results: vec![],
+                                        result_counts: vec![],
                                         error: Some(format!("Search failed: {}", e)),
                                     });
                             }
@@ -244,6 +280,16 @@ pub fn FolderImport() -> Element {
         }
     };
 
+    let on_load_more = {
+        let app = app.clone();
+        move |_| {
+            let app = app.clone();
+            spawn(async move {
+                load_more_search_results(&app).await;
+            });
+        }
+    };
+
     let on_manual_match_select = {
         let app = app.clone();
         move |index: usize| {
@@ -264,6 +310,38 @@ pub fn FolderImport() -> Element {
         }
     };
 
+    let on_switch_to_manual_entry = {
+        let app = app.clone();
+        move |_| {
+            app.state
+                .import()
+                .write()
+                .dispatch(CandidateEvent::SwitchToManualEntry);
+        }
+    };
+
+    let on_manual_entry_confirm = {
+        let app = app.clone();
+        move |entry: ManualEntry| {
+            app.state
+                .import()
+                .write()
+                .dispatch(CandidateEvent::ConfirmManualEntry(entry));
+        }
+    };
+
+    let on_save_release_template = {
+        let app = app.clone();
+        move |entry: ManualEntry| save_release_template(&app, entry)
+    };
+
+    let on_cover_visible = EventHandler::new({
+        let app = app.clone();
+        move |(release_id, visible): (String, bool)| {
+            app.cover_fetcher.set_visible(&release_id, visible)
+        }
+    });
+
     let on_retry_discid_lookup = {
         let app = app.clone();
         move |_| {
@@ -335,8 +413,7 @@ pub fn FolderImport() -> Element {
                 let confirmed = app.state.import().read().get_confirmed_candidate();
                 if let Some(candidate) = confirmed {
                     if let Err(e) =
-                        confirm_and_start_import(&app, candidate, ImportSource::Folder, navigator)
-                            .await
+                        confirm_and_start_import(&app, candidate, import_source, navigator).await
                     {
                         warn!("Failed to confirm and start import: {}", e);
                     }
@@ -345,6 +422,237 @@ pub fn FolderImport() -> Element {
         }
     };
 
+    let on_check_audio_quality = {
+        let app = app.clone();
+        move |_| {
+            let app = app.clone();
+            spawn(async move {
+                let (key, paths) = {
+                    let import_store = app.state.import();
+                    let st = import_store.read();
+                    let Some(key) = st.current_candidate_key.clone() else {
+                        return;
+                    };
+                    let Some(candidate_state) = st.current_candidate_state() else {
+                        return;
+                    };
+                    let paths: Vec<std::path::PathBuf> = match &candidate_state.files().audio {
+                        AudioContentInfo::TrackFiles(tracks) => {
+                            tracks.iter().map(|t| t.path.clone().into()).collect()
+                        }
+                        AudioContentInfo::CueFlacPairs(pairs) => pairs
+                            .iter()
+                            .map(|p| std::path::Path::new(&p.cue_path).with_file_name(&p.flac_name))
+                            .collect(),
+                    };
+                    (key, paths)
+                };
+
+                app.state
+                    .import()
+                    .write()
+                    .audio_quality_checks
+                    .insert(key.clone(), AudioQualityCheck::Checking);
+
+                let result = tokio::task::spawn_blocking(move || {
+                    bae_core::import::check_audio_files(&paths)
+                })
+                .await;
+
+                let mut import_store = app.state.import();
+                match result {
+                    Ok(checks) => {
+                        let files = checks
+                            .iter()
+                            .map(|c| AudioFileQuality {
+                                name: c
+                                    .path
+                                    .file_name()
+                                    .map(|n| n.to_string_lossy().to_string())
+                                    .unwrap_or_default(),
+                                error: c.error.clone(),
+                                decode_errors: c.decode_errors,
+                                duration_mismatch_ms: c.duration_mismatch_ms(),
+                                clipped_runs: c.clipped_runs,
+                                decoder_fallback: c.decoder_fallback.map(|f| f.label().to_string()),
+                            })
+                            .collect();
+                        import_store
+                            .write()
+                            .audio_quality_checks
+                            .insert(key, AudioQualityCheck::Done(files));
+                    }
+                    Err(e) => {
+                        warn!("Audio check task failed: {}", e);
+
+                        import_store.write().audio_quality_checks.remove(&key);
+                    }
+                }
+            });
+        }
+    };
+
+    let on_check_duplicate_audio = {
+        let app = app.clone();
+        move |_| {
+            let app = app.clone();
+            spawn(async move {
+                let (key, paths) = {
+                    let import_store = app.state.import();
+                    let st = import_store.read();
+                    let Some(key) = st.current_candidate_key.clone() else {
+                        return;
+                    };
+                    let Some(candidate_state) = st.current_candidate_state() else {
+                        return;
+                    };
+                    // A CUE/FLAC disc is one file, so only file-per-track releases can repeat a track
+                    let paths: Vec<std::path::PathBuf> = match &candidate_state.files().audio {
+                        AudioContentInfo::TrackFiles(tracks) => {
+                            tracks.iter().map(|t| t.path.clone().into()).collect()
+                        }
+                        AudioContentInfo::CueFlacPairs(_) => Vec::new(),
+                    };
+                    (key, paths)
+                };
+
+                app.state
+                    .import()
+                    .write()
+                    .duplicate_audio_checks
+                    .insert(key.clone(), DuplicateAudioCheck::Checking);
+
+                let result = tokio::task::spawn_blocking(move || {
+                    bae_core::import::find_duplicate_audio(&paths)
+                })
+                .await;
+
+                let mut import_store = app.state.import();
+                match result {
+                    Ok(groups) => {
+                        let groups = groups
+                            .into_iter()
+                            .map(|g| DuplicateAudioGroup {
+                                files: g
+                                    .files
+                                    .iter()
+                                    .map(|p| p.to_string_lossy().to_string())
+                                    .collect(),
+                                keep: 0,
+                            })
+                            .collect();
+                        import_store
+                            .write()
+                            .duplicate_audio_checks
+                            .insert(key, DuplicateAudioCheck::Done(groups));
+                    }
+                    Err(e) => {
+                        warn!("Duplicate audio check task failed: {}", e);
+
+                        import_store.write().duplicate_audio_checks.remove(&key);
+                    }
+                }
+            });
+        }
+    };
+
+    let on_keep_duplicate = {
+        let app = app.clone();
+        move |(group_index, file_index): (usize, usize)| {
+            let mut import_store = app.state.import();
+            let mut st = import_store.write();
+            let Some(key) = st.current_candidate_key.clone() else {
+                return;
+            };
+            if let Some(DuplicateAudioCheck::Done(groups)) = st.duplicate_audio_checks.get_mut(&key)
+            {
+                if let Some(group) = groups.get_mut(group_index) {
+                    group.keep = file_index;
+                }
+            }
+        }
+    };
+
+    let on_split_sides = {
+        let app = app.clone();
+        move |_| {
+            let app = app.clone();
+            spawn(async move {
+                let (key, candidate, sides) = {
+                    let import_store = app.state.import();
+                    let st = import_store.read();
+                    let Some(key) = st.current_candidate_key.clone() else {
+                        return;
+                    };
+                    let Some(candidate) = st.get_confirmed_candidate() else {
+                        return;
+                    };
+                    let Some(candidate_state) = st.current_candidate_state() else {
+                        return;
+                    };
+                    // Each track file is taken to be one side
+                    let sides: Vec<(String, String)> = match &candidate_state.files().audio {
+                        AudioContentInfo::TrackFiles(files) => files
+                            .iter()
+                            .map(|f| (f.path.clone(), f.name.clone()))
+                            .collect(),
+                        AudioContentInfo::CueFlacPairs(_) => Vec::new(),
+                    };
+                    (key, candidate, sides)
+                };
+
+                app.state
+                    .import()
+                    .write()
+                    .needle_drop_checks
+                    .insert(key.clone(), NeedleDropCheck::Analyzing);
+
+                let check = match split_suggestions(&candidate, sides).await {
+                    Ok(sides) => NeedleDropCheck::Ready(sides),
+                    Err(e) => {
+                        warn!("Couldn't set up side split: {}", e);
+                        NeedleDropCheck::Failed(e)
+                    }
+                };
+                app.state
+                    .import()
+                    .write()
+                    .needle_drop_checks
+                    .insert(key, check);
+            });
+        }
+    };
+
+    let on_side_track_start_change = {
+        let app = app.clone();
+        move |(side_index, track_index, start_ms): (usize, usize, u64)| {
+            let mut import_store = app.state.import();
+            let mut st = import_store.write();
+            let Some(key) = st.current_candidate_key.clone() else {
+                return;
+            };
+            if let Some(NeedleDropCheck::Ready(sides)) = st.needle_drop_checks.get_mut(&key) {
+                if let Some(track) = sides
+                    .get_mut(side_index)
+                    .and_then(|side| side.tracks.get_mut(track_index))
+                {
+                    track.start_ms = start_ms;
+                }
+            }
+        }
+    };
+
+    let on_cancel_side_split = {
+        let app = app.clone();
+        move |_| {
+            let mut import_store = app.state.import();
+            let mut st = import_store.write();
+            if let Some(key) = st.current_candidate_key.clone() {
+                st.needle_drop_checks.remove(&key);
+            }
+        }
+    };
+
     // Search field change handlers
     let on_search_source_change = {
         let app = app.clone();
@@ -476,6 +784,7 @@ pub fn FolderImport() -> Element {
         navigator.push(Route::AlbumDetail {
             album_id,
             release_id: String::new(),
+            track_id: String::new(),
         });
     };
 
@@ -502,6 +811,8 @@ pub fn FolderImport() -> Element {
             selected_text_file: selected_text_file.read().clone(),
             text_file_content,
             storage_profiles,
+            storage_estimate: storage_estimate(),
+            from_archive: import_source == ImportSource::Archive,
             on_folder_select_click: on_folder_select,
             on_text_file_select: move |name| selected_text_file.set(Some(name)),
             on_text_file_close: move |_| selected_text_file.set(None),
@@ -519,15 +830,76 @@ pub fn FolderImport() -> Element {
             on_manual_match_select,
             on_search: move |_| perform_search(),
             on_cancel_search: move |_| cancel_search(),
+            on_load_more,
             on_manual_confirm,
+            on_switch_to_manual_entry,
+            on_manual_entry_confirm,
+            on_save_release_template,
             on_retry_discid_lookup,
+            on_cover_visible: Some(on_cover_visible),
             on_select_remote_cover,
             on_select_local_cover,
             on_storage_profile_change,
             on_edit,
             on_confirm,
+            on_check_audio_quality,
+            on_check_duplicate_audio,
+            on_keep_duplicate,
             on_configure_storage,
             on_view_duplicate,
+            on_split_sides,
+            on_side_track_start_change,
+            on_cancel_side_split,
         }
     }
 }
+
+/// Match the release's tracks to the side recordings and suggest where each
+/// track starts, from the gaps in the audio
+async fn split_suggestions(
+    candidate: &MatchCandidate,
+    sides: Vec<(String, String)>,
+) -> Result<Vec<NeedleDropSide>, String> {
+    if sides.is_empty() {
+        return Err("Only releases with one file per side can be split".to_string());
+    }
+    let tracks = fetch_release_tracks(candidate).await?;
+
+    let paths: Vec<std::path::PathBuf> = sides.iter().map(|(path, _)| path.into()).collect();
+    let analyses = tokio::task::spawn_blocking(move || {
+        paths
+            .iter()
+            .map(|path| analyze_side(path))
+            .collect::<Result<Vec<_>, String>>()
+    })
+    .await
+    .map_err(|e| format!("Side analysis task failed: {}", e))??;
+
+    let durations: Vec<u64> = analyses.iter().map(|a| a.duration_ms).collect();
+    let side_tracks = assign_tracks_to_sides(&tracks, &durations)?;
+
+    Ok(sides
+        .into_iter()
+        .zip(analyses)
+        .zip(side_tracks)
+        .map(|(((path, name), analysis), tracks)| {
+            let starts = suggest_track_starts(&analysis, &tracks);
+            NeedleDropSide {
+                path,
+                name,
+                duration_ms: analysis.duration_ms,
+                waveform: analysis.waveform,
+                tracks: tracks
+                    .into_iter()
+                    .zip(starts)
+                    .map(|(track, start_ms)| NeedleDropTrack {
+                        position: track.position,
+                        title: track.title,
+                        start_ms,
+                        expected_duration_ms: track.duration_ms,
+                    })
+                    .collect(),
+            }
+        })
+        .collect())
+}
@@ -3,8 +3,10 @@ use super::cd_import::CdImport;
 use super::folder_import::FolderImport;
 #[cfg(feature = "torrent")]
 use super::torrent_import::TorrentImport;
-use crate::ui::app_service::use_app;
-use crate::ui::import_helpers::{has_unclean_state, load_selected_release};
+use crate::ui::app_service::{use_app, AppService};
+use crate::ui::import_helpers::{
+    has_unclean_state, load_selected_release, pick_and_scan, remove_extracted_candidates,
+};
 use bae_ui::stores::AppStateStoreExt;
 use bae_ui::{ConfirmDialogView, ImportSource, ImportView};
 use dioxus::prelude::*;
@@ -34,6 +36,7 @@ pub fn ImportPage() -> Element {
                 pending_switch.set(Some(source));
             } else {
                 // Switch directly
+                remove_extracted_candidates(&app, detected_paths(&app));
                 let mut import_store = app.state.import();
                 let mut state = import_store.write();
                 state.selected_import_source = source;
@@ -46,6 +49,7 @@ pub fn ImportPage() -> Element {
         let app = app.clone();
         move |_| {
             if let Some(source) = pending_switch() {
+                remove_extracted_candidates(&app, detected_paths(&app));
                 let mut import_store = app.state.import();
                 let mut state = import_store.write();
                 state.selected_import_source = source;
@@ -55,6 +59,18 @@ pub fn ImportPage() -> Element {
         }
     };
 
+    // Covers for the match list on screen, fetched a few at a time; a new
+    // list cancels what's left of the old one
+    let match_list_release_ids = use_memo(move || import_store.read().match_list_release_ids());
+    use_effect({
+        let app = app.clone();
+        move || app.fetch_cover_thumbnails(match_list_release_ids())
+    });
+    use_drop({
+        let cover_fetcher = app.cover_fetcher.clone();
+        move || cover_fetcher.cancel()
+    });
+
     let on_cancel_switch = move |_| {
         pending_switch.set(None);
     };
@@ -65,24 +81,7 @@ pub fn ImportPage() -> Element {
         move |_| {
             let app = app.clone();
             spawn(async move {
-                if let Some(path) = rfd::AsyncFileDialog::new().pick_folder().await {
-                    let path_str = path.path().to_string_lossy().to_string();
-                    let import_handle = app.import_handle.clone();
-
-                    {
-                        let mut import_store = app.state.import();
-                        if import_store.read().detected_candidates.is_empty() {
-                            import_store.write().reset();
-                        }
-                        import_store.write().is_scanning_candidates = true;
-                    }
-
-                    if let Err(e) =
-                        import_handle.enqueue_folder_scan(std::path::PathBuf::from(path_str))
-                    {
-                        warn!("Failed to add folder to scan: {}", e);
-                    }
-                }
+                pick_and_scan(&app).await;
             });
         }
     };
@@ -103,6 +102,14 @@ pub fn ImportPage() -> Element {
     let on_remove_candidate = {
         let app = app.clone();
         move |index: usize| {
+            let path = app
+                .state
+                .import()
+                .read()
+                .detected_candidates
+                .get(index)
+                .map(|c| c.path.clone());
+            remove_extracted_candidates(&app, path.into_iter().collect());
             app.state.import().write().remove_detected_release(index);
         }
     };
@@ -110,6 +117,7 @@ pub fn ImportPage() -> Element {
     let on_clear_all = {
         let app = app.clone();
         move |_| {
+            remove_extracted_candidates(&app, detected_paths(&app));
             let mut store = app.state.import();
             let mut state = store.write();
             state.detected_candidates.clear();
@@ -136,7 +144,7 @@ pub fn ImportPage() -> Element {
             on_open_folder,
 
             match selected_source {
-                ImportSource::Folder => rsx! {
+                ImportSource::Folder | ImportSource::Archive => rsx! {
                     FolderImport {}
                 },
                 #[cfg(feature = "torrent")]
@@ -168,3 +176,14 @@ pub fn ImportPage() -> Element {
         }
     }
 }
+
+/// Folders of every candidate in the list
+fn detected_paths(app: &AppService) -> Vec<String> {
+    app.state
+        .import()
+        .read()
+        .detected_candidates
+        .iter()
+        .map(|c| c.path.clone())
+        .collect()
+}
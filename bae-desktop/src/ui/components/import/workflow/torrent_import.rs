@@ -2,13 +2,16 @@
 
 use crate::ui::app_service::use_app;
 use crate::ui::import_helpers::{
-    confirm_and_start_import, lookup_discid, search_by_barcode, search_by_catalog_number,
-    search_general, DiscIdLookupResult,
+    confirm_and_start_import, load_more_search_results, lookup_discid, remember_current_search,
+    search_by_barcode, search_by_catalog_number, search_general, DiscIdLookupResult, SearchPages,
 };
+use bae_core::torrent::client::TorrentFile;
+use bae_core::torrent::detect_album_folders;
 use bae_core::torrent::ffi::TorrentInfo as BaeTorrentInfo;
 use bae_ui::components::import::{TorrentImportView, TrackerConnectionStatus, TrackerStatus};
 use bae_ui::display_types::{
-    MatchCandidate, SearchSource, SearchTab, TorrentFileInfo, TorrentInfo as DisplayTorrentInfo,
+    MatchCandidate, SearchSource, SearchTab, TorrentAlbumFolderInfo, TorrentFileInfo,
+    TorrentInfo as DisplayTorrentInfo,
 };
 use bae_ui::stores::import::CandidateEvent;
 use bae_ui::stores::{AppStateStoreExt, StorageProfilesStateStoreExt};
@@ -41,6 +44,26 @@ fn to_display_torrent_info(info: &BaeTorrentInfo) -> DisplayTorrentInfo {
     }
 }
 
+/// Release folders in the torrent, for picking which albums to download
+fn to_album_folders(info: &BaeTorrentInfo) -> Vec<TorrentAlbumFolderInfo> {
+    let files: Vec<TorrentFile> = info
+        .files
+        .iter()
+        .map(|f| TorrentFile {
+            path: PathBuf::from(&f.path),
+            size: f.size,
+        })
+        .collect();
+    detect_album_folders(&files)
+        .into_iter()
+        .map(|folder| TorrentAlbumFolderInfo {
+            path: folder.path.to_string_lossy().to_string(),
+            file_count: folder.file_count,
+            total_size: folder.total_size,
+        })
+        .collect()
+}
+
 /// Generate mock tracker statuses from tracker URLs
 fn generate_tracker_statuses(trackers: &[String]) -> Vec<TrackerStatus> {
     trackers
@@ -80,6 +103,7 @@ pub fn TorrentImport() -> Element {
     let is_dragging = use_signal(|| false);
     let mut input_mode = use_signal(|| TorrentInputMode::File);
     let torrent_info_signal = use_signal(|| Option::<BaeTorrentInfo>::None);
+    let mut selected_album_folders = use_signal(Vec::<String>::new);
 
     // Get lenses for reactive props
     let import_state = app.state.import();
@@ -92,9 +116,26 @@ pub fn TorrentImport() -> Element {
         .map(|info| generate_tracker_statuses(&info.trackers))
         .unwrap_or_default();
     let display_torrent_info = torrent_info_read.as_ref().map(to_display_torrent_info);
+    let album_folders = torrent_info_read
+        .as_ref()
+        .map(to_album_folders)
+        .unwrap_or_default();
+    let selected_folders = selected_album_folders.read().clone();
+    // Once albums are picked, only their files are listed
     let torrent_files = display_torrent_info
         .as_ref()
-        .map(|info| info.files.clone())
+        .map(|info| {
+            info.files
+                .iter()
+                .filter(|f| {
+                    selected_folders.is_empty()
+                        || selected_folders
+                            .iter()
+                            .any(|folder| std::path::Path::new(&f.path).starts_with(folder))
+                })
+                .cloned()
+                .collect()
+        })
         .unwrap_or_default();
     drop(torrent_info_read);
 
@@ -115,6 +156,7 @@ pub fn TorrentImport() -> Element {
 
                     match bae_core::torrent::parse_torrent_info(&path) {
                         Ok(info) => {
+                            selected_album_folders.set(Vec::new());
                             torrent_info_signal.set(Some(info));
                             app.state.import().write().switch_candidate(Some(path_str));
                         }
@@ -139,6 +181,16 @@ pub fn TorrentImport() -> Element {
         move |_| {
             app.state.import().write().reset();
             torrent_info_signal.set(None);
+            selected_album_folders.set(Vec::new());
+        }
+    };
+
+    let on_toggle_album_folder = move |path: String| {
+        let mut selected = selected_album_folders.write();
+        if let Some(pos) = selected.iter().position(|p| *p == path) {
+            selected.remove(pos);
+        } else {
+            selected.push(path);
         }
     };
 
@@ -196,6 +248,8 @@ pub fn TorrentImport() -> Element {
                     return;
                 };
 
+                remember_current_search(&app);
+
                 let tab = search_state.search_tab;
                 let source = search_state.search_source;
 
@@ -215,6 +269,7 @@ pub fn TorrentImport() -> Element {
                                 .write()
                                 .dispatch(CandidateEvent::SearchComplete {
                                     results: vec![],
+                                    result_counts: vec![],
                                     error: Some("Please fill in at least one field".to_string()),
                                 });
                             return;
@@ -222,14 +277,23 @@ pub fn TorrentImport() -> Element {
 
                         import_store.write().dispatch(CandidateEvent::StartSearch);
 
-                        let result =
-                            search_general(metadata, source, artist, album, year, label).await;
+                        let result = search_general(
+                            metadata,
+                            source,
+                            artist,
+                            album,
+                            year,
+                            label,
+                            SearchPages::default(),
+                        )
+                        .await;
                         match result {
-                            Ok(candidates) => {
+                            Ok(page) => {
                                 import_store
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
-                                        results: candidates,
+                                        results: page.candidates,
+                                        result_counts: page.result_counts,
                                         error: None,
                                     });
                             }
@@ -238,6 +302,7 @@ pub fn TorrentImport() -> Element {
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
                                         results: vec![],
+                                        result_counts: vec![],
                                         error: Some(format!("Search failed: {}", e)),
                                     });
                             }
@@ -250,6 +315,7 @@ pub fn TorrentImport() -> Element {
                                 .write()
                                 .dispatch(CandidateEvent::SearchComplete {
                                     results: vec![],
+                                    result_counts: vec![],
                                     error: Some("Please enter a catalog number".to_string()),
                                 });
                             return;
@@ -257,13 +323,20 @@ pub fn TorrentImport() -> Element {
 
                         import_store.write().dispatch(CandidateEvent::StartSearch);
 
-                        let result = search_by_catalog_number(metadata, source, catno).await;
+                        let result = search_by_catalog_number(
+                            metadata,
+                            source,
+                            catno,
+                            SearchPages::default(),
+                        )
+                        .await;
                         match result {
-                            Ok(candidates) => {
+                            Ok(page) => {
                                 import_store
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
-                                        results: candidates,
+                                        results: page.candidates,
+                                        result_counts: page.result_counts,
                                         error: None,
                                     });
                             }
@@ -272,6 +345,7 @@ pub fn TorrentImport() -> Element {
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
                                         results: vec![],
+                                        result_counts: vec![],
                                         error: Some(format!("Search failed: {}", e)),
                                     });
                             }
@@ -284,6 +358,7 @@ pub fn TorrentImport() -> Element {
                                 .write()
                                 .dispatch(CandidateEvent::SearchComplete {
                                     results: vec![],
+                                    result_counts: vec![],
                                     error: Some("Please enter a barcode".to_string()),
                                 });
                             return;
@@ -291,13 +366,16 @@ pub fn TorrentImport() -> Element {
 
                         import_store.write().dispatch(CandidateEvent::StartSearch);
 
-                        let result = search_by_barcode(metadata, source, barcode).await;
+                        let result =
+                            search_by_barcode(metadata, source, barcode, SearchPages::default())
+                                .await;
                         match result {
-                            Ok(candidates) => {
+                            Ok(page) => {
                                 import_store
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
-                                        results: candidates,
+                                        results: page.candidates,
+                                        result_counts: page.result_counts,
                                         error: None,
                                     });
                             }
@@ -306,6 +384,7 @@ pub fn TorrentImport() -> Element {
                                     .write()
                                     .dispatch(CandidateEvent::SearchComplete {
                                         results: vec![],
+                                        result_counts: vec![],
                                         error: Some(format!("Search failed: {}", e)),
                                     });
                             }
@@ -327,6 +406,16 @@ pub fn TorrentImport() -> Element {
         }
     };
 
+    let on_load_more = {
+        let app = app.clone();
+        move |_| {
+            let app = app.clone();
+            spawn(async move {
+                load_more_search_results(&app).await;
+            });
+        }
+    };
+
     let on_manual_match_select = {
         let app = app.clone();
         move |index: usize| {
@@ -347,6 +436,13 @@ pub fn TorrentImport() -> Element {
         }
     };
 
+    let on_cover_visible = EventHandler::new({
+        let app = app.clone();
+        move |(release_id, visible): (String, bool)| {
+            app.cover_fetcher.set_visible(&release_id, visible)
+        }
+    });
+
     let on_retry_discid_lookup = {
         let app = app.clone();
         move |_| {
@@ -514,6 +610,9 @@ pub fn TorrentImport() -> Element {
             torrent_info: display_torrent_info,
             tracker_statuses,
             torrent_files,
+            album_folders,
+            selected_album_folders: selected_folders,
+            on_toggle_album_folder,
             input_mode: *input_mode.read(),
             is_dragging: *is_dragging.read(),
             on_mode_change: move |mode| input_mode.set(mode),
@@ -535,8 +634,10 @@ pub fn TorrentImport() -> Element {
             on_manual_match_select,
             on_search: move |_| perform_search(),
             on_cancel_search: move |_| cancel_search(),
+            on_load_more,
             on_manual_confirm,
             on_retry_discid_lookup,
+            on_cover_visible: Some(on_cover_visible),
             on_detect_metadata,
             on_select_remote_cover: |_| {},
             on_select_local_cover: |_| {},
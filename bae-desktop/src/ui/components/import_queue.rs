@@ -0,0 +1,86 @@
+//! Import queue page component
+//!
+//! Uses bae-ui's ImportQueueView; pause, resume, cancel and retry go to the
+//! import service, which reports back through import progress.
+
+use crate::ui::app_service::use_app;
+use crate::ui::display_types::active_import_to_display;
+use crate::ui::Route;
+use bae_core::import::ImportServiceHandle;
+use bae_ui::stores::{ActiveImportsUiStateStoreExt, AppStateStoreExt, ConfigStateStoreExt};
+use bae_ui::ImportQueueView;
+use dioxus::prelude::*;
+use std::future::Future;
+
+/// Import queue page component
+#[component]
+pub fn ImportQueuePage() -> Element {
+    let app = use_app();
+    let store_imports = app.state.active_imports().imports().read().clone();
+    let imports = store_imports.iter().map(active_import_to_display).collect();
+    let max_concurrent = *app.state.config().max_concurrent_imports().read();
+
+    let on_import_click = move |import_id: String| {
+        let release_id = store_imports
+            .iter()
+            .find(|i| i.import_id == import_id)
+            .and_then(|i| i.release_id.clone());
+        if let Some(release_id) = release_id {
+            navigator().push(Route::AlbumDetail {
+                album_id: release_id,
+                release_id: String::new(),
+                track_id: String::new(),
+            });
+        }
+    };
+
+    let on_dismiss = {
+        let app = app.clone();
+        move |import_id: String| {
+            app.state
+                .active_imports()
+                .imports()
+                .with_mut(|list| list.retain(|i| i.import_id != import_id));
+            let library_manager = app.library_manager.clone();
+            spawn(async move {
+                if let Err(e) = library_manager.get().delete_import(&import_id).await {
+                    tracing::warn!("Failed to delete import from DB: {}", e);
+                }
+            });
+        }
+    };
+
+    let handle = app.import_handle.clone();
+
+    rsx! {
+        ImportQueueView {
+            imports,
+            max_concurrent,
+            on_import_click,
+            on_pause: queue_action(&handle, |h, id| async move { h.pause_import(&id).await }),
+            on_resume: queue_action(&handle, |h, id| async move { h.resume_import(&id).await }),
+            on_cancel: queue_action(&handle, |h, id| async move { h.cancel_import(&id).await }),
+            on_retry: queue_action(&handle, |h, id| async move { h.retry_import(&id).await }),
+            on_dismiss,
+        }
+    }
+}
+
+/// Send a queue action for an import; the service reports the outcome
+fn queue_action<F, Fut>(handle: &ImportServiceHandle, action: F) -> impl FnMut(String) + 'static
+where
+    F: Fn(ImportServiceHandle, String) -> Fut + Copy + 'static,
+    Fut: Future<Output = Result<(), String>> + 'static,
+{
+    let handle = handle.clone();
+    move |import_id: String| {
+        let handle = handle.clone();
+        spawn(async move {
+            if let Err(e) = action(handle, import_id).await {
+                tracing::warn!("Import queue action failed: {}", e);
+            }
+        });
+    }
+}
+
+pub use ImportQueuePage as ImportQueue;
@@ -3,9 +3,10 @@
 //! Thin wrapper that bridges App state to ImportsDropdownView.
 
 use crate::ui::app_service::use_app;
-use crate::ui::{image_url, Route};
-use bae_ui::display_types::{ActiveImport as DisplayActiveImport, ImportStatus};
-use bae_ui::stores::{ActiveImportsUiStateStoreExt, AppStateStoreExt, ImportOperationStatus};
+use crate::ui::display_types::active_import_to_display;
+use crate::ui::Route;
+use bae_ui::display_types::ActiveImport as DisplayActiveImport;
+use bae_ui::stores::{ActiveImportsUiStateStoreExt, AppStateStoreExt};
 use bae_ui::ImportsDropdownView;
 use dioxus::prelude::*;
 
@@ -18,34 +19,8 @@ pub fn ImportsDropdown(mut is_open: Signal<bool>) -> Element {
     let imports = imports_store.read();
     let navigator = use_navigator();
 
-    // Convert to display types
-    let display_imports: Vec<DisplayActiveImport> = imports
-        .iter()
-        .map(|i| {
-            let cover_url = i
-                .cover_image_id
-                .as_ref()
-                .map(|id| image_url(id))
-                .or_else(|| i.cover_art_url.clone());
-
-            DisplayActiveImport {
-                import_id: i.import_id.clone(),
-                album_title: i.album_title.clone(),
-                artist_name: i.artist_name.clone(),
-                status: match i.status {
-                    ImportOperationStatus::Pending => ImportStatus::Preparing,
-                    ImportOperationStatus::Preparing => ImportStatus::Preparing,
-                    ImportOperationStatus::Importing => ImportStatus::Importing,
-                    ImportOperationStatus::Complete => ImportStatus::Complete,
-                    ImportOperationStatus::Failed => ImportStatus::Failed,
-                },
-                current_step_text: i.current_step.map(|s| format!("{:?}", s)),
-                progress_percent: i.progress_percent,
-                release_id: i.release_id.clone(),
-                cover_url,
-            }
-        })
-        .collect();
+    let display_imports: Vec<DisplayActiveImport> =
+        imports.iter().map(active_import_to_display).collect();
 
     // Build a map from import_id to release_id for navigation
     let release_ids: std::collections::HashMap<String, Option<String>> = imports
@@ -67,6 +42,7 @@ pub fn ImportsDropdown(mut is_open: Signal<bool>) -> Element {
                             .push(Route::AlbumDetail {
                                 album_id: rid.clone(),
                                 release_id: String::new(),
+                                track_id: String::new(),
                             });
                     }
                 }
@@ -91,6 +67,10 @@ pub fn ImportsDropdown(mut is_open: Signal<bool>) -> Element {
                     });
                 }
             },
+            on_view_queue: move |_| {
+                is_open.set(false);
+                navigator.push(Route::ImportQueue {});
+            },
             on_clear_all: {
                 let app = app.clone();
                 move |_| {
@@ -6,8 +6,8 @@
 use crate::ui::app_service::use_app;
 use crate::ui::components::album_detail::utils::get_album_track_ids;
 use crate::ui::Route;
-use bae_ui::stores::AppStateStoreExt;
-use bae_ui::LibraryView;
+use bae_ui::stores::{AppStateStoreExt, ConfigStateStoreExt};
+use bae_ui::{LibrarySort, LibraryView};
 use dioxus::prelude::*;
 
 /// Library page component - passes state lens to bae-ui's LibraryView
@@ -20,11 +20,21 @@ pub fn LibraryPage() -> Element {
     // Pass the state lens directly - don't read here!
     let state = app.state.library();
 
+    // Seeds the view's own sort and filter
+    let default_sort = app
+        .state
+        .config()
+        .library_sort()
+        .read()
+        .unwrap_or(LibrarySort::Title);
+    let default_quality_filter = *app.state.config().library_quality_filter().read();
+
     // Navigation callback - navigate to album detail
     let on_album_click = move |album_id: String| {
         navigator().push(Route::AlbumDetail {
             album_id,
             release_id: String::new(),
+            track_id: String::new(),
         });
     };
 
@@ -63,6 +73,19 @@ pub fn LibraryPage() -> Element {
         navigator().push(Route::ImportWorkflowManager {});
     };
 
+    let on_find_replace_preview = {
+        let app = app.clone();
+        move |request| app.preview_find_replace(request)
+    };
+    let on_find_replace_apply = {
+        let app = app.clone();
+        move |rows| app.apply_find_replace(rows)
+    };
+    let on_find_replace_reset = {
+        let app = app.clone();
+        move |_| app.reset_find_replace()
+    };
+
     rsx! {
         LibraryView {
             state,
@@ -70,6 +93,11 @@ pub fn LibraryPage() -> Element {
             on_play_album,
             on_add_album_to_queue,
             on_empty_action,
+            default_sort,
+            default_quality_filter,
+            on_find_replace_preview,
+            on_find_replace_apply,
+            on_find_replace_reset,
         }
     }
 }
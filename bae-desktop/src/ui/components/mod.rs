@@ -6,15 +6,23 @@ pub mod title_bar;
 pub mod album_detail;
 pub mod app;
 pub mod app_layout;
+pub mod history;
 pub mod import;
+pub mod import_queue;
 pub mod library;
 pub mod now_playing_bar;
 pub mod queue_sidebar;
 pub mod settings;
+pub mod snapshot_restore;
+pub mod tracks;
+pub mod undo_toast;
 
 pub use album_detail::AlbumDetail;
 pub use app::App;
 pub use app_layout::AppLayout;
+pub use history::History;
+pub use import_queue::ImportQueue;
 pub use library::Library;
 pub use settings::Settings;
 pub use title_bar::TitleBar;
+pub use tracks::Tracks;
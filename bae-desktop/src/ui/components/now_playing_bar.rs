@@ -39,6 +39,7 @@ pub fn NowPlayingBar() -> Element {
                         navigator().push(Route::AlbumDetail {
                             album_id,
                             release_id,
+                            track_id: String::new(),
                         });
                     }
                 });
@@ -52,6 +53,7 @@ pub fn NowPlayingBar() -> Element {
     let playback_for_resume = playback_handle.clone();
     let playback_for_next = playback_handle.clone();
     let playback_for_seek = playback_handle.clone();
+    let playback_for_pitch = playback_handle.clone();
 
     rsx! {
         NowPlayingBarView {
@@ -66,6 +68,8 @@ pub fn NowPlayingBar() -> Element {
                 sidebar_is_open.set(!current);
             },
             on_track_click,
+            on_add_bookmark: move |name: String| app.add_bookmark(&name),
+            on_pitch_change: move |semitones: i32| playback_for_pitch.set_pitch(semitones),
             on_dismiss_error: Some(EventHandler::new(move |_| playback_error_store.set(None))),
         }
     }
@@ -5,7 +5,10 @@
 
 use crate::ui::app_service::use_app;
 use crate::ui::Route;
-use bae_ui::stores::{AppStateStoreExt, SidebarStateStoreExt, UiStateStoreExt};
+use bae_core::playback;
+use bae_ui::stores::{
+    AppStateStoreExt, PlaybackUiStateStoreExt, RepeatMode, SidebarStateStoreExt, UiStateStoreExt,
+};
 use bae_ui::QueueSidebarView;
 use dioxus::prelude::*;
 
@@ -33,6 +36,7 @@ pub fn QueueSidebar() -> Element {
                     navigator().push(Route::AlbumDetail {
                         album_id,
                         release_id: String::new(),
+                        track_id: String::new(),
                     });
                 }
             });
@@ -41,6 +45,38 @@ pub fn QueueSidebar() -> Element {
 
     let playback_for_clear = playback_handle.clone();
     let playback_for_remove = playback_handle.clone();
+    let playback_for_play_next = playback_handle.clone();
+    let playback_for_add = playback_handle.clone();
+    let playback_for_clear_history = playback_handle.clone();
+    let playback_for_shuffle = playback_handle.clone();
+    let playback_for_repeat = playback_handle.clone();
+
+    // Play files straight from disk; they join the queue as external items
+    let on_open_files = {
+        let playback_handle = playback_handle.clone();
+        move |_| {
+            let playback_handle = playback_handle.clone();
+            spawn(async move {
+                if let Some(files) = rfd::AsyncFileDialog::new()
+                    .set_title("Open Files")
+                    .add_filter(
+                        "Audio",
+                        &["flac", "mp3", "m4a", "ogg", "opus", "wav", "aiff", "wv"],
+                    )
+                    .pick_files()
+                    .await
+                {
+                    let track_ids: Vec<String> = files
+                        .iter()
+                        .map(|f| playback::external_track_id(f.path()))
+                        .collect();
+                    if !track_ids.is_empty() {
+                        playback_handle.play_album(track_ids);
+                    }
+                }
+            });
+        }
+    };
 
     rsx! {
         QueueSidebarView {
@@ -48,8 +84,24 @@ pub fn QueueSidebar() -> Element {
             playback: playback_store,
             on_close: move |_| is_open.set(false),
             on_clear: move |_| playback_for_clear.clear_queue(),
+            on_open_files,
             on_remove: move |idx: usize| playback_for_remove.remove_from_queue(idx),
             on_track_click,
+            on_history_play_next: move |track_id: String| playback_for_play_next.add_next(vec![track_id]),
+            on_history_add_to_queue: move |track_id: String| playback_for_add.add_to_queue(vec![track_id]),
+            on_clear_history: move |_| playback_for_clear_history.clear_history(),
+            on_toggle_shuffle: move |_| {
+                let enabled = *playback_store.shuffle().read();
+                playback_for_shuffle.set_shuffle(!enabled);
+            },
+            on_cycle_repeat: move |_| {
+                let next = match *playback_store.repeat_mode().read() {
+                    RepeatMode::None => playback::RepeatMode::Track,
+                    RepeatMode::Track => playback::RepeatMode::Album,
+                    RepeatMode::Album => playback::RepeatMode::None,
+                };
+                playback_for_repeat.set_repeat_mode(next);
+            },
         }
     }
 }
@@ -1,14 +1,17 @@
-//! About section wrapper - handles library stats, delegates UI to AboutSectionView
+//! About section wrapper - handles library stats and service health, delegates UI to AboutSectionView
 
 use crate::ui::app_service::use_app;
 use crate::updater;
-use bae_ui::stores::{AppStateStoreExt, LibraryStateStoreExt};
+use bae_ui::stores::{AppStateStoreExt, HealthStateStoreExt, LibraryStateStoreExt};
 use bae_ui::AboutSectionView;
 use dioxus::prelude::*;
 
 const VERSION: &str = env!("BAE_VERSION");
 
-/// About section - version info and library stats
+/// How often service health is checked again while the section is open
+const HEALTH_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// About section - version info, service health and library stats
 #[component]
 pub fn AboutSection() -> Element {
     let app = use_app();
@@ -16,6 +19,31 @@ pub fn AboutSection() -> Element {
     // Read album count from Store
     let album_count = use_memo(move || app.state.library().albums().read().len());
 
+    let check_app = app.clone();
+    use_future(move || {
+        let app = check_app.clone();
+        async move {
+            loop {
+                app.check_service_health();
+                tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+            }
+        }
+    });
+
+    let services = app.state.health().services().read().clone();
+    let is_checking_health = *app.state.health().is_checking().read();
+    let restarting = *app.state.health().restarting().read();
+
+    let check_health = {
+        let app = app.clone();
+        move |_| app.check_service_health()
+    };
+
+    let restart_service = {
+        let app = app.clone();
+        move |kind| app.restart_service(kind)
+    };
+
     rsx! {
         AboutSectionView {
             version: VERSION.to_string(),
@@ -23,6 +51,11 @@ pub fn AboutSection() -> Element {
             on_check_updates: move |_| {
                 updater::check_for_updates();
             },
+            services,
+            is_checking_health,
+            restarting,
+            on_check_health: check_health,
+            on_restart_service: restart_service,
         }
     }
 }
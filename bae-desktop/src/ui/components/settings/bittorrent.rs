@@ -19,6 +19,9 @@ pub fn BitTorrentSection() -> Element {
     let store_max_uploads = *config_store.torrent_max_uploads().read();
     let store_max_uploads_per_torrent = *config_store.torrent_max_uploads_per_torrent().read();
     let store_bind_interface = config_store.torrent_bind_interface().read().clone();
+    let store_enable_ipv6 = *config_store.torrent_enable_ipv6().read();
+    let store_enable_dht = *config_store.torrent_enable_dht().read();
+    let store_dht_bootstrap_nodes = config_store.torrent_dht_bootstrap_nodes().read().clone();
 
     let mut editing_section = use_signal(|| Option::<String>::None);
     let mut is_saving = use_signal(|| false);
@@ -52,6 +55,13 @@ pub fn BitTorrentSection() -> Element {
     let original_bind = store_bind_interface.clone().unwrap_or_default();
     let initial_bind = original_bind.clone();
     let mut bind_interface = use_signal(move || initial_bind.clone());
+    let mut enable_ipv6 = use_signal(move || store_enable_ipv6);
+
+    // Edit state for DHT, bootstrap nodes one per line
+    let original_dht_nodes = store_dht_bootstrap_nodes.join("\n");
+    let initial_dht_nodes = original_dht_nodes.clone();
+    let mut enable_dht = use_signal(move || store_enable_dht);
+    let mut dht_bootstrap_nodes = use_signal(move || initial_dht_nodes.clone());
 
     // Original values for change detection
     let original_port = store_listen_port.map(|p| p.to_string()).unwrap_or_default();
@@ -77,7 +87,13 @@ pub fn BitTorrentSection() -> Element {
                 || *max_uploads.read() != original_max_up
                 || *max_uploads_per_torrent.read() != original_max_up_torrent
         }
-        Some("interface") => *bind_interface.read() != original_bind,
+        Some("interface") => {
+            *bind_interface.read() != original_bind || *enable_ipv6.read() != store_enable_ipv6
+        }
+        Some("dht") => {
+            *enable_dht.read() != store_enable_dht
+                || *dht_bootstrap_nodes.read() != original_dht_nodes
+        }
         _ => false,
     };
 
@@ -90,6 +106,9 @@ pub fn BitTorrentSection() -> Element {
         max_uploads: store_max_uploads,
         max_uploads_per_torrent: store_max_uploads_per_torrent,
         bind_interface: store_bind_interface,
+        enable_ipv6: store_enable_ipv6,
+        enable_dht: store_enable_dht,
+        dht_bootstrap_nodes: store_dht_bootstrap_nodes,
     };
 
     let save_changes = {
@@ -104,6 +123,15 @@ pub fn BitTorrentSection() -> Element {
             let new_max_up: Option<i32> = max_uploads.read().parse().ok();
             let new_max_up_torrent: Option<i32> = max_uploads_per_torrent.read().parse().ok();
             let new_interface = bind_interface.read().clone();
+            let new_ipv6 = *enable_ipv6.read();
+            let new_dht = *enable_dht.read();
+            let new_dht_nodes: Vec<String> = dht_bootstrap_nodes
+                .read()
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect();
 
             is_saving.set(true);
             save_error.set(None);
@@ -126,6 +154,11 @@ pub fn BitTorrentSection() -> Element {
                     } else {
                         Some(new_interface)
                     };
+                    config.torrent_enable_ipv6 = new_ipv6;
+                }
+                Some("dht") => {
+                    config.torrent_enable_dht = new_dht;
+                    config.torrent_dht_bootstrap_nodes = new_dht_nodes;
                 }
                 _ => {}
             });
@@ -144,6 +177,9 @@ pub fn BitTorrentSection() -> Element {
         max_uploads.set(original_max_up.clone());
         max_uploads_per_torrent.set(original_max_up_torrent.clone());
         bind_interface.set(original_bind.clone());
+        enable_ipv6.set(store_enable_ipv6);
+        enable_dht.set(store_enable_dht);
+        dht_bootstrap_nodes.set(original_dht_nodes.clone());
         editing_section.set(None);
         save_error.set(None);
     };
@@ -159,6 +195,9 @@ pub fn BitTorrentSection() -> Element {
             edit_max_uploads: max_uploads.read().clone(),
             edit_max_uploads_per_torrent: max_uploads_per_torrent.read().clone(),
             edit_bind_interface: bind_interface.read().clone(),
+            edit_enable_ipv6: *enable_ipv6.read(),
+            edit_enable_dht: *enable_dht.read(),
+            edit_dht_bootstrap_nodes: dht_bootstrap_nodes.read().clone(),
             is_saving: *is_saving.read(),
             has_changes,
             save_error: save_error.read().clone(),
@@ -172,6 +211,9 @@ pub fn BitTorrentSection() -> Element {
             on_max_uploads_change: move |val| max_uploads.set(val),
             on_max_uploads_per_torrent_change: move |val| max_uploads_per_torrent.set(val),
             on_bind_interface_change: move |val| bind_interface.set(val),
+            on_enable_ipv6_change: move |val| enable_ipv6.set(val),
+            on_enable_dht_change: move |val| enable_dht.set(val),
+            on_dht_bootstrap_nodes_change: move |val| dht_bootstrap_nodes.set(val),
         }
     }
 }
@@ -0,0 +1,44 @@
+//! CD ripping section wrapper - reads disc insert settings from the config store,
+//! delegates UI to CdRippingSectionView
+
+use crate::ui::app_service::use_app;
+use crate::ui::display_types::cd_insert_action_from_display;
+use bae_ui::stores::{AppStateStoreExt, ConfigStateStoreExt};
+use bae_ui::{CdInsertAction, CdRippingSectionView};
+use dioxus::prelude::*;
+
+#[component]
+pub fn CdRippingSection() -> Element {
+    let app = use_app();
+
+    let config_store = app.state.config();
+    let insert_action = config_store
+        .cd_insert_action()
+        .read()
+        .unwrap_or(CdInsertAction::Nothing);
+    let eject_after_rip = *config_store.cd_eject_after_rip().read();
+
+    let save_insert_action = {
+        let app = app.clone();
+        move |action: CdInsertAction| {
+            app.save_config(move |config| {
+                config.cd_insert_action = cd_insert_action_from_display(action);
+            });
+        }
+    };
+
+    let save_eject_after_rip = move |eject: bool| {
+        app.save_config(move |config| {
+            config.cd_eject_after_rip = eject;
+        });
+    };
+
+    rsx! {
+        CdRippingSectionView {
+            insert_action,
+            eject_after_rip,
+            on_insert_action_change: save_insert_action,
+            on_eject_after_rip_change: save_eject_after_rip,
+        }
+    }
+}
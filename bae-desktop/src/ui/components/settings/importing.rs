@@ -0,0 +1,154 @@
+//! Importing section wrapper - edits folder patterns, low power imports and
+//! how many imports run at once in the config store, lists series templates, delegates UI to
+//! ImportingSectionView
+
+use crate::ui::app_service::use_app;
+use crate::ui::import_helpers::delete_release_template;
+use bae_core::import::folder_pattern::{FolderPattern, PatternFields, FOLDER_PATTERN_PRESETS};
+use bae_ui::stores::{AppStateStoreExt, ConfigStateStoreExt, ImportStateStoreExt};
+use bae_ui::{FolderPatternPreset, FolderPatternTest, ImportingSectionView};
+use dioxus::prelude::*;
+use std::path::Path;
+
+#[component]
+pub fn ImportingSection() -> Element {
+    let app = use_app();
+
+    let store_patterns = app.state.config().folder_patterns().read().join("\n");
+    let low_power_import = *app.state.config().low_power_import().read();
+    let max_concurrent_imports = *app.state.config().max_concurrent_imports().read();
+    let release_templates = app.state.import().release_templates().read().clone();
+
+    let mut save_error = use_signal(|| Option::<String>::None);
+    let initial_patterns = store_patterns.clone();
+    let mut patterns = use_signal(move || initial_patterns);
+    let mut sample_path = use_signal(String::new);
+
+    let has_changes = *patterns.read() != store_patterns;
+
+    let save_changes = {
+        let app = app.clone();
+        move |_| {
+            let lines: Vec<String> = patterns
+                .read()
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect();
+            for (i, line) in lines.iter().enumerate() {
+                if let Err(e) = FolderPattern::parse(line) {
+                    save_error.set(Some(format!("Line {}: {}", i + 1, e)));
+                    return;
+                }
+            }
+
+            save_error.set(None);
+            patterns.set(lines.join("\n"));
+            app.save_config(move |config| {
+                config.folder_patterns = lines;
+            });
+        }
+    };
+
+    let save_low_power_import = {
+        let app = app.clone();
+        move |low_power: bool| {
+            app.save_config(move |config| {
+                config.low_power_import = low_power;
+            });
+        }
+    };
+
+    let save_max_concurrent_imports = {
+        let app = app.clone();
+        move |max_concurrent: u32| {
+            app.save_config(move |config| {
+                config.max_concurrent_imports = max_concurrent;
+            });
+        }
+    };
+
+    let remove_release_template = {
+        let app = app.clone();
+        move |id: String| delete_release_template(&app, id)
+    };
+
+    let cancel_edit = move |_| {
+        patterns.set(store_patterns.clone());
+        save_error.set(None);
+    };
+
+    let add_preset = move |template: String| {
+        patterns.with_mut(|text| {
+            if !text.trim().is_empty() {
+                text.truncate(text.trim_end().len());
+                text.push('\n');
+            }
+            text.push_str(&template);
+        });
+    };
+
+    let presets = FOLDER_PATTERN_PRESETS
+        .iter()
+        .map(|(name, template)| FolderPatternPreset {
+            name: name.to_string(),
+            template: template.to_string(),
+        })
+        .collect();
+
+    // Try the templates as typed, so they can be checked before saving
+    let sample_result = {
+        let sample_path = sample_path.read();
+        let sample_path = sample_path.trim();
+        (!sample_path.is_empty()).then(|| {
+            let text = patterns.read();
+            text.lines()
+                .filter_map(|line| FolderPattern::parse(line).ok())
+                .find_map(|pattern| {
+                    pattern.match_path(Path::new(sample_path)).map(|fields| {
+                        FolderPatternTest::Matched {
+                            template: pattern.template().to_string(),
+                            fields: field_labels(fields),
+                        }
+                    })
+                })
+                .unwrap_or(FolderPatternTest::NoMatch)
+        })
+    };
+
+    rsx! {
+        ImportingSectionView {
+            edit_patterns: patterns.read().clone(),
+            presets,
+            has_changes,
+            save_error: save_error.read().clone(),
+            sample_path: sample_path.read().clone(),
+            sample_result,
+            release_templates,
+            low_power_import,
+            max_concurrent_imports,
+            on_patterns_change: move |text| patterns.set(text),
+            on_add_preset: add_preset,
+            on_save: save_changes,
+            on_cancel: cancel_edit,
+            on_sample_path_change: move |path| sample_path.set(path),
+            on_remove_release_template: remove_release_template,
+            on_low_power_import_change: save_low_power_import,
+            on_max_concurrent_imports_change: save_max_concurrent_imports,
+        }
+    }
+}
+
+fn field_labels(fields: PatternFields) -> Vec<(String, String)> {
+    [
+        ("Artist", fields.artist),
+        ("Album", fields.album),
+        ("Year", fields.year.map(|y| y.to_string())),
+        ("Format", fields.format),
+        ("Label", fields.label),
+        ("Catalog number", fields.catalog_number),
+    ]
+    .into_iter()
+    .filter_map(|(label, value)| value.map(|v| (label.to_string(), v)))
+    .collect()
+}
@@ -0,0 +1,170 @@
+//! Library section wrapper - handles config state, delegates UI to LibrarySectionView
+
+use crate::ui::app_service::use_app;
+use crate::ui::display_types::{
+    featured_credit_style_from_display, library_sort_from_display, quality_to_db,
+    startup_view_from_display, track_click_action_from_display,
+};
+use bae_ui::stores::{
+    AppStateStoreExt, ConfigStateStoreExt, MaintenanceStateStoreExt, SnapshotsStateStoreExt,
+    StorageProfilesStateStoreExt,
+};
+use bae_ui::{
+    AudioQuality, FeaturedCreditStyle, LibrarySectionView, LibrarySort, StartupView,
+    StorageLocation, TrackClickAction,
+};
+use dioxus::prelude::*;
+
+#[component]
+pub fn LibrarySection() -> Element {
+    let app = use_app();
+
+    // Read config from Store
+    let config_store = app.state.config();
+    let startup_view = config_store
+        .startup_view()
+        .read()
+        .unwrap_or(StartupView::Library);
+    let library_sort = config_store
+        .library_sort()
+        .read()
+        .unwrap_or(LibrarySort::Title);
+    let quality_filter = *config_store.library_quality_filter().read();
+    let track_click_action = config_store
+        .track_click_action()
+        .read()
+        .unwrap_or(TrackClickAction::Select);
+    let featured_credit_style = config_store
+        .featured_credit_style()
+        .read()
+        .unwrap_or(FeaturedCreditStyle::Standardize);
+    let is_renormalizing_credits = *app.state.maintenance().is_renormalizing_credits().read();
+    let credits_result = app.state.maintenance().credits_result().read().clone();
+    let snapshot_profiles = app
+        .state
+        .storage_profiles()
+        .profiles()
+        .read()
+        .iter()
+        .filter(|profile| profile.location == StorageLocation::Cloud)
+        .cloned()
+        .collect::<Vec<_>>();
+    let snapshot_profile_id = config_store.snapshot_profile_id().read().clone();
+    let snapshot_interval_hours = *config_store.snapshot_interval_hours().read();
+    let snapshots_kept = *config_store.snapshots_kept().read();
+    let snapshots = app.state.snapshots().snapshots().read().clone();
+    let is_snapshotting = *app.state.snapshots().is_snapshotting().read();
+    let snapshot_result = app.state.snapshots().snapshot_result().read().clone();
+
+    // Choices save as soon as they're picked
+    let save_startup_view = {
+        let app = app.clone();
+        move |view: StartupView| {
+            app.save_config(move |config| {
+                config.startup_view = startup_view_from_display(view);
+            });
+        }
+    };
+
+    let save_library_sort = {
+        let app = app.clone();
+        move |sort: LibrarySort| {
+            app.save_config(move |config| {
+                config.library_sort = library_sort_from_display(sort);
+            });
+        }
+    };
+
+    let save_quality_filter = {
+        let app = app.clone();
+        move |quality: Option<AudioQuality>| {
+            app.save_config(move |config| {
+                config.library_quality_filter = quality.map(quality_to_db);
+            });
+        }
+    };
+
+    let save_track_click_action = {
+        let app = app.clone();
+        move |action: TrackClickAction| {
+            app.save_config(move |config| {
+                config.track_click_action = track_click_action_from_display(action);
+            });
+        }
+    };
+
+    let save_featured_credit_style = {
+        let app = app.clone();
+        move |style: FeaturedCreditStyle| {
+            app.save_config(move |config| {
+                config.featured_credit_style = featured_credit_style_from_display(style);
+            });
+        }
+    };
+
+    let renormalize_credits = {
+        let app = app.clone();
+        move |_| app.renormalize_artist_credits()
+    };
+
+    let save_snapshot_profile = {
+        let app = app.clone();
+        move |profile_id: Option<String>| {
+            app.save_config(move |config| {
+                config.snapshot_profile_id = profile_id;
+            });
+        }
+    };
+
+    let save_snapshot_interval = {
+        let app = app.clone();
+        move |hours: Option<u32>| {
+            app.save_config(move |config| {
+                config.snapshot_interval_hours = hours;
+            });
+        }
+    };
+
+    let save_snapshots_kept = {
+        let app = app.clone();
+        move |count: u32| {
+            app.save_config(move |config| {
+                config.snapshots_kept = count;
+            });
+        }
+    };
+
+    let snapshot_now = {
+        let app = app.clone();
+        move |_| app.snapshot_library_now()
+    };
+
+    rsx! {
+        LibrarySectionView {
+            startup_view,
+            library_sort,
+            quality_filter,
+            on_startup_view_change: save_startup_view,
+            on_library_sort_change: save_library_sort,
+            on_quality_filter_change: save_quality_filter,
+            track_click_action,
+            on_track_click_action_change: save_track_click_action,
+            featured_credit_style,
+            on_featured_credit_style_change: save_featured_credit_style,
+            is_renormalizing_credits,
+            credits_result,
+            on_renormalize_credits: renormalize_credits,
+            snapshot_profiles,
+            snapshot_profile_id,
+            snapshot_interval_hours,
+            snapshots_kept,
+            snapshots,
+            is_snapshotting,
+            snapshot_result,
+            on_snapshot_profile_change: save_snapshot_profile,
+            on_snapshot_interval_change: save_snapshot_interval,
+            on_snapshots_kept_change: save_snapshots_kept,
+            on_snapshot_now: snapshot_now,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Maintenance section wrapper - reads verification and maintenance state, delegates UI to MaintenanceSectionView
+
+use crate::ui::app_service::use_app;
+use bae_ui::stores::{
+    AppStateStoreExt, ConfigStateStoreExt, MaintenanceStateStoreExt, VerificationStateStoreExt,
+};
+use bae_ui::MaintenanceSectionView;
+use dioxus::prelude::*;
+
+#[component]
+pub fn MaintenanceSection() -> Element {
+    let app = use_app();
+
+    let verification_interval_days = *app.state.config().verification_interval_days().read();
+    let verification_runs = app.state.verification().runs().read().clone();
+    let is_verifying = *app.state.verification().is_running().read();
+    let repairing_issue_id = app.state.verification().repairing_issue_id().read().clone();
+    let repair_error = app.state.verification().repair_error().read().clone();
+    let is_compacting_database = *app.state.maintenance().is_compacting_database().read();
+    let database_result = app.state.maintenance().database_result().read().clone();
+    let is_clearing_cache = *app.state.maintenance().is_clearing_cache().read();
+    let cache_result = app.state.maintenance().cache_result().read().clone();
+
+    let save_verification_interval = {
+        let app = app.clone();
+        move |days: Option<u32>| {
+            app.save_config(move |config| {
+                config.verification_interval_days = days;
+            });
+        }
+    };
+
+    let verify_now = {
+        let app = app.clone();
+        move |_| app.verify_library_now()
+    };
+
+    let repair_issue = {
+        let app = app.clone();
+        move |issue_id: String| app.repair_verification_issue(issue_id)
+    };
+
+    let compact_database = {
+        let app = app.clone();
+        move |_| app.compact_database()
+    };
+
+    let clear_cache = {
+        let app = app.clone();
+        move |_| app.clear_cache()
+    };
+
+    rsx! {
+        MaintenanceSectionView {
+            verification_interval_days,
+            verification_runs,
+            is_verifying,
+            on_verification_interval_change: save_verification_interval,
+            on_verify_now: verify_now,
+            repairing_issue_id,
+            repair_error,
+            on_repair_issue: repair_issue,
+            is_compacting_database,
+            database_result,
+            on_compact_database: compact_database,
+            is_clearing_cache,
+            cache_result,
+            on_clear_cache: clear_cache,
+        }
+    }
+}
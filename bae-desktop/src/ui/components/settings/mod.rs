@@ -1,7 +1,16 @@
 mod about;
 mod api_keys;
 mod bittorrent;
+mod cd_ripping;
 mod encryption;
+mod importing;
+mod library;
+mod maintenance;
+mod network;
+mod playback;
+mod remote;
+mod scrobbling;
+mod seeding;
 mod storage_profiles;
 mod subsonic;
 
@@ -22,18 +31,45 @@ pub fn Settings() -> Element {
                 SettingsTab::StorageProfiles => rsx! {
                     storage_profiles::StorageProfilesSection {}
                 },
+                SettingsTab::Library => rsx! {
+                    library::LibrarySection {}
+                },
+                SettingsTab::Maintenance => rsx! {
+                    maintenance::MaintenanceSection {}
+                },
+                SettingsTab::Importing => rsx! {
+                    importing::ImportingSection {}
+                },
                 SettingsTab::ApiKeys => rsx! {
                     api_keys::ApiKeysSection {}
                 },
                 SettingsTab::Encryption => rsx! {
                     encryption::EncryptionSection {}
                 },
+                SettingsTab::Playback => rsx! {
+                    playback::PlaybackSection {}
+                },
+                SettingsTab::Scrobbling => rsx! {
+                    scrobbling::ScrobblingSection {}
+                },
+                SettingsTab::Network => rsx! {
+                    network::NetworkSection {}
+                },
                 SettingsTab::BitTorrent => rsx! {
                     bittorrent::BitTorrentSection {}
                 },
+                SettingsTab::Seeding => rsx! {
+                    seeding::SeedingSection {}
+                },
+                SettingsTab::CdRipping => rsx! {
+                    cd_ripping::CdRippingSection {}
+                },
                 SettingsTab::Subsonic => rsx! {
                     subsonic::SubsonicSection {}
                 },
+                SettingsTab::Remote => rsx! {
+                    remote::RemoteSection {}
+                },
                 SettingsTab::About => rsx! {
                     about::AboutSection {}
                 },
@@ -0,0 +1,43 @@
+//! Network section wrapper - reads metered settings from the config store,
+//! delegates UI to NetworkSectionView
+
+use crate::ui::app_service::use_app;
+use bae_ui::stores::{AppStateStoreExt, ConfigStateStoreExt};
+use bae_ui::NetworkSectionView;
+use dioxus::prelude::*;
+
+#[component]
+pub fn NetworkSection() -> Element {
+    let app = use_app();
+
+    let config_store = app.state.config();
+    let metered_mode = *config_store.metered_mode().read();
+    let allow_cloud_prefetch = *config_store.metered_allow_cloud_prefetch().read();
+    let allow_torrents = *config_store.metered_allow_torrents().read();
+
+    // Write all three together so one toggle never undoes another
+    let save = move |metered_mode: bool, allow_cloud_prefetch: bool, allow_torrents: bool| {
+        app.save_config(move |config| {
+            config.metered_mode = metered_mode;
+            config.metered_allow_cloud_prefetch = allow_cloud_prefetch;
+            config.metered_allow_torrents = allow_torrents;
+        });
+    };
+
+    rsx! {
+        NetworkSectionView {
+            metered_mode,
+            allow_cloud_prefetch,
+            allow_torrents,
+            on_metered_mode_change: {
+                let save = save.clone();
+                move |enabled| save(enabled, allow_cloud_prefetch, allow_torrents)
+            },
+            on_allow_cloud_prefetch_change: {
+                let save = save.clone();
+                move |allow| save(metered_mode, allow, allow_torrents)
+            },
+            on_allow_torrents_change: move |allow| save(metered_mode, allow_cloud_prefetch, allow),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Playback section wrapper - handles config state, delegates UI to PlaybackSectionView
+
+use crate::ui::app_service::use_app;
+use crate::ui::display_types::{dither_from_display, output_bit_depth_from_display};
+use bae_core::playback::{exclusive_mode_supported, output_device_names, BufferSettings};
+use bae_ui::stores::{AppStateStoreExt, ConfigStateStoreExt};
+use bae_ui::{Dither, OutputBitDepth, PlaybackSectionView};
+use dioxus::prelude::*;
+
+#[component]
+pub fn PlaybackSection() -> Element {
+    let app = use_app();
+
+    // Read config from Store
+    let config_store = app.state.config();
+    let store_decode_ahead = *config_store.playback_decode_ahead_ms().read();
+    let store_gapless_preroll = *config_store.playback_gapless_preroll_ms().read();
+    let output_bit_depth = config_store
+        .playback_output_bit_depth()
+        .read()
+        .unwrap_or(OutputBitDepth::Auto);
+    let dither = config_store
+        .playback_dither()
+        .read()
+        .unwrap_or(Dither::Tpdf);
+    let exclusive_devices = config_store.playback_exclusive_devices().read().clone();
+    let output_devices = use_hook(output_device_names);
+    let output_device = config_store.playback_output_device().read().clone();
+    let skip_silence = *config_store.playback_skip_silence().read();
+    let min_silence_ms = *config_store.playback_min_silence_ms().read();
+    let in_background = *config_store.playback_in_background().read();
+
+    let mut is_editing = use_signal(|| false);
+    let mut is_saving = use_signal(|| false);
+    let mut save_error = use_signal(|| Option::<String>::None);
+
+    let mut decode_ahead = use_signal(move || store_decode_ahead.to_string());
+    let mut gapless_preroll = use_signal(move || store_gapless_preroll.to_string());
+
+    let has_changes = *decode_ahead.read() != store_decode_ahead.to_string()
+        || *gapless_preroll.read() != store_gapless_preroll.to_string();
+
+    let save_changes = {
+        let app = app.clone();
+        move |_| {
+            let (Ok(new_decode_ahead), Ok(new_gapless_preroll)) = (
+                decode_ahead.read().parse::<u32>(),
+                gapless_preroll.read().parse::<u32>(),
+            ) else {
+                save_error.set(Some("Enter whole numbers of milliseconds.".to_string()));
+                return;
+            };
+            let settings = BufferSettings {
+                decode_ahead_ms: new_decode_ahead,
+                gapless_preroll_ms: new_gapless_preroll,
+            }
+            .clamped();
+
+            is_saving.set(true);
+            save_error.set(None);
+
+            app.save_config(move |config| {
+                config.playback_decode_ahead_ms = settings.decode_ahead_ms;
+                config.playback_gapless_preroll_ms = settings.gapless_preroll_ms;
+            });
+
+            decode_ahead.set(settings.decode_ahead_ms.to_string());
+            gapless_preroll.set(settings.gapless_preroll_ms.to_string());
+            is_saving.set(false);
+            is_editing.set(false);
+        }
+    };
+
+    // Output choices save as soon as they're picked
+    let save_output_bit_depth = {
+        let app = app.clone();
+        move |depth: OutputBitDepth| {
+            app.save_config(move |config| {
+                config.playback_output_bit_depth = output_bit_depth_from_display(depth);
+            });
+        }
+    };
+
+    let save_output_device = {
+        let app = app.clone();
+        move |device: Option<String>| {
+            app.save_config(move |config| config.playback_output_device = device);
+        }
+    };
+
+    let save_dither = {
+        let app = app.clone();
+        move |dither: Dither| {
+            app.save_config(move |config| {
+                config.playback_dither = dither_from_display(dither);
+            });
+        }
+    };
+
+    let save_exclusive = {
+        let app = app.clone();
+        move |(device, exclusive): (String, bool)| {
+            app.save_config(move |config| {
+                config.playback_exclusive_devices.retain(|d| *d != device);
+                if exclusive {
+                    config.playback_exclusive_devices.push(device);
+                }
+            });
+        }
+    };
+
+    let save_skip_silence = {
+        let app = app.clone();
+        move |enabled: bool| {
+            app.save_config(move |config| config.playback_skip_silence = enabled);
+        }
+    };
+
+    let save_min_silence = {
+        let app = app.clone();
+        move |ms: u32| {
+            app.save_config(move |config| config.playback_min_silence_ms = ms);
+        }
+    };
+
+    let save_in_background = {
+        let app = app.clone();
+        move |enabled: bool| {
+            app.save_config(move |config| config.playback_in_background = enabled);
+        }
+    };
+
+    let cancel_edit = move |_| {
+        decode_ahead.set(store_decode_ahead.to_string());
+        gapless_preroll.set(store_gapless_preroll.to_string());
+        is_editing.set(false);
+        save_error.set(None);
+    };
+
+    rsx! {
+        PlaybackSectionView {
+            output_bit_depth,
+            dither,
+            output_devices,
+            output_device,
+            exclusive_devices,
+            exclusive_supported: exclusive_mode_supported(),
+            skip_silence,
+            min_silence_ms,
+            in_background,
+            decode_ahead_ms: store_decode_ahead,
+            gapless_preroll_ms: store_gapless_preroll,
+            min_ms: BufferSettings::MIN_MS,
+            max_ms: BufferSettings::MAX_MS,
+            is_editing: *is_editing.read(),
+            edit_decode_ahead_ms: decode_ahead.read().clone(),
+            edit_gapless_preroll_ms: gapless_preroll.read().clone(),
+            is_saving: *is_saving.read(),
+            has_changes,
+            save_error: save_error.read().clone(),
+            on_edit_start: move |_| is_editing.set(true),
+            on_cancel: cancel_edit,
+            on_save: save_changes,
+            on_decode_ahead_change: move |val| decode_ahead.set(val),
+            on_gapless_preroll_change: move |val| gapless_preroll.set(val),
+            on_output_device_change: save_output_device,
+            on_output_bit_depth_change: save_output_bit_depth,
+            on_dither_change: save_dither,
+            on_exclusive_change: save_exclusive,
+            on_skip_silence_change: save_skip_silence,
+            on_min_silence_change: save_min_silence,
+            on_in_background_change: save_in_background,
+        }
+    }
+}
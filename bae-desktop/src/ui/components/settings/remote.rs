@@ -0,0 +1,96 @@
+//! Phone remote section wrapper - handles config state and pairing, delegates
+//! UI to RemoteSectionView
+
+use crate::ui::app_service::use_app;
+use bae_core::network;
+use bae_core::remote::{self, DEFAULT_REMOTE_PORT};
+use bae_ui::stores::{AppStateStoreExt, ConfigStateStoreExt};
+use bae_ui::RemoteSectionView;
+use dioxus::prelude::*;
+
+#[component]
+pub fn RemoteSection() -> Element {
+    let app = use_app();
+
+    // Read config from Store
+    let config_store = app.state.config();
+    let store_enabled = *config_store.remote_enabled().read();
+    let store_port = *config_store.remote_port().read();
+    let store_token = config_store.remote_token().read().clone();
+
+    let mut is_editing = use_signal(|| false);
+    let mut is_saving = use_signal(|| false);
+    let mut save_error = use_signal(|| Option::<String>::None);
+
+    let mut enabled = use_signal(move || store_enabled);
+    let mut port = use_signal(move || store_port.to_string());
+
+    let has_changes = *enabled.read() != store_enabled || *port.read() != store_port.to_string();
+
+    let pairing_url = store_token
+        .as_deref()
+        .and_then(|token| network::lan_ipv4().map(|ip| remote::pairing_url(ip, store_port, token)));
+    let pairing_qr_url = pairing_url
+        .as_deref()
+        .and_then(remote::pairing_qr_svg)
+        .map(|svg| format!("data:image/svg+xml;utf8,{}", urlencoding::encode(&svg)));
+
+    let save_changes = {
+        let app = app.clone();
+        move |_| {
+            let new_enabled = *enabled.read();
+            let new_port: u16 = port.read().parse().unwrap_or(DEFAULT_REMOTE_PORT);
+
+            is_saving.set(true);
+            save_error.set(None);
+
+            app.save_config(move |config| {
+                config.remote_enabled = new_enabled;
+                config.remote_port = new_port;
+                if new_enabled && config.remote_token.is_none() {
+                    config.remote_token = Some(remote::generate_token());
+                }
+            });
+
+            is_saving.set(false);
+            is_editing.set(false);
+        }
+    };
+
+    let unpair = {
+        let app = app.clone();
+        move |_| {
+            app.save_config(|config| {
+                config.remote_token = Some(remote::generate_token());
+            });
+        }
+    };
+
+    let cancel_edit = move |_| {
+        enabled.set(store_enabled);
+        port.set(store_port.to_string());
+        is_editing.set(false);
+        save_error.set(None);
+    };
+
+    rsx! {
+        RemoteSectionView {
+            enabled: store_enabled,
+            port: store_port,
+            pairing_url,
+            pairing_qr_url,
+            is_editing: *is_editing.read(),
+            edit_enabled: *enabled.read(),
+            edit_port: port.read().clone(),
+            is_saving: *is_saving.read(),
+            has_changes,
+            save_error: save_error.read().clone(),
+            on_edit_start: move |_| is_editing.set(true),
+            on_cancel: cancel_edit,
+            on_save: save_changes,
+            on_enabled_change: move |val| enabled.set(val),
+            on_port_change: move |val| port.set(val),
+            on_unpair: unpair,
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Scrobbling section wrapper - runs the Last.fm and ListenBrainz connect
+//! flows, delegates UI to ScrobblingSectionView
+
+use crate::ui::app_service::use_app;
+use bae_core::scrobble::{lastfm, listenbrainz};
+use bae_ui::stores::{AppStateStoreExt, ConfigStateStoreExt};
+use bae_ui::ScrobblingSectionView;
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// How often the queued scrobble count refreshes while the tab is open
+const PENDING_REFRESH: Duration = Duration::from_secs(5);
+
+#[component]
+pub fn ScrobblingSection() -> Element {
+    let app = use_app();
+
+    let config_store = app.state.config();
+    let lastfm_username = config_store.lastfm_username().read().clone();
+    let listenbrainz_connected = *config_store.listenbrainz_connected().read();
+
+    let mut api_key = use_signal(String::new);
+    let mut api_secret = use_signal(String::new);
+    // Token from Last.fm waiting for the user to approve it in the browser
+    let mut lastfm_token = use_signal(|| Option::<String>::None);
+    let mut listenbrainz_token = use_signal(String::new);
+    let mut is_busy = use_signal(|| false);
+    let mut error = use_signal(|| Option::<String>::None);
+    let mut pending_scrobbles = use_signal(|| 0i64);
+
+    let scrobble_handle = app.scrobble_handle.clone();
+    use_future(move || {
+        let scrobble_handle = scrobble_handle.clone();
+        async move {
+            loop {
+                pending_scrobbles.set(scrobble_handle.pending_count().await);
+                tokio::time::sleep(PENDING_REFRESH).await;
+            }
+        }
+    });
+
+    let lastfm_connect = move |_| {
+        let key = api_key.read().trim().to_string();
+        let secret = api_secret.read().trim().to_string();
+        is_busy.set(true);
+        error.set(None);
+        spawn(async move {
+            match lastfm::get_token(&key, &secret).await {
+                Ok(token) => {
+                    let url = lastfm::auth_url(&key, &token);
+                    let _ = std::process::Command::new("open").arg(&url).spawn();
+                    lastfm_token.set(Some(token));
+                }
+                Err(e) => error.set(Some(format!("Couldn't reach Last.fm: {}", e))),
+            }
+            is_busy.set(false);
+        });
+    };
+
+    let lastfm_finish = {
+        let app = app.clone();
+        move |_| {
+            let Some(token) = lastfm_token.read().clone() else {
+                return;
+            };
+            let key = api_key.read().trim().to_string();
+            let secret = api_secret.read().trim().to_string();
+            let app = app.clone();
+            is_busy.set(true);
+            error.set(None);
+            spawn(async move {
+                match lastfm::get_session(&key, &secret, &token).await {
+                    Ok(account) => {
+                        if let Err(e) =
+                            app.update_scrobble_accounts(|accounts| accounts.lastfm = Some(account))
+                        {
+                            error.set(Some(format!("Failed to save account: {}", e)));
+                        }
+                        api_key.set(String::new());
+                        api_secret.set(String::new());
+                        lastfm_token.set(None);
+                    }
+                    Err(e) => {
+                        // Start over with a fresh token
+                        lastfm_token.set(None);
+                        error.set(Some(format!("Last.fm didn't connect: {}", e)));
+                    }
+                }
+                is_busy.set(false);
+            });
+        }
+    };
+
+    let lastfm_disconnect = {
+        let app = app.clone();
+        move |_| {
+            error.set(None);
+            if let Err(e) = app.update_scrobble_accounts(|accounts| accounts.lastfm = None) {
+                error.set(Some(format!("Failed to save account: {}", e)));
+            }
+        }
+    };
+
+    let listenbrainz_connect = {
+        let app = app.clone();
+        move |_| {
+            let token = listenbrainz_token.read().trim().to_string();
+            let app = app.clone();
+            is_busy.set(true);
+            error.set(None);
+            spawn(async move {
+                match listenbrainz::validate_token(&token).await {
+                    Ok(_) => {
+                        if let Err(e) = app.update_scrobble_accounts(|accounts| {
+                            accounts.listenbrainz_token = Some(token)
+                        }) {
+                            error.set(Some(format!("Failed to save account: {}", e)));
+                        }
+                        listenbrainz_token.set(String::new());
+                    }
+                    Err(e) => error.set(Some(format!("ListenBrainz didn't connect: {}", e))),
+                }
+                is_busy.set(false);
+            });
+        }
+    };
+
+    let listenbrainz_disconnect = move |_| {
+        error.set(None);
+        if let Err(e) = app.update_scrobble_accounts(|accounts| accounts.listenbrainz_token = None)
+        {
+            error.set(Some(format!("Failed to save account: {}", e)));
+        }
+    };
+
+    rsx! {
+        ScrobblingSectionView {
+            lastfm_username,
+            lastfm_api_key: api_key.read().clone(),
+            lastfm_api_secret: api_secret.read().clone(),
+            lastfm_awaiting_approval: lastfm_token.read().is_some(),
+            listenbrainz_connected,
+            listenbrainz_token: listenbrainz_token.read().clone(),
+            pending_scrobbles: *pending_scrobbles.read(),
+            is_busy: *is_busy.read(),
+            error: error.read().clone(),
+            on_lastfm_api_key_change: move |value| api_key.set(value),
+            on_lastfm_api_secret_change: move |value| api_secret.set(value),
+            on_lastfm_connect: lastfm_connect,
+            on_lastfm_finish: lastfm_finish,
+            on_lastfm_disconnect: lastfm_disconnect,
+            on_listenbrainz_token_change: move |value| listenbrainz_token.set(value),
+            on_listenbrainz_connect: listenbrainz_connect,
+            on_listenbrainz_disconnect: listenbrainz_disconnect,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Seeding section wrapper - reads kept torrents from the seeding store,
+//! delegates UI to SeedingSectionView. Without torrent support there's
+//! nothing kept to show.
+
+#[cfg(feature = "torrent")]
+use crate::ui::app_service::use_app;
+#[cfg(feature = "torrent")]
+use bae_core::torrent::seeding::SEED_STATS_INTERVAL;
+#[cfg(feature = "torrent")]
+use bae_ui::stores::{AppStateStoreExt, SeedingStateStoreExt};
+#[cfg(feature = "torrent")]
+use bae_ui::SeedingSectionView;
+use dioxus::prelude::*;
+
+#[component]
+pub fn SeedingSection() -> Element {
+    #[cfg(feature = "torrent")]
+    {
+        let app = use_app();
+
+        // Ratios move while the tab is open, as often as the stats are saved
+        let refresh_app = app.clone();
+        use_future(move || {
+            let app = refresh_app.clone();
+            async move {
+                loop {
+                    app.load_seeding();
+                    tokio::time::sleep(SEED_STATS_INTERVAL).await;
+                }
+            }
+        });
+
+        let torrents = app.state.seeding().torrents().read().clone();
+        let error = app.state.seeding().error().read().clone();
+
+        let pause = {
+            let app = app.clone();
+            move |release_id: String| app.pause_seeding(release_id)
+        };
+        let resume = {
+            let app = app.clone();
+            move |release_id: String| app.resume_seeding(release_id)
+        };
+        let remove = {
+            let app = app.clone();
+            move |release_id: String| app.remove_seed(release_id)
+        };
+        let change_goals = move |(release_id, ratio, hours): (String, Option<f64>, Option<u32>)| {
+            app.set_seed_goals(release_id, ratio, hours)
+        };
+
+        rsx! {
+            SeedingSectionView {
+                torrents,
+                error,
+                on_pause: pause,
+                on_resume: resume,
+                on_remove: remove,
+                on_goals_change: change_goals,
+            }
+        }
+    }
+
+    #[cfg(not(feature = "torrent"))]
+    rsx! {}
+}
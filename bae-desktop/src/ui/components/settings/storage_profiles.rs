@@ -1,7 +1,7 @@
 //! Storage Profiles section wrapper - handles persistence, delegates UI to StorageProfilesSectionView
 
 use crate::ui::app_service::use_app;
-use bae_ui::stores::{AppStateStoreExt, StorageProfilesStateStoreExt};
+use bae_ui::stores::{AppStateStoreExt, ConfigStateStoreExt, StorageProfilesStateStoreExt};
 use bae_ui::{StorageProfile, StorageProfilesSectionView};
 use dioxus::prelude::*;
 
@@ -14,6 +14,7 @@ pub fn StorageProfilesSection() -> Element {
     let store = app.state.storage_profiles();
     let profiles = store.profiles();
     let is_loading = store.loading();
+    let cloud_price_per_gb = *app.state.config().cloud_price_per_gb().read();
 
     // Local UI state for editing
     let mut editing_profile = use_signal(|| Option::<StorageProfile>::None);
@@ -45,6 +46,13 @@ pub fn StorageProfilesSection() -> Element {
         }
     };
 
+    let handle_cloud_price_change = {
+        let app = app.clone();
+        move |price: f64| {
+            app.save_config(move |config| config.cloud_price_per_gb = price);
+        }
+    };
+
     let handle_edit = move |profile: StorageProfile| {
         editing_profile.set(Some(profile));
         is_creating.set(false);
@@ -68,6 +76,8 @@ pub fn StorageProfilesSection() -> Element {
                 editing_profile.set(None);
                 is_creating.set(false);
             },
+            cloud_price_per_gb,
+            on_cloud_price_change: handle_cloud_price_change,
         }
     }
 }
@@ -1,8 +1,12 @@
-//! Subsonic section wrapper - handles config state, delegates UI to SubsonicSectionView
+//! Subsonic section wrapper - handles config state and client shares, delegates
+//! UI to SubsonicSectionView and ClientSharesView
 
 use crate::ui::app_service::use_app;
-use bae_ui::stores::{AppStateStoreExt, ConfigStateStoreExt};
-use bae_ui::SubsonicSectionView;
+use bae_core::transcode::OPUS_BITRATES_KBPS;
+use bae_ui::stores::{
+    AppStateStoreExt, ClientSharesStateStoreExt, ConfigStateStoreExt, LibraryStateStoreExt,
+};
+use bae_ui::{ClientSharesView, ShareableAlbum, SubsonicSectionView};
 use dioxus::prelude::*;
 
 #[component]
@@ -13,6 +17,7 @@ pub fn SubsonicSection() -> Element {
     let config_store = app.state.config();
     let store_enabled = *config_store.subsonic_enabled().read();
     let store_port = *config_store.subsonic_port().read();
+    let store_bitrate = *config_store.subsonic_transcode_bitrate_kbps().read();
 
     let mut is_editing = use_signal(|| false);
     let mut is_saving = use_signal(|| false);
@@ -20,14 +25,18 @@ pub fn SubsonicSection() -> Element {
 
     let mut enabled = use_signal(move || store_enabled);
     let mut port = use_signal(move || store_port.to_string());
+    let mut bitrate = use_signal(move || store_bitrate);
 
-    let has_changes = *enabled.read() != store_enabled || *port.read() != store_port.to_string();
+    let has_changes = *enabled.read() != store_enabled
+        || *port.read() != store_port.to_string()
+        || *bitrate.read() != store_bitrate;
 
     let save_changes = {
         let app = app.clone();
         move |_| {
             let new_enabled = *enabled.read();
             let new_port: u16 = port.read().parse().unwrap_or(4533);
+            let new_bitrate = *bitrate.read();
 
             is_saving.set(true);
             save_error.set(None);
@@ -35,6 +44,7 @@ pub fn SubsonicSection() -> Element {
             app.save_config(move |config| {
                 config.subsonic_enabled = new_enabled;
                 config.subsonic_port = new_port;
+                config.subsonic_transcode_bitrate_kbps = new_bitrate;
             });
 
             is_saving.set(false);
@@ -42,9 +52,56 @@ pub fn SubsonicSection() -> Element {
         }
     };
 
+    let shares = app.state.client_shares().shares().read().clone();
+    let shares_error = app.state.client_shares().error().read().clone();
+    let albums = {
+        let library = app.state.library();
+        let artists_by_album = library.artists_by_album().read();
+        let mut albums: Vec<ShareableAlbum> = library
+            .albums()
+            .read()
+            .iter()
+            .map(|album| ShareableAlbum {
+                id: album.id.clone(),
+                title: album.title.clone(),
+                artist: artists_by_album
+                    .get(&album.id)
+                    .map(|artists| {
+                        artists
+                            .iter()
+                            .map(|a| a.name.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default(),
+            })
+            .collect();
+        albums.sort_by(|a, b| {
+            (a.artist.to_lowercase(), a.title.to_lowercase())
+                .cmp(&(b.artist.to_lowercase(), b.title.to_lowercase()))
+        });
+        albums
+    };
+
+    let create_share = {
+        let app = app.clone();
+        move |name: String| app.create_client_share(name)
+    };
+    let delete_share = {
+        let app = app.clone();
+        move |share_id: String| app.delete_client_share(&share_id)
+    };
+    let set_album_shared = {
+        let app = app.clone();
+        move |(share_id, album_id, shared): (String, String, bool)| {
+            app.set_album_shared(&share_id, &album_id, shared)
+        }
+    };
+
     let cancel_edit = move |_| {
         enabled.set(store_enabled);
         port.set(store_port.to_string());
+        bitrate.set(store_bitrate);
         is_editing.set(false);
         save_error.set(None);
     };
@@ -53,9 +110,12 @@ pub fn SubsonicSection() -> Element {
         SubsonicSectionView {
             enabled: store_enabled,
             port: store_port,
+            transcode_bitrate_kbps: store_bitrate,
+            transcode_bitrates_kbps: OPUS_BITRATES_KBPS.to_vec(),
             is_editing: *is_editing.read(),
             edit_enabled: *enabled.read(),
             edit_port: port.read().clone(),
+            edit_transcode_bitrate_kbps: *bitrate.read(),
             is_saving: *is_saving.read(),
             has_changes,
             save_error: save_error.read().clone(),
@@ -64,6 +124,17 @@ pub fn SubsonicSection() -> Element {
             on_save: save_changes,
             on_enabled_change: move |val| enabled.set(val),
             on_port_change: move |val| port.set(val),
+            on_transcode_bitrate_change: move |val| bitrate.set(val),
+        }
+        div { class: "max-w-2xl mt-6",
+            ClientSharesView {
+                shares,
+                albums,
+                error: shares_error,
+                on_create: create_share,
+                on_delete: delete_share,
+                on_set_album_shared: set_album_shared,
+            }
         }
     }
 }
@@ -0,0 +1,49 @@
+//! Restore picker for a library database found damaged at launch
+//!
+//! The damaged database was moved aside before the app started, leaving an
+//! empty library. This offers the library's snapshots in its place; the one
+//! picked is downloaded now and swapped in when bae restarts.
+
+use crate::ui::app_service::use_app;
+use bae_ui::stores::{AppStateStoreExt, SnapshotsStateStoreExt};
+use bae_ui::{LibrarySnapshot, SnapshotRestoreModal};
+use dioxus::prelude::*;
+
+/// Restore picker, open from launch until dismissed, if the database was
+/// damaged
+#[component]
+pub fn SnapshotRestoreHandler() -> Element {
+    let app = use_app();
+    let mut show = use_signal(|| true);
+    let Some(damaged_database) = app.state.snapshots().damaged_database().read().clone() else {
+        return rsx! {};
+    };
+    let is_open: ReadSignal<bool> = show.into();
+    let snapshots = app.state.snapshots().snapshots().read().clone();
+    let is_restoring = *app.state.snapshots().is_restoring().read();
+    let restore_staged = *app.state.snapshots().restore_staged().read();
+    let error = app.state.snapshots().restore_error().read().clone();
+
+    let on_restore = {
+        let app = app.clone();
+        move |snapshot: LibrarySnapshot| app.restore_snapshot(snapshot)
+    };
+    let on_restart = {
+        let app = app.clone();
+        move |_| app.restart_app()
+    };
+
+    rsx! {
+        SnapshotRestoreModal {
+            is_open,
+            damaged_database,
+            snapshots,
+            is_restoring,
+            restore_staged,
+            error,
+            on_restore,
+            on_restart,
+            on_close: move |_| show.set(false),
+        }
+    }
+}
@@ -1,17 +1,18 @@
 //! Title bar wrapper for desktop app
 //!
 //! Wraps the shared TitleBarView with desktop-specific behavior:
-//! window dragging (macOS), zoom (macOS), and database-backed search.
+//! window dragging (macOS), zoom (macOS), and full-text library search.
 
 use crate::ui::app_service::use_app;
+use crate::ui::components::album_detail::utils::get_album_track_ids;
 use crate::ui::components::imports_button::ImportsButton;
 use crate::ui::components::imports_dropdown::ImportsDropdown;
 use crate::ui::Route;
-use bae_ui::display_types::Album;
+use bae_core::db::{DbSearchHit, DbSearchResults};
 use bae_ui::stores::{
     AppStateStoreExt, LibraryStateStoreExt, SearchStateStoreExt, UiStateStoreExt,
 };
-use bae_ui::{NavItem, SearchResult, TitleBarView};
+use bae_ui::{NavItem, SearchResult, SearchResultKind, TitleBarView};
 #[cfg(target_os = "macos")]
 use cocoa::appkit::NSApplication;
 #[cfg(target_os = "macos")]
@@ -23,6 +24,10 @@ use dioxus::prelude::*;
 use dispatch::Queue;
 #[cfg(target_os = "macos")]
 use objc::{msg_send, sel, sel_impl};
+use tracing::warn;
+
+/// Most results of each kind shown under the search box
+const SEARCH_RESULTS_PER_KIND: usize = 5;
 
 /// Custom title bar component with navigation and search
 /// On macOS: includes window dragging, zoom, and space for traffic lights
@@ -38,42 +43,38 @@ pub fn TitleBar() -> Element {
     let mut search_query_store = search_store.query();
     let mut show_results = use_signal(|| false);
     let show_results_read: ReadSignal<bool> = show_results.into();
-    let mut filtered_albums = use_signal(Vec::<Album>::new);
+    let mut search_hits = use_signal(DbSearchResults::default);
     let imports_dropdown_open = use_signal(|| false);
 
     // Read albums from global store (populated by App component)
     let albums_store = app.state.library().albums();
-    let artists_store = app.state.library().artists_by_album();
 
-    // Filter albums based on search query
+    // Search the library index as the query changes
     use_effect({
+        let library_manager = app.library_manager.clone();
         move || {
-            let query = search_query_store.read().to_lowercase();
-            if query.is_empty() {
-                filtered_albums.set(Vec::new());
+            let query = search_query_store.read().clone();
+            if query.trim().is_empty() {
+                search_hits.set(DbSearchResults::default());
                 show_results.set(false);
-            } else {
-                let albums = albums_store.read();
-                let artists_map = artists_store.read();
-                let filtered = albums
-                    .iter()
-                    .filter(|album| {
-                        if album.title.to_lowercase().contains(&query) {
-                            return true;
-                        }
-                        if let Some(artists) = artists_map.get(&album.id) {
-                            return artists
-                                .iter()
-                                .any(|artist| artist.name.to_lowercase().contains(&query));
-                        }
-                        false
-                    })
-                    .take(10)
-                    .cloned()
-                    .collect();
-                filtered_albums.set(filtered);
-                show_results.set(true);
+                return;
             }
+            let library_manager = library_manager.clone();
+            spawn(async move {
+                match library_manager
+                    .get()
+                    .search_library(&query, SEARCH_RESULTS_PER_KIND)
+                    .await
+                {
+                    // Drop results for a query that has since changed
+                    Ok(hits) if *search_query_store.peek() == query => {
+                        search_hits.set(hits);
+                        show_results.set(true);
+                    }
+                    Ok(_) => {}
+                    Err(e) => warn!("Library search failed: {}", e),
+                }
+            });
         }
     });
 
@@ -84,6 +85,16 @@ pub fn TitleBar() -> Element {
             label: "Library".to_string(),
             is_active: matches!(current_route, Route::Library {} | Route::AlbumDetail { .. }),
         },
+        NavItem {
+            id: "tracks".to_string(),
+            label: "Tracks".to_string(),
+            is_active: matches!(current_route, Route::Tracks {}),
+        },
+        NavItem {
+            id: "history".to_string(),
+            label: "History".to_string(),
+            is_active: matches!(current_route, Route::History {}),
+        },
         NavItem {
             id: "import".to_string(),
             label: "Import".to_string(),
@@ -91,35 +102,49 @@ pub fn TitleBar() -> Element {
         },
     ];
 
-    // Convert filtered albums to search results
+    // Convert hits to search results, grouped artists, albums, tracks
     let search_results: Vec<SearchResult> = {
-        let artists_map = artists_store.read();
-        filtered_albums()
-            .iter()
-            .map(|album| {
-                let artists = artists_map.get(&album.id).cloned().unwrap_or_default();
-                let artist_name = if artists.is_empty() {
-                    "Unknown Artist".to_string()
-                } else {
-                    artists
-                        .iter()
-                        .map(|a| a.name.as_str())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                };
-                let subtitle = if let Some(year) = album.year {
-                    format!("{} • {}", artist_name, year)
-                } else {
-                    artist_name
-                };
-                SearchResult {
-                    id: album.id.clone(),
-                    title: album.title.clone(),
-                    subtitle,
-                    cover_url: album.cover_url.clone(),
-                }
-            })
-            .collect()
+        let hits = search_hits.read();
+        let albums = albums_store.read();
+        let find_album = |album_id: &Option<String>| {
+            album_id
+                .as_ref()
+                .and_then(|id| albums.iter().find(|a| &a.id == id))
+        };
+        let artists = hits.artists.iter().map(|hit| SearchResult {
+            kind: SearchResultKind::Artist,
+            id: hit.id.clone(),
+            title: hit.title.clone(),
+            subtitle: "Artist".to_string(),
+            cover_url: None,
+        });
+        let album_results = hits.albums.iter().map(|hit| {
+            let album = find_album(&hit.album_id);
+            let artist_name = if hit.artist.is_empty() {
+                "Unknown Artist".to_string()
+            } else {
+                hit.artist.clone()
+            };
+            let subtitle = match album.and_then(|a| a.year) {
+                Some(year) => format!("{} • {}", artist_name, year),
+                None => artist_name,
+            };
+            SearchResult {
+                kind: SearchResultKind::Album,
+                id: hit.id.clone(),
+                title: hit.title.clone(),
+                subtitle,
+                cover_url: album.and_then(|a| a.cover_url.clone()),
+            }
+        });
+        let tracks = hits.tracks.iter().map(|hit| SearchResult {
+            kind: SearchResultKind::Track,
+            id: hit.id.clone(),
+            title: hit.title.clone(),
+            subtitle: format!("{} • {}", hit.artist, hit.album_title),
+            cover_url: find_album(&hit.album_id).and_then(|a| a.cover_url.clone()),
+        });
+        artists.chain(album_results).chain(tracks).collect()
     };
 
     // Platform-specific: left padding for traffic lights on macOS
@@ -148,6 +173,8 @@ pub fn TitleBar() -> Element {
             on_nav_click: move |id: String| {
                 let route = match id.as_str() {
                     "library" => Route::Library {},
+                    "tracks" => Route::Tracks {},
+                    "history" => Route::History {},
                     "import" => Route::ImportWorkflowManager {},
                     _ => return,
                 };
@@ -156,15 +183,56 @@ pub fn TitleBar() -> Element {
             search_value: search_query_store.read().clone(),
             on_search_change: move |value| search_query_store.set(value),
             search_results,
-            on_search_result_click: move |album_id: String| {
+            on_search_result_click: move |id: String| {
+                let Some((kind, hit)) = find_hit(&search_hits.peek(), &id) else {
+                    return;
+                };
+                if kind == SearchResultKind::Artist {
+                    // Narrow the search to the artist's albums and tracks
+                    search_query_store.set(hit.title);
+                    return;
+                }
                 show_results.set(false);
                 search_query_store.set(String::new());
+                // Tracks open their album at the track's release and row
+                let track_id = match kind {
+                    SearchResultKind::Track => hit.id,
+                    _ => String::new(),
+                };
                 navigator()
                     .push(Route::AlbumDetail {
-                        album_id,
-                        release_id: String::new(),
+                        album_id: hit.album_id.unwrap_or_default(),
+                        release_id: hit.release_id.unwrap_or_default(),
+                        track_id,
                     });
             },
+            on_search_result_play: {
+                let library_manager = app.library_manager.clone();
+                let playback = app.playback_handle.clone();
+                move |id: String| {
+                    let Some((kind, hit)) = find_hit(&search_hits.peek(), &id) else {
+                        return;
+                    };
+                    if kind == SearchResultKind::Artist {
+                        search_query_store.set(hit.title);
+                        return;
+                    }
+                    show_results.set(false);
+                    search_query_store.set(String::new());
+                    if kind == SearchResultKind::Track {
+                        playback.play(hit.id);
+                        return;
+                    }
+                    let library_manager = library_manager.clone();
+                    let playback = playback.clone();
+                    spawn(async move {
+                        let result = get_album_track_ids(&library_manager, &hit.id).await;
+                        if let Ok(track_ids) = result {
+                            playback.play_album(track_ids);
+                        }
+                    });
+                }
+            },
             show_search_results: show_results_read,
             on_search_dismiss: move |_| show_results.set(false),
             on_search_focus: move |_| {
@@ -187,6 +255,21 @@ pub fn TitleBar() -> Element {
     }
 }
 
+/// A search hit by ID, with its kind
+fn find_hit(results: &DbSearchResults, id: &str) -> Option<(SearchResultKind, DbSearchHit)> {
+    [
+        (SearchResultKind::Artist, &results.artists),
+        (SearchResultKind::Album, &results.albums),
+        (SearchResultKind::Track, &results.tracks),
+    ]
+    .into_iter()
+    .find_map(|(kind, hits)| {
+        hits.iter()
+            .find(|hit| hit.id == id)
+            .map(|hit| (kind, hit.clone()))
+    })
+}
+
 /// Perform window zoom (maximize/restore) using native macOS API
 #[cfg(target_os = "macos")]
 fn perform_zoom() {
@@ -0,0 +1,59 @@
+//! Tracks page component
+//!
+//! Uses bae-ui's TracksView; tracks are (re)loaded into the app store on mount.
+
+use crate::ui::app_service::use_app;
+use crate::ui::Route;
+use bae_ui::display_types::TrackClickAction;
+use bae_ui::stores::{AppStateStoreExt, ConfigStateStoreExt};
+use bae_ui::TracksView;
+use dioxus::prelude::*;
+
+/// Tracks page component - passes state lens to bae-ui's TracksView
+#[component]
+pub fn TracksPage() -> Element {
+    let app = use_app();
+
+    use_effect({
+        let app = app.clone();
+        move || app.load_library_tracks()
+    });
+
+    let state = app.state.tracks();
+    let click_action = app
+        .state
+        .config()
+        .track_click_action()
+        .read()
+        .unwrap_or(TrackClickAction::Select);
+
+    let on_play = {
+        let playback = app.playback_handle.clone();
+        move |track_ids: Vec<String>| playback.play_album(track_ids)
+    };
+
+    let on_add_to_queue = {
+        let playback = app.playback_handle.clone();
+        move |track_ids: Vec<String>| playback.add_to_queue(track_ids)
+    };
+
+    let on_album_click = move |album_id: String| {
+        navigator().push(Route::AlbumDetail {
+            album_id,
+            release_id: String::new(),
+            track_id: String::new(),
+        });
+    };
+
+    rsx! {
+        TracksView {
+            state,
+            click_action,
+            on_play,
+            on_add_to_queue,
+            on_album_click,
+        }
+    }
+}
+
+pub use TracksPage as Tracks;
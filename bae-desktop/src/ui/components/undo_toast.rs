@@ -0,0 +1,41 @@
+//! Undo toast component
+//!
+//! Shows the last undo or redo from the app store, with the reverse action.
+
+use crate::ui::app_service::use_app;
+use bae_ui::stores::{AppStateStoreExt, UndoStateStoreExt};
+use bae_ui::UndoToast;
+use dioxus::prelude::*;
+
+/// Toast for the last undo or redo, if it hasn't been dismissed
+#[component]
+pub fn UndoToastHandler() -> Element {
+    let app = use_app();
+    let Some(message) = app.state.undo().message().read().clone() else {
+        return rsx! {};
+    };
+    let offers_redo = *app.state.undo().offers_redo().read();
+
+    let on_undo = {
+        let app = app.clone();
+        move |_| app.undo()
+    };
+    let on_redo = {
+        let app = app.clone();
+        move |_| app.redo()
+    };
+    let on_dismiss = {
+        let app = app.clone();
+        move |_| app.dismiss_undo_toast()
+    };
+
+    rsx! {
+        UndoToast {
+            message,
+            offers_redo,
+            on_undo,
+            on_redo,
+            on_dismiss,
+        }
+    }
+}
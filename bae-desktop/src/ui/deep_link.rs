@@ -0,0 +1,143 @@
+//! bae:// deep links
+//!
+//! - `bae://album/{album_id}` opens the album
+//! - `bae://track/{track_id}` opens the track's album and starts playing it
+//!
+//! The scheme is registered with the OS via Info.plist on macOS, where links
+//! opened while the app runs arrive as tao `Opened` events. On other platforms
+//! the link is passed as a command-line argument at launch.
+//!
+//! Asset URLs on the same scheme (bae://image, bae://local) are served to the
+//! webview by the custom protocol handler and never reach here.
+
+use crate::ui::app_service::use_app;
+use crate::ui::Route;
+use dioxus::desktop::tao::event::Event;
+use dioxus::desktop::use_wry_event_handler;
+use dioxus::prelude::*;
+use tracing::{info, warn};
+
+/// A parsed bae:// deep link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    Album(String),
+    Track(String),
+}
+
+impl DeepLink {
+    /// Parse `bae://album/{id}` or `bae://track/{id}`, ignoring any query or trailing slash
+    pub fn parse(url: &str) -> Option<Self> {
+        let rest = url.strip_prefix("bae://")?;
+        let rest = rest.split(['?', '#']).next().unwrap_or_default();
+        let (kind, id) = rest.trim_end_matches('/').split_once('/')?;
+        if id.is_empty() || id.contains('/') {
+            return None;
+        }
+        match kind {
+            "album" => Some(DeepLink::Album(id.to_string())),
+            "track" => Some(DeepLink::Track(id.to_string())),
+            _ => None,
+        }
+    }
+}
+
+/// Deep link passed on the command line at launch, if any
+pub fn launch_deep_link() -> Option<DeepLink> {
+    std::env::args()
+        .skip(1)
+        .find_map(|arg| DeepLink::parse(&arg))
+}
+
+/// Routes deep links from launch arguments and OS open-URL events.
+/// Must be rendered inside the router.
+#[component]
+pub fn DeepLinkHandler() -> Element {
+    let app = use_app();
+
+    let open = use_callback(move |link: DeepLink| {
+        info!("Opening deep link: {:?}", link);
+
+        match link {
+            DeepLink::Album(album_id) => {
+                navigator().push(Route::AlbumDetail {
+                    album_id,
+                    release_id: String::new(),
+                    track_id: String::new(),
+                });
+            }
+            DeepLink::Track(track_id) => {
+                let library_manager = app.library_manager.clone();
+                let playback = app.playback_handle.clone();
+                spawn(async move {
+                    match library_manager
+                        .get()
+                        .get_album_id_for_track(&track_id)
+                        .await
+                    {
+                        Ok(album_id) => {
+                            navigator().push(Route::AlbumDetail {
+                                album_id,
+                                release_id: String::new(),
+                                track_id: track_id.clone(),
+                            });
+                            playback.play(track_id);
+                        }
+                        Err(e) => {
+                            warn!("Deep link to unknown track {}: {}", track_id, e);
+                        }
+                    }
+                });
+            }
+        }
+    });
+
+    use_hook(move || {
+        if let Some(link) = launch_deep_link() {
+            open.call(link);
+        }
+    });
+
+    use_wry_event_handler(move |event, _| {
+        if let Event::Opened { urls } = event {
+            for url in urls {
+                match DeepLink::parse(url.as_str()) {
+                    Some(link) => open.call(link),
+                    None => {
+                        warn!("Ignoring unsupported URL: {}", url);
+                    }
+                }
+            }
+        }
+    });
+
+    rsx! {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_album_link() {
+        assert_eq!(
+            DeepLink::parse("bae://album/abc"),
+            Some(DeepLink::Album("abc".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_track_link_with_trailing_slash_and_query() {
+        assert_eq!(
+            DeepLink::parse("bae://track/abc/?source=share"),
+            Some(DeepLink::Track("abc".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_rejects_asset_and_malformed_urls() {
+        assert_eq!(DeepLink::parse("bae://image/abc"), None);
+        assert_eq!(DeepLink::parse("bae://album/"), None);
+        assert_eq!(DeepLink::parse("bae://album/a/b"), None);
+        assert_eq!(DeepLink::parse("https://album/abc"), None);
+    }
+}
@@ -0,0 +1,77 @@
+//! What inserting an audio CD does
+//!
+//! Per the CD Ripping settings, a newly inserted disc opens the CD import
+//! with that drive selected, optionally ripping straight away when DiscID
+//! recognizes the disc. The CD import picks the disc up from
+//! `ImportState::inserted_cd`. Without CD ripping built in, nothing is watched.
+
+#[cfg(feature = "cd-rip")]
+use crate::ui::app_service::{use_app, AppService};
+#[cfg(feature = "cd-rip")]
+use crate::ui::import_helpers::has_unclean_state;
+#[cfg(feature = "cd-rip")]
+use crate::ui::Route;
+#[cfg(feature = "cd-rip")]
+use bae_core::cd::watch_for_discs;
+#[cfg(feature = "cd-rip")]
+use bae_ui::stores::import::InsertedCd;
+#[cfg(feature = "cd-rip")]
+use bae_ui::stores::{AppStateStoreExt, ConfigStateStoreExt};
+#[cfg(feature = "cd-rip")]
+use bae_ui::{CdInsertAction, ImportSource};
+use dioxus::prelude::*;
+#[cfg(feature = "cd-rip")]
+use tracing::info;
+
+/// Watches the CD drives for the life of the app.
+/// Must be rendered inside the router.
+#[component]
+pub fn DiscInsertHandler() -> Element {
+    #[cfg(feature = "cd-rip")]
+    {
+        let app = use_app();
+        use_hook(move || {
+            spawn(open_inserted_discs(app));
+        });
+    }
+
+    rsx! {}
+}
+
+/// Open the CD import for each disc that goes in
+#[cfg(feature = "cd-rip")]
+async fn open_inserted_discs(app: AppService) {
+    let mut inserted = watch_for_discs();
+    while let Some(drive) = inserted.recv().await {
+        let action = app
+            .state
+            .config()
+            .cd_insert_action()
+            .read()
+            .unwrap_or(CdInsertAction::Nothing);
+        if action == CdInsertAction::Nothing {
+            continue;
+        }
+
+        // Never throw away an import the user is in the middle of
+        if has_unclean_state(&app) {
+            info!(
+                "Ignoring disc in {:?}: an import is in progress",
+                drive.device_path
+            );
+            continue;
+        }
+
+        {
+            let mut import_store = app.state.import();
+            let mut state = import_store.write();
+            state.selected_import_source = ImportSource::Cd;
+            state.reset();
+            state.inserted_cd = Some(InsertedCd {
+                device_path: drive.device_path.to_string_lossy().to_string(),
+                auto_rip: action == CdInsertAction::AutoRip,
+            });
+        }
+        navigator().push(Route::ImportWorkflowManager {});
+    }
+}
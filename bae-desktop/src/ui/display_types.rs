@@ -1,10 +1,28 @@
 //! Conversions from DB types to bae-ui display types
 
 use crate::ui::image_url;
-use bae_core::db::{DbAlbum, DbArtist, DbRelease, DbTrack, ImportStatus};
+use bae_core::config;
+use bae_core::db::{
+    self, DbAlbum, DbArtist, DbFile, DbLibraryTrack, DbRelease, DbReleaseTemplate, DbTrack,
+    DbTrackBookmark, DbTrackPlay, DbTrackPlayStats, DbVerificationIssue, DbVerificationRun,
+    ImportStatus, VerificationIssueKind,
+};
+use bae_core::health;
+use bae_core::import::release_template::template_title;
+use bae_core::import::{DownloadHealth, PrepareStep};
+use bae_core::library::compare;
+use bae_core::library::edit::{AlbumEdits, AlbumFields, ReleaseFields, TrackFields};
+use bae_core::library::find_replace::{self, ReplaceField, ReplacePreview};
+use bae_core::library::snapshot;
+use bae_core::playback;
+use bae_ui::stores::import::PrepareStage;
 
 // Re-export bae-ui types so existing code continues to work
-pub use bae_ui::{Album, Artist, Release, Track, TrackImportState};
+pub use bae_ui::{
+    Album, AlbumPalette, Artist, AudioQuality, File, LibraryTrack, OutputFormatInfo, Release,
+    ReleaseTemplate, Track, TrackBookmark, TrackImportState, TrackPlay, TrackPlayCount,
+    VerificationIssue, VerificationRun,
+};
 
 pub fn album_from_db_ref(db: &DbAlbum) -> Album {
     let cover_url = db
@@ -19,9 +37,108 @@ pub fn album_from_db_ref(db: &DbAlbum) -> Album {
         year: db.year,
         cover_url,
         is_compilation: db.is_compilation,
+        date_added: db.created_at.timestamp(),
+        play_count: db.play_count.max(0) as u32,
+        last_played: db.last_played_at.map(|t| t.timestamp()),
+        palette: palette_from_db_ref(db),
+        quality: None,
+        is_offline: false,
     }
 }
 
+pub fn bookmark_from_db(db: &DbTrackBookmark) -> TrackBookmark {
+    TrackBookmark {
+        id: db.id.clone(),
+        track_id: db.track_id.clone(),
+        name: db.name.clone(),
+        position_ms: db.position_ms.max(0) as u64,
+    }
+}
+
+pub fn release_template_from_db(db: &DbReleaseTemplate) -> ReleaseTemplate {
+    ReleaseTemplate {
+        id: db.id.clone(),
+        artist: db.artist.clone(),
+        title_pattern: db.title_pattern.clone(),
+        next_title: template_title(db),
+        cover_url: db.cover_url.clone(),
+    }
+}
+
+pub fn download_health_to_display(health: &DownloadHealth) -> bae_ui::TorrentDownloadHealth {
+    bae_ui::TorrentDownloadHealth {
+        download_rate: health.download_rate,
+        peers: health.peers,
+        seeds: health.seeds,
+        downloaded_bytes: health.downloaded_bytes,
+        total_bytes: health.total_bytes,
+        eta_secs: health.eta_secs,
+        piece_bar: health.piece_bar.clone(),
+    }
+}
+
+/// The confirm step's checklist groups the import's preparation steps into stages
+pub fn prepare_stage_from_step(step: PrepareStep) -> PrepareStage {
+    match step {
+        PrepareStep::ParsingMetadata => PrepareStage::FetchingRelease,
+        PrepareStep::DownloadingCoverArt => PrepareStage::FetchingCover,
+        PrepareStep::ExtractingDiscImages => PrepareStage::ExtractingDiscImages,
+        PrepareStep::SplittingSides => PrepareStage::SplittingSides,
+        PrepareStep::DiscoveringFiles | PrepareStep::ValidatingTracks => {
+            PrepareStage::MatchingFiles
+        }
+        PrepareStep::SavingToDatabase | PrepareStep::ExtractingDurations => {
+            PrepareStage::WritingMetadata
+        }
+    }
+}
+
+/// An import from the active imports store, as the imports dropdown and
+/// queue page show it
+pub fn active_import_to_display(import: &bae_ui::stores::ActiveImport) -> bae_ui::ActiveImport {
+    use bae_ui::stores::ImportOperationStatus;
+    use bae_ui::ImportStatus;
+
+    let cover_url = import
+        .cover_image_id
+        .as_ref()
+        .map(|id| image_url(id))
+        .or_else(|| import.cover_art_url.clone());
+
+    bae_ui::ActiveImport {
+        import_id: import.import_id.clone(),
+        album_title: import.album_title.clone(),
+        artist_name: import.artist_name.clone(),
+        status: match import.status {
+            ImportOperationStatus::Pending => ImportStatus::Preparing,
+            ImportOperationStatus::Preparing => ImportStatus::Preparing,
+            ImportOperationStatus::Queued => ImportStatus::Queued,
+            ImportOperationStatus::Importing => ImportStatus::Importing,
+            ImportOperationStatus::Paused => ImportStatus::Paused,
+            ImportOperationStatus::Complete => ImportStatus::Complete,
+            ImportOperationStatus::Failed => ImportStatus::Failed,
+            ImportOperationStatus::Cancelled => ImportStatus::Cancelled,
+        },
+        current_step_text: import.current_step.map(|s| format!("{:?}", s)),
+        progress_percent: import.progress_percent,
+        release_id: import.release_id.clone(),
+        cover_url,
+        store_bytes_per_sec: import.store_bytes_per_sec,
+        error_message: import.error_message.clone(),
+        can_pause: import.can_pause(),
+        can_resume: import.can_resume(),
+        can_cancel: import.can_cancel(),
+        can_retry: import.can_retry(),
+    }
+}
+
+pub fn palette_from_db_ref(db: &DbAlbum) -> Option<AlbumPalette> {
+    Some(AlbumPalette {
+        dominant: db.dominant_color.clone()?,
+        accent: db.accent_color.clone()?,
+    })
+}
+
 pub fn artist_from_db_ref(db: &DbArtist) -> Artist {
     Artist {
         id: db.id.clone(),
@@ -43,6 +160,63 @@ pub fn track_from_db_ref(db: &DbTrack) -> Track {
         } else {
             TrackImportState::None
         },
+        quality: None,
+        bookmarks: Vec::new(),
+    }
+}
+
+pub fn library_track_from_db(db: &DbLibraryTrack) -> LibraryTrack {
+    LibraryTrack {
+        id: db.track.id.clone(),
+        title: db.track.title.clone(),
+        artist_name: db.artist_name.clone().unwrap_or_default(),
+        album_id: db.album_id.clone(),
+        album_title: db.album_title.clone(),
+        year: db.album_year,
+        disc_number: db.track.disc_number,
+        track_number: db.track.track_number,
+        duration_ms: db.track.duration_ms,
+    }
+}
+
+pub fn track_play_from_db(db: &DbTrackPlay) -> TrackPlay {
+    TrackPlay {
+        id: db.id.clone(),
+        track: library_track_from_db(&db.track),
+        played_at: db.played_at.timestamp(),
+    }
+}
+
+pub fn track_play_count_from_db(db: &DbTrackPlayStats) -> TrackPlayCount {
+    TrackPlayCount {
+        track: library_track_from_db(&db.track),
+        play_count: db.play_count.max(0) as u32,
+        last_played: db.last_played_at.timestamp(),
+    }
+}
+
+pub fn quality_from_db(quality: db::AudioQuality) -> AudioQuality {
+    match quality {
+        db::AudioQuality::Lossy => AudioQuality::Lossy,
+        db::AudioQuality::Lossless => AudioQuality::Lossless,
+        db::AudioQuality::HiRes => AudioQuality::HiRes,
+    }
+}
+
+pub fn quality_to_db(quality: AudioQuality) -> db::AudioQuality {
+    match quality {
+        AudioQuality::Lossy => db::AudioQuality::Lossy,
+        AudioQuality::Lossless => db::AudioQuality::Lossless,
+        AudioQuality::HiRes => db::AudioQuality::HiRes,
+    }
+}
+
+pub fn file_from_db_ref(db: &DbFile) -> File {
+    File {
+        id: db.id.clone(),
+        filename: db.original_filename.clone(),
+        file_size: db.file_size,
+        format: db.format.clone(),
     }
 }
 
@@ -61,3 +235,394 @@ pub fn release_from_db_ref(db: &DbRelease) -> Release {
         musicbrainz_release_id: None,
     }
 }
+
+pub fn output_bit_depth_to_display(depth: playback::OutputBitDepth) -> bae_ui::OutputBitDepth {
+    match depth {
+        playback::OutputBitDepth::Auto => bae_ui::OutputBitDepth::Auto,
+        playback::OutputBitDepth::Float32 => bae_ui::OutputBitDepth::Float32,
+        playback::OutputBitDepth::Int24 => bae_ui::OutputBitDepth::Int24,
+        playback::OutputBitDepth::Int16 => bae_ui::OutputBitDepth::Int16,
+    }
+}
+
+pub fn output_bit_depth_from_display(depth: bae_ui::OutputBitDepth) -> playback::OutputBitDepth {
+    match depth {
+        bae_ui::OutputBitDepth::Auto => playback::OutputBitDepth::Auto,
+        bae_ui::OutputBitDepth::Float32 => playback::OutputBitDepth::Float32,
+        bae_ui::OutputBitDepth::Int24 => playback::OutputBitDepth::Int24,
+        bae_ui::OutputBitDepth::Int16 => playback::OutputBitDepth::Int16,
+    }
+}
+
+pub fn dither_to_display(dither: playback::Dither) -> bae_ui::Dither {
+    match dither {
+        playback::Dither::Off => bae_ui::Dither::Off,
+        playback::Dither::Tpdf => bae_ui::Dither::Tpdf,
+        playback::Dither::NoiseShaped => bae_ui::Dither::NoiseShaped,
+    }
+}
+
+pub fn dither_from_display(dither: bae_ui::Dither) -> playback::Dither {
+    match dither {
+        bae_ui::Dither::Off => playback::Dither::Off,
+        bae_ui::Dither::Tpdf => playback::Dither::Tpdf,
+        bae_ui::Dither::NoiseShaped => playback::Dither::NoiseShaped,
+    }
+}
+
+pub fn startup_view_to_display(view: config::StartupView) -> bae_ui::StartupView {
+    match view {
+        config::StartupView::Library => bae_ui::StartupView::Library,
+        config::StartupView::NowPlaying => bae_ui::StartupView::NowPlaying,
+        config::StartupView::LastRoute => bae_ui::StartupView::LastRoute,
+    }
+}
+
+pub fn startup_view_from_display(view: bae_ui::StartupView) -> config::StartupView {
+    match view {
+        bae_ui::StartupView::Library => config::StartupView::Library,
+        bae_ui::StartupView::NowPlaying => config::StartupView::NowPlaying,
+        bae_ui::StartupView::LastRoute => config::StartupView::LastRoute,
+    }
+}
+
+pub fn track_click_action_to_display(action: config::TrackClickAction) -> bae_ui::TrackClickAction {
+    match action {
+        config::TrackClickAction::Play => bae_ui::TrackClickAction::Play,
+        config::TrackClickAction::AddToQueue => bae_ui::TrackClickAction::AddToQueue,
+        config::TrackClickAction::Select => bae_ui::TrackClickAction::Select,
+    }
+}
+
+pub fn track_click_action_from_display(
+    action: bae_ui::TrackClickAction,
+) -> config::TrackClickAction {
+    match action {
+        bae_ui::TrackClickAction::Play => config::TrackClickAction::Play,
+        bae_ui::TrackClickAction::AddToQueue => config::TrackClickAction::AddToQueue,
+        bae_ui::TrackClickAction::Select => config::TrackClickAction::Select,
+    }
+}
+
+pub fn cd_insert_action_to_display(action: config::CdInsertAction) -> bae_ui::CdInsertAction {
+    match action {
+        config::CdInsertAction::Nothing => bae_ui::CdInsertAction::Nothing,
+        config::CdInsertAction::OpenImport => bae_ui::CdInsertAction::OpenImport,
+        config::CdInsertAction::AutoRip => bae_ui::CdInsertAction::AutoRip,
+    }
+}
+
+pub fn cd_insert_action_from_display(action: bae_ui::CdInsertAction) -> config::CdInsertAction {
+    match action {
+        bae_ui::CdInsertAction::Nothing => config::CdInsertAction::Nothing,
+        bae_ui::CdInsertAction::OpenImport => config::CdInsertAction::OpenImport,
+        bae_ui::CdInsertAction::AutoRip => config::CdInsertAction::AutoRip,
+    }
+}
+
+pub fn featured_credit_style_to_display(
+    style: config::FeaturedCreditStyle,
+) -> bae_ui::FeaturedCreditStyle {
+    match style {
+        config::FeaturedCreditStyle::Keep => bae_ui::FeaturedCreditStyle::Keep,
+        config::FeaturedCreditStyle::Standardize => bae_ui::FeaturedCreditStyle::Standardize,
+        config::FeaturedCreditStyle::Split => bae_ui::FeaturedCreditStyle::Split,
+    }
+}
+
+pub fn featured_credit_style_from_display(
+    style: bae_ui::FeaturedCreditStyle,
+) -> config::FeaturedCreditStyle {
+    match style {
+        bae_ui::FeaturedCreditStyle::Keep => config::FeaturedCreditStyle::Keep,
+        bae_ui::FeaturedCreditStyle::Standardize => config::FeaturedCreditStyle::Standardize,
+        bae_ui::FeaturedCreditStyle::Split => config::FeaturedCreditStyle::Split,
+    }
+}
+
+pub fn library_sort_to_display(sort: config::LibrarySort) -> bae_ui::LibrarySort {
+    match sort {
+        config::LibrarySort::Title => bae_ui::LibrarySort::Title,
+        config::LibrarySort::DateAdded => bae_ui::LibrarySort::DateAdded,
+        config::LibrarySort::LastPlayed => bae_ui::LibrarySort::LastPlayed,
+        config::LibrarySort::PlayCount => bae_ui::LibrarySort::PlayCount,
+    }
+}
+
+pub fn library_sort_from_display(sort: bae_ui::LibrarySort) -> config::LibrarySort {
+    match sort {
+        bae_ui::LibrarySort::Title => config::LibrarySort::Title,
+        bae_ui::LibrarySort::DateAdded => config::LibrarySort::DateAdded,
+        bae_ui::LibrarySort::LastPlayed => config::LibrarySort::LastPlayed,
+        bae_ui::LibrarySort::PlayCount => config::LibrarySort::PlayCount,
+    }
+}
+
+pub fn output_format_to_display(format: &playback::OutputFormat) -> OutputFormatInfo {
+    let sample_format = match format.sample_format {
+        playback::OutputSampleFormat::F32 => "32-bit float",
+        playback::OutputSampleFormat::I24In32 => "24-bit",
+        playback::OutputSampleFormat::I16 => "16-bit",
+    };
+    OutputFormatInfo {
+        device_name: format.device_name.clone(),
+        sample_rate: format.sample_rate,
+        channels: format.channels,
+        sample_format: sample_format.to_string(),
+        dither: (format.dither != playback::Dither::Off).then(|| dither_to_display(format.dither)),
+        exclusive: format.exclusive,
+    }
+}
+
+/// `first_failures` maps file IDs to when a run first reported them
+pub fn verification_run_from_db(
+    run: &DbVerificationRun,
+    issues: &[DbVerificationIssue],
+    first_failures: &std::collections::HashMap<String, chrono::DateTime<chrono::Utc>>,
+) -> VerificationRun {
+    VerificationRun {
+        id: run.id.clone(),
+        scheduled: run.scheduled,
+        started_at: run.started_at.timestamp(),
+        finished: run.finished_at.is_some(),
+        files_checked: run.files_checked,
+        issues: issues
+            .iter()
+            .map(|issue| VerificationIssue {
+                id: issue.id.clone(),
+                kind: verification_issue_kind_to_display(issue.kind),
+                filename: issue.filename.clone(),
+                storage_profile_name: issue.storage_profile_name.clone(),
+                error: issue.error.clone(),
+                first_seen: issue
+                    .file_id
+                    .as_ref()
+                    .and_then(|file_id| first_failures.get(file_id))
+                    .unwrap_or(&run.started_at)
+                    .timestamp(),
+                repaired: issue.repaired,
+            })
+            .collect(),
+    }
+}
+
+fn verification_issue_kind_to_display(
+    kind: VerificationIssueKind,
+) -> bae_ui::VerificationIssueKind {
+    match kind {
+        VerificationIssueKind::Missing => bae_ui::VerificationIssueKind::Missing,
+        VerificationIssueKind::Corrupt => bae_ui::VerificationIssueKind::Corrupt,
+        VerificationIssueKind::Orphaned => bae_ui::VerificationIssueKind::Orphaned,
+    }
+}
+
+pub fn album_edits_from_display(edit: &bae_ui::AlbumEdit) -> AlbumEdits {
+    AlbumEdits {
+        album_id: edit.album_id.clone(),
+        album: Some(AlbumFields {
+            title: edit.title.clone(),
+            year: edit.year,
+        }),
+        artists: Some(edit.artists.clone()),
+        releases: edit
+            .release
+            .iter()
+            .map(|r| {
+                (
+                    r.id.clone(),
+                    ReleaseFields {
+                        release_name: r.release_name.clone(),
+                        year: r.year,
+                        format: r.format.clone(),
+                        label: r.label.clone(),
+                        catalog_number: r.catalog_number.clone(),
+                        country: r.country.clone(),
+                        barcode: r.barcode.clone(),
+                    },
+                )
+            })
+            .collect(),
+        tracks: edit
+            .tracks
+            .iter()
+            .map(|t| {
+                (
+                    t.id.clone(),
+                    TrackFields {
+                        title: t.title.clone(),
+                        disc_number: t.disc_number,
+                        track_number: t.track_number,
+                    },
+                )
+            })
+            .collect(),
+    }
+}
+
+pub fn find_replace_from_display(
+    request: &bae_ui::FindReplaceRequest,
+) -> find_replace::FindReplace {
+    find_replace::FindReplace {
+        find: request.find.clone(),
+        replace: request.replace.clone(),
+        use_regex: request.use_regex,
+        case_sensitive: request.case_sensitive,
+        fields: request
+            .fields
+            .iter()
+            .map(|field| match field {
+                bae_ui::FindReplaceField::AlbumTitle => ReplaceField::AlbumTitle,
+                bae_ui::FindReplaceField::TrackTitle => ReplaceField::TrackTitle,
+                bae_ui::FindReplaceField::ArtistName => ReplaceField::ArtistName,
+            })
+            .collect(),
+    }
+}
+
+pub fn find_replace_row_to_display(row: &ReplacePreview) -> bae_ui::FindReplaceRow {
+    bae_ui::FindReplaceRow {
+        field: match row.field {
+            ReplaceField::AlbumTitle => bae_ui::FindReplaceField::AlbumTitle,
+            ReplaceField::TrackTitle => bae_ui::FindReplaceField::TrackTitle,
+            ReplaceField::ArtistName => bae_ui::FindReplaceField::ArtistName,
+        },
+        entity_id: row.entity_id.clone(),
+        before: row.before.clone(),
+        after: row.after.clone(),
+    }
+}
+
+pub fn find_replace_row_from_display(row: &bae_ui::FindReplaceRow) -> ReplacePreview {
+    ReplacePreview {
+        field: match row.field {
+            bae_ui::FindReplaceField::AlbumTitle => ReplaceField::AlbumTitle,
+            bae_ui::FindReplaceField::TrackTitle => ReplaceField::TrackTitle,
+            bae_ui::FindReplaceField::ArtistName => ReplaceField::ArtistName,
+        },
+        entity_id: row.entity_id.clone(),
+        before: row.before.clone(),
+        after: row.after.clone(),
+    }
+}
+
+/// Loudness starts out as measuring; it's filled in once decoding finishes
+pub fn release_comparison_from_core(
+    comparison: &compare::ReleaseComparison,
+) -> bae_ui::ReleaseComparison {
+    bae_ui::ReleaseComparison {
+        left: compared_edition_from_core(&comparison.left),
+        right: compared_edition_from_core(&comparison.right),
+        tracks: comparison
+            .tracks
+            .iter()
+            .map(|pair| bae_ui::ComparedTrack {
+                left: pair.left.as_ref().map(compared_track_side_from_db),
+                right: pair.right.as_ref().map(compared_track_side_from_db),
+            })
+            .collect(),
+    }
+}
+
+fn compared_edition_from_core(edition: &compare::EditionSummary) -> bae_ui::ComparedEdition {
+    bae_ui::ComparedEdition {
+        release_id: edition.release_id.clone(),
+        track_count: edition.track_count,
+        total_duration_ms: edition.total_duration_ms,
+        format: edition.format.clone(),
+        sample_rate: edition.sample_rate,
+        bits_per_sample: edition.bits_per_sample,
+        bitrate_kbps: edition.bitrate_kbps,
+        dynamic_range: edition.dynamic_range,
+        loudness: bae_ui::LoudnessMeasurement::Measuring,
+    }
+}
+
+fn compared_track_side_from_db(track: &DbTrack) -> bae_ui::ComparedTrackSide {
+    bae_ui::ComparedTrackSide {
+        title: track.title.clone(),
+        duration_ms: track.duration_ms,
+    }
+}
+
+pub fn loudness_to_display(
+    loudness: Option<bae_core::audio_codec::LoudnessStats>,
+) -> bae_ui::LoudnessMeasurement {
+    match loudness {
+        Some(stats) => bae_ui::LoudnessMeasurement::Measured {
+            peak_dbfs: stats.peak_dbfs,
+            rms_dbfs: stats.rms_dbfs,
+        },
+        None => bae_ui::LoudnessMeasurement::Unavailable,
+    }
+}
+
+pub fn library_snapshot_from_core(snapshot: &snapshot::LibrarySnapshot) -> bae_ui::LibrarySnapshot {
+    bae_ui::LibrarySnapshot {
+        location: snapshot.location.clone(),
+        created_at: snapshot.created_at.timestamp(),
+        size_bytes: snapshot.size_bytes,
+    }
+}
+
+pub fn service_kind_to_display(kind: health::ServiceKind) -> bae_ui::ServiceKind {
+    match kind {
+        health::ServiceKind::Database => bae_ui::ServiceKind::Database,
+        health::ServiceKind::Cache => bae_ui::ServiceKind::Cache,
+        health::ServiceKind::CloudStorage => bae_ui::ServiceKind::CloudStorage,
+        health::ServiceKind::Torrent => bae_ui::ServiceKind::Torrent,
+        health::ServiceKind::PlaybackOutput => bae_ui::ServiceKind::PlaybackOutput,
+        health::ServiceKind::Keyring => bae_ui::ServiceKind::Keyring,
+    }
+}
+
+pub fn service_kind_from_display(kind: bae_ui::ServiceKind) -> health::ServiceKind {
+    match kind {
+        bae_ui::ServiceKind::Database => health::ServiceKind::Database,
+        bae_ui::ServiceKind::Cache => health::ServiceKind::Cache,
+        bae_ui::ServiceKind::CloudStorage => health::ServiceKind::CloudStorage,
+        bae_ui::ServiceKind::Torrent => health::ServiceKind::Torrent,
+        bae_ui::ServiceKind::PlaybackOutput => health::ServiceKind::PlaybackOutput,
+        bae_ui::ServiceKind::Keyring => health::ServiceKind::Keyring,
+    }
+}
+
+pub fn service_health_to_display(
+    service: &health::ServiceHealth,
+    now: chrono::DateTime<chrono::Utc>,
+) -> bae_ui::ServiceHealth {
+    bae_ui::ServiceHealth {
+        kind: service_kind_to_display(service.kind),
+        status: match service.status {
+            health::ServiceStatus::Running => bae_ui::ServiceStatus::Running,
+            health::ServiceStatus::NotStarted => bae_ui::ServiceStatus::NotStarted,
+            health::ServiceStatus::Failed => bae_ui::ServiceStatus::Failed,
+        },
+        uptime_secs: service
+            .running_since
+            .map(|since| (now - since).num_seconds().max(0) as u64),
+        last_error: service.last_error.as_ref().map(|e| e.message.clone()),
+        last_error_at: service.last_error.as_ref().map(|e| e.at.timestamp()),
+        can_restart: service.kind.can_restart(),
+    }
+}
+
+#[cfg(feature = "torrent")]
+pub fn seeding_torrent_from_db(torrent: &db::DbTorrent) -> bae_ui::SeedingTorrent {
+    use bae_core::torrent::seeding::{seed_goal_reached, upload_ratio};
+    bae_ui::SeedingTorrent {
+        release_id: torrent.release_id.clone(),
+        name: torrent.torrent_name.clone(),
+        is_seeding: torrent.is_seeding,
+        size_bytes: torrent.total_size_bytes,
+        uploaded_bytes: torrent.uploaded_bytes,
+        ratio: upload_ratio(
+            torrent.uploaded_bytes,
+            torrent.downloaded_bytes,
+            torrent.total_size_bytes,
+        ),
+        seeding_secs: torrent.seeding_secs,
+        ratio_goal: torrent.seed_ratio_goal,
+        time_goal_hours: torrent.seed_time_goal_secs.map(|secs| (secs / 3600) as u32),
+        goal_met: seed_goal_reached(torrent),
+    }
+}
@@ -4,31 +4,43 @@
 //! These replace the methods from ImportContext.
 
 use crate::ui::app_service::AppService;
+use crate::ui::display_types::{
+    download_health_to_display, prepare_stage_from_step, release_template_from_db,
+};
 use crate::ui::Route;
 use bae_core::discogs::client::DiscogsSearchParams;
 use bae_core::discogs::{DiscogsClient, DiscogsRelease};
-use bae_core::import::cover_art::fetch_cover_art_from_archive;
+use bae_core::import::archive;
+use bae_core::import::folder_pattern::{parse_folder_patterns, FolderPattern};
+use bae_core::import::needle_drop::{
+    release_tracks_from_discogs, release_tracks_from_mb_json, ReleaseTrack, SideSplit,
+};
+use bae_core::import::stored_release::{read_stored_release, StoredRelease};
 use bae_core::import::{
     cover_art, detect_folder_contents, DetectedCandidate as CoreDetectedCandidate, ImportProgress,
-    ImportRequest, MatchCandidate, MatchSource, ScanEvent,
+    ImportRequest, ManualRelease, MatchCandidate, MatchSource, ScanEvent,
 };
+use bae_core::library::SharedLibraryManager;
 use bae_core::musicbrainz::{
     lookup_by_discid, lookup_release_by_id, search_releases_with_params, ExternalUrls, MbRelease,
     ReleaseSearchParams,
 };
 use bae_ui::display_types::{
-    AudioContentInfo, CategorizedFileInfo, FolderMetadata as DisplayFolderMetadata,
-    MatchCandidate as DisplayMatchCandidate, MatchSourceType, SearchSource, SelectedCover,
+    append_deduplicated, AudioContentInfo, CategorizedFileInfo,
+    FolderMetadata as DisplayFolderMetadata, ManualEntry, MatchCandidate as DisplayMatchCandidate,
+    MatchSourceType, NeedleDropCheck, SearchSource, SearchTab, SelectedCover, SourceResultCount,
 };
-use bae_ui::stores::import::CandidateEvent;
-use bae_ui::stores::AppStateStoreExt;
+use bae_ui::stores::import::{CandidateEvent, PrepareStage, RecentSearch, SearchField};
+#[cfg(feature = "cd-rip")]
+use bae_ui::stores::ConfigStateStoreExt;
+use bae_ui::stores::{AppState, AppStateStoreExt, ImportStateStoreExt};
 use bae_ui::ImportSource;
 use dioxus::prelude::*;
 use dioxus::router::Navigator;
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::sync::broadcast;
-use tracing::{debug, error, info, warn};
+use tracing::{error, info, warn};
 
 // ============================================================================
 // Conversion helpers (from old import_context/state.rs)
@@ -93,6 +105,7 @@ pub fn to_display_candidate(candidate: &MatchCandidate) -> DisplayMatchCandidate
         country,
         label,
         catalog_number,
+        barcode,
         original_year,
         musicbrainz_release_id,
         musicbrainz_release_group_id,
@@ -105,6 +118,7 @@ pub fn to_display_candidate(candidate: &MatchCandidate) -> DisplayMatchCandidate
             release.country.clone(),
             release.label.clone(),
             release.catalog_number.clone(),
+            release.barcode.clone(),
             release.first_release_date.clone(),
             Some(release.release_id.clone()),
             Some(release.release_group_id.clone()),
@@ -116,7 +130,11 @@ pub fn to_display_candidate(candidate: &MatchCandidate) -> DisplayMatchCandidate
             result.format.as_ref().map(|v| v.join(", ")),
             result.country.clone(),
             result.label.as_ref().map(|v| v.join(", ")),
-            None,
+            result.catno.clone(),
+            result
+                .barcode
+                .as_ref()
+                .and_then(|codes| codes.first().cloned()),
             None,
             None,
             None,
@@ -133,16 +151,58 @@ pub fn to_display_candidate(candidate: &MatchCandidate) -> DisplayMatchCandidate
         },
         year: candidate.year(),
         cover_url: candidate.cover_art_url(),
+        cover_thumbnail_url: None,
         format,
         country,
         label,
         catalog_number,
+        barcode,
         source_type,
         original_year,
         musicbrainz_release_id,
         musicbrainz_release_group_id,
         discogs_release_id,
         discogs_master_id,
+        track_titles: Vec::new(),
+    }
+}
+
+/// Candidate for the release a folder was imported as before
+fn stored_release_candidate(stored: &StoredRelease) -> DisplayMatchCandidate {
+    DisplayMatchCandidate {
+        title: stored.album.title.clone(),
+        artist: stored.artist_name.clone(),
+        year: stored
+            .release
+            .year
+            .or(stored.album.year)
+            .map(|y| y.to_string()),
+        cover_url: None,
+        cover_thumbnail_url: None,
+        format: stored.release.format.clone(),
+        country: stored.release.country.clone(),
+        label: stored.release.label.clone(),
+        catalog_number: stored.release.catalog_number.clone(),
+        barcode: stored.release.barcode.clone(),
+        source_type: MatchSourceType::Stored,
+        original_year: stored.album.year.map(|y| y.to_string()),
+        musicbrainz_release_id: stored
+            .album
+            .musicbrainz_release
+            .as_ref()
+            .map(|r| r.release_id.clone()),
+        musicbrainz_release_group_id: stored
+            .album
+            .musicbrainz_release
+            .as_ref()
+            .map(|r| r.release_group_id.clone()),
+        discogs_release_id: stored.release.discogs_release_id.clone(),
+        discogs_master_id: stored
+            .album
+            .discogs_release
+            .as_ref()
+            .map(|r| r.master_id.clone()),
+        track_titles: stored.tracks.iter().map(|t| t.title.clone()).collect(),
     }
 }
 
@@ -178,6 +238,7 @@ pub enum DiscIdLookupResult {
 /// Detect local metadata and files for a candidate before it is shown in the UI.
 pub fn detect_candidate_locally(
     candidate: &CoreDetectedCandidate,
+    patterns: &[FolderPattern],
 ) -> Result<(CategorizedFileInfo, DisplayFolderMetadata), String> {
     let files = categorized_files_from_scanned(&candidate.files);
 
@@ -186,7 +247,7 @@ pub fn detect_candidate_locally(
         candidate.name, candidate.path
     );
 
-    let folder_contents = detect_folder_contents(candidate.path.clone())
+    let folder_contents = detect_folder_contents(candidate.path.clone(), patterns)
         .map_err(|e| format!("Failed to detect folder contents: {}", e))?;
     let core_metadata = folder_contents.metadata;
 
@@ -269,6 +330,38 @@ async fn handle_discid_lookup_result(
 // Search helpers
 // ============================================================================
 
+/// One page of display-ready search results
+pub struct SearchResultsPage {
+    pub candidates: Vec<DisplayMatchCandidate>,
+    /// Result count per source that was queried
+    pub result_counts: Vec<SourceResultCount>,
+}
+
+/// Zero-based page index to fetch from each source.
+///
+/// Sources are paged separately: in combined mode one source can fail or run
+/// out of results while the other keeps going.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SearchPages {
+    pub musicbrainz: u32,
+    pub discogs: u32,
+}
+
+impl SearchPages {
+    /// The next page of each source after the ones already loaded
+    fn after(result_counts: &[SourceResultCount]) -> SearchPages {
+        let mut pages = SearchPages::default();
+        for count in result_counts {
+            match count.source {
+                MatchSourceType::MusicBrainz => pages.musicbrainz = count.pages_loaded,
+                MatchSourceType::Discogs => pages.discogs = count.pages_loaded,
+                MatchSourceType::Manual | MatchSourceType::Stored => {}
+            }
+        }
+        pages
+    }
+}
+
 fn non_empty(s: String) -> Option<String> {
     if s.trim().is_empty() {
         None
@@ -281,11 +374,19 @@ fn non_empty(s: String) -> Option<String> {
 async fn search_mb_and_rank(
     params: ReleaseSearchParams,
     metadata: Option<bae_core::import::FolderMetadata>,
-) -> Result<Vec<DisplayMatchCandidate>, String> {
-    match search_releases_with_params(&params).await {
-        Ok(releases) => {
-            info!("✓ MusicBrainz search returned {} result(s)", releases.len());
-            let mut candidates = if let Some(ref meta) = metadata {
+    page: u32,
+) -> Result<SearchResultsPage, String> {
+    match search_releases_with_params(&params, page).await {
+        Ok(search_page) => {
+            let total = search_page.total;
+            let has_more = search_page.has_more();
+            let releases = search_page.releases;
+            info!(
+                "✓ MusicBrainz search returned {} result(s) of {}",
+                releases.len(),
+                total
+            );
+            let candidates = if let Some(ref meta) = metadata {
                 use bae_core::import::rank_mb_matches;
                 rank_mb_matches(meta, releases)
             } else {
@@ -300,31 +401,16 @@ async fn search_mb_and_rank(
                     .collect()
             };
 
-            let cover_art_futures: Vec<_> = candidates
-                .iter()
-                .map(|candidate| {
-                    let release_id = match &candidate.source {
-                        MatchSource::MusicBrainz(release) => release.release_id.clone(),
-                        _ => String::new(),
-                    };
-                    async move {
-                        if !release_id.is_empty() {
-                            debug!("Fetching cover art for release {}", release_id);
-                            fetch_cover_art_from_archive(&release_id).await
-                        } else {
-                            None
-                        }
-                    }
-                })
-                .collect();
-            let cover_art_results = futures::future::join_all(cover_art_futures).await;
-            for (candidate, cover_url) in candidates.iter_mut().zip(cover_art_results.into_iter()) {
-                if candidate.cover_art_url.is_none() {
-                    candidate.cover_art_url = cover_url;
-                }
-            }
-
-            Ok(candidates.iter().map(to_display_candidate).collect())
+            // Covers are fetched once the list is on screen, see CoverFetcher
+            Ok(SearchResultsPage {
+                candidates: candidates.iter().map(to_display_candidate).collect(),
+                result_counts: vec![SourceResultCount {
+                    source: MatchSourceType::MusicBrainz,
+                    total,
+                    has_more,
+                    pages_loaded: page + 1,
+                }],
+            })
         }
         Err(e) => {
             warn!("✗ MusicBrainz search failed: {}", e);
@@ -338,10 +424,18 @@ async fn search_discogs_and_rank(
     client: &DiscogsClient,
     params: DiscogsSearchParams,
     metadata: Option<bae_core::import::FolderMetadata>,
-) -> Result<Vec<DisplayMatchCandidate>, String> {
-    match client.search_with_params(&params).await {
-        Ok(results) => {
-            info!("✓ Discogs search returned {} result(s)", results.len());
+    page: u32,
+) -> Result<SearchResultsPage, String> {
+    match client.search_with_params(&params, page).await {
+        Ok(search_page) => {
+            let total = search_page.total;
+            let has_more = search_page.has_more();
+            let results = search_page.results;
+            info!(
+                "✓ Discogs search returned {} result(s) of {}",
+                results.len(),
+                total
+            );
             let candidates: Vec<MatchCandidate> = if let Some(ref meta) = metadata {
                 use bae_core::import::rank_discogs_matches;
                 rank_discogs_matches(meta, results)
@@ -357,7 +451,15 @@ async fn search_discogs_and_rank(
                     .collect()
             };
 
-            Ok(candidates.iter().map(to_display_candidate).collect())
+            Ok(SearchResultsPage {
+                candidates: candidates.iter().map(to_display_candidate).collect(),
+                result_counts: vec![SourceResultCount {
+                    source: MatchSourceType::Discogs,
+                    total,
+                    has_more,
+                    pages_loaded: page + 1,
+                }],
+            })
         }
         Err(e) => {
             warn!("✗ Discogs search failed: {}", e);
@@ -366,87 +468,156 @@ async fn search_discogs_and_rank(
     }
 }
 
-/// General search by artist, album, year, label
-pub async fn search_general(
+/// Run a search against the selected source(s).
+///
+/// In `Both` mode MusicBrainz and Discogs are queried in parallel. If only one
+/// of them fails, the other's results are still returned.
+async fn search_sources(
     metadata: Option<DisplayFolderMetadata>,
     source: SearchSource,
-    artist: String,
-    album: String,
-    year: String,
-    label: String,
-) -> Result<Vec<DisplayMatchCandidate>, String> {
+    mb_params: ReleaseSearchParams,
+    discogs_params: DiscogsSearchParams,
+    pages: SearchPages,
+) -> Result<SearchResultsPage, String> {
     let core_metadata = metadata.as_ref().map(from_display_metadata);
     match source {
         SearchSource::MusicBrainz => {
-            let params = ReleaseSearchParams {
-                artist: non_empty(artist),
-                album: non_empty(album),
-                year: non_empty(year),
-                label: non_empty(label),
-                catalog_number: None,
-                barcode: None,
-                format: None,
-                country: None,
-            };
-            info!("🎵 MusicBrainz general search: {:?}", params);
-            search_mb_and_rank(params, core_metadata).await
+            info!("🎵 MusicBrainz search: {:?}", mb_params);
+            search_mb_and_rank(mb_params, core_metadata, pages.musicbrainz).await
         }
         SearchSource::Discogs => {
             let client = get_discogs_client()?;
-            let params = DiscogsSearchParams {
-                artist: non_empty(artist),
-                release_title: non_empty(album),
-                year: non_empty(year),
-                label: non_empty(label),
-                catno: None,
-                barcode: None,
-                format: None,
-                country: None,
+            info!("🔍 Discogs search: {:?}", discogs_params);
+            search_discogs_and_rank(&client, discogs_params, core_metadata, pages.discogs).await
+        }
+        SearchSource::Both => {
+            info!(
+                "🔎 Combined search: MusicBrainz {:?}, Discogs {:?}",
+                mb_params, discogs_params
+            );
+            let mb_search = search_mb_and_rank(mb_params, core_metadata.clone(), pages.musicbrainz);
+            let discogs_search = async {
+                let client = get_discogs_client()?;
+                search_discogs_and_rank(
+                    &client,
+                    discogs_params,
+                    core_metadata.clone(),
+                    pages.discogs,
+                )
+                .await
             };
-            info!("🔍 Discogs general search: {:?}", params);
-            search_discogs_and_rank(&client, params, core_metadata).await
+            let (mb_result, discogs_result) = futures::join!(mb_search, discogs_search);
+            combine_search_pages(mb_result, discogs_result)
         }
     }
 }
 
+/// Merge MusicBrainz and Discogs pages for `Both` mode.
+///
+/// Results are interleaved so each source's best-ranked candidates stay near
+/// the top, then releases already returned by the other source are dropped.
+fn combine_search_pages(
+    mb_result: Result<SearchResultsPage, String>,
+    discogs_result: Result<SearchResultsPage, String>,
+) -> Result<SearchResultsPage, String> {
+    match (mb_result, discogs_result) {
+        (Ok(mb_page), Ok(discogs_page)) => {
+            let mut interleaved = Vec::new();
+            let mut mb_iter = mb_page.candidates.into_iter();
+            let mut discogs_iter = discogs_page.candidates.into_iter();
+            loop {
+                let mb_next = mb_iter.next();
+                let discogs_next = discogs_iter.next();
+                if mb_next.is_none() && discogs_next.is_none() {
+                    break;
+                }
+                interleaved.extend(mb_next);
+                interleaved.extend(discogs_next);
+            }
+
+            let mut candidates = Vec::new();
+            append_deduplicated(&mut candidates, interleaved);
+
+            Ok(SearchResultsPage {
+                candidates,
+                result_counts: mb_page
+                    .result_counts
+                    .into_iter()
+                    .chain(discogs_page.result_counts)
+                    .collect(),
+            })
+        }
+        (Ok(page), Err(e)) | (Err(e), Ok(page)) => {
+            warn!("Combined search: one source failed, using the other: {}", e);
+            Ok(page)
+        }
+        (Err(mb_err), Err(discogs_err)) => Err(format!("{}; {}", mb_err, discogs_err)),
+    }
+}
+
+/// General search by artist, album, year, label
+///
+/// Pass `SearchPages::default()` for a fresh search.
+pub async fn search_general(
+    metadata: Option<DisplayFolderMetadata>,
+    source: SearchSource,
+    artist: String,
+    album: String,
+    year: String,
+    label: String,
+    pages: SearchPages,
+) -> Result<SearchResultsPage, String> {
+    let mb_params = ReleaseSearchParams {
+        artist: non_empty(artist.clone()),
+        album: non_empty(album.clone()),
+        year: non_empty(year.clone()),
+        label: non_empty(label.clone()),
+        catalog_number: None,
+        barcode: None,
+        format: None,
+        country: None,
+    };
+    let discogs_params = DiscogsSearchParams {
+        artist: non_empty(artist),
+        release_title: non_empty(album),
+        year: non_empty(year),
+        label: non_empty(label),
+        catno: None,
+        barcode: None,
+        format: None,
+        country: None,
+    };
+    search_sources(metadata, source, mb_params, discogs_params, pages).await
+}
+
 /// Search by catalog number only
 pub async fn search_by_catalog_number(
     metadata: Option<DisplayFolderMetadata>,
     source: SearchSource,
     catalog_number: String,
-) -> Result<Vec<DisplayMatchCandidate>, String> {
-    let core_metadata = metadata.as_ref().map(from_display_metadata);
-    match source {
-        SearchSource::MusicBrainz => {
-            let params = ReleaseSearchParams {
-                artist: None,
-                album: None,
-                year: None,
-                label: None,
-                catalog_number: Some(catalog_number),
-                barcode: None,
-                format: None,
-                country: None,
-            };
-            info!("🎵 MusicBrainz catalog number search: {:?}", params);
-            search_mb_and_rank(params, core_metadata).await
-        }
-        SearchSource::Discogs => {
-            let client = get_discogs_client()?;
-            let params = DiscogsSearchParams {
-                artist: None,
-                release_title: None,
-                year: None,
-                label: None,
-                catno: Some(catalog_number),
-                barcode: None,
-                format: None,
-                country: None,
-            };
-            info!("🔍 Discogs catalog number search: {:?}", params);
-            search_discogs_and_rank(&client, params, core_metadata).await
-        }
-    }
+    pages: SearchPages,
+) -> Result<SearchResultsPage, String> {
+    let mb_params = ReleaseSearchParams {
+        artist: None,
+        album: None,
+        year: None,
+        label: None,
+        catalog_number: Some(catalog_number.clone()),
+        barcode: None,
+        format: None,
+        country: None,
+    };
+    let discogs_params = DiscogsSearchParams {
+        artist: None,
+        release_title: None,
+        year: None,
+        label: None,
+        catno: Some(catalog_number),
+        barcode: None,
+        format: None,
+        country: None,
+    };
+    search_sources(metadata, source, mb_params, discogs_params, pages).await
 }
 
 /// Search by barcode only
@@ -454,39 +625,290 @@ pub async fn search_by_barcode(
     metadata: Option<DisplayFolderMetadata>,
     source: SearchSource,
     barcode: String,
-) -> Result<Vec<DisplayMatchCandidate>, String> {
-    let core_metadata = metadata.as_ref().map(from_display_metadata);
+    pages: SearchPages,
+) -> Result<SearchResultsPage, String> {
+    let mb_params = ReleaseSearchParams {
+        artist: None,
+        album: None,
+        year: None,
+        label: None,
+        catalog_number: None,
+        barcode: Some(barcode.clone()),
+        format: None,
+        country: None,
+    };
+    let discogs_params = DiscogsSearchParams {
+        artist: None,
+        release_title: None,
+        year: None,
+        label: None,
+        catno: None,
+        barcode: Some(barcode),
+        format: None,
+        country: None,
+    };
+    search_sources(metadata, source, mb_params, discogs_params, pages).await
+}
+
+/// Fetch the next page for the current candidate's manual search and append it.
+///
+/// Re-runs the query the last search ran with, at the next page index. Results
+/// are dispatched to the candidate that was current when loading started.
+pub async fn load_more_search_results(app: &AppService) {
+    let mut import_store = app.state.import();
+    let Some(key) = import_store.read().current_candidate_key.clone() else {
+        return;
+    };
+    let search_state = import_store.read().get_search_state();
+    let metadata = import_store.read().get_metadata();
+
+    let Some(search_state) = search_state else {
+        return;
+    };
+    if search_state.is_loading_more || !search_state.has_more_results() {
+        return;
+    }
+    // Continue the query that produced the results, not what the form says now
+    let Some(query) = search_state.executed_query.clone() else {
+        return;
+    };
+
+    import_store
+        .write()
+        .dispatch_to_candidate(&key, CandidateEvent::StartLoadMore);
+
+    let pages = SearchPages::after(&search_state.result_counts);
+    // In combined mode, stop querying a source once it has run out of pages
+    let source = match query.source {
+        SearchSource::Both => {
+            let remaining: Vec<_> = search_state
+                .result_counts
+                .iter()
+                .filter(|c| c.has_more)
+                .map(|c| c.source)
+                .collect();
+            match remaining.as_slice() {
+                [MatchSourceType::MusicBrainz] => SearchSource::MusicBrainz,
+                [MatchSourceType::Discogs] => SearchSource::Discogs,
+                _ => SearchSource::Both,
+            }
+        }
+        source => source,
+    };
+    info!("Loading search results {:?} from {:?}", pages, source);
+
+    let result = match query.tab {
+        SearchTab::General => {
+            search_general(
+                metadata,
+                source,
+                query.artist,
+                query.album,
+                query.year,
+                query.label,
+                pages,
+            )
+            .await
+        }
+        SearchTab::CatalogNumber => {
+            search_by_catalog_number(metadata, source, query.catalog_number, pages).await
+        }
+        SearchTab::Barcode => search_by_barcode(metadata, source, query.barcode, pages).await,
+    };
+
+    let event = match result {
+        Ok(page) => CandidateEvent::LoadMoreComplete {
+            results: page.candidates,
+            result_counts: page.result_counts,
+            error: None,
+        },
+        Err(e) => CandidateEvent::LoadMoreComplete {
+            results: vec![],
+            result_counts: vec![],
+            error: Some(format!("Search failed: {}", e)),
+        },
+    };
+    import_store.write().dispatch_to_candidate(&key, event);
+}
+
+// ============================================================================
+// Search history
+// ============================================================================
+
+fn search_source_key(source: SearchSource) -> &'static str {
     match source {
-        SearchSource::MusicBrainz => {
-            let params = ReleaseSearchParams {
-                artist: None,
-                album: None,
-                year: None,
-                label: None,
-                catalog_number: None,
-                barcode: Some(barcode),
-                format: None,
-                country: None,
-            };
-            info!("🎵 MusicBrainz barcode search: {:?}", params);
-            search_mb_and_rank(params, core_metadata).await
+        SearchSource::MusicBrainz => "musicbrainz",
+        SearchSource::Discogs => "discogs",
+        SearchSource::Both => "both",
+    }
+}
+
+fn search_source_from_key(key: &str) -> Option<SearchSource> {
+    match key {
+        "musicbrainz" => Some(SearchSource::MusicBrainz),
+        "discogs" => Some(SearchSource::Discogs),
+        "both" => Some(SearchSource::Both),
+        _ => None,
+    }
+}
+
+fn search_field_key(field: SearchField) -> &'static str {
+    match field {
+        SearchField::Artist => "artist",
+        SearchField::Album => "album",
+        SearchField::Year => "year",
+        SearchField::Label => "label",
+        SearchField::CatalogNumber => "catalog_number",
+        SearchField::Barcode => "barcode",
+    }
+}
+
+fn search_field_from_key(key: &str) -> Option<SearchField> {
+    match key {
+        "artist" => Some(SearchField::Artist),
+        "album" => Some(SearchField::Album),
+        "year" => Some(SearchField::Year),
+        "label" => Some(SearchField::Label),
+        "catalog_number" => Some(SearchField::CatalogNumber),
+        "barcode" => Some(SearchField::Barcode),
+        _ => None,
+    }
+}
+
+/// Load remembered manual search values from the database into the Store
+pub async fn load_search_history(state: &Store<AppState>, library_manager: &SharedLibraryManager) {
+    match library_manager.get().get_search_history().await {
+        Ok(entries) => {
+            let recent: Vec<RecentSearch> = entries
+                .into_iter()
+                .filter_map(|entry| {
+                    Some(RecentSearch {
+                        source: search_source_from_key(&entry.source)?,
+                        field: search_field_from_key(&entry.field)?,
+                        value: entry.value,
+                    })
+                })
+                .collect();
+            state.import().recent_searches().set(recent);
         }
-        SearchSource::Discogs => {
-            let client = get_discogs_client()?;
-            let params = DiscogsSearchParams {
-                artist: None,
-                release_title: None,
-                year: None,
-                label: None,
-                catno: None,
-                barcode: Some(barcode),
-                format: None,
-                country: None,
-            };
-            info!("🔍 Discogs barcode search: {:?}", params);
-            search_discogs_and_rank(&client, params, core_metadata).await
+        Err(e) => {
+            warn!("Failed to load search history: {}", e);
+        }
+    }
+}
+
+/// Remember the values of the current manual search so later imports can
+/// suggest them. Only fields on the active tab are recorded.
+pub fn remember_current_search(app: &AppService) {
+    let Some(search_state) = app.state.import().read().get_search_state() else {
+        return;
+    };
+
+    let source = search_state.search_source;
+    let values: Vec<(SearchField, String)> = match search_state.search_tab {
+        SearchTab::General => vec![
+            (SearchField::Artist, search_state.search_artist),
+            (SearchField::Album, search_state.search_album),
+        ],
+        SearchTab::CatalogNumber => vec![(
+            SearchField::CatalogNumber,
+            search_state.search_catalog_number,
+        )],
+        SearchTab::Barcode => vec![(SearchField::Barcode, search_state.search_barcode)],
+    }
+    .into_iter()
+    .map(|(field, value)| (field, value.trim().to_string()))
+    .filter(|(_, value)| !value.is_empty())
+    .collect();
+    if values.is_empty() {
+        return;
+    }
+
+    {
+        let mut import_store = app.state.import();
+        let mut import_state = import_store.write();
+        for (field, value) in &values {
+            import_state.remember_search(source, *field, value);
         }
     }
+
+    let library_manager = app.library_manager.clone();
+    spawn(async move {
+        for (field, value) in values {
+            if let Err(e) = library_manager
+                .get()
+                .record_search_value(search_source_key(source), search_field_key(field), &value)
+                .await
+            {
+                warn!("Failed to save search history: {}", e);
+            }
+        }
+    });
+}
+
+/// Load saved series templates from the database into the Store
+pub async fn load_release_templates(
+    state: &Store<AppState>,
+    library_manager: &SharedLibraryManager,
+) {
+    match library_manager.get().get_release_templates().await {
+        Ok(templates) => {
+            state
+                .import()
+                .release_templates()
+                .set(templates.iter().map(release_template_from_db).collect());
+        }
+        Err(e) => {
+            warn!("Failed to load release templates: {}", e);
+        }
+    }
+}
+
+/// Save a manual entry's artist, title and cover as a series template
+pub fn save_release_template(app: &AppService, entry: ManualEntry) {
+    let state = app.state;
+    let library_manager = app.library_manager.clone();
+    spawn(async move {
+        if let Err(e) = library_manager
+            .get()
+            .save_release_template(&entry.artist, &entry.title, entry.cover_url)
+            .await
+        {
+            warn!("Failed to save release template: {}", e);
+            return;
+        }
+        load_release_templates(&state, &library_manager).await;
+    });
+}
+
+pub fn delete_release_template(app: &AppService, id: String) {
+    let state = app.state;
+    let library_manager = app.library_manager.clone();
+    spawn(async move {
+        if let Err(e) = library_manager.get().delete_release_template(&id).await {
+            warn!("Failed to delete release template: {}", e);
+            return;
+        }
+        load_release_templates(&state, &library_manager).await;
+    });
+}
+
+/// Count up the series template a manually entered release came from, so
+/// the next entry starts at the following volume
+fn advance_release_templates(app: &AppService, artist: String, title: String) {
+    let state = app.state;
+    let library_manager = app.library_manager.clone();
+    spawn(async move {
+        match library_manager
+            .get()
+            .advance_release_templates(&artist, &title)
+            .await
+        {
+            Ok(true) => load_release_templates(&state, &library_manager).await,
+            Ok(false) => {}
+            Err(e) => warn!("Failed to advance release template: {}", e),
+        }
+    });
 }
 
 // ============================================================================
@@ -509,6 +931,36 @@ async fn fetch_discogs_release(
     }
 }
 
+/// Tracks of a match candidate's release, for splitting vinyl side recordings
+pub async fn fetch_release_tracks(
+    candidate: &DisplayMatchCandidate,
+) -> Result<Vec<ReleaseTrack>, String> {
+    match candidate.source_type {
+        MatchSourceType::Discogs => {
+            let release_id = candidate
+                .discogs_release_id
+                .as_ref()
+                .ok_or_else(|| "Missing Discogs release ID".to_string())?;
+            let master_id = candidate.discogs_master_id.as_deref().unwrap_or_default();
+            let release = fetch_discogs_release(release_id, master_id).await?;
+            Ok(release_tracks_from_discogs(&release))
+        }
+        MatchSourceType::MusicBrainz => {
+            let release_id = candidate
+                .musicbrainz_release_id
+                .as_ref()
+                .ok_or_else(|| "Missing MusicBrainz release ID".to_string())?;
+            let (_mb_release, _external_urls, raw) = lookup_release_by_id(release_id)
+                .await
+                .map_err(|e| format!("Failed to fetch MusicBrainz release: {}", e))?;
+            Ok(release_tracks_from_mb_json(&raw))
+        }
+        MatchSourceType::Manual | MatchSourceType::Stored => {
+            Err("Sides can only be split for releases from MusicBrainz or Discogs".to_string())
+        }
+    }
+}
+
 /// Confirm a match candidate and start the import workflow.
 pub async fn confirm_and_start_import(
     app: &AppService,
@@ -572,17 +1024,48 @@ pub async fn confirm_and_start_import(
                 return Err("Duplicate album found".to_string());
             }
         }
+        // Nothing to compare against without database IDs
+        MatchSourceType::Manual => {}
+        // The import checks its release isn't in the library already
+        MatchSourceType::Stored => {}
     }
 
     // Get state from store
-    let (storage_profile_id, metadata, selected_cover) = {
+    let (storage_profile_id, metadata, selected_cover, excluded_files, needle_drop_sides) = {
         let state = import_store.read();
+        let excluded_files: Vec<PathBuf> = state
+            .duplicate_audio_checks
+            .get(&candidate_key)
+            .map(|check| check.excluded_files())
+            .unwrap_or_default()
+            .into_iter()
+            .map(PathBuf::from)
+            .collect();
+        let needle_drop_sides: Vec<SideSplit> = match state.needle_drop_checks.get(&candidate_key) {
+            Some(NeedleDropCheck::Ready(sides)) => sides
+                .iter()
+                .map(|side| SideSplit {
+                    path: PathBuf::from(&side.path),
+                    track_starts_ms: side.tracks.iter().map(|t| t.start_ms).collect(),
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
         (
             state.get_storage_profile_id(),
             state.get_metadata(),
             state.get_selected_cover(),
+            excluded_files,
+            needle_drop_sides,
         )
     };
+    // Extractions are deleted once stored, so there'd be nothing to play in place
+    if import_source == ImportSource::Archive && storage_profile_id.is_none() {
+        import_store.write().dispatch(CandidateEvent::ImportFailed(
+            "Releases from archives need a storage profile".to_string(),
+        ));
+        return Err("No storage profile for archive import".to_string());
+    }
     let master_year = metadata.as_ref().and_then(|m| m.year).unwrap_or(1970);
 
     let (cover_art_url, selected_cover_filename) = match selected_cover {
@@ -594,86 +1077,235 @@ pub async fn confirm_and_start_import(
         None => (None, None),
     };
 
-    let request = match import_source {
-        ImportSource::Folder => match candidate.source_type {
-            MatchSourceType::Discogs => {
-                let release_id = candidate
-                    .discogs_release_id
-                    .as_ref()
-                    .ok_or_else(|| "Missing Discogs release ID".to_string())?;
-                let master_id = candidate
-                    .discogs_master_id
-                    .as_ref()
-                    .ok_or_else(|| "Discogs result has no master_id".to_string())?;
-
-                let discogs_release = fetch_discogs_release(release_id, master_id).await?;
-
-                ImportRequest::Folder {
-                    import_id: import_id.clone(),
-                    discogs_release: Some(discogs_release),
-                    mb_release: None,
-                    folder: PathBuf::from(&candidate_key),
-                    master_year,
-                    cover_art_url: cover_art_url.clone(),
-                    storage_profile_id: storage_profile_id.clone(),
-                    selected_cover_filename: selected_cover_filename.clone(),
+    import_store
+        .write()
+        .dispatch(CandidateEvent::ImportPreparing(
+            PrepareStage::FetchingRelease,
+        ));
+    let request: Result<ImportRequest, String> = async {
+        Ok(match import_source {
+            ImportSource::Folder | ImportSource::Archive => match candidate.source_type {
+                MatchSourceType::Discogs => {
+                    let release_id = candidate
+                        .discogs_release_id
+                        .as_ref()
+                        .ok_or_else(|| "Missing Discogs release ID".to_string())?;
+                    let master_id = candidate
+                        .discogs_master_id
+                        .as_ref()
+                        .ok_or_else(|| "Discogs result has no master_id".to_string())?;
+
+                    let discogs_release = fetch_discogs_release(release_id, master_id).await?;
+
+                    ImportRequest::Folder {
+                        import_id: import_id.clone(),
+                        discogs_release: Some(discogs_release),
+                        mb_release: None,
+                        manual_release: None,
+                        folder: PathBuf::from(&candidate_key),
+                        master_year,
+                        cover_art_url: cover_art_url.clone(),
+                        storage_profile_id: storage_profile_id.clone(),
+                        selected_cover_filename: selected_cover_filename.clone(),
+                        excluded_files: excluded_files.clone(),
+                        needle_drop_sides: needle_drop_sides.clone(),
+                    }
                 }
-            }
-            MatchSourceType::MusicBrainz => {
-                let release_id = candidate
-                    .musicbrainz_release_id
-                    .as_ref()
-                    .ok_or_else(|| "Missing MusicBrainz release ID".to_string())?;
-
-                info!(
-                    "Starting import for MusicBrainz release: {}",
-                    candidate.title
-                );
+                MatchSourceType::MusicBrainz => {
+                    let release_id = candidate
+                        .musicbrainz_release_id
+                        .as_ref()
+                        .ok_or_else(|| "Missing MusicBrainz release ID".to_string())?;
+
+                    info!(
+                        "Starting import for MusicBrainz release: {}",
+                        candidate.title
+                    );
+
+                    let (mb_release, _external_urls, _raw) = lookup_release_by_id(release_id)
+                        .await
+                        .map_err(|e| format!("Failed to fetch MusicBrainz release: {}", e))?;
+
+                    ImportRequest::Folder {
+                        import_id: import_id.clone(),
+                        discogs_release: None,
+                        mb_release: Some(mb_release),
+                        manual_release: None,
+                        folder: PathBuf::from(&candidate_key),
+                        master_year,
+                        cover_art_url: cover_art_url.clone(),
+                        storage_profile_id: storage_profile_id.clone(),
+                        selected_cover_filename: selected_cover_filename.clone(),
+                        excluded_files: excluded_files.clone(),
+                        needle_drop_sides: needle_drop_sides.clone(),
+                    }
+                }
+                MatchSourceType::Manual => {
+                    info!(
+                        "Starting import for manually entered release: {}",
+                        candidate.title
+                    );
+
+                    let manual_release = ManualRelease {
+                        title: candidate.title.clone(),
+                        artist: candidate.artist.clone(),
+                        year: candidate
+                            .year
+                            .as_deref()
+                            .and_then(|y| y.trim().parse().ok()),
+                        tracks: candidate.track_titles.clone(),
+                    };
 
-                let (mb_release, _external_urls, _raw) = lookup_release_by_id(release_id)
-                    .await
-                    .map_err(|e| format!("Failed to fetch MusicBrainz release: {}", e))?;
+                    ImportRequest::Folder {
+                        import_id: import_id.clone(),
+                        discogs_release: None,
+                        mb_release: None,
+                        manual_release: Some(manual_release),
+                        folder: PathBuf::from(&candidate_key),
+                        master_year,
+                        cover_art_url: cover_art_url.clone(),
+                        storage_profile_id: storage_profile_id.clone(),
+                        selected_cover_filename: selected_cover_filename.clone(),
+                        excluded_files: excluded_files.clone(),
+                        needle_drop_sides: needle_drop_sides.clone(),
+                    }
+                }
+                MatchSourceType::Stored => {
+                    info!("Importing {} again from its stored metadata", candidate_key);
+
+                    ImportRequest::Replay {
+                        import_id: import_id.clone(),
+                        folder: PathBuf::from(&candidate_key),
+                        storage_profile_id: storage_profile_id.clone(),
+                        selected_cover_filename: selected_cover_filename.clone(),
+                    }
+                }
+            },
+            #[cfg(feature = "cd-rip")]
+            ImportSource::Cd => {
+                let (discogs_release, mb_release, manual_release) = match candidate.source_type {
+                    MatchSourceType::Discogs => {
+                        let release_id = candidate
+                            .discogs_release_id
+                            .as_ref()
+                            .ok_or_else(|| "Missing Discogs release ID".to_string())?;
+                        let master_id = candidate
+                            .discogs_master_id
+                            .as_ref()
+                            .ok_or_else(|| "Discogs result has no master_id".to_string())?;
+                        (
+                            Some(fetch_discogs_release(release_id, master_id).await?),
+                            None,
+                            None,
+                        )
+                    }
+                    MatchSourceType::MusicBrainz => {
+                        let release_id = candidate
+                            .musicbrainz_release_id
+                            .as_ref()
+                            .ok_or_else(|| "Missing MusicBrainz release ID".to_string())?;
+                        let (mb_release, _external_urls, _raw) = lookup_release_by_id(release_id)
+                            .await
+                            .map_err(|e| format!("Failed to fetch MusicBrainz release: {}", e))?;
+                        (None, Some(mb_release), None)
+                    }
+                    // Ripped under placeholder metadata; the disc is identified
+                    // once its DiscID can be looked up
+                    MatchSourceType::Manual => {
+                        info!("Ripping unidentified disc: {}", candidate.title);
+                        let manual_release = ManualRelease {
+                            title: candidate.title.clone(),
+                            artist: candidate.artist.clone(),
+                            year: None,
+                            tracks: candidate.track_titles.clone(),
+                        };
+                        (None, None, Some(manual_release))
+                    }
+                    MatchSourceType::Stored => {
+                        return Err("CDs need a MusicBrainz or Discogs release".to_string());
+                    }
+                };
 
-                ImportRequest::Folder {
+                ImportRequest::CD {
                     import_id: import_id.clone(),
-                    discogs_release: None,
-                    mb_release: Some(mb_release),
-                    folder: PathBuf::from(&candidate_key),
+                    discogs_release,
+                    mb_release,
+                    manual_release,
+                    drive_path: PathBuf::from(&candidate_key),
                     master_year,
                     cover_art_url: cover_art_url.clone(),
                     storage_profile_id: storage_profile_id.clone(),
                     selected_cover_filename: selected_cover_filename.clone(),
+                    eject_when_ripped: *app.state.config().cd_eject_after_rip().read(),
                 }
             }
-        },
-        _ => return Err("This import source is not yet supported".to_string()),
+            _ => return Err("This import source is not yet supported".to_string()),
+        })
+    }
+    .await;
+    let request = match request {
+        Ok(request) => request,
+        Err(e) => {
+            import_store
+                .write()
+                .dispatch(CandidateEvent::ImportFailed(e.clone()));
+            return Err(e);
+        }
     };
 
     let import_handle = app.import_handle.clone();
-    match import_handle.send_request(request).await {
+    // Subscribed before sending, so the preparation steps reach the checklist
+    let mut progress_rx = import_handle
+        .progress_handle
+        .subscribe_import(import_id.clone());
+    let send = import_handle.send_request(request);
+    tokio::pin!(send);
+    let sent = loop {
+        tokio::select! {
+            sent = &mut send => break sent,
+            Some(event) = progress_rx.recv() => show_prepare_step(app, &candidate_key, event),
+        }
+    };
+    // Steps sent just before the request returned
+    while let Ok(event) = progress_rx.try_recv() {
+        show_prepare_step(app, &candidate_key, event);
+    }
+    match sent {
         Ok((album_id, _release_id)) => {
             info!("Import started successfully: {}", album_id);
-            import_store.write().dispatch(CandidateEvent::ImportStarted);
+            {
+                let mut state = import_store.write();
+                state.dispatch(CandidateEvent::ImportStarted);
+                state
+                    .candidate_imports
+                    .insert(candidate_key.clone(), import_id.clone());
+            }
+            if candidate.source_type == MatchSourceType::Manual {
+                advance_release_templates(app, candidate.artist.clone(), candidate.title.clone());
+            }
 
             // Spawn a task to listen for import completion
-            let progress_handle = import_handle.progress_handle.clone();
             let mut import_store_clone = app.state.import();
             spawn(async move {
-                let mut progress_rx = progress_handle.subscribe_import(import_id.clone());
                 while let Some(event) = progress_rx.recv().await {
                     match event {
+                        ImportProgress::Downloading { health, .. } => {
+                            import_store_clone.write().torrent_download =
+                                Some(download_health_to_display(&health));
+                        }
                         ImportProgress::Complete { .. } => {
                             info!("Import completed for candidate: {}", candidate_key);
-                            import_store_clone.write().dispatch_to_candidate(
-                                &candidate_key,
-                                CandidateEvent::ImportComplete,
-                            );
+                            let mut state = import_store_clone.write();
+                            state.torrent_download = None;
+                            state.dispatch_to_import(&import_id, CandidateEvent::ImportComplete);
                             break;
                         }
                         ImportProgress::Failed { error, .. } => {
                             warn!("Import failed for candidate {}: {}", candidate_key, error);
-                            import_store_clone.write().dispatch_to_candidate(
-                                &candidate_key,
+                            let mut state = import_store_clone.write();
+                            state.torrent_download = None;
+                            state.dispatch_to_import(
+                                &import_id,
                                 CandidateEvent::ImportFailed(error),
                             );
                             break;
@@ -716,6 +1348,7 @@ pub async fn confirm_and_start_import(
                 navigator.push(Route::AlbumDetail {
                     album_id,
                     release_id: String::new(),
+                    track_id: String::new(),
                 });
                 Ok(())
             }
@@ -731,6 +1364,16 @@ pub async fn confirm_and_start_import(
     }
 }
 
+/// Move a candidate's preparation checklist on to the stage a step belongs to
+fn show_prepare_step(app: &AppService, candidate_key: &str, event: ImportProgress) {
+    if let ImportProgress::Preparing { step, .. } = event {
+        app.state.import().write().dispatch_to_candidate(
+            candidate_key,
+            CandidateEvent::ImportPreparing(prepare_stage_from_step(step)),
+        );
+    }
+}
+
 /// Load a selected release by index, performing DiscID lookup if needed
 pub async fn load_selected_release(
     app: &AppService,
@@ -765,6 +1408,21 @@ pub async fn load_selected_release(
         .discid_lookup_attempted
         .insert(release_path.clone());
 
+    // A folder imported before knows its release already
+    match read_stored_release(Path::new(&release_path)) {
+        Ok(Some(stored)) => {
+            info!("Found stored release metadata in {}", release_path);
+            import_store
+                .write()
+                .dispatch(CandidateEvent::StoredReleaseFound(
+                    stored_release_candidate(&stored),
+                ));
+            return Ok(());
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Ignoring stored release metadata: {}", e),
+    }
+
     let mb_discid = import_store
         .read()
         .get_metadata()
@@ -844,6 +1502,7 @@ pub async fn consume_scan_events(app: AppService, mut rx: broadcast::Receiver<Sc
         };
 
         let mut first_selected_index = None;
+        let patterns = parse_folder_patterns(&app.state.config().folder_patterns().read());
 
         loop {
             match rx.recv().await {
@@ -853,7 +1512,7 @@ pub async fn consume_scan_events(app: AppService, mut rx: broadcast::Receiver<Sc
                         continue;
                     }
 
-                    let (files, metadata) = match detect_candidate_locally(&candidate) {
+                    let (files, metadata) = match detect_candidate_locally(&candidate, &patterns) {
                         Ok(result) => result,
                         Err(e) => {
                             warn!(
@@ -914,6 +1573,65 @@ pub async fn consume_scan_events(app: AppService, mut rx: broadcast::Receiver<Sc
     }
 }
 
+/// Ask for a folder to scan, or an archive when importing from one, and queue
+/// the scan
+pub async fn pick_and_scan(app: &AppService) {
+    let from_archive = app.state.import().read().selected_import_source == ImportSource::Archive;
+    let dialog = rfd::AsyncFileDialog::new();
+    let picked = if from_archive {
+        dialog
+            .add_filter("Archives", archive::ARCHIVE_EXTENSIONS)
+            .pick_file()
+            .await
+    } else {
+        dialog.pick_folder().await
+    };
+    let Some(picked) = picked else {
+        return;
+    };
+
+    // Clear existing candidates if this is the first folder
+    {
+        let mut import_store = app.state.import();
+        if import_store.read().detected_candidates.is_empty() {
+            import_store.write().reset();
+        }
+        import_store.write().is_scanning_candidates = true;
+    }
+
+    if let Err(e) = app
+        .import_handle
+        .enqueue_folder_scan(picked.path().to_path_buf())
+    {
+        warn!("Failed to add folder to scan: {}", e);
+    }
+}
+
+/// Delete the extracted archive contents behind candidates dropped from the
+/// list. Candidates that are still importing keep theirs until the import
+/// finishes with them.
+pub fn remove_extracted_candidates(app: &AppService, paths: Vec<String>) {
+    let paths: Vec<String> = {
+        let import_store = app.state.import();
+        let state = import_store.read();
+        paths
+            .into_iter()
+            .filter(|path| {
+                !state
+                    .candidate_states
+                    .get(path)
+                    .is_some_and(|s| s.is_import_in_progress())
+            })
+            .collect()
+    };
+    tokio::task::spawn_blocking(move || {
+        let root = archive::extraction_root();
+        for path in paths {
+            archive::remove_extracted(Path::new(&path), &root);
+        }
+    });
+}
+
 // ============================================================================
 // Navigation helpers
 // ============================================================================
@@ -923,7 +1641,7 @@ pub fn has_unclean_state(app: &AppService) -> bool {
     let import_store = app.state.import();
     let state = import_store.read();
     match state.selected_import_source {
-        ImportSource::Folder => !state.detected_candidates.is_empty(),
+        ImportSource::Folder | ImportSource::Archive => !state.detected_candidates.is_empty(),
         ImportSource::Torrent => false, // TODO: implement torrent state check
         ImportSource::Cd => state.current_candidate_key.is_some(),
     }
@@ -1009,11 +1727,144 @@ pub fn categorized_files_from_scanned(
     let mut documents: Vec<FileInfo> = files.documents.iter().map(scanned_to_file_info).collect();
     documents.sort_by(|a, b| a.name.cmp(&b.name));
 
+    let mut videos: Vec<FileInfo> = files.videos.iter().map(scanned_to_file_info).collect();
+    videos.sort_by(|a, b| a.name.cmp(&b.name));
+
     CategorizedFileInfo {
         audio,
         artwork,
         documents,
+        videos,
         bad_audio_count: files.bad_audio_count,
         bad_image_count: files.bad_image_count,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(source_type: MatchSourceType, title: &str, catno: &str) -> DisplayMatchCandidate {
+        DisplayMatchCandidate {
+            title: title.to_string(),
+            artist: "Artist".to_string(),
+            year: None,
+            cover_url: None,
+            cover_thumbnail_url: None,
+            format: None,
+            country: None,
+            label: None,
+            catalog_number: Some(catno.to_string()),
+            barcode: None,
+            source_type,
+            original_year: None,
+            musicbrainz_release_id: None,
+            musicbrainz_release_group_id: None,
+            discogs_release_id: None,
+            discogs_master_id: None,
+            track_titles: vec![],
+        }
+    }
+
+    fn page(source: MatchSourceType, candidates: Vec<DisplayMatchCandidate>) -> SearchResultsPage {
+        SearchResultsPage {
+            candidates,
+            result_counts: vec![SourceResultCount {
+                source,
+                total: 50,
+                has_more: true,
+                pages_loaded: 1,
+            }],
+        }
+    }
+
+    fn titles(page: &SearchResultsPage) -> Vec<&str> {
+        page.candidates.iter().map(|c| c.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_combine_interleaves_sources() {
+        let mb = page(
+            MatchSourceType::MusicBrainz,
+            vec![
+                candidate(MatchSourceType::MusicBrainz, "mb1", "A-1"),
+                candidate(MatchSourceType::MusicBrainz, "mb2", "A-2"),
+                candidate(MatchSourceType::MusicBrainz, "mb3", "A-3"),
+            ],
+        );
+        let discogs = page(
+            MatchSourceType::Discogs,
+            vec![candidate(MatchSourceType::Discogs, "dg1", "B-1")],
+        );
+
+        let combined = combine_search_pages(Ok(mb), Ok(discogs)).unwrap();
+        assert_eq!(titles(&combined), vec!["mb1", "dg1", "mb2", "mb3"]);
+        assert_eq!(combined.result_counts.len(), 2);
+    }
+
+    #[test]
+    fn test_combine_drops_cross_source_duplicates() {
+        let mb = page(
+            MatchSourceType::MusicBrainz,
+            vec![candidate(MatchSourceType::MusicBrainz, "mb1", "SWR-001")],
+        );
+        let discogs = page(
+            MatchSourceType::Discogs,
+            vec![
+                candidate(MatchSourceType::Discogs, "dg1", "SWR 001"),
+                candidate(MatchSourceType::Discogs, "dg2", "SWR-002"),
+            ],
+        );
+
+        let combined = combine_search_pages(Ok(mb), Ok(discogs)).unwrap();
+        assert_eq!(titles(&combined), vec!["mb1", "dg2"]);
+    }
+
+    #[test]
+    fn test_combine_keeps_working_source_when_other_fails() {
+        let discogs = page(
+            MatchSourceType::Discogs,
+            vec![candidate(MatchSourceType::Discogs, "dg1", "B-1")],
+        );
+
+        let combined =
+            combine_search_pages(Err("MusicBrainz search failed".to_string()), Ok(discogs))
+                .unwrap();
+        assert_eq!(titles(&combined), vec!["dg1"]);
+        // The failed source reports no count, so its page counter stays put
+        assert_eq!(combined.result_counts.len(), 1);
+        assert_eq!(combined.result_counts[0].source, MatchSourceType::Discogs);
+    }
+
+    #[test]
+    fn test_combine_fails_when_both_sources_fail() {
+        let result =
+            combine_search_pages(Err("mb down".to_string()), Err("discogs down".to_string()));
+        assert_eq!(result.err(), Some("mb down; discogs down".to_string()));
+    }
+
+    #[test]
+    fn test_pages_after_tracks_each_source() {
+        let counts = vec![
+            SourceResultCount {
+                source: MatchSourceType::MusicBrainz,
+                total: 100,
+                has_more: true,
+                pages_loaded: 3,
+            },
+            SourceResultCount {
+                source: MatchSourceType::Discogs,
+                total: 100,
+                has_more: true,
+                pages_loaded: 2,
+            },
+        ];
+        assert_eq!(
+            SearchPages::after(&counts),
+            SearchPages {
+                musicbrainz: 3,
+                discogs: 2,
+            }
+        );
+    }
+}
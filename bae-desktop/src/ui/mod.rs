@@ -2,11 +2,15 @@ pub mod app;
 pub mod app_context;
 pub mod app_service;
 pub mod components;
+pub mod deep_link;
+pub mod disc_insert;
 pub mod display_types;
 pub mod import_helpers;
 pub mod local_file_url;
-mod protocol_handler;
+pub(crate) mod protocol_handler;
 pub mod shortcuts;
+pub mod startup;
+pub mod tray;
 #[cfg(target_os = "macos")]
 pub mod window_activation;
 pub use app::*;
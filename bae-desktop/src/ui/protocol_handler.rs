@@ -96,7 +96,7 @@ fn handle_image(image_id: &str, services: &ImageServices) -> ProtocolResponse {
     }
 }
 
-async fn serve_image(
+pub(crate) async fn serve_image(
     image_id: &str,
     services: &ImageServices,
 ) -> Result<(Vec<u8>, &'static str), String> {
@@ -1,8 +1,10 @@
 //! App-level keyboard shortcuts
 //!
-//! Maps Cmd+N (macOS) / Ctrl+N (Windows/Linux) to navigation actions.
+//! Maps Cmd+N (macOS) / Ctrl+N (Windows/Linux) to navigation actions, and
+//! Cmd/Ctrl+Z and Shift+Cmd/Ctrl+Z to undo and redo of library edits.
 //! Also provides a mechanism for native menus to request navigation.
 
+use crate::ui::app_service::use_app;
 use crate::ui::Route;
 #[cfg(target_os = "macos")]
 use bae_core::playback::RepeatMode;
@@ -23,6 +25,7 @@ pub enum NavAction {
 pub enum NavTarget {
     Library,
     Import,
+    Settings,
 }
 
 impl NavTarget {
@@ -30,6 +33,7 @@ impl NavTarget {
         match self {
             NavTarget::Library => Route::Library {},
             NavTarget::Import => Route::ImportWorkflowManager {},
+            NavTarget::Settings => Route::Settings {},
         }
     }
 }
@@ -71,6 +75,13 @@ pub fn subscribe_playback_actions() -> broadcast::Receiver<PlaybackAction> {
         .subscribe()
 }
 
+/// Send a navigation action from the main thread (the tray menu).
+pub fn send_nav(action: NavAction) {
+    if let Some(tx) = NAV_SENDER.get() {
+        let _ = tx.send(action);
+    }
+}
+
 /// Request a navigation action (called from native menu handlers).
 /// On macOS, dispatches to main thread via GCD.
 #[cfg(target_os = "macos")]
@@ -137,6 +148,46 @@ pub fn handle_shortcut(evt: &KeyboardEvent) -> Option<NavAction> {
     None
 }
 
+/// Library edit history actions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditAction {
+    Undo,
+    Redo,
+}
+
+/// Undo is the platform modifier plus Z; redo adds Shift (or Ctrl+Y off macOS)
+fn handle_edit_shortcut(evt: &KeyboardEvent) -> Option<EditAction> {
+    let mods = evt.modifiers();
+    #[cfg(target_os = "macos")]
+    let has_modifier = mods.meta() && !mods.ctrl() && !mods.alt();
+    #[cfg(not(target_os = "macos"))]
+    let has_modifier = mods.ctrl() && !mods.meta() && !mods.alt();
+    if !has_modifier {
+        return None;
+    }
+
+    match evt.key() {
+        Key::Character(c) if c.eq_ignore_ascii_case("z") && mods.shift() => Some(EditAction::Redo),
+        Key::Character(c) if c.eq_ignore_ascii_case("z") => Some(EditAction::Undo),
+        #[cfg(not(target_os = "macos"))]
+        Key::Character(c) if c == "y" && !mods.shift() => Some(EditAction::Redo),
+        _ => None,
+    }
+}
+
+/// Whether a text field has focus, in which case undo belongs to the field
+async fn is_editing_text() -> bool {
+    document::eval(
+        r#"
+        const el = document.activeElement;
+        return !!el && (el.isContentEditable || ["INPUT", "TEXTAREA", "SELECT"].includes(el.tagName));
+        "#,
+    )
+    .join::<bool>()
+    .await
+    .unwrap_or(false)
+}
+
 fn execute_nav_action(action: NavAction) {
     match action {
         NavAction::Back => navigator().go_back(),
@@ -159,10 +210,24 @@ pub fn ShortcutsHandler(children: Element) -> Element {
         });
     });
 
+    let app = use_app();
     let onkeydown = move |evt: KeyboardEvent| {
         if let Some(action) = handle_shortcut(&evt) {
             evt.prevent_default();
             execute_nav_action(action);
+            return;
+        }
+        if let Some(action) = handle_edit_shortcut(&evt) {
+            let app = app.clone();
+            spawn(async move {
+                if is_editing_text().await {
+                    return;
+                }
+                match action {
+                    EditAction::Undo => app.undo(),
+                    EditAction::Redo => app.redo(),
+                }
+            });
         }
     };
 
@@ -0,0 +1,55 @@
+//! What the window opens on at launch
+//!
+//! Per the Library settings, that's the library, the library with the queue
+//! sidebar open, or the route that was open when the app last closed. A deep
+//! link passed at launch wins over all of them.
+
+use crate::ui::app_service::use_app;
+use crate::ui::deep_link::launch_deep_link;
+use crate::ui::Route;
+use bae_core::config::StartupView;
+use bae_ui::stores::{AppStateStoreExt, SidebarStateStoreExt, UiStateStoreExt};
+use dioxus::prelude::*;
+use tracing::warn;
+
+/// Applies the startup view once, then records each route for the next launch.
+/// Must be rendered inside the router.
+#[component]
+pub fn StartupHandler() -> Element {
+    let app = use_app();
+    let route = use_route::<Route>();
+
+    use_hook({
+        let app = app.clone();
+        move || {
+            if launch_deep_link().is_some() {
+                return;
+            }
+            match app.config.startup_view {
+                StartupView::Library => {}
+                StartupView::NowPlaying => app.state.ui().sidebar().is_open().set(true),
+                StartupView::LastRoute => {
+                    let last_route = app.config.last_route.as_deref();
+                    match last_route.map(str::parse::<Route>) {
+                        Some(Ok(route)) => {
+                            navigator().replace(route);
+                        }
+                        Some(Err(_)) => {
+                            warn!("Ignoring unparseable last route: {:?}", last_route);
+                        }
+                        None => {}
+                    }
+                }
+            }
+        }
+    });
+
+    use_effect(use_reactive((&route,), move |(route,)| {
+        crate::crash_report::set_context("route", route.to_string());
+        if let Err(e) = app.config.save_last_route(&route.to_string()) {
+            warn!("Failed to save last route: {}", e);
+        }
+    }));
+
+    rsx! {}
+}
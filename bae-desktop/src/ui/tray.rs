@@ -0,0 +1,205 @@
+//! Tray icon with a mini player (a menu bar extra on macOS)
+//!
+//! The menu shows what's playing, controls playback and opens the main
+//! window. With background playback on, closing the window only hides it,
+//! so the tray is how it comes back.
+
+use crate::ui::app_service::AppService;
+use crate::ui::shortcuts::{send_nav, NavAction, NavTarget};
+use bae_ui::stores::{
+    AppStateStoreExt, ConfigStateStoreExt, PlaybackStatus, PlaybackUiStateStoreExt,
+};
+use dioxus::desktop::muda::{MenuEvent, MenuItem, PredefinedMenuItem};
+use dioxus::desktop::trayicon::{init_tray_icon, DioxusTrayIcon, DioxusTrayMenu};
+use dioxus::desktop::{use_tray_menu_event_handler, window, WindowCloseBehaviour};
+use dioxus::prelude::*;
+use tracing::warn;
+
+const TRAY_ICON: &[u8] = include_bytes!("../../assets/favicon.ico");
+
+/// Menu items that follow playback
+#[derive(Clone)]
+struct PlaybackItems {
+    now_playing: MenuItem,
+    play_pause: MenuItem,
+    next: MenuItem,
+    previous: MenuItem,
+}
+
+/// What the playback items show for the current playback
+#[derive(Debug, PartialEq)]
+struct MenuState {
+    now_playing: String,
+    play_pause: &'static str,
+    has_track: bool,
+}
+
+impl MenuState {
+    fn new(status: PlaybackStatus, title: Option<&str>, artist: &str) -> Self {
+        let now_playing = match title {
+            Some(title) if artist.is_empty() => title.to_string(),
+            Some(title) => format!("{} - {}", artist, title),
+            None => "Not playing".to_string(),
+        };
+        MenuState {
+            // A single & marks a mnemonic in menu text
+            now_playing: now_playing.replace('&', "&&"),
+            play_pause: if status == PlaybackStatus::Playing {
+                "Pause"
+            } else {
+                "Play"
+            },
+            has_track: status != PlaybackStatus::Stopped,
+        }
+    }
+}
+
+/// Put bae in the tray, and hide the window on close instead of quitting
+/// while background playback is on
+pub fn use_tray(app: &AppService) {
+    let items = use_hook(|| {
+        let items = PlaybackItems {
+            now_playing: MenuItem::new("Not playing", false, None),
+            play_pause: MenuItem::with_id("play_pause", "Play", false, None),
+            next: MenuItem::with_id("next", "Next", false, None),
+            previous: MenuItem::with_id("previous", "Previous", false, None),
+        };
+        let menu = DioxusTrayMenu::new();
+        if let Err(e) = menu.append_items(&[
+            &items.now_playing,
+            &PredefinedMenuItem::separator(),
+            &items.play_pause,
+            &items.next,
+            &items.previous,
+            &PredefinedMenuItem::separator(),
+            &MenuItem::with_id("show", "Show bae", true, None),
+            &MenuItem::with_id("library", "Library", true, None),
+            &MenuItem::with_id("import", "Import", true, None),
+            &MenuItem::with_id("settings", "Settings", true, None),
+            &PredefinedMenuItem::separator(),
+            &MenuItem::with_id("quit", "Quit bae", true, None),
+        ]) {
+            warn!("Failed to build tray menu: {}", e);
+        }
+        init_tray_icon(menu, tray_icon());
+        items
+    });
+
+    let playback = app.state.playback();
+    use_effect(move || {
+        let status = *playback.status().read();
+        let title = playback
+            .current_track()
+            .read()
+            .as_ref()
+            .map(|item| item.track.title.clone());
+        let artist = playback.artist_name().read().clone();
+        let menu = MenuState::new(status, title.as_deref(), &artist);
+        items.now_playing.set_text(menu.now_playing);
+        items.play_pause.set_text(menu.play_pause);
+        items.play_pause.set_enabled(menu.has_track);
+        items.next.set_enabled(menu.has_track);
+        items.previous.set_enabled(menu.has_track);
+    });
+
+    let in_background = app.state.config().playback_in_background();
+    use_effect(move || {
+        window().set_close_behavior(if *in_background.read() {
+            WindowCloseBehaviour::LastWindowHides
+        } else {
+            WindowCloseBehaviour::LastWindowExitsApp
+        });
+    });
+
+    let playback_handle = app.playback_handle.clone();
+    use_tray_menu_event_handler(move |event: &MenuEvent| match event.id.0.as_str() {
+        "play_pause" => match *playback.status().peek() {
+            PlaybackStatus::Playing => playback_handle.pause(),
+            PlaybackStatus::Paused => playback_handle.resume(),
+            PlaybackStatus::Stopped | PlaybackStatus::Loading => {}
+        },
+        "next" => playback_handle.next(),
+        "previous" => playback_handle.previous(),
+        "show" => show_window(),
+        "library" => open(NavTarget::Library),
+        "import" => open(NavTarget::Import),
+        "settings" => open(NavTarget::Settings),
+        "quit" => {
+            let window = window();
+            window.set_close_behavior(WindowCloseBehaviour::LastWindowExitsApp);
+            window.close();
+        }
+        _ => {}
+    });
+}
+
+fn show_window() {
+    let window = window();
+    window.set_visible(true);
+    window.set_minimized(false);
+    window.set_focus();
+}
+
+fn open(target: NavTarget) {
+    send_nav(NavAction::GoTo(target));
+    show_window();
+}
+
+/// The app icon, sized for the tray
+fn tray_icon() -> Option<DioxusTrayIcon> {
+    let image = image::load_from_memory_with_format(TRAY_ICON, image::ImageFormat::Ico)
+        .inspect_err(|e| warn!("Failed to load tray icon: {}", e))
+        .ok()?
+        .thumbnail(64, 64)
+        .into_rgba8();
+    let (width, height) = image.dimensions();
+    DioxusTrayIcon::from_rgba(image.into_raw(), width, height).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_menu_shows_the_track_and_offers_the_opposite_of_the_current_state() {
+        assert_eq!(
+            MenuState::new(PlaybackStatus::Playing, Some("Airbag"), "Radiohead"),
+            MenuState {
+                now_playing: "Radiohead - Airbag".to_string(),
+                play_pause: "Pause",
+                has_track: true,
+            }
+        );
+        assert_eq!(
+            MenuState::new(PlaybackStatus::Paused, Some("Airbag"), ""),
+            MenuState {
+                now_playing: "Airbag".to_string(),
+                play_pause: "Play",
+                has_track: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_menu_disables_controls_when_stopped() {
+        assert_eq!(
+            MenuState::new(PlaybackStatus::Stopped, None, ""),
+            MenuState {
+                now_playing: "Not playing".to_string(),
+                play_pause: "Play",
+                has_track: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_ampersands_are_not_taken_as_mnemonics() {
+        let menu = MenuState::new(
+            PlaybackStatus::Playing,
+            Some("Rock & Roll"),
+            "Simon & Garfunkel",
+        );
+
+        assert_eq!(menu.now_playing, "Simon && Garfunkel - Rock && Roll");
+    }
+}
@@ -3,6 +3,7 @@ use std::process::Command;
 
 fn main() {
     copy_shared_assets();
+    detect_generated_library();
     generate_tailwind();
 }
 
@@ -19,6 +20,19 @@ fn copy_shared_assets() {
     }
 }
 
+/// Enable the generated large-library fixture when one has been written
+fn detect_generated_library() {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let library_json = Path::new(manifest_dir).join("fixtures/library.json");
+
+    println!("cargo:rustc-check-cfg=cfg(generated_library)");
+    println!("cargo:rerun-if-changed={}", library_json.display());
+
+    if library_json.exists() {
+        println!("cargo:rustc-cfg=generated_library");
+    }
+}
+
 fn generate_tailwind() {
     let manifest_dir = env!("CARGO_MANIFEST_DIR");
     let tailwind_input = Path::new(manifest_dir).join("tailwind.css");
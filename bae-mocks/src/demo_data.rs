@@ -1,8 +1,15 @@
 //! Demo data for screenshot generation
 //!
 //! Provides static fixture data for rendering the UI without a database.
+//!
+//! The curated `fixtures/data.json` is always loaded. If `fixtures/library.json`
+//! exists at build time (written by bae-core's `generate_library` example), its
+//! albums are appended so the demo runs against a realistically sized library.
 
-use bae_ui::{Album, Artist, Release, Track, TrackImportState};
+use bae_ui::{
+    Album, Artist, AudioQuality, LibraryTrack, Release, Track, TrackImportState, TrackPlay,
+    TrackPlayCount,
+};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::OnceLock;
@@ -10,6 +17,12 @@ use std::sync::OnceLock;
 /// Embedded fixture data (compiled into the binary)
 const FIXTURE_JSON: &str = include_str!("../fixtures/data.json");
 
+/// Generated large-library fixture, if one was present at build time
+#[cfg(generated_library)]
+const GENERATED_FIXTURE_JSON: Option<&str> = Some(include_str!("../fixtures/library.json"));
+#[cfg(not(generated_library))]
+const GENERATED_FIXTURE_JSON: Option<&str> = None;
+
 #[derive(Debug, Deserialize)]
 struct FixtureData {
     albums: Vec<FixtureAlbum>,
@@ -22,6 +35,9 @@ struct FixtureAlbum {
     year: i32,
     #[serde(default)]
     tracks: Vec<String>,
+    /// Generated entries simulating a broken import
+    #[serde(default)]
+    unavailable: bool,
 }
 
 /// Parsed demo data, lazily initialized
@@ -59,6 +75,14 @@ fn get_demo_data() -> &'static DemoData {
     DEMO_DATA.get_or_init(|| {
         let fixture: FixtureData =
             serde_json::from_str(FIXTURE_JSON).expect("Failed to parse fixture JSON");
+        let curated_count = fixture.albums.len();
+        let generated: Vec<FixtureAlbum> = GENERATED_FIXTURE_JSON
+            .map(|json| {
+                serde_json::from_str::<FixtureData>(json)
+                    .expect("Failed to parse generated library JSON")
+                    .albums
+            })
+            .unwrap_or_default();
 
         let mut albums = Vec::new();
         let mut artist_ids: HashMap<String, String> = HashMap::new();
@@ -66,7 +90,7 @@ fn get_demo_data() -> &'static DemoData {
         let mut tracks_by_album = HashMap::new();
         let mut releases_by_album = HashMap::new();
 
-        for album_data in fixture.albums {
+        for album_data in fixture.albums.into_iter().chain(generated) {
             // Get or create artist
             let artist_id = artist_ids
                 .entry(album_data.artist.clone())
@@ -79,13 +103,23 @@ fn get_demo_data() -> &'static DemoData {
                 album_data.artist, album_data.title
             ));
 
-            // Create album
+            // Create album with varied play stats so library sorting has something to show
+            let index = albums.len() as i64;
+            // Only curated albums have cover images
+            let has_cover = albums.len() < curated_count;
+            let quality = AudioQuality::ALL[index as usize % AudioQuality::ALL.len()];
             albums.push(Album {
                 id: album_id.clone(),
                 title: album_data.title.clone(),
                 year: Some(album_data.year),
-                cover_url: Some(cover_url(&album_data.artist, &album_data.title)),
+                cover_url: has_cover.then(|| cover_url(&album_data.artist, &album_data.title)),
                 is_compilation: false,
+                date_added: 1_700_000_000 + (index * 5 % 61) * 86_400,
+                play_count: (index * 17 % 45) as u32,
+                last_played: (index % 4 != 0).then(|| 1_718_000_000 + (index * 7 % 47) * 3_600),
+                palette: None,
+                quality: Some(quality),
+                is_offline: index % 9 == 0,
             });
 
             // Link artist to album
@@ -127,8 +161,14 @@ fn get_demo_data() -> &'static DemoData {
                         track_number: Some((i + 1) as i32),
                         disc_number: Some(1),
                         duration_ms: Some(180_000 + (i as i64 * 30_000)), // Fake durations 3:00-5:30
-                        is_available: true,
-                        import_state: TrackImportState::Complete,
+                        is_available: !album_data.unavailable,
+                        import_state: if album_data.unavailable {
+                            TrackImportState::None
+                        } else {
+                            TrackImportState::Complete
+                        },
+                        quality: Some(quality),
+                        bookmarks: Vec::new(),
                     }
                 })
                 .collect();
@@ -172,6 +212,73 @@ pub fn get_tracks_for_album(album_id: &str) -> Vec<Track> {
         .unwrap_or_default()
 }
 
+/// Get every track as a flat list, in album order
+pub fn get_library_tracks() -> Vec<LibraryTrack> {
+    let data = get_demo_data();
+    let mut albums = data.albums.clone();
+    albums.sort_by(|a, b| a.title.cmp(&b.title));
+    albums
+        .iter()
+        .flat_map(|album| {
+            let artist_name = data
+                .artists_by_album
+                .get(&album.id)
+                .map(|artists| {
+                    artists
+                        .iter()
+                        .map(|a| a.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .unwrap_or_default();
+            data.tracks_by_album
+                .get(&album.id)
+                .into_iter()
+                .flatten()
+                .map(move |track| LibraryTrack {
+                    id: track.id.clone(),
+                    title: track.title.clone(),
+                    artist_name: artist_name.clone(),
+                    album_id: album.id.clone(),
+                    album_title: album.title.clone(),
+                    year: album.year,
+                    disc_number: track.disc_number,
+                    track_number: track.track_number,
+                    duration_ms: track.duration_ms,
+                })
+        })
+        .collect()
+}
+
+/// A few days of listening, newest first
+pub fn get_track_plays() -> Vec<TrackPlay> {
+    get_library_tracks()
+        .into_iter()
+        .step_by(3)
+        .take(30)
+        .enumerate()
+        .map(|(index, track)| TrackPlay {
+            id: format!("play-{}", index),
+            track,
+            played_at: 1_718_900_000 - (index as i64) * 9_000,
+        })
+        .collect()
+}
+
+/// Most played tracks among the demo plays
+pub fn get_top_tracks() -> Vec<TrackPlayCount> {
+    get_track_plays()
+        .into_iter()
+        .take(10)
+        .enumerate()
+        .map(|(index, play)| TrackPlayCount {
+            track: play.track,
+            play_count: 24 - index as u32 * 2,
+            last_played: play.played_at,
+        })
+        .collect()
+}
+
 /// Get releases for a specific album
 pub fn get_releases_for_album(album_id: &str) -> Vec<Release> {
     get_demo_data()
@@ -11,9 +11,9 @@ pub mod ui;
 
 use dioxus::prelude::*;
 use pages::{
-    AlbumDetail, DemoLayout, Import, Library, MockAlbumDetail, MockButton, MockDropdownTest,
-    MockFolderImport, MockIndex, MockLibrary, MockMenu, MockPill, MockTextInput, MockTitleBar,
-    MockTooltip, Settings,
+    AlbumDetail, DemoLayout, History, Import, Library, MockAlbumDetail, MockButton,
+    MockDropdownTest, MockFolderImport, MockIndex, MockLibrary, MockMenu, MockPill, MockTextInput,
+    MockTitleBar, MockTooltip, Settings, Tracks,
 };
 
 pub const FAVICON: Asset = asset!("/assets/favicon.ico");
@@ -34,6 +34,10 @@ pub enum Route {
     Library {},
     #[route("/app/album/:album_id")]
     AlbumDetail { album_id: String },
+    #[route("/app/tracks")]
+    Tracks {},
+    #[route("/app/history")]
+    History {},
     #[route("/app/import")]
     Import {},
     #[route("/app/settings")]
@@ -2,7 +2,12 @@
 
 use super::framework::{ControlRegistryBuilder, MockPage, MockPanel, Preset};
 use bae_ui::stores::{AlbumDetailState, AlbumDetailStateStoreExt};
-use bae_ui::{Album, AlbumDetailView, Artist, PlaybackDisplay, Release, Track, TrackImportState};
+use bae_ui::{
+    Album, AlbumDetailView, AlbumPalette, Artist, AudioQuality, ComparedEdition, ComparedTrack,
+    ComparedTrackSide, File, LoudnessMeasurement, OfflineDownload, PlaybackDisplay, Release,
+    ReleaseArchiveStatus, ReleaseComparison, ReleaseReference, Track, TrackBookmark,
+    TrackClickAction, TrackImportState,
+};
 use dioxus::prelude::*;
 
 #[component]
@@ -26,12 +31,60 @@ pub fn AlbumDetailMock(initial_state: Option<String>) -> Element {
             "Multiple",
             vec![("Single", "Single"), ("Multiple", "Multiple")],
         )
+        .enum_control(
+            "click",
+            "Track Click",
+            "select",
+            vec![
+                ("select", "Select"),
+                ("play", "Play"),
+                ("add_to_queue", "Add to Queue"),
+            ],
+        )
+        .bool_control("palette", "Cover Palette", true)
+        .bool_control("videos", "Videos", true)
+        .enum_control(
+            "archive",
+            "Archive",
+            "None",
+            vec![
+                ("None", "None"),
+                ("Archived", "Archived"),
+                ("Restoring", "Restoring"),
+                ("Restored", "Restored"),
+            ],
+        )
+        .enum_control(
+            "reference",
+            "Reference",
+            "None",
+            vec![
+                ("None", "None"),
+                ("Referenced", "Referenced"),
+                ("Migrating", "Migrating"),
+                ("Failed", "Failed"),
+            ],
+        )
+        .enum_control(
+            "offline",
+            "Offline",
+            "None",
+            vec![
+                ("None", "None"),
+                ("Downloading", "Downloading"),
+                ("Failed", "Failed"),
+                ("Offline", "Available"),
+            ],
+        )
         .with_presets(vec![
             Preset::new("Default"),
             Preset::new("Playing").set_string("playback", "Playing"),
             Preset::new("Paused").set_string("playback", "Paused"),
             Preset::new("Loading").set_string("playback", "Loading"),
             Preset::new("Single Release").set_string("releases", "Single"),
+            Preset::new("Archived").set_string("archive", "Archived"),
+            Preset::new("Referenced").set_string("reference", "Referenced"),
+            Preset::new("Downloading Offline").set_string("offline", "Downloading"),
         ])
         .build(initial_state);
 
@@ -41,10 +94,45 @@ pub fn AlbumDetailMock(initial_state: Option<String>) -> Element {
     // Local state
     let position_ms = use_signal(|| 45_000u64);
     let mut selected_release_id = use_signal(|| Some("release-1".to_string()));
+    let mut compared_pair = use_signal(|| None::<(String, String)>);
+    let mut preferred_release_id = use_signal(|| Some("release-2".to_string()));
+    let mut editing = use_signal(|| false);
 
     // Parse state from registry
     let playback_state = registry.get_string("playback");
     let releases_mode = registry.get_string("releases");
+    let track_click_action = TrackClickAction::from_key(&registry.get_string("click"))
+        .unwrap_or(TrackClickAction::Select);
+    let show_palette = registry.get_bool("palette");
+    let show_videos = registry.get_bool("videos");
+    let archive_status = match registry.get_string("archive").as_str() {
+        "Archived" => Some(ReleaseArchiveStatus::Archived),
+        "Restoring" => Some(ReleaseArchiveStatus::Restoring),
+        "Restored" => Some(ReleaseArchiveStatus::Restored),
+        _ => None,
+    };
+    let reference_mode = registry.get_string("reference");
+    let reference = (reference_mode != "None").then(|| ReleaseReference {
+        profile_name: "NAS".to_string(),
+        target_profile_name: Some("Encrypted Cloud".to_string()),
+        migrating: reference_mode == "Migrating",
+        error: (reference_mode == "Failed")
+            .then(|| "Failed to read 03 - Track.flac: No such file or directory".to_string()),
+    });
+    let offline_mode = registry.get_string("offline");
+    let offline_download = match offline_mode.as_str() {
+        "Downloading" => Some(OfflineDownload {
+            files_done: 4,
+            files_total: 11,
+            error: None,
+        }),
+        "Failed" => Some(OfflineDownload {
+            files_done: 4,
+            files_total: 11,
+            error: Some("Cloud storage unreachable".to_string()),
+        }),
+        _ => None,
+    };
 
     // Mock data
     let album = Album {
@@ -53,6 +141,15 @@ pub fn AlbumDetailMock(initial_state: Option<String>) -> Element {
         year: Some(2023),
         cover_url: Some("/covers/the-midnight-signal_neon-frequencies.png".to_string()),
         is_compilation: false,
+        date_added: 1_709_510_400,
+        play_count: 12,
+        last_played: Some(1_718_900_000),
+        palette: show_palette.then(|| AlbumPalette {
+            dominant: "#1e2a5c".to_string(),
+            accent: "#e8703a".to_string(),
+        }),
+        quality: Some(AudioQuality::HiRes),
+        is_offline: offline_mode != "None",
     };
 
     let artists = vec![Artist {
@@ -114,9 +211,49 @@ pub fn AlbumDetailMock(initial_state: Option<String>) -> Element {
         duration_ms: Some(*duration),
         is_available: true,
         import_state: TrackImportState::Complete,
+        quality: Some(AudioQuality::HiRes),
+        bookmarks: if *id == "track-3" {
+            vec![
+                TrackBookmark {
+                    id: "bookmark-1".to_string(),
+                    track_id: id.to_string(),
+                    name: "Break".to_string(),
+                    position_ms: 94_000,
+                },
+                TrackBookmark {
+                    id: "bookmark-2".to_string(),
+                    track_id: id.to_string(),
+                    name: "Second drop".to_string(),
+                    position_ms: 221_500,
+                },
+            ]
+        } else {
+            Vec::new()
+        },
     })
     .collect();
 
+    let videos = if show_videos {
+        vec![
+            File {
+                id: "file-video-1".to_string(),
+                filename: "Broadcast (Official Video).mp4".to_string(),
+                file_size: 184_000_000,
+                format: "mp4".to_string(),
+            },
+            File {
+                id: "file-video-2".to_string(),
+                filename: "Making of Neon Frequencies.mkv".to_string(),
+                file_size: 1_250_000_000,
+                format: "mkv".to_string(),
+            },
+        ]
+    } else {
+        vec![]
+    };
+
+    let comparison = compared_pair().map(|(left, right)| mock_comparison(left, right, &tracks));
+
     // Derive count/ids/disc_info before moving tracks
     let track_count = tracks.len();
     let track_ids: Vec<String> = tracks.iter().map(|t| t.id.clone()).collect();
@@ -128,6 +265,11 @@ pub fn AlbumDetailMock(initial_state: Option<String>) -> Element {
     // Create store once, then update when registry values change
     let mut state = use_store(AlbumDetailState::default);
 
+    let stored_bytes = releases
+        .iter()
+        .map(|r| (r.id.clone(), 412_000_000))
+        .collect();
+
     state.set(AlbumDetailState {
         album: Some(album),
         artists,
@@ -137,12 +279,22 @@ pub fn AlbumDetailMock(initial_state: Option<String>) -> Element {
         track_disc_info,
         releases,
         files: vec![],
+        videos,
         images: vec![],
         selected_release_id: selected_release_id(),
+        preferred_release_id: preferred_release_id(),
         loading: false,
         error: None,
         import_progress: None,
         import_error: None,
+        archive_status,
+        reference,
+        dynamic_range: Some(12),
+        stored_bytes,
+        comparison,
+        editing: editing(),
+        edit_error: None,
+        can_keep_offline: true,
     });
 
     // Get tracks lens for per-track reactivity
@@ -175,6 +327,7 @@ pub fn AlbumDetailMock(initial_state: Option<String>) -> Element {
                 state,
                 tracks,
                 playback,
+                track_click_action,
                 on_release_select: move |id| selected_release_id.set(Some(id)),
                 on_album_deleted: |_| {},
                 on_export_release: |_| {},
@@ -186,9 +339,74 @@ pub fn AlbumDetailMock(initial_state: Option<String>) -> Element {
                 on_track_add_next: |_| {},
                 on_track_add_to_queue: |_| {},
                 on_track_export: |_| {},
+                on_track_rename: |_| {},
+                on_track_bookmark_jump: |_| {},
+                on_track_bookmark_delete: |_| {},
                 on_play_album: |_| {},
                 on_add_album_to_queue: |_| {},
+                on_restore_release: |_| {},
+                on_migrate_release: |_| {},
+                on_play_video: |_| {},
+                on_compare_releases: move |pair| compared_pair.set(Some(pair)),
+                on_set_preferred_release: move |id| preferred_release_id.set(id),
+                on_edit: move |_| editing.set(true),
+                on_cancel_edit: move |_| editing.set(false),
+                on_save_edits: move |_| editing.set(false),
+                offline_download,
+                on_set_offline: |_| {},
             }
         }
     }
 }
+
+/// The deluxe edition runs a little longer per track and adds a bonus track
+fn mock_comparison(left: String, right: String, tracks: &[Track]) -> ReleaseComparison {
+    let edition = |release_id: String| {
+        let deluxe = release_id == "release-2";
+        ComparedEdition {
+            release_id,
+            track_count: tracks.len() + usize::from(deluxe),
+            total_duration_ms: tracks.iter().filter_map(|t| t.duration_ms).sum::<i64>()
+                + if deluxe { 262_000 } else { 0 },
+            format: Some("FLAC".to_string()),
+            sample_rate: Some(if deluxe { 96_000 } else { 44_100 }),
+            bits_per_sample: Some(if deluxe { 24 } else { 16 }),
+            bitrate_kbps: Some(if deluxe { 2_840 } else { 912 }),
+            dynamic_range: Some(if deluxe { 7 } else { 12 }),
+            loudness: if deluxe {
+                LoudnessMeasurement::Measuring
+            } else {
+                LoudnessMeasurement::Measured {
+                    peak_dbfs: -0.1,
+                    rms_dbfs: -8.4,
+                }
+            },
+        }
+    };
+    let side = |track: &Track, extra_ms: i64| ComparedTrackSide {
+        title: track.title.clone(),
+        duration_ms: track.duration_ms.map(|d| d + extra_ms),
+    };
+
+    let mut compared: Vec<ComparedTrack> = tracks
+        .iter()
+        .enumerate()
+        .map(|(i, track)| ComparedTrack {
+            left: Some(side(track, 0)),
+            right: Some(side(track, if i == 2 { 14_000 } else { 0 })),
+        })
+        .collect();
+    compared.push(ComparedTrack {
+        left: None,
+        right: Some(ComparedTrackSide {
+            title: "Broadcast (Extended Mix)".to_string(),
+            duration_ms: Some(262_000),
+        }),
+    });
+
+    ReleaseComparison {
+        left: edition(left),
+        right: edition(right),
+        tracks: compared,
+    }
+}
@@ -2,13 +2,16 @@
 
 use super::framework::{ControlRegistryBuilder, MockPage, MockPanel, Preset};
 use bae_ui::stores::import::{
-    CandidateState, ConfirmPhase, ConfirmingState, IdentifyingState, ImportState, ManualSearchState,
+    CandidateState, ConfirmPhase, ConfirmingState, IdentifyingState, ImportState,
+    ManualSearchState, PrepareStage, PrepareStageProgress, PrepareStageStatus, RecentSearch,
+    SearchField,
 };
 use bae_ui::{
     AudioContentInfo, CategorizedFileInfo, CueFlacPairInfo, DetectedCandidate,
     DetectedCandidateStatus, FileInfo, FolderImportView, FolderMetadata, IdentifyMode,
-    ImportSource, ImportStep, ImportView, MatchCandidate, MatchSourceType, SearchSource, SearchTab,
-    SelectedCover, StorageLocation, StorageProfile,
+    ImportSource, ImportStep, ImportView, ManualEntry, MatchCandidate, MatchSourceType, QuotaLevel,
+    ReleaseTemplate, SearchSource, SearchTab, SelectedCover, SourceResultCount, StorageEstimate,
+    StorageLocation, StorageProfile,
 };
 use dioxus::prelude::*;
 use std::collections::HashMap;
@@ -83,6 +86,7 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
                 ("DiscIdLookup", "Disc ID Lookup"),
                 ("MultipleExactMatches", "Multiple Exact Matches"),
                 ("ManualSearch", "Manual Search"),
+                ("ManualEntry", "Manual Entry"),
             ],
         )
         .visible_when("state", "Identifying")
@@ -130,6 +134,9 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
             Preset::new("Manual Search")
                 .set_string("state", "Identifying")
                 .set_string("identify_mode", "ManualSearch"),
+            Preset::new("Manual Entry")
+                .set_string("state", "Identifying")
+                .set_string("identify_mode", "ManualEntry"),
             Preset::new("Confirm")
                 .set_string("state", "Confirming")
                 .set_string("confirm_phase", "Ready"),
@@ -162,14 +169,6 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
         _ => ImportStep::Identify,
     };
 
-    // Parse identify mode (mock disc ID for DiscIdLookup)
-    let mock_disc_id = "XzPS7vW.HPHsYemQh0HBUGr8vuU-".to_string();
-    let identify_mode = match registry.get_string("identify_mode").as_str() {
-        "DiscIdLookup" => IdentifyMode::DiscIdLookup(mock_disc_id.clone()),
-        "MultipleExactMatches" => IdentifyMode::MultipleExactMatches(mock_disc_id.clone()),
-        _ => IdentifyMode::ManualSearch,
-    };
-
     // Parse search phase
     let search_phase_str = registry.get_string("search_phase");
     let is_searching = search_phase_str == "Searching";
@@ -212,6 +211,7 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
                     mock_file("rip.log", 4_500, "LOG"),
                     mock_file("info.txt", 1_200, "TXT"),
                 ],
+                videos: vec![mock_file("Broadcast (Official Video).mp4", 184_000_000, "MP4")],
                 ..Default::default()
             },
         ),
@@ -343,32 +343,38 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
             artist: "The Midnight Signal".to_string(),
             year: Some("2023".to_string()),
             cover_url: Some("/covers/the-midnight-signal_neon-frequencies.png".to_string()),
+            cover_thumbnail_url: None,
             format: Some("CD".to_string()),
             country: Some("US".to_string()),
             label: Some("Synthwave Records".to_string()),
             catalog_number: Some("SWR-001".to_string()),
+            barcode: None,
             source_type: MatchSourceType::MusicBrainz,
             original_year: Some("2023".to_string()),
             musicbrainz_release_id: Some("mock-mb-release-001".to_string()),
             musicbrainz_release_group_id: Some("mock-mb-rg-001".to_string()),
             discogs_release_id: None,
             discogs_master_id: None,
+            track_titles: Vec::new(),
         },
         MatchCandidate {
             title: "Neon Frequencies (Deluxe)".to_string(),
             artist: "The Midnight Signal".to_string(),
             year: Some("2023".to_string()),
             cover_url: Some("/covers/the-midnight-signal_neon-frequencies.png".to_string()),
+            cover_thumbnail_url: None,
             format: Some("Digital".to_string()),
             country: Some("XW".to_string()),
             label: Some("Synthwave Records".to_string()),
             catalog_number: Some("SWR-001D".to_string()),
+            barcode: None,
             source_type: MatchSourceType::MusicBrainz,
             original_year: Some("2023".to_string()),
             musicbrainz_release_id: Some("mock-mb-release-002".to_string()),
             musicbrainz_release_group_id: Some("mock-mb-rg-001".to_string()),
             discogs_release_id: None,
             discogs_master_id: None,
+            track_titles: Vec::new(),
         },
     ];
 
@@ -403,6 +409,18 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
         ],
     });
 
+    // Parse identify mode (mock disc ID for DiscIdLookup)
+    let mock_disc_id = "XzPS7vW.HPHsYemQh0HBUGr8vuU-".to_string();
+    let identify_mode = match registry.get_string("identify_mode").as_str() {
+        "DiscIdLookup" => IdentifyMode::DiscIdLookup(mock_disc_id.clone()),
+        "MultipleExactMatches" => IdentifyMode::MultipleExactMatches(mock_disc_id.clone()),
+        "ManualEntry" => IdentifyMode::ManualEntry(ManualEntry::from_detected(
+            detected_metadata.as_ref().unwrap(),
+            &folder_files,
+        )),
+        _ => IdentifyMode::ManualSearch,
+    };
+
     let storage_profiles = use_signal(|| {
         vec![
             StorageProfile {
@@ -419,6 +437,16 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
                 is_default: false,
                 ..Default::default()
             },
+            StorageProfile {
+                id: "profile-3".to_string(),
+                name: "Archive Bucket".to_string(),
+                location: StorageLocation::Cloud,
+                is_default: false,
+                quota_bytes: Some(200 * 1024 * 1024 * 1024),
+                used_bytes: 203 * 1024 * 1024 * 1024,
+                quota_level: Some(QuotaLevel::Exceeded),
+                ..Default::default()
+            },
         ]
     });
 
@@ -455,6 +483,18 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
         search_results: manual_match_candidates.clone(),
         selected_result_index: selected_match_index(),
         error_message: None,
+        result_counts: if has_searched {
+            vec![SourceResultCount {
+                source: MatchSourceType::MusicBrainz,
+                total: manual_match_candidates.len() as u32 + 25,
+                has_more: true,
+                pages_loaded: 1,
+            }]
+        } else {
+            vec![]
+        },
+        is_loading_more: false,
+        executed_query: None,
     };
 
     // Build candidate state based on step
@@ -480,12 +520,37 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
         }),
         bae_ui::ImportStep::Confirm => {
             let phase = match confirm_phase_str.as_str() {
-                "Preparing" => ConfirmPhase::Preparing("Fetching release data...".to_string()),
+                "Preparing" => ConfirmPhase::Preparing,
                 "Importing" => ConfirmPhase::Importing,
                 "Failed" => ConfirmPhase::Failed(import_error.clone().unwrap_or_default()),
                 "Completed" => ConfirmPhase::Completed,
                 _ => ConfirmPhase::Ready,
             };
+            // Partway through, or failed at matching files
+            let prepare_stage_status = |stage: PrepareStage| match (&phase, stage) {
+                (_, PrepareStage::FetchingRelease) => PrepareStageStatus::Done,
+                (_, PrepareStage::ExtractingDiscImages | PrepareStage::SplittingSides) => {
+                    PrepareStageStatus::Skipped
+                }
+                (ConfirmPhase::Preparing, PrepareStage::FetchingCover) => {
+                    PrepareStageStatus::Running
+                }
+                (ConfirmPhase::Failed(error), PrepareStage::MatchingFiles) => {
+                    PrepareStageStatus::Failed(error.clone())
+                }
+                (ConfirmPhase::Failed(_), PrepareStage::FetchingCover) => PrepareStageStatus::Done,
+                _ => PrepareStageStatus::Pending,
+            };
+            let prepare_stages = match &phase {
+                ConfirmPhase::Preparing | ConfirmPhase::Failed(_) => PrepareStage::ALL
+                    .into_iter()
+                    .map(|stage| PrepareStageProgress {
+                        stage,
+                        status: prepare_stage_status(stage),
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
             CandidateState::Confirming(Box::new(ConfirmingState {
                 files: folder_files.clone(),
                 metadata: detected_metadata.clone().unwrap_or_default(),
@@ -499,18 +564,22 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
                         format: None,
                         label: None,
                         catalog_number: None,
+                        barcode: None,
                         country: None,
                         cover_url: None,
+                        cover_thumbnail_url: None,
                         source_type: MatchSourceType::MusicBrainz,
                         original_year: None,
                         musicbrainz_release_id: None,
                         musicbrainz_release_group_id: None,
                         discogs_release_id: None,
                         discogs_master_id: None,
+                        track_titles: Vec::new(),
                     }),
                 selected_cover: selected_cover(),
                 selected_profile_id: selected_profile_id(),
                 phase,
+                prepare_stages,
                 auto_matches: exact_match_candidates.clone(),
                 search_state: mock_search_state,
                 source_disc_id: Some(mock_disc_id.clone()),
@@ -542,10 +611,36 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
         current_release_index: 0,
         selected_import_source: ImportSource::Folder,
         cd_toc_info: None,
+        recent_searches: vec![
+            RecentSearch {
+                source: SearchSource::MusicBrainz,
+                field: SearchField::Artist,
+                value: "The Midnight Signal".to_string(),
+            },
+            RecentSearch {
+                source: SearchSource::MusicBrainz,
+                field: SearchField::Album,
+                value: "Neon Frequencies".to_string(),
+            },
+        ],
+        audio_quality_checks: HashMap::new(),
+        duplicate_audio_checks: HashMap::new(),
+        needle_drop_checks: HashMap::new(),
+        inserted_cd: None,
+        candidate_imports: HashMap::new(),
+        torrent_download: None,
+        release_templates: vec![ReleaseTemplate {
+            id: "template-1".to_string(),
+            artist: "The Midnight Signal".to_string(),
+            title_pattern: "Late Transmissions Vol. {n}".to_string(),
+            next_title: "Late Transmissions Vol. 4".to_string(),
+            cover_url: Some(MOCK_COVERS[0].to_string()),
+        }],
     });
 
     let registry_for_search = registry.clone();
     let registry_for_cancel = registry.clone();
+    let registry_for_manual_entry = registry.clone();
 
     rsx! {
         MockPanel {
@@ -566,6 +661,12 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
                     selected_text_file: None,
                     text_file_content: None,
                     storage_profiles,
+                    storage_estimate: (selected_profile_id().as_deref() == Some("profile-1"))
+                        .then_some(StorageEstimate {
+                            object_count: 14,
+                            stored_bytes: 412_000_000,
+                            monthly_cost: 0.009,
+                        }),
                     on_folder_select_click: |_| {},
                     on_text_file_select: |_| {},
                     on_text_file_close: |_| {},
@@ -583,7 +684,11 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
                     on_manual_match_select: move |idx| selected_match_index.set(Some(idx)),
                     on_search: move |_| registry_for_search.set_string("search_phase", "Searching".to_string()),
                     on_cancel_search: move |_| registry_for_cancel.set_string("search_phase", "Empty".to_string()),
+                    on_load_more: |_| {},
                     on_manual_confirm: |_| {},
+                    on_switch_to_manual_entry: move |_| registry_for_manual_entry.set_string("identify_mode", "ManualEntry".to_string()),
+                    on_manual_entry_confirm: |_| {},
+                    on_save_release_template: |_| {},
                     on_retry_discid_lookup: |_| {},
                     on_select_remote_cover: move |url| {
                         selected_cover
@@ -598,8 +703,14 @@ pub fn FolderImportMock(initial_state: Option<String>) -> Element {
                     on_storage_profile_change: move |id| selected_profile_id.set(id),
                     on_edit: |_| {},
                     on_confirm: |_| {},
+                    on_check_audio_quality: |_| {},
+                    on_check_duplicate_audio: |_| {},
+                    on_keep_duplicate: |_| {},
                     on_configure_storage: |_| {},
                     on_view_duplicate: |_| {},
+                    on_split_sides: |_| {},
+                    on_side_track_start_change: |_| {},
+                    on_cancel_side_split: |_| {},
                 }
             }
         }
@@ -18,19 +18,21 @@ impl Breakpoint {
 /// Default breakpoints
 pub const DEFAULT_BREAKPOINTS: &[Breakpoint] = &[
     Breakpoint::new("Mobile", 375),
+    Breakpoint::new("Half 13\"", 720),
     Breakpoint::new("Tablet", 768),
     Breakpoint::new("Desktop", 1280),
     Breakpoint::new("Full", 0),
 ];
 
-/// Viewport container - just applies width constraint
+/// Viewport container - applies width constraint and acts as the query
+/// container, so bae-ui's `@`-prefixed responsive classes follow the chosen width
 #[component]
 pub fn MockViewport(width: u32, children: Element) -> Element {
     // When width is 0 (Full), use w-full to expand; otherwise use fixed width
     let class = if width > 0 {
-        "bg-surface-base rounded-lg overflow-hidden flex flex-grow"
+        "@container bg-surface-base rounded-lg overflow-hidden flex flex-grow"
     } else {
-        "bg-surface-base rounded-lg overflow-hidden w-full flex flex-grow"
+        "@container bg-surface-base rounded-lg overflow-hidden w-full flex flex-grow"
     };
     let style = if width > 0 {
         format!("width: {}px; margin: 0 auto;", width)
@@ -2,7 +2,7 @@
 
 use super::framework::{ControlRegistryBuilder, MockPage, MockPanel, Preset};
 use bae_ui::stores::LibraryState;
-use bae_ui::{Album, Artist, LibraryView};
+use bae_ui::{Album, Artist, AudioQuality, LibrarySort, LibraryView};
 use dioxus::prelude::*;
 use std::collections::HashMap;
 
@@ -58,6 +58,8 @@ pub fn LibraryMock(initial_state: Option<String>) -> Element {
         artists_by_album,
         loading,
         error,
+        find_replace_preview: None,
+        find_replace_error: None,
     });
 
     let cycle_val = cycle();
@@ -71,6 +73,11 @@ pub fn LibraryMock(initial_state: Option<String>) -> Element {
                 on_play_album: |_| {},
                 on_add_album_to_queue: |_| {},
                 on_empty_action: |_| {},
+                default_sort: LibrarySort::Title,
+                default_quality_filter: None,
+                on_find_replace_preview: |_| {},
+                on_find_replace_apply: |_| {},
+                on_find_replace_reset: |_| {},
             }
         }
     }
@@ -215,6 +222,12 @@ fn mock_albums_with_artists(count: usize) -> (Vec<Album>, HashMap<String, Vec<Ar
             year: Some(year),
             cover_url: Some(cover.to_string()),
             is_compilation: false,
+            date_added: 1_700_000_000 + (i as i64 * 7 % 97) * 86_400,
+            play_count: (i * 13 % 40) as u32,
+            last_played: (i % 3 != 0).then(|| 1_718_000_000 + (i as i64 * 11 % 53) * 3_600),
+            palette: None,
+            quality: Some(AudioQuality::ALL[i % AudioQuality::ALL.len()]),
+            is_offline: i % 5 == 0,
         });
 
         artists_by_album.insert(
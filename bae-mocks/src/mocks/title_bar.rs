@@ -13,6 +13,8 @@ pub fn TitleBarMock(initial_state: Option<String>) -> Element {
             "library",
             vec![
                 ("library", "Library"),
+                ("tracks", "Tracks"),
+                ("history", "History"),
                 ("import", "Import"),
                 ("settings", "Settings"),
                 ("none", "None"),
@@ -43,6 +45,16 @@ pub fn TitleBarMock(initial_state: Option<String>) -> Element {
             label: "Library".to_string(),
             is_active: active_nav == "library",
         },
+        NavItem {
+            id: "tracks".to_string(),
+            label: "Tracks".to_string(),
+            is_active: active_nav == "tracks",
+        },
+        NavItem {
+            id: "history".to_string(),
+            label: "History".to_string(),
+            is_active: active_nav == "history",
+        },
         NavItem {
             id: "import".to_string(),
             label: "Import".to_string(),
@@ -73,6 +85,7 @@ pub fn TitleBarMock(initial_state: Option<String>) -> Element {
                 on_search_change: |_| {},
                 search_results,
                 on_search_result_click: |_| {},
+                on_search_result_play: |_| {},
                 show_search_results,
                 on_search_dismiss: |_| {},
                 on_search_focus: |_| {},
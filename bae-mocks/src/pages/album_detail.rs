@@ -3,7 +3,7 @@
 use crate::demo_data;
 use crate::Route;
 use bae_ui::stores::{AlbumDetailState, AlbumDetailStateStoreExt};
-use bae_ui::{AlbumDetailView, BackButton, ErrorDisplay, PlaybackDisplay};
+use bae_ui::{AlbumDetailView, BackButton, ErrorDisplay, PlaybackDisplay, TrackClickAction};
 use dioxus::prelude::*;
 
 #[component]
@@ -33,12 +33,22 @@ pub fn AlbumDetail(album_id: String) -> Element {
         track_disc_info,
         releases,
         files: vec![],
+        videos: vec![],
         images: vec![],
         selected_release_id,
+        preferred_release_id: None,
         loading: false,
         error: None,
         import_progress: None,
         import_error: None,
+        archive_status: None,
+        reference: None,
+        dynamic_range: None,
+        stored_bytes: Default::default(),
+        comparison: None,
+        editing: false,
+        edit_error: None,
+        can_keep_offline: false,
     });
 
     // Get tracks lens for per-track reactivity
@@ -56,6 +66,7 @@ pub fn AlbumDetail(album_id: String) -> Element {
                 state,
                 tracks,
                 playback: PlaybackDisplay::Stopped,
+                track_click_action: TrackClickAction::Select,
                 on_release_select: |_release_id: String| {},
                 on_album_deleted: |_| {},
                 on_export_release: |_| {},
@@ -67,8 +78,19 @@ pub fn AlbumDetail(album_id: String) -> Element {
                 on_track_add_next: |_| {},
                 on_track_add_to_queue: |_| {},
                 on_track_export: |_| {},
+                on_track_rename: |_| {},
+                on_track_bookmark_jump: |_| {},
+                on_track_bookmark_delete: |_| {},
                 on_play_album: |_| {},
                 on_add_album_to_queue: |_| {},
+                on_restore_release: |_| {},
+                on_migrate_release: |_| {},
+                on_play_video: |_| {},
+                on_compare_releases: |_| {},
+                on_set_preferred_release: |_| {},
+                on_edit: |_| {},
+                on_cancel_edit: |_| {},
+                on_save_edits: |_| {},
             }
         } else {
             ErrorDisplay { message: "Album not found in demo data".to_string() }
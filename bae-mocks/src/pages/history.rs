@@ -0,0 +1,33 @@
+//! Listening history page
+
+use crate::demo_data;
+use crate::Route;
+use bae_ui::stores::HistoryState;
+use bae_ui::HistoryView;
+use dioxus::prelude::*;
+
+#[component]
+pub fn History() -> Element {
+    let state = use_store(|| HistoryState {
+        plays: demo_data::get_track_plays(),
+        top_tracks: demo_data::get_top_tracks(),
+        loading: false,
+        error: None,
+    });
+    let mut from = use_signal(String::new);
+    let mut to = use_signal(String::new);
+
+    rsx! {
+        HistoryView {
+            state,
+            from: from(),
+            to: to(),
+            on_from_change: move |value| from.set(value),
+            on_to_change: move |value| to.set(value),
+            on_play: |_| {},
+            on_album_click: move |album_id: String| {
+                navigator().push(Route::AlbumDetail { album_id });
+            },
+        }
+    }
+}
@@ -23,7 +23,7 @@ pub fn Import() -> Element {
             on_open_folder: |_| {},
 
             match *selected_source.read() {
-                ImportSource::Folder => rsx! {
+                ImportSource::Folder | ImportSource::Archive => rsx! {
                     FolderImportDemo {}
                 },
                 ImportSource::Torrent => rsx! {
@@ -4,9 +4,9 @@ use crate::demo_data;
 use crate::Route;
 use bae_ui::stores::{PlaybackStatus, PlaybackUiState, SidebarState, SidebarStateStoreExt};
 use bae_ui::{
-    ActiveImport, AppLayoutView, ImportStatus, ImportsButtonView, ImportsDropdownView, NavItem,
-    NowPlayingBarView, QueueItem, QueueSidebarView, SearchResult, TitleBarView, Track,
-    TrackImportState,
+    ActiveImport, AlbumPalette, AppLayoutView, ImportStatus, ImportsButtonView,
+    ImportsDropdownView, NavItem, NowPlayingBarView, OutputFormatInfo, QueueItem, QueueSidebarView,
+    SearchResult, SearchResultKind, TitleBarView, Track, TrackImportState,
 };
 use dioxus::prelude::*;
 
@@ -19,6 +19,8 @@ fn mock_playing_track() -> Track {
         duration_ms: Some(245_000),
         is_available: true,
         import_state: TrackImportState::Complete,
+        quality: None,
+        bookmarks: Vec::new(),
     }
 }
 
@@ -33,6 +35,12 @@ fn mock_active_imports() -> Vec<ActiveImport> {
             progress_percent: Some(67),
             release_id: Some("release-1".to_string()),
             cover_url: Some("/covers/the-midnight-signal_neon-frequencies.png".to_string()),
+            store_bytes_per_sec: None,
+            error_message: None,
+            can_pause: true,
+            can_resume: false,
+            can_cancel: true,
+            can_retry: false,
         },
         ActiveImport {
             import_id: "import-2".to_string(),
@@ -43,6 +51,12 @@ fn mock_active_imports() -> Vec<ActiveImport> {
             progress_percent: None,
             release_id: None,
             cover_url: None,
+            store_bytes_per_sec: None,
+            error_message: None,
+            can_pause: false,
+            can_resume: false,
+            can_cancel: true,
+            can_retry: false,
         },
         ActiveImport {
             import_id: "import-3".to_string(),
@@ -53,6 +67,12 @@ fn mock_active_imports() -> Vec<ActiveImport> {
             progress_percent: Some(100),
             release_id: Some("release-3".to_string()),
             cover_url: Some("/covers/velvet-mathematics_proof-by-induction.png".to_string()),
+            store_bytes_per_sec: Some(87 * 1024 * 1024),
+            error_message: None,
+            can_pause: false,
+            can_resume: false,
+            can_cancel: false,
+            can_retry: false,
         },
     ]
 }
@@ -68,9 +88,12 @@ fn mock_queue() -> Vec<QueueItem> {
                 duration_ms: Some(198_000),
                 is_available: true,
                 import_state: TrackImportState::Complete,
+                quality: None,
+                bookmarks: Vec::new(),
             },
             album_title: "Neon Frequencies".to_string(),
             cover_url: Some("/covers/the-midnight-signal_neon-frequencies.png".to_string()),
+            is_external: false,
         },
         QueueItem {
             track: Track {
@@ -81,9 +104,28 @@ fn mock_queue() -> Vec<QueueItem> {
                 duration_ms: Some(312_000),
                 is_available: true,
                 import_state: TrackImportState::Complete,
+                quality: None,
+                bookmarks: Vec::new(),
             },
             album_title: "Set Theory".to_string(),
             cover_url: Some("/covers/velvet-mathematics_set-theory.png".to_string()),
+            is_external: false,
+        },
+        QueueItem {
+            track: Track {
+                id: "external:/Users/demo/Downloads/field-recording.flac".to_string(),
+                title: "field-recording".to_string(),
+                track_number: None,
+                disc_number: None,
+                duration_ms: Some(143_000),
+                is_available: true,
+                import_state: TrackImportState::Complete,
+                quality: None,
+                bookmarks: Vec::new(),
+            },
+            album_title: "Downloads".to_string(),
+            cover_url: None,
+            is_external: true,
         },
     ]
 }
@@ -104,23 +146,43 @@ pub fn DemoLayout() -> Element {
         track: mock_track.clone(),
         album_title: "Neon Frequencies".to_string(),
         cover_url: Some("/covers/the-midnight-signal_neon-frequencies.png".to_string()),
+        is_external: false,
     };
 
     // Create playback store with mock data
     let playback_store = use_store(move || PlaybackUiState {
         status: PlaybackStatus::Playing,
-        queue: vec!["queue-track-1".to_string(), "queue-track-2".to_string()],
+        queue: vec![
+            "queue-track-1".to_string(),
+            "queue-track-2".to_string(),
+            "external:/Users/demo/Downloads/field-recording.flac".to_string(),
+        ],
         current_track_id: Some("mock-track-1".to_string()),
         current_release_id: Some("release-1".to_string()),
         current_track: Some(current_queue_item),
         queue_items: mock_queue(),
+        history_items: mock_queue().into_iter().take(2).collect(),
         position_ms: 45_000,
         duration_ms: 245_000,
         pregap_ms: None,
         artist_name: "The Midnight Signal".to_string(),
         cover_url: Some("/covers/the-midnight-signal_neon-frequencies.png".to_string()),
+        palette: Some(AlbumPalette {
+            dominant: "#1e2a5c".to_string(),
+            accent: "#e8703a".to_string(),
+        }),
         playback_error: None,
         repeat_mode: Default::default(),
+        shuffle: false,
+        output_format: Some(OutputFormatInfo {
+            device_name: "MacBook Pro Speakers".to_string(),
+            sample_rate: 48000,
+            channels: 2,
+            sample_format: "32-bit float".to_string(),
+            dither: None,
+            exclusive: false,
+        }),
+        pitch_semitones: 0,
     });
 
     // Create sidebar store
@@ -155,6 +217,7 @@ pub fn DemoLayout() -> Element {
                         .map(|a| a.name.clone())
                         .unwrap_or_else(|| "Unknown Artist".to_string());
                     SearchResult {
+                        kind: SearchResultKind::Album,
                         id: album.id,
                         title: album.title,
                         subtitle: artist_name,
@@ -172,6 +235,16 @@ pub fn DemoLayout() -> Element {
             label: "Library".to_string(),
             is_active: matches!(current_route, Route::Library {} | Route::AlbumDetail { .. }),
         },
+        NavItem {
+            id: "tracks".to_string(),
+            label: "Tracks".to_string(),
+            is_active: matches!(current_route, Route::Tracks {}),
+        },
+        NavItem {
+            id: "history".to_string(),
+            label: "History".to_string(),
+            is_active: matches!(current_route, Route::History {}),
+        },
         NavItem {
             id: "import".to_string(),
             label: "Import".to_string(),
@@ -190,6 +263,8 @@ pub fn DemoLayout() -> Element {
                     on_nav_click: move |id: String| {
                         let _ = match id.as_str() {
                             "library" => navigator().push(Route::Library {}),
+                            "tracks" => navigator().push(Route::Tracks {}),
+                            "history" => navigator().push(Route::History {}),
                             "import" => navigator().push(Route::Import {}),
                             _ => None,
                         };
@@ -205,6 +280,10 @@ pub fn DemoLayout() -> Element {
                         search_query.set(String::new());
                         navigator().push(Route::AlbumDetail { album_id });
                     },
+                    on_search_result_play: move |_| {
+                        show_search_results.set(false);
+                        search_query.set(String::new());
+                    },
                     show_search_results: show_search_results_read,
                     on_search_dismiss: move |_| show_search_results.set(false),
                     on_search_focus: move |_| {
@@ -229,6 +308,7 @@ pub fn DemoLayout() -> Element {
                             on_import_click: move |_id: String| imports_open.set(false),
                             on_import_dismiss: move |_id: String| {},
                             on_clear_all: move |_| {},
+                            on_view_queue: move |_| imports_open.set(false),
                         }
                     },
                     // No window drag/zoom on web
@@ -248,6 +328,8 @@ pub fn DemoLayout() -> Element {
                         sidebar_is_open.set(!current);
                     },
                     on_track_click: move |_track_id: String| {},
+                    on_add_bookmark: |_| {},
+                    on_pitch_change: move |_semitones| {},
                 }
             },
             queue_sidebar: rsx! {
@@ -256,8 +338,14 @@ pub fn DemoLayout() -> Element {
                     playback: playback_store,
                     on_close: move |_| sidebar_is_open.set(false),
                     on_clear: move |_| {},
+                    on_open_files: move |_| {},
                     on_remove: move |_idx| {},
                     on_track_click: move |_track_id: String| {},
+                    on_history_play_next: move |_track_id: String| {},
+                    on_history_add_to_queue: move |_track_id: String| {},
+                    on_clear_history: move |_| {},
+                    on_toggle_shuffle: move |_| {},
+                    on_cycle_repeat: move |_| {},
                 }
             },
             Outlet::<Route> {}
@@ -3,22 +3,22 @@
 use crate::demo_data;
 use crate::Route;
 use bae_ui::stores::LibraryState;
-use bae_ui::{Album, Artist, LibraryView};
+use bae_ui::{LibrarySort, LibraryView};
 use dioxus::prelude::*;
-use std::collections::HashMap;
-
-// TODO: Remove this - temporary for testing large libraries
-const ALBUM_COUNT: usize = 2000;
 
 #[component]
 pub fn Library() -> Element {
-    let (albums, artists_by_album) = generate_albums(ALBUM_COUNT);
+    // Large libraries come from a generated fixture (see demo_data)
+    let albums = demo_data::get_albums();
+    let artists_by_album = demo_data::get_artists_by_album();
 
     let state = use_store(|| LibraryState {
         albums,
         artists_by_album,
         loading: false,
         error: None,
+        find_replace_preview: None,
+        find_replace_error: None,
     });
 
     rsx! {
@@ -30,41 +30,11 @@ pub fn Library() -> Element {
             on_play_album: |_| {},
             on_add_album_to_queue: |_| {},
             on_empty_action: |_| {},
+            default_sort: LibrarySort::Title,
+            default_quality_filter: None,
+            on_find_replace_preview: |_| {},
+            on_find_replace_apply: |_| {},
+            on_find_replace_reset: |_| {},
         }
     }
 }
-
-fn generate_albums(count: usize) -> (Vec<Album>, HashMap<String, Vec<Artist>>) {
-    let base_albums = demo_data::get_albums();
-    let base_artists = demo_data::get_artists_by_album();
-
-    let mut albums = Vec::with_capacity(count);
-    let mut artists_by_album = HashMap::new();
-
-    for i in 0..count {
-        let idx = i % base_albums.len();
-        let base = &base_albums[idx];
-
-        // First batch uses real IDs (so album detail navigation works),
-        // subsequent batches use generated IDs
-        let id = if i < base_albums.len() {
-            base.id.clone()
-        } else {
-            format!("album-{}", i + 1)
-        };
-
-        albums.push(Album {
-            id: id.clone(),
-            title: base.title.clone(),
-            year: base.year,
-            cover_url: base.cover_url.clone(),
-            is_compilation: base.is_compilation,
-        });
-
-        if let Some(artists) = base_artists.get(&base.id) {
-            artists_by_album.insert(id, artists.clone());
-        }
-    }
-
-    (albums, artists_by_album)
-}
@@ -13,6 +13,12 @@ fn generate_test_albums() -> Vec<(Album, Vec<Artist>)> {
                 year: Some(2020 + (i % 5)),
                 cover_url: None,
                 is_compilation: false,
+                date_added: 1_700_000_000 + i as i64 * 86_400,
+                play_count: i as u32,
+                last_played: None,
+                palette: None,
+                quality: None,
+                is_offline: false,
             };
             let artist = Artist {
                 id: format!("artist-{}", i),
@@ -1,14 +1,17 @@
 //! Demo and mock pages
 
 mod album_detail;
+mod history;
 mod import;
 mod layout;
 mod library;
 mod mock_dropdown;
 mod mock_index;
 mod settings;
+mod tracks;
 
 pub use album_detail::AlbumDetail;
+pub use history::History;
 pub use import::Import;
 pub use layout::DemoLayout;
 pub use library::Library;
@@ -18,3 +21,4 @@ pub use mock_index::{
     MockTextInput, MockTitleBar, MockTooltip,
 };
 pub use settings::Settings;
+pub use tracks::Tracks;
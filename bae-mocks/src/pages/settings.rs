@@ -2,8 +2,15 @@
 
 use bae_ui::{
     AboutSectionView, ApiKeysSectionView, BitTorrentSectionView, BitTorrentSettings,
-    EncryptionSectionView, SettingsTab, SettingsView, StorageLocation, StorageProfile,
-    StorageProfilesSectionView, SubsonicSectionView,
+    CdInsertAction, CdRippingSectionView, ClientShare, ClientSharesView, CloudProvider, Dither,
+    EncryptionSectionView, FeaturedCreditStyle, FilenamePolicy, FolderPatternPreset,
+    FolderPatternTest, ImportingSectionView, LibrarySectionView, LibrarySnapshot, LibrarySort,
+    MaintenanceSectionView, NetworkSectionView, OutputBitDepth, PlacementPolicy,
+    PlaybackSectionView, QuotaLevel, ReleaseTemplate, RemoteSectionView, ScrobblingSectionView,
+    SeedingSectionView, SeedingTorrent, ServiceHealth, ServiceKind, ServiceStatus, SettingsTab,
+    SettingsView, ShareableAlbum, StartupView, StorageLocation, StorageProfile,
+    StorageProfilesSectionView, SubsonicSectionView, TrackClickAction, VerificationIssue,
+    VerificationIssueKind, VerificationRun,
 };
 use dioxus::prelude::*;
 
@@ -29,6 +36,57 @@ pub fn Settings() -> Element {
                         on_set_default: |_| {},
                         on_save: |_| {},
                         on_cancel_edit: |_| {},
+                        cloud_price_per_gb: 0.023,
+                        on_cloud_price_change: |_| {},
+                    }
+                },
+                SettingsTab::Library => rsx! {
+                    LibrarySectionView {
+                        startup_view: StartupView::LastRoute,
+                        library_sort: LibrarySort::DateAdded,
+                        quality_filter: None,
+                        on_startup_view_change: |_| {},
+                        on_library_sort_change: |_| {},
+                        on_quality_filter_change: |_| {},
+                        track_click_action: TrackClickAction::Select,
+                        on_track_click_action_change: |_| {},
+                        featured_credit_style: FeaturedCreditStyle::Standardize,
+                        on_featured_credit_style_change: |_| {},
+                        is_renormalizing_credits: false,
+                        credits_result: None,
+                        on_renormalize_credits: |_| {},
+                        snapshot_profiles: mock_storage_profiles()
+                            .into_iter()
+                            .filter(|profile| profile.location == StorageLocation::Cloud)
+                            .collect::<Vec<_>>(),
+                        snapshot_profile_id: Some("profile-1".to_string()),
+                        snapshot_interval_hours: Some(24),
+                        snapshots_kept: 7,
+                        snapshots: mock_snapshots(),
+                        is_snapshotting: false,
+                        snapshot_result: None,
+                        on_snapshot_profile_change: |_| {},
+                        on_snapshot_interval_change: |_| {},
+                        on_snapshots_kept_change: |_| {},
+                        on_snapshot_now: |_| {},
+                    }
+                },
+                SettingsTab::Maintenance => rsx! {
+                    MaintenanceSectionView {
+                        verification_interval_days: Some(30),
+                        verification_runs: mock_verification_runs(),
+                        is_verifying: false,
+                        on_verification_interval_change: |_| {},
+                        on_verify_now: |_| {},
+                        repairing_issue_id: None,
+                        repair_error: None,
+                        on_repair_issue: |_| {},
+                        is_compacting_database: false,
+                        database_result: None,
+                        on_compact_database: |_| {},
+                        is_clearing_cache: false,
+                        cache_result: Some("Removed 312 files (1.4 GB)".to_string()),
+                        on_clear_cache: |_| {},
                     }
                 },
                 SettingsTab::ApiKeys => rsx! {
@@ -52,6 +110,75 @@ pub fn Settings() -> Element {
                         key_length: 32,
                     }
                 },
+                SettingsTab::Playback => rsx! {
+                    PlaybackSectionView {
+                        output_bit_depth: OutputBitDepth::Auto,
+                        dither: Dither::Tpdf,
+                        output_devices: vec![
+                            "MacBook Pro Speakers".to_string(),
+                            "USB Audio DAC".to_string(),
+                        ],
+                        output_device: Some("USB Audio DAC".to_string()),
+                        exclusive_devices: vec!["USB Audio DAC".to_string()],
+                        exclusive_supported: true,
+                        skip_silence: true,
+                        min_silence_ms: 2000,
+                        in_background: true,
+                        decode_ahead_ms: 100,
+                        gapless_preroll_ms: 100,
+                        min_ms: 50,
+                        max_ms: 5000,
+                        is_editing: false,
+                        edit_decode_ahead_ms: "100".to_string(),
+                        edit_gapless_preroll_ms: "100".to_string(),
+                        is_saving: false,
+                        has_changes: false,
+                        save_error: None,
+                        on_edit_start: |_| {},
+                        on_cancel: |_| {},
+                        on_save: |_| {},
+                        on_decode_ahead_change: |_| {},
+                        on_gapless_preroll_change: |_| {},
+                        on_output_device_change: |_| {},
+                        on_output_bit_depth_change: |_| {},
+                        on_dither_change: |_| {},
+                        on_exclusive_change: |_| {},
+                        on_skip_silence_change: |_| {},
+                        on_min_silence_change: |_| {},
+                        on_in_background_change: |_| {},
+                    }
+                },
+                SettingsTab::Scrobbling => rsx! {
+                    ScrobblingSectionView {
+                        lastfm_username: Some("listener".to_string()),
+                        lastfm_api_key: String::new(),
+                        lastfm_api_secret: String::new(),
+                        lastfm_awaiting_approval: false,
+                        listenbrainz_connected: false,
+                        listenbrainz_token: String::new(),
+                        pending_scrobbles: 3,
+                        is_busy: false,
+                        error: None,
+                        on_lastfm_api_key_change: |_| {},
+                        on_lastfm_api_secret_change: |_| {},
+                        on_lastfm_connect: |_| {},
+                        on_lastfm_finish: |_| {},
+                        on_lastfm_disconnect: |_| {},
+                        on_listenbrainz_token_change: |_| {},
+                        on_listenbrainz_connect: |_| {},
+                        on_listenbrainz_disconnect: |_| {},
+                    }
+                },
+                SettingsTab::Network => rsx! {
+                    NetworkSectionView {
+                        metered_mode: true,
+                        allow_cloud_prefetch: false,
+                        allow_torrents: false,
+                        on_metered_mode_change: |_| {},
+                        on_allow_cloud_prefetch_change: |_| {},
+                        on_allow_torrents_change: |_| {},
+                    }
+                },
                 SettingsTab::BitTorrent => rsx! {
                     BitTorrentSectionView {
                         settings: BitTorrentSettings {
@@ -63,6 +190,9 @@ pub fn Settings() -> Element {
                             max_uploads: Some(10),
                             max_uploads_per_torrent: Some(5),
                             bind_interface: None,
+                            enable_ipv6: true,
+                            enable_dht: true,
+                            dht_bootstrap_nodes: vec!["dht.transmissionbt.com:6881".to_string()],
                         },
                         editing_section: None,
                         edit_listen_port: String::new(),
@@ -72,6 +202,9 @@ pub fn Settings() -> Element {
                         edit_max_uploads: String::new(),
                         edit_max_uploads_per_torrent: String::new(),
                         edit_bind_interface: String::new(),
+                        edit_enable_ipv6: true,
+                        edit_enable_dht: true,
+                        edit_dht_bootstrap_nodes: String::new(),
                         is_saving: false,
                         has_changes: false,
                         save_error: None,
@@ -85,15 +218,111 @@ pub fn Settings() -> Element {
                         on_max_uploads_change: |_| {},
                         on_max_uploads_per_torrent_change: |_| {},
                         on_bind_interface_change: |_| {},
+                        on_enable_ipv6_change: |_| {},
+                        on_enable_dht_change: |_| {},
+                        on_dht_bootstrap_nodes_change: |_| {},
+                    }
+                },
+                SettingsTab::Seeding => rsx! {
+                    SeedingSectionView {
+                        torrents: vec![
+                            SeedingTorrent {
+                                release_id: "release-1".to_string(),
+                                name: "Kind of Blue (1959) [FLAC]".to_string(),
+                                is_seeding: true,
+                                size_bytes: 312_000_000,
+                                uploaded_bytes: 421_000_000,
+                                ratio: 1.35,
+                                seeding_secs: 3 * 86400 + 5 * 3600,
+                                ratio_goal: Some(2.0),
+                                time_goal_hours: None,
+                                goal_met: false,
+                            },
+                            SeedingTorrent {
+                                release_id: "release-2".to_string(),
+                                name: "In Rainbows (2007) [24-96]".to_string(),
+                                is_seeding: false,
+                                size_bytes: 890_000_000,
+                                uploaded_bytes: 1_020_000_000,
+                                ratio: 1.15,
+                                seeding_secs: 8 * 86400,
+                                ratio_goal: Some(1.0),
+                                time_goal_hours: Some(168),
+                                goal_met: true,
+                            },
+                        ],
+                        error: None,
+                        on_pause: |_| {},
+                        on_resume: |_| {},
+                        on_remove: |_| {},
+                        on_goals_change: |_| {},
+                    }
+                },
+                SettingsTab::Importing => rsx! {
+                    ImportingSectionView {
+                        edit_patterns: "{artist}/{year} - {album} [{format}]\n{artist} - {album} ({year})"
+                            .to_string(),
+                        presets: vec![
+                            FolderPatternPreset {
+                                name: "Artist - Album".to_string(),
+                                template: "{artist} - {album}".to_string(),
+                            },
+                            FolderPatternPreset {
+                                name: "Artist/Year - Album".to_string(),
+                                template: "{artist}/{year} - {album}".to_string(),
+                            },
+                        ],
+                        has_changes: false,
+                        save_error: None,
+                        sample_path: "/music/Miles Davis/1959 - Kind of Blue [FLAC]".to_string(),
+                        sample_result: Some(FolderPatternTest::Matched {
+                            template: "{artist}/{year} - {album} [{format}]".to_string(),
+                            fields: vec![
+                                ("Artist".to_string(), "Miles Davis".to_string()),
+                                ("Album".to_string(), "Kind of Blue".to_string()),
+                                ("Year".to_string(), "1959".to_string()),
+                                ("Format".to_string(), "FLAC".to_string()),
+                            ],
+                        }),
+                        on_patterns_change: |_| {},
+                        on_add_preset: |_| {},
+                        on_save: |_| {},
+                        on_cancel: |_| {},
+                        on_sample_path_change: |_| {},
+                        release_templates: vec![
+                            ReleaseTemplate {
+                                id: "template-1".to_string(),
+                                artist: "Night Shift Radio".to_string(),
+                                title_pattern: "Night Shift Mix Vol. {n}".to_string(),
+                                next_title: "Night Shift Mix Vol. 13".to_string(),
+                                cover_url: None,
+                            },
+                        ],
+                        on_remove_release_template: |_| {},
+                        low_power_import: false,
+                        on_low_power_import_change: |_| {},
+                        max_concurrent_imports: 1,
+                        on_max_concurrent_imports_change: |_| {},
+                    }
+                },
+                SettingsTab::CdRipping => rsx! {
+                    CdRippingSectionView {
+                        insert_action: CdInsertAction::AutoRip,
+                        eject_after_rip: true,
+                        on_insert_action_change: |_| {},
+                        on_eject_after_rip_change: |_| {},
                     }
                 },
                 SettingsTab::Subsonic => rsx! {
                     SubsonicSectionView {
                         enabled: true,
                         port: 4533,
+                        transcode_bitrate_kbps: 128,
+                        transcode_bitrates_kbps: vec![64, 96, 128, 160, 192, 256],
                         is_editing: false,
                         edit_enabled: true,
                         edit_port: "4533".to_string(),
+                        edit_transcode_bitrate_kbps: 128,
                         is_saving: false,
                         has_changes: false,
                         save_error: None,
@@ -102,6 +331,55 @@ pub fn Settings() -> Element {
                         on_save: |_| {},
                         on_enabled_change: |_| {},
                         on_port_change: |_| {},
+                        on_transcode_bitrate_change: |_| {},
+                    }
+                    div { class: "max-w-2xl mt-6",
+                        ClientSharesView {
+                            shares: vec![
+                                ClientShare {
+                                    id: "share-1".to_string(),
+                                    name: "Phone".to_string(),
+                                    token: "4f1c2a9d8e7b4c3a9f0e1d2c3b4a5f6e".to_string(),
+                                    album_ids: vec!["album-1".to_string()],
+                                },
+                            ],
+                            albums: vec![
+                                ShareableAlbum {
+                                    id: "album-1".to_string(),
+                                    title: "Kind of Blue".to_string(),
+                                    artist: "Miles Davis".to_string(),
+                                },
+                                ShareableAlbum {
+                                    id: "album-2".to_string(),
+                                    title: "A Love Supreme".to_string(),
+                                    artist: "John Coltrane".to_string(),
+                                },
+                            ],
+                            error: None,
+                            on_create: |_| {},
+                            on_delete: |_| {},
+                            on_set_album_shared: |_| {},
+                        }
+                    }
+                },
+                SettingsTab::Remote => rsx! {
+                    RemoteSectionView {
+                        enabled: true,
+                        port: 4534,
+                        pairing_url: Some("http://192.168.1.20:4534/#token=4f1c2a9d8e7b4c3a9f0e1d2c3b4a5f6e".to_string()),
+                        pairing_qr_url: None,
+                        is_editing: false,
+                        edit_enabled: true,
+                        edit_port: "4534".to_string(),
+                        is_saving: false,
+                        has_changes: false,
+                        save_error: None,
+                        on_edit_start: |_| {},
+                        on_cancel: |_| {},
+                        on_save: |_| {},
+                        on_enabled_change: |_| {},
+                        on_port_change: |_| {},
+                        on_unpair: |_| {},
                     }
                 },
                 SettingsTab::About => rsx! {
@@ -109,6 +387,11 @@ pub fn Settings() -> Element {
                         version: "0.1.0-demo".to_string(),
                         album_count: 20,
                         on_check_updates: |_| {},
+                        services: mock_service_health(),
+                        is_checking_health: false,
+                        restarting: None,
+                        on_check_health: |_| {},
+                        on_restart_service: |_| {},
                     }
                 },
             }
@@ -123,26 +406,173 @@ fn mock_storage_profiles() -> Vec<StorageProfile> {
             name: "Cloud Storage".to_string(),
             location: StorageLocation::Cloud,
             location_path: String::new(),
+            extra_location_paths: Vec::new(),
+            placement_policy: PlacementPolicy::FillFirst,
             encrypted: true,
+            compressed: true,
+            filename_policy: FilenamePolicy::Replace,
             is_default: true,
+            cloud_provider: CloudProvider::S3,
             cloud_bucket: Some("my-music-bucket".to_string()),
             cloud_region: Some("us-east-1".to_string()),
             cloud_endpoint: None,
             cloud_access_key: Some("AKIA***".to_string()),
             cloud_secret_key: Some("***".to_string()),
+            cloud_archival: false,
+            quota_bytes: Some(500 * 1024 * 1024 * 1024),
+            used_bytes: 431 * 1024 * 1024 * 1024,
+            quota_level: Some(QuotaLevel::Warning),
         },
         StorageProfile {
             id: "profile-2".to_string(),
             name: "Local Backup".to_string(),
             location: StorageLocation::Local,
             location_path: "/Users/demo/Music/bae".to_string(),
+            extra_location_paths: vec!["/Volumes/Archive/bae".to_string()],
+            placement_policy: PlacementPolicy::FillFirst,
             encrypted: false,
+            compressed: false,
+            filename_policy: FilenamePolicy::Transliterate,
             is_default: false,
+            cloud_provider: CloudProvider::S3,
             cloud_bucket: None,
             cloud_region: None,
             cloud_endpoint: None,
             cloud_access_key: None,
             cloud_secret_key: None,
+            cloud_archival: false,
+            quota_bytes: None,
+            used_bytes: 87 * 1024 * 1024 * 1024,
+            quota_level: None,
+        },
+        StorageProfile {
+            id: "profile-3".to_string(),
+            name: "NAS".to_string(),
+            location: StorageLocation::Reference,
+            location_path: "/Volumes/nas/music".to_string(),
+            extra_location_paths: Vec::new(),
+            placement_policy: PlacementPolicy::FillFirst,
+            encrypted: false,
+            compressed: false,
+            filename_policy: FilenamePolicy::Replace,
+            is_default: false,
+            cloud_provider: CloudProvider::S3,
+            cloud_bucket: None,
+            cloud_region: None,
+            cloud_endpoint: None,
+            cloud_access_key: None,
+            cloud_secret_key: None,
+            cloud_archival: false,
+            quota_bytes: None,
+            used_bytes: 2 * 1024 * 1024 * 1024 * 1024,
+            quota_level: None,
+        },
+    ]
+}
+
+fn mock_snapshots() -> Vec<LibrarySnapshot> {
+    [1_761_969_600, 1_761_883_200, 1_761_796_800]
+        .into_iter()
+        .enumerate()
+        .map(|(i, created_at)| LibrarySnapshot {
+            location: format!("s3://my-music-bucket/files/snapshot-{}", i),
+            created_at,
+            size_bytes: 48 * 1024 * 1024 - i as u64 * 512 * 1024,
+        })
+        .collect()
+}
+
+fn mock_service_health() -> Vec<ServiceHealth> {
+    let running = |kind: ServiceKind, uptime_secs: u64| ServiceHealth {
+        kind,
+        status: ServiceStatus::Running,
+        uptime_secs: Some(uptime_secs),
+        last_error: None,
+        last_error_at: None,
+        can_restart: matches!(
+            kind,
+            ServiceKind::Cache | ServiceKind::CloudStorage | ServiceKind::PlaybackOutput
+        ),
+    };
+    vec![
+        running(ServiceKind::Database, 3 * 3600 + 12 * 60),
+        running(ServiceKind::Cache, 3 * 3600 + 12 * 60),
+        ServiceHealth {
+            last_error: Some("Backup Bucket: connection timed out".to_string()),
+            last_error_at: Some(1_761_969_600),
+            ..running(ServiceKind::CloudStorage, 25 * 60)
+        },
+        ServiceHealth {
+            status: ServiceStatus::NotStarted,
+            uptime_secs: None,
+            ..running(ServiceKind::Torrent, 0)
+        },
+        ServiceHealth {
+            status: ServiceStatus::Failed,
+            uptime_secs: None,
+            last_error: Some("Audio device not found".to_string()),
+            last_error_at: Some(1_761_975_000),
+            ..running(ServiceKind::PlaybackOutput, 0)
+        },
+        running(ServiceKind::Keyring, 3 * 3600 + 12 * 60),
+    ]
+}
+
+fn mock_verification_runs() -> Vec<VerificationRun> {
+    let damaged = VerificationIssue {
+        id: "issue-1".to_string(),
+        kind: VerificationIssueKind::Corrupt,
+        filename: "CD1/04 - Interlude.flac".to_string(),
+        storage_profile_name: "External Drive".to_string(),
+        error: "3 decode errors in audio stream".to_string(),
+        first_seen: 1_759_276_800,
+        repaired: false,
+    };
+    vec![
+        VerificationRun {
+            id: "run-3".to_string(),
+            scheduled: true,
+            started_at: 1_761_955_200,
+            finished: true,
+            files_checked: 4_812,
+            issues: vec![
+                VerificationIssue {
+                    id: "issue-2".to_string(),
+                    ..damaged.clone()
+                },
+                VerificationIssue {
+                    id: "issue-3".to_string(),
+                    kind: VerificationIssueKind::Missing,
+                    filename: "05 - Coda.flac".to_string(),
+                    error: "Not found in storage".to_string(),
+                    first_seen: 1_761_955_200,
+                    ..damaged.clone()
+                },
+                VerificationIssue {
+                    id: "issue-4".to_string(),
+                    kind: VerificationIssueKind::Orphaned,
+                    filename: "0b7f3c2e-5d1a-4e8b-9c6f-2a4d8e1b7c3f/cover (1).jpg".to_string(),
+                    error: "Not part of any release".to_string(),
+                    first_seen: 1_761_955_200,
+                    ..damaged.clone()
+                },
+            ],
+        },
+        VerificationRun {
+            id: "run-2".to_string(),
+            scheduled: true,
+            started_at: 1_759_276_800,
+            finished: true,
+            files_checked: 4_790,
+            issues: vec![damaged],
+        },
+        VerificationRun {
+            id: "run-1".to_string(),
+            scheduled: false,
+            started_at: 1_756_684_800,
+            finished: true,
+            files_checked: 4_702,
+            issues: Vec::new(),
         },
     ]
 }
@@ -0,0 +1,28 @@
+//! Tracks page
+
+use crate::demo_data;
+use crate::Route;
+use bae_ui::stores::TracksState;
+use bae_ui::{TrackClickAction, TracksView};
+use dioxus::prelude::*;
+
+#[component]
+pub fn Tracks() -> Element {
+    let state = use_store(|| TracksState {
+        tracks: demo_data::get_library_tracks(),
+        loading: false,
+        error: None,
+    });
+
+    rsx! {
+        TracksView {
+            state,
+            click_action: TrackClickAction::Select,
+            on_play: |_| {},
+            on_add_to_queue: |_| {},
+            on_album_click: move |album_id: String| {
+                navigator().push(Route::AlbumDetail { album_id });
+            },
+        }
+    }
+}
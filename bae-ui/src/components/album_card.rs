@@ -1,7 +1,7 @@
 //! Album card component - pure view with callbacks
 
-use crate::components::icons::{EllipsisIcon, ImageIcon, PlayIcon, PlusIcon};
-use crate::components::{MenuDropdown, MenuItem, Placement};
+use crate::components::icons::{DownloadIcon, EllipsisIcon, ImageIcon, PlayIcon, PlusIcon};
+use crate::components::{MenuDropdown, MenuItem, Placement, QualityBadge};
 use crate::display_types::{Album, Artist};
 use dioxus::prelude::*;
 
@@ -23,6 +23,8 @@ pub fn AlbumCard(
     let album_title = album.title.clone();
     let album_year = album.year;
     let cover_url = album.cover_url.clone();
+    let quality = album.quality;
+    let is_offline = album.is_offline;
 
     let mut show_dropdown = use_signal(|| false);
     let is_open: ReadSignal<bool> = show_dropdown.into();
@@ -94,8 +96,22 @@ pub fn AlbumCard(
                     title: "{artist_name}",
                     "{artist_name}"
                 }
-                if let Some(year) = album_year {
-                    p { class: "text-gray-500 text-xs mt-1", "{year}" }
+                if album_year.is_some() || quality.is_some() || is_offline {
+                    div { class: "flex items-center gap-2 mt-1",
+                        if let Some(year) = album_year {
+                            p { class: "text-gray-500 text-xs", "{year}" }
+                        }
+                        if let Some(quality) = quality {
+                            QualityBadge { quality }
+                        }
+                        if is_offline {
+                            span {
+                                class: "text-sky-300",
+                                title: "Available offline",
+                                DownloadIcon { class: "w-3.5 h-3.5" }
+                            }
+                        }
+                    }
                 }
             }
 
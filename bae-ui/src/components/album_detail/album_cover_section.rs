@@ -20,6 +20,16 @@ pub fn AlbumCoverSection(
     on_delete_album: EventHandler<String>,
     on_view_release_info: EventHandler<String>,
     on_open_gallery: EventHandler<String>,
+    on_edit: EventHandler<()>,
+    /// Export the release as a package for someone without bae
+    #[props(default)]
+    on_export_gift: Option<EventHandler<String>>,
+    /// Whether the album has cloud files that could be kept offline
+    #[props(default)]
+    can_keep_offline: bool,
+    /// Called with true to keep the album offline, false to drop the offline copy
+    #[props(default)]
+    on_set_offline: Option<EventHandler<bool>>,
 ) -> Element {
     let mut show_dropdown = use_signal(|| false);
     let is_open: ReadSignal<bool> = show_dropdown.into();
@@ -115,8 +125,48 @@ pub fn AlbumCoverSection(
                                 "Export"
                             }
                         }
+                        if let Some(handler) = on_export_gift {
+                            MenuItem {
+                                disabled: is_deleting || is_exporting,
+                                onclick: {
+                                    let release_id = release_id.clone();
+                                    move |_| {
+                                        show_dropdown.set(false);
+                                        handler.call(release_id.clone());
+                                    }
+                                },
+                                "Export as Gift..."
+                            }
+                        }
+                    }
+                }
+                if can_keep_offline {
+                    if let Some(handler) = on_set_offline {
+                        MenuItem {
+                            disabled: is_deleting,
+                            onclick: {
+                                let is_offline = album.is_offline;
+                                move |_| {
+                                    show_dropdown.set(false);
+                                    handler.call(!is_offline);
+                                }
+                            },
+                            if album.is_offline {
+                                "Remove Offline Copy"
+                            } else {
+                                "Keep Offline"
+                            }
+                        }
                     }
                 }
+                MenuItem {
+                    disabled: is_deleting || is_exporting,
+                    onclick: move |_| {
+                        show_dropdown.set(false);
+                        on_edit.call(());
+                    },
+                    "Edit"
+                }
                 MenuItem {
                     disabled: is_deleting,
                     danger: true,
@@ -0,0 +1,348 @@
+//! Edit mode for an album's metadata
+
+use crate::components::icons::{PlusIcon, XIcon};
+use crate::components::{
+    Button, ButtonSize, ButtonVariant, ChromelessButton, TextInput, TextInputSize,
+};
+use crate::display_types::{Album, AlbumEdit, Artist, Release, ReleaseEdit, Track, TrackEdit};
+use dioxus::prelude::*;
+
+/// A track's fields as typed
+#[derive(Clone, Debug, PartialEq)]
+struct TrackDraft {
+    id: String,
+    title: String,
+    disc_number: String,
+    track_number: String,
+}
+
+/// A release's fields as typed
+#[derive(Clone, Debug, PartialEq, Default)]
+struct ReleaseDraft {
+    release_name: String,
+    year: String,
+    format: String,
+    label: String,
+    catalog_number: String,
+    country: String,
+    barcode: String,
+}
+
+/// Form for fixing an album's title, year and artists, the viewed release's
+/// details, and its track titles and numbers. Nothing is written until Save.
+#[component]
+pub fn AlbumEditor(
+    album: Album,
+    artists: Vec<Artist>,
+    /// The release being viewed
+    release: Option<Release>,
+    tracks: Vec<Track>,
+    /// Why the last save failed
+    error: Option<String>,
+    on_save: EventHandler<AlbumEdit>,
+    on_cancel: EventHandler<()>,
+) -> Element {
+    let initial_title = album.title.clone();
+    let mut title = use_signal(move || initial_title);
+    let mut year = use_signal(move || album.year.map(|y| y.to_string()).unwrap_or_default());
+    let mut artist_names = use_signal(move || {
+        let names: Vec<String> = artists.iter().map(|a| a.name.clone()).collect();
+        if names.is_empty() {
+            vec![String::new()]
+        } else {
+            names
+        }
+    });
+    let initial_release = release.clone();
+    let mut release_draft = use_signal(move || {
+        initial_release
+            .as_ref()
+            .map(|r| ReleaseDraft {
+                release_name: r.release_name.clone().unwrap_or_default(),
+                year: r.year.map(|y| y.to_string()).unwrap_or_default(),
+                format: r.format.clone().unwrap_or_default(),
+                label: r.label.clone().unwrap_or_default(),
+                catalog_number: r.catalog_number.clone().unwrap_or_default(),
+                country: r.country.clone().unwrap_or_default(),
+                barcode: r.barcode.clone().unwrap_or_default(),
+            })
+            .unwrap_or_default()
+    });
+    let mut track_drafts = use_signal(move || {
+        tracks
+            .iter()
+            .map(|t| TrackDraft {
+                id: t.id.clone(),
+                title: t.title.clone(),
+                disc_number: t.disc_number.map(|n| n.to_string()).unwrap_or_default(),
+                track_number: t.track_number.map(|n| n.to_string()).unwrap_or_default(),
+            })
+            .collect::<Vec<_>>()
+    });
+    let mut form_error = use_signal(|| Option::<String>::None);
+
+    let album_id = album.id.clone();
+    let release_id = release.as_ref().map(|r| r.id.clone());
+    let save = move |_| {
+        let release = release_draft.read();
+        let edit = build_edit(
+            &album_id,
+            &title.read(),
+            &year.read(),
+            &artist_names.read(),
+            release_id.as_deref().map(|id| (id, &*release)),
+            &track_drafts.read(),
+        );
+        match edit {
+            Ok(edit) => {
+                form_error.set(None);
+                on_save.call(edit);
+            }
+            Err(e) => form_error.set(Some(e)),
+        }
+    };
+
+    let error = form_error().or(error);
+
+    rsx! {
+        div { class: "space-y-6",
+            div { class: "flex items-center justify-between",
+                h2 { class: "text-lg font-semibold text-white", "Edit Album" }
+                div { class: "flex gap-2",
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        onclick: move |_| on_cancel.call(()),
+                        "Cancel"
+                    }
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        size: ButtonSize::Small,
+                        onclick: save,
+                        "Save"
+                    }
+                }
+            }
+
+            if let Some(error) = error {
+                p { class: "text-sm text-red-400", "{error}" }
+            }
+
+            // Album
+            div { class: "grid grid-cols-[8rem_1fr] gap-x-4 gap-y-3 items-center text-sm",
+                label { class: "text-gray-400", "Title" }
+                TextInput {
+                    value: title(),
+                    on_input: move |v| title.set(v),
+                    size: TextInputSize::Small,
+                }
+                label { class: "text-gray-400", "Year" }
+                div { class: "w-24",
+                    TextInput {
+                        value: year(),
+                        on_input: move |v| year.set(v),
+                        size: TextInputSize::Small,
+                    }
+                }
+                label { class: "text-gray-400 self-start pt-1.5", "Artists" }
+                div { class: "space-y-2",
+                    for (i , name) in artist_names().into_iter().enumerate() {
+                        div { key: "{i}", class: "flex items-center gap-2",
+                            TextInput {
+                                value: name,
+                                on_input: move |v| artist_names.with_mut(|names| names[i] = v),
+                                size: TextInputSize::Small,
+                            }
+                            if artist_names.read().len() > 1 {
+                                ChromelessButton {
+                                    class: Some("p-1 text-gray-500 hover:text-white".to_string()),
+                                    title: Some("Remove artist".to_string()),
+                                    aria_label: Some("Remove artist".to_string()),
+                                    onclick: move |_| {
+                                        artist_names.with_mut(|names| {
+                                            names.remove(i);
+                                        });
+                                    },
+                                    XIcon { class: "w-4 h-4" }
+                                }
+                            }
+                        }
+                    }
+                    ChromelessButton {
+                        class: Some(
+                            "flex items-center gap-1 text-gray-400 hover:text-white text-sm".to_string(),
+                        ),
+                        onclick: move |_| artist_names.with_mut(|names| names.push(String::new())),
+                        PlusIcon { class: "w-4 h-4" }
+                        "Add artist"
+                    }
+                }
+            }
+
+            // Release
+            if release.is_some() {
+                div { class: "space-y-3",
+                    h3 { class: "text-sm font-semibold text-gray-400 uppercase tracking-wide",
+                        "Release"
+                    }
+                    div { class: "grid grid-cols-[8rem_1fr] gap-x-4 gap-y-3 items-center text-sm",
+                        ReleaseField {
+                            label: "Name",
+                            value: release_draft.read().release_name.clone(),
+                            on_input: move |v| release_draft.with_mut(|r| r.release_name = v),
+                        }
+                        ReleaseField {
+                            label: "Year",
+                            value: release_draft.read().year.clone(),
+                            on_input: move |v| release_draft.with_mut(|r| r.year = v),
+                        }
+                        ReleaseField {
+                            label: "Format",
+                            value: release_draft.read().format.clone(),
+                            on_input: move |v| release_draft.with_mut(|r| r.format = v),
+                        }
+                        ReleaseField {
+                            label: "Label",
+                            value: release_draft.read().label.clone(),
+                            on_input: move |v| release_draft.with_mut(|r| r.label = v),
+                        }
+                        ReleaseField {
+                            label: "Catalog number",
+                            value: release_draft.read().catalog_number.clone(),
+                            on_input: move |v| release_draft.with_mut(|r| r.catalog_number = v),
+                        }
+                        ReleaseField {
+                            label: "Country",
+                            value: release_draft.read().country.clone(),
+                            on_input: move |v| release_draft.with_mut(|r| r.country = v),
+                        }
+                        ReleaseField {
+                            label: "Barcode",
+                            value: release_draft.read().barcode.clone(),
+                            on_input: move |v| release_draft.with_mut(|r| r.barcode = v),
+                        }
+                    }
+                }
+            }
+
+            // Tracks
+            div { class: "space-y-2",
+                h3 { class: "text-sm font-semibold text-gray-400 uppercase tracking-wide",
+                    "Tracks"
+                }
+                div { class: "grid grid-cols-[3.5rem_3.5rem_1fr] gap-2 text-xs text-gray-500",
+                    span { "Disc" }
+                    span { "#" }
+                    span { "Title" }
+                }
+                for (i , track) in track_drafts().into_iter().enumerate() {
+                    div {
+                        key: "{track.id}",
+                        class: "grid grid-cols-[3.5rem_3.5rem_1fr] gap-2",
+                        TextInput {
+                            value: track.disc_number,
+                            on_input: move |v| track_drafts.with_mut(|t| t[i].disc_number = v),
+                            size: TextInputSize::Small,
+                        }
+                        TextInput {
+                            value: track.track_number,
+                            on_input: move |v| track_drafts.with_mut(|t| t[i].track_number = v),
+                            size: TextInputSize::Small,
+                        }
+                        TextInput {
+                            value: track.title,
+                            on_input: move |v| track_drafts.with_mut(|t| t[i].title = v),
+                            size: TextInputSize::Small,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ReleaseField(label: &'static str, value: String, on_input: EventHandler<String>) -> Element {
+    rsx! {
+        label { class: "text-gray-400", "{label}" }
+        TextInput { value, on_input, size: TextInputSize::Small }
+    }
+}
+
+/// Check the typed fields and turn them into an edit
+fn build_edit(
+    album_id: &str,
+    title: &str,
+    year: &str,
+    artist_names: &[String],
+    release: Option<(&str, &ReleaseDraft)>,
+    tracks: &[TrackDraft],
+) -> Result<AlbumEdit, String> {
+    if title.trim().is_empty() {
+        return Err("The album needs a title".to_string());
+    }
+    let artists: Vec<String> = artist_names
+        .iter()
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .collect();
+    if artists.is_empty() {
+        return Err("The album needs at least one artist".to_string());
+    }
+    let year = parse_number(year, "Year")?;
+
+    let release = match release {
+        Some((id, r)) => Some(ReleaseEdit {
+            id: id.to_string(),
+            release_name: non_empty(&r.release_name),
+            year: parse_number(&r.year, "Release year")?,
+            format: non_empty(&r.format),
+            label: non_empty(&r.label),
+            catalog_number: non_empty(&r.catalog_number),
+            country: non_empty(&r.country),
+            barcode: non_empty(&r.barcode),
+        }),
+        None => None,
+    };
+
+    let tracks = tracks
+        .iter()
+        .map(|t| {
+            if t.title.trim().is_empty() {
+                return Err("Every track needs a title".to_string());
+            }
+            Ok(TrackEdit {
+                id: t.id.clone(),
+                title: t.title.trim().to_string(),
+                disc_number: parse_number(&t.disc_number, "Disc number")?,
+                track_number: parse_number(&t.track_number, "Track number")?,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(AlbumEdit {
+        album_id: album_id.to_string(),
+        title: title.trim().to_string(),
+        year,
+        artists,
+        release,
+        tracks,
+    })
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// A blank field is no number
+fn parse_number(value: &str, field: &str) -> Result<Option<i32>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match value.parse::<i32>() {
+        Ok(n) if n > 0 => Ok(Some(n)),
+        _ => Err(format!("{} must be a whole number", field)),
+    }
+}
@@ -1,5 +1,6 @@
 //! Album metadata display component
 
+use crate::components::utils::format_date;
 use crate::display_types::{Album, Artist, Release};
 use dioxus::prelude::*;
 
@@ -9,6 +10,8 @@ pub fn AlbumMetadata(
     artists: Vec<Artist>,
     track_count: usize,
     selected_release: Option<Release>,
+    /// DR score of the selected release
+    dynamic_range: Option<i32>,
 ) -> Element {
     let artist_name = if artists.is_empty() {
         "Unknown Artist".to_string()
@@ -22,6 +25,12 @@ pub fn AlbumMetadata(
             .join(", ")
     };
 
+    let date_added = format_date(album.date_added);
+    let plays = match album.play_count {
+        1 => "1 play".to_string(),
+        n => format!("{} plays", n),
+    };
+
     rsx! {
         div {
             h1 { class: "text-2xl font-bold text-white mb-2", "{album.title}" }
@@ -31,6 +40,20 @@ pub fn AlbumMetadata(
                     " · {year}"
                 }
             }
+            div { class: "text-sm text-gray-500 space-y-0.5 mb-2",
+                p { "Added {date_added}" }
+                p {
+                    "{plays}"
+                    if let Some(last_played) = album.last_played {
+                        " · Last played {format_date(last_played)}"
+                    }
+                }
+                if let Some(dr) = dynamic_range {
+                    p { title: "Dynamic range: DR8 and below is heavily compressed, DR12 and up is dynamic",
+                        "DR{dr}"
+                    }
+                }
+            }
         }
     }
 }
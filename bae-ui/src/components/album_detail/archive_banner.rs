@@ -0,0 +1,61 @@
+//! Archival storage banner
+
+use crate::components::icons::{CheckIcon, CloudOffIcon, LoaderIcon, XIcon};
+use crate::components::{Button, ButtonSize, ButtonVariant, ChromelessButton};
+use crate::display_types::ReleaseArchiveStatus;
+use dioxus::prelude::*;
+
+/// Warns that a release sits in cold storage before the user tries to play it,
+/// and tracks the restore through to "ready to stream"
+#[component]
+pub fn ArchiveBanner(
+    status: ReleaseArchiveStatus,
+    on_restore: EventHandler<()>,
+    on_dismiss: EventHandler<()>,
+) -> Element {
+    rsx! {
+        div { class: "flex items-start gap-3 p-4 mb-4 rounded-lg bg-surface-raised border border-border-subtle",
+            match status {
+                ReleaseArchiveStatus::Archived => rsx! {
+                    CloudOffIcon { class: "w-5 h-5 text-sky-400 flex-shrink-0 mt-0.5" }
+                    div { class: "flex-1 text-sm",
+                        p { class: "text-white font-medium", "In archival storage" }
+                        p { class: "text-gray-400 mt-1",
+                            "This release can't play until it's restored. Restores usually take 3-5 hours, up to 12 hours for Deep Archive."
+                        }
+                    }
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        onclick: move |_| on_restore.call(()),
+                        "Restore"
+                    }
+                },
+                ReleaseArchiveStatus::Restoring => rsx! {
+                    LoaderIcon { class: "w-5 h-5 text-sky-400 flex-shrink-0 mt-0.5 animate-spin" }
+                    div { class: "flex-1 text-sm",
+                        p { class: "text-white font-medium", "Restoring from archival storage" }
+                        p { class: "text-gray-400 mt-1",
+                            "This can take several hours. The release will be marked ready once it can stream."
+                        }
+                    }
+                },
+                ReleaseArchiveStatus::Restored => rsx! {
+                    CheckIcon { class: "w-5 h-5 text-green-400 flex-shrink-0 mt-0.5" }
+                    div { class: "flex-1 text-sm",
+                        p { class: "text-white font-medium", "Ready to stream" }
+                        p { class: "text-gray-400 mt-1",
+                            "Restored from archival storage. The restored copy expires after a few days."
+                        }
+                    }
+                    ChromelessButton {
+                        class: Some("text-gray-400 hover:text-white".to_string()),
+                        aria_label: Some("Dismiss".to_string()),
+                        onclick: move |_| on_dismiss.call(()),
+                        XIcon { class: "w-4 h-4" }
+                    }
+                },
+            }
+        }
+    }
+}
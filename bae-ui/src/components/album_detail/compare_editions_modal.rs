@@ -0,0 +1,248 @@
+//! Side-by-side comparison of two editions of an album
+
+use crate::components::icons::XIcon;
+use crate::components::utils::format_duration;
+use crate::components::{Modal, Select, SelectOption};
+use crate::display_types::{
+    ComparedEdition, ComparedTrack, ComparedTrackSide, LoudnessMeasurement, Release,
+    ReleaseComparison,
+};
+use dioxus::prelude::*;
+
+/// Durations closer than this read as the same track length
+const DURATION_TOLERANCE_MS: i64 = 1000;
+
+/// Modal comparing track lists, formats and loudness of two releases
+#[component]
+pub fn CompareEditionsModal(
+    is_open: ReadSignal<bool>,
+    releases: Vec<Release>,
+    left_release_id: String,
+    right_release_id: String,
+    /// Comparison of the chosen pair; anything else shows as loading
+    comparison: Option<ReleaseComparison>,
+    /// Called with (left, right) release IDs when either side changes
+    on_change: EventHandler<(String, String)>,
+    on_close: EventHandler<()>,
+) -> Element {
+    let comparison = comparison
+        .filter(|c| c.left.release_id == left_release_id && c.right.release_id == right_release_id);
+
+    rsx! {
+        Modal { is_open, on_close: move |_| on_close.call(()),
+            div { class: "bg-gray-800 rounded-lg shadow-xl max-w-3xl w-full mx-4 max-h-[80vh] flex flex-col",
+                div { class: "flex items-center justify-between px-6 pt-6 pb-4 border-b border-gray-700",
+                    h2 { class: "text-xl font-bold text-white", "Compare Editions" }
+                    button {
+                        class: "text-gray-400 hover:text-white transition-colors",
+                        onclick: move |_| on_close.call(()),
+                        XIcon { class: "w-5 h-5" }
+                    }
+                }
+                div { class: "p-6 overflow-y-auto flex-1 space-y-6",
+                    div { class: "grid grid-cols-[8rem_1fr_1fr] gap-3 items-center",
+                        div {}
+                        ReleasePicker {
+                            releases: releases.clone(),
+                            value: left_release_id.clone(),
+                            onchange: {
+                                let right = right_release_id.clone();
+                                move |left: String| on_change.call((left, right.clone()))
+                            },
+                        }
+                        ReleasePicker {
+                            releases: releases.clone(),
+                            value: right_release_id.clone(),
+                            onchange: {
+                                let left = left_release_id.clone();
+                                move |right: String| on_change.call((left.clone(), right))
+                            },
+                        }
+                    }
+                    if let Some(comparison) = comparison {
+                        SummaryTable {
+                            left: comparison.left.clone(),
+                            right: comparison.right.clone(),
+                        }
+                        TrackTable { tracks: comparison.tracks.clone() }
+                    } else {
+                        div { class: "text-gray-400 text-center py-8", "Comparing editions..." }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ReleasePicker(releases: Vec<Release>, value: String, onchange: EventHandler<String>) -> Element {
+    rsx! {
+        Select { value, onchange,
+            for release in releases.iter() {
+                SelectOption {
+                    key: "{release.id}",
+                    value: release.id.clone(),
+                    label: release_label(release),
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SummaryTable(left: ComparedEdition, right: ComparedEdition) -> Element {
+    let rows = [
+        (
+            "Format",
+            left.format.clone().unwrap_or_else(|| "—".to_string()),
+            right.format.clone().unwrap_or_else(|| "—".to_string()),
+        ),
+        ("Resolution", resolution(&left), resolution(&right)),
+        ("Bitrate", bitrate(&left), bitrate(&right)),
+        (
+            "Tracks",
+            left.track_count.to_string(),
+            right.track_count.to_string(),
+        ),
+        (
+            "Duration",
+            format_duration(left.total_duration_ms),
+            format_duration(right.total_duration_ms),
+        ),
+        (
+            "Peak",
+            loudness(&left.loudness, |peak, _| format!("{:.1} dBFS", peak)),
+            loudness(&right.loudness, |peak, _| format!("{:.1} dBFS", peak)),
+        ),
+        (
+            "Loudness (RMS)",
+            loudness(&left.loudness, |_, rms| format!("{:.1} dBFS", rms)),
+            loudness(&right.loudness, |_, rms| format!("{:.1} dBFS", rms)),
+        ),
+        (
+            "Peak to RMS",
+            loudness(&left.loudness, |peak, rms| format!("{:.1} dB", peak - rms)),
+            loudness(&right.loudness, |peak, rms| format!("{:.1} dB", peak - rms)),
+        ),
+        ("DR", dynamic_range(&left), dynamic_range(&right)),
+    ];
+
+    rsx! {
+        div { class: "grid grid-cols-[8rem_1fr_1fr] gap-x-3 gap-y-2 text-sm",
+            for (label , left_value , right_value) in rows {
+                {
+                    let differs = left_value != right_value;
+                    let value_class = if differs { "text-white" } else { "text-gray-400" };
+                    rsx! {
+                        div { class: "text-gray-500", "{label}" }
+                        div { class: "{value_class}", "{left_value}" }
+                        div { class: "{value_class}", "{right_value}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TrackTable(tracks: Vec<ComparedTrack>) -> Element {
+    rsx! {
+        div { class: "border-t border-gray-700 pt-4 space-y-1 text-sm",
+            for (index , track) in tracks.iter().enumerate() {
+                div {
+                    key: "{index}",
+                    class: "grid grid-cols-[8rem_1fr_1fr] gap-3 py-1.5 px-2 rounded hover:bg-gray-700/50",
+                    div { class: "text-xs self-center",
+                        match duration_difference(track) {
+                            Some(diff) if diff.abs() >= DURATION_TOLERANCE_MS => rsx! {
+                                span { class: "text-amber-400", {format_signed_duration(diff)} }
+                            },
+                            Some(_) => rsx! {
+                                span { class: "text-gray-500", "Same length" }
+                            },
+                            None if track.left.is_none() => rsx! {
+                                span { class: "text-gray-500", "Only on right" }
+                            },
+                            None if track.right.is_none() => rsx! {
+                                span { class: "text-gray-500", "Only on left" }
+                            },
+                            None => rsx! {},
+                        }
+                    }
+                    TrackSideCell { side: track.left.clone() }
+                    TrackSideCell { side: track.right.clone() }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TrackSideCell(side: Option<ComparedTrackSide>) -> Element {
+    let Some(side) = side else {
+        return rsx! {
+            div { class: "text-gray-600", "—" }
+        };
+    };
+
+    rsx! {
+        div { class: "flex items-baseline gap-2 min-w-0",
+            span { class: "text-gray-200 truncate flex-1", title: "{side.title}", "{side.title}" }
+            if let Some(duration) = side.duration_ms {
+                span { class: "text-gray-500 tabular-nums", {format_duration(duration)} }
+            }
+        }
+    }
+}
+
+/// Right minus left duration, when both sides have one
+fn duration_difference(track: &ComparedTrack) -> Option<i64> {
+    let left = track.left.as_ref()?.duration_ms?;
+    let right = track.right.as_ref()?.duration_ms?;
+    Some(right - left)
+}
+
+fn format_signed_duration(diff_ms: i64) -> String {
+    let sign = if diff_ms < 0 { "-" } else { "+" };
+    format!("{}{}", sign, format_duration(diff_ms.abs()))
+}
+
+fn release_label(release: &Release) -> String {
+    match (&release.release_name, release.year) {
+        (Some(name), _) => name.clone(),
+        (None, Some(year)) => format!("Release ({})", year),
+        (None, None) => "Release".to_string(),
+    }
+}
+
+fn resolution(edition: &ComparedEdition) -> String {
+    match (edition.bits_per_sample, edition.sample_rate) {
+        (Some(bits), Some(rate)) => format!("{}-bit / {} kHz", bits, rate as f64 / 1000.0),
+        _ => "—".to_string(),
+    }
+}
+
+fn bitrate(edition: &ComparedEdition) -> String {
+    edition
+        .bitrate_kbps
+        .map(|kbps| format!("{} kbps", kbps))
+        .unwrap_or_else(|| "—".to_string())
+}
+
+fn dynamic_range(edition: &ComparedEdition) -> String {
+    edition
+        .dynamic_range
+        .map(|dr| format!("DR{}", dr))
+        .unwrap_or_else(|| "—".to_string())
+}
+
+fn loudness(measurement: &LoudnessMeasurement, show: impl Fn(f64, f64) -> String) -> String {
+    match measurement {
+        LoudnessMeasurement::Measuring => "Measuring...".to_string(),
+        LoudnessMeasurement::Measured {
+            peak_dbfs,
+            rms_dbfs,
+        } => show(*peak_dbfs, *rms_dbfs),
+        LoudnessMeasurement::Unavailable => "—".to_string(),
+    }
+}
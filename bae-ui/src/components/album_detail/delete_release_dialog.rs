@@ -1,5 +1,6 @@
 //! Delete release confirmation dialog
 
+use crate::components::utils::format_file_size;
 use crate::components::{Button, ButtonSize, ButtonVariant, Modal};
 use dioxus::prelude::*;
 
@@ -8,10 +9,16 @@ pub fn DeleteReleaseDialog(
     is_open: ReadSignal<bool>,
     release_id: String,
     is_last_release: bool,
+    /// Bytes deleting frees in bae's storage; 0 when the files aren't bae's
+    stored_bytes: u64,
     is_deleting: Signal<bool>,
-    on_confirm: EventHandler<String>,
+    /// Called with (release ID, keep files)
+    on_confirm: EventHandler<(String, bool)>,
     on_cancel: EventHandler<()>,
 ) -> Element {
+    let mut keep_files = use_signal(|| false);
+    let freed = format_file_size(stored_bytes as i64);
+
     rsx! {
         Modal {
             is_open,
@@ -22,7 +29,7 @@ pub fn DeleteReleaseDialog(
             },
             div { class: "bg-gray-800 rounded-lg p-6 max-w-md w-full mx-4",
                 h2 { class: "text-xl font-bold text-white mb-4", "Delete Release?" }
-                p { class: "text-gray-300 mb-6",
+                p { class: "text-gray-300 mb-4",
                     "Are you sure you want to delete this release? This will delete all tracks and associated data for this release."
                     if is_last_release {
                         " Since this is the only release, the album will also be deleted."
@@ -30,6 +37,31 @@ pub fn DeleteReleaseDialog(
                         ""
                     }
                 }
+                if stored_bytes > 0 {
+                    div { class: "mb-6 space-y-3",
+                        p { class: "text-gray-300",
+                            if keep_files() {
+                                "The stored files will be left in place."
+                            } else {
+                                "This frees {freed} of storage."
+                            }
+                        }
+                        label { class: "flex items-center gap-3 cursor-pointer",
+                            input {
+                                r#type: "checkbox",
+                                class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600",
+                                checked: keep_files(),
+                                disabled: is_deleting(),
+                                onchange: move |e| keep_files.set(e.checked()),
+                            }
+                            span { class: "text-sm text-gray-300", "Keep the stored files" }
+                        }
+                    }
+                } else {
+                    p { class: "text-sm text-gray-400 mb-6",
+                        "The files were left where they were imported from, so they won't be touched."
+                    }
+                }
                 div { class: "flex gap-3 justify-end",
                     Button {
                         variant: ButtonVariant::Secondary,
@@ -51,7 +83,7 @@ pub fn DeleteReleaseDialog(
                             let release_id = release_id.clone();
                             move |_| {
                                 if !is_deleting() {
-                                    on_confirm.call(release_id.clone());
+                                    on_confirm.call((release_id.clone(), keep_files()));
                                 }
                             }
                         },
@@ -2,24 +2,38 @@
 
 mod album_art;
 mod album_cover_section;
+mod album_editor;
 mod album_metadata;
+mod archive_banner;
+mod compare_editions_modal;
 mod delete_album_dialog;
 mod delete_release_dialog;
 mod export_error_toast;
+mod offline_status;
 mod play_album_button;
+mod reference_banner;
 mod release_info_modal;
 pub mod release_tabs_section;
+mod track_renamed_toast;
 mod track_row;
+mod videos_section;
 mod view;
 
 pub use album_art::AlbumArt;
 pub use album_cover_section::AlbumCoverSection;
+pub use album_editor::AlbumEditor;
 pub use album_metadata::AlbumMetadata;
+pub use archive_banner::ArchiveBanner;
+pub use compare_editions_modal::CompareEditionsModal;
 pub use delete_album_dialog::DeleteAlbumDialog;
 pub use delete_release_dialog::DeleteReleaseDialog;
 pub use export_error_toast::ExportErrorToast;
+pub use offline_status::OfflineStatus;
 pub use play_album_button::PlayAlbumButton;
+pub use reference_banner::ReferenceBanner;
 pub use release_info_modal::ReleaseInfoModal;
 pub use release_tabs_section::ReleaseTabsSection;
+pub use track_renamed_toast::TrackRenamedToast;
 pub use track_row::TrackRow;
+pub use videos_section::VideosSection;
 pub use view::AlbumDetailView;
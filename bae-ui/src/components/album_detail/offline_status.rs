@@ -0,0 +1,34 @@
+//! Offline availability line under the play button
+
+use crate::components::icons::{CheckIcon, DownloadIcon};
+use crate::display_types::OfflineDownload;
+use dioxus::prelude::*;
+
+/// Shows a kept-offline album as available, downloading, or stopped on an
+/// error. Renders nothing for albums that aren't kept offline.
+#[component]
+pub fn OfflineStatus(is_offline: bool, download: Option<OfflineDownload>) -> Element {
+    let Some(download) = download else {
+        if !is_offline {
+            return rsx! {};
+        }
+        return rsx! {
+            div { class: "flex items-center gap-2 mt-3 text-sm text-gray-400",
+                CheckIcon { class: "w-4 h-4 text-green-400" }
+                "Available offline"
+            }
+        };
+    };
+
+    rsx! {
+        div { class: "mt-3 text-sm",
+            div { class: "flex items-center gap-2 text-gray-400",
+                DownloadIcon { class: "w-4 h-4 text-sky-400" }
+                "Downloading for offline · {download.files_done} of {download.files_total} files"
+            }
+            if let Some(error) = &download.error {
+                p { class: "text-red-400 mt-1 break-words", "{error}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Reference profile banner
+
+use crate::components::icons::{FolderIcon, LoaderIcon};
+use crate::components::{Button, ButtonSize, ButtonVariant};
+use crate::display_types::ReleaseReference;
+use dioxus::prelude::*;
+
+/// Notes that a release is read from a folder bae doesn't manage, and offers
+/// to copy it into the default profile
+#[component]
+pub fn ReferenceBanner(reference: ReleaseReference, on_migrate: EventHandler<()>) -> Element {
+    rsx! {
+        div { class: "flex items-start gap-3 p-4 mb-4 rounded-lg bg-surface-raised border border-border-subtle",
+            if reference.migrating {
+                LoaderIcon { class: "w-5 h-5 text-gray-400 flex-shrink-0 mt-0.5 animate-spin" }
+            } else {
+                FolderIcon { class: "w-5 h-5 text-gray-400 flex-shrink-0 mt-0.5" }
+            }
+            div { class: "flex-1 text-sm",
+                p { class: "text-white font-medium", "Referenced from {reference.profile_name}" }
+                p { class: "text-gray-400 mt-1",
+                    if reference.migrating {
+                        "Copying files into managed storage. The originals stay where they are."
+                    } else {
+                        "These files are read where they are. bae won't move or delete them."
+                    }
+                }
+                if let Some(error) = reference.error.as_ref() {
+                    p { class: "text-red-400 mt-1", "{error}" }
+                }
+            }
+            if let Some(target) = reference.target_profile_name.as_ref() {
+                if !reference.migrating {
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        onclick: move |_| on_migrate.call(()),
+                        "Move to {target}"
+                    }
+                }
+            }
+        }
+    }
+}
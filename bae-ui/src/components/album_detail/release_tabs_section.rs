@@ -1,5 +1,6 @@
 //! Release tabs section for multi-release albums
 
+use crate::components::icons::StarIcon;
 use crate::components::{ChromelessButton, MenuDropdown, MenuItem, Placement};
 use crate::display_types::Release;
 use dioxus::prelude::*;
@@ -16,13 +17,22 @@ pub struct ReleaseTorrentInfo {
 pub fn ReleaseTabsSection(
     releases: Vec<Release>,
     selected_release_id: Option<String>,
+    /// Edition the album plays by default, marked with a star
+    preferred_release_id: Option<String>,
     on_release_select: EventHandler<String>,
+    /// Make a release the default edition, or clear it with None
+    on_set_preferred: EventHandler<Option<String>>,
     is_deleting: ReadSignal<bool>,
     is_exporting: Signal<bool>,
     export_error: Signal<Option<String>>,
     on_view_files: EventHandler<String>,
     on_delete_release: EventHandler<String>,
     on_export: EventHandler<String>,
+    /// Export a release as a package for someone without bae
+    #[props(default)]
+    on_export_gift: Option<EventHandler<String>>,
+    /// Open the edition comparison starting from this release
+    on_compare: EventHandler<String>,
     // Optional: torrent info per release (keyed by release_id)
     #[props(default)] torrent_info: std::collections::HashMap<String, ReleaseTorrentInfo>,
     // Optional: torrent action callbacks
@@ -37,6 +47,7 @@ pub fn ReleaseTabsSection(
                 for release in releases.iter() {
                     {
                         let is_selected = selected_release_id.as_ref() == Some(&release.id);
+                        let is_preferred = preferred_release_id.as_ref() == Some(&release.id);
                         let release_id = release.id.clone();
                         let torrent = torrent_info.get(&release.id).cloned().unwrap_or_default();
                         rsx! {
@@ -44,6 +55,7 @@ pub fn ReleaseTabsSection(
                                 key: "{release.id}",
                                 release: release.clone(),
                                 is_selected,
+                                is_preferred,
                                 show_release_dropdown,
                                 on_release_select: {
                                     let release_id = release_id.clone();
@@ -60,6 +72,22 @@ pub fn ReleaseTabsSection(
                                     let release_id = release_id.clone();
                                     move |_| on_export.call(release_id.clone())
                                 },
+                                on_export_gift: on_export_gift
+                                    .map(|handler| {
+                                        let release_id = release_id.clone();
+                                        EventHandler::new(move |_: ()| handler.call(release_id.clone()))
+                                    }),
+                                on_compare: {
+                                    let release_id = release_id.clone();
+                                    move |_| on_compare.call(release_id.clone())
+                                },
+                                on_toggle_preferred: {
+                                    let release_id = release_id.clone();
+                                    move |_| {
+                                        on_set_preferred
+                                            .call(if is_preferred { None } else { Some(release_id.clone()) })
+                                    }
+                                },
                                 on_delete: {
                                     let release_id = release_id.clone();
                                     move |_| on_delete_release.call(release_id.clone())
@@ -88,6 +116,7 @@ pub fn ReleaseTabsSection(
 fn ReleaseTab(
     release: Release,
     is_selected: bool,
+    is_preferred: bool,
     show_release_dropdown: Signal<Option<String>>,
     on_release_select: EventHandler<()>,
     is_deleting: ReadSignal<bool>,
@@ -95,9 +124,12 @@ fn ReleaseTab(
     torrent: ReleaseTorrentInfo,
     on_view_files: EventHandler<()>,
     on_export: EventHandler<()>,
+    on_compare: EventHandler<()>,
+    on_toggle_preferred: EventHandler<()>,
     on_delete: EventHandler<()>,
     #[props(default)] on_start_seeding: Option<EventHandler<()>>,
     #[props(default)] on_stop_seeding: Option<EventHandler<()>>,
+    #[props(default)] on_export_gift: Option<EventHandler<()>>,
 ) -> Element {
     let release_id = release.id.clone();
     let anchor_id = format!("release-tab-{}", release.id);
@@ -129,6 +161,9 @@ fn ReleaseTab(
             ChromelessButton {
                 class: Some(tab_class.to_string()),
                 onclick: move |_| on_release_select.call(()),
+                if is_preferred {
+                    StarIcon { class: "w-3 h-3 inline mr-1.5 -mt-0.5 text-amber-400" }
+                }
                 {
                     if let Some(ref name) = release.release_name {
                         name.clone()
@@ -174,6 +209,26 @@ fn ReleaseTab(
                     },
                     "Release Info"
                 }
+                MenuItem {
+                    disabled: is_deleting(),
+                    onclick: move |_| {
+                        show_release_dropdown.set(None);
+                        on_compare.call(());
+                    },
+                    "Compare Editions"
+                }
+                MenuItem {
+                    disabled: is_deleting(),
+                    onclick: move |_| {
+                        show_release_dropdown.set(None);
+                        on_toggle_preferred.call(());
+                    },
+                    if is_preferred {
+                        "Unset Default Edition"
+                    } else {
+                        "Set as Default Edition"
+                    }
+                }
                 if torrent.has_torrent {
                     if torrent.is_seeding {
                         if let Some(ref handler) = on_stop_seeding {
@@ -217,6 +272,16 @@ fn ReleaseTab(
                         "Export"
                     }
                 }
+                if let Some(handler) = on_export_gift {
+                    MenuItem {
+                        disabled: is_deleting() || is_exporting(),
+                        onclick: move |_| {
+                            show_release_dropdown.set(None);
+                            handler.call(());
+                        },
+                        "Export as Gift..."
+                    }
+                }
                 MenuItem {
                     disabled: is_deleting() || is_exporting(),
                     danger: true,
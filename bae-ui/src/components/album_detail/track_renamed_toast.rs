@@ -0,0 +1,33 @@
+//! Track renamed toast with undo
+
+use crate::components::icons::XIcon;
+use crate::components::ChromelessButton;
+use dioxus::prelude::*;
+
+/// Confirms an inline track rename and offers to revert it
+#[component]
+pub fn TrackRenamedToast(
+    /// The new track title
+    title: String,
+    on_undo: EventHandler<()>,
+    on_dismiss: EventHandler<()>,
+) -> Element {
+    rsx! {
+        div { class: "fixed bottom-20 right-4 bg-surface-overlay border border-border-strong text-white px-6 py-4 rounded-lg shadow-lg z-50 max-w-md",
+            div { class: "flex items-center justify-between gap-4",
+                span { class: "flex-1 text-sm text-gray-300 truncate", "Renamed to “{title}”" }
+                ChromelessButton {
+                    class: Some("text-sm font-medium text-accent-soft hover:text-white".to_string()),
+                    onclick: move |_| on_undo.call(()),
+                    "Undo"
+                }
+                ChromelessButton {
+                    class: Some("text-gray-400 hover:text-white".to_string()),
+                    aria_label: Some("Dismiss".to_string()),
+                    onclick: move |_| on_dismiss.call(()),
+                    XIcon { class: "w-4 h-4" }
+                }
+            }
+        }
+    }
+}
@@ -3,10 +3,10 @@
 //! Accepts `ReadStore<Track>` for per-track reactivity.
 //! Only this row re-renders when its track's import state changes.
 
-use crate::components::icons::{EllipsisIcon, PauseIcon, PlayIcon};
+use crate::components::icons::{BookmarkIcon, EllipsisIcon, PauseIcon, PlayIcon, XIcon};
 use crate::components::utils::format_duration;
-use crate::components::{ChromelessButton, MenuDropdown, MenuItem, Placement};
-use crate::display_types::{Artist, TrackImportState};
+use crate::components::{ChromelessButton, MenuDropdown, MenuItem, Placement, QualityBadge};
+use crate::display_types::{Artist, TrackBookmark, TrackClickAction, TrackImportState};
 use dioxus::prelude::*;
 
 /// Individual track row component - reads from its track store for granular reactivity
@@ -23,16 +23,27 @@ pub fn TrackRow(
     is_paused: bool,
     is_loading: bool,
     show_spinner: bool,
+    /// What a click on the row does
+    click_action: TrackClickAction,
+    is_selected: bool,
     // Callbacks
+    on_select: EventHandler<String>,
     on_play: EventHandler<String>,
     on_pause: EventHandler<()>,
     on_resume: EventHandler<()>,
     on_add_next: EventHandler<String>,
     on_add_to_queue: EventHandler<String>,
     on_export: EventHandler<String>,
+    /// Called with (track_id, new_title) when an inline title edit is committed
+    on_rename: EventHandler<(String, String)>,
+    /// Called with (track_id, position_ms) when a bookmark is clicked
+    on_bookmark_jump: EventHandler<(String, u64)>,
+    /// Called with the bookmark id
+    on_bookmark_delete: EventHandler<String>,
 ) -> Element {
     // Read track data at this leaf level
     let track = track.read();
+    let mut is_editing = use_signal(|| false);
 
     let is_active = is_playing || is_paused;
 
@@ -52,6 +63,8 @@ pub fn TrackRow(
     let row_class = if is_available {
         if is_active {
             "relative flex items-center py-2 px-4 rounded-lg group overflow-clip bg-accent/10 hover:bg-accent/15 transition-colors cursor-pointer"
+        } else if is_selected {
+            "relative flex items-center py-2 px-4 rounded-lg group overflow-clip bg-hover transition-colors cursor-pointer"
         } else {
             "relative flex items-center py-2 px-4 rounded-lg group overflow-clip hover:bg-hover transition-colors cursor-pointer"
         }
@@ -65,51 +78,75 @@ pub fn TrackRow(
     let track_id = track.id.clone();
     let track_id_for_play = track_id.clone();
     let track_id_for_menu = track_id.clone();
+    let track_id_for_click = track_id.clone();
+    let track_id_for_double_click = track_id.clone();
 
     rsx! {
-        div { class: "{row_class}",
+        div {
+            class: "{row_class}",
+            onclick: move |_| {
+                if !is_available {
+                    return;
+                }
+                let track_id = track_id_for_click.clone();
+                match click_action {
+                    TrackClickAction::Play => on_play.call(track_id),
+                    TrackClickAction::AddToQueue => on_add_to_queue.call(track_id),
+                    TrackClickAction::Select => on_select.call(track_id),
+                }
+            },
+            ondoubleclick: move |_| {
+                if is_available && click_action == TrackClickAction::Select {
+                    on_play.call(track_id_for_double_click.clone());
+                }
+            },
             // Play/pause button area
-            if is_available {
-                if show_spinner {
-                    div { class: "w-6 flex items-center justify-center",
-                        div { class: "animate-spin rounded-full h-4 w-4 border-b-2 border-blue-400" }
-                    }
-                } else if is_playing {
-                    ChromelessButton {
-                        class: Some(
-                            "w-6 h-6 rounded-full border border-blue-400 opacity-0 group-hover:opacity-100 transition-opacity flex items-center justify-center text-blue-400 hover:text-blue-300 hover:bg-blue-400/10"
-                                .to_string(),
-                        ),
-                        aria_label: Some("Pause".to_string()),
-                        onclick: move |_| on_pause.call(()),
-                        PauseIcon { class: "w-3 h-3" }
-                    }
-                } else if is_paused {
-                    ChromelessButton {
-                        class: Some(
-                            "w-6 h-6 rounded-full border border-blue-400 flex items-center justify-center text-blue-400 hover:text-blue-300 hover:bg-blue-400/10 transition-colors"
-                                .to_string(),
-                        ),
-                        aria_label: Some("Resume".to_string()),
-                        onclick: move |_| on_resume.call(()),
-                        PlayIcon { class: "w-3 h-3" }
+            div {
+                class: "flex-shrink-0",
+                onclick: |evt| evt.stop_propagation(),
+                ondoubleclick: |evt| evt.stop_propagation(),
+                if is_available {
+                    if show_spinner {
+                        div { class: "w-6 flex items-center justify-center",
+                            div { class: "animate-spin rounded-full h-4 w-4 border-b-2 border-blue-400" }
+                        }
+                    } else if is_playing {
+                        ChromelessButton {
+                            class: Some(
+                                "w-6 h-6 rounded-full border border-blue-400 opacity-0 group-hover:opacity-100 transition-opacity flex items-center justify-center text-blue-400 hover:text-blue-300 hover:bg-blue-400/10"
+                                    .to_string(),
+                            ),
+                            aria_label: Some("Pause".to_string()),
+                            onclick: move |_| on_pause.call(()),
+                            PauseIcon { class: "w-3 h-3" }
+                        }
+                    } else if is_paused {
+                        ChromelessButton {
+                            class: Some(
+                                "w-6 h-6 rounded-full border border-blue-400 flex items-center justify-center text-blue-400 hover:text-blue-300 hover:bg-blue-400/10 transition-colors"
+                                    .to_string(),
+                            ),
+                            aria_label: Some("Resume".to_string()),
+                            onclick: move |_| on_resume.call(()),
+                            PlayIcon { class: "w-3 h-3" }
+                        }
+                    } else {
+                        ChromelessButton {
+                            class: Some(
+                                "w-6 h-6 rounded-full border border-blue-400 opacity-0 group-hover:opacity-100 transition-opacity flex items-center justify-center text-blue-400 hover:text-blue-300 hover:bg-blue-400/10"
+                                    .to_string(),
+                            ),
+                            aria_label: Some("Play".to_string()),
+                            onclick: {
+                                let track_id = track_id_for_play.clone();
+                                move |_| on_play.call(track_id.clone())
+                            },
+                            PlayIcon { class: "w-3 h-3" }
+                        }
                     }
                 } else {
-                    ChromelessButton {
-                        class: Some(
-                            "w-6 h-6 rounded-full border border-blue-400 opacity-0 group-hover:opacity-100 transition-opacity flex items-center justify-center text-blue-400 hover:text-blue-300 hover:bg-blue-400/10"
-                                .to_string(),
-                        ),
-                        aria_label: Some("Play".to_string()),
-                        onclick: {
-                            let track_id = track_id_for_play.clone();
-                            move |_| on_play.call(track_id.clone())
-                        },
-                        PlayIcon { class: "w-3 h-3" }
-                    }
+                    div { class: "w-6" }
                 }
-            } else {
-                div { class: "w-6" }
             }
 
             // Track number
@@ -125,10 +162,27 @@ pub fn TrackRow(
 
             // Track title and artists
             div { class: "flex-1 min-w-0 max-w-md ml-4",
-                h3 {
-                    class: "font-medium transition-colors truncate",
-                    class: if is_importing { "text-gray-500" } else if is_active { "text-accent-soft" } else { "text-white group-hover:text-accent-soft" },
-                    "{track.title}"
+                if is_editing() {
+                    TrackTitleEditor {
+                        title: track.title.clone(),
+                        on_commit: {
+                            let track_id = track_id.clone();
+                            let old_title = track.title.clone();
+                            move |title: String| {
+                                is_editing.set(false);
+                                if !title.is_empty() && title != old_title {
+                                    on_rename.call((track_id.clone(), title));
+                                }
+                            }
+                        },
+                        on_cancel: move |_| is_editing.set(false),
+                    }
+                } else {
+                    h3 {
+                        class: "font-medium transition-colors truncate",
+                        class: if is_importing { "text-gray-500" } else if is_active { "text-accent-soft" } else { "text-white group-hover:text-accent-soft" },
+                        "{track.title}"
+                    }
                 }
                 if is_compilation && !artists.is_empty() {
                     p {
@@ -143,6 +197,19 @@ pub fn TrackRow(
                         }
                     }
                 }
+                if !track.bookmarks.is_empty() {
+                    TrackBookmarks {
+                        bookmarks: track.bookmarks.clone(),
+                        on_jump: on_bookmark_jump,
+                        on_delete: on_bookmark_delete,
+                    }
+                }
+            }
+
+            if let Some(quality) = track.quality {
+                div { class: "ml-4",
+                    QualityBadge { quality }
+                }
             }
 
             // Duration / Import progress
@@ -161,24 +228,128 @@ pub fn TrackRow(
 
             // Context menu
             if is_available {
-                TrackMenu {
-                    track_id: track_id_for_menu,
-                    on_export,
-                    on_add_next,
-                    on_add_to_queue,
+                div {
+                    onclick: |evt| evt.stop_propagation(),
+                    ondoubleclick: |evt| evt.stop_propagation(),
+                    TrackMenu {
+                        track_id: track_id_for_menu,
+                        on_export,
+                        on_add_next,
+                        on_add_to_queue,
+                        on_rename: move |_| is_editing.set(true),
+                    }
                 }
             }
         }
     }
 }
 
-/// Track context menu (export, play next, add to queue)
+/// Bookmark chips under the track title. Clicking one plays from there.
+#[component]
+fn TrackBookmarks(
+    bookmarks: Vec<TrackBookmark>,
+    on_jump: EventHandler<(String, u64)>,
+    on_delete: EventHandler<String>,
+) -> Element {
+    rsx! {
+        div {
+            class: "flex flex-wrap gap-1 mt-1",
+            onclick: |evt| evt.stop_propagation(),
+            ondoubleclick: |evt| evt.stop_propagation(),
+            for bookmark in bookmarks {
+                div {
+                    key: "{bookmark.id}",
+                    class: "group/bookmark flex items-center gap-1 pl-1.5 pr-1 py-0.5 rounded bg-surface-raised text-xs text-gray-400",
+                    ChromelessButton {
+                        class: Some("flex items-center gap-1 hover:text-white transition-colors".to_string()),
+                        aria_label: Some(format!("Play from {}", bookmark.name)),
+                        onclick: {
+                            let track_id = bookmark.track_id.clone();
+                            let position_ms = bookmark.position_ms;
+                            move |_| on_jump.call((track_id.clone(), position_ms))
+                        },
+                        BookmarkIcon { class: "w-3 h-3" }
+                        span { "{bookmark.name}" }
+                        span { class: "font-mono text-gray-500",
+                            {format_duration(bookmark.position_ms as i64)}
+                        }
+                    }
+                    ChromelessButton {
+                        class: Some(
+                            "opacity-0 group-hover/bookmark:opacity-100 text-gray-500 hover:text-white transition-all"
+                                .to_string(),
+                        ),
+                        aria_label: Some(format!("Delete bookmark {}", bookmark.name)),
+                        onclick: {
+                            let id = bookmark.id.clone();
+                            move |_| on_delete.call(id.clone())
+                        },
+                        XIcon { class: "w-3 h-3" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Inline title input. Enter or blur commits the trimmed value, Escape cancels.
+#[component]
+fn TrackTitleEditor(
+    title: String,
+    on_commit: EventHandler<String>,
+    on_cancel: EventHandler<()>,
+) -> Element {
+    let mut draft = use_signal(|| title.clone());
+    // Enter commits and unmounts the input, which may also fire blur
+    let mut is_done = use_signal(|| false);
+
+    rsx! {
+        input {
+            r#type: "text",
+            class: "w-full bg-surface-input border border-border-strong rounded px-1 -mx-1 font-medium text-white focus:outline-none focus:ring-1 focus:ring-accent/50",
+            aria_label: "Track title",
+            value: "{draft}",
+            onclick: |evt| evt.stop_propagation(),
+            ondoubleclick: |evt| evt.stop_propagation(),
+            oninput: move |evt| draft.set(evt.value()),
+            onkeydown: move |evt| {
+                if is_done() {
+                    return;
+                }
+                match evt.key() {
+                    Key::Enter => {
+                        is_done.set(true);
+                        on_commit.call(draft.read().trim().to_string());
+                    }
+                    Key::Escape => {
+                        is_done.set(true);
+                        on_cancel.call(());
+                    }
+                    _ => {}
+                }
+            },
+            onblur: move |_| {
+                if !is_done() {
+                    is_done.set(true);
+                    on_commit.call(draft.read().trim().to_string());
+                }
+            },
+            onmounted: move |event| async move {
+                let _ = event.data().set_focus(true).await;
+            },
+        }
+    }
+}
+
+/// Track context menu (export, play next, add to queue, rename)
 #[component]
 fn TrackMenu(
     track_id: String,
     on_export: EventHandler<String>,
     on_add_next: EventHandler<String>,
     on_add_to_queue: EventHandler<String>,
+    /// Start editing the title inline
+    on_rename: EventHandler<()>,
 ) -> Element {
     let mut show_menu = use_signal(|| false);
     let is_open: ReadSignal<bool> = show_menu.into();
@@ -237,6 +408,13 @@ fn TrackMenu(
                 },
                 "Add to Queue"
             }
+            MenuItem {
+                onclick: move |_| {
+                    show_menu.set(false);
+                    on_rename.call(());
+                },
+                "Rename"
+            }
         }
     }
 }
@@ -0,0 +1,42 @@
+//! Video extras section
+
+use crate::components::icons::{PlayIcon, VideoIcon};
+use crate::components::utils::format_file_size;
+use crate::components::ChromelessButton;
+use crate::display_types::File;
+use dioxus::prelude::*;
+
+/// Music videos and bonus content stored with the release. They open in the
+/// system video player.
+#[component]
+pub fn VideosSection(videos: Vec<File>, on_play: EventHandler<String>) -> Element {
+    rsx! {
+        div { class: "mt-8",
+            h3 { class: "text-sm font-semibold text-gray-400 uppercase tracking-wide pb-2",
+                "Videos"
+            }
+            div { class: "space-y-1",
+                for video in videos {
+                    ChromelessButton {
+                        key: "{video.id}",
+                        class: Some(
+                            "flex items-center gap-3 w-full px-3 py-2 rounded-lg text-left hover:bg-white/5 group"
+                                .to_string(),
+                        ),
+                        aria_label: Some(format!("Play {}", video.filename)),
+                        onclick: {
+                            let id = video.id.clone();
+                            move |_| on_play.call(id.clone())
+                        },
+                        VideoIcon { class: "w-4 h-4 text-gray-400 flex-shrink-0 group-hover:hidden" }
+                        PlayIcon { class: "w-4 h-4 text-white flex-shrink-0 hidden group-hover:block" }
+                        span { class: "flex-1 text-sm text-white truncate", "{video.filename}" }
+                        span { class: "text-xs text-gray-500 flex-shrink-0",
+                            {format!("{} • {}", format_file_size(video.file_size), video.format.to_uppercase())}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -7,15 +7,25 @@
 //! - Each TrackRow only re-renders when its specific track changes
 
 use super::album_cover_section::AlbumCoverSection;
+use super::album_editor::AlbumEditor;
 use super::album_metadata::AlbumMetadata;
+use super::archive_banner::ArchiveBanner;
+use super::compare_editions_modal::CompareEditionsModal;
 use super::delete_album_dialog::DeleteAlbumDialog;
 use super::delete_release_dialog::DeleteReleaseDialog;
 use super::export_error_toast::ExportErrorToast;
+use super::offline_status::OfflineStatus;
 use super::play_album_button::PlayAlbumButton;
+use super::reference_banner::ReferenceBanner;
 use super::release_info_modal::{ReleaseInfoModal, Tab};
 use super::release_tabs_section::{ReleaseTabsSection, ReleaseTorrentInfo};
+use super::track_renamed_toast::TrackRenamedToast;
 use super::track_row::TrackRow;
-use crate::display_types::{File, Image, PlaybackDisplay, Track};
+use super::videos_section::VideosSection;
+use crate::display_types::{
+    AlbumEdit, File, Image, OfflineDownload, PlaybackDisplay, ReleaseArchiveStatus, Track,
+    TrackClickAction,
+};
 use crate::stores::album_detail::{AlbumDetailState, AlbumDetailStateStoreExt};
 use dioxus::prelude::*;
 use std::collections::HashSet;
@@ -31,19 +41,45 @@ pub fn AlbumDetailView(
     tracks: ReadStore<Vec<Track>>,
     /// Playback state (from separate playback store)
     playback: PlaybackDisplay,
+    /// What a click on a track row does
+    track_click_action: TrackClickAction,
+    /// Track to select and scroll to, e.g. one picked from search
+    #[props(default)]
+    highlighted_track_id: Option<String>,
     on_release_select: EventHandler<String>,
     on_album_deleted: EventHandler<()>,
     on_export_release: EventHandler<String>,
-    on_delete_album: EventHandler<String>,
-    on_delete_release: EventHandler<String>,
+    /// Called with (album ID, keep files)
+    on_delete_album: EventHandler<(String, bool)>,
+    /// Called with (release ID, keep files)
+    on_delete_release: EventHandler<(String, bool)>,
     on_track_play: EventHandler<String>,
     on_track_pause: EventHandler<()>,
     on_track_resume: EventHandler<()>,
     on_track_add_next: EventHandler<String>,
     on_track_add_to_queue: EventHandler<String>,
     on_track_export: EventHandler<String>,
+    /// Called with (track_id, title) for inline renames and their undo
+    on_track_rename: EventHandler<(String, String)>,
+    /// Called with (track_id, position_ms) to play a track from a bookmark
+    on_track_bookmark_jump: EventHandler<(String, u64)>,
+    on_track_bookmark_delete: EventHandler<String>,
     on_play_album: EventHandler<Vec<String>>,
     on_add_album_to_queue: EventHandler<Vec<String>>,
+    /// Request a restore of an archived release
+    on_restore_release: EventHandler<String>,
+    /// Copy a referenced release into the default storage profile
+    on_migrate_release: EventHandler<String>,
+    /// Open a video extra (by file ID) in the system player
+    on_play_video: EventHandler<String>,
+    /// Called with (left, right) release IDs to compare two editions
+    on_compare_releases: EventHandler<(String, String)>,
+    /// Make a release the album's default edition, or clear it with None
+    on_set_preferred_release: EventHandler<Option<String>>,
+    /// Switch the page to edit mode
+    on_edit: EventHandler<()>,
+    on_cancel_edit: EventHandler<()>,
+    on_save_edits: EventHandler<AlbumEdit>,
     #[props(default)] modal_files: Vec<File>,
     #[props(default)] modal_images: Vec<Image>,
     #[props(default)] modal_loading_files: bool,
@@ -53,6 +89,15 @@ pub fn AlbumDetailView(
     #[props(default)] torrent_info: std::collections::HashMap<String, ReleaseTorrentInfo>,
     #[props(default)] on_start_seeding: Option<EventHandler<String>>,
     #[props(default)] on_stop_seeding: Option<EventHandler<String>>,
+    /// Export a release as a package for someone without bae
+    #[props(default)]
+    on_export_gift: Option<EventHandler<String>>,
+    /// The album's offline download, while it runs or after it failed
+    #[props(default)]
+    offline_download: Option<OfflineDownload>,
+    /// Keep the album offline (true) or drop its offline copy (false)
+    #[props(default)]
+    on_set_offline: Option<EventHandler<bool>>,
 ) -> Element {
     // UI-local state for dialogs
     let is_deleting = use_signal(|| false);
@@ -61,27 +106,44 @@ pub fn AlbumDetailView(
     let mut show_album_delete_confirm = use_signal(|| false);
     let mut show_release_delete_confirm = use_signal(|| None::<String>);
     let mut show_release_info_modal = use_signal(|| None::<(String, Tab)>);
+    // (left, right) release IDs while the compare view is open
+    let mut show_compare = use_signal(|| None::<(String, String)>);
+    // Last inline rename as (track_id, old_title, new_title), for the undo toast
+    let mut last_rename = use_signal(|| None::<(String, String, String)>);
 
     // Check if album exists - only subscribe to this field via lens
-    if state.album().read().is_none() {
+    let Some(palette) = state.album().read().as_ref().map(|a| a.palette.clone()) else {
         return rsx! {};
-    }
+    };
+    let editing = *state.editing().read();
+    // Header fades from the cover's dominant color into the page background
+    let palette_style = palette
+        .map(|p| {
+            format!(
+                "background-image: linear-gradient(to bottom, {}66 0, transparent 480px);",
+                p.dominant
+            )
+        })
+        .unwrap_or_default();
 
     rsx! {
         // Scrollable container
         div {
             class: "flex-grow min-h-0 overflow-y-auto",
+            style: "{palette_style}",
             "data-testid": "album-detail",
 
             // Content wrapper with flex layout and width containment
-            div { class: "container mx-auto flex flex-col lg:flex-row gap-8 p-6",
-                // Left column - album info, cover, metadata, play button (sticky on desktop)
-                div { class: "w-full lg:flex-shrink-0 lg:w-[360px] lg:self-start lg:sticky lg:top-6",
+            div { class: "container mx-auto flex flex-col @5xl:flex-row gap-6 @5xl:gap-8 p-4 @3xl:p-6",
+                // Left column - album info, cover, metadata, play button (sticky on desktop).
+                // Stacked on narrow windows; capped so the cover doesn't fill the screen
+                div { class: "w-full max-w-md mx-auto @5xl:mx-0 @5xl:max-w-none @5xl:flex-shrink-0 @5xl:w-[360px] @5xl:self-start @5xl:sticky @5xl:top-6",
                     AlbumInfoSection {
                         state,
                         is_deleting,
                         is_exporting,
                         on_export: on_export_release,
+                        on_export_gift,
                         on_delete_album: EventHandler::new(move |_: String| {
                             show_album_delete_confirm.set(true);
                         }),
@@ -93,35 +155,79 @@ pub fn AlbumDetailView(
                         }),
                         on_play_album,
                         on_add_to_queue: on_add_album_to_queue,
+                        on_edit,
+                        offline_download,
+                        on_set_offline,
                     }
                 }
 
-                // Right column - release tabs + tracklist
+                // Right column - release tabs + tracklist, or the editor
                 div { class: "flex-1 min-w-0",
-                    ReleaseTabsSectionWrapper {
-                        state,
-                        is_deleting,
-                        is_exporting,
-                        export_error,
-                        torrent_info: torrent_info.clone(),
-                        on_release_select,
-                        on_view_files: move |id| show_release_info_modal.set(Some((id, Tab::Details))),
-                        on_delete_release: move |id| show_release_delete_confirm.set(Some(id)),
-                        on_export: on_export_release,
-                        on_start_seeding,
-                        on_stop_seeding,
-                    }
+                    if editing {
+                        AlbumEditorSection {
+                            state,
+                            on_save: on_save_edits,
+                            on_cancel: on_cancel_edit,
+                        }
+                    } else {
+                        ArchiveBannerSection { state, on_restore_release }
+                        ReferenceBannerSection { state, on_migrate_release }
+
+                        ReleaseTabsSectionWrapper {
+                            state,
+                            is_deleting,
+                            is_exporting,
+                            export_error,
+                            torrent_info: torrent_info.clone(),
+                            on_release_select,
+                            on_set_preferred: on_set_preferred_release,
+                            on_view_files: move |id| show_release_info_modal.set(Some((id, Tab::Details))),
+                            on_delete_release: move |id| show_release_delete_confirm.set(Some(id)),
+                            on_export: on_export_release,
+                            on_export_gift,
+                            on_compare: move |left: String| {
+                                let right = state
+                                    .releases()
+                                    .read()
+                                    .iter()
+                                    .find(|r| r.id != left)
+                                    .map(|r| r.id.clone());
+                                if let Some(right) = right {
+                                    show_compare.set(Some((left.clone(), right.clone())));
+                                    on_compare_releases.call((left, right));
+                                }
+                            },
+                            on_start_seeding,
+                            on_stop_seeding,
+                        }
 
-                    TrackListSection {
-                        state,
-                        tracks,
-                        playback,
-                        on_track_play,
-                        on_track_pause,
-                        on_track_resume,
-                        on_track_add_next,
-                        on_track_add_to_queue,
-                        on_track_export,
+                        TrackListSection {
+                            state,
+                            tracks,
+                            playback,
+                            click_action: track_click_action,
+                            highlighted_track_id,
+                            on_track_play,
+                            on_track_pause,
+                            on_track_resume,
+                            on_track_add_next,
+                            on_track_add_to_queue,
+                            on_track_export,
+                            on_track_rename: move |(track_id, title): (String, String)| {
+                                let old_title = tracks
+                                    .read()
+                                    .iter()
+                                    .find(|t| t.id == track_id)
+                                    .map(|t| t.title.clone())
+                                    .unwrap_or_default();
+                                last_rename.set(Some((track_id.clone(), old_title, title.clone())));
+                                on_track_rename.call((track_id, title));
+                            },
+                            on_track_bookmark_jump,
+                            on_track_bookmark_delete,
+                        }
+
+                        VideosSectionWrapper { state, on_play_video }
                     }
                 }
             }
@@ -155,12 +261,32 @@ pub fn AlbumDetailView(
             modal_images_error,
         }
 
+        CompareEditionsModalWrapper {
+            state,
+            show: show_compare,
+            on_change: move |pair: (String, String)| {
+                show_compare.set(Some(pair.clone()));
+                on_compare_releases.call(pair);
+            },
+        }
+
         if let Some(ref error) = export_error() {
             ExportErrorToast {
                 error: error.clone(),
                 on_dismiss: move |_| export_error.set(None),
             }
         }
+
+        if let Some((track_id, old_title, new_title)) = last_rename() {
+            TrackRenamedToast {
+                title: new_title,
+                on_undo: move |_| {
+                    last_rename.set(None);
+                    on_track_rename.call((track_id.clone(), old_title.clone()));
+                },
+                on_dismiss: move |_| last_rename.set(None),
+            }
+        }
     }
 }
 
@@ -175,11 +301,15 @@ fn AlbumInfoSection(
     is_deleting: Signal<bool>,
     is_exporting: Signal<bool>,
     on_export: EventHandler<String>,
+    on_export_gift: Option<EventHandler<String>>,
     on_delete_album: EventHandler<String>,
     on_view_release_info: EventHandler<String>,
     on_open_gallery: EventHandler<String>,
     on_play_album: EventHandler<Vec<String>>,
     on_add_to_queue: EventHandler<Vec<String>>,
+    on_edit: EventHandler<()>,
+    offline_download: Option<OfflineDownload>,
+    on_set_offline: Option<EventHandler<bool>>,
 ) -> Element {
     // Use lenses to read individual fields - avoids subscribing to track changes
     let album = state.album().read().clone();
@@ -195,6 +325,7 @@ fn AlbumInfoSection(
     // Use derived fields - these don't change during import progress updates
     let track_count = *state.track_count().read();
     let track_ids = state.track_ids().read().clone();
+    let can_keep_offline = *state.can_keep_offline().read();
 
     rsx! {
         AlbumCoverSection {
@@ -205,15 +336,20 @@ fn AlbumInfoSection(
             first_release_id: releases.first().map(|r| r.id.clone()),
             has_single_release: releases.len() == 1,
             on_export,
+            on_export_gift,
             on_delete_album,
             on_view_release_info,
             on_open_gallery,
+            on_edit,
+            can_keep_offline,
+            on_set_offline,
         }
         AlbumMetadata {
             album: album.clone(),
             artists,
             track_count,
             selected_release: releases.iter().find(|r| Some(r.id.clone()) == selected_release_id).cloned(),
+            dynamic_range: *state.dynamic_range().read(),
         }
         PlayAlbumButton {
             track_ids,
@@ -223,6 +359,89 @@ fn AlbumInfoSection(
             on_play_album,
             on_add_to_queue,
         }
+        OfflineStatus { is_offline: album.is_offline, download: offline_download }
+    }
+}
+
+/// Edit mode - seeds the form from the album and the selected release
+#[component]
+fn AlbumEditorSection(
+    state: ReadStore<AlbumDetailState>,
+    on_save: EventHandler<AlbumEdit>,
+    on_cancel: EventHandler<()>,
+) -> Element {
+    let Some(album) = state.album().read().clone() else {
+        return rsx! {};
+    };
+    let artists = state.artists().read().clone();
+    let selected_release_id = state.selected_release_id().read().clone();
+    let release = state
+        .releases()
+        .read()
+        .iter()
+        .find(|r| Some(&r.id) == selected_release_id.as_ref())
+        .cloned();
+    let tracks = state.tracks().read().clone();
+    let error = state.edit_error().read().clone();
+
+    rsx! {
+        AlbumEditor {
+            key: "{album.id}",
+            album,
+            artists,
+            release,
+            tracks,
+            error,
+            on_save,
+            on_cancel,
+        }
+    }
+}
+
+/// Archive banner for the selected release - uses lenses
+#[component]
+fn ArchiveBannerSection(
+    state: ReadStore<AlbumDetailState>,
+    on_restore_release: EventHandler<String>,
+) -> Element {
+    let mut dismissed = use_signal(|| false);
+    let Some(status) = *state.archive_status().read() else {
+        return rsx! {};
+    };
+    let Some(release_id) = state.selected_release_id().read().clone() else {
+        return rsx! {};
+    };
+    if status == ReleaseArchiveStatus::Restored && dismissed() {
+        return rsx! {};
+    }
+
+    rsx! {
+        ArchiveBanner {
+            status,
+            on_restore: move |_| on_restore_release.call(release_id.clone()),
+            on_dismiss: move |_| dismissed.set(true),
+        }
+    }
+}
+
+/// Reference banner for the selected release - uses lenses
+#[component]
+fn ReferenceBannerSection(
+    state: ReadStore<AlbumDetailState>,
+    on_migrate_release: EventHandler<String>,
+) -> Element {
+    let Some(reference) = state.reference().read().clone() else {
+        return rsx! {};
+    };
+    let Some(release_id) = state.selected_release_id().read().clone() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        ReferenceBanner {
+            reference,
+            on_migrate: move |_| on_migrate_release.call(release_id.clone()),
+        }
     }
 }
 
@@ -235,15 +454,19 @@ fn ReleaseTabsSectionWrapper(
     export_error: Signal<Option<String>>,
     torrent_info: std::collections::HashMap<String, ReleaseTorrentInfo>,
     on_release_select: EventHandler<String>,
+    on_set_preferred: EventHandler<Option<String>>,
     on_view_files: EventHandler<String>,
     on_delete_release: EventHandler<String>,
     on_export: EventHandler<String>,
+    on_export_gift: Option<EventHandler<String>>,
+    on_compare: EventHandler<String>,
     on_start_seeding: Option<EventHandler<String>>,
     on_stop_seeding: Option<EventHandler<String>>,
 ) -> Element {
     // Use lenses
     let releases = state.releases().read().clone();
     let selected_release_id = state.selected_release_id().read().clone();
+    let preferred_release_id = state.preferred_release_id().read().clone();
 
     if releases.len() <= 1 {
         return rsx! {};
@@ -253,13 +476,17 @@ fn ReleaseTabsSectionWrapper(
         ReleaseTabsSection {
             releases,
             selected_release_id,
+            preferred_release_id,
             on_release_select,
+            on_set_preferred,
             is_deleting,
             is_exporting,
             export_error,
             on_view_files,
             on_delete_release,
             on_export,
+            on_export_gift,
+            on_compare,
             torrent_info,
             on_start_seeding,
             on_stop_seeding,
@@ -273,12 +500,17 @@ fn TrackListSection(
     state: ReadStore<AlbumDetailState>,
     tracks: ReadStore<Vec<Track>>,
     playback: PlaybackDisplay,
+    click_action: TrackClickAction,
+    highlighted_track_id: Option<String>,
     on_track_play: EventHandler<String>,
     on_track_pause: EventHandler<()>,
     on_track_resume: EventHandler<()>,
     on_track_add_next: EventHandler<String>,
     on_track_add_to_queue: EventHandler<String>,
     on_track_export: EventHandler<String>,
+    on_track_rename: EventHandler<(String, String)>,
+    on_track_bookmark_jump: EventHandler<(String, u64)>,
+    on_track_bookmark_delete: EventHandler<String>,
 ) -> Element {
     // Use lenses for individual fields - avoids subscribing to track import_state changes
     let artists = state.artists().read().clone();
@@ -302,8 +534,29 @@ fn TrackListSection(
         PlaybackDisplay::Stopped => None,
     };
 
+    let mut selected_track_id = use_signal(|| None::<String>);
+
     // Use derived fields to avoid subscribing to track changes
     let track_count = *state.track_count().read();
+
+    // Select the highlighted track once it's listed, and bring it into view
+    use_effect(use_reactive(
+        (&highlighted_track_id, &track_count),
+        move |(highlighted_track_id, _)| {
+            let Some(track_id) = highlighted_track_id else {
+                return;
+            };
+            if !state.track_ids().read().contains(&track_id) {
+                return;
+            }
+            selected_track_id.set(Some(track_id.clone()));
+            let _ = document::eval(&format!(
+                "document.getElementById('{}')?.scrollIntoView({{ block: 'center' }})",
+                track_element_id(&track_id)
+            ));
+        },
+    ));
+
     if track_count == 0 {
         return rsx! {
             div { class: "text-center py-8 text-gray-400",
@@ -351,7 +604,7 @@ fn TrackListSection(
                     let is_loading = is_this_track
                         && matches!(playback, PlaybackDisplay::Loading { .. });
                     rsx! {
-                        div { key: "track-{track_id}",
+                        div { key: "track-{track_id}", id: track_element_id(&track_id),
                             if show_disc_header {
                                 h3 { class: "text-sm font-semibold text-gray-400 uppercase tracking-wide pt-4 pb-2 first:pt-0",
                                     "{disc_label}"
@@ -366,12 +619,18 @@ fn TrackListSection(
                                 is_paused,
                                 is_loading,
                                 show_spinner: is_loading,
+                                click_action,
+                                is_selected: selected_track_id.read().as_ref() == Some(&track_id),
+                                on_select: move |id| selected_track_id.set(Some(id)),
                                 on_play: on_track_play,
                                 on_pause: on_track_pause,
                                 on_resume: on_track_resume,
                                 on_add_next: on_track_add_next,
                                 on_add_to_queue: on_track_add_to_queue,
                                 on_export: on_track_export,
+                                on_rename: on_track_rename,
+                                on_bookmark_jump: on_track_bookmark_jump,
+                                on_bookmark_delete: on_track_bookmark_delete,
                             }
                         }
                     }
@@ -381,6 +640,27 @@ fn TrackListSection(
     }
 }
 
+/// DOM id of a track's row
+fn track_element_id(track_id: &str) -> String {
+    format!("album-track-{}", track_id)
+}
+
+/// Video extras section - uses lens
+#[component]
+fn VideosSectionWrapper(
+    state: ReadStore<AlbumDetailState>,
+    on_play_video: EventHandler<String>,
+) -> Element {
+    let videos = state.videos().read().clone();
+    if videos.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        VideosSection { videos, on_play: on_play_video }
+    }
+}
+
 // ============================================================================
 // Dialog wrappers - only read state when shown
 // ============================================================================
@@ -390,7 +670,7 @@ fn DeleteAlbumDialogWrapper(
     state: ReadStore<AlbumDetailState>,
     show: Signal<bool>,
     is_deleting: Signal<bool>,
-    on_delete_album: EventHandler<String>,
+    on_delete_album: EventHandler<(String, bool)>,
     on_album_deleted: EventHandler<()>,
 ) -> Element {
     // Use lenses
@@ -401,6 +681,7 @@ fn DeleteAlbumDialogWrapper(
         .map(|a| a.id.clone())
         .unwrap_or_default();
     let release_count = state.releases().read().len();
+    let stored_bytes: u64 = state.stored_bytes().read().values().sum();
 
     // Create read signal from show
     let is_open: ReadSignal<bool> = show.into();
@@ -410,10 +691,11 @@ fn DeleteAlbumDialogWrapper(
             is_open,
             album_id: album_id.clone(),
             release_count,
+            stored_bytes,
             is_deleting,
-            on_confirm: move |album_id: String| {
+            on_confirm: move |(album_id, keep_files): (String, bool)| {
                 show.set(false);
-                on_delete_album.call(album_id);
+                on_delete_album.call((album_id, keep_files));
                 on_album_deleted.call(());
             },
             on_cancel: move |_| show.set(false),
@@ -426,7 +708,7 @@ fn DeleteReleaseDialogWrapper(
     state: ReadStore<AlbumDetailState>,
     show: Signal<Option<String>>,
     is_deleting: Signal<bool>,
-    on_delete_release: EventHandler<String>,
+    on_delete_release: EventHandler<(String, bool)>,
     on_album_deleted: EventHandler<()>,
 ) -> Element {
     // Derive is_open from Option<String>
@@ -438,16 +720,23 @@ fn DeleteReleaseDialogWrapper(
     // Use lens
     let releases = state.releases().read().clone();
     let is_last = releases.len() == 1;
+    let stored_bytes = state
+        .stored_bytes()
+        .read()
+        .get(&release_id_to_delete)
+        .copied()
+        .unwrap_or(0);
 
     rsx! {
         DeleteReleaseDialog {
             is_open,
             release_id: release_id_to_delete.clone(),
             is_last_release: is_last,
+            stored_bytes,
             is_deleting,
-            on_confirm: move |release_id: String| {
+            on_confirm: move |(release_id, keep_files): (String, bool)| {
                 show.set(None);
-                on_delete_release.call(release_id);
+                on_delete_release.call((release_id, keep_files));
                 if is_last {
                     on_album_deleted.call(());
                 }
@@ -521,3 +810,31 @@ fn ReleaseInfoModalWrapper(
         }
     }
 }
+
+#[component]
+fn CompareEditionsModalWrapper(
+    state: ReadStore<AlbumDetailState>,
+    show: Signal<Option<(String, String)>>,
+    on_change: EventHandler<(String, String)>,
+) -> Element {
+    let is_open_memo = use_memo(move || show().is_some());
+    let is_open: ReadSignal<bool> = is_open_memo.into();
+
+    let Some((left_release_id, right_release_id)) = show() else {
+        return rsx! {};
+    };
+    let releases = state.releases().read().clone();
+    let comparison = state.comparison().read().clone();
+
+    rsx! {
+        CompareEditionsModal {
+            is_open,
+            releases,
+            left_release_id,
+            right_release_id,
+            comparison,
+            on_change,
+            on_close: move |_| show.set(None),
+        }
+    }
+}
@@ -46,6 +46,7 @@ pub fn AppLayoutView(
 #[component]
 fn LayoutContainer(children: Element) -> Element {
     rsx! {
-        div { class: "h-screen flex flex-col", {children} }
+        // Query container so views adapt to the window (or mock viewport) width
+        div { class: "@container h-screen flex flex-col", {children} }
     }
 }
@@ -23,6 +23,7 @@ use wasm_bindgen_x::JsCast;
 
 pub use crate::floating_ui::Placement;
 use crate::floating_ui::{self, ComputePositionOptions};
+use crate::ipc;
 
 /// Counter for generating unique dropdown IDs
 static DROPDOWN_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
@@ -60,7 +61,7 @@ pub fn Dropdown(
     let popover_id_clone = popover_id.clone();
 
     // Effect to handle open/close
-    use_effect(move || {
+    ipc::use_guarded_effect(move || {
         let is_open_val = is_open();
         let anchor_id = anchor_id.clone();
         let popover_id = popover_id_clone.clone();
@@ -118,15 +119,18 @@ pub fn Dropdown(
                 };
 
                 spawn(async move {
-                    if let Ok(result) =
-                        floating_ui::compute_position(&anchor, &floating, options).await
-                    {
-                        let style = format!(
-                            "position: absolute; top: 0; left: 0; width: max-content; margin: 0; transform: translate({}px, {}px); opacity: 1;",
-                            result.x, result.y
-                        );
-                        let _ = floating.set_attribute("style", &style);
-                    }
+                    let _ = ipc::guard_async(async move {
+                        if let Ok(result) =
+                            floating_ui::compute_position(&anchor, &floating, options).await
+                        {
+                            let style = format!(
+                                "position: absolute; top: 0; left: 0; width: max-content; margin: 0; transform: translate({}px, {}px); opacity: 1;",
+                                result.x, result.y
+                            );
+                            let _ = floating.set_attribute("style", &style);
+                        }
+                    })
+                    .await;
                 });
             }
         } else {
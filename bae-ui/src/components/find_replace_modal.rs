@@ -0,0 +1,277 @@
+//! Find and replace across album titles, track titles and artist names
+
+use crate::components::icons::XIcon;
+use crate::components::{Button, ButtonSize, ButtonVariant, Modal, TextInput, TextInputSize};
+use crate::display_types::{Album, Artist, FindReplaceField, FindReplaceRequest, FindReplaceRow};
+use dioxus::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Modal for batch metadata fixes over chosen albums. Changes are previewed
+/// in full before anything is written.
+#[component]
+pub fn FindReplaceModal(
+    is_open: ReadSignal<bool>,
+    albums: Vec<Album>,
+    artists_by_album: HashMap<String, Vec<Artist>>,
+    /// Values the current preview would change (None = not previewed yet)
+    preview: Option<Vec<FindReplaceRow>>,
+    /// Why the last preview or apply failed
+    error: Option<String>,
+    on_preview: EventHandler<FindReplaceRequest>,
+    on_apply: EventHandler<Vec<FindReplaceRow>>,
+    /// The pattern or selection changed, so the preview no longer applies
+    on_reset: EventHandler<()>,
+    on_close: EventHandler<()>,
+) -> Element {
+    let mut find = use_signal(String::new);
+    let mut replace = use_signal(String::new);
+    let mut use_regex = use_signal(|| false);
+    let mut case_sensitive = use_signal(|| false);
+    let mut fields = use_signal(|| FindReplaceField::ALL.to_vec());
+    let mut selected = use_signal(HashSet::<String>::new);
+    let mut album_filter = use_signal(String::new);
+
+    // Selected albums in library order, so previews list them that way
+    let album_ids: Vec<String> = albums
+        .iter()
+        .filter(|album| selected.read().contains(&album.id))
+        .map(|album| album.id.clone())
+        .collect();
+    let filter = album_filter().to_lowercase();
+    let shown: Vec<Album> = albums
+        .into_iter()
+        .filter(|album| filter.is_empty() || album.title.to_lowercase().contains(&filter))
+        .collect();
+    let shown_ids: Vec<String> = shown.iter().map(|album| album.id.clone()).collect();
+    let selected_count = album_ids.len();
+    let can_preview = !find().is_empty() && selected_count > 0 && !fields.read().is_empty();
+    let change_count = preview.as_ref().map_or(0, |rows| rows.len());
+
+    rsx! {
+        Modal { is_open, on_close: move |_| on_close.call(()),
+            div { class: "bg-gray-800 rounded-lg shadow-xl max-w-3xl w-full mx-4 max-h-[85vh] flex flex-col",
+                div { class: "flex items-center justify-between px-6 pt-6 pb-4 border-b border-gray-700",
+                    h2 { class: "text-xl font-bold text-white", "Find & Replace" }
+                    button {
+                        class: "text-gray-400 hover:text-white transition-colors",
+                        onclick: move |_| on_close.call(()),
+                        XIcon { class: "w-5 h-5" }
+                    }
+                }
+                div { class: "p-6 overflow-y-auto flex-1 space-y-5",
+                    div { class: "grid grid-cols-[6rem_1fr] gap-3 items-center",
+                        label { class: "text-sm text-gray-400", "Find" }
+                        TextInput {
+                            value: find(),
+                            on_input: move |value| {
+                                find.set(value);
+                                on_reset.call(());
+                            },
+                            size: TextInputSize::Small,
+                            placeholder: "[Remastered 2011]",
+                            monospace: use_regex(),
+                        }
+                        label { class: "text-sm text-gray-400", "Replace with" }
+                        TextInput {
+                            value: replace(),
+                            on_input: move |value| {
+                                replace.set(value);
+                                on_reset.call(());
+                            },
+                            size: TextInputSize::Small,
+                            placeholder: "Leave empty to remove",
+                            monospace: use_regex(),
+                        }
+                    }
+                    div { class: "flex flex-wrap gap-x-6 gap-y-2 text-sm text-gray-300",
+                        Checkbox {
+                            label: "Regular expression",
+                            checked: use_regex(),
+                            onchange: move |checked| {
+                                use_regex.set(checked);
+                                on_reset.call(());
+                            },
+                        }
+                        Checkbox {
+                            label: "Match case",
+                            checked: case_sensitive(),
+                            onchange: move |checked| {
+                                case_sensitive.set(checked);
+                                on_reset.call(());
+                            },
+                        }
+                        for field in FindReplaceField::ALL {
+                            Checkbox {
+                                key: "{field.label()}",
+                                label: field.label(),
+                                checked: fields.read().contains(&field),
+                                onchange: move |checked| {
+                                    fields.with_mut(|fields| {
+                                        fields.retain(|f| *f != field);
+                                        if checked {
+                                            fields.push(field);
+                                        }
+                                    });
+                                    on_reset.call(());
+                                },
+                            }
+                        }
+                    }
+
+                    div { class: "space-y-2",
+                        div { class: "flex items-center gap-3",
+                            span { class: "text-sm text-gray-400 flex-1", "Albums ({selected_count} selected)" }
+                            Button {
+                                variant: ButtonVariant::Ghost,
+                                size: ButtonSize::Small,
+                                onclick: {
+                                    let shown_ids = shown_ids.clone();
+                                    move |_| {
+                                        selected.with_mut(|selected| selected.extend(shown_ids.iter().cloned()));
+                                        on_reset.call(());
+                                    }
+                                },
+                                "Select all shown"
+                            }
+                            Button {
+                                variant: ButtonVariant::Ghost,
+                                size: ButtonSize::Small,
+                                onclick: move |_| {
+                                    selected.set(HashSet::new());
+                                    on_reset.call(());
+                                },
+                                "Clear"
+                            }
+                        }
+                        TextInput {
+                            value: album_filter(),
+                            on_input: move |value| album_filter.set(value),
+                            size: TextInputSize::Small,
+                            placeholder: "Filter albums",
+                        }
+                        div { class: "max-h-48 overflow-y-auto rounded-lg bg-gray-900/50 p-2 space-y-1",
+                            for album in shown {
+                                {
+                                    let album_id = album.id.clone();
+                                    let artist = artists_by_album
+                                        .get(&album.id)
+                                        .map(|artists| {
+                                            artists.iter().map(|a| a.name.as_str()).collect::<Vec<_>>().join(", ")
+                                        })
+                                        .unwrap_or_default();
+                                    rsx! {
+                                        label {
+                                            key: "{album.id}",
+                                            class: "flex items-center gap-2 px-2 py-1 rounded hover:bg-gray-700/50 cursor-pointer text-sm",
+                                            input {
+                                                r#type: "checkbox",
+                                                class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600",
+                                                checked: selected.read().contains(&album.id),
+                                                onchange: move |e| {
+                                                    let album_id = album_id.clone();
+                                                    selected.with_mut(|selected| {
+                                                        if e.checked() {
+                                                            selected.insert(album_id);
+                                                        } else {
+                                                            selected.remove(&album_id);
+                                                        }
+                                                    });
+                                                    on_reset.call(());
+                                                },
+                                            }
+                                            span { class: "text-gray-200 truncate", "{album.title}" }
+                                            span { class: "text-gray-500 truncate", "{artist}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    if let Some(error) = error {
+                        p { class: "text-sm text-red-400", "{error}" }
+                    }
+
+                    if let Some(rows) = preview {
+                        PreviewTable { rows }
+                    }
+                }
+                div { class: "flex justify-end gap-3 px-6 py-4 border-t border-gray-700",
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Medium,
+                        disabled: !can_preview,
+                        onclick: move |_| {
+                            on_preview
+                                .call(FindReplaceRequest {
+                                    album_ids: album_ids.clone(),
+                                    find: find(),
+                                    replace: replace(),
+                                    use_regex: use_regex(),
+                                    case_sensitive: case_sensitive(),
+                                    fields: fields(),
+                                })
+                        },
+                        "Preview"
+                    }
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        size: ButtonSize::Medium,
+                        disabled: change_count == 0,
+                        onclick: {
+                            let preview = preview.clone();
+                            move |_| {
+                                if let Some(rows) = preview.clone() {
+                                    on_apply.call(rows);
+                                }
+                            }
+                        },
+                        if change_count == 1 {
+                            "Apply 1 change"
+                        } else {
+                            "Apply {change_count} changes"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn Checkbox(label: &'static str, checked: bool, onchange: EventHandler<bool>) -> Element {
+    rsx! {
+        label { class: "flex items-center gap-2 cursor-pointer",
+            input {
+                r#type: "checkbox",
+                class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600",
+                checked,
+                onchange: move |e| onchange.call(e.checked()),
+            }
+            "{label}"
+        }
+    }
+}
+
+/// Every value the find-and-replace would change, before and after
+#[component]
+fn PreviewTable(rows: Vec<FindReplaceRow>) -> Element {
+    if rows.is_empty() {
+        return rsx! {
+            p { class: "text-sm text-gray-400 text-center py-4", "Nothing matches in the selected albums" }
+        };
+    }
+
+    rsx! {
+        div { class: "grid grid-cols-[4rem_1fr_1fr] gap-x-3 gap-y-1.5 text-sm",
+            div {}
+            div { class: "text-gray-500", "Before" }
+            div { class: "text-gray-500", "After" }
+            for row in rows {
+                div { class: "text-gray-500", "{row.field.row_label()}" }
+                div { class: "text-gray-400 break-words", "{row.before}" }
+                div { class: "text-white break-words", "{row.after}" }
+            }
+        }
+    }
+}
@@ -16,6 +16,7 @@ use dioxus_core::{Runtime, RuntimeGuard, Task};
 use wasm_bindgen_x::JsCast;
 
 use crate::floating_ui::{self, ComputePositionOptions, Placement};
+use crate::ipc;
 
 /// Delay before showing tooltip (in milliseconds)
 const TOOLTIP_DELAY_MS: u64 = 700;
@@ -128,10 +129,13 @@ pub fn use_tooltip_handle() -> TooltipHandle {
             task.cancel();
         }
         if let Some(cleanup) = blur_cleanup.peek().as_ref() {
-            let _ = cleanup.window.remove_event_listener_with_callback(
-                "blur",
-                cleanup.callback.as_ref().unchecked_ref(),
-            );
+            // Unmounting can race the window closing
+            let _ = ipc::guard(|| {
+                cleanup.window.remove_event_listener_with_callback(
+                    "blur",
+                    cleanup.callback.as_ref().unchecked_ref(),
+                )
+            });
         }
     });
 
@@ -174,7 +178,7 @@ pub fn TooltipPopover(
     local_anchor.set((handle.anchor)());
     let mut floating_ref: Signal<Option<Rc<MountedData>>> = use_signal(|| None);
 
-    use_effect(move || {
+    ipc::use_guarded_effect(move || {
         let visible = local_visible();
 
         let Some(floating_mounted) = floating_ref() else {
@@ -226,15 +230,18 @@ pub fn TooltipPopover(
             };
 
             spawn(async move {
-                if let Ok(result) =
-                    floating_ui::compute_position(&anchor_el, &floating, options).await
-                {
-                    let style = format!(
-                        "position: absolute; top: 0; left: 0; width: max-content; margin: 0; transform: translate({}px, {}px); opacity: 1;",
-                        result.x, result.y
-                    );
-                    let _ = floating.set_attribute("style", &style);
-                }
+                let _ = ipc::guard_async(async move {
+                    if let Ok(result) =
+                        floating_ui::compute_position(&anchor_el, &floating, options).await
+                    {
+                        let style = format!(
+                            "position: absolute; top: 0; left: 0; width: max-content; margin: 0; transform: translate({}px, {}px); opacity: 1;",
+                            result.x, result.y
+                        );
+                        let _ = floating.set_attribute("style", &style);
+                    }
+                })
+                .await;
             });
         } else {
             if !is_popover_open {
@@ -0,0 +1,193 @@
+//! Listening history view - plays grouped by day, with the most played
+//! tracks alongside
+
+use crate::components::helpers::{ErrorDisplay, LoadingSpinner};
+use crate::components::utils::{format_date, format_duration};
+use crate::components::{Button, ButtonSize, ButtonVariant};
+use crate::display_types::{TrackPlay, TrackPlayCount};
+use crate::stores::history::{HistoryState, HistoryStateStoreExt};
+use dioxus::prelude::*;
+
+const DATE_INPUT_CLASS: &str = "px-2.5 py-1.5 text-sm bg-gray-800/50 rounded-lg text-gray-300 focus:outline-none focus:ring-1 focus:ring-accent/50 [color-scheme:dark]";
+
+/// Listening history, filtered to a date range
+#[component]
+pub fn HistoryView(
+    state: ReadStore<HistoryState>,
+    /// First day shown, as YYYY-MM-DD (empty = no lower bound)
+    from: String,
+    /// Last day shown, as YYYY-MM-DD (empty = up to today)
+    to: String,
+    on_from_change: EventHandler<String>,
+    on_to_change: EventHandler<String>,
+    /// Play the given tracks in order
+    on_play: EventHandler<Vec<String>>,
+    on_album_click: EventHandler<String>,
+) -> Element {
+    let loading = *state.loading().read();
+    let error = state.error().read().clone();
+    let plays = state.plays().read().clone();
+    let top_tracks = state.top_tracks().read().clone();
+    let has_range = !from.is_empty() || !to.is_empty();
+    let days = group_by_day(plays);
+
+    rsx! {
+        div { class: "flex-grow overflow-y-auto flex flex-col py-6 @3xl:py-10",
+            div { class: "container mx-auto flex flex-col px-4 @3xl:px-0",
+                div { class: "flex flex-wrap items-center justify-between gap-3 mb-6",
+                    h1 { class: "text-2xl @3xl:text-3xl font-bold text-white", "History" }
+                    div { class: "flex items-center gap-2",
+                        label { class: "text-sm text-gray-400", "From" }
+                        input {
+                            r#type: "date",
+                            class: DATE_INPUT_CLASS,
+                            value: "{from}",
+                            max: if to.is_empty() { None } else { Some(to.clone()) },
+                            onchange: move |e| on_from_change.call(e.value()),
+                        }
+                        label { class: "text-sm text-gray-400 ml-2", "To" }
+                        input {
+                            r#type: "date",
+                            class: DATE_INPUT_CLASS,
+                            value: "{to}",
+                            min: if from.is_empty() { None } else { Some(from.clone()) },
+                            onchange: move |e| on_to_change.call(e.value()),
+                        }
+                        if has_range {
+                            Button {
+                                variant: ButtonVariant::Secondary,
+                                size: ButtonSize::Small,
+                                class: Some("ml-2".to_string()),
+                                onclick: move |_| {
+                                    on_from_change.call(String::new());
+                                    on_to_change.call(String::new());
+                                },
+                                "All Time"
+                            }
+                        }
+                    }
+                }
+                if loading {
+                    LoadingSpinner { message: "Loading history...".to_string() }
+                } else if let Some(err) = error {
+                    ErrorDisplay { message: err }
+                } else if days.is_empty() {
+                    p { class: "text-center py-12 text-gray-400",
+                        if has_range {
+                            "Nothing played in these dates"
+                        } else {
+                            "Tracks you listen to will show up here"
+                        }
+                    }
+                } else {
+                    div { class: "flex flex-col @3xl:flex-row gap-8",
+                        div { class: "flex-1 min-w-0 flex flex-col gap-6",
+                            for (day , plays) in days {
+                                div { key: "{day}",
+                                    h2 { class: "text-sm font-semibold text-gray-400 uppercase tracking-wide mb-2",
+                                        "{day}"
+                                    }
+                                    for play in plays {
+                                        HistoryRow {
+                                            key: "{play.id}",
+                                            play,
+                                            on_play,
+                                            on_album_click,
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        TopTracks { tracks: top_tracks, on_play }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn HistoryRow(
+    play: TrackPlay,
+    on_play: EventHandler<Vec<String>>,
+    on_album_click: EventHandler<String>,
+) -> Element {
+    let track = play.track;
+    let track_id = track.id.clone();
+    let album_id = track.album_id.clone();
+
+    rsx! {
+        div {
+            class: "grid grid-cols-[minmax(0,3fr)_minmax(0,2fr)_minmax(0,2fr)_4rem] gap-3 px-4 py-2 text-sm rounded cursor-default select-none hover:bg-gray-800/60",
+            ondoubleclick: move |_| on_play.call(vec![track_id.clone()]),
+            div { class: "text-gray-200 truncate", title: "{track.title}", "{track.title}" }
+            div { class: "text-gray-400 truncate", title: "{track.artist_name}", "{track.artist_name}" }
+            div { class: "truncate",
+                span {
+                    class: "text-gray-400 hover:text-white hover:underline cursor-pointer",
+                    title: "{track.album_title}",
+                    onclick: move |_| on_album_click.call(album_id.clone()),
+                    "{track.album_title}"
+                }
+            }
+            div { class: "text-right text-gray-500 tabular-nums",
+                if let Some(duration) = track.duration_ms {
+                    {format_duration(duration)}
+                }
+            }
+        }
+    }
+}
+
+/// Most played tracks in the range, with their play counts
+#[component]
+fn TopTracks(tracks: Vec<TrackPlayCount>, on_play: EventHandler<Vec<String>>) -> Element {
+    rsx! {
+        div { class: "@3xl:w-80 shrink-0",
+            div { class: "bg-gray-800/60 rounded-lg p-4",
+                h2 { class: "text-sm font-semibold text-gray-400 uppercase tracking-wide mb-3",
+                    "Most Played"
+                }
+                ol { class: "flex flex-col gap-1",
+                    for (index , entry) in tracks.into_iter().enumerate() {
+                        li {
+                            key: "{entry.track.id}",
+                            class: "flex items-center gap-3 px-2 py-1.5 rounded text-sm cursor-default select-none hover:bg-gray-700/60",
+                            ondoubleclick: {
+                                let track_id = entry.track.id.clone();
+                                move |_| on_play.call(vec![track_id.clone()])
+                            },
+                            span { class: "w-5 text-right text-gray-500 tabular-nums", "{index + 1}" }
+                            div { class: "flex-1 min-w-0",
+                                div { class: "text-gray-200 truncate", "{entry.track.title}" }
+                                div { class: "text-xs text-gray-500 truncate",
+                                    "{entry.track.artist_name}"
+                                }
+                            }
+                            span { class: "text-gray-400 tabular-nums",
+                                if entry.play_count == 1 {
+                                    "1 play"
+                                } else {
+                                    "{entry.play_count} plays"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Plays split into runs of the same day, keeping their order
+fn group_by_day(plays: Vec<TrackPlay>) -> Vec<(String, Vec<TrackPlay>)> {
+    let mut days: Vec<(String, Vec<TrackPlay>)> = Vec::new();
+    for play in plays {
+        let day = format_date(play.played_at);
+        match days.last_mut() {
+            Some((last, plays)) if *last == day => plays.push(play),
+            _ => days.push((day, vec![play])),
+        }
+    }
+    days
+}
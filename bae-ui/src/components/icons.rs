@@ -719,6 +719,31 @@ pub fn LayersIcon(#[props(default = "w-4 h-4")] class: &'static str) -> Element
     }
 }
 
+/// Video icon (film frame with play triangle)
+#[component]
+pub fn VideoIcon(#[props(default = "w-4 h-4")] class: &'static str) -> Element {
+    rsx! {
+        svg {
+            class: "{class}",
+            xmlns: "http://www.w3.org/2000/svg",
+            view_box: "0 0 24 24",
+            fill: "none",
+            stroke: "currentColor",
+            stroke_width: "2",
+            stroke_linecap: "round",
+            stroke_linejoin: "round",
+            rect {
+                x: "2",
+                y: "4",
+                width: "20",
+                height: "16",
+                rx: "2",
+            }
+            path { d: "m10 9 5 3-5 3z" }
+        }
+    }
+}
+
 /// Settings gear icon
 #[component]
 pub fn SettingsIcon(#[props(default = "w-4 h-4")] class: &'static str) -> Element {
@@ -737,3 +762,21 @@ pub fn SettingsIcon(#[props(default = "w-4 h-4")] class: &'static str) -> Elemen
         }
     }
 }
+
+/// Bookmark icon (named positions in a track)
+#[component]
+pub fn BookmarkIcon(#[props(default = "w-4 h-4")] class: &'static str) -> Element {
+    rsx! {
+        svg {
+            class: "{class}",
+            xmlns: "http://www.w3.org/2000/svg",
+            view_box: "0 0 24 24",
+            fill: "none",
+            stroke: "currentColor",
+            stroke_width: "2",
+            stroke_linecap: "round",
+            stroke_linejoin: "round",
+            path { d: "m19 21-7-4-7 4V5a2 2 0 0 1 2-2h10a2 2 0 0 1 2 2v16z" }
+        }
+    }
+}
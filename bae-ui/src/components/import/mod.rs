@@ -18,7 +18,7 @@ pub use workflow::{
     ImageLightboxView, ImportErrorDisplayView, ManualSearchPanelView, MatchItemView, MatchListView,
     MetadataDetectionPromptView, MetadataDisplayView, MultipleExactMatchesView,
     ReleaseSelectorView, ReleaseSidebarView, SearchSourceSelectorView, SelectedSourceView,
-    SmartFileDisplayView, TextFileModalView, TorrentFilesDisplayView, TorrentImportView,
-    TorrentImportViewProps, TorrentInfoDisplayView, TorrentTrackerDisplayView,
-    TrackerConnectionStatus, TrackerStatus,
+    SmartFileDisplayView, TextFileModalView, TorrentAlbumFoldersView, TorrentDownloadView,
+    TorrentFilesDisplayView, TorrentImportView, TorrentImportViewProps, TorrentInfoDisplayView,
+    TorrentTrackerDisplayView, TrackerConnectionStatus, TrackerStatus,
 };
@@ -8,6 +8,7 @@ use dioxus::prelude::*;
 pub enum ImportSource {
     #[default]
     Folder,
+    Archive,
     Torrent,
     Cd,
 }
@@ -16,6 +17,7 @@ impl ImportSource {
     pub fn label(&self) -> &'static str {
         match self {
             ImportSource::Folder => "Folder",
+            ImportSource::Archive => "Archive",
             ImportSource::Torrent => "Torrent",
             ImportSource::Cd => "CD",
         }
@@ -24,6 +26,7 @@ impl ImportSource {
     pub fn all() -> &'static [ImportSource] {
         &[
             ImportSource::Folder,
+            ImportSource::Archive,
             #[cfg(feature = "torrent")]
             ImportSource::Torrent,
             #[cfg(feature = "cd-rip")]
@@ -3,6 +3,8 @@
 //! Two-panel layout with resizable left sidebar containing:
 //! - Import header and source selector
 //! - Workflow-specific sidebar content (releases, CD drives, etc.)
+//!
+//! Narrow windows stack the header and a collapsible release list above the content.
 
 use super::source_selector::{ImportSource, ImportSourceSelectorView};
 use super::workflow::{
@@ -40,9 +42,11 @@ pub fn ImportView(
     let show_source_selector = ImportSource::all().len() > 1;
 
     if show_sidebar {
+        let candidate_count = state.read().detected_candidates.len();
+
         rsx! {
-            div { class: "flex flex-grow min-h-0",
-                // Left panel - resizable sidebar with header
+            div { class: "flex flex-col @4xl:flex-row flex-grow min-h-0",
+                // Left panel - resizable sidebar with header (wide windows)
                 ResizablePanel {
                     storage_key: "import-sidebar-width",
                     min_size: MIN_SIDEBAR_WIDTH,
@@ -51,13 +55,12 @@ pub fn ImportView(
                     grabber_span_ratio: 0.95,
                     direction: ResizeDirection::Horizontal,
                     position: PanelPosition::Relative,
+                    class: "@max-4xl:hidden",
                     div { class: "flex flex-col h-full",
-                        // Header with title and source selector
-                        div { class: "pt-6 px-5 pb-4 flex items-center gap-4",
-                            h1 { class: "text-2xl font-bold text-white", "Import" }
-                            if show_source_selector {
-                                ImportSourceSelectorView { selected_source, on_source_select }
-                            }
+                        ImportHeader {
+                            show_source_selector,
+                            selected_source,
+                            on_source_select,
                         }
                         // Sidebar content
                         div { class: "flex-1 min-h-0",
@@ -72,6 +75,29 @@ pub fn ImportView(
                         }
                     }
                 }
+                // Narrow windows: header with the release list collapsed above the content
+                div { class: "@4xl:hidden flex-shrink-0",
+                    ImportHeader {
+                        show_source_selector,
+                        selected_source,
+                        on_source_select,
+                    }
+                    details { class: "mx-2 mb-2 rounded-xl bg-gray-900/40",
+                        summary { class: "px-4 py-2 text-sm text-gray-300 cursor-pointer select-none",
+                            "Releases ({candidate_count})"
+                        }
+                        div { class: "h-64",
+                            ReleaseSidebarView {
+                                state,
+                                on_select: on_candidate_select,
+                                on_add_folder,
+                                on_remove: on_remove_candidate,
+                                on_clear_all,
+                                on_open_folder,
+                            }
+                        }
+                    }
+                }
                 // Right panel - main content
                 div { class: "flex-1 min-h-0 flex flex-col", {children} }
             }
@@ -80,12 +106,10 @@ pub fn ImportView(
         // No sidebar - header above full-width content
         rsx! {
             div { class: "flex flex-col flex-grow min-h-0",
-                // Header with title and source selector
-                div { class: "pt-6 px-5 pb-4 flex items-center gap-4",
-                    h1 { class: "text-2xl font-bold text-white", "Import" }
-                    if show_source_selector {
-                        ImportSourceSelectorView { selected_source, on_source_select }
-                    }
+                ImportHeader {
+                    show_source_selector,
+                    selected_source,
+                    on_source_select,
                 }
                 // Main content - full width
                 div { class: "flex-1 min-h-0 flex flex-col", {children} }
@@ -93,3 +117,20 @@ pub fn ImportView(
         }
     }
 }
+
+/// Import title with the source selector
+#[component]
+fn ImportHeader(
+    show_source_selector: bool,
+    selected_source: ImportSource,
+    on_source_select: EventHandler<ImportSource>,
+) -> Element {
+    rsx! {
+        div { class: "pt-4 px-4 pb-3 @4xl:pt-6 @4xl:px-5 @4xl:pb-4 flex flex-wrap items-center gap-4",
+            h1 { class: "text-2xl font-bold text-white", "Import" }
+            if show_source_selector {
+                ImportSourceSelectorView { selected_source, on_source_select }
+            }
+        }
+    }
+}
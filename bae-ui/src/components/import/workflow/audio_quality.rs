@@ -0,0 +1,108 @@
+//! Audio quality report for the confirm step
+
+use crate::components::icons::{AlertTriangleIcon, CheckIcon, LoaderIcon};
+use crate::components::{Button, ButtonSize, ButtonVariant};
+use crate::display_types::{AudioFileQuality, AudioQualityCheck};
+use dioxus::prelude::*;
+
+/// Offers a full decode of the release's audio before it's imported, then
+/// lists the files that failed to decode, came up short, or clip
+#[component]
+pub fn AudioQualityReportView(
+    check: Option<AudioQualityCheck>,
+    on_check: EventHandler<()>,
+) -> Element {
+    rsx! {
+        div { class: "rounded-lg bg-gray-800/50 p-4 text-sm",
+            match check {
+                None => rsx! {
+                    div { class: "flex items-center gap-3",
+                        p { class: "flex-1 text-gray-400",
+                            "Decode every file to catch truncated or corrupt audio before it's stored."
+                        }
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            size: ButtonSize::Small,
+                            onclick: move |_| on_check.call(()),
+                            "Check audio"
+                        }
+                    }
+                },
+                Some(AudioQualityCheck::Checking) => rsx! {
+                    div { class: "flex items-center gap-2 text-gray-400",
+                        LoaderIcon { class: "w-4 h-4 animate-spin" }
+                        "Decoding audio..."
+                    }
+                },
+                Some(AudioQualityCheck::Done(files)) => rsx! {
+                    AudioQualityResults { files }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn AudioQualityResults(files: Vec<AudioFileQuality>) -> Element {
+    let flagged: Vec<AudioFileQuality> = files
+        .iter()
+        .filter(|f| f.is_damaged() || f.clipped_runs > 0 || f.decoder_fallback.is_some())
+        .cloned()
+        .collect();
+    let damaged_count = files.iter().filter(|f| f.is_damaged()).count();
+    let file_count = files.len();
+
+    rsx! {
+        if flagged.is_empty() {
+            div { class: "flex items-center gap-2 text-gray-300",
+                CheckIcon { class: "w-4 h-4 text-green-400" }
+                "All {file_count} files decoded cleanly"
+            }
+        } else {
+            div { class: "space-y-3",
+                div { class: "flex items-center gap-2 text-gray-300",
+                    AlertTriangleIcon { class: "w-4 h-4 text-amber-500" }
+                    if damaged_count > 0 {
+                        "{damaged_count} of {file_count} files look damaged"
+                    } else {
+                        "No damage found, but some files need attention"
+                    }
+                }
+                ul { class: "space-y-1",
+                    for file in flagged {
+                        li { class: "flex gap-3",
+                            span { class: "text-gray-300 truncate", "{file.name}" }
+                            span { class: "text-gray-500 ml-auto flex-shrink-0", {describe_issues(&file)} }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn describe_issues(file: &AudioFileQuality) -> String {
+    if let Some(ref error) = file.error {
+        return error.clone();
+    }
+
+    let mut issues = Vec::new();
+    if file.decode_errors > 0 {
+        issues.push(format!("{} decode errors", file.decode_errors));
+    }
+    if let Some(mismatch_ms) = file.duration_mismatch_ms {
+        let seconds = mismatch_ms.unsigned_abs() as f64 / 1000.0;
+        if mismatch_ms < 0 {
+            issues.push(format!("{:.1}s shorter than its header", seconds));
+        } else {
+            issues.push(format!("{:.1}s longer than its header", seconds));
+        }
+    }
+    if file.clipped_runs > 0 {
+        issues.push(format!("{} clipped passages", file.clipped_runs));
+    }
+    if let Some(ref fallback) = file.decoder_fallback {
+        issues.push(format!("only decodes with {}", fallback));
+    }
+    issues.join(", ")
+}
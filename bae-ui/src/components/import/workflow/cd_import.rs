@@ -57,8 +57,15 @@ pub struct CdImportViewProps {
     pub on_manual_match_select: EventHandler<usize>,
     pub on_search: EventHandler<()>,
     pub on_cancel_search: EventHandler<()>,
+    pub on_load_more: EventHandler<()>,
     pub on_manual_confirm: EventHandler<MatchCandidate>,
     pub on_retry_discid_lookup: EventHandler<()>,
+    /// Rip under placeholder metadata after a failed DiscID lookup
+    pub on_rip_unidentified: EventHandler<()>,
+    /// (MusicBrainz release, visible) as match candidates scroll in and out
+    /// of view
+    #[props(default)]
+    pub on_cover_visible: Option<EventHandler<(String, bool)>>,
     pub on_select_remote_cover: EventHandler<String>,
     pub on_select_local_cover: EventHandler<String>,
     pub on_storage_profile_change: EventHandler<Option<String>>,
@@ -113,8 +120,11 @@ pub fn CdImportView(props: CdImportViewProps) -> Element {
                             on_manual_match_select: props.on_manual_match_select,
                             on_search: props.on_search,
                             on_cancel_search: props.on_cancel_search,
+                            on_load_more: props.on_load_more,
                             on_manual_confirm: props.on_manual_confirm,
                             on_retry_discid_lookup: props.on_retry_discid_lookup,
+                            on_rip_unidentified: props.on_rip_unidentified,
+                            on_cover_visible: props.on_cover_visible,
                         }
                     }
                 },
@@ -157,8 +167,11 @@ fn CdIdentifyContent(
     on_manual_match_select: EventHandler<usize>,
     on_search: EventHandler<()>,
     on_cancel_search: EventHandler<()>,
+    on_load_more: EventHandler<()>,
     on_manual_confirm: EventHandler<MatchCandidate>,
     on_retry_discid_lookup: EventHandler<()>,
+    on_rip_unidentified: EventHandler<()>,
+    on_cover_visible: Option<EventHandler<(String, bool)>>,
 ) -> Element {
     // Read TOC info at leaf level
     let st = state.read();
@@ -184,13 +197,16 @@ fn CdIdentifyContent(
                 CdTocDisplayView { toc: toc_info, is_reading: is_looking_up }
             }
             match identify_mode {
-                IdentifyMode::Created | IdentifyMode::DiscIdLookup(_) => rsx! {},
+                IdentifyMode::Created
+                | IdentifyMode::DiscIdLookup(_)
+                | IdentifyMode::ManualEntry(_) => rsx! {},
                 IdentifyMode::MultipleExactMatches(_) => rsx! {
                     MultipleExactMatchesView {
                         state,
                         on_select: on_exact_match_select,
                         on_confirm: on_confirm_exact_match,
                         on_switch_to_manual_search,
+                        on_cover_visible,
                     }
                 },
                 IdentifyMode::ManualSearch => rsx! {
@@ -199,6 +215,7 @@ fn CdIdentifyContent(
                             error_message: discid_lookup_error,
                             is_retrying: is_looking_up,
                             on_retry: on_retry_discid_lookup,
+                            on_identify_later: Some(on_rip_unidentified),
                         }
                     }
                     ManualSearchPanelView {
@@ -212,8 +229,10 @@ fn CdIdentifyContent(
                         on_match_select: on_manual_match_select,
                         on_search,
                         on_cancel_search,
+                        on_load_more,
                         on_confirm: on_manual_confirm,
                         on_switch_to_exact_matches,
+                        on_cover_visible,
                     }
                 },
             }
@@ -255,20 +274,20 @@ fn CdConfirmContent(
         .unwrap_or_default();
     let selected_profile_id = st.get_storage_profile_id();
 
-    let (is_importing, preparing_step_text, import_error) = st
+    let (is_importing, prepare_stages, import_error) = st
         .current_candidate_state()
         .and_then(|s| match s {
-            CandidateState::Confirming(cs) => Some(&cs.phase),
+            CandidateState::Confirming(cs) => Some(cs),
             _ => None,
         })
-        .map(|phase| match phase {
-            ConfirmPhase::Ready => (false, None, None),
-            ConfirmPhase::Preparing(msg) => (false, Some(msg.clone()), None),
-            ConfirmPhase::Importing => (true, None, None),
-            ConfirmPhase::Failed(err) => (false, None, Some(err.clone())),
-            ConfirmPhase::Completed => (false, None, None),
+        .map(|cs| match &cs.phase {
+            ConfirmPhase::Ready => (false, Vec::new(), None),
+            ConfirmPhase::Preparing => (true, cs.prepare_checklist(), None),
+            ConfirmPhase::Importing => (true, Vec::new(), None),
+            ConfirmPhase::Failed(err) => (false, cs.prepare_checklist(), Some(err.clone())),
+            ConfirmPhase::Completed => (false, Vec::new(), None),
         })
-        .unwrap_or((false, None, None));
+        .unwrap_or((false, Vec::new(), None));
 
     let import_error = import_error.or_else(|| st.import_error_message.clone());
     let duplicate_album_id = st.duplicate_album_id.clone();
@@ -295,8 +314,9 @@ fn CdConfirmContent(
                 remote_cover_url: candidate.cover_url.clone(),
                 storage_profiles,
                 selected_profile_id,
+                storage_estimate: None,
                 is_importing,
-                preparing_step_text,
+                prepare_stages,
                 on_select_remote_cover,
                 on_select_local_cover,
                 on_storage_profile_change,
@@ -31,15 +31,22 @@ pub fn CdRipperView(
                             }
                             Select {
                                 value: selected_drive.clone().unwrap_or_default(),
-                                onchange: move |val: String| {
-                                    if !val.is_empty() {
-                                        on_drive_select.call(val);
+                                onchange: {
+                                    let drives = drives.clone();
+                                    move |val: String| {
+                                        // A drive that's ripping can't take another import
+                                        let ripping = drives
+                                            .iter()
+                                            .any(|d| d.device_path == val && d.is_ripping);
+                                        if !val.is_empty() && !ripping {
+                                            on_drive_select.call(val);
+                                        }
                                     }
                                 },
                                 for drive in drives.iter() {
                                     SelectOption {
                                         value: "{drive.device_path}",
-                                        label: drive.name.clone(),
+                                        label: if drive.is_ripping { format!("{} (ripping)", drive.name) } else { drive.name.clone() },
                                     }
                                 }
                             }
@@ -1,11 +1,15 @@
 //! Confirmation view component
 
-use crate::components::icons::ImageIcon;
+use crate::components::icons::{AlertTriangleIcon, CheckIcon, ImageIcon, LoaderIcon};
+use crate::components::utils::format_file_size;
 use crate::components::{
-    Button, ButtonSize, ButtonVariant, ChromelessButton, Modal, Select, SelectOption,
+    Button, ButtonSize, ButtonVariant, ChromelessButton, Modal, QuotaLevel, Select, SelectOption,
     StorageProfile,
 };
-use crate::display_types::{FileInfo, MatchCandidate, MatchSourceType, SelectedCover};
+use crate::display_types::{
+    FileInfo, MatchCandidate, MatchSourceType, SelectedCover, StorageEstimate,
+};
+use crate::stores::import::{PrepareStageProgress, PrepareStageStatus};
 use dioxus::prelude::*;
 
 /// Final confirmation view before import
@@ -25,10 +29,12 @@ pub fn ConfirmationView(
     storage_profiles: ReadSignal<Vec<StorageProfile>>,
     /// Currently selected storage profile ID
     selected_profile_id: Option<String>,
+    /// Cloud footprint with the selected profile, if it's a cloud profile
+    storage_estimate: Option<StorageEstimate>,
     /// Whether import is in progress
     is_importing: bool,
-    /// Current preparing step text (if preparing)
-    preparing_step_text: Option<String>,
+    /// Preparation checklist, while preparing or after a stage failed
+    prepare_stages: Vec<PrepareStageProgress>,
     /// Called when user selects a remote cover
     on_select_remote_cover: EventHandler<String>,
     /// Called when user selects a local cover
@@ -44,17 +50,30 @@ pub fn ConfirmationView(
 ) -> Element {
     let mut show_cover_modal = use_signal(|| false);
     let is_cover_modal_open: ReadSignal<bool> = show_cover_modal.into();
+    let mut quota_override = use_signal(|| false);
+
+    let over_quota_profile = storage_profiles
+        .read()
+        .iter()
+        .find(|p| Some(&p.id) == selected_profile_id.as_ref())
+        .filter(|p| p.quota_level.is_some_and(|l| l != QuotaLevel::Normal))
+        .cloned();
+    let blocked_by_quota = over_quota_profile
+        .as_ref()
+        .is_some_and(|p| p.quota_level == Some(QuotaLevel::Exceeded))
+        && !*quota_override.read();
 
     let release_year = candidate.year.clone();
     let original_year = candidate.original_year.clone();
 
     let (format_text, country_text, label_text) = match candidate.source_type {
-        MatchSourceType::MusicBrainz => (
+        // Stored releases keep what their source gave them
+        MatchSourceType::MusicBrainz | MatchSourceType::Stored => (
             candidate.format.clone(),
             candidate.country.clone(),
             candidate.label.clone(),
         ),
-        MatchSourceType::Discogs => (None, None, None),
+        MatchSourceType::Discogs | MatchSourceType::Manual => (None, None, None),
     };
 
     let has_cover_options = !artwork_files.is_empty() || remote_cover_url.is_some();
@@ -143,6 +162,7 @@ pub fn ConfirmationView(
                     value: selected_profile_id.clone().unwrap_or_else(|| "__none__".to_string()),
                     disabled: is_importing,
                     onchange: move |val: String| {
+                        quota_override.set(false);
                         if val == "__none__" {
                             on_storage_profile_change.call(None);
                         } else {
@@ -168,16 +188,10 @@ pub fn ConfirmationView(
                     "Configure"
                 }
 
-                // Import status and button
-                if is_importing {
-                    if let Some(ref step) = preparing_step_text {
-                        span { class: "text-sm text-gray-400", "{step}" }
-                    }
-                }
                 Button {
                     variant: ButtonVariant::Primary,
                     size: ButtonSize::Small,
-                    disabled: is_importing,
+                    disabled: is_importing || blocked_by_quota,
                     loading: is_importing,
                     onclick: move |_| on_confirm.call(()),
                     if is_importing {
@@ -186,6 +200,19 @@ pub fn ConfirmationView(
                     "Import"
                 }
             }
+            if let Some(estimate) = storage_estimate {
+                div { class: "flex justify-end gap-3 px-5 -mt-3 text-xs text-gray-500",
+                    span { "{estimate.object_count} objects" }
+                    span { "{format_file_size(estimate.stored_bytes as i64)} stored" }
+                    span { {format!("about ${:.2}/month", estimate.monthly_cost)} }
+                }
+            }
+            if let Some(profile) = over_quota_profile {
+                QuotaNotice { profile, quota_override, is_importing }
+            }
+            if !prepare_stages.is_empty() {
+                PrepareChecklist { stages: prepare_stages }
+            }
         }
 
         // Cover art selection modal
@@ -284,3 +311,75 @@ pub fn ConfirmationView(
         }
     }
 }
+
+/// What happens between clicking Import and the import being queued, one
+/// row per stage. A failure shows its error on the stage it happened in.
+#[component]
+fn PrepareChecklist(stages: Vec<PrepareStageProgress>) -> Element {
+    rsx! {
+        ul { class: "flex flex-col items-end gap-1 px-5 -mt-3 text-xs",
+            for progress in stages {
+                li { key: "{progress.stage.label()}", class: "flex items-center gap-2",
+                    match progress.status {
+                        PrepareStageStatus::Pending => rsx! {
+                            span { class: "text-gray-500", "{progress.stage.label()}" }
+                            div { class: "w-3.5 h-3.5 rounded-full border border-gray-600" }
+                        },
+                        PrepareStageStatus::Running => rsx! {
+                            span { class: "text-gray-200", "{progress.stage.label()}..." }
+                            LoaderIcon { class: "w-3.5 h-3.5 text-gray-300 animate-spin" }
+                        },
+                        PrepareStageStatus::Done => rsx! {
+                            span { class: "text-gray-400", "{progress.stage.label()}" }
+                            CheckIcon { class: "w-3.5 h-3.5 text-green-400" }
+                        },
+                        PrepareStageStatus::Skipped => rsx! {
+                            span { class: "text-gray-600 line-through", "{progress.stage.label()}" }
+                            div { class: "w-3.5 h-3.5" }
+                        },
+                        PrepareStageStatus::Failed(ref error) => rsx! {
+                            span { class: "text-red-400", "{progress.stage.label()}: {error}" }
+                            AlertTriangleIcon { class: "w-3.5 h-3.5 text-red-400" }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Warning for a storage profile near or over its quota. Over quota, the
+/// import stays blocked until the user overrides it.
+#[component]
+fn QuotaNotice(
+    profile: StorageProfile,
+    quota_override: Signal<bool>,
+    is_importing: bool,
+) -> Element {
+    let used = format_file_size(profile.used_bytes as i64);
+    let quota = format_file_size(profile.quota_bytes.unwrap_or(0) as i64);
+
+    rsx! {
+        if profile.quota_level == Some(QuotaLevel::Exceeded) {
+            div { class: "mx-5 -mt-2 p-3 bg-red-900/30 border border-red-700 rounded-lg flex items-center gap-4",
+                p { class: "flex-1 text-sm text-red-300",
+                    "{profile.name} is over its quota ({used} of {quota})."
+                }
+                label { class: "flex items-center gap-2 text-sm text-red-200 cursor-pointer",
+                    input {
+                        r#type: "checkbox",
+                        class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600",
+                        checked: *quota_override.read(),
+                        disabled: is_importing,
+                        onchange: move |e| quota_override.set(e.checked()),
+                    }
+                    "Import anyway"
+                }
+            }
+        } else {
+            p { class: "px-5 -mt-2 text-right text-xs text-amber-400",
+                "{profile.name} is nearing its quota ({used} of {quota})"
+            }
+        }
+    }
+}
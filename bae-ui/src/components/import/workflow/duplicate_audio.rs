@@ -0,0 +1,99 @@
+//! Duplicate recording check for the confirm step
+
+use crate::components::icons::{AlertTriangleIcon, CheckIcon, LoaderIcon};
+use crate::components::{Button, ButtonSize, ButtonVariant};
+use crate::display_types::{DuplicateAudioCheck, DuplicateAudioGroup};
+use dioxus::prelude::*;
+
+/// Offers to fingerprint the release's audio to find the same track stored
+/// twice, then lets the user pick which copy of each to import
+#[component]
+pub fn DuplicateAudioView(
+    check: Option<DuplicateAudioCheck>,
+    on_check: EventHandler<()>,
+    /// (group index, file index) of the copy to keep
+    on_keep: EventHandler<(usize, usize)>,
+) -> Element {
+    rsx! {
+        div { class: "rounded-lg bg-gray-800/50 p-4 text-sm",
+            match check {
+                None => rsx! {
+                    div { class: "flex items-center gap-3",
+                        p { class: "flex-1 text-gray-400",
+                            "Fingerprint the audio to find tracks that are in this folder more than once."
+                        }
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            size: ButtonSize::Small,
+                            onclick: move |_| on_check.call(()),
+                            "Find duplicates"
+                        }
+                    }
+                },
+                Some(DuplicateAudioCheck::Checking) => rsx! {
+                    div { class: "flex items-center gap-2 text-gray-400",
+                        LoaderIcon { class: "w-4 h-4 animate-spin" }
+                        "Fingerprinting audio..."
+                    }
+                },
+                Some(DuplicateAudioCheck::Done(groups)) if groups.is_empty() => rsx! {
+                    div { class: "flex items-center gap-2 text-gray-300",
+                        CheckIcon { class: "w-4 h-4 text-green-400" }
+                        "No duplicate tracks found"
+                    }
+                },
+                Some(DuplicateAudioCheck::Done(groups)) => rsx! {
+                    div { class: "space-y-3",
+                        div { class: "flex items-center gap-2 text-gray-300",
+                            AlertTriangleIcon { class: "w-4 h-4 text-amber-500" }
+                            "{groups.len()} tracks have more than one copy. Only the selected copy is imported."
+                        }
+                        for (group_index , group) in groups.into_iter().enumerate() {
+                            DuplicateGroupChoice { group_index, group, on_keep }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn DuplicateGroupChoice(
+    group_index: usize,
+    group: DuplicateAudioGroup,
+    on_keep: EventHandler<(usize, usize)>,
+) -> Element {
+    let name = format!("duplicate_audio_{}", group_index);
+
+    rsx! {
+        div { class: "space-y-1 pl-6",
+            for (file_index , file) in group.files.iter().enumerate() {
+                label {
+                    class: "flex items-center gap-2 cursor-pointer group",
+                    title: "{file}",
+                    input {
+                        r#type: "radio",
+                        name: "{name}",
+                        class: "accent-gray-200",
+                        checked: group.keep == file_index,
+                        onchange: move |_| on_keep.call((group_index, file_index)),
+                    }
+                    span { class: "text-gray-300 group-hover:text-white transition-colors truncate",
+                        {short_path(file)}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parent folder and file name, enough to tell copies in different subfolders apart
+fn short_path(path: &str) -> String {
+    let mut parts = path.rsplit(['/', '\\']);
+    let file = parts.next().unwrap_or(path);
+    match parts.next() {
+        Some(parent) if !parent.is_empty() => format!("{}/{}", parent, file),
+        _ => file.to_string(),
+    }
+}
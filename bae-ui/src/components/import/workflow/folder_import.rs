@@ -19,14 +19,18 @@
 //! actually render values.
 
 use super::{
-    ConfirmationView, DiscIdPill, DiscIdSource, ImportErrorDisplayView, LoadingIndicator,
-    ManualSearchPanelView, MultipleExactMatchesView, SmartFileDisplayView,
+    AudioQualityReportView, ConfirmationView, DiscIdPill, DiscIdSource, DuplicateAudioView,
+    ImportErrorDisplayView, LoadingIndicator, ManualEntryView, ManualSearchPanelView,
+    MultipleExactMatchesView, NeedleDropView, SmartFileDisplayView,
 };
 use crate::components::icons::{CloudOffIcon, LoaderIcon};
 use crate::components::StorageProfile;
 use crate::components::{Button, ButtonSize, ButtonVariant};
 use crate::components::{PanelPosition, ResizablePanel, ResizeDirection};
-use crate::display_types::{IdentifyMode, ImportStep, MatchCandidate, SearchSource, SearchTab};
+use crate::display_types::{
+    AudioContentInfo, IdentifyMode, ImportStep, ManualEntry, MatchCandidate, SearchSource,
+    SearchTab, StorageEstimate,
+};
 use crate::stores::import::{CandidateState, ConfirmPhase, ImportState, ImportStateStoreExt};
 use dioxus::prelude::*;
 
@@ -54,6 +58,12 @@ pub struct FolderImportViewProps {
     // === External data (not in ImportState) ===
     /// Storage profiles (from app context)
     pub storage_profiles: ReadSignal<Vec<StorageProfile>>,
+    /// Cloud footprint with the selected storage profile, if it's a cloud profile
+    pub storage_estimate: Option<StorageEstimate>,
+
+    /// Releases come from an archive the user picks, not a folder
+    #[props(default)]
+    pub from_archive: bool,
 
     // === Callbacks ===
     pub on_folder_select_click: EventHandler<()>,
@@ -73,15 +83,32 @@ pub struct FolderImportViewProps {
     pub on_manual_match_select: EventHandler<usize>,
     pub on_search: EventHandler<()>,
     pub on_cancel_search: EventHandler<()>,
+    pub on_load_more: EventHandler<()>,
     pub on_manual_confirm: EventHandler<MatchCandidate>,
+    pub on_switch_to_manual_entry: EventHandler<()>,
+    pub on_manual_entry_confirm: EventHandler<ManualEntry>,
+    /// Save the entry's artist, title and cover as a series template
+    pub on_save_release_template: EventHandler<ManualEntry>,
     pub on_retry_discid_lookup: EventHandler<()>,
+    /// (MusicBrainz release, visible) as match candidates scroll in and out
+    /// of view
+    #[props(default)]
+    pub on_cover_visible: Option<EventHandler<(String, bool)>>,
     pub on_select_remote_cover: EventHandler<String>,
     pub on_select_local_cover: EventHandler<String>,
     pub on_storage_profile_change: EventHandler<Option<String>>,
     pub on_edit: EventHandler<()>,
     pub on_confirm: EventHandler<()>,
+    pub on_check_audio_quality: EventHandler<()>,
+    pub on_check_duplicate_audio: EventHandler<()>,
+    /// (group index, file index) of the duplicate copy to import
+    pub on_keep_duplicate: EventHandler<(usize, usize)>,
     pub on_configure_storage: EventHandler<()>,
     pub on_view_duplicate: EventHandler<String>,
+    pub on_split_sides: EventHandler<()>,
+    /// (side index, track index, start in ms)
+    pub on_side_track_start_change: EventHandler<(usize, usize, u64)>,
+    pub on_cancel_side_split: EventHandler<()>,
 }
 
 /// Folder import workflow view - main content area only
@@ -104,13 +131,14 @@ pub fn FolderImportView(props: FolderImportViewProps) -> Element {
             div { class: "flex-1 flex flex-col",
                 EmptyView {
                     is_scanning,
+                    from_archive: props.from_archive,
                     on_folder_select: props.on_folder_select_click,
                 }
             }
         } else {
             // Detail pane: unified area for selected folder
             // Uses subtle background and rounded corners to visually group Files + Workflow as one unit
-            div { class: "flex-1 flex flex-col min-h-0 m-2 ml-0 bg-gray-900/40 rounded-xl overflow-clip",
+            div { class: "flex-1 flex flex-col min-h-0 m-2 @4xl:ml-0 bg-gray-900/40 rounded-xl overflow-clip",
                 // Context header showing folder name and step
                 DetailHeader { state }
 
@@ -131,6 +159,7 @@ pub fn FolderImportView(props: FolderImportViewProps) -> Element {
                             state,
                             step,
                             storage_profiles: props.storage_profiles,
+                            storage_estimate: props.storage_estimate,
                             on_skip_detection: props.on_skip_detection,
                             on_exact_match_select: props.on_exact_match_select,
                             on_confirm_exact_match: props.on_confirm_exact_match,
@@ -145,15 +174,26 @@ pub fn FolderImportView(props: FolderImportViewProps) -> Element {
                             on_manual_match_select: props.on_manual_match_select,
                             on_search: props.on_search,
                             on_cancel_search: props.on_cancel_search,
+                            on_load_more: props.on_load_more,
                             on_manual_confirm: props.on_manual_confirm,
+                            on_switch_to_manual_entry: props.on_switch_to_manual_entry,
+                            on_manual_entry_confirm: props.on_manual_entry_confirm,
+                            on_save_release_template: props.on_save_release_template,
                             on_retry_discid_lookup: props.on_retry_discid_lookup,
+                            on_cover_visible: props.on_cover_visible,
                             on_select_remote_cover: props.on_select_remote_cover,
                             on_select_local_cover: props.on_select_local_cover,
                             on_storage_profile_change: props.on_storage_profile_change,
                             on_edit: props.on_edit,
                             on_confirm: props.on_confirm,
+                            on_check_audio_quality: props.on_check_audio_quality,
+                            on_check_duplicate_audio: props.on_check_duplicate_audio,
+                            on_keep_duplicate: props.on_keep_duplicate,
                             on_configure_storage: props.on_configure_storage,
                             on_view_duplicate: props.on_view_duplicate,
+                            on_split_sides: props.on_split_sides,
+                            on_side_track_start_change: props.on_side_track_start_change,
+                            on_cancel_side_split: props.on_cancel_side_split,
                         }
                     }
                 }
@@ -168,13 +208,29 @@ pub fn FolderImportView(props: FolderImportViewProps) -> Element {
 
 /// Empty state shown when no candidates are detected yet
 #[component]
-fn EmptyView(is_scanning: bool, on_folder_select: EventHandler<()>) -> Element {
+fn EmptyView(is_scanning: bool, from_archive: bool, on_folder_select: EventHandler<()>) -> Element {
     rsx! {
         div { class: "flex-1 flex items-center justify-center px-6 py-4",
             div { class: "w-full max-w-3xl text-center space-y-3",
                 if is_scanning {
                     LoaderIcon { class: "w-5 h-5 text-gray-400 animate-spin mx-auto" }
-                    p { class: "text-sm text-gray-400", "Scanning folder for releases..." }
+                    p { class: "text-sm text-gray-400",
+                        if from_archive {
+                            "Extracting archive..."
+                        } else {
+                            "Scanning folder for releases..."
+                        }
+                    }
+                } else if from_archive {
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        size: ButtonSize::Medium,
+                        onclick: move |_| on_folder_select.call(()),
+                        "Select archive"
+                    }
+                    p { class: "text-sm text-gray-400",
+                        "Extracts zip, 7z or rar downloads and scans them for releases"
+                    }
                 } else {
                     Button {
                         variant: ButtonVariant::Primary,
@@ -195,6 +251,7 @@ fn WorkflowContent(
     state: ReadStore<ImportState>,
     step: ImportStep,
     storage_profiles: ReadSignal<Vec<StorageProfile>>,
+    storage_estimate: Option<StorageEstimate>,
     on_skip_detection: EventHandler<()>,
     on_exact_match_select: EventHandler<usize>,
     on_confirm_exact_match: EventHandler<MatchCandidate>,
@@ -209,15 +266,26 @@ fn WorkflowContent(
     on_manual_match_select: EventHandler<usize>,
     on_search: EventHandler<()>,
     on_cancel_search: EventHandler<()>,
+    on_load_more: EventHandler<()>,
     on_manual_confirm: EventHandler<MatchCandidate>,
+    on_switch_to_manual_entry: EventHandler<()>,
+    on_manual_entry_confirm: EventHandler<ManualEntry>,
+    on_save_release_template: EventHandler<ManualEntry>,
     on_retry_discid_lookup: EventHandler<()>,
+    on_cover_visible: Option<EventHandler<(String, bool)>>,
     on_select_remote_cover: EventHandler<String>,
     on_select_local_cover: EventHandler<String>,
     on_storage_profile_change: EventHandler<Option<String>>,
     on_edit: EventHandler<()>,
     on_confirm: EventHandler<()>,
+    on_check_audio_quality: EventHandler<()>,
+    on_check_duplicate_audio: EventHandler<()>,
+    on_keep_duplicate: EventHandler<(usize, usize)>,
     on_configure_storage: EventHandler<()>,
     on_view_duplicate: EventHandler<String>,
+    on_split_sides: EventHandler<()>,
+    on_side_track_start_change: EventHandler<(usize, usize, u64)>,
+    on_cancel_side_split: EventHandler<()>,
 ) -> Element {
     rsx! {
         div { class: "flex-1 min-h-0 overflow-auto bg-gray-900/40 rounded-tl-xl",
@@ -239,21 +307,33 @@ fn WorkflowContent(
                         on_manual_match_select,
                         on_search,
                         on_cancel_search,
+                        on_load_more,
                         on_manual_confirm,
+                        on_switch_to_manual_entry,
+                        on_manual_entry_confirm,
+                        on_save_release_template,
                         on_retry_discid_lookup,
+                        on_cover_visible,
                     }
                 },
                 ImportStep::Confirm => rsx! {
                     ConfirmStep {
                         state,
                         storage_profiles,
+                        storage_estimate,
                         on_select_remote_cover,
                         on_select_local_cover,
                         on_storage_profile_change,
                         on_edit,
                         on_confirm,
+                        on_check_audio_quality,
+                        on_check_duplicate_audio,
+                        on_keep_duplicate,
                         on_configure_storage,
                         on_view_duplicate,
+                        on_split_sides,
+                        on_side_track_start_change,
+                        on_cancel_side_split,
                     }
                 },
             }
@@ -283,11 +363,21 @@ fn IdentifyStep(
     on_manual_match_select: EventHandler<usize>,
     on_search: EventHandler<()>,
     on_cancel_search: EventHandler<()>,
+    on_load_more: EventHandler<()>,
     on_manual_confirm: EventHandler<MatchCandidate>,
+    on_switch_to_manual_entry: EventHandler<()>,
+    on_manual_entry_confirm: EventHandler<ManualEntry>,
+    on_save_release_template: EventHandler<ManualEntry>,
     on_retry_discid_lookup: EventHandler<()>,
+    on_cover_visible: Option<EventHandler<(String, bool)>>,
 ) -> Element {
     // Read to determine mode - this is routing
     let mode = state.read().get_identify_mode();
+    let candidate_key = state
+        .read()
+        .current_candidate_key
+        .clone()
+        .unwrap_or_default();
 
     rsx! {
         match mode {
@@ -306,6 +396,7 @@ fn IdentifyStep(
                     on_select: on_exact_match_select,
                     on_confirm: on_confirm_exact_match,
                     on_switch_to_manual_search,
+                    on_cover_visible,
                 }
             },
             IdentifyMode::ManualSearch => rsx! {
@@ -320,8 +411,21 @@ fn IdentifyStep(
                     on_match_select: on_manual_match_select,
                     on_search,
                     on_cancel_search,
+                    on_load_more,
                     on_confirm: on_manual_confirm,
                     on_switch_to_exact_matches,
+                    on_enter_manually: Some(on_switch_to_manual_entry),
+                    on_cover_visible,
+                }
+            },
+            IdentifyMode::ManualEntry(entry) => rsx! {
+                ManualEntryView {
+                    key: "{candidate_key}",
+                    entry,
+                    templates: state.release_templates().read().clone(),
+                    on_confirm: on_manual_entry_confirm,
+                    on_save_template: on_save_release_template,
+                    on_cancel: on_switch_to_manual_search,
                 }
             },
         }
@@ -337,13 +441,20 @@ fn IdentifyStep(
 fn ConfirmStep(
     state: ReadStore<ImportState>,
     storage_profiles: ReadSignal<Vec<StorageProfile>>,
+    storage_estimate: Option<StorageEstimate>,
     on_select_remote_cover: EventHandler<String>,
     on_select_local_cover: EventHandler<String>,
     on_storage_profile_change: EventHandler<Option<String>>,
     on_edit: EventHandler<()>,
     on_confirm: EventHandler<()>,
+    on_check_audio_quality: EventHandler<()>,
+    on_check_duplicate_audio: EventHandler<()>,
+    on_keep_duplicate: EventHandler<(usize, usize)>,
     on_configure_storage: EventHandler<()>,
     on_view_duplicate: EventHandler<String>,
+    on_split_sides: EventHandler<()>,
+    on_side_track_start_change: EventHandler<(usize, usize, u64)>,
+    on_cancel_side_split: EventHandler<()>,
 ) -> Element {
     // Read state at this level to get confirm-specific data
     let st = state.read();
@@ -356,27 +467,51 @@ fn ConfirmStep(
         .unwrap_or_default();
     let selected_profile_id = st.get_storage_profile_id();
 
-    let (is_importing, preparing_step_text, import_error) = st
+    let (is_importing, prepare_stages, import_error) = st
         .current_candidate_state()
         .and_then(|s| match s {
-            CandidateState::Confirming(cs) => Some(&cs.phase),
+            CandidateState::Confirming(cs) => Some(cs),
             _ => None,
         })
-        .map(|phase| match phase {
-            ConfirmPhase::Ready => (false, None, None),
-            ConfirmPhase::Preparing(msg) => (false, Some(msg.clone()), None),
-            ConfirmPhase::Importing => (true, None, None),
-            ConfirmPhase::Failed(err) => (false, None, Some(err.clone())),
-            ConfirmPhase::Completed => (false, None, None),
+        .map(|cs| match &cs.phase {
+            ConfirmPhase::Ready => (false, Vec::new(), None),
+            ConfirmPhase::Preparing => (true, cs.prepare_checklist(), None),
+            ConfirmPhase::Importing => (true, Vec::new(), None),
+            ConfirmPhase::Failed(err) => (false, cs.prepare_checklist(), Some(err.clone())),
+            ConfirmPhase::Completed => (false, Vec::new(), None),
         })
-        .unwrap_or((false, None, None));
+        .unwrap_or((false, Vec::new(), None));
 
     let import_error = import_error.or_else(|| st.import_error_message.clone());
     let duplicate_album_id = st.duplicate_album_id.clone();
+    let audio_quality_check = st
+        .current_candidate_key
+        .as_ref()
+        .and_then(|key| st.audio_quality_checks.get(key))
+        .cloned();
+    let duplicate_audio_check = st
+        .current_candidate_key
+        .as_ref()
+        .and_then(|key| st.duplicate_audio_checks.get(key))
+        .cloned();
+    let needle_drop_check = st
+        .current_candidate_key
+        .as_ref()
+        .and_then(|key| st.needle_drop_checks.get(key))
+        .cloned();
+    let has_track_files = st
+        .current_candidate_state()
+        .is_some_and(|s| matches!(s.files().audio, AudioContentInfo::TrackFiles(_)));
 
     let Some(candidate) = confirmed_candidate else {
         return rsx! {};
     };
+    // Offered for vinyl releases recorded one file per side
+    let is_vinyl = candidate
+        .format
+        .as_ref()
+        .is_some_and(|f| f.to_lowercase().contains("vinyl"));
+    let show_needle_drop = needle_drop_check.is_some() || (is_vinyl && has_track_files);
 
     rsx! {
         div { class: "space-y-6",
@@ -388,8 +523,9 @@ fn ConfirmStep(
                 remote_cover_url: candidate.cover_url.clone(),
                 storage_profiles,
                 selected_profile_id,
+                storage_estimate,
                 is_importing,
-                preparing_step_text,
+                prepare_stages,
                 on_select_remote_cover,
                 on_select_local_cover,
                 on_storage_profile_change,
@@ -398,6 +534,26 @@ fn ConfirmStep(
                 on_configure_storage,
             }
 
+            AudioQualityReportView {
+                check: audio_quality_check,
+                on_check: on_check_audio_quality,
+            }
+
+            DuplicateAudioView {
+                check: duplicate_audio_check,
+                on_check: on_check_duplicate_audio,
+                on_keep: on_keep_duplicate,
+            }
+
+            if show_needle_drop {
+                NeedleDropView {
+                    check: needle_drop_check,
+                    on_split: on_split_sides,
+                    on_start_change: on_side_track_start_change,
+                    on_cancel: on_cancel_side_split,
+                }
+            }
+
             ImportErrorDisplayView {
                 error_message: import_error,
                 duplicate_album_id,
@@ -472,6 +628,8 @@ fn FilesColumn(
             grabber_span_ratio: 0.8,
             direction: ResizeDirection::Horizontal,
             position: PanelPosition::Relative,
+            // Very narrow windows give the workflow the full width
+            class: "@max-2xl:hidden",
             snap_points,
             div { class: "h-full overflow-y-auto pt-1 px-4 pb-4 bg-gray-800/30",
                 SmartFileDisplayView {
@@ -0,0 +1,149 @@
+//! Manual entry view component - release details typed in by hand
+
+use crate::components::{Button, ButtonSize, ButtonVariant, TextInput, TextInputSize};
+use crate::display_types::{ManualEntry, ReleaseTemplate};
+use dioxus::prelude::*;
+
+/// Form for releases no metadata database knows about. Starts from what
+/// detection found and edits a local copy until confirmed. Saved series
+/// templates fill in the artist, next title and cover in one click.
+#[component]
+pub fn ManualEntryView(
+    entry: ManualEntry,
+    templates: Vec<ReleaseTemplate>,
+    on_confirm: EventHandler<ManualEntry>,
+    /// Save the draft's artist, title and cover as a series template
+    on_save_template: EventHandler<ManualEntry>,
+    /// Go back to searching the databases
+    on_cancel: EventHandler<()>,
+) -> Element {
+    let mut draft = use_signal(|| entry.clone());
+    let can_continue = {
+        let d = draft.read();
+        !d.title.trim().is_empty() && !d.artist.trim().is_empty() && !d.tracks.is_empty()
+    };
+    // A series needs a volume number in the title to count up from
+    let can_save_template = {
+        let d = draft.read();
+        let (artist, title) = (d.artist.trim(), d.title.trim());
+        !artist.is_empty()
+            && title.contains(|c: char| c.is_ascii_digit())
+            && !templates
+                .iter()
+                .any(|t| t.artist == artist && t.next_title == title)
+    };
+    let tracks = draft.read().tracks.clone();
+
+    rsx! {
+        div { class: "p-5 space-y-4",
+            div { class: "flex justify-between items-center",
+                p { class: "text-sm text-gray-300",
+                    "Enter the details for a release that isn't in MusicBrainz or Discogs"
+                }
+                Button {
+                    variant: ButtonVariant::Outline,
+                    size: ButtonSize::Small,
+                    onclick: move |_| on_cancel.call(()),
+                    "Search instead"
+                }
+            }
+
+            if !templates.is_empty() {
+                div { class: "flex flex-wrap items-center gap-2",
+                    span { class: "text-xs text-gray-400 mr-1", "Series" }
+                    for template in templates.iter().cloned() {
+                        Button {
+                            key: "{template.id}",
+                            variant: ButtonVariant::Outline,
+                            size: ButtonSize::Small,
+                            onclick: move |_| draft.write().apply_template(&template),
+                            "{template.artist} · {template.next_title}"
+                        }
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800/20 rounded-lg p-4 space-y-4",
+                div { class: "flex gap-3",
+                    div { class: "flex-1",
+                        label { class: "block text-xs text-gray-400 mb-1.5", "Artist" }
+                        TextInput {
+                            value: draft.read().artist.clone(),
+                            on_input: move |v| draft.write().artist = v,
+                            size: TextInputSize::Medium,
+                            autofocus: true,
+                        }
+                    }
+                    div { class: "flex-1",
+                        label { class: "block text-xs text-gray-400 mb-1.5", "Album" }
+                        TextInput {
+                            value: draft.read().title.clone(),
+                            on_input: move |v| draft.write().title = v,
+                            size: TextInputSize::Medium,
+                        }
+                    }
+                    div { class: "w-24 shrink-0",
+                        label { class: "block text-xs text-gray-400 mb-1.5", "Year" }
+                        TextInput {
+                            value: draft.read().year.clone(),
+                            on_input: move |v| draft.write().year = v,
+                            size: TextInputSize::Medium,
+                            placeholder: "Optional",
+                        }
+                    }
+                }
+
+                div {
+                    label { class: "block text-xs text-gray-400 mb-1.5", "Cover" }
+                    TextInput {
+                        value: draft.read().cover_url.clone().unwrap_or_default(),
+                        on_input: move |v: String| {
+                            draft.write().cover_url = (!v.trim().is_empty()).then_some(v);
+                        },
+                        size: TextInputSize::Medium,
+                        placeholder: "Image URL (optional)",
+                    }
+                }
+
+                div { class: "space-y-1.5",
+                    label { class: "block text-xs text-gray-400", "Tracks" }
+                    if tracks.is_empty() {
+                        p { class: "text-sm text-gray-500", "No audio files found" }
+                    }
+                    for (index , title) in tracks.into_iter().enumerate() {
+                        div { key: "{index}", class: "flex items-center gap-3",
+                            span { class: "w-6 text-right text-sm text-gray-500 tabular-nums",
+                                "{index + 1}"
+                            }
+                            div { class: "flex-1",
+                                TextInput {
+                                    value: title,
+                                    on_input: move |v| draft.write().tracks[index] = v,
+                                    size: TextInputSize::Small,
+                                    placeholder: "Untitled",
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "flex justify-between items-center",
+                Button {
+                    variant: ButtonVariant::Ghost,
+                    size: ButtonSize::Small,
+                    disabled: !can_save_template,
+                    onclick: move |_| on_save_template.call(draft.read().clone()),
+                    "Save as series template"
+                }
+                Button {
+                    variant: ButtonVariant::Primary,
+                    size: ButtonSize::Medium,
+                    disabled: !can_continue,
+                    onclick: move |_| on_confirm.call(draft.read().clone()),
+                    "Continue"
+                }
+            }
+        }
+    }
+}
@@ -6,7 +6,7 @@ use super::{DiscIdPill, DiscIdSource, LoadingIndicator};
 use crate::components::{Button, ButtonSize, ButtonVariant, TextInput, TextInputSize};
 use crate::display_types::{MatchCandidate, SearchSource, SearchTab};
 use crate::floating_ui::Placement;
-use crate::stores::import::ImportState;
+use crate::stores::import::{ImportState, SearchField};
 use dioxus::prelude::*;
 
 /// Manual search panel with tabs for General/Catalog#/Barcode search
@@ -24,8 +24,16 @@ pub fn ManualSearchPanelView(
     on_match_select: EventHandler<usize>,
     on_search: EventHandler<()>,
     on_cancel_search: EventHandler<()>,
+    on_load_more: EventHandler<()>,
     on_confirm: EventHandler<MatchCandidate>,
     on_switch_to_exact_matches: EventHandler<String>,
+    /// Offers typing in the details when nothing matches (folder imports only)
+    #[props(default)]
+    on_enter_manually: Option<EventHandler<()>>,
+    /// Called with a candidate's MusicBrainz release when it scrolls into or
+    /// out of view
+    #[props(default)]
+    on_cover_visible: Option<EventHandler<(String, bool)>>,
 ) -> Element {
     // Read state at this leaf component
     let st = state.read();
@@ -72,6 +80,23 @@ pub fn ManualSearchPanelView(
         .map(|s| s.search_results.clone())
         .unwrap_or_default();
     let selected = search_state.as_ref().and_then(|s| s.selected_result_index);
+    let result_count = candidates.len();
+    let result_counts = search_state
+        .as_ref()
+        .map(|s| s.result_counts.clone())
+        .unwrap_or_default();
+    let loading_more = search_state
+        .as_ref()
+        .map(|s| s.is_loading_more)
+        .unwrap_or(false);
+    let has_more = search_state
+        .as_ref()
+        .map(|s| s.has_more_results())
+        .unwrap_or(false);
+    let recent_artists = st.recent_search_values(source, SearchField::Artist);
+    let recent_albums = st.recent_search_values(source, SearchField::Album);
+    let recent_catalogs = st.recent_search_values(source, SearchField::CatalogNumber);
+    let recent_barcodes = st.recent_search_values(source, SearchField::Barcode);
 
     drop(st);
 
@@ -151,8 +176,14 @@ pub fn ManualSearchPanelView(
                             div { class: "flex gap-3",
                                 div { class: "flex-1",
                                     label { class: "block text-xs text-gray-400 mb-1.5", "Artist" }
+                                    datalist { id: "recent-search-artist",
+                                        for value in recent_artists.iter() {
+                                            option { value: "{value}" }
+                                        }
+                                    }
                                     TextInput {
                                         value: artist,
+                                        list: "recent-search-artist".to_string(),
                                         on_input: move |v| on_artist_change.call(v),
                                         size: TextInputSize::Medium,
                                         autofocus: true,
@@ -161,8 +192,14 @@ pub fn ManualSearchPanelView(
                                 }
                                 div { class: "flex-1",
                                     label { class: "block text-xs text-gray-400 mb-1.5", "Album" }
+                                    datalist { id: "recent-search-album",
+                                        for value in recent_albums.iter() {
+                                            option { value: "{value}" }
+                                        }
+                                    }
                                     TextInput {
                                         value: album,
+                                        list: "recent-search-album".to_string(),
                                         on_input: move |v| on_album_change.call(v),
                                         size: TextInputSize::Medium,
                                         disabled: searching,
@@ -184,8 +221,14 @@ pub fn ManualSearchPanelView(
                             div { class: "flex gap-3",
                                 div { class: "flex-1",
                                     label { class: "block text-xs text-gray-400 mb-1.5", "Catalog Number" }
+                                    datalist { id: "recent-search-catalog-number",
+                                        for value in recent_catalogs.iter() {
+                                            option { value: "{value}" }
+                                        }
+                                    }
                                     TextInput {
                                         value: catalog,
+                                        list: "recent-search-catalog-number".to_string(),
                                         on_input: move |v| on_catalog_number_change.call(v),
                                         size: TextInputSize::Medium,
                                         placeholder: "e.g. WPCR-80001",
@@ -209,8 +252,14 @@ pub fn ManualSearchPanelView(
                             div { class: "flex gap-3",
                                 div { class: "flex-1",
                                     label { class: "block text-xs text-gray-400 mb-1.5", "Barcode" }
+                                    datalist { id: "recent-search-barcode",
+                                        for value in recent_barcodes.iter() {
+                                            option { value: "{value}" }
+                                        }
+                                    }
                                     TextInput {
                                         value: barcode,
+                                        list: "recent-search-barcode".to_string(),
                                         on_input: move |v| on_barcode_change.call(v),
                                         size: TextInputSize::Medium,
                                         placeholder: "e.g. 4943674251780",
@@ -257,12 +306,60 @@ pub fn ManualSearchPanelView(
                     p { class: "text-gray-400", "No results found" }
                 }
             } else if !candidates.is_empty() {
+                // Loading more keeps this branch (it isn't `searching`) and
+                // only appends rows, so nothing above is remounted and the
+                // scroll position is kept
+                match result_counts.as_slice() {
+                    [] => rsx! {},
+                    [count] => rsx! {
+                        p { class: "text-xs text-gray-400",
+                            "Showing {result_count} of {count.total} results from {count.source.display_name()}"
+                        }
+                    },
+                    counts => rsx! {
+                        p { class: "text-xs text-gray-400 flex gap-3",
+                            span { "Showing {result_count} results" }
+                            for count in counts.iter() {
+                                span { key: "{count.source.display_name()}",
+                                    "{count.source.display_name()}: {count.total}"
+                                }
+                            }
+                        }
+                    },
+                }
                 MatchResultsPanel {
                     candidates,
                     selected_index: selected,
                     on_select: move |index| on_match_select.call(index),
                     on_confirm,
                     confirm_button_text: "Confirm",
+                    on_cover_visible,
+                }
+                if has_more {
+                    div { class: "flex justify-center",
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            size: ButtonSize::Small,
+                            disabled: loading_more,
+                            loading: loading_more,
+                            onclick: move |_| on_load_more.call(()),
+                            "Load more"
+                        }
+                    }
+                }
+            }
+
+            if let Some(on_enter_manually) = on_enter_manually {
+                if !searching {
+                    div { class: "flex items-center justify-center gap-2 pt-2 text-sm text-gray-400",
+                        "Own recording or bootleg?"
+                        Button {
+                            variant: ButtonVariant::Ghost,
+                            size: ButtonSize::Small,
+                            onclick: move |_| on_enter_manually.call(()),
+                            "Enter details by hand"
+                        }
+                    }
                 }
             }
         }
@@ -1,6 +1,7 @@
 //! Match item view component
 
 use crate::components::icons::ImageIcon;
+use crate::components::{Pill, PillVariant};
 use crate::display_types::{MatchCandidate, MatchSourceType};
 use dioxus::prelude::*;
 
@@ -10,6 +11,10 @@ pub fn MatchItemView(
     candidate: MatchCandidate,
     is_selected: bool,
     on_select: EventHandler<()>,
+    /// Called with the candidate's MusicBrainz release when it scrolls into
+    /// or out of view
+    #[props(default)]
+    on_cover_visible: Option<EventHandler<(String, bool)>>,
 ) -> Element {
     let border_class = if is_selected {
         "border-transparent bg-blue-900/30 ring-1 ring-blue-500"
@@ -36,13 +41,29 @@ pub fn MatchItemView(
                 .as_ref()
                 .map(|c| format!("Catalog: {}", c)),
         ),
-        MatchSourceType::Discogs => (None, None, None, None),
+        MatchSourceType::Discogs | MatchSourceType::Manual | MatchSourceType::Stored => {
+            (None, None, None, None)
+        }
     };
 
+    let cover = candidate
+        .cover_thumbnail_url
+        .clone()
+        .or_else(|| candidate.cover_url.clone());
+    let release_id = candidate.musicbrainz_release_id.clone();
+
     rsx! {
         div {
             class: "border rounded-lg px-3 py-2 cursor-pointer transition-colors {border_class}",
             onclick: move |_| on_select.call(()),
+            onvisible: move |evt: VisibleEvent| {
+                let (Some(handler), Some(release_id)) = (on_cover_visible, release_id.clone()) else {
+                    return;
+                };
+                if let Ok(visible) = evt.data().is_intersecting() {
+                    handler.call((release_id, visible));
+                }
+            },
 
             div { class: "flex items-center gap-3",
                 // Radio indicator
@@ -54,7 +75,7 @@ pub fn MatchItemView(
 
                 // Cover art
                 div { class: "w-10 h-10 flex-shrink-0 bg-gray-700 rounded overflow-clip",
-                    if let Some(ref cover_url) = candidate.cover_url {
+                    if let Some(ref cover_url) = cover {
                         img {
                             src: "{cover_url}",
                             alt: "Album cover",
@@ -69,7 +90,10 @@ pub fn MatchItemView(
 
                 // Info
                 div { class: "flex-1 min-w-0",
-                    h4 { class: "text-sm font-medium text-white truncate", "{candidate.title}" }
+                    div { class: "flex items-center gap-2",
+                        h4 { class: "text-sm font-medium text-white truncate", "{candidate.title}" }
+                        Pill { variant: PillVariant::Muted, "{candidate.source_type.display_name()}" }
+                    }
                     div { class: "text-xs text-gray-400 flex flex-wrap gap-x-3",
                         if let Some(ref year) = candidate.year {
                             span { "{year}" }
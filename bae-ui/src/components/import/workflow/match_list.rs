@@ -1,16 +1,36 @@
 //! Match list view component
 
 use super::match_item::MatchItemView;
+use crate::components::list_navigation::{
+    list_item_id, list_key_action, scroll_list_item_into_view, ListKeyAction,
+};
 use crate::display_types::MatchCandidate;
 use dioxus::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counter for generating unique list IDs
+static MATCH_LIST_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 /// Displays a list of match candidates with selection
+///
+/// The list is focusable: arrow keys move the selection, Enter confirms it
+/// and Escape gives up focus.
 #[component]
 pub fn MatchListView(
     candidates: Vec<MatchCandidate>,
     selected_index: Option<usize>,
     on_select: EventHandler<usize>,
+    on_confirm: EventHandler<usize>,
+    /// Called with a candidate's MusicBrainz release when it scrolls into or
+    /// out of view
+    #[props(default)]
+    on_cover_visible: Option<EventHandler<(String, bool)>>,
 ) -> Element {
+    let list_id = use_hook(|| {
+        let id = MATCH_LIST_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+        format!("match-list-{}", id)
+    });
+
     if candidates.is_empty() {
         return rsx! {
             p { class: "text-gray-400 text-center",
@@ -19,14 +39,43 @@ pub fn MatchListView(
         };
     }
 
+    let candidate_count = candidates.len();
+
     rsx! {
-        div { class: "space-y-2",
+        div {
+            class: "space-y-2 rounded-lg focus:outline-none focus-visible:ring-1 focus-visible:ring-blue-500/50",
+            tabindex: 0,
+            role: "listbox",
+            onkeydown: {
+                let list_id = list_id.clone();
+                move |evt: KeyboardEvent| {
+                    match list_key_action(&evt.key(), selected_index, candidate_count) {
+                        Some(ListKeyAction::Move(index)) => {
+                            evt.prevent_default();
+                            on_select.call(index);
+                            scroll_list_item_into_view(&list_id, index);
+                        }
+                        Some(ListKeyAction::Confirm(index)) => {
+                            evt.prevent_default();
+                            on_confirm.call(index);
+                        }
+                        Some(ListKeyAction::Dismiss) => {
+                            let _ = document::eval("document.activeElement?.blur()");
+                        }
+                        None => {}
+                    }
+                }
+            },
+            // Keyed by position: load more only appends, so rows already
+            // shown keep their keys and aren't remounted
             for (index , candidate) in candidates.iter().enumerate() {
-                MatchItemView {
-                    key: "{index}",
-                    candidate: candidate.clone(),
-                    is_selected: selected_index == Some(index),
-                    on_select: move |_| on_select.call(index),
+                div { key: "{index}", id: list_item_id(&list_id, index),
+                    MatchItemView {
+                        candidate: candidate.clone(),
+                        is_selected: selected_index == Some(index),
+                        on_select: move |_| on_select.call(index),
+                        on_cover_visible,
+                    }
                 }
             }
         }
@@ -13,6 +13,10 @@ pub fn MatchResultsPanel(
     on_select: EventHandler<usize>,
     on_confirm: EventHandler<MatchCandidate>,
     confirm_button_text: &'static str,
+    /// Called with a candidate's MusicBrainz release when it scrolls into or
+    /// out of view
+    #[props(default)]
+    on_cover_visible: Option<EventHandler<(String, bool)>>,
 ) -> Element {
     if candidates.is_empty() {
         return rsx! {};
@@ -24,6 +28,15 @@ pub fn MatchResultsPanel(
                 candidates: candidates.clone(),
                 selected_index,
                 on_select: move |index| on_select.call(index),
+                on_confirm: {
+                    let candidates = candidates.clone();
+                    move |index: usize| {
+                        if let Some(candidate) = candidates.get(index) {
+                            on_confirm.call(candidate.clone());
+                        }
+                    }
+                },
+                on_cover_visible,
             }
 
             if let Some(index) = selected_index {
@@ -2,19 +2,23 @@
 //!
 //! Pure, props-based components for the import workflow UI.
 
+mod audio_quality;
 mod cd_import;
 mod cd_ripper;
 mod cd_toc_display;
 mod confirmation;
+mod duplicate_audio;
 mod file_list;
 mod folder_import;
 mod image_lightbox;
+mod manual_entry;
 mod manual_search_panel;
 mod match_item;
 mod match_list;
 mod match_results_panel;
 mod metadata_display;
 mod multiple_exact_matches;
+mod needle_drop;
 mod release_selector;
 mod release_sidebar;
 mod search_source_selector;
@@ -24,19 +28,23 @@ mod text_file_modal;
 mod torrent_display;
 mod torrent_import;
 
+pub use audio_quality::AudioQualityReportView;
 pub use cd_import::{CdImportView, CdImportViewProps};
 pub use cd_ripper::CdRipperView;
 pub use cd_toc_display::{CdTocDisplayView, CdTocInfo};
 pub use confirmation::ConfirmationView;
+pub use duplicate_audio::DuplicateAudioView;
 pub use file_list::FileListView;
 pub use folder_import::{FolderImportView, FolderImportViewProps};
 pub use image_lightbox::ImageLightboxView;
+pub use manual_entry::ManualEntryView;
 pub use manual_search_panel::ManualSearchPanelView;
 pub use match_item::MatchItemView;
 pub use match_list::MatchListView;
 pub use match_results_panel::MatchResultsPanel;
 pub use metadata_display::MetadataDisplayView;
 pub use multiple_exact_matches::MultipleExactMatchesView;
+pub use needle_drop::NeedleDropView;
 pub use release_selector::ReleaseSelectorView;
 pub use release_sidebar::{
     ReleaseSidebarView, DEFAULT_SIDEBAR_WIDTH, MAX_SIDEBAR_WIDTH, MIN_SIDEBAR_WIDTH,
@@ -49,7 +57,8 @@ pub use shared::{
 pub use smart_file_display::SmartFileDisplayView;
 pub use text_file_modal::TextFileModalView;
 pub use torrent_display::{
-    MetadataDetectionPromptView, TorrentFilesDisplayView, TorrentInfoDisplayView,
-    TorrentTrackerDisplayView, TrackerConnectionStatus, TrackerStatus,
+    MetadataDetectionPromptView, TorrentAlbumFoldersView, TorrentDownloadView,
+    TorrentFilesDisplayView, TorrentInfoDisplayView, TorrentTrackerDisplayView,
+    TrackerConnectionStatus, TrackerStatus,
 };
 pub use torrent_import::{TorrentImportView, TorrentImportViewProps};
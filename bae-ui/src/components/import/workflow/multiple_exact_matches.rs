@@ -17,6 +17,10 @@ pub fn MultipleExactMatchesView(
     on_select: EventHandler<usize>,
     on_confirm: EventHandler<MatchCandidate>,
     on_switch_to_manual_search: EventHandler<()>,
+    /// Called with a candidate's MusicBrainz release when it scrolls into or
+    /// out of view
+    #[props(default)]
+    on_cover_visible: Option<EventHandler<(String, bool)>>,
 ) -> Element {
     // Read state at leaf - these are computed values
     let st = state.read();
@@ -62,6 +66,7 @@ pub fn MultipleExactMatchesView(
                 on_select: move |index| on_select.call(index),
                 on_confirm: move |candidate| on_confirm.call(candidate),
                 confirm_button_text: "Continue",
+                on_cover_visible,
             }
         }
     }
@@ -0,0 +1,228 @@
+//! Vinyl side splitting for the confirm step
+
+use crate::components::icons::{AlertTriangleIcon, LoaderIcon};
+use crate::components::utils::format_duration;
+use crate::components::{Button, ButtonSize, ButtonVariant};
+use crate::display_types::{NeedleDropCheck, NeedleDropSide};
+use dioxus::prelude::*;
+
+/// A track this far off the release's listed length is flagged
+const LENGTH_WARNING_MS: u64 = 5_000;
+
+/// Offers to split recordings of whole vinyl sides into the release's tracks,
+/// then lets the user adjust where each track starts
+#[component]
+pub fn NeedleDropView(
+    check: Option<NeedleDropCheck>,
+    on_split: EventHandler<()>,
+    /// (side index, track index, start in ms)
+    on_start_change: EventHandler<(usize, usize, u64)>,
+    /// Import the files as they are
+    on_cancel: EventHandler<()>,
+) -> Element {
+    rsx! {
+        div { class: "rounded-lg bg-gray-800/50 p-4 text-sm",
+            match check {
+                None => rsx! {
+                    div { class: "flex items-center gap-3",
+                        p { class: "flex-1 text-gray-400",
+                            "Recorded one file per side? Split each side into the release's tracks."
+                        }
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            size: ButtonSize::Small,
+                            onclick: move |_| on_split.call(()),
+                            "Split sides"
+                        }
+                    }
+                },
+                Some(NeedleDropCheck::Analyzing) => rsx! {
+                    div { class: "flex items-center gap-2 text-gray-400",
+                        LoaderIcon { class: "w-4 h-4 animate-spin" }
+                        "Finding the gaps between tracks..."
+                    }
+                },
+                Some(NeedleDropCheck::Failed(error)) => rsx! {
+                    div { class: "flex items-center gap-3",
+                        AlertTriangleIcon { class: "w-4 h-4 text-amber-500 flex-shrink-0" }
+                        p { class: "flex-1 text-gray-300", "{error}" }
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            size: ButtonSize::Small,
+                            onclick: move |_| on_cancel.call(()),
+                            "Dismiss"
+                        }
+                    }
+                },
+                Some(NeedleDropCheck::Ready(sides)) => rsx! {
+                    div { class: "space-y-4",
+                        div { class: "flex items-center gap-3",
+                            p { class: "flex-1 text-gray-300",
+                                "Each side is split at the track starts below. Audio before a side's first track is left out."
+                            }
+                            Button {
+                                variant: ButtonVariant::Outline,
+                                size: ButtonSize::Small,
+                                onclick: move |_| on_cancel.call(()),
+                                "Don't split"
+                            }
+                        }
+                        for (side_index , side) in sides.into_iter().enumerate() {
+                            SideSplitView {
+                                key: "{side.path}",
+                                side_index,
+                                side,
+                                on_start_change,
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn SideSplitView(
+    side_index: usize,
+    side: NeedleDropSide,
+    on_start_change: EventHandler<(usize, usize, u64)>,
+) -> Element {
+    let duration = side.duration_ms.max(1) as f64;
+    let bar_count = side.waveform.len().max(1) as f64;
+    // Scaled to the side's loudest point, so quiet recordings still show their gaps
+    let peak = side
+        .waveform
+        .iter()
+        .fold(0.0f32, |peak, &l| peak.max(l))
+        .max(0.001);
+
+    rsx! {
+        div { class: "space-y-2",
+            h4 { class: "text-gray-300 font-medium truncate", title: "{side.path}",
+                "{side.name}"
+            }
+
+            // Waveform with a marker at each track start
+            div { class: "relative h-12 bg-gray-900/60 rounded flex items-center overflow-hidden",
+                for (i , level) in side.waveform.iter().enumerate() {
+                    div {
+                        key: "{i}",
+                        class: "absolute bottom-1/2 translate-y-1/2 bg-gray-500",
+                        style: "left: {i as f64 / bar_count * 100.0}%; width: {100.0 / bar_count}%; height: {(level / peak * 100.0).max(2.0)}%",
+                    }
+                }
+                for track in side.tracks.iter() {
+                    div {
+                        key: "{track.position}",
+                        class: "absolute top-0 bottom-0 w-px bg-accent",
+                        style: "left: {track.start_ms as f64 / duration * 100.0}%",
+                        title: "{track.position}",
+                    }
+                }
+            }
+
+            table { class: "w-full text-left",
+                thead {
+                    tr { class: "text-xs text-gray-500",
+                        th { class: "font-normal py-1 w-12", "" }
+                        th { class: "font-normal py-1", "Title" }
+                        th { class: "font-normal py-1 w-28", "Starts at" }
+                        th { class: "font-normal py-1 w-20 text-right", "Length" }
+                        th { class: "font-normal py-1 w-20 text-right", "Listed" }
+                    }
+                }
+                tbody {
+                    for (track_index , track) in side.tracks.iter().enumerate() {
+                        {
+                            let length = side.track_duration_ms(track_index);
+                            let length_off = track
+                                .expected_duration_ms
+                                .is_some_and(|expected| expected.abs_diff(length) > LENGTH_WARNING_MS);
+                            let length_class = if length_off { "text-amber-400" } else { "text-gray-400" };
+                            // A start has to stay between its neighbours
+                            let earliest = track_index
+                                .checked_sub(1)
+                                .map_or(0, |i| side.tracks[i].start_ms + 1);
+                            let latest = side
+                                .tracks
+                                .get(track_index + 1)
+                                .map_or(side.duration_ms, |next| next.start_ms)
+                                .saturating_sub(1);
+                            rsx! {
+                                tr { key: "{track.position}", class: "text-gray-300",
+                                    td { class: "py-1 text-gray-500", "{track.position}" }
+                                    td { class: "py-1 truncate", "{track.title}" }
+                                    td { class: "py-1",
+                                        StartInput {
+                                            start_ms: track.start_ms,
+                                            earliest,
+                                            latest,
+                                            on_change: move |start_ms| on_start_change.call((side_index, track_index, start_ms)),
+                                        }
+                                    }
+                                    td { class: "py-1 text-right tabular-nums {length_class}",
+                                        {format_duration(length as i64)}
+                                    }
+                                    td { class: "py-1 text-right tabular-nums text-gray-500",
+                                        if let Some(expected) = track.expected_duration_ms {
+                                            {format_duration(expected as i64)}
+                                        } else {
+                                            "—"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Track start as "m:ss.s", applied when the field loses focus or on enter
+#[component]
+fn StartInput(start_ms: u64, earliest: u64, latest: u64, on_change: EventHandler<u64>) -> Element {
+    let mut invalid = use_signal(|| false);
+    let border = if invalid() { "ring-1 ring-red-500" } else { "" };
+
+    rsx! {
+        input {
+            r#type: "text",
+            class: "w-24 px-2 py-1 bg-gray-800/50 rounded font-mono text-sm text-gray-300 focus:outline-none focus:ring-1 focus:ring-accent/50 {border}",
+            value: "{format_start(start_ms)}",
+            onchange: move |e| match parse_start(&e.value()).filter(|ms| (earliest..=latest).contains(ms)) {
+                Some(ms) => {
+                    invalid.set(false);
+                    on_change.call(ms);
+                }
+                None => invalid.set(true),
+            },
+        }
+    }
+}
+
+fn format_start(ms: u64) -> String {
+    format!(
+        "{}:{:02}.{}",
+        ms / 60_000,
+        (ms / 1000) % 60,
+        (ms % 1000) / 100
+    )
+}
+
+/// Parse "m:ss", "m:ss.s" or plain seconds
+fn parse_start(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let (minutes, seconds) = match text.split_once(':') {
+        Some((m, s)) => (m.trim().parse::<u64>().ok()?, s.trim()),
+        None => (0, text),
+    };
+    let seconds: f64 = seconds.parse().ok()?;
+    let seconds_limit = if text.contains(':') { 60.0 } else { f64::MAX };
+    if !(0.0..seconds_limit).contains(&seconds) {
+        return None;
+    }
+    Some(minutes * 60_000 + (seconds * 1000.0).round() as u64)
+}
@@ -10,6 +10,10 @@ use crate::floating_ui::Placement;
 use crate::platform;
 use crate::stores::import::{ImportState, ImportStateStoreExt};
 use dioxus::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counter for generating unique menu anchor IDs
+static SIDEBAR_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 pub const MIN_SIDEBAR_WIDTH: f64 = 350.0;
 pub const MAX_SIDEBAR_WIDTH: f64 = 500.0;
@@ -44,8 +48,11 @@ pub fn ReleaseSidebarView(
     let is_open: ReadSignal<bool> = show_menu.into();
     let mut show_clear_confirm = use_signal(|| false);
     let is_clear_confirm_open: ReadSignal<bool> = show_clear_confirm.into();
-    // Static ID - only one sidebar menu per page
-    let anchor_id = "release-sidebar-menu";
+    // Unique per instance - narrow layouts render a second, collapsible sidebar
+    let anchor_id = use_hook(|| {
+        let id = SIDEBAR_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+        format!("release-sidebar-menu-{}", id)
+    });
 
     rsx! {
         // Panel container - padding comes from parent ImportView
@@ -94,7 +101,7 @@ pub fn ReleaseSidebarView(
                 // Dropdown menu for candidates list
                 if !candidates.is_empty() {
                     MenuDropdown {
-                        anchor_id: anchor_id.to_string(),
+                        anchor_id: anchor_id.clone(),
                         is_open,
                         on_close: move |_| show_menu.set(false),
                         MenuItem {
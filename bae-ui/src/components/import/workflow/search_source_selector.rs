@@ -3,7 +3,7 @@
 use crate::display_types::SearchSource;
 use dioxus::prelude::*;
 
-/// Radio buttons to select between MusicBrainz and Discogs
+/// Radio buttons to select MusicBrainz, Discogs, or both at once
 #[component]
 pub fn SearchSourceSelectorView(
     selected_source: SearchSource,
@@ -35,6 +35,18 @@ pub fn SearchSourceSelectorView(
                     "Discogs"
                 }
             }
+            label { class: "flex items-center gap-2 cursor-pointer group",
+                input {
+                    r#type: "radio",
+                    name: "search_source",
+                    class: "accent-gray-200",
+                    checked: selected_source == SearchSource::Both,
+                    onchange: move |_| on_select.call(SearchSource::Both),
+                }
+                span { class: "text-xs text-gray-300 group-hover:text-white transition-colors",
+                    "Both"
+                }
+            }
         }
     }
 }
@@ -8,7 +8,8 @@ use dioxus::prelude::*;
 
 /// Display DiscID lookup error with retry and optional skip buttons.
 /// When `disc_id` and `on_skip` are provided, shows a centered view with disc ID pill.
-/// Otherwise, shows a simple banner suitable for inline display.
+/// Otherwise, shows a simple banner suitable for inline display, which offers
+/// to go ahead without a match when `on_identify_later` is provided.
 #[component]
 pub fn DiscIdLookupErrorView(
     error_message: Option<String>,
@@ -16,6 +17,7 @@ pub fn DiscIdLookupErrorView(
     on_retry: EventHandler<()>,
     #[props(default)] disc_id: Option<String>,
     #[props(default)] on_skip: Option<EventHandler<()>>,
+    #[props(default)] on_identify_later: Option<EventHandler<()>>,
 ) -> Element {
     let Some(ref error) = error_message else {
         return rsx! {};
@@ -71,6 +73,11 @@ pub fn DiscIdLookupErrorView(
                     AlertTriangleIcon { class: "w-5 h-5 text-amber-500 flex-shrink-0 mt-0.5" }
                     div { class: "flex-1",
                         p { class: "text-sm text-amber-200", "{error}" }
+                        if on_identify_later.is_some() {
+                            p { class: "text-sm text-gray-400 mt-1",
+                                "You can rip the disc now. bae keeps trying the lookup and fills in the details once it succeeds."
+                            }
+                        }
                         div { class: "mt-3 flex gap-2",
                             Button {
                                 variant: ButtonVariant::Primary,
@@ -84,6 +91,15 @@ pub fn DiscIdLookupErrorView(
                                     "Retry Lookup"
                                 }
                             }
+                            if let Some(on_identify_later) = on_identify_later {
+                                Button {
+                                    variant: ButtonVariant::Outline,
+                                    size: ButtonSize::Small,
+                                    disabled: is_retrying,
+                                    onclick: move |_| on_identify_later.call(()),
+                                    "Rip now, identify later"
+                                }
+                            }
                         }
                     }
                 }
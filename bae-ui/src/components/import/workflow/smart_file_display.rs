@@ -1,7 +1,7 @@
 //! Smart file display view component
 
 use super::{ImageLightboxView, TextFileModalView};
-use crate::components::icons::{DiscIcon, FileTextIcon, RowsIcon};
+use crate::components::icons::{DiscIcon, FileTextIcon, RowsIcon, VideoIcon};
 use crate::display_types::{AudioContentInfo, CategorizedFileInfo, CueFlacPairInfo, FileInfo};
 use dioxus::prelude::*;
 
@@ -74,6 +74,7 @@ pub fn SmartFileDisplayView(
     let has_audio = !matches!(&files.audio, AudioContentInfo::TrackFiles(t) if t.is_empty());
     let has_artwork = !files.artwork.is_empty();
     let has_documents = !files.documents.is_empty();
+    let has_videos = !files.videos.is_empty();
 
     rsx! {
         div { class: "space-y-5",
@@ -121,6 +122,21 @@ pub fn SmartFileDisplayView(
                     }
                 }
             }
+
+            // Videos section - stored with the release, not played here
+            if has_videos {
+                FileSection { label: "Videos",
+                    div { class: "flex flex-col gap-1",
+                        for video in files.videos.iter() {
+                            FileRow { key: "{video.path}", bg: "bg-white/5",
+                                VideoIcon { class: "w-4 h-4 text-gray-400 flex-shrink-0" }
+                                span { class: "text-xs text-gray-200 truncate", {video.name.clone()} }
+                                span { class: "text-xs text-gray-500 flex-shrink-0", {video.format.clone()} }
+                            }
+                        }
+                    }
+                }
+            }
         }
 
         // Text file modal
@@ -2,8 +2,11 @@
 
 use super::file_list::FileListView;
 use crate::components::icons::{ChevronDownIcon, ChevronRightIcon};
+use crate::components::utils::format_file_size;
 use crate::components::{Button, ButtonSize, ButtonVariant};
-use crate::display_types::{FileInfo, TorrentFileInfo, TorrentInfo};
+use crate::display_types::{
+    FileInfo, TorrentAlbumFolderInfo, TorrentDownloadHealth, TorrentFileInfo, TorrentInfo,
+};
 use dioxus::prelude::*;
 
 /// Tracker status for display
@@ -289,6 +292,91 @@ pub fn TorrentInfoDisplayView(info: TorrentInfo) -> Element {
     }
 }
 
+/// Live download state: rate, time left, who it's coming from, and which
+/// parts of the torrent have arrived
+#[component]
+pub fn TorrentDownloadView(health: TorrentDownloadHealth) -> Element {
+    let percent = if health.total_bytes > 0 {
+        health.downloaded_bytes as f64 / health.total_bytes as f64 * 100.0
+    } else {
+        0.0
+    };
+    let rate = format!("{}/s", format_file_size(health.download_rate as i64));
+    let eta = match health.eta_secs {
+        Some(secs) => format_eta(secs),
+        None => "Stalled".to_string(),
+    };
+    let stalled = health.eta_secs.is_none();
+    let no_peers = health.peers == 0;
+
+    rsx! {
+        div { class: "mt-4 p-3 bg-gray-800 rounded border border-gray-700 space-y-3",
+            div { class: "flex items-center justify-between",
+                h3 { class: "text-sm font-semibold text-gray-300 uppercase tracking-wide",
+                    "Downloading"
+                }
+                span { class: "text-sm text-gray-400 tabular-nums",
+                    "{format_file_size(health.downloaded_bytes as i64)} of {format_file_size(health.total_bytes as i64)} ({percent:.0}%)"
+                }
+            }
+            div { class: "flex h-3 rounded overflow-hidden bg-gray-900 gap-px",
+                for (index , have) in health.piece_bar.iter().copied().enumerate() {
+                    div {
+                        key: "{index}",
+                        class: "flex-1 bg-blue-500",
+                        style: "opacity: {0.15 + have * 0.85}",
+                    }
+                }
+            }
+            div { class: "grid grid-cols-4 gap-4 text-xs",
+                div {
+                    div { class: "text-gray-400", "Speed" }
+                    div { class: "text-gray-200 font-medium tabular-nums", {rate} }
+                }
+                div {
+                    div { class: "text-gray-400", "Time left" }
+                    div {
+                        class: "font-medium tabular-nums",
+                        class: if stalled { "text-yellow-400" } else { "text-gray-200" },
+                        {eta}
+                    }
+                }
+                div {
+                    div { class: "text-gray-400", "Peers" }
+                    div {
+                        class: "font-medium tabular-nums",
+                        class: if no_peers { "text-yellow-400" } else { "text-blue-400" },
+                        {health.peers.to_string()}
+                    }
+                }
+                div {
+                    div { class: "text-gray-400", "Seeders" }
+                    div { class: "text-green-400 font-medium tabular-nums",
+                        {health.seeds.to_string()}
+                    }
+                }
+            }
+            if no_peers {
+                p { class: "text-xs text-yellow-400",
+                    "No peers connected. This torrent may be dead; another source could be faster."
+                }
+            }
+        }
+    }
+}
+
+/// Time left as e.g. "1h 05m" or "42s"
+fn format_eta(secs: u64) -> String {
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
 /// Torrent files display (expandable list)
 #[component]
 pub fn TorrentFilesDisplayView(files: Vec<TorrentFileInfo>) -> Element {
@@ -346,6 +434,61 @@ pub fn TorrentFilesDisplayView(files: Vec<TorrentFileInfo>) -> Element {
     }
 }
 
+/// Album folder picker for torrents that hold more than one release.
+/// Only the checked folders are downloaded.
+#[component]
+pub fn TorrentAlbumFoldersView(
+    folders: Vec<TorrentAlbumFolderInfo>,
+    selected: Vec<String>,
+    on_toggle: EventHandler<String>,
+) -> Element {
+    if folders.len() < 2 {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "mt-4 space-y-2",
+            h3 { class: "text-sm font-semibold text-gray-300 uppercase tracking-wide",
+                "Albums"
+            }
+            p { class: "text-xs text-gray-400",
+                "This torrent holds {folders.len()} albums. Pick the ones to download and import."
+            }
+            div { class: "space-y-1",
+                for folder in folders {
+                    label {
+                        key: "{folder.path}",
+                        class: "flex items-center gap-2 text-sm cursor-pointer group",
+                        title: "{folder.path}",
+                        input {
+                            r#type: "checkbox",
+                            class: "accent-gray-200",
+                            checked: selected.contains(&folder.path),
+                            onchange: {
+                                let path = folder.path.clone();
+                                move |_| on_toggle.call(path.clone())
+                            },
+                        }
+                        span { class: "flex-1 text-gray-300 group-hover:text-white transition-colors truncate",
+                            {folder_name(&folder.path)}
+                        }
+                        span { class: "text-xs text-gray-500",
+                            "{folder.file_count} files, {format_file_size(folder.total_size)}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn folder_name(path: &str) -> String {
+    match path.rsplit('/').next() {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => path.to_string(),
+    }
+}
+
 /// Prompt to detect metadata from CUE/log files
 #[component]
 pub fn MetadataDetectionPromptView(on_detect: EventHandler<()>) -> Element {
@@ -15,11 +15,13 @@
 use super::{
     ConfirmationView, DiscIdLookupErrorView, ImportErrorDisplayView, ManualSearchPanelView,
     MetadataDetectionPromptView, MultipleExactMatchesView, SelectedSourceView,
-    TorrentFilesDisplayView, TorrentInfoDisplayView, TorrentTrackerDisplayView, TrackerStatus,
+    TorrentAlbumFoldersView, TorrentDownloadView, TorrentFilesDisplayView, TorrentInfoDisplayView,
+    TorrentTrackerDisplayView, TrackerStatus,
 };
 use crate::components::StorageProfile;
 use crate::display_types::{
-    IdentifyMode, ImportStep, MatchCandidate, SearchSource, SearchTab, TorrentFileInfo, TorrentInfo,
+    IdentifyMode, ImportStep, MatchCandidate, SearchSource, SearchTab, TorrentAlbumFolderInfo,
+    TorrentFileInfo, TorrentInfo,
 };
 use crate::stores::import::{CandidateState, ConfirmPhase, ImportState};
 use crate::{TorrentInputMode, TorrentInputView};
@@ -38,6 +40,12 @@ pub struct TorrentImportViewProps {
     pub tracker_statuses: Vec<TrackerStatus>,
     /// Files in the torrent
     pub torrent_files: Vec<TorrentFileInfo>,
+    /// Release folders found in the torrent
+    pub album_folders: Vec<TorrentAlbumFolderInfo>,
+    /// Paths of the album folders picked for download
+    pub selected_album_folders: Vec<String>,
+    /// Callback when an album folder is checked or unchecked
+    pub on_toggle_album_folder: EventHandler<String>,
     /// Current input mode (file or magnet)
     pub input_mode: TorrentInputMode,
     /// True if dragging over drop zone
@@ -67,8 +75,13 @@ pub struct TorrentImportViewProps {
     pub on_manual_match_select: EventHandler<usize>,
     pub on_search: EventHandler<()>,
     pub on_cancel_search: EventHandler<()>,
+    pub on_load_more: EventHandler<()>,
     pub on_manual_confirm: EventHandler<MatchCandidate>,
     pub on_retry_discid_lookup: EventHandler<()>,
+    /// (MusicBrainz release, visible) as match candidates scroll in and out
+    /// of view
+    #[props(default)]
+    pub on_cover_visible: Option<EventHandler<(String, bool)>>,
     pub on_detect_metadata: EventHandler<()>,
     pub on_select_remote_cover: EventHandler<String>,
     pub on_select_local_cover: EventHandler<String>,
@@ -112,6 +125,9 @@ pub fn TorrentImportView(props: TorrentImportViewProps) -> Element {
                             torrent_info: props.torrent_info.clone(),
                             tracker_statuses: props.tracker_statuses.clone(),
                             torrent_files: props.torrent_files.clone(),
+                            album_folders: props.album_folders.clone(),
+                            selected_album_folders: props.selected_album_folders.clone(),
+                            on_toggle_album_folder: props.on_toggle_album_folder,
                             on_clear: props.on_clear,
                             on_exact_match_select: props.on_exact_match_select,
                             on_confirm_exact_match: props.on_confirm_exact_match,
@@ -126,8 +142,10 @@ pub fn TorrentImportView(props: TorrentImportViewProps) -> Element {
                             on_manual_match_select: props.on_manual_match_select,
                             on_search: props.on_search,
                             on_cancel_search: props.on_cancel_search,
+                            on_load_more: props.on_load_more,
                             on_manual_confirm: props.on_manual_confirm,
                             on_retry_discid_lookup: props.on_retry_discid_lookup,
+                            on_cover_visible: props.on_cover_visible,
                             on_detect_metadata: props.on_detect_metadata,
                         }
                     }
@@ -163,6 +181,9 @@ fn TorrentIdentifyContent(
     torrent_info: Option<TorrentInfo>,
     tracker_statuses: Vec<TrackerStatus>,
     torrent_files: Vec<TorrentFileInfo>,
+    album_folders: Vec<TorrentAlbumFolderInfo>,
+    selected_album_folders: Vec<String>,
+    on_toggle_album_folder: EventHandler<String>,
     on_clear: EventHandler<()>,
     on_exact_match_select: EventHandler<usize>,
     on_confirm_exact_match: EventHandler<MatchCandidate>,
@@ -177,8 +198,10 @@ fn TorrentIdentifyContent(
     on_manual_match_select: EventHandler<usize>,
     on_search: EventHandler<()>,
     on_cancel_search: EventHandler<()>,
+    on_load_more: EventHandler<()>,
     on_manual_confirm: EventHandler<MatchCandidate>,
     on_retry_discid_lookup: EventHandler<()>,
+    on_cover_visible: Option<EventHandler<(String, bool)>>,
     on_detect_metadata: EventHandler<()>,
 ) -> Element {
     // Read state at leaf level
@@ -207,16 +230,24 @@ fn TorrentIdentifyContent(
                 if let Some(ref info) = torrent_info {
                     TorrentInfoDisplayView { info: info.clone() }
                 }
+                TorrentAlbumFoldersView {
+                    folders: album_folders,
+                    selected: selected_album_folders,
+                    on_toggle: on_toggle_album_folder,
+                }
                 TorrentFilesDisplayView { files: torrent_files }
             }
             match identify_mode {
-                IdentifyMode::Created | IdentifyMode::DiscIdLookup(_) => rsx! {},
+                IdentifyMode::Created
+                | IdentifyMode::DiscIdLookup(_)
+                | IdentifyMode::ManualEntry(_) => rsx! {},
                 IdentifyMode::MultipleExactMatches(_) => rsx! {
                     MultipleExactMatchesView {
                         state,
                         on_select: on_exact_match_select,
                         on_confirm: on_confirm_exact_match,
                         on_switch_to_manual_search,
+                        on_cover_visible,
                     }
                 },
                 IdentifyMode::ManualSearch => rsx! {
@@ -241,8 +272,10 @@ fn TorrentIdentifyContent(
                         on_match_select: on_manual_match_select,
                         on_search,
                         on_cancel_search,
+                        on_load_more,
                         on_confirm: on_manual_confirm,
                         on_switch_to_exact_matches,
+                        on_cover_visible,
                     }
                 },
             }
@@ -279,23 +312,24 @@ fn TorrentConfirmContent(
         .unwrap_or_default();
     let selected_profile_id = st.get_storage_profile_id();
 
-    let (is_importing, preparing_step_text, import_error) = st
+    let (is_importing, prepare_stages, import_error) = st
         .current_candidate_state()
         .and_then(|s| match s {
-            CandidateState::Confirming(cs) => Some(&cs.phase),
+            CandidateState::Confirming(cs) => Some(cs),
             _ => None,
         })
-        .map(|phase| match phase {
-            ConfirmPhase::Ready => (false, None, None),
-            ConfirmPhase::Preparing(msg) => (false, Some(msg.clone()), None),
-            ConfirmPhase::Importing => (true, None, None),
-            ConfirmPhase::Failed(err) => (false, None, Some(err.clone())),
-            ConfirmPhase::Completed => (false, None, None),
+        .map(|cs| match &cs.phase {
+            ConfirmPhase::Ready => (false, Vec::new(), None),
+            ConfirmPhase::Preparing => (true, cs.prepare_checklist(), None),
+            ConfirmPhase::Importing => (true, Vec::new(), None),
+            ConfirmPhase::Failed(err) => (false, cs.prepare_checklist(), Some(err.clone())),
+            ConfirmPhase::Completed => (false, Vec::new(), None),
         })
-        .unwrap_or((false, None, None));
+        .unwrap_or((false, Vec::new(), None));
 
     let import_error = import_error.or_else(|| st.import_error_message.clone());
     let duplicate_album_id = st.duplicate_album_id.clone();
+    let torrent_download = st.torrent_download.clone();
     drop(st);
 
     let Some(candidate) = confirmed_candidate else {
@@ -313,7 +347,11 @@ fn TorrentConfirmContent(
                 if let Some(ref info) = torrent_info {
                     TorrentInfoDisplayView { info: info.clone() }
                 }
-                TorrentFilesDisplayView { files: torrent_files }
+                if let Some(health) = torrent_download {
+                    TorrentDownloadView { health }
+                } else {
+                    TorrentFilesDisplayView { files: torrent_files }
+                }
             }
             ConfirmationView {
                 candidate: candidate.clone(),
@@ -323,8 +361,9 @@ fn TorrentConfirmContent(
                 remote_cover_url: candidate.cover_url.clone(),
                 storage_profiles,
                 selected_profile_id,
+                storage_estimate: None,
                 is_importing,
-                preparing_step_text,
+                prepare_stages,
                 on_select_remote_cover,
                 on_select_local_cover,
                 on_storage_profile_change,
@@ -18,9 +18,12 @@ pub fn ImportsButtonView(
         return rsx! {};
     }
 
-    let has_in_progress = imports
-        .iter()
-        .any(|i| i.status == ImportStatus::Preparing || i.status == ImportStatus::Importing);
+    let has_in_progress = imports.iter().any(|i| {
+        matches!(
+            i.status,
+            ImportStatus::Preparing | ImportStatus::Queued | ImportStatus::Importing
+        )
+    });
     let has_failed = imports.iter().any(|i| i.status == ImportStatus::Failed);
 
     let badge_color = if has_failed {
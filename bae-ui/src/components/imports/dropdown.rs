@@ -3,6 +3,7 @@
 //! Pure, props-based dropdown showing list of active imports with progress.
 
 use crate::components::icons::{CheckIcon, DownloadIcon, FileTextIcon, ImageIcon, XIcon};
+use crate::components::utils::format_file_size;
 use crate::display_types::{ActiveImport, ImportStatus};
 use dioxus::prelude::*;
 
@@ -15,6 +16,8 @@ pub fn ImportsDropdownView(
     on_import_click: EventHandler<String>,
     on_import_dismiss: EventHandler<String>,
     on_clear_all: EventHandler<()>,
+    /// Open the import queue page
+    on_view_queue: EventHandler<()>,
 ) -> Element {
     if !is_open {
         return rsx! {};
@@ -40,14 +43,24 @@ pub fn ImportsDropdownView(
                     span { class: "text-xs text-gray-500", "({import_count})" }
                 }
 
-                if import_count > 0 {
+                div { class: "flex items-center gap-1",
                     button {
-                        class: "text-xs text-gray-400 hover:text-red-400 transition-colors px-2 py-1 rounded hover:bg-gray-700/50",
+                        class: "text-xs text-gray-400 hover:text-white transition-colors px-2 py-1 rounded hover:bg-gray-700/50",
                         onclick: move |e: Event<MouseData>| {
                             e.stop_propagation();
-                            on_clear_all.call(());
+                            on_view_queue.call(());
                         },
-                        "Clear all"
+                        "View queue"
+                    }
+                    if import_count > 0 {
+                        button {
+                            class: "text-xs text-gray-400 hover:text-red-400 transition-colors px-2 py-1 rounded hover:bg-gray-700/50",
+                            onclick: move |e: Event<MouseData>| {
+                                e.stop_propagation();
+                                on_clear_all.call(());
+                            },
+                            "Clear all"
+                        }
                     }
                 }
             }
@@ -84,30 +97,14 @@ fn ImportItemView(
     let is_complete = import.status == ImportStatus::Complete;
     let is_failed = import.status == ImportStatus::Failed;
     let is_importing = import.status == ImportStatus::Importing;
+    let is_in_progress = matches!(
+        import.status,
+        ImportStatus::Preparing | ImportStatus::Importing
+    );
     let progress_percent = import.progress_percent.unwrap_or(0);
 
-    let status_color = match import.status {
-        ImportStatus::Preparing => "text-yellow-500",
-        ImportStatus::Importing => "text-indigo-400",
-        ImportStatus::Complete => "text-green-500",
-        ImportStatus::Failed => "text-red-500",
-    };
-
-    let status_text = match import.status {
-        ImportStatus::Preparing => import
-            .current_step_text
-            .clone()
-            .unwrap_or_else(|| "Preparing...".to_string()),
-        ImportStatus::Importing => {
-            if progress_percent > 0 {
-                format!("{}% complete", progress_percent)
-            } else {
-                "Starting...".to_string()
-            }
-        }
-        ImportStatus::Complete => "Import complete".to_string(),
-        ImportStatus::Failed => "Import failed".to_string(),
-    };
+    let status_color = status_color(import.status);
+    let status_text = status_text(&import);
 
     let cursor_class = if is_complete {
         "cursor-pointer"
@@ -152,7 +149,7 @@ fn ImportItemView(
                         div { class: "absolute -bottom-0.5 -right-0.5 w-4 h-4 bg-red-500 rounded-full flex items-center justify-center",
                             XIcon { class: "h-2.5 w-2.5 text-white" }
                         }
-                    } else {
+                    } else if is_in_progress {
                         // Animated spinner for in-progress
                         div { class: "absolute -bottom-0.5 -right-0.5 w-4 h-4 bg-indigo-500 rounded-full flex items-center justify-center",
                             svg {
@@ -210,3 +207,37 @@ fn ImportItemView(
         }
     }
 }
+
+pub(super) fn status_color(status: ImportStatus) -> &'static str {
+    match status {
+        ImportStatus::Preparing => "text-yellow-500",
+        ImportStatus::Queued | ImportStatus::Paused | ImportStatus::Cancelled => "text-gray-400",
+        ImportStatus::Importing => "text-indigo-400",
+        ImportStatus::Complete => "text-green-500",
+        ImportStatus::Failed => "text-red-500",
+    }
+}
+
+pub(super) fn status_text(import: &ActiveImport) -> String {
+    match import.status {
+        ImportStatus::Preparing => import
+            .current_step_text
+            .clone()
+            .unwrap_or_else(|| "Preparing...".to_string()),
+        ImportStatus::Queued => "Waiting in queue".to_string(),
+        ImportStatus::Importing => match import.progress_percent.unwrap_or(0) {
+            0 => "Starting...".to_string(),
+            percent => format!("{}% complete", percent),
+        },
+        ImportStatus::Paused => "Paused".to_string(),
+        ImportStatus::Complete => match import.store_bytes_per_sec {
+            Some(rate) => format!(
+                "Import complete · stored at {}/s",
+                format_file_size(rate as i64)
+            ),
+            None => "Import complete".to_string(),
+        },
+        ImportStatus::Failed => "Import failed".to_string(),
+        ImportStatus::Cancelled => "Cancelled".to_string(),
+    }
+}
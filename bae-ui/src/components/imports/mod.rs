@@ -1,9 +1,12 @@
 //! Imports indicator components
 //!
-//! Pure, props-based components for the imports button and dropdown in the title bar.
+//! Pure, props-based components for the imports button and dropdown in the
+//! title bar, and the import queue page.
 
 mod button;
 mod dropdown;
+mod queue;
 
 pub use button::ImportsButtonView;
 pub use dropdown::ImportsDropdownView;
+pub use queue::ImportQueueView;
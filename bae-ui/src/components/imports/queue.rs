@@ -0,0 +1,227 @@
+//! Import queue page view
+//!
+//! Pure, props-based page listing every import in the queue, with pause,
+//! resume, cancel and retry for each.
+
+use super::dropdown::{status_color, status_text};
+use crate::components::icons::{FileTextIcon, ImageIcon};
+use crate::components::{Button, ButtonSize, ButtonVariant};
+use crate::display_types::{ActiveImport, ImportStatus};
+use dioxus::prelude::*;
+
+/// Imports grouped by where they stand in the queue
+#[component]
+pub fn ImportQueueView(
+    imports: Vec<ActiveImport>,
+    /// Queued imports that run at once
+    max_concurrent: u32,
+    on_import_click: EventHandler<String>,
+    on_pause: EventHandler<String>,
+    on_resume: EventHandler<String>,
+    on_cancel: EventHandler<String>,
+    on_retry: EventHandler<String>,
+    on_dismiss: EventHandler<String>,
+) -> Element {
+    let running: Vec<ActiveImport> = imports
+        .iter()
+        .filter(|i| matches!(i.status, ImportStatus::Preparing | ImportStatus::Importing))
+        .cloned()
+        .collect();
+    let waiting: Vec<ActiveImport> = imports
+        .iter()
+        .filter(|i| matches!(i.status, ImportStatus::Queued | ImportStatus::Paused))
+        .cloned()
+        .collect();
+    let finished: Vec<ActiveImport> = imports
+        .iter()
+        .filter(|i| {
+            matches!(
+                i.status,
+                ImportStatus::Complete | ImportStatus::Failed | ImportStatus::Cancelled
+            )
+        })
+        .cloned()
+        .collect();
+    let groups: Vec<(&str, Vec<ActiveImport>)> = [
+        ("Running", running),
+        ("Waiting", waiting),
+        ("Finished", finished),
+    ]
+    .into_iter()
+    .filter(|(_, group)| !group.is_empty())
+    .collect();
+    let slots = if max_concurrent == 1 {
+        "One import runs at a time".to_string()
+    } else {
+        format!("{} imports run at a time", max_concurrent)
+    };
+
+    rsx! {
+        div { class: "flex-grow overflow-y-auto flex flex-col py-6 @3xl:py-10",
+            div { class: "container mx-auto flex flex-col px-4 @3xl:px-0 max-w-3xl",
+                div { class: "flex items-baseline justify-between gap-3 mb-6",
+                    h1 { class: "text-2xl @3xl:text-3xl font-bold text-white", "Import Queue" }
+                    span { class: "text-sm text-gray-400", "{slots}" }
+                }
+                if imports.is_empty() {
+                    div { class: "py-12 text-center",
+                        FileTextIcon { class: "h-10 w-10 text-gray-600 mx-auto mb-3" }
+                        p { class: "text-gray-400", "Imports you start will show up here" }
+                    }
+                }
+                for (title , group) in groups {
+                    section { key: "{title}", class: "mb-8",
+                        h2 { class: "text-sm font-semibold text-gray-400 uppercase tracking-wide mb-2",
+                            "{title}"
+                        }
+                        div { class: "bg-gray-800/30 rounded-lg divide-y divide-gray-800",
+                            for import in group {
+                                QueueItemView {
+                                    key: "{import.import_id}",
+                                    import,
+                                    on_click: on_import_click,
+                                    on_pause,
+                                    on_resume,
+                                    on_cancel,
+                                    on_retry,
+                                    on_dismiss,
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// One import in the queue, with the actions it allows
+#[component]
+fn QueueItemView(
+    import: ActiveImport,
+    on_click: EventHandler<String>,
+    on_pause: EventHandler<String>,
+    on_resume: EventHandler<String>,
+    on_cancel: EventHandler<String>,
+    on_retry: EventHandler<String>,
+    on_dismiss: EventHandler<String>,
+) -> Element {
+    let is_complete = import.status == ImportStatus::Complete;
+    let is_finished = matches!(
+        import.status,
+        ImportStatus::Complete | ImportStatus::Failed | ImportStatus::Cancelled
+    );
+    let progress_percent = import.progress_percent.unwrap_or(0);
+    let show_progress = import.status == ImportStatus::Importing && progress_percent > 0;
+    let status_color = status_color(import.status);
+    let status_text = status_text(&import);
+    let id = import.import_id.clone();
+
+    rsx! {
+        div { class: "flex items-center gap-4 px-4 py-3",
+            div { class: "flex-shrink-0 w-12 h-12 bg-gray-700 rounded overflow-clip",
+                if let Some(ref url) = import.cover_url {
+                    img {
+                        src: "{url}",
+                        alt: "Album cover",
+                        class: "w-full h-full object-cover",
+                    }
+                } else {
+                    div { class: "w-full h-full flex items-center justify-center text-gray-500",
+                        ImageIcon { class: "w-5 h-5" }
+                    }
+                }
+            }
+
+            div { class: "flex-1 min-w-0",
+                if is_complete {
+                    button {
+                        class: "text-sm font-medium text-white truncate hover:underline",
+                        onclick: {
+                            let id = id.clone();
+                            move |_| on_click.call(id.clone())
+                        },
+                        "{import.album_title}"
+                    }
+                } else {
+                    p { class: "text-sm font-medium text-white truncate", "{import.album_title}" }
+                }
+                if !import.artist_name.is_empty() {
+                    p { class: "text-xs text-gray-400 truncate", "{import.artist_name}" }
+                }
+                p { class: "text-xs {status_color} mt-1", "{status_text}" }
+                if let Some(ref error) = import.error_message {
+                    p { class: "text-xs text-red-400/80 mt-0.5 truncate", title: "{error}",
+                        "{error}"
+                    }
+                }
+                if show_progress {
+                    div { class: "mt-2 h-1.5 bg-gray-700 rounded-full overflow-clip",
+                        div {
+                            class: "h-full bg-gradient-to-r from-indigo-500 to-indigo-400 transition-all duration-300 ease-out",
+                            style: "width: {progress_percent}%",
+                        }
+                    }
+                }
+            }
+
+            div { class: "flex-shrink-0 flex items-center gap-2",
+                if import.can_pause {
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        onclick: {
+                            let id = id.clone();
+                            move |_| on_pause.call(id.clone())
+                        },
+                        "Pause"
+                    }
+                }
+                if import.can_resume {
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        onclick: {
+                            let id = id.clone();
+                            move |_| on_resume.call(id.clone())
+                        },
+                        "Resume"
+                    }
+                }
+                if import.can_retry {
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        onclick: {
+                            let id = id.clone();
+                            move |_| on_retry.call(id.clone())
+                        },
+                        "Retry"
+                    }
+                }
+                if import.can_cancel {
+                    Button {
+                        variant: ButtonVariant::Ghost,
+                        size: ButtonSize::Small,
+                        onclick: {
+                            let id = id.clone();
+                            move |_| on_cancel.call(id.clone())
+                        },
+                        "Cancel"
+                    }
+                }
+                if is_finished {
+                    Button {
+                        variant: ButtonVariant::Ghost,
+                        size: ButtonSize::Small,
+                        onclick: {
+                            let id = id.clone();
+                            move |_| on_dismiss.call(id.clone())
+                        },
+                        "Dismiss"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Recovery from failed webview calls
+//!
+//! See [`crate::ipc`]. Guarded calls under an [`IpcBoundary`] report failures
+//! to it, and it rebuilds its children so nothing is left half updated.
+
+use crate::components::ErrorToast;
+use crate::ipc::{IpcBoundaryContext, IpcError};
+use dioxus::prelude::*;
+
+/// Remounts before failures only get the toast, so a call that fails every
+/// time can't keep the children rebuilding
+const MAX_REMOUNTS: u32 = 3;
+
+/// Remounts its children, with a toast, when a webview call under it fails
+#[component]
+pub fn IpcBoundary(children: Element) -> Element {
+    let mut failure = use_signal(|| None::<IpcError>);
+    use_context_provider(|| IpcBoundaryContext { failure });
+    let mut generation = use_signal(|| 0u32);
+    let mut show_toast = use_signal(|| false);
+
+    use_effect(move || {
+        if failure.read().is_none() {
+            return;
+        }
+        failure.set(None);
+        show_toast.set(true);
+        if *generation.peek() < MAX_REMOUNTS {
+            generation += 1;
+        }
+    });
+
+    rsx! {
+        // A new key remounts the children
+        for generation in [generation()] {
+            div { key: "{generation}", class: "contents", {children.clone()} }
+        }
+        if show_toast() {
+            ErrorToast {
+                title: None,
+                message: "Part of the window failed to update and was reloaded.",
+                on_dismiss: move |_| show_toast.set(false),
+            }
+        }
+    }
+}
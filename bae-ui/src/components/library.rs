@@ -7,8 +7,12 @@
 use crate::components::album_card::AlbumCard;
 use crate::components::helpers::{ErrorDisplay, LoadingSpinner};
 use crate::components::icons::ImageIcon;
-use crate::components::{Button, ButtonSize, ButtonVariant};
-use crate::display_types::{Album, Artist};
+use crate::components::{
+    Button, ButtonSize, ButtonVariant, FindReplaceModal, Select, SelectOption,
+};
+use crate::display_types::{
+    Album, Artist, AudioQuality, FindReplaceRequest, FindReplaceRow, LibrarySort,
+};
 use crate::stores::library::{LibraryState, LibraryStateStoreExt};
 use dioxus::prelude::*;
 use dioxus_virtual_scroll::{KeyFn, RenderFn, ScrollTarget, VirtualGrid, VirtualGridConfig};
@@ -35,21 +39,81 @@ pub fn LibraryView(
     on_add_album_to_queue: EventHandler<String>,
     // Empty state action (e.g., navigate to import)
     on_empty_action: EventHandler<()>,
+    /// Sort and quality filter the view opens with
+    default_sort: LibrarySort,
+    default_quality_filter: Option<AudioQuality>,
+    /// Find-and-replace: compute a preview, write a previewed batch, and
+    /// drop a preview that no longer matches the form
+    on_find_replace_preview: EventHandler<FindReplaceRequest>,
+    on_find_replace_apply: EventHandler<Vec<FindReplaceRow>>,
+    on_find_replace_reset: EventHandler<()>,
 ) -> Element {
     // Use lenses to subscribe only to specific fields for routing decisions
     let loading = *state.loading().read();
     let error = state.error().read().clone();
-    let albums = state.albums().read().clone();
+    let mut sort = use_signal(|| default_sort);
+    let mut quality_filter = use_signal(|| default_quality_filter);
+    let mut albums = state.albums().read().clone();
+    let has_albums = !albums.is_empty();
+    if let Some(quality) = quality_filter() {
+        albums.retain(|a| a.quality == Some(quality));
+    }
+    sort.read().apply(&mut albums);
     let artists_by_album = state.artists_by_album().read().clone();
+    let recently_played = recently_played_albums(&albums);
+    let most_played = most_played_albums(&albums);
 
     let mut scroll_target: Signal<Option<Rc<MountedData>>> = use_signal(|| None);
+    let mut show_find_replace = use_signal(|| false);
 
     rsx! {
         div {
-            class: "flex-grow overflow-y-auto flex flex-col py-10",
+            class: "flex-grow overflow-y-auto flex flex-col py-6 @3xl:py-10",
             onmounted: move |evt| scroll_target.set(Some(evt.data())),
-            div { class: "container mx-auto flex flex-col",
-                h1 { class: "text-3xl font-bold text-white mb-6", "Music Library" }
+            div { class: "container mx-auto flex flex-col px-4 @3xl:px-0",
+                div { class: "flex flex-wrap items-center justify-between gap-3 mb-6",
+                    h1 { class: "text-2xl @3xl:text-3xl font-bold text-white", "Music Library" }
+                    if has_albums {
+                        div { class: "flex items-center gap-2",
+                            Button {
+                                variant: ButtonVariant::Secondary,
+                                size: ButtonSize::Small,
+                                class: Some("mr-2".to_string()),
+                                onclick: move |_| show_find_replace.set(true),
+                                "Find & Replace"
+                            }
+                            label { class: "text-sm text-gray-400", "Quality" }
+                            Select {
+                                value: quality_filter().map_or("all", |q| q.key()),
+                                onchange: move |key: String| quality_filter.set(AudioQuality::from_key(&key)),
+                                SelectOption { value: "all", label: "All" }
+                                for quality in AudioQuality::ALL {
+                                    SelectOption {
+                                        key: "{quality.key()}",
+                                        value: quality.key(),
+                                        label: quality.label(),
+                                    }
+                                }
+                            }
+                            label { class: "text-sm text-gray-400 ml-2", "Sort by" }
+                            Select {
+                                value: sort.read().key(),
+                                onchange: move |key: String| {
+                                    if let Some(s) = LibrarySort::from_key(&key) {
+                                        sort.set(s);
+                                    }
+                                },
+                                for option in LibrarySort::ALL {
+                                    SelectOption {
+                                        key: "{option.key()}",
+                                        value: option.key(),
+                                        label: option.label(),
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
                 if loading {
                     LoadingSpinner { message: "Loading your music library...".to_string() }
                 } else if let Some(err) = error {
@@ -57,6 +121,8 @@ pub fn LibraryView(
                     p { class: "text-sm mt-2 text-gray-400",
                         "An error occurred while loading your music library."
                     }
+                } else if has_albums && albums.is_empty() {
+                    p { class: "text-center py-12 text-gray-400", "No albums at this quality" }
                 } else if albums.is_empty() {
                     div { class: "text-center py-12",
                         div { class: "text-gray-400 mb-4",
@@ -74,6 +140,24 @@ pub fn LibraryView(
                         }
                     }
                 } else {
+                    if quality_filter().is_none() {
+                        AlbumShelf {
+                            title: "Recently Played",
+                            albums: recently_played,
+                            artists_by_album: artists_by_album.clone(),
+                            on_album_click,
+                            on_play_album,
+                            on_add_album_to_queue,
+                        }
+                        AlbumShelf {
+                            title: "Most Played",
+                            albums: most_played,
+                            artists_by_album: artists_by_album.clone(),
+                            on_album_click,
+                            on_play_album,
+                            on_add_album_to_queue,
+                        }
+                    }
                     AlbumGrid {
                         albums,
                         artists_by_album,
@@ -85,6 +169,90 @@ pub fn LibraryView(
                 }
             }
         }
+        if has_albums {
+            FindReplaceModal {
+                is_open: show_find_replace,
+                albums: state.albums().read().clone(),
+                artists_by_album: state.artists_by_album().read().clone(),
+                preview: state.find_replace_preview().read().clone(),
+                error: state.find_replace_error().read().clone(),
+                on_preview: on_find_replace_preview,
+                on_apply: move |rows| {
+                    show_find_replace.set(false);
+                    on_find_replace_apply.call(rows);
+                },
+                on_reset: on_find_replace_reset,
+                on_close: move |_| {
+                    show_find_replace.set(false);
+                    on_find_replace_reset.call(());
+                },
+            }
+        }
+    }
+}
+
+/// Albums shown on each shelf
+const SHELF_SIZE: usize = 12;
+
+/// Played albums, last played first
+fn recently_played_albums(albums: &[Album]) -> Vec<Album> {
+    let mut played: Vec<Album> = albums
+        .iter()
+        .filter(|a| a.last_played.is_some())
+        .cloned()
+        .collect();
+    played.sort_by(|a, b| b.last_played.cmp(&a.last_played));
+    played.truncate(SHELF_SIZE);
+    played
+}
+
+/// Played albums, most plays first
+fn most_played_albums(albums: &[Album]) -> Vec<Album> {
+    let mut played: Vec<Album> = albums
+        .iter()
+        .filter(|a| a.play_count > 0)
+        .cloned()
+        .collect();
+    played.sort_by(|a, b| {
+        b.play_count
+            .cmp(&a.play_count)
+            .then(b.last_played.cmp(&a.last_played))
+    });
+    played.truncate(SHELF_SIZE);
+    played
+}
+
+/// A titled row of albums that scrolls sideways, hidden when empty
+#[component]
+fn AlbumShelf(
+    title: &'static str,
+    albums: Vec<Album>,
+    artists_by_album: HashMap<String, Vec<Artist>>,
+    on_album_click: EventHandler<String>,
+    on_play_album: EventHandler<String>,
+    on_add_album_to_queue: EventHandler<String>,
+) -> Element {
+    if albums.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "mb-8",
+            h2 { class: "text-lg font-semibold text-white mb-3", "{title}" }
+            div { class: "flex gap-6 overflow-x-auto pb-2",
+                for album in albums {
+                    div { key: "{album.id}", class: "w-40 shrink-0",
+                        AlbumCard {
+                            artists: artists_by_album.get(&album.id).cloned().unwrap_or_default(),
+                            album,
+                            on_click: on_album_click,
+                            on_play: on_play_album,
+                            on_add_to_queue: on_add_album_to_queue,
+                        }
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -0,0 +1,93 @@
+//! Keyboard navigation for selectable lists
+//!
+//! Shared by the title bar search results and the import match lists so
+//! arrow keys, Enter and Escape behave the same everywhere.
+
+use dioxus::prelude::*;
+
+/// What a key press means for a list with an optional active item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListKeyAction {
+    /// Make the item at this index active
+    Move(usize),
+    /// Confirm (open/play) the item at this index
+    Confirm(usize),
+    /// Close the list or give up focus
+    Dismiss,
+}
+
+/// Map a key press to a list action. Arrow keys clamp at the ends; Enter
+/// only confirms when an item is active.
+pub fn list_key_action(key: &Key, active: Option<usize>, len: usize) -> Option<ListKeyAction> {
+    match key {
+        Key::Escape => Some(ListKeyAction::Dismiss),
+        _ if len == 0 => None,
+        Key::ArrowDown => Some(ListKeyAction::Move(
+            active.map_or(0, |i| (i + 1).min(len - 1)),
+        )),
+        Key::ArrowUp => Some(ListKeyAction::Move(
+            active.map_or(len - 1, |i| i.saturating_sub(1)),
+        )),
+        Key::Enter => active.filter(|i| *i < len).map(ListKeyAction::Confirm),
+        _ => None,
+    }
+}
+
+/// DOM id for a list item, so keyboard moves can scroll it into view
+pub fn list_item_id(list_id: &str, index: usize) -> String {
+    format!("{}-item-{}", list_id, index)
+}
+
+/// Scroll a list item into view without moving it if already visible
+pub fn scroll_list_item_into_view(list_id: &str, index: usize) {
+    let _ = document::eval(&format!(
+        "document.getElementById('{}')?.scrollIntoView({{ block: 'nearest' }})",
+        list_item_id(list_id, index)
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrows_start_at_the_ends_and_clamp() {
+        assert_eq!(
+            list_key_action(&Key::ArrowDown, None, 3),
+            Some(ListKeyAction::Move(0))
+        );
+        assert_eq!(
+            list_key_action(&Key::ArrowUp, None, 3),
+            Some(ListKeyAction::Move(2))
+        );
+        assert_eq!(
+            list_key_action(&Key::ArrowDown, Some(2), 3),
+            Some(ListKeyAction::Move(2))
+        );
+        assert_eq!(
+            list_key_action(&Key::ArrowUp, Some(0), 3),
+            Some(ListKeyAction::Move(0))
+        );
+    }
+
+    #[test]
+    fn test_enter_confirms_only_an_active_item_in_range() {
+        assert_eq!(
+            list_key_action(&Key::Enter, Some(1), 3),
+            Some(ListKeyAction::Confirm(1))
+        );
+        assert_eq!(list_key_action(&Key::Enter, None, 3), None);
+        // The list shrank under a stale active index
+        assert_eq!(list_key_action(&Key::Enter, Some(3), 3), None);
+    }
+
+    #[test]
+    fn test_escape_dismisses_even_an_empty_list() {
+        assert_eq!(
+            list_key_action(&Key::Escape, None, 0),
+            Some(ListKeyAction::Dismiss)
+        );
+        assert_eq!(list_key_action(&Key::ArrowDown, None, 0), None);
+        assert_eq!(list_key_action(&Key::Enter, Some(0), 0), None);
+    }
+}
@@ -6,28 +6,36 @@ pub mod app_layout;
 pub mod button;
 pub mod dropdown;
 pub mod error_toast;
+pub mod find_replace_modal;
 pub mod helpers;
+pub mod history;
 pub mod icons;
 pub mod import;
 pub mod imports;
+pub mod ipc_boundary;
 pub mod library;
+pub mod list_navigation;
 pub mod menu;
 pub mod modal;
 pub mod pill;
 pub mod playback;
+pub mod quality_badge;
 pub mod resizable_panel;
 pub mod select;
 pub mod settings;
+pub mod snapshot_restore;
 pub mod text_input;
 pub mod title_bar;
+pub mod tracks;
+pub mod undo_toast;
 pub mod utils;
 
 pub use album_card::AlbumCard;
 pub use album_detail::release_tabs_section::ReleaseTorrentInfo;
 pub use album_detail::{
-    AlbumArt, AlbumCoverSection, AlbumDetailView, AlbumMetadata, DeleteAlbumDialog,
-    DeleteReleaseDialog, ExportErrorToast, PlayAlbumButton, ReleaseInfoModal, ReleaseTabsSection,
-    TrackRow,
+    AlbumArt, AlbumCoverSection, AlbumDetailView, AlbumMetadata, CompareEditionsModal,
+    DeleteAlbumDialog, DeleteReleaseDialog, ExportErrorToast, PlayAlbumButton, ReleaseInfoModal,
+    ReleaseTabsSection, TrackRow,
 };
 pub use app_layout::AppLayoutView;
 pub use button::{Button, ButtonSize, ButtonVariant, ChromelessButton};
@@ -36,15 +44,18 @@ pub use dioxus_virtual_scroll::{
 };
 pub use dropdown::{Dropdown, Placement};
 pub use error_toast::ErrorToast;
+pub use find_replace_modal::FindReplaceModal;
 pub use helpers::{
     BackButton, ConfirmDialogView, ErrorDisplay, LoadingSpinner, Tooltip, TooltipBubble,
 };
+pub use history::HistoryView;
 pub use icons::{
-    AlertTriangleIcon, ArrowLeftIcon, CheckIcon, ChevronDownIcon, ChevronLeftIcon,
+    AlertTriangleIcon, ArrowLeftIcon, BookmarkIcon, CheckIcon, ChevronDownIcon, ChevronLeftIcon,
     ChevronRightIcon, CloudOffIcon, DiscIcon, DownloadIcon, EllipsisIcon, ExternalLinkIcon,
     FileIcon, FileTextIcon, FolderIcon, ImageIcon, InfoIcon, KeyIcon, LayersIcon, LoaderIcon,
     LockIcon, MenuIcon, MonitorIcon, PauseIcon, PencilIcon, PlayIcon, PlusIcon, RefreshIcon,
-    RowsIcon, SettingsIcon, SkipBackIcon, SkipForwardIcon, StarIcon, TrashIcon, UploadIcon, XIcon,
+    RowsIcon, SettingsIcon, SkipBackIcon, SkipForwardIcon, StarIcon, TrashIcon, UploadIcon,
+    VideoIcon, XIcon,
 };
 pub use import::{
     CdDriveStatus, CdSelectorView, ConfirmationView, DiscIdLookupErrorView, FileListView,
@@ -55,19 +66,29 @@ pub use import::{
     TorrentInputMode, TorrentInputView, TorrentTrackerDisplayView, TrackerConnectionStatus,
     TrackerStatus,
 };
-pub use imports::{ImportsButtonView, ImportsDropdownView};
+pub use imports::{ImportQueueView, ImportsButtonView, ImportsDropdownView};
+pub use ipc_boundary::IpcBoundary;
 pub use library::LibraryView;
+pub use list_navigation::{list_key_action, ListKeyAction};
 pub use menu::{MenuDivider, MenuDropdown, MenuItem};
 pub use modal::Modal;
 pub use pill::{Pill, PillVariant};
 pub use playback::{NowPlayingBarView, QueueSidebarState, QueueSidebarView};
+pub use quality_badge::QualityBadge;
 pub use resizable_panel::{GrabBar, PanelPosition, ResizablePanel, ResizeDirection};
 pub use select::{Select, SelectOption};
 pub use settings::{
     AboutSectionView, ApiKeysSectionView, BitTorrentSectionView, BitTorrentSettings,
-    EncryptionSectionView, SettingsTab, SettingsView, StorageLocation, StorageProfile,
-    StorageProfileEditorView, StorageProfilesSectionView, SubsonicSectionView,
+    CdRippingSectionView, ClientShare, ClientSharesView, CloudProvider, EncryptionSectionView,
+    FilenamePolicy, FolderPatternPreset, FolderPatternTest, ImportingSectionView,
+    LibrarySectionView, MaintenanceSectionView, NetworkSectionView, PlacementPolicy,
+    PlaybackSectionView, QuotaLevel, ScrobblingSectionView, SeedingSectionView, SettingsTab,
+    SettingsView, StorageLocation, StorageProfile, StorageProfileEditorView,
+    StorageProfilesSectionView, SubsonicSectionView,
 };
+pub use snapshot_restore::SnapshotRestoreModal;
 pub use text_input::{TextInput, TextInputSize};
-pub use title_bar::{NavItem, SearchResult, TitleBarView};
+pub use title_bar::{NavItem, SearchResult, SearchResultKind, TitleBarView};
+pub use tracks::TracksView;
+pub use undo_toast::UndoToast;
 pub use utils::{format_duration, format_file_size};
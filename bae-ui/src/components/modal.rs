@@ -20,6 +20,8 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use dioxus::prelude::*;
 use wasm_bindgen_x::JsCast;
 
+use crate::ipc;
+
 /// Counter for generating unique modal IDs
 static MODAL_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -45,7 +47,7 @@ pub fn Modal(
     let dialog_id_for_rsx = dialog_id.clone();
 
     // Control dialog open/close state via showModal()/close()
-    use_effect(move || {
+    ipc::use_guarded_effect(move || {
         let is_open = is_open();
 
         let Some(window) = web_sys_x::window() else {
@@ -5,11 +5,19 @@
 //! Each sub-component reads only the fields it needs for granular reactivity.
 
 use crate::components::error_toast::ErrorToast;
-use crate::components::icons::{MenuIcon, PauseIcon, PlayIcon, SkipBackIcon, SkipForwardIcon};
-use crate::components::{Button, ButtonSize, ButtonVariant, ChromelessButton};
+use crate::components::icons::{
+    BookmarkIcon, InfoIcon, MenuIcon, PauseIcon, PlayIcon, SkipBackIcon, SkipForwardIcon,
+};
+use crate::components::{
+    Button, ButtonSize, ButtonVariant, ChromelessButton, Dropdown, Placement, TextInput,
+    TextInputSize,
+};
 use crate::stores::playback::{PlaybackStatus, PlaybackUiState, PlaybackUiStateStoreExt};
 use dioxus::prelude::*;
 
+/// Furthest pitch shift offered either way, in semitones
+const MAX_PITCH_SEMITONES: i32 = 12;
+
 /// Now playing bar view - accepts store for granular reactivity
 #[component]
 pub fn NowPlayingBarView(
@@ -23,11 +31,30 @@ pub fn NowPlayingBarView(
     on_seek: EventHandler<u64>,
     on_toggle_queue: EventHandler<()>,
     on_track_click: EventHandler<String>,
+    /// Called with a name to bookmark the current position
+    on_add_bookmark: EventHandler<String>,
+    /// Called with a shift in semitones; zero turns pitch shifting off
+    on_pitch_change: EventHandler<i32>,
     #[props(default)] on_dismiss_error: Option<EventHandler<()>>,
 ) -> Element {
+    // Subtle wash from the album's dominant color, accent on the top border
+    let palette_style = state
+        .palette()
+        .read()
+        .as_ref()
+        .map(|p| {
+            format!(
+                "background-image: linear-gradient(to right, {}59, transparent 60%); border-top-color: {};",
+                p.dominant, p.accent
+            )
+        })
+        .unwrap_or_default();
+
     rsx! {
-        div { class: "right-0 bg-gray-800 text-white p-4 border-t border-gray-700",
-            div { class: "flex items-center gap-4",
+        div {
+            class: "right-0 bg-gray-800 text-white px-3 py-3 @3xl:p-4 border-t border-gray-700",
+            style: "{palette_style}",
+            div { class: "flex items-center gap-2 @3xl:gap-4",
                 PlaybackControlsSection {
                     state,
                     on_previous,
@@ -42,6 +69,12 @@ pub fn NowPlayingBarView(
 
                 PositionSection { state, on_seek }
 
+                BookmarksSection { state, on_seek, on_add_bookmark }
+
+                PitchSection { state, on_pitch_change }
+
+                OutputFormatSection { state }
+
                 Button {
                     variant: ButtonVariant::Secondary,
                     size: ButtonSize::Medium,
@@ -75,7 +108,7 @@ fn PlaybackControlsSection(
     let main_btn_base = "w-10 h-10 rounded flex items-center justify-center";
 
     rsx! {
-        div { class: "flex items-center gap-2",
+        div { class: "flex items-center gap-1 @3xl:gap-2 flex-shrink-0",
             ChromelessButton {
                 class: Some(
                     if is_loading {
@@ -196,10 +229,10 @@ fn TrackInfoSection(
     let track_id = track.as_ref().map(|t| t.id.clone());
 
     rsx! {
-        div { class: "flex-1",
+        div { class: "flex-1 min-w-0",
             if let Some(ref track) = track {
                 div {
-                    class: "font-semibold cursor-pointer hover:text-blue-300 transition-colors",
+                    class: "font-semibold truncate cursor-pointer hover:text-blue-300 transition-colors",
                     onclick: move |_| {
                         if let Some(ref id) = track_id {
                             on_track_click.call(id.clone());
@@ -207,7 +240,7 @@ fn TrackInfoSection(
                     },
                     "{track.title}"
                 }
-                div { class: "text-sm text-gray-400", "{artist_name}" }
+                div { class: "text-sm text-gray-400 truncate", "{artist_name}" }
             } else if is_loading {
                 div { class: "font-semibold text-gray-400", "Loading..." }
                 div { class: "text-sm text-gray-500", "Loading" }
@@ -245,8 +278,10 @@ fn PositionSection(state: ReadStore<PlaybackUiState>, on_seek: EventHandler<u64>
 
     rsx! {
         if has_position {
-            div { class: "flex items-center gap-2 text-sm text-gray-400",
-                span { class: "w-12 text-right", "{format_display_time(display_position_ms, pregap_ms)}" }
+            div { class: "flex items-center gap-2 text-sm text-gray-400 flex-shrink-0",
+                span { class: "hidden @2xl:inline w-12 text-right",
+                    "{format_display_time(display_position_ms, pregap_ms)}"
+                }
                 if duration_ms > 0 {
                     {
                         let pregap = pregap_ms.unwrap_or(0).max(0) as u64;
@@ -260,7 +295,7 @@ fn PositionSection(state: ReadStore<PlaybackUiState>, on_seek: EventHandler<u64>
                         rsx! {
                             input {
                                 r#type: "range",
-                                class: "w-64 h-2 bg-gray-700 rounded-lg appearance-none cursor-pointer",
+                                class: "w-24 @2xl:w-40 @5xl:w-64 h-2 bg-gray-700 rounded-lg appearance-none cursor-pointer",
                                 style: "background: linear-gradient(to right, #3b82f6 0%, #3b82f6 {progress_percent}%, #374151 {progress_percent}%, #374151 100%);",
                                 min: "0",
                                 max: "{duration_ms / 1000}",
@@ -284,21 +319,264 @@ fn PositionSection(state: ReadStore<PlaybackUiState>, on_seek: EventHandler<u64>
                                     }
                                 },
                             }
-                            span { class: "w-12", "{format_duration_ms(duration_ms)}" }
+                            span { class: "hidden @2xl:inline w-12", "{format_duration_ms(duration_ms)}" }
                         }
                     }
                 } else {
-                    div { class: "w-64 h-2 bg-gray-700 rounded-lg",
+                    div { class: "w-24 @2xl:w-40 @5xl:w-64 h-2 bg-gray-700 rounded-lg",
                         div {
                             class: "h-full bg-blue-600 rounded-lg",
                             style: "width: 50%;",
                         }
                     }
-                    span { class: "w-12", "--:--" }
+                    span { class: "hidden @2xl:inline w-12", "--:--" }
                 }
             }
         } else {
-            div { class: "w-72" }
+            div { class: "w-24 @2xl:w-[17rem] @5xl:w-[23rem] flex-shrink-0" }
+        }
+    }
+}
+
+/// Bookmarks popover for the current track - reads current_track, position_ms, pregap_ms
+#[component]
+fn BookmarksSection(
+    state: ReadStore<PlaybackUiState>,
+    on_seek: EventHandler<u64>,
+    on_add_bookmark: EventHandler<String>,
+) -> Element {
+    let mut show_bookmarks = use_signal(|| false);
+    let is_open: ReadSignal<bool> = show_bookmarks.into();
+    let mut name = use_signal(String::new);
+    let anchor_id = "now-playing-bookmarks".to_string();
+
+    let Some(bookmarks) = state
+        .current_track()
+        .read()
+        .as_ref()
+        .map(|item| item.track.bookmarks.clone())
+    else {
+        return rsx! {};
+    };
+    let pregap_ms = *state.pregap_ms().read();
+
+    rsx! {
+        ChromelessButton {
+            id: Some(anchor_id.clone()),
+            class: Some("hidden @3xl:block p-1 text-gray-400 hover:text-white".to_string()),
+            title: Some("Bookmarks".to_string()),
+            aria_label: Some("Bookmarks".to_string()),
+            onclick: move |evt: MouseEvent| {
+                evt.stop_propagation();
+                show_bookmarks.set(!show_bookmarks());
+            },
+            BookmarkIcon { class: "w-4 h-4" }
+        }
+        Dropdown {
+            anchor_id,
+            is_open,
+            on_close: move |_| show_bookmarks.set(false),
+            placement: Placement::TopEnd,
+            class: "bg-surface-overlay border border-border-strong rounded-lg shadow-lg p-3 w-64",
+            div { class: "text-xs font-medium text-gray-400 uppercase tracking-wide mb-2",
+                "Bookmarks"
+            }
+            if bookmarks.is_empty() {
+                p { class: "text-xs text-gray-500 mb-2", "No bookmarks in this track" }
+            }
+            for bookmark in bookmarks {
+                ChromelessButton {
+                    key: "{bookmark.id}",
+                    class: Some(
+                        "w-full flex items-center justify-between gap-2 px-2 py-1 rounded text-xs text-white hover:bg-hover"
+                            .to_string(),
+                    ),
+                    onclick: move |_| {
+                        show_bookmarks.set(false);
+                        on_seek.call(bookmark.position_ms);
+                    },
+                    span { class: "truncate", "{bookmark.name}" }
+                    span { class: "font-mono text-gray-400",
+                        "{format_display_time(bookmark.position_ms, pregap_ms)}"
+                    }
+                }
+            }
+            div { class: "flex items-center gap-2 mt-2",
+                TextInput {
+                    value: name(),
+                    on_input: move |value| name.set(value),
+                    size: TextInputSize::Small,
+                    placeholder: "Name",
+                }
+                Button {
+                    variant: ButtonVariant::Secondary,
+                    size: ButtonSize::Small,
+                    onclick: move |_| {
+                        let position_ms = *state.position_ms().peek();
+                        let trimmed = name.read().trim().to_string();
+                        // Unnamed bookmarks are named after their position
+                        let label = if trimmed.is_empty() {
+                            format_display_time(position_ms, pregap_ms)
+                        } else {
+                            trimmed
+                        };
+                        name.set(String::new());
+                        on_add_bookmark.call(label);
+                    },
+                    "Add"
+                }
+            }
+        }
+    }
+}
+
+/// Pitch shift popover - reads only pitch_semitones
+///
+/// Highlighted while a shift is set: the audio is being processed, so
+/// playback isn't bit-perfect.
+#[component]
+fn PitchSection(state: ReadStore<PlaybackUiState>, on_pitch_change: EventHandler<i32>) -> Element {
+    let mut show_pitch = use_signal(|| false);
+    let is_open: ReadSignal<bool> = show_pitch.into();
+    let anchor_id = "now-playing-pitch".to_string();
+
+    let semitones = *state.pitch_semitones().read();
+    let shifted = semitones != 0;
+    let label = format_semitones(semitones);
+
+    rsx! {
+        ChromelessButton {
+            id: Some(anchor_id.clone()),
+            class: Some(
+                if shifted {
+                    "hidden @3xl:block px-1.5 py-0.5 rounded bg-amber-500/20 text-xs font-mono text-amber-300 hover:bg-amber-500/30"
+                        .to_string()
+                } else {
+                    "hidden @3xl:block px-1.5 py-0.5 rounded text-xs text-gray-400 hover:text-white"
+                        .to_string()
+                },
+            ),
+            title: Some(
+                if shifted {
+                    format!("Pitch {label} (processing active)")
+                } else {
+                    "Pitch".to_string()
+                },
+            ),
+            aria_label: Some("Pitch".to_string()),
+            onclick: move |evt: MouseEvent| {
+                evt.stop_propagation();
+                show_pitch.set(!show_pitch());
+            },
+            if shifted {
+                "{label}"
+            } else {
+                "Pitch"
+            }
+        }
+        Dropdown {
+            anchor_id,
+            is_open,
+            on_close: move |_| show_pitch.set(false),
+            placement: Placement::TopEnd,
+            class: "bg-surface-overlay border border-border-strong rounded-lg shadow-lg p-3 w-64",
+            div { class: "text-xs font-medium text-gray-400 uppercase tracking-wide mb-2",
+                "Pitch"
+            }
+            div { class: "flex items-center gap-2",
+                Button {
+                    variant: ButtonVariant::Secondary,
+                    size: ButtonSize::Small,
+                    disabled: semitones <= -MAX_PITCH_SEMITONES,
+                    onclick: move |_| on_pitch_change.call(semitones - 1),
+                    "−"
+                }
+                span { class: "flex-1 text-center text-sm font-mono text-white", "{label}" }
+                Button {
+                    variant: ButtonVariant::Secondary,
+                    size: ButtonSize::Small,
+                    disabled: semitones >= MAX_PITCH_SEMITONES,
+                    onclick: move |_| on_pitch_change.call(semitones + 1),
+                    "+"
+                }
+                Button {
+                    variant: ButtonVariant::Secondary,
+                    size: ButtonSize::Small,
+                    disabled: !shifted,
+                    onclick: move |_| on_pitch_change.call(0),
+                    "Reset"
+                }
+            }
+            if shifted {
+                p { class: "text-xs text-amber-300 mt-2",
+                    "Processing active: the audio is resampled, so playback isn't bit-perfect."
+                }
+            } else {
+                p { class: "text-xs text-gray-500 mt-2",
+                    "Transpose in semitones without changing the tempo."
+                }
+            }
+        }
+    }
+}
+
+/// Output device format popover - reads output_format and pitch_semitones
+#[component]
+fn OutputFormatSection(state: ReadStore<PlaybackUiState>) -> Element {
+    let mut show_details = use_signal(|| false);
+    let is_open: ReadSignal<bool> = show_details.into();
+    let anchor_id = "now-playing-output-format".to_string();
+
+    let Some(format) = state.output_format().read().clone() else {
+        return rsx! {};
+    };
+    let pitch_semitones = *state.pitch_semitones().read();
+    let dither = format.dither.map(|d| d.label()).unwrap_or("None");
+    let mode = if format.exclusive {
+        "Exclusive"
+    } else {
+        "Shared"
+    };
+
+    rsx! {
+        ChromelessButton {
+            id: Some(anchor_id.clone()),
+            class: Some("hidden @3xl:block p-1 text-gray-400 hover:text-white".to_string()),
+            title: Some("Output format".to_string()),
+            aria_label: Some("Output format".to_string()),
+            onclick: move |evt: MouseEvent| {
+                evt.stop_propagation();
+                show_details.set(!show_details());
+            },
+            InfoIcon { class: "w-4 h-4" }
+        }
+        Dropdown {
+            anchor_id,
+            is_open,
+            on_close: move |_| show_details.set(false),
+            placement: Placement::TopEnd,
+            class: "bg-surface-overlay border border-border-strong rounded-lg shadow-lg p-3 w-64",
+            div { class: "text-xs font-medium text-gray-400 uppercase tracking-wide mb-2",
+                "Output"
+            }
+            div { class: "grid grid-cols-[auto_1fr] gap-x-3 gap-y-1 text-xs",
+                span { class: "text-gray-500", "Device" }
+                span { class: "text-white truncate", "{format.device_name}" }
+                span { class: "text-gray-500", "Sample rate" }
+                span { class: "text-white font-mono", "{format.sample_rate} Hz" }
+                span { class: "text-gray-500", "Channels" }
+                span { class: "text-white font-mono", "{format.channels}" }
+                span { class: "text-gray-500", "Format" }
+                span { class: "text-white font-mono", "{format.sample_format}" }
+                span { class: "text-gray-500", "Dither" }
+                span { class: "text-white", "{dither}" }
+                span { class: "text-gray-500", "Mode" }
+                span { class: "text-white", "{mode}" }
+                if pitch_semitones != 0 {
+                    span { class: "text-gray-500", "Processing" }
+                    span { class: "text-amber-300", "Pitch {format_semitones(pitch_semitones)}" }
+                }
+            }
         }
     }
 }
@@ -329,6 +607,15 @@ fn PlaybackErrorSection(
     }
 }
 
+/// "+2 st", "−3 st", "0 st"
+fn format_semitones(semitones: i32) -> String {
+    match semitones {
+        0 => "0 st".to_string(),
+        s if s > 0 => format!("+{} st", s),
+        s => format!("−{} st", -s),
+    }
+}
+
 fn format_duration_ms(ms: u64) -> String {
     let total_secs = ms / 1000;
     let mins = total_secs / 60;
@@ -4,11 +4,11 @@
 //! Accepts `ReadStore<PlaybackUiState>` and reads fields via lenses.
 //! Each section only re-renders when its specific data changes.
 
-use crate::components::icons::{ImageIcon, MenuIcon, XIcon};
+use crate::components::icons::{ImageIcon, MenuIcon, PlusIcon, SkipForwardIcon, XIcon};
 use crate::components::utils::format_duration;
 use crate::components::{Button, ButtonSize, ButtonVariant, ChromelessButton};
 use crate::display_types::QueueItem;
-use crate::stores::playback::{PlaybackUiState, PlaybackUiStateStoreExt};
+use crate::stores::playback::{PlaybackUiState, PlaybackUiStateStoreExt, RepeatMode};
 use crate::stores::ui::{SidebarState, SidebarStateStoreExt};
 use dioxus::prelude::*;
 
@@ -28,8 +28,17 @@ pub fn QueueSidebarView(
     // Callbacks
     on_close: EventHandler<()>,
     on_clear: EventHandler<()>,
+    /// Pick audio files from disk and play them without importing
+    on_open_files: EventHandler<()>,
     on_remove: EventHandler<usize>,
     on_track_click: EventHandler<String>,
+    /// Queue a recently played track right after the current one
+    on_history_play_next: EventHandler<String>,
+    on_history_add_to_queue: EventHandler<String>,
+    on_clear_history: EventHandler<()>,
+    on_toggle_shuffle: EventHandler<()>,
+    /// Step through repeat off, track, album
+    on_cycle_repeat: EventHandler<()>,
 ) -> Element {
     // Read is_open via lens - only this check re-runs when visibility changes
     let is_open = *sidebar.is_open().read();
@@ -43,16 +52,38 @@ pub fn QueueSidebarView(
             div { class: "flex-1 overflow-y-auto",
                 NowPlayingSection { playback, on_track_click, on_remove }
 
-                UpNextSection { playback, on_track_click, on_remove }
+                UpNextSection {
+                    playback,
+                    on_track_click,
+                    on_remove,
+                    on_toggle_shuffle,
+                    on_cycle_repeat,
+                }
+
+                HistorySection {
+                    playback,
+                    on_track_click,
+                    on_play_next: on_history_play_next,
+                    on_add_to_queue: on_history_add_to_queue,
+                    on_clear: on_clear_history,
+                }
             }
 
             // Footer with controls
             div { class: "flex items-center justify-between p-4 border-t border-gray-700",
-                Button {
-                    variant: ButtonVariant::Secondary,
-                    size: ButtonSize::Small,
-                    onclick: move |_| on_clear.call(()),
-                    "Clear"
+                div { class: "flex items-center gap-2",
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        onclick: move |_| on_clear.call(()),
+                        "Clear"
+                    }
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        onclick: move |_| on_open_files.call(()),
+                        "Open files"
+                    }
                 }
                 Button {
                     variant: ButtonVariant::Secondary,
@@ -97,22 +128,93 @@ fn NowPlayingSection(
     }
 }
 
-/// Up next section - reads only queue_items
+/// Recently played section - reads only history_items
+#[component]
+fn HistorySection(
+    playback: ReadStore<PlaybackUiState>,
+    on_track_click: EventHandler<String>,
+    on_play_next: EventHandler<String>,
+    on_add_to_queue: EventHandler<String>,
+    on_clear: EventHandler<()>,
+) -> Element {
+    // Read only history_items via lens
+    let history = playback.history_items().read().clone();
+
+    if history.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            div { class: "flex items-center justify-between px-4 pt-4 pb-2",
+                h3 { class: "text-sm font-semibold text-gray-400 uppercase tracking-wide",
+                    "Recently played"
+                }
+                ChromelessButton {
+                    class: Some("text-xs text-gray-500 hover:text-gray-300".to_string()),
+                    onclick: move |_| on_clear.call(()),
+                    "Clear"
+                }
+            }
+            for (index , item) in history.iter().enumerate() {
+                HistoryItemView {
+                    // The same track can appear more than once
+                    key: "{index}-{item.track.id}",
+                    item: item.clone(),
+                    on_click: on_track_click,
+                    on_play_next,
+                    on_add_to_queue,
+                }
+            }
+        }
+    }
+}
+
+/// Up next section - reads queue_items, shuffle and repeat_mode
 #[component]
 fn UpNextSection(
     playback: ReadStore<PlaybackUiState>,
     on_track_click: EventHandler<String>,
     on_remove: EventHandler<usize>,
+    on_toggle_shuffle: EventHandler<()>,
+    on_cycle_repeat: EventHandler<()>,
 ) -> Element {
     // Read only queue_items via lens
     let queue = playback.queue_items().read().clone();
+    let shuffle = *playback.shuffle().read();
+    let repeat_mode = *playback.repeat_mode().read();
+
+    let toggle_class = |active: bool| {
+        if active {
+            "text-xs text-indigo-400 hover:text-indigo-300".to_string()
+        } else {
+            "text-xs text-gray-500 hover:text-gray-300".to_string()
+        }
+    };
+    let repeat_label = match repeat_mode {
+        RepeatMode::None => "Repeat",
+        RepeatMode::Track => "Repeat track",
+        RepeatMode::Album => "Repeat album",
+    };
 
     rsx! {
         div {
-            div { class: "px-4 pt-4 pb-2",
+            div { class: "flex items-center justify-between px-4 pt-4 pb-2",
                 h3 { class: "text-sm font-semibold text-gray-400 uppercase tracking-wide",
                     "Up next"
                 }
+                div { class: "flex items-center gap-3",
+                    ChromelessButton {
+                        class: Some(toggle_class(shuffle)),
+                        onclick: move |_| on_toggle_shuffle.call(()),
+                        "Shuffle"
+                    }
+                    ChromelessButton {
+                        class: Some(toggle_class(repeat_mode != RepeatMode::None)),
+                        onclick: move |_| on_cycle_repeat.call(()),
+                        "{repeat_label}"
+                    }
+                }
             }
             if !queue.is_empty() {
                 for (index , item) in queue.iter().enumerate() {
@@ -142,49 +244,7 @@ fn QueueItemView(
 ) -> Element {
     rsx! {
         div { class: if is_current { "flex items-center gap-3 p-3 border-b border-gray-700 bg-blue-500/10 hover:bg-blue-500/15 group" } else { "flex items-center gap-3 p-3 border-b border-gray-700 hover:bg-gray-800 group" },
-            // Album cover
-            div { class: "w-12 h-12 flex-shrink-0 bg-gray-700 rounded overflow-clip",
-                if let Some(ref url) = item.cover_url {
-                    img {
-                        src: "{url}",
-                        alt: "Album cover",
-                        class: "w-full h-full object-cover",
-                    }
-                } else {
-                    div { class: "w-full h-full flex items-center justify-center text-gray-500",
-                        ImageIcon { class: "w-6 h-6" }
-                    }
-                }
-            }
-            // Track info
-            div { class: "flex-1 min-w-0",
-                div { class: "flex items-center gap-2",
-                    ChromelessButton {
-                        class: Some(
-                            if is_current {
-                                "font-medium text-blue-300 hover:text-blue-200 text-left truncate flex-1"
-                                    .to_string()
-                            } else {
-                                "font-medium text-white hover:text-blue-300 text-left truncate flex-1"
-                                    .to_string()
-                            },
-                        ),
-                        onclick: {
-                            let track_id = item.track.id.clone();
-                            move |_| on_click.call(track_id.clone())
-                        },
-                        "{item.track.title}"
-                    }
-                    span { class: "text-sm text-gray-400 flex-shrink-0",
-                        if let Some(duration_ms) = item.track.duration_ms {
-                            {format_duration(duration_ms)}
-                        } else {
-                            "—:—"
-                        }
-                    }
-                }
-                div { class: "text-sm text-gray-400 truncate", "{item.album_title}" }
-            }
+            QueueItemInfo { item, is_current, on_click }
             // Remove button (only for non-current tracks)
             if !is_current {
                 ChromelessButton {
@@ -200,3 +260,97 @@ fn QueueItemView(
         }
     }
 }
+
+/// A track that already played, with actions to queue it again
+#[component]
+fn HistoryItemView(
+    item: QueueItem,
+    on_click: EventHandler<String>,
+    on_play_next: EventHandler<String>,
+    on_add_to_queue: EventHandler<String>,
+) -> Element {
+    let action_class = "px-1.5 py-1 text-gray-400 hover:text-white rounded";
+
+    rsx! {
+        div { class: "flex items-center gap-3 p-3 border-b border-gray-700 hover:bg-gray-800 group",
+            QueueItemInfo { item: item.clone(), is_current: false, on_click }
+            div { class: "flex items-center opacity-0 group-hover:opacity-100 transition-opacity",
+                ChromelessButton {
+                    class: Some(action_class.to_string()),
+                    aria_label: Some("Play next".to_string()),
+                    onclick: {
+                        let track_id = item.track.id.clone();
+                        move |_| on_play_next.call(track_id.clone())
+                    },
+                    SkipForwardIcon { class: "w-4 h-4" }
+                }
+                ChromelessButton {
+                    class: Some(action_class.to_string()),
+                    aria_label: Some("Add to queue".to_string()),
+                    onclick: {
+                        let track_id = item.track.id.clone();
+                        move |_| on_add_to_queue.call(track_id.clone())
+                    },
+                    PlusIcon { class: "w-4 h-4" }
+                }
+            }
+        }
+    }
+}
+
+/// Cover, title, duration and album of a queue row
+#[component]
+fn QueueItemInfo(item: QueueItem, is_current: bool, on_click: EventHandler<String>) -> Element {
+    rsx! {
+        // Album cover
+        div { class: "w-12 h-12 flex-shrink-0 bg-gray-700 rounded overflow-clip",
+            if let Some(ref url) = item.cover_url {
+                img {
+                    src: "{url}",
+                    alt: "Album cover",
+                    class: "w-full h-full object-cover",
+                }
+            } else {
+                div { class: "w-full h-full flex items-center justify-center text-gray-500",
+                    ImageIcon { class: "w-6 h-6" }
+                }
+            }
+        }
+        // Track info
+        div { class: "flex-1 min-w-0",
+            div { class: "flex items-center gap-2",
+                ChromelessButton {
+                    class: Some(
+                        if is_current {
+                            "font-medium text-blue-300 hover:text-blue-200 text-left truncate flex-1"
+                                .to_string()
+                        } else {
+                            "font-medium text-white hover:text-blue-300 text-left truncate flex-1"
+                                .to_string()
+                        },
+                    ),
+                    onclick: {
+                        let track_id = item.track.id.clone();
+                        move |_| on_click.call(track_id.clone())
+                    },
+                    "{item.track.title}"
+                }
+                span { class: "text-sm text-gray-400 flex-shrink-0",
+                    if let Some(duration_ms) = item.track.duration_ms {
+                        {format_duration(duration_ms)}
+                    } else {
+                        "—:—"
+                    }
+                }
+            }
+            div { class: "flex items-center gap-2 text-sm text-gray-400 min-w-0",
+                if item.is_external {
+                    span { class: "px-1.5 py-0.5 text-xs rounded bg-gray-700 text-gray-300 flex-shrink-0",
+                        "External"
+                    }
+                }
+                span { class: "truncate", "{item.album_title}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Compact audio quality label for album cards and track rows
+
+use crate::display_types::AudioQuality;
+use dioxus::prelude::*;
+
+#[component]
+pub fn QualityBadge(quality: AudioQuality) -> Element {
+    let color = match quality {
+        AudioQuality::Lossy => "text-amber-300 border-amber-400/40",
+        AudioQuality::Lossless => "text-gray-300 border-gray-500/60",
+        AudioQuality::HiRes => "text-sky-300 border-sky-400/40",
+    };
+
+    rsx! {
+        span { class: "inline-flex items-center px-1.5 py-px text-[10px] font-medium uppercase tracking-wide rounded border {color}",
+            {quality.label()}
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! Resizable panel component with drag handle
 
+use crate::ipc;
 use crate::wasm_utils::DocumentEventListener;
 use dioxus::prelude::*;
 
@@ -113,7 +114,7 @@ pub fn ResizablePanel(
 
     // Load initial size from localStorage after mount (can't do this in use_hook
     // because the wry-bindgen JS bridge may not be ready during component init)
-    use_effect(move || {
+    ipc::use_guarded_effect(move || {
         if loaded_from_storage() {
             return;
         }
@@ -135,7 +136,7 @@ pub fn ResizablePanel(
     let grabber_offset_percent = (100.0 - grabber_span_percent) / 2.0;
 
     // Save size to localStorage when resize ends
-    use_effect(move || {
+    ipc::use_guarded_effect(move || {
         if is_resizing() {
             return;
         }
@@ -146,7 +147,7 @@ pub fn ResizablePanel(
     });
 
     // Document-level mouse listeners for resize dragging
-    use_effect(move || {
+    ipc::use_guarded_effect(move || {
         use web_sys_x::js_sys;
 
         if !is_resizing() {
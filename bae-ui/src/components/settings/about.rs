@@ -1,6 +1,8 @@
 //! About section view
 
+use crate::components::utils::{format_date_time, format_uptime};
 use crate::components::{Button, ButtonSize, ButtonVariant};
+use crate::display_types::{ServiceHealth, ServiceKind, ServiceStatus};
 use dioxus::prelude::*;
 
 /// About section view
@@ -12,6 +14,13 @@ pub fn AboutSectionView(
     album_count: usize,
     /// Callback for check updates button
     on_check_updates: EventHandler<()>,
+    /// Subsystem health from the last check, in display order
+    services: Vec<ServiceHealth>,
+    is_checking_health: bool,
+    /// Subsystem being restarted
+    restarting: Option<ServiceKind>,
+    on_check_health: EventHandler<()>,
+    on_restart_service: EventHandler<ServiceKind>,
 ) -> Element {
     rsx! {
         div { class: "max-w-2xl",
@@ -39,6 +48,39 @@ pub fn AboutSectionView(
                 }
             }
 
+            div { class: "bg-gray-800 rounded-lg p-6 mb-6",
+                div { class: "flex items-center justify-between mb-4",
+                    h3 { class: "text-lg font-medium text-white", "Health" }
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        disabled: is_checking_health,
+                        loading: is_checking_health,
+                        onclick: move |_| on_check_health.call(()),
+                        if is_checking_health {
+                            "Checking..."
+                        } else {
+                            "Check now"
+                        }
+                    }
+                }
+                if services.is_empty() {
+                    p { class: "text-sm text-gray-400", "Not checked yet" }
+                } else {
+                    div { class: "divide-y divide-gray-700",
+                        for service in services {
+                            ServiceHealthRow {
+                                key: "{service.kind.label()}",
+                                service: service.clone(),
+                                is_restarting: restarting == Some(service.kind),
+                                restart_disabled: restarting.is_some(),
+                                on_restart: move |kind| on_restart_service.call(kind),
+                            }
+                        }
+                    }
+                }
+            }
+
             div { class: "bg-gray-800 rounded-lg p-6",
                 h3 { class: "text-lg font-medium text-white mb-4", "Library Statistics" }
                 div { class: "bg-gray-700 rounded-lg p-4 text-center",
@@ -49,3 +91,61 @@ pub fn AboutSectionView(
         }
     }
 }
+
+/// One subsystem: status, uptime, last error, and a restart button if it
+/// can be restarted on its own
+#[component]
+fn ServiceHealthRow(
+    service: ServiceHealth,
+    is_restarting: bool,
+    restart_disabled: bool,
+    on_restart: EventHandler<ServiceKind>,
+) -> Element {
+    let (dot, status) = match service.status {
+        ServiceStatus::Running => (
+            "bg-green-500",
+            match service.uptime_secs {
+                Some(secs) => format!("Up {}", format_uptime(secs)),
+                None => "Running".to_string(),
+            },
+        ),
+        ServiceStatus::NotStarted => ("bg-gray-500", "Not in use".to_string()),
+        ServiceStatus::Failed => ("bg-red-500", "Failed".to_string()),
+    };
+    let kind = service.kind;
+
+    rsx! {
+        div { class: "py-3 flex items-start gap-3",
+            span { class: "mt-1.5 w-2 h-2 rounded-full shrink-0 {dot}" }
+            div { class: "flex-1 min-w-0",
+                div { class: "flex items-center justify-between gap-3",
+                    span { class: "text-white", {kind.label()} }
+                    span { class: "text-sm text-gray-400", "{status}" }
+                }
+                if let Some(error) = &service.last_error {
+                    p { class: "text-xs text-gray-400 mt-1 break-words",
+                        if let Some(at) = service.last_error_at {
+                            "Last error ({format_date_time(at)}): "
+                        } else {
+                            "Last error: "
+                        }
+                        span {
+                            class: if service.status == ServiceStatus::Failed { "text-red-400" } else { "text-gray-300" },
+                            "{error}"
+                        }
+                    }
+                }
+            }
+            if service.can_restart {
+                Button {
+                    variant: ButtonVariant::Secondary,
+                    size: ButtonSize::Small,
+                    disabled: restart_disabled,
+                    loading: is_restarting,
+                    onclick: move |_| on_restart.call(kind),
+                    "Restart"
+                }
+            }
+        }
+    }
+}
@@ -14,6 +14,10 @@ pub struct BitTorrentSettings {
     pub max_uploads: Option<i32>,
     pub max_uploads_per_torrent: Option<i32>,
     pub bind_interface: Option<String>,
+    pub enable_ipv6: bool,
+    pub enable_dht: bool,
+    /// DHT bootstrap nodes as host:port (empty = libtorrent defaults)
+    pub dht_bootstrap_nodes: Vec<String>,
 }
 
 /// BitTorrent section view
@@ -30,6 +34,10 @@ pub fn BitTorrentSectionView(
     edit_max_uploads: String,
     edit_max_uploads_per_torrent: String,
     edit_bind_interface: String,
+    edit_enable_ipv6: bool,
+    edit_enable_dht: bool,
+    /// Bootstrap nodes, one host:port per line
+    edit_dht_bootstrap_nodes: String,
     /// State flags
     is_saving: bool,
     has_changes: bool,
@@ -45,6 +53,9 @@ pub fn BitTorrentSectionView(
     on_max_uploads_change: EventHandler<String>,
     on_max_uploads_per_torrent_change: EventHandler<String>,
     on_bind_interface_change: EventHandler<String>,
+    on_enable_ipv6_change: EventHandler<bool>,
+    on_enable_dht_change: EventHandler<bool>,
+    on_dht_bootstrap_nodes_change: EventHandler<String>,
 ) -> Element {
     rsx! {
         div { class: "max-w-2xl space-y-6",
@@ -223,6 +234,88 @@ pub fn BitTorrentSectionView(
                                 "Bind to a specific interface (e.g., VPN tunnel). Leave empty for default."
                             }
                         }
+                        div { class: "flex items-center gap-3",
+                            input {
+                                r#type: "checkbox",
+                                class: "w-4 h-4 rounded bg-gray-700 border-gray-600 text-indigo-600 focus:ring-indigo-500",
+                                checked: edit_enable_ipv6,
+                                onchange: move |e| on_enable_ipv6_change.call(e.checked()),
+                            }
+                            label { class: "text-sm text-gray-300", "Listen on IPv6 as well as IPv4" }
+                        }
+
+                        SectionSaveButtons {
+                            has_changes,
+                            is_saving,
+                            save_error: save_error.clone(),
+                            on_save,
+                            on_cancel: on_cancel_edit,
+                        }
+                    }
+                } else {
+                    div { class: "space-y-2 text-sm",
+                        div { class: "flex items-center",
+                            span { class: "text-gray-400 w-36", "Interface:" }
+                            if let Some(ref iface) = settings.bind_interface {
+                                span { class: "text-white font-mono", "{iface}" }
+                            } else {
+                                span { class: "text-gray-500 italic", "Default" }
+                            }
+                        }
+                        div { class: "flex items-center",
+                            span { class: "text-gray-400 w-36", "IPv6:" }
+                            span { class: if settings.enable_ipv6 { "text-green-400" } else { "text-gray-500" },
+                                if settings.enable_ipv6 {
+                                    "Enabled"
+                                } else {
+                                    "Disabled"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // DHT Section
+            div { class: "bg-gray-800 rounded-lg p-6",
+                div { class: "flex items-center justify-between mb-4",
+                    h3 { class: "text-lg font-medium text-white", "DHT" }
+                    if editing_section.as_deref() != Some("dht") {
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            size: ButtonSize::Small,
+                            onclick: move |_| on_edit_section.call("dht".to_string()),
+                            "Edit"
+                        }
+                    }
+                }
+
+                if editing_section.as_deref() == Some("dht") {
+                    div { class: "space-y-4",
+                        div { class: "flex items-center gap-3",
+                            input {
+                                r#type: "checkbox",
+                                class: "w-4 h-4 rounded bg-gray-700 border-gray-600 text-indigo-600 focus:ring-indigo-500",
+                                checked: edit_enable_dht,
+                                onchange: move |e| on_enable_dht_change.call(e.checked()),
+                            }
+                            label { class: "text-sm text-gray-300",
+                                "Find peers through the DHT"
+                            }
+                        }
+                        div { class: "space-y-2",
+                            label { class: "text-sm text-gray-400", "Bootstrap nodes:" }
+                            textarea {
+                                class: "w-full h-24 px-3 py-2 bg-gray-700 border border-gray-600 rounded-lg text-white font-mono text-sm focus:outline-none focus:ring-2 focus:ring-indigo-500 disabled:opacity-50",
+                                placeholder: "dht.example.org:6881",
+                                disabled: !edit_enable_dht,
+                                value: "{edit_dht_bootstrap_nodes}",
+                                oninput: move |e| on_dht_bootstrap_nodes_change.call(e.value()),
+                            }
+                            p { class: "text-xs text-gray-500",
+                                "One host:port per line. Leave empty for libtorrent's default nodes."
+                            }
+                        }
 
                         SectionSaveButtons {
                             has_changes,
@@ -233,12 +326,30 @@ pub fn BitTorrentSectionView(
                         }
                     }
                 } else {
-                    div { class: "text-sm",
-                        span { class: "text-gray-400", "Interface: " }
-                        if let Some(ref iface) = settings.bind_interface {
-                            span { class: "text-white font-mono", "{iface}" }
-                        } else {
-                            span { class: "text-gray-500 italic", "Default" }
+                    div { class: "space-y-2 text-sm",
+                        div { class: "flex items-center",
+                            span { class: "text-gray-400 w-36", "DHT:" }
+                            span { class: if settings.enable_dht { "text-green-400" } else { "text-gray-500" },
+                                if settings.enable_dht {
+                                    "Enabled"
+                                } else {
+                                    "Disabled"
+                                }
+                            }
+                        }
+                        if settings.enable_dht {
+                            div { class: "flex items-start",
+                                span { class: "text-gray-400 w-36 shrink-0", "Bootstrap nodes:" }
+                                if settings.dht_bootstrap_nodes.is_empty() {
+                                    span { class: "text-gray-500 italic", "Default" }
+                                } else {
+                                    div { class: "space-y-1",
+                                        for node in settings.dht_bootstrap_nodes.iter() {
+                                            div { class: "text-white font-mono", "{node}" }
+                                        }
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -0,0 +1,63 @@
+//! CD ripping section view
+
+use crate::components::{Select, SelectOption};
+use crate::display_types::CdInsertAction;
+use dioxus::prelude::*;
+
+/// CD ripping section view - what inserting a disc does, and ejecting after a rip
+#[component]
+pub fn CdRippingSectionView(
+    insert_action: CdInsertAction,
+    /// Eject the disc once it's ripped
+    eject_after_rip: bool,
+    on_insert_action_change: EventHandler<CdInsertAction>,
+    on_eject_after_rip_change: EventHandler<bool>,
+) -> Element {
+    rsx! {
+        div { class: "max-w-2xl space-y-6",
+            h2 { class: "text-xl font-semibold text-white mb-6", "CD Ripping" }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Inserting a Disc" }
+                div { class: "space-y-4",
+                    div { class: "flex items-center gap-4",
+                        label { class: "text-sm text-gray-400 w-40", "Inserting an audio CD:" }
+                        Select {
+                            value: insert_action.key(),
+                            onchange: move |key: String| {
+                                if let Some(action) = CdInsertAction::from_key(&key) {
+                                    on_insert_action_change.call(action);
+                                }
+                            },
+                            for option in CdInsertAction::ALL {
+                                SelectOption {
+                                    key: "{option.key()}",
+                                    value: option.key(),
+                                    label: option.label(),
+                                }
+                            }
+                        }
+                    }
+                    p { class: "text-xs text-gray-500",
+                        "A disc is recognized when its DiscID matches exactly one release. Otherwise the CD import opens so you can pick the release. Nothing happens while another import is in progress."
+                    }
+
+                    label { class: "flex items-start gap-3 cursor-pointer",
+                        input {
+                            r#type: "checkbox",
+                            class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600 mt-0.5",
+                            checked: eject_after_rip,
+                            onchange: move |e| on_eject_after_rip_change.call(e.checked()),
+                        }
+                        div {
+                            span { class: "text-white block", "Eject when ripped" }
+                            span { class: "text-xs text-gray-500",
+                                "Eject the disc as soon as every track is read, so the next one can go in while the import finishes."
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
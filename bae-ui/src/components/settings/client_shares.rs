@@ -0,0 +1,207 @@
+//! Client shares view - which albums each Subsonic client may see
+
+use crate::components::{Button, ButtonSize, ButtonVariant, TextInput, TextInputSize};
+use dioxus::prelude::*;
+
+/// Most albums listed at once when picking a share's albums
+const MAX_LISTED_ALBUMS: usize = 50;
+
+/// A set of albums a client can see, unlocked by its token
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientShare {
+    pub id: String,
+    pub name: String,
+    /// What the client sends as its password
+    pub token: String,
+    pub album_ids: Vec<String>,
+}
+
+/// An album that can be added to a share
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShareableAlbum {
+    pub id: String,
+    pub title: String,
+    pub artist: String,
+}
+
+/// Client shares view
+#[component]
+pub fn ClientSharesView(
+    shares: Vec<ClientShare>,
+    albums: Vec<ShareableAlbum>,
+    error: Option<String>,
+    /// Create a share with the given name
+    on_create: EventHandler<String>,
+    on_delete: EventHandler<String>,
+    /// Called with (share ID, album ID, shared)
+    on_set_album_shared: EventHandler<(String, String, bool)>,
+) -> Element {
+    let mut new_name = use_signal(String::new);
+    let mut expanded_share = use_signal(|| None::<String>);
+    let mut album_filter = use_signal(String::new);
+
+    let create = move |_| {
+        let name = new_name.read().trim().to_string();
+        if !name.is_empty() {
+            on_create.call(name);
+            new_name.set(String::new());
+        }
+    };
+
+    rsx! {
+        div { class: "bg-gray-800 rounded-lg p-6",
+            h3 { class: "text-lg font-medium text-white mb-4", "Client Shares" }
+            div { class: "space-y-4",
+                p { class: "text-sm text-gray-400",
+                    "Give each client only the albums it should see. Once a share exists, clients must sign in with a share's token as their password and see just that share's albums. Without shares, including after the last one is deleted, the whole library is open to every client."
+                }
+
+                if let Some(error) = error {
+                    div { class: "p-3 bg-red-900/30 border border-red-700 rounded-lg text-sm text-red-300",
+                        "{error}"
+                    }
+                }
+
+                for share in shares {
+                    {
+                        let is_expanded = expanded_share().as_deref() == Some(share.id.as_str());
+                        let album_count = share.album_ids.len();
+                        let share_id = share.id.clone();
+                        let delete_id = share.id.clone();
+                        rsx! {
+                            div { key: "{share.id}", class: "border border-gray-700 rounded-lg p-4 space-y-3",
+                                div { class: "flex items-center justify-between gap-4",
+                                    div { class: "min-w-0",
+                                        span { class: "text-white block", "{share.name}" }
+                                        span { class: "text-xs text-gray-500",
+                                            if album_count == 1 {
+                                                "1 album"
+                                            } else {
+                                                "{album_count} albums"
+                                            }
+                                        }
+                                    }
+                                    div { class: "flex gap-2",
+                                        Button {
+                                            variant: ButtonVariant::Secondary,
+                                            size: ButtonSize::Small,
+                                            onclick: move |_| {
+                                                if is_expanded {
+                                                    expanded_share.set(None);
+                                                } else {
+                                                    expanded_share.set(Some(share_id.clone()));
+                                                    album_filter.set(String::new());
+                                                }
+                                            },
+                                            if is_expanded {
+                                                "Done"
+                                            } else {
+                                                "Albums"
+                                            }
+                                        }
+                                        Button {
+                                            variant: ButtonVariant::Danger,
+                                            size: ButtonSize::Small,
+                                            onclick: move |_| on_delete.call(delete_id.clone()),
+                                            "Delete"
+                                        }
+                                    }
+                                }
+                                div { class: "flex items-center gap-2 text-sm",
+                                    span { class: "text-gray-400", "Token:" }
+                                    span { class: "text-indigo-400 font-mono select-all", "{share.token}" }
+                                }
+                                if is_expanded {
+                                    ShareAlbumPicker {
+                                        share: share.clone(),
+                                        albums: albums.clone(),
+                                        filter: album_filter(),
+                                        on_filter_change: move |text| album_filter.set(text),
+                                        on_set_album_shared,
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "flex items-center gap-3",
+                    div { class: "flex-1",
+                        TextInput {
+                            value: new_name(),
+                            on_input: move |v| new_name.set(v),
+                            size: TextInputSize::Small,
+                            placeholder: "Phone, car, web player...",
+                        }
+                    }
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        size: ButtonSize::Small,
+                        disabled: new_name.read().trim().is_empty(),
+                        onclick: create,
+                        "Add Share"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ShareAlbumPicker(
+    share: ClientShare,
+    albums: Vec<ShareableAlbum>,
+    filter: String,
+    on_filter_change: EventHandler<String>,
+    on_set_album_shared: EventHandler<(String, String, bool)>,
+) -> Element {
+    let needle = filter.trim().to_lowercase();
+    let matching: Vec<ShareableAlbum> = albums
+        .into_iter()
+        .filter(|a| {
+            needle.is_empty()
+                || a.title.to_lowercase().contains(&needle)
+                || a.artist.to_lowercase().contains(&needle)
+        })
+        .collect();
+    let hidden = matching.len().saturating_sub(MAX_LISTED_ALBUMS);
+
+    rsx! {
+        div { class: "space-y-2",
+            TextInput {
+                value: filter,
+                on_input: on_filter_change,
+                size: TextInputSize::Small,
+                placeholder: "Filter albums",
+            }
+            div { class: "max-h-64 overflow-y-auto space-y-1",
+                for album in matching.into_iter().take(MAX_LISTED_ALBUMS) {
+                    {
+                        let shared = share.album_ids.contains(&album.id);
+                        let share_id = share.id.clone();
+                        let album_id = album.id.clone();
+                        rsx! {
+                            label {
+                                key: "{album.id}",
+                                class: "flex items-center gap-3 py-1 text-sm cursor-pointer",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600",
+                                    checked: shared,
+                                    onchange: move |e| on_set_album_shared.call((share_id.clone(), album_id.clone(), e.checked())),
+                                }
+                                span { class: "text-white truncate", "{album.title}" }
+                                span { class: "text-gray-500 truncate", "{album.artist}" }
+                            }
+                        }
+                    }
+                }
+            }
+            if hidden > 0 {
+                p { class: "text-xs text-gray-500",
+                    "{hidden} more albums match. Narrow the filter to see them."
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! Importing section view
+
+use crate::components::{
+    Button, ButtonSize, ButtonVariant, Select, SelectOption, TextInput, TextInputSize,
+};
+use crate::display_types::ReleaseTemplate;
+use dioxus::prelude::*;
+
+/// A built-in folder-name template
+#[derive(Clone, Debug, PartialEq)]
+pub struct FolderPatternPreset {
+    pub name: String,
+    pub template: String,
+}
+
+/// What the folder patterns read from a sample path
+#[derive(Clone, Debug, PartialEq)]
+pub enum FolderPatternTest {
+    Matched {
+        template: String,
+        /// (field label, value)
+        fields: Vec<(String, String)>,
+    },
+    NoMatch,
+}
+
+/// Importing section view - folder-name templates that pre-fill release
+/// metadata, series templates for manual entry, and how hard imports may
+/// work the machine
+#[component]
+pub fn ImportingSectionView(
+    /// Templates being edited, one per line
+    edit_patterns: String,
+    presets: Vec<FolderPatternPreset>,
+    /// State flags
+    has_changes: bool,
+    save_error: Option<String>,
+    /// Folder path to try the templates on
+    sample_path: String,
+    sample_result: Option<FolderPatternTest>,
+    release_templates: Vec<ReleaseTemplate>,
+    low_power_import: bool,
+    /// Queued imports that run at once
+    max_concurrent_imports: u32,
+    /// Callbacks
+    on_patterns_change: EventHandler<String>,
+    /// Append a preset's template
+    on_add_preset: EventHandler<String>,
+    on_save: EventHandler<()>,
+    on_cancel: EventHandler<()>,
+    on_sample_path_change: EventHandler<String>,
+    /// Remove a series template by ID
+    on_remove_release_template: EventHandler<String>,
+    on_low_power_import_change: EventHandler<bool>,
+    on_max_concurrent_imports_change: EventHandler<u32>,
+) -> Element {
+    rsx! {
+        div { class: "max-w-2xl space-y-6",
+            h2 { class: "text-xl font-semibold text-white mb-6", "Importing" }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Folder Patterns" }
+                div { class: "space-y-4",
+                    p { class: "text-sm text-gray-400",
+                        "Describe how your folders are named, and releases imported from them start with the artist, album, year and more read from the path. The first pattern that fits wins, and what it reads is trusted over guesses from the folder name."
+                    }
+                    textarea {
+                        class: "w-full h-32 px-3 py-2 bg-gray-700 border border-gray-600 rounded-lg text-white font-mono text-sm focus:outline-none focus:ring-2 focus:ring-indigo-500",
+                        placeholder: "{{artist}}/{{year}} - {{album}} [{{format}}]",
+                        value: "{edit_patterns}",
+                        oninput: move |e| on_patterns_change.call(e.value()),
+                    }
+                    p { class: "text-xs text-gray-500",
+                        "One pattern per line. Placeholders: {{artist}}, {{album}}, {{year}}, {{format}}, {{label}}, {{catalog}}, and {{*}} for text to skip. Use / to read parent folders too. Copy the list to share it."
+                    }
+
+                    if let Some(error) = save_error {
+                        div { class: "p-3 bg-red-900/30 border border-red-700 rounded-lg text-sm text-red-300",
+                            "{error}"
+                        }
+                    }
+
+                    div { class: "flex gap-3",
+                        Button {
+                            variant: ButtonVariant::Primary,
+                            size: ButtonSize::Medium,
+                            disabled: !has_changes,
+                            onclick: move |_| on_save.call(()),
+                            "Save"
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            size: ButtonSize::Medium,
+                            disabled: !has_changes,
+                            onclick: move |_| on_cancel.call(()),
+                            "Cancel"
+                        }
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Presets" }
+                div { class: "space-y-2",
+                    for preset in presets {
+                        div {
+                            key: "{preset.template}",
+                            class: "flex items-center justify-between gap-4 text-sm",
+                            div {
+                                span { class: "text-white block", "{preset.name}" }
+                                span { class: "text-xs text-gray-500 font-mono", "{preset.template}" }
+                            }
+                            Button {
+                                variant: ButtonVariant::Secondary,
+                                size: ButtonSize::Small,
+                                onclick: {
+                                    let template = preset.template.clone();
+                                    move |_| on_add_preset.call(template.clone())
+                                },
+                                "Add"
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Try a Folder" }
+                div { class: "space-y-3",
+                    TextInput {
+                        value: sample_path,
+                        on_input: on_sample_path_change,
+                        size: TextInputSize::Small,
+                        placeholder: "/music/Miles Davis/1959 - Kind of Blue [FLAC]",
+                    }
+                    match sample_result {
+                        None => rsx! {},
+                        Some(FolderPatternTest::NoMatch) => rsx! {
+                            p { class: "text-sm text-gray-500", "No pattern fits this folder." }
+                        },
+                        Some(FolderPatternTest::Matched { template, fields }) => rsx! {
+                            div { class: "space-y-2 text-sm",
+                                p { class: "text-gray-400",
+                                    "Read with "
+                                    span { class: "font-mono text-indigo-400", "{template}" }
+                                }
+                                for (label , value) in fields {
+                                    div { key: "{label}", class: "flex items-center gap-2",
+                                        span { class: "text-gray-400 w-32", "{label}:" }
+                                        span { class: "text-white", "{value}" }
+                                    }
+                                }
+                            }
+                        },
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Series Templates" }
+                div { class: "space-y-3",
+                    p { class: "text-sm text-gray-400",
+                        "Save a template from the manual entry form when importing a mixtape or podcast episode, and the next one starts with its artist, cover and title, with the volume number counted up."
+                    }
+                    if release_templates.is_empty() {
+                        p { class: "text-sm text-gray-500", "No templates saved." }
+                    }
+                    for template in release_templates {
+                        div {
+                            key: "{template.id}",
+                            class: "flex items-center justify-between gap-4 text-sm",
+                            div {
+                                span { class: "text-white block", "{template.artist}" }
+                                span { class: "text-xs text-gray-500",
+                                    span { class: "font-mono", "{template.title_pattern}" }
+                                    " · next: {template.next_title}"
+                                }
+                            }
+                            Button {
+                                variant: ButtonVariant::Secondary,
+                                size: ButtonSize::Small,
+                                onclick: {
+                                    let id = template.id.clone();
+                                    move |_| on_remove_release_template.call(id.clone())
+                                },
+                                "Remove"
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Performance" }
+                label { class: "flex items-start gap-3 cursor-pointer",
+                    input {
+                        r#type: "checkbox",
+                        class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600 mt-0.5",
+                        checked: low_power_import,
+                        onchange: move |e| on_low_power_import_change.call(e.checked()),
+                    }
+                    div {
+                        span { class: "text-white block", "Low power imports" }
+                        span { class: "text-xs text-gray-500",
+                            "Store one file or rip one disc at a time, with a pause between files, so imports don't heat up fanless machines. Imports always slow down while the system is busy or hot."
+                        }
+                    }
+                }
+                div { class: "flex items-center gap-4 mt-4",
+                    label { class: "text-sm text-gray-400 w-40", "Imports at once:" }
+                    Select {
+                        value: max_concurrent_imports.to_string(),
+                        onchange: move |value: String| {
+                            if let Ok(count) = value.parse() {
+                                on_max_concurrent_imports_change.call(count);
+                            }
+                        },
+                        for count in 1..=4u32 {
+                            SelectOption {
+                                key: "{count}",
+                                value: count.to_string(),
+                                label: count.to_string(),
+                            }
+                        }
+                    }
+                }
+                p { class: "text-xs text-gray-500 mt-2",
+                    "Folder and torrent imports wait in the queue until one of these slots is free. CDs rip right away, one per drive."
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! Library section view
+
+use super::storage_profiles::StorageProfile;
+use crate::components::icons::CheckIcon;
+use crate::components::utils::{format_date_time, format_file_size};
+use crate::components::{Button, ButtonSize, ButtonVariant, Select, SelectOption};
+use crate::display_types::{
+    AudioQuality, FeaturedCreditStyle, LibrarySnapshot, LibrarySort, StartupView, TrackClickAction,
+};
+use dioxus::prelude::*;
+
+/// Snapshot schedule choices: (hours between snapshots, label)
+const SNAPSHOT_SCHEDULES: [(u32, &str); 3] = [(6, "Every 6 hours"), (24, "Daily"), (168, "Weekly")];
+
+/// How many snapshots to keep
+const SNAPSHOTS_KEPT_CHOICES: [u32; 4] = [3, 7, 14, 30];
+
+/// Library section view - what opens at launch and how the library starts out
+#[component]
+pub fn LibrarySectionView(
+    startup_view: StartupView,
+    /// Sort the library opens with
+    library_sort: LibrarySort,
+    /// Quality filter the library opens with (None = all albums)
+    quality_filter: Option<AudioQuality>,
+    on_startup_view_change: EventHandler<StartupView>,
+    on_library_sort_change: EventHandler<LibrarySort>,
+    on_quality_filter_change: EventHandler<Option<AudioQuality>>,
+    /// What clicking a track row does in album and track lists
+    track_click_action: TrackClickAction,
+    on_track_click_action_change: EventHandler<TrackClickAction>,
+    /// How featured-artist credits are normalized at import
+    featured_credit_style: FeaturedCreditStyle,
+    on_featured_credit_style_change: EventHandler<FeaturedCreditStyle>,
+    is_renormalizing_credits: bool,
+    /// Outcome of the last library-wide credit re-normalization
+    credits_result: Option<String>,
+    on_renormalize_credits: EventHandler<()>,
+    /// Cloud profiles snapshots can go to
+    snapshot_profiles: Vec<StorageProfile>,
+    /// Profile snapshots go to (None = no snapshots)
+    snapshot_profile_id: Option<String>,
+    /// Hours between scheduled snapshots (None = not scheduled)
+    snapshot_interval_hours: Option<u32>,
+    snapshots_kept: u32,
+    /// Snapshots in the bucket, newest first
+    snapshots: Vec<LibrarySnapshot>,
+    is_snapshotting: bool,
+    /// Outcome of the last snapshot
+    snapshot_result: Option<String>,
+    on_snapshot_profile_change: EventHandler<Option<String>>,
+    on_snapshot_interval_change: EventHandler<Option<u32>>,
+    on_snapshots_kept_change: EventHandler<u32>,
+    on_snapshot_now: EventHandler<()>,
+) -> Element {
+    rsx! {
+        div { class: "max-w-2xl space-y-6",
+            h2 { class: "text-xl font-semibold text-white mb-6", "Library" }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Defaults" }
+                div { class: "space-y-4",
+                    div { class: "flex items-center gap-4",
+                        label { class: "text-sm text-gray-400 w-40", "At launch, open:" }
+                        Select {
+                            value: startup_view.key(),
+                            onchange: move |key: String| {
+                                if let Some(view) = StartupView::from_key(&key) {
+                                    on_startup_view_change.call(view);
+                                }
+                            },
+                            for option in StartupView::ALL {
+                                SelectOption {
+                                    key: "{option.key()}",
+                                    value: option.key(),
+                                    label: option.label(),
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex items-center gap-4",
+                        label { class: "text-sm text-gray-400 w-40", "Sort by:" }
+                        Select {
+                            value: library_sort.key(),
+                            onchange: move |key: String| {
+                                if let Some(sort) = LibrarySort::from_key(&key) {
+                                    on_library_sort_change.call(sort);
+                                }
+                            },
+                            for option in LibrarySort::ALL {
+                                SelectOption {
+                                    key: "{option.key()}",
+                                    value: option.key(),
+                                    label: option.label(),
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex items-center gap-4",
+                        label { class: "text-sm text-gray-400 w-40", "Quality:" }
+                        Select {
+                            value: quality_filter.map_or("all", |q| q.key()),
+                            onchange: move |key: String| on_quality_filter_change.call(AudioQuality::from_key(&key)),
+                            SelectOption { value: "all", label: "All" }
+                            for quality in AudioQuality::ALL {
+                                SelectOption {
+                                    key: "{quality.key()}",
+                                    value: quality.key(),
+                                    label: quality.label(),
+                                }
+                            }
+                        }
+                    }
+                    p { class: "text-xs text-gray-500",
+                        "The library opens sorted and filtered this way. Changing them from the library itself doesn't change these defaults."
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Track Lists" }
+                div { class: "space-y-4",
+                    div { class: "flex items-center gap-4",
+                        label { class: "text-sm text-gray-400 w-40", "Clicking a track:" }
+                        Select {
+                            value: track_click_action.key(),
+                            onchange: move |key: String| {
+                                if let Some(action) = TrackClickAction::from_key(&key) {
+                                    on_track_click_action_change.call(action);
+                                }
+                            },
+                            for option in TrackClickAction::ALL {
+                                SelectOption {
+                                    key: "{option.key()}",
+                                    value: option.key(),
+                                    label: option.label(),
+                                }
+                            }
+                        }
+                    }
+                    p { class: "text-xs text-gray-500",
+                        "Applies to album track lists and the Tracks view. Cmd/Ctrl and Shift-click always select."
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                div { class: "flex items-center justify-between mb-4",
+                    h3 { class: "text-lg font-medium text-white", "Artist Credits" }
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        disabled: is_renormalizing_credits,
+                        loading: is_renormalizing_credits,
+                        onclick: move |_| on_renormalize_credits.call(()),
+                        if is_renormalizing_credits {
+                            "Updating..."
+                        } else {
+                            "Apply to library"
+                        }
+                    }
+                }
+                div { class: "space-y-4",
+                    div { class: "flex items-center gap-4",
+                        label { class: "text-sm text-gray-400 w-40", "Featured artists:" }
+                        Select {
+                            value: featured_credit_style.key(),
+                            onchange: move |key: String| {
+                                if let Some(style) = FeaturedCreditStyle::from_key(&key) {
+                                    on_featured_credit_style_change.call(style);
+                                }
+                            },
+                            for option in FeaturedCreditStyle::ALL {
+                                SelectOption {
+                                    key: "{option.key()}",
+                                    value: option.key(),
+                                    label: option.label(),
+                                }
+                            }
+                        }
+                    }
+                    p { class: "text-xs text-gray-500",
+                        "\"Artist ft. Guest\" and \"Artist (featuring Guest)\" become \"Artist feat. Guest\", or Artist and Guest as separate credits. Applies to new imports; the credit as the source spelled it is kept, so applying to the library works from the originals."
+                    }
+                    if let Some(result) = credits_result {
+                        p { class: "text-sm text-gray-400", "{result}" }
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                div { class: "flex items-center justify-between mb-4",
+                    h3 { class: "text-lg font-medium text-white", "Snapshots" }
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        disabled: is_snapshotting || snapshot_profile_id.is_none(),
+                        loading: is_snapshotting,
+                        onclick: move |_| on_snapshot_now.call(()),
+                        if is_snapshotting {
+                            "Uploading..."
+                        } else {
+                            "Snapshot now"
+                        }
+                    }
+                }
+                div { class: "space-y-4",
+                    div { class: "flex items-center gap-4",
+                        label { class: "text-sm text-gray-400 w-40", "Upload to:" }
+                        Select {
+                            value: snapshot_profile_id.clone().unwrap_or_else(|| "off".to_string()),
+                            onchange: move |key: String| {
+                                on_snapshot_profile_change.call((key != "off").then_some(key));
+                            },
+                            SelectOption { value: "off", label: "Don't snapshot" }
+                            for profile in snapshot_profiles {
+                                SelectOption {
+                                    key: "{profile.id}",
+                                    value: profile.id.clone(),
+                                    label: profile.name.clone(),
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex items-center gap-4",
+                        label { class: "text-sm text-gray-400 w-40", "Run automatically:" }
+                        Select {
+                            value: snapshot_interval_hours.map_or("off".to_string(), |h| h.to_string()),
+                            onchange: move |key: String| on_snapshot_interval_change.call(key.parse().ok()),
+                            SelectOption { value: "off", label: "Never" }
+                            for (hours , label) in SNAPSHOT_SCHEDULES {
+                                SelectOption {
+                                    key: "{hours}",
+                                    value: hours.to_string(),
+                                    label,
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex items-center gap-4",
+                        label { class: "text-sm text-gray-400 w-40", "Keep:" }
+                        Select {
+                            value: snapshots_kept.to_string(),
+                            onchange: move |key: String| {
+                                if let Ok(count) = key.parse() {
+                                    on_snapshots_kept_change.call(count);
+                                }
+                            },
+                            for count in SNAPSHOTS_KEPT_CHOICES {
+                                SelectOption {
+                                    key: "{count}",
+                                    value: count.to_string(),
+                                    label: format!("{} snapshots", count),
+                                }
+                            }
+                        }
+                    }
+                    p { class: "text-xs text-gray-500",
+                        "Copies the library database to a cloud bucket, encrypted with your library key, so it can be put back if the copy on this computer is damaged. Needs an encryption key. Older snapshots are deleted from the bucket."
+                    }
+                    if let Some(result) = snapshot_result {
+                        p { class: "text-sm text-gray-400", "{result}" }
+                    }
+                    if snapshots.is_empty() {
+                        p { class: "text-sm text-gray-500 italic", "No snapshots yet" }
+                    } else {
+                        div { class: "divide-y divide-gray-700",
+                            for snapshot in snapshots {
+                                div {
+                                    key: "{snapshot.location}",
+                                    class: "py-2 flex items-center gap-3 text-sm",
+                                    CheckIcon { class: "w-4 h-4 text-green-400" }
+                                    span { class: "text-white flex-1", {format_date_time(snapshot.created_at)} }
+                                    span { class: "text-gray-400", {format_file_size(snapshot.size_bytes as i64)} }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
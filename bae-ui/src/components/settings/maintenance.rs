@@ -0,0 +1,264 @@
+//! Maintenance section view
+
+use crate::components::icons::{AlertTriangleIcon, CheckIcon};
+use crate::components::utils::format_date;
+use crate::components::{Button, ButtonSize, ButtonVariant, Select, SelectOption};
+use crate::display_types::{VerificationIssue, VerificationIssueKind, VerificationRun};
+use dioxus::prelude::*;
+
+/// Verification schedule choices: (days between runs, label)
+const VERIFICATION_SCHEDULES: [(u32, &str); 3] =
+    [(7, "Weekly"), (30, "Monthly"), (90, "Every 3 months")];
+
+/// Maintenance section view - library verification and repairs, database and cache upkeep
+#[component]
+pub fn MaintenanceSectionView(
+    /// Days between scheduled verification runs (None = not scheduled)
+    verification_interval_days: Option<u32>,
+    /// Recent verification runs, newest first
+    verification_runs: Vec<VerificationRun>,
+    is_verifying: bool,
+    on_verification_interval_change: EventHandler<Option<u32>>,
+    on_verify_now: EventHandler<()>,
+    /// Issue being repaired, if any
+    repairing_issue_id: Option<String>,
+    /// Why the last repair failed
+    repair_error: Option<String>,
+    /// Called with an issue ID
+    on_repair_issue: EventHandler<String>,
+    is_compacting_database: bool,
+    /// Outcome of the last database compaction
+    database_result: Option<String>,
+    on_compact_database: EventHandler<()>,
+    is_clearing_cache: bool,
+    /// Outcome of the last cache clear
+    cache_result: Option<String>,
+    on_clear_cache: EventHandler<()>,
+) -> Element {
+    rsx! {
+        div { class: "max-w-2xl space-y-6",
+            h2 { class: "text-xl font-semibold text-white mb-6", "Maintenance" }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                div { class: "flex items-center justify-between mb-4",
+                    h3 { class: "text-lg font-medium text-white", "Verify Library" }
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        disabled: is_verifying,
+                        loading: is_verifying,
+                        onclick: move |_| on_verify_now.call(()),
+                        if is_verifying {
+                            "Verifying..."
+                        } else {
+                            "Verify now"
+                        }
+                    }
+                }
+                div { class: "space-y-4",
+                    div { class: "flex items-center gap-4",
+                        label { class: "text-sm text-gray-400 w-40", "Run automatically:" }
+                        Select {
+                            value: verification_interval_days.map_or("off".to_string(), |d| d.to_string()),
+                            onchange: move |key: String| on_verification_interval_change.call(key.parse().ok()),
+                            SelectOption { value: "off", label: "Never" }
+                            for (days , label) in VERIFICATION_SCHEDULES {
+                                SelectOption {
+                                    key: "{days}",
+                                    value: days.to_string(),
+                                    label,
+                                }
+                            }
+                        }
+                    }
+                    p { class: "text-xs text-gray-500",
+                        "Reads every stored file back, checking it against the checksum taken when it was stored and decrypting and decoding it, to catch missing and damaged copies. Also finds files in local release folders that aren't part of any release. Runs in the background while bae is open. Archival cloud profiles are skipped."
+                    }
+                    p { class: "text-xs text-gray-500",
+                        "Missing and corrupt files are restored from the folder they were imported from, if it's still there. Orphaned files are deleted."
+                    }
+                    if let Some(error) = repair_error {
+                        p { class: "text-sm text-red-400", "{error}" }
+                    }
+                    if verification_runs.is_empty() {
+                        p { class: "text-sm text-gray-500 italic", "No verification runs yet" }
+                    } else {
+                        div { class: "divide-y divide-gray-700",
+                            for run in verification_runs {
+                                VerificationRunRow {
+                                    key: "{run.id}",
+                                    run,
+                                    repairing_issue_id: repairing_issue_id.clone(),
+                                    on_repair_issue,
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Database and Cache" }
+                div { class: "space-y-4",
+                    div { class: "flex items-start justify-between gap-4",
+                        div { class: "space-y-1",
+                            p { class: "text-sm text-white", "Compact database" }
+                            p { class: "text-xs text-gray-500",
+                                "Reclaims space left behind by deleted albums and refreshes the statistics searches rely on."
+                            }
+                            if let Some(result) = database_result {
+                                p { class: "text-sm text-gray-400", "{result}" }
+                            }
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            size: ButtonSize::Small,
+                            disabled: is_compacting_database,
+                            loading: is_compacting_database,
+                            onclick: move |_| on_compact_database.call(()),
+                            if is_compacting_database {
+                                "Compacting..."
+                            } else {
+                                "Compact"
+                            }
+                        }
+                    }
+                    div { class: "flex items-start justify-between gap-4",
+                        div { class: "space-y-1",
+                            p { class: "text-sm text-white", "Clear cache" }
+                            p { class: "text-xs text-gray-500",
+                                "Deletes files downloaded from cloud storage for playback. They are fetched again when needed."
+                            }
+                            if let Some(result) = cache_result {
+                                p { class: "text-sm text-gray-400", "{result}" }
+                            }
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            size: ButtonSize::Small,
+                            disabled: is_clearing_cache,
+                            loading: is_clearing_cache,
+                            onclick: move |_| on_clear_cache.call(()),
+                            if is_clearing_cache {
+                                "Clearing..."
+                            } else {
+                                "Clear"
+                            }
+                        }
+                    }
+                    p { class: "text-xs text-gray-500",
+                        "Also available from the command line: bae maintenance compact-db, bae maintenance clear-cache."
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn VerificationRunRow(
+    run: VerificationRun,
+    repairing_issue_id: Option<String>,
+    on_repair_issue: EventHandler<String>,
+) -> Element {
+    let mut expanded = use_signal(|| false);
+    let issue_count = run.issues.len();
+    let open_count = run.issues.iter().filter(|issue| !issue.repaired).count();
+    let kind = if run.scheduled { "Scheduled" } else { "Manual" };
+
+    rsx! {
+        div { class: "py-2 text-sm",
+            div {
+                class: "flex items-center gap-3",
+                class: if issue_count > 0 { "cursor-pointer" },
+                onclick: move |_| {
+                    if issue_count > 0 {
+                        expanded.toggle();
+                    }
+                },
+                if open_count > 0 {
+                    AlertTriangleIcon { class: "w-4 h-4 text-red-400" }
+                } else {
+                    CheckIcon { class: "w-4 h-4 text-green-400" }
+                }
+                span { class: "text-white w-28", {format_date(run.started_at)} }
+                span { class: "text-gray-500 w-20", "{kind}" }
+                span { class: "text-gray-400 flex-1",
+                    if !run.finished {
+                        "Interrupted after {run.files_checked} files"
+                    } else {
+                        "{run.files_checked} files checked"
+                    }
+                }
+                if open_count > 0 {
+                    span { class: "text-red-400", "{open_count} to repair" }
+                } else if issue_count > 0 {
+                    span { class: "text-gray-500", "All repaired" }
+                }
+            }
+            if *expanded.read() {
+                div { class: "mt-2 ml-7 space-y-3",
+                    for kind in VerificationIssueKind::ALL {
+                        if run.issues.iter().any(|issue| issue.kind == kind) {
+                            div { key: "{kind.label()}", class: "space-y-2",
+                                div { class: "text-xs font-medium text-gray-400 uppercase tracking-wide",
+                                    "{kind.label()}"
+                                }
+                                for issue in run.issues.iter().filter(|issue| issue.kind == kind) {
+                                    VerificationIssueRow {
+                                        key: "{issue.id}",
+                                        issue: issue.clone(),
+                                        is_repairing: repairing_issue_id.as_deref() == Some(issue.id.as_str()),
+                                        repair_disabled: repairing_issue_id.is_some(),
+                                        on_repair_issue,
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn VerificationIssueRow(
+    issue: VerificationIssue,
+    is_repairing: bool,
+    /// Another repair is in progress
+    repair_disabled: bool,
+    on_repair_issue: EventHandler<String>,
+) -> Element {
+    let issue_id = issue.id.clone();
+
+    rsx! {
+        div { class: "flex items-start gap-3",
+            div { class: "flex-1 min-w-0 space-y-0.5",
+                div { class: "flex items-center gap-2",
+                    span {
+                        class: "text-gray-200 truncate",
+                        title: "{issue.filename}",
+                        "{issue.filename}"
+                    }
+                    span { class: "text-xs text-gray-500 shrink-0", "on {issue.storage_profile_name}" }
+                }
+                div { class: "text-xs text-gray-500",
+                    "{issue.error} · first seen {format_date(issue.first_seen)}"
+                }
+            }
+            if issue.repaired {
+                span { class: "text-xs text-green-400 shrink-0", "Repaired" }
+            } else {
+                Button {
+                    variant: ButtonVariant::Secondary,
+                    size: ButtonSize::Small,
+                    disabled: repair_disabled,
+                    loading: is_repairing,
+                    onclick: move |_| on_repair_issue.call(issue_id.clone()),
+                    "{issue.kind.repair_label()}"
+                }
+            }
+        }
+    }
+}
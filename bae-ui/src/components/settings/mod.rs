@@ -5,7 +5,17 @@
 mod about;
 mod api_keys;
 mod bittorrent;
+mod cd_ripping;
+mod client_shares;
 mod encryption;
+mod importing;
+mod library;
+mod maintenance;
+mod network;
+mod playback;
+mod remote;
+mod scrobbling;
+mod seeding;
 mod storage_profiles;
 mod subsonic;
 mod view;
@@ -13,9 +23,20 @@ mod view;
 pub use about::AboutSectionView;
 pub use api_keys::ApiKeysSectionView;
 pub use bittorrent::{BitTorrentSectionView, BitTorrentSettings};
+pub use cd_ripping::CdRippingSectionView;
+pub use client_shares::{ClientShare, ClientSharesView, ShareableAlbum};
 pub use encryption::EncryptionSectionView;
+pub use importing::{FolderPatternPreset, FolderPatternTest, ImportingSectionView};
+pub use library::LibrarySectionView;
+pub use maintenance::MaintenanceSectionView;
+pub use network::NetworkSectionView;
+pub use playback::PlaybackSectionView;
+pub use remote::RemoteSectionView;
+pub use scrobbling::ScrobblingSectionView;
+pub use seeding::SeedingSectionView;
 pub use storage_profiles::{
-    StorageLocation, StorageProfile, StorageProfileEditorView, StorageProfilesSectionView,
+    CloudProvider, FilenamePolicy, PlacementPolicy, QuotaLevel, StorageLocation, StorageProfile,
+    StorageProfileEditorView, StorageProfilesSectionView,
 };
 pub use subsonic::SubsonicSectionView;
 pub use view::{SettingsTab, SettingsView};
@@ -0,0 +1,85 @@
+//! Network section view
+
+use dioxus::prelude::*;
+
+/// Network section view - metered mode and what keeps running under it
+#[component]
+pub fn NetworkSectionView(
+    /// Whether metered mode is on
+    metered_mode: bool,
+    /// Keep preloading cloud tracks in metered mode
+    allow_cloud_prefetch: bool,
+    /// Keep torrent traffic running in metered mode
+    allow_torrents: bool,
+    on_metered_mode_change: EventHandler<bool>,
+    on_allow_cloud_prefetch_change: EventHandler<bool>,
+    on_allow_torrents_change: EventHandler<bool>,
+) -> Element {
+    let override_class = if metered_mode {
+        "space-y-4 pl-7"
+    } else {
+        "space-y-4 pl-7 opacity-50"
+    };
+
+    rsx! {
+        div { class: "max-w-2xl space-y-6",
+            h2 { class: "text-xl font-semibold text-white mb-6", "Network" }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Metered Connection" }
+                div { class: "space-y-4",
+                    label { class: "flex items-start gap-3 cursor-pointer",
+                        input {
+                            r#type: "checkbox",
+                            class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600 mt-0.5",
+                            checked: metered_mode,
+                            onchange: move |e| on_metered_mode_change.call(e.checked()),
+                        }
+                        div {
+                            span { class: "text-white block", "Metered mode" }
+                            span { class: "text-xs text-gray-500",
+                                "Hold back background traffic, for tethering or capped connections. Playing a track still streams it."
+                            }
+                        }
+                    }
+
+                    div { class: override_class,
+                        p { class: "text-sm text-gray-400", "While metered, still allow:" }
+                        label { class: "flex items-start gap-3 cursor-pointer",
+                            input {
+                                r#type: "checkbox",
+                                class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600 mt-0.5",
+                                checked: allow_cloud_prefetch,
+                                disabled: !metered_mode,
+                                onchange: move |e| on_allow_cloud_prefetch_change.call(e.checked()),
+                            }
+                            div {
+                                span { class: "text-white block", "Cloud prefetch" }
+                                span { class: "text-xs text-gray-500",
+                                    "Download the next track from cloud storage before the current one ends, for gapless playback."
+                                }
+                            }
+                        }
+                        if cfg!(feature = "torrent") {
+                            label { class: "flex items-start gap-3 cursor-pointer",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600 mt-0.5",
+                                    checked: allow_torrents,
+                                    disabled: !metered_mode,
+                                    onchange: move |e| on_allow_torrents_change.call(e.checked()),
+                                }
+                                div {
+                                    span { class: "text-white block", "Torrents" }
+                                    span { class: "text-xs text-gray-500",
+                                        "Torrent downloads and seeding. When off, torrent imports wait until metered mode is turned off."
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
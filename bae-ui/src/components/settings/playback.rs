@@ -0,0 +1,331 @@
+//! Playback section view
+
+use crate::components::{Button, ButtonSize, ButtonVariant, Select, SelectOption};
+use crate::display_types::{Dither, OutputBitDepth};
+use dioxus::prelude::*;
+
+/// Choices for how long silence plays before it's skipped
+const MIN_SILENCE_OPTIONS_MS: [u32; 5] = [1000, 2000, 5000, 10_000, 30_000];
+
+/// Playback section view - output format and advanced decoder buffering
+#[component]
+pub fn PlaybackSectionView(
+    /// Sample format requested from the output device
+    output_bit_depth: OutputBitDepth,
+    /// Dither used when the output is 16-bit
+    dither: Dither,
+    /// Output devices on this machine
+    output_devices: Vec<String>,
+    /// Device chosen to play through. None follows the system default.
+    output_device: Option<String>,
+    /// Devices held exclusively while playing
+    exclusive_devices: Vec<String>,
+    /// Whether the platform supports exclusive mode
+    exclusive_supported: bool,
+    /// Whether long silences are skipped
+    skip_silence: bool,
+    /// How long silence plays before it's skipped, in ms
+    min_silence_ms: u32,
+    /// Whether closing the window keeps playback going from the tray
+    in_background: bool,
+    /// How far the decoder runs ahead of playback, in ms
+    decode_ahead_ms: u32,
+    /// How much of the next track is pre-rolled for gapless playback, in ms
+    gapless_preroll_ms: u32,
+    /// Allowed range for both values, in ms
+    min_ms: u32,
+    max_ms: u32,
+    /// Whether currently in edit mode
+    is_editing: bool,
+    /// Temporary values while editing
+    edit_decode_ahead_ms: String,
+    edit_gapless_preroll_ms: String,
+    /// State flags
+    is_saving: bool,
+    has_changes: bool,
+    save_error: Option<String>,
+    /// Callbacks
+    on_edit_start: EventHandler<()>,
+    on_cancel: EventHandler<()>,
+    on_save: EventHandler<()>,
+    on_decode_ahead_change: EventHandler<String>,
+    on_gapless_preroll_change: EventHandler<String>,
+    /// Device name, or None for the system default
+    on_output_device_change: EventHandler<Option<String>>,
+    on_output_bit_depth_change: EventHandler<OutputBitDepth>,
+    on_dither_change: EventHandler<Dither>,
+    /// Device name and whether it should be held exclusively
+    on_exclusive_change: EventHandler<(String, bool)>,
+    on_skip_silence_change: EventHandler<bool>,
+    on_min_silence_change: EventHandler<u32>,
+    on_in_background_change: EventHandler<bool>,
+) -> Element {
+    rsx! {
+        div { class: "max-w-2xl space-y-6",
+            h2 { class: "text-xl font-semibold text-white mb-6", "Playback" }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Output" }
+                div { class: "space-y-4",
+                    div { class: "flex items-center gap-4",
+                        label { class: "text-sm text-gray-400 w-40", "Device:" }
+                        Select {
+                            value: output_device.clone().unwrap_or_default(),
+                            onchange: move |name: String| {
+                                on_output_device_change.call(Some(name).filter(|name| !name.is_empty()));
+                            },
+                            SelectOption { value: "", label: "System default" }
+                            if let Some(device) = output_device.as_ref().filter(|d| !output_devices.contains(*d)) {
+                                SelectOption {
+                                    value: device.clone(),
+                                    label: format!("{} (not connected)", device),
+                                }
+                            }
+                            for device in output_devices.iter() {
+                                SelectOption {
+                                    key: "{device}",
+                                    value: device.clone(),
+                                    label: device.clone(),
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex items-center gap-4",
+                        label { class: "text-sm text-gray-400 w-40", "Bit depth:" }
+                        Select {
+                            value: output_bit_depth.key(),
+                            onchange: move |key: String| {
+                                if let Some(depth) = OutputBitDepth::from_key(&key) {
+                                    on_output_bit_depth_change.call(depth);
+                                }
+                            },
+                            for depth in OutputBitDepth::ALL {
+                                SelectOption {
+                                    key: "{depth.key()}",
+                                    value: depth.key(),
+                                    label: depth.label(),
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex items-center gap-4",
+                        label { class: "text-sm text-gray-400 w-40", "Dither:" }
+                        Select {
+                            value: dither.key(),
+                            onchange: move |key: String| {
+                                if let Some(dither) = Dither::from_key(&key) {
+                                    on_dither_change.call(dither);
+                                }
+                            },
+                            for option in Dither::ALL {
+                                SelectOption {
+                                    key: "{option.key()}",
+                                    value: option.key(),
+                                    label: option.label(),
+                                }
+                            }
+                        }
+                    }
+                    p { class: "text-xs text-gray-500",
+                        "Switching devices carries on from the same spot. If the chosen device is disconnected, "
+                        "playback moves to the system default and comes back when it's reconnected."
+                    }
+                    p { class: "text-xs text-gray-500",
+                        "Playback runs in 32-bit float. Dither is only applied when the device takes 16-bit samples; "
+                        "if it doesn't support the chosen depth, its default format is used."
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Exclusive Mode" }
+                if exclusive_supported {
+                    div { class: "space-y-3",
+                        for device in output_devices {
+                            label {
+                                key: "{device}",
+                                class: "flex items-center gap-3 cursor-pointer",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600",
+                                    checked: exclusive_devices.contains(&device),
+                                    onchange: {
+                                        let device = device.clone();
+                                        move |e: FormEvent| on_exclusive_change.call((device.clone(), e.checked()))
+                                    },
+                                }
+                                span { class: "text-white", "{device}" }
+                            }
+                        }
+                        p { class: "text-xs text-gray-500",
+                            "While playing, bae holds checked devices so no other app can use them, and switches them to each track's sample rate. "
+                            "With volume at 100% the output is bit-perfect. Devices are released when playback stops."
+                        }
+                    }
+                } else {
+                    p { class: "text-sm text-gray-400", "Exclusive mode is only available on macOS." }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Skip Silence" }
+                div { class: "space-y-4",
+                    label { class: "flex items-start gap-3 cursor-pointer",
+                        input {
+                            r#type: "checkbox",
+                            class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600 mt-0.5",
+                            checked: skip_silence,
+                            onchange: move |e| on_skip_silence_change.call(e.checked()),
+                        }
+                        div {
+                            span { class: "text-white block", "Skip long silences" }
+                            span { class: "text-xs text-gray-500",
+                                "Cuts silent intros, outros and gaps, like the dead air between sets on live recordings."
+                            }
+                        }
+                    }
+                    if skip_silence {
+                        div { class: "flex items-center gap-4",
+                            label { class: "text-sm text-gray-400 w-40", "Skip after:" }
+                            Select {
+                                value: min_silence_ms.to_string(),
+                                onchange: move |value: String| {
+                                    if let Ok(ms) = value.parse::<u32>() {
+                                        on_min_silence_change.call(ms);
+                                    }
+                                },
+                                for ms in MIN_SILENCE_OPTIONS_MS {
+                                    SelectOption {
+                                        key: "{ms}",
+                                        value: ms.to_string(),
+                                        label: format!("{} s of silence", ms / 1000),
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "Background" }
+                label { class: "flex items-start gap-3 cursor-pointer",
+                    input {
+                        r#type: "checkbox",
+                        class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600 mt-0.5",
+                        checked: in_background,
+                        onchange: move |e| on_in_background_change.call(e.checked()),
+                    }
+                    div {
+                        span { class: "text-white block", "Keep playing when the window is closed" }
+                        span { class: "text-xs text-gray-500",
+                            "bae stays in the system tray (the menu bar on macOS), where you can control playback "
+                            "and open the window again. Quit from the tray menu."
+                        }
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                div { class: "flex items-center justify-between mb-4",
+                    h3 { class: "text-lg font-medium text-white", "Advanced Buffering" }
+                    if !is_editing {
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            size: ButtonSize::Small,
+                            onclick: move |_| on_edit_start.call(()),
+                            "Edit"
+                        }
+                    }
+                }
+
+                if is_editing {
+                    div { class: "space-y-4",
+                        div { class: "flex items-center gap-4",
+                            label { class: "text-sm text-gray-400 w-40", "Decode ahead (ms):" }
+                            input {
+                                r#type: "number",
+                                class: "w-28 px-3 py-2 bg-gray-700 border border-gray-600 rounded-lg text-white focus:outline-none focus:ring-2 focus:ring-indigo-500",
+                                min: "{min_ms}",
+                                max: "{max_ms}",
+                                step: "50",
+                                value: "{edit_decode_ahead_ms}",
+                                oninput: move |e| on_decode_ahead_change.call(e.value()),
+                            }
+                        }
+                        div { class: "flex items-center gap-4",
+                            label { class: "text-sm text-gray-400 w-40", "Gapless pre-roll (ms):" }
+                            input {
+                                r#type: "number",
+                                class: "w-28 px-3 py-2 bg-gray-700 border border-gray-600 rounded-lg text-white focus:outline-none focus:ring-2 focus:ring-indigo-500",
+                                min: "{min_ms}",
+                                max: "{max_ms}",
+                                step: "50",
+                                value: "{edit_gapless_preroll_ms}",
+                                oninput: move |e| on_gapless_preroll_change.call(e.value()),
+                            }
+                        }
+                        p { class: "text-xs text-gray-500",
+                            "Values are kept between {min_ms} and {max_ms} ms."
+                        }
+                    }
+                } else {
+                    div { class: "space-y-2 text-sm",
+                        div { class: "flex items-center gap-2",
+                            span { class: "text-gray-400", "Decode ahead:" }
+                            span { class: "text-white font-mono", "{decode_ahead_ms} ms" }
+                        }
+                        div { class: "flex items-center gap-2",
+                            span { class: "text-gray-400", "Gapless pre-roll:" }
+                            span { class: "text-white font-mono", "{gapless_preroll_ms} ms" }
+                        }
+                    }
+                }
+            }
+
+            if is_editing {
+                if let Some(error) = save_error {
+                    div { class: "p-3 bg-red-900/30 border border-red-700 rounded-lg text-sm text-red-300",
+                        "{error}"
+                    }
+                }
+
+                div { class: "flex gap-3",
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        size: ButtonSize::Medium,
+                        disabled: !has_changes || is_saving,
+                        loading: is_saving,
+                        onclick: move |_| on_save.call(()),
+                        if is_saving {
+                            "Saving..."
+                        } else {
+                            "Save Changes"
+                        }
+                    }
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Medium,
+                        onclick: move |_| on_cancel.call(()),
+                        "Cancel"
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                h3 { class: "text-lg font-medium text-white mb-4", "About Buffering" }
+                div { class: "space-y-3 text-sm text-gray-400",
+                    p {
+                        "Decode ahead is how much audio is decoded before it is played. "
+                        "Larger values ride out slow cloud or network sources; smaller values start playback sooner."
+                    }
+                    p {
+                        "Gapless pre-roll is how much of the next track is decoded before the current one ends. "
+                        "Raise it if transitions stutter on high-latency storage."
+                    }
+                    p { "Changes to bit depth, dither, skip silence and buffering apply from the next track or seek." }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Scrobbling section view
+
+use crate::components::{Button, ButtonSize, ButtonVariant};
+use dioxus::prelude::*;
+
+const SECRET_INPUT_CLASS: &str = "w-full px-4 py-2 bg-gray-700 border border-gray-600 rounded-lg text-white placeholder-gray-500 focus:outline-none focus:ring-2 focus:ring-indigo-500 focus:border-transparent";
+
+/// Scrobbling section view - connect Last.fm and ListenBrainz accounts
+#[component]
+pub fn ScrobblingSectionView(
+    /// Connected Last.fm user, if any
+    lastfm_username: Option<String>,
+    lastfm_api_key: String,
+    lastfm_api_secret: String,
+    /// The browser was opened for the user to approve bae on Last.fm
+    lastfm_awaiting_approval: bool,
+    listenbrainz_connected: bool,
+    listenbrainz_token: String,
+    /// Scrobbles not yet submitted, e.g. while offline
+    pending_scrobbles: i64,
+    /// A connect request is in flight
+    is_busy: bool,
+    error: Option<String>,
+    on_lastfm_api_key_change: EventHandler<String>,
+    on_lastfm_api_secret_change: EventHandler<String>,
+    /// Get a token and open the Last.fm approval page
+    on_lastfm_connect: EventHandler<()>,
+    /// Finish connecting once the user approved bae
+    on_lastfm_finish: EventHandler<()>,
+    on_lastfm_disconnect: EventHandler<()>,
+    on_listenbrainz_token_change: EventHandler<String>,
+    on_listenbrainz_connect: EventHandler<()>,
+    on_listenbrainz_disconnect: EventHandler<()>,
+) -> Element {
+    rsx! {
+        div { class: "max-w-2xl space-y-6",
+            h2 { class: "text-xl font-semibold text-white mb-6", "Scrobbling" }
+            p { class: "text-sm text-gray-400",
+                "Tracks count as listened once they've played for half their length or four minutes. Listens made while offline are kept and sent later."
+            }
+
+            if let Some(error) = error {
+                div { class: "p-3 bg-red-900/30 border border-red-700 rounded-lg text-sm text-red-300",
+                    "{error}"
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                div { class: "flex items-center justify-between mb-4",
+                    h3 { class: "text-lg font-medium text-white", "Last.fm" }
+                    if let Some(username) = &lastfm_username {
+                        span { class: "px-3 py-1 bg-green-900 text-green-300 rounded-full text-sm",
+                            "Connected as {username}"
+                        }
+                    }
+                }
+                if lastfm_username.is_some() {
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        onclick: move |_| on_lastfm_disconnect.call(()),
+                        "Disconnect"
+                    }
+                } else if lastfm_awaiting_approval {
+                    div { class: "space-y-4",
+                        p { class: "text-sm text-gray-400",
+                            "Allow access on the Last.fm page that opened in your browser, then come back here."
+                        }
+                        Button {
+                            variant: ButtonVariant::Primary,
+                            size: ButtonSize::Medium,
+                            disabled: is_busy,
+                            loading: is_busy,
+                            onclick: move |_| on_lastfm_finish.call(()),
+                            "Finish Connecting"
+                        }
+                    }
+                } else {
+                    div { class: "space-y-4",
+                        div {
+                            label { class: "block text-sm font-medium text-gray-400 mb-2",
+                                "API Key"
+                            }
+                            input {
+                                r#type: "password",
+                                class: SECRET_INPUT_CLASS,
+                                placeholder: "Enter your Last.fm API key",
+                                value: "{lastfm_api_key}",
+                                oninput: move |e| on_lastfm_api_key_change.call(e.value()),
+                            }
+                        }
+                        div {
+                            label { class: "block text-sm font-medium text-gray-400 mb-2",
+                                "Shared Secret"
+                            }
+                            input {
+                                r#type: "password",
+                                class: SECRET_INPUT_CLASS,
+                                placeholder: "Enter the API account's shared secret",
+                                value: "{lastfm_api_secret}",
+                                oninput: move |e| on_lastfm_api_secret_change.call(e.value()),
+                            }
+                        }
+                        Button {
+                            variant: ButtonVariant::Primary,
+                            size: ButtonSize::Medium,
+                            disabled: is_busy || lastfm_api_key.is_empty() || lastfm_api_secret.is_empty(),
+                            loading: is_busy,
+                            onclick: move |_| on_lastfm_connect.call(()),
+                            "Connect"
+                        }
+                        div { class: "p-4 bg-gray-700/50 rounded-lg",
+                            p { class: "text-sm text-gray-400",
+                                "Create an API account at "
+                                a {
+                                    class: "text-indigo-400 hover:text-indigo-300",
+                                    href: "https://www.last.fm/api/account/create",
+                                    target: "_blank",
+                                    "last.fm/api/account/create"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "bg-gray-800 rounded-lg p-6",
+                div { class: "flex items-center justify-between mb-4",
+                    h3 { class: "text-lg font-medium text-white", "ListenBrainz" }
+                    if listenbrainz_connected {
+                        span { class: "px-3 py-1 bg-green-900 text-green-300 rounded-full text-sm",
+                            "Connected"
+                        }
+                    }
+                }
+                if listenbrainz_connected {
+                    Button {
+                        variant: ButtonVariant::Secondary,
+                        size: ButtonSize::Small,
+                        onclick: move |_| on_listenbrainz_disconnect.call(()),
+                        "Disconnect"
+                    }
+                } else {
+                    div { class: "space-y-4",
+                        div {
+                            label { class: "block text-sm font-medium text-gray-400 mb-2",
+                                "User Token"
+                            }
+                            input {
+                                r#type: "password",
+                                class: SECRET_INPUT_CLASS,
+                                placeholder: "Enter your ListenBrainz user token",
+                                value: "{listenbrainz_token}",
+                                oninput: move |e| on_listenbrainz_token_change.call(e.value()),
+                            }
+                        }
+                        Button {
+                            variant: ButtonVariant::Primary,
+                            size: ButtonSize::Medium,
+                            disabled: is_busy || listenbrainz_token.is_empty(),
+                            loading: is_busy,
+                            onclick: move |_| on_listenbrainz_connect.call(()),
+                            "Connect"
+                        }
+                        div { class: "p-4 bg-gray-700/50 rounded-lg",
+                            p { class: "text-sm text-gray-400",
+                                "Find your token at "
+                                a {
+                                    class: "text-indigo-400 hover:text-indigo-300",
+                                    href: "https://listenbrainz.org/settings/",
+                                    target: "_blank",
+                                    "listenbrainz.org/settings"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if pending_scrobbles > 0 {
+                p { class: "text-sm text-gray-400",
+                    "{pending_scrobbles} scrobbles waiting to be sent"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! Seeding section view
+
+use crate::components::icons::{PauseIcon, PlayIcon, TrashIcon};
+use crate::components::utils::format_file_size;
+use crate::components::{
+    Button, ButtonSize, ButtonVariant, ChromelessButton, TextInput, TextInputSize,
+};
+use crate::display_types::SeedingTorrent;
+use dioxus::prelude::*;
+
+/// Seeding section view - torrents kept for seeding, with their ratio and
+/// seeding goals
+#[component]
+pub fn SeedingSectionView(
+    torrents: Vec<SeedingTorrent>,
+    /// Why the last change failed
+    error: Option<String>,
+    on_pause: EventHandler<String>,
+    on_resume: EventHandler<String>,
+    /// Stop seeding and delete the kept download
+    on_remove: EventHandler<String>,
+    /// Release ID, ratio goal, seed time goal in hours
+    on_goals_change: EventHandler<(String, Option<f64>, Option<u32>)>,
+) -> Element {
+    let seeding_count = torrents.iter().filter(|t| t.is_seeding).count();
+
+    rsx! {
+        div { class: "max-w-3xl space-y-6",
+            h2 { class: "text-xl font-semibold text-white mb-6", "Seeding" }
+
+            p { class: "text-sm text-gray-400",
+                "Torrent imports set to seed keep their original download and seed it from there. Upload and seeding time add up across restarts. A torrent stops seeding once it meets either goal."
+            }
+
+            if let Some(error) = error {
+                p { class: "text-sm text-red-400", "{error}" }
+            }
+
+            if torrents.is_empty() {
+                div { class: "bg-gray-800 rounded-lg p-6 text-center text-gray-400",
+                    "Nothing is kept for seeding. Choose to seed after download when importing a torrent."
+                }
+            } else {
+                p { class: "text-sm text-gray-500", "{seeding_count} of {torrents.len()} seeding" }
+                div { class: "space-y-3",
+                    for torrent in torrents {
+                        SeedingRow {
+                            key: "{torrent.release_id}",
+                            torrent: torrent.clone(),
+                            on_pause,
+                            on_resume,
+                            on_remove,
+                            on_goals_change,
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SeedingRow(
+    torrent: SeedingTorrent,
+    on_pause: EventHandler<String>,
+    on_resume: EventHandler<String>,
+    on_remove: EventHandler<String>,
+    on_goals_change: EventHandler<(String, Option<f64>, Option<u32>)>,
+) -> Element {
+    let mut show_remove_confirm = use_signal(|| false);
+    let original_ratio = torrent
+        .ratio_goal
+        .map(|r| r.to_string())
+        .unwrap_or_default();
+    let original_hours = torrent
+        .time_goal_hours
+        .map(|h| h.to_string())
+        .unwrap_or_default();
+    let initial_ratio = original_ratio.clone();
+    let initial_hours = original_hours.clone();
+    let mut ratio_goal = use_signal(move || initial_ratio.clone());
+    let mut hours_goal = use_signal(move || initial_hours.clone());
+    let mut goal_error = use_signal(|| Option::<String>::None);
+    let goals_changed =
+        *ratio_goal.read() != original_ratio || *hours_goal.read() != original_hours;
+
+    let status = if torrent.is_seeding {
+        "Seeding"
+    } else if torrent.goal_met {
+        "Goal met"
+    } else {
+        "Paused"
+    };
+    let status_class = if torrent.is_seeding {
+        "bg-green-900 text-green-300"
+    } else {
+        "bg-gray-700 text-gray-300"
+    };
+    let resume_title = if torrent.goal_met {
+        "Raise or clear the goal to seed again"
+    } else {
+        "Resume"
+    };
+    let uploaded = format_file_size(torrent.uploaded_bytes);
+    let size = format_file_size(torrent.size_bytes);
+    let seeded = format_seed_time(torrent.seeding_secs);
+    let ratio = format!("{:.2}", torrent.ratio);
+
+    let save_goals = {
+        let release_id = torrent.release_id.clone();
+        move |_| {
+            let ratio = ratio_goal.read().trim().to_string();
+            let hours = hours_goal.read().trim().to_string();
+            let ratio = if ratio.is_empty() {
+                None
+            } else {
+                match ratio.parse::<f64>() {
+                    Ok(r) if r > 0.0 => Some(r),
+                    _ => {
+                        goal_error.set(Some("Ratio goal must be a number above 0".to_string()));
+                        return;
+                    }
+                }
+            };
+            let hours = if hours.is_empty() {
+                None
+            } else {
+                match hours.parse::<u32>() {
+                    Ok(h) if h > 0 => Some(h),
+                    _ => {
+                        goal_error.set(Some(
+                            "Seed time goal must be a whole number of hours".to_string(),
+                        ));
+                        return;
+                    }
+                }
+            };
+            goal_error.set(None);
+            on_goals_change.call((release_id.clone(), ratio, hours));
+        }
+    };
+
+    rsx! {
+        div { class: "bg-gray-800 rounded-lg p-4",
+            div { class: "flex items-start justify-between gap-4",
+                div { class: "flex-1 min-w-0",
+                    div { class: "flex items-center gap-3",
+                        h3 { class: "font-medium text-white truncate", "{torrent.name}" }
+                        span { class: "px-2 py-0.5 rounded text-xs flex-shrink-0 {status_class}",
+                            "{status}"
+                        }
+                    }
+                    div { class: "flex flex-wrap gap-x-4 gap-y-1 mt-2 text-sm text-gray-400",
+                        span { "Ratio {ratio}" }
+                        span { "Uploaded {uploaded} of {size}" }
+                        span { "Seeded {seeded}" }
+                    }
+                }
+                div { class: "flex items-center gap-2",
+                    if torrent.is_seeding {
+                        ChromelessButton {
+                            class: Some(
+                                "p-2 text-gray-400 hover:text-white hover:bg-gray-700 rounded-lg transition-colors"
+                                    .to_string(),
+                            ),
+                            title: Some("Pause".to_string()),
+                            aria_label: Some("Pause".to_string()),
+                            onclick: {
+                                let release_id = torrent.release_id.clone();
+                                move |_| on_pause.call(release_id.clone())
+                            },
+                            PauseIcon { class: "w-5 h-5" }
+                        }
+                    } else {
+                        ChromelessButton {
+                            class: Some(
+                                "p-2 text-gray-400 hover:text-white hover:bg-gray-700 rounded-lg transition-colors disabled:opacity-40 disabled:cursor-not-allowed"
+                                    .to_string(),
+                            ),
+                            // Would stop again straight away
+                            disabled: torrent.goal_met,
+                            title: Some(resume_title.to_string()),
+                            aria_label: Some("Resume".to_string()),
+                            onclick: {
+                                let release_id = torrent.release_id.clone();
+                                move |_| on_resume.call(release_id.clone())
+                            },
+                            PlayIcon { class: "w-5 h-5" }
+                        }
+                    }
+                    ChromelessButton {
+                        class: Some(
+                            "p-2 text-gray-400 hover:text-red-400 hover:bg-gray-700 rounded-lg transition-colors"
+                                .to_string(),
+                        ),
+                        title: Some("Remove".to_string()),
+                        aria_label: Some("Remove".to_string()),
+                        onclick: move |_| show_remove_confirm.set(true),
+                        TrashIcon { class: "w-5 h-5" }
+                    }
+                }
+            }
+
+            div { class: "flex items-center gap-3 mt-4 text-sm",
+                label { class: "text-gray-400", "Ratio goal" }
+                div { class: "w-20",
+                    TextInput {
+                        value: ratio_goal(),
+                        on_input: move |value| ratio_goal.set(value),
+                        size: TextInputSize::Small,
+                        placeholder: "None",
+                    }
+                }
+                label { class: "text-gray-400 ml-2", "Seed time goal (hours)" }
+                div { class: "w-20",
+                    TextInput {
+                        value: hours_goal(),
+                        on_input: move |value| hours_goal.set(value),
+                        size: TextInputSize::Small,
+                        placeholder: "None",
+                    }
+                }
+                if goals_changed {
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        size: ButtonSize::Small,
+                        onclick: save_goals,
+                        "Save"
+                    }
+                }
+            }
+            if let Some(error) = goal_error() {
+                p { class: "text-sm text-red-400 mt-2", "{error}" }
+            }
+
+            if *show_remove_confirm.read() {
+                div { class: "mt-4 p-3 bg-red-900/30 border border-red-700 rounded-lg",
+                    p { class: "text-sm text-red-300 mb-3",
+                        "Stop seeding and delete the kept download? The album stays in your library."
+                    }
+                    div { class: "flex gap-2",
+                        Button {
+                            variant: ButtonVariant::Danger,
+                            size: ButtonSize::Small,
+                            onclick: {
+                                let release_id = torrent.release_id.clone();
+                                move |_| {
+                                    on_remove.call(release_id.clone());
+                                    show_remove_confirm.set(false);
+                                }
+                            },
+                            "Remove"
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            size: ButtonSize::Small,
+                            onclick: move |_| show_remove_confirm.set(false),
+                            "Cancel"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Seeding time as e.g. "3d 4h" or "45m"
+fn format_seed_time(secs: i64) -> String {
+    let minutes = secs / 60;
+    let hours = minutes / 60;
+    let days = hours / 24;
+    if days > 0 {
+        format!("{}d {}h", days, hours % 24)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
@@ -4,8 +4,10 @@
 //! Accepts `ReadSignal` props and reads at leaf level for granular reactivity.
 
 use crate::components::icons::{CheckIcon, PencilIcon, PlusIcon, TrashIcon};
+use crate::components::utils::format_file_size;
 use crate::components::{
-    Button, ButtonSize, ButtonVariant, ChromelessButton, TextInput, TextInputSize,
+    Button, ButtonSize, ButtonVariant, ChromelessButton, Select, SelectOption, TextInput,
+    TextInputSize,
 };
 use dioxus::prelude::*;
 
@@ -15,6 +17,8 @@ pub enum StorageLocation {
     #[default]
     Local,
     Cloud,
+    /// Existing folder indexed in place, never written to
+    Reference,
 }
 
 impl StorageLocation {
@@ -22,10 +26,119 @@ impl StorageLocation {
         match self {
             StorageLocation::Local => "Local",
             StorageLocation::Cloud => "Cloud",
+            StorageLocation::Reference => "Reference",
         }
     }
 }
 
+/// API used to talk to a cloud bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CloudProvider {
+    #[default]
+    S3,
+    B2,
+}
+
+impl CloudProvider {
+    pub const ALL: [CloudProvider; 2] = [CloudProvider::S3, CloudProvider::B2];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            CloudProvider::S3 => "s3",
+            CloudProvider::B2 => "b2",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CloudProvider::S3 => "S3 or S3-compatible",
+            CloudProvider::B2 => "Backblaze B2",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<CloudProvider> {
+        CloudProvider::ALL.into_iter().find(|p| p.key() == key)
+    }
+}
+
+/// How illegal characters in stored filenames are rewritten
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilenamePolicy {
+    Strip,
+    #[default]
+    Replace,
+    Transliterate,
+}
+
+impl FilenamePolicy {
+    pub const ALL: [FilenamePolicy; 3] = [
+        FilenamePolicy::Replace,
+        FilenamePolicy::Strip,
+        FilenamePolicy::Transliterate,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            FilenamePolicy::Strip => "strip",
+            FilenamePolicy::Replace => "replace",
+            FilenamePolicy::Transliterate => "transliterate",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FilenamePolicy::Strip => "Remove illegal characters",
+            FilenamePolicy::Replace => "Replace illegal characters with _",
+            FilenamePolicy::Transliterate => "Replace illegal characters with lookalikes",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<FilenamePolicy> {
+        FilenamePolicy::ALL.into_iter().find(|p| p.key() == key)
+    }
+}
+
+/// Which directory of a multi-directory local profile new files go to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlacementPolicy {
+    #[default]
+    FillFirst,
+    MostFreeSpace,
+}
+
+impl PlacementPolicy {
+    pub const ALL: [PlacementPolicy; 2] =
+        [PlacementPolicy::FillFirst, PlacementPolicy::MostFreeSpace];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            PlacementPolicy::FillFirst => "fill_first",
+            PlacementPolicy::MostFreeSpace => "most_free_space",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PlacementPolicy::FillFirst => "Fill each directory in order",
+            PlacementPolicy::MostFreeSpace => "Use the directory with the most free space",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<PlacementPolicy> {
+        PlacementPolicy::ALL.into_iter().find(|p| p.key() == key)
+    }
+}
+
+/// How close a profile is to its soft quota
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaLevel {
+    Normal,
+    Warning,
+    Exceeded,
+}
+
+const BYTES_PER_GB: u64 = 1024 * 1024 * 1024;
+
 /// Storage profile display data
 #[derive(Debug, Clone, PartialEq, Default, Store)]
 pub struct StorageProfile {
@@ -33,13 +146,26 @@ pub struct StorageProfile {
     pub name: String,
     pub location: StorageLocation,
     pub location_path: String,
+    /// Further local directories, for profiles spanning several drives
+    pub extra_location_paths: Vec<String>,
+    pub placement_policy: PlacementPolicy,
     pub encrypted: bool,
+    pub compressed: bool,
+    pub filename_policy: FilenamePolicy,
     pub is_default: bool,
+    pub cloud_provider: CloudProvider,
     pub cloud_bucket: Option<String>,
     pub cloud_region: Option<String>,
     pub cloud_endpoint: Option<String>,
     pub cloud_access_key: Option<String>,
     pub cloud_secret_key: Option<String>,
+    pub cloud_archival: bool,
+    /// Soft limit on stored bytes
+    pub quota_bytes: Option<u64>,
+    /// Bytes stored under this profile so far
+    pub used_bytes: u64,
+    /// Usage against the quota, if one is set
+    pub quota_level: Option<QuotaLevel>,
 }
 
 /// Storage profiles section view
@@ -57,6 +183,9 @@ pub fn StorageProfilesSectionView(
     on_set_default: EventHandler<String>,
     on_save: EventHandler<StorageProfile>,
     on_cancel_edit: EventHandler<()>,
+    /// Price per GB-month used for import cost estimates
+    cloud_price_per_gb: f64,
+    on_cloud_price_change: EventHandler<f64>,
 ) -> Element {
     // Read at this level - this is a leaf component
     let profiles = profiles.read();
@@ -114,6 +243,32 @@ pub fn StorageProfilesSectionView(
                 }
             }
 
+            div { class: "mt-6 bg-gray-800 rounded-lg p-4 flex items-center justify-between gap-4",
+                div {
+                    h3 { class: "text-sm font-medium text-white", "Cloud Price" }
+                    p { class: "text-xs text-gray-500",
+                        "Per GB per month, used to estimate costs before importing to a cloud profile"
+                    }
+                }
+                div { class: "flex items-center gap-2 text-gray-400",
+                    "$"
+                    input {
+                        r#type: "number",
+                        class: "w-24 px-3 py-2 bg-gray-700 border border-gray-600 rounded-lg text-white focus:outline-none focus:ring-2 focus:ring-indigo-500",
+                        min: "0",
+                        step: "0.001",
+                        value: "{cloud_price_per_gb}",
+                        onchange: move |e| {
+                            if let Ok(price) = e.value().parse::<f64>() {
+                                if price >= 0.0 {
+                                    on_cloud_price_change.call(price);
+                                }
+                            }
+                        },
+                    }
+                }
+            }
+
             div { class: "mt-6 p-4 bg-gray-700/50 rounded-lg",
                 p { class: "text-sm text-gray-400",
                     "Storage profiles determine how release files are stored. You can have multiple profiles "
@@ -153,13 +308,34 @@ fn ProfileCard(
                         span { class: "px-2 py-1 bg-gray-700 text-gray-300 rounded text-xs",
                             "{profile.location.label()}"
                         }
+                        if profile.location == StorageLocation::Cloud
+                            && profile.cloud_provider == CloudProvider::B2
+                        {
+                            span { class: "px-2 py-1 bg-gray-700 text-gray-300 rounded text-xs",
+                                "B2"
+                            }
+                        }
                         if profile.encrypted {
                             span { class: "px-2 py-1 bg-green-900 text-green-300 rounded text-xs",
                                 "Encrypted"
                             }
                         }
+                        if profile.compressed {
+                            span { class: "px-2 py-1 bg-gray-700 text-gray-300 rounded text-xs",
+                                "Compressed"
+                            }
+                        }
+                        if profile.cloud_archival {
+                            span { class: "px-2 py-1 bg-sky-900 text-sky-300 rounded text-xs",
+                                "Archival"
+                            }
+                        }
                     }
                     p { class: "text-sm text-gray-500 mt-2 font-mono", "{profile.location_path}" }
+                    for path in profile.extra_location_paths.iter() {
+                        p { class: "text-sm text-gray-500 font-mono", "{path}" }
+                    }
+                    QuotaUsage { profile: profile.clone() }
                 }
                 div { class: "flex items-center gap-2",
                     if !profile.is_default {
@@ -234,6 +410,45 @@ fn ProfileCard(
     }
 }
 
+/// Usage against the profile's quota, or plain usage when there's no quota
+#[component]
+fn QuotaUsage(profile: StorageProfile) -> Element {
+    let used = format_file_size(profile.used_bytes as i64);
+    let Some(quota_bytes) = profile.quota_bytes else {
+        return rsx! {
+            p { class: "text-xs text-gray-500 mt-2", "{used} stored" }
+        };
+    };
+    let quota = format_file_size(quota_bytes as i64);
+    let percent = if quota_bytes == 0 {
+        100.0
+    } else {
+        (profile.used_bytes as f64 / quota_bytes as f64 * 100.0).min(100.0)
+    };
+    let (bar_class, text_class) = match profile.quota_level {
+        Some(QuotaLevel::Exceeded) => ("bg-red-500", "text-red-400"),
+        Some(QuotaLevel::Warning) => ("bg-amber-500", "text-amber-400"),
+        _ => ("bg-indigo-500", "text-gray-500"),
+    };
+
+    rsx! {
+        div { class: "mt-3 max-w-xs",
+            div { class: "h-1.5 bg-gray-700 rounded-full overflow-clip",
+                div {
+                    class: "h-full {bar_class}",
+                    style: "width: {percent}%",
+                }
+            }
+            p { class: "text-xs mt-1 {text_class}",
+                "{used} of {quota}"
+                if profile.quota_level == Some(QuotaLevel::Exceeded) {
+                    " · over quota, new imports need confirmation"
+                }
+            }
+        }
+    }
+}
+
 /// Profile editor form view
 #[component]
 pub fn StorageProfileEditorView(
@@ -255,6 +470,24 @@ pub fn StorageProfileEditorView(
             .map(|p| p.location_path.clone())
             .unwrap_or_default()
     });
+    let mut extra_location_paths = use_signal(|| {
+        profile
+            .as_ref()
+            .map(|p| p.extra_location_paths.clone())
+            .unwrap_or_default()
+    });
+    let mut placement_policy = use_signal(|| {
+        profile
+            .as_ref()
+            .map(|p| p.placement_policy)
+            .unwrap_or_default()
+    });
+    let mut cloud_provider = use_signal(|| {
+        profile
+            .as_ref()
+            .map(|p| p.cloud_provider)
+            .unwrap_or_default()
+    });
     let mut cloud_bucket = use_signal(|| {
         profile
             .as_ref()
@@ -285,9 +518,25 @@ pub fn StorageProfileEditorView(
             .and_then(|p| p.cloud_secret_key.clone())
             .unwrap_or_default()
     });
+    let mut cloud_archival =
+        use_signal(|| profile.as_ref().map(|p| p.cloud_archival).unwrap_or(false));
     let mut show_secrets = use_signal(|| false);
     let mut encrypted = use_signal(|| profile.as_ref().map(|p| p.encrypted).unwrap_or(true));
+    let mut compressed = use_signal(|| profile.as_ref().map(|p| p.compressed).unwrap_or(false));
+    let mut filename_policy = use_signal(|| {
+        profile
+            .as_ref()
+            .map(|p| p.filename_policy)
+            .unwrap_or_default()
+    });
     let mut is_default = use_signal(|| profile.as_ref().map(|p| p.is_default).unwrap_or(false));
+    let mut quota_gb = use_signal(|| {
+        profile
+            .as_ref()
+            .and_then(|p| p.quota_bytes)
+            .map(|b| (b / BYTES_PER_GB).to_string())
+            .unwrap_or_default()
+    });
     let mut validation_error = use_signal(|| Option::<String>::None);
 
     let existing_id = profile.as_ref().map(|p| p.id.clone());
@@ -298,13 +547,25 @@ pub fn StorageProfileEditorView(
         let new_name = name.read().clone();
         let new_location = *location.read();
         let new_location_path = location_path.read().clone();
+        let new_extra_location_paths: Vec<String> = extra_location_paths
+            .read()
+            .iter()
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty())
+            .collect();
+        let new_placement_policy = *placement_policy.read();
+        let new_cloud_provider = *cloud_provider.read();
         let new_cloud_bucket = cloud_bucket.read().clone();
         let new_cloud_region = cloud_region.read().clone();
         let new_cloud_endpoint = cloud_endpoint.read().clone();
         let new_cloud_access_key = cloud_access_key.read().clone();
         let new_cloud_secret_key = cloud_secret_key.read().clone();
+        let new_cloud_archival = *cloud_archival.read();
         let new_encrypted = *encrypted.read();
+        let new_compressed = *compressed.read();
+        let new_filename_policy = *filename_policy.read();
         let new_is_default = *is_default.read();
+        let new_quota_gb = quota_gb.read().trim().to_string();
 
         // Validation
         if new_name.trim().is_empty() {
@@ -312,7 +573,19 @@ pub fn StorageProfileEditorView(
             return;
         }
 
-        if new_location == StorageLocation::Local {
+        let new_quota_bytes = if new_quota_gb.is_empty() {
+            None
+        } else {
+            match new_quota_gb.parse::<u64>() {
+                Ok(gb) if gb > 0 => Some(gb * BYTES_PER_GB),
+                _ => {
+                    validation_error.set(Some("Quota must be a whole number of GB".to_string()));
+                    return;
+                }
+            }
+        };
+
+        if new_location != StorageLocation::Cloud {
             if new_location_path.trim().is_empty() {
                 validation_error.set(Some("Directory path is required".to_string()));
                 return;
@@ -322,7 +595,7 @@ pub fn StorageProfileEditorView(
                 validation_error.set(Some("Bucket name is required".to_string()));
                 return;
             }
-            if new_cloud_region.trim().is_empty() {
+            if new_cloud_provider == CloudProvider::S3 && new_cloud_region.trim().is_empty() {
                 validation_error.set(Some("Region is required".to_string()));
                 return;
             }
@@ -340,24 +613,37 @@ pub fn StorageProfileEditorView(
             id: existing_id.clone().unwrap_or_default(),
             name: new_name,
             location: new_location,
-            location_path: if new_location == StorageLocation::Local {
+            location_path: if new_location != StorageLocation::Cloud {
                 new_location_path
             } else {
                 String::new()
             },
-            encrypted: new_encrypted,
+            extra_location_paths: if new_location == StorageLocation::Local {
+                new_extra_location_paths
+            } else {
+                Vec::new()
+            },
+            placement_policy: new_placement_policy,
+            // Referenced files are read as they are
+            encrypted: new_encrypted && new_location != StorageLocation::Reference,
+            compressed: new_compressed && new_location != StorageLocation::Reference,
+            filename_policy: new_filename_policy,
             is_default: new_is_default,
+            cloud_provider: new_cloud_provider,
             cloud_bucket: if new_location == StorageLocation::Cloud {
                 Some(new_cloud_bucket)
             } else {
                 None
             },
-            cloud_region: if new_location == StorageLocation::Cloud {
+            cloud_region: if new_location == StorageLocation::Cloud
+                && new_cloud_provider == CloudProvider::S3
+            {
                 Some(new_cloud_region)
             } else {
                 None
             },
             cloud_endpoint: if new_location == StorageLocation::Cloud
+                && new_cloud_provider == CloudProvider::S3
                 && !new_cloud_endpoint.trim().is_empty()
             {
                 Some(new_cloud_endpoint)
@@ -374,6 +660,12 @@ pub fn StorageProfileEditorView(
             } else {
                 None
             },
+            cloud_archival: new_location == StorageLocation::Cloud
+                && new_cloud_provider == CloudProvider::S3
+                && new_cloud_archival,
+            quota_bytes: new_quota_bytes,
+            used_bytes: 0,
+            quota_level: None,
         };
 
         on_save.call(profile);
@@ -410,7 +702,7 @@ pub fn StorageProfileEditorView(
                                 checked: *location.read() == StorageLocation::Cloud,
                                 onchange: move |_| location.set(StorageLocation::Cloud),
                             }
-                            span { class: "text-white", "Cloud (S3)" }
+                            span { class: "text-white", "Cloud" }
                         }
                         label { class: "flex items-center gap-2 cursor-pointer",
                             input {
@@ -422,6 +714,16 @@ pub fn StorageProfileEditorView(
                             }
                             span { class: "text-white", "Local Filesystem" }
                         }
+                        label { class: "flex items-center gap-2 cursor-pointer",
+                            input {
+                                r#type: "radio",
+                                name: "location",
+                                class: "text-indigo-600 focus:ring-indigo-500",
+                                checked: *location.read() == StorageLocation::Reference,
+                                onchange: move |_| location.set(StorageLocation::Reference),
+                            }
+                            span { class: "text-white", "Existing Folder (files stay in place)" }
+                        }
                     }
                 }
 
@@ -437,40 +739,138 @@ pub fn StorageProfileEditorView(
                             placeholder: "/path/to/storage",
                         }
                     }
-                } else {
+                    for (index, path) in extra_location_paths.read().iter().cloned().enumerate() {
+                        div { key: "{index}", class: "flex items-center gap-2",
+                            div { class: "flex-1",
+                                TextInput {
+                                    value: path,
+                                    on_input: move |v| extra_location_paths.write()[index] = v,
+                                    size: TextInputSize::Medium,
+                                    placeholder: "/path/to/another/drive",
+                                }
+                            }
+                            ChromelessButton {
+                                class: Some(
+                                    "p-2 text-gray-400 hover:text-red-400 hover:bg-gray-700 rounded-lg transition-colors"
+                                        .to_string(),
+                                ),
+                                title: Some("Remove directory".to_string()),
+                                aria_label: Some("Remove directory".to_string()),
+                                onclick: move |_| {
+                                    extra_location_paths.write().remove(index);
+                                },
+                                TrashIcon { class: "w-5 h-5" }
+                            }
+                        }
+                    }
+                    Button {
+                        variant: ButtonVariant::Ghost,
+                        size: ButtonSize::Small,
+                        class: Some("text-sm text-indigo-400 hover:text-indigo-300".to_string()),
+                        onclick: move |_| extra_location_paths.write().push(String::new()),
+                        "Add another directory"
+                    }
+                    if !extra_location_paths.read().is_empty() {
+                        div {
+                            label { class: "block text-sm font-medium text-gray-400 mb-2",
+                                "Placement"
+                            }
+                            Select {
+                                value: placement_policy.read().key(),
+                                onchange: move |key: String| {
+                                    if let Some(policy) = PlacementPolicy::from_key(&key) {
+                                        placement_policy.set(policy);
+                                    }
+                                },
+                                for policy in PlacementPolicy::ALL {
+                                    SelectOption {
+                                        key: "{policy.key()}",
+                                        value: policy.key(),
+                                        label: policy.label(),
+                                    }
+                                }
+                            }
+                            p { class: "text-xs text-gray-500 mt-1",
+                                "Where new files go. Files are found on any of the directories, so releases keep playing after one drive fills up."
+                            }
+                        }
+                    }
+                } else if *location.read() == StorageLocation::Reference {
                     div {
                         label { class: "block text-sm font-medium text-gray-400 mb-2",
-                            "Bucket Name"
+                            "Folder Path"
                         }
                         TextInput {
-                            value: cloud_bucket(),
-                            on_input: move |v| cloud_bucket.set(v),
+                            value: location_path(),
+                            on_input: move |v| location_path.set(v),
                             size: TextInputSize::Medium,
-                            placeholder: "my-music-bucket",
+                            placeholder: "/Volumes/nas/music",
+                        }
+                        p { class: "text-xs text-gray-500 mt-1",
+                            "Imports from this folder are indexed where they are. bae never copies files into it or deletes from it. Releases can be moved to another profile later."
                         }
                     }
+                } else {
                     div {
                         label { class: "block text-sm font-medium text-gray-400 mb-2",
-                            "Region"
+                            "Provider"
                         }
-                        TextInput {
-                            value: cloud_region(),
-                            on_input: move |v| cloud_region.set(v),
-                            size: TextInputSize::Medium,
-                            placeholder: "us-east-1",
+                        Select {
+                            value: cloud_provider.read().key(),
+                            onchange: move |key: String| {
+                                if let Some(provider) = CloudProvider::from_key(&key) {
+                                    cloud_provider.set(provider);
+                                }
+                            },
+                            for provider in CloudProvider::ALL {
+                                SelectOption {
+                                    key: "{provider.key()}",
+                                    value: provider.key(),
+                                    label: provider.label(),
+                                }
+                            }
+                        }
+                        if *cloud_provider.read() == CloudProvider::B2 {
+                            p { class: "text-xs text-gray-500 mt-1",
+                                "Uses B2's native API. The bucket must already exist."
+                            }
                         }
                     }
                     div {
                         label { class: "block text-sm font-medium text-gray-400 mb-2",
-                            "Custom Endpoint (optional)"
+                            "Bucket Name"
                         }
                         TextInput {
-                            value: cloud_endpoint(),
-                            on_input: move |v| cloud_endpoint.set(v),
+                            value: cloud_bucket(),
+                            on_input: move |v| cloud_bucket.set(v),
                             size: TextInputSize::Medium,
-                            placeholder: "https://minio.example.com",
+                            placeholder: "my-music-bucket",
+                        }
+                    }
+                    if *cloud_provider.read() == CloudProvider::S3 {
+                        div {
+                            label { class: "block text-sm font-medium text-gray-400 mb-2",
+                                "Region"
+                            }
+                            TextInput {
+                                value: cloud_region(),
+                                on_input: move |v| cloud_region.set(v),
+                                size: TextInputSize::Medium,
+                                placeholder: "us-east-1",
+                            }
+                        }
+                        div {
+                            label { class: "block text-sm font-medium text-gray-400 mb-2",
+                                "Custom Endpoint (optional)"
+                            }
+                            TextInput {
+                                value: cloud_endpoint(),
+                                on_input: move |v| cloud_endpoint.set(v),
+                                size: TextInputSize::Medium,
+                                placeholder: "https://minio.example.com",
+                            }
+                            p { class: "text-xs text-gray-500 mt-1", "Leave empty for AWS S3" }
                         }
-                        p { class: "text-xs text-gray-500 mt-1", "Leave empty for AWS S3" }
                     }
 
                     div { class: "flex items-center justify-between",
@@ -489,7 +889,11 @@ pub fn StorageProfileEditorView(
                     }
                     div {
                         label { class: "block text-sm font-medium text-gray-400 mb-2",
-                            "Access Key ID"
+                            if *cloud_provider.read() == CloudProvider::B2 {
+                                "Application Key ID"
+                            } else {
+                                "Access Key ID"
+                            }
                         }
                         input {
                             r#type: if *show_secrets.read() { "text" } else { "password" },
@@ -502,7 +906,11 @@ pub fn StorageProfileEditorView(
                     }
                     div {
                         label { class: "block text-sm font-medium text-gray-400 mb-2",
-                            "Secret Access Key"
+                            if *cloud_provider.read() == CloudProvider::B2 {
+                                "Application Key"
+                            } else {
+                                "Secret Access Key"
+                            }
                         }
                         input {
                             r#type: if *show_secrets.read() { "text" } else { "password" },
@@ -513,20 +921,94 @@ pub fn StorageProfileEditorView(
                             oninput: move |e| cloud_secret_key.set(e.value()),
                         }
                     }
+
+                    if *cloud_provider.read() == CloudProvider::S3 {
+                        label { class: "flex items-start gap-3 cursor-pointer",
+                            input {
+                                r#type: "checkbox",
+                                class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600 mt-0.5",
+                                checked: *cloud_archival.read(),
+                                onchange: move |e| cloud_archival.set(e.checked()),
+                            }
+                            div {
+                                span { class: "text-white block", "Archival bucket" }
+                                span { class: "text-xs text-gray-500",
+                                    "Lifecycle rules move objects to Glacier or Deep Archive. bae checks availability before playback and can request restores."
+                                }
+                            }
+                        }
+                    }
                 }
 
-                div { class: "space-y-3",
-                    label { class: "flex items-start gap-3 cursor-pointer",
-                        input {
-                            r#type: "checkbox",
-                            class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600 mt-0.5",
-                            checked: *encrypted.read(),
-                            onchange: move |e| encrypted.set(e.checked()),
+                if *location.read() != StorageLocation::Reference {
+                    div {
+                        label { class: "block text-sm font-medium text-gray-400 mb-2",
+                            "Filenames"
                         }
-                        div {
-                            span { class: "text-white block", "Encrypted" }
-                            span { class: "text-xs text-gray-500",
-                                "AES-256 encryption. Data is unreadable without your key."
+                        Select {
+                            value: filename_policy.read().key(),
+                            onchange: move |key: String| {
+                                if let Some(policy) = FilenamePolicy::from_key(&key) {
+                                    filename_policy.set(policy);
+                                }
+                            },
+                            for policy in FilenamePolicy::ALL {
+                                SelectOption {
+                                    key: "{policy.key()}",
+                                    value: policy.key(),
+                                    label: policy.label(),
+                                }
+                            }
+                        }
+                        p { class: "text-xs text-gray-500 mt-1",
+                            "Applies to characters like ? : * | that some filesystems reject. Original names are kept for export."
+                        }
+                    }
+                }
+
+                div {
+                    label { class: "block text-sm font-medium text-gray-400 mb-2",
+                        "Quota in GB (optional)"
+                    }
+                    TextInput {
+                        value: quota_gb(),
+                        on_input: move |v| quota_gb.set(v),
+                        size: TextInputSize::Medium,
+                        placeholder: "500",
+                    }
+                    p { class: "text-xs text-gray-500 mt-1",
+                        "Warns at 80%. Imports past the quota ask for confirmation first."
+                    }
+                }
+
+                if *location.read() != StorageLocation::Reference {
+                    div { class: "space-y-3",
+                        label { class: "flex items-start gap-3 cursor-pointer",
+                            input {
+                                r#type: "checkbox",
+                                class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600 mt-0.5",
+                                checked: *encrypted.read(),
+                                onchange: move |e| encrypted.set(e.checked()),
+                            }
+                            div {
+                                span { class: "text-white block", "Encrypted" }
+                                span { class: "text-xs text-gray-500",
+                                    "AES-256 encryption. Data is unreadable without your key."
+                                }
+                            }
+                        }
+                        label { class: "flex items-start gap-3 cursor-pointer",
+                            input {
+                                r#type: "checkbox",
+                                class: "rounded text-indigo-600 focus:ring-indigo-500 bg-gray-700 border-gray-600 mt-0.5",
+                                checked: *compressed.read(),
+                                onchange: move |e| compressed.set(e.checked()),
+                            }
+                            div {
+                                span { class: "text-white block", "Compressed" }
+                                span { class: "text-xs text-gray-500",
+                                    "zstd compression for logs, cue sheets, documents and artwork. Audio is stored as-is."
+                                }
                             }
                         }
                     }
@@ -1,6 +1,6 @@
 //! Subsonic section view
 
-use crate::components::{Button, ButtonSize, ButtonVariant};
+use crate::components::{Button, ButtonSize, ButtonVariant, Select, SelectOption};
 use dioxus::prelude::*;
 
 /// Subsonic section view
@@ -10,11 +10,16 @@ pub fn SubsonicSectionView(
     enabled: bool,
     /// Port number
     port: u16,
+    /// Opus bitrate for clients that ask for transcoding, in kbps
+    transcode_bitrate_kbps: u32,
+    /// Bitrates to choose from, in kbps
+    transcode_bitrates_kbps: Vec<u32>,
     /// Whether currently in edit mode
     is_editing: bool,
     /// Temporary values while editing
     edit_enabled: bool,
     edit_port: String,
+    edit_transcode_bitrate_kbps: u32,
     /// State flags
     is_saving: bool,
     has_changes: bool,
@@ -25,6 +30,7 @@ pub fn SubsonicSectionView(
     on_save: EventHandler<()>,
     on_enabled_change: EventHandler<bool>,
     on_port_change: EventHandler<String>,
+    on_transcode_bitrate_change: EventHandler<u32>,
 ) -> Element {
     rsx! {
         div { class: "max-w-2xl space-y-6",
@@ -65,6 +71,24 @@ pub fn SubsonicSectionView(
                                 oninput: move |e| on_port_change.call(e.value()),
                             }
                         }
+                        div { class: "flex items-center gap-4",
+                            label { class: "text-sm text-gray-400 w-32", "Opus bitrate:" }
+                            Select {
+                                value: edit_transcode_bitrate_kbps.to_string(),
+                                onchange: move |value: String| {
+                                    if let Ok(kbps) = value.parse::<u32>() {
+                                        on_transcode_bitrate_change.call(kbps);
+                                    }
+                                },
+                                for kbps in transcode_bitrates_kbps {
+                                    SelectOption {
+                                        key: "{kbps}",
+                                        value: kbps.to_string(),
+                                        label: format!("{} kbps", kbps),
+                                    }
+                                }
+                            }
+                        }
                     }
                 } else {
                     div { class: "space-y-2 text-sm",
@@ -82,6 +106,10 @@ pub fn SubsonicSectionView(
                             span { class: "text-gray-400", "Port:" }
                             span { class: "text-white font-mono", "{port}" }
                         }
+                        div { class: "flex items-center gap-2",
+                            span { class: "text-gray-400", "Transcoding:" }
+                            span { class: "text-white", "Opus, {transcode_bitrate_kbps} kbps" }
+                        }
                         if enabled {
                             div { class: "flex items-center gap-2",
                                 span { class: "text-gray-400", "URL:" }
@@ -139,6 +167,10 @@ pub fn SubsonicSectionView(
                         span { class: "font-mono text-indigo-400", "http://YOUR_IP:{port}" }
                         " (or use localhost for the same device)."
                     }
+                    p {
+                        "Apps get your files as they are unless they ask for a lower bitrate, like many do on "
+                        "mobile data. Then tracks are converted to Opus, at the app's limit or the bitrate above."
+                    }
                 }
             }
         }
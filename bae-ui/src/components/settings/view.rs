@@ -8,10 +8,19 @@ use dioxus::prelude::*;
 pub enum SettingsTab {
     #[default]
     StorageProfiles,
+    Library,
+    Maintenance,
+    Importing,
     ApiKeys,
     Encryption,
+    Playback,
+    Scrobbling,
+    Network,
     BitTorrent,
+    Seeding,
+    CdRipping,
     Subsonic,
+    Remote,
     About,
 }
 
@@ -19,10 +28,19 @@ impl SettingsTab {
     pub fn label(&self) -> &'static str {
         match self {
             SettingsTab::StorageProfiles => "Storage Profiles",
+            SettingsTab::Library => "Library",
+            SettingsTab::Maintenance => "Maintenance",
+            SettingsTab::Importing => "Importing",
             SettingsTab::ApiKeys => "API Keys",
             SettingsTab::Encryption => "Encryption",
+            SettingsTab::Playback => "Playback",
+            SettingsTab::Scrobbling => "Scrobbling",
+            SettingsTab::Network => "Network",
             SettingsTab::BitTorrent => "BitTorrent",
+            SettingsTab::Seeding => "Seeding",
+            SettingsTab::CdRipping => "CD Ripping",
             SettingsTab::Subsonic => "Subsonic",
+            SettingsTab::Remote => "Remote",
             SettingsTab::About => "About",
         }
     }
@@ -30,11 +48,22 @@ impl SettingsTab {
     pub fn all() -> &'static [SettingsTab] {
         &[
             SettingsTab::StorageProfiles,
+            SettingsTab::Library,
+            SettingsTab::Maintenance,
+            SettingsTab::Importing,
             SettingsTab::ApiKeys,
             SettingsTab::Encryption,
+            SettingsTab::Playback,
+            SettingsTab::Scrobbling,
+            SettingsTab::Network,
             #[cfg(feature = "torrent")]
             SettingsTab::BitTorrent,
+            #[cfg(feature = "torrent")]
+            SettingsTab::Seeding,
+            #[cfg(feature = "cd-rip")]
+            SettingsTab::CdRipping,
             SettingsTab::Subsonic,
+            SettingsTab::Remote,
             SettingsTab::About,
         ]
     }
@@ -0,0 +1,115 @@
+//! Restore picker shown when the library database was damaged at launch
+
+use crate::components::icons::AlertTriangleIcon;
+use crate::components::utils::{format_date_time, format_file_size};
+use crate::components::{Button, ButtonSize, ButtonVariant, Modal};
+use crate::display_types::LibrarySnapshot;
+use dioxus::prelude::*;
+
+/// Offers the library's snapshots to put back in place of a damaged
+/// database. A restore takes effect when bae restarts.
+#[component]
+pub fn SnapshotRestoreModal(
+    is_open: ReadSignal<bool>,
+    /// Where the damaged database was moved
+    damaged_database: String,
+    /// Newest first
+    snapshots: Vec<LibrarySnapshot>,
+    is_restoring: bool,
+    /// A snapshot is downloaded and waits for a restart
+    restore_staged: bool,
+    /// Why the last restore failed
+    error: Option<String>,
+    on_restore: EventHandler<LibrarySnapshot>,
+    on_restart: EventHandler<()>,
+    on_close: EventHandler<()>,
+) -> Element {
+    let mut selected = use_signal(|| snapshots.first().map(|s| s.location.clone()));
+    let chosen = snapshots
+        .iter()
+        .find(|s| Some(&s.location) == selected.read().as_ref())
+        .cloned();
+
+    rsx! {
+        Modal { is_open, on_close: move |_| on_close.call(()),
+            div { class: "bg-gray-800 rounded-lg shadow-xl max-w-lg w-full mx-4 p-6 space-y-4",
+                div { class: "flex items-center gap-3",
+                    AlertTriangleIcon { class: "w-6 h-6 text-yellow-400 shrink-0" }
+                    h2 { class: "text-xl font-bold text-white", "Library database damaged" }
+                }
+                p { class: "text-sm text-gray-300",
+                    "bae couldn't read its library database, so it started with an empty one. The damaged file was kept at:"
+                }
+                p { class: "text-xs text-gray-400 font-mono break-all", "{damaged_database}" }
+
+                if restore_staged {
+                    p { class: "text-sm text-gray-300",
+                        "The snapshot is downloaded. Restart bae to finish restoring it."
+                    }
+                } else if snapshots.is_empty() {
+                    p { class: "text-sm text-gray-400",
+                        "There are no snapshots of this library to restore. Turn on snapshots in Settings → Library so this can be undone next time."
+                    }
+                } else {
+                    p { class: "text-sm text-gray-300", "Restore a snapshot from the cloud:" }
+                    div { class: "max-h-64 overflow-y-auto divide-y divide-gray-700 border border-gray-700 rounded",
+                        for snapshot in snapshots.iter().cloned() {
+                            label {
+                                key: "{snapshot.location}",
+                                class: "flex items-center gap-3 px-3 py-2 text-sm cursor-pointer hover:bg-gray-700/50",
+                                input {
+                                    r#type: "radio",
+                                    name: "snapshot",
+                                    checked: Some(&snapshot.location) == selected.read().as_ref(),
+                                    onchange: {
+                                        let location = snapshot.location.clone();
+                                        move |_| selected.set(Some(location.clone()))
+                                    },
+                                }
+                                span { class: "text-white flex-1", {format_date_time(snapshot.created_at)} }
+                                span { class: "text-gray-400", {format_file_size(snapshot.size_bytes as i64)} }
+                            }
+                        }
+                    }
+                }
+
+                if let Some(error) = error {
+                    p { class: "text-sm text-red-400", "{error}" }
+                }
+
+                div { class: "flex justify-end gap-3 pt-2",
+                    if restore_staged {
+                        Button {
+                            variant: ButtonVariant::Primary,
+                            size: ButtonSize::Medium,
+                            onclick: move |_| on_restart.call(()),
+                            "Restart bae"
+                        }
+                    } else {
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            size: ButtonSize::Medium,
+                            disabled: is_restoring,
+                            onclick: move |_| on_close.call(()),
+                            "Continue with empty library"
+                        }
+                        if let Some(snapshot) = chosen {
+                            Button {
+                                variant: ButtonVariant::Primary,
+                                size: ButtonSize::Medium,
+                                disabled: is_restoring,
+                                loading: is_restoring,
+                                onclick: move |_| on_restore.call(snapshot.clone()),
+                                if is_restoring {
+                                    "Downloading..."
+                                } else {
+                                    "Restore"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -22,6 +22,9 @@ pub fn TextInput(
     #[props(default)] monospace: bool,
     #[props(default)] id: Option<String>,
     #[props(default)] autofocus: bool,
+    /// Id of a `datalist` providing suggestions
+    #[props(default)]
+    list: Option<String>,
 ) -> Element {
     let padding = match size {
         TextInputSize::Small => "px-2.5 py-1.5 text-sm",
@@ -45,6 +48,7 @@ pub fn TextInput(
             r#type: "text",
             class: "{class}",
             id: id.as_deref(),
+            list: list.as_deref(),
             value: "{value}",
             placeholder,
             disabled,
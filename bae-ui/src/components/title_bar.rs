@@ -5,6 +5,9 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use crate::components::icons::{ImageIcon, SettingsIcon};
+use crate::components::list_navigation::{
+    list_item_id, list_key_action, scroll_list_item_into_view, ListKeyAction,
+};
 use crate::components::{ChromelessButton, Dropdown, Placement};
 use dioxus::prelude::*;
 
@@ -19,9 +22,29 @@ pub struct NavItem {
     pub is_active: bool,
 }
 
+/// What a search result points at
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchResultKind {
+    Artist,
+    Album,
+    Track,
+}
+
+impl SearchResultKind {
+    /// Heading for a group of results of this kind
+    pub fn group_label(self) -> &'static str {
+        match self {
+            SearchResultKind::Artist => "Artists",
+            SearchResultKind::Album => "Albums",
+            SearchResultKind::Track => "Tracks",
+        }
+    }
+}
+
 /// Search result for title bar dropdown
 #[derive(Clone, PartialEq)]
 pub struct SearchResult {
+    pub kind: SearchResultKind,
     pub id: String,
     pub title: String,
     pub subtitle: String,
@@ -38,8 +61,11 @@ pub fn TitleBarView(
     // Search
     search_value: String,
     on_search_change: EventHandler<String>,
+    /// Results of one kind are listed together under a heading
     search_results: Vec<SearchResult>,
     on_search_result_click: EventHandler<String>,
+    /// Shift+Enter on a search result plays it instead of opening it
+    on_search_result_play: EventHandler<String>,
     show_search_results: ReadSignal<bool>,
     on_search_dismiss: EventHandler<()>,
     on_search_focus: EventHandler<()>,
@@ -58,6 +84,11 @@ pub fn TitleBarView(
         let id = BUTTON_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
         format!("search-input-{}", id)
     });
+    let results_list_id = format!("{}-results", search_input_id);
+    // Keyboard-highlighted result; the first result is active after typing
+    let mut active_result = use_signal(|| None::<usize>);
+    let results_len = search_results.len();
+    let active = active_result().filter(|i| *i < results_len);
 
     rsx! {
         // Title bar
@@ -112,11 +143,38 @@ pub fn TitleBarView(
                         autocomplete: "off",
                         class: "w-full h-7 px-2 bg-surface-input border border-border-default rounded text-white text-xs placeholder-gray-400 focus:outline-none focus:border-border-strong",
                         value: "{search_value}",
-                        oninput: move |evt| on_search_change.call(evt.value()),
+                        oninput: move |evt| {
+                            active_result.set(Some(0));
+                            on_search_change.call(evt.value());
+                        },
                         onfocus: move |_| on_search_focus.call(()),
-                        onkeydown: move |evt| {
-                            if evt.key() == Key::Escape {
-                                on_search_dismiss.call(());
+                        onkeydown: {
+                            let search_results = search_results.clone();
+                            let results_list_id = results_list_id.clone();
+                            move |evt: KeyboardEvent| {
+                                if !show_search_results() && evt.key() == Key::ArrowDown {
+                                    evt.prevent_default();
+                                    on_search_focus.call(());
+                                    return;
+                                }
+                                match list_key_action(&evt.key(), active, search_results.len()) {
+                                    Some(ListKeyAction::Move(index)) => {
+                                        evt.prevent_default();
+                                        active_result.set(Some(index));
+                                        scroll_list_item_into_view(&results_list_id, index);
+                                    }
+                                    Some(ListKeyAction::Confirm(index)) => {
+                                        evt.prevent_default();
+                                        let id = search_results[index].id.clone();
+                                        if evt.modifiers().shift() {
+                                            on_search_result_play.call(id);
+                                        } else {
+                                            on_search_result_click.call(id);
+                                        }
+                                    }
+                                    Some(ListKeyAction::Dismiss) => on_search_dismiss.call(()),
+                                    None => {}
+                                }
                             }
                         },
                     }
@@ -129,13 +187,26 @@ pub fn TitleBarView(
                             on_close: on_search_dismiss,
                             placement: Placement::Bottom,
                             class: "bg-surface-overlay border border-border-strong rounded-lg shadow-lg w-64 max-h-96 overflow-y-auto",
-                            for result in search_results.iter() {
+                            for (index , result) in search_results.iter().enumerate() {
+                                if index == 0 || search_results[index - 1].kind != result.kind {
+                                    div {
+                                        key: "{result.kind.group_label()}",
+                                        class: "px-3 pt-2 pb-1 text-[10px] font-semibold text-gray-500 uppercase tracking-wide",
+                                        "{result.kind.group_label()}"
+                                    }
+                                }
                                 SearchResultItem {
                                     key: "{result.id}",
+                                    element_id: list_item_id(&results_list_id, index),
                                     result: result.clone(),
+                                    is_active: active == Some(index),
                                     on_click: on_search_result_click,
+                                    on_hover: move |_| active_result.set(Some(index)),
                                 }
                             }
+                            div { class: "px-3 py-1.5 text-[10px] text-gray-500",
+                                "Enter to open · Shift+Enter to play"
+                            }
                         }
                     }
                 }
@@ -175,12 +246,21 @@ fn NavButton(is_active: bool, on_click: EventHandler<()>, children: Element) ->
 
 /// Search result item in the dropdown
 #[component]
-fn SearchResultItem(result: SearchResult, on_click: EventHandler<String>) -> Element {
+fn SearchResultItem(
+    element_id: String,
+    result: SearchResult,
+    is_active: bool,
+    on_click: EventHandler<String>,
+    on_hover: EventHandler<()>,
+) -> Element {
     let id = result.id.clone();
+    let active_class = if is_active { "bg-hover" } else { "" };
 
     rsx! {
         div {
-            class: "flex items-center gap-3 px-3 py-2 hover:bg-hover border-b border-border-strong last:border-b-0 cursor-pointer",
+            id: "{element_id}",
+            class: "flex items-center gap-3 px-3 py-2 cursor-pointer {active_class}",
+            onmouseenter: move |_| on_hover.call(()),
             onclick: {
                 let id = id.clone();
                 move |evt| {
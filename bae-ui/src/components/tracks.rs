@@ -0,0 +1,329 @@
+//! Tracks view - every track in the library as one flat list
+//!
+//! Rows have a fixed height, so only the rows inside the scrolled viewport
+//! are rendered and libraries with tens of thousands of tracks stay smooth.
+
+use crate::components::helpers::{ErrorDisplay, LoadingSpinner};
+use crate::components::utils::format_duration;
+use crate::components::{Button, ButtonSize, ButtonVariant, TextInput, TextInputSize};
+use crate::display_types::{LibraryTrack, TrackClickAction, TrackSortColumn};
+use crate::stores::tracks::{TracksState, TracksStateStoreExt};
+use dioxus::prelude::*;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// Height of one track row in pixels
+const ROW_HEIGHT: f64 = 36.0;
+
+/// Rows rendered above and below the viewport so fast scrolling doesn't flash
+const OVERSCAN_ROWS: usize = 10;
+
+/// Viewport height assumed until the list has been measured
+const DEFAULT_VIEWPORT_HEIGHT: f64 = 800.0;
+
+const COLUMNS_CLASS: &str = "grid grid-cols-[3rem_minmax(0,3fr)_minmax(0,2fr)_minmax(0,2fr)_4rem_4rem] gap-3 px-4 items-center";
+
+/// Flat, searchable and sortable list of every track in the library
+#[component]
+pub fn TracksView(
+    state: ReadStore<TracksState>,
+    /// What a plain click on a row does; modifier clicks always select
+    click_action: TrackClickAction,
+    /// Play the given tracks in order
+    on_play: EventHandler<Vec<String>>,
+    on_add_to_queue: EventHandler<Vec<String>>,
+    on_album_click: EventHandler<String>,
+) -> Element {
+    let loading = *state.loading().read();
+    let error = state.error().read().clone();
+    let all_tracks = state.tracks().read().clone();
+    let total = all_tracks.len();
+
+    let mut search = use_signal(String::new);
+    let mut sort_column = use_signal(|| TrackSortColumn::Album);
+    let mut descending = use_signal(|| false);
+    let mut selected: Signal<HashSet<String>> = use_signal(HashSet::new);
+    let mut anchor: Signal<Option<usize>> = use_signal(|| None);
+    let mut scroll_top = use_signal(|| 0.0);
+    let mut viewport_height = use_signal(|| DEFAULT_VIEWPORT_HEIGHT);
+    let mut container: Signal<Option<Rc<MountedData>>> = use_signal(|| None);
+
+    let mut tracks = filter_tracks(all_tracks, &search.read());
+    sort_column.read().apply(&mut tracks, descending());
+    let visible_ids: Vec<String> = tracks.iter().map(|t| t.id.clone()).collect();
+
+    let first_row = ((scroll_top() / ROW_HEIGHT) as usize).saturating_sub(OVERSCAN_ROWS);
+    let row_count = (viewport_height() / ROW_HEIGHT).ceil() as usize + OVERSCAN_ROWS * 2;
+    let last_row = (first_row + row_count).min(tracks.len());
+    let first_row = first_row.min(last_row);
+
+    let selected_ids: Vec<String> = visible_ids
+        .iter()
+        .filter(|id| selected.read().contains(*id))
+        .cloned()
+        .collect();
+
+    let measure = move || async move {
+        let Some(element) = container() else {
+            return;
+        };
+        if let Ok(offset) = element.get_scroll_offset().await {
+            scroll_top.set(offset.y);
+        }
+        if let Ok(rect) = element.get_client_rect().await {
+            viewport_height.set(rect.size.height);
+        }
+    };
+
+    let mut on_header_click = move |column: TrackSortColumn| {
+        if sort_column() == column {
+            descending.set(!descending());
+        } else {
+            sort_column.set(column);
+            descending.set(false);
+        }
+        anchor.set(None);
+    };
+
+    let on_row_click = {
+        let visible_ids = visible_ids.clone();
+        move |(index, evt): (usize, MouseEvent)| {
+            let modifiers = evt.modifiers();
+            let id = visible_ids[index].clone();
+            let is_plain = !(modifiers.shift() || modifiers.meta() || modifiers.ctrl());
+            match click_action {
+                TrackClickAction::Play if is_plain => {
+                    on_play.call(visible_ids[index..].to_vec());
+                    return;
+                }
+                TrackClickAction::AddToQueue if is_plain => {
+                    on_add_to_queue.call(vec![id]);
+                    return;
+                }
+                _ => {}
+            }
+            match anchor() {
+                Some(start) if modifiers.shift() => {
+                    let range = start.min(index)..=start.max(index);
+                    selected.set(visible_ids[range].iter().cloned().collect());
+                }
+                _ if modifiers.meta() || modifiers.ctrl() => {
+                    let mut selected = selected.write();
+                    if !selected.remove(&id) {
+                        selected.insert(id);
+                    }
+                    anchor.set(Some(index));
+                }
+                _ => {
+                    selected.set(HashSet::from([id]));
+                    anchor.set(Some(index));
+                }
+            }
+        }
+    };
+
+    let on_row_double_click = {
+        let visible_ids = visible_ids.clone();
+        move |index: usize| {
+            if click_action == TrackClickAction::Select {
+                on_play.call(visible_ids[index..].to_vec());
+            }
+        }
+    };
+
+    rsx! {
+        div { class: "flex-grow flex flex-col min-h-0 py-6 @3xl:py-10",
+            div { class: "container mx-auto flex flex-col min-h-0 flex-grow px-4 @3xl:px-0",
+                div { class: "flex flex-wrap items-center justify-between gap-3 mb-6",
+                    h1 { class: "text-2xl @3xl:text-3xl font-bold text-white", "Tracks" }
+                    if total > 0 {
+                        div { class: "w-64",
+                            TextInput {
+                                value: search(),
+                                on_input: move |value: String| {
+                                    search.set(value);
+                                    anchor.set(None);
+                                },
+                                size: TextInputSize::Small,
+                                placeholder: "Filter tracks",
+                            }
+                        }
+                    }
+                }
+                if !selected_ids.is_empty() {
+                    div { class: "flex items-center gap-3 mb-3 px-4 py-2 rounded-lg bg-gray-800/60",
+                        span { class: "text-sm text-gray-300 flex-1",
+                            if selected_ids.len() == 1 {
+                                "1 track selected"
+                            } else {
+                                "{selected_ids.len()} tracks selected"
+                            }
+                        }
+                        Button {
+                            variant: ButtonVariant::Primary,
+                            size: ButtonSize::Small,
+                            onclick: {
+                                let ids = selected_ids.clone();
+                                move |_| on_play.call(ids.clone())
+                            },
+                            "Play"
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            size: ButtonSize::Small,
+                            onclick: {
+                                let ids = selected_ids.clone();
+                                move |_| on_add_to_queue.call(ids.clone())
+                            },
+                            "Add to Queue"
+                        }
+                        Button {
+                            variant: ButtonVariant::Secondary,
+                            size: ButtonSize::Small,
+                            onclick: move |_| {
+                                selected.set(HashSet::new());
+                                anchor.set(None);
+                            },
+                            "Clear"
+                        }
+                    }
+                }
+                if loading {
+                    LoadingSpinner { message: "Loading tracks...".to_string() }
+                } else if let Some(err) = error {
+                    ErrorDisplay { message: err }
+                } else if total == 0 {
+                    p { class: "text-center py-12 text-gray-400", "No tracks in your library yet" }
+                } else {
+                    div { class: "{COLUMNS_CLASS} py-2 border-b border-gray-700 text-xs uppercase tracking-wide text-gray-500 select-none",
+                        div { class: "text-right", "#" }
+                        for column in TrackSortColumn::ALL {
+                            div {
+                                key: "{column.label()}",
+                                class: if column == TrackSortColumn::Duration { "text-right cursor-pointer hover:text-gray-300" } else { "cursor-pointer hover:text-gray-300" },
+                                onclick: move |_| on_header_click(column),
+                                "{column.label()}"
+                                if sort_column() == column {
+                                    if descending() {
+                                        " ↓"
+                                    } else {
+                                        " ↑"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if tracks.is_empty() {
+                        p { class: "text-center py-12 text-gray-400", "No tracks match \"{search}\"" }
+                    } else {
+                        div {
+                            class: "flex-grow min-h-0 overflow-y-auto",
+                            onmounted: move |evt| async move {
+                                container.set(Some(evt.data()));
+                                measure().await;
+                            },
+                            onscroll: move |_| measure(),
+                            onresize: move |_| measure(),
+                            div {
+                                class: "relative",
+                                style: "height: {tracks.len() as f64 * ROW_HEIGHT}px;",
+                                for index in first_row..last_row {
+                                    TrackListRow {
+                                        key: "{tracks[index].id}",
+                                        track: tracks[index].clone(),
+                                        top: index as f64 * ROW_HEIGHT,
+                                        is_selected: selected.read().contains(&tracks[index].id),
+                                        onclick: {
+                                            let mut on_row_click = on_row_click.clone();
+                                            move |evt: MouseEvent| on_row_click((index, evt))
+                                        },
+                                        ondoubleclick: {
+                                            let on_row_double_click = on_row_double_click.clone();
+                                            move |_| on_row_double_click(index)
+                                        },
+                                        on_album_click,
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TrackListRow(
+    track: LibraryTrack,
+    /// Offset from the top of the list in pixels
+    top: f64,
+    is_selected: bool,
+    onclick: EventHandler<MouseEvent>,
+    ondoubleclick: EventHandler<MouseEvent>,
+    on_album_click: EventHandler<String>,
+) -> Element {
+    let row_class = if is_selected {
+        "bg-accent/20"
+    } else {
+        "hover:bg-gray-800/60"
+    };
+    let number = match (track.disc_number, track.track_number) {
+        (Some(disc), Some(number)) if disc > 1 => format!("{}-{}", disc, number),
+        (_, Some(number)) => number.to_string(),
+        _ => String::new(),
+    };
+    let album_id = track.album_id.clone();
+
+    rsx! {
+        div {
+            class: "{COLUMNS_CLASS} absolute inset-x-0 text-sm rounded cursor-default select-none {row_class}",
+            style: "top: {top}px; height: {ROW_HEIGHT}px;",
+            onclick: move |evt| onclick.call(evt),
+            ondoubleclick: move |evt| ondoubleclick.call(evt),
+            div { class: "text-right text-gray-500 tabular-nums", "{number}" }
+            div { class: "text-gray-200 truncate", title: "{track.title}", "{track.title}" }
+            div { class: "text-gray-400 truncate", title: "{track.artist_name}", "{track.artist_name}" }
+            div { class: "truncate",
+                span {
+                    class: "text-gray-400 hover:text-white hover:underline cursor-pointer",
+                    title: "{track.album_title}",
+                    onclick: move |evt| {
+                        evt.stop_propagation();
+                        on_album_click.call(album_id.clone());
+                    },
+                    "{track.album_title}"
+                }
+            }
+            div { class: "text-gray-500 tabular-nums",
+                if let Some(year) = track.year {
+                    "{year}"
+                }
+            }
+            div { class: "text-right text-gray-500 tabular-nums",
+                if let Some(duration) = track.duration_ms {
+                    {format_duration(duration)}
+                }
+            }
+        }
+    }
+}
+
+/// Tracks whose title, artist or album contains every word of the query
+fn filter_tracks(tracks: Vec<LibraryTrack>, query: &str) -> Vec<LibraryTrack> {
+    let words: Vec<String> = query.split_whitespace().map(|w| w.to_lowercase()).collect();
+    if words.is_empty() {
+        return tracks;
+    }
+    tracks
+        .into_iter()
+        .filter(|track| {
+            let haystack = format!(
+                "{} {} {}",
+                track.title, track.artist_name, track.album_title
+            )
+            .to_lowercase();
+            words.iter().all(|word| haystack.contains(word.as_str()))
+        })
+        .collect()
+}
@@ -0,0 +1,45 @@
+//! Undo/redo toast
+
+use crate::components::icons::XIcon;
+use crate::components::ChromelessButton;
+use dioxus::prelude::*;
+
+/// Confirms an undo or redo and offers to reverse it
+#[component]
+pub fn UndoToast(
+    message: String,
+    /// Offer "Redo" after an undo, "Undo" after a redo
+    offers_redo: bool,
+    on_undo: EventHandler<()>,
+    on_redo: EventHandler<()>,
+    on_dismiss: EventHandler<()>,
+) -> Element {
+    rsx! {
+        div { class: "fixed bottom-20 right-4 bg-surface-overlay border border-border-strong text-white px-6 py-4 rounded-lg shadow-lg z-50 max-w-md",
+            div { class: "flex items-center justify-between gap-4",
+                span { class: "flex-1 text-sm text-gray-300 truncate", "{message}" }
+                ChromelessButton {
+                    class: Some("text-sm font-medium text-accent-soft hover:text-white".to_string()),
+                    onclick: move |_| {
+                        if offers_redo {
+                            on_redo.call(());
+                        } else {
+                            on_undo.call(());
+                        }
+                    },
+                    if offers_redo {
+                        "Redo"
+                    } else {
+                        "Undo"
+                    }
+                }
+                ChromelessButton {
+                    class: Some("text-gray-400 hover:text-white".to_string()),
+                    aria_label: Some("Dismiss".to_string()),
+                    onclick: move |_| on_dismiss.call(()),
+                    XIcon { class: "w-4 h-4" }
+                }
+            }
+        }
+    }
+}
@@ -20,3 +20,49 @@ pub fn format_file_size(bytes: i64) -> String {
         format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
     }
 }
+
+/// Format a unix timestamp (seconds, UTC) as e.g. "Mar 4, 2024"
+pub fn format_date(timestamp: i64) -> String {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    // Civil-from-days conversion (proleptic Gregorian calendar)
+    let days = timestamp.div_euclid(86_400);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{} {}, {}", MONTHS[(month - 1) as usize], day, year)
+}
+
+/// Format an uptime as its two largest units, e.g. "3d 4h" or "12m"
+pub fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        "under a minute".to_string()
+    }
+}
+
+/// Format a unix timestamp (seconds, UTC) as e.g. "Mar 4, 2024 18:05 UTC"
+pub fn format_date_time(timestamp: i64) -> String {
+    let secs = timestamp.rem_euclid(86_400);
+    format!(
+        "{} {:02}:{:02} UTC",
+        format_date(timestamp),
+        secs / 3600,
+        secs % 3600 / 60
+    )
+}
@@ -5,26 +5,282 @@
 //! that can work with either real or demo data.
 
 use dioxus::prelude::*;
+use serde::Serialize;
 
 /// Album display info
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Album {
     pub id: String,
     pub title: String,
     pub year: Option<i32>,
     pub cover_url: Option<String>,
     pub is_compilation: bool,
+    /// Unix timestamp (seconds) when the album was added to the library
+    pub date_added: i64,
+    /// Number of completed track plays
+    pub play_count: u32,
+    /// Unix timestamp (seconds) of the last completed track play
+    pub last_played: Option<i64>,
+    /// Cover colors for tinting album headers and Now Playing
+    pub palette: Option<AlbumPalette>,
+    /// Lowest quality among the album's tracks
+    pub quality: Option<AudioQuality>,
+    /// Kept in the local cache so it plays without a connection
+    pub is_offline: bool,
+}
+
+/// Technical quality tier of stored audio, lowest first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum AudioQuality {
+    Lossy,
+    /// Lossless at CD resolution or below
+    Lossless,
+    /// Lossless above CD resolution
+    HiRes,
+}
+
+impl AudioQuality {
+    pub const ALL: [AudioQuality; 3] = [
+        AudioQuality::Lossy,
+        AudioQuality::Lossless,
+        AudioQuality::HiRes,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            AudioQuality::Lossy => "lossy",
+            AudioQuality::Lossless => "lossless",
+            AudioQuality::HiRes => "hi_res",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AudioQuality::Lossy => "Lossy",
+            AudioQuality::Lossless => "Lossless",
+            AudioQuality::HiRes => "Hi-Res",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<AudioQuality> {
+        AudioQuality::ALL.into_iter().find(|q| q.key() == key)
+    }
+}
+
+/// Order in which the library grid lists albums
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LibrarySort {
+    Title,
+    DateAdded,
+    LastPlayed,
+    PlayCount,
+}
+
+impl LibrarySort {
+    pub const ALL: [LibrarySort; 4] = [
+        LibrarySort::Title,
+        LibrarySort::DateAdded,
+        LibrarySort::LastPlayed,
+        LibrarySort::PlayCount,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            LibrarySort::Title => "title",
+            LibrarySort::DateAdded => "date_added",
+            LibrarySort::LastPlayed => "last_played",
+            LibrarySort::PlayCount => "play_count",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LibrarySort::Title => "Title",
+            LibrarySort::DateAdded => "Date Added",
+            LibrarySort::LastPlayed => "Last Played",
+            LibrarySort::PlayCount => "Play Count",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<LibrarySort> {
+        LibrarySort::ALL.into_iter().find(|s| s.key() == key)
+    }
+
+    /// Sort albums in place. Albums arrive ordered by title, so the stable
+    /// sorts below keep title order among ties.
+    pub fn apply(&self, albums: &mut [Album]) {
+        match self {
+            LibrarySort::Title => {}
+            LibrarySort::DateAdded => albums.sort_by(|a, b| b.date_added.cmp(&a.date_added)),
+            LibrarySort::LastPlayed => albums.sort_by(|a, b| b.last_played.cmp(&a.last_played)),
+            LibrarySort::PlayCount => albums.sort_by(|a, b| b.play_count.cmp(&a.play_count)),
+        }
+    }
+}
+
+/// What the window shows at launch
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StartupView {
+    Library,
+    NowPlaying,
+    LastRoute,
+}
+
+impl StartupView {
+    pub const ALL: [StartupView; 3] = [
+        StartupView::Library,
+        StartupView::NowPlaying,
+        StartupView::LastRoute,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            StartupView::Library => "library",
+            StartupView::NowPlaying => "now_playing",
+            StartupView::LastRoute => "last_route",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StartupView::Library => "Library",
+            StartupView::NowPlaying => "Library with the queue open",
+            StartupView::LastRoute => "Where I left off",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<StartupView> {
+        StartupView::ALL.into_iter().find(|v| v.key() == key)
+    }
+}
+
+/// What a single click on a track row does
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrackClickAction {
+    Play,
+    AddToQueue,
+    /// Select the row; double-click plays
+    Select,
+}
+
+impl TrackClickAction {
+    pub const ALL: [TrackClickAction; 3] = [
+        TrackClickAction::Select,
+        TrackClickAction::Play,
+        TrackClickAction::AddToQueue,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            TrackClickAction::Play => "play",
+            TrackClickAction::AddToQueue => "add_to_queue",
+            TrackClickAction::Select => "select",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TrackClickAction::Play => "Plays it",
+            TrackClickAction::AddToQueue => "Adds it to the queue",
+            TrackClickAction::Select => "Selects it (double-click plays)",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<TrackClickAction> {
+        TrackClickAction::ALL.into_iter().find(|a| a.key() == key)
+    }
+}
+
+/// What happens when an audio CD is inserted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CdInsertAction {
+    Nothing,
+    OpenImport,
+    AutoRip,
+}
+
+impl CdInsertAction {
+    pub const ALL: [CdInsertAction; 3] = [
+        CdInsertAction::Nothing,
+        CdInsertAction::OpenImport,
+        CdInsertAction::AutoRip,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            CdInsertAction::Nothing => "nothing",
+            CdInsertAction::OpenImport => "open_import",
+            CdInsertAction::AutoRip => "auto_rip",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CdInsertAction::Nothing => "Nothing",
+            CdInsertAction::OpenImport => "Opens the CD import",
+            CdInsertAction::AutoRip => "Rips it if the disc is recognized",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<CdInsertAction> {
+        CdInsertAction::ALL.into_iter().find(|a| a.key() == key)
+    }
+}
+
+/// How featured-artist credits are normalized at import
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeaturedCreditStyle {
+    Keep,
+    Standardize,
+    Split,
+}
+
+impl FeaturedCreditStyle {
+    pub const ALL: [FeaturedCreditStyle; 3] = [
+        FeaturedCreditStyle::Standardize,
+        FeaturedCreditStyle::Split,
+        FeaturedCreditStyle::Keep,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            FeaturedCreditStyle::Keep => "keep",
+            FeaturedCreditStyle::Standardize => "standardize",
+            FeaturedCreditStyle::Split => "split",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FeaturedCreditStyle::Keep => "Keep as credited",
+            FeaturedCreditStyle::Standardize => "Spell as \"feat.\"",
+            FeaturedCreditStyle::Split => "Credit separately",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<FeaturedCreditStyle> {
+        FeaturedCreditStyle::ALL
+            .into_iter()
+            .find(|s| s.key() == key)
+    }
+}
+
+/// Colors extracted from an album cover at import, as `#rrggbb`
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AlbumPalette {
+    pub dominant: String,
+    pub accent: String,
 }
 
 /// Artist display info
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Artist {
     pub id: String,
     pub name: String,
 }
 
 /// Track import state for UI display
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
 pub enum TrackImportState {
     /// Track import not started or not applicable
     None,
@@ -34,8 +290,41 @@ pub enum TrackImportState {
     Complete,
 }
 
+/// An album's files downloading into the cache to play offline
+#[derive(Clone, Debug, PartialEq)]
+pub struct OfflineDownload {
+    pub files_done: usize,
+    pub files_total: usize,
+    /// Why the download stopped; it resumes on the next try
+    pub error: Option<String>,
+}
+
+/// Availability of a release kept on an archival storage profile
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReleaseArchiveStatus {
+    /// Files are in cold storage and must be restored before playback
+    Archived,
+    /// Restore requested, files not yet readable
+    Restoring,
+    /// Restored copies are readable for a limited time
+    Restored,
+}
+
+/// A release indexed in place from a reference profile
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReleaseReference {
+    /// Name of the reference profile
+    pub profile_name: String,
+    /// Default profile to move the release into, unless that's a reference one too
+    pub target_profile_name: Option<String>,
+    /// Whether the files are being copied into the target
+    pub migrating: bool,
+    /// Why the last move failed
+    pub error: Option<String>,
+}
+
 /// Track display info
-#[derive(Clone, Debug, PartialEq, Store)]
+#[derive(Clone, Debug, PartialEq, Store, Serialize)]
 pub struct Track {
     pub id: String,
     pub title: String,
@@ -45,6 +334,95 @@ pub struct Track {
     pub is_available: bool,
     /// Import state for reactive UI updates during import
     pub import_state: TrackImportState,
+    /// Quality of the stored audio, once the track has an audio format
+    pub quality: Option<AudioQuality>,
+    pub bookmarks: Vec<TrackBookmark>,
+}
+
+/// A track in the flat all-tracks view
+#[derive(Clone, Debug, PartialEq)]
+pub struct LibraryTrack {
+    pub id: String,
+    pub title: String,
+    pub artist_name: String,
+    pub album_id: String,
+    pub album_title: String,
+    pub year: Option<i32>,
+    pub disc_number: Option<i32>,
+    pub track_number: Option<i32>,
+    pub duration_ms: Option<i64>,
+}
+
+/// One play of a track, in the listening history
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackPlay {
+    pub id: String,
+    pub track: LibraryTrack,
+    /// Unix timestamp (seconds)
+    pub played_at: i64,
+}
+
+/// A track with how often it was played
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackPlayCount {
+    pub track: LibraryTrack,
+    pub play_count: u32,
+    /// Unix timestamp (seconds)
+    pub last_played: i64,
+}
+
+/// Column the tracks view is sorted by
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrackSortColumn {
+    Title,
+    Artist,
+    Album,
+    Year,
+    Duration,
+}
+
+impl TrackSortColumn {
+    pub const ALL: [TrackSortColumn; 5] = [
+        TrackSortColumn::Title,
+        TrackSortColumn::Artist,
+        TrackSortColumn::Album,
+        TrackSortColumn::Year,
+        TrackSortColumn::Duration,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TrackSortColumn::Title => "Title",
+            TrackSortColumn::Artist => "Artist",
+            TrackSortColumn::Album => "Album",
+            TrackSortColumn::Year => "Year",
+            TrackSortColumn::Duration => "Time",
+        }
+    }
+
+    /// Sort tracks in place. Tracks arrive in album order, so the stable
+    /// sorts below keep album and track order among ties.
+    pub fn apply(&self, tracks: &mut [LibraryTrack], descending: bool) {
+        match self {
+            TrackSortColumn::Title => tracks.sort_by_key(|t| t.title.to_lowercase()),
+            TrackSortColumn::Artist => tracks.sort_by_key(|t| t.artist_name.to_lowercase()),
+            TrackSortColumn::Album => {}
+            TrackSortColumn::Year => tracks.sort_by_key(|t| t.year),
+            TrackSortColumn::Duration => tracks.sort_by_key(|t| t.duration_ms),
+        }
+        if descending {
+            tracks.reverse();
+        }
+    }
+}
+
+/// A named position inside a track
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TrackBookmark {
+    pub id: String,
+    pub track_id: String,
+    pub name: String,
+    pub position_ms: u64,
 }
 
 /// Playback display state
@@ -67,11 +445,13 @@ pub enum PlaybackDisplay {
 }
 
 /// Queue item for display
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct QueueItem {
     pub track: Track,
     pub album_title: String,
     pub cover_url: Option<String>,
+    /// Opened from disk rather than the library
+    pub is_external: bool,
 }
 
 /// Release display info
@@ -90,6 +470,139 @@ pub struct Release {
     pub musicbrainz_release_id: Option<String>,
 }
 
+/// An album page's metadata as saved from edit mode
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlbumEdit {
+    pub album_id: String,
+    pub title: String,
+    pub year: Option<i32>,
+    /// Album artists in credit order, by name
+    pub artists: Vec<String>,
+    /// The release being viewed
+    pub release: Option<ReleaseEdit>,
+    pub tracks: Vec<TrackEdit>,
+}
+
+/// A release's metadata as saved from edit mode
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReleaseEdit {
+    pub id: String,
+    pub release_name: Option<String>,
+    pub year: Option<i32>,
+    pub format: Option<String>,
+    pub label: Option<String>,
+    pub catalog_number: Option<String>,
+    pub country: Option<String>,
+    pub barcode: Option<String>,
+}
+
+/// A track's metadata as saved from edit mode
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackEdit {
+    pub id: String,
+    pub title: String,
+    pub disc_number: Option<i32>,
+    pub track_number: Option<i32>,
+}
+
+/// Which values a library find-and-replace looks at
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FindReplaceField {
+    AlbumTitle,
+    TrackTitle,
+    ArtistName,
+}
+
+impl FindReplaceField {
+    pub const ALL: [FindReplaceField; 3] = [
+        FindReplaceField::AlbumTitle,
+        FindReplaceField::TrackTitle,
+        FindReplaceField::ArtistName,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FindReplaceField::AlbumTitle => "Album titles",
+            FindReplaceField::TrackTitle => "Track titles",
+            FindReplaceField::ArtistName => "Artist names",
+        }
+    }
+
+    /// Label for one row of a preview
+    pub fn row_label(&self) -> &'static str {
+        match self {
+            FindReplaceField::AlbumTitle => "Album",
+            FindReplaceField::TrackTitle => "Track",
+            FindReplaceField::ArtistName => "Artist",
+        }
+    }
+}
+
+/// A find-and-replace to preview over some albums
+#[derive(Clone, Debug, PartialEq)]
+pub struct FindReplaceRequest {
+    pub album_ids: Vec<String>,
+    pub find: String,
+    pub replace: String,
+    pub use_regex: bool,
+    pub case_sensitive: bool,
+    pub fields: Vec<FindReplaceField>,
+}
+
+/// One value a find-and-replace would change
+#[derive(Clone, Debug, PartialEq)]
+pub struct FindReplaceRow {
+    pub field: FindReplaceField,
+    /// Album, track or artist the value belongs to
+    pub entity_id: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Side-by-side comparison of two editions of an album
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReleaseComparison {
+    pub left: ComparedEdition,
+    pub right: ComparedEdition,
+    pub tracks: Vec<ComparedTrack>,
+}
+
+/// Format, size and loudness of one compared edition
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComparedEdition {
+    pub release_id: String,
+    pub track_count: usize,
+    pub total_duration_ms: i64,
+    pub format: Option<String>,
+    pub sample_rate: Option<i64>,
+    pub bits_per_sample: Option<i64>,
+    pub bitrate_kbps: Option<i64>,
+    /// DR score, once the edition's tracks have been measured
+    pub dynamic_range: Option<i32>,
+    pub loudness: LoudnessMeasurement,
+}
+
+/// Loudness of an edition; measuring decodes all its audio, so it arrives late
+#[derive(Clone, Debug, PartialEq)]
+pub enum LoudnessMeasurement {
+    Measuring,
+    Measured { peak_dbfs: f64, rms_dbfs: f64 },
+    Unavailable,
+}
+
+/// A track and its counterpart on the other edition, if there is one
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComparedTrack {
+    pub left: Option<ComparedTrackSide>,
+    pub right: Option<ComparedTrackSide>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComparedTrackSide {
+    pub title: String,
+    pub duration_ms: Option<i64>,
+}
+
 /// File display info
 #[derive(Clone, Debug, PartialEq)]
 pub struct File {
@@ -112,9 +625,12 @@ pub struct Image {
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ImportStatus {
     Preparing,
+    Queued,
     Importing,
+    Paused,
     Complete,
     Failed,
+    Cancelled,
 }
 
 /// Active import for UI display
@@ -129,6 +645,14 @@ pub struct ActiveImport {
     pub progress_percent: Option<u8>,
     pub release_id: Option<String>,
     pub cover_url: Option<String>,
+    /// Store throughput, once every file is stored
+    pub store_bytes_per_sec: Option<u64>,
+    pub error_message: Option<String>,
+    /// Queue actions the import allows
+    pub can_pause: bool,
+    pub can_resume: bool,
+    pub can_cancel: bool,
+    pub can_retry: bool,
 }
 
 // ============================================================================
@@ -157,6 +681,111 @@ pub enum IdentifyMode {
     MultipleExactMatches(String),
     /// No exact match; user searches manually
     ManualSearch,
+    /// Not in any database; user types in the release details
+    ManualEntry(ManualEntry),
+}
+
+/// Release details typed in by the user, for own recordings, rehearsal tapes
+/// and bootlegs that no metadata database knows about
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ManualEntry {
+    pub title: String,
+    pub artist: String,
+    pub year: String,
+    /// One title per audio file, in file order
+    pub tracks: Vec<String>,
+    /// Cover image URL, typed in or from a series template. Selected at
+    /// confirmation.
+    pub cover_url: Option<String>,
+}
+
+/// A saved series for manual entry (monthly mixes, podcast episodes), shown
+/// with the details its next release gets
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReleaseTemplate {
+    pub id: String,
+    pub artist: String,
+    /// Album title with `{n}` for the volume number
+    pub title_pattern: String,
+    /// Album title of the next release in the series
+    pub next_title: String,
+    pub cover_url: Option<String>,
+}
+
+impl ManualEntry {
+    /// Start from what detection found: tags and folder name for the release,
+    /// file names for the tracks. CUE/FLAC tracks start out blank.
+    pub fn from_detected(metadata: &FolderMetadata, files: &CategorizedFileInfo) -> Self {
+        let tracks = match &files.audio {
+            AudioContentInfo::TrackFiles(tracks) => tracks
+                .iter()
+                .map(|file| track_title_from_filename(&file.name))
+                .collect(),
+            AudioContentInfo::CueFlacPairs(pairs) => {
+                vec![String::new(); pairs.iter().map(|p| p.track_count).sum()]
+            }
+        };
+        ManualEntry {
+            title: metadata.album.clone().unwrap_or_default(),
+            artist: metadata.artist.clone().unwrap_or_default(),
+            year: metadata.year.map(|y| y.to_string()).unwrap_or_default(),
+            tracks,
+            cover_url: None,
+        }
+    }
+
+    pub fn from_candidate(candidate: &MatchCandidate) -> Self {
+        ManualEntry {
+            title: candidate.title.clone(),
+            artist: candidate.artist.clone(),
+            year: candidate.year.clone().unwrap_or_default(),
+            tracks: candidate.track_titles.clone(),
+            cover_url: candidate.cover_url.clone(),
+        }
+    }
+
+    /// Fill in the next release of a template's series, keeping the tracks
+    pub fn apply_template(&mut self, template: &ReleaseTemplate) {
+        self.artist = template.artist.clone();
+        self.title = template.next_title.clone();
+        self.cover_url = template.cover_url.clone();
+    }
+
+    pub fn into_candidate(self) -> MatchCandidate {
+        let year = self.year.trim().to_string();
+        MatchCandidate {
+            title: self.title.trim().to_string(),
+            artist: self.artist.trim().to_string(),
+            year: (!year.is_empty()).then_some(year),
+            cover_url: self.cover_url,
+            cover_thumbnail_url: None,
+            format: None,
+            country: None,
+            label: None,
+            catalog_number: None,
+            barcode: None,
+            source_type: MatchSourceType::Manual,
+            original_year: None,
+            musicbrainz_release_id: None,
+            musicbrainz_release_group_id: None,
+            discogs_release_id: None,
+            discogs_master_id: None,
+            track_titles: self.tracks,
+        }
+    }
+}
+
+/// "01 - Intro.flac" -> "Intro"
+fn track_title_from_filename(name: &str) -> String {
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    let title = stem
+        .trim_start_matches(|c: char| c.is_ascii_digit())
+        .trim_start_matches([' ', '-', '.', '_']);
+    if title.is_empty() {
+        stem.to_string()
+    } else {
+        title.to_string()
+    }
 }
 
 /// Search tab for manual search panel
@@ -174,6 +803,8 @@ pub enum SearchSource {
     #[default]
     MusicBrainz,
     Discogs,
+    /// Query MusicBrainz and Discogs in parallel and merge the results
+    Both,
 }
 
 impl SearchSource {
@@ -181,15 +812,43 @@ impl SearchSource {
         match self {
             SearchSource::MusicBrainz => "MusicBrainz",
             SearchSource::Discogs => "Discogs",
+            SearchSource::Both => "MusicBrainz and Discogs",
         }
     }
 }
 
+/// Result count reported by a single metadata source for a manual search
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SourceResultCount {
+    pub source: MatchSourceType,
+    /// Total matches the source reports for the query
+    pub total: u32,
+    /// Whether the source has pages beyond those already loaded
+    pub has_more: bool,
+    /// Pages fetched from the source so far (also its next page index)
+    pub pages_loaded: u32,
+}
+
 /// Match candidate source type
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum MatchSourceType {
     MusicBrainz,
     Discogs,
+    /// Details entered by hand, no database behind them
+    Manual,
+    /// The release a folder was imported as before, kept in its `.bae` folder
+    Stored,
+}
+
+impl MatchSourceType {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            MatchSourceType::MusicBrainz => "MusicBrainz",
+            MatchSourceType::Discogs => "Discogs",
+            MatchSourceType::Manual => "Manual entry",
+            MatchSourceType::Stored => "Imported before",
+        }
+    }
 }
 
 /// Match candidate for UI display
@@ -199,10 +858,13 @@ pub struct MatchCandidate {
     pub artist: String,
     pub year: Option<String>,
     pub cover_url: Option<String>,
+    /// Small cover for the match list, fetched after the candidate is shown
+    pub cover_thumbnail_url: Option<String>,
     pub format: Option<String>,
     pub country: Option<String>,
     pub label: Option<String>,
     pub catalog_number: Option<String>,
+    pub barcode: Option<String>,
     pub source_type: MatchSourceType,
     /// Original year / first release date (for MusicBrainz)
     pub original_year: Option<String>,
@@ -215,6 +877,61 @@ pub struct MatchCandidate {
     pub discogs_release_id: Option<String>,
     /// Discogs master ID
     pub discogs_master_id: Option<String>,
+    /// Track titles of a manual entry, in file order (empty for database matches)
+    pub track_titles: Vec<String>,
+}
+
+impl MatchCandidate {
+    /// Whether `other` is the same physical release.
+    ///
+    /// Every identifier both candidates carry (barcode, catalog number) has to
+    /// agree, and they have to share at least one. Identifiers are reduced to
+    /// uppercase alphanumerics so formatting differences between MusicBrainz
+    /// and Discogs ("SWR-001" vs "SWR 001") don't matter.
+    pub fn same_release(&self, other: &MatchCandidate) -> bool {
+        fn normalize(s: &Option<String>) -> Option<String> {
+            let normalized: String = s
+                .as_deref()?
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric())
+                .map(|c| c.to_ascii_uppercase())
+                .collect();
+            (!normalized.is_empty()).then_some(normalized)
+        }
+        let shared = [
+            (normalize(&self.barcode), normalize(&other.barcode)),
+            (
+                normalize(&self.catalog_number),
+                normalize(&other.catalog_number),
+            ),
+        ];
+        let mut compared = false;
+        for pair in shared {
+            if let (Some(a), Some(b)) = pair {
+                if a != b {
+                    return false;
+                }
+                compared = true;
+            }
+        }
+        compared
+    }
+}
+
+/// Append `incoming` to `candidates`, dropping any candidate that a different
+/// source already returned (see [`MatchCandidate::same_release`]).
+///
+/// Duplicates within one source are kept: MusicBrainz and Discogs both list
+/// regional pressings that share a catalog number as separate releases.
+pub fn append_deduplicated(candidates: &mut Vec<MatchCandidate>, incoming: Vec<MatchCandidate>) {
+    for candidate in incoming {
+        let duplicate = candidates.iter().any(|existing| {
+            existing.source_type != candidate.source_type && existing.same_release(&candidate)
+        });
+        if !duplicate {
+            candidates.push(candidate);
+        }
+    }
 }
 
 /// Detected folder metadata for UI display
@@ -277,6 +994,8 @@ pub struct CategorizedFileInfo {
     pub artwork: Vec<FileInfo>,
     /// Document files (.log, .txt, .nfo) - CUE files in pairs are NOT here
     pub documents: Vec<FileInfo>,
+    /// Video extras (.mkv, .mp4)
+    pub videos: Vec<FileInfo>,
     /// Number of corrupt/incomplete audio files (not included in `audio`)
     pub bad_audio_count: usize,
     /// Number of corrupt image files (not included in `artwork`)
@@ -290,13 +1009,147 @@ impl CategorizedFileInfo {
             AudioContentInfo::CueFlacPairs(pairs) => pairs.len() * 2,
             AudioContentInfo::TrackFiles(tracks) => tracks.len(),
         };
-        audio_count + self.artwork.len() + self.documents.len()
+        audio_count + self.artwork.len() + self.documents.len() + self.videos.len()
     }
 
     /// Check if empty
     pub fn is_empty(&self) -> bool {
         self.total_count() == 0
     }
+
+    /// Size of each file, one entry per file as counted by [`Self::total_count`].
+    /// A CUE/FLAC pair's combined size goes on its FLAC entry.
+    pub fn file_sizes(&self) -> Vec<u64> {
+        let audio: Vec<u64> = match &self.audio {
+            AudioContentInfo::CueFlacPairs(pairs) => {
+                pairs.iter().flat_map(|p| [p.total_size, 0]).collect()
+            }
+            AudioContentInfo::TrackFiles(tracks) => tracks.iter().map(|f| f.size).collect(),
+        };
+        let others = self
+            .artwork
+            .iter()
+            .chain(&self.documents)
+            .chain(&self.videos);
+        audio.into_iter().chain(others.map(|f| f.size)).collect()
+    }
+}
+
+/// Outcome of fully decoding one audio file before import
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioFileQuality {
+    pub name: String,
+    /// The file couldn't be read or opened by the decoder
+    pub error: Option<String>,
+    pub decode_errors: u32,
+    /// Decoded minus declared duration, when they disagree
+    pub duration_mismatch_ms: Option<i64>,
+    pub clipped_runs: u32,
+    /// The decoder workaround the file needed to open, if any
+    pub decoder_fallback: Option<String>,
+}
+
+impl AudioFileQuality {
+    pub fn is_damaged(&self) -> bool {
+        self.error.is_some() || self.decode_errors > 0 || self.duration_mismatch_ms.is_some()
+    }
+}
+
+/// Per-release audio check, run on request from the confirm step
+#[derive(Clone, Debug, PartialEq)]
+pub enum AudioQualityCheck {
+    Checking,
+    Done(Vec<AudioFileQuality>),
+}
+
+/// Estimated cloud footprint of a release, for the import confirm step
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StorageEstimate {
+    pub object_count: usize,
+    /// Size as stored, after encryption
+    pub stored_bytes: u64,
+    pub monthly_cost: f64,
+}
+
+/// Files in a release that hold the same recording, and the one to import
+#[derive(Clone, Debug, PartialEq)]
+pub struct DuplicateAudioGroup {
+    /// Full paths of the copies
+    pub files: Vec<String>,
+    /// Index into `files` of the copy to keep
+    pub keep: usize,
+}
+
+/// Per-release duplicate recording check, run on request from the confirm step
+#[derive(Clone, Debug, PartialEq)]
+pub enum DuplicateAudioCheck {
+    Checking,
+    Done(Vec<DuplicateAudioGroup>),
+}
+
+impl DuplicateAudioCheck {
+    /// Copies the user chose not to import
+    pub fn excluded_files(&self) -> Vec<String> {
+        match self {
+            DuplicateAudioCheck::Checking => Vec::new(),
+            DuplicateAudioCheck::Done(groups) => groups
+                .iter()
+                .flat_map(|g| {
+                    g.files
+                        .iter()
+                        .enumerate()
+                        .filter(move |(i, _)| *i != g.keep)
+                        .map(|(_, f)| f.clone())
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A track on a vinyl side and where it starts in the side's recording
+#[derive(Clone, Debug, PartialEq)]
+pub struct NeedleDropTrack {
+    /// Position as printed on the release, e.g. "A1"
+    pub position: String,
+    pub title: String,
+    pub start_ms: u64,
+    /// Length the release lists, if it does
+    pub expected_duration_ms: Option<u64>,
+}
+
+/// A vinyl side recorded as one file, split into the release's tracks
+#[derive(Clone, Debug, PartialEq)]
+pub struct NeedleDropSide {
+    /// Full path of the recording
+    pub path: String,
+    pub name: String,
+    pub duration_ms: u64,
+    /// Level across the side, from 0.0 to 1.0
+    pub waveform: Vec<f32>,
+    pub tracks: Vec<NeedleDropTrack>,
+}
+
+impl NeedleDropSide {
+    /// How long a track runs: up to the next track's start, or the end of the side
+    pub fn track_duration_ms(&self, index: usize) -> u64 {
+        let Some(track) = self.tracks.get(index) else {
+            return 0;
+        };
+        let end = self
+            .tracks
+            .get(index + 1)
+            .map_or(self.duration_ms, |next| next.start_ms);
+        end.saturating_sub(track.start_ms)
+    }
+}
+
+/// Per-release split of vinyl side recordings into tracks, set up on request
+/// from the confirm step
+#[derive(Clone, Debug, PartialEq)]
+pub enum NeedleDropCheck {
+    Analyzing,
+    Ready(Vec<NeedleDropSide>),
+    Failed(String),
 }
 
 /// Torrent file info for UI display
@@ -306,6 +1159,15 @@ pub struct TorrentFileInfo {
     pub size: i64,
 }
 
+/// A release folder inside a multi-album torrent
+#[derive(Clone, Debug, PartialEq)]
+pub struct TorrentAlbumFolderInfo {
+    /// Folder path relative to the torrent root
+    pub path: String,
+    pub file_count: usize,
+    pub total_size: i64,
+}
+
 /// Torrent info for UI display
 #[derive(Clone, Debug, PartialEq)]
 pub struct TorrentInfo {
@@ -321,6 +1183,21 @@ pub struct TorrentInfo {
     pub files: Vec<TorrentFileInfo>,
 }
 
+/// Live state of a torrent download, for the import page
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TorrentDownloadHealth {
+    /// Payload bytes per second
+    pub download_rate: u64,
+    pub peers: u32,
+    pub seeds: u32,
+    pub downloaded_bytes: u64,
+    pub total_bytes: u64,
+    /// None while nothing is coming in
+    pub eta_secs: Option<u64>,
+    /// Share of each stretch of the torrent that's downloaded, 0.0 to 1.0
+    pub piece_bar: Vec<f32>,
+}
+
 /// Selected cover for import UI
 #[derive(Clone, Debug, PartialEq, Store)]
 pub enum SelectedCover {
@@ -368,4 +1245,420 @@ pub struct DetectedCandidate {
 pub struct CdDriveInfo {
     pub device_path: String,
     pub name: String,
+    /// Busy ripping another disc
+    pub is_ripping: bool,
+}
+
+/// Sample format requested from the output device
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputBitDepth {
+    Auto,
+    Float32,
+    Int24,
+    Int16,
+}
+
+impl OutputBitDepth {
+    pub const ALL: [OutputBitDepth; 4] = [
+        OutputBitDepth::Auto,
+        OutputBitDepth::Float32,
+        OutputBitDepth::Int24,
+        OutputBitDepth::Int16,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            OutputBitDepth::Auto => "auto",
+            OutputBitDepth::Float32 => "float32",
+            OutputBitDepth::Int24 => "int24",
+            OutputBitDepth::Int16 => "int16",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OutputBitDepth::Auto => "Device default",
+            OutputBitDepth::Float32 => "32-bit float",
+            OutputBitDepth::Int24 => "24-bit",
+            OutputBitDepth::Int16 => "16-bit",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<OutputBitDepth> {
+        OutputBitDepth::ALL.into_iter().find(|d| d.key() == key)
+    }
+}
+
+/// Dither applied when output is reduced to 16 bits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dither {
+    Off,
+    Tpdf,
+    NoiseShaped,
+}
+
+impl Dither {
+    pub const ALL: [Dither; 3] = [Dither::Tpdf, Dither::NoiseShaped, Dither::Off];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Dither::Off => "off",
+            Dither::Tpdf => "tpdf",
+            Dither::NoiseShaped => "noise_shaped",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Dither::Off => "Off",
+            Dither::Tpdf => "TPDF",
+            Dither::NoiseShaped => "Noise-shaped",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Dither> {
+        Dither::ALL.into_iter().find(|d| d.key() == key)
+    }
+}
+
+/// Format negotiated with the output device, for the diagnostics popover
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutputFormatInfo {
+    pub device_name: String,
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// e.g. "24-bit" or "32-bit float"
+    pub sample_format: String,
+    /// Set only when the output is dithered
+    pub dither: Option<Dither>,
+    /// Whether bae holds the device exclusively
+    pub exclusive: bool,
+}
+
+/// What a verification run found wrong with a file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerificationIssueKind {
+    /// Tracked in the library but gone from storage
+    Missing,
+    /// Present but unreadable, or different from what was stored
+    Corrupt,
+    /// In a release folder but not part of any release
+    Orphaned,
+}
+
+impl VerificationIssueKind {
+    pub const ALL: [VerificationIssueKind; 3] = [
+        VerificationIssueKind::Missing,
+        VerificationIssueKind::Corrupt,
+        VerificationIssueKind::Orphaned,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VerificationIssueKind::Missing => "Missing",
+            VerificationIssueKind::Corrupt => "Corrupt",
+            VerificationIssueKind::Orphaned => "Orphaned",
+        }
+    }
+
+    /// What the Repair button does for this kind of issue
+    pub fn repair_label(&self) -> &'static str {
+        match self {
+            VerificationIssueKind::Missing | VerificationIssueKind::Corrupt => "Restore",
+            VerificationIssueKind::Orphaned => "Delete",
+        }
+    }
+}
+
+/// A file a verification run found missing, damaged or untracked
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerificationIssue {
+    pub id: String,
+    pub kind: VerificationIssueKind,
+    pub filename: String,
+    pub storage_profile_name: String,
+    pub error: String,
+    /// When any run first reported this file (unix seconds)
+    pub first_seen: i64,
+    /// Fixed since the run that reported it
+    pub repaired: bool,
+}
+
+/// One pass of the library verification job
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerificationRun {
+    pub id: String,
+    /// Started by the schedule rather than by hand
+    pub scheduled: bool,
+    /// Unix seconds
+    pub started_at: i64,
+    /// False while running, or if the app quit mid-run
+    pub finished: bool,
+    pub files_checked: i64,
+    pub issues: Vec<VerificationIssue>,
+}
+
+/// An encrypted copy of the library database in a cloud bucket
+#[derive(Clone, Debug, PartialEq)]
+pub struct LibrarySnapshot {
+    pub location: String,
+    /// Unix seconds
+    pub created_at: i64,
+    pub size_bytes: u64,
+}
+
+/// A subsystem shown in the health panel
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServiceKind {
+    Database,
+    Cache,
+    CloudStorage,
+    Torrent,
+    PlaybackOutput,
+    Keyring,
+}
+
+impl ServiceKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ServiceKind::Database => "Library database",
+            ServiceKind::Cache => "File cache",
+            ServiceKind::CloudStorage => "Cloud storage",
+            ServiceKind::Torrent => "Torrent session",
+            ServiceKind::PlaybackOutput => "Audio output",
+            ServiceKind::Keyring => "Keyring",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServiceStatus {
+    Running,
+    /// Starts on first use, or isn't set up
+    NotStarted,
+    Failed,
+}
+
+/// How a subsystem was doing at its last check
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServiceHealth {
+    pub kind: ServiceKind,
+    pub status: ServiceStatus,
+    /// Seconds since it started or recovered, while running
+    pub uptime_secs: Option<u64>,
+    pub last_error: Option<String>,
+    /// Unix seconds
+    pub last_error_at: Option<i64>,
+    /// Can be restarted without restarting bae
+    pub can_restart: bool,
+}
+
+/// A torrent whose original download is kept for seeding
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeedingTorrent {
+    pub release_id: String,
+    pub name: String,
+    /// False when paused, or stopped after meeting a goal
+    pub is_seeding: bool,
+    pub size_bytes: i64,
+    pub uploaded_bytes: i64,
+    /// Uploaded per byte downloaded
+    pub ratio: f64,
+    pub seeding_secs: i64,
+    pub ratio_goal: Option<f64>,
+    pub time_goal_hours: Option<u32>,
+    pub goal_met: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(
+        source_type: MatchSourceType,
+        title: &str,
+        barcode: Option<&str>,
+        catalog_number: Option<&str>,
+    ) -> MatchCandidate {
+        MatchCandidate {
+            title: title.to_string(),
+            artist: "Artist".to_string(),
+            year: None,
+            cover_url: None,
+            cover_thumbnail_url: None,
+            format: None,
+            country: None,
+            label: None,
+            catalog_number: catalog_number.map(str::to_string),
+            barcode: barcode.map(str::to_string),
+            source_type,
+            original_year: None,
+            musicbrainz_release_id: None,
+            musicbrainz_release_group_id: None,
+            discogs_release_id: None,
+            discogs_master_id: None,
+            track_titles: vec![],
+        }
+    }
+
+    fn titles(candidates: &[MatchCandidate]) -> Vec<&str> {
+        candidates.iter().map(|c| c.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_drops_release_the_other_source_already_returned() {
+        let mut candidates = vec![candidate(
+            MatchSourceType::MusicBrainz,
+            "mb",
+            None,
+            Some("SWR-001"),
+        )];
+        append_deduplicated(
+            &mut candidates,
+            vec![candidate(
+                MatchSourceType::Discogs,
+                "discogs",
+                Some("5 012345 678900"),
+                Some("swr 001"),
+            )],
+        );
+        assert_eq!(titles(&candidates), vec!["mb"]);
+    }
+
+    #[test]
+    fn test_keeps_candidates_whose_shared_identifiers_disagree() {
+        let mut candidates = vec![candidate(
+            MatchSourceType::MusicBrainz,
+            "mb",
+            Some("5012345678900"),
+            Some("SWR-001"),
+        )];
+        append_deduplicated(
+            &mut candidates,
+            vec![candidate(
+                MatchSourceType::Discogs,
+                "discogs",
+                Some("5012345678917"),
+                Some("SWR-001"),
+            )],
+        );
+        assert_eq!(titles(&candidates), vec!["mb", "discogs"]);
+    }
+
+    #[test]
+    fn test_keeps_candidates_without_a_shared_identifier() {
+        let mut candidates = vec![candidate(
+            MatchSourceType::MusicBrainz,
+            "mb",
+            Some("5012345678900"),
+            None,
+        )];
+        append_deduplicated(
+            &mut candidates,
+            vec![candidate(
+                MatchSourceType::Discogs,
+                "discogs",
+                None,
+                Some("SWR-001"),
+            )],
+        );
+        assert_eq!(titles(&candidates), vec!["mb", "discogs"]);
+    }
+
+    #[test]
+    fn test_keeps_duplicates_from_the_same_source() {
+        let mut candidates = vec![candidate(
+            MatchSourceType::Discogs,
+            "uk",
+            None,
+            Some("SWR-001"),
+        )];
+        append_deduplicated(
+            &mut candidates,
+            vec![candidate(
+                MatchSourceType::Discogs,
+                "us",
+                None,
+                Some("SWR-001"),
+            )],
+        );
+        assert_eq!(titles(&candidates), vec!["uk", "us"]);
+    }
+
+    fn album(title: &str, date_added: i64, play_count: u32, last_played: Option<i64>) -> Album {
+        Album {
+            id: title.to_string(),
+            title: title.to_string(),
+            year: None,
+            cover_url: None,
+            is_compilation: false,
+            date_added,
+            play_count,
+            last_played,
+            palette: None,
+            quality: None,
+            is_offline: false,
+        }
+    }
+
+    fn album_titles(albums: &[Album]) -> Vec<&str> {
+        albums.iter().map(|a| a.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_library_sorts_put_the_most_recent_and_most_played_first() {
+        // In title order, as the library loads them
+        let albums = vec![
+            album("Amnesiac", 300, 4, None),
+            album("Kid A", 100, 9, Some(50)),
+            album("OK Computer", 200, 4, Some(80)),
+        ];
+
+        let sorted = |sort: LibrarySort| {
+            let mut albums = albums.clone();
+            sort.apply(&mut albums);
+            albums
+        };
+
+        assert_eq!(
+            album_titles(&sorted(LibrarySort::Title)),
+            vec!["Amnesiac", "Kid A", "OK Computer"]
+        );
+        assert_eq!(
+            album_titles(&sorted(LibrarySort::DateAdded)),
+            vec!["Amnesiac", "OK Computer", "Kid A"]
+        );
+        // Never played sorts last
+        assert_eq!(
+            album_titles(&sorted(LibrarySort::LastPlayed)),
+            vec!["OK Computer", "Kid A", "Amnesiac"]
+        );
+        // Ties keep title order
+        assert_eq!(
+            album_titles(&sorted(LibrarySort::PlayCount)),
+            vec!["Kid A", "Amnesiac", "OK Computer"]
+        );
+    }
+
+    #[test]
+    fn test_library_settings_keys_round_trip() {
+        for sort in LibrarySort::ALL {
+            assert_eq!(LibrarySort::from_key(sort.key()), Some(sort));
+        }
+        for view in StartupView::ALL {
+            assert_eq!(StartupView::from_key(view.key()), Some(view));
+        }
+        for quality in AudioQuality::ALL {
+            assert_eq!(AudioQuality::from_key(quality.key()), Some(quality));
+        }
+        assert_eq!(LibrarySort::from_key("artist"), None);
+    }
+
+    #[test]
+    fn test_track_click_action_keys_round_trip() {
+        for action in TrackClickAction::ALL {
+            assert_eq!(TrackClickAction::from_key(action.key()), Some(action));
+        }
+        assert_eq!(TrackClickAction::from_key("double_click"), None);
+    }
 }
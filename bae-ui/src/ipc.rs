@@ -0,0 +1,181 @@
+//! Guarding calls across the webview bridge
+//!
+//! On desktop, every web-sys call is a round trip to the webview. A call that
+//! races window teardown or a component unmounting (the imports dropdown open
+//! while navigating, say) can come back empty, and the bridge panics decoding
+//! it: "Failed to decode return value: U8BufferEmpty". Left alone that takes
+//! the whole app down.
+//!
+//! [`guard`] and [`guard_async`] turn that panic into an [`IpcError`] and tell
+//! the nearest [`IpcBoundary`](crate::components::IpcBoundary), which shows a
+//! toast and remounts its children. Any other panic carries on as before.
+
+use dioxus::prelude::*;
+use std::any::Any;
+use std::cell::Cell;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::task::Poll;
+use tracing::warn;
+
+/// What the bridge panics with when a call comes back empty or garbled
+const DECODE_FAILURE: &str = "Failed to decode return value";
+
+thread_local! {
+    /// Depth of guarded calls on this thread
+    static GUARDED: Cell<u32> = const { Cell::new(0) };
+}
+
+/// A webview call that failed partway, leaving the UI possibly out of step
+#[derive(Debug, Clone, PartialEq)]
+pub struct IpcError {
+    pub message: String,
+}
+
+impl std::fmt::Display for IpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "webview call failed: {}", self.message)
+    }
+}
+
+impl std::error::Error for IpcError {}
+
+/// Remount signal shared by an [`IpcBoundary`](crate::components::IpcBoundary)
+/// with everything under it
+#[derive(Clone, Copy)]
+pub(crate) struct IpcBoundaryContext {
+    pub(crate) failure: Signal<Option<IpcError>>,
+}
+
+/// Run `f`, which calls into the webview, turning a failed call into an error
+pub fn guard<T>(f: impl FnOnce() -> T) -> Result<T, IpcError> {
+    GUARDED.with(|depth| depth.set(depth.get() + 1));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    GUARDED.with(|depth| depth.set(depth.get() - 1));
+    match result {
+        Ok(value) => Ok(value),
+        Err(payload) => match panic_message(payload.as_ref()) {
+            Some(message) if is_decode_failure(message) => {
+                let error = IpcError {
+                    message: message.to_string(),
+                };
+                report(error.clone());
+                Err(error)
+            }
+            _ => panic::resume_unwind(payload),
+        },
+    }
+}
+
+/// [`guard`] for a future, checked on every poll
+pub async fn guard_async<F: Future>(future: F) -> Result<F::Output, IpcError> {
+    let mut future = std::pin::pin!(future);
+    std::future::poll_fn(move |cx| match guard(|| future.as_mut().poll(cx)) {
+        Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+        Ok(Poll::Pending) => Poll::Pending,
+        Err(e) => Poll::Ready(Err(e)),
+    })
+    .await
+}
+
+/// `use_effect` for effects that call into the webview
+pub fn use_guarded_effect(mut f: impl FnMut() + 'static) -> Effect {
+    use_effect(move || {
+        let _ = guard(&mut f);
+    })
+}
+
+/// Whether the current thread is inside [`guard`], so a panic hook can leave
+/// recoverable failures to it
+pub fn in_guarded_call() -> bool {
+    GUARDED.with(|depth| depth.get() > 0)
+}
+
+/// Whether a panic message is the bridge failing to decode a reply
+pub fn is_decode_failure(message: &str) -> bool {
+    message.contains(DECODE_FAILURE)
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> Option<&str> {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+}
+
+fn report(error: IpcError) {
+    warn!("Recovered from {}", error);
+    if let Some(mut boundary) = try_consume_context::<IpcBoundaryContext>() {
+        boundary.failure.set(Some(error));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECODE_PANIC: &str = "Failed to decode return value: U8BufferEmpty";
+
+    fn decode_panic() {
+        panic!("{}", DECODE_PANIC)
+    }
+
+    fn other_panic() {
+        panic!("index out of bounds")
+    }
+
+    #[test]
+    fn test_guard_passes_value_through() {
+        assert_eq!(guard(|| 7), Ok(7));
+        assert!(!in_guarded_call());
+    }
+
+    #[test]
+    fn test_guard_catches_decode_failure() {
+        let result = guard(decode_panic);
+        assert_eq!(
+            result,
+            Err(IpcError {
+                message: DECODE_PANIC.to_string(),
+            })
+        );
+        assert!(!in_guarded_call());
+    }
+
+    #[test]
+    fn test_guard_reraises_other_panics() {
+        let outer = panic::catch_unwind(|| guard(other_panic));
+        let payload = outer.unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), Some("index out of bounds"));
+        assert!(!in_guarded_call());
+    }
+
+    #[test]
+    fn test_nested_guards_track_depth() {
+        let result = guard(|| {
+            assert!(in_guarded_call());
+            assert!(guard(decode_panic).is_err());
+            // Still inside the outer guard
+            in_guarded_call()
+        });
+        assert_eq!(result, Ok(true));
+        assert!(!in_guarded_call());
+    }
+
+    fn poll_once<F: Future>(future: F) -> Poll<F::Output> {
+        let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+        std::pin::pin!(future).poll(&mut cx)
+    }
+
+    #[test]
+    fn test_guard_async_catches_decode_failure_while_polling() {
+        assert_eq!(poll_once(guard_async(async { 3 })), Poll::Ready(Ok(3)));
+
+        let failed = poll_once(guard_async(async {
+            std::future::ready(()).await;
+            decode_panic()
+        }));
+        assert!(matches!(failed, Poll::Ready(Err(_))));
+        assert!(!in_guarded_call());
+    }
+}
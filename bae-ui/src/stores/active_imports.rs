@@ -8,9 +8,24 @@ pub enum ImportOperationStatus {
     #[default]
     Pending,
     Preparing,
+    /// Waiting in the import queue for a free slot
+    Queued,
     Importing,
+    /// Held in the queue until resumed
+    Paused,
     Complete,
     Failed,
+    /// Stopped by the user, release removed
+    Cancelled,
+}
+
+/// Where an import's files come from
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ImportKind {
+    #[default]
+    Folder,
+    Torrent,
+    Cd,
 }
 
 /// Preparation step during import
@@ -18,6 +33,8 @@ pub enum ImportOperationStatus {
 pub enum PrepareStep {
     ParsingMetadata,
     DownloadingCoverArt,
+    ExtractingDiscImages,
+    SplittingSides,
     DiscoveringFiles,
     ValidatingTracks,
     SavingToDatabase,
@@ -31,6 +48,7 @@ pub struct ActiveImport {
     pub album_title: String,
     pub artist_name: String,
     pub status: ImportOperationStatus,
+    pub kind: ImportKind,
     pub current_step: Option<PrepareStep>,
     pub progress_percent: Option<u8>,
     pub release_id: Option<String>,
@@ -38,6 +56,36 @@ pub struct ActiveImport {
     pub cover_art_url: Option<String>,
     /// Stored cover image ID (shown after import complete)
     pub cover_image_id: Option<String>,
+    /// How fast files were compressed, encrypted and stored, once they all are
+    pub store_bytes_per_sec: Option<u64>,
+    /// Why the import failed
+    pub error_message: Option<String>,
+}
+
+impl ActiveImport {
+    /// A running import starts over when resumed, which only folder
+    /// imports can do
+    pub fn can_pause(&self) -> bool {
+        match self.status {
+            ImportOperationStatus::Queued => self.kind != ImportKind::Cd,
+            ImportOperationStatus::Importing => self.kind == ImportKind::Folder,
+            _ => false,
+        }
+    }
+
+    pub fn can_resume(&self) -> bool {
+        self.status == ImportOperationStatus::Paused
+    }
+
+    pub fn can_cancel(&self) -> bool {
+        self.can_pause() || self.can_resume()
+    }
+
+    pub fn can_retry(&self) -> bool {
+        self.status == ImportOperationStatus::Failed
+            && self.kind == ImportKind::Folder
+            && self.release_id.is_some()
+    }
 }
 
 /// UI state for active imports (shown in toolbar dropdown)
@@ -1,6 +1,9 @@
 //! Album detail state store
 
-use crate::display_types::{Album, Artist, File, Image, Release, Track};
+use crate::display_types::{
+    Album, Artist, File, Image, Release, ReleaseArchiveStatus, ReleaseComparison, ReleaseReference,
+    Track,
+};
 use dioxus::prelude::*;
 
 /// State for the album detail view
@@ -22,10 +25,14 @@ pub struct AlbumDetailState {
     pub releases: Vec<Release>,
     /// Files for the current release
     pub files: Vec<File>,
+    /// Video extras of the current release
+    pub videos: Vec<File>,
     /// Images for this album
     pub images: Vec<Image>,
     /// Currently selected release ID
     pub selected_release_id: Option<String>,
+    /// Edition the album plays by default, if the user picked one
+    pub preferred_release_id: Option<String>,
     /// Whether the album data is loading
     pub loading: bool,
     /// Error message if loading failed
@@ -34,4 +41,21 @@ pub struct AlbumDetailState {
     pub import_progress: Option<u8>,
     /// Import error message if import failed
     pub import_error: Option<String>,
+    /// Archive status of the selected release; None unless it's on an archival profile
+    /// and not plainly available
+    pub archive_status: Option<ReleaseArchiveStatus>,
+    /// Set when the selected release is indexed from a reference profile
+    pub reference: Option<ReleaseReference>,
+    /// DR score of the selected release, once its tracks have been measured
+    pub dynamic_range: Option<i32>,
+    /// Bytes each release's files take up in bae's storage, by release ID
+    pub stored_bytes: std::collections::HashMap<String, u64>,
+    /// Edition comparison last requested from the compare view
+    pub comparison: Option<ReleaseComparison>,
+    /// Whether the page is in edit mode
+    pub editing: bool,
+    /// Why saving the edits failed
+    pub edit_error: Option<String>,
+    /// Whether any release is in cloud storage, so there's something to keep offline
+    pub can_keep_offline: bool,
 }
@@ -5,12 +5,21 @@
 
 use super::active_imports::ActiveImportsUiState;
 use super::album_detail::AlbumDetailState;
+use super::client_shares::ClientSharesState;
 use super::config::ConfigState;
+use super::health::HealthState;
+use super::history::HistoryState;
 use super::import::ImportState;
 use super::library::LibraryState;
+use super::maintenance::MaintenanceState;
 use super::playback::PlaybackUiState;
+use super::seeding::SeedingState;
+use super::snapshots::SnapshotsState;
 use super::storage_profiles::StorageProfilesState;
+use super::tracks::TracksState;
 use super::ui::UiState;
+use super::undo::UndoState;
+use super::verification::VerificationState;
 use dioxus::prelude::*;
 
 /// Top-level application state combining all sub-states
@@ -22,6 +31,10 @@ pub struct AppState {
     pub library: LibraryState,
     /// Album detail view state
     pub album_detail: AlbumDetailState,
+    /// Flat all-tracks view state
+    pub tracks: TracksState,
+    /// Listening history view state
+    pub history: HistoryState,
     /// Active imports shown in toolbar dropdown
     pub active_imports: ActiveImportsUiState,
     /// Playback state (playing/paused, queue)
@@ -32,4 +45,18 @@ pub struct AppState {
     pub config: ConfigState,
     /// Storage profiles
     pub storage_profiles: StorageProfilesState,
+    /// Albums shared with Subsonic clients
+    pub client_shares: ClientSharesState,
+    /// Library verification history
+    pub verification: VerificationState,
+    /// Library maintenance jobs
+    pub maintenance: MaintenanceState,
+    /// Library database snapshots
+    pub snapshots: SnapshotsState,
+    /// Torrents kept for seeding
+    pub seeding: SeedingState,
+    /// Last undo or redo, for its toast
+    pub undo: UndoState,
+    /// Subsystem health, for Settings → About
+    pub health: HealthState,
 }
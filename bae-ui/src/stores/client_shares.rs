@@ -0,0 +1,12 @@
+//! Client shares state store
+
+use crate::components::settings::ClientShare;
+use dioxus::prelude::*;
+
+/// Client shares state
+#[derive(Clone, Debug, Default, PartialEq, Store)]
+pub struct ClientSharesState {
+    pub shares: Vec<ClientShare>,
+    /// Why the last load or change failed
+    pub error: Option<String>,
+}
@@ -1,5 +1,9 @@
 //! Application configuration state store
 
+use crate::display_types::{
+    AudioQuality, CdInsertAction, Dither, FeaturedCreditStyle, LibrarySort, OutputBitDepth,
+    StartupView, TrackClickAction,
+};
 use dioxus::prelude::*;
 
 /// Application configuration state
@@ -16,6 +20,88 @@ pub struct ConfigState {
     pub subsonic_enabled: bool,
     /// Subsonic server port
     pub subsonic_port: u16,
+    /// Opus bitrate for Subsonic clients that ask for transcoding, in kbps
+    pub subsonic_transcode_bitrate_kbps: u32,
+
+    // Phone remote settings
+    /// Whether the phone remote is served on the LAN
+    pub remote_enabled: bool,
+    /// Phone remote port
+    pub remote_port: u16,
+    /// Token paired phones send (None until the remote is first enabled)
+    pub remote_token: Option<String>,
+
+    // Playback settings
+    /// How far the decoder runs ahead of playback, in ms
+    pub playback_decode_ahead_ms: u32,
+    /// How much of the next track is pre-rolled for gapless playback, in ms
+    pub playback_gapless_preroll_ms: u32,
+    /// Output device to play through (None = system default)
+    pub playback_output_device: Option<String>,
+    /// Sample format requested from the output device (None until config loads)
+    pub playback_output_bit_depth: Option<OutputBitDepth>,
+    /// Dither used when the output is 16-bit (None until config loads)
+    pub playback_dither: Option<Dither>,
+    /// Output devices held exclusively while playing
+    pub playback_exclusive_devices: Vec<String>,
+    /// Whether long silences are skipped during playback
+    pub playback_skip_silence: bool,
+    /// How long silence plays before it's skipped, in ms
+    pub playback_min_silence_ms: u32,
+    /// Whether closing the window keeps playback going from the tray
+    pub playback_in_background: bool,
+
+    // Library settings
+    /// What the window shows at launch (None until config loads)
+    pub startup_view: Option<StartupView>,
+    /// Library sort at launch (None until config loads)
+    pub library_sort: Option<LibrarySort>,
+    /// Library quality filter at launch (None = all albums)
+    pub library_quality_filter: Option<AudioQuality>,
+    /// Days between scheduled library verification runs (None = not scheduled)
+    pub verification_interval_days: Option<u32>,
+    /// Storage profile library snapshots go to (None = no snapshots)
+    pub snapshot_profile_id: Option<String>,
+    /// Hours between scheduled library snapshots (None = not scheduled)
+    pub snapshot_interval_hours: Option<u32>,
+    /// Library snapshots kept in the bucket
+    pub snapshots_kept: u32,
+    /// What clicking a track row does (None until config loads)
+    pub track_click_action: Option<TrackClickAction>,
+    /// Featured-artist credit normalization at import (None until config loads)
+    pub featured_credit_style: Option<FeaturedCreditStyle>,
+
+    // Network settings
+    /// Whether metered mode is on
+    pub metered_mode: bool,
+    /// Keep preloading cloud tracks in metered mode
+    pub metered_allow_cloud_prefetch: bool,
+    /// Keep torrent traffic running in metered mode
+    pub metered_allow_torrents: bool,
+
+    // CD ripping settings
+    /// What inserting an audio CD does (None until config loads)
+    pub cd_insert_action: Option<CdInsertAction>,
+    /// Eject a CD once it's ripped
+    pub cd_eject_after_rip: bool,
+
+    // Import settings
+    /// Folder-name templates tried in order when detecting an import's metadata
+    pub folder_patterns: Vec<String>,
+    /// Cap import work to one file or disc at a time
+    pub low_power_import: bool,
+    /// Queued imports that run at once
+    pub max_concurrent_imports: u32,
+
+    // Scrobbling settings
+    /// Connected Last.fm user, if any
+    pub lastfm_username: Option<String>,
+    /// Whether a ListenBrainz token is set
+    pub listenbrainz_connected: bool,
+
+    // Storage settings
+    /// Cloud storage price per GB-month, for import cost estimates
+    pub cloud_price_per_gb: f64,
 
     // BitTorrent settings
     /// Interface to bind torrent client to
@@ -24,6 +110,12 @@ pub struct ConfigState {
     pub torrent_listen_port: Option<u16>,
     /// Enable UPnP port forwarding
     pub torrent_enable_upnp: bool,
+    /// Listen on IPv6 as well as IPv4
+    pub torrent_enable_ipv6: bool,
+    /// Enable the DHT
+    pub torrent_enable_dht: bool,
+    /// DHT bootstrap nodes as host:port (empty = libtorrent defaults)
+    pub torrent_dht_bootstrap_nodes: Vec<String>,
     /// Global max connections (None = unlimited)
     pub torrent_max_connections: Option<i32>,
     /// Max connections per torrent (None = unlimited)
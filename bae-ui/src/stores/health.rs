@@ -0,0 +1,14 @@
+//! Service health state store
+
+use crate::display_types::{ServiceHealth, ServiceKind};
+use dioxus::prelude::*;
+
+/// Health of bae's subsystems, for the panel in Settings → About
+#[derive(Clone, Debug, Default, PartialEq, Store)]
+pub struct HealthState {
+    /// In display order
+    pub services: Vec<ServiceHealth>,
+    pub is_checking: bool,
+    /// Subsystem being restarted
+    pub restarting: Option<ServiceKind>,
+}
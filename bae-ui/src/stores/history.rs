@@ -0,0 +1,17 @@
+//! Listening history state store
+
+use crate::display_types::{TrackPlay, TrackPlayCount};
+use dioxus::prelude::*;
+
+/// State for the listening history view
+#[derive(Clone, Debug, Default, PartialEq, Store)]
+pub struct HistoryState {
+    /// Plays in the selected date range, newest first
+    pub plays: Vec<TrackPlay>,
+    /// Tracks played most in the selected date range
+    pub top_tracks: Vec<TrackPlayCount>,
+    /// Whether the history is loading
+    pub loading: bool,
+    /// Error message if loading failed
+    pub error: Option<String>,
+}
@@ -4,8 +4,10 @@
 //! bae-desktop (real import) and bae-mocks (design tool).
 
 use crate::display_types::{
-    CategorizedFileInfo, DetectedCandidate, FolderMetadata, IdentifyMode, MatchCandidate,
-    SearchSource, SearchTab, SelectedCover,
+    append_deduplicated, AudioContentInfo, AudioQualityCheck, CategorizedFileInfo,
+    DetectedCandidate, DuplicateAudioCheck, FolderMetadata, IdentifyMode, ManualEntry,
+    MatchCandidate, MatchSourceType, NeedleDropCheck, ReleaseTemplate, SearchSource, SearchTab,
+    SelectedCover, SourceResultCount, TorrentDownloadHealth,
 };
 use dioxus::prelude::*;
 
@@ -63,6 +65,47 @@ pub struct ManualSearchState {
     pub search_results: Vec<MatchCandidate>,
     pub selected_result_index: Option<usize>,
     pub error_message: Option<String>,
+    /// Result counts reported by each source queried in the last search
+    pub result_counts: Vec<SourceResultCount>,
+    /// True while fetching the next page of results
+    pub is_loading_more: bool,
+    /// The query the last search ran with. Later pages come from this, not
+    /// from the form, which may have been edited since.
+    pub executed_query: Option<SearchQuery>,
+}
+
+/// A manual search as it was run
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchQuery {
+    pub source: SearchSource,
+    pub tab: SearchTab,
+    pub artist: String,
+    pub album: String,
+    pub year: String,
+    pub label: String,
+    pub catalog_number: String,
+    pub barcode: String,
+}
+
+impl ManualSearchState {
+    /// The query the form currently describes
+    pub fn current_query(&self) -> SearchQuery {
+        SearchQuery {
+            source: self.search_source,
+            tab: self.search_tab,
+            artist: self.search_artist.clone(),
+            album: self.search_album.clone(),
+            year: self.search_year.clone(),
+            label: self.search_label.clone(),
+            catalog_number: self.search_catalog_number.clone(),
+            barcode: self.search_barcode.clone(),
+        }
+    }
+
+    /// Whether any queried source has results beyond the ones already loaded
+    pub fn has_more_results(&self) -> bool {
+        self.result_counts.iter().any(|c| c.has_more)
+    }
 }
 
 /// State for the Confirm step
@@ -80,6 +123,8 @@ pub struct ConfirmingState {
     pub selected_profile_id: Option<String>,
     /// Current phase within Confirm step
     pub phase: ConfirmPhase,
+    /// Checklist of the preparation after Confirm is clicked (empty until then)
+    pub prepare_stages: Vec<PrepareStageProgress>,
     /// Cached auto-match results (for returning to Identify)
     pub auto_matches: Vec<MatchCandidate>,
     /// Manual search state (for returning to Identify)
@@ -94,8 +139,8 @@ pub enum ConfirmPhase {
     /// User can edit cover/profile and click Confirm
     #[default]
     Ready,
-    /// Fetching/preparing after clicking Confirm
-    Preparing(String),
+    /// Fetching/preparing after clicking Confirm; progress is in `prepare_stages`
+    Preparing,
     /// Import command sent, controls disabled
     Importing,
     /// Error occurred
@@ -104,6 +149,64 @@ pub enum ConfirmPhase {
     Completed,
 }
 
+/// A stage of getting an import ready after Confirm is clicked, in the order
+/// they run
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PrepareStage {
+    /// Fetching the full release from MusicBrainz or Discogs
+    FetchingRelease,
+    /// Downloading the chosen remote cover
+    FetchingCover,
+    /// Extracting tracks from disc images (CUE/BIN and the like)
+    ExtractingDiscImages,
+    /// Splitting needle-drop recordings of vinyl sides into track files
+    SplittingSides,
+    /// Finding the audio files and matching them to tracks
+    MatchingFiles,
+    /// Saving the album, its tracks and their durations to the library
+    WritingMetadata,
+}
+
+impl PrepareStage {
+    pub const ALL: [PrepareStage; 6] = [
+        PrepareStage::FetchingRelease,
+        PrepareStage::FetchingCover,
+        PrepareStage::ExtractingDiscImages,
+        PrepareStage::SplittingSides,
+        PrepareStage::MatchingFiles,
+        PrepareStage::WritingMetadata,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PrepareStage::FetchingRelease => "Fetching release",
+            PrepareStage::FetchingCover => "Fetching cover",
+            PrepareStage::ExtractingDiscImages => "Extracting disc images",
+            PrepareStage::SplittingSides => "Splitting vinyl sides",
+            PrepareStage::MatchingFiles => "Matching files to tracks",
+            PrepareStage::WritingMetadata => "Writing metadata",
+        }
+    }
+}
+
+/// Where a preparation stage is at
+#[derive(Clone, Debug, PartialEq)]
+pub enum PrepareStageStatus {
+    Pending,
+    Running,
+    Done,
+    /// Nothing to do for this import, e.g. no remote cover was chosen
+    Skipped,
+    Failed(String),
+}
+
+/// One row of the preparation checklist
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrepareStageProgress {
+    pub stage: PrepareStage,
+    pub status: PrepareStageStatus,
+}
+
 // ============================================================================
 // Event Types
 // ============================================================================
@@ -127,6 +230,9 @@ pub enum CandidateEvent {
         matches: Vec<MatchCandidate>,
         error: Option<String>,
     },
+    /// The folder was imported before and kept the release it was imported
+    /// as; it's confirmed without matching
+    StoredReleaseFound(MatchCandidate),
 
     // --- Manual search events ---
     /// User updates a search field
@@ -142,6 +248,17 @@ pub enum CandidateEvent {
     /// Search completed (from async operation)
     SearchComplete {
         results: Vec<MatchCandidate>,
+        /// Result count per source queried (empty on error)
+        result_counts: Vec<SourceResultCount>,
+        error: Option<String>,
+    },
+    /// User requests the next page of search results
+    StartLoadMore,
+    /// Next page of search results arrived (from async operation)
+    LoadMoreComplete {
+        results: Vec<MatchCandidate>,
+        /// Updated counts for the sources that were queried
+        result_counts: Vec<SourceResultCount>,
         error: Option<String>,
     },
     /// User selects a result from manual search
@@ -149,6 +266,12 @@ pub enum CandidateEvent {
     /// User confirms the selected search result
     ConfirmSearchResult,
 
+    // --- Manual entry events ---
+    /// User gives up on matching and types in the release details
+    SwitchToManualEntry,
+    /// User confirms the details they typed in
+    ConfirmManualEntry(ManualEntry),
+
     // --- Confirm step events ---
     /// User clicks "Edit" to go back to Identify
     GoBackToIdentify,
@@ -158,8 +281,8 @@ pub enum CandidateEvent {
     SelectStorageProfile(Option<String>),
     /// User clicks "Import" button
     StartImport,
-    /// Import is preparing (from async operation)
-    ImportPreparing(String),
+    /// Import moved on to a preparation stage (from async operation)
+    ImportPreparing(PrepareStage),
     /// Import command sent successfully
     ImportStarted,
     /// Import failed (from async operation)
@@ -169,7 +292,7 @@ pub enum CandidateEvent {
 }
 
 /// Which search field is being updated
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum SearchField {
     Artist,
     Album,
@@ -212,7 +335,7 @@ impl CandidateState {
     pub fn is_import_in_progress(&self) -> bool {
         matches!(
             self,
-            CandidateState::Confirming(s) if matches!(s.phase, ConfirmPhase::Importing | ConfirmPhase::Preparing(_))
+            CandidateState::Confirming(s) if matches!(s.phase, ConfirmPhase::Importing | ConfirmPhase::Preparing)
         )
     }
 
@@ -261,6 +384,7 @@ impl IdentifyingState {
                             selected_cover: None,
                             selected_profile_id: None,
                             phase: ConfirmPhase::Ready,
+                            prepare_stages: Vec::new(),
                             auto_matches: self.auto_matches,
                             search_state: self.search_state,
                             source_disc_id,
@@ -317,6 +441,7 @@ impl IdentifyingState {
                         selected_cover: None,
                         selected_profile_id: None,
                         phase: ConfirmPhase::Ready,
+                        prepare_stages: Vec::new(),
                         auto_matches: vec![],
                         search_state: state.search_state,
                         source_disc_id: disc_id,
@@ -357,22 +482,68 @@ impl IdentifyingState {
             }
             CandidateEvent::StartSearch => {
                 let mut state = self;
+                state.search_state.executed_query = Some(state.search_state.current_query());
                 state.search_state.is_searching = true;
+                state.search_state.is_loading_more = false;
                 state.search_state.error_message = None;
                 CandidateState::Identifying(state)
             }
             CandidateEvent::CancelSearch => {
                 let mut state = self;
                 state.search_state.is_searching = false;
+                state.search_state.is_loading_more = false;
                 CandidateState::Identifying(state)
             }
-            CandidateEvent::SearchComplete { results, error } => {
+            CandidateEvent::SearchComplete {
+                results,
+                result_counts,
+                error,
+            } => {
                 let mut state = self;
                 state.search_state.is_searching = false;
+                state.search_state.is_loading_more = false;
                 state.search_state.has_searched = true;
                 state.search_state.search_results = results;
                 state.search_state.error_message = error;
                 state.search_state.selected_result_index = None;
+                state.search_state.result_counts = result_counts;
+                CandidateState::Identifying(state)
+            }
+            CandidateEvent::StartLoadMore => {
+                let mut state = self;
+                if !state.search_state.is_searching && state.search_state.has_more_results() {
+                    state.search_state.is_loading_more = true;
+                    state.search_state.error_message = None;
+                }
+                CandidateState::Identifying(state)
+            }
+            CandidateEvent::LoadMoreComplete {
+                results,
+                result_counts,
+                error,
+            } => {
+                let mut state = self;
+                // Ignore pages that arrive after the user cancelled or started a new search
+                if !state.search_state.is_loading_more {
+                    return CandidateState::Identifying(state);
+                }
+                state.search_state.is_loading_more = false;
+                if let Some(err) = error {
+                    state.search_state.error_message = Some(err);
+                } else {
+                    append_deduplicated(&mut state.search_state.search_results, results);
+                    for count in result_counts {
+                        match state
+                            .search_state
+                            .result_counts
+                            .iter_mut()
+                            .find(|c| c.source == count.source)
+                        {
+                            Some(existing) => *existing = count,
+                            None => state.search_state.result_counts.push(count),
+                        }
+                    }
+                }
                 CandidateState::Identifying(state)
             }
             CandidateEvent::SelectSearchResult(idx) => {
@@ -393,6 +564,7 @@ impl IdentifyingState {
                             selected_cover: None,
                             selected_profile_id: None,
                             phase: ConfirmPhase::Ready,
+                            prepare_stages: Vec::new(),
                             auto_matches: state.auto_matches,
                             search_state: state.search_state,
                             source_disc_id: None, // Coming from manual search
@@ -401,6 +573,45 @@ impl IdentifyingState {
                 }
                 CandidateState::Identifying(state)
             }
+            CandidateEvent::SwitchToManualEntry => {
+                let mut state = self;
+                let entry = ManualEntry::from_detected(&state.metadata, &state.files);
+                state.mode = IdentifyMode::ManualEntry(entry);
+                CandidateState::Identifying(state)
+            }
+            CandidateEvent::ConfirmManualEntry(entry) => {
+                // A template's cover is chosen up front
+                let selected_cover = entry.cover_url.clone().map(|url| SelectedCover::Remote {
+                    url,
+                    source: String::new(),
+                });
+                CandidateState::Confirming(Box::new(ConfirmingState {
+                    files: self.files,
+                    metadata: self.metadata,
+                    confirmed_candidate: entry.into_candidate(),
+                    selected_cover,
+                    selected_profile_id: None,
+                    phase: ConfirmPhase::Ready,
+                    prepare_stages: Vec::new(),
+                    auto_matches: self.auto_matches,
+                    search_state: self.search_state,
+                    source_disc_id: None,
+                }))
+            }
+            CandidateEvent::StoredReleaseFound(candidate) => {
+                CandidateState::Confirming(Box::new(ConfirmingState {
+                    files: self.files,
+                    metadata: self.metadata,
+                    confirmed_candidate: candidate,
+                    selected_cover: None,
+                    selected_profile_id: None,
+                    phase: ConfirmPhase::Ready,
+                    prepare_stages: Vec::new(),
+                    auto_matches: self.auto_matches,
+                    search_state: self.search_state,
+                    source_disc_id: None,
+                }))
+            }
             CandidateEvent::GoBackToIdentify
             | CandidateEvent::SelectCover(_)
             | CandidateEvent::SelectStorageProfile(_)
@@ -414,10 +625,68 @@ impl IdentifyingState {
 }
 
 impl ConfirmingState {
+    /// The preparation checklist, while preparing or after a stage failed
+    pub fn prepare_checklist(&self) -> Vec<PrepareStageProgress> {
+        let failed = self
+            .prepare_stages
+            .iter()
+            .any(|s| matches!(s.status, PrepareStageStatus::Failed(_)));
+        if self.phase == ConfirmPhase::Preparing || failed {
+            self.prepare_stages.clone()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Mark `stage` running. Stages before it are done if they ran and
+    /// skipped if they didn't.
+    fn start_prepare_stage(&mut self, stage: PrepareStage) {
+        for progress in &mut self.prepare_stages {
+            if progress.stage == stage {
+                progress.status = PrepareStageStatus::Running;
+            } else if progress.stage < stage {
+                progress.status = match progress.status {
+                    PrepareStageStatus::Running => PrepareStageStatus::Done,
+                    PrepareStageStatus::Pending => PrepareStageStatus::Skipped,
+                    ref status => status.clone(),
+                };
+            }
+        }
+    }
+
+    /// Preparation is over: the running stage is done, the rest weren't needed
+    fn finish_prepare_stages(&mut self) {
+        for progress in &mut self.prepare_stages {
+            progress.status = match progress.status {
+                PrepareStageStatus::Running => PrepareStageStatus::Done,
+                PrepareStageStatus::Pending => PrepareStageStatus::Skipped,
+                ref status => status.clone(),
+            };
+        }
+    }
+
+    /// Put the error on the stage that was running. Checks made before the
+    /// first stage, like the duplicate check, fail without one.
+    fn fail_prepare_stage(&mut self, error: &str) {
+        if let Some(progress) = self
+            .prepare_stages
+            .iter_mut()
+            .find(|s| s.status == PrepareStageStatus::Running)
+        {
+            progress.status = PrepareStageStatus::Failed(error.to_string());
+        }
+    }
+
     fn on_event(self, event: CandidateEvent) -> CandidateState {
         match event {
             CandidateEvent::GoBackToIdentify => {
                 let mode = match (&self.source_disc_id, self.auto_matches.is_empty()) {
+                    // Back to the form, with what was typed in
+                    _ if self.confirmed_candidate.source_type == MatchSourceType::Manual => {
+                        IdentifyMode::ManualEntry(ManualEntry::from_candidate(
+                            &self.confirmed_candidate,
+                        ))
+                    }
                     (Some(disc_id), false) => IdentifyMode::MultipleExactMatches(disc_id.clone()),
                     _ => IdentifyMode::ManualSearch,
                 };
@@ -445,21 +714,34 @@ impl ConfirmingState {
             }
             CandidateEvent::StartImport => {
                 let mut state = self;
-                state.phase = ConfirmPhase::Preparing("Starting...".to_string());
+                state.phase = ConfirmPhase::Preparing;
+                state.prepare_stages = PrepareStage::ALL
+                    .into_iter()
+                    .map(|stage| PrepareStageProgress {
+                        stage,
+                        status: PrepareStageStatus::Pending,
+                    })
+                    .collect();
                 CandidateState::Confirming(Box::new(state))
             }
-            CandidateEvent::ImportPreparing(step) => {
+            CandidateEvent::ImportPreparing(stage) => {
                 let mut state = self;
-                state.phase = ConfirmPhase::Preparing(step);
+                if state.phase == ConfirmPhase::Preparing {
+                    state.start_prepare_stage(stage);
+                }
                 CandidateState::Confirming(Box::new(state))
             }
             CandidateEvent::ImportStarted => {
                 let mut state = self;
+                state.finish_prepare_stages();
                 state.phase = ConfirmPhase::Importing;
                 CandidateState::Confirming(Box::new(state))
             }
             CandidateEvent::ImportFailed(error) => {
                 let mut state = self;
+                if state.phase == ConfirmPhase::Preparing {
+                    state.fail_prepare_stage(&error);
+                }
                 state.phase = ConfirmPhase::Failed(error);
                 CandidateState::Confirming(Box::new(state))
             }
@@ -474,14 +756,19 @@ impl ConfirmingState {
             | CandidateEvent::SwitchToMultipleExactMatches(_)
             | CandidateEvent::StartDiscIdLookup(_)
             | CandidateEvent::DiscIdLookupComplete { .. }
+            | CandidateEvent::StoredReleaseFound(_)
             | CandidateEvent::UpdateSearchField { .. }
             | CandidateEvent::SetSearchTab(_)
             | CandidateEvent::SetSearchSource(_)
             | CandidateEvent::StartSearch
             | CandidateEvent::CancelSearch
             | CandidateEvent::SearchComplete { .. }
+            | CandidateEvent::StartLoadMore
+            | CandidateEvent::LoadMoreComplete { .. }
             | CandidateEvent::SelectSearchResult(_)
-            | CandidateEvent::ConfirmSearchResult => CandidateState::Confirming(Box::new(self)),
+            | CandidateEvent::ConfirmSearchResult
+            | CandidateEvent::SwitchToManualEntry
+            | CandidateEvent::ConfirmManualEntry(_) => CandidateState::Confirming(Box::new(self)),
         }
     }
 }
@@ -490,6 +777,25 @@ impl ConfirmingState {
 // Global Import State
 // ============================================================================
 
+/// Maximum remembered values per (source, field) pair
+const RECENT_SEARCHES_PER_FIELD: usize = 20;
+
+/// A previously used manual search value, offered as a suggestion
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecentSearch {
+    pub source: SearchSource,
+    pub field: SearchField,
+    pub value: String,
+}
+
+/// An audio CD that was just inserted, waiting for the CD import to pick it up
+#[derive(Clone, Debug, PartialEq)]
+pub struct InsertedCd {
+    pub device_path: String,
+    /// Start ripping if DiscID finds a single release
+    pub auto_rip: bool,
+}
+
 /// Global import workflow state (not per-candidate)
 #[derive(Clone, Debug, Default, PartialEq, Store)]
 pub struct ImportState {
@@ -521,6 +827,22 @@ pub struct ImportState {
     pub selected_import_source: crate::ImportSource,
     /// CD TOC info: (disc_id, first_track, last_track)
     pub cd_toc_info: Option<(String, u8, u8)>,
+    /// Recently used manual search values, most recent first (survives reset)
+    pub recent_searches: Vec<RecentSearch>,
+    /// Saved series that pre-fill manual entry, oldest first (survives reset)
+    pub release_templates: Vec<ReleaseTemplate>,
+    /// Audio checks requested from the confirm step, by candidate key
+    pub audio_quality_checks: std::collections::HashMap<String, AudioQualityCheck>,
+    /// Duplicate recording checks requested from the confirm step, by candidate key
+    pub duplicate_audio_checks: std::collections::HashMap<String, DuplicateAudioCheck>,
+    /// Vinyl side splits set up from the confirm step, by candidate key
+    pub needle_drop_checks: std::collections::HashMap<String, NeedleDropCheck>,
+    /// Disc inserted while the app was open, for the CD import to select
+    pub inserted_cd: Option<InsertedCd>,
+    /// Import each candidate last started, by candidate key
+    pub candidate_imports: std::collections::HashMap<String, String>,
+    /// Live state of the torrent being downloaded for the current import
+    pub torrent_download: Option<TorrentDownloadHealth>,
 }
 
 impl ImportState {
@@ -538,6 +860,47 @@ impl ImportState {
         self.discid_lookup_attempted.clear();
         self.selected_release_indices = Vec::new();
         self.current_release_index = 0;
+        self.audio_quality_checks.clear();
+        self.duplicate_audio_checks.clear();
+        self.needle_drop_checks.clear();
+        self.candidate_imports.clear();
+        self.torrent_download = None;
+    }
+
+    /// Recently used values for a search field on a source, most recent first
+    pub fn recent_search_values(&self, source: SearchSource, field: SearchField) -> Vec<String> {
+        self.recent_searches
+            .iter()
+            .filter(|r| r.source == source && r.field == field)
+            .map(|r| r.value.clone())
+            .collect()
+    }
+
+    /// Move a search value to the front of the history, dropping the oldest
+    /// value for that source and field once the limit is reached
+    pub fn remember_search(&mut self, source: SearchSource, field: SearchField, value: &str) {
+        let value = value.trim();
+        if value.is_empty() {
+            return;
+        }
+        self.recent_searches
+            .retain(|r| !(r.source == source && r.field == field && r.value == value));
+        self.recent_searches.insert(
+            0,
+            RecentSearch {
+                source,
+                field,
+                value: value.to_string(),
+            },
+        );
+        let mut kept = 0;
+        self.recent_searches.retain(|r| {
+            if r.source != source || r.field != field {
+                return true;
+            }
+            kept += 1;
+            kept <= RECENT_SEARCHES_PER_FIELD
+        });
     }
 
     /// Get the current candidate's state (if any)
@@ -574,6 +937,24 @@ impl ImportState {
         }
     }
 
+    /// Dispatch an event to the candidate that started an import.
+    ///
+    /// Several imports can be in flight at once (one per CD drive), and a
+    /// drive's key is reused for the next disc, so an outcome only reaches
+    /// the candidate if this is still the import it last started.
+    pub fn dispatch_to_import(&mut self, import_id: &str, event: CandidateEvent) {
+        let Some(key) = self
+            .candidate_imports
+            .iter()
+            .find(|(_, id)| id.as_str() == import_id)
+            .map(|(key, _)| key.clone())
+        else {
+            return;
+        };
+        self.candidate_imports.remove(&key);
+        self.dispatch_to_candidate(&key, event);
+    }
+
     /// Initialize state machine for a candidate after detection completes
     pub fn init_state_machine(
         &mut self,
@@ -716,6 +1097,52 @@ impl ImportState {
         })
     }
 
+    /// MusicBrainz releases in the match list on screen, in list order
+    pub fn match_list_release_ids(&self) -> Vec<String> {
+        let candidates = match self.get_identify_mode() {
+            IdentifyMode::MultipleExactMatches(_) => self.get_exact_match_candidates(),
+            IdentifyMode::ManualSearch => self
+                .get_search_state()
+                .map(|s| s.search_results)
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        candidates
+            .into_iter()
+            .filter_map(|c| c.musicbrainz_release_id)
+            .collect()
+    }
+
+    /// Show a release's cover thumbnail wherever it's a candidate. `cover_url`
+    /// fills in the full-size cover where none was known.
+    pub fn set_cover_thumbnail(&mut self, release_id: &str, thumbnail_url: &str, cover_url: &str) {
+        for state in self.candidate_states.values_mut() {
+            let (auto_matches, search_state, confirmed) = match state {
+                CandidateState::Identifying(is) => {
+                    (&mut is.auto_matches, &mut is.search_state, None)
+                }
+                CandidateState::Confirming(cs) => (
+                    &mut cs.auto_matches,
+                    &mut cs.search_state,
+                    Some(&mut cs.confirmed_candidate),
+                ),
+            };
+            let candidates = auto_matches
+                .iter_mut()
+                .chain(search_state.search_results.iter_mut())
+                .chain(confirmed);
+            for candidate in candidates {
+                if candidate.musicbrainz_release_id.as_deref() != Some(release_id) {
+                    continue;
+                }
+                candidate.cover_thumbnail_url = Some(thumbnail_url.to_string());
+                if candidate.cover_url.is_none() {
+                    candidate.cover_url = Some(cover_url.to_string());
+                }
+            }
+        }
+    }
+
     /// Get selected cover from current candidate state
     pub fn get_selected_cover(&self) -> Option<SelectedCover> {
         self.current_candidate_state().and_then(|s| match s {
@@ -834,3 +1261,280 @@ impl ImportState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identifying() -> CandidateState {
+        CandidateState::Identifying(IdentifyingState {
+            files: CategorizedFileInfo::default(),
+            metadata: FolderMetadata::default(),
+            mode: IdentifyMode::ManualSearch,
+            auto_matches: Vec::new(),
+            selected_match_index: None,
+            search_state: ManualSearchState::default(),
+            discid_lookup_error: None,
+            disc_id_not_found: None,
+            source_disc_id: None,
+        })
+    }
+
+    fn search_state(state: &CandidateState) -> &ManualSearchState {
+        match state {
+            CandidateState::Identifying(s) => &s.search_state,
+            CandidateState::Confirming(_) => panic!("expected Identifying"),
+        }
+    }
+
+    #[test]
+    fn test_editing_the_form_after_a_search_keeps_the_executed_query() {
+        let state = identifying()
+            .transition(CandidateEvent::UpdateSearchField {
+                field: SearchField::Artist,
+                value: "Radiohead".to_string(),
+            })
+            .transition(CandidateEvent::StartSearch)
+            .transition(CandidateEvent::UpdateSearchField {
+                field: SearchField::Artist,
+                value: "Portishead".to_string(),
+            })
+            .transition(CandidateEvent::SetSearchTab(SearchTab::Barcode))
+            .transition(CandidateEvent::SetSearchSource(SearchSource::Discogs));
+
+        let query = search_state(&state).executed_query.clone().unwrap();
+        assert_eq!(query.artist, "Radiohead");
+        assert_eq!(query.tab, SearchTab::General);
+        assert_eq!(query.source, SearchSource::MusicBrainz);
+    }
+
+    #[test]
+    fn test_a_new_search_replaces_the_executed_query() {
+        let state = identifying()
+            .transition(CandidateEvent::StartSearch)
+            .transition(CandidateEvent::SetSearchTab(SearchTab::CatalogNumber))
+            .transition(CandidateEvent::UpdateSearchField {
+                field: SearchField::CatalogNumber,
+                value: "XLLP 782".to_string(),
+            })
+            .transition(CandidateEvent::StartSearch);
+
+        let query = search_state(&state).executed_query.clone().unwrap();
+        assert_eq!(query.tab, SearchTab::CatalogNumber);
+        assert_eq!(query.catalog_number, "XLLP 782");
+    }
+
+    fn count(source: MatchSourceType, pages_loaded: u32) -> SourceResultCount {
+        SourceResultCount {
+            source,
+            total: 100,
+            has_more: true,
+            pages_loaded,
+        }
+    }
+
+    #[test]
+    fn test_load_more_advances_only_the_sources_that_returned_a_page() {
+        let state = identifying()
+            .transition(CandidateEvent::SetSearchSource(SearchSource::Both))
+            .transition(CandidateEvent::StartSearch)
+            .transition(CandidateEvent::SearchComplete {
+                results: vec![],
+                result_counts: vec![
+                    count(MatchSourceType::MusicBrainz, 1),
+                    count(MatchSourceType::Discogs, 1),
+                ],
+                error: None,
+            })
+            .transition(CandidateEvent::StartLoadMore)
+            // Discogs failed, so only MusicBrainz reports a new page
+            .transition(CandidateEvent::LoadMoreComplete {
+                results: vec![],
+                result_counts: vec![count(MatchSourceType::MusicBrainz, 2)],
+                error: None,
+            });
+
+        assert_eq!(
+            search_state(&state).result_counts,
+            vec![
+                count(MatchSourceType::MusicBrainz, 2),
+                count(MatchSourceType::Discogs, 1),
+            ]
+        );
+    }
+
+    fn candidate(title: &str) -> MatchCandidate {
+        MatchCandidate {
+            title: title.to_string(),
+            artist: "Radiohead".to_string(),
+            year: None,
+            cover_url: None,
+            cover_thumbnail_url: None,
+            format: None,
+            country: None,
+            label: None,
+            catalog_number: None,
+            barcode: None,
+            source_type: MatchSourceType::MusicBrainz,
+            original_year: None,
+            musicbrainz_release_id: None,
+            musicbrainz_release_group_id: None,
+            discogs_release_id: None,
+            discogs_master_id: None,
+            track_titles: vec![],
+        }
+    }
+
+    #[test]
+    fn test_load_more_keeps_the_loaded_results_in_place() {
+        // The results list is keyed by position, so the rows already shown
+        // keep their keys, and the page its scroll offset, only if the new
+        // page goes after them and the list never gives way to the spinner
+        let first_page = vec![candidate("Kid A"), candidate("Amnesiac")];
+        let loading = identifying()
+            .transition(CandidateEvent::StartSearch)
+            .transition(CandidateEvent::SearchComplete {
+                results: first_page.clone(),
+                result_counts: vec![count(MatchSourceType::MusicBrainz, 1)],
+                error: None,
+            })
+            .transition(CandidateEvent::SelectSearchResult(1))
+            .transition(CandidateEvent::StartLoadMore);
+        assert!(!search_state(&loading).is_searching);
+        assert_eq!(search_state(&loading).search_results, first_page);
+
+        let state = loading.transition(CandidateEvent::LoadMoreComplete {
+            results: vec![candidate("Hail to the Thief")],
+            result_counts: vec![count(MatchSourceType::MusicBrainz, 2)],
+            error: None,
+        });
+        let search = search_state(&state);
+        assert_eq!(search.search_results[..2], first_page[..]);
+        assert_eq!(search.search_results[2].title, "Hail to the Thief");
+        assert_eq!(search.selected_result_index, Some(1));
+    }
+
+    fn confirming() -> CandidateState {
+        CandidateState::Confirming(Box::new(ConfirmingState {
+            files: CategorizedFileInfo::default(),
+            metadata: FolderMetadata::default(),
+            confirmed_candidate: candidate("In Rainbows"),
+            selected_cover: None,
+            selected_profile_id: None,
+            phase: ConfirmPhase::Ready,
+            prepare_stages: Vec::new(),
+            auto_matches: Vec::new(),
+            search_state: ManualSearchState::default(),
+            source_disc_id: None,
+        }))
+    }
+
+    fn stage_statuses(state: &CandidateState) -> Vec<(PrepareStage, PrepareStageStatus)> {
+        match state {
+            CandidateState::Confirming(s) => s
+                .prepare_stages
+                .iter()
+                .map(|p| (p.stage, p.status.clone()))
+                .collect(),
+            CandidateState::Identifying(_) => panic!("expected Confirming"),
+        }
+    }
+
+    #[test]
+    fn test_starting_a_stage_settles_the_ones_before_it() {
+        let state = confirming()
+            .transition(CandidateEvent::StartImport)
+            .transition(CandidateEvent::ImportPreparing(
+                PrepareStage::FetchingRelease,
+            ))
+            .transition(CandidateEvent::ImportPreparing(PrepareStage::MatchingFiles));
+
+        assert_eq!(
+            stage_statuses(&state),
+            vec![
+                (PrepareStage::FetchingRelease, PrepareStageStatus::Done),
+                (PrepareStage::FetchingCover, PrepareStageStatus::Skipped),
+                (
+                    PrepareStage::ExtractingDiscImages,
+                    PrepareStageStatus::Skipped
+                ),
+                (PrepareStage::SplittingSides, PrepareStageStatus::Skipped),
+                (PrepareStage::MatchingFiles, PrepareStageStatus::Running),
+                (PrepareStage::WritingMetadata, PrepareStageStatus::Pending),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_started_finishes_the_checklist() {
+        let state = confirming()
+            .transition(CandidateEvent::StartImport)
+            .transition(CandidateEvent::ImportPreparing(PrepareStage::FetchingCover))
+            .transition(CandidateEvent::ImportStarted);
+
+        assert_eq!(
+            stage_statuses(&state),
+            vec![
+                (PrepareStage::FetchingRelease, PrepareStageStatus::Skipped),
+                (PrepareStage::FetchingCover, PrepareStageStatus::Done),
+                (
+                    PrepareStage::ExtractingDiscImages,
+                    PrepareStageStatus::Skipped
+                ),
+                (PrepareStage::SplittingSides, PrepareStageStatus::Skipped),
+                (PrepareStage::MatchingFiles, PrepareStageStatus::Skipped),
+                (PrepareStage::WritingMetadata, PrepareStageStatus::Skipped),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_a_failure_marks_only_the_running_stage() {
+        let state = confirming()
+            .transition(CandidateEvent::StartImport)
+            .transition(CandidateEvent::ImportPreparing(
+                PrepareStage::FetchingRelease,
+            ))
+            .transition(CandidateEvent::ImportPreparing(
+                PrepareStage::SplittingSides,
+            ))
+            .transition(CandidateEvent::ImportFailed("no silence found".to_string()));
+
+        assert_eq!(
+            stage_statuses(&state),
+            vec![
+                (PrepareStage::FetchingRelease, PrepareStageStatus::Done),
+                (PrepareStage::FetchingCover, PrepareStageStatus::Skipped),
+                (
+                    PrepareStage::ExtractingDiscImages,
+                    PrepareStageStatus::Skipped
+                ),
+                (
+                    PrepareStage::SplittingSides,
+                    PrepareStageStatus::Failed("no silence found".to_string()),
+                ),
+                (PrepareStage::MatchingFiles, PrepareStageStatus::Pending),
+                (PrepareStage::WritingMetadata, PrepareStageStatus::Pending),
+            ]
+        );
+        let CandidateState::Confirming(confirming) = &state else {
+            unreachable!()
+        };
+        // The checklist stays up so the failed stage is visible
+        assert_eq!(
+            confirming.prepare_checklist().len(),
+            PrepareStage::ALL.len()
+        );
+    }
+
+    #[test]
+    fn test_a_failure_before_the_first_stage_marks_no_stage() {
+        let state = confirming()
+            .transition(CandidateEvent::StartImport)
+            .transition(CandidateEvent::ImportFailed("already imported".to_string()));
+
+        assert!(stage_statuses(&state)
+            .iter()
+            .all(|(_, status)| *status == PrepareStageStatus::Pending));
+    }
+}
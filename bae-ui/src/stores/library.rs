@@ -1,6 +1,6 @@
 //! Library state store
 
-use crate::display_types::{Album, Artist};
+use crate::display_types::{Album, Artist, FindReplaceRow, OfflineDownload};
 use dioxus::prelude::*;
 use std::collections::HashMap;
 
@@ -15,4 +15,11 @@ pub struct LibraryState {
     pub loading: bool,
     /// Error message if loading failed
     pub error: Option<String>,
+    /// Values the last find-and-replace preview would change (None = no
+    /// preview yet)
+    pub find_replace_preview: Option<Vec<FindReplaceRow>>,
+    /// Why the last find-and-replace preview or apply failed
+    pub find_replace_error: Option<String>,
+    /// Offline downloads that are running or failed, by album ID
+    pub offline_downloads: HashMap<String, OfflineDownload>,
 }
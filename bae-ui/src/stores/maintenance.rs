@@ -0,0 +1,17 @@
+//! Library maintenance state store
+
+use dioxus::prelude::*;
+
+/// Library-wide maintenance jobs: whether each is running and how it last went
+#[derive(Clone, Debug, Default, PartialEq, Store)]
+pub struct MaintenanceState {
+    pub is_renormalizing_credits: bool,
+    /// Outcome of the last artist credit re-normalization
+    pub credits_result: Option<String>,
+    pub is_compacting_database: bool,
+    /// Outcome of the last database compaction
+    pub database_result: Option<String>,
+    pub is_clearing_cache: bool,
+    /// Outcome of the last cache clear
+    pub cache_result: Option<String>,
+}
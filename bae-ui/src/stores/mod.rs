@@ -7,19 +7,37 @@
 pub mod active_imports;
 pub mod album_detail;
 pub mod app;
+pub mod client_shares;
 pub mod config;
+pub mod health;
+pub mod history;
 pub mod import;
 pub mod library;
+pub mod maintenance;
 pub mod playback;
+pub mod seeding;
+pub mod snapshots;
 pub mod storage_profiles;
+pub mod tracks;
 pub mod ui;
+pub mod undo;
+pub mod verification;
 
 pub use active_imports::*;
 pub use album_detail::*;
 pub use app::*;
+pub use client_shares::*;
 pub use config::*;
+pub use health::*;
+pub use history::*;
 pub use import::*;
 pub use library::*;
+pub use maintenance::*;
 pub use playback::*;
+pub use seeding::*;
+pub use snapshots::*;
 pub use storage_profiles::*;
+pub use tracks::*;
 pub use ui::*;
+pub use undo::*;
+pub use verification::*;
@@ -1,10 +1,11 @@
 //! Playback UI state store
 
-use crate::display_types::QueueItem;
+use crate::display_types::{AlbumPalette, OutputFormatInfo, QueueItem};
 use dioxus::prelude::*;
+use serde::Serialize;
 
 /// Playback state enum matching bae-core's PlaybackState
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
 pub enum PlaybackStatus {
     #[default]
     Stopped,
@@ -37,6 +38,8 @@ pub struct PlaybackUiState {
     pub current_track: Option<QueueItem>,
     /// Queue items with full display info (track + album title + cover)
     pub queue_items: Vec<QueueItem>,
+    /// Tracks that played before the current one, most recent first
+    pub history_items: Vec<QueueItem>,
     /// Current playback position in milliseconds
     pub position_ms: u64,
     /// Track duration in milliseconds (0 if unknown)
@@ -47,8 +50,16 @@ pub struct PlaybackUiState {
     pub artist_name: String,
     /// Cover art URL for current track
     pub cover_url: Option<String>,
+    /// Cover palette of the current track's album
+    pub palette: Option<AlbumPalette>,
     /// Transient playback error message
     pub playback_error: Option<String>,
     /// Repeat mode
     pub repeat_mode: RepeatMode,
+    /// Whether the upcoming queue is shuffled
+    pub shuffle: bool,
+    /// Format negotiated with the output device, once audio output is up
+    pub output_format: Option<OutputFormatInfo>,
+    /// Pitch shift in semitones; zero when off
+    pub pitch_semitones: i32,
 }
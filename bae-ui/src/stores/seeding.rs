@@ -0,0 +1,13 @@
+//! Seeding state store
+
+use crate::display_types::SeedingTorrent;
+use dioxus::prelude::*;
+
+/// Torrents kept for seeding
+#[derive(Clone, Debug, Default, PartialEq, Store)]
+pub struct SeedingState {
+    /// Oldest first
+    pub torrents: Vec<SeedingTorrent>,
+    /// Why the last pause, resume or remove failed
+    pub error: Option<String>,
+}
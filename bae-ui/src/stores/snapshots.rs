@@ -0,0 +1,22 @@
+//! Library snapshot state store
+
+use crate::display_types::LibrarySnapshot;
+use dioxus::prelude::*;
+
+/// Snapshots of the library database, and restoring one after the database
+/// was found damaged at launch
+#[derive(Clone, Debug, Default, PartialEq, Store)]
+pub struct SnapshotsState {
+    /// Newest first
+    pub snapshots: Vec<LibrarySnapshot>,
+    pub is_snapshotting: bool,
+    /// Outcome of the last snapshot
+    pub snapshot_result: Option<String>,
+    /// Where the damaged database was moved at launch, if it was
+    pub damaged_database: Option<String>,
+    pub is_restoring: bool,
+    /// A snapshot is downloaded and replaces the database at the next launch
+    pub restore_staged: bool,
+    /// Why the last restore failed
+    pub restore_error: Option<String>,
+}
@@ -0,0 +1,15 @@
+//! All-tracks view state store
+
+use crate::display_types::LibraryTrack;
+use dioxus::prelude::*;
+
+/// State for the flat tracks view
+#[derive(Clone, Debug, Default, PartialEq, Store)]
+pub struct TracksState {
+    /// Tracks of each album's default edition
+    pub tracks: Vec<LibraryTrack>,
+    /// Whether the tracks are loading
+    pub loading: bool,
+    /// Error message if loading failed
+    pub error: Option<String>,
+}
@@ -0,0 +1,12 @@
+//! Undo toast state store
+
+use dioxus::prelude::*;
+
+/// Outcome of the last undo or redo, shown as a toast
+#[derive(Clone, Debug, Default, PartialEq, Store)]
+pub struct UndoState {
+    /// What was undone or redone (None = no toast)
+    pub message: Option<String>,
+    /// The last action was an undo, so the toast offers redo rather than undo
+    pub offers_redo: bool,
+}
@@ -0,0 +1,16 @@
+//! Library verification state store
+
+use crate::display_types::VerificationRun;
+use dioxus::prelude::*;
+
+/// Library verification history and whether a run is in progress
+#[derive(Clone, Debug, Default, PartialEq, Store)]
+pub struct VerificationState {
+    /// Recent runs, newest first
+    pub runs: Vec<VerificationRun>,
+    pub is_running: bool,
+    /// Issue being repaired, if any
+    pub repairing_issue_id: Option<String>,
+    /// Why the last repair failed
+    pub repair_error: Option<String>,
+}
@@ -60,9 +60,12 @@ impl DocumentEventListener {
 
 impl Drop for DocumentEventListener {
     fn drop(&mut self) {
-        let _ = self.document.remove_event_listener_with_callback(
-            self.event_name,
-            self.callback.as_ref().unchecked_ref(),
-        );
+        // Dropped on unmount, which can race the window closing
+        let _ = crate::ipc::guard(|| {
+            self.document.remove_event_listener_with_callback(
+                self.event_name,
+                self.callback.as_ref().unchecked_ref(),
+            )
+        });
     }
 }